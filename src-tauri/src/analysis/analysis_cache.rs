@@ -0,0 +1,181 @@
+//! Detected items of recent analyses, kept so `build_install_tasks` can turn a
+//! selection into install tasks without scanning the inputs again
+//!
+//! Entries are keyed by the signature of the input they were found in: its
+//! path, size and modification time. A lookup drops entries older than
+//! [`CACHE_TTL`] and entries whose input no longer has the same signature, so
+//! an archive replaced or re-downloaded after the analysis is scanned again.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use crate::models::DetectedItem;
+
+/// How long analysis results stay usable
+const CACHE_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Inputs kept at most; the oldest go first
+const MAX_CACHED_INPUTS: usize = 64;
+
+/// A detected item with what the analysis knew about it
+#[derive(Debug, Clone)]
+pub struct CachedItem {
+    pub item: DetectedItem,
+    pub password: Option<String>,
+    pub verification_preferences: Option<HashMap<String, bool>>,
+}
+
+struct CachedInput {
+    input_path: String,
+    cached_at: Instant,
+    /// Items by stable id
+    items: HashMap<String, CachedItem>,
+}
+
+static CACHE: LazyLock<Mutex<HashMap<String, CachedInput>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// `<path>|<size>|<mtime in ms>` of an input, or `None` when it is gone
+fn input_signature(path: &str) -> Option<String> {
+    let metadata = fs::metadata(Path::new(path)).ok()?;
+    let modified_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    Some(format!(
+        "{}|{}|{}",
+        path.replace('\\', "/"),
+        metadata.len(),
+        modified_ms
+    ))
+}
+
+/// Remember the items of one analysis, replacing earlier results for the same
+/// inputs
+pub fn store(items: Vec<CachedItem>) {
+    let mut by_input: HashMap<String, Vec<CachedItem>> = HashMap::new();
+    for cached in items {
+        by_input
+            .entry(cached.item.original_input_path.clone())
+            .or_default()
+            .push(cached);
+    }
+
+    let Ok(mut cache) = CACHE.lock() else {
+        return;
+    };
+    for (input_path, items) in by_input {
+        let Some(signature) = input_signature(&input_path) else {
+            continue;
+        };
+        cache.retain(|_, entry| entry.input_path != input_path);
+        cache.insert(
+            signature,
+            CachedInput {
+                input_path,
+                cached_at: Instant::now(),
+                items: items
+                    .into_iter()
+                    .map(|cached| (cached.item.stable_id(), cached))
+                    .collect(),
+            },
+        );
+    }
+
+    while cache.len() > MAX_CACHED_INPUTS {
+        let oldest = cache
+            .iter()
+            .min_by_key(|(_, entry)| entry.cached_at)
+            .map(|(signature, _)| signature.clone());
+        match oldest {
+            Some(signature) => cache.remove(&signature),
+            None => break,
+        };
+    }
+}
+
+/// Items for task ids returned by an analysis, in the order asked for. Ids the
+/// analyzer suffixed to keep them unique (`<id>-2`) resolve to their item.
+/// Returns the found items and the ids that are unknown, expired or whose
+/// input changed.
+pub fn lookup(ids: &[String]) -> (Vec<(String, CachedItem)>, Vec<String>) {
+    let Ok(mut cache) = CACHE.lock() else {
+        return (Vec::new(), ids.to_vec());
+    };
+    cache.retain(|signature, entry| {
+        entry.cached_at.elapsed() < CACHE_TTL
+            && input_signature(&entry.input_path).as_deref() == Some(signature.as_str())
+    });
+
+    let mut found = Vec::new();
+    let mut missing = Vec::new();
+    for id in ids {
+        let stable_id = id.split('-').next().unwrap_or(id);
+        match cache.values().find_map(|entry| entry.items.get(stable_id)) {
+            Some(cached) => found.push((id.clone(), cached.clone())),
+            None => missing.push(id.clone()),
+        }
+    }
+    (found, missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AddonCompatibility, AddonType, TargetRootKind, UpdaterProvider};
+
+    fn detected(input: &str, root: &str) -> CachedItem {
+        CachedItem {
+            item: DetectedItem {
+                addon_type: AddonType::Livery,
+                path: input.to_string(),
+                original_input_path: input.to_string(),
+                display_name: root.to_string(),
+                archive_internal_root: Some(root.to_string()),
+                extraction_chain: None,
+                navdata_info: None,
+                livery_aircraft_type: None,
+                livery_target_folder: None,
+                livery_target_candidates: Vec::new(),
+                version_info: None,
+                companion_paths: Vec::new(),
+                sim_version_hints: None,
+                compatibility: AddonCompatibility::default(),
+                updater_provider: UpdaterProvider::default(),
+                target_root_kind: TargetRootKind::default(),
+            },
+            password: None,
+            verification_preferences: None,
+        }
+    }
+
+    #[test]
+    fn test_lookup_finds_items_until_the_archive_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("liveries.zip");
+        fs::write(&archive, b"first").unwrap();
+        let input = archive.to_string_lossy().to_string();
+
+        let first = detected(&input, "Red");
+        let second = detected(&input, "Blue");
+        let first_id = first.item.stable_id();
+        let second_id = second.item.stable_id();
+        store(vec![first, second]);
+
+        let (found, missing) = lookup(&[format!("{}-2", second_id), "unknown".to_string()]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, format!("{}-2", second_id));
+        assert_eq!(found[0].1.item.display_name, "Blue");
+        assert_eq!(missing, vec!["unknown".to_string()]);
+
+        fs::write(&archive, b"replaced with other content").unwrap();
+        let (found, missing) = lookup(std::slice::from_ref(&first_id));
+        assert!(found.is_empty());
+        assert_eq!(missing, vec![first_id]);
+    }
+}
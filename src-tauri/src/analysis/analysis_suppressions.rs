@@ -0,0 +1,260 @@
+//! Analysis warnings the user chose to hide
+//!
+//! Each suppression is a `(kind, key)` pair where the key is a stable
+//! identifier of one warning, prefixed with its kind:
+//!
+//! - `duplicate_plugin:AviTab|AviTab (2)`: sorted folder names of a duplicate
+//!   plugin group
+//! - `airport_conflict:EGKK`: airport identifier defined by several packages
+//! - `compatibility:<display name>`: compatibility warning of an analyzed addon
+//!
+//! Reports drop suppressed entries and carry how many were dropped, so the UI
+//! can offer to reveal them.
+
+use crate::database::entities::analysis_suppressions;
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    AnalysisResult, AnalysisSuppression, ManagementData, PluginDuplicateLocation, PluginInfo,
+};
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const KIND_DUPLICATE_PLUGIN: &str = "duplicate_plugin";
+pub const KIND_AIRPORT_CONFLICT: &str = "airport_conflict";
+pub const KIND_COMPATIBILITY: &str = "compatibility";
+
+const KINDS: &[&str] = &[
+    KIND_DUPLICATE_PLUGIN,
+    KIND_AIRPORT_CONFLICT,
+    KIND_COMPATIBILITY,
+];
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn to_suppression(row: analysis_suppressions::Model) -> AnalysisSuppression {
+    AnalysisSuppression {
+        id: row.id,
+        kind: row.kind,
+        key: row.key,
+        created_at: row.created_at,
+    }
+}
+
+/// Key of a duplicate plugin group, independent of scan order
+pub fn duplicate_plugin_key(locations: &[PluginDuplicateLocation]) -> String {
+    let mut folders: Vec<&str> = locations
+        .iter()
+        .map(|location| location.folder_name.as_str())
+        .collect();
+    folders.sort_unstable();
+    format!("{}:{}", KIND_DUPLICATE_PLUGIN, folders.join("|"))
+}
+
+pub fn airport_conflict_key(airport_id: &str) -> String {
+    format!("{}:{}", KIND_AIRPORT_CONFLICT, airport_id)
+}
+
+pub fn compatibility_key(display_name: &str) -> String {
+    format!("{}:{}", KIND_COMPATIBILITY, display_name)
+}
+
+/// Suppress a warning. Suppressing the same warning again returns the
+/// existing entry.
+pub async fn add_suppression(
+    conn: &DatabaseConnection,
+    kind: &str,
+    key: &str,
+) -> ApiResult<AnalysisSuppression> {
+    if !KINDS.contains(&kind) {
+        return Err(ApiError::validation(format!(
+            "Unknown analysis suppression kind: {}",
+            kind
+        )));
+    }
+    let identifier = key
+        .strip_prefix(kind)
+        .and_then(|rest| rest.strip_prefix(':'));
+    if identifier.is_none_or(|identifier| identifier.trim().is_empty()) {
+        return Err(ApiError::validation(format!(
+            "Suppression key must start with \"{}:\"",
+            kind
+        )));
+    }
+
+    let existing = analysis_suppressions::Entity::find()
+        .filter(analysis_suppressions::Column::Kind.eq(kind))
+        .filter(analysis_suppressions::Column::Key.eq(key))
+        .one(conn)
+        .await?;
+    if let Some(row) = existing {
+        return Ok(to_suppression(row));
+    }
+
+    let created_at = now_secs();
+    let active = analysis_suppressions::ActiveModel {
+        id: ActiveValue::NotSet,
+        kind: Set(kind.to_string()),
+        key: Set(key.to_string()),
+        created_at: Set(created_at),
+    };
+    let result = analysis_suppressions::Entity::insert(active)
+        .exec(conn)
+        .await?;
+    Ok(AnalysisSuppression {
+        id: result.last_insert_id,
+        kind: kind.to_string(),
+        key: key.to_string(),
+        created_at,
+    })
+}
+
+/// Remove a suppression so its warning shows up again.
+/// Returns whether a row was deleted.
+pub async fn remove_suppression(conn: &DatabaseConnection, id: i64) -> ApiResult<bool> {
+    let result = analysis_suppressions::Entity::delete_by_id(id)
+        .exec(conn)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// All suppressions, newest first
+pub async fn list_suppressions(conn: &DatabaseConnection) -> ApiResult<Vec<AnalysisSuppression>> {
+    let rows = analysis_suppressions::Entity::find()
+        .order_by_desc(analysis_suppressions::Column::CreatedAt)
+        .order_by_desc(analysis_suppressions::Column::Id)
+        .all(conn)
+        .await?;
+    Ok(rows.into_iter().map(to_suppression).collect())
+}
+
+/// Keys suppressed for one kind of warning
+pub async fn suppressed_keys(conn: &DatabaseConnection, kind: &str) -> ApiResult<HashSet<String>> {
+    let rows = analysis_suppressions::Entity::find()
+        .filter(analysis_suppressions::Column::Kind.eq(kind))
+        .all(conn)
+        .await?;
+    Ok(rows.into_iter().map(|row| row.key).collect())
+}
+
+/// Drop the items whose key is suppressed and return how many were dropped
+pub fn retain_unsuppressed<T>(
+    items: &mut Vec<T>,
+    suppressed: &HashSet<String>,
+    key: impl Fn(&T) -> String,
+) -> usize {
+    let before = items.len();
+    items.retain(|item| !suppressed.contains(&key(item)));
+    before - items.len()
+}
+
+/// Hide suppressed duplicate groups of a plugin scan
+pub async fn filter_plugin_duplicates(
+    conn: &DatabaseConnection,
+    data: &mut ManagementData<PluginInfo>,
+) -> ApiResult<()> {
+    if data.duplicates.is_empty() {
+        return Ok(());
+    }
+    let suppressed = suppressed_keys(conn, KIND_DUPLICATE_PLUGIN).await?;
+    data.suppressed_count = retain_unsuppressed(&mut data.duplicates, &suppressed, |group| {
+        group.suppression_key.clone()
+    });
+    Ok(())
+}
+
+/// Hide suppressed compatibility warnings of an analysis
+pub async fn filter_compatibility_warnings(
+    conn: &DatabaseConnection,
+    result: &mut AnalysisResult,
+) -> ApiResult<()> {
+    if result.compatibility_warnings.is_empty() {
+        return Ok(());
+    }
+    let suppressed = suppressed_keys(conn, KIND_COMPATIBILITY).await?;
+    result.suppressed_warning_count =
+        retain_unsuppressed(&mut result.compatibility_warnings, &suppressed, |warning| {
+            warning.suppression_key.clone()
+        });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+
+    fn location(folder_name: &str) -> PluginDuplicateLocation {
+        PluginDuplicateLocation {
+            relative_path: format!("Resources/plugins/{}", folder_name),
+            folder_name: folder_name.to_string(),
+            aircraft_folder: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn suppressions_round_trip() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        let key = duplicate_plugin_key(&[location("AviTab (2)"), location("AviTab")]);
+        assert_eq!(key, "duplicate_plugin:AviTab|AviTab (2)");
+
+        let added = add_suppression(&conn, KIND_DUPLICATE_PLUGIN, &key)
+            .await
+            .unwrap();
+        let again = add_suppression(&conn, KIND_DUPLICATE_PLUGIN, &key)
+            .await
+            .unwrap();
+        assert_eq!(added.id, again.id);
+        add_suppression(&conn, KIND_AIRPORT_CONFLICT, &airport_conflict_key("EGKK"))
+            .await
+            .unwrap();
+
+        let listed = list_suppressions(&conn).await.unwrap();
+        assert_eq!(listed.len(), 2);
+        let plugin_keys = suppressed_keys(&conn, KIND_DUPLICATE_PLUGIN).await.unwrap();
+        assert!(plugin_keys.contains(&key));
+        assert_eq!(plugin_keys.len(), 1);
+
+        assert!(remove_suppression(&conn, added.id).await.unwrap());
+        assert!(!remove_suppression(&conn, added.id).await.unwrap());
+        let listed = list_suppressions(&conn).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].key, "airport_conflict:EGKK");
+    }
+
+    #[tokio::test]
+    async fn keys_must_match_their_kind() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        assert!(add_suppression(&conn, "case_collision", "case_collision:a")
+            .await
+            .is_err());
+        assert!(add_suppression(&conn, KIND_AIRPORT_CONFLICT, "EGKK")
+            .await
+            .is_err());
+        assert!(
+            add_suppression(&conn, KIND_AIRPORT_CONFLICT, "airport_conflict:")
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn suppressed_items_are_counted() {
+        let mut names = vec!["EGKK".to_string(), "KSEA".to_string()];
+        let suppressed = HashSet::from([airport_conflict_key("EGKK")]);
+        let dropped = retain_unsuppressed(&mut names, &suppressed, |id| airport_conflict_key(id));
+        assert_eq!(dropped, 1);
+        assert_eq!(names, vec!["KSEA".to_string()]);
+    }
+}
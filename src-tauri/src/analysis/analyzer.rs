@@ -2,15 +2,20 @@ use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use uuid::Uuid;
 
+use crate::analysis_cache::{self, CachedItem};
+use crate::analysis_suppressions;
+use crate::archive_input::{detect_archive_format, ArchiveFormat};
 use crate::installer::{MAX_COMPRESSION_RATIO, MAX_EXTRACTION_SIZE};
 use crate::livery_patterns;
 use crate::logger;
 use crate::logger::{tr, LogMsg};
 use crate::models::{
-    AddonType, AnalysisResult, DetectedItem, InstallTask, NavdataCycle, NavdataInfo,
+    internal_location_key, AddonCompatibility, AddonType, AnalysisResult, CompatibilityWarning,
+    DetectedItem, InstallTask, InstallTaskOverride, NavdataCycle, NavdataInfo, SimVersionHints,
+    TargetRootKind,
 };
+use crate::plugin_platforms;
 use crate::scanner::{NestedPasswordRequiredError, PasswordRequiredError, Scanner};
 
 pub struct Analyzer {
@@ -31,6 +36,27 @@ impl Analyzer {
         xplane_path: &str,
         passwords: Option<HashMap<String, String>>,
         verification_preferences: Option<HashMap<String, bool>>,
+    ) -> AnalysisResult {
+        self.analyze_with_livery_hints(
+            paths,
+            xplane_path,
+            passwords,
+            verification_preferences,
+            &HashMap::new(),
+        )
+    }
+
+    /// Like [`Analyzer::analyze`], with user-chosen target aircraft for inputs that
+    /// hold liveries no livery pattern knows about. `livery_target_hints` maps an
+    /// input path to an aircraft folder relative to `Aircraft/`. Hints are only used
+    /// when the regular scan finds nothing in that input.
+    pub fn analyze_with_livery_hints(
+        &self,
+        paths: Vec<String>,
+        xplane_path: &str,
+        passwords: Option<HashMap<String, String>>,
+        verification_preferences: Option<HashMap<String, bool>>,
+        livery_target_hints: &HashMap<String, String>,
     ) -> AnalysisResult {
         // Normalize split-volume inputs (e.g. .z01/.zip.001/part2.rar) to a stable
         // archive entry path and deduplicate equivalent paths.
@@ -79,6 +105,17 @@ impl Analyzer {
         let passwords_ref = normalized_passwords.as_ref();
         let xplane_root = Path::new(xplane_path);
 
+        // Key hints the same way as the inputs, dropping folders that escape Aircraft/
+        let livery_hints: HashMap<String, String> = livery_target_hints
+            .iter()
+            .filter_map(|(path, folder)| {
+                let key = crate::archive_input::normalize_archive_entry_path(Path::new(path))
+                    .to_string_lossy()
+                    .to_string();
+                Some((key, Self::sanitize_aircraft_folder(folder)?))
+            })
+            .collect();
+
         // Parallel scan all paths using rayon for better performance
         let results: Vec<_> = normalized_paths
             .par_iter()
@@ -103,22 +140,61 @@ impl Analyzer {
 
                     if is_in_target_dir {
                         let error_msg = tr(LogMsg::CannotInstallFromXPlane);
-                        return (path_str.clone(), Err(anyhow::anyhow!(error_msg)));
+                        return (path_str.clone(), Err(anyhow::anyhow!(error_msg)), None);
                     }
                 }
 
-                let password = passwords_ref.and_then(|p| p.get(path_str).map(|s| s.as_str()));
-                // Use scan_path_with_passwords when we have a full passwords map
-                // This ensures nested archive passwords (keyed as "parent/nested") are
-                // available in ScanContext for lookup during nested archive scanning
-                if let Some(all_passwords) = passwords_ref {
-                    (
+                // Scanning an incomplete split set would only report a corrupt archive
+                let missing_parts = crate::archive_input::missing_split_parts(path);
+                if !missing_parts.is_empty() {
+                    return (
                         path_str.clone(),
-                        self.scanner.scan_path_with_passwords(path, all_passwords),
-                    )
-                } else {
-                    (path_str.clone(), self.scanner.scan_path(path, password))
+                        Err(anyhow::anyhow!(
+                            "Split archive is missing {} part(s): {}",
+                            missing_parts.len(),
+                            missing_parts.join(", ")
+                        )),
+                        None,
+                    );
                 }
+
+                // The full passwords map goes to the scanner so nested archive
+                // passwords (keyed as "parent/nested") can be found while scanning
+                let no_passwords = HashMap::new();
+                let (result, diagnostics) = match self
+                    .scanner
+                    .scan_path_with_diagnostics(path, passwords_ref.unwrap_or(&no_passwords))
+                {
+                    Ok((items, diagnostics)) => (Ok(items), Some(diagnostics)),
+                    Err(e) => (Err(e), None),
+                };
+
+                // Pattern-based detection wins; structure-based livery detection only
+                // runs for inputs the user pointed at an aircraft and nothing matched
+                let result = match (result, livery_hints.get(path_str)) {
+                    (Ok(items), Some(aircraft_folder)) if items.is_empty() => {
+                        let liveries = self.scanner.detect_liveries_for_target(
+                            path,
+                            &xplane_root.join("Aircraft").join(aircraft_folder),
+                            aircraft_folder,
+                        );
+                        logger::log_info(
+                            &format!(
+                                "Detected {} livery(s) for {} in {}",
+                                liveries.len(),
+                                aircraft_folder,
+                                path_str
+                            ),
+                            Some("analyzer"),
+                        );
+                        Ok(liveries)
+                    }
+                    (result, _) => result,
+                };
+                // Only inputs that produced nothing need explaining
+                let diagnostics =
+                    diagnostics.filter(|_| result.as_ref().is_ok_and(|items| items.is_empty()));
+                (path_str.clone(), result, diagnostics)
             })
             .collect();
 
@@ -129,8 +205,22 @@ impl Analyzer {
         let mut nested_password_required = HashMap::new(); // NEW: Track nested password requirements
                                                            // Track which archives have passwords for setting on tasks later
         let mut archive_passwords: HashMap<String, String> = HashMap::new();
+        let mut diagnostics = Vec::new();
 
-        for (path_str, result) in results {
+        for (path_str, result, scan_diagnostics) in results {
+            if let Some(scan_diagnostics) = scan_diagnostics {
+                logger::log_info(
+                    &format!(
+                        "No addons found in {}: {} entries, {} skipped marker(s), {} ignored path(s)",
+                        path_str,
+                        scan_diagnostics.entry_count,
+                        scan_diagnostics.skipped_marker_count,
+                        scan_diagnostics.ignored_path_count
+                    ),
+                    Some("analyzer"),
+                );
+                diagnostics.push(scan_diagnostics);
+            }
             match result {
                 Ok(detected) => {
                     // Store password for this archive if provided
@@ -215,6 +305,52 @@ impl Analyzer {
             })
             .collect();
 
+        // Judge each addon against the target X-Plane version
+        let xplane_major_version =
+            crate::xplane_version::get_xplane_version(xplane_root).map(|v| v.major);
+        let filtered: Vec<DetectedItem> = filtered
+            .into_iter()
+            .map(|mut item| {
+                item.compatibility = Self::evaluate_compatibility(
+                    item.sim_version_hints.as_ref(),
+                    xplane_major_version,
+                );
+                if let Some(reason) = Self::plugin_platform_warning(
+                    &item,
+                    Self::find_password(&archive_passwords, &item.path).as_deref(),
+                ) {
+                    logger::log_info(
+                        &format!("{}: {}", item.display_name, reason),
+                        Some("analyzer"),
+                    );
+                    Self::add_incompatibility(&mut item.compatibility, reason);
+                }
+                item.target_root_kind = Self::suggest_target_root(&item);
+                if item.target_root_kind == TargetRootKind::GlobalScenery {
+                    logger::log_info(
+                        &format!(
+                            "{} looks like a base mesh, suggesting Global Scenery",
+                            item.display_name
+                        ),
+                        Some("analyzer"),
+                    );
+                }
+                item
+            })
+            .collect();
+
+        // Keep the items so a selection can be turned into tasks without a rescan
+        analysis_cache::store(
+            filtered
+                .iter()
+                .map(|item| CachedItem {
+                    item: item.clone(),
+                    password: Self::find_password(&archive_passwords, &item.path),
+                    verification_preferences: verification_preferences.clone(),
+                })
+                .collect(),
+        );
+
         // Convert to install tasks, passing archive passwords
         let tasks: Vec<InstallTask> = filtered
             .into_iter()
@@ -231,9 +367,24 @@ impl Analyzer {
         // Deduplicate tasks by target path (e.g., multiple .acf files in same aircraft folder)
         let mut tasks = self.deduplicate_by_target_path(tasks);
 
+        // Stable order and ids so re-analysis doesn't reshuffle the frontend's task list
+        Self::sort_tasks_deterministically(&mut tasks);
+
         // Collect file hashes for verification
         self.collect_hashes_for_tasks(&mut tasks);
 
+        let compatibility_warnings = Self::collect_compatibility_warnings(&tasks);
+        if !compatibility_warnings.is_empty() {
+            logger::log_info(
+                &format!(
+                    "Warning: {} addon(s) look incompatible with X-Plane {}",
+                    compatibility_warnings.len(),
+                    xplane_major_version.unwrap_or_default()
+                ),
+                Some("analyzer"),
+            );
+        }
+
         logger::log_info(
             &format!("{}: {} task(s)", tr(LogMsg::AnalysisCompleted), tasks.len()),
             Some("analyzer"),
@@ -263,7 +414,207 @@ impl Analyzer {
             errors,
             password_required,
             nested_password_required,
+            xplane_major_version,
+            compatibility_warnings,
+            suppressed_warning_count: 0,
+            diagnostics,
+        }
+    }
+
+    /// Install tasks for items of an earlier analysis, looked up by task id in the
+    /// analysis cache, without scanning the inputs again. Overrides are keyed by
+    /// task id; the tasks keep the ids they were requested with.
+    pub fn build_tasks_from_cache(
+        &self,
+        items: Vec<(String, CachedItem)>,
+        xplane_path: &str,
+        overrides: &HashMap<String, InstallTaskOverride>,
+    ) -> Result<Vec<InstallTask>, String> {
+        let mut tasks = Vec::with_capacity(items.len());
+        for (task_id, cached) in items {
+            let mut item = cached.item;
+            // Taken before a rename, so the profile stays with the detected addon
+            let profile_identity = item.profile_identity();
+            if let Some(task_override) = overrides.get(&task_id) {
+                Self::apply_override(&mut item, task_override)?;
+            }
+
+            let passwords: HashMap<String, String> = cached
+                .password
+                .map(|password| HashMap::from([(item.path.clone(), password)]))
+                .unwrap_or_default();
+            let mut task = self.create_install_task(
+                item,
+                xplane_path,
+                &passwords,
+                cached.verification_preferences.as_ref(),
+            );
+            task.id = task_id;
+            task.profile_identity = Some(profile_identity);
+            tasks.push(task);
+        }
+
+        self.collect_hashes_for_tasks(&mut tasks);
+
+        logger::log_info(
+            &format!("Built {} task(s) from cached analysis", tasks.len()),
+            Some("analyzer"),
+        );
+        Ok(tasks)
+    }
+
+    fn apply_override(
+        item: &mut DetectedItem,
+        task_override: &InstallTaskOverride,
+    ) -> Result<(), String> {
+        if let Some(ref raw) = task_override.display_name {
+            let name = raw.trim();
+            if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\', ':']) {
+                return Err(format!("Invalid name: {}", raw));
+            }
+            item.display_name = name.to_string();
+        }
+
+        if let Some(ref raw) = task_override.livery_target_folder {
+            if item.addon_type != AddonType::Livery {
+                return Err(format!(
+                    "Only liveries can be moved to another aircraft: {}",
+                    item.display_name
+                ));
+            }
+            let folder = Self::sanitize_aircraft_folder(raw)
+                .ok_or_else(|| format!("Invalid aircraft folder: {}", raw))?;
+            item.livery_target_folder = Some(folder);
+        }
+        Ok(())
+    }
+
+    /// Compare the version hints read by the scanner with the target X-Plane major version
+    fn evaluate_compatibility(
+        hints: Option<&SimVersionHints>,
+        xplane_major: Option<u32>,
+    ) -> AddonCompatibility {
+        let (Some(hints), Some(xplane_major)) = (hints, xplane_major) else {
+            return AddonCompatibility::Unknown;
+        };
+
+        if let Some(acf_version) = hints.acf_format_version {
+            // 1100 => X-Plane 11, 1200 => X-Plane 12
+            let acf_major = acf_version / 100;
+            if acf_major > xplane_major {
+                return AddonCompatibility::LikelyIncompatible {
+                    reason: format!(
+                        "Aircraft was saved for X-Plane {} and can't be loaded by X-Plane {}",
+                        acf_major, xplane_major
+                    ),
+                };
+            }
+            // X-Plane loads aircraft from the previous major version, but not older ones
+            if acf_major + 1 < xplane_major {
+                return AddonCompatibility::LikelyIncompatible {
+                    reason: format!(
+                        "Aircraft was made for X-Plane {} and is not supported by X-Plane {}",
+                        acf_major, xplane_major
+                    ),
+                };
+            }
+        }
+
+        if xplane_major >= 12 && !hints.legacy_library_exports.is_empty() {
+            return AddonCompatibility::LikelyIncompatible {
+                reason: format!(
+                    "Library replaces X-Plane 11 default scenery that X-Plane {} no longer includes ({})",
+                    xplane_major,
+                    hints.legacy_library_exports.join(", ")
+                ),
+            };
+        }
+
+        if hints.acf_format_version.is_some() {
+            AddonCompatibility::Compatible
+        } else {
+            AddonCompatibility::Unknown
+        }
+    }
+
+    /// Mark `compatibility` as likely incompatible for `reason`, keeping a
+    /// reason found earlier
+    fn add_incompatibility(compatibility: &mut AddonCompatibility, reason: String) {
+        *compatibility = match std::mem::take(compatibility) {
+            AddonCompatibility::LikelyIncompatible { reason: earlier } => {
+                AddonCompatibility::LikelyIncompatible {
+                    reason: format!("{}; {}", earlier, reason),
+                }
+            }
+            _ => AddonCompatibility::LikelyIncompatible { reason },
+        };
+    }
+
+    /// Warning for a plugin without a binary X-Plane loads on this platform.
+    /// Binaries inside nested archives aren't checked.
+    fn plugin_platform_warning(item: &DetectedItem, password: Option<&str>) -> Option<String> {
+        if item.addon_type != AddonType::Plugin || item.extraction_chain.is_some() {
+            return None;
         }
+        let source = Path::new(&item.path);
+        let binaries = if source.is_dir() {
+            let (xpl_files, xfmp_files) = crate::management_index::find_xpl_and_xfmp_files(source);
+            let files: Vec<&String> = xpl_files.iter().chain(xfmp_files.iter()).collect();
+            plugin_platforms::from_folder(source, &files[..])
+        } else {
+            let format = detect_archive_format(source)?;
+            let files = match crate::installer::list_archive_files(
+                source,
+                format,
+                item.archive_internal_root.as_deref(),
+                password,
+            ) {
+                Ok(files) => files,
+                Err(e) => {
+                    logger::log_debug(
+                        &format!("Could not list {} for a platform check: {}", item.path, e),
+                        Some("analyzer"),
+                        None,
+                    );
+                    return None;
+                }
+            };
+            let files: Vec<&String> = files
+                .iter()
+                .filter(|file| {
+                    let lower = file.to_ascii_lowercase();
+                    lower.ends_with(".xpl") || lower.ends_with(".xfmp")
+                })
+                .collect();
+            if files.is_empty() {
+                return None;
+            }
+            plugin_platforms::from_paths(&files[..])
+        };
+
+        if plugin_platforms::supports_current_platform(&binaries) {
+            return None;
+        }
+        Some(format!(
+            "Plugin has no binary X-Plane can load on {} (found: {})",
+            plugin_platforms::current_platform_name(),
+            plugin_platforms::describe(&binaries)
+        ))
+    }
+
+    fn collect_compatibility_warnings(tasks: &[InstallTask]) -> Vec<CompatibilityWarning> {
+        tasks
+            .iter()
+            .filter_map(|task| match &task.compatibility {
+                AddonCompatibility::LikelyIncompatible { reason } => Some(CompatibilityWarning {
+                    task_id: task.id.clone(),
+                    display_name: task.display_name.clone(),
+                    reason: reason.clone(),
+                    suppression_key: analysis_suppressions::compatibility_key(&task.display_name),
+                }),
+                _ => None,
+            })
+            .collect()
     }
 
     fn format_scan_error_for_display(path_str: &str, error: &anyhow::Error) -> String {
@@ -300,6 +651,32 @@ impl Analyzer {
         seen.into_values().collect()
     }
 
+    /// Sort tasks by source path, then location inside the source, then addon type.
+    /// Ids that still collide get a deterministic `-2`, `-3`... suffix so the
+    /// frontend can key on them.
+    fn sort_tasks_deterministically(tasks: &mut [InstallTask]) {
+        tasks.sort_by_cached_key(|task| {
+            (
+                task.source_path.replace('\\', "/"),
+                internal_location_key(
+                    task.archive_internal_root.as_deref(),
+                    task.extraction_chain.as_ref(),
+                ),
+                format!("{:?}", task.addon_type),
+                task.target_path.clone(),
+            )
+        });
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for task in tasks.iter_mut() {
+            let count = seen.entry(task.id.clone()).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                task.id = format!("{}-{}", task.id, count);
+            }
+        }
+    }
+
     /// Deduplicate detected items based on path hierarchy
     /// Different addon types are deduplicated separately to allow multiple types from one archive
     fn deduplicate(&self, items: Vec<DetectedItem>) -> Vec<DetectedItem> {
@@ -692,73 +1069,220 @@ impl Analyzer {
         })
     }
 
-    /// Find the aircraft folder that matches the given aircraft type ID for livery installation
-    /// Returns (aircraft_folder_path, found) where found indicates if the aircraft was found
-    fn find_aircraft_for_livery(
-        &self,
-        xplane_path: &str,
-        aircraft_type_id: &str,
-    ) -> Option<PathBuf> {
+    /// Normalize a user-supplied aircraft folder to `/`-separated components below
+    /// `Aircraft/`, rejecting anything that could point outside of it
+    fn sanitize_aircraft_folder(raw: &str) -> Option<String> {
+        let normalized = raw.trim().replace('\\', "/");
+        let trimmed = normalized.trim_matches('/');
+        let trimmed = trimmed
+            .strip_prefix("Aircraft/")
+            .filter(|rest| !rest.is_empty())
+            .unwrap_or(trimmed);
+
+        let components: Vec<&str> = trimmed.split('/').collect();
+        let valid = components
+            .iter()
+            .all(|c| !c.is_empty() && *c != "." && *c != ".." && !c.contains(':'));
+        (valid && !trimmed.is_empty()).then(|| components.join("/"))
+    }
+
+    /// Installed aircraft folders whose .acf (or disabled .xfma) file matches the
+    /// given aircraft type ID, relative to `Aircraft/` with `/` separators
+    fn find_aircraft_folders_for_livery(xplane_path: &str, aircraft_type_id: &str) -> Vec<String> {
         let aircraft_dir = Path::new(xplane_path).join("Aircraft");
         if !aircraft_dir.exists() {
-            return None;
+            return Vec::new();
         }
 
-        // Recursively search for .acf files that match the aircraft type
-        for entry in walkdir::WalkDir::new(&aircraft_dir)
+        // Recursively search for aircraft files that match the aircraft type
+        let mut folders: Vec<String> = walkdir::WalkDir::new(&aircraft_dir)
             .max_depth(4) // Limit depth to avoid scanning too deep
             .into_iter()
             .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    if ext.eq_ignore_ascii_case("acf") {
-                        if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-                            // Check if this ACF file matches our aircraft type
-                            if let Some(matched_type) =
-                                livery_patterns::check_acf_identifier(file_name)
-                            {
-                                if matched_type == aircraft_type_id {
-                                    // Found matching aircraft, return its parent directory
-                                    if let Some(parent) = path.parent() {
-                                        logger::log_info(
-                                            &format!(
-                                                "Found aircraft for livery: {} -> {}",
-                                                aircraft_type_id,
-                                                parent.display()
-                                            ),
-                                            Some("analyzer"),
-                                        );
-                                        return Some(parent.to_path_buf());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+            .filter(|entry| {
+                let path = entry.path();
+                path.is_file()
+                    && path.extension().is_some_and(|ext| {
+                        ext.eq_ignore_ascii_case("acf") || ext.eq_ignore_ascii_case("xfma")
+                    })
+                    && path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .and_then(livery_patterns::check_acf_identifier)
+                        .is_some_and(|matched_type| matched_type == aircraft_type_id)
+            })
+            .filter_map(|entry| {
+                let relative = entry.path().parent()?.strip_prefix(&aircraft_dir).ok()?;
+                let components: Vec<String> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect();
+                (!components.is_empty()).then(|| components.join("/"))
+            })
+            .collect();
+        folders.sort();
+        folders.dedup();
 
         logger::log_info(
-            &format!("Aircraft not found for livery type: {}", aircraft_type_id),
+            &format!(
+                "Found {} aircraft for livery type {}: {:?}",
+                folders.len(),
+                aircraft_type_id,
+                folders
+            ),
             Some("analyzer"),
         );
-        None
+        folders
+    }
+
+    /// Check the aircraft folder chosen for a livery task and point the task's
+    /// target at it. A livery that fits several installed aircraft must name one
+    /// of them; one that fits none can't be installed.
+    pub fn apply_livery_target_choice(
+        task: &mut InstallTask,
+        xplane_path: &str,
+    ) -> Result<(), String> {
+        if task.addon_type != AddonType::Livery {
+            return Ok(());
+        }
+        if let Some(ref reason) = task.not_installable_reason {
+            return Err(format!("{}: {}", task.display_name, reason));
+        }
+
+        let folder = match task.target_aircraft_folder.as_deref() {
+            Some(raw) => Self::sanitize_aircraft_folder(raw)
+                .ok_or_else(|| format!("Invalid aircraft folder: {}", raw))?,
+            None if task.livery_target_candidates.len() > 1 => {
+                return Err(format!(
+                    "{} fits several installed aircraft, choose one of: {}",
+                    task.display_name,
+                    task.livery_target_candidates.join(", ")
+                ));
+            }
+            None => return Ok(()),
+        };
+        if task.livery_target_candidates.len() > 1
+            && !task.livery_target_candidates.contains(&folder)
+        {
+            return Err(format!(
+                "{} does not fit the aircraft in Aircraft/{}",
+                task.display_name, folder
+            ));
+        }
+
+        let aircraft_dir = Path::new(xplane_path).join("Aircraft").join(&folder);
+        if !aircraft_dir.is_dir() {
+            return Err(format!("Aircraft folder not found: Aircraft/{}", folder));
+        }
+        let livery_name = Path::new(&task.target_path)
+            .file_name()
+            .map(|name| name.to_os_string())
+            .ok_or_else(|| format!("Invalid livery target: {}", task.target_path))?;
+        let target_path = aircraft_dir.join("liveries").join(livery_name);
+
+        task.conflict_exists = target_path.exists().then_some(true);
+        task.target_path = target_path.to_string_lossy().to_string();
+        task.target_aircraft_folder = Some(folder);
+        Ok(())
+    }
+
+    /// Point a scenery task's target at the root folder it should install into,
+    /// which the user may have changed from the analyzer's suggestion
+    pub fn apply_target_root_choice(
+        task: &mut InstallTask,
+        xplane_path: &str,
+    ) -> Result<(), String> {
+        if task.addon_type != AddonType::Scenery {
+            if task.target_root_kind == TargetRootKind::GlobalScenery {
+                return Err(format!(
+                    "{}: only scenery can be installed into Global Scenery",
+                    task.display_name
+                ));
+            }
+            return Ok(());
+        }
+
+        let root = Path::new(xplane_path).join(task.target_root_kind.folder_name());
+        if task.target_root_kind == TargetRootKind::GlobalScenery && !root.is_dir() {
+            return Err(format!(
+                "Global Scenery folder not found: {}",
+                root.display()
+            ));
+        }
+        let folder_name = Path::new(&task.target_path)
+            .file_name()
+            .map(|name| name.to_os_string())
+            .ok_or_else(|| format!("Invalid scenery target: {}", task.target_path))?;
+        let target_path = root.join(folder_name);
+        if target_path != Path::new(&task.target_path) {
+            task.conflict_exists = target_path.exists().then_some(true);
+            task.target_path = target_path.to_string_lossy().to_string();
+        }
+        Ok(())
+    }
+
+    /// Suggest Global Scenery for scenery packages that look like a base mesh
+    /// meant for it, judged from the files the package installs
+    fn suggest_target_root(item: &DetectedItem) -> TargetRootKind {
+        if item.addon_type != AddonType::Scenery || item.extraction_chain.is_some() {
+            return TargetRootKind::CustomScenery;
+        }
+        let source = Path::new(&item.path);
+        let files = if source.is_dir() {
+            walkdir::WalkDir::new(source)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_file())
+                .filter_map(|entry| {
+                    let relative = entry.path().strip_prefix(source).ok()?;
+                    Some(relative.to_string_lossy().replace('\\', "/"))
+                })
+                .collect()
+        } else {
+            let Some(format) = crate::archive_input::detect_archive_format(source) else {
+                return TargetRootKind::CustomScenery;
+            };
+            match crate::installer::list_archive_files(
+                source,
+                format,
+                item.archive_internal_root.as_deref(),
+                None,
+            ) {
+                Ok(files) => files,
+                Err(e) => {
+                    logger::log_debug(
+                        &format!("Could not list {} for a root suggestion: {}", item.path, e),
+                        Some("analyzer"),
+                        None,
+                    );
+                    return TargetRootKind::CustomScenery;
+                }
+            }
+        };
+
+        if crate::scenery_classifier::is_global_scenery_mesh(&item.display_name, &files) {
+            TargetRootKind::GlobalScenery
+        } else {
+            TargetRootKind::CustomScenery
+        }
     }
 
     /// Create an install task from a detected item
     fn create_install_task(
         &self,
-        item: DetectedItem,
+        mut item: DetectedItem,
         xplane_path: &str,
         archive_passwords: &HashMap<String, String>,
         verification_preferences: Option<&HashMap<String, bool>>,
     ) -> InstallTask {
+        let id = item.stable_id();
         let xplane_root = Path::new(xplane_path);
 
         // For Livery type, we need special handling to find the target aircraft
         // For LuaScript type, we need to check if FlyWithLua is installed
+        let mut target_aircraft_folder = None;
+        let mut not_installable_reason = None;
         let (target_path, livery_aircraft_found, flywithlua_installed) =
             if item.addon_type == AddonType::Livery {
                 // Extract the livery name from display_name (remove the aircraft name suffix)
@@ -771,16 +1295,44 @@ impl Analyzer {
                     item.display_name.clone()
                 };
 
-                if let Some(ref aircraft_type_id) = item.livery_aircraft_type {
-                    // Try to find the target aircraft
-                    if let Some(aircraft_folder) =
-                        self.find_aircraft_for_livery(xplane_path, aircraft_type_id)
-                    {
-                        // Found the aircraft, install to its liveries folder
-                        let liveries_path = aircraft_folder.join("liveries").join(&livery_name);
+                if let Some(ref aircraft_folder) = item.livery_target_folder {
+                    // The user chose the aircraft, no need to search for it
+                    let aircraft_dir = xplane_root.join("Aircraft").join(aircraft_folder);
+                    let found = aircraft_dir.is_dir();
+                    target_aircraft_folder = Some(aircraft_folder.clone());
+                    if !found {
+                        not_installable_reason = Some(format!(
+                            "Aircraft folder not found: Aircraft/{}",
+                            aircraft_folder
+                        ));
+                    }
+                    (
+                        aircraft_dir.join("liveries").join(&livery_name),
+                        found,
+                        true,
+                    )
+                } else if let Some(ref aircraft_type_id) = item.livery_aircraft_type {
+                    // Find every installed aircraft the livery fits
+                    item.livery_target_candidates =
+                        Self::find_aircraft_folders_for_livery(xplane_path, aircraft_type_id);
+                    if let Some(first) = item.livery_target_candidates.first() {
+                        // With several candidates the user has to choose; until then
+                        // the first one stands in for the conflict check
+                        if item.livery_target_candidates.len() == 1 {
+                            target_aircraft_folder = Some(first.clone());
+                        }
+                        let liveries_path = xplane_root
+                            .join("Aircraft")
+                            .join(first)
+                            .join("liveries")
+                            .join(&livery_name);
                         (liveries_path, true, true)
                     } else {
                         // Aircraft not found, use a placeholder path
+                        let aircraft_name = livery_patterns::get_aircraft_name(aircraft_type_id)
+                            .unwrap_or_else(|| aircraft_type_id.clone());
+                        not_installable_reason =
+                            Some(format!("No installed aircraft matches {}", aircraft_name));
                         let placeholder = xplane_root
                             .join("Aircraft")
                             .join("[Aircraft Not Found]")
@@ -790,6 +1342,8 @@ impl Analyzer {
                     }
                 } else {
                     // No aircraft type specified, shouldn't happen but handle gracefully
+                    not_installable_reason =
+                        Some("The aircraft this livery belongs to is unknown".to_string());
                     let placeholder = xplane_root
                         .join("Aircraft")
                         .join("[Unknown Aircraft]")
@@ -813,7 +1367,7 @@ impl Analyzer {
                 let target_base = match item.addon_type {
                     AddonType::Aircraft => xplane_root.join("Aircraft"),
                     AddonType::Scenery | AddonType::SceneryLibrary => {
-                        xplane_root.join("Custom Scenery")
+                        xplane_root.join(item.target_root_kind.folder_name())
                     }
                     AddonType::Plugin => xplane_root.join("Resources").join("plugins"),
                     AddonType::Navdata => {
@@ -928,13 +1482,23 @@ impl Analyzer {
             "directory"
         };
 
+        // For Navdata: an older cycle than X-Plane uses now needs allow_downgrade
+        let navdata_downgrade_from = item.navdata_info.as_ref().and_then(|new_info| {
+            crate::management_index::navdata_downgrade_from(
+                new_info,
+                existing_navdata_info.as_ref(),
+                xplane_root,
+            )
+        });
+
         // Check if verification is enabled for this source type
         let enable_verification = verification_preferences
             .and_then(|prefs| prefs.get(source_type).copied())
             .unwrap_or(true); // Default to true if not specified
 
+        let profile_identity = item.profile_identity();
         InstallTask {
-            id: Uuid::new_v4().to_string(),
+            id,
             addon_type: item.addon_type,
             source_path: item.path,
             original_input_path: Some(item.original_input_path),
@@ -944,12 +1508,16 @@ impl Analyzer {
             archive_internal_root: item.archive_internal_root,
             extraction_chain: item.extraction_chain,
             should_overwrite: false, // Default to false, controlled by frontend
+            allow_case_collision_rename: false,
+            allow_symlinks: false,
             password,
             estimated_size,
             size_warning,
             size_confirmed: false, // User must confirm if there's a warning
             existing_navdata_info,
             new_navdata_info: item.navdata_info,
+            navdata_downgrade_from,
+            allow_downgrade: false,
             existing_version_info,
             new_version_info: item.version_info,
             backup_liveries: true,     // Default to true (safe)
@@ -960,8 +1528,17 @@ impl Analyzer {
             enable_verification,       // Based on verification preferences
             livery_aircraft_type: item.livery_aircraft_type,
             livery_aircraft_found,
+            livery_target_candidates: item.livery_target_candidates,
+            target_aircraft_folder,
+            not_installable_reason,
             flywithlua_installed,
             companion_paths: item.companion_paths,
+            compatibility: item.compatibility,
+            updater_provider: item.updater_provider,
+            suggested_root_kind: item.target_root_kind,
+            target_root_kind: item.target_root_kind,
+            profile_identity: Some(profile_identity),
+            install_profile: None,
         }
     }
 
@@ -1025,6 +1602,11 @@ impl Analyzer {
             return (None, None);
         }
 
+        let parts = crate::archive_input::split_archive_parts(source_path);
+        if parts.len() > 1 {
+            return self.estimate_split_archive_size(source_path, &parts);
+        }
+
         let extension = source_path
             .extension()
             .and_then(|s| s.to_str())
@@ -1072,6 +1654,11 @@ impl Analyzer {
             }
         };
 
+        // Check cache first
+        if let Some(cached) = crate::cache::get_cached_metadata(archive_path) {
+            return self.check_size_warning(archive_size, cached.uncompressed_size);
+        }
+
         let mut archive = match ZipArchive::new(file) {
             Ok(a) => a,
             Err(e) => {
@@ -1096,6 +1683,8 @@ impl Analyzer {
         // If we couldn't get any size info, use conservative estimate
         if total_uncompressed == 0 && !archive.is_empty() {
             total_uncompressed = archive_size.saturating_mul(5);
+        } else if total_uncompressed > 0 {
+            crate::cache::cache_metadata(archive_path, total_uncompressed, archive.len());
         }
 
         self.check_size_warning(archive_size, total_uncompressed)
@@ -1205,6 +1794,62 @@ impl Analyzer {
         self.check_size_warning(archive_size, estimated_uncompressed)
     }
 
+    /// Estimate the uncompressed size of a split archive set. The compressed size
+    /// is that of all volumes together, so the ratio check sees the whole set.
+    fn estimate_split_archive_size(
+        &self,
+        source_path: &Path,
+        parts: &[PathBuf],
+    ) -> (Option<u64>, Option<String>) {
+        let archive_size: u64 = parts
+            .iter()
+            .filter_map(|part| fs::metadata(part).ok())
+            .map(|meta| meta.len())
+            .sum();
+
+        if let Some(cached) = crate::cache::get_cached_metadata(source_path) {
+            return self.check_size_warning(archive_size, cached.uncompressed_size);
+        }
+
+        // Listing ZIP and 7z volumes would mean joining them into one file
+        // first, so those are estimated from the summed volume sizes alone.
+        // unrar follows the volumes on its own from the first one.
+        let first_part = parts.first().map(PathBuf::as_path).unwrap_or(source_path);
+        if detect_archive_format(first_part) != Some(ArchiveFormat::Rar) {
+            return self.check_size_warning(archive_size, archive_size.saturating_mul(5));
+        }
+
+        // (uncompressed size, entry count) of the whole set
+        let listing = unrar::Archive::new(first_part)
+            .open_for_listing()
+            .ok()
+            .map(|archive| {
+                archive.flatten().fold((0u64, 0usize), |(total, count), e| {
+                    (total.saturating_add(e.unpacked_size), count + 1)
+                })
+            });
+
+        let estimated_uncompressed = match listing {
+            Some((total, file_count)) if total > 0 => {
+                crate::cache::cache_metadata(source_path, total, file_count);
+                total
+            }
+            _ => {
+                logger::log_debug(
+                    &format!(
+                        "Could not list split archive for size estimation: {}",
+                        source_path.display()
+                    ),
+                    Some("analyzer"),
+                    Some("analyzer.rs"),
+                );
+                archive_size.saturating_mul(5)
+            }
+        };
+
+        self.check_size_warning(archive_size, estimated_uncompressed)
+    }
+
     /// Check if the archive size warrants a warning
     fn check_size_warning(
         &self,
@@ -1272,6 +1917,7 @@ impl Analyzer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{ExtractionChain, NestedArchiveInfo, UpdaterProvider};
 
     // Helper function to create DetectedItem for tests
     fn create_detected_item(
@@ -1289,8 +1935,14 @@ mod tests {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info: None,
             companion_paths: Vec::new(),
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }
     }
 
@@ -1330,12 +1982,16 @@ mod tests {
             conflict_exists: None,
             archive_internal_root: None,
             should_overwrite: false,
+            allow_case_collision_rename: false,
+            allow_symlinks: false,
             password: None,
             estimated_size: None,
             size_warning: None,
             size_confirmed: false,
             existing_navdata_info: None,
             new_navdata_info: None,
+            navdata_downgrade_from: None,
+            allow_downgrade: false,
             existing_version_info: None,
             new_version_info: None,
             backup_liveries: true,
@@ -1347,8 +2003,17 @@ mod tests {
             enable_verification: true,
             livery_aircraft_type: None,
             livery_aircraft_found: false,
+            livery_target_candidates: Vec::new(),
+            target_aircraft_folder: None,
+            not_installable_reason: None,
             flywithlua_installed: true,
             companion_paths: Vec::new(),
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            suggested_root_kind: TargetRootKind::CustomScenery,
+            target_root_kind: TargetRootKind::CustomScenery,
+            profile_identity: None,
+            install_profile: None,
         }
     }
 
@@ -1788,6 +2453,378 @@ mod tests {
         assert_eq!(plugin_names[0], "Standalone");
     }
 
+    #[test]
+    fn test_stable_id_ignores_password_and_path_separators() {
+        let mut item = create_detected_item(
+            AddonType::Aircraft,
+            "C:\\Downloads\\pack.zip",
+            "A330",
+            Some("A330".to_string()),
+        );
+        let id = item.stable_id();
+        assert_eq!(id.len(), 32);
+
+        item.path = "C:/Downloads/pack.zip".to_string();
+        assert_eq!(item.stable_id(), id);
+
+        // Same location reached through an extraction chain, before and after the
+        // nested archive's password is known
+        let chain = |password: Option<&str>| ExtractionChain {
+            archives: vec![NestedArchiveInfo {
+                internal_path: "inner.7z".to_string(),
+                password: password.map(str::to_string),
+                format: "7z".to_string(),
+                password_required: true,
+            }],
+            final_internal_root: Some("A330".to_string()),
+        };
+        item.extraction_chain = Some(chain(None));
+        let chained_id = item.stable_id();
+        assert_ne!(chained_id, id);
+        item.extraction_chain = Some(chain(Some("secret")));
+        assert_eq!(item.stable_id(), chained_id);
+
+        item.addon_type = AddonType::Livery;
+        assert_ne!(item.stable_id(), chained_id);
+    }
+
+    #[test]
+    fn test_repeated_analysis_yields_same_ids_and_order() {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+        use zip::ZipWriter;
+
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        let xplane_root = temp.path().join("X-Plane 12");
+        fs::create_dir_all(xplane_root.join("Aircraft")).expect("failed to create Aircraft");
+
+        let archive_path = temp.path().join("fleet.zip");
+        let mut writer =
+            ZipWriter::new(fs::File::create(&archive_path).expect("failed to create zip"));
+        for name in ["Zulu/zulu.acf", "Alpha/alpha.acf", "Mike/mike.acf"] {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .expect("failed to start zip entry");
+            writer
+                .write_all(b"I\n1100 version\n")
+                .expect("failed to write acf");
+        }
+        writer.finish().expect("failed to finish zip");
+
+        let analyzer = Analyzer::new();
+        let paths = vec![archive_path.to_string_lossy().to_string()];
+        let xplane_path = xplane_root.to_string_lossy().to_string();
+        let ids = |result: &AnalysisResult| -> Vec<(String, Option<String>)> {
+            result
+                .tasks
+                .iter()
+                .map(|t| (t.id.clone(), t.archive_internal_root.clone()))
+                .collect()
+        };
+
+        let first = analyzer.analyze(paths.clone(), &xplane_path, None, None);
+        assert_eq!(first.tasks.len(), 3);
+        let roots: Vec<Option<String>> = first
+            .tasks
+            .iter()
+            .map(|t| t.archive_internal_root.clone())
+            .collect();
+        let mut sorted_roots = roots.clone();
+        sorted_roots.sort();
+        assert_eq!(roots, sorted_roots);
+
+        for _ in 0..3 {
+            let again = analyzer.analyze(paths.clone(), &xplane_path, None, None);
+            assert_eq!(ids(&again), ids(&first));
+        }
+
+        // Re-scan with a password map, as done after the password prompt
+        let mut passwords = HashMap::new();
+        passwords.insert(paths[0].clone(), "secret".to_string());
+        let with_password = analyzer.analyze(paths, &xplane_path, Some(passwords), None);
+        assert_eq!(ids(&with_password), ids(&first));
+    }
+
+    #[test]
+    fn test_sort_tasks_disambiguates_colliding_ids() {
+        let mut tasks = vec![
+            create_install_task("same", AddonType::Plugin, "/b", "/x/b", "B"),
+            create_install_task("same", AddonType::Plugin, "/a", "/x/a", "A"),
+        ];
+        Analyzer::sort_tasks_deterministically(&mut tasks);
+        assert_eq!(tasks[0].source_path, "/a");
+        assert_eq!(tasks[0].id, "same");
+        assert_eq!(tasks[1].id, "same-2");
+    }
+
+    #[test]
+    fn test_evaluate_compatibility_against_xplane_major() {
+        let acf = |version| SimVersionHints {
+            acf_format_version: Some(version),
+            legacy_library_exports: Vec::new(),
+        };
+
+        assert_eq!(
+            Analyzer::evaluate_compatibility(Some(&acf(1100)), Some(12)),
+            AddonCompatibility::Compatible
+        );
+        assert!(matches!(
+            Analyzer::evaluate_compatibility(Some(&acf(1200)), Some(11)),
+            AddonCompatibility::LikelyIncompatible { .. }
+        ));
+        assert!(matches!(
+            Analyzer::evaluate_compatibility(Some(&acf(1000)), Some(12)),
+            AddonCompatibility::LikelyIncompatible { .. }
+        ));
+        assert_eq!(
+            Analyzer::evaluate_compatibility(Some(&acf(1200)), None),
+            AddonCompatibility::Unknown
+        );
+        assert_eq!(
+            Analyzer::evaluate_compatibility(None, Some(12)),
+            AddonCompatibility::Unknown
+        );
+
+        let library = SimVersionHints {
+            acf_format_version: None,
+            legacy_library_exports: vec!["lib/g8/roads.net".to_string()],
+        };
+        assert!(matches!(
+            Analyzer::evaluate_compatibility(Some(&library), Some(12)),
+            AddonCompatibility::LikelyIncompatible { .. }
+        ));
+        assert_eq!(
+            Analyzer::evaluate_compatibility(Some(&library), Some(11)),
+            AddonCompatibility::Unknown
+        );
+
+        let mut compatibility = Analyzer::evaluate_compatibility(Some(&library), Some(12));
+        Analyzer::add_incompatibility(&mut compatibility, "No Linux binary".to_string());
+        let AddonCompatibility::LikelyIncompatible { reason } = compatibility else {
+            panic!("expected an incompatibility");
+        };
+        assert!(reason.starts_with("Library replaces X-Plane 11 default scenery"));
+        assert!(reason.ends_with("; No Linux binary"));
+    }
+
+    #[test]
+    fn test_livery_target_hint_detects_unknown_livery() {
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        let xplane_root = temp.path().join("X-Plane 12");
+        let aircraft_objects = xplane_root.join("Aircraft/Niche Jet/objects");
+        fs::create_dir_all(&aircraft_objects).expect("failed to create aircraft");
+        fs::write(aircraft_objects.join("fuselage.png"), b"png").unwrap();
+
+        let pack = temp.path().join("Livery Pack");
+        fs::create_dir_all(pack.join("Blue/objects")).unwrap();
+        fs::write(pack.join("Blue/objects/Fuselage.png"), b"png").unwrap();
+        fs::write(pack.join("Blue/icon11.png"), b"png").unwrap();
+
+        let analyzer = Analyzer::new();
+        let paths = vec![pack.to_string_lossy().to_string()];
+        let xplane_path = xplane_root.to_string_lossy().to_string();
+
+        let unhinted = analyzer.analyze(paths.clone(), &xplane_path, None, None);
+        assert!(unhinted.tasks.is_empty());
+
+        let hints = HashMap::from([(paths[0].clone(), "Niche Jet".to_string())]);
+        let result = analyzer.analyze_with_livery_hints(paths, &xplane_path, None, None, &hints);
+        assert_eq!(result.tasks.len(), 1);
+        let task = &result.tasks[0];
+        assert_eq!(task.addon_type, AddonType::Livery);
+        assert!(task.livery_aircraft_found);
+        assert_eq!(
+            Path::new(&task.target_path),
+            xplane_root.join("Aircraft/Niche Jet/liveries/Blue")
+        );
+    }
+
+    #[test]
+    fn test_sanitize_aircraft_folder_stays_inside_aircraft() {
+        assert_eq!(
+            Analyzer::sanitize_aircraft_folder(" Aircraft\\Laminar Research/Cessna 172/ "),
+            Some("Laminar Research/Cessna 172".to_string())
+        );
+        assert_eq!(Analyzer::sanitize_aircraft_folder("../Resources"), None);
+        assert_eq!(Analyzer::sanitize_aircraft_folder("C:/Aircraft/A320"), None);
+        assert_eq!(Analyzer::sanitize_aircraft_folder("  "), None);
+    }
+
+    #[test]
+    fn test_build_tasks_from_cache_applies_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let xplane_root = dir.path();
+        let analyzer = Analyzer::new();
+        let cached = |item: DetectedItem| CachedItem {
+            item,
+            password: None,
+            verification_preferences: Some(HashMap::from([("zip".to_string(), false)])),
+        };
+        let livery = create_detected_item(
+            AddonType::Livery,
+            "/downloads/pack.zip",
+            "Red (A320)",
+            Some("Red".to_string()),
+        );
+        let scenery = create_detected_item(
+            AddonType::Scenery,
+            "/downloads/pack.zip",
+            "KSEA",
+            Some("KSEA".to_string()),
+        );
+
+        let overrides = HashMap::from([(
+            "livery-id".to_string(),
+            InstallTaskOverride {
+                display_name: Some(" Red Tail ".to_string()),
+                livery_target_folder: Some("Aircraft/Airbus A320".to_string()),
+            },
+        )]);
+        let tasks = analyzer
+            .build_tasks_from_cache(
+                vec![
+                    ("livery-id".to_string(), cached(livery)),
+                    ("scenery-id".to_string(), cached(scenery.clone())),
+                ],
+                &xplane_root.to_string_lossy(),
+                &overrides,
+            )
+            .unwrap();
+        assert_eq!(tasks[0].id, "livery-id");
+        assert_eq!(tasks[0].display_name, "Red Tail");
+        assert_eq!(
+            Path::new(&tasks[0].target_path),
+            xplane_root.join("Aircraft/Airbus A320/liveries/Red Tail")
+        );
+        assert_eq!(
+            Path::new(&tasks[1].target_path),
+            xplane_root.join("Custom Scenery/KSEA")
+        );
+
+        let moved_scenery = HashMap::from([(
+            "scenery-id".to_string(),
+            InstallTaskOverride {
+                display_name: None,
+                livery_target_folder: Some("Airbus A320".to_string()),
+            },
+        )]);
+        let renamed_outside = HashMap::from([(
+            "scenery-id".to_string(),
+            InstallTaskOverride {
+                display_name: Some("../KSEA".to_string()),
+                livery_target_folder: None,
+            },
+        )]);
+        for overrides in [moved_scenery, renamed_outside] {
+            assert!(analyzer
+                .build_tasks_from_cache(
+                    vec![("scenery-id".to_string(), cached(scenery.clone()))],
+                    &xplane_root.to_string_lossy(),
+                    &overrides,
+                )
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_livery_fitting_several_aircraft_needs_a_chosen_target() {
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        let xplane_root = temp.path().join("X-Plane 12");
+        for folder in ["ToLiss A320", "Backup/ToLiss A320 old"] {
+            let aircraft = xplane_root.join("Aircraft").join(folder);
+            fs::create_dir_all(&aircraft).unwrap();
+            fs::write(aircraft.join("a320_StdDef.acf"), b"acf").unwrap();
+        }
+        let xplane_path = xplane_root.to_string_lossy().to_string();
+
+        let analyzer = Analyzer::new();
+        let mut livery = create_detected_item(
+            AddonType::Livery,
+            "/downloads/red.zip",
+            "Red (ToLiss A320)",
+            Some("Red".to_string()),
+        );
+        livery.livery_aircraft_type = Some("TOLISS_A320".to_string());
+        let mut task =
+            analyzer.create_install_task(livery.clone(), &xplane_path, &HashMap::new(), None);
+        assert!(task.livery_aircraft_found);
+        assert_eq!(
+            task.livery_target_candidates,
+            vec![
+                "Backup/ToLiss A320 old".to_string(),
+                "ToLiss A320".to_string()
+            ]
+        );
+        assert!(Analyzer::apply_livery_target_choice(&mut task.clone(), &xplane_path).is_err());
+
+        task.target_aircraft_folder = Some("Other".to_string());
+        assert!(Analyzer::apply_livery_target_choice(&mut task.clone(), &xplane_path).is_err());
+        task.target_aircraft_folder = Some("Aircraft/ToLiss A320".to_string());
+        Analyzer::apply_livery_target_choice(&mut task, &xplane_path).unwrap();
+        assert_eq!(
+            Path::new(&task.target_path),
+            xplane_root.join("Aircraft/ToLiss A320/liveries/Red")
+        );
+
+        fs::remove_dir_all(xplane_root.join("Aircraft")).unwrap();
+        let mut missing = analyzer.create_install_task(livery, &xplane_path, &HashMap::new(), None);
+        assert!(!missing.livery_aircraft_found);
+        assert!(missing.not_installable_reason.is_some());
+        assert!(Analyzer::apply_livery_target_choice(&mut missing, &xplane_path).is_err());
+    }
+
+    #[test]
+    fn test_base_mesh_folder_is_suggested_for_global_scenery() {
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        let xplane_root = temp.path().join("X-Plane 12");
+        fs::create_dir_all(xplane_root.join("Global Scenery")).unwrap();
+        let xplane_path = xplane_root.to_string_lossy().to_string();
+        let mesh = temp.path().join("downloads").join("HD_Mesh_Scenery_v4");
+        fs::create_dir_all(mesh.join("Earth nav data").join("+40-080")).unwrap();
+        fs::write(
+            mesh.join("Earth nav data")
+                .join("+40-080")
+                .join("+42-073.dsf"),
+            b"XPLNEDSF",
+        )
+        .unwrap();
+
+        let mut item = create_detected_item(
+            AddonType::Scenery,
+            &mesh.to_string_lossy(),
+            "HD_Mesh_Scenery_v4",
+            None,
+        );
+        item.target_root_kind = Analyzer::suggest_target_root(&item);
+        assert_eq!(item.target_root_kind, TargetRootKind::GlobalScenery);
+
+        let analyzer = Analyzer::new();
+        let mut task = analyzer.create_install_task(item, &xplane_path, &HashMap::new(), None);
+        assert_eq!(task.suggested_root_kind, TargetRootKind::GlobalScenery);
+        assert_eq!(
+            Path::new(&task.target_path),
+            xplane_root.join("Global Scenery/HD_Mesh_Scenery_v4")
+        );
+
+        // The user keeps it in Custom Scenery instead
+        task.target_root_kind = TargetRootKind::CustomScenery;
+        Analyzer::apply_target_root_choice(&mut task, &xplane_path).unwrap();
+        assert_eq!(
+            Path::new(&task.target_path),
+            xplane_root.join("Custom Scenery/HD_Mesh_Scenery_v4")
+        );
+
+        let mut plugin = create_install_task(
+            "plugin",
+            AddonType::Plugin,
+            "/downloads/plugin.zip",
+            "/xplane/Resources/plugins/plugin",
+            "plugin",
+        );
+        plugin.target_root_kind = TargetRootKind::GlobalScenery;
+        assert!(Analyzer::apply_target_root_choice(&mut plugin, &xplane_path).is_err());
+    }
+
     #[test]
     fn test_format_scan_error_for_invalid_zip_is_user_friendly() {
         let error = anyhow::anyhow!("invalid Zip archive: Could not find EOCD");
@@ -1797,4 +2834,27 @@ mod tests {
         assert!(formatted.contains("Invalid or incomplete ZIP archive"));
         assert!(!formatted.contains("Could not find EOCD"));
     }
+
+    #[test]
+    fn test_size_warning_handles_archives_past_4gb() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        let analyzer = Analyzer::new();
+
+        // A legitimate Zip64 scenery pack: sizes past u32 with a modest ratio
+        assert_eq!(
+            analyzer.check_size_warning(5 * GIB, 12 * GIB),
+            (Some(12 * GIB), None)
+        );
+
+        let (_, warning) = analyzer.check_size_warning(10 * 1024 * 1024, 6 * GIB);
+        assert_eq!(warning, Some(format!("SUSPICIOUS_RATIO:614:{}", 6 * GIB)));
+
+        assert_eq!(
+            analyzer.check_size_warning(u64::MAX / 2, u64::MAX).1,
+            Some(format!(
+                "LARGE_SIZE:{:.2}",
+                u64::MAX as f64 / 1024.0 / 1024.0 / 1024.0
+            ))
+        );
+    }
 }
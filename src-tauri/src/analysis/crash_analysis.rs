@@ -707,14 +707,20 @@ pub fn score_crash_causes(parsed: &ParsedMinidump, log_issues: &[LogIssue]) -> V
 
 // ========== Main Entry Point ==========
 
+/// Analyze `report_path`, or else the most recent crash dump of `xplane_path`
 pub async fn analyze_crash_report(
     xplane_path: &str,
+    report_path: Option<&Path>,
     log_issues: &[LogIssue],
     skip_date_check: bool,
 ) -> Result<Option<DeepCrashAnalysis>, String> {
     logger::log_info("Deep crash analysis started", Some(LOG_CTX));
 
-    let dmp_path = match find_most_recent_crash_report(xplane_path, skip_date_check) {
+    let found = match report_path {
+        Some(path) => Some(path.to_path_buf()),
+        None => find_most_recent_crash_report(xplane_path, skip_date_check),
+    };
+    let dmp_path = match found {
         Some(p) => p,
         None => {
             logger::log_info(
@@ -92,7 +92,11 @@ const CATEGORIES: &[CategorySpec] = &[
 fn folder_size(path: &Path) -> (u64, usize) {
     let mut total: u64 = 0;
     let mut count: usize = 0;
-    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
         if entry.file_type().is_file() {
             total += entry.metadata().map(|m| m.len()).unwrap_or(0);
             count += 1;
@@ -215,7 +219,11 @@ pub fn scan_folder_disk_usage(
     let mut file_count: usize = 0;
     let mut files: Vec<FileEntry> = Vec::new();
 
-    for entry in WalkDir::new(&folder).into_iter().filter_map(|e| e.ok()) {
+    for entry in WalkDir::new(&folder)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
         if entry.file_type().is_file() {
             let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
             total_bytes += size;
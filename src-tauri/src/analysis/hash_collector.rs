@@ -1,11 +1,16 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::io::Read;
-use std::path::{Path, PathBuf};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
 
-use crate::models::{FileHash, HashAlgorithm, InstallTask};
+use crate::models::{
+    DuplicateFileGroup, DuplicateFilesReport, FileHash, HardlinkDedupResult, HardlinkSkipReason,
+    HardlinkSkippedFile, HardlinkedFile, HashAlgorithm, InstallTask,
+};
+use crate::task_control::TaskControl;
 
 pub struct HashCollector;
 
@@ -104,8 +109,11 @@ impl HashCollector {
             let file = archive.by_index_raw(i)?;
             let name = file.name().replace('\\', "/");
 
-            // Skip directories
-            if file.is_dir() {
+            // Skip directories, and symlinks (S_IFLNK) the installer never extracts as files
+            let is_link = file
+                .unix_mode()
+                .is_some_and(|mode| mode & 0o170000 == 0o120000);
+            if file.is_dir() || is_link {
                 continue;
             }
 
@@ -240,6 +248,458 @@ impl HashCollector {
     }
 }
 
+// ========== Duplicate files ==========
+//
+// Ortho scenery and aircraft often ship the same large textures several times.
+// Files are grouped by size first, and only files sharing a size are hashed.
+// Paths that are already hardlinks of one file count once.
+
+/// X-Plane folders scanned when no roots are given
+pub const DEFAULT_DUPLICATE_ROOTS: &[&str] = &["Custom Scenery", "Aircraft"];
+
+/// Volume and index of a file; paths with the same identity are hardlinks of
+/// one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct FileIdentity {
+    volume: u64,
+    index: u64,
+}
+
+#[cfg(unix)]
+fn file_identity(path: &Path) -> Option<FileIdentity> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = fs::metadata(path).ok()?;
+    Some(FileIdentity {
+        volume: metadata.dev(),
+        index: metadata.ino(),
+    })
+}
+
+#[cfg(windows)]
+fn file_identity(path: &Path) -> Option<FileIdentity> {
+    use std::os::windows::io::AsRawHandle;
+    use winapi::um::fileapi::{GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION};
+
+    let file = fs::File::open(path).ok()?;
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+    let result = unsafe { GetFileInformationByHandle(file.as_raw_handle() as _, &mut info) };
+    if result == 0 {
+        return None;
+    }
+    Some(FileIdentity {
+        volume: info.dwVolumeSerialNumber as u64,
+        index: ((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64,
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+fn file_identity(_path: &Path) -> Option<FileIdentity> {
+    None
+}
+
+/// Relative path made only of plain components
+fn is_plain_relative(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Canonical path of a folder or file inside the X-Plane installation
+fn resolve_xplane_child(xplane_path: &Path, relative: &str) -> Result<PathBuf> {
+    if !is_plain_relative(relative) {
+        return Err(anyhow!("Path traversal is not allowed: {}", relative));
+    }
+    crate::path_utils::validate_xplane_child_path(xplane_path, &xplane_path.join(relative))
+        .map_err(|e| anyhow!("Invalid path {}: {}", relative, e))
+}
+
+/// SHA-256 of a file, or `None` when cancelled between buffers
+fn sha256_cancellable(path: &Path, task_control: &TaskControl) -> Result<Option<String>> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 4 * 1024 * 1024];
+    loop {
+        if task_control.is_cancelled() {
+            return Ok(None);
+        }
+        let bytes_read = file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+    Ok(Some(format!("{:x}", hasher.finalize())))
+}
+
+struct DuplicateCandidate {
+    relative: String,
+    path: PathBuf,
+    size: u64,
+}
+
+/// Find files with identical contents under `roots` (relative to the X-Plane
+/// folder, [`DEFAULT_DUPLICATE_ROOTS`] when empty). Files smaller than
+/// `min_file_size` are ignored. When cancelled, the groups only cover the
+/// files hashed so far.
+pub fn find_duplicate_files(
+    xplane_path: &Path,
+    roots: &[String],
+    min_file_size: u64,
+    task_control: &TaskControl,
+) -> Result<DuplicateFilesReport> {
+    let roots: Vec<String> = if roots.is_empty() {
+        DEFAULT_DUPLICATE_ROOTS
+            .iter()
+            .map(|root| root.to_string())
+            .collect()
+    } else {
+        roots.to_vec()
+    };
+
+    let mut by_size: HashMap<u64, Vec<DuplicateCandidate>> = HashMap::new();
+    let mut seen_identities = HashSet::new();
+    let mut scanned_files = 0usize;
+    let mut cancelled = false;
+
+    'roots: for root in &roots {
+        let root_path = resolve_xplane_child(xplane_path, root)?;
+        let root_label = root.replace('\\', "/").trim_end_matches('/').to_string();
+        for entry in walkdir::WalkDir::new(&root_path)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+        {
+            if task_control.is_cancelled() {
+                cancelled = true;
+                break 'roots;
+            }
+            scanned_files += 1;
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let size = metadata.len();
+            if size == 0 || size < min_file_size {
+                continue;
+            }
+            // Overlapping roots and existing hardlinks list one file twice
+            if let Some(identity) = file_identity(entry.path()) {
+                if !seen_identities.insert(identity) {
+                    continue;
+                }
+            }
+            let Ok(inner) = entry.path().strip_prefix(&root_path) else {
+                continue;
+            };
+            by_size.entry(size).or_default().push(DuplicateCandidate {
+                relative: format!(
+                    "{}/{}",
+                    root_label,
+                    inner.to_string_lossy().replace('\\', "/")
+                ),
+                path: entry.path().to_path_buf(),
+                size,
+            });
+        }
+    }
+
+    let candidates: Vec<DuplicateCandidate> = by_size
+        .into_values()
+        .filter(|files| files.len() > 1)
+        .flatten()
+        .collect();
+
+    let hashed: Vec<(String, &DuplicateCandidate)> = candidates
+        .par_iter()
+        .filter_map(
+            |candidate| match sha256_cancellable(&candidate.path, task_control) {
+                Ok(Some(hash)) => Some((hash, candidate)),
+                Ok(None) => None,
+                Err(e) => {
+                    crate::logger::log_error(
+                        &format!("Failed to compute hash for {}: {}", candidate.relative, e),
+                        Some("hash_collector"),
+                    );
+                    None
+                }
+            },
+        )
+        .collect();
+    cancelled |= task_control.is_cancelled();
+    let hashed_files = hashed.len();
+
+    let mut by_content: BTreeMap<(u64, String), Vec<String>> = BTreeMap::new();
+    for (hash, candidate) in hashed {
+        by_content
+            .entry((candidate.size, hash))
+            .or_default()
+            .push(candidate.relative.clone());
+    }
+
+    let mut groups: Vec<DuplicateFileGroup> = by_content
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|((size_bytes, hash), mut paths)| {
+            paths.sort();
+            DuplicateFileGroup {
+                reclaimable_bytes: size_bytes.saturating_mul(paths.len() as u64 - 1),
+                hash,
+                size_bytes,
+                paths,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.reclaimable_bytes
+            .cmp(&a.reclaimable_bytes)
+            .then_with(|| a.paths.cmp(&b.paths))
+    });
+    let total_reclaimable_bytes = groups.iter().fold(0u64, |total, group| {
+        total.saturating_add(group.reclaimable_bytes)
+    });
+
+    crate::logger::log_info(
+        &format!(
+            "Duplicate scan: {} files scanned, {} hashed, {} groups, {} bytes reclaimable{}",
+            scanned_files,
+            hashed_files,
+            groups.len(),
+            total_reclaimable_bytes,
+            if cancelled { ", cancelled" } else { "" }
+        ),
+        Some("hash_collector"),
+    );
+
+    Ok(DuplicateFilesReport {
+        groups,
+        total_reclaimable_bytes,
+        scanned_files,
+        hashed_files,
+        cancelled,
+    })
+}
+
+/// Whether a failed `hard_link` means the filesystem has no hardlinks at all
+fn is_hardlink_unsupported(err: &io::Error) -> bool {
+    #[cfg(windows)]
+    const UNSUPPORTED_OS_ERRORS: &[i32] = &[1, 50]; // ERROR_INVALID_FUNCTION, ERROR_NOT_SUPPORTED
+    #[cfg(unix)]
+    const UNSUPPORTED_OS_ERRORS: &[i32] = &[libc::EPERM, libc::EOPNOTSUPP, libc::ENOTSUP];
+    #[cfg(not(any(unix, windows)))]
+    const UNSUPPORTED_OS_ERRORS: &[i32] = &[];
+
+    err.kind() == io::ErrorKind::Unsupported
+        || err
+            .raw_os_error()
+            .is_some_and(|code| UNSUPPORTED_OS_ERRORS.contains(&code))
+}
+
+/// Replace `duplicate` with a hardlink to `keep`. The link is created next to
+/// the duplicate and renamed over it, so a failure leaves the duplicate intact.
+fn replace_with_hardlink(keep: &Path, duplicate: &Path) -> io::Result<()> {
+    let file_name = duplicate
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let temp = duplicate.with_file_name(format!(
+        ".{}.xfast-link-{}",
+        file_name.to_string_lossy(),
+        uuid::Uuid::new_v4().simple()
+    ));
+    fs::hard_link(keep, &temp)?;
+    if let Err(e) = fs::rename(&temp, duplicate) {
+        let _ = fs::remove_file(&temp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn skip(result: &mut HardlinkDedupResult, path: &str, reason: HardlinkSkipReason, message: String) {
+    result.skipped.push(HardlinkSkippedFile {
+        path: path.to_string(),
+        reason,
+        message,
+    });
+}
+
+/// Replace every file of each group but the first with a hardlink to the
+/// first. Files are re-hashed first and skipped when they changed since the
+/// scan, live on another volume than the kept file, or the filesystem has no
+/// hardlinks. Linked files share their contents from then on: editing one
+/// edits all of them.
+pub fn deduplicate_with_hardlinks(
+    xplane_path: &Path,
+    groups: &[DuplicateFileGroup],
+    task_control: &TaskControl,
+) -> Result<HardlinkDedupResult> {
+    let mut result = HardlinkDedupResult::default();
+    let mut unsupported_volumes = HashSet::new();
+
+    'groups: for group in groups {
+        let Some((keep_relative, duplicates)) = group.paths.split_first() else {
+            continue;
+        };
+        let keep = match resolve_xplane_child(xplane_path, keep_relative) {
+            Ok(path) if path.is_file() => path,
+            Ok(_) => {
+                skip(
+                    &mut result,
+                    keep_relative,
+                    HardlinkSkipReason::Missing,
+                    "Kept file no longer exists".to_string(),
+                );
+                continue;
+            }
+            Err(e) => {
+                skip(
+                    &mut result,
+                    keep_relative,
+                    HardlinkSkipReason::Failed,
+                    e.to_string(),
+                );
+                continue;
+            }
+        };
+        let keep_identity = file_identity(&keep);
+        let keep_matches = match sha256_cancellable(&keep, task_control) {
+            Ok(Some(hash)) => hash == group.hash,
+            Ok(None) => {
+                result.cancelled = true;
+                break;
+            }
+            Err(_) => false,
+        };
+        if !keep_matches {
+            skip(
+                &mut result,
+                keep_relative,
+                HardlinkSkipReason::Changed,
+                "Kept file changed since the scan".to_string(),
+            );
+            continue;
+        }
+
+        for relative in duplicates {
+            let duplicate = match resolve_xplane_child(xplane_path, relative) {
+                Ok(path) => path,
+                Err(e) => {
+                    skip(
+                        &mut result,
+                        relative,
+                        HardlinkSkipReason::Failed,
+                        e.to_string(),
+                    );
+                    continue;
+                }
+            };
+            let is_regular_file = fs::symlink_metadata(xplane_path.join(relative))
+                .is_ok_and(|metadata| metadata.file_type().is_file());
+            if !is_regular_file {
+                skip(
+                    &mut result,
+                    relative,
+                    HardlinkSkipReason::Missing,
+                    "File no longer exists".to_string(),
+                );
+                continue;
+            }
+
+            let identity = file_identity(&duplicate);
+            if identity.is_some() && identity == keep_identity {
+                skip(
+                    &mut result,
+                    relative,
+                    HardlinkSkipReason::AlreadyLinked,
+                    format!("Already a hardlink of {}", keep_relative),
+                );
+                continue;
+            }
+            if let (Some(identity), Some(keep_identity)) = (identity, keep_identity) {
+                if identity.volume != keep_identity.volume {
+                    skip(
+                        &mut result,
+                        relative,
+                        HardlinkSkipReason::DifferentVolume,
+                        format!("{} is on another volume", keep_relative),
+                    );
+                    continue;
+                }
+                if unsupported_volumes.contains(&identity.volume) {
+                    skip(
+                        &mut result,
+                        relative,
+                        HardlinkSkipReason::Unsupported,
+                        "The filesystem does not support hardlinks".to_string(),
+                    );
+                    continue;
+                }
+            }
+
+            match sha256_cancellable(&duplicate, task_control) {
+                Ok(Some(hash)) if hash == group.hash => {}
+                Ok(Some(_)) | Err(_) => {
+                    skip(
+                        &mut result,
+                        relative,
+                        HardlinkSkipReason::Changed,
+                        "File changed since the scan".to_string(),
+                    );
+                    continue;
+                }
+                Ok(None) => {
+                    result.cancelled = true;
+                    break 'groups;
+                }
+            }
+
+            match replace_with_hardlink(&keep, &duplicate) {
+                Ok(()) => {
+                    result.reclaimed_bytes =
+                        result.reclaimed_bytes.saturating_add(group.size_bytes);
+                    result.linked.push(HardlinkedFile {
+                        path: relative.clone(),
+                        target: keep_relative.clone(),
+                        size_bytes: group.size_bytes,
+                    });
+                }
+                Err(e) if is_hardlink_unsupported(&e) => {
+                    if let Some(identity) = identity {
+                        unsupported_volumes.insert(identity.volume);
+                    }
+                    skip(
+                        &mut result,
+                        relative,
+                        HardlinkSkipReason::Unsupported,
+                        format!("The filesystem does not support hardlinks: {}", e),
+                    );
+                }
+                Err(e) => {
+                    skip(
+                        &mut result,
+                        relative,
+                        HardlinkSkipReason::Failed,
+                        e.to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    crate::logger::log_info(
+        &format!(
+            "Hardlinked {} duplicate files, {} skipped, {} bytes reclaimed{}",
+            result.linked.len(),
+            result.skipped.len(),
+            result.reclaimed_bytes,
+            if result.cancelled { ", cancelled" } else { "" }
+        ),
+        Some("hash_collector"),
+    );
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,12 +717,16 @@ mod tests {
             archive_internal_root: None,
             extraction_chain,
             should_overwrite: false,
+            allow_case_collision_rename: false,
+            allow_symlinks: false,
             password: None,
             estimated_size: None,
             size_warning: None,
             size_confirmed: false,
             existing_navdata_info: None,
             new_navdata_info: None,
+            navdata_downgrade_from: None,
+            allow_downgrade: false,
             existing_version_info: None,
             new_version_info: None,
             backup_liveries: true,
@@ -273,8 +737,17 @@ mod tests {
             enable_verification: true,
             livery_aircraft_type: None,
             livery_aircraft_found: false,
+            livery_target_candidates: Vec::new(),
+            target_aircraft_folder: None,
+            not_installable_reason: None,
             flywithlua_installed: false,
             companion_paths: vec![],
+            compatibility: crate::models::AddonCompatibility::Unknown,
+            updater_provider: crate::models::UpdaterProvider::None,
+            suggested_root_kind: crate::models::TargetRootKind::CustomScenery,
+            target_root_kind: crate::models::TargetRootKind::CustomScenery,
+            profile_identity: None,
+            install_profile: None,
         }
     }
 
@@ -294,10 +767,97 @@ mod tests {
                 internal_path: "nested.7z".to_string(),
                 password: None,
                 format: "7z".to_string(),
+                password_required: false,
             }],
             final_internal_root: None,
         }));
 
         assert!(!collector.should_collect_hashes(&task));
     }
+
+    fn write_file(root: &Path, relative: &str, contents: &[u8]) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn duplicate_files_are_grouped_by_size_then_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(
+            root,
+            "Custom Scenery/Ortho A/textures/a.dds",
+            b"texture-one",
+        );
+        write_file(root, "Aircraft/C172/objects/a.dds", b"texture-one");
+        write_file(
+            root,
+            "Custom Scenery/Ortho B/textures/a.dds",
+            b"texture-one",
+        );
+        // Same size, different contents
+        write_file(
+            root,
+            "Custom Scenery/Ortho B/textures/b.dds",
+            b"texture-two",
+        );
+        write_file(root, "Custom Scenery/Ortho B/small.txt", b"x");
+        write_file(root, "Aircraft/C172/small.txt", b"x");
+
+        let report = find_duplicate_files(root, &[], 2, &TaskControl::new()).unwrap();
+
+        assert_eq!(report.groups.len(), 1);
+        let group = &report.groups[0];
+        assert_eq!(
+            group.paths,
+            vec![
+                "Aircraft/C172/objects/a.dds",
+                "Custom Scenery/Ortho A/textures/a.dds",
+                "Custom Scenery/Ortho B/textures/a.dds",
+            ]
+        );
+        assert_eq!(group.reclaimable_bytes, 22);
+        assert_eq!(report.total_reclaimable_bytes, 22);
+        assert_eq!(report.scanned_files, 6);
+        assert_eq!(report.hashed_files, 4);
+        assert!(!report.cancelled);
+
+        assert!(
+            find_duplicate_files(root, &["../outside".to_string()], 0, &TaskControl::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn hardlink_dedup_links_duplicates_and_skips_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write_file(root, "Custom Scenery/A/t.dds", b"same-texture");
+        write_file(root, "Custom Scenery/B/t.dds", b"same-texture");
+        write_file(root, "Custom Scenery/C/t.dds", b"same-texture");
+
+        let report = find_duplicate_files(root, &[], 0, &TaskControl::new()).unwrap();
+        assert_eq!(report.groups.len(), 1);
+        write_file(root, "Custom Scenery/C/t.dds", b"edited-image");
+
+        let result = deduplicate_with_hardlinks(root, &report.groups, &TaskControl::new()).unwrap();
+        assert_eq!(result.linked.len(), 1);
+        assert_eq!(result.linked[0].path, "Custom Scenery/B/t.dds");
+        assert_eq!(result.linked[0].target, "Custom Scenery/A/t.dds");
+        assert_eq!(result.reclaimed_bytes, 12);
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].reason, HardlinkSkipReason::Changed);
+        assert_eq!(
+            fs::read(root.join("Custom Scenery/B/t.dds")).unwrap(),
+            b"same-texture"
+        );
+
+        // Linked files count once in a new scan and are not linked again
+        let rescan = find_duplicate_files(root, &[], 0, &TaskControl::new()).unwrap();
+        assert!(rescan.groups.is_empty());
+        let again = deduplicate_with_hardlinks(root, &report.groups, &TaskControl::new()).unwrap();
+        assert!(again.linked.is_empty());
+        assert_eq!(again.skipped[0].reason, HardlinkSkipReason::AlreadyLinked);
+    }
 }
@@ -3,29 +3,44 @@
 //! This module defines patterns to detect aircraft liveries and map them
 //! to their corresponding aircraft types.
 
-use serde::Deserialize;
-use std::path::Path;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{LazyLock, RwLock};
-use std::time::Duration;
 
+use crate::app_dirs;
+use crate::error::{ApiError, ApiResult};
 use crate::logger;
+use crate::remote_dataset::{self, RemoteDataset};
+use crate::settings_store;
 
 /// Represents a single detection rule for livery identification
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectionRule {
-    /// Pattern type: "path" for folder path matching, "file" for filename matching
+    /// Pattern type: "path" for folder path matching, "file" for filename matching,
+    /// "regex" for a case-insensitive regular expression over the whole file path
     pub pattern_type: String,
     /// The pattern to match (folder path or filename glob)
     /// Supports '*' (0+ chars) and '?' (0-1 chars) wildcards
     pub pattern: String,
+    /// For "regex" rules: how many trailing path components (the matched file
+    /// included) lie inside the livery folder. Defaults to 1, the file's parent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_depth: Option<usize>,
     /// Lowercased pattern for case-insensitive matching
     #[serde(skip)]
     pub pattern_lower: String,
+    /// Compiled pattern of a "regex" rule
+    #[serde(skip)]
+    pub regex: Option<Regex>,
+    /// Index of the rule in the user's entry for this aircraft, if the user added it
+    #[serde(skip)]
+    pub user_index: Option<usize>,
 }
 
 /// Represents a livery pattern definition
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LiveryPattern {
     /// Unique identifier for the aircraft type (e.g., "FF777")
     pub aircraft_type_id: String,
@@ -35,18 +50,19 @@ pub struct LiveryPattern {
     pub detection_rules: Vec<DetectionRule>,
     /// ACF file name patterns that identify this aircraft (without extension)
     /// Supports '*' (0+ chars) and '?' (0-1 chars) wildcards, case-insensitive
+    #[serde(default)]
     pub acf_identifiers: Vec<String>,
     /// Lowercased ACF identifiers for case-insensitive matching
     #[serde(skip)]
     pub acf_identifiers_lower: Vec<String>,
 }
 
-/// Remote JSON schema for livery patterns
-#[derive(Debug, Deserialize)]
+/// JSON schema for livery patterns, shared by the remote and user files
+#[derive(Debug, Serialize, Deserialize)]
 struct LiveryPatternsData {
-    #[allow(dead_code)]
+    #[serde(default)]
     version: u32,
-    #[allow(dead_code)]
+    #[serde(default)]
     updated: String,
     patterns: Vec<LiveryPattern>,
 }
@@ -54,19 +70,70 @@ struct LiveryPatternsData {
 /// Remote URL for the livery patterns JSON file via proxy
 const REMOTE_URL: &str = "https://x-fast-manager.vercel.app/api/livery-patterns-data";
 
-/// Loaded livery patterns (embedded by default, remote override when available)
-static LIVERY_PATTERNS: LazyLock<RwLock<Vec<LiveryPattern>>> =
+/// Fewest patterns a complete remote file can have
+const MIN_REMOTE_PATTERNS: usize = 5;
+
+/// Version written to the user patterns file
+const USER_PATTERNS_VERSION: u32 = 1;
+
+/// Deepest livery root a user regex rule can ask for
+const MAX_ROOT_DEPTH: usize = 16;
+
+/// Embedded patterns, replaced by the remote set when it can be fetched
+static BASE_PATTERNS: LazyLock<RwLock<Vec<LiveryPattern>>> =
     LazyLock::new(|| RwLock::new(load_embedded_patterns()));
 
+/// Patterns from the user patterns file, as written (not prepared)
+static USER_PATTERNS: LazyLock<RwLock<Vec<LiveryPattern>>> =
+    LazyLock::new(|| RwLock::new(load_user_patterns()));
+
+/// Base and user patterns merged; this is what detection consults
+static LIVERY_PATTERNS: LazyLock<RwLock<Vec<LiveryPattern>>> = LazyLock::new(|| {
+    let base = BASE_PATTERNS
+        .read()
+        .expect("livery patterns lock poisoned during merge")
+        .clone();
+    let user = USER_PATTERNS
+        .read()
+        .expect("user livery patterns lock poisoned during merge");
+    RwLock::new(merge_patterns(base, &user))
+});
+
 /// Ensure we only attempt remote fetch once per startup
 static REMOTE_FETCHED: AtomicBool = AtomicBool::new(false);
 
+fn compile_rule_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+/// Normalize patterns for matching. Regex rules that don't compile are dropped
+/// with a warning so one bad rule can't break detection for everything else.
 fn prepare_patterns(mut patterns: Vec<LiveryPattern>) -> Vec<LiveryPattern> {
     for pattern in &mut patterns {
-        for rule in &mut pattern.detection_rules {
+        let aircraft_type_id = &pattern.aircraft_type_id;
+        pattern.detection_rules.retain_mut(|rule| {
             rule.pattern_type = rule.pattern_type.trim().to_lowercase();
             rule.pattern_lower = rule.pattern.to_lowercase();
-        }
+            if rule.pattern_type != "regex" {
+                return true;
+            }
+            match compile_rule_regex(&rule.pattern) {
+                Ok(regex) => {
+                    rule.regex = Some(regex);
+                    true
+                }
+                Err(e) => {
+                    logger::log_info(
+                        &format!(
+                            "Skipping invalid livery regex '{}' for {}: {}",
+                            rule.pattern, aircraft_type_id, e
+                        ),
+                        Some("livery_patterns"),
+                    );
+                    false
+                }
+            }
+        });
         pattern.acf_identifiers_lower = pattern
             .acf_identifiers
             .iter()
@@ -77,6 +144,57 @@ fn prepare_patterns(mut patterns: Vec<LiveryPattern>) -> Vec<LiveryPattern> {
     patterns
 }
 
+/// Merge prepared `base` patterns with the raw `user` patterns. User entries come
+/// first so their rules win when a path matches more than one aircraft; a user entry
+/// for an aircraft already in `base` adds to its rules and overrides its name.
+fn merge_patterns(base: Vec<LiveryPattern>, user: &[LiveryPattern]) -> Vec<LiveryPattern> {
+    let mut user = user.to_vec();
+    for pattern in &mut user {
+        for (index, rule) in pattern.detection_rules.iter_mut().enumerate() {
+            rule.user_index = Some(index);
+        }
+    }
+
+    let mut base = base;
+    let mut merged = Vec::with_capacity(base.len() + user.len());
+    for mut pattern in prepare_patterns(user) {
+        if let Some(pos) = base
+            .iter()
+            .position(|p| p.aircraft_type_id == pattern.aircraft_type_id)
+        {
+            let existing = base.remove(pos);
+            if pattern.aircraft_name.trim().is_empty() {
+                pattern.aircraft_name = existing.aircraft_name;
+            }
+            pattern.detection_rules.extend(existing.detection_rules);
+            pattern.acf_identifiers.extend(existing.acf_identifiers);
+            pattern
+                .acf_identifiers_lower
+                .extend(existing.acf_identifiers_lower);
+        }
+        merged.push(pattern);
+    }
+    merged.extend(base);
+    merged
+}
+
+/// Recompute the merged set after the base or user patterns changed
+fn rebuild_merged_patterns() {
+    let base = BASE_PATTERNS
+        .read()
+        .expect("livery patterns lock poisoned during merge")
+        .clone();
+    let merged = {
+        let user = USER_PATTERNS
+            .read()
+            .expect("user livery patterns lock poisoned during merge");
+        merge_patterns(base, &user)
+    };
+    *LIVERY_PATTERNS
+        .write()
+        .expect("livery patterns lock poisoned during merge") = merged;
+}
+
 fn load_embedded_patterns() -> Vec<LiveryPattern> {
     let embedded_json = include_str!("../../../data/livery_patterns.json");
 
@@ -95,29 +213,69 @@ fn load_embedded_patterns() -> Vec<LiveryPattern> {
     }
 }
 
-async fn fetch_remote_patterns() -> Result<Vec<LiveryPattern>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("XFast Manager")
-        .timeout(Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let remote_url =
-        std::env::var("XFAST_LIVERY_PATTERNS_API_URL").unwrap_or_else(|_| REMOTE_URL.to_string());
-    let response = client
-        .get(&remote_url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("HTTP status: {}", response.status()));
+fn user_patterns_path() -> PathBuf {
+    app_dirs::get_user_livery_patterns_path()
+}
+
+fn load_user_patterns() -> Vec<LiveryPattern> {
+    let path = user_patterns_path();
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            logger::log_info(
+                &format!("Failed to read {}: {}", path.display(), e),
+                Some("livery_patterns"),
+            );
+            return Vec::new();
+        }
+    };
+
+    match serde_json::from_str::<LiveryPatternsData>(&content) {
+        Ok(data) => data.patterns,
+        Err(e) => {
+            logger::log_info(
+                &format!(
+                    "Failed to parse {}: {}, ignoring user livery patterns",
+                    path.display(),
+                    e
+                ),
+                Some("livery_patterns"),
+            );
+            Vec::new()
+        }
     }
+}
+
+fn save_user_patterns(patterns: &[LiveryPattern]) -> ApiResult<()> {
+    let data = LiveryPatternsData {
+        version: USER_PATTERNS_VERSION,
+        updated: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        patterns: patterns.to_vec(),
+    };
+    let json = serde_json::to_vec_pretty(&data)
+        .map_err(|e| ApiError::internal(format!("Failed to serialize livery patterns: {}", e)))?;
+    settings_store::write_atomic(&user_patterns_path(), &json)
+        .map_err(|e| ApiError::internal(format!("Failed to save livery patterns: {}", e)))
+}
 
-    let data: LiveryPatternsData = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+async fn fetch_remote_patterns() -> Result<Vec<LiveryPattern>, String> {
+    let dataset = RemoteDataset {
+        name: "livery_patterns",
+        url: std::env::var("XFAST_LIVERY_PATTERNS_API_URL")
+            .unwrap_or_else(|_| REMOTE_URL.to_string()),
+        min_entries: MIN_REMOTE_PATTERNS,
+    };
+    let cached = BASE_PATTERNS
+        .read()
+        .expect("livery patterns lock poisoned during remote fetch")
+        .len();
+
+    let data: LiveryPatternsData =
+        remote_dataset::fetch(&dataset, cached, |data: &LiveryPatternsData| {
+            data.patterns.len()
+        })
+        .await?;
 
     Ok(prepare_patterns(data.patterns))
 }
@@ -129,22 +287,16 @@ pub async fn ensure_patterns_loaded() {
     }
 
     match fetch_remote_patterns().await {
-        Ok(patterns) if !patterns.is_empty() => {
-            let mut guard = LIVERY_PATTERNS
+        Ok(patterns) => {
+            *BASE_PATTERNS
                 .write()
-                .expect("livery patterns lock poisoned during remote fetch");
-            *guard = patterns;
+                .expect("livery patterns lock poisoned during remote fetch") = patterns;
+            rebuild_merged_patterns();
             logger::log_info(
                 "Fetched livery patterns from remote",
                 Some("livery_patterns"),
             );
         }
-        Ok(_) => {
-            logger::log_info(
-                "Remote livery patterns were empty; keeping embedded data",
-                Some("livery_patterns"),
-            );
-        }
         Err(e) => {
             logger::log_info(
                 &format!(
@@ -157,15 +309,167 @@ pub async fn ensure_patterns_loaded() {
     }
 }
 
+/// A detection rule as listed by [`list_patterns`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveryRuleInfo {
+    pub pattern_type: String,
+    pub pattern: String,
+    pub root_depth: Option<usize>,
+    /// Index to pass to [`remove_user_pattern`]; `None` for built-in rules
+    pub user_index: Option<usize>,
+}
+
+/// An aircraft and its merged detection rules, as listed by [`list_patterns`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveryPatternInfo {
+    pub aircraft_type_id: String,
+    pub aircraft_name: String,
+    pub rules: Vec<LiveryRuleInfo>,
+}
+
+/// The merged pattern set, in matching order
+pub fn list_patterns() -> Vec<LiveryPatternInfo> {
+    LIVERY_PATTERNS
+        .read()
+        .expect("livery patterns lock poisoned during listing")
+        .iter()
+        .map(|pattern| LiveryPatternInfo {
+            aircraft_type_id: pattern.aircraft_type_id.clone(),
+            aircraft_name: pattern.aircraft_name.clone(),
+            rules: pattern
+                .detection_rules
+                .iter()
+                .map(|rule| LiveryRuleInfo {
+                    pattern_type: rule.pattern_type.clone(),
+                    pattern: rule.pattern.clone(),
+                    root_depth: rule.root_depth,
+                    user_index: rule.user_index,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Add a regex rule for `aircraft_id` to the user patterns file
+pub fn add_user_pattern(
+    aircraft_id: &str,
+    aircraft_name: &str,
+    path_regex: &str,
+    root_depth: usize,
+) -> ApiResult<Vec<LiveryPatternInfo>> {
+    let aircraft_id = aircraft_id.trim();
+    if aircraft_id.is_empty() {
+        return Err(ApiError::validation("Aircraft ID is required"));
+    }
+    if path_regex.trim().is_empty() {
+        return Err(ApiError::validation("Path pattern is required"));
+    }
+    compile_rule_regex(path_regex)
+        .map_err(|e| ApiError::validation(format!("Invalid path pattern: {}", e)))?;
+    if !(1..=MAX_ROOT_DEPTH).contains(&root_depth) {
+        return Err(ApiError::validation(format!(
+            "Root depth must be between 1 and {}",
+            MAX_ROOT_DEPTH
+        )));
+    }
+
+    {
+        let mut user = USER_PATTERNS
+            .write()
+            .expect("user livery patterns lock poisoned during add");
+        let mut updated = user.clone();
+        let rule = DetectionRule {
+            pattern_type: "regex".to_string(),
+            pattern: path_regex.to_string(),
+            root_depth: Some(root_depth),
+            pattern_lower: String::new(),
+            regex: None,
+            user_index: None,
+        };
+        match updated
+            .iter_mut()
+            .find(|p| p.aircraft_type_id == aircraft_id)
+        {
+            Some(pattern) => {
+                if !aircraft_name.trim().is_empty() {
+                    pattern.aircraft_name = aircraft_name.trim().to_string();
+                }
+                pattern.detection_rules.push(rule);
+            }
+            None => updated.push(LiveryPattern {
+                aircraft_type_id: aircraft_id.to_string(),
+                aircraft_name: aircraft_name.trim().to_string(),
+                detection_rules: vec![rule],
+                acf_identifiers: Vec::new(),
+                acf_identifiers_lower: Vec::new(),
+            }),
+        }
+        save_user_patterns(&updated)?;
+        *user = updated;
+    }
+
+    rebuild_merged_patterns();
+    logger::log_info(
+        &format!("Added user livery pattern for {}", aircraft_id),
+        Some("livery_patterns"),
+    );
+    Ok(list_patterns())
+}
+
+/// Remove rule `pattern_index` from the user's entry for `aircraft_id`
+pub fn remove_user_pattern(
+    aircraft_id: &str,
+    pattern_index: usize,
+) -> ApiResult<Vec<LiveryPatternInfo>> {
+    {
+        let mut user = USER_PATTERNS
+            .write()
+            .expect("user livery patterns lock poisoned during remove");
+        let mut updated = user.clone();
+        let pos = updated
+            .iter()
+            .position(|p| p.aircraft_type_id == aircraft_id)
+            .ok_or_else(|| {
+                ApiError::not_found(format!("No user livery patterns for {}", aircraft_id))
+            })?;
+        let rules = &mut updated[pos].detection_rules;
+        if pattern_index >= rules.len() {
+            return Err(ApiError::not_found(format!(
+                "Livery pattern {} not found for {}",
+                pattern_index, aircraft_id
+            )));
+        }
+        rules.remove(pattern_index);
+        if rules.is_empty() && updated[pos].acf_identifiers.is_empty() {
+            updated.remove(pos);
+        }
+        save_user_patterns(&updated)?;
+        *user = updated;
+    }
+
+    rebuild_merged_patterns();
+    logger::log_info(
+        &format!("Removed user livery pattern for {}", aircraft_id),
+        Some("livery_patterns"),
+    );
+    Ok(list_patterns())
+}
+
 /// Check if a path matches a livery pattern
 /// Returns (aircraft_type_id, livery_root_path) if matched
 pub fn check_livery_pattern(file_path: &str) -> Option<(String, String)> {
-    let normalized = file_path.replace('\\', "/");
-    let normalized_lower = normalized.to_lowercase();
-
     let patterns = LIVERY_PATTERNS
         .read()
         .expect("livery patterns lock poisoned during pattern check");
+    match_livery_pattern(&patterns, file_path)
+}
+
+fn match_livery_pattern(patterns: &[LiveryPattern], file_path: &str) -> Option<(String, String)> {
+    let normalized = file_path.replace('\\', "/");
+    let normalized_lower = normalized.to_lowercase();
+
     for pattern in patterns.iter() {
         for rule in pattern.detection_rules.iter() {
             match rule.pattern_type.as_str() {
@@ -189,6 +493,11 @@ pub fn check_livery_pattern(file_path: &str) -> Option<(String, String)> {
                         return Some((pattern.aircraft_type_id.clone(), livery_root));
                     }
                 }
+                "regex" => {
+                    if let Some(livery_root) = match_regex_pattern(&normalized, rule) {
+                        return Some((pattern.aircraft_type_id.clone(), livery_root));
+                    }
+                }
                 _ => {}
             }
         }
@@ -197,6 +506,20 @@ pub fn check_livery_pattern(file_path: &str) -> Option<(String, String)> {
     None
 }
 
+/// Match a regex rule and return the livery root if matched: the path without
+/// its last `root_depth` components
+fn match_regex_pattern(normalized: &str, rule: &DetectionRule) -> Option<String> {
+    if !rule.regex.as_ref()?.is_match(normalized) {
+        return None;
+    }
+    let parts: Vec<&str> = normalized.split('/').collect();
+    let depth = rule.root_depth.unwrap_or(1).max(1);
+    if depth > parts.len() {
+        return None;
+    }
+    Some(parts[..parts.len() - depth].join("/"))
+}
+
 /// Match a file pattern and return the livery root if matched
 fn match_file_pattern(
     normalized: &str,
@@ -516,4 +839,94 @@ mod tests {
         // Unknown
         assert_eq!(check_acf_identifier("unknown.acf"), None);
     }
+
+    fn user_pattern(aircraft_type_id: &str, rules: &[(&str, usize)]) -> LiveryPattern {
+        LiveryPattern {
+            aircraft_type_id: aircraft_type_id.to_string(),
+            aircraft_name: format!("User {}", aircraft_type_id),
+            detection_rules: rules
+                .iter()
+                .map(|(pattern, root_depth)| DetectionRule {
+                    pattern_type: "regex".to_string(),
+                    pattern: pattern.to_string(),
+                    root_depth: Some(*root_depth),
+                    pattern_lower: String::new(),
+                    regex: None,
+                    user_index: None,
+                })
+                .collect(),
+            acf_identifiers: Vec::new(),
+            acf_identifiers_lower: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_user_regex_pattern_root_depth() {
+        let merged = merge_patterns(
+            Vec::new(),
+            &[user_pattern("MY_A350", &[(r"objects/a350[^/]*\.dds$", 2)])],
+        );
+        assert_eq!(
+            match_livery_pattern(&merged, "Pack\\Livery\\Objects\\A350_Fuselage.dds"),
+            Some(("MY_A350".to_string(), "Pack/Livery".to_string()))
+        );
+        assert_eq!(
+            match_livery_pattern(&merged, "Pack/Livery/objects/a350.png"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_user_patterns_win_over_base() {
+        let base = load_embedded_patterns();
+        let merged = merge_patterns(
+            base,
+            &[
+                user_pattern("MY_777", &[(r"/objects/777/", 3)]),
+                user_pattern("FF_B777", &[(r"fuselage_custom\.png$", 1)]),
+            ],
+        );
+
+        // The user rule claims a path the built-in FF_B777 rule also matches
+        assert_eq!(
+            match_livery_pattern(&merged, "MyLivery/objects/777/texture.png"),
+            Some(("MY_777".to_string(), "MyLivery".to_string()))
+        );
+
+        // A user entry for a known aircraft adds to its rules instead of replacing them
+        let ff777 = merged
+            .iter()
+            .find(|p| p.aircraft_type_id == "FF_B777")
+            .unwrap();
+        assert_eq!(ff777.aircraft_name, "User FF_B777");
+        assert_eq!(ff777.detection_rules[0].user_index, Some(0));
+        assert!(ff777.detection_rules.len() > 1);
+        assert!(!ff777.acf_identifiers_lower.is_empty());
+        assert_eq!(
+            merged
+                .iter()
+                .filter(|p| p.aircraft_type_id == "FF_B777")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_invalid_user_regex_is_skipped() {
+        let merged = merge_patterns(
+            Vec::new(),
+            &[user_pattern(
+                "MY_A350",
+                &[(r"objects/(a350", 2), (r"a350_icon\.png$", 1)],
+            )],
+        );
+        let rules = &merged[0].detection_rules;
+        assert_eq!(rules.len(), 1);
+        // Indices still refer to the rules as stored in the user file
+        assert_eq!(rules[0].user_index, Some(1));
+        assert_eq!(
+            match_livery_pattern(&merged, "Livery/a350_icon.png"),
+            Some(("MY_A350".to_string(), "Livery".to_string()))
+        );
+    }
 }
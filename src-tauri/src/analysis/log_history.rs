@@ -0,0 +1,196 @@
+//! X-Plane log issue history
+//!
+//! Summaries of analyzed Log.txt files are stored one per X-Plane session,
+//! identified by the log's modification time and size, so recording the same
+//! session twice is a no-op. Only the newest `log_history_limit` (a backend
+//! setting) snapshots are kept per X-Plane installation.
+
+use crate::database::entities::xplane_log_snapshots;
+use crate::error::{ApiError, ApiResult};
+use crate::models::XPlaneLogSnapshot;
+use crate::path_utils;
+use crate::settings_store;
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use std::path::Path;
+
+/// Store a snapshot unless one for the same session exists.
+/// Returns whether a new row was written.
+pub async fn record_snapshot(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    snapshot: &XPlaneLogSnapshot,
+) -> ApiResult<bool> {
+    let keep = settings_store::get().log_history_limit.max(1) as usize;
+    record_snapshot_for_key(
+        conn,
+        &path_utils::normalize_xplane_key(xplane_path),
+        snapshot,
+        keep,
+    )
+    .await
+}
+
+async fn record_snapshot_for_key(
+    conn: &DatabaseConnection,
+    xplane_key: &str,
+    snapshot: &XPlaneLogSnapshot,
+    keep: usize,
+) -> ApiResult<bool> {
+    let existing = xplane_log_snapshots::Entity::find()
+        .filter(xplane_log_snapshots::Column::XplanePath.eq(xplane_key))
+        .filter(xplane_log_snapshots::Column::LogModifiedAt.eq(snapshot.log_modified_at))
+        .filter(xplane_log_snapshots::Column::LogSize.eq(snapshot.log_size as i64))
+        .one(conn)
+        .await?;
+    if existing.is_some() {
+        return Ok(false);
+    }
+
+    let active = xplane_log_snapshots::ActiveModel {
+        id: ActiveValue::NotSet,
+        xplane_path: Set(xplane_key.to_string()),
+        recorded_at: Set(snapshot.recorded_at),
+        log_modified_at: Set(snapshot.log_modified_at),
+        log_size: Set(snapshot.log_size as i64),
+        xplane_version: Set(snapshot.xplane_version.clone()),
+        crash_detected: Set(snapshot.crash_detected),
+        category_counts: Set(serde_json::to_string(&snapshot.category_counts)
+            .map_err(|e| ApiError::internal(format!("Failed to serialize log snapshot: {}", e)))?),
+        total_high: Set(snapshot.total_high as i32),
+        total_medium: Set(snapshot.total_medium as i32),
+        total_low: Set(snapshot.total_low as i32),
+    };
+    xplane_log_snapshots::Entity::insert(active)
+        .exec(conn)
+        .await?;
+    prune_snapshots(conn, xplane_key, keep).await?;
+
+    Ok(true)
+}
+
+/// Drop everything but the newest `keep` sessions
+async fn prune_snapshots<C: ConnectionTrait>(
+    conn: &C,
+    xplane_key: &str,
+    keep: usize,
+) -> ApiResult<()> {
+    // SQLite rejects OFFSET without LIMIT, so skip the kept rows here
+    let ids: Vec<i64> = xplane_log_snapshots::Entity::find()
+        .select_only()
+        .column(xplane_log_snapshots::Column::Id)
+        .filter(xplane_log_snapshots::Column::XplanePath.eq(xplane_key))
+        .order_by_desc(xplane_log_snapshots::Column::LogModifiedAt)
+        .order_by_desc(xplane_log_snapshots::Column::Id)
+        .into_tuple()
+        .all(conn)
+        .await?;
+
+    let dropped: Vec<i64> = ids.into_iter().skip(keep).collect();
+    if !dropped.is_empty() {
+        xplane_log_snapshots::Entity::delete_many()
+            .filter(xplane_log_snapshots::Column::Id.is_in(dropped))
+            .exec(conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Load the newest `limit` snapshots for an installation, oldest first
+pub async fn load_history(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    limit: u64,
+) -> ApiResult<Vec<XPlaneLogSnapshot>> {
+    load_history_for_key(conn, &path_utils::normalize_xplane_key(xplane_path), limit).await
+}
+
+async fn load_history_for_key(
+    conn: &DatabaseConnection,
+    xplane_key: &str,
+    limit: u64,
+) -> ApiResult<Vec<XPlaneLogSnapshot>> {
+    let rows = xplane_log_snapshots::Entity::find()
+        .filter(xplane_log_snapshots::Column::XplanePath.eq(xplane_key))
+        .order_by_desc(xplane_log_snapshots::Column::LogModifiedAt)
+        .order_by_desc(xplane_log_snapshots::Column::Id)
+        .limit(limit)
+        .all(conn)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .rev()
+        .map(|row| XPlaneLogSnapshot {
+            recorded_at: row.recorded_at,
+            log_modified_at: row.log_modified_at,
+            log_size: row.log_size.max(0) as u64,
+            xplane_version: row.xplane_version,
+            crash_detected: row.crash_detected,
+            category_counts: serde_json::from_str(&row.category_counts).unwrap_or_default(),
+            total_high: row.total_high.max(0) as u32,
+            total_medium: row.total_medium.max(0) as u32,
+            total_low: row.total_low.max(0) as u32,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+    use std::collections::BTreeMap;
+
+    const XPLANE_KEY: &str = "/xplane";
+
+    fn snapshot(log_modified_at: i64, crash_detected: bool) -> XPlaneLogSnapshot {
+        XPlaneLogSnapshot {
+            recorded_at: log_modified_at + 60,
+            log_modified_at,
+            log_size: 4_096,
+            xplane_version: Some("12.1.0".to_string()),
+            crash_detected,
+            category_counts: BTreeMap::from([("plugin_error".to_string(), 3)]),
+            total_high: u32::from(crash_detected),
+            total_medium: 1,
+            total_low: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn same_session_is_recorded_once_and_old_sessions_are_pruned() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        assert!(
+            record_snapshot_for_key(&conn, XPLANE_KEY, &snapshot(100, false), 3)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !record_snapshot_for_key(&conn, XPLANE_KEY, &snapshot(100, false), 3)
+                .await
+                .unwrap()
+        );
+
+        for session in 2..=5 {
+            record_snapshot_for_key(&conn, XPLANE_KEY, &snapshot(session * 100, session >= 4), 3)
+                .await
+                .unwrap();
+        }
+
+        let history = load_history_for_key(&conn, XPLANE_KEY, 10).await.unwrap();
+        let sessions: Vec<i64> = history.iter().map(|s| s.log_modified_at).collect();
+        assert_eq!(sessions, vec![300, 400, 500]);
+        assert!(!history[0].crash_detected);
+        assert!(history[2].crash_detected);
+        assert_eq!(history[0].category_counts.get("plugin_error"), Some(&3));
+
+        let latest = load_history_for_key(&conn, XPLANE_KEY, 1).await.unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].log_modified_at, 500);
+    }
+}
@@ -0,0 +1,355 @@
+//! X-Plane log analysis attached to bug reports
+//!
+//! Bug reports about sim crashes are hard to act on without the user's
+//! Log.txt. A compact summary of the log analysis, and of the crash dump when
+//! the log reports a crash, goes into the report payload instead of the log
+//! itself.
+//!
+//! The whole payload is kept under [`MAX_BUG_REPORT_PAYLOAD_BYTES`]. When the
+//! summary doesn't fit, parts are dropped in a fixed order so the same log
+//! always gives the same report:
+//!
+//! 1. samples of medium and low severity issues, last issue first
+//! 2. the crash dump summary
+//! 3. samples of high severity issues, last issue first
+//! 4. trailing lines of the crash info
+//! 5. issues themselves, last issue first
+//!
+//! Issue categories with their counts and the crash info survive longest.
+
+use crate::crash_analysis::DeepCrashAnalysis;
+use crate::XPlaneLogAnalysis;
+use serde::Serialize;
+
+/// Size limit of the JSON payload sent for one bug report
+pub const MAX_BUG_REPORT_PAYLOAD_BYTES: usize = 150 * 1024;
+
+/// Lines of the crash context kept before any size based truncation
+const MAX_CRASH_INFO_LINES: usize = 100;
+
+/// Crash causes and stack frames kept from the crash dump analysis
+const MAX_CRASH_CAUSES: usize = 5;
+const MAX_STACK_FRAMES: usize = 10;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogIssueSummary {
+    pub category: String,
+    pub severity: String,
+    pub match_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_line: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashCauseSummary {
+    pub cause_key: String,
+    pub score: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blamed_module: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashDumpSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exception_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crash_module: Option<String>,
+    pub crash_causes: Vec<CrashCauseSummary>,
+    /// Top stack frames as `module+offset`
+    pub crash_stack: Vec<String>,
+}
+
+/// What a bug report carries of the X-Plane log analysis
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XPlaneLogSummary {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xplane_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gpu_driver: Option<String>,
+    pub is_xplane_log: bool,
+    pub crash_detected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crash_info: Option<String>,
+    pub issues: Vec<LogIssueSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crash_dump: Option<CrashDumpSummary>,
+    /// Set when parts were dropped to stay under the payload limit
+    pub truncated: bool,
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "high" => 0,
+        "medium" => 1,
+        _ => 2,
+    }
+}
+
+fn summarize_crash_dump(deep: &DeepCrashAnalysis) -> CrashDumpSummary {
+    CrashDumpSummary {
+        exception_type: deep.exception.as_ref().map(|e| e.exception_type.clone()),
+        crash_module: deep.exception.as_ref().and_then(|e| e.crash_module.clone()),
+        crash_causes: deep
+            .crash_causes
+            .iter()
+            .take(MAX_CRASH_CAUSES)
+            .map(|cause| CrashCauseSummary {
+                cause_key: cause.cause_key.clone(),
+                score: cause.score,
+                blamed_module: cause.blamed_module.clone(),
+            })
+            .collect(),
+        crash_stack: deep
+            .crash_stack
+            .iter()
+            .take(MAX_STACK_FRAMES)
+            .map(|frame| {
+                format!(
+                    "{}+{}",
+                    frame.module_name.as_deref().unwrap_or("?"),
+                    frame.offset
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Summary of a log analysis, before any size based truncation.
+/// Issues are ordered by severity, then category.
+pub fn summarize(
+    analysis: &XPlaneLogAnalysis,
+    deep_crash: Option<&DeepCrashAnalysis>,
+) -> XPlaneLogSummary {
+    let mut issues: Vec<LogIssueSummary> = analysis
+        .issues
+        .iter()
+        .map(|issue| LogIssueSummary {
+            category: issue.category.clone(),
+            severity: issue.severity.clone(),
+            match_count: issue.match_count,
+            sample_line: Some(issue.sample_line.clone()).filter(|s| !s.is_empty()),
+        })
+        .collect();
+    issues.sort_by(|a, b| {
+        severity_rank(&a.severity)
+            .cmp(&severity_rank(&b.severity))
+            .then_with(|| a.category.cmp(&b.category))
+    });
+
+    XPlaneLogSummary {
+        xplane_version: analysis.system_info.xplane_version.clone(),
+        gpu_model: analysis.system_info.gpu_model.clone(),
+        gpu_driver: analysis.system_info.gpu_driver.clone(),
+        is_xplane_log: analysis.is_xplane_log,
+        crash_detected: analysis.crash_detected,
+        crash_info: analysis
+            .crash_info
+            .as_deref()
+            .map(|info| keep_lines(info, MAX_CRASH_INFO_LINES)),
+        issues,
+        crash_dump: deep_crash.map(summarize_crash_dump),
+        truncated: false,
+    }
+}
+
+/// First `count` lines of `text`
+fn keep_lines(text: &str, count: usize) -> String {
+    text.lines().take(count).collect::<Vec<_>>().join("\n")
+}
+
+fn serialized_len<T: Serialize>(value: &T) -> usize {
+    serde_json::to_vec(value)
+        .map(|bytes| bytes.len())
+        .unwrap_or(usize::MAX)
+}
+
+/// Drop parts of `summary` in priority order until it serializes to at most
+/// `budget` bytes. Returns false when even the bare summary is too large.
+pub fn fit_to_budget(summary: &mut XPlaneLogSummary, budget: usize) -> bool {
+    if serialized_len(summary) <= budget {
+        return true;
+    }
+    summary.truncated = true;
+    let fits = |summary: &XPlaneLogSummary| serialized_len(summary) <= budget;
+
+    for high in [false, true] {
+        for i in (0..summary.issues.len()).rev() {
+            if fits(summary) {
+                return true;
+            }
+            if (severity_rank(&summary.issues[i].severity) == 0) == high {
+                summary.issues[i].sample_line = None;
+            }
+        }
+        if !high {
+            if fits(summary) {
+                return true;
+            }
+            summary.crash_dump = None;
+        }
+    }
+
+    // Halve the crash info until it fits or is gone
+    while !fits(summary) {
+        let Some(info) = summary.crash_info.as_deref() else {
+            break;
+        };
+        let lines = info.lines().count();
+        summary.crash_info = (lines > 1).then(|| keep_lines(info, lines / 2));
+    }
+
+    while !fits(summary) && summary.issues.pop().is_some() {}
+    fits(summary)
+}
+
+/// Add `summary` to a bug report payload under `xplaneAnalysis`, truncated so
+/// the payload stays under [`MAX_BUG_REPORT_PAYLOAD_BYTES`]. The field is left
+/// out when the rest of the payload leaves no room for it.
+pub fn attach_to_payload(payload: &mut serde_json::Value, mut summary: XPlaneLogSummary) {
+    // The field name, quotes, colon and comma
+    let overhead = "\"xplaneAnalysis\":,".len();
+    let budget = MAX_BUG_REPORT_PAYLOAD_BYTES
+        .saturating_sub(serialized_len(payload))
+        .saturating_sub(overhead);
+    if !fit_to_budget(&mut summary, budget) {
+        return;
+    }
+    if let (Some(object), Ok(value)) = (payload.as_object_mut(), serde_json::to_value(&summary)) {
+        object.insert("xplaneAnalysis".to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(category: &str, severity: &str, sample_len: usize) -> LogIssueSummary {
+        LogIssueSummary {
+            category: category.to_string(),
+            severity: severity.to_string(),
+            match_count: 3,
+            sample_line: Some("x".repeat(sample_len)),
+        }
+    }
+
+    fn summary() -> XPlaneLogSummary {
+        XPlaneLogSummary {
+            xplane_version: Some("12.1.0".to_string()),
+            gpu_model: None,
+            gpu_driver: None,
+            is_xplane_log: true,
+            crash_detected: true,
+            crash_info: Some(
+                (0..40)
+                    .map(|i| format!("E/SYS: crash line {:02}", i))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+            issues: vec![
+                issue("crash", "high", 1000),
+                issue("vulkan_device_error", "high", 1000),
+                issue("plugin_error", "medium", 1000),
+                issue("missing_texture", "low", 1000),
+            ],
+            crash_dump: Some(CrashDumpSummary {
+                exception_type: Some("EXCEPTION_ACCESS_VIOLATION".to_string()),
+                crash_module: Some("bad.xpl".to_string()),
+                crash_causes: Vec::new(),
+                crash_stack: vec!["bad.xpl+0x10".to_string(); 10],
+            }),
+            truncated: false,
+        }
+    }
+
+    fn samples(summary: &XPlaneLogSummary) -> Vec<bool> {
+        summary
+            .issues
+            .iter()
+            .map(|issue| issue.sample_line.is_some())
+            .collect()
+    }
+
+    #[test]
+    fn test_summary_within_budget_is_untouched() {
+        let mut s = summary();
+        assert!(fit_to_budget(&mut s, MAX_BUG_REPORT_PAYLOAD_BYTES));
+        assert!(!s.truncated);
+        assert_eq!(samples(&s), vec![true, true, true, true]);
+    }
+
+    #[test]
+    fn test_low_priority_samples_and_crash_dump_go_first() {
+        let full = serialized_len(&summary());
+
+        // Room for everything but one medium/low sample
+        let mut s = summary();
+        assert!(fit_to_budget(&mut s, full - 500));
+        assert!(s.truncated);
+        assert_eq!(samples(&s), vec![true, true, true, false]);
+        assert!(s.crash_dump.is_some());
+
+        // Medium/low samples and the crash dump are gone before high samples
+        let mut s = summary();
+        assert!(fit_to_budget(&mut s, full - 2100));
+        assert_eq!(samples(&s), vec![true, true, false, false]);
+        assert!(s.crash_dump.is_none());
+
+        // High samples go before any crash info line
+        let mut s = summary();
+        assert!(fit_to_budget(&mut s, full - 3500));
+        assert_eq!(samples(&s), vec![false, false, false, false]);
+        assert_eq!(s.crash_info.as_deref().unwrap().lines().count(), 40);
+        assert_eq!(s.issues.len(), 4);
+    }
+
+    #[test]
+    fn test_crash_info_shrinks_before_issues_are_dropped() {
+        let mut bare = summary();
+        bare.crash_dump = None;
+        bare.crash_info = None;
+        for issue in &mut bare.issues {
+            issue.sample_line = None;
+        }
+        bare.truncated = true;
+        let bare_len = serialized_len(&bare);
+
+        let mut s = summary();
+        assert!(fit_to_budget(&mut s, bare_len + 200));
+        let kept_lines = s.crash_info.as_deref().unwrap().lines().count();
+        assert!(kept_lines > 0 && kept_lines < 40);
+        assert!(s
+            .crash_info
+            .as_deref()
+            .unwrap()
+            .starts_with("E/SYS: crash line 00"));
+        assert_eq!(s.issues.len(), 4);
+
+        // Below the bare summary, issues go from the end
+        let mut s = summary();
+        assert!(fit_to_budget(&mut s, bare_len - 10));
+        assert!(s.crash_info.is_none());
+        assert_eq!(s.issues.len(), 3);
+        assert_eq!(s.issues[0].category, "crash");
+
+        let mut s = summary();
+        assert!(!fit_to_budget(&mut s, 10));
+    }
+
+    #[test]
+    fn test_attached_payload_stays_under_limit() {
+        let mut payload = serde_json::json!({
+            "errorTitle": "Crash",
+            "logs": "l".repeat(MAX_BUG_REPORT_PAYLOAD_BYTES - 3000),
+        });
+        attach_to_payload(&mut payload, summary());
+        assert!(payload.get("xplaneAnalysis").is_some());
+        assert!(serialized_len(&payload) <= MAX_BUG_REPORT_PAYLOAD_BYTES);
+        assert_eq!(payload["xplaneAnalysis"]["truncated"], true);
+    }
+}
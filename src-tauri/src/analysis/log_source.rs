@@ -0,0 +1,225 @@
+//! Where an X-Plane log is read from for analysis
+//!
+//! X-Plane overwrites `Log.txt` at every start, so the evidence of a crash is
+//! gone once the sim was launched again. Besides the `Log.txt` of an install,
+//! a log can be read from a copy saved elsewhere, or out of a .zip or .7z
+//! someone sent. Only the `Log.txt` entry of an archive is read, into memory
+//! and up to [`MAX_ARCHIVED_LOG_BYTES`].
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Largest `Log.txt` read out of an archive
+pub const MAX_ARCHIVED_LOG_BYTES: u64 = 50 * 1024 * 1024;
+
+const LOG_FILE_NAME: &str = "Log.txt";
+
+/// Where an analyzed log came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogSource {
+    /// `Log.txt` in the X-Plane folder
+    XplaneFolder,
+    /// A log file picked by the user
+    File,
+    /// The `Log.txt` entry of a .zip or .7z
+    Archive,
+}
+
+pub struct LoadedLog {
+    pub lines: Vec<String>,
+    pub source: LogSource,
+    /// The log file, or the archive holding it
+    pub path: PathBuf,
+    /// For archives: path of the log inside the archive
+    pub archive_entry: Option<String>,
+}
+
+fn to_lines(bytes: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(bytes)
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_log_entry(name: &str) -> bool {
+    name.rsplit(['/', '\\'])
+        .next()
+        .is_some_and(|file_name| file_name.eq_ignore_ascii_case(LOG_FILE_NAME))
+}
+
+/// Read all of `reader`, failing when it holds more than [`MAX_ARCHIVED_LOG_BYTES`]
+fn read_bounded(reader: impl Read) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    reader
+        .take(MAX_ARCHIVED_LOG_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Read error: {e}"))?;
+    if bytes.len() as u64 > MAX_ARCHIVED_LOG_BYTES {
+        return Err(format!(
+            "{} in the archive is larger than {} MB",
+            LOG_FILE_NAME,
+            MAX_ARCHIVED_LOG_BYTES / (1024 * 1024)
+        ));
+    }
+    Ok(bytes)
+}
+
+fn read_zip_log(path: &Path) -> Result<(String, Vec<u8>), String> {
+    let file = fs::File::open(path).map_err(|e| format!("Cannot open archive: {e}"))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Cannot read zip archive: {e}"))?;
+    let name = archive
+        .file_names()
+        .find(|name| is_log_entry(name))
+        .map(str::to_string)
+        .ok_or_else(|| format!("No {} in the archive", LOG_FILE_NAME))?;
+    let entry = archive
+        .by_name(&name)
+        .map_err(|e| format!("Cannot read {name} from the archive: {e}"))?;
+    Ok((name, read_bounded(entry)?))
+}
+
+fn read_7z_log(path: &Path) -> Result<(String, Vec<u8>), String> {
+    let mut archive = sevenz_rust2::ArchiveReader::open(path, sevenz_rust2::Password::empty())
+        .map_err(|e| format!("Cannot read 7z archive: {e}"))?;
+    let mut found = None;
+    archive
+        .for_each_entries(|entry, reader| {
+            if entry.is_directory() || !is_log_entry(entry.name()) {
+                return Ok(true);
+            }
+            found = Some((entry.name().to_string(), read_bounded(reader)));
+            Ok(false)
+        })
+        .map_err(|e| format!("Cannot read 7z archive: {e}"))?;
+    let (name, bytes) = found.ok_or_else(|| format!("No {} in the archive", LOG_FILE_NAME))?;
+    Ok((name, bytes?))
+}
+
+/// Read a log file, or the `Log.txt` inside a .zip or .7z
+fn load_log_file(path: &Path) -> Result<LoadedLog, String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    let (archive_entry, bytes) = match extension.as_deref() {
+        Some("zip") => read_zip_log(path).map(|(name, bytes)| (Some(name), bytes))?,
+        Some("7z") => read_7z_log(path).map(|(name, bytes)| (Some(name), bytes))?,
+        _ => (
+            None,
+            fs::read(path).map_err(|e| format!("Cannot open {}: {e}", path.display()))?,
+        ),
+    };
+    Ok(LoadedLog {
+        lines: to_lines(&bytes),
+        source: if archive_entry.is_some() {
+            LogSource::Archive
+        } else {
+            LogSource::File
+        },
+        path: path.to_path_buf(),
+        archive_entry,
+    })
+}
+
+/// Read the log at `log_file_path`, or else the `Log.txt` of `xplane_path`
+pub fn load_log(
+    xplane_path: Option<&Path>,
+    log_file_path: Option<&Path>,
+) -> Result<LoadedLog, String> {
+    if let Some(log_file_path) = log_file_path {
+        return load_log_file(log_file_path);
+    }
+    let xplane_path =
+        xplane_path.ok_or_else(|| "An X-Plane folder or a log file is required".to_string())?;
+    let path = xplane_path.join(LOG_FILE_NAME);
+    let bytes = fs::read(&path).map_err(|e| format!("Cannot open Log.txt: {e}"))?;
+    Ok(LoadedLog {
+        lines: to_lines(&bytes),
+        source: LogSource::XplaneFolder,
+        path,
+        archive_entry: None,
+    })
+}
+
+/// Newest `.dmp` crash dump X-Plane wrote next to its log, in
+/// `Output/crash_reports` or the `reports` folder inside it. Dumps are what
+/// `analyze_crash_report` parses; the `.rpt` text reports beside them aren't.
+pub fn newest_crash_report(xplane_path: &Path) -> Option<PathBuf> {
+    let crash_reports = xplane_path.join("Output").join("crash_reports");
+    [crash_reports.join("reports"), crash_reports]
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("dmp"))
+        })
+        .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_log_is_read_from_a_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("crash.zip");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&archive_path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("notes.txt", options).unwrap();
+        writer.write_all(b"not the log").unwrap();
+        writer.start_file("X-Plane 12/Log.txt", options).unwrap();
+        writer
+            .write_all(b"Log.txt for X-Plane 12.1.4\nThis application has crashed\n")
+            .unwrap();
+        writer.finish().unwrap();
+
+        let log = load_log(None, Some(&archive_path)).unwrap();
+        assert_eq!(log.source, LogSource::Archive);
+        assert_eq!(log.archive_entry.as_deref(), Some("X-Plane 12/Log.txt"));
+        assert_eq!(log.lines.len(), 2);
+
+        let log_path = dir.path().join("Log_saved.txt");
+        fs::write(&log_path, "Log.txt for X-Plane 12.1.4\n").unwrap();
+        let log = load_log(Some(dir.path()), Some(&log_path)).unwrap();
+        assert_eq!(log.source, LogSource::File);
+        assert!(log.archive_entry.is_none());
+    }
+
+    #[test]
+    fn test_newest_crash_report_is_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let reports = dir
+            .path()
+            .join("Output")
+            .join("crash_reports")
+            .join("reports");
+        fs::create_dir_all(&reports).unwrap();
+        assert!(newest_crash_report(dir.path()).is_none());
+
+        let older = reports.join("older.dmp");
+        fs::write(&older, "old").unwrap();
+        let old_time = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(&older)
+            .unwrap()
+            .set_modified(old_time)
+            .unwrap();
+        fs::write(reports.join("newer.dmp"), "dump").unwrap();
+        fs::write(reports.join("newest.rpt"), "text report").unwrap();
+
+        assert_eq!(
+            newest_crash_report(dir.path()),
+            Some(reports.join("newer.dmp"))
+        );
+    }
+}
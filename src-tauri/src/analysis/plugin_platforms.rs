@@ -0,0 +1,339 @@
+//! Which platforms a plugin has binaries for
+//!
+//! X-Plane loads `<plugin>/<os>_x64/<name>.xpl`, and still loads the older fat
+//! layout: `64/win.xpl` and `64/lin.xpl`, plus a `mac.xpl` at the root holding
+//! several architectures. Folder names alone don't say whether a Mac binary
+//! runs natively on Apple Silicon: the arm64 build ships inside the universal
+//! binary in `mac_x64`. Where a binary can be read, its header decides (Mach-O
+//! slices, ELF class, PE machine); otherwise its path does.
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use crate::models::PluginPlatformBinaries;
+
+/// Bytes read from the start of a binary to identify it
+const HEADER_LEN: usize = 4096;
+
+/// Universal Mach-O binaries list at most this many slices
+const MAX_FAT_ARCHS: u32 = 16;
+
+const CPU_TYPE_X86_64: u32 = 0x0100_0007;
+const CPU_TYPE_ARM64: u32 = 0x0100_000C;
+
+/// What one plugin binary was built for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryKind {
+    WinX64,
+    Mac {
+        x64: bool,
+        arm64: bool,
+    },
+    /// A Mac binary whose header couldn't be read; the x86_64 slice is assumed
+    MacByPath,
+    LinX64,
+    Legacy32,
+    Unrecognized,
+}
+
+fn read_u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+/// Slices of a Mach-O binary, or None when `header` isn't one
+fn mach_o_kind(header: &[u8]) -> Option<BinaryKind> {
+    let magic = read_u32_be(header, 0)?;
+    let cpu_types: Vec<u32> = match magic {
+        // Universal binary: big-endian header, then one entry per slice
+        0xCAFE_BABE | 0xCAFE_BABF => {
+            let count = read_u32_be(header, 4)?;
+            // Java class files share the magic and have a version number here
+            if count == 0 || count > MAX_FAT_ARCHS {
+                return None;
+            }
+            let entry_len = if magic == 0xCAFE_BABE { 20 } else { 32 };
+            (0..count as usize)
+                .filter_map(|index| read_u32_be(header, 8 + index * entry_len))
+                .collect()
+        }
+        // Single 64-bit slice, little-endian
+        0xCFFA_EDFE => vec![read_u32_le(header, 4)?],
+        // Single 32-bit slice (i386 little-endian, PowerPC big-endian)
+        0xCEFA_EDFE | 0xFEED_FACE => return Some(BinaryKind::Legacy32),
+        _ => return None,
+    };
+    let x64 = cpu_types.contains(&CPU_TYPE_X86_64);
+    let arm64 = cpu_types.contains(&CPU_TYPE_ARM64);
+    Some(if x64 || arm64 {
+        BinaryKind::Mac { x64, arm64 }
+    } else {
+        BinaryKind::Legacy32
+    })
+}
+
+/// Platform of a binary from its header, or None when the header doesn't tell
+fn kind_from_header(header: &[u8]) -> Option<BinaryKind> {
+    if let Some(kind) = mach_o_kind(header) {
+        return Some(kind);
+    }
+    if header.starts_with(b"\x7fELF") {
+        return match header.get(4)? {
+            1 => Some(BinaryKind::Legacy32),
+            2 => Some(BinaryKind::LinX64),
+            _ => None,
+        };
+    }
+    if header.starts_with(b"MZ") {
+        let pe_offset = read_u32_le(header, 0x3C)? as usize;
+        if header.get(pe_offset..pe_offset + 4)? != b"PE\0\0" {
+            return None;
+        }
+        return match read_u16_le(header, pe_offset + 4)? {
+            0x8664 => Some(BinaryKind::WinX64),
+            0x014C => Some(BinaryKind::Legacy32),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// Platform of a binary from its path relative to the plugin folder
+fn kind_from_path(relative_path: &str) -> BinaryKind {
+    let components: Vec<String> = relative_path
+        .split(['/', '\\'])
+        .filter(|component| !component.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    let Some((file_name, folders)) = components.split_last() else {
+        return BinaryKind::Unrecognized;
+    };
+    let stem = file_name
+        .strip_suffix(".xpl")
+        .or_else(|| file_name.strip_suffix(".xfmp"))
+        .unwrap_or(file_name);
+
+    for folder in folders.iter().rev() {
+        match folder.as_str() {
+            "win_x64" => return BinaryKind::WinX64,
+            "lin_x64" => return BinaryKind::LinX64,
+            "mac_x64" => return BinaryKind::MacByPath,
+            "mac_arm64" => {
+                return BinaryKind::Mac {
+                    x64: false,
+                    arm64: true,
+                }
+            }
+            "32" | "win32" | "lin32" => return BinaryKind::Legacy32,
+            "64" => {
+                return match stem {
+                    "win" => BinaryKind::WinX64,
+                    "lin" => BinaryKind::LinX64,
+                    "mac" => BinaryKind::MacByPath,
+                    _ => BinaryKind::Unrecognized,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Fat plugin layout: 32-bit win.xpl/lin.xpl and a universal mac.xpl at the root
+    match stem {
+        "win" | "lin" => BinaryKind::Legacy32,
+        "mac" => BinaryKind::MacByPath,
+        _ => BinaryKind::Unrecognized,
+    }
+}
+
+fn read_header(path: &Path) -> Option<Vec<u8>> {
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    fs::File::open(path)
+        .ok()?
+        .take(HEADER_LEN as u64)
+        .read_to_end(&mut header)
+        .ok()?;
+    Some(header)
+}
+
+fn record(binaries: &mut PluginPlatformBinaries, kind: BinaryKind) {
+    match kind {
+        BinaryKind::WinX64 => binaries.win_x64 = true,
+        BinaryKind::Mac { x64, arm64 } => {
+            binaries.mac_x64 |= x64;
+            binaries.mac_arm64 |= arm64;
+        }
+        BinaryKind::MacByPath => {
+            binaries.mac_x64 = true;
+            binaries.mac_arch_unknown = true;
+        }
+        BinaryKind::LinX64 => binaries.lin_x64 = true,
+        BinaryKind::Legacy32 => binaries.legacy_32bit = true,
+        BinaryKind::Unrecognized => binaries.unrecognized = true,
+    }
+}
+
+/// Platforms of binaries listed by path only, e.g. inside an archive
+pub fn from_paths<S: AsRef<str>>(relative_paths: &[S]) -> PluginPlatformBinaries {
+    let mut binaries = PluginPlatformBinaries::default();
+    for path in relative_paths {
+        record(&mut binaries, kind_from_path(path.as_ref()));
+    }
+    binaries
+}
+
+/// Platforms of the binaries at `relative_paths` in `folder`, reading each
+/// binary's header and falling back to its path
+pub fn from_folder<S: AsRef<str>>(folder: &Path, relative_paths: &[S]) -> PluginPlatformBinaries {
+    let mut binaries = PluginPlatformBinaries::default();
+    for path in relative_paths {
+        let path = path.as_ref();
+        let kind = read_header(&folder.join(path))
+            .and_then(|header| kind_from_header(&header))
+            .unwrap_or_else(|| kind_from_path(path));
+        record(&mut binaries, kind);
+    }
+    binaries
+}
+
+/// Whether X-Plane on `os` and `arch` (as in [`std::env::consts::OS`] and
+/// [`std::env::consts::ARCH`]) can load one of the binaries. On a Mac the
+/// binary needs a slice for the CPU. Binaries of unknown platform, and Mac
+/// binaries of unknown architectures, are given the benefit of the doubt.
+pub fn supports(binaries: &PluginPlatformBinaries, os: &str, arch: &str) -> bool {
+    binaries.unrecognized
+        || match os {
+            "windows" => binaries.win_x64,
+            "macos" => match arch {
+                "aarch64" => binaries.mac_arm64 || binaries.mac_arch_unknown,
+                "x86_64" => binaries.mac_x64,
+                _ => binaries.mac_x64 || binaries.mac_arm64,
+            },
+            "linux" => binaries.lin_x64,
+            _ => true,
+        }
+}
+
+/// Whether X-Plane on this computer can load one of the binaries
+pub fn supports_current_platform(binaries: &PluginPlatformBinaries) -> bool {
+    supports(binaries, std::env::consts::OS, std::env::consts::ARCH)
+}
+
+/// Name of this computer's operating system, for messages
+pub fn current_platform_name() -> &'static str {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("windows", _) => "Windows",
+        ("macos", "aarch64") => "macOS (Apple Silicon)",
+        ("macos", _) => "macOS",
+        ("linux", _) => "Linux",
+        (other, _) => other,
+    }
+}
+
+/// Platforms with binaries, for messages
+pub fn describe(binaries: &PluginPlatformBinaries) -> String {
+    let names: Vec<&str> = [
+        (binaries.win_x64, "Windows"),
+        (binaries.mac_x64 || binaries.mac_arm64, "macOS"),
+        (binaries.lin_x64, "Linux"),
+        (binaries.legacy_32bit, "32-bit only"),
+    ]
+    .into_iter()
+    .filter_map(|(present, name)| present.then_some(name))
+    .collect();
+    if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn universal_header(cpu_types: &[u32]) -> Vec<u8> {
+        let mut header = Vec::new();
+        header.extend_from_slice(&0xCAFE_BABEu32.to_be_bytes());
+        header.extend_from_slice(&(cpu_types.len() as u32).to_be_bytes());
+        for cpu_type in cpu_types {
+            header.extend_from_slice(&cpu_type.to_be_bytes());
+            header.extend_from_slice(&[0; 16]);
+        }
+        header
+    }
+
+    #[test]
+    fn test_paths_cover_modern_and_legacy_layouts() {
+        let binaries = from_paths(&["win_x64/Foo.xpl", "64/lin.xpl", "mac.xpl", "32/win.xpl"]);
+        assert_eq!(
+            binaries,
+            PluginPlatformBinaries {
+                win_x64: true,
+                mac_x64: true,
+                mac_arm64: false,
+                mac_arch_unknown: true,
+                lin_x64: true,
+                legacy_32bit: true,
+                unrecognized: false,
+            }
+        );
+
+        let windows_only = from_paths(&["win_x64/Foo.xpl", "win_x64/Foo.xfmp"]);
+        assert!(supports(&windows_only, "windows", "x86_64"));
+        assert!(!supports(&windows_only, "linux", "x86_64"));
+        assert!(!supports(&windows_only, "macos", "aarch64"));
+        assert_eq!(describe(&windows_only), "Windows");
+
+        assert!(supports(&from_paths(&["Foo.xpl"]), "linux", "x86_64"));
+        // The universal binary in mac_x64 may hold the arm64 build
+        assert!(supports(
+            &from_paths(&["mac_x64/Foo.xpl"]),
+            "macos",
+            "aarch64"
+        ));
+    }
+
+    #[test]
+    fn test_universal_mach_o_reports_its_arm64_slice() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("mac_x64")).unwrap();
+        fs::write(
+            dir.path().join("mac_x64").join("Foo.xpl"),
+            universal_header(&[CPU_TYPE_X86_64, CPU_TYPE_ARM64]),
+        )
+        .unwrap();
+        let binaries = from_folder(dir.path(), &["mac_x64/Foo.xpl"]);
+        assert!(binaries.mac_x64 && binaries.mac_arm64);
+
+        fs::write(
+            dir.path().join("mac_x64").join("Foo.xpl"),
+            universal_header(&[CPU_TYPE_X86_64]),
+        )
+        .unwrap();
+        let binaries = from_folder(dir.path(), &["mac_x64/Foo.xpl"]);
+        assert!(binaries.mac_x64 && !binaries.mac_arm64);
+        assert!(supports(&binaries, "macos", "x86_64"));
+        assert!(!supports(&binaries, "macos", "aarch64"));
+
+        // A root binary named after no platform is identified by its header
+        let mut elf = b"\x7fELF\x02".to_vec();
+        elf.resize(64, 0);
+        fs::write(dir.path().join("Foo.xpl"), elf).unwrap();
+        let binaries = from_folder(dir.path(), &["Foo.xpl"]);
+        assert!(binaries.lin_x64 && !binaries.unrecognized);
+    }
+}
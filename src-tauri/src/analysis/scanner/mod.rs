@@ -9,7 +9,9 @@ use crate::archive_input::{detect_archive_format, prepare_archive_for_read, Arch
 use crate::livery_patterns;
 use crate::logger;
 use crate::models::{
-    AddonType, DetectedItem, ExtractionChain, NavdataCycle, NavdataInfo, NestedArchiveInfo,
+    AddonCompatibility, AddonType, DetectedItem, ExtractionChain, NavdataCycle, NavdataInfo,
+    NestedArchiveInfo, ScanDiagnostics, SimVersionHints, SkippedMarker, SkippedMarkerReason,
+    TargetRootKind, UpdaterProvider,
 };
 
 #[path = "rar.rs"]
@@ -62,6 +64,20 @@ pub(super) struct ScanContext {
     parent_chain: Vec<NestedArchiveInfo>,
     /// Password map for archives (key: archive path, value: password)
     passwords: HashMap<String, String>,
+    /// What the scan saw, for explaining an input without addons
+    diagnostics: ScanDiagnostics,
+    /// Top-level archive entries already listed in `diagnostics`
+    top_level_seen: HashSet<String>,
+}
+
+/// Top-level archive entries listed in diagnostics at most
+const MAX_DIAGNOSTIC_ENTRIES: usize = 50;
+
+/// Whether a path names a file one of the detectors looks for
+fn is_marker_path(path: &str) -> bool {
+    [".acf", ".xpl", ".dsf", "library.txt", "cycle.json", ".lua"]
+        .iter()
+        .any(|suffix| path.ends_with(suffix))
 }
 
 impl ScanContext {
@@ -71,6 +87,65 @@ impl ScanContext {
             max_depth: 2,
             parent_chain: Vec::new(),
             passwords: HashMap::new(),
+            diagnostics: ScanDiagnostics::default(),
+            top_level_seen: HashSet::new(),
+        }
+    }
+
+    /// Path of an archive entry as seen from the dropped input
+    fn diagnostic_path(&self, path: &str) -> String {
+        let mut full: Vec<&str> = self
+            .parent_chain
+            .iter()
+            .map(|info| info.internal_path.as_str())
+            .collect();
+        full.push(path);
+        full.join("/")
+    }
+
+    /// Count an archive entry and list its top-level name for the outer archive
+    fn note_archive_entry(&mut self, path: &str) {
+        if self.depth > 0 {
+            return;
+        }
+        self.diagnostics.entry_count += 1;
+        let top_level = path
+            .split('/')
+            .find(|part| !part.is_empty())
+            .unwrap_or(path);
+        if self.top_level_seen.contains(top_level) {
+            return;
+        }
+        if self.diagnostics.top_level_entries.len() < MAX_DIAGNOSTIC_ENTRIES {
+            self.top_level_seen.insert(top_level.to_string());
+            self.diagnostics
+                .top_level_entries
+                .push(top_level.to_string());
+        } else {
+            self.diagnostics.top_level_truncated = true;
+        }
+    }
+
+    /// Record a path skipped by the ignore rules
+    fn note_ignored(&mut self, path: &str) {
+        self.diagnostics.ignored_path_count += 1;
+        if is_marker_path(path) {
+            self.note_skipped_marker(path, SkippedMarkerReason::Ignored);
+        }
+    }
+
+    /// Record a marker file that didn't become an addon. Livery markers and
+    /// other non-marker paths are left out.
+    fn note_skipped_marker(&mut self, path: &str, reason: SkippedMarkerReason) {
+        if !is_marker_path(path) {
+            return;
+        }
+        self.diagnostics.skipped_marker_count += 1;
+        if self.diagnostics.skipped_markers.len() < MAX_DIAGNOSTIC_ENTRIES {
+            let path = self.diagnostic_path(path);
+            self.diagnostics
+                .skipped_markers
+                .push(SkippedMarker { path, reason });
         }
     }
 
@@ -87,9 +162,27 @@ impl ScanContext {
         self.depth += 1;
     }
 
-    fn pop_archive(&mut self) {
-        self.parent_chain.pop();
+    /// Pop the innermost archive, returning it with any flags set while it was scanned
+    fn pop_archive(&mut self) -> Option<NestedArchiveInfo> {
         self.depth = self.depth.saturating_sub(1);
+        self.parent_chain.pop()
+    }
+
+    /// Password recorded for the nested archive currently being scanned.
+    /// Nested archives are scanned from temp files, so their passwords can't
+    /// be looked up by path.
+    fn current_nested_password(&self) -> Option<String> {
+        self.parent_chain
+            .last()
+            .and_then(|info| info.password.clone())
+    }
+
+    /// Record that the archive currently being scanned is encrypted
+    fn mark_current_archive_encrypted(&mut self) {
+        self.diagnostics.encrypted = true;
+        if let Some(info) = self.parent_chain.last_mut() {
+            info.password_required = true;
+        }
     }
 
     /// Get password for a nested archive by checking the password map
@@ -282,6 +375,289 @@ fn infer_version_from_name(text: &str) -> Option<String> {
         .map(|matched| matched.as_str().replace('_', "."))
 }
 
+/// Bytes read from the start of an .acf file; the format version is on the second line
+const ACF_HEADER_BYTES: u64 = 64;
+
+/// Virtual-path prefixes of the X-Plane 11 default library that X-Plane 12 no
+/// longer ships. Libraries exporting into them override art that isn't there.
+const LEGACY_LIBRARY_PREFIXES: &[&str] = &["lib/g8/", "lib/g10/terrain10/", "lib/g10/forests/"];
+
+/// Parse the format version from an .acf header ("I\n1100 Version\nACF ...")
+fn parse_acf_format_version(header: &str) -> Option<u32> {
+    let mut tokens = header.lines().nth(1)?.split_whitespace();
+    let version = tokens.next()?.parse().ok()?;
+    tokens
+        .next()
+        .is_some_and(|t| t.eq_ignore_ascii_case("version"))
+        .then_some(version)
+}
+
+/// Virtual paths exported by a library.txt that fall under [`LEGACY_LIBRARY_PREFIXES`]
+fn parse_legacy_library_exports(library_txt: &str) -> Vec<String> {
+    let mut exports = Vec::new();
+    for line in library_txt.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let virtual_path = match tokens.first().map(|t| t.to_ascii_uppercase()) {
+            Some(directive) if !directive.starts_with("EXPORT") => continue,
+            // EXPORT_RATIO/EXPORT_SEASON take an extra argument before the virtual path
+            Some(directive) if directive == "EXPORT_RATIO" || directive == "EXPORT_SEASON" => {
+                tokens.get(2)
+            }
+            Some(_) => tokens.get(1),
+            None => continue,
+        };
+        let Some(virtual_path) = virtual_path else {
+            continue;
+        };
+        let normalized = virtual_path.replace('\\', "/").to_ascii_lowercase();
+        if LEGACY_LIBRARY_PREFIXES
+            .iter()
+            .any(|prefix| normalized.starts_with(prefix))
+            && !exports.contains(&normalized)
+        {
+            exports.push(normalized);
+        }
+    }
+    exports
+}
+
+/// Module names passed to `require` in a Lua script, ignoring line comments
+fn parse_lua_requires(lua_content: &str) -> Vec<String> {
+    static REQUIRE_RE: OnceLock<Regex> = OnceLock::new();
+    let re = REQUIRE_RE
+        .get_or_init(|| Regex::new(r#"\brequire\s*\(?\s*["']([A-Za-z0-9_./\-]+)["']"#).unwrap());
+
+    let mut modules = Vec::new();
+    for line in lua_content.lines() {
+        let code = line.split("--").next().unwrap_or("");
+        for cap in re.captures_iter(code) {
+            let module = cap[1].to_string();
+            if !modules.contains(&module) {
+                modules.push(module);
+            }
+        }
+    }
+    modules
+}
+
+/// Companions a FlyWithLua script depends on, relative to the script's folder:
+/// folders/files referenced through SCRIPT_DIRECTORY, a folder named after the
+/// script, a sibling Modules folder and the files behind `require` calls.
+/// `exists` answers whether a script-relative path is present, so the same
+/// rules apply to on-disk folders and to archive listings. The installer puts
+/// a Modules companion into FlyWithLua/Modules rather than Scripts.
+fn collect_lua_companions(
+    script_file_name: &str,
+    lua_content: Option<&str>,
+    exists: impl Fn(&str) -> bool,
+) -> Vec<String> {
+    let mut companions: Vec<String> = Vec::new();
+    let mut add = |name: String| {
+        if !name.eq_ignore_ascii_case(script_file_name) && !companions.contains(&name) {
+            companions.push(name);
+        }
+    };
+
+    if let Some(content) = lua_content {
+        let mut referenced = Scanner::parse_lua_companions(content);
+        referenced.sort();
+        for name in referenced {
+            if exists(&name) {
+                add(name);
+            }
+        }
+    }
+
+    let stem = Path::new(script_file_name)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("");
+    if Scanner::is_valid_companion_component(stem) && exists(stem) {
+        add(stem.to_string());
+    }
+
+    if let Some(modules_dir) = ["Modules", "modules"].into_iter().find(|dir| exists(*dir)) {
+        add(modules_dir.to_string());
+    }
+
+    for module in lua_content.map(parse_lua_requires).unwrap_or_default() {
+        let module_path = module.trim_end_matches(".lua").replace('.', "/");
+        let candidates = [
+            format!("{}.lua", module_path),
+            format!("{}/init.lua", module_path),
+        ];
+        if let Some(found) = candidates
+            .iter()
+            .find(|candidate| exists(candidate.as_str()))
+        {
+            if let Some(first) = Scanner::extract_first_component(found) {
+                add(first);
+            }
+        }
+    }
+
+    companions
+}
+
+fn non_empty_hints(hints: SimVersionHints) -> Option<SimVersionHints> {
+    (!hints.is_empty()).then_some(hints)
+}
+
+fn acf_hints_from_header(header: &[u8]) -> Option<SimVersionHints> {
+    non_empty_hints(SimVersionHints {
+        acf_format_version: parse_acf_format_version(&String::from_utf8_lossy(header)),
+        legacy_library_exports: Vec::new(),
+    })
+}
+
+fn library_hints_from_contents(library_txt: &str) -> Option<SimVersionHints> {
+    non_empty_hints(SimVersionHints {
+        acf_format_version: None,
+        legacy_library_exports: parse_legacy_library_exports(library_txt),
+    })
+}
+
+/// Preview icons X-Plane shows in the livery picker
+const LIVERY_ICON_NAMES: &[&str] = &["icon11.png", "icon.png"];
+
+/// How deep to walk a folder when looking for livery structure
+const LIVERY_SCAN_DEPTH: usize = 6;
+
+fn is_texture_file(lower_name: &str) -> bool {
+    lower_name.ends_with(".png") || lower_name.ends_with(".dds")
+}
+
+/// Find folders that look like aircraft liveries among `/`-separated file paths:
+/// an `objects` folder with textures (named like the target aircraft's own textures
+/// when those are known), or an icon next to `*_paint` textures. Returns the
+/// outermost livery roots; `""` means the listing root itself is a livery.
+fn find_livery_roots(
+    entries: &[String],
+    aircraft_textures: Option<&HashSet<String>>,
+) -> Vec<String> {
+    let mut roots: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
+    let mut icon_dirs: HashSet<&str> = HashSet::new();
+    let mut paint_dirs: HashSet<&str> = HashSet::new();
+
+    for entry in entries {
+        if Scanner::should_ignore_archive_path(entry) {
+            continue;
+        }
+        let (dir, file_name) = entry.rsplit_once('/').unwrap_or(("", entry.as_str()));
+        let lower = file_name.to_ascii_lowercase();
+        if !is_texture_file(&lower) {
+            continue;
+        }
+
+        let (parent, dir_name) = dir.rsplit_once('/').unwrap_or(("", dir));
+        if dir_name.eq_ignore_ascii_case("objects")
+            && aircraft_textures.is_none_or(|names| names.contains(&lower))
+        {
+            roots.insert(parent.to_string());
+        }
+        if LIVERY_ICON_NAMES.contains(&lower.as_str()) {
+            icon_dirs.insert(dir);
+        }
+        if lower.ends_with("_paint.png") || lower.ends_with("_paint.dds") {
+            paint_dirs.insert(dir);
+        }
+    }
+    roots.extend(
+        icon_dirs
+            .intersection(&paint_dirs)
+            .map(|dir| dir.to_string()),
+    );
+
+    // Sorted order puts parents before their children
+    let mut outermost: Vec<String> = Vec::new();
+    for root in roots {
+        let nested = outermost
+            .iter()
+            .any(|outer| outer.is_empty() || root.starts_with(&format!("{}/", outer)));
+        if !nested {
+            outermost.push(root);
+        }
+    }
+    outermost
+}
+
+/// Whether updates can be tracked for this addon type
+fn tracks_updates(addon_type: &AddonType) -> bool {
+    matches!(
+        addon_type,
+        AddonType::Aircraft
+            | AddonType::Plugin
+            | AddonType::Scenery
+            | AddonType::SceneryLibrary
+            | AddonType::Livery
+    )
+}
+
+/// Whether an archive entry is a skunkcrafts cfg or an x-updater profile,
+/// wherever it sits; [`updater_provider_from_markers`] checks the location
+fn is_updater_marker_path(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    file_name.eq_ignore_ascii_case("skunkcrafts_updater.cfg")
+        || crate::x_updater_profile::is_profile_file_name(file_name)
+}
+
+/// Updater whose metadata sits in the addon root `internal_root` (the archive
+/// root when `None`), judged from the marker paths of an archive listing.
+/// skunkcrafts wins over x-updater, as on the management page.
+fn updater_provider_from_markers(
+    markers: &[String],
+    internal_root: Option<&str>,
+) -> UpdaterProvider {
+    let prefix = internal_root
+        .map(|root| root.trim_end_matches('/'))
+        .filter(|root| !root.is_empty())
+        .map(|root| format!("{}/", root))
+        .unwrap_or_default();
+
+    let mut provider = UpdaterProvider::None;
+    for marker in markers {
+        let Some(relative) = marker.strip_prefix(prefix.as_str()) else {
+            continue;
+        };
+        if relative.eq_ignore_ascii_case("skunkcrafts_updater.cfg") {
+            return UpdaterProvider::Skunkcrafts;
+        }
+        if crate::x_updater_profile::is_profile_entry_path(relative) {
+            provider = UpdaterProvider::XUpdater;
+        }
+    }
+    provider
+}
+
+/// Fill in `updater_provider` for the items detected directly in an archive.
+/// Items from nested archives already carry an extraction chain and were
+/// filled in by the scan of their own archive.
+fn apply_archive_updater_providers(items: &mut [DetectedItem], markers: &[String]) {
+    for item in items.iter_mut() {
+        if item.extraction_chain.is_none() && tracks_updates(&item.addon_type) {
+            item.updater_provider =
+                updater_provider_from_markers(markers, item.archive_internal_root.as_deref());
+        }
+    }
+}
+
+/// Fill in `updater_provider` for items detected in a folder on disk
+fn apply_folder_updater_providers(items: &mut [DetectedItem]) {
+    for item in items.iter_mut() {
+        if !tracks_updates(&item.addon_type) {
+            continue;
+        }
+        let root = Path::new(&item.path);
+        item.updater_provider = if root.join("skunkcrafts_updater.cfg").is_file() {
+            UpdaterProvider::Skunkcrafts
+        } else if crate::x_updater_profile::find_profile_in_folder(root).is_some() {
+            UpdaterProvider::XUpdater
+        } else {
+            UpdaterProvider::None
+        };
+    }
+}
+
 /// Scans a directory or archive and detects addon types based on markers
 ///
 /// Scanner is thread-safe as it contains no mutable state.
@@ -438,33 +814,16 @@ impl Scanner {
         }
     }
 
-    /// Scan a path (file or directory) and detect all addon types
-    pub fn scan_path(&self, path: &Path, password: Option<&str>) -> Result<Vec<DetectedItem>> {
-        let original_input_path = path.to_string_lossy().to_string();
-        let mut ctx = ScanContext::new();
-        if let Some(pwd) = password {
-            ctx.passwords
-                .insert(path.to_string_lossy().to_string(), pwd.to_string());
-        }
-        let mut items = self.scan_path_with_context(path, &mut ctx)?;
-
-        // Set original_input_path for all detected items
-        for item in &mut items {
-            item.original_input_path = original_input_path.clone();
-        }
-
-        Ok(items)
-    }
-
-    /// Scan a path with a full passwords map (supports nested archive passwords)
-    /// Unlike scan_path which only accepts a single password for the outer archive,
-    /// this method injects ALL passwords into ScanContext so that nested archive
-    /// passwords (keyed as "parent_path/nested_name") can be found during scanning.
-    pub fn scan_path_with_passwords(
+    /// Scan a path (file or directory) and detect all addon types, also
+    /// returning what the scan saw. All passwords go into the ScanContext so
+    /// nested archive passwords (keyed as "parent_path/nested_name") can be found
+    /// during scanning. The diagnostics are collected along the way, so they cost
+    /// next to nothing when the scan finds addons.
+    pub fn scan_path_with_diagnostics(
         &self,
         path: &Path,
         passwords: &HashMap<String, String>,
-    ) -> Result<Vec<DetectedItem>> {
+    ) -> Result<(Vec<DetectedItem>, ScanDiagnostics)> {
         let original_input_path = path.to_string_lossy().to_string();
         let mut ctx = ScanContext::new();
         // Insert all provided passwords into context
@@ -480,7 +839,11 @@ impl Scanner {
             item.original_input_path = original_input_path.clone();
         }
 
-        Ok(items)
+        let mut diagnostics = ctx.diagnostics;
+        diagnostics.is_archive =
+            path.is_file() && path.extension().and_then(|s| s.to_str()) != Some("lua");
+        diagnostics.input_path = original_input_path;
+        Ok((items, diagnostics))
     }
 
     /// Internal method: Scan a path with context (supports nested archives)
@@ -492,7 +855,7 @@ impl Scanner {
         let mut detected_items = Vec::new();
 
         if path.is_dir() {
-            detected_items.extend(self.scan_directory(path)?);
+            detected_items.extend(self.scan_directory(path, ctx)?);
         } else if path.is_file() {
             // Check if it's a standalone .lua file
             if path.extension().and_then(|s| s.to_str()) == Some("lua") {
@@ -522,7 +885,8 @@ impl Scanner {
                 ctx.passwords
                     .get(&normalized_archive.to_string_lossy().to_string())
                     .cloned()
-            });
+            })
+            .or_else(|| ctx.current_nested_password());
 
         match detect_archive_format(archive_path)
             .or_else(|| detect_archive_format(&normalized_archive))
@@ -542,7 +906,7 @@ impl Scanner {
 
     /// Scan a directory using breadth-first (level-by-level) traversal
     /// When a marker file is found, the entire addon root directory is skipped
-    fn scan_directory(&self, dir: &Path) -> Result<Vec<DetectedItem>> {
+    fn scan_directory(&self, dir: &Path, ctx: &mut ScanContext) -> Result<Vec<DetectedItem>> {
         use std::collections::VecDeque;
 
         let mut detected = Vec::new();
@@ -558,8 +922,17 @@ impl Scanner {
 
         const MAX_DEPTH: usize = 15;
 
+        // Path below the dropped folder, as listed in diagnostics
+        let relative = |path: &Path| {
+            path.strip_prefix(dir)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+
         while let Some((current_dir, depth)) = queue.pop_front() {
             if depth > MAX_DEPTH {
+                ctx.diagnostics.folders_too_deep += 1;
                 continue;
             }
 
@@ -578,6 +951,7 @@ impl Scanner {
                 Ok(entries) => entries,
                 Err(_) => continue,
             };
+            ctx.diagnostics.max_depth_reached = ctx.diagnostics.max_depth_reached.max(depth);
 
             // Separate files and subdirectories
             let mut files: Vec<PathBuf> = Vec::new();
@@ -588,6 +962,17 @@ impl Scanner {
 
                 // Skip ignored paths
                 if Self::should_ignore_path(&path) {
+                    ctx.note_ignored(&relative(&path));
+                    continue;
+                }
+
+                // Symlinked folders aren't followed: a link back to a parent would
+                // otherwise be re-scanned until MAX_DEPTH and report duplicates
+                if crate::dir_walk::is_symlinked_dir(&entry) {
+                    logger::log_info(
+                        &format!("Skipping symlinked folder during scan: {}", path.display()),
+                        Some("scanner"),
+                    );
                     continue;
                 }
 
@@ -598,6 +983,8 @@ impl Scanner {
                 }
             }
 
+            ctx.diagnostics.entry_count += files.len();
+
             // First pass on files: identify plugin directories and aircraft directories
             for file_path in &files {
                 let file_ext = file_path.extension().and_then(|s| s.to_str());
@@ -643,11 +1030,19 @@ impl Scanner {
 
                 // Skip .acf/.dsf files inside plugin directories
                 if (file_ext == Some("acf") || file_ext == Some("dsf")) && is_inside_plugin {
+                    ctx.note_skipped_marker(
+                        &relative(file_path),
+                        SkippedMarkerReason::InsidePlugin,
+                    );
                     continue;
                 }
 
                 // Skip .xpl files inside aircraft directories (embedded plugins)
                 if file_ext == Some("xpl") && is_inside_aircraft {
+                    ctx.note_skipped_marker(
+                        &relative(file_path),
+                        SkippedMarkerReason::InsideAircraft,
+                    );
                     continue;
                 }
 
@@ -699,6 +1094,17 @@ impl Scanner {
                         continue;
                     }
                 }
+
+                let is_marker = file_path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(is_marker_path);
+                if is_marker {
+                    ctx.note_skipped_marker(
+                        &relative(file_path),
+                        SkippedMarkerReason::Unrecognized,
+                    );
+                }
             }
 
             // Add subdirectories to queue (only if not in skip_dirs)
@@ -709,6 +1115,7 @@ impl Scanner {
             }
         }
 
+        apply_folder_updater_providers(&mut detected);
         Ok(detected)
     }
 
@@ -765,6 +1172,7 @@ impl Scanner {
         // Read version info from the install folder
         let (version, _, _) = crate::management_index::read_version_info_with_url(&install_path);
         let version_info = version.map(|v| crate::models::VersionInfo { version: Some(v) });
+        let sim_version_hints = Self::read_acf_hints(file_path);
 
         Ok(Some(DetectedItem {
             original_input_path: String::new(),
@@ -775,8 +1183,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info,
             companion_paths: Vec::new(),
+            sim_version_hints,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -856,10 +1270,13 @@ impl Scanner {
             )
         };
 
-        let version_info = if include_version {
-            self.read_version_from_archive(archive_path, internal_root.as_deref())
+        let (version_info, sim_version_hints) = if include_version {
+            (
+                self.read_version_from_archive(archive_path, internal_root.as_deref()),
+                self.read_acf_hints_from_archive(archive_path, file_path),
+            )
         } else {
-            None
+            (None, None)
         };
 
         Ok(Some(DetectedItem {
@@ -871,8 +1288,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info,
             companion_paths: Vec::new(),
+            sim_version_hints,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -903,6 +1326,10 @@ impl Scanner {
             .unwrap_or("Unknown Library")
             .to_string();
 
+        let sim_version_hints = fs::read(file_path)
+            .ok()
+            .and_then(|bytes| library_hints_from_contents(&String::from_utf8_lossy(&bytes)));
+
         Ok(Some(DetectedItem {
             original_input_path: String::new(),
             addon_type: AddonType::SceneryLibrary,
@@ -912,8 +1339,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info: None,
             companion_paths: Vec::new(),
+            sim_version_hints,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -938,8 +1371,14 @@ impl Scanner {
                 extraction_chain: None,
                 navdata_info: None,
                 livery_aircraft_type: None,
+                livery_target_folder: None,
+                livery_target_candidates: Vec::new(),
                 version_info: None,
                 companion_paths: Vec::new(),
+                sim_version_hints: None,
+                compatibility: AddonCompatibility::Unknown,
+                updater_provider: UpdaterProvider::None,
+                target_root_kind: TargetRootKind::CustomScenery,
             }))
         } else {
             Ok(None)
@@ -1015,6 +1454,8 @@ impl Scanner {
             )
         };
 
+        let sim_version_hints = self.read_library_hints_from_archive(archive_path, file_path);
+
         Ok(Some(DetectedItem {
             original_input_path: String::new(),
             addon_type: AddonType::SceneryLibrary,
@@ -1024,8 +1465,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info: None,
             companion_paths: Vec::new(),
+            sim_version_hints,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -1075,8 +1522,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info: None,
             companion_paths: Vec::new(),
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -1152,8 +1605,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info,
             companion_paths: Vec::new(),
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -1237,8 +1696,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info,
             companion_paths: Vec::new(),
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -1302,8 +1767,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: Some(navdata_info),
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info: None,
             companion_paths: Vec::new(),
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -1369,8 +1840,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: Some(navdata_info),
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info: None,
             companion_paths: Vec::new(),
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -1407,8 +1884,14 @@ impl Scanner {
                 extraction_chain: None,
                 navdata_info: None,
                 livery_aircraft_type: Some(aircraft_type_id.clone()),
+                livery_target_folder: None,
+                livery_target_candidates: Vec::new(),
                 version_info: None,
                 companion_paths: Vec::new(),
+                sim_version_hints: None,
+                compatibility: AddonCompatibility::Unknown,
+                updater_provider: UpdaterProvider::None,
+                target_root_kind: TargetRootKind::CustomScenery,
             }))
         } else {
             Ok(None)
@@ -1457,14 +1940,145 @@ impl Scanner {
                 extraction_chain: None,
                 navdata_info: None,
                 livery_aircraft_type: Some(aircraft_type_id.clone()),
+                livery_target_folder: None,
+                livery_target_candidates: Vec::new(),
                 version_info: None,
                 companion_paths: Vec::new(),
+                sim_version_hints: None,
+                compatibility: AddonCompatibility::Unknown,
+                updater_provider: UpdaterProvider::None,
+                target_root_kind: TargetRootKind::CustomScenery,
             }))
         } else {
             Ok(None)
         }
     }
 
+    /// Fallback livery detection for inputs no pattern matched: look for livery
+    /// structure and attribute every livery found to the user-chosen aircraft
+    /// folder (relative to `Aircraft/`). `aircraft_dir` is that folder inside the
+    /// X-Plane installation; its textures are used to confirm `objects` folders.
+    pub fn detect_liveries_for_target(
+        &self,
+        path: &Path,
+        aircraft_dir: &Path,
+        aircraft_folder: &str,
+    ) -> Vec<DetectedItem> {
+        let Some(entries) = Self::list_relative_files(path) else {
+            return Vec::new();
+        };
+        let aircraft_textures: HashSet<String> = fs::read_dir(aircraft_dir.join("objects"))
+            .map(|dir| {
+                dir.flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().to_ascii_lowercase())
+                    .filter(|name| is_texture_file(name))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let aircraft_textures = (!aircraft_textures.is_empty()).then_some(&aircraft_textures);
+
+        let aircraft_name = aircraft_folder
+            .rsplit('/')
+            .next()
+            .unwrap_or(aircraft_folder);
+        let is_dir = path.is_dir();
+
+        find_livery_roots(&entries, aircraft_textures)
+            .into_iter()
+            .map(|root| {
+                let livery_name = if root.is_empty() {
+                    let name = if is_dir {
+                        path.file_name()
+                    } else {
+                        path.file_stem()
+                    };
+                    name.and_then(|s| s.to_str())
+                        .unwrap_or("Unknown Livery")
+                        .to_string()
+                } else {
+                    root.rsplit('/').next().unwrap_or(&root).to_string()
+                };
+                let (item_path, archive_internal_root) = if is_dir {
+                    (path.join(&root), None)
+                } else {
+                    (path.to_path_buf(), (!root.is_empty()).then_some(root))
+                };
+
+                DetectedItem {
+                    original_input_path: path.to_string_lossy().to_string(),
+                    addon_type: AddonType::Livery,
+                    path: item_path.to_string_lossy().to_string(),
+                    display_name: format!("{} ({})", livery_name, aircraft_name),
+                    archive_internal_root,
+                    extraction_chain: None,
+                    navdata_info: None,
+                    livery_aircraft_type: Some(aircraft_folder.to_string()),
+                    livery_target_folder: Some(aircraft_folder.to_string()),
+                    livery_target_candidates: Vec::new(),
+                    version_info: None,
+                    companion_paths: Vec::new(),
+                    sim_version_hints: None,
+                    compatibility: AddonCompatibility::Unknown,
+                    updater_provider: UpdaterProvider::None,
+                    target_root_kind: TargetRootKind::CustomScenery,
+                }
+            })
+            .collect()
+    }
+
+    /// Relative `/`-separated paths of all files in a folder or archive
+    fn list_relative_files(path: &Path) -> Option<Vec<String>> {
+        if path.is_dir() {
+            return Some(
+                walkdir::WalkDir::new(path)
+                    .max_depth(LIVERY_SCAN_DEPTH)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.file_type().is_file())
+                    .filter_map(|e| {
+                        let relative = e.path().strip_prefix(path).ok()?;
+                        Some(relative.to_string_lossy().replace('\\', "/"))
+                    })
+                    .collect(),
+            );
+        }
+
+        let format = detect_archive_format(path)?;
+        let files = match format {
+            ArchiveFormat::Zip => {
+                let prepared = prepare_archive_for_read(path, format).ok()?;
+                let archive =
+                    ::zip::ZipArchive::new(fs::File::open(prepared.read_path()).ok()?).ok()?;
+                archive
+                    .file_names()
+                    .filter(|name| !name.ends_with('/'))
+                    .map(|name| name.replace('\\', "/"))
+                    .collect()
+            }
+            ArchiveFormat::SevenZ => {
+                let prepared = prepare_archive_for_read(path, format).ok()?;
+                let archive = sevenz_rust2::Archive::open(prepared.read_path()).ok()?;
+                archive
+                    .files
+                    .iter()
+                    .filter(|entry| !entry.is_directory())
+                    .map(|entry| entry.name().replace('\\', "/"))
+                    .collect()
+            }
+            ArchiveFormat::Rar => {
+                let normalized = crate::archive_input::normalize_archive_entry_path(path);
+                unrar::Archive::new(&normalized)
+                    .open_for_listing()
+                    .ok()?
+                    .flatten()
+                    .filter(|entry| !entry.is_directory())
+                    .map(|entry| entry.filename.to_string_lossy().replace('\\', "/"))
+                    .collect()
+            }
+        };
+        Some(files)
+    }
+
     /// Parse Lua script content to find companion files/folders referenced by SCRIPT_DIRECTORY
     fn parse_lua_companions(lua_content: &str) -> Vec<String> {
         let re = Regex::new(r#"SCRIPT_DIRECTORY\s*\.\.\s*["']([^"']+)["']"#).unwrap();
@@ -1528,21 +2142,16 @@ impl Scanner {
             .unwrap_or("Unknown Script")
             .to_string();
 
-        // Read the lua file content to parse companions
-        let mut companion_paths = Vec::new();
-        if let Ok(lua_content) = fs::read_to_string(file_path) {
-            let companion_names = Self::parse_lua_companions(&lua_content);
-
-            // Check if companions exist in the same directory as the .lua file
-            if let Some(parent_dir) = file_path.parent() {
-                for companion_name in companion_names {
-                    let companion_path = parent_dir.join(&companion_name);
-                    if companion_path.exists() {
-                        companion_paths.push(companion_name);
-                    }
-                }
+        // Companions must exist in the same directory as the .lua file
+        let lua_content = fs::read_to_string(file_path).ok();
+        let companion_paths = match file_path.parent() {
+            Some(parent_dir) => {
+                collect_lua_companions(&display_name, lua_content.as_deref(), |relative| {
+                    parent_dir.join(relative).exists()
+                })
             }
-        }
+            None => Vec::new(),
+        };
 
         Ok(Some(DetectedItem {
             original_input_path: String::new(),
@@ -1553,8 +2162,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info: None,
             companion_paths,
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -1580,58 +2195,52 @@ impl Scanner {
             .filter(|p| !p.as_os_str().is_empty())
             .map(|p| p.to_string_lossy().to_string());
 
-        // Parse companions from archive
-        let mut companion_paths = Vec::new();
-        let mut companion_names = if let Some(content) = lua_content {
-            Self::parse_lua_companions(content)
-        } else if let Ok(content) = self.read_lua_from_archive(archive_path, file_path) {
-            Self::parse_lua_companions(&content)
-        } else {
-            Vec::new()
+        // Companions are detected from the archive listing; nothing is extracted
+        let read_content;
+        let content = match lua_content {
+            Some(content) => Some(content),
+            None => {
+                read_content = self.read_lua_from_archive(archive_path, file_path).ok();
+                read_content.as_deref()
+            }
         };
 
-        let use_layout_inference =
-            companion_names.is_empty() && lua_content.map(|s| s.is_empty()).unwrap_or(false);
+        let owned_entries;
+        let entries: &[String] = if let Some(entries) = archive_entries {
+            entries
+        } else if let Ok(listed) = self.list_archive_entries(archive_path) {
+            owned_entries = listed;
+            &owned_entries
+        } else {
+            &[]
+        };
 
-        if !companion_names.is_empty() || use_layout_inference {
-            let owned_entries;
-            let entries: &[String] = if let Some(entries) = archive_entries {
-                entries
-            } else if let Ok(listed) = self.list_archive_entries(archive_path) {
-                owned_entries = listed;
-                &owned_entries
+        let lua_parent = internal_root.as_deref().unwrap_or("").replace('\\', "/");
+        // Companion exists if the exact file exists, or there are nested files under that folder.
+        let exists_in_archive = |relative: &str| {
+            let companion_prefix = if lua_parent.is_empty() {
+                relative.to_string()
             } else {
-                &[]
+                format!("{}/{}", lua_parent, relative)
             };
+            let slash_prefix = format!("{}/", companion_prefix);
+            entries.iter().any(|entry| {
+                let entry = entry.replace('\\', "/");
+                entry == companion_prefix || entry.starts_with(&slash_prefix)
+            })
+        };
 
-            // Solid 7z fast-scan fallback:
-            // if lua content was intentionally skipped (empty placeholder),
-            // infer companion candidates from sibling archive entries.
-            if use_layout_inference {
-                companion_names =
-                    Self::infer_lua_companions_from_archive_entries(file_path, entries);
-            }
+        let mut companion_paths =
+            collect_lua_companions(&display_name, content, &exists_in_archive);
 
-            let lua_parent = internal_root.as_deref().unwrap_or("");
-            for companion_name in companion_names {
-                // Build the expected companion path in archive
-                let companion_prefix = if lua_parent.is_empty() {
-                    companion_name.clone()
-                } else {
-                    format!("{}/{}", lua_parent, companion_name)
-                };
-                let slash_prefix = format!("{}/", companion_prefix);
-                let backslash_prefix = format!("{}\\", companion_prefix);
-
-                // Companion exists if the exact file exists, or there are nested files under that folder.
-                if entries.iter().any(|entry| {
-                    entry == &companion_prefix
-                        || entry.starts_with(&slash_prefix)
-                        || entry.starts_with(&backslash_prefix)
-                }) {
-                    companion_paths.push(companion_name);
-                }
-            }
+        // Solid 7z fast-scan fallback:
+        // if lua content was intentionally skipped (empty placeholder),
+        // infer companion candidates from sibling archive entries.
+        if companion_paths.is_empty() && content.is_some_and(str::is_empty) {
+            companion_paths = Self::infer_lua_companions_from_archive_entries(file_path, entries)
+                .into_iter()
+                .filter(|name| exists_in_archive(name))
+                .collect();
         }
 
         Ok(Some(DetectedItem {
@@ -1643,8 +2252,14 @@ impl Scanner {
             extraction_chain: None,
             navdata_info: None,
             livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
             version_info: None,
             companion_paths,
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::Unknown,
+            updater_provider: UpdaterProvider::None,
+            target_root_kind: TargetRootKind::CustomScenery,
         }))
     }
 
@@ -2163,6 +2778,54 @@ impl Scanner {
         }
     }
 
+    /// Read the format version from the header of an .acf file
+    fn read_acf_hints(acf_path: &Path) -> Option<SimVersionHints> {
+        use std::io::Read;
+
+        let mut header = Vec::new();
+        fs::File::open(acf_path)
+            .ok()?
+            .take(ACF_HEADER_BYTES)
+            .read_to_end(&mut header)
+            .ok()?;
+        acf_hints_from_header(&header)
+    }
+
+    /// Read the .acf header from a ZIP archive. Solid 7z and RAR archives would
+    /// need to decompress everything before the file, so their aircraft stay unjudged.
+    fn read_acf_hints_from_archive(
+        &self,
+        archive_path: &Path,
+        acf_path: &str,
+    ) -> Option<SimVersionHints> {
+        use std::io::Read;
+
+        if detect_archive_format(archive_path) != Some(ArchiveFormat::Zip) {
+            return None;
+        }
+        let prepared = prepare_archive_for_read(archive_path, ArchiveFormat::Zip).ok()?;
+        let file = fs::File::open(prepared.read_path()).ok()?;
+        let mut archive = ::zip::ZipArchive::new(file).ok()?;
+        let entry = archive.by_name(acf_path).ok()?;
+
+        let mut header = Vec::new();
+        entry.take(ACF_HEADER_BYTES).read_to_end(&mut header).ok()?;
+        acf_hints_from_header(&header)
+    }
+
+    /// Check the EXPORT directives of a library.txt inside a ZIP archive
+    fn read_library_hints_from_archive(
+        &self,
+        archive_path: &Path,
+        library_path: &str,
+    ) -> Option<SimVersionHints> {
+        if detect_archive_format(archive_path) != Some(ArchiveFormat::Zip) {
+            return None;
+        }
+        let content = self.read_file_from_zip(archive_path, library_path).ok()?;
+        library_hints_from_contents(&content)
+    }
+
     /// Read a file from a ZIP archive
     fn read_file_from_zip(&self, archive_path: &Path, file_path: &str) -> Result<String> {
         use std::io::Read;
@@ -2489,9 +3152,60 @@ impl Scanner {
 #[cfg(test)]
 mod tests {
     use super::{
-        infer_version_from_name, plan_version_file_reads, resolve_nested_display_name,
-        VersionFileReadPlan,
+        collect_lua_companions, infer_version_from_name, parse_acf_format_version,
+        parse_legacy_library_exports, parse_lua_requires, plan_version_file_reads,
+        resolve_nested_display_name, ScanContext, VersionFileReadPlan,
     };
+    use crate::models::NestedArchiveInfo;
+
+    #[test]
+    fn lua_requires_skip_comments_and_accept_call_forms() {
+        let script = "local lip = require(\"LIP\")\n\
+            local ui = require 'sgs.ui'\n\
+            -- local old = require(\"old_module\")\n\
+            local again = require(\"LIP\")\n";
+
+        assert_eq!(
+            parse_lua_requires(script),
+            vec!["LIP".to_string(), "sgs.ui".to_string()]
+        );
+    }
+
+    #[test]
+    fn lua_companions_include_data_folder_modules_and_required_files() {
+        let present = [
+            "SGS.lua",
+            "SGS/sounds/door.wav",
+            "Modules/LIP.lua",
+            "sgs/ui.lua",
+            "helpers.lua",
+            "unrelated/readme.txt",
+        ];
+        let exists = |relative: &str| {
+            present
+                .iter()
+                .any(|p| *p == relative || p.starts_with(&format!("{}/", relative)))
+        };
+        let script = "dofile(SCRIPT_DIRECTORY .. \"SGS/config.lua\")\n\
+            local lip = require(\"LIP\")\n\
+            local ui = require(\"sgs.ui\")\n\
+            local h = require(\"helpers\")\n\
+            local missing = require(\"not_shipped\")\n";
+
+        assert_eq!(
+            collect_lua_companions("SGS.lua", Some(script), exists),
+            vec![
+                "SGS".to_string(),
+                "Modules".to_string(),
+                "sgs".to_string(),
+                "helpers.lua".to_string(),
+            ]
+        );
+        assert_eq!(
+            collect_lua_companions("Other.lua", None, exists),
+            vec!["Modules".to_string()]
+        );
+    }
 
     #[test]
     fn nested_archive_display_name_preserves_detected_name() {
@@ -2523,6 +3237,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn acf_format_version_is_read_from_header() {
+        assert_eq!(
+            parse_acf_format_version("I\n1100 Version\nACF\n\nP acf/_name A320"),
+            Some(1100)
+        );
+        assert_eq!(
+            parse_acf_format_version("A\r\n1200 version\r\nACF"),
+            Some(1200)
+        );
+        assert_eq!(parse_acf_format_version("I\nACF\n"), None);
+    }
+
+    #[test]
+    fn legacy_library_exports_only_match_xp11_prefixes() {
+        let library = "A\n800\nLIBRARY\n\n\
+            EXPORT lib/g10/terrain10/grass.ter terrain/grass.ter\n\
+            EXPORT_RATIO 0.5 lib/g8/roads.net roads/roads.net\n\
+            EXPORT lib/airport/vehicles/car.obj objects/car.obj\n\
+            EXPORT_EXCLUDE LIB/G10/TERRAIN10/grass.ter terrain/grass.ter\n";
+
+        assert_eq!(
+            parse_legacy_library_exports(library),
+            vec![
+                "lib/g10/terrain10/grass.ter".to_string(),
+                "lib/g8/roads.net".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn version_read_plan_keeps_only_shallowest_matching_version_files() {
         let plan = plan_version_file_reads(
@@ -2570,6 +3314,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn updater_provider_is_read_from_markers_under_the_internal_root() {
+        let markers: Vec<String> = [
+            "A320/x-updater/x-updater.cnf",
+            "B737/skunkcrafts_updater.cfg",
+            "B737/x-updater.cnf",
+            "C172/plugins/skunkcrafts_updater.cfg",
+        ]
+        .iter()
+        .map(|path| path.to_string())
+        .collect();
+        assert!(markers.iter().all(|path| is_updater_marker_path(path)));
+        assert!(!is_updater_marker_path("A320/readme.txt"));
+
+        assert_eq!(
+            updater_provider_from_markers(&markers, Some("A320")),
+            UpdaterProvider::XUpdater
+        );
+        assert_eq!(
+            updater_provider_from_markers(&markers, Some("B737/")),
+            UpdaterProvider::Skunkcrafts
+        );
+        // Only the addon root counts, not embedded plugins
+        assert_eq!(
+            updater_provider_from_markers(&markers, Some("C172")),
+            UpdaterProvider::None
+        );
+        assert_eq!(
+            updater_provider_from_markers(&markers, None),
+            UpdaterProvider::None
+        );
+    }
+
     #[test]
     fn infer_version_from_name_supports_v_dot_stage_patterns() {
         assert_eq!(
@@ -2582,4 +3359,131 @@ mod tests {
     fn infer_version_from_name_ignores_plain_model_numbers() {
         assert_eq!(infer_version_from_name("737NG Series"), None);
     }
+
+    #[test]
+    fn scan_context_tracks_encryption_of_current_nested_archive() {
+        let mut ctx = ScanContext::new();
+        ctx.mark_current_archive_encrypted();
+        assert_eq!(ctx.current_nested_password(), None);
+
+        ctx.push_archive(NestedArchiveInfo {
+            internal_path: "inner/locked.7z".to_string(),
+            password: Some("secret".to_string()),
+            format: "7z".to_string(),
+            password_required: false,
+        });
+        assert_eq!(ctx.current_nested_password().as_deref(), Some("secret"));
+
+        ctx.mark_current_archive_encrypted();
+        let popped = ctx
+            .pop_archive()
+            .expect("nested archive should be on the chain");
+        assert!(popped.password_required);
+        assert!(!ctx.is_nested_archive_scan());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn directory_scan_does_not_follow_symlink_loops() {
+        use super::Scanner;
+        use crate::models::AddonType;
+        use std::fs;
+        use std::os::unix::fs::symlink;
+        use std::time::{Duration, Instant};
+
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        let plugin_bin = root.join("Pack").join("MyPlugin").join("64");
+        fs::create_dir_all(&plugin_bin).expect("failed to create plugin dir");
+        fs::write(plugin_bin.join("win.xpl"), "xpl").expect("failed to write xpl");
+        symlink(root, root.join("Pack").join("back_to_root")).expect("failed to create loop");
+
+        let started = Instant::now();
+        let items = Scanner::new()
+            .scan_directory(root, &mut ScanContext::new())
+            .expect("scan should succeed");
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].addon_type, AddonType::Plugin);
+    }
+
+    #[test]
+    fn archive_without_addons_reports_diagnostics() {
+        use super::Scanner;
+        use crate::models::SkippedMarkerReason;
+        use ::zip::write::SimpleFileOptions;
+        use std::collections::HashMap;
+        use std::fs;
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        let archive = temp.path().join("pack.zip");
+        let mut writer = ::zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        for name in [
+            "readme.txt",
+            "Docs/manual.pdf",
+            "Docs/tile.dsf",
+            "__MACOSX/Docs/._plane.acf",
+        ] {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(b"data").unwrap();
+        }
+        writer.finish().unwrap();
+
+        let (items, diagnostics) = Scanner::new()
+            .scan_path_with_diagnostics(&archive, &HashMap::new())
+            .expect("scan should succeed");
+        assert!(items.is_empty());
+        assert!(diagnostics.is_archive);
+        assert!(!diagnostics.encrypted);
+        assert_eq!(diagnostics.entry_count, 4);
+        assert_eq!(
+            diagnostics.top_level_entries,
+            vec!["readme.txt", "Docs", "__MACOSX"]
+        );
+        assert_eq!(diagnostics.ignored_path_count, 1);
+        assert_eq!(diagnostics.skipped_marker_count, 2);
+        let skipped: Vec<(&str, SkippedMarkerReason)> = diagnostics
+            .skipped_markers
+            .iter()
+            .map(|marker| (marker.path.as_str(), marker.reason))
+            .collect();
+        assert!(skipped.contains(&("__MACOSX/Docs/._plane.acf", SkippedMarkerReason::Ignored)));
+        assert!(skipped.contains(&("Docs/tile.dsf", SkippedMarkerReason::Unrecognized)));
+    }
+
+    #[test]
+    fn livery_roots_are_found_by_structure() {
+        let entries: Vec<String> = [
+            "Pack/Blue/objects/fuselage.png",
+            "Pack/Blue/objects/Wings.dds",
+            "Pack/Blue/objects/extra/decal.png",
+            "Pack/Red/icon11.png",
+            "Pack/Red/A320_paint.png",
+            "Pack/readme.txt",
+            "Pack/Green/icon11.png",
+            "__MACOSX/Pack/Teal/objects/fuselage.png",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        assert_eq!(
+            find_livery_roots(&entries, None),
+            vec!["Pack/Blue".to_string(), "Pack/Red".to_string()]
+        );
+
+        // Known aircraft textures must match
+        let textures = HashSet::from(["cabin.png".to_string()]);
+        assert_eq!(
+            find_livery_roots(&entries, Some(&textures)),
+            vec!["Pack/Red".to_string()]
+        );
+
+        let single = vec!["objects/fuselage.png".to_string()];
+        assert_eq!(find_livery_roots(&single, None), vec![String::new()]);
+    }
 }
@@ -19,8 +19,9 @@ impl Scanner {
 
         // First, scan the archive normally for direct addon markers
         let scan_markers_start = std::time::Instant::now();
+        let read_versions = !ctx.is_nested_archive_scan();
         let mut detected =
-            self.scan_rar_internal(archive_path, password, !ctx.is_nested_archive_scan())?;
+            self.scan_rar_internal(archive_path, password, read_versions, Some(ctx))?;
         crate::log_debug!(
             &format!(
                 "[TIMING] RAR marker scan completed in {:.2}ms: {} addons detected",
@@ -126,6 +127,9 @@ impl Scanner {
                             detected.extend(nested_items);
                         }
                         Err(e) => {
+                            if e.downcast_ref::<NestedPasswordRequiredError>().is_some() {
+                                return Err(e);
+                            }
                             if e.downcast_ref::<PasswordRequiredError>().is_some() {
                                 return Err(anyhow::anyhow!(NestedPasswordRequiredError {
                                     parent_archive: archive_path.to_string_lossy().to_string(),
@@ -246,6 +250,7 @@ impl Scanner {
             internal_path: nested_path.to_string(),
             password: nested_password.clone(),
             format: format.clone(),
+            password_required: false,
         };
 
         // Push to context chain
@@ -275,7 +280,7 @@ impl Scanner {
         };
 
         // Pop from context chain
-        ctx.pop_archive();
+        let nested_info = ctx.pop_archive().unwrap_or(nested_info);
 
         // Process results
         match nested_result {
@@ -320,7 +325,7 @@ impl Scanner {
         archive_path: &Path,
         password: Option<&str>,
     ) -> Result<Vec<DetectedItem>> {
-        self.scan_rar_internal(archive_path, password, true)
+        self.scan_rar_internal(archive_path, password, true, None)
     }
 
     fn scan_rar_internal(
@@ -328,6 +333,7 @@ impl Scanner {
         archive_path: &Path,
         password: Option<&str>,
         read_archive_versions: bool,
+        mut ctx: Option<&mut ScanContext>,
     ) -> Result<Vec<DetectedItem>> {
         let normalized_archive_path =
             crate::archive_input::normalize_archive_entry_path(archive_path);
@@ -374,13 +380,29 @@ impl Scanner {
         );
 
         let mut files: Vec<String> = Vec::new();
+        let mut has_encrypted = false;
 
         // Collect all file paths
         let enumerate_start = std::time::Instant::now();
         for e in archive.flatten() {
+            if e.is_encrypted() {
+                has_encrypted = true;
+            }
             files.push(e.filename.to_string_lossy().to_string().replace('\\', "/"));
         }
 
+        // Headers may be readable even when file data is encrypted
+        if has_encrypted {
+            if password.is_none() {
+                return Err(anyhow::anyhow!(PasswordRequiredError {
+                    archive_path: archive_path.to_string_lossy().to_string(),
+                }));
+            }
+            if let Some(ctx) = ctx.as_deref_mut() {
+                ctx.mark_current_archive_encrypted();
+            }
+        }
+
         crate::log_debug!(
             &format!(
                 "[TIMING] RAR enumeration completed in {:.2}ms: {} files",
@@ -405,13 +427,28 @@ impl Scanner {
         let mut aircraft_dirs: HashSet<String> = HashSet::new();
         let mut marker_files: Vec<(String, &str)> = Vec::new(); // (path, marker_type)
         let mut detected_livery_roots: HashSet<String> = HashSet::new();
+        let mut updater_markers: Vec<String> = Vec::new();
 
         for file_path in &files {
+            if let Some(ctx) = ctx.as_deref_mut() {
+                ctx.note_archive_entry(file_path);
+                if !ctx.can_recurse() && is_archive_file(file_path) {
+                    ctx.diagnostics.nested_archives_too_deep += 1;
+                }
+            }
+
             // Skip ignored paths
             if Self::should_ignore_archive_path(file_path) {
+                if let Some(ctx) = ctx.as_deref_mut() {
+                    ctx.note_ignored(file_path);
+                }
                 continue;
             }
 
+            if is_updater_marker_path(file_path) {
+                updater_markers.push(file_path.clone());
+            }
+
             // Identify plugin directories, aircraft directories, and marker files
             if file_path.ends_with(".xpl") {
                 if let Some(parent) = Path::new(file_path).parent() {
@@ -517,6 +554,9 @@ impl Scanner {
             if (marker_type == "acf" || marker_type == "dsf")
                 && Self::is_archive_path_inside_plugin_dirs(&file_path, &plugin_dirs)
             {
+                if let Some(ctx) = ctx.as_deref_mut() {
+                    ctx.note_skipped_marker(&file_path, SkippedMarkerReason::InsidePlugin);
+                }
                 continue;
             }
 
@@ -524,6 +564,9 @@ impl Scanner {
             if marker_type == "xpl"
                 && Self::is_archive_path_inside_aircraft_dirs(&file_path, &aircraft_dirs)
             {
+                if let Some(ctx) = ctx.as_deref_mut() {
+                    ctx.note_skipped_marker(&file_path, SkippedMarkerReason::InsideAircraft);
+                }
                 continue;
             }
 
@@ -572,6 +615,8 @@ impl Scanner {
                     skip_prefixes.push(String::new());
                 }
                 detected.push(item);
+            } else if let Some(ctx) = ctx.as_deref_mut() {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::Unrecognized);
             }
         }
 
@@ -584,6 +629,7 @@ impl Scanner {
             "scanner_timing"
         );
 
+        apply_archive_updater_providers(&mut detected, &updater_markers);
         Ok(detected)
     }
 
@@ -598,9 +644,7 @@ impl Scanner {
             crate::archive_input::normalize_archive_entry_path(archive_path);
 
         // Create secure temp directory using tempfile crate
-        let temp_dir = tempfile::Builder::new()
-            .prefix("xfi_rar_read_")
-            .tempdir()
+        let temp_dir = crate::staging::temp_dir("rar_read")
             .context("Failed to create secure temp directory")?;
 
         // Extract to temp using the typestate pattern (with password if provided)
@@ -30,10 +30,13 @@ impl Scanner {
         }
 
         // Fast path: encryption detection from metadata only.
-        if password.is_none() && Self::archive_has_encrypted_blocks(&archive) {
-            return Err(anyhow::anyhow!(PasswordRequiredError {
-                archive_path: archive_path.to_string_lossy().to_string(),
-            }));
+        if Self::archive_has_encrypted_blocks(&archive) {
+            if password.is_none() {
+                return Err(anyhow::anyhow!(PasswordRequiredError {
+                    archive_path: archive_path.to_string_lossy().to_string(),
+                }));
+            }
+            ctx.mark_current_archive_encrypted();
         }
 
         let enumerate_start = std::time::Instant::now();
@@ -50,22 +53,33 @@ impl Scanner {
         let mut marker_files: Vec<(String, &str)> = Vec::new();
         let mut nested_archives: Vec<String> = Vec::new();
         let mut detected_livery_roots: HashSet<String> = HashSet::new();
+        let mut updater_markers: Vec<String> = Vec::new();
         let mut archive_entries: Vec<String> = Vec::new();
 
         for entry in &archive.files {
             let file_path = entry.name().to_string();
             let normalized = file_path.replace('\\', "/");
+            ctx.note_archive_entry(&normalized);
 
             if !entry.is_directory() && entry.has_stream() {
                 archive_entries.push(normalized.clone());
             }
 
             if Self::should_ignore_archive_path(&normalized) {
+                ctx.note_ignored(&normalized);
                 continue;
             }
 
-            if ctx.can_recurse() && !entry.is_directory() && is_archive_file(&normalized) {
-                nested_archives.push(normalized.clone());
+            if is_updater_marker_path(&normalized) {
+                updater_markers.push(normalized.clone());
+            }
+
+            if !entry.is_directory() && is_archive_file(&normalized) {
+                if ctx.can_recurse() {
+                    nested_archives.push(normalized.clone());
+                } else {
+                    ctx.diagnostics.nested_archives_too_deep += 1;
+                }
             }
 
             if let Some((_, livery_root)) = livery_patterns::check_livery_pattern(&normalized) {
@@ -166,12 +180,14 @@ impl Scanner {
             if (marker_type == "acf" || marker_type == "dsf")
                 && Self::is_archive_path_inside_plugin_dirs(&file_path, &plugin_dirs)
             {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::InsidePlugin);
                 continue;
             }
 
             if marker_type == "xpl"
                 && Self::is_archive_path_inside_aircraft_dirs(&file_path, &aircraft_dirs)
             {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::InsideAircraft);
                 continue;
             }
 
@@ -241,6 +257,8 @@ impl Scanner {
                     skip_prefixes.push(String::new());
                 }
                 detected.push(item);
+            } else {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::Unrecognized);
             }
         }
 
@@ -342,6 +360,9 @@ impl Scanner {
                         detected.extend(nested_items);
                     }
                     Err(e) => {
+                        if e.downcast_ref::<NestedPasswordRequiredError>().is_some() {
+                            return Err(e);
+                        }
                         if e.downcast_ref::<PasswordRequiredError>().is_some() {
                             return Err(anyhow::anyhow!(NestedPasswordRequiredError {
                                 parent_archive: archive_path.to_string_lossy().to_string(),
@@ -374,6 +395,7 @@ impl Scanner {
             "scanner_timing"
         );
 
+        apply_archive_updater_providers(&mut detected, &updater_markers);
         Ok(detected)
     }
 
@@ -541,6 +563,7 @@ impl Scanner {
             internal_path: nested_path.to_string(),
             password: nested_password.clone(),
             format: format.clone(),
+            password_required: false,
         };
 
         // Push to context chain
@@ -570,7 +593,7 @@ impl Scanner {
         };
 
         // Pop from context chain
-        ctx.pop_archive();
+        let nested_info = ctx.pop_archive().unwrap_or(nested_info);
 
         // Process results
         match nested_result {
@@ -696,6 +719,7 @@ impl Scanner {
         let mut aircraft_dirs: HashSet<String> = HashSet::new();
         let mut marker_files: Vec<(String, &str)> = Vec::new(); // (path, marker_type)
         let mut detected_livery_roots: HashSet<String> = HashSet::new();
+        let mut updater_markers: Vec<String> = Vec::new();
 
         for entry in &archive.files {
             let file_path = entry.name().to_string();
@@ -706,6 +730,10 @@ impl Scanner {
                 continue;
             }
 
+            if is_updater_marker_path(&normalized) {
+                updater_markers.push(normalized.clone());
+            }
+
             // Check for livery patterns first (before any potential moves)
             if let Some((_, livery_root)) = livery_patterns::check_livery_pattern(&normalized) {
                 if !detected_livery_roots.contains(&livery_root) {
@@ -842,6 +870,7 @@ impl Scanner {
             }
         }
 
+        apply_archive_updater_providers(&mut detected, &updater_markers);
         Ok(detected)
     }
 
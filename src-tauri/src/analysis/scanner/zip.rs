@@ -158,6 +158,7 @@ impl Scanner {
         let mut nested_archives: Vec<(usize, String, bool)> = Vec::new(); // (index, path, encrypted)
         let mut has_encrypted = false;
         let mut detected_livery_roots: HashSet<String> = HashSet::new();
+        let mut updater_markers: Vec<String> = Vec::new();
 
         for i in 0..archive.len() {
             let file: ::zip::read::ZipFile<'_> = match archive.by_index_raw(i) {
@@ -190,12 +191,18 @@ impl Scanner {
             }
 
             let file_path = file.name().replace('\\', "/");
+            ctx.note_archive_entry(&file_path);
 
             // Skip ignored paths
             if Self::should_ignore_archive_path(&file_path) {
+                ctx.note_ignored(&file_path);
                 continue;
             }
 
+            if is_updater_marker_path(&file_path) {
+                updater_markers.push(file_path.clone());
+            }
+
             // Check if this is a nested archive (for recursive scanning)
             if !file.is_dir() && is_archive_file(&file_path) {
                 nested_archives.push((i, file_path.clone(), is_encrypted));
@@ -267,6 +274,10 @@ impl Scanner {
             }));
         }
 
+        if has_encrypted {
+            ctx.mark_current_archive_encrypted();
+        }
+
         // If password was provided and archive has encrypted files, verify password by trying to read first encrypted file
         if has_encrypted {
             if let Some(pwd) = password_str {
@@ -360,6 +371,7 @@ impl Scanner {
             if (marker_type == "acf" || marker_type == "dsf")
                 && Self::is_archive_path_inside_plugin_dirs(&file_path, &plugin_dirs)
             {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::InsidePlugin);
                 continue;
             }
 
@@ -367,6 +379,7 @@ impl Scanner {
             if marker_type == "xpl"
                 && Self::is_archive_path_inside_aircraft_dirs(&file_path, &aircraft_dirs)
             {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::InsideAircraft);
                 continue;
             }
 
@@ -434,6 +447,8 @@ impl Scanner {
                     skip_prefixes.push(String::new());
                 }
                 detected.push(item);
+            } else {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::Unrecognized);
             }
         }
 
@@ -446,6 +461,10 @@ impl Scanner {
             "scanner_timing"
         );
 
+        if !ctx.can_recurse() {
+            ctx.diagnostics.nested_archives_too_deep += nested_archives.len();
+        }
+
         // Recursively scan nested archives if depth allows
         if ctx.can_recurse() && !nested_archives.is_empty() {
             let nested_start = std::time::Instant::now();
@@ -494,6 +513,10 @@ impl Scanner {
                         detected.extend(nested_items);
                     }
                     Err(e) => {
+                        // Deeper levels already identified the encrypted archive
+                        if e.downcast_ref::<NestedPasswordRequiredError>().is_some() {
+                            return Err(e);
+                        }
                         // Check if it's a password error for nested archive
                         if let Some(_pwd_err) = e.downcast_ref::<PasswordRequiredError>() {
                             // Convert to nested password error
@@ -529,6 +552,7 @@ impl Scanner {
             "scanner_timing"
         );
 
+        apply_archive_updater_providers(&mut detected, &updater_markers);
         Ok(detected)
     }
 
@@ -581,6 +605,7 @@ impl Scanner {
                 internal_path: nested_path.to_string(),
                 password: nested_password.clone(),
                 format,
+                password_required: false,
             };
 
             // Push to context chain
@@ -596,7 +621,7 @@ impl Scanner {
                 Err(e) => Err(anyhow::anyhow!("Failed to open nested ZIP: {}", e)),
             };
 
-            ctx.pop_archive();
+            let nested_info = ctx.pop_archive().unwrap_or(nested_info);
 
             (nested_info, nested_result)
         } else if format == "7z" && !is_encrypted {
@@ -617,6 +642,7 @@ impl Scanner {
                     internal_path: nested_path.to_string(),
                     password: nested_password,
                     format,
+                    password_required: false,
                 };
 
                 ctx.push_archive(nested_info.clone());
@@ -634,8 +660,25 @@ impl Scanner {
                         Some(pwd) => sevenz_rust2::Password::from(pwd),
                         None => sevenz_rust2::Password::empty(),
                     };
-                    let reader = sevenz_rust2::ArchiveReader::new(buffered, password)
-                        .map_err(|e| anyhow::anyhow!("Failed to open nested 7z from ZIP: {}", e))?;
+                    let reader =
+                        sevenz_rust2::ArchiveReader::new(buffered, password).map_err(|e| {
+                            let err_str = format!("{:?}", e);
+                            let is_password_error = err_str.contains("password")
+                                || err_str.contains("Password")
+                                || err_str.contains("encrypted");
+                            if is_password_error && nested_info.password.is_none() {
+                                // Encrypted headers: the nested archive needs its own password
+                                anyhow::anyhow!(PasswordRequiredError {
+                                    archive_path: format!(
+                                        "{}/{}",
+                                        parent_path.display(),
+                                        nested_path
+                                    ),
+                                })
+                            } else {
+                                anyhow::anyhow!("Failed to open nested 7z from ZIP: {}", e)
+                            }
+                        })?;
                     let archive = reader.archive().clone();
                     self.scan_7z_archive_with_reader(
                         parent_path,
@@ -647,7 +690,7 @@ impl Scanner {
                     )
                 })();
 
-                ctx.pop_archive();
+                let nested_info = ctx.pop_archive().unwrap_or(nested_info);
 
                 (nested_info, scan_result)
             } else {
@@ -658,6 +701,7 @@ impl Scanner {
                     internal_path: nested_path.to_string(),
                     password: nested_password,
                     format,
+                    password_required: false,
                 };
 
                 ctx.push_archive(nested_info.clone());
@@ -671,7 +715,7 @@ impl Scanner {
                 let nested_result =
                     self.scan_nested_non_zip_from_reader(nested_file, &nested_info.format, ctx);
 
-                ctx.pop_archive();
+                let nested_info = ctx.pop_archive().unwrap_or(nested_info);
 
                 (nested_info, nested_result)
             }
@@ -684,6 +728,7 @@ impl Scanner {
                 internal_path: nested_path.to_string(),
                 password: nested_password,
                 format,
+                password_required: false,
             };
 
             ctx.push_archive(nested_info.clone());
@@ -712,7 +757,7 @@ impl Scanner {
                 self.scan_nested_non_zip_from_reader(nested_file, &nested_info.format, ctx)
             };
 
-            ctx.pop_archive();
+            let nested_info = ctx.pop_archive().unwrap_or(nested_info);
 
             (nested_info, nested_result)
         };
@@ -775,6 +820,7 @@ impl Scanner {
         let mut aircraft_dirs: HashSet<String> = HashSet::new();
         let mut marker_files: Vec<(usize, String, &str)> = Vec::new(); // (index, path, marker_type)
         let mut detected_livery_roots: HashSet<String> = HashSet::new();
+        let mut updater_markers: Vec<String> = Vec::new();
         let mut has_encrypted = false;
 
         for i in 0..archive.len() {
@@ -787,9 +833,19 @@ impl Scanner {
 
             // Skip ignored paths
             if Self::should_ignore_archive_path(&file_path) {
+                ctx.note_ignored(&file_path);
                 continue;
             }
 
+            // Archives inside an in-memory archive aren't opened
+            if !file.is_dir() && is_archive_file(&file_path) {
+                ctx.diagnostics.nested_archives_too_deep += 1;
+            }
+
+            if is_updater_marker_path(&file_path) {
+                updater_markers.push(file_path.clone());
+            }
+
             // Check for livery patterns
             if let Some((_, livery_root)) = livery_patterns::check_livery_pattern(&file_path) {
                 if !detected_livery_roots.contains(&livery_root) {
@@ -850,6 +906,7 @@ impl Scanner {
                     archive_path: format!("{}/{}", parent_str, nested_path),
                 }));
             }
+            ctx.mark_current_archive_encrypted();
         }
 
         // Sort marker files by depth, then by type priority (aircraft first)
@@ -883,6 +940,7 @@ impl Scanner {
             if (marker_type == "acf" || marker_type == "dsf")
                 && Self::is_archive_path_inside_plugin_dirs(&file_path, &plugin_dirs)
             {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::InsidePlugin);
                 continue;
             }
 
@@ -890,6 +948,7 @@ impl Scanner {
             if marker_type == "xpl"
                 && Self::is_archive_path_inside_aircraft_dirs(&file_path, &aircraft_dirs)
             {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::InsideAircraft);
                 continue;
             }
 
@@ -943,9 +1002,12 @@ impl Scanner {
                     skip_prefixes.push(String::new());
                 }
                 detected.push(item);
+            } else {
+                ctx.note_skipped_marker(&file_path, SkippedMarkerReason::Unrecognized);
             }
         }
 
+        apply_archive_updater_providers(&mut detected, &updater_markers);
         Ok(detected)
     }
 
@@ -1014,6 +1076,7 @@ impl Scanner {
         let mut marker_files: Vec<(usize, String, bool, &str)> = Vec::new(); // (index, path, encrypted, marker_type)
         let mut has_encrypted = false;
         let mut detected_livery_roots: HashSet<String> = HashSet::new();
+        let mut updater_markers: Vec<String> = Vec::new();
 
         for i in 0..archive.len() {
             let file: ::zip::read::ZipFile<'_> = match archive.by_index_raw(i) {
@@ -1052,6 +1115,10 @@ impl Scanner {
                 continue;
             }
 
+            if is_updater_marker_path(&file_path) {
+                updater_markers.push(file_path.clone());
+            }
+
             // Check for livery patterns first (before any potential moves)
             if let Some((_, livery_root)) = livery_patterns::check_livery_pattern(&file_path) {
                 if !detected_livery_roots.contains(&livery_root) {
@@ -1259,6 +1326,7 @@ impl Scanner {
             }
         }
 
+        apply_archive_updater_providers(&mut detected, &updater_markers);
         Ok(detected)
     }
 }
@@ -0,0 +1,112 @@
+//! X-Plane installations seen before
+//!
+//! The database is shared by every installation the user points XFast at, and
+//! per-install rows carry the normalized X-Plane root. The distinct roots in the
+//! scenery index, install history and log snapshots are the installations the
+//! user has worked with; each is reported with its current version.
+
+use crate::error::{ApiError, ApiResult};
+use crate::xplane_version::{self, XPlaneVersion};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// One row per (root, source) with the number of indexed scenery packages
+const KNOWN_INSTALLS_SQL: &str = "SELECT xplane_path, COUNT(*) AS package_count \
+     FROM scenery_packages WHERE xplane_path <> '' GROUP BY xplane_path \
+     UNION ALL SELECT DISTINCT xplane_path, 0 FROM install_history \
+     UNION ALL SELECT DISTINCT xplane_path, 0 FROM xplane_log_snapshots";
+
+/// An X-Plane installation that has been used with XFast
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownXPlaneInstall {
+    /// Normalized X-Plane root, as stored in the database
+    pub path: String,
+    /// Whether the root still exists on disk
+    pub exists: bool,
+    /// Number of scenery packages indexed for this installation
+    pub indexed_package_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<XPlaneVersion>,
+}
+
+/// Distinct roots with their package counts, ordered by path
+async fn load_known_roots(conn: &DatabaseConnection) -> ApiResult<BTreeMap<String, u64>> {
+    let rows = conn
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            KNOWN_INSTALLS_SQL,
+        ))
+        .await?;
+
+    let mut roots: BTreeMap<String, u64> = BTreeMap::new();
+    for row in rows {
+        let path: String = row.try_get("", "xplane_path")?;
+        let count: i64 = row.try_get("", "package_count")?;
+        if path.is_empty() {
+            continue;
+        }
+        *roots.entry(path).or_default() += count.max(0) as u64;
+    }
+    Ok(roots)
+}
+
+/// Roots of the installations used before, ordered by path
+pub async fn known_roots(conn: &DatabaseConnection) -> ApiResult<Vec<String>> {
+    Ok(load_known_roots(conn).await?.into_keys().collect())
+}
+
+/// Installations used before, with the version each one reports now.
+/// Roots that no longer exist are kept so their index can still be cleaned up.
+pub async fn list_known_installs(conn: &DatabaseConnection) -> ApiResult<Vec<KnownXPlaneInstall>> {
+    let roots = load_known_roots(conn).await?;
+
+    // Version probes may read large binaries
+    tokio::task::spawn_blocking(move || {
+        roots
+            .into_iter()
+            .map(|(path, indexed_package_count)| {
+                let root = Path::new(&path);
+                let exists = root.is_dir();
+                let version = exists
+                    .then(|| xplane_version::get_xplane_version(root))
+                    .flatten();
+                KnownXPlaneInstall {
+                    path,
+                    exists,
+                    indexed_package_count,
+                    version,
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+
+    #[tokio::test]
+    async fn known_roots_merge_sources_and_count_packages() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+        conn.execute_unprepared(
+            "INSERT INTO scenery_packages (xplane_path, folder_name, category, last_modified, indexed_at) \
+             VALUES ('/xp/main', 'A', 'Airport', 0, 0), ('/xp/main', 'B', 'Mesh', 0, 0), \
+             ('/xp/beta', 'A', 'Airport', 0, 0), ('', 'Legacy', 'Other', 0, 0)",
+        )
+        .await
+        .unwrap();
+
+        let roots = load_known_roots(&conn).await.unwrap();
+        assert_eq!(
+            roots.into_iter().collect::<Vec<_>>(),
+            vec![("/xp/beta".to_string(), 1), ("/xp/main".to_string(), 2)]
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! Where an X-Plane installation lives, and whether XFast can write there
+//!
+//! Most "access denied" (os error 5) reports come from installs under
+//! Program Files, where writes need elevation, or inside a folder synced by
+//! OneDrive, Dropbox or Google Drive, whose clients lock and offload files.
+//! The diagnosis names the cause with flags the frontend maps to guidance:
+//!
+//! - Program Files is recognized by folder name and by the `ProgramFiles*`
+//!   environment variables.
+//! - Sync roots are recognized by folder name, the OneDrive environment
+//!   variables, Dropbox's `.dropbox` marker and, on Windows, the cloud file
+//!   attributes set by sync clients.
+//! - Write access is probed by creating and deleting a file in the folders
+//!   installs write to. The installer runs the same probe before any task.
+//! - Key files are sampled for the read-only attribute.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+/// Folders below the X-Plane root that installs write to
+const PROBE_DIRS: &[&str] = &["Custom Scenery", "Resources/plugins"];
+
+/// Folders sampled for read-only files, with how deep to look
+const READ_ONLY_SAMPLE_DIRS: &[(&str, usize)] = &[
+    ("Custom Scenery", 2),
+    ("Resources/plugins", 2),
+    ("Output/preferences", 1),
+];
+
+/// Files looked at per folder when sampling for the read-only attribute
+const READ_ONLY_SAMPLE_LIMIT: usize = 500;
+
+/// Read-only files listed in the report
+const MAX_READ_ONLY_EXAMPLES: usize = 20;
+
+/// Windows file attributes set on placeholders of cloud sync clients
+#[cfg(windows)]
+const CLOUD_FILE_ATTRIBUTES: u32 = 0x0000_1000 // OFFLINE
+    | 0x0008_0000 // PINNED
+    | 0x0010_0000 // UNPINNED
+    | 0x0040_0000; // RECALL_ON_DATA_ACCESS
+
+/// A problem with where X-Plane is installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum XPlaneLocationIssue {
+    /// Under Program Files, where writes need elevation
+    ProgramFiles,
+    /// Inside a folder kept in sync by a cloud storage client
+    CloudSync,
+    /// A write probe failed in at least one folder installs write to
+    NotWritable,
+    /// Key files carry the read-only attribute
+    ReadOnlyFiles,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncProvider {
+    OneDrive,
+    Dropbox,
+    GoogleDrive,
+    /// Recognized only by cloud file attributes
+    Other,
+}
+
+/// Outcome of creating and deleting a file in one folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteProbeResult {
+    /// Folder relative to the X-Plane root, with `/` separators
+    pub folder: String,
+    pub exists: bool,
+    pub writable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Raw OS error, e.g. 5 for access denied on Windows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os_error: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XPlaneLocationReport {
+    pub xplane_path: String,
+    pub issues: Vec<XPlaneLocationIssue>,
+    pub in_program_files: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_provider: Option<SyncProvider>,
+    /// Synced folder the installation is inside of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_root: Option<String>,
+    pub write_probes: Vec<WriteProbeResult>,
+    /// Read-only files among those sampled
+    pub read_only_file_count: usize,
+    /// A few of them, relative to the X-Plane root
+    pub read_only_examples: Vec<String>,
+}
+
+fn eq_ignore_case(a: &std::ffi::OsStr, b: &str) -> bool {
+    a.to_string_lossy().eq_ignore_ascii_case(b)
+}
+
+/// Lowercased path with `/` separators, for prefix checks across platforms
+fn comparable(path: &Path) -> String {
+    let text = path.to_string_lossy().replace('\\', "/").to_lowercase();
+    text.trim_end_matches('/').to_string()
+}
+
+fn starts_with_dir(path: &Path, root: &Path) -> bool {
+    let path = comparable(path);
+    let root = comparable(root);
+    !root.is_empty() && (path == root || path.starts_with(&format!("{}/", root)))
+}
+
+/// Directories named by `vars` that are set
+fn env_dirs(vars: &[&str]) -> Vec<PathBuf> {
+    vars.iter()
+        .filter_map(|var| std::env::var_os(var))
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn is_in_program_files(path: &Path) -> bool {
+    path.components().any(|component| {
+        let name = component.as_os_str();
+        eq_ignore_case(name, "Program Files") || eq_ignore_case(name, "Program Files (x86)")
+    }) || env_dirs(&["ProgramFiles", "ProgramFiles(x86)", "ProgramW6432"])
+        .iter()
+        .any(|root| starts_with_dir(path, root))
+}
+
+/// Sync client owning a folder with this name, if the name is one they use
+fn provider_for_folder_name(name: &str) -> Option<SyncProvider> {
+    let lower = name.to_lowercase();
+    if lower == "onedrive" || lower.starts_with("onedrive - ") {
+        Some(SyncProvider::OneDrive)
+    } else if lower == "dropbox" || lower.starts_with("dropbox (") {
+        Some(SyncProvider::Dropbox)
+    } else if lower == "google drive" || lower == "googledrive" || lower == "my drive" {
+        Some(SyncProvider::GoogleDrive)
+    } else {
+        None
+    }
+}
+
+#[cfg(windows)]
+fn has_cloud_attributes(path: &Path) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    fs::metadata(path)
+        .map(|meta| meta.file_attributes() & CLOUD_FILE_ATTRIBUTES != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(windows))]
+fn has_cloud_attributes(_path: &Path) -> bool {
+    false
+}
+
+/// The sync client and root of the nearest synced folder containing `path`
+fn find_sync_root(path: &Path) -> Option<(SyncProvider, PathBuf)> {
+    for root in env_dirs(&["OneDrive", "OneDriveConsumer", "OneDriveCommercial"]) {
+        if starts_with_dir(path, &root) {
+            return Some((SyncProvider::OneDrive, root));
+        }
+    }
+    for ancestor in path.ancestors() {
+        if let Some(provider) = ancestor
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(provider_for_folder_name)
+        {
+            return Some((provider, ancestor.to_path_buf()));
+        }
+        if ancestor.join(".dropbox").is_file() {
+            return Some((SyncProvider::Dropbox, ancestor.to_path_buf()));
+        }
+    }
+    has_cloud_attributes(path).then(|| (SyncProvider::Other, path.to_path_buf()))
+}
+
+/// Create, write and delete a file in `dir`
+pub fn probe_write_access(dir: &Path) -> std::io::Result<()> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let probe = dir.join(format!(".xfm_write_probe_{}_{}", std::process::id(), nanos));
+    let written = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .and_then(|mut file| file.write_all(b"xfm"));
+    let removed = fs::remove_file(&probe);
+    written?;
+    removed
+}
+
+/// Nearest existing directory at or above `path`
+pub fn nearest_existing_dir(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.is_dir())
+        .map(Path::to_path_buf)
+}
+
+fn probe_folder(xplane_path: &Path, folder: &str) -> WriteProbeResult {
+    let dir = xplane_path.join(folder);
+    if !dir.is_dir() {
+        return WriteProbeResult {
+            folder: folder.to_string(),
+            exists: false,
+            writable: false,
+            error: None,
+            os_error: None,
+        };
+    }
+    let result = probe_write_access(&dir);
+    WriteProbeResult {
+        folder: folder.to_string(),
+        exists: true,
+        writable: result.is_ok(),
+        os_error: result.as_ref().err().and_then(|e| e.raw_os_error()),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+/// Read-only files among a bounded sample of the folders installs touch
+fn sample_read_only_files(xplane_path: &Path) -> (usize, Vec<String>) {
+    let mut count = 0;
+    let mut examples = Vec::new();
+    for (folder, depth) in READ_ONLY_SAMPLE_DIRS {
+        let files = WalkDir::new(xplane_path.join(folder))
+            .max_depth(*depth)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .take(READ_ONLY_SAMPLE_LIMIT);
+        for entry in files {
+            let read_only = entry
+                .metadata()
+                .map(|meta| meta.permissions().readonly())
+                .unwrap_or(false);
+            if !read_only {
+                continue;
+            }
+            count += 1;
+            if examples.len() < MAX_READ_ONLY_EXAMPLES {
+                if let Ok(relative) = entry.path().strip_prefix(xplane_path) {
+                    examples.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+    }
+    (count, examples)
+}
+
+/// Check where the installation at `xplane_path` lives and whether installs
+/// can write to it
+pub fn diagnose_xplane_location(xplane_path: &Path) -> XPlaneLocationReport {
+    let in_program_files = is_in_program_files(xplane_path);
+    let sync = find_sync_root(xplane_path);
+    let write_probes: Vec<WriteProbeResult> = PROBE_DIRS
+        .iter()
+        .map(|folder| probe_folder(xplane_path, folder))
+        .collect();
+    let (read_only_file_count, read_only_examples) = sample_read_only_files(xplane_path);
+
+    let mut issues = Vec::new();
+    if in_program_files {
+        issues.push(XPlaneLocationIssue::ProgramFiles);
+    }
+    if sync.is_some() {
+        issues.push(XPlaneLocationIssue::CloudSync);
+    }
+    if write_probes
+        .iter()
+        .any(|probe| probe.exists && !probe.writable)
+    {
+        issues.push(XPlaneLocationIssue::NotWritable);
+    }
+    if read_only_file_count > 0 {
+        issues.push(XPlaneLocationIssue::ReadOnlyFiles);
+    }
+
+    let (sync_provider, sync_root) = match sync {
+        Some((provider, root)) => (Some(provider), Some(root.to_string_lossy().into_owned())),
+        None => (None, None),
+    };
+    XPlaneLocationReport {
+        xplane_path: xplane_path.to_string_lossy().into_owned(),
+        issues,
+        in_program_files,
+        sync_provider,
+        sync_root,
+        write_probes,
+        read_only_file_count,
+        read_only_examples,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_location_names_are_recognized() {
+        assert!(is_in_program_files(Path::new(
+            "C:\\Program Files\\X-Plane 12"
+        )));
+        assert!(is_in_program_files(Path::new(
+            "/mnt/c/Program Files (x86)/X-Plane 11"
+        )));
+        assert!(!is_in_program_files(Path::new("/games/X-Plane 12")));
+
+        let (provider, root) =
+            find_sync_root(Path::new("/home/pilot/OneDrive - Contoso/Sims/X-Plane 12")).unwrap();
+        assert_eq!(provider, SyncProvider::OneDrive);
+        assert!(root.ends_with("OneDrive - Contoso"));
+        assert_eq!(
+            provider_for_folder_name("Dropbox (Personal)"),
+            Some(SyncProvider::Dropbox)
+        );
+        assert_eq!(
+            provider_for_folder_name("My Drive"),
+            Some(SyncProvider::GoogleDrive)
+        );
+        assert_eq!(provider_for_folder_name("OneDriveBackup"), None);
+    }
+
+    #[test]
+    fn test_writable_install_has_no_issues() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().join("X-Plane 12");
+        fs::create_dir_all(root.join("Custom Scenery")).unwrap();
+        fs::write(root.join("Custom Scenery/scenery_packs.ini"), b"I\n").unwrap();
+
+        let report = diagnose_xplane_location(&root);
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+        assert!(report.write_probes[0].exists && report.write_probes[0].writable);
+        assert!(!report.write_probes[1].exists);
+        assert_eq!(
+            fs::read_dir(root.join("Custom Scenery")).unwrap().count(),
+            1
+        );
+
+        let ini = root.join("Custom Scenery/scenery_packs.ini");
+        let mut permissions = fs::metadata(&ini).unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&ini, permissions).unwrap();
+        let report = diagnose_xplane_location(&root);
+        assert_eq!(report.issues, vec![XPlaneLocationIssue::ReadOnlyFiles]);
+        assert_eq!(
+            report.read_only_examples,
+            vec!["Custom Scenery/scenery_packs.ini".to_string()]
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! X-Plane version probe — reads the installed simulator version from the
+//! installation binary, falling back to Log.txt when the binary can't be read.
+//!
+//! Log.txt only exists after the sim has run at least once, so the binary is
+//! probed first:
+//! - Windows: the `ProductVersion`/`FileVersion` strings of `X-Plane.exe`
+//! - macOS: `CFBundleShortVersionString`/`CFBundleVersion` in `X-Plane.app`'s Info.plist
+//! - Linux: the embedded "X-Plane 12.x.y" string in `X-Plane-x86_64`
+//!
+//! Results are cached per installation and invalidated when the probed file's
+//! modification time changes (i.e. after a sim update).
+
+use dashmap::DashMap;
+use regex::bytes::Regex as BytesRegex;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::SystemTime;
+
+/// Windows executable name
+const WINDOWS_EXECUTABLE: &str = "X-Plane.exe";
+/// macOS application bundle name
+const MAC_APP_BUNDLE: &str = "X-Plane.app";
+/// Linux executable name
+const LINUX_EXECUTABLE: &str = "X-Plane-x86_64";
+/// Upper bound on how much of a binary is scanned for version strings
+const MAX_BINARY_SCAN_BYTES: u64 = 512 * 1024 * 1024;
+/// Read chunk size for binary scanning
+const SCAN_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+/// Bytes carried over between chunks so matches spanning a boundary are found
+const SCAN_OVERLAP: usize = 256;
+/// Only the log header carries the version line
+const LOG_HEADER_LINES: usize = 150;
+
+/// Where a version was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VersionProbeSource {
+    WindowsExecutable,
+    MacInfoPlist,
+    LinuxBinary,
+    LogFile,
+}
+
+/// Installed X-Plane version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XPlaneVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// Release suffix such as "r1" or "b3"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// Build number, e.g. "121004"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<String>,
+    /// Raw version text as found in the probed source
+    pub raw: String,
+    pub source: VersionProbeSource,
+    /// Full path of the file the version was read from
+    pub source_path: String,
+}
+
+impl XPlaneVersion {
+    /// "12.1.4-r1" style display string
+    pub fn display(&self) -> String {
+        match &self.revision {
+            Some(rev) => format!("{}.{}.{}-{}", self.major, self.minor, self.patch, rev),
+            None => format!("{}.{}.{}", self.major, self.minor, self.patch),
+        }
+    }
+}
+
+struct CachedVersion {
+    modified: SystemTime,
+    version: XPlaneVersion,
+}
+
+/// Cache keyed by X-Plane root path
+static VERSION_CACHE: LazyLock<DashMap<String, CachedVersion>> = LazyLock::new(DashMap::new);
+
+/// "12.1.4-r1 (build 121004" / "12.1.4" / "12.1.4.121004"
+static VERSION_TEXT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(1[0-3])\.(\d{1,2})(?:\.(\d{1,2}))?(?:\.(\d{3,7}))?(?:-([a-z]+\d*))?(?:\s*\(build\s+(\d+))?",
+    )
+    .expect("valid version text regex")
+});
+
+/// Embedded "X-Plane 12.1.4-r1 (build 121004" string in native binaries
+static BINARY_VERSION_RE: LazyLock<BytesRegex> = LazyLock::new(|| {
+    BytesRegex::new(r"X-Plane (1[0-3])\.(\d{1,2})\.(\d{1,2})(?:-([a-z]+\d*))?(?: \(build (\d+))?")
+        .expect("valid binary version regex")
+});
+
+/// Probe the X-Plane version for an installation, using the cache when the
+/// probed file hasn't changed since the last probe.
+pub fn get_xplane_version(xplane_root: &Path) -> Option<XPlaneVersion> {
+    let key = xplane_root.to_string_lossy().to_string();
+
+    if let Some(cached) = VERSION_CACHE.get(&key) {
+        let still_valid = file_modified(Path::new(&cached.version.source_path))
+            .map(|m| m == cached.modified)
+            .unwrap_or(false);
+        if still_valid {
+            return Some(cached.version.clone());
+        }
+    }
+    VERSION_CACHE.remove(&key);
+
+    let version = probe_xplane_version(xplane_root)?;
+    if let Some(modified) = file_modified(Path::new(&version.source_path)) {
+        VERSION_CACHE.insert(
+            key,
+            CachedVersion {
+                modified,
+                version: version.clone(),
+            },
+        );
+    }
+    Some(version)
+}
+
+/// Probe without consulting the cache. Binaries are tried first, Log.txt last.
+pub fn probe_xplane_version(xplane_root: &Path) -> Option<XPlaneVersion> {
+    let probes: [(PathBuf, fn(&Path) -> Option<XPlaneVersion>); 4] = [
+        (
+            xplane_root.join(WINDOWS_EXECUTABLE),
+            probe_windows_executable,
+        ),
+        (
+            xplane_root
+                .join(MAC_APP_BUNDLE)
+                .join("Contents")
+                .join("Info.plist"),
+            probe_mac_info_plist,
+        ),
+        (xplane_root.join(LINUX_EXECUTABLE), probe_linux_binary),
+        (xplane_root.join("Log.txt"), probe_log_file),
+    ];
+
+    for (path, probe) in probes.iter() {
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(version) = probe(path) {
+            return Some(version);
+        }
+        crate::log_debug!(
+            &format!("No X-Plane version found in {}", path.display()),
+            "xplane_version"
+        );
+    }
+
+    None
+}
+
+fn file_modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Parse free-form version text ("12.1.4-r1 (build 121004 ...)", "12.1.4.121004").
+fn parse_version_text(
+    text: &str,
+    source: VersionProbeSource,
+    source_path: &Path,
+) -> Option<XPlaneVersion> {
+    let caps = VERSION_TEXT_RE.captures(text)?;
+    let num = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u32>().ok());
+
+    Some(XPlaneVersion {
+        major: num(1)?,
+        minor: num(2)?,
+        patch: num(3).unwrap_or(0),
+        revision: caps.get(5).map(|m| m.as_str().to_lowercase()),
+        build: caps
+            .get(6)
+            .or_else(|| caps.get(4))
+            .map(|m| m.as_str().to_string()),
+        raw: caps.get(0)?.as_str().trim().to_string(),
+        source,
+        source_path: source_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Scan a file chunk by chunk (bounded by `MAX_BINARY_SCAN_BYTES`), handing each
+/// window to `find` until it returns a value.
+fn scan_file_bounded<T>(path: &Path, mut find: impl FnMut(&[u8]) -> Option<T>) -> Option<T> {
+    let file = fs::File::open(path).ok()?;
+    let mut reader = file.take(MAX_BINARY_SCAN_BYTES);
+    let mut window: Vec<u8> = Vec::with_capacity(SCAN_CHUNK_SIZE + SCAN_OVERLAP);
+    let mut chunk = vec![0u8; SCAN_CHUNK_SIZE];
+
+    loop {
+        let read = reader.read(&mut chunk).ok()?;
+        if read == 0 {
+            return None;
+        }
+        window.extend_from_slice(&chunk[..read]);
+        if let Some(found) = find(&window) {
+            return Some(found);
+        }
+        let keep_from = window.len().saturating_sub(SCAN_OVERLAP);
+        window.drain(..keep_from);
+    }
+}
+
+/// Find an embedded "X-Plane 12.x.y" string in a binary.
+fn find_embedded_version(window: &[u8]) -> Option<String> {
+    BINARY_VERSION_RE
+        .find(window)
+        .map(|m| String::from_utf8_lossy(m.as_bytes()).to_string())
+}
+
+/// Find a UTF-16LE `key` string in a PE version resource and return its value.
+fn find_version_resource_value(window: &[u8], key: &str) -> Option<String> {
+    let mut needle: Vec<u8> = key.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+    needle.extend_from_slice(&[0, 0]);
+
+    let start = window
+        .windows(needle.len())
+        .position(|w| w == needle.as_slice())?;
+    let mut pos = start + needle.len();
+    // Value is DWORD-aligned; skip the padding
+    while pos + 1 < window.len() && window[pos] == 0 && window[pos + 1] == 0 {
+        pos += 2;
+    }
+
+    let mut units = Vec::new();
+    while pos + 1 < window.len() && units.len() < 64 {
+        let unit = u16::from_le_bytes([window[pos], window[pos + 1]]);
+        if unit == 0 {
+            break;
+        }
+        units.push(unit);
+        pos += 2;
+    }
+
+    let value = String::from_utf16_lossy(&units).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+fn probe_windows_executable(path: &Path) -> Option<XPlaneVersion> {
+    let found = scan_file_bounded(path, |window| {
+        find_version_resource_value(window, "ProductVersion")
+            .or_else(|| find_version_resource_value(window, "FileVersion"))
+            .filter(|v| VERSION_TEXT_RE.is_match(v))
+            .or_else(|| find_embedded_version(window))
+    })?;
+    parse_version_text(&found, VersionProbeSource::WindowsExecutable, path)
+}
+
+fn probe_linux_binary(path: &Path) -> Option<XPlaneVersion> {
+    let found = scan_file_bounded(path, find_embedded_version)?;
+    parse_version_text(&found, VersionProbeSource::LinuxBinary, path)
+}
+
+/// Read the `<string>` value following `<key>name</key>` in an XML plist.
+fn plist_string_value(content: &str, name: &str) -> Option<String> {
+    let key = format!("<key>{}</key>", name);
+    let after_key = &content[content.find(&key)? + key.len()..];
+    let open = after_key.find("<string>")?;
+    // The value must directly follow the key
+    if !after_key[..open].trim().is_empty() {
+        return None;
+    }
+    let value_start = open + "<string>".len();
+    let value_end = after_key[value_start..].find("</string>")? + value_start;
+    let value = after_key[value_start..value_end].trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+fn probe_mac_info_plist(path: &Path) -> Option<XPlaneVersion> {
+    let content = fs::read_to_string(path).ok()?;
+    let short = plist_string_value(&content, "CFBundleShortVersionString")?;
+    let mut version = parse_version_text(&short, VersionProbeSource::MacInfoPlist, path)?;
+    if version.build.is_none() {
+        version.build = plist_string_value(&content, "CFBundleVersion")
+            .filter(|b| b.chars().all(|c| c.is_ascii_digit()));
+    }
+    Some(version)
+}
+
+fn probe_log_file(path: &Path) -> Option<XPlaneVersion> {
+    use std::io::{BufRead, BufReader};
+
+    let file = fs::File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(LOG_HEADER_LINES)
+        .map_while(|l| l.ok())
+        .find_map(|line| {
+            // "Log.txt for X-Plane 12.1.4-r1 (build 121004 d8d8dd1a 2024/12/05)"
+            let pos = line.find("Log.txt for ")?;
+            parse_version_text(
+                &line[pos + "Log.txt for ".len()..],
+                VersionProbeSource::LogFile,
+                path,
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_log_header_version() {
+        let v = parse_version_text(
+            "X-Plane 12.1.4-r1 (build 121004 d8d8dd1a 2024/12/05)",
+            VersionProbeSource::LogFile,
+            Path::new("Log.txt"),
+        )
+        .unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (12, 1, 4));
+        assert_eq!(v.revision.as_deref(), Some("r1"));
+        assert_eq!(v.build.as_deref(), Some("121004"));
+        assert_eq!(v.display(), "12.1.4-r1");
+    }
+
+    #[test]
+    fn parses_four_part_resource_version() {
+        let v = parse_version_text(
+            "12.0.9.120009",
+            VersionProbeSource::WindowsExecutable,
+            Path::new("X-Plane.exe"),
+        )
+        .unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (12, 0, 9));
+        assert_eq!(v.build.as_deref(), Some("120009"));
+        assert!(v.revision.is_none());
+    }
+
+    #[test]
+    fn rejects_unrelated_text() {
+        assert!(parse_version_text(
+            "OpenGL 4.6.0",
+            VersionProbeSource::LogFile,
+            Path::new("Log.txt")
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn finds_utf16_version_resource() {
+        let mut data = b"\x00garbage".to_vec();
+        data.extend(
+            "ProductVersion"
+                .encode_utf16()
+                .flat_map(|c| c.to_le_bytes()),
+        );
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend("11.55.0".encode_utf16().flat_map(|c| c.to_le_bytes()));
+        data.extend_from_slice(&[0, 0]);
+        assert_eq!(
+            find_version_resource_value(&data, "ProductVersion").as_deref(),
+            Some("11.55.0")
+        );
+    }
+
+    #[test]
+    fn finds_embedded_binary_version() {
+        let data = b"\x7fELF...X-Plane 12.2.0-b3 (build 122003)...";
+        assert_eq!(
+            find_embedded_version(data).as_deref(),
+            Some("X-Plane 12.2.0-b3 (build 122003")
+        );
+    }
+
+    #[test]
+    fn reads_plist_values() {
+        let plist = "<dict>\n<key>CFBundleShortVersionString</key>\n<string>12.1.0</string>\n\
+                     <key>CFBundleVersion</key><string>121000</string>\n</dict>";
+        assert_eq!(
+            plist_string_value(plist, "CFBundleShortVersionString").as_deref(),
+            Some("12.1.0")
+        );
+        assert_eq!(
+            plist_string_value(plist, "CFBundleVersion").as_deref(),
+            Some("121000")
+        );
+    }
+
+    #[test]
+    fn probe_falls_back_to_log_and_caches_by_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Log.txt"),
+            "Log.txt for X-Plane 12.1.1-r2 (build 121101 abcdef 2024/08/01)\n",
+        )
+        .unwrap();
+
+        let v = get_xplane_version(dir.path()).unwrap();
+        assert_eq!(v.source, VersionProbeSource::LogFile);
+        assert_eq!(v.display(), "12.1.1-r2");
+        assert!(VERSION_CACHE.contains_key(&dir.path().to_string_lossy().to_string()));
+    }
+
+    #[test]
+    fn probe_prefers_linux_binary_over_log() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(LINUX_EXECUTABLE),
+            b"\x7fELF\x00\x00X-Plane 12.1.4-r1 (build 121004)\x00",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("Log.txt"),
+            "Log.txt for X-Plane 12.0.0-r1\n",
+        )
+        .unwrap();
+
+        let v = probe_xplane_version(dir.path()).unwrap();
+        assert_eq!(v.source, VersionProbeSource::LinuxBinary);
+        assert_eq!((v.major, v.minor, v.patch), (12, 1, 4));
+        assert_eq!(v.build.as_deref(), Some("121004"));
+    }
+}
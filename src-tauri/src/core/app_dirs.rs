@@ -3,12 +3,54 @@
 //! All persistent data (logs, database, cache) should use paths from this module
 //! to ensure consistent storage location across the application.
 //! The paths match Tauri store plugin's default locations.
+//!
+//! Every location is also listed in [`app_storage_locations`], which the stored
+//! data summary and erase commands rely on. New storage must be added there too.
 
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// App identifier matching tauri.conf.json
 const APP_IDENTIFIER: &str = "com.xfastmanager.tool";
 
+/// Per-aircraft state file written by the ACF variant toggles
+pub const AIRCRAFT_ACF_STATE_FILE_NAME: &str = ".xfastmanager-acf-state.json";
+
+/// Prefix of the staging folders atomic installs create in the X-Plane root
+pub const ATOMIC_INSTALL_TEMP_PREFIX: &str = ".xfastmanager_temp_";
+
+/// Kind of data the app stores, as shown by the stored data summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageCategory {
+    /// Scenery index database and its SQLite journals
+    Database,
+    Logs,
+    /// Frontend settings store
+    Settings,
+    /// Update check cache, downloaded app installers and addon icons
+    Caches,
+    /// Library link submissions waiting to be sent
+    Submissions,
+    /// Addon updater logins written into addon folders, the list of those
+    /// folders and the saved proxy password
+    Credentials,
+    /// X-Plane preferences backups
+    Backups,
+    /// State and leftover files XFast wrote inside the X-Plane folder
+    XplaneFiles,
+    /// Anything else found in the app data directory
+    Other,
+}
+
+/// A file or directory the app writes to, outside the X-Plane folder
+#[derive(Debug, Clone)]
+pub struct StorageLocation {
+    pub category: StorageCategory,
+    pub path: PathBuf,
+    pub description: &'static str,
+}
+
 /// Get the app data directory for persistent storage
 ///
 /// Returns platform-specific paths (matching Tauri store default):
@@ -72,6 +114,158 @@ pub fn get_update_cache_path() -> PathBuf {
     get_app_data_dir().join("update_check_cache.txt")
 }
 
+/// Get the directory app self-update installers are downloaded into
+pub fn get_update_download_dir() -> PathBuf {
+    get_app_data_dir().join("updates")
+}
+
+/// Get the directory addons downloaded from a pasted URL are staged in
+pub fn get_url_download_dir() -> PathBuf {
+    get_app_data_dir().join("downloads")
+}
+
+/// Get the directory the download queue keeps its files in, one folder per item
+pub fn get_download_queue_dir() -> PathBuf {
+    get_app_data_dir().join("download_queue")
+}
+
+/// Get the directory addon icons from updater metadata are cached in
+pub fn get_icon_cache_dir() -> PathBuf {
+    get_app_data_dir().join("icon_cache")
+}
+
+/// Get the file queued library link submissions are persisted to
+pub fn get_pending_submissions_path() -> PathBuf {
+    get_app_data_dir().join("pending_submissions.json")
+}
+
+/// Get the list of addon profiles that credentials were written into
+pub fn get_credential_ledger_path() -> PathBuf {
+    get_app_data_dir().join("credential_locations.json")
+}
+
+/// File name of the saved proxy password, next to the backend settings
+pub const PROXY_CREDENTIALS_FILE: &str = "proxy_credentials.json";
+
+/// Get the file the proxy password is kept in, out of the settings file
+pub fn get_proxy_credentials_path() -> PathBuf {
+    get_app_data_dir().join(PROXY_CREDENTIALS_FILE)
+}
+
+/// Get the livery detection patterns the user added (see `livery_patterns`)
+pub fn get_user_livery_patterns_path() -> PathBuf {
+    get_app_data_dir().join("user_livery_patterns.json")
+}
+
+/// Get the frontend settings store (`Store.load('settings.json')`)
+pub fn get_settings_store_path() -> PathBuf {
+    get_app_data_dir().join("settings.json")
+}
+
+/// Get the settings file owned by the backend (see `settings_store`)
+pub fn get_backend_settings_path() -> PathBuf {
+    get_app_data_dir().join("backend_settings.json")
+}
+
+/// Get the directory X-Plane preferences backups are kept in
+pub fn get_preference_backups_dir() -> PathBuf {
+    get_app_data_dir().join("preference_backups")
+}
+
+/// Get the directory addon files replaced by updates are kept in
+pub fn get_addon_backups_dir() -> PathBuf {
+    get_app_data_dir().join("addon_backups")
+}
+
+/// Every location the app stores data in, outside the X-Plane folder
+pub fn app_storage_locations() -> Vec<StorageLocation> {
+    let database = get_database_path();
+    let location = |category, path, description| StorageLocation {
+        category,
+        path,
+        description,
+    };
+    vec![
+        location(
+            StorageCategory::Database,
+            database.clone(),
+            "Scenery index database",
+        ),
+        location(
+            StorageCategory::Database,
+            database.with_extension("db-wal"),
+            "Database write-ahead journal",
+        ),
+        location(
+            StorageCategory::Database,
+            database.with_extension("db-shm"),
+            "Database shared-memory index",
+        ),
+        location(StorageCategory::Logs, get_logs_dir(), "Application logs"),
+        location(
+            StorageCategory::Settings,
+            get_settings_store_path(),
+            "Settings and preferences",
+        ),
+        location(
+            StorageCategory::Settings,
+            get_backend_settings_path(),
+            "Backend settings",
+        ),
+        location(
+            StorageCategory::Settings,
+            get_user_livery_patterns_path(),
+            "User livery detection patterns",
+        ),
+        location(
+            StorageCategory::Caches,
+            get_update_cache_path(),
+            "Last update check time",
+        ),
+        location(
+            StorageCategory::Caches,
+            get_update_download_dir(),
+            "Downloaded app updates",
+        ),
+        location(StorageCategory::Caches, get_icon_cache_dir(), "Addon icons"),
+        location(
+            StorageCategory::Caches,
+            get_url_download_dir(),
+            "Addons downloaded from a URL",
+        ),
+        location(
+            StorageCategory::Caches,
+            get_download_queue_dir(),
+            "Addons downloaded through the download queue",
+        ),
+        location(
+            StorageCategory::Submissions,
+            get_pending_submissions_path(),
+            "Queued library link submissions",
+        ),
+        location(
+            StorageCategory::Credentials,
+            get_credential_ledger_path(),
+            "Addon folders holding saved updater logins",
+        ),
+        location(
+            StorageCategory::Credentials,
+            get_proxy_credentials_path(),
+            "Saved proxy password",
+        ),
+        location(
+            StorageCategory::Backups,
+            get_preference_backups_dir(),
+            "X-Plane preferences backups",
+        ),
+        location(
+            StorageCategory::Backups,
+            get_addon_backups_dir(),
+            "Addon files kept from before updates",
+        ),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +281,18 @@ mod tests {
         let data_dir = get_app_data_dir();
         assert!(data_dir.to_string_lossy().contains(APP_IDENTIFIER));
     }
+
+    #[test]
+    fn test_storage_locations_are_unique_children_of_app_data_dir() {
+        let data_dir = get_app_data_dir();
+        let locations = app_storage_locations();
+        let unique: std::collections::HashSet<_> = locations.iter().map(|l| &l.path).collect();
+        assert_eq!(unique.len(), locations.len());
+        assert!(locations
+            .iter()
+            .all(|l| l.path.parent() == Some(data_dir.as_path())));
+        assert!(locations
+            .iter()
+            .all(|l| l.category != StorageCategory::XplaneFiles));
+    }
 }
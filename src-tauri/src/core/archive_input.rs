@@ -365,9 +365,181 @@ fn concat_parts_to_temp(parts: &[PathBuf], format: ArchiveFormat) -> Result<Prep
     })
 }
 
+fn collect_rar_split_parts(path: &Path) -> Option<Vec<PathBuf>> {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = path.file_name()?.to_str()?;
+
+    if let Some((prefix, width, _)) = split_rar_part(file_name) {
+        let mut parts = Vec::new();
+        let mut index = 1u32;
+        loop {
+            let part_name = format!("{}.part{:0width$}.rar", prefix, index, width = width);
+            if let Some(part_path) = find_existing_sibling(parent, &part_name) {
+                parts.push(part_path);
+                index += 1;
+                continue;
+            }
+            break;
+        }
+        return (!parts.is_empty()).then_some(parts);
+    }
+
+    if file_name.to_ascii_lowercase().ends_with(".rar") {
+        let stem = &file_name[..(file_name.len() - 4)];
+        // The .rar comes first, followed by .r00, .r01, ...
+        let mut parts = vec![path.to_path_buf()];
+        let mut index = 0u32;
+        loop {
+            let part_name = format!("{}.r{:02}", stem, index);
+            if let Some(part_path) = find_existing_sibling(parent, &part_name) {
+                parts.push(part_path);
+                index += 1;
+                continue;
+            }
+            break;
+        }
+        return (parts.len() > 1).then_some(parts);
+    }
+
+    None
+}
+
+/// All volumes of the split archive set `path` belongs to, in reading order.
+/// Empty when `path` is not part of a split set.
+pub fn split_archive_parts(path: &Path) -> Vec<PathBuf> {
+    let normalized = normalize_archive_entry_path(path);
+    let parts = match detect_archive_format(&normalized) {
+        Some(ArchiveFormat::Zip) => collect_zip_split_parts(&normalized).ok().flatten(),
+        Some(ArchiveFormat::SevenZ) => collect_7z_split_parts(&normalized).ok().flatten(),
+        Some(ArchiveFormat::Rar) => collect_rar_split_parts(&normalized),
+        None => None,
+    };
+    parts.unwrap_or_default()
+}
+
+/// Numeric volume extension such as `z01` or `r00`, with the leading letter
+fn volume_extension_index(ext: &str, letter: char) -> Option<u32> {
+    let lower = ext.to_ascii_lowercase();
+    let digits = lower.strip_prefix(letter)?;
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Names `name_of(first..=last)` not found in `parent`, where `last` is the
+/// highest volume index `index_of` reads from the files there
+fn missing_volumes(
+    parent: &Path,
+    first: u32,
+    name_of: impl Fn(u32) -> String,
+    index_of: impl Fn(&str) -> Option<u32>,
+) -> Vec<String> {
+    let last = fs::read_dir(parent)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| index_of(entry.file_name().to_str()?))
+        .max()
+        .unwrap_or(first);
+    (first..=last)
+        .map(name_of)
+        .filter(|name| find_existing_sibling(parent, name).is_none())
+        .collect()
+}
+
+/// File names of the volumes missing from the split archive set `path` belongs
+/// to. Only gaps before the last present volume (and a missing `.zip`/`.rar`
+/// that completes a `.z01`/`.r00` set) can be told from the names alone.
+pub fn missing_split_parts(path: &Path) -> Vec<String> {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    let Some(file_name) = path.file_name().and_then(|s| s.to_str()) else {
+        return Vec::new();
+    };
+    let lower = file_name.to_ascii_lowercase();
+
+    let numbered = split_numbered_series(file_name, ".zip.")
+        .or_else(|| split_numbered_series(file_name, ".7z."))
+        .map(|(prefix, width, _)| (prefix, width))
+        .or_else(|| {
+            let prefix = format!("{}.", file_name);
+            (lower.ends_with(".7z")
+                && find_existing_sibling(parent, &format!("{}001", prefix)).is_some())
+            .then_some((prefix, 3))
+        });
+    if let Some((prefix, width)) = numbered {
+        return missing_volumes(
+            parent,
+            1,
+            |index| format!("{}{:0width$}", prefix, index, width = width),
+            |name| {
+                split_numbered_series(name, ".")
+                    .filter(|(p, w, _)| p.eq_ignore_ascii_case(&prefix) && *w == width)
+                    .map(|(_, _, index)| index)
+            },
+        );
+    }
+
+    if let Some((prefix, width, _)) = split_rar_part(file_name) {
+        return missing_volumes(
+            parent,
+            1,
+            |index| format!("{}.part{:0width$}.rar", prefix, index, width = width),
+            |name| {
+                split_rar_part(name)
+                    .filter(|(p, w, _)| p.eq_ignore_ascii_case(&prefix) && *w == width)
+                    .map(|(_, _, index)| index)
+            },
+        );
+    }
+
+    let Some((stem, ext)) = file_name.rsplit_once('.') else {
+        return Vec::new();
+    };
+    // `.z01`, `.z02`, ... end with a `.zip`; `.rar` is followed by `.r00`, `.r01`, ...
+    for (letter, first, anchor_ext) in [('z', 1, "zip"), ('r', 0, "rar")] {
+        let first_volume = format!("{}.{}{:02}", stem, letter, first);
+        let in_set = volume_extension_index(ext, letter).is_some()
+            || (ext.eq_ignore_ascii_case(anchor_ext)
+                && find_existing_sibling(parent, &first_volume).is_some());
+        if !in_set {
+            continue;
+        }
+
+        let mut missing = missing_volumes(
+            parent,
+            first,
+            |index| format!("{}.{}{:02}", stem, letter, index),
+            |name| {
+                let (name_stem, name_ext) = name.rsplit_once('.')?;
+                if !name_stem.eq_ignore_ascii_case(stem) {
+                    return None;
+                }
+                volume_extension_index(name_ext, letter)
+            },
+        );
+        let anchor = format!("{}.{}", stem, anchor_ext);
+        if find_existing_sibling(parent, &anchor).is_none() {
+            missing.push(anchor);
+        }
+        return missing;
+    }
+
+    Vec::new()
+}
+
 pub fn prepare_archive_for_read(path: &Path, format: ArchiveFormat) -> Result<PreparedArchive> {
     let normalized = normalize_archive_entry_path(path);
 
+    let missing = missing_split_parts(&normalized);
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Split archive {} is missing part(s): {}",
+            normalized.display(),
+            missing.join(", ")
+        );
+    }
+
     let parts = match format {
         ArchiveFormat::Zip => collect_zip_split_parts(&normalized)?,
         ArchiveFormat::SevenZ => collect_7z_split_parts(&normalized)?,
@@ -392,7 +564,7 @@ pub fn prepare_archive_for_read(path: &Path, format: ArchiveFormat) -> Result<Pr
 
 #[cfg(test)]
 mod tests {
-    use super::{detect_archive_format, ArchiveFormat};
+    use super::{detect_archive_format, missing_split_parts, split_archive_parts, ArchiveFormat};
     use std::fs;
     use tempfile::tempdir;
 
@@ -417,4 +589,60 @@ mod tests {
         assert_eq!(detect_archive_format(&zip), Some(ArchiveFormat::Zip));
         assert_eq!(detect_archive_format(&z01), Some(ArchiveFormat::Zip));
     }
+
+    #[test]
+    fn split_archive_parts_orders_volumes_and_reports_gaps() {
+        let temp = tempdir().expect("failed to create tempdir");
+        for name in [
+            "ortho.z01",
+            "ortho.z02",
+            "ortho.zip",
+            "scenery.7z.001",
+            "scenery.7z.003",
+            "hangar.rar",
+            "hangar.r00",
+            "hangar.r01",
+        ] {
+            fs::write(temp.path().join(name), b"part").expect("failed to write part");
+        }
+
+        let zip_parts: Vec<String> = split_archive_parts(&temp.path().join("ortho.z02"))
+            .iter()
+            .map(|part| part.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(zip_parts, vec!["ortho.z01", "ortho.z02", "ortho.zip"]);
+        assert!(missing_split_parts(&temp.path().join("ortho.zip")).is_empty());
+
+        let rar_parts: Vec<String> = split_archive_parts(&temp.path().join("hangar.r01"))
+            .iter()
+            .map(|part| part.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(rar_parts, vec!["hangar.rar", "hangar.r00", "hangar.r01"]);
+        assert!(missing_split_parts(&temp.path().join("hangar.rar")).is_empty());
+
+        assert_eq!(
+            missing_split_parts(&temp.path().join("scenery.7z.001")),
+            vec!["scenery.7z.002".to_string()]
+        );
+
+        fs::remove_file(temp.path().join("ortho.zip")).expect("failed to remove zip");
+        assert_eq!(
+            missing_split_parts(&temp.path().join("ortho.z01")),
+            vec!["ortho.zip".to_string()]
+        );
+    }
+
+    #[test]
+    fn split_archive_parts_ignores_single_archives() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let archive = temp.path().join("livery.part1.rar");
+        fs::write(&archive, b"part").expect("failed to write rar");
+        fs::write(temp.path().join("livery.part2.rar"), b"part").expect("failed to write rar");
+        let single = temp.path().join("plugin.zip");
+        fs::write(&single, b"zip").expect("failed to write zip");
+
+        assert_eq!(split_archive_parts(&archive).len(), 2);
+        assert!(split_archive_parts(&single).is_empty());
+        assert!(missing_split_parts(&single).is_empty());
+    }
 }
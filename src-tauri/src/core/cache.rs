@@ -1,7 +1,30 @@
+//! In-memory caches for archive and directory size metadata
+//!
+//! Archive entries are keyed by a fingerprint of the archive's contents (its
+//! size plus the first and last 64 KB) rather than its path, so a renamed or
+//! moved archive still hits and an archive replaced in place misses. They are
+//! also persisted to the `archive_metadata_cache` table, capped at
+//! `MAX_ARCHIVE_ENTRIES` by least-recent use, and reloaded on startup after
+//! dropping rows whose archive is gone or has changed.
+
 use dashmap::DashMap;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait, QueryFilter,
+    Set, Statement,
+};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
-use std::sync::LazyLock;
-use std::time::{Duration, SystemTime};
+use std::sync::{LazyLock, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use crate::database::entities::archive_metadata_cache;
+use crate::database::DatabaseState;
+use crate::error::ApiResult;
+use crate::logger;
 
 /// Cached metadata for an archive file
 #[derive(Clone, Debug)]
@@ -10,7 +33,12 @@ pub struct ArchiveMetadata {
     // Retained for potential future cache inspection / diagnostics
     #[allow(dead_code)]
     pub file_count: usize,
-    pub cached_at: SystemTime,
+    /// Path the archive was last seen at, used to prune entries on startup
+    pub path: String,
+    pub file_size: u64,
+    /// Archive modification time (Unix seconds) when it was cached
+    pub modified_at: i64,
+    pub last_used: SystemTime,
 }
 
 /// Cached metadata for a directory
@@ -20,11 +48,15 @@ pub struct DirectoryMetadata {
     // Retained for potential future cache inspection / diagnostics
     #[allow(dead_code)]
     pub file_count: usize,
+    /// Newest modification time of any file inside the directory
+    pub newest_modified: Option<SystemTime>,
     pub cached_at: SystemTime,
     pub last_modified: SystemTime,
 }
 
-/// Global cache for archive metadata
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Global cache for archive metadata, keyed by content fingerprint
 /// Uses DashMap for thread-safe concurrent access without locks
 static ARCHIVE_CACHE: LazyLock<DashMap<String, ArchiveMetadata>> = LazyLock::new(DashMap::new);
 
@@ -34,20 +66,20 @@ static DIRECTORY_CACHE: LazyLock<DashMap<String, DirectoryMetadata>> = LazyLock:
 /// Cache TTL (Time To Live) - 5 minutes
 const CACHE_TTL: Duration = Duration::from_secs(300);
 
-/// Maximum number of entries in each cache to prevent unbounded memory growth
+/// Maximum number of entries in the directory cache to prevent unbounded memory growth
 const MAX_CACHE_SIZE: usize = 1000;
 
+/// Maximum number of archive entries kept in memory and on disk
+const MAX_ARCHIVE_ENTRIES: usize = 500;
+
+/// Bytes hashed from each end of an archive for its fingerprint
+const FINGERPRINT_CHUNK: u64 = 64 * 1024;
+
 /// Trait to extract `cached_at` from different cache entry types
 trait CachedEntry {
     fn cached_at(&self) -> SystemTime;
 }
 
-impl CachedEntry for ArchiveMetadata {
-    fn cached_at(&self) -> SystemTime {
-        self.cached_at
-    }
-}
-
 impl CachedEntry for DirectoryMetadata {
     fn cached_at(&self) -> SystemTime {
         self.cached_at
@@ -123,44 +155,280 @@ fn evict_expired_and_oldest<V: CachedEntry>(cache: &DashMap<String, V>) {
     }
 }
 
+/// Identity of an archive file: content fingerprint plus the size and
+/// modification time it had when read
+struct ArchiveKey {
+    fingerprint: String,
+    file_size: u64,
+    modified_at: i64,
+}
+
+impl ArchiveKey {
+    /// Hash the file size and the first and last `FINGERPRINT_CHUNK` bytes.
+    /// Returns None if the file cannot be read.
+    fn read(path: &Path) -> Option<Self> {
+        let mut file = fs::File::open(path).ok()?;
+        let metadata = file.metadata().ok()?;
+        if !metadata.is_file() {
+            return None;
+        }
+        let file_size = metadata.len();
+
+        let mut hasher = Sha256::new();
+        hasher.update(file_size.to_le_bytes());
+        let mut buffer = Vec::with_capacity(FINGERPRINT_CHUNK as usize);
+        (&mut file)
+            .take(FINGERPRINT_CHUNK)
+            .read_to_end(&mut buffer)
+            .ok()?;
+        hasher.update(&buffer);
+        if file_size > FINGERPRINT_CHUNK {
+            let tail_start = file_size
+                .saturating_sub(FINGERPRINT_CHUNK)
+                .max(FINGERPRINT_CHUNK);
+            file.seek(SeekFrom::Start(tail_start)).ok()?;
+            buffer.clear();
+            file.take(FINGERPRINT_CHUNK).read_to_end(&mut buffer).ok()?;
+            hasher.update(&buffer);
+        }
+
+        Some(Self {
+            fingerprint: format!("{:x}", hasher.finalize()),
+            file_size,
+            modified_at: modified_secs(&metadata),
+        })
+    }
+}
+
+fn modified_secs(metadata: &fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn unix_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
 /// Get cached metadata for an archive
 pub fn get_cached_metadata(path: &Path) -> Option<ArchiveMetadata> {
-    let key = path.to_string_lossy().to_string();
-
-    if let Some(entry) = ARCHIVE_CACHE.get(&key) {
-        let metadata = entry.value();
+    let Some(key) = ArchiveKey::read(path) else {
+        crate::performance::record_cache_miss();
+        return None;
+    };
 
-        // Check if cache is still valid
-        if let Ok(elapsed) = metadata.cached_at.elapsed() {
-            if elapsed < CACHE_TTL {
-                crate::performance::record_cache_hit();
-                return Some(metadata.clone());
-            } else {
-                // Cache expired, remove it
-                drop(entry);
-                ARCHIVE_CACHE.remove(&key);
-            }
+    let metadata = ARCHIVE_CACHE.get_mut(&key.fingerprint).map(|mut entry| {
+        // The same content may have moved since it was cached
+        entry.path = path.to_string_lossy().to_string();
+        entry.file_size = key.file_size;
+        entry.modified_at = key.modified_at;
+        entry.last_used = SystemTime::now();
+        entry.clone()
+    });
+
+    match metadata {
+        Some(metadata) => {
+            crate::performance::record_cache_hit();
+            persist_in_background(key.fingerprint, metadata.clone());
+            Some(metadata)
+        }
+        None => {
+            crate::performance::record_cache_miss();
+            None
         }
     }
-
-    crate::performance::record_cache_miss();
-    None
 }
 
 /// Store metadata in cache
-/// Automatically evicts oldest entries when cache exceeds size limit
+/// Evicts the least recently used entries when the cache is at capacity
 pub fn cache_metadata(path: &Path, uncompressed_size: u64, file_count: usize) {
-    let key = path.to_string_lossy().to_string();
+    let Some(key) = ArchiveKey::read(path) else {
+        return;
+    };
     let metadata = ArchiveMetadata {
         uncompressed_size,
         file_count,
-        cached_at: SystemTime::now(),
+        path: path.to_string_lossy().to_string(),
+        file_size: key.file_size,
+        modified_at: key.modified_at,
+        last_used: SystemTime::now(),
     };
 
-    // Evict old entries if cache is at capacity
-    evict_expired_and_oldest(&ARCHIVE_CACHE);
+    ARCHIVE_CACHE.insert(key.fingerprint.clone(), metadata.clone());
+    evict_least_recently_used();
+    persist_in_background(key.fingerprint, metadata);
+}
+
+/// Trim the archive cache to `MAX_ARCHIVE_ENTRIES` by last use
+fn evict_least_recently_used() {
+    let excess = ARCHIVE_CACHE.len().saturating_sub(MAX_ARCHIVE_ENTRIES);
+    if excess == 0 {
+        return;
+    }
+
+    let mut entries: Vec<(String, SystemTime)> = ARCHIVE_CACHE
+        .iter()
+        .map(|entry| (entry.key().clone(), entry.value().last_used))
+        .collect();
+    entries.sort_by_key(|(_, last_used)| *last_used);
+    for (key, _) in entries.into_iter().take(excess) {
+        ARCHIVE_CACHE.remove(&key);
+    }
+}
+
+/// Drop cached metadata for the archive at `path`, in memory and on disk.
+/// Matches by path and, if the file still exists, by its current fingerprint.
+/// Returns the number of persisted rows removed.
+pub async fn invalidate_archive(conn: &DatabaseConnection, path: &Path) -> ApiResult<u64> {
+    let path_key = path.to_string_lossy().to_string();
+    let fingerprint = ArchiveKey::read(path).map(|key| key.fingerprint);
+
+    ARCHIVE_CACHE.retain(|key, entry| {
+        entry.path != path_key && fingerprint.as_deref() != Some(key.as_str())
+    });
+
+    let mut condition = archive_metadata_cache::Column::Path.eq(path_key);
+    if let Some(fingerprint) = fingerprint {
+        condition = condition.or(archive_metadata_cache::Column::Fingerprint.eq(fingerprint));
+    }
+    let result = archive_metadata_cache::Entity::delete_many()
+        .filter(condition)
+        .exec(conn)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Register the app handle and load the persisted archive cache in the background
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle.clone());
+    tauri::async_runtime::spawn(async move {
+        let conn = app_handle.state::<DatabaseState>().get();
+        match load_persisted(&conn).await {
+            Ok((loaded, pruned)) => logger::log_info(
+                &format!(
+                    "Loaded {} archive cache entries ({} stale entries pruned)",
+                    loaded, pruned
+                ),
+                Some("cache"),
+            ),
+            Err(e) => logger::log_error(
+                &format!("Failed to load archive cache: {}", e),
+                Some("cache"),
+            ),
+        }
+    });
+}
+
+/// Persist an archive entry without blocking the caller. Does nothing before
+/// `init` (e.g. in tests).
+fn persist_in_background(fingerprint: String, metadata: ArchiveMetadata) {
+    let Some(app_handle) = APP_HANDLE.get() else {
+        return;
+    };
+    let conn = app_handle.state::<DatabaseState>().get();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = persist_entry(&conn, &fingerprint, &metadata).await {
+            logger::log_error(
+                &format!("Failed to persist archive cache entry: {}", e),
+                Some("cache"),
+            );
+        }
+    });
+}
+
+/// Upsert an archive entry and trim the table to `MAX_ARCHIVE_ENTRIES`
+async fn persist_entry(
+    conn: &DatabaseConnection,
+    fingerprint: &str,
+    metadata: &ArchiveMetadata,
+) -> ApiResult<()> {
+    let active = archive_metadata_cache::ActiveModel {
+        fingerprint: Set(fingerprint.to_string()),
+        path: Set(metadata.path.clone()),
+        file_size: Set(metadata.file_size as i64),
+        modified_at: Set(metadata.modified_at),
+        uncompressed_size: Set(metadata.uncompressed_size as i64),
+        file_count: Set(metadata.file_count as i64),
+        last_used_at: Set(unix_secs(metadata.last_used)),
+    };
+    archive_metadata_cache::Entity::insert(active)
+        .on_conflict(
+            OnConflict::column(archive_metadata_cache::Column::Fingerprint)
+                .update_columns([
+                    archive_metadata_cache::Column::Path,
+                    archive_metadata_cache::Column::FileSize,
+                    archive_metadata_cache::Column::ModifiedAt,
+                    archive_metadata_cache::Column::UncompressedSize,
+                    archive_metadata_cache::Column::FileCount,
+                    archive_metadata_cache::Column::LastUsedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(conn)
+        .await?;
+    trim_persisted(conn).await
+}
 
-    ARCHIVE_CACHE.insert(key, metadata);
+async fn trim_persisted(conn: &DatabaseConnection) -> ApiResult<()> {
+    conn.execute(Statement::from_sql_and_values(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM archive_metadata_cache WHERE fingerprint NOT IN ( \
+             SELECT fingerprint FROM archive_metadata_cache \
+             ORDER BY last_used_at DESC LIMIT ?)",
+        [(MAX_ARCHIVE_ENTRIES as i64).into()],
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Delete persisted rows whose archive no longer exists or no longer has the
+/// cached size and modification time, then load the rest into memory.
+/// Returns (loaded, pruned).
+async fn load_persisted(conn: &DatabaseConnection) -> ApiResult<(usize, usize)> {
+    let rows = archive_metadata_cache::Entity::find().all(conn).await?;
+
+    let mut stale = Vec::new();
+    let mut loaded = 0;
+    for row in rows {
+        let current = fs::metadata(&row.path)
+            .ok()
+            .filter(|m| m.is_file())
+            .map(|m| (m.len() as i64, modified_secs(&m)));
+        if current != Some((row.file_size, row.modified_at)) {
+            stale.push(row.fingerprint);
+            continue;
+        }
+
+        let last_used = UNIX_EPOCH + Duration::from_secs(row.last_used_at.max(0) as u64);
+        ARCHIVE_CACHE
+            .entry(row.fingerprint)
+            .or_insert_with(|| ArchiveMetadata {
+                uncompressed_size: row.uncompressed_size.max(0) as u64,
+                file_count: row.file_count.max(0) as usize,
+                path: row.path,
+                file_size: row.file_size as u64,
+                modified_at: row.modified_at,
+                last_used,
+            });
+        loaded += 1;
+    }
+
+    let pruned = stale.len();
+    for chunk in stale.chunks(500) {
+        archive_metadata_cache::Entity::delete_many()
+            .filter(archive_metadata_cache::Column::Fingerprint.is_in(chunk.to_vec()))
+            .exec(conn)
+            .await?;
+    }
+    trim_persisted(conn).await?;
+    evict_least_recently_used();
+
+    Ok((loaded, pruned))
 }
 
 /// Get cached directory metadata
@@ -200,7 +468,12 @@ pub fn get_cached_directory_metadata(path: &Path) -> Option<DirectoryMetadata> {
 
 /// Store directory metadata in cache
 /// Automatically evicts oldest entries when cache exceeds size limit
-pub fn cache_directory_metadata(path: &Path, total_size: u64, file_count: usize) {
+pub fn cache_directory_metadata(
+    path: &Path,
+    total_size: u64,
+    file_count: usize,
+    newest_modified: Option<SystemTime>,
+) {
     let key = path.to_string_lossy().to_string();
 
     // Get directory's last modified time
@@ -211,6 +484,7 @@ pub fn cache_directory_metadata(path: &Path, total_size: u64, file_count: usize)
     let metadata = DirectoryMetadata {
         total_size,
         file_count,
+        newest_modified,
         cached_at: SystemTime::now(),
         last_modified,
     };
@@ -232,12 +506,20 @@ pub fn clear_all_caches() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
     use std::path::PathBuf;
     use std::thread;
 
+    fn write_archive(dir: &Path, name: &str, contents: &[u8]) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
     #[test]
     fn test_cache_basic() {
-        let path = PathBuf::from("/test/archive.zip");
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_archive(dir.path(), "archive.zip", b"test_cache_basic");
 
         // Initially no cache
         assert!(get_cached_metadata(&path).is_none());
@@ -251,25 +533,31 @@ mod tests {
     }
 
     #[test]
-    fn test_cache_expiration() {
-        let path = PathBuf::from("/test/expire.zip");
-
-        // Cache with old timestamp
-        let old_metadata = ArchiveMetadata {
-            uncompressed_size: 500,
-            file_count: 10,
-            cached_at: SystemTime::now() - Duration::from_secs(400), // Older than TTL
-        };
-
-        ARCHIVE_CACHE.insert(path.to_string_lossy().to_string(), old_metadata);
-
-        // Should return None due to expiration
-        assert!(get_cached_metadata(&path).is_none());
+    fn test_cache_follows_content_not_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut contents = vec![0u8; 3 * FINGERPRINT_CHUNK as usize];
+        contents[..16].copy_from_slice(b"follows_content_");
+        let path = write_archive(dir.path(), "original.7z", &contents);
+        cache_metadata(&path, 500, 10);
+
+        // A moved archive still hits
+        let moved = dir.path().join("moved.7z");
+        fs::rename(&path, &moved).unwrap();
+        let cached = get_cached_metadata(&moved).unwrap();
+        assert_eq!(cached.uncompressed_size, 500);
+        assert_eq!(cached.path, moved.to_string_lossy());
+
+        // Same size, different tail: replaced in place, so it misses
+        let last = contents.len() - 1;
+        contents[last] = 1;
+        fs::write(&moved, &contents).unwrap();
+        assert!(get_cached_metadata(&moved).is_none());
     }
 
     #[test]
     fn test_cache_thread_safety() {
-        let path = PathBuf::from("/test/concurrent.zip");
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_archive(dir.path(), "concurrent.zip", b"test_cache_thread_safety");
 
         // Spawn multiple threads writing to cache
         let handles: Vec<_> = (0..10)
@@ -288,4 +576,43 @@ mod tests {
         // Should have cached data (last write wins)
         assert!(get_cached_metadata(&path).is_some());
     }
+
+    #[tokio::test]
+    async fn test_persisted_entries_pruned_and_invalidated() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let kept = write_archive(dir.path(), "kept.zip", b"persisted_kept");
+        let changed = write_archive(dir.path(), "changed.zip", b"persisted_changed");
+        let missing = write_archive(dir.path(), "missing.zip", b"persisted_missing");
+        for (path, size) in [(&kept, 1), (&changed, 2), (&missing, 3)] {
+            let key = ArchiveKey::read(path).unwrap();
+            let metadata = ArchiveMetadata {
+                uncompressed_size: size,
+                file_count: 1,
+                path: path.to_string_lossy().to_string(),
+                file_size: key.file_size,
+                modified_at: key.modified_at,
+                last_used: SystemTime::now(),
+            };
+            persist_entry(&conn, &key.fingerprint, &metadata)
+                .await
+                .unwrap();
+        }
+        fs::write(&changed, b"persisted_changed and grown").unwrap();
+        fs::remove_file(&missing).unwrap();
+
+        let (loaded, pruned) = load_persisted(&conn).await.unwrap();
+        assert_eq!((loaded, pruned), (1, 2));
+        assert_eq!(get_cached_metadata(&kept).unwrap().uncompressed_size, 1);
+
+        assert_eq!(invalidate_archive(&conn, &kept).await.unwrap(), 1);
+        assert!(get_cached_metadata(&kept).is_none());
+        let remaining = archive_metadata_cache::Entity::find()
+            .all(&conn)
+            .await
+            .unwrap();
+        assert!(remaining.is_empty());
+    }
 }
@@ -0,0 +1,181 @@
+//! Symlink-safe directory walking
+//!
+//! Walks default to not following symlinks (walkdir's default, and
+//! `DirEntry::file_type()` for manual `read_dir` traversals). The few walks that
+//! must follow links, e.g. scenery packages relocated into Custom Scenery via a
+//! symlink or junction, use [`LinkFollowingWalk`], which enters every physical
+//! directory at most once so a link back to a parent can't loop or inflate results.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::{DirEntry, WalkDir};
+
+use crate::logger;
+
+/// Identity of a directory on disk, independent of the path used to reach it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DirIdentity {
+    /// (device, inode) pair
+    #[cfg(unix)]
+    Inode(u64, u64),
+    /// Fully resolved path (used where std doesn't expose a stable file id)
+    #[cfg(not(unix))]
+    Canonical(PathBuf),
+}
+
+/// Resolve the identity of `path`, following symlinks
+pub fn dir_identity(path: &Path) -> Option<DirIdentity> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = fs::metadata(path).ok()?;
+        Some(DirIdentity::Inode(metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        fs::canonicalize(path).ok().map(DirIdentity::Canonical)
+    }
+}
+
+/// Set of directories already entered by a walk
+#[derive(Debug, Default)]
+pub struct VisitedDirs {
+    seen: HashSet<DirIdentity>,
+}
+
+impl VisitedDirs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `path` as visited. Returns `false` when the same physical directory
+    /// was already entered through another path. Directories whose identity can't
+    /// be resolved are treated as new.
+    pub fn first_visit(&mut self, path: &Path) -> bool {
+        match dir_identity(path) {
+            Some(identity) => self.seen.insert(identity),
+            None => true,
+        }
+    }
+}
+
+/// Whether the entry is a symlink (or junction) pointing at a directory
+pub fn is_symlinked_dir(entry: &fs::DirEntry) -> bool {
+    entry.file_type().map(|ft| ft.is_symlink()).unwrap_or(false) && entry.path().is_dir()
+}
+
+/// Depth-limited walk that follows symlinks but never enters the same directory twice
+pub struct LinkFollowingWalk {
+    inner: walkdir::IntoIter,
+    visited: VisitedDirs,
+    cycles: Vec<PathBuf>,
+    unreadable: Vec<PathBuf>,
+}
+
+impl LinkFollowingWalk {
+    pub fn new(root: &Path, max_depth: usize) -> Self {
+        Self {
+            inner: WalkDir::new(root)
+                .follow_links(true)
+                .max_depth(max_depth)
+                .into_iter(),
+            visited: VisitedDirs::new(),
+            cycles: Vec::new(),
+            unreadable: Vec::new(),
+        }
+    }
+
+    /// Paths that were not descended into because they lead back to a visited directory
+    pub fn cycles(&self) -> &[PathBuf] {
+        &self.cycles
+    }
+
+    /// Paths that were skipped because they couldn't be read
+    pub fn unreadable(&self) -> &[PathBuf] {
+        &self.unreadable
+    }
+
+    fn record_cycle(&mut self, path: PathBuf) {
+        logger::log_info(
+            &format!(
+                "Warning: symlink cycle at {}, not descending into it again",
+                path.display()
+            ),
+            Some("dir_walk"),
+        );
+        self.cycles.push(path);
+    }
+}
+
+impl Iterator for LinkFollowingWalk {
+    type Item = DirEntry;
+
+    fn next(&mut self) -> Option<DirEntry> {
+        loop {
+            match self.inner.next()? {
+                Ok(entry) => {
+                    if entry.file_type().is_dir() && !self.visited.first_visit(entry.path()) {
+                        self.inner.skip_current_dir();
+                        self.record_cycle(entry.path().to_path_buf());
+                        continue;
+                    }
+                    return Some(entry);
+                }
+                Err(e) => {
+                    // walkdir reports links to an ancestor as errors; everything else
+                    // (permissions, races with deletion) is skipped like before
+                    match (e.loop_ancestor(), e.path()) {
+                        (Some(_), Some(path)) => self.record_cycle(path.to_path_buf()),
+                        (None, Some(path)) => self.unreadable.push(path.to_path_buf()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::tempdir;
+
+    #[test]
+    fn link_following_walk_terminates_on_cycles_and_counts_files_once() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        fs::create_dir_all(root.join("a/b")).expect("failed to create dirs");
+        fs::write(root.join("a/b/file.txt"), "data").expect("failed to write file");
+        // Link back to the walk root and a second link to an already walked folder
+        symlink(root, root.join("a/b/to_root")).expect("failed to create loop link");
+        symlink(root.join("a"), root.join("alias_of_a")).expect("failed to create alias link");
+
+        let mut walk = LinkFollowingWalk::new(root, 64);
+        let files: Vec<PathBuf> = walk
+            .by_ref()
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .collect();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(walk.cycles().len(), 2);
+    }
+
+    #[test]
+    fn symlinked_dirs_are_recognized_without_following() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        fs::create_dir(root.join("real")).expect("failed to create dir");
+        symlink(root, root.join("real/loop")).expect("failed to create loop link");
+
+        let entries: Vec<fs::DirEntry> = fs::read_dir(root.join("real"))
+            .expect("failed to read dir")
+            .flatten()
+            .collect();
+        assert_eq!(entries.len(), 1);
+        assert!(is_symlinked_dir(&entries[0]));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::user_messages::{LocalizedError, UserMsg};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -29,14 +30,23 @@ pub enum ApiErrorCode {
     Cancelled,
     /// Disk space insufficient
     InsufficientSpace,
+    /// The disk filled up while writing
+    DiskFull,
+    /// Archive failed integrity checks (bad CRC, truncated or invalid structure)
+    ArchiveCorrupt,
     /// Path traversal attack detected
     SecurityViolation,
     /// Operation timeout
     Timeout,
+    /// Network request timed out
+    NetworkTimeout,
     /// Database error (SQLite operations)
     DatabaseError,
     /// Migration failed (schema upgrade failed)
     MigrationFailed,
+    /// The addon has an x-updater profile but no Skunkcrafts updater metadata,
+    /// which the addon updater doesn't support
+    UpdaterProfileUnsupported,
     /// Internal error (unexpected condition)
     Internal,
 }
@@ -55,10 +65,14 @@ impl fmt::Display for ApiErrorCode {
             ApiErrorCode::IncorrectPassword => write!(f, "incorrect_password"),
             ApiErrorCode::Cancelled => write!(f, "cancelled"),
             ApiErrorCode::InsufficientSpace => write!(f, "insufficient_space"),
+            ApiErrorCode::DiskFull => write!(f, "disk_full"),
+            ApiErrorCode::ArchiveCorrupt => write!(f, "archive_corrupt"),
             ApiErrorCode::SecurityViolation => write!(f, "security_violation"),
             ApiErrorCode::Timeout => write!(f, "timeout"),
+            ApiErrorCode::NetworkTimeout => write!(f, "network_timeout"),
             ApiErrorCode::DatabaseError => write!(f, "database_error"),
             ApiErrorCode::MigrationFailed => write!(f, "migration_failed"),
+            ApiErrorCode::UpdaterProfileUnsupported => write!(f, "updater_profile_unsupported"),
             ApiErrorCode::Internal => write!(f, "internal"),
         }
     }
@@ -74,6 +88,12 @@ pub struct ApiError {
     /// Optional additional details (stack trace, field name, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// Catalog key of `message`, for the frontend to show it localized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<String>,
+    /// Positional arguments of the catalog message
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub message_args: Vec<String>,
 }
 
 impl ApiError {
@@ -83,6 +103,21 @@ impl ApiError {
             code,
             message: message.into(),
             details: None,
+            message_key: None,
+            message_args: Vec::new(),
+        }
+    }
+
+    /// Create an API error from a catalog message, rendered in English with
+    /// its key and arguments attached
+    pub fn localized(code: ApiErrorCode, message: impl Into<LocalizedError>) -> Self {
+        let message = message.into();
+        Self {
+            code,
+            message: message.to_string(),
+            details: None,
+            message_key: Some(message.msg.key().to_string()),
+            message_args: message.args,
         }
     }
 
@@ -99,6 +134,8 @@ impl ApiError {
             code,
             message: message.into(),
             details: Some(details.into()),
+            message_key: None,
+            message_args: Vec::new(),
         }
     }
 
@@ -177,6 +214,21 @@ impl ApiError {
         Self::new(ApiErrorCode::Cancelled, message)
     }
 
+    /// Create a disk full error
+    pub fn disk_full(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::DiskFull, message)
+    }
+
+    /// Create an archive corrupt error
+    pub fn archive_corrupt(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::ArchiveCorrupt, message)
+    }
+
+    /// Create a network timeout error
+    pub fn network_timeout(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::NetworkTimeout, message)
+    }
+
     /// Create a database error
     pub fn database(message: impl Into<String>) -> Self {
         Self::new(ApiErrorCode::DatabaseError, message)
@@ -200,96 +252,236 @@ impl fmt::Display for ApiError {
 
 impl std::error::Error for ApiError {}
 
-/// Convert from std::io::Error to ApiError
-impl From<std::io::Error> for ApiError {
-    fn from(err: std::io::Error) -> Self {
-        let code = match err.kind() {
-            std::io::ErrorKind::NotFound => ApiErrorCode::NotFound,
-            std::io::ErrorKind::PermissionDenied => ApiErrorCode::PermissionDenied,
-            std::io::ErrorKind::AlreadyExists => ApiErrorCode::ConflictExists,
-            std::io::ErrorKind::TimedOut => ApiErrorCode::Timeout,
-            _ => ApiErrorCode::Internal,
-        };
-        ApiError::new(code, err.to_string())
+/// Error code for an I/O error, based on its kind and OS error number rather
+/// than the (localized) message
+fn io_error_code(err: &std::io::Error) -> ApiErrorCode {
+    // ENOSPC on Unix, ERROR_HANDLE_DISK_FULL / ERROR_DISK_FULL on Windows
+    #[cfg(windows)]
+    const DISK_FULL_OS_ERRORS: &[i32] = &[39, 112];
+    #[cfg(not(windows))]
+    const DISK_FULL_OS_ERRORS: &[i32] = &[28];
+
+    if err
+        .raw_os_error()
+        .is_some_and(|code| DISK_FULL_OS_ERRORS.contains(&code))
+    {
+        return ApiErrorCode::DiskFull;
+    }
+    match err.kind() {
+        std::io::ErrorKind::NotFound => ApiErrorCode::NotFound,
+        std::io::ErrorKind::PermissionDenied => ApiErrorCode::PermissionDenied,
+        std::io::ErrorKind::AlreadyExists => ApiErrorCode::ConflictExists,
+        std::io::ErrorKind::TimedOut => ApiErrorCode::Timeout,
+        _ => ApiErrorCode::Internal,
     }
 }
 
-/// Convert from anyhow::Error to ApiError
-impl From<anyhow::Error> for ApiError {
-    fn from(err: anyhow::Error) -> Self {
-        // Try to extract more specific error information
-        let message = err.to_string();
-        let message_lower = message.to_lowercase();
-
-        // Check for common error patterns and use appropriate convenience methods
-
-        // Password errors
-        if message_lower.contains("password") || message_lower.contains("encrypted") {
-            if message_lower.contains("wrong") || message_lower.contains("incorrect") {
-                return ApiError::incorrect_password(message);
-            }
-            return ApiError::password_required(message);
+/// Error code for a typed error somewhere in an anyhow chain, if one is recognized
+fn chain_error_code(err: &anyhow::Error) -> Option<ApiErrorCode> {
+    for cause in err.chain() {
+        if let Some(api_err) = cause.downcast_ref::<ApiError>() {
+            return Some(api_err.code.clone());
         }
-
-        // Cancellation errors
-        if message_lower.contains("cancelled")
-            || message_lower.contains("canceled")
-            || message_lower.contains("aborted")
+        if cause
+            .downcast_ref::<crate::scanner::PasswordRequiredError>()
+            .is_some()
+            || cause
+                .downcast_ref::<crate::scanner::NestedPasswordRequiredError>()
+                .is_some()
         {
-            return ApiError::cancelled(message);
+            return Some(ApiErrorCode::PasswordRequired);
         }
-
-        // Archive errors
-        if message_lower.contains("archive")
-            || message_lower.contains("zip")
-            || message_lower.contains("extract")
-            || message_lower.contains("decompress")
-        {
-            return ApiError::archive(message);
+        if let Some(zip_err) = cause.downcast_ref::<zip::result::ZipError>() {
+            return Some(match zip_err {
+                zip::result::ZipError::Io(io_err) => io_error_code(io_err),
+                zip::result::ZipError::InvalidPassword => ApiErrorCode::IncorrectPassword,
+                zip::result::ZipError::FileNotFound => ApiErrorCode::NotFound,
+                zip::result::ZipError::UnsupportedArchive(_) => ApiErrorCode::ArchiveError,
+                _ => ApiErrorCode::ArchiveCorrupt,
+            });
         }
-
-        // Disk space errors
-        if message_lower.contains("disk space")
-            || message_lower.contains("insufficient space")
-            || message_lower.contains("no space")
-            || message_lower.contains("storage full")
-        {
-            return ApiError::insufficient_space(message);
+        if let Some(http_err) = cause.downcast_ref::<reqwest::Error>() {
+            return Some(if http_err.is_timeout() {
+                ApiErrorCode::NetworkTimeout
+            } else {
+                ApiErrorCode::NetworkError
+            });
         }
+        if let Some(io_err) = cause.downcast_ref::<std::io::Error>() {
+            let code = io_error_code(io_err);
+            if code != ApiErrorCode::Internal {
+                return Some(code);
+            }
+        }
+    }
+    None
+}
 
-        // Corruption errors
-        if message_lower.contains("corrupt")
-            || message_lower.contains("malformed")
-            || message_lower.contains("invalid format")
-            || message_lower.contains("unexpected format")
-        {
-            return ApiError::corrupted(message);
+/// Error code for a message that carries no typed error, from common wording
+fn message_error_code(message: &str) -> ApiErrorCode {
+    let message_lower = message.to_lowercase();
+    let mentions_archive = [
+        "archive",
+        "zip",
+        ".rar",
+        " rar",
+        "7z",
+        "extract",
+        "decompress",
+    ]
+    .iter()
+    .any(|word| message_lower.contains(word));
+
+    // Password errors
+    if message_lower.contains("password") || message_lower.contains("encrypted") {
+        if message_lower.contains("wrong") || message_lower.contains("incorrect") {
+            return ApiErrorCode::IncorrectPassword;
         }
+        return ApiErrorCode::PasswordRequired;
+    }
+
+    // Cancellation errors
+    if message_lower.contains("cancelled")
+        || message_lower.contains("canceled")
+        || message_lower.contains("aborted")
+    {
+        return ApiErrorCode::Cancelled;
+    }
+
+    // Disk filled up during a write
+    if message_lower.contains("no space left")
+        || message_lower.contains("disk full")
+        || message_lower.contains("disk is full")
+    {
+        return ApiErrorCode::DiskFull;
+    }
+
+    // Archive integrity errors
+    if mentions_archive
+        && (message_lower.contains("crc")
+            || message_lower.contains("checksum")
+            || message_lower.contains("corrupt")
+            || message_lower.contains("truncated"))
+    {
+        return ApiErrorCode::ArchiveCorrupt;
+    }
+
+    // Archive errors
+    if mentions_archive {
+        return ApiErrorCode::ArchiveError;
+    }
+
+    // Network timeouts
+    if (message_lower.contains("timed out") || message_lower.contains("timeout"))
+        && (message_lower.contains("request")
+            || message_lower.contains("download")
+            || message_lower.contains("connect"))
+    {
+        return ApiErrorCode::NetworkTimeout;
+    }
+
+    // Disk space errors
+    if message_lower.contains("disk space")
+        || message_lower.contains("insufficient space")
+        || message_lower.contains("no space")
+        || message_lower.contains("storage full")
+    {
+        return ApiErrorCode::InsufficientSpace;
+    }
+
+    // Corruption errors
+    if message_lower.contains("corrupt")
+        || message_lower.contains("malformed")
+        || message_lower.contains("invalid format")
+        || message_lower.contains("unexpected format")
+    {
+        return ApiErrorCode::CorruptedData;
+    }
+
+    // Conflict errors
+    if message_lower.contains("already exists")
+        || message_lower.contains("conflict")
+        || message_lower.contains("duplicate")
+    {
+        return ApiErrorCode::ConflictExists;
+    }
+
+    // Not found errors
+    if message_lower.contains("not found") || message_lower.contains("does not exist") {
+        return ApiErrorCode::NotFound;
+    }
+
+    // Permission errors
+    if message_lower.contains("permission") || message_lower.contains("access denied") {
+        return ApiErrorCode::PermissionDenied;
+    }
+
+    // Security errors
+    if message_lower.contains("traversal") || message_lower.contains("security") {
+        return ApiErrorCode::SecurityViolation;
+    }
+
+    ApiErrorCode::Internal
+}
+
+/// Machine-readable code for an error, preferring typed errors in its chain
+/// over the wording of its message
+pub fn error_code(err: &anyhow::Error) -> ApiErrorCode {
+    chain_error_code(err).unwrap_or_else(|| message_error_code(&format!("{:#}", err)))
+}
+
+/// Convert from std::io::Error to ApiError
+impl From<std::io::Error> for ApiError {
+    fn from(err: std::io::Error) -> Self {
+        ApiError::new(io_error_code(&err), err.to_string())
+    }
+}
 
-        // Conflict errors
-        if message_lower.contains("already exists")
-            || message_lower.contains("conflict")
-            || message_lower.contains("duplicate")
+/// Catalog message of an error, unless context was added on top of it
+pub fn localized_message(err: &anyhow::Error) -> Option<&LocalizedError> {
+    err.chain()
+        .next()
+        .and_then(|outer| outer.downcast_ref::<LocalizedError>())
+}
+
+/// Convert from anyhow::Error to ApiError
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        // An ApiError sent through anyhow unchanged keeps its key and details
+        if let Some(api_err) = err
+            .chain()
+            .next()
+            .and_then(|outer| outer.downcast_ref::<ApiError>())
         {
-            return ApiError::conflict(message);
+            return api_err.clone();
         }
-
-        // Not found errors
-        if message_lower.contains("not found") || message_lower.contains("does not exist") {
-            return ApiError::not_found(message);
+        let code = error_code(&err);
+        match localized_message(&err) {
+            Some(message) => ApiError::localized(code, message.clone()),
+            None => ApiError::new(code, err.to_string()),
         }
+    }
+}
 
-        // Permission errors
-        if message_lower.contains("permission") || message_lower.contains("access denied") {
-            return ApiError::permission_denied(message);
-        }
+/// Convert from a catalog message, classified by its English wording
+impl From<LocalizedError> for ApiError {
+    fn from(message: LocalizedError) -> Self {
+        let code = message_error_code(&message.to_string());
+        ApiError::localized(code, message)
+    }
+}
 
-        // Security errors
-        if message_lower.contains("traversal") || message_lower.contains("security") {
-            return ApiError::security_violation(message);
-        }
+/// Convert from a plain error message, classified by its wording
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        ApiError::new(message_error_code(&message), message)
+    }
+}
 
-        ApiError::internal(message)
+/// Compatibility shim for helpers and callers that still deal in plain strings
+impl From<ApiError> for String {
+    fn from(err: ApiError) -> Self {
+        err.to_string()
     }
 }
 
@@ -325,6 +517,46 @@ impl<T> ToTauriError<T> for ApiResult<T> {
     }
 }
 
+/// Extension trait to add context to an error while keeping its code
+///
+/// # Example
+/// ```ignore
+/// fn my_command() -> ApiResult<()> {
+///     do_something().api_context("Failed to do something")
+/// }
+/// ```
+pub trait ApiContext<T> {
+    /// Prefix the error message with `context`, classifying the error if needed
+    fn api_context(self, context: &str) -> ApiResult<T>;
+
+    /// Wrap the error message in a catalog message whose only argument is
+    /// the original message
+    fn api_context_msg(self, context: UserMsg) -> ApiResult<T>;
+}
+
+impl<T, E: Into<ApiError>> ApiContext<T> for std::result::Result<T, E> {
+    fn api_context(self, context: &str) -> ApiResult<T> {
+        self.map_err(|e| {
+            let mut err = e.into();
+            err.message = format!("{}: {}", context, err.message);
+            // The catalog message no longer matches the full text
+            err.message_key = None;
+            err.message_args.clear();
+            err
+        })
+    }
+
+    fn api_context_msg(self, context: UserMsg) -> ApiResult<T> {
+        self.map_err(|e| {
+            let err = e.into();
+            ApiError {
+                details: err.details,
+                ..ApiError::localized(err.code, context.error_with([err.message]))
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -460,4 +692,112 @@ mod tests {
         let api_err: ApiError = anyhow_err.into();
         assert_eq!(api_err.code, ApiErrorCode::Internal);
     }
+
+    #[test]
+    fn test_io_error_codes_ignore_message_wording() {
+        let localized =
+            std::io::Error::new(std::io::ErrorKind::PermissionDenied, "Zugriff verweigert");
+        assert_eq!(
+            ApiError::from(localized).code,
+            ApiErrorCode::PermissionDenied
+        );
+
+        #[cfg(not(windows))]
+        let disk_full = std::io::Error::from_raw_os_error(28);
+        #[cfg(windows)]
+        let disk_full = std::io::Error::from_raw_os_error(112);
+        assert_eq!(ApiError::from(disk_full).code, ApiErrorCode::DiskFull);
+    }
+
+    #[test]
+    fn test_anyhow_chain_codes() {
+        let zip_err =
+            anyhow::Error::from(zip::result::ZipError::InvalidArchive("bad header".into()))
+                .context("Failed to open addon.zip");
+        assert_eq!(error_code(&zip_err), ApiErrorCode::ArchiveCorrupt);
+
+        let io_err = anyhow::Error::from(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "denied",
+        ))
+        .context("Failed to extract archive");
+        assert_eq!(ApiError::from(io_err).code, ApiErrorCode::PermissionDenied);
+
+        let api_err =
+            anyhow::Error::from(ApiError::disk_full("Disk full")).context("Install failed");
+        assert_eq!(error_code(&api_err), ApiErrorCode::DiskFull);
+
+        let unchanged = ApiError::from(anyhow::Error::from(ApiError::with_details(
+            ApiErrorCode::UpdaterProfileUnsupported,
+            "Unsupported",
+            "details",
+        )));
+        assert_eq!(unchanged.code, ApiErrorCode::UpdaterProfileUnsupported);
+        assert_eq!(unchanged.message, "Unsupported");
+        assert_eq!(unchanged.details.as_deref(), Some("details"));
+    }
+
+    #[test]
+    fn test_message_codes() {
+        assert_eq!(
+            ApiError::from("CRC mismatch while extracting archive".to_string()).code,
+            ApiErrorCode::ArchiveCorrupt
+        );
+        assert_eq!(
+            ApiError::from("Download request timed out".to_string()).code,
+            ApiErrorCode::NetworkTimeout
+        );
+        assert_eq!(
+            ApiError::from("No space left on device".to_string()).code,
+            ApiErrorCode::DiskFull
+        );
+    }
+
+    #[test]
+    fn test_api_context_keeps_code() {
+        let result: std::result::Result<(), std::io::Error> =
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "missing"));
+        let err = result
+            .api_context("Failed to read scenery_packs.ini")
+            .unwrap_err();
+        assert_eq!(err.code, ApiErrorCode::NotFound);
+        assert_eq!(err.message, "Failed to read scenery_packs.ini: missing");
+
+        let as_string: String = err.into();
+        assert!(as_string.starts_with("[not_found]"));
+    }
+
+    #[test]
+    fn test_localized_errors_keep_key_and_args() {
+        let err = anyhow::Error::from(UserMsg::AddonTargetNotFound.error_with(["/xp/Aircraft/A"]));
+        let api_err = ApiError::from(err);
+        assert_eq!(api_err.code, ApiErrorCode::NotFound);
+        assert_eq!(
+            api_err.message,
+            "Target path does not exist: /xp/Aircraft/A"
+        );
+        assert_eq!(
+            api_err.message_key.as_deref(),
+            Some("addon_target_not_found")
+        );
+        assert_eq!(api_err.message_args, vec!["/xp/Aircraft/A".to_string()]);
+
+        let wrapped =
+            anyhow::Error::from(UserMsg::XUpdaterMissingLogin.error()).context("Failed to scan");
+        assert!(ApiError::from(wrapped).message_key.is_none());
+
+        let result: std::result::Result<(), std::io::Error> =
+            Err(std::io::Error::new(std::io::ErrorKind::NotFound, "missing"));
+        let err = result
+            .api_context_msg(UserMsg::ResetSortOrderFailed)
+            .unwrap_err();
+        assert_eq!(err.code, ApiErrorCode::NotFound);
+        assert_eq!(err.message, "Failed to reset sort order: missing");
+        assert_eq!(err.message_key.as_deref(), Some("reset_sort_order_failed"));
+        assert_eq!(err.message_args, vec!["missing".to_string()]);
+
+        let json = serde_json::to_value(ApiError::internal("plain")).unwrap();
+        assert!(json.get("message_key").is_none());
+        assert!(json.get("message_args").is_none());
+    }
 }
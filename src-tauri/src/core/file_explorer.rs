@@ -0,0 +1,148 @@
+//! Reveal files in the system file manager
+//!
+//! Unlike opening a folder, revealing selects the item inside its parent:
+//!
+//! - Windows: `explorer /select,"<path>"`. Explorer parses its command line
+//!   itself, so the argument is passed raw with the path quoted; otherwise a
+//!   comma in the path ends it early.
+//! - macOS: `open -R <path>`.
+//! - Linux: `org.freedesktop.FileManager1.ShowItems` over D-Bus, which most
+//!   file managers implement. Without one, the parent folder is opened.
+
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+/// Drop the `\\?\` prefix of canonicalized Windows paths, which Explorer
+/// doesn't understand
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn strip_verbatim_prefix(path: &str) -> String {
+    if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", unc)
+    } else if let Some(local) = path.strip_prefix(r"\\?\") {
+        local.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+/// Raw Explorer argument selecting `path`
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn windows_select_arg(path: &str) -> String {
+    format!(
+        "/select,\"{}\"",
+        strip_verbatim_prefix(path).replace('/', "\\")
+    )
+}
+
+/// `file://` URI of an absolute path, with everything but unreserved
+/// characters and separators percent-encoded. dbus-send splits array
+/// arguments on commas, so those must not appear literally.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for &byte in path.as_os_str().as_encoded_bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+/// Open the parent folder of `path`, or `path` itself when it has none
+#[cfg(target_os = "linux")]
+fn open_parent(path: &Path) -> Result<()> {
+    let folder = path.parent().unwrap_or(path);
+    std::process::Command::new("xdg-open")
+        .arg(folder)
+        .spawn()
+        .map_err(|e| anyhow!("Failed to open folder: {}", e))?;
+    Ok(())
+}
+
+/// Show `path` selected in the system file manager
+pub fn reveal_in_explorer(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Err(anyhow!("Path not found: {}", path.display()));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+
+        std::process::Command::new("explorer")
+            .raw_arg(windows_select_arg(&path.to_string_lossy()))
+            .spawn()
+            .map_err(|e| anyhow!("Failed to reveal file: {}", e))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg("-R")
+            .arg(path)
+            .spawn()
+            .map_err(|e| anyhow!("Failed to reveal file: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        // --print-reply waits for the file manager, so a missing service shows
+        // up in the exit status
+        let shown = std::process::Command::new("dbus-send")
+            .args([
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.FileManager1",
+                "--type=method_call",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+            ])
+            .arg(format!("array:string:{}", file_uri(&absolute)))
+            .arg("string:")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        if !shown {
+            open_parent(&absolute)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_select_arg_quotes_spaces_and_commas() {
+        assert_eq!(
+            windows_select_arg(r"C:\X-Plane 12\Aircraft\A320, Neo\liveries\Red, White"),
+            r#"/select,"C:\X-Plane 12\Aircraft\A320, Neo\liveries\Red, White""#
+        );
+        assert_eq!(
+            windows_select_arg(r"\\?\D:\X-Plane 12\Log.txt"),
+            r#"/select,"D:\X-Plane 12\Log.txt""#
+        );
+        assert_eq!(
+            windows_select_arg(r"\\?\UNC\nas\sims\X-Plane 12/Log.txt"),
+            r#"/select,"\\nas\sims\X-Plane 12\Log.txt""#
+        );
+    }
+
+    #[test]
+    fn test_file_uri_encodes_separating_characters() {
+        assert_eq!(
+            file_uri(Path::new("/home/pilot/X-Plane 12/a,b/Log.txt")),
+            "file:///home/pilot/X-Plane%2012/a%2Cb/Log.txt"
+        );
+        assert_eq!(
+            file_uri(Path::new("/sim/Ünïcode")),
+            "file:///sim/%C3%9Cn%C3%AFcode"
+        );
+    }
+}
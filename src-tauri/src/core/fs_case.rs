@@ -0,0 +1,496 @@
+//! Filesystem case-sensitivity helpers
+//!
+//! Archives don't always agree with an installed copy on folder casing
+//! (`Objects/` vs `objects/`). On case-insensitive volumes (the macOS and
+//! Windows defaults) both names refer to the same folder, so the existing
+//! on-disk spelling is reused. On case-sensitive volumes they become two
+//! parallel folders, which is reported as a warning.
+//!
+//! Archives made on case-sensitive systems can also hold two files that differ
+//! only by case (`Textures/foo.png` and `textures/Foo.png`). Extracted to a
+//! case-insensitive volume, the second silently overwrites the first; see
+//! [`find_case_collisions`].
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+use crate::logger;
+
+/// Probe results per volume, so each volume is probed once per session
+static VOLUME_CASE_INSENSITIVE: LazyLock<Mutex<HashMap<VolumeId, bool>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum VolumeId {
+    #[cfg(unix)]
+    Device(u64),
+    #[cfg(not(unix))]
+    Root(String),
+}
+
+/// Nearest ancestor of `path` (including itself) that is an existing directory
+fn existing_dir(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|p| p.is_dir())
+}
+
+fn volume_id(dir: &Path) -> Option<VolumeId> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        fs::metadata(dir).ok().map(|m| VolumeId::Device(m.dev()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let canonical = fs::canonicalize(dir).ok()?;
+        let root: PathBuf = canonical
+            .components()
+            .take_while(|c| matches!(c, Component::Prefix(_) | Component::RootDir))
+            .collect();
+        Some(VolumeId::Root(root.to_string_lossy().to_lowercase()))
+    }
+}
+
+/// Whether `a` and `b` live on the same volume; `None` when either can't be
+/// read. Like [`is_case_insensitive`], paths that don't exist yet are resolved
+/// to their nearest existing ancestor.
+pub fn same_volume(a: &Path, b: &Path) -> Option<bool> {
+    let a = volume_id(existing_dir(a)?)?;
+    let b = volume_id(existing_dir(b)?)?;
+    Some(a == b)
+}
+
+/// Whether names on the volume holding `path` are matched case-insensitively.
+/// `path` doesn't need to exist; its nearest existing ancestor is probed.
+pub fn is_case_insensitive(path: &Path) -> bool {
+    let Some(dir) = existing_dir(path) else {
+        return default_case_insensitive();
+    };
+    let Some(volume) = volume_id(dir) else {
+        return default_case_insensitive();
+    };
+
+    if let Some(&cached) = VOLUME_CASE_INSENSITIVE.lock().unwrap().get(&volume) {
+        return cached;
+    }
+
+    let result = probe_case_insensitive(dir).unwrap_or_else(|e| {
+        logger::log_info(
+            &format!(
+                "Warning: could not probe case sensitivity in {}: {}",
+                dir.display(),
+                e
+            ),
+            Some("fs_case"),
+        );
+        default_case_insensitive()
+    });
+    crate::log_debug!(
+        &format!(
+            "Volume of {} is case-{}",
+            dir.display(),
+            if result { "insensitive" } else { "sensitive" }
+        ),
+        "fs_case"
+    );
+
+    VOLUME_CASE_INSENSITIVE
+        .lock()
+        .unwrap()
+        .insert(volume, result);
+    result
+}
+
+/// Platform default when a volume can't be probed (read-only, permissions)
+fn default_case_insensitive() -> bool {
+    cfg!(any(windows, target_os = "macos"))
+}
+
+/// Create a lowercase marker file in `dir` and check whether its uppercase name resolves
+fn probe_case_insensitive(dir: &Path) -> io::Result<bool> {
+    let name = format!(".xfast-case-probe-{}", uuid::Uuid::new_v4().simple());
+    let probe = dir.join(&name);
+    fs::File::create(&probe)?;
+    let result = fs::symlink_metadata(dir.join(name.to_uppercase())).is_ok();
+    let _ = fs::remove_file(&probe);
+    Ok(result)
+}
+
+/// Name of a directory in `dir` that matches `name` only when ignoring case.
+/// Returns `None` if there is no such directory, or if `name` itself exists exactly.
+pub fn find_case_variant(dir: &Path, name: &OsStr) -> Option<OsString> {
+    let wanted = name.to_string_lossy().to_lowercase();
+    let mut variant = None;
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let entry_name = entry.file_name();
+        if entry_name == name {
+            return None;
+        }
+        if variant.is_none()
+            && entry_name.to_string_lossy().to_lowercase() == wanted
+            && entry.path().is_dir()
+        {
+            variant = Some(entry_name);
+        }
+    }
+    variant
+}
+
+/// On case-insensitive volumes, rewrite the components of `relative` that already exist
+/// under `root` with a different casing to their on-disk spelling, returning the full
+/// path. Returns `None` when nothing differs or the volume is case-sensitive (where the
+/// other casing names a different folder).
+pub fn resolve_existing_case(root: &Path, relative: &Path) -> Option<PathBuf> {
+    if !is_case_insensitive(root) {
+        return None;
+    }
+    resolve_case_components(root, relative)
+}
+
+fn resolve_case_components(root: &Path, relative: &Path) -> Option<PathBuf> {
+    let mut resolved = root.to_path_buf();
+    let mut changed = false;
+    let mut components = relative.components();
+
+    while let Some(component) = components.next() {
+        let Component::Normal(name) = component else {
+            resolved.push(component);
+            continue;
+        };
+        if let Some(variant) = find_case_variant(&resolved, name) {
+            resolved.push(variant);
+            changed = true;
+            continue;
+        }
+        let exists = resolved.join(name).exists();
+        resolved.push(name);
+        if !exists {
+            // Nothing below a missing folder can exist either
+            resolved.extend(components.by_ref());
+            break;
+        }
+    }
+
+    changed.then_some(resolved)
+}
+
+/// Directory created by an install under a different casing than an existing one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseVariant {
+    /// Path the archive asked for, relative to the install root
+    pub requested: PathBuf,
+    /// Existing sibling with different casing, relative to the install root
+    pub existing: PathBuf,
+}
+
+/// Tracks directories an install is about to create inside an existing addon folder
+/// and reports those that differ only by case from an existing directory.
+///
+/// On case-insensitive volumes the existing folder is reused by the OS, so the
+/// mapping is only logged. On case-sensitive volumes a parallel folder would be
+/// created, which is logged as a warning. Directories are checked once each, so
+/// it's cheap to call for every extracted file, including from rayon workers.
+pub struct CaseVariantTracker {
+    root: PathBuf,
+    active: bool,
+    case_insensitive: bool,
+    checked: Mutex<HashSet<PathBuf>>,
+}
+
+impl CaseVariantTracker {
+    /// Tracker for installs into `root`. Inactive when `root` doesn't exist yet,
+    /// since a fresh folder can't clash with anything.
+    pub fn new(root: &Path) -> Self {
+        let active = root.is_dir();
+        Self {
+            root: root.to_path_buf(),
+            active,
+            case_insensitive: active && is_case_insensitive(root),
+            checked: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Check every directory leading to `relative_dir` before it is created.
+    /// Returns the case variants this call found; each is logged as well.
+    pub fn check_dir(&self, relative_dir: &Path) -> Vec<CaseVariant> {
+        let mut found = Vec::new();
+        if !self.active {
+            return found;
+        }
+
+        // Holding the lock for the whole walk keeps a parallel worker from creating
+        // a directory between another worker's check and its recording
+        let mut checked = self.checked.lock().unwrap();
+        let mut parent = PathBuf::new();
+        for component in relative_dir.components() {
+            let Component::Normal(name) = component else {
+                continue;
+            };
+            let current = parent.join(name);
+            if checked.insert(current.clone()) {
+                if let Some(existing) = find_case_variant(&self.root.join(&parent), name) {
+                    let variant = CaseVariant {
+                        requested: current.clone(),
+                        existing: parent.join(existing),
+                    };
+                    self.log(&variant);
+                    found.push(variant);
+                }
+            }
+            parent = current;
+        }
+        found
+    }
+
+    /// Check the parent directories of a file about to be written
+    pub fn check_file(&self, relative_file: &Path) -> Vec<CaseVariant> {
+        match relative_file.parent() {
+            Some(parent) => self.check_dir(parent),
+            None => Vec::new(),
+        }
+    }
+
+    fn log(&self, variant: &CaseVariant) {
+        if self.case_insensitive {
+            logger::log_info(
+                &format!(
+                    "Reusing existing folder '{}' for '{}' in {}",
+                    variant.existing.display(),
+                    variant.requested.display(),
+                    self.root.display()
+                ),
+                Some("fs_case"),
+            );
+        } else {
+            logger::log_info(
+                &format!(
+                    "Warning: installing '{}' next to existing '{}' in {} creates two folders that differ only by case",
+                    variant.requested.display(),
+                    variant.existing.display(),
+                    self.root.display()
+                ),
+                Some("fs_case"),
+            );
+        }
+    }
+}
+
+/// Two archive files whose paths differ only by case
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseCollision {
+    /// Earlier entry, which keeps its path
+    pub kept: String,
+    /// Later entry that would overwrite `kept` on a case-insensitive volume
+    pub colliding: String,
+    /// Unused path `colliding` can be extracted to instead
+    pub renamed: String,
+}
+
+impl std::fmt::Display for CaseCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' and '{}'", self.kept, self.colliding)
+    }
+}
+
+/// Find files in `paths` (archive order, '/'-separated) that differ only by case
+/// from an earlier file. Folders differing by case are not reported, since their
+/// contents merge. Each colliding path gets a rename target with a `_caseN` suffix
+/// on the file stem that doesn't clash with any other path.
+pub fn find_case_collisions<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<CaseCollision> {
+    let paths: Vec<&str> = paths.into_iter().collect();
+    let mut taken: HashSet<String> = paths.iter().map(|p| p.to_lowercase()).collect();
+    let mut first_seen: HashMap<String, &str> = HashMap::new();
+    let mut collisions = Vec::new();
+
+    for path in paths {
+        let kept = *first_seen.entry(path.to_lowercase()).or_insert(path);
+        // Exact duplicates overwrite each other on any volume
+        if kept == path {
+            continue;
+        }
+
+        let (parent, name) = match path.rsplit_once('/') {
+            Some((parent, name)) => (Some(parent), name),
+            None => (None, path),
+        };
+        let (stem, extension) = match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+            _ => (name, None),
+        };
+        let renamed = (2..)
+            .map(|n| {
+                let mut candidate = String::new();
+                if let Some(parent) = parent {
+                    candidate.push_str(parent);
+                    candidate.push('/');
+                }
+                candidate.push_str(&format!("{}_case{}", stem, n));
+                if let Some(extension) = extension {
+                    candidate.push('.');
+                    candidate.push_str(extension);
+                }
+                candidate
+            })
+            .find(|candidate| taken.insert(candidate.to_lowercase()))
+            .expect("an unused suffix always exists");
+
+        collisions.push(CaseCollision {
+            kept: kept.to_string(),
+            colliding: path.to_string(),
+            renamed,
+        });
+    }
+
+    collisions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn probe_matches_actual_filesystem_behavior_and_cleans_up() {
+        let temp = tempdir().expect("failed to create tempdir");
+        fs::write(temp.path().join("lower"), "x").expect("failed to write file");
+        let expected = temp.path().join("LOWER").exists();
+
+        assert_eq!(
+            probe_case_insensitive(temp.path()).expect("probe should succeed"),
+            expected
+        );
+        // Cached per volume, so a second query agrees without re-probing
+        assert_eq!(is_case_insensitive(temp.path()), expected);
+        assert_eq!(
+            is_case_insensitive(&temp.path().join("missing/child")),
+            expected
+        );
+
+        let leftovers: Vec<_> = fs::read_dir(temp.path())
+            .unwrap()
+            .flatten()
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with(".xfast-case-probe")
+            })
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn find_case_variant_ignores_exact_matches_and_files() {
+        let temp = tempdir().expect("failed to create tempdir");
+        fs::create_dir(temp.path().join("objects")).unwrap();
+        fs::write(temp.path().join("readme.txt"), "x").unwrap();
+
+        assert_eq!(
+            find_case_variant(temp.path(), OsStr::new("Objects")),
+            Some(OsString::from("objects"))
+        );
+        assert_eq!(find_case_variant(temp.path(), OsStr::new("objects")), None);
+        assert_eq!(
+            find_case_variant(temp.path(), OsStr::new("README.TXT")),
+            None
+        );
+        assert_eq!(find_case_variant(temp.path(), OsStr::new("textures")), None);
+    }
+
+    #[test]
+    fn resolve_case_components_maps_existing_folders_to_disk_casing() {
+        let temp = tempdir().expect("failed to create tempdir");
+        fs::create_dir_all(temp.path().join("Aircraft/a320neo/objects")).unwrap();
+
+        let resolved = resolve_case_components(
+            temp.path(),
+            Path::new("Aircraft/A320neo/Objects/new/file.obj"),
+        )
+        .expect("casing should be remapped");
+        assert_eq!(
+            resolved,
+            temp.path().join("Aircraft/a320neo/objects/new/file.obj")
+        );
+
+        assert_eq!(
+            resolve_case_components(temp.path(), Path::new("Aircraft/a320neo/objects")),
+            None
+        );
+    }
+
+    #[test]
+    fn tracker_records_each_case_variant_once() {
+        let temp = tempdir().expect("failed to create tempdir");
+        fs::create_dir_all(temp.path().join("objects/trees")).unwrap();
+
+        let tracker = CaseVariantTracker::new(temp.path());
+        let variants: Vec<_> = [
+            tracker.check_file(Path::new("Objects/a.obj")),
+            tracker.check_file(Path::new("Objects/b.obj")),
+            tracker.check_dir(Path::new("objects/Trees")),
+            tracker.check_dir(Path::new("textures")),
+        ]
+        .concat();
+
+        assert_eq!(
+            variants,
+            vec![
+                CaseVariant {
+                    requested: PathBuf::from("Objects"),
+                    existing: PathBuf::from("objects"),
+                },
+                CaseVariant {
+                    requested: PathBuf::from("objects/Trees"),
+                    existing: PathBuf::from("objects/trees"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tracker_is_inactive_for_new_install_roots() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let tracker = CaseVariantTracker::new(&temp.path().join("fresh"));
+        assert!(tracker.check_dir(Path::new("Objects")).is_empty());
+    }
+
+    #[test]
+    fn case_collisions_are_reported_once_per_later_file() {
+        let collisions = find_case_collisions([
+            "Textures/foo.png",
+            "textures/bar.png",
+            "textures/Foo.png",
+            "TEXTURES/FOO.PNG",
+            "textures/foo_case2.png",
+            "Readme",
+            "readme",
+            "Textures/foo.png",
+        ]);
+
+        assert_eq!(
+            collisions,
+            vec![
+                CaseCollision {
+                    kept: "Textures/foo.png".to_string(),
+                    colliding: "textures/Foo.png".to_string(),
+                    // `_case2` is taken by a real entry
+                    renamed: "textures/Foo_case3.png".to_string(),
+                },
+                CaseCollision {
+                    kept: "Textures/foo.png".to_string(),
+                    colliding: "TEXTURES/FOO.PNG".to_string(),
+                    renamed: "TEXTURES/FOO_case4.PNG".to_string(),
+                },
+                CaseCollision {
+                    kept: "Readme".to_string(),
+                    colliding: "readme".to_string(),
+                    renamed: "readme_case2".to_string(),
+                },
+            ]
+        );
+        assert!(find_case_collisions(["Objects/a.obj", "objects/b.obj"]).is_empty());
+    }
+}
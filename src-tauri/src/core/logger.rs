@@ -1,13 +1,14 @@
 use chrono::Local;
+use std::collections::VecDeque;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{LazyLock, Mutex};
 
 use crate::app_dirs;
 
-const MAX_LOG_SIZE: u64 = 3 * 1024 * 1024; // 3MB
-const TRIM_TARGET_SIZE: u64 = 2 * 1024 * 1024; // Trim to 2MB when exceeds max
+const MAX_LOG_SIZE: u64 = 5 * 1024 * 1024; // 5MB per file
+const MAX_LOG_FILES: usize = 5; // Current file plus 4 rotated ones (.1 = newest)
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LogLevel {
@@ -130,8 +131,12 @@ impl LoggerInner {
             return;
         }
 
-        // Rotate if needed before writing (skip for first log as we're truncating anyway)
-        if !self.is_first_log {
+        // The previous session's log is rotated away on the first write so it stays
+        // available (e.g. for support bundles) instead of being overwritten
+        if self.is_first_log {
+            self.rotate_previous_session();
+            self.is_first_log = false;
+        } else {
             self.rotate_if_needed();
         }
 
@@ -151,21 +156,12 @@ impl LoggerInner {
             timestamp, level_str, ctx, loc, message
         );
 
-        // On first log, truncate the file to start fresh; otherwise append
-        let mut options = OpenOptions::new();
-        options.create(true);
-        if self.is_first_log {
-            options.write(true).truncate(true);
-        } else {
-            options.append(true);
-        }
-
-        match options.open(&self.log_path) {
+        match OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+        {
             Ok(mut file) => {
-                if self.is_first_log {
-                    self.is_first_log = false;
-                }
-
                 if let Err(e) = file.write_all(line.as_bytes()) {
                     eprintln!("Failed to write log: {}", e);
                 }
@@ -179,74 +175,32 @@ impl LoggerInner {
     fn rotate_if_needed(&self) {
         if let Ok(metadata) = fs::metadata(&self.log_path) {
             if metadata.len() > MAX_LOG_SIZE {
-                self.trim_log_file();
+                rotate_log_files(&self.log_path);
             }
         }
     }
 
-    fn trim_log_file(&self) {
-        // Read entire file
-        let content = match fs::read_to_string(&self.log_path) {
-            Ok(c) => c,
-            Err(_) => return,
-        };
-
-        let lines: Vec<&str> = content.lines().collect();
-        if lines.is_empty() {
-            return;
-        }
-
-        // Calculate how many bytes we need to remove
-        let current_size = content.len() as u64;
-        if current_size <= TRIM_TARGET_SIZE {
-            return;
-        }
-
-        let bytes_to_remove = current_size - TRIM_TARGET_SIZE;
-
-        // Find how many lines to skip from the beginning
-        let mut bytes_counted: u64 = 0;
-        let mut lines_to_skip = 0;
-
-        for line in &lines {
-            bytes_counted += line.len() as u64 + 1; // +1 for newline
-            lines_to_skip += 1;
-            if bytes_counted >= bytes_to_remove {
-                break;
+    fn rotate_previous_session(&self) {
+        if let Ok(metadata) = fs::metadata(&self.log_path) {
+            if metadata.len() > 0 {
+                rotate_log_files(&self.log_path);
             }
         }
-
-        // Keep the remaining lines
-        let remaining_lines: Vec<&str> = lines.into_iter().skip(lines_to_skip).collect();
-        let new_content = remaining_lines.join("\n") + "\n";
-
-        // Write back
-        if let Err(e) = fs::write(&self.log_path, new_content) {
-            eprintln!("Failed to trim log file: {}", e);
-        }
     }
 
     fn read_recent_lines(&self, count: usize) -> Vec<String> {
-        let file = match File::open(&self.log_path) {
-            Ok(f) => f,
-            Err(_) => return Vec::new(),
-        };
-
-        let reader = BufReader::new(file);
-        let all_lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
-
-        // Return last N lines
-        let start = if all_lines.len() > count {
-            all_lines.len() - count
-        } else {
-            0
-        };
-
-        all_lines[start..].to_vec()
+        read_recent_lines_from(&log_files_oldest_first(&self.log_path), count)
     }
 
     fn read_all(&self) -> String {
-        fs::read_to_string(&self.log_path).unwrap_or_default()
+        log_files_oldest_first(&self.log_path)
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .collect()
+    }
+
+    fn get_log_files(&self) -> Vec<PathBuf> {
+        log_files_oldest_first(&self.log_path)
     }
 
     fn get_log_path(&self) -> PathBuf {
@@ -261,6 +215,66 @@ impl LoggerInner {
     }
 }
 
+/// Path of the `index`-th rotated file (`xfastmanager.log.1` is the newest)
+fn rotated_log_path(log_path: &Path, index: usize) -> PathBuf {
+    let mut name = log_path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Shift `log` -> `log.1` -> `log.2` ..., dropping the oldest file
+fn rotate_log_files(log_path: &Path) {
+    let oldest = rotated_log_path(log_path, MAX_LOG_FILES - 1);
+    let _ = fs::remove_file(&oldest);
+
+    for index in (1..MAX_LOG_FILES - 1).rev() {
+        let from = rotated_log_path(log_path, index);
+        if from.exists() {
+            let _ = fs::rename(&from, rotated_log_path(log_path, index + 1));
+        }
+    }
+
+    if let Err(e) = fs::rename(log_path, rotated_log_path(log_path, 1)) {
+        eprintln!("Failed to rotate log file: {}", e);
+    }
+}
+
+/// Existing log files in chronological order (oldest rotated file first, current file last)
+fn log_files_oldest_first(log_path: &Path) -> Vec<PathBuf> {
+    (1..MAX_LOG_FILES)
+        .rev()
+        .map(|index| rotated_log_path(log_path, index))
+        .chain(std::iter::once(log_path.to_path_buf()))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Last `count` lines across `files`, walking back from the newest file only as far as needed
+fn read_recent_lines_from(files: &[PathBuf], count: usize) -> Vec<String> {
+    let mut recent: VecDeque<String> = VecDeque::with_capacity(count);
+
+    for path in files.iter().rev() {
+        if recent.len() >= count {
+            break;
+        }
+
+        let file = match File::open(path) {
+            Ok(f) => f,
+            Err(_) => continue,
+        };
+        let lines: Vec<String> = BufReader::new(file).lines().map_while(Result::ok).collect();
+
+        for line in lines.into_iter().rev() {
+            if recent.len() >= count {
+                break;
+            }
+            recent.push_front(line);
+        }
+    }
+
+    recent.into()
+}
+
 static LOGGER: LazyLock<Mutex<LoggerInner>> = LazyLock::new(|| Mutex::new(LoggerInner::new()));
 
 // Public API
@@ -348,6 +362,15 @@ pub fn get_all_logs() -> String {
     }
 }
 
+/// All log files on disk, oldest first
+pub fn get_log_files() -> Vec<PathBuf> {
+    if let Ok(logger) = LOGGER.lock() {
+        logger.get_log_files()
+    } else {
+        Vec::new()
+    }
+}
+
 pub fn get_log_path() -> PathBuf {
     if let Ok(logger) = LOGGER.lock() {
         logger.get_log_path()
@@ -363,3 +386,44 @@ pub fn get_log_folder() -> PathBuf {
         PathBuf::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rotation_keeps_bounded_history_and_reads_in_order() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let log_path = temp.path().join("xfastmanager.log");
+
+        for session in 0..(MAX_LOG_FILES + 2) {
+            fs::write(
+                &log_path,
+                format!("session {} a\nsession {} b\n", session, session),
+            )
+            .expect("failed to write log");
+            if session < MAX_LOG_FILES + 1 {
+                rotate_log_files(&log_path);
+            }
+        }
+
+        let files = log_files_oldest_first(&log_path);
+        assert_eq!(files.len(), MAX_LOG_FILES);
+        assert_eq!(files.last(), Some(&log_path));
+        assert!(!rotated_log_path(&log_path, MAX_LOG_FILES).exists());
+
+        let recent = read_recent_lines_from(&files, 3);
+        assert_eq!(
+            recent,
+            vec![
+                format!("session {} b", MAX_LOG_FILES),
+                format!("session {} a", MAX_LOG_FILES + 1),
+                format!("session {} b", MAX_LOG_FILES + 1),
+            ]
+        );
+
+        let oldest = fs::read_to_string(&files[0]).expect("failed to read oldest log");
+        assert!(oldest.starts_with("session 2 a"));
+    }
+}
@@ -1,10 +1,29 @@
 //! Shared path validation utilities
 //!
 //! Provides helpers for preventing path traversal attacks by canonicalizing
-//! paths and verifying they remain within an expected base directory.
+//! paths and verifying they remain within an expected base directory, plus
+//! directory link helpers (junctions on Windows, symlinks elsewhere).
+//!
+//! Users often move Custom Scenery, Aircraft or Resources to another drive and
+//! link them back into the X-Plane folder. The canonical locations of those
+//! roots are trusted bases in their own right, so paths below them validate
+//! even when they resolve outside the X-Plane root.
 
+use std::fs;
 use std::io;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+
+/// X-Plane folders that may be links to another location. Nested roots come
+/// before their parents so they match first.
+pub const TRUSTED_XPLANE_ROOTS: &[&str] = &[
+    "Resources/plugins",
+    "Resources",
+    "Custom Scenery",
+    "Global Scenery",
+    "Custom Data",
+    "Aircraft",
+];
 
 /// Canonicalize `candidate` and verify it is contained within `base`.
 ///
@@ -22,3 +41,270 @@ pub fn validate_child_path(base: &Path, candidate: &Path) -> io::Result<PathBuf>
     }
     Ok(canonical_candidate)
 }
+
+/// Canonical X-Plane root followed by the canonical form of each existing trusted root
+pub fn trusted_xplane_bases(xplane_root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut bases = vec![xplane_root.canonicalize()?];
+    for name in TRUSTED_XPLANE_ROOTS {
+        if let Ok(base) = xplane_root.join(name).canonicalize() {
+            if !bases.contains(&base) {
+                bases.push(base);
+            }
+        }
+    }
+    Ok(bases)
+}
+
+/// [`validate_child_path`] with the X-Plane root as base that also accepts
+/// paths inside trusted roots linked to another location. The X-Plane root
+/// itself is rejected.
+///
+/// Returns the canonical form of `candidate`, which must exist.
+pub fn validate_xplane_child_path(xplane_root: &Path, candidate: &Path) -> io::Result<PathBuf> {
+    let canonical_candidate = candidate.canonicalize()?;
+    let bases = trusted_xplane_bases(xplane_root)?;
+    if canonical_candidate == bases[0] {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "path is the X-Plane folder itself",
+        ));
+    }
+    if bases
+        .iter()
+        .any(|base| canonical_candidate.starts_with(base))
+    {
+        return Ok(canonical_candidate);
+    }
+    Err(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "path traversal attempt detected",
+    ))
+}
+
+/// Validate an install target that may not exist yet. It must lie inside a
+/// trusted root, written either below the X-Plane path or below the root's
+/// canonical location, with only plain folder names after the root. Folders
+/// below the root are not resolved, so existing links there (such as a
+/// scenery pack linked from another drive) can still be reinstalled.
+///
+/// Returns the target below the canonical root.
+pub fn validate_xplane_target_path(xplane_root: &Path, target: &Path) -> io::Result<PathBuf> {
+    for name in TRUSTED_XPLANE_ROOTS {
+        let root = xplane_root.join(name);
+        // A root that doesn't exist yet is created below the X-Plane root
+        let Ok(canonical_root) = root
+            .canonicalize()
+            .or_else(|_| xplane_root.canonicalize().map(|r| r.join(name)))
+        else {
+            continue;
+        };
+        let Some(relative) = target
+            .strip_prefix(&root)
+            .or_else(|_| target.strip_prefix(&canonical_root))
+            .ok()
+        else {
+            continue;
+        };
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "path traversal attempt detected",
+            ));
+        }
+        return Ok(canonical_root.join(relative));
+    }
+    Err(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        "path is outside the X-Plane addon folders",
+    ))
+}
+
+/// Stable key for an X-Plane installation, used to scope per-install database rows
+pub fn normalize_xplane_key(path: &Path) -> String {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let text = canonical.to_string_lossy().replace('\\', "/");
+    if cfg!(windows) {
+        text.to_ascii_lowercase()
+    } else {
+        text
+    }
+}
+
+// Unicode normalization of file and folder names
+//
+// macOS hands out names decomposed (NFD) while scenery_packs.ini entries and
+// names typed elsewhere are usually composed (NFC), so the same folder can
+// arrive in either form. Names are compared in NFC; what is written keeps the
+// form found on disk.
+
+/// NFC form of a file or folder name
+pub fn normalize_name(name: &str) -> String {
+    name.nfc().collect()
+}
+
+/// Whether two file or folder names are the same once both are in NFC
+pub fn names_match(a: &str, b: &str) -> bool {
+    a == b || normalize_name(a) == normalize_name(b)
+}
+
+/// Name of the entry in `dir` that `name` refers to, as stored on disk: `name`
+/// itself when present, otherwise an entry equal to it in NFC
+pub fn on_disk_name(dir: &Path, name: &str) -> Option<String> {
+    let names: Vec<String> = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    if names.iter().any(|existing| existing == name) {
+        return Some(name.to_string());
+    }
+    let wanted = normalize_name(name);
+    names
+        .into_iter()
+        .find(|existing| normalize_name(existing) == wanted)
+}
+
+// Directory links (junction on Windows, symlink on Unix)
+
+#[cfg(windows)]
+pub fn create_directory_link(target: &Path, link_path: &Path) -> Result<(), String> {
+    junction::create(target, link_path).map_err(|e| {
+        format!(
+            "Failed to create junction {} -> {}: {}",
+            link_path.display(),
+            target.display(),
+            e
+        )
+    })
+}
+
+#[cfg(unix)]
+pub fn create_directory_link(target: &Path, link_path: &Path) -> Result<(), String> {
+    std::os::unix::fs::symlink(target, link_path).map_err(|e| {
+        format!(
+            "Failed to create symlink {} -> {}: {}",
+            link_path.display(),
+            target.display(),
+            e
+        )
+    })
+}
+
+#[cfg(windows)]
+pub fn is_link(path: &Path) -> bool {
+    junction::exists(path).unwrap_or(false)
+}
+
+#[cfg(unix)]
+pub fn is_link(path: &Path) -> bool {
+    path.symlink_metadata()
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+pub fn remove_directory_link(link_path: &Path) -> Result<(), String> {
+    junction::delete(link_path)
+        .map_err(|e| format!("Failed to remove junction {}: {}", link_path.display(), e))
+}
+
+#[cfg(unix)]
+pub fn remove_directory_link(link_path: &Path) -> Result<(), String> {
+    std::fs::remove_file(link_path)
+        .map_err(|e| format!("Failed to remove symlink {}: {}", link_path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linked_roots_are_trusted_outside_the_xplane_root() {
+        let xplane = tempfile::tempdir().unwrap();
+        let other_drive = tempfile::tempdir().unwrap();
+        let scenery = other_drive.path().join("Scenery");
+        fs::create_dir_all(scenery.join("Airport_A")).unwrap();
+        fs::create_dir_all(other_drive.path().join("Elsewhere")).unwrap();
+        create_directory_link(&scenery, &xplane.path().join("Custom Scenery")).unwrap();
+        assert!(is_link(&xplane.path().join("Custom Scenery")));
+
+        let existing = xplane.path().join("Custom Scenery").join("Airport_A");
+        let canonical_scenery = scenery.canonicalize().unwrap();
+        assert!(validate_child_path(xplane.path(), &existing).is_err());
+        assert_eq!(
+            validate_xplane_child_path(xplane.path(), &existing).unwrap(),
+            canonical_scenery.join("Airport_A")
+        );
+        assert!(
+            validate_xplane_child_path(xplane.path(), &other_drive.path().join("Elsewhere"))
+                .is_err()
+        );
+        assert!(validate_xplane_child_path(xplane.path(), xplane.path()).is_err());
+
+        // Targets that don't exist yet, through the link or the canonical location
+        let new_target = xplane.path().join("Custom Scenery").join("Airport_B");
+        assert_eq!(
+            validate_xplane_target_path(xplane.path(), &new_target).unwrap(),
+            canonical_scenery.join("Airport_B")
+        );
+        assert_eq!(
+            validate_xplane_target_path(xplane.path(), &canonical_scenery.join("Airport_C"))
+                .unwrap(),
+            canonical_scenery.join("Airport_C")
+        );
+    }
+
+    #[test]
+    fn target_paths_reject_traversal_and_foreign_folders() {
+        let xplane = tempfile::tempdir().unwrap();
+        fs::create_dir_all(xplane.path().join("Custom Scenery")).unwrap();
+        fs::create_dir_all(xplane.path().join("Output")).unwrap();
+
+        let escaping = xplane
+            .path()
+            .join("Custom Scenery")
+            .join("..")
+            .join("..")
+            .join("evil");
+        assert!(validate_xplane_target_path(xplane.path(), &escaping).is_err());
+        assert!(validate_xplane_target_path(
+            xplane.path(),
+            &xplane.path().join("Output").join("x")
+        )
+        .is_err());
+        // Missing roots are created inside the X-Plane root
+        assert_eq!(
+            validate_xplane_target_path(
+                xplane.path(),
+                &xplane.path().join("Aircraft").join("A330")
+            )
+            .unwrap(),
+            xplane
+                .path()
+                .canonicalize()
+                .unwrap()
+                .join("Aircraft")
+                .join("A330")
+        );
+    }
+
+    #[test]
+    fn composed_and_decomposed_names_match() {
+        let composed = "Новосибирск Толмачёво";
+        let decomposed: String = composed.nfd().collect();
+        assert_ne!(composed, decomposed);
+        assert!(names_match(composed, &decomposed));
+        assert!(!names_match(composed, "Новосибирск Толмачево"));
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(&decomposed)).unwrap();
+        assert_eq!(
+            on_disk_name(dir.path(), composed).as_deref(),
+            Some(decomposed.as_str())
+        );
+        assert_eq!(on_disk_name(dir.path(), "Missing"), None);
+    }
+}
@@ -1,120 +1,548 @@
-#[cfg(target_os = "windows")]
+//! "Install to X-Plane" entries in the file manager's context menu
+//!
+//! On Windows the entries live under `HKCU\Software\Classes`, one per file type
+//! (`SystemFileAssociations\.<ext>`) plus `Directory` for folders, so no
+//! elevation is needed. On Linux a desktop entry (the "Open With" list of
+//! Nautilus and Dolphin) and a Dolphin service menu list the chosen MIME types.
+//! macOS has no equivalent and reports itself as unsupported.
+#![cfg_attr(not(any(target_os = "windows", target_os = "linux")), allow(dead_code))]
+
 use anyhow::Result;
-#[cfg(target_os = "windows")]
-use std::env;
-#[cfg(target_os = "windows")]
-use winreg::enums::*;
-#[cfg(target_os = "windows")]
-use winreg::RegKey;
+use serde::{Deserialize, Serialize};
 
-#[cfg(target_os = "windows")]
-pub fn register_context_menu() -> Result<()> {
-    let exe_path = env::current_exe()?;
-    let exe_path_str = exe_path.to_string_lossy();
+/// What the context menu entry can be registered for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContextMenuTarget {
+    Zip,
+    SevenZ,
+    Rar,
+    Lua,
+    Folders,
+}
 
-    // Use HKEY_CURRENT_USER instead of HKEY_CLASSES_ROOT to avoid requiring admin privileges
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+impl ContextMenuTarget {
+    pub const ALL: [ContextMenuTarget; 5] = [
+        ContextMenuTarget::Zip,
+        ContextMenuTarget::SevenZ,
+        ContextMenuTarget::Rar,
+        ContextMenuTarget::Lua,
+        ContextMenuTarget::Folders,
+    ];
 
-    // Register for files (*)
-    let (shell_key, _) = hkcu.create_subkey(r"Software\Classes\*\shell\XFast Manager")?;
-    shell_key.set_value("", &"Install to X-Plane")?;
-    shell_key.set_value("Icon", &exe_path_str.to_string())?;
+    /// File extension with the leading dot, `None` for folders
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    fn extension(self) -> Option<&'static str> {
+        match self {
+            ContextMenuTarget::Zip => Some(".zip"),
+            ContextMenuTarget::SevenZ => Some(".7z"),
+            ContextMenuTarget::Rar => Some(".rar"),
+            ContextMenuTarget::Lua => Some(".lua"),
+            ContextMenuTarget::Folders => None,
+        }
+    }
 
-    let (command_key, _) = hkcu.create_subkey(r"Software\Classes\*\shell\XFast Manager\command")?;
-    command_key.set_value("", &format!("\"{}\" \"%1\"", exe_path_str))?;
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn mime_types(self) -> &'static [&'static str] {
+        match self {
+            ContextMenuTarget::Zip => &["application/zip"],
+            ContextMenuTarget::SevenZ => &["application/x-7z-compressed"],
+            ContextMenuTarget::Rar => &["application/vnd.rar", "application/x-rar"],
+            ContextMenuTarget::Lua => &["text/x-lua"],
+            ContextMenuTarget::Folders => &["inode/directory"],
+        }
+    }
+}
 
-    // Register for directories
-    let (dir_shell_key, _) =
-        hkcu.create_subkey(r"Software\Classes\Directory\shell\XFast Manager")?;
-    dir_shell_key.set_value("", &"Install to X-Plane")?;
-    dir_shell_key.set_value("Icon", &exe_path_str.to_string())?;
+/// Which targets the entry is registered for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextMenuOptions {
+    pub zip: bool,
+    pub seven_z: bool,
+    pub rar: bool,
+    pub lua: bool,
+    pub folders: bool,
+}
 
-    let (dir_command_key, _) =
-        hkcu.create_subkey(r"Software\Classes\Directory\shell\XFast Manager\command")?;
-    dir_command_key.set_value("", &format!("\"{}\" \"%1\"", exe_path_str))?;
+impl Default for ContextMenuOptions {
+    fn default() -> Self {
+        Self {
+            zip: true,
+            seven_z: true,
+            rar: true,
+            lua: true,
+            folders: true,
+        }
+    }
+}
 
-    Ok(())
+impl ContextMenuOptions {
+    pub fn none() -> Self {
+        Self {
+            zip: false,
+            seven_z: false,
+            rar: false,
+            lua: false,
+            folders: false,
+        }
+    }
+
+    pub fn enabled(&self, target: ContextMenuTarget) -> bool {
+        match target {
+            ContextMenuTarget::Zip => self.zip,
+            ContextMenuTarget::SevenZ => self.seven_z,
+            ContextMenuTarget::Rar => self.rar,
+            ContextMenuTarget::Lua => self.lua,
+            ContextMenuTarget::Folders => self.folders,
+        }
+    }
+
+    pub fn set(&mut self, target: ContextMenuTarget, enabled: bool) {
+        match target {
+            ContextMenuTarget::Zip => self.zip = enabled,
+            ContextMenuTarget::SevenZ => self.seven_z = enabled,
+            ContextMenuTarget::Rar => self.rar = enabled,
+            ContextMenuTarget::Lua => self.lua = enabled,
+            ContextMenuTarget::Folders => self.folders = enabled,
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        ContextMenuTarget::ALL
+            .iter()
+            .any(|target| self.enabled(*target))
+    }
 }
 
-#[cfg(target_os = "windows")]
-pub fn unregister_context_menu() -> Result<()> {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+/// Registration state for the settings page
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextMenuStatus {
+    /// False on platforms without a context menu integration
+    pub supported: bool,
+    /// Registered for at least one target
+    pub registered: bool,
+    pub options: ContextMenuOptions,
+}
 
-    // Try to delete for files
-    let _ = hkcu.delete_subkey_all(r"Software\Classes\*\shell\XFast Manager");
+impl ContextMenuStatus {
+    fn from_options(options: ContextMenuOptions) -> Self {
+        Self {
+            supported: true,
+            registered: options.any(),
+            options,
+        }
+    }
+
+    #[cfg_attr(any(target_os = "windows", target_os = "linux"), allow(dead_code))]
+    fn unsupported() -> Self {
+        Self {
+            supported: false,
+            registered: false,
+            options: ContextMenuOptions::none(),
+        }
+    }
+}
 
-    // Try to delete for directories
-    let _ = hkcu.delete_subkey_all(r"Software\Classes\Directory\shell\XFast Manager");
+/// What [`sync_registry_paths`] fixed
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextMenuSyncReport {
+    /// Targets whose entry pointed at another executable and was rewritten
+    pub repaired: Vec<ContextMenuTarget>,
+    /// An entry left by an older version was replaced by per-type entries
+    pub migrated_legacy: bool,
+}
 
+/// Register for every target
+pub fn register_context_menu() -> Result<()> {
+    let status = set_context_menu_options(ContextMenuOptions::default())?;
+    if !status.supported {
+        anyhow::bail!("Context menu registration is not supported on this platform");
+    }
     Ok(())
 }
 
-#[cfg(target_os = "windows")]
-pub fn is_context_menu_registered() -> bool {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-    // Check if the registry key exists
-    hkcu.open_subkey(r"Software\Classes\*\shell\XFast Manager")
-        .is_ok()
+pub fn unregister_context_menu() -> Result<()> {
+    let status = set_context_menu_options(ContextMenuOptions::none())?;
+    if !status.supported {
+        anyhow::bail!("Context menu unregistration is not supported on this platform");
+    }
+    Ok(())
 }
 
-/// Get the exe path currently stored in the registry
-#[cfg(target_os = "windows")]
-fn get_registered_exe_path() -> Option<String> {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-    let command_key = hkcu
-        .open_subkey(r"Software\Classes\*\shell\XFast Manager\command")
-        .ok()?;
-    let value: String = command_key.get_value("").ok()?;
-    // Parse "\"C:\path\to\exe.exe\" \"%1\"" format
-    let path = value.trim_start_matches('"');
-    let end = path.find("\" \"")?;
-    Some(path[..end].to_string())
-}
-
-/// Sync registry paths on startup
-/// Returns Ok(true) if paths were updated, Ok(false) if no update needed
 #[cfg(target_os = "windows")]
-pub fn sync_registry_paths() -> Result<bool> {
-    if !is_context_menu_registered() {
-        return Ok(false);
+mod platform {
+    use super::{ContextMenuOptions, ContextMenuStatus, ContextMenuSyncReport, ContextMenuTarget};
+    use anyhow::Result;
+    use std::env;
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    const MENU_KEY: &str = "XFast Manager";
+    const MENU_LABEL: &str = "Install to X-Plane";
+    /// Older versions registered a single entry for every file
+    const LEGACY_FILES_KEY: &str = r"Software\Classes\*\shell\XFast Manager";
+
+    fn shell_key(target: ContextMenuTarget) -> String {
+        match target.extension() {
+            Some(ext) => format!(
+                r"Software\Classes\SystemFileAssociations\{}\shell\{}",
+                ext, MENU_KEY
+            ),
+            None => format!(r"Software\Classes\Directory\shell\{}", MENU_KEY),
+        }
+    }
+
+    fn write_entry(hkcu: &RegKey, key_path: &str, exe_path: &str) -> Result<()> {
+        let (shell_key, _) = hkcu.create_subkey(key_path)?;
+        shell_key.set_value("", &MENU_LABEL)?;
+        shell_key.set_value("Icon", &exe_path.to_string())?;
+
+        let (command_key, _) = hkcu.create_subkey(format!(r"{}\command", key_path))?;
+        command_key.set_value("", &format!("\"{}\" \"%1\"", exe_path))?;
+        Ok(())
+    }
+
+    /// Exe path stored in an entry's command
+    fn registered_exe_path(hkcu: &RegKey, key_path: &str) -> Option<String> {
+        let command_key = hkcu.open_subkey(format!(r"{}\command", key_path)).ok()?;
+        let value: String = command_key.get_value("").ok()?;
+        // Parse "\"C:\path\to\exe.exe\" \"%1\"" format
+        let path = value.trim_start_matches('"');
+        let end = path.find("\" \"")?;
+        Some(path[..end].to_string())
+    }
+
+    fn current_exe_path() -> Result<String> {
+        Ok(env::current_exe()?.to_string_lossy().to_string())
+    }
+
+    pub fn set_options(options: ContextMenuOptions) -> Result<ContextMenuStatus> {
+        let exe_path = current_exe_path()?;
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+
+        for target in ContextMenuTarget::ALL {
+            let key_path = shell_key(target);
+            if options.enabled(target) {
+                write_entry(&hkcu, &key_path, &exe_path)?;
+            } else {
+                let _ = hkcu.delete_subkey_all(&key_path);
+            }
+        }
+        let _ = hkcu.delete_subkey_all(LEGACY_FILES_KEY);
+
+        Ok(status())
+    }
+
+    pub fn status() -> ContextMenuStatus {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let legacy = hkcu.open_subkey(LEGACY_FILES_KEY).is_ok();
+
+        let mut options = ContextMenuOptions::none();
+        for target in ContextMenuTarget::ALL {
+            let registered = hkcu.open_subkey(shell_key(target)).is_ok()
+                || (legacy && target.extension().is_some());
+            options.set(target, registered);
+        }
+        ContextMenuStatus::from_options(options)
+    }
+
+    pub fn sync() -> Result<ContextMenuSyncReport> {
+        let exe_path = current_exe_path()?;
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let mut report = ContextMenuSyncReport::default();
+
+        for target in ContextMenuTarget::ALL {
+            let key_path = shell_key(target);
+            if let Some(registered) = registered_exe_path(&hkcu, &key_path) {
+                if registered != exe_path {
+                    write_entry(&hkcu, &key_path, &exe_path)?;
+                    report.repaired.push(target);
+                }
+            }
+        }
+
+        if hkcu.open_subkey(LEGACY_FILES_KEY).is_ok() {
+            // Keep the menu on the file types it was shown for, but not on every file
+            for target in ContextMenuTarget::ALL {
+                let key_path = shell_key(target);
+                if target.extension().is_some() && hkcu.open_subkey(&key_path).is_err() {
+                    write_entry(&hkcu, &key_path, &exe_path)?;
+                }
+            }
+            hkcu.delete_subkey_all(LEGACY_FILES_KEY)?;
+            report.migrated_legacy = true;
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{ContextMenuOptions, ContextMenuStatus, ContextMenuSyncReport, ContextMenuTarget};
+    use anyhow::{Context, Result};
+    use std::fs;
+    use std::path::PathBuf;
+
+    const DESKTOP_FILE_NAME: &str = "xfast-manager-install.desktop";
+
+    /// `Open With` entry, shown by Nautilus and Dolphin for the listed MIME types
+    fn application_entry_path() -> Option<PathBuf> {
+        Some(
+            dirs::data_dir()?
+                .join("applications")
+                .join(DESKTOP_FILE_NAME),
+        )
+    }
+
+    /// Dolphin context menu action
+    fn service_menu_path() -> Option<PathBuf> {
+        Some(
+            dirs::data_dir()?
+                .join("kio")
+                .join("servicemenus")
+                .join(DESKTOP_FILE_NAME),
+        )
+    }
+
+    /// Quote an argument for the `Exec` key of a desktop entry
+    fn quote_exec_arg(arg: &str) -> String {
+        let mut quoted = String::with_capacity(arg.len() + 2);
+        quoted.push('"');
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    fn mime_type_list(options: &ContextMenuOptions) -> String {
+        ContextMenuTarget::ALL
+            .iter()
+            .filter(|target| options.enabled(**target))
+            .flat_map(|target| target.mime_types())
+            .map(|mime| format!("{};", mime))
+            .collect()
+    }
+
+    pub(super) fn application_entry(exe_path: &str, options: &ContextMenuOptions) -> String {
+        format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name=XFast Manager\n\
+             GenericName=Install to X-Plane\n\
+             Exec={} %F\n\
+             Terminal=false\n\
+             NoDisplay=true\n\
+             MimeType={}\n",
+            quote_exec_arg(exe_path),
+            mime_type_list(options)
+        )
+    }
+
+    pub(super) fn service_menu(exe_path: &str, options: &ContextMenuOptions) -> String {
+        format!(
+            "[Desktop Entry]\n\
+             Type=Service\n\
+             X-KDE-ServiceTypes=KonqPopupMenu/Plugin\n\
+             MimeType={}\n\
+             Actions=installToXPlane;\n\
+             \n\
+             [Desktop Action installToXPlane]\n\
+             Name=Install to X-Plane\n\
+             Exec={} %F\n",
+            mime_type_list(options),
+            quote_exec_arg(exe_path)
+        )
+    }
+
+    /// Targets and executable of an entry written by [`application_entry`]
+    pub(super) fn parse_application_entry(
+        content: &str,
+    ) -> Option<(ContextMenuOptions, Option<String>)> {
+        let mime_types: Vec<&str> = content
+            .lines()
+            .find_map(|line| line.strip_prefix("MimeType="))?
+            .split(';')
+            .filter(|mime| !mime.is_empty())
+            .collect();
+        let mut options = ContextMenuOptions::none();
+        for target in ContextMenuTarget::ALL {
+            let registered = target
+                .mime_types()
+                .iter()
+                .any(|mime| mime_types.contains(mime));
+            options.set(target, registered);
+        }
+
+        let exe_path = content
+            .lines()
+            .find_map(|line| line.strip_prefix("Exec=\""))
+            .and_then(|exec| {
+                let mut path = String::new();
+                let mut chars = exec.chars();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => path.push(chars.next()?),
+                        '"' => return Some(path),
+                        _ => path.push(c),
+                    }
+                }
+                None
+            });
+        Some((options, exe_path))
+    }
+
+    fn read_entry() -> Option<(ContextMenuOptions, Option<String>)> {
+        let content = fs::read_to_string(application_entry_path()?).ok()?;
+        parse_application_entry(&content)
     }
 
-    let current_path = env::current_exe()?.to_string_lossy().to_string();
+    fn write_entries(exe_path: &str, options: &ContextMenuOptions) -> Result<()> {
+        let (Some(application), Some(service)) = (application_entry_path(), service_menu_path())
+        else {
+            anyhow::bail!("Could not determine the user data directory");
+        };
 
-    if let Some(registered_path) = get_registered_exe_path() {
-        if registered_path != current_path {
-            // Path mismatch, re-register to update paths
-            register_context_menu()?;
-            return Ok(true);
+        for (path, content) in [
+            (&application, application_entry(exe_path, options)),
+            (&service, service_menu(exe_path, options)),
+        ] {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
         }
+
+        // Dolphin only loads service menus that are executable
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&service, fs::Permissions::from_mode(0o755))?;
+        refresh_desktop_database();
+        Ok(())
+    }
+
+    fn remove_entries() {
+        for path in [application_entry_path(), service_menu_path()]
+            .into_iter()
+            .flatten()
+        {
+            let _ = fs::remove_file(path);
+        }
+        refresh_desktop_database();
+    }
+
+    /// Best effort: file managers pick up the change sooner with a fresh cache
+    fn refresh_desktop_database() {
+        if let Some(applications) =
+            application_entry_path().and_then(|p| p.parent().map(|parent| parent.to_path_buf()))
+        {
+            let _ = std::process::Command::new("update-desktop-database")
+                .arg(applications)
+                .output();
+        }
+    }
+
+    /// The AppImage file when running from one; `current_exe` would point into
+    /// its mount, which is gone once the app exits
+    fn current_exe_path() -> Result<String> {
+        if let Some(appimage) = std::env::var_os("APPIMAGE").filter(|path| !path.is_empty()) {
+            return Ok(appimage.to_string_lossy().to_string());
+        }
+        Ok(std::env::current_exe()?.to_string_lossy().to_string())
+    }
+
+    pub fn set_options(options: ContextMenuOptions) -> Result<ContextMenuStatus> {
+        if options.any() {
+            write_entries(&current_exe_path()?, &options)?;
+        } else {
+            remove_entries();
+        }
+        Ok(status())
+    }
+
+    pub fn status() -> ContextMenuStatus {
+        let options = read_entry()
+            .map(|(options, _)| options)
+            .unwrap_or_else(ContextMenuOptions::none);
+        ContextMenuStatus::from_options(options)
     }
 
-    Ok(false)
+    pub fn sync() -> Result<ContextMenuSyncReport> {
+        let mut report = ContextMenuSyncReport::default();
+        let Some((options, registered_exe)) = read_entry() else {
+            return Ok(report);
+        };
+        let exe_path = current_exe_path()?;
+        if options.any() && registered_exe.as_deref() != Some(exe_path.as_str()) {
+            write_entries(&exe_path, &options)?;
+            report.repaired = ContextMenuTarget::ALL
+                .into_iter()
+                .filter(|target| options.enabled(*target))
+                .collect();
+        }
+        Ok(report)
+    }
 }
 
-#[cfg(not(target_os = "windows"))]
-use anyhow::Result;
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+mod platform {
+    use super::{ContextMenuOptions, ContextMenuStatus, ContextMenuSyncReport};
+    use anyhow::Result;
 
-#[cfg(not(target_os = "windows"))]
-pub fn register_context_menu() -> Result<()> {
-    Err(anyhow::anyhow!(
-        "Context menu registration is only supported on Windows"
-    ))
+    pub fn set_options(_options: ContextMenuOptions) -> Result<ContextMenuStatus> {
+        Ok(ContextMenuStatus::unsupported())
+    }
+
+    pub fn status() -> ContextMenuStatus {
+        ContextMenuStatus::unsupported()
+    }
+
+    pub fn sync() -> Result<ContextMenuSyncReport> {
+        Ok(ContextMenuSyncReport::default())
+    }
 }
 
-#[cfg(not(target_os = "windows"))]
-pub fn unregister_context_menu() -> Result<()> {
-    Err(anyhow::anyhow!(
-        "Context menu unregistration is only supported on Windows"
-    ))
+/// Register for the enabled targets and remove the entries of the others
+pub fn set_context_menu_options(options: ContextMenuOptions) -> Result<ContextMenuStatus> {
+    platform::set_options(options)
+}
+
+/// Registration state per target
+pub fn is_context_menu_registered() -> ContextMenuStatus {
+    platform::status()
 }
 
-#[cfg(not(target_os = "windows"))]
-pub fn is_context_menu_registered() -> bool {
-    false
+/// Point entries at the current executable after the app moved, and replace
+/// entries left by older versions
+pub fn sync_registry_paths() -> Result<ContextMenuSyncReport> {
+    platform::sync()
 }
 
-#[cfg(not(target_os = "windows"))]
-pub fn sync_registry_paths() -> Result<bool> {
-    Ok(false)
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::platform::{application_entry, parse_application_entry};
+    use super::*;
+
+    #[test]
+    fn test_application_entry_round_trips_targets_and_exe() {
+        let mut options = ContextMenuOptions::none();
+        options.set(ContextMenuTarget::Rar, true);
+        options.set(ContextMenuTarget::Folders, true);
+        let exe = "/opt/XFast \"Manager\"/xfastmanager";
+
+        let entry = application_entry(exe, &options);
+        assert!(entry.contains("MimeType=application/vnd.rar;application/x-rar;inode/directory;"));
+
+        let (parsed, parsed_exe) = parse_application_entry(&entry).unwrap();
+        assert_eq!(parsed, options);
+        assert_eq!(parsed_exe.as_deref(), Some(exe));
+    }
 }
@@ -0,0 +1,584 @@
+//! Typed settings persisted by the backend
+//!
+//! Frontend preferences live in `settings.json`, which the Tauri store plugin
+//! owns. Settings the backend itself needs are kept separately in
+//! `backend_settings.json` as a versioned [`BackendSettings`] struct:
+//!
+//! - Every field has a serde default, so files written by older versions load
+//!   with the new fields filled in. Fields this version doesn't know are kept
+//!   and written back, so running an older build doesn't drop them.
+//! - Files from older schema versions go through [`MIGRATIONS`] before they are
+//!   deserialized, which is where fields get renamed or moved.
+//! - Writes go to a temp file that is fsynced and renamed over the old file.
+//! - A file that can't be parsed is moved aside for inspection and defaults are
+//!   used instead.
+//! - The proxy password is kept in its own file ([`app_dirs::PROXY_CREDENTIALS_FILE`])
+//!   rather than in the settings file, like updater logins are kept out of it,
+//!   and the frontend only ever sees whether one is set.
+//!
+//! The current settings are cached in memory; [`get`] never touches the disk.
+//! Every change is emitted to the frontend as `backend-settings-changed`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, OnceLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+use crate::app_dirs;
+use crate::logger;
+
+/// Schema version written by this build
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Event emitted with the new [`BackendSettings`] after every change
+pub const SETTINGS_CHANGED_EVENT: &str = "backend-settings-changed";
+
+/// Default for [`BackendSettings::max_url_download_bytes`] (8 GiB)
+pub const DEFAULT_MAX_URL_DOWNLOAD_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+
+/// Default for [`BackendSettings::log_history_limit`]
+pub const DEFAULT_LOG_HISTORY_LIMIT: u32 = 200;
+
+/// Rewrites the raw JSON object of one schema version into the next.
+/// `MIGRATIONS[i]` upgrades version `i + 1` to `i + 2`.
+type Migration = fn(&mut Map<String, Value>);
+
+/// Schema migrations, in order. Append one whenever a field is renamed or moved
+/// and bump [`SETTINGS_VERSION`].
+const MIGRATIONS: &[Migration] = &[];
+
+static STORE: LazyLock<SettingsStore> =
+    LazyLock::new(|| SettingsStore::open(app_dirs::get_backend_settings_path()));
+
+/// Used to notify the frontend of changes
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Settings persisted by the backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackendSettings {
+    /// Schema version the file was written with
+    pub version: u32,
+    /// Skip optional network fetches (remote livery patterns and library links)
+    pub offline_mode: bool,
+    /// Largest file `download_and_analyze` accepts from a pasted URL, in bytes
+    pub max_url_download_bytes: u64,
+    /// Repeat the last full maintenance run weekly while idle and on AC power
+    pub auto_maintenance: bool,
+    /// X-Plane log snapshots kept per installation; older ones are pruned
+    pub log_history_limit: u32,
+    /// Check scenery_packs.ini order after installing scenery and report violations
+    pub validate_scenery_order_after_install: bool,
+    /// Proxy, certificate and timeout settings for outbound HTTP requests
+    pub network: NetworkSettings,
+    /// Folder installs and extractions stage in (see `staging`); the system
+    /// temp folder when unset
+    pub staging_dir: Option<String>,
+    /// Update channel chosen for X-Updater addons, keyed by addon folder path.
+    /// SkunkCrafts addons keep theirs in their own cfg.
+    pub addon_update_channels: BTreeMap<String, String>,
+    /// Fields written by a newer version, preserved as-is
+    #[serde(flatten)]
+    pub unknown_fields: Map<String, Value>,
+}
+
+impl Default for BackendSettings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            offline_mode: false,
+            max_url_download_bytes: DEFAULT_MAX_URL_DOWNLOAD_BYTES,
+            auto_maintenance: true,
+            log_history_limit: DEFAULT_LOG_HISTORY_LIMIT,
+            validate_scenery_order_after_install: false,
+            network: NetworkSettings::default(),
+            staging_dir: None,
+            addon_update_channels: BTreeMap::new(),
+            unknown_fields: Map::new(),
+        }
+    }
+}
+
+/// Outbound HTTP settings, applied by `http_client` to every client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkSettings {
+    /// Proxy for all requests, e.g. `http://proxy:8080` or `socks5://127.0.0.1:1080`
+    pub proxy_url: Option<String>,
+    pub proxy_username: Option<String>,
+    /// Saved in [`app_dirs::PROXY_CREDENTIALS_FILE`], never in the settings file.
+    /// Write-only: serialized as a `hasProxyPassword` flag so the password
+    /// never reaches the settings file or the webview.
+    #[serde(
+        rename(serialize = "hasProxyPassword", deserialize = "proxyPassword"),
+        serialize_with = "serialize_is_set"
+    )]
+    pub proxy_password: Option<String>,
+    /// Use the proxy configured in the OS or environment when no proxy URL is set
+    pub use_system_proxy: bool,
+    /// Extra trusted root certificate(s) in PEM format, for TLS-intercepting proxies
+    pub extra_root_certificate_pem: Option<String>,
+    /// Replaces the built-in request timeouts, in seconds
+    pub timeout_secs: Option<u64>,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            proxy_url: None,
+            proxy_username: None,
+            proxy_password: None,
+            use_system_proxy: true,
+            extra_root_certificate_pem: None,
+            timeout_secs: None,
+        }
+    }
+}
+
+impl NetworkSettings {
+    /// Settings sent by the frontend only carry a password the user just
+    /// typed: keep the `saved` one when there is none, and clear it when the
+    /// new one is empty
+    pub fn keep_saved_password(&mut self, saved: &NetworkSettings) {
+        match self.proxy_password.as_deref() {
+            None => self.proxy_password = saved.proxy_password.clone(),
+            Some("") => self.proxy_password = None,
+            Some(_) => {}
+        }
+    }
+}
+
+fn serialize_is_set<S: serde::Serializer>(
+    value: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bool(value.is_some())
+}
+
+/// Content of [`app_dirs::PROXY_CREDENTIALS_FILE`]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProxyCredentials {
+    #[serde(default)]
+    proxy_password: Option<String>,
+}
+
+/// A settings file with its in-memory copy
+pub struct SettingsStore {
+    path: PathBuf,
+    /// Where the proxy password is saved, next to the settings file
+    credentials_path: PathBuf,
+    current: RwLock<BackendSettings>,
+    /// Serializes writers so concurrent updates don't overwrite each other
+    write_lock: Mutex<()>,
+}
+
+impl SettingsStore {
+    /// Load settings from `path`, falling back to defaults if the file is
+    /// missing or unreadable
+    pub fn open(path: PathBuf) -> Self {
+        let credentials_path = path.with_file_name(app_dirs::PROXY_CREDENTIALS_FILE);
+        let mut settings = load_settings(&path, MIGRATIONS);
+        settings.network.proxy_password = load_proxy_password(&credentials_path);
+        Self {
+            path,
+            credentials_path,
+            current: RwLock::new(settings),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// The cached settings
+    pub fn get(&self) -> BackendSettings {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Apply `change` to the current settings and persist the result.
+    /// The cached copy is only replaced once the file was written.
+    pub fn update(
+        &self,
+        change: impl FnOnce(&mut BackendSettings),
+    ) -> Result<BackendSettings, String> {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut settings = self.get();
+        let previous_password = settings.network.proxy_password.clone();
+        change(&mut settings);
+        settings.version = settings.version.max(SETTINGS_VERSION);
+
+        if settings.network.proxy_password != previous_password {
+            save_proxy_password(
+                &self.credentials_path,
+                settings.network.proxy_password.as_deref(),
+            )
+            .map_err(|e| format!("Failed to save proxy password: {}", e))?;
+        }
+        let content = serde_json::to_vec_pretty(&settings)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        write_atomic(&self.path, &content)
+            .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = settings.clone();
+        Ok(settings)
+    }
+}
+
+/// Remember the app handle used for change events
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+/// The current backend settings
+pub fn get() -> BackendSettings {
+    STORE.get()
+}
+
+/// Change and persist the backend settings, notifying the frontend
+pub fn update(change: impl FnOnce(&mut BackendSettings)) -> Result<BackendSettings, String> {
+    let settings = STORE.update(change)?;
+    if let Some(app_handle) = APP_HANDLE.get() {
+        let _ = app_handle.emit(SETTINGS_CHANGED_EVENT, &settings);
+    }
+    Ok(settings)
+}
+
+fn load_proxy_password(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    serde_json::from_slice::<ProxyCredentials>(&content)
+        .ok()?
+        .proxy_password
+}
+
+/// Save `password` to `path`, or remove the file when there is none
+fn save_proxy_password(path: &Path, password: Option<&str>) -> io::Result<()> {
+    match password.filter(|password| !password.is_empty()) {
+        Some(password) => {
+            let credentials = ProxyCredentials {
+                proxy_password: Some(password.to_string()),
+            };
+            write_atomic(path, &serde_json::to_vec_pretty(&credentials)?)
+        }
+        None => match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        },
+    }
+}
+
+fn load_settings(path: &Path, migrations: &[Migration]) -> BackendSettings {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return BackendSettings::default(),
+        Err(e) => {
+            logger::log_error(
+                &format!("Failed to read settings file: {}, using defaults", e),
+                Some("settings_store"),
+            );
+            return BackendSettings::default();
+        }
+    };
+
+    let parsed = serde_json::from_slice::<Value>(&content)
+        .map_err(|e| e.to_string())
+        .and_then(|mut value| {
+            let from_version = migrate(&mut value, migrations)?;
+            let settings =
+                serde_json::from_value::<BackendSettings>(value).map_err(|e| e.to_string())?;
+            Ok((from_version, settings))
+        });
+
+    match parsed {
+        Ok((from_version, settings)) => {
+            if from_version < settings.version {
+                logger::log_info(
+                    &format!(
+                        "Migrated settings from version {} to {}",
+                        from_version, settings.version
+                    ),
+                    Some("settings_store"),
+                );
+                // Write the migrated file now so later writers start from the new shape
+                if let Ok(content) = serde_json::to_vec_pretty(&settings) {
+                    if let Err(e) = write_atomic(path, &content) {
+                        logger::log_error(
+                            &format!("Failed to save migrated settings: {}", e),
+                            Some("settings_store"),
+                        );
+                    }
+                }
+            }
+            settings
+        }
+        Err(e) => {
+            let backup = preserve_corrupt_file(path);
+            logger::log_error(
+                &format!(
+                    "Settings file is corrupt ({}), using defaults; kept it as {}",
+                    e,
+                    backup
+                        .as_deref()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_else(|| "nothing (rename failed)".to_string())
+                ),
+                Some("settings_store"),
+            );
+            BackendSettings::default()
+        }
+    }
+}
+
+/// Run the migrations needed to bring `value` up to the latest version.
+/// Returns the version the file was written with.
+fn migrate(value: &mut Value, migrations: &[Migration]) -> Result<u32, String> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| "settings file is not a JSON object".to_string())?;
+
+    // Files without a version predate versioning and are treated as version 1
+    let from_version = object
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(1, |v| v as u32)
+        .max(1);
+    let latest = migrations.len() as u32 + 1;
+    if from_version > latest {
+        logger::log_info(
+            &format!(
+                "Warning: settings were written by a newer version (schema {}), unknown fields are kept",
+                from_version
+            ),
+            Some("settings_store"),
+        );
+        return Ok(from_version);
+    }
+
+    for migration in &migrations[(from_version - 1) as usize..] {
+        migration(object);
+    }
+    object.insert("version".to_string(), Value::from(latest));
+    Ok(from_version)
+}
+
+/// Move an unreadable settings file next to the original for inspection
+fn preserve_corrupt_file(path: &Path) -> Option<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".corrupt-{}", timestamp));
+    let backup = PathBuf::from(backup);
+    fs::rename(path, &backup).ok().map(|_| backup)
+}
+
+/// Write `content` to `path` so readers see either the old or the new file,
+/// never a partial one
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no parent"))?;
+    fs::create_dir_all(parent)?;
+
+    let mut temp_name = path.as_os_str().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+
+    let result = (|| -> io::Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp_path, path)?;
+        // Persist the rename itself; directories can't be opened this way on Windows
+        #[cfg(unix)]
+        fs::File::open(parent)?.sync_all()?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn missing_file_uses_defaults_and_updates_persist() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let path = temp.path().join("backend_settings.json");
+
+        let store = SettingsStore::open(path.clone());
+        assert_eq!(store.get(), BackendSettings::default());
+        assert!(!path.exists());
+
+        store.update(|s| s.offline_mode = true).unwrap();
+        assert!(store.get().offline_mode);
+
+        let reopened = SettingsStore::open(path.clone());
+        assert!(reopened.get().offline_mode);
+        assert!(!temp.path().join("backend_settings.json.tmp").exists());
+    }
+
+    #[test]
+    fn proxy_password_is_kept_out_of_the_settings_file() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let path = temp.path().join("backend_settings.json");
+        let credentials = temp.path().join(app_dirs::PROXY_CREDENTIALS_FILE);
+
+        let store = SettingsStore::open(path.clone());
+        store
+            .update(|s| s.network.proxy_password = Some("secret".to_string()))
+            .unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("secret"));
+        assert!(fs::read_to_string(&credentials).unwrap().contains("secret"));
+        assert_eq!(
+            SettingsStore::open(path.clone())
+                .get()
+                .network
+                .proxy_password,
+            Some("secret".to_string())
+        );
+
+        store.update(|s| s.network.proxy_password = None).unwrap();
+        assert!(!credentials.exists());
+    }
+
+    #[test]
+    fn proxy_password_is_write_only() {
+        let saved = NetworkSettings {
+            proxy_password: Some("secret".to_string()),
+            ..NetworkSettings::default()
+        };
+        let json = serde_json::to_value(&saved).unwrap();
+        assert!(!json.to_string().contains("secret"));
+        assert_eq!(json["hasProxyPassword"], Value::Bool(true));
+
+        // Sending the settings back unchanged keeps the saved password
+        let mut echoed: NetworkSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(echoed.proxy_password, None);
+        echoed.keep_saved_password(&saved);
+        assert_eq!(echoed.proxy_password, Some("secret".to_string()));
+
+        let mut replaced: NetworkSettings =
+            serde_json::from_value(serde_json::json!({ "proxyPassword": "new" })).unwrap();
+        replaced.keep_saved_password(&saved);
+        assert_eq!(replaced.proxy_password, Some("new".to_string()));
+
+        let mut cleared: NetworkSettings =
+            serde_json::from_value(serde_json::json!({ "proxyPassword": "" })).unwrap();
+        cleared.keep_saved_password(&saved);
+        assert_eq!(cleared.proxy_password, None);
+    }
+
+    #[test]
+    fn corrupt_file_falls_back_to_defaults_and_is_kept() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let path = temp.path().join("backend_settings.json");
+        fs::write(&path, "{\"offlineMode\": tru").unwrap();
+
+        let store = SettingsStore::open(path.clone());
+        assert_eq!(store.get(), BackendSettings::default());
+        assert!(!path.exists());
+
+        let kept: Vec<_> = fs::read_dir(temp.path())
+            .unwrap()
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().contains(".corrupt-"))
+            .collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(
+            fs::read_to_string(kept[0].path()).unwrap(),
+            "{\"offlineMode\": tru"
+        );
+
+        // A wrongly typed field is as unusable as broken JSON
+        fs::write(&path, "{\"offlineMode\": \"yes\"}").unwrap();
+        assert_eq!(
+            SettingsStore::open(path.clone()).get(),
+            BackendSettings::default()
+        );
+    }
+
+    #[test]
+    fn migrations_run_from_stored_version_and_unknown_fields_survive() {
+        fn rename_offline(object: &mut Map<String, Value>) {
+            if let Some(value) = object.remove("offline") {
+                object.insert("offlineMode".to_string(), value);
+            }
+        }
+        let migrations: &[Migration] = &[rename_offline];
+
+        let temp = tempdir().expect("failed to create tempdir");
+        let path = temp.path().join("backend_settings.json");
+        fs::write(
+            &path,
+            r#"{"version": 1, "offline": true, "fromTheFuture": [1, 2]}"#,
+        )
+        .unwrap();
+
+        let settings = load_settings(&path, migrations);
+        assert_eq!(settings.version, 2);
+        assert!(settings.offline_mode);
+        // Files written before a field existed get its default
+        assert_eq!(settings.network, NetworkSettings::default());
+        assert_eq!(
+            settings.unknown_fields.get("fromTheFuture"),
+            Some(&serde_json::json!([1, 2]))
+        );
+
+        // The migrated file was written back and needs no further migration
+        let mut stored: Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(migrate(&mut stored, migrations), Ok(2));
+        assert_eq!(stored["fromTheFuture"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn concurrent_updates_are_serialized() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let path = temp.path().join("backend_settings.json");
+        let store = Arc::new(SettingsStore::open(path.clone()));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let store = Arc::clone(&store);
+                std::thread::spawn(move || {
+                    for j in 0..10 {
+                        store
+                            .update(|s| {
+                                let count = s
+                                    .unknown_fields
+                                    .get("count")
+                                    .and_then(Value::as_u64)
+                                    .unwrap_or(0);
+                                s.unknown_fields
+                                    .insert("count".to_string(), Value::from(count + 1));
+                                s.offline_mode = (i + j) % 2 == 0;
+                            })
+                            .unwrap();
+                        // Readers never see a half-applied update
+                        assert_eq!(store.get().version, SETTINGS_VERSION);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(store.get().unknown_fields["count"], Value::from(80));
+        let reopened = SettingsStore::open(path);
+        assert_eq!(reopened.get(), store.get());
+    }
+}
@@ -0,0 +1,281 @@
+//! Where installs and archive extractions stage their files
+//!
+//! Extractions stage in the system temp folder by default, which is often on a
+//! small system drive. Users can pick another folder instead; it is kept in the
+//! backend settings as `stagingDir`. Atomic installs stage in the X-Plane folder
+//! unless a staging folder is configured, so their final move stays a rename.
+//! When the configured folder disappears (an unplugged drive), staging falls
+//! back to the system temp folder.
+//!
+//! Every staging directory gets [`MARKER_FILE_NAME`] inside it. Staging
+//! directories left behind by a crash are removed at startup once they are
+//! older than [`STALE_AFTER`], but only when they carry both our name prefix
+//! and the marker, so nothing we didn't create is ever deleted.
+
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+use crate::logger;
+use crate::settings_store;
+
+/// Name prefix of every staging directory
+pub const STAGING_PREFIX: &str = "xfastmanager_staging_";
+
+/// File inside each staging directory marking it as ours
+const MARKER_FILE_NAME: &str = ".xfastmanager-staging";
+
+/// Age after which a leftover staging directory is removed at startup
+const STALE_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Staging folder settings as shown in the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StagingConfig {
+    /// Folder chosen by the user; None for the system temp folder
+    pub staging_dir: Option<String>,
+    /// Folder staging uses right now
+    pub effective_dir: String,
+    /// The chosen folder is missing, so the system temp folder is used
+    pub using_fallback: bool,
+}
+
+/// `configured` when it is an existing folder
+fn existing_dir(configured: Option<&str>) -> Option<PathBuf> {
+    configured.map(PathBuf::from).filter(|path| path.is_dir())
+}
+
+/// The configured staging folder, when one is set and still present
+pub fn configured_root() -> Option<PathBuf> {
+    existing_dir(settings_store::get().staging_dir.as_deref())
+}
+
+/// Folder extractions stage in: the configured one or the system temp folder
+pub fn staging_root() -> PathBuf {
+    configured_root().unwrap_or_else(std::env::temp_dir)
+}
+
+fn write_marker(dir: &Path) -> io::Result<()> {
+    fs::write(
+        dir.join(MARKER_FILE_NAME),
+        format!(
+            "Staging directory created by XFast Manager {}\n",
+            env!("CARGO_PKG_VERSION")
+        ),
+    )
+}
+
+/// New staging directory in `root` that the caller removes when done
+pub fn create_dir_in(root: &Path, label: &str) -> io::Result<PathBuf> {
+    let dir = root.join(format!(
+        "{}{}_{}",
+        STAGING_PREFIX,
+        label,
+        uuid::Uuid::new_v4()
+    ));
+    fs::create_dir_all(&dir)?;
+    if let Err(e) = write_marker(&dir) {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+    Ok(dir)
+}
+
+/// New staging directory in the staging root, removed when dropped
+pub fn temp_dir(label: &str) -> io::Result<TempDir> {
+    let dir = tempfile::Builder::new()
+        .prefix(&format!("{}{}_", STAGING_PREFIX, label))
+        .tempdir_in(staging_root())?;
+    write_marker(dir.path())?;
+    Ok(dir)
+}
+
+/// Remove staging directories in `root` whose marker is older than `max_age`.
+/// Returns how many were removed.
+pub fn cleanup_stale(root: &Path, max_age: Duration) -> usize {
+    let Ok(read_dir) = fs::read_dir(root) else {
+        return 0;
+    };
+    let now = SystemTime::now();
+    let mut removed = 0;
+    for entry in read_dir.flatten() {
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(STAGING_PREFIX)
+            || !entry.file_type().is_ok_and(|ft| ft.is_dir())
+        {
+            continue;
+        }
+        let path = entry.path();
+        let Ok(created) = fs::metadata(path.join(MARKER_FILE_NAME)).and_then(|m| m.modified())
+        else {
+            continue;
+        };
+        if now.duration_since(created).unwrap_or_default() < max_age {
+            continue;
+        }
+        match fs::remove_dir_all(&path) {
+            Ok(()) => removed += 1,
+            Err(e) => logger::log_error(
+                &format!("Failed to remove stale staging directory {:?}: {}", path, e),
+                Some("staging"),
+            ),
+        }
+    }
+    removed
+}
+
+/// Remove stale staging directories from the configured folder and the system
+/// temp folder. Run once at startup.
+pub fn cleanup_stale_staging() {
+    let mut roots = vec![std::env::temp_dir()];
+    if let Some(configured) = configured_root() {
+        if !roots.contains(&configured) {
+            roots.push(configured);
+        }
+    }
+    let removed: usize = roots
+        .iter()
+        .map(|root| cleanup_stale(root, STALE_AFTER))
+        .sum();
+    if removed > 0 {
+        logger::log_info(
+            &format!("Removed {} stale staging director(ies)", removed),
+            Some("staging"),
+        );
+    }
+}
+
+/// Check that `dir` can hold staging directories: an absolute, writable folder
+/// outside the X-Plane folder. Creates it when missing.
+fn validate_staging_dir(dir: &str, xplane_path: Option<&Path>) -> Result<PathBuf, String> {
+    let path = PathBuf::from(dir.trim());
+    if !path.is_absolute() {
+        return Err("Staging folder must be an absolute path".to_string());
+    }
+    let existed = path.exists();
+    fs::create_dir_all(&path).map_err(|e| format!("Failed to create staging folder: {}", e))?;
+    let canonical = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve staging folder: {}", e))?;
+
+    if let Some(xplane_root) = xplane_path.and_then(|p| p.canonicalize().ok()) {
+        if canonical.starts_with(&xplane_root) {
+            if !existed {
+                let _ = fs::remove_dir(&path);
+            }
+            return Err("Staging folder must not be inside the X-Plane folder".to_string());
+        }
+    }
+
+    let probe = path.join(format!("{}write_test", STAGING_PREFIX));
+    fs::write(&probe, b"")
+        .and_then(|()| fs::remove_file(&probe))
+        .map_err(|e| format!("Staging folder is not writable: {}", e))?;
+    Ok(path)
+}
+
+fn config_for(staging_dir: Option<String>) -> StagingConfig {
+    let configured = existing_dir(staging_dir.as_deref());
+    StagingConfig {
+        using_fallback: staging_dir.is_some() && configured.is_none(),
+        effective_dir: configured
+            .unwrap_or_else(std::env::temp_dir)
+            .to_string_lossy()
+            .to_string(),
+        staging_dir,
+    }
+}
+
+pub fn get_config() -> StagingConfig {
+    config_for(settings_store::get().staging_dir)
+}
+
+/// Use `dir` for staging, or the system temp folder when None
+pub fn set_config(
+    dir: Option<String>,
+    xplane_path: Option<&Path>,
+) -> Result<StagingConfig, String> {
+    let staging_dir = match dir.filter(|dir| !dir.trim().is_empty()) {
+        Some(dir) => Some(
+            validate_staging_dir(&dir, xplane_path)?
+                .to_string_lossy()
+                .to_string(),
+        ),
+        None => None,
+    };
+    let settings = settings_store::update(|current| current.staging_dir = staging_dir)?;
+    logger::log_info(
+        &format!("Staging folder set to {:?}", settings.staging_dir),
+        Some("staging"),
+    );
+    Ok(config_for(settings.staging_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_removes_only_marked_stale_directories() {
+        let root = tempfile::tempdir().unwrap();
+        let stale = create_dir_in(root.path(), "install").unwrap();
+        let fresh = create_dir_in(root.path(), "install").unwrap();
+        let unmarked = root.path().join(format!("{}user_folder", STAGING_PREFIX));
+        let other = root.path().join("other_folder");
+        fs::create_dir_all(&unmarked).unwrap();
+        fs::create_dir_all(&other).unwrap();
+        fs::write(other.join(MARKER_FILE_NAME), "").unwrap();
+
+        let two_days_ago = SystemTime::now() - Duration::from_secs(48 * 60 * 60);
+        for marker in [stale.join(MARKER_FILE_NAME), other.join(MARKER_FILE_NAME)] {
+            fs::File::options()
+                .write(true)
+                .open(marker)
+                .unwrap()
+                .set_modified(two_days_ago)
+                .unwrap();
+        }
+
+        assert_eq!(cleanup_stale(root.path(), STALE_AFTER), 1);
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(unmarked.exists());
+        assert!(other.exists());
+    }
+
+    #[test]
+    fn test_missing_configured_folder_falls_back_to_temp_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let configured = root.path().join("staging");
+        fs::create_dir_all(&configured).unwrap();
+        let configured_text = configured.to_string_lossy().to_string();
+
+        let config = config_for(Some(configured_text.clone()));
+        assert_eq!(config.effective_dir, configured_text);
+        assert!(!config.using_fallback);
+
+        fs::remove_dir_all(&configured).unwrap();
+        let config = config_for(Some(configured_text));
+        assert!(config.using_fallback);
+        assert_eq!(PathBuf::from(config.effective_dir), std::env::temp_dir());
+    }
+
+    #[test]
+    fn test_staging_dir_must_be_outside_xplane() {
+        let xplane = tempfile::tempdir().unwrap();
+        let inside = xplane.path().join("Staging");
+        let outside = tempfile::tempdir().unwrap();
+
+        assert!(validate_staging_dir(&inside.to_string_lossy(), Some(xplane.path())).is_err());
+        assert!(!inside.exists());
+        assert!(validate_staging_dir("relative/staging", None).is_err());
+        let validated =
+            validate_staging_dir(&outside.path().to_string_lossy(), Some(xplane.path())).unwrap();
+        assert_eq!(validated, outside.path());
+    }
+}
@@ -0,0 +1,468 @@
+//! Stored data summary and erase
+//!
+//! Lists everything XFast keeps on this machine, grouped by [`StorageCategory`],
+//! and removes selected categories. App data locations come from
+//! [`app_dirs::app_storage_locations`]; anything else found in the app data
+//! directory is listed as `Other`, so nothing is left out when the registry
+//! lags behind.
+//!
+//! Inside the X-Plane folder only files XFast wrote itself are considered: updater
+//! logins saved by the credentials commands, ACF variant state files and leftover
+//! install staging folders. They are listed separately and only touched when the
+//! caller confirms them. The plugin quarantine state is never listed, since
+//! removing it would leave quarantined plugins without a way back.
+
+use crate::app_dirs::{self, StorageCategory, StorageLocation};
+use crate::x_updater_profile;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Depth below `Aircraft` searched for ACF variant state files
+const ACF_STATE_SEARCH_DEPTH: usize = 4;
+
+/// What erasing an entry does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EraseAction {
+    /// Delete the file or folder
+    Delete,
+    /// Remove the login and license key lines, keeping the rest of the file
+    ClearCredentials,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredDataEntry {
+    pub category: StorageCategory,
+    pub path: String,
+    pub description: String,
+    pub size_bytes: u64,
+    pub action: EraseAction,
+    pub in_xplane_folder: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredDataCategory {
+    pub category: StorageCategory,
+    pub size_bytes: u64,
+    pub entries: Vec<StoredDataEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredDataSummary {
+    pub app_data_dir: String,
+    /// App data, grouped by category
+    pub categories: Vec<StoredDataCategory>,
+    /// Files XFast wrote inside X-Plane folders, which need separate confirmation
+    pub xplane_files: Vec<StoredDataEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraseFailure {
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraseResult {
+    pub dry_run: bool,
+    /// Entries removed, or in a dry run the entries that would be removed
+    pub removed: Vec<StoredDataEntry>,
+    pub failed: Vec<EraseFailure>,
+    pub freed_bytes: u64,
+}
+
+/// Summarize stored data. X-Plane files other than saved logins are only
+/// searched for when `xplane_path` is given.
+pub fn get_stored_data_summary(xplane_path: Option<&Path>) -> StoredDataSummary {
+    let app_data_dir = app_dirs::get_app_data_dir();
+    let app_entries = collect_app_entries(&app_data_dir, &app_dirs::app_storage_locations());
+
+    let mut grouped: BTreeMap<StorageCategory, StoredDataCategory> = BTreeMap::new();
+    for entry in app_entries {
+        let group = grouped
+            .entry(entry.category)
+            .or_insert_with(|| StoredDataCategory {
+                category: entry.category,
+                size_bytes: 0,
+                entries: Vec::new(),
+            });
+        group.size_bytes += entry.size_bytes;
+        group.entries.push(entry);
+    }
+
+    StoredDataSummary {
+        app_data_dir: app_data_dir.to_string_lossy().to_string(),
+        categories: grouped.into_values().collect(),
+        xplane_files: collect_xplane_entries(
+            xplane_path,
+            &x_updater_profile::recorded_credential_files(),
+        ),
+    }
+}
+
+/// Entries that erasing `categories` would remove. X-Plane files are only
+/// included when `include_xplane_files` confirms them.
+pub fn plan_erase(
+    summary: &StoredDataSummary,
+    categories: &[StorageCategory],
+    include_xplane_files: bool,
+) -> Vec<StoredDataEntry> {
+    let selected: HashSet<StorageCategory> = categories.iter().copied().collect();
+    let xplane_entries: Vec<&StoredDataEntry> = summary
+        .xplane_files
+        .iter()
+        .filter(|entry| selected.contains(&entry.category))
+        .collect();
+    // Without the list of profiles, saved logins that stay behind couldn't be found again
+    let keep_credential_ledger = !include_xplane_files
+        && xplane_entries
+            .iter()
+            .any(|entry| entry.action == EraseAction::ClearCredentials);
+    let credential_ledger = app_dirs::get_credential_ledger_path();
+
+    let mut plan: Vec<StoredDataEntry> = Vec::new();
+    if include_xplane_files {
+        plan.extend(xplane_entries.into_iter().cloned());
+    }
+    plan.extend(
+        summary
+            .categories
+            .iter()
+            .filter(|group| selected.contains(&group.category))
+            .flat_map(|group| group.entries.iter())
+            .filter(|entry| {
+                !(keep_credential_ledger && Path::new(&entry.path) == credential_ledger)
+            })
+            .cloned(),
+    );
+    plan
+}
+
+/// Remove the planned entries, X-Plane files first
+pub fn erase_entries(plan: &[StoredDataEntry]) -> EraseResult {
+    let mut removed = Vec::new();
+    let mut failed = Vec::new();
+    let mut cleared_profiles = Vec::new();
+
+    for entry in plan {
+        let path = PathBuf::from(&entry.path);
+        let outcome = match entry.action {
+            EraseAction::Delete => remove_path(&path),
+            EraseAction::ClearCredentials => x_updater_profile::clear_credentials_in_file(&path)
+                .map(|_| cleared_profiles.push(path.clone()))
+                .map_err(|e| e.to_string()),
+        };
+        match outcome {
+            Ok(()) => removed.push(entry.clone()),
+            Err(error) => failed.push(EraseFailure {
+                path: entry.path.clone(),
+                error,
+            }),
+        }
+    }
+
+    if !cleared_profiles.is_empty() {
+        if let Err(e) = x_updater_profile::forget_credential_files(&cleared_profiles) {
+            crate::logger::log_error(
+                &format!("Failed to update credentials locations: {}", e),
+                Some("stored_data"),
+            );
+        }
+    }
+
+    let freed_bytes = removed
+        .iter()
+        .filter(|entry| entry.action == EraseAction::Delete)
+        .map(|entry| entry.size_bytes)
+        .sum();
+    EraseResult {
+        dry_run: false,
+        removed,
+        failed,
+        freed_bytes,
+    }
+}
+
+/// Result of a dry run, listing exactly what [`erase_entries`] would remove
+pub fn dry_run_result(plan: Vec<StoredDataEntry>) -> EraseResult {
+    let freed_bytes = plan
+        .iter()
+        .filter(|entry| entry.action == EraseAction::Delete)
+        .map(|entry| entry.size_bytes)
+        .sum();
+    EraseResult {
+        dry_run: true,
+        removed: plan,
+        failed: Vec::new(),
+        freed_bytes,
+    }
+}
+
+fn collect_app_entries(app_data_dir: &Path, locations: &[StorageLocation]) -> Vec<StoredDataEntry> {
+    let mut entries: Vec<StoredDataEntry> = locations
+        .iter()
+        .filter(|location| location.path.exists())
+        .map(|location| StoredDataEntry {
+            category: location.category,
+            path: location.path.to_string_lossy().to_string(),
+            description: location.description.to_string(),
+            size_bytes: path_size(&location.path),
+            action: EraseAction::Delete,
+            in_xplane_folder: false,
+        })
+        .collect();
+
+    let registered: HashSet<&Path> = locations.iter().map(|l| l.path.as_path()).collect();
+    if let Ok(read_dir) = fs::read_dir(app_data_dir) {
+        let mut unregistered: Vec<PathBuf> = read_dir
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| !registered.contains(path.as_path()))
+            .collect();
+        unregistered.sort();
+        entries.extend(unregistered.into_iter().map(|path| StoredDataEntry {
+            category: StorageCategory::Other,
+            size_bytes: path_size(&path),
+            path: path.to_string_lossy().to_string(),
+            description: "Unrecognized app data".to_string(),
+            action: EraseAction::Delete,
+            in_xplane_folder: false,
+        }));
+    }
+
+    entries
+}
+
+fn collect_xplane_entries(
+    xplane_path: Option<&Path>,
+    credential_files: &[PathBuf],
+) -> Vec<StoredDataEntry> {
+    let mut entries: Vec<StoredDataEntry> = credential_files
+        .iter()
+        .map(|path| StoredDataEntry {
+            category: StorageCategory::Credentials,
+            path: path.to_string_lossy().to_string(),
+            description: "Saved addon updater login".to_string(),
+            size_bytes: 0,
+            action: EraseAction::ClearCredentials,
+            in_xplane_folder: true,
+        })
+        .collect();
+
+    let Some(xplane_path) = xplane_path else {
+        return entries;
+    };
+
+    let xplane_file = |path: PathBuf, description: &str| StoredDataEntry {
+        category: StorageCategory::XplaneFiles,
+        size_bytes: path_size(&path),
+        path: path.to_string_lossy().to_string(),
+        description: description.to_string(),
+        action: EraseAction::Delete,
+        in_xplane_folder: true,
+    };
+
+    if let Ok(read_dir) = fs::read_dir(xplane_path) {
+        let mut staging: Vec<PathBuf> = read_dir
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(app_dirs::ATOMIC_INSTALL_TEMP_PREFIX)
+                    && entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false)
+            })
+            .map(|entry| entry.path())
+            .collect();
+        staging.sort();
+        entries.extend(
+            staging
+                .into_iter()
+                .map(|path| xplane_file(path, "Leftover install staging folder")),
+        );
+    }
+
+    let acf_states = WalkDir::new(xplane_path.join("Aircraft"))
+        .max_depth(ACF_STATE_SEARCH_DEPTH)
+        .sort_by_file_name()
+        .into_iter()
+        .flatten()
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && entry.file_name() == app_dirs::AIRCRAFT_ACF_STATE_FILE_NAME
+        })
+        .map(|entry| xplane_file(entry.into_path(), "Aircraft variant state"));
+    entries.extend(acf_states);
+
+    entries
+}
+
+fn path_size(path: &Path) -> u64 {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => WalkDir::new(path)
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum(),
+        Ok(metadata) => metadata.len(),
+        Err(_) => 0,
+    }
+}
+
+fn remove_path(path: &Path) -> Result<(), String> {
+    let result = match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    };
+    result.map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn location(category: StorageCategory, path: PathBuf) -> StorageLocation {
+        StorageLocation {
+            category,
+            path,
+            description: "test",
+        }
+    }
+
+    #[test]
+    fn app_entries_include_registered_and_unrecognized_data() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        fs::write(root.join("scenery.db"), vec![0u8; 10]).unwrap();
+        fs::create_dir_all(root.join("logs")).unwrap();
+        fs::write(root.join("logs").join("a.log"), vec![0u8; 5]).unwrap();
+        fs::write(root.join("leftover.tmp"), vec![0u8; 3]).unwrap();
+
+        let locations = vec![
+            location(StorageCategory::Database, root.join("scenery.db")),
+            location(StorageCategory::Logs, root.join("logs")),
+            location(StorageCategory::Submissions, root.join("missing.json")),
+        ];
+        let entries = collect_app_entries(root, &locations);
+
+        let summary: Vec<(StorageCategory, u64)> = entries
+            .iter()
+            .map(|entry| (entry.category, entry.size_bytes))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (StorageCategory::Database, 10),
+                (StorageCategory::Logs, 5),
+                (StorageCategory::Other, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn xplane_entries_only_list_files_xfast_wrote() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let xplane = temp.path();
+        let aircraft = xplane.join("Aircraft").join("Laminar").join("Cessna");
+        fs::create_dir_all(&aircraft).unwrap();
+        fs::write(aircraft.join(app_dirs::AIRCRAFT_ACF_STATE_FILE_NAME), "{}").unwrap();
+        fs::write(aircraft.join("Cessna.acf"), "acf").unwrap();
+        fs::create_dir_all(xplane.join(format!("{}abc", app_dirs::ATOMIC_INSTALL_TEMP_PREFIX)))
+            .unwrap();
+        fs::create_dir_all(xplane.join("Custom Scenery")).unwrap();
+
+        let profile = xplane.join("x-updater.cfg");
+        let entries = collect_xplane_entries(Some(xplane), std::slice::from_ref(&profile));
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].action, EraseAction::ClearCredentials);
+        assert!(entries.iter().all(|entry| entry.in_xplane_folder));
+        assert!(entries[1].path.ends_with("abc"));
+        assert!(entries[2]
+            .path
+            .ends_with(app_dirs::AIRCRAFT_ACF_STATE_FILE_NAME));
+    }
+
+    #[test]
+    fn plan_requires_confirmation_for_xplane_files() {
+        let entry = |category, path: &str, action, in_xplane_folder| StoredDataEntry {
+            category,
+            path: path.to_string(),
+            description: String::new(),
+            size_bytes: 1,
+            action,
+            in_xplane_folder,
+        };
+        let ledger = app_dirs::get_credential_ledger_path();
+        let summary = StoredDataSummary {
+            app_data_dir: String::new(),
+            categories: vec![StoredDataCategory {
+                category: StorageCategory::Credentials,
+                size_bytes: 1,
+                entries: vec![entry(
+                    StorageCategory::Credentials,
+                    &ledger.to_string_lossy(),
+                    EraseAction::Delete,
+                    false,
+                )],
+            }],
+            xplane_files: vec![entry(
+                StorageCategory::Credentials,
+                "/xplane/Aircraft/A/x-updater.cfg",
+                EraseAction::ClearCredentials,
+                true,
+            )],
+        };
+
+        // The ledger stays while logins it points to are left in place
+        assert!(plan_erase(&summary, &[StorageCategory::Credentials], false).is_empty());
+
+        let plan = plan_erase(&summary, &[StorageCategory::Credentials], true);
+        assert_eq!(plan.len(), 2);
+        assert!(plan[0].in_xplane_folder);
+
+        assert!(plan_erase(&summary, &[StorageCategory::Logs], true).is_empty());
+    }
+
+    #[test]
+    fn erase_removes_files_and_folders_and_reports_sizes() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let file = temp.path().join("cache.txt");
+        let dir = temp.path().join("updates");
+        fs::write(&file, vec![0u8; 4]).unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("setup.exe"), vec![0u8; 6]).unwrap();
+
+        let plan: Vec<StoredDataEntry> = collect_app_entries(
+            temp.path(),
+            &[
+                location(StorageCategory::Caches, file.clone()),
+                location(StorageCategory::Caches, dir.clone()),
+            ],
+        );
+
+        let dry_run = dry_run_result(plan.clone());
+        assert!(dry_run.dry_run);
+        assert_eq!(dry_run.freed_bytes, 10);
+        assert!(file.exists() && dir.exists());
+
+        let result = erase_entries(&plan);
+        assert!(result.failed.is_empty());
+        assert_eq!(result.removed.len(), 2);
+        assert_eq!(result.freed_bytes, 10);
+        assert!(!file.exists() && !dir.exists());
+    }
+}
@@ -0,0 +1,205 @@
+//! Support bundle export
+//!
+//! Packs everything needed to diagnose a user report into a single zip:
+//! the app's (rotated) log files, X-Plane's latest Log.txt, scenery index
+//! statistics and basic app/OS information.
+
+use crate::app_dirs;
+use crate::logger;
+use crate::models::SceneryIndexStats;
+use crate::xplane_version;
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemInfo {
+    app_version: String,
+    os: String,
+    arch: String,
+    generated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    xplane_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    xplane_version: Option<String>,
+}
+
+/// Everything that goes into a bundle, gathered before any zip is written
+struct BundleContents<'a> {
+    log_files: Vec<PathBuf>,
+    xplane_log: Option<PathBuf>,
+    scenery_stats: Option<&'a SceneryIndexStats>,
+    system_info: SystemInfo,
+}
+
+/// Destination folder for bundles: the user's Downloads folder, falling back
+/// to the app data directory when the platform doesn't report one
+fn bundle_output_dir() -> PathBuf {
+    dirs::download_dir().unwrap_or_else(app_dirs::get_app_data_dir)
+}
+
+/// Build a support bundle in the Downloads folder and return its path
+pub fn export_support_bundle(
+    xplane_path: Option<&Path>,
+    scenery_stats: Option<&SceneryIndexStats>,
+) -> Result<PathBuf> {
+    let output_dir = bundle_output_dir();
+    fs::create_dir_all(&output_dir)?;
+
+    let file_name = format!(
+        "XFast-Manager-support-{}.zip",
+        Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let output_path = output_dir.join(file_name);
+
+    let xplane_log = xplane_path
+        .map(|p| p.join("Log.txt"))
+        .filter(|p| p.is_file());
+
+    let contents = BundleContents {
+        log_files: logger::get_log_files(),
+        xplane_log,
+        scenery_stats,
+        system_info: SystemInfo {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            generated_at: Local::now().to_rfc3339(),
+            xplane_path: xplane_path.map(|p| p.to_string_lossy().to_string()),
+            xplane_version: xplane_path
+                .and_then(xplane_version::get_xplane_version)
+                .map(|v| v.display()),
+        },
+    };
+
+    write_bundle(&output_path, &contents)?;
+
+    logger::log_info(
+        &format!("Support bundle exported to {}", output_path.display()),
+        Some("support"),
+    );
+
+    Ok(output_path)
+}
+
+fn write_bundle(output_path: &Path, contents: &BundleContents) -> Result<()> {
+    let file = File::create(output_path)
+        .map_err(|e| anyhow!("Failed to create {}: {}", output_path.display(), e))?;
+    let mut writer = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for log_file in &contents.log_files {
+        let Some(name) = log_file.file_name() else {
+            continue;
+        };
+        add_file(
+            &mut writer,
+            options,
+            &format!("logs/{}", name.to_string_lossy()),
+            log_file,
+        )?;
+    }
+
+    if let Some(xplane_log) = &contents.xplane_log {
+        add_file(&mut writer, options, "xplane/Log.txt", xplane_log)?;
+    }
+
+    if let Some(stats) = contents.scenery_stats {
+        writer.start_file("scenery_index_stats.json", options)?;
+        writer.write_all(serde_json::to_string_pretty(stats)?.as_bytes())?;
+    }
+
+    writer.start_file("system_info.json", options)?;
+    writer.write_all(serde_json::to_string_pretty(&contents.system_info)?.as_bytes())?;
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// Copy a file into the archive; files that vanish or can't be read are skipped
+/// so one locked log doesn't prevent the rest of the bundle from being written
+fn add_file(
+    writer: &mut ZipWriter<File>,
+    options: SimpleFileOptions,
+    entry_name: &str,
+    source: &Path,
+) -> Result<()> {
+    let mut input = match File::open(source) {
+        Ok(f) => f,
+        Err(e) => {
+            logger::log_info(
+                &format!("Skipping {} in support bundle: {}", source.display(), e),
+                Some("support"),
+            );
+            return Ok(());
+        }
+    };
+
+    writer.start_file(entry_name, options)?;
+    io::copy(&mut input, writer)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::time::SystemTime;
+    use tempfile::tempdir;
+
+    #[test]
+    fn bundle_contains_logs_xplane_log_and_metadata() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let log_path = temp.path().join("xfastmanager.log");
+        let rotated_path = temp.path().join("xfastmanager.log.1");
+        let xplane_log = temp.path().join("Log.txt");
+        fs::write(&log_path, "current").expect("failed to write log");
+        fs::write(&rotated_path, "previous").expect("failed to write rotated log");
+        fs::write(&xplane_log, "X-Plane 12.1.4-r1").expect("failed to write Log.txt");
+
+        let stats = SceneryIndexStats {
+            total_packages: 3,
+            by_category: HashMap::new(),
+            by_region: HashMap::new(),
+            last_updated: SystemTime::now(),
+        };
+        let contents = BundleContents {
+            log_files: vec![rotated_path, log_path],
+            xplane_log: Some(xplane_log),
+            scenery_stats: Some(&stats),
+            system_info: SystemInfo {
+                app_version: "1.0.0".to_string(),
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                generated_at: "now".to_string(),
+                xplane_path: None,
+                xplane_version: None,
+            },
+        };
+
+        let output_path = temp.path().join("bundle.zip");
+        write_bundle(&output_path, &contents).expect("bundle should be written");
+
+        let archive =
+            zip::ZipArchive::new(File::open(&output_path).expect("failed to open bundle"))
+                .expect("bundle should be a valid zip");
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "logs/xfastmanager.log",
+                "logs/xfastmanager.log.1",
+                "scenery_index_stats.json",
+                "system_info.json",
+                "xplane/Log.txt",
+            ]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// Task control state for managing installation cancellation and skipping
@@ -11,6 +11,19 @@ pub struct TaskControl {
     skip_current: Arc<AtomicBool>,
     /// List of files/directories created during installation (for cleanup)
     processed_paths: Arc<Mutex<Vec<PathBuf>>>,
+    /// Number of installations currently running
+    active_installs: Arc<AtomicUsize>,
+}
+
+/// Marks an installation as running until dropped
+pub struct ActiveInstallGuard {
+    active_installs: Arc<AtomicUsize>,
+}
+
+impl Drop for ActiveInstallGuard {
+    fn drop(&mut self) {
+        self.active_installs.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl TaskControl {
@@ -19,9 +32,23 @@ impl TaskControl {
             cancel_all: Arc::new(AtomicBool::new(false)),
             skip_current: Arc::new(AtomicBool::new(false)),
             processed_paths: Arc::new(Mutex::new(Vec::new())),
+            active_installs: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Mark an installation as running for as long as the guard lives
+    pub fn begin_install(&self) -> ActiveInstallGuard {
+        self.active_installs.fetch_add(1, Ordering::SeqCst);
+        ActiveInstallGuard {
+            active_installs: Arc::clone(&self.active_installs),
         }
     }
 
+    /// Check if an installation is running
+    pub fn is_install_active(&self) -> bool {
+        self.active_installs.load(Ordering::SeqCst) > 0
+    }
+
     /// Request cancellation of all tasks
     pub fn request_cancel_all(&self) {
         self.cancel_all.store(true, Ordering::SeqCst);
@@ -101,6 +128,18 @@ mod tests {
         assert!(control.get_processed_paths().is_empty());
     }
 
+    #[test]
+    fn test_active_install_guard() {
+        let control = TaskControl::new();
+        let first = control.begin_install();
+        let second = control.clone().begin_install();
+        assert!(control.is_install_active());
+        drop(first);
+        assert!(control.is_install_active());
+        drop(second);
+        assert!(!control.is_install_active());
+    }
+
     #[test]
     fn test_task_control_default() {
         let control = TaskControl::default();
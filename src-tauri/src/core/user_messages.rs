@@ -0,0 +1,382 @@
+//! Catalog of user-facing backend messages
+//!
+//! Each message has a stable key and an English template with positional
+//! `{0}`, `{1}`, ... placeholders. Errors built from the catalog keep the
+//! rendered English text as their message and also carry the key and
+//! arguments, so the frontend can show the message in the user's language
+//! from its `errors.messages` map.
+
+use std::fmt;
+
+/// Declares [`UserMsg`] together with its list of variants, so the list used
+/// by the catalog tests can't miss a message
+macro_rules! user_messages {
+    ($($variant:ident,)*) => {
+        /// Catalog message keys
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum UserMsg {
+            $($variant,)*
+        }
+
+        impl UserMsg {
+            /// Every message, in declaration order
+            #[cfg(test)]
+            pub const ALL: &'static [UserMsg] = &[$(UserMsg::$variant,)*];
+        }
+    };
+}
+
+user_messages! {
+    // Installer
+    InstallationFailed,
+    TaskCancelled,
+    TaskSkipped,
+    TaskPanicked,
+    InstallQueueClosed,
+    VerificationFailed,
+    DependencyNotInstalled,
+    NavdataDowngradeNotAllowed,
+    // Scenery commands
+    InvalidFolderName,
+    InvalidPath,
+    SceneryFolderNotFound,
+    SceneryFolderNotFoundNamed,
+    ScenerySourceFolderNotFound,
+    SceneryEntryExists,
+    SceneryHasDependents,
+    PreviewSceneryDeletionFailed,
+    CheckSceneryDependentsFailed,
+    DeletePermissionDenied,
+    DeleteSceneryLinkFailed,
+    DeleteSceneryFileFailed,
+    DeleteSceneryFolderFailed,
+    SceneryClassificationFailed,
+    ResetSortOrderFailed,
+    ResetGlobalAirportsPositionFailed,
+    RebuildIndexFailed,
+    SyncSceneryPacksFailed,
+    GetSceneryManagerDataFailed,
+    GlobalAirportsCannotBePinned,
+    UpdateGlobalAirportsStateFailed,
+    UpdateGlobalAirportsPositionFailed,
+    UpdateGlobalAirportsCategoryFailed,
+    UpdateSceneryEntryFailed,
+    MoveGlobalAirportsFailed,
+    MoveSceneryEntryFailed,
+    UpdateIndexFailed,
+    ApplySceneryChangesFailed,
+    // Addon updater
+    AddonUpdateFailed,
+    AddonUpdateRollbackFailed,
+    AddonTargetNotFound,
+    LegacyUpdaterNoCredentials,
+    NoUpdaterCredentials,
+    XUpdaterProfileNotFound,
+    XUpdaterProfileUnsupported,
+    XUpdaterMissingLogin,
+    XUpdaterMissingLicenseKey,
+    XUpdaterAuthFailed,
+    XUpdaterNoProductsForAccount,
+    XUpdaterNoMatchingProduct,
+}
+
+impl UserMsg {
+    /// Key of the message in the frontend `errors.messages` map
+    pub fn key(&self) -> &'static str {
+        match self {
+            UserMsg::InstallationFailed => "installation_failed",
+            UserMsg::TaskCancelled => "task_cancelled",
+            UserMsg::TaskSkipped => "task_skipped",
+            UserMsg::TaskPanicked => "task_panicked",
+            UserMsg::InstallQueueClosed => "install_queue_closed",
+            UserMsg::VerificationFailed => "verification_failed",
+            UserMsg::DependencyNotInstalled => "dependency_not_installed",
+            UserMsg::NavdataDowngradeNotAllowed => "navdata_downgrade_not_allowed",
+            UserMsg::InvalidFolderName => "invalid_folder_name",
+            UserMsg::InvalidPath => "invalid_path",
+            UserMsg::SceneryFolderNotFound => "scenery_folder_not_found",
+            UserMsg::SceneryFolderNotFoundNamed => "scenery_folder_not_found_named",
+            UserMsg::ScenerySourceFolderNotFound => "scenery_source_folder_not_found",
+            UserMsg::SceneryEntryExists => "scenery_entry_exists",
+            UserMsg::SceneryHasDependents => "scenery_has_dependents",
+            UserMsg::PreviewSceneryDeletionFailed => "preview_scenery_deletion_failed",
+            UserMsg::CheckSceneryDependentsFailed => "check_scenery_dependents_failed",
+            UserMsg::DeletePermissionDenied => "delete_permission_denied",
+            UserMsg::DeleteSceneryLinkFailed => "delete_scenery_link_failed",
+            UserMsg::DeleteSceneryFileFailed => "delete_scenery_file_failed",
+            UserMsg::DeleteSceneryFolderFailed => "delete_scenery_folder_failed",
+            UserMsg::SceneryClassificationFailed => "scenery_classification_failed",
+            UserMsg::ResetSortOrderFailed => "reset_sort_order_failed",
+            UserMsg::ResetGlobalAirportsPositionFailed => "reset_global_airports_position_failed",
+            UserMsg::RebuildIndexFailed => "rebuild_index_failed",
+            UserMsg::SyncSceneryPacksFailed => "sync_scenery_packs_failed",
+            UserMsg::GetSceneryManagerDataFailed => "get_scenery_manager_data_failed",
+            UserMsg::GlobalAirportsCannotBePinned => "global_airports_cannot_be_pinned",
+            UserMsg::UpdateGlobalAirportsStateFailed => "update_global_airports_state_failed",
+            UserMsg::UpdateGlobalAirportsPositionFailed => "update_global_airports_position_failed",
+            UserMsg::UpdateGlobalAirportsCategoryFailed => "update_global_airports_category_failed",
+            UserMsg::UpdateSceneryEntryFailed => "update_scenery_entry_failed",
+            UserMsg::MoveGlobalAirportsFailed => "move_global_airports_failed",
+            UserMsg::MoveSceneryEntryFailed => "move_scenery_entry_failed",
+            UserMsg::UpdateIndexFailed => "update_index_failed",
+            UserMsg::ApplySceneryChangesFailed => "apply_scenery_changes_failed",
+            UserMsg::AddonUpdateFailed => "addon_update_failed",
+            UserMsg::AddonUpdateRollbackFailed => "addon_update_rollback_failed",
+            UserMsg::AddonTargetNotFound => "addon_target_not_found",
+            UserMsg::LegacyUpdaterNoCredentials => "legacy_updater_no_credentials",
+            UserMsg::NoUpdaterCredentials => "no_updater_credentials",
+            UserMsg::XUpdaterProfileNotFound => "xupdater_profile_not_found",
+            UserMsg::XUpdaterProfileUnsupported => "xupdater_profile_unsupported",
+            UserMsg::XUpdaterMissingLogin => "xupdater_missing_login",
+            UserMsg::XUpdaterMissingLicenseKey => "xupdater_missing_license_key",
+            UserMsg::XUpdaterAuthFailed => "xupdater_auth_failed",
+            UserMsg::XUpdaterNoProductsForAccount => "xupdater_no_products_for_account",
+            UserMsg::XUpdaterNoMatchingProduct => "xupdater_no_matching_product",
+        }
+    }
+
+    /// English template, also the fallback when the frontend has no translation
+    pub fn template(&self) -> &'static str {
+        match self {
+            UserMsg::InstallationFailed => "Installation failed: {0}",
+            UserMsg::TaskCancelled => "Cancelled by user",
+            UserMsg::TaskSkipped => "Skipped by user",
+            UserMsg::TaskPanicked => "Task panicked: {0}",
+            UserMsg::InstallQueueClosed => "Semaphore closed",
+            UserMsg::VerificationFailed => "Verification failed: {0}",
+            UserMsg::DependencyNotInstalled => "Skipped because '{0}' was not installed",
+            UserMsg::NavdataDowngradeNotAllowed => {
+                "Navdata cycle {0} is older than the installed cycle {1}. Allow the downgrade to install it anyway."
+            }
+            UserMsg::InvalidFolderName => "Invalid folder name: path traversal not allowed",
+            UserMsg::InvalidPath => "Invalid path: {0}",
+            UserMsg::SceneryFolderNotFound => "Scenery folder not found",
+            UserMsg::SceneryFolderNotFoundNamed => "Scenery folder not found: {0}",
+            UserMsg::ScenerySourceFolderNotFound => "Scenery source folder not found",
+            UserMsg::SceneryEntryExists => "A scenery entry named '{0}' already exists",
+            UserMsg::SceneryHasDependents => {
+                "{0} enabled packages depend on libraries only {1} provides"
+            }
+            UserMsg::PreviewSceneryDeletionFailed => "Failed to preview scenery deletion: {0}",
+            UserMsg::CheckSceneryDependentsFailed => "Failed to check scenery dependents: {0}",
+            UserMsg::DeletePermissionDenied => "Permission denied when deleting: {0}",
+            UserMsg::DeleteSceneryLinkFailed => "Failed to delete scenery link: {0} ({1}; {2})",
+            UserMsg::DeleteSceneryFileFailed => "Failed to delete scenery file: {0}",
+            UserMsg::DeleteSceneryFolderFailed => "Failed to delete scenery folder: {0}",
+            UserMsg::SceneryClassificationFailed => "Classification failed: {0}",
+            UserMsg::ResetSortOrderFailed => "Failed to reset sort order: {0}",
+            UserMsg::ResetGlobalAirportsPositionFailed => {
+                "Failed to reset Global Airports position: {0}"
+            }
+            UserMsg::RebuildIndexFailed => "Failed to rebuild index: {0}",
+            UserMsg::SyncSceneryPacksFailed => "Failed to sync scenery packs: {0}",
+            UserMsg::GetSceneryManagerDataFailed => "Failed to get scenery manager data: {0}",
+            UserMsg::GlobalAirportsCannotBePinned => "Global Airports cannot be pinned",
+            UserMsg::UpdateGlobalAirportsStateFailed => {
+                "Failed to update Global Airports state: {0}"
+            }
+            UserMsg::UpdateGlobalAirportsPositionFailed => {
+                "Failed to update Global Airports position: {0}"
+            }
+            UserMsg::UpdateGlobalAirportsCategoryFailed => {
+                "Failed to update Global Airports category: {0}"
+            }
+            UserMsg::UpdateSceneryEntryFailed => "Failed to update scenery entry: {0}",
+            UserMsg::MoveGlobalAirportsFailed => "Failed to move Global Airports entry: {0}",
+            UserMsg::MoveSceneryEntryFailed => "Failed to move scenery entry: {0}",
+            UserMsg::UpdateIndexFailed => "Failed to update index: {0}",
+            UserMsg::ApplySceneryChangesFailed => "Failed to apply scenery changes: {0}",
+            UserMsg::AddonUpdateFailed => "Update failed: {0}",
+            UserMsg::AddonUpdateRollbackFailed => "Update failed: {0}. Rollback also failed: {1}",
+            UserMsg::AddonTargetNotFound => "Target path does not exist: {0}",
+            UserMsg::LegacyUpdaterNoCredentials => {
+                "This addon is using legacy updater metadata and does not support account credentials"
+            }
+            UserMsg::NoUpdaterCredentials => "No existing updater credentials found for this addon",
+            UserMsg::XUpdaterProfileNotFound => "No x-updater profile was found in '{0}'",
+            UserMsg::XUpdaterProfileUnsupported => {
+                "'{0}' uses an x-updater profile without Skunkcrafts updater metadata, which can't be updated here. Update it with the developer's updater instead."
+            }
+            UserMsg::XUpdaterMissingLogin => "x-updater profile is missing login/username",
+            UserMsg::XUpdaterMissingLicenseKey => "x-updater profile is missing license key",
+            UserMsg::XUpdaterAuthFailed => "x-updater authentication failed: HTTP {0} ({1})",
+            UserMsg::XUpdaterNoProductsForAccount => {
+                "x-updater returned no products for this account"
+            }
+            UserMsg::XUpdaterNoMatchingProduct => {
+                "x-updater did not return a product matching '{0}'"
+            }
+        }
+    }
+
+    /// Message without arguments
+    pub fn error(self) -> LocalizedError {
+        self.error_with(std::iter::empty::<String>())
+    }
+
+    /// Message with positional arguments
+    pub fn error_with<I, S>(self, args: I) -> LocalizedError
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        LocalizedError {
+            msg: self,
+            args: args.into_iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+}
+
+/// Substitute `{n}` placeholders with their arguments in a single pass, so
+/// placeholders inside an argument are left as they are
+pub fn render(template: &str, args: &[String]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let arg = after.find('}').and_then(|end| {
+            let digits = &after[..end];
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let index = digits.parse::<usize>().ok()?;
+            Some((args.get(index)?, end))
+        });
+        match arg {
+            Some((arg, end)) => {
+                rendered.push_str(arg);
+                rest = &after[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Catalog message with its arguments. Displays as the English text, so it
+/// can travel through `anyhow` like any other error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalizedError {
+    pub msg: UserMsg,
+    pub args: Vec<String>,
+}
+
+impl fmt::Display for LocalizedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&render(self.msg.template(), &self.args))
+    }
+}
+
+impl std::error::Error for LocalizedError {}
+
+impl From<UserMsg> for LocalizedError {
+    fn from(msg: UserMsg) -> Self {
+        msg.error()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of distinct `{n}` placeholders in a template
+    fn placeholder_count(template: &str) -> usize {
+        (0..)
+            .take_while(|index| template.contains(&format!("{{{}}}", index)))
+            .count()
+    }
+
+    const FRONTEND_LOCALES: &[(&str, &str)] = &[
+        ("en", include_str!("../../../src/i18n/en.ts")),
+        ("zh", include_str!("../../../src/i18n/zh.ts")),
+        ("es", include_str!("../../../src/i18n/es.ts")),
+        ("fr", include_str!("../../../src/i18n/fr.ts")),
+        ("de", include_str!("../../../src/i18n/de.ts")),
+        ("ja", include_str!("../../../src/i18n/ja.ts")),
+        ("pt", include_str!("../../../src/i18n/pt.ts")),
+        ("hi", include_str!("../../../src/i18n/hi.ts")),
+        ("ar", include_str!("../../../src/i18n/ar.ts")),
+        ("ru", include_str!("../../../src/i18n/ru.ts")),
+        ("ko", include_str!("../../../src/i18n/ko.ts")),
+    ];
+
+    /// Body of the `errors.messages` map of a locale file
+    fn messages_block(source: &str) -> &str {
+        let errors = source.find("\n  errors: {").expect("errors section");
+        let start = errors
+            + source[errors..]
+                .find("\n    messages: {")
+                .expect("errors.messages map");
+        let end = start + source[start..].find("\n    },").expect("end of messages");
+        &source[start..end]
+    }
+
+    /// Translation of one key, which may be wrapped onto the next line
+    fn translation(block: &str, key: &str) -> Option<String> {
+        let prefix = format!("      {}:", key);
+        let mut lines = block.lines().skip_while(|line| !line.starts_with(&prefix));
+        let mut text = lines.next()?[prefix.len()..].trim().to_string();
+        for line in lines.take_while(|line| line.starts_with("        ")) {
+            text.push_str(line.trim());
+        }
+        Some(text)
+    }
+
+    #[test]
+    fn keys_are_unique() {
+        let mut keys: Vec<&str> = UserMsg::ALL.iter().map(|msg| msg.key()).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        assert_eq!(keys.len(), UserMsg::ALL.len());
+    }
+
+    #[test]
+    fn every_key_is_translated_in_each_locale() {
+        for (locale, source) in FRONTEND_LOCALES {
+            let block = messages_block(source);
+            for msg in UserMsg::ALL {
+                let text = translation(block, msg.key()).unwrap_or_else(|| {
+                    panic!("{} is missing errors.messages.{}", locale, msg.key())
+                });
+                assert_eq!(
+                    placeholder_count(&text),
+                    placeholder_count(msg.template()),
+                    "{} errors.messages.{} has the wrong placeholders",
+                    locale,
+                    msg.key()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn renders_positional_arguments() {
+        let err = UserMsg::SceneryHasDependents.error_with([3.to_string(), "Lib".to_string()]);
+        assert_eq!(
+            err.to_string(),
+            "3 enabled packages depend on libraries only Lib provides"
+        );
+        assert_eq!(
+            UserMsg::TaskCancelled.error().to_string(),
+            "Cancelled by user"
+        );
+        assert_eq!(
+            placeholder_count(UserMsg::DeleteSceneryLinkFailed.template()),
+            3
+        );
+    }
+
+    #[test]
+    fn arguments_are_not_rendered_again() {
+        assert_eq!(
+            render("{0} and {1}", &["{1}".to_string(), "b".to_string()]),
+            "{1} and b"
+        );
+        assert_eq!(render("{0} {2} {x", &["a".to_string()]), "a {2} {x");
+    }
+}
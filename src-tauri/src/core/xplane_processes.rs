@@ -0,0 +1,292 @@
+//! Running X-Plane processes and the installation each one runs from
+//!
+//! With several installations, a sim running from one doesn't stop changes to
+//! another, so guards ask whether X-Plane runs from the folder they modify.
+//! Processes are listed with their executable: from /proc on Linux, `ps` on
+//! macOS and a CIM query on Windows, falling back to `tasklist`, which only
+//! gives PIDs. The installation is the folder holding the executable, or the
+//! .app bundle on macOS. A process whose executable can't be read could belong
+//! to any installation and counts as running from every one.
+//!
+//! `launch_xplane` records the PID it spawned so a sim started from XFast can
+//! be told apart. On macOS the sim is started through `open`, whose PID is not
+//! the sim's, so it is never recognized there.
+
+use crate::logger;
+use crate::path_utils::normalize_xplane_key;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+/// PIDs spawned by `launch_xplane` in this session
+static LAUNCHED_PIDS: LazyLock<Mutex<HashSet<u32>>> = LazyLock::new(Default::default);
+
+/// A running X-Plane process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XPlaneProcess {
+    pub pid: u32,
+    /// None when the process can't be inspected, e.g. it runs elevated
+    pub executable_path: Option<String>,
+    /// Known installation the executable belongs to
+    pub matched_install: Option<String>,
+    /// Started by `launch_xplane` in this session
+    pub launched_by_app: bool,
+}
+
+/// Remember a PID spawned by `launch_xplane`
+pub fn record_launch(pid: u32) {
+    if let Ok(mut pids) = LAUNCHED_PIDS.lock() {
+        pids.insert(pid);
+    }
+}
+
+/// Whether a file name is the X-Plane executable: `X-Plane.exe`, `X-Plane`
+/// inside the macOS bundle, `X-Plane-x86_64` on Linux
+fn is_xplane_executable_name(name: &str) -> bool {
+    let stem = name
+        .strip_suffix(".exe")
+        .or_else(|| name.strip_suffix(".EXE"))
+        .unwrap_or(name);
+    stem.eq_ignore_ascii_case("X-Plane")
+        || stem
+            .get(..8)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("X-Plane-"))
+}
+
+/// X-Plane root an executable runs from
+fn install_root(executable: &Path) -> Option<&Path> {
+    let bundle = executable.ancestors().find(|ancestor| {
+        ancestor
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("app"))
+    });
+    match bundle {
+        Some(bundle) => bundle.parent(),
+        None => executable.parent(),
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn run(command: &str, args: &[&str]) -> Option<String> {
+    let mut cmd = std::process::Command::new(command);
+    cmd.args(args);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    match cmd.output() {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+        Ok(_) => None,
+        Err(e) => {
+            logger::log_debug(
+                &format!("Failed to run {}: {}", command, e),
+                Some("app"),
+                None,
+            );
+            None
+        }
+    }
+}
+
+/// PID and executable of each process named like the X-Plane executable
+#[cfg(target_os = "windows")]
+fn list_raw() -> Vec<(u32, Option<PathBuf>)> {
+    const CIM_QUERY: &str = "Get-CimInstance Win32_Process -Filter \"Name LIKE 'X-Plane%'\" | \
+         ForEach-Object { \"$($_.ProcessId)|$($_.Name)|$($_.ExecutablePath)\" }";
+    if let Some(stdout) = run(
+        "powershell",
+        &["-NoProfile", "-NonInteractive", "-Command", CIM_QUERY],
+    ) {
+        return stdout
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.trim().splitn(3, '|');
+                let pid = fields.next()?.parse().ok()?;
+                let name = fields.next()?;
+                let path = fields.next().map(str::trim).filter(|p| !p.is_empty());
+                is_xplane_executable_name(name).then(|| (pid, path.map(PathBuf::from)))
+            })
+            .collect();
+    }
+
+    // Without PowerShell: PIDs only, as "X-Plane.exe","1234",...
+    run(
+        "tasklist",
+        &["/FI", "IMAGENAME eq X-Plane.exe", "/FO", "CSV", "/NH"],
+    )
+    .map(|stdout| {
+        stdout
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split("\",\"");
+                let name = fields.next()?.trim_start_matches('"');
+                let pid = fields.next()?.parse().ok()?;
+                is_xplane_executable_name(name).then_some((pid, None))
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn list_raw() -> Vec<(u32, Option<PathBuf>)> {
+    // `comm` is the full executable path on macOS
+    run("ps", &["-axo", "pid=,comm="])
+        .map(|stdout| {
+            stdout
+                .lines()
+                .filter_map(|line| {
+                    let (pid, command) = line.trim().split_once(char::is_whitespace)?;
+                    let path = PathBuf::from(command.trim());
+                    let name = path.file_name()?.to_str()?;
+                    if !is_xplane_executable_name(name) {
+                        return None;
+                    }
+                    Some((pid.parse().ok()?, path.is_absolute().then_some(path)))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn list_raw() -> Vec<(u32, Option<PathBuf>)> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            // The exe link of other users' processes can't be read; comm still names them
+            let executable = std::fs::read_link(entry.path().join("exe")).ok();
+            let name = match &executable {
+                Some(path) => path.file_name()?.to_string_lossy().into_owned(),
+                None => std::fs::read_to_string(entry.path().join("comm"))
+                    .ok()?
+                    .trim()
+                    .to_string(),
+            };
+            is_xplane_executable_name(&name).then_some((pid, executable))
+        })
+        .collect()
+}
+
+fn to_processes(raw: Vec<(u32, Option<PathBuf>)>, known_roots: &[String]) -> Vec<XPlaneProcess> {
+    let known: Vec<(String, &String)> = known_roots
+        .iter()
+        .map(|root| (normalize_xplane_key(Path::new(root)), root))
+        .collect();
+    let mut launched = LAUNCHED_PIDS.lock().ok();
+    // Forget launches that ended, their PIDs may be reused
+    if let Some(pids) = launched.as_mut() {
+        pids.retain(|pid| raw.iter().any(|(running, _)| running == pid));
+    }
+
+    raw.into_iter()
+        .map(|(pid, executable)| {
+            let matched_install = executable
+                .as_deref()
+                .and_then(install_root)
+                .map(normalize_xplane_key)
+                .and_then(|key| {
+                    known
+                        .iter()
+                        .find(|(known_key, _)| *known_key == key)
+                        .map(|(_, root)| (*root).clone())
+                });
+            XPlaneProcess {
+                pid,
+                executable_path: executable.map(|path| path.to_string_lossy().into_owned()),
+                matched_install,
+                launched_by_app: launched.as_ref().is_some_and(|pids| pids.contains(&pid)),
+            }
+        })
+        .collect()
+}
+
+async fn list_raw_blocking() -> Vec<(u32, Option<PathBuf>)> {
+    match tokio::task::spawn_blocking(list_raw).await {
+        Ok(raw) => raw,
+        Err(e) => {
+            logger::log_debug(&format!("Task join error: {}", e), Some("app"), None);
+            Vec::new()
+        }
+    }
+}
+
+/// Running X-Plane processes, matched against the `known_roots` installations
+pub async fn running_processes(known_roots: &[String]) -> Vec<XPlaneProcess> {
+    let processes = to_processes(list_raw_blocking().await, known_roots);
+    logger::log_debug(
+        &format!("X-Plane processes running: {}", processes.len()),
+        Some("app"),
+        None,
+    );
+    processes
+}
+
+/// Whether X-Plane runs from any installation
+pub async fn is_any_running() -> bool {
+    !list_raw_blocking().await.is_empty()
+}
+
+/// Whether X-Plane runs from `xplane_path`, or from a place that can't be read
+pub async fn is_running_at(xplane_path: &Path) -> bool {
+    let key = normalize_xplane_key(xplane_path);
+    list_raw_blocking().await.iter().any(|(_, executable)| {
+        match executable.as_deref().and_then(install_root) {
+            Some(root) => normalize_xplane_key(root) == key,
+            None => true,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_processes_match_the_install_they_run_from() {
+        let temp = tempfile::tempdir().unwrap();
+        let xp11 = temp.path().join("X-Plane 11");
+        let xp12 = temp.path().join("X-Plane 12");
+        let bundle_exe = xp12.join("X-Plane.app").join("Contents").join("MacOS");
+        std::fs::create_dir_all(&xp11).unwrap();
+        std::fs::create_dir_all(&bundle_exe).unwrap();
+        let known = vec![
+            xp11.to_string_lossy().into_owned(),
+            xp12.to_string_lossy().into_owned(),
+        ];
+
+        record_launch(4242);
+        let processes = to_processes(
+            vec![
+                (4242, Some(xp11.join("X-Plane.exe"))),
+                (77, Some(bundle_exe.join("X-Plane"))),
+                (
+                    78,
+                    Some(temp.path().join("Elsewhere").join("X-Plane-x86_64")),
+                ),
+                (79, None),
+            ],
+            &known,
+        );
+        assert_eq!(processes[0].matched_install.as_ref(), Some(&known[0]));
+        assert!(processes[0].launched_by_app);
+        assert_eq!(processes[1].matched_install.as_ref(), Some(&known[1]));
+        assert!(!processes[1].launched_by_app);
+        assert!(processes[2].matched_install.is_none());
+        assert!(processes[3].matched_install.is_none());
+
+        assert!(is_xplane_executable_name("X-Plane.exe"));
+        assert!(is_xplane_executable_name("X-Plane-x86_64"));
+        assert!(!is_xplane_executable_name("X-Plane Installer.exe"));
+    }
+}
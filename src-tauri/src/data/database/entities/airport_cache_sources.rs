@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "airport_cache_sources")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    /// Absolute path of the parsed apt.dat
+    pub apt_dat_path: String,
+    /// apt.dat modification time (Unix seconds) when it was parsed
+    pub mtime: i64,
+    pub indexed_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {
+    Entries,
+}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        match self {
+            Self::Entries => Entity::has_many(super::airport_cache_entries::Entity).into(),
+        }
+    }
+}
+
+impl Related<super::airport_cache_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entries.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "analysis_suppressions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    /// Kind of warning, e.g. `duplicate_plugin`
+    pub kind: String,
+    /// Stable identifier of the warning, prefixed with its kind
+    pub key: String,
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations defined")
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
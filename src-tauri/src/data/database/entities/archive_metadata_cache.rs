@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "archive_metadata_cache")]
+pub struct Model {
+    /// SHA-256 over the archive size and its first and last 64 KB
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub fingerprint: String,
+    /// Path the archive was last seen at
+    pub path: String,
+    pub file_size: i64,
+    /// Archive modification time (Unix seconds) when it was cached
+    pub modified_at: i64,
+    pub uncompressed_size: i64,
+    pub file_count: i64,
+    /// Unix seconds of the last cache hit or insert, for LRU eviction
+    pub last_used_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "deferred_scenery_applies")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub xplane_path: String,
+    /// `Vec<SceneryEntryUpdate>` as JSON, one per folder
+    pub entries: String,
+    /// Folder names deleted while X-Plane was running, as a JSON array
+    pub deleted_folders: String,
+    /// When the first change of this apply was queued
+    pub queued_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations defined")
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "download_queue")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub url: String,
+    pub xplane_path: String,
+    /// "queued", "downloading", "analyzing", "completed" or "failed"
+    pub status: String,
+    /// Name the server gave the file; the partial download is `<file_name>.part`
+    pub file_name: Option<String>,
+    /// Bytes received when the download last stopped
+    pub downloaded_bytes: i64,
+    pub total_bytes: Option<i64>,
+    /// Validators of the first response, sent as `If-Range` when resuming
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub error_message: Option<String>,
+    /// `AnalysisResult` of the downloaded file as JSON
+    pub analysis: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations defined")
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -3,6 +3,9 @@ use sea_orm::entity::prelude::*;
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "index_metadata")]
 pub struct Model {
+    /// Normalized X-Plane root the entry belongs to
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub xplane_path: String,
     #[sea_orm(primary_key, auto_increment = false)]
     pub key: String,
     pub value: String,
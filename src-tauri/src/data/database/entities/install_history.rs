@@ -0,0 +1,37 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "install_history")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub xplane_path: String,
+    /// Serialized `AddonType`, e.g. "SceneryLibrary"
+    pub addon_type: String,
+    pub display_name: String,
+    pub target_path: String,
+    /// File name of the archive or folder the addon was installed from
+    pub source_name: Option<String>,
+    pub installed_at: i64,
+    pub bytes: Option<i64>,
+    pub manifest_path: Option<String>,
+    /// "installed", "replaced" or "uninstalled"
+    pub status: String,
+    pub uninstalled_at: Option<i64>,
+    /// SHA-256 of the source archive, or the signature of a source folder
+    pub source_signature: Option<String>,
+    /// File count, total size and newest modification time of the target
+    /// right after the install
+    pub target_signature: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations defined")
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
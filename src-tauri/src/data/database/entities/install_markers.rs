@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "install_markers")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub task_id: String,
+    pub display_name: String,
+    pub addon_type: String,
+    pub target_path: String,
+    pub xplane_path: String,
+    /// The target was already there when the task started
+    pub target_existed: bool,
+    pub started_at: i64,
+    /// Set once a launch found the marker and left the target for the user:
+    /// "targetExisted", "looksComplete", "keptInPlace", "cleanupFailed" or
+    /// "setAside" (then `target_path` is the set-aside copy)
+    pub review_reason: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations defined")
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "maintenance_runs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub xplane_path: String,
+    /// JSON array of the requested sections, in run order
+    pub sections: String,
+    /// JSON array of the finished section reports
+    pub results: String,
+    /// `running`, `cancelled` or `completed`
+    pub status: String,
+    /// Started by the background scheduler rather than the user
+    pub scheduled: bool,
+    pub started_at: i64,
+    /// Unix seconds of the last persisted progress
+    pub updated_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations defined")
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,8 +1,21 @@
 pub mod activity_log;
 pub mod addon_presets;
+pub mod airport_cache_entries;
+pub mod airport_cache_sources;
+pub mod analysis_suppressions;
+pub mod archive_metadata_cache;
+pub mod deferred_scenery_applies;
+pub mod download_queue;
 pub mod exported_libraries;
 pub mod gateway_installs;
 pub mod index_metadata;
+pub mod install_history;
+pub mod install_markers;
+pub mod install_profiles;
+pub mod launch_profiles;
+pub mod maintenance_runs;
 pub mod missing_libraries;
 pub mod required_libraries;
 pub mod scenery_packages;
+pub mod scenery_stats_snapshots;
+pub mod xplane_log_snapshots;
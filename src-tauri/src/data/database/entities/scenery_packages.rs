@@ -5,6 +5,9 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i64,
+    /// Normalized X-Plane root the package belongs to, empty for rows indexed
+    /// before installations were told apart
+    pub xplane_path: String,
     pub folder_name: String,
     pub category: String,
     pub sub_priority: i32,
@@ -18,11 +21,16 @@ pub struct Model {
     pub has_objects: bool,
     pub texture_count: i32,
     pub earth_nav_tile_count: i32,
+    pub total_size_bytes: i64,
+    pub newest_file_modified: Option<i64>,
     pub enabled: bool,
     pub sort_order: i32,
     pub actual_path: Option<String>,
     pub continent: Option<String>,
     pub original_category: Option<String>,
+    pub classifier_version: i32,
+    /// Keeps its position when the list is sorted automatically
+    pub pinned: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
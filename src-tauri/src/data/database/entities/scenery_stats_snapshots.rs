@@ -0,0 +1,25 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "scenery_stats_snapshots")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub xplane_path: String,
+    pub taken_at: i64,
+    pub category: String,
+    pub package_count: i64,
+    pub total_size_bytes: i64,
+    pub missing_library_count: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations defined")
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
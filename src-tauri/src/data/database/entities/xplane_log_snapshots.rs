@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "xplane_log_snapshots")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub xplane_path: String,
+    pub recorded_at: i64,
+    /// Unix seconds; together with `log_size` identifies the session
+    pub log_modified_at: i64,
+    pub log_size: i64,
+    pub xplane_version: Option<String>,
+    pub crash_detected: bool,
+    /// JSON object of issue category -> matching line count
+    pub category_counts: String,
+    pub total_high: i32,
+    pub total_medium: i32,
+    pub total_low: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter)]
+pub enum Relation {}
+
+impl RelationTrait for Relation {
+    fn def(&self) -> RelationDef {
+        panic!("No relations defined")
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,246 @@
+//! Integrity self-check and compaction of the scenery database
+//!
+//! Runs at startup and on demand. It checks the file with
+//! `PRAGMA integrity_check`, removes library rows whose package no longer
+//! exists, and compacts the file when too much of it is free pages. A file
+//! that fails the check is only reported: deleting rows from or rewriting a
+//! damaged file could lose what is still readable. Orphaned
+//! rows are left behind when an older build deleted packages with foreign keys
+//! off, or when a migration stopped part-way.
+
+use crate::error::ApiError;
+use crate::logger;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement, TransactionTrait};
+use serde::Serialize;
+
+/// Share of free pages above which the database is vacuumed
+const VACUUM_FREE_PAGE_RATIO: f64 = 0.25;
+
+/// Problems `PRAGMA integrity_check` reports at most
+const MAX_INTEGRITY_MESSAGES: usize = 100;
+
+/// Tables with a `package_id` referencing `scenery_packages`
+const PACKAGE_CHILD_TABLES: [&str; 3] = [
+    "required_libraries",
+    "missing_libraries",
+    "exported_libraries",
+];
+
+/// Outcome of a maintenance run
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseMaintenanceReport {
+    /// `PRAGMA integrity_check` reported no problems
+    pub integrity_ok: bool,
+    /// Problems reported by `PRAGMA integrity_check`
+    pub integrity_messages: Vec<String>,
+    /// Integrity problems plus orphaned rows found
+    pub issues_found: u64,
+    /// Library rows removed because their package no longer exists
+    pub orphaned_rows_removed: u64,
+    /// Share of the database that was free pages before the run
+    pub free_page_ratio: f64,
+    /// The database was checkpointed and vacuumed
+    pub vacuumed: bool,
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+}
+
+async fn pragma_i64(conn: &DatabaseConnection, sql: &str) -> Result<i64, ApiError> {
+    let row = conn
+        .query_one(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            sql.to_string(),
+        ))
+        .await
+        .map_err(ApiError::from)?;
+    Ok(row
+        .and_then(|r| r.try_get_by_index::<i64>(0).ok())
+        .unwrap_or(0))
+}
+
+/// Size of the database in bytes and its share of free pages
+async fn size_and_free_ratio(conn: &DatabaseConnection) -> Result<(u64, f64), ApiError> {
+    let page_size = pragma_i64(conn, "PRAGMA page_size").await?.max(0) as u64;
+    let page_count = pragma_i64(conn, "PRAGMA page_count").await?.max(0) as u64;
+    let free_pages = pragma_i64(conn, "PRAGMA freelist_count").await?.max(0) as u64;
+    let ratio = if page_count == 0 {
+        0.0
+    } else {
+        free_pages as f64 / page_count as f64
+    };
+    Ok((page_size * page_count, ratio))
+}
+
+/// Problems found by `PRAGMA integrity_check`; empty when the file is sound
+async fn integrity_check(conn: &DatabaseConnection) -> Result<Vec<String>, ApiError> {
+    let rows = conn
+        .query_all(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            format!("PRAGMA integrity_check({})", MAX_INTEGRITY_MESSAGES),
+        ))
+        .await
+        .map_err(ApiError::from)?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| row.try_get_by_index::<String>(0).ok())
+        .filter(|message| message != "ok")
+        .collect())
+}
+
+/// Delete library rows whose package is gone, all in one transaction.
+/// Returns how many rows were removed.
+async fn remove_orphaned_rows(conn: &DatabaseConnection) -> Result<u64, ApiError> {
+    let txn = conn.begin().await.map_err(ApiError::from)?;
+    let mut removed = 0;
+    for table in PACKAGE_CHILD_TABLES {
+        let result = txn
+            .execute(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                format!(
+                    "DELETE FROM \"{}\" WHERE package_id NOT IN (SELECT id FROM scenery_packages)",
+                    table
+                ),
+            ))
+            .await
+            .map_err(ApiError::from)?;
+        if result.rows_affected > 0 {
+            logger::log_info(
+                &format!(
+                    "Removed {} orphaned row(s) from {}",
+                    result.rows_affected, table
+                ),
+                Some("database"),
+            );
+        }
+        removed += result.rows_affected;
+    }
+    txn.commit().await.map_err(ApiError::from)?;
+    Ok(removed)
+}
+
+async fn checkpoint_and_vacuum(conn: &DatabaseConnection) -> Result<(), ApiError> {
+    for sql in [
+        "PRAGMA wal_checkpoint(TRUNCATE)",
+        "VACUUM",
+        "PRAGMA wal_checkpoint(TRUNCATE)",
+    ] {
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            sql.to_string(),
+        ))
+        .await
+        .map_err(ApiError::from)?;
+    }
+    Ok(())
+}
+
+/// Check, repair and compact the database, stopping after the check when it
+/// reports problems. Only reachable through
+/// [`super::DatabaseState::run_maintenance`], which holds the reset lock for
+/// the whole run so it never overlaps a reset or reopen.
+pub(super) async fn run_maintenance(
+    conn: &DatabaseConnection,
+) -> Result<DatabaseMaintenanceReport, ApiError> {
+    let (size_before_bytes, free_page_ratio) = size_and_free_ratio(conn).await?;
+
+    let integrity_messages = integrity_check(conn).await?;
+    if integrity_messages.is_empty() {
+        logger::log_info("Database integrity check passed", Some("database"));
+    } else {
+        logger::log_error(
+            &format!(
+                "Database integrity check reported {} problem(s), skipping cleanup and compaction: {}",
+                integrity_messages.len(),
+                integrity_messages.join("; ")
+            ),
+            Some("database"),
+        );
+        return Ok(DatabaseMaintenanceReport {
+            integrity_ok: false,
+            issues_found: integrity_messages.len() as u64,
+            integrity_messages,
+            orphaned_rows_removed: 0,
+            free_page_ratio,
+            vacuumed: false,
+            size_before_bytes,
+            size_after_bytes: size_before_bytes,
+        });
+    }
+
+    let orphaned_rows_removed = remove_orphaned_rows(conn).await?;
+
+    // Removed rows free pages too, so decide on the ratio after the cleanup
+    let (_, free_ratio_after_cleanup) = size_and_free_ratio(conn).await?;
+    let vacuumed = free_ratio_after_cleanup > VACUUM_FREE_PAGE_RATIO;
+    if vacuumed {
+        checkpoint_and_vacuum(conn).await?;
+    }
+    let (size_after_bytes, _) = size_and_free_ratio(conn).await?;
+
+    let report = DatabaseMaintenanceReport {
+        integrity_ok: integrity_messages.is_empty(),
+        issues_found: integrity_messages.len() as u64 + orphaned_rows_removed,
+        integrity_messages,
+        orphaned_rows_removed,
+        free_page_ratio,
+        vacuumed,
+        size_before_bytes,
+        size_after_bytes,
+    };
+    logger::log_info(
+        &format!(
+            "Database maintenance finished: {} issue(s), {} orphaned row(s) removed, {} -> {} bytes{}",
+            report.issues_found,
+            report.orphaned_rows_removed,
+            report.size_before_bytes,
+            report.size_after_bytes,
+            if report.vacuumed { " (vacuumed)" } else { "" }
+        ),
+        Some("database"),
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+
+    async fn execute(conn: &DatabaseConnection, sql: &str) {
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Sqlite,
+            sql.to_string(),
+        ))
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_removes_orphaned_library_rows() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        execute(&conn, "PRAGMA foreign_keys=OFF").await;
+        execute(
+            &conn,
+            "INSERT INTO required_libraries (package_id, library_name) VALUES (4242, 'opensceneryx')",
+        )
+        .await;
+        execute(
+            &conn,
+            "INSERT INTO missing_libraries (package_id, library_name) VALUES (4242, 'ruscenery')",
+        )
+        .await;
+        execute(&conn, "PRAGMA foreign_keys=ON").await;
+
+        let report = run_maintenance(&conn).await.unwrap();
+        assert!(report.integrity_ok);
+        assert_eq!(report.orphaned_rows_removed, 2);
+        assert_eq!(report.issues_found, 2);
+        assert!(report.size_after_bytes > 0);
+
+        let again = run_maintenance(&conn).await.unwrap();
+        assert_eq!(again.issues_found, 0);
+    }
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing rows start at 0 and are filled in by the next incremental scan
+        if !manager
+            .has_column("scenery_packages", "total_size_bytes")
+            .await?
+        {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(SceneryPackages::Table)
+                        .add_column(
+                            ColumnDef::new(SceneryPackages::TotalSizeBytes)
+                                .big_integer()
+                                .not_null()
+                                .default(0),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SceneryPackages::Table)
+                    .drop_column(SceneryPackages::TotalSizeBytes)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum SceneryPackages {
+    Table,
+    TotalSizeBytes,
+}
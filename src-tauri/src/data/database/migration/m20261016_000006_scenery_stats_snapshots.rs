@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+/// Depends on `scenery_packages.total_size_bytes` from migration 005, which the
+/// snapshot aggregation reads.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SceneryStatsSnapshots::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SceneryStatsSnapshots::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SceneryStatsSnapshots::XplanePath)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SceneryStatsSnapshots::TakenAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SceneryStatsSnapshots::Category)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SceneryStatsSnapshots::PackageCount)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SceneryStatsSnapshots::TotalSizeBytes)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SceneryStatsSnapshots::MissingLibraryCount)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_scenery_stats_snapshot_taken_at")
+                    .table(SceneryStatsSnapshots::Table)
+                    .col(SceneryStatsSnapshots::XplanePath)
+                    .col(SceneryStatsSnapshots::TakenAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SceneryStatsSnapshots::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum SceneryStatsSnapshots {
+    Table,
+    Id,
+    XplanePath,
+    TakenAt,
+    Category,
+    PackageCount,
+    TotalSizeBytes,
+    MissingLibraryCount,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AirportCacheSources::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AirportCacheSources::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AirportCacheSources::AptDatPath)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AirportCacheSources::Mtime)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AirportCacheSources::IndexedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AirportCacheEntries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AirportCacheEntries::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AirportCacheEntries::SourceId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AirportCacheEntries::Icao)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AirportCacheEntries::Name)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AirportCacheEntries::Latitude)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AirportCacheEntries::Longitude)
+                            .double()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_airport_cache_entries_source")
+                            .from(AirportCacheEntries::Table, AirportCacheEntries::SourceId)
+                            .to(AirportCacheSources::Table, AirportCacheSources::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_airport_cache_entries_source")
+                    .table(AirportCacheEntries::Table)
+                    .col(AirportCacheEntries::SourceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_airport_cache_entries_icao")
+                    .table(AirportCacheEntries::Table)
+                    .col(AirportCacheEntries::Icao)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AirportCacheEntries::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(AirportCacheSources::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum AirportCacheSources {
+    Table,
+    Id,
+    AptDatPath,
+    Mtime,
+    IndexedAt,
+}
+
+#[derive(Iden)]
+enum AirportCacheEntries {
+    Table,
+    Id,
+    SourceId,
+    Icao,
+    Name,
+    Latitude,
+    Longitude,
+}
@@ -0,0 +1,37 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Library names are matched case-insensitively, so the dependency lookups need
+/// NOCASE indexes; the binary ones from the init migration cannot serve them.
+const INDEXES: &[(&str, &str)] = &[
+    ("idx_required_libraries_name_nocase", "required_libraries"),
+    ("idx_exported_libraries_name_nocase", "exported_libraries"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        for (name, table) in INDEXES {
+            conn.execute_unprepared(&format!(
+                "CREATE INDEX IF NOT EXISTS \"{}\" ON \"{}\" (library_name COLLATE NOCASE)",
+                name, table
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        for (name, _) in INDEXES {
+            conn.execute_unprepared(&format!("DROP INDEX IF EXISTS \"{}\"", name))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MaintenanceRuns::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MaintenanceRuns::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MaintenanceRuns::XplanePath)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MaintenanceRuns::Sections)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MaintenanceRuns::Results).string().not_null())
+                    .col(ColumnDef::new(MaintenanceRuns::Status).string().not_null())
+                    .col(
+                        ColumnDef::new(MaintenanceRuns::Scheduled)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(MaintenanceRuns::StartedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MaintenanceRuns::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MaintenanceRuns::FinishedAt).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_maintenance_runs_path")
+                    .table(MaintenanceRuns::Table)
+                    .col(MaintenanceRuns::XplanePath)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MaintenanceRuns::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum MaintenanceRuns {
+    Table,
+    Id,
+    XplanePath,
+    Sections,
+    Results,
+    Status,
+    Scheduled,
+    StartedAt,
+    UpdatedAt,
+    FinishedAt,
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing rows start as NULL and are filled in by the next incremental scan
+        if !manager
+            .has_column("scenery_packages", "newest_file_modified")
+            .await?
+        {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(SceneryPackages::Table)
+                        .add_column(
+                            ColumnDef::new(SceneryPackages::NewestFileModified)
+                                .big_integer()
+                                .null(),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SceneryPackages::Table)
+                    .drop_column(SceneryPackages::NewestFileModified)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum SceneryPackages {
+    Table,
+    NewestFileModified,
+}
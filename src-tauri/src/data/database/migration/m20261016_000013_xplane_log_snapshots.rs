@@ -0,0 +1,112 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(XplaneLogSnapshots::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::XplanePath)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::RecordedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::LogModifiedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::LogSize)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(XplaneLogSnapshots::XplaneVersion).string())
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::CrashDetected)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::CategoryCounts)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::TotalHigh)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::TotalMedium)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(XplaneLogSnapshots::TotalLow)
+                            .integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One snapshot per X-Plane session, identified by the log's mtime and size
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_xplane_log_snapshots_session")
+                    .table(XplaneLogSnapshots::Table)
+                    .col(XplaneLogSnapshots::XplanePath)
+                    .col(XplaneLogSnapshots::LogModifiedAt)
+                    .col(XplaneLogSnapshots::LogSize)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(XplaneLogSnapshots::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum XplaneLogSnapshots {
+    Table,
+    Id,
+    XplanePath,
+    RecordedAt,
+    LogModifiedAt,
+    LogSize,
+    XplaneVersion,
+    CrashDetected,
+    CategoryCounts,
+    TotalHigh,
+    TotalMedium,
+    TotalLow,
+}
@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InstallHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InstallHistory::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InstallHistory::XplanePath)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InstallHistory::AddonType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InstallHistory::DisplayName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InstallHistory::TargetPath)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InstallHistory::SourceName).string())
+                    .col(
+                        ColumnDef::new(InstallHistory::InstalledAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InstallHistory::Bytes).big_integer())
+                    .col(ColumnDef::new(InstallHistory::ManifestPath).string())
+                    .col(ColumnDef::new(InstallHistory::Status).string().not_null())
+                    .col(ColumnDef::new(InstallHistory::UninstalledAt).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_install_history_installed_at")
+                    .table(InstallHistory::Table)
+                    .col(InstallHistory::XplanePath)
+                    .col(InstallHistory::InstalledAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Finding the earlier installs a new one replaces
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_install_history_target")
+                    .table(InstallHistory::Table)
+                    .col(InstallHistory::XplanePath)
+                    .col(InstallHistory::TargetPath)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InstallHistory::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum InstallHistory {
+    Table,
+    Id,
+    XplanePath,
+    AddonType,
+    DisplayName,
+    TargetPath,
+    SourceName,
+    InstalledAt,
+    Bytes,
+    ManifestPath,
+    Status,
+    UninstalledAt,
+}
@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing rows start at 0 so the next quick scan reclassifies them
+        if !manager
+            .has_column("scenery_packages", "classifier_version")
+            .await?
+        {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(SceneryPackages::Table)
+                        .add_column(
+                            ColumnDef::new(SceneryPackages::ClassifierVersion)
+                                .integer()
+                                .not_null()
+                                .default(0),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SceneryPackages::Table)
+                    .drop_column(SceneryPackages::ClassifierVersion)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum SceneryPackages {
+    Table,
+    ClassifierVersion,
+}
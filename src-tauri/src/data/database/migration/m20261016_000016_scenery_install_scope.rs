@@ -0,0 +1,146 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Columns shared by the old and the scoped package table, copied as-is
+const PACKAGE_COLUMNS: &str = "id, folder_name, category, sub_priority, last_modified, \
+     indexed_at, has_apt_dat, airport_id, has_dsf, has_library_txt, has_textures, has_objects, \
+     texture_count, earth_nav_tile_count, total_size_bytes, newest_file_modified, enabled, \
+     sort_order, actual_path, continent, original_category, classifier_version";
+
+/// Package table with folder names unique per installation instead of globally
+const SCOPED_PACKAGES_SQL: &str = "CREATE TABLE \"scenery_packages_scoped\" ( \
+     \"id\" integer NOT NULL PRIMARY KEY AUTOINCREMENT, \
+     \"xplane_path\" varchar NOT NULL DEFAULT '', \
+     \"folder_name\" varchar NOT NULL, \
+     \"category\" varchar NOT NULL, \
+     \"sub_priority\" integer NOT NULL DEFAULT 0, \
+     \"last_modified\" bigint NOT NULL, \
+     \"indexed_at\" bigint NOT NULL, \
+     \"has_apt_dat\" boolean NOT NULL DEFAULT FALSE, \
+     \"airport_id\" varchar, \
+     \"has_dsf\" boolean NOT NULL DEFAULT FALSE, \
+     \"has_library_txt\" boolean NOT NULL DEFAULT FALSE, \
+     \"has_textures\" boolean NOT NULL DEFAULT FALSE, \
+     \"has_objects\" boolean NOT NULL DEFAULT FALSE, \
+     \"texture_count\" integer NOT NULL DEFAULT 0, \
+     \"earth_nav_tile_count\" integer NOT NULL DEFAULT 0, \
+     \"total_size_bytes\" bigint NOT NULL DEFAULT 0, \
+     \"newest_file_modified\" bigint, \
+     \"enabled\" boolean NOT NULL DEFAULT TRUE, \
+     \"sort_order\" integer NOT NULL DEFAULT 0, \
+     \"actual_path\" varchar, \
+     \"continent\" varchar, \
+     \"original_category\" varchar, \
+     \"classifier_version\" integer NOT NULL DEFAULT 0, \
+     UNIQUE (\"xplane_path\", \"folder_name\"))";
+
+/// Metadata keyed by installation and key
+const SCOPED_METADATA_SQL: &str = "CREATE TABLE \"index_metadata_scoped\" ( \
+     \"xplane_path\" varchar NOT NULL DEFAULT '', \
+     \"key\" varchar NOT NULL, \
+     \"value\" varchar NOT NULL, \
+     PRIMARY KEY (\"xplane_path\", \"key\"))";
+
+/// Indexes on the package table, dropped together with the old table
+const PACKAGE_INDEXES: &[(&str, &str)] = &[
+    ("idx_packages_category", "category"),
+    ("idx_packages_sort_order", "sort_order"),
+    ("idx_packages_category_order", "category, sort_order"),
+    ("idx_packages_enabled", "enabled"),
+    ("idx_packages_continent", "continent"),
+];
+
+/// Statements replacing `table` with `scoped_table`, keeping `columns` of every row.
+/// They run as one batch on a single pooled connection, because foreign keys must
+/// stay off there while the old table is dropped or its library rows would cascade.
+fn swap_table_sql(table: &str, scoped_table: &str, create_sql: &str, columns: &str) -> String {
+    format!(
+        "PRAGMA foreign_keys = OFF; \
+         DROP TABLE IF EXISTS \"{scoped}\"; \
+         {create}; \
+         INSERT OR IGNORE INTO \"{scoped}\" ({columns}) SELECT {columns} FROM \"{table}\"; \
+         DROP TABLE \"{table}\"; \
+         ALTER TABLE \"{scoped}\" RENAME TO \"{table}\"; \
+         PRAGMA foreign_keys = ON;",
+        scoped = scoped_table,
+        create = create_sql,
+        columns = columns,
+        table = table,
+    )
+}
+
+async fn create_package_indexes(conn: &SchemaManagerConnection<'_>) -> Result<(), DbErr> {
+    for (name, columns) in PACKAGE_INDEXES {
+        conn.execute_unprepared(&format!(
+            "CREATE INDEX IF NOT EXISTS \"{}\" ON \"scenery_packages\" ({})",
+            name, columns
+        ))
+        .await?;
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing rows get an empty path and are adopted by the first installation
+        // that opens the index (see SceneryQueries::adopt_unscoped_rows)
+        if manager
+            .has_column("scenery_packages", "xplane_path")
+            .await?
+        {
+            return Ok(());
+        }
+
+        let conn = manager.get_connection();
+        conn.execute_unprepared(&swap_table_sql(
+            "scenery_packages",
+            "scenery_packages_scoped",
+            SCOPED_PACKAGES_SQL,
+            PACKAGE_COLUMNS,
+        ))
+        .await?;
+        create_package_indexes(conn).await?;
+        conn.execute_unprepared(&swap_table_sql(
+            "index_metadata",
+            "index_metadata_scoped",
+            SCOPED_METADATA_SQL,
+            "key, value",
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Folder names are unique again, so only one installation's row survives each
+        let conn = manager.get_connection();
+        let unscoped_packages = SCOPED_PACKAGES_SQL
+            .replace("\"xplane_path\" varchar NOT NULL DEFAULT '', ", "")
+            .replace(
+                "UNIQUE (\"xplane_path\", \"folder_name\")",
+                "UNIQUE (\"folder_name\")",
+            );
+        conn.execute_unprepared(&swap_table_sql(
+            "scenery_packages",
+            "scenery_packages_scoped",
+            &unscoped_packages,
+            PACKAGE_COLUMNS,
+        ))
+        .await?;
+        create_package_indexes(conn).await?;
+        conn.execute_unprepared(&swap_table_sql(
+            "index_metadata",
+            "index_metadata_scoped",
+            "CREATE TABLE \"index_metadata_scoped\" ( \
+             \"key\" varchar NOT NULL PRIMARY KEY, \
+             \"value\" varchar NOT NULL)",
+            "key, value",
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AnalysisSuppressions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AnalysisSuppressions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AnalysisSuppressions::Kind)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AnalysisSuppressions::Key)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(AnalysisSuppressions::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A warning is suppressed at most once
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_analysis_suppressions_kind_key")
+                    .table(AnalysisSuppressions::Table)
+                    .col(AnalysisSuppressions::Kind)
+                    .col(AnalysisSuppressions::Key)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AnalysisSuppressions::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum AnalysisSuppressions {
+    Table,
+    Id,
+    Kind,
+    Key,
+    CreatedAt,
+}
@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_column("scenery_packages", "pinned").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(SceneryPackages::Table)
+                        .add_column(
+                            ColumnDef::new(SceneryPackages::Pinned)
+                                .boolean()
+                                .not_null()
+                                .default(false),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SceneryPackages::Table)
+                    .drop_column(SceneryPackages::Pinned)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum SceneryPackages {
+    Table,
+    Pinned,
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager
+            .has_column("install_history", "source_signature")
+            .await?
+        {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(InstallHistory::Table)
+                        .add_column(ColumnDef::new(InstallHistory::SourceSignature).string())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        if !manager
+            .has_column("install_history", "target_signature")
+            .await?
+        {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(InstallHistory::Table)
+                        .add_column(ColumnDef::new(InstallHistory::TargetSignature).string())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InstallHistory::Table)
+                    .drop_column(InstallHistory::TargetSignature)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(InstallHistory::Table)
+                    .drop_column(InstallHistory::SourceSignature)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum InstallHistory {
+    Table,
+    SourceSignature,
+    TargetSignature,
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(InstallProfiles::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InstallProfiles::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InstallProfiles::Identity)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(InstallProfiles::AddonType)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InstallProfiles::Profile).text().not_null())
+                    .col(
+                        ColumnDef::new(InstallProfiles::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One profile per addon; a later install replaces it
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_install_profiles_identity")
+                    .table(InstallProfiles::Table)
+                    .col(InstallProfiles::Identity)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InstallProfiles::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum InstallProfiles {
+    Table,
+    Id,
+    Identity,
+    AddonType,
+    Profile,
+    UpdatedAt,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DownloadQueue::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DownloadQueue::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(DownloadQueue::Url).string().not_null())
+                    .col(
+                        ColumnDef::new(DownloadQueue::XplanePath)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DownloadQueue::Status).string().not_null())
+                    .col(ColumnDef::new(DownloadQueue::FileName).string())
+                    .col(
+                        ColumnDef::new(DownloadQueue::DownloadedBytes)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(DownloadQueue::TotalBytes).big_integer())
+                    .col(ColumnDef::new(DownloadQueue::Etag).string())
+                    .col(ColumnDef::new(DownloadQueue::LastModified).string())
+                    .col(ColumnDef::new(DownloadQueue::ErrorMessage).string())
+                    .col(ColumnDef::new(DownloadQueue::Analysis).text())
+                    .col(
+                        ColumnDef::new(DownloadQueue::CreatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DownloadQueue::UpdatedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DownloadQueue::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum DownloadQueue {
+    Table,
+    Id,
+    Url,
+    XplanePath,
+    Status,
+    FileName,
+    DownloadedBytes,
+    TotalBytes,
+    Etag,
+    LastModified,
+    ErrorMessage,
+    Analysis,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeferredSceneryApplies::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DeferredSceneryApplies::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DeferredSceneryApplies::XplanePath)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DeferredSceneryApplies::Entries)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DeferredSceneryApplies::DeletedFolders)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DeferredSceneryApplies::QueuedAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One pending apply per X-Plane install; later changes are merged into it
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_deferred_scenery_applies_xplane_path")
+                    .table(DeferredSceneryApplies::Table)
+                    .col(DeferredSceneryApplies::XplanePath)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(DeferredSceneryApplies::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum DeferredSceneryApplies {
+    Table,
+    Id,
+    XplanePath,
+    Entries,
+    DeletedFolders,
+    QueuedAt,
+}
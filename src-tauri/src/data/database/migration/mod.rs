@@ -4,6 +4,25 @@ mod m20260220_000001_init;
 mod m20260306_000002_activity_log;
 mod m20260306_000003_presets;
 mod m20260329_000004_gateway_installs;
+mod m20261016_000005_package_size;
+mod m20261016_000006_scenery_stats_snapshots;
+mod m20261016_000007_launch_profiles;
+mod m20261016_000008_airport_cache;
+mod m20261016_000009_library_name_nocase;
+mod m20261016_000010_archive_metadata_cache;
+mod m20261016_000011_maintenance_runs;
+mod m20261016_000012_package_newest_modified;
+mod m20261016_000013_xplane_log_snapshots;
+mod m20261016_000014_install_history;
+mod m20261016_000015_package_classifier_version;
+mod m20261016_000016_scenery_install_scope;
+mod m20261016_000017_analysis_suppressions;
+mod m20261016_000018_package_pinned;
+mod m20261016_000019_install_history_signature;
+mod m20261016_000020_install_profiles;
+mod m20261016_000021_download_queue;
+mod m20261016_000022_install_markers;
+mod m20261016_000023_deferred_scenery_applies;
 
 pub struct Migrator;
 
@@ -15,6 +34,25 @@ impl MigratorTrait for Migrator {
             Box::new(m20260306_000002_activity_log::Migration),
             Box::new(m20260306_000003_presets::Migration),
             Box::new(m20260329_000004_gateway_installs::Migration),
+            Box::new(m20261016_000005_package_size::Migration),
+            Box::new(m20261016_000006_scenery_stats_snapshots::Migration),
+            Box::new(m20261016_000007_launch_profiles::Migration),
+            Box::new(m20261016_000008_airport_cache::Migration),
+            Box::new(m20261016_000009_library_name_nocase::Migration),
+            Box::new(m20261016_000010_archive_metadata_cache::Migration),
+            Box::new(m20261016_000011_maintenance_runs::Migration),
+            Box::new(m20261016_000012_package_newest_modified::Migration),
+            Box::new(m20261016_000013_xplane_log_snapshots::Migration),
+            Box::new(m20261016_000014_install_history::Migration),
+            Box::new(m20261016_000015_package_classifier_version::Migration),
+            Box::new(m20261016_000016_scenery_install_scope::Migration),
+            Box::new(m20261016_000017_analysis_suppressions::Migration),
+            Box::new(m20261016_000018_package_pinned::Migration),
+            Box::new(m20261016_000019_install_history_signature::Migration),
+            Box::new(m20261016_000020_install_profiles::Migration),
+            Box::new(m20261016_000021_download_queue::Migration),
+            Box::new(m20261016_000022_install_markers::Migration),
+            Box::new(m20261016_000023_deferred_scenery_applies::Migration),
         ]
     }
 }
@@ -63,6 +63,19 @@ pub async fn reset_schema(conn: &DatabaseConnection) -> Result<(), ApiError> {
         "activity_log",
         "addon_presets",
         "gateway_installs",
+        "scenery_stats_snapshots",
+        "launch_profiles",
+        "airport_cache_entries",
+        "airport_cache_sources",
+        "archive_metadata_cache",
+        "maintenance_runs",
+        "xplane_log_snapshots",
+        "install_history",
+        "analysis_suppressions",
+        "install_profiles",
+        "download_queue",
+        "install_markers",
+        "deferred_scenery_applies",
         "schema_version",   // legacy rusqlite version table
         "seaql_migrations", // reset migration tracking so migration 001 re-runs
     ] {
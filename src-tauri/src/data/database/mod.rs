@@ -5,6 +5,7 @@
 
 mod connection;
 pub mod entities;
+mod integrity;
 mod migration;
 mod migrations;
 mod queries;
@@ -16,6 +17,7 @@ pub use connection::open_memory_connection;
 #[cfg(test)]
 pub use connection::open_memory_connection_async;
 pub use connection::{delete_database, open_connection_async};
+pub use integrity::DatabaseMaintenanceReport;
 #[cfg(test)]
 pub use migrations::apply_migrations;
 pub use migrations::apply_migrations_async;
@@ -32,16 +34,25 @@ use sea_orm::DatabaseConnection;
 /// `reset_and_reinitialize` can atomically swap the pool for a fresh one after
 /// a schema reset, clearing all sqlx prepared-statement caches that would
 /// otherwise return stale column-descriptor errors after DROP + CREATE TABLE.
-pub struct DatabaseState(std::sync::RwLock<DatabaseConnection>);
+///
+/// Resets, reopens and maintenance runs take the async reset lock, so one of
+/// them never swaps or vacuums the pool while another is still using it.
+pub struct DatabaseState {
+    conn: std::sync::RwLock<DatabaseConnection>,
+    reset_lock: tokio::sync::Mutex<()>,
+}
 
 impl DatabaseState {
     pub fn new(conn: DatabaseConnection) -> Self {
-        Self(std::sync::RwLock::new(conn))
+        Self {
+            conn: std::sync::RwLock::new(conn),
+            reset_lock: tokio::sync::Mutex::new(()),
+        }
     }
 
     /// Return a clone of the current connection (cheap `Arc` clone, sync).
     pub fn get(&self) -> DatabaseConnection {
-        self.0.read().expect("database lock poisoned").clone()
+        self.conn.read().expect("database lock poisoned").clone()
     }
 
     /// Drop all managed tables, re-open a fresh pool, and run migrations.
@@ -51,6 +62,8 @@ impl DatabaseState {
     /// eliminating stale column-descriptor errors that persist after an
     /// in-place schema reset on a live pool.
     pub async fn reset(&self) -> Result<(), crate::error::ApiError> {
+        let _reset_guard = self.reset_lock.lock().await;
+
         // Step 1 — reset schema on the current connection (no lock held).
         reset_schema(&self.get()).await?;
 
@@ -59,7 +72,7 @@ impl DatabaseState {
 
         // Step 3 — atomically swap old pool for the new one under the write lock.
         let old_conn = {
-            let mut lock = self.0.write().expect("database lock poisoned");
+            let mut lock = self.conn.write().expect("database lock poisoned");
             std::mem::replace(&mut *lock, new_conn)
         };
 
@@ -72,4 +85,38 @@ impl DatabaseState {
         );
         Ok(())
     }
+
+    /// Close the pool, run `with_closed` on the blocking pool while nothing holds
+    /// the database files open, then open a fresh pool and apply migrations.
+    ///
+    /// The database is reopened even if `with_closed` fails, so the app stays usable.
+    pub async fn close_and_reopen<T: Send + 'static>(
+        &self,
+        with_closed: impl FnOnce() -> T + Send + 'static,
+    ) -> Result<T, crate::error::ApiError> {
+        let _reset_guard = self.reset_lock.lock().await;
+        let _ = self.get().close().await;
+        let result = tokio::task::spawn_blocking(with_closed).await;
+
+        let new_conn = open_connection_async().await?;
+        apply_migrations_async(&new_conn).await?;
+        let old_conn = {
+            let mut lock = self.conn.write().expect("database lock poisoned");
+            std::mem::replace(&mut *lock, new_conn)
+        };
+        drop(old_conn);
+
+        crate::logger::log_info("Database reopened", Some("database"));
+        result.map_err(|e| crate::error::ApiError::internal(format!("Task join error: {}", e)))
+    }
+
+    /// Integrity check, then orphaned row cleanup and compaction if the check
+    /// passed. Holds the reset lock for the whole run, after waiting for any
+    /// reset or reopen in progress to finish.
+    pub async fn run_maintenance(
+        &self,
+    ) -> Result<DatabaseMaintenanceReport, crate::error::ApiError> {
+        let _reset_guard = self.reset_lock.lock().await;
+        integrity::run_maintenance(&self.get()).await
+    }
 }
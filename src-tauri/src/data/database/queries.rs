@@ -2,11 +2,16 @@
 
 use crate::error::ApiError;
 use crate::logger;
-use crate::models::{SceneryCategory, SceneryIndex, SceneryPackageInfo};
-use sea_orm::sea_query::{Expr, OnConflict};
+use crate::models::{
+    LibraryDependencyStatus, LibraryDependent, NewlyMissingLibrary, PackageLibraryDependency,
+    SceneryCategory, SceneryIndex, SceneryManagerFilter, SceneryManagerSort,
+    SceneryManagerSortField, SceneryPackageInfo,
+};
+use sea_orm::sea_query::{Expr, LikeExpr, OnConflict, Query, SelectStatement};
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
-    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseBackend,
+    DatabaseConnection, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    Set, Statement, TransactionTrait,
 };
 use std::collections::HashMap;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -15,6 +20,140 @@ use super::entities::{
     exported_libraries, index_metadata, missing_libraries, required_libraries, scenery_packages,
 };
 
+/// Packages written per multi-row insert when saving a whole index
+const PACKAGE_INSERT_BATCH_SIZE: usize = 100;
+
+/// Library rows written per multi-row insert, well below SQLite's variable limit
+const LIBRARY_INSERT_BATCH_SIZE: usize = 500;
+
+/// Enabled packages requiring names the library exports. A name is exclusive when
+/// no other enabled package exports it. Library names compare case-insensitively,
+/// served by the NOCASE indexes on both library tables.
+const LIBRARY_DEPENDENTS_SQL: &str = "SELECT d.folder_name, d.category, \
+     COUNT(DISTINCT r.library_name COLLATE NOCASE) AS affected_library_count, \
+     COUNT(DISTINCT CASE WHEN NOT EXISTS ( \
+         SELECT 1 FROM exported_libraries oe \
+         JOIN scenery_packages op ON op.id = oe.package_id \
+         WHERE oe.library_name = r.library_name COLLATE NOCASE \
+         AND op.xplane_path = lib.xplane_path AND op.enabled = 1 AND op.id <> lib.id \
+     ) THEN r.library_name COLLATE NOCASE END) AS exclusive_library_count \
+     FROM scenery_packages lib \
+     JOIN exported_libraries le ON le.package_id = lib.id \
+     JOIN required_libraries r ON r.library_name = le.library_name COLLATE NOCASE \
+     JOIN scenery_packages d ON d.id = r.package_id \
+     WHERE lib.xplane_path = ? AND lib.folder_name = ? \
+     AND d.xplane_path = lib.xplane_path AND d.enabled = 1 AND d.id <> lib.id \
+     GROUP BY d.id \
+     ORDER BY exclusive_library_count DESC, d.sort_order";
+
+/// Required names only the given package exports, per requiring package, that
+/// are not already recorded as missing. Disabled packages count as exporters,
+/// matching how missing libraries are computed during indexing.
+const ORPHANED_LIBRARY_REFERENCES_SQL: &str = "SELECT d.folder_name, r.library_name \
+     FROM scenery_packages lib \
+     JOIN exported_libraries le ON le.package_id = lib.id \
+     JOIN required_libraries r ON r.library_name = le.library_name COLLATE NOCASE \
+     JOIN scenery_packages d ON d.id = r.package_id \
+     WHERE lib.xplane_path = ? AND lib.folder_name = ? \
+     AND d.xplane_path = lib.xplane_path AND d.id <> lib.id \
+     AND NOT EXISTS ( \
+         SELECT 1 FROM exported_libraries oe \
+         JOIN scenery_packages op ON op.id = oe.package_id \
+         WHERE oe.library_name = r.library_name COLLATE NOCASE \
+         AND op.xplane_path = lib.xplane_path AND op.id <> lib.id \
+     ) \
+     AND NOT EXISTS (SELECT 1 FROM missing_libraries m \
+         WHERE m.package_id = d.id AND m.library_name = r.library_name) \
+     GROUP BY r.id \
+     ORDER BY d.sort_order, r.id";
+
+/// Record a library name as missing for a package of an installation
+const INSERT_MISSING_LIBRARY_SQL: &str =
+    "INSERT INTO missing_libraries (package_id, library_name) \
+     SELECT id, ? FROM scenery_packages WHERE xplane_path = ? AND folder_name = ?";
+
+/// One row per (required library, exporting package of the same installation);
+/// the provider columns are NULL when nothing else exports the name
+const PACKAGE_DEPENDENCIES_SQL: &str = "SELECT r.library_name, \
+     p.folder_name AS provider, \
+     p.enabled AS provider_enabled, \
+     EXISTS (SELECT 1 FROM missing_libraries m \
+         WHERE m.package_id = r.package_id AND m.library_name = r.library_name) AS missing \
+     FROM scenery_packages pkg \
+     JOIN required_libraries r ON r.package_id = pkg.id \
+     LEFT JOIN exported_libraries e \
+         ON e.library_name = r.library_name COLLATE NOCASE AND e.package_id <> pkg.id \
+         AND e.package_id IN (SELECT id FROM scenery_packages WHERE xplane_path = pkg.xplane_path) \
+     LEFT JOIN scenery_packages p ON p.id = e.package_id \
+     WHERE pkg.xplane_path = ? AND pkg.folder_name = ? \
+     ORDER BY r.id, p.enabled DESC, p.sort_order";
+
+/// Every missing library name with the package referencing it, in scenery order
+const MISSING_LIBRARY_REFERENCES_SQL: &str = "SELECT m.library_name, p.folder_name, p.category \
+     FROM missing_libraries m \
+     JOIN scenery_packages p ON p.id = m.package_id \
+     WHERE p.xplane_path = ? \
+     ORDER BY p.sort_order, m.id";
+
+/// Packages requiring a name exported by another package, keyed by the exporter
+const LIBRARY_PACKAGE_DEPENDENTS_SQL: &str = "SELECT DISTINCT lib.folder_name AS library_folder, \
+     d.folder_name AS dependent \
+     FROM scenery_packages lib \
+     JOIN exported_libraries le ON le.package_id = lib.id \
+     JOIN required_libraries r ON r.library_name = le.library_name COLLATE NOCASE \
+     JOIN scenery_packages d ON d.id = r.package_id \
+     WHERE lib.xplane_path = ? AND d.xplane_path = lib.xplane_path AND d.id <> lib.id";
+
+/// Packages that can provide libraries, once per exported name (or once with a
+/// NULL name when library.txt exports nothing yet)
+const LIBRARY_PROVIDER_CANDIDATES_SQL: &str = "SELECT p.folder_name, p.enabled, e.library_name \
+     FROM scenery_packages p \
+     LEFT JOIN exported_libraries e ON e.package_id = p.id \
+     WHERE p.xplane_path = ? AND (p.has_library_txt = 1 OR p.category = 'Library') \
+     ORDER BY p.sort_order, p.id";
+
+/// Shortest key considered for a prefix match, so short names like "lib" don't
+/// match every library
+const MIN_FUZZY_PREFIX_LEN: usize = 5;
+
+/// Comparison key for library and folder names: lowercase alphanumerics only,
+/// without a trailing "library"/"lib" and version digits
+fn library_match_key(name: &str) -> String {
+    let mut key: String = name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    key.truncate(key.trim_end_matches(|c: char| c.is_ascii_digit()).len());
+    for suffix in ["library", "lib"] {
+        if key.len() > suffix.len() + 2 && key.ends_with(suffix) {
+            key.truncate(key.len() - suffix.len());
+            break;
+        }
+    }
+    key
+}
+
+/// 2 for equal keys, 1 when one is a long enough prefix of the other
+fn library_match_score(wanted: &str, candidate: &str) -> u8 {
+    if wanted.is_empty() || candidate.is_empty() {
+        return 0;
+    }
+    if wanted == candidate {
+        return 2;
+    }
+    let (short, long) = if wanted.len() <= candidate.len() {
+        (wanted, candidate)
+    } else {
+        (candidate, wanted)
+    };
+    if short.len() >= MIN_FUZZY_PREFIX_LEN && long.starts_with(short) {
+        1
+    } else {
+        0
+    }
+}
+
 /// Convert SystemTime to Unix timestamp (seconds)
 fn systemtime_to_unix(time: &SystemTime) -> i64 {
     time.duration_since(UNIX_EPOCH)
@@ -42,6 +181,7 @@ fn category_to_string(category: &SceneryCategory) -> &'static str {
         SceneryCategory::Library => "Library",
         SceneryCategory::Overlay => "Overlay",
         SceneryCategory::AirportMesh => "AirportMesh",
+        SceneryCategory::OrthoTile => "OrthoTile",
         SceneryCategory::Mesh => "Mesh",
         SceneryCategory::Other => "Other",
         SceneryCategory::Unrecognized => "Unrecognized",
@@ -57,70 +197,147 @@ fn string_to_category(s: &str) -> SceneryCategory {
         "Library" => SceneryCategory::Library,
         "Overlay" => SceneryCategory::Overlay,
         "AirportMesh" => SceneryCategory::AirportMesh,
+        "OrthoTile" => SceneryCategory::OrthoTile,
         "Mesh" => SceneryCategory::Mesh,
         "Unrecognized" => SceneryCategory::Unrecognized,
         _ => SceneryCategory::Other,
     }
 }
 
+/// Convert a package row to SceneryPackageInfo, without its library lists
+fn package_info_from_model(pkg: &scenery_packages::Model) -> SceneryPackageInfo {
+    SceneryPackageInfo {
+        folder_name: pkg.folder_name.clone(),
+        category: string_to_category(&pkg.category),
+        sub_priority: pkg.sub_priority as u8,
+        last_modified: unix_to_systemtime(pkg.last_modified),
+        indexed_at: unix_to_systemtime(pkg.indexed_at),
+        has_apt_dat: pkg.has_apt_dat,
+        airport_id: pkg.airport_id.clone(),
+        has_dsf: pkg.has_dsf,
+        has_library_txt: pkg.has_library_txt,
+        has_textures: pkg.has_textures,
+        has_objects: pkg.has_objects,
+        texture_count: pkg.texture_count as usize,
+        earth_nav_tile_count: pkg.earth_nav_tile_count as u32,
+        total_size_bytes: pkg.total_size_bytes.max(0) as u64,
+        newest_file_modified: pkg.newest_file_modified,
+        enabled: pkg.enabled,
+        sort_order: pkg.sort_order as u32,
+        required_libraries: Vec::new(),
+        missing_libraries: Vec::new(),
+        exported_library_names: Vec::new(),
+        actual_path: pkg.actual_path.clone(),
+        continent: pkg.continent.clone(),
+        original_category: pkg
+            .original_category
+            .as_ref()
+            .map(|s| string_to_category(s)),
+        classifier_version: pkg.classifier_version.max(0) as u32,
+        pinned: pkg.pinned,
+    }
+}
+
+/// Ids of the packages belonging to an installation, for scoping library rows
+fn install_package_ids(install: &str) -> SelectStatement {
+    Query::select()
+        .column(scenery_packages::Column::Id)
+        .from(scenery_packages::Entity)
+        .and_where(scenery_packages::Column::XplanePath.eq(install))
+        .to_owned()
+}
+
+/// Escape LIKE wildcards so user input matches literally (used with `ESCAPE '\\'`)
+fn escape_like_pattern(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 /// Scenery database query operations
 pub struct SceneryQueries;
 
 impl SceneryQueries {
     pub async fn get_metadata(
         conn: &DatabaseConnection,
+        install: &str,
         key: &str,
     ) -> Result<Option<String>, ApiError> {
-        Self::get_metadata_async(conn, key).await
+        Self::get_metadata_async(conn, install, key).await
     }
 
     pub async fn set_metadata(
         conn: &DatabaseConnection,
+        install: &str,
         key: &str,
         value: &str,
     ) -> Result<(), ApiError> {
-        Self::set_metadata_async(conn, key, value).await
+        Self::set_metadata_async(conn, install, key, value).await
+    }
+
+    /// Hand rows indexed before installations were told apart (empty
+    /// `xplane_path`) to `install`, unless it already has packages of its own.
+    /// Returns whether any package was adopted.
+    pub async fn adopt_unscoped_rows(
+        conn: &DatabaseConnection,
+        install: &str,
+    ) -> Result<bool, ApiError> {
+        if install.is_empty() || Self::has_packages(conn, install).await? {
+            return Ok(false);
+        }
+
+        let txn = conn.begin().await.map_err(ApiError::from)?;
+        let adopted = scenery_packages::Entity::update_many()
+            .col_expr(scenery_packages::Column::XplanePath, Expr::value(install))
+            .filter(scenery_packages::Column::XplanePath.eq(""))
+            .exec(&txn)
+            .await
+            .map_err(ApiError::from)?;
+        // Keys the installation already set itself win over the legacy values
+        txn.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "UPDATE OR IGNORE index_metadata SET xplane_path = ? WHERE xplane_path = ''",
+            [install.into()],
+        ))
+        .await
+        .map_err(ApiError::from)?;
+        txn.commit().await.map_err(ApiError::from)?;
+
+        if adopted.rows_affected > 0 {
+            logger::log_info(
+                &format!(
+                    "Assigned {} previously indexed packages to {}",
+                    adopted.rows_affected, install
+                ),
+                Some("database"),
+            );
+        }
+        Ok(adopted.rows_affected > 0)
     }
 
-    /// Load all scenery packages from the database into a SceneryIndex
-    pub async fn load_all(conn: &DatabaseConnection) -> Result<SceneryIndex, ApiError> {
+    /// Load all scenery packages of an installation into a SceneryIndex
+    pub async fn load_all(
+        conn: &DatabaseConnection,
+        install: &str,
+    ) -> Result<SceneryIndex, ApiError> {
         let packages = scenery_packages::Entity::find()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
             .all(conn)
             .await
             .map_err(ApiError::from)?;
 
-        let required_libs = Self::load_all_required_libraries(conn).await?;
-        let missing_libs = Self::load_all_missing_libraries(conn).await?;
-        let exported_libs = Self::load_all_exported_libraries(conn).await?;
+        let required_libs = Self::load_all_required_libraries(conn, install).await?;
+        let missing_libs = Self::load_all_missing_libraries(conn, install).await?;
+        let exported_libs = Self::load_all_exported_libraries(conn, install).await?;
 
         let mut package_map: HashMap<String, SceneryPackageInfo> = HashMap::new();
         for pkg in packages {
-            let mut info = SceneryPackageInfo {
-                folder_name: pkg.folder_name.clone(),
-                category: string_to_category(&pkg.category),
-                sub_priority: pkg.sub_priority as u8,
-                last_modified: unix_to_systemtime(pkg.last_modified),
-                indexed_at: unix_to_systemtime(pkg.indexed_at),
-                has_apt_dat: pkg.has_apt_dat,
-                airport_id: pkg.airport_id.clone(),
-                has_dsf: pkg.has_dsf,
-                has_library_txt: pkg.has_library_txt,
-                has_textures: pkg.has_textures,
-                has_objects: pkg.has_objects,
-                texture_count: pkg.texture_count as usize,
-                earth_nav_tile_count: pkg.earth_nav_tile_count as u32,
-                enabled: pkg.enabled,
-                sort_order: pkg.sort_order as u32,
-                required_libraries: Vec::new(),
-                missing_libraries: Vec::new(),
-                exported_library_names: Vec::new(),
-                actual_path: pkg.actual_path.clone(),
-                continent: pkg.continent.clone(),
-                original_category: pkg
-                    .original_category
-                    .as_ref()
-                    .map(|s| string_to_category(s)),
-            };
+            let mut info = package_info_from_model(&pkg);
 
             if let Some(libs) = required_libs.get(&pkg.id) {
                 info.required_libraries = libs.clone();
@@ -135,13 +352,13 @@ impl SceneryQueries {
             package_map.insert(info.folder_name.clone(), info);
         }
 
-        let last_updated = Self::get_metadata_async(conn, "last_updated")
+        let last_updated = Self::get_metadata_async(conn, install, "last_updated")
             .await?
             .and_then(|s| s.parse::<i64>().ok())
             .map(unix_to_systemtime)
             .unwrap_or_else(SystemTime::now);
 
-        let version = Self::get_metadata_async(conn, "version")
+        let version = Self::get_metadata_async(conn, install, "version")
             .await?
             .and_then(|s| s.parse::<u32>().ok())
             .unwrap_or(1);
@@ -155,8 +372,10 @@ impl SceneryQueries {
 
     async fn load_all_required_libraries(
         conn: &DatabaseConnection,
+        install: &str,
     ) -> Result<HashMap<i64, Vec<String>>, ApiError> {
         let rows = required_libraries::Entity::find()
+            .filter(required_libraries::Column::PackageId.in_subquery(install_package_ids(install)))
             .order_by_asc(required_libraries::Column::PackageId)
             .order_by_asc(required_libraries::Column::Id)
             .all(conn)
@@ -174,8 +393,10 @@ impl SceneryQueries {
 
     async fn load_all_missing_libraries(
         conn: &DatabaseConnection,
+        install: &str,
     ) -> Result<HashMap<i64, Vec<String>>, ApiError> {
         let rows = missing_libraries::Entity::find()
+            .filter(missing_libraries::Column::PackageId.in_subquery(install_package_ids(install)))
             .order_by_asc(missing_libraries::Column::PackageId)
             .order_by_asc(missing_libraries::Column::Id)
             .all(conn)
@@ -193,8 +414,10 @@ impl SceneryQueries {
 
     async fn load_all_exported_libraries(
         conn: &DatabaseConnection,
+        install: &str,
     ) -> Result<HashMap<i64, Vec<String>>, ApiError> {
         let rows = exported_libraries::Entity::find()
+            .filter(exported_libraries::Column::PackageId.in_subquery(install_package_ids(install)))
             .order_by_asc(exported_libraries::Column::PackageId)
             .order_by_asc(exported_libraries::Column::Id)
             .all(conn)
@@ -212,29 +435,39 @@ impl SceneryQueries {
 
     async fn get_metadata_async(
         conn: &DatabaseConnection,
+        install: &str,
         key: &str,
     ) -> Result<Option<String>, ApiError> {
-        let row = index_metadata::Entity::find_by_id(key.to_string())
+        let row = index_metadata::Entity::find_by_id((install.to_string(), key.to_string()))
             .one(conn)
             .await
             .map_err(ApiError::from)?;
         Ok(row.map(|m| m.value))
     }
 
-    async fn set_metadata_async<C>(conn: &C, key: &str, value: &str) -> Result<(), ApiError>
+    async fn set_metadata_async<C>(
+        conn: &C,
+        install: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<(), ApiError>
     where
         C: ConnectionTrait,
     {
         let active = index_metadata::ActiveModel {
+            xplane_path: Set(install.to_string()),
             key: Set(key.to_string()),
             value: Set(value.to_string()),
         };
 
         index_metadata::Entity::insert(active)
             .on_conflict(
-                OnConflict::column(index_metadata::Column::Key)
-                    .update_column(index_metadata::Column::Value)
-                    .to_owned(),
+                OnConflict::columns([
+                    index_metadata::Column::XplanePath,
+                    index_metadata::Column::Key,
+                ])
+                .update_column(index_metadata::Column::Value)
+                .to_owned(),
             )
             .exec(conn)
             .await
@@ -242,93 +475,185 @@ impl SceneryQueries {
         Ok(())
     }
 
-    /// Save a complete SceneryIndex to the database (replaces all data)
-    pub async fn save_all(conn: &DatabaseConnection, index: &SceneryIndex) -> Result<(), ApiError> {
+    /// Save a complete SceneryIndex for an installation (replaces its data)
+    pub async fn save_all(
+        conn: &DatabaseConnection,
+        install: &str,
+        index: &SceneryIndex,
+    ) -> Result<(), ApiError> {
+        let txn = conn.begin().await.map_err(ApiError::from)?;
+        Self::delete_install_packages_async(&txn, install).await?;
+        Self::write_index_async(&txn, install, index).await?;
+        txn.commit().await.map_err(ApiError::from)?;
+        Ok(())
+    }
+
+    /// Replace an installation's index along with all of its metadata, as a
+    /// full rebuild does. Everything happens in one transaction, so a failed
+    /// write leaves the previous index in place.
+    pub async fn replace_all(
+        conn: &DatabaseConnection,
+        install: &str,
+        index: &SceneryIndex,
+    ) -> Result<(), ApiError> {
         let txn = conn.begin().await.map_err(ApiError::from)?;
+        Self::clear_install_async(&txn, install).await?;
+        Self::write_index_async(&txn, install, index).await?;
+        txn.commit().await.map_err(ApiError::from)?;
+        Ok(())
+    }
 
+    /// Insert the packages of `index`, which must not exist yet, in batches
+    /// of [`PACKAGE_INSERT_BATCH_SIZE`] and record its version
+    async fn write_index_async<C>(
+        conn: &C,
+        install: &str,
+        index: &SceneryIndex,
+    ) -> Result<(), ApiError>
+    where
+        C: ConnectionTrait,
+    {
+        let mut packages: Vec<&SceneryPackageInfo> = index.packages.values().collect();
+        packages.sort_by_key(|info| info.sort_order);
+        for batch in packages.chunks(PACKAGE_INSERT_BATCH_SIZE) {
+            Self::insert_package_batch_async(conn, install, batch).await?;
+        }
+
+        Self::set_metadata_async(conn, install, "version", &index.version.to_string()).await?;
+        Self::set_metadata_async(
+            conn,
+            install,
+            "last_updated",
+            &systemtime_to_unix(&index.last_updated).to_string(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Insert packages with multi-row statements, then their library rows
+    async fn insert_package_batch_async<C>(
+        conn: &C,
+        install: &str,
+        batch: &[&SceneryPackageInfo],
+    ) -> Result<(), ApiError>
+    where
+        C: ConnectionTrait,
+    {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        scenery_packages::Entity::insert_many(
+            batch.iter().map(|info| package_active_model(install, info)),
+        )
+        .exec_without_returning(conn)
+        .await
+        .map_err(ApiError::from)?;
+
+        // Multi-row inserts only report the last id, so read back the batch
+        let package_ids: HashMap<String, i64> = scenery_packages::Entity::find()
+            .select_only()
+            .column(scenery_packages::Column::FolderName)
+            .column(scenery_packages::Column::Id)
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .filter(
+                scenery_packages::Column::FolderName
+                    .is_in(batch.iter().map(|info| info.folder_name.clone())),
+            )
+            .into_tuple::<(String, i64)>()
+            .all(conn)
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .collect();
+
+        let mut required = Vec::new();
+        let mut missing = Vec::new();
+        let mut exported = Vec::new();
+        for info in batch {
+            let package_id = *package_ids.get(&info.folder_name).ok_or_else(|| {
+                ApiError::database(format!("Inserted package not found: {}", info.folder_name))
+            })?;
+            required.extend(info.required_libraries.iter().map(|name| {
+                required_libraries::ActiveModel {
+                    id: ActiveValue::NotSet,
+                    package_id: Set(package_id),
+                    library_name: Set(name.clone()),
+                }
+            }));
+            missing.extend(info.missing_libraries.iter().map(|name| {
+                missing_libraries::ActiveModel {
+                    id: ActiveValue::NotSet,
+                    package_id: Set(package_id),
+                    library_name: Set(name.clone()),
+                }
+            }));
+            exported.extend(info.exported_library_names.iter().map(|name| {
+                exported_libraries::ActiveModel {
+                    id: ActiveValue::NotSet,
+                    package_id: Set(package_id),
+                    library_name: Set(name.clone()),
+                }
+            }));
+        }
+
+        for chunk in required.chunks(LIBRARY_INSERT_BATCH_SIZE) {
+            required_libraries::Entity::insert_many(chunk.to_vec())
+                .exec_without_returning(conn)
+                .await
+                .map_err(ApiError::from)?;
+        }
+        for chunk in missing.chunks(LIBRARY_INSERT_BATCH_SIZE) {
+            missing_libraries::Entity::insert_many(chunk.to_vec())
+                .exec_without_returning(conn)
+                .await
+                .map_err(ApiError::from)?;
+        }
+        for chunk in exported.chunks(LIBRARY_INSERT_BATCH_SIZE) {
+            exported_libraries::Entity::insert_many(chunk.to_vec())
+                .exec_without_returning(conn)
+                .await
+                .map_err(ApiError::from)?;
+        }
+        Ok(())
+    }
+
+    /// Delete the packages of an installation along with their library rows
+    async fn delete_install_packages_async<C>(conn: &C, install: &str) -> Result<(), ApiError>
+    where
+        C: ConnectionTrait,
+    {
         required_libraries::Entity::delete_many()
-            .exec(&txn)
+            .filter(required_libraries::Column::PackageId.in_subquery(install_package_ids(install)))
+            .exec(conn)
             .await
             .map_err(ApiError::from)?;
         missing_libraries::Entity::delete_many()
-            .exec(&txn)
+            .filter(missing_libraries::Column::PackageId.in_subquery(install_package_ids(install)))
+            .exec(conn)
             .await
             .map_err(ApiError::from)?;
         exported_libraries::Entity::delete_many()
-            .exec(&txn)
+            .filter(exported_libraries::Column::PackageId.in_subquery(install_package_ids(install)))
+            .exec(conn)
             .await
             .map_err(ApiError::from)?;
         scenery_packages::Entity::delete_many()
-            .exec(&txn)
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .exec(conn)
             .await
             .map_err(ApiError::from)?;
-
-        for info in index.packages.values() {
-            let package_id = Self::insert_package_async(&txn, info).await?;
-            Self::insert_libraries_async(
-                &txn,
-                package_id,
-                &info.required_libraries,
-                LibraryKind::Required,
-            )
-            .await?;
-            Self::insert_libraries_async(
-                &txn,
-                package_id,
-                &info.missing_libraries,
-                LibraryKind::Missing,
-            )
-            .await?;
-            Self::insert_libraries_async(
-                &txn,
-                package_id,
-                &info.exported_library_names,
-                LibraryKind::Exported,
-            )
-            .await?;
-        }
-
-        Self::set_metadata_async(&txn, "version", &index.version.to_string()).await?;
-        Self::set_metadata_async(
-            &txn,
-            "last_updated",
-            &systemtime_to_unix(&index.last_updated).to_string(),
-        )
-        .await?;
-
-        txn.commit().await.map_err(ApiError::from)?;
         Ok(())
     }
 
-    async fn insert_package_async<C>(conn: &C, info: &SceneryPackageInfo) -> Result<i64, ApiError>
+    async fn insert_package_async<C>(
+        conn: &C,
+        install: &str,
+        info: &SceneryPackageInfo,
+    ) -> Result<i64, ApiError>
     where
         C: ConnectionTrait,
     {
-        let active = scenery_packages::ActiveModel {
-            id: ActiveValue::NotSet,
-            folder_name: Set(info.folder_name.clone()),
-            category: Set(category_to_string(&info.category).to_string()),
-            sub_priority: Set(info.sub_priority as i32),
-            last_modified: Set(systemtime_to_unix(&info.last_modified)),
-            indexed_at: Set(systemtime_to_unix(&info.indexed_at)),
-            has_apt_dat: Set(info.has_apt_dat),
-            airport_id: Set(info.airport_id.clone()),
-            has_dsf: Set(info.has_dsf),
-            has_library_txt: Set(info.has_library_txt),
-            has_textures: Set(info.has_textures),
-            has_objects: Set(info.has_objects),
-            texture_count: Set(info.texture_count as i32),
-            earth_nav_tile_count: Set(info.earth_nav_tile_count as i32),
-            enabled: Set(info.enabled),
-            sort_order: Set(info.sort_order as i32),
-            actual_path: Set(info.actual_path.clone()),
-            continent: Set(info.continent.clone()),
-            original_category: Set(info
-                .original_category
-                .as_ref()
-                .map(category_to_string)
-                .map(|s| s.to_string())),
-        };
+        let active = package_active_model(install, info);
 
         let result = scenery_packages::Entity::insert(active)
             .exec(conn)
@@ -390,11 +715,13 @@ impl SceneryQueries {
     /// Update a single package in the database
     pub async fn update_package(
         conn: &DatabaseConnection,
+        install: &str,
         info: &SceneryPackageInfo,
     ) -> Result<(), ApiError> {
         let txn = conn.begin().await.map_err(ApiError::from)?;
 
         let existing = scenery_packages::Entity::find()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
             .filter(scenery_packages::Column::FolderName.eq(&info.folder_name))
             .one(&txn)
             .await
@@ -415,16 +742,20 @@ impl SceneryQueries {
             active.has_objects = Set(info.has_objects);
             active.texture_count = Set(info.texture_count as i32);
             active.earth_nav_tile_count = Set(info.earth_nav_tile_count as i32);
+            active.total_size_bytes = Set(info.total_size_bytes as i64);
+            active.newest_file_modified = Set(info.newest_file_modified);
             active.enabled = Set(info.enabled);
             active.sort_order = Set(info.sort_order as i32);
             active.actual_path = Set(info.actual_path.clone());
             active.continent = Set(info.continent.clone());
+            active.classifier_version = Set(info.classifier_version as i32);
+            active.pinned = Set(info.pinned);
 
             active.update(&txn).await.map_err(ApiError::from)?;
 
             Self::update_package_libraries_async(&txn, id, info).await?;
         } else {
-            let package_id = Self::insert_package_async(&txn, info).await?;
+            let package_id = Self::insert_package_async(&txn, install, info).await?;
             Self::insert_libraries_async(
                 &txn,
                 package_id,
@@ -450,6 +781,7 @@ impl SceneryQueries {
 
         Self::set_metadata_async(
             &txn,
+            install,
             "last_updated",
             &systemtime_to_unix(&SystemTime::now()).to_string(),
         )
@@ -511,9 +843,11 @@ impl SceneryQueries {
     /// Delete a package from the database
     pub async fn delete_package(
         conn: &DatabaseConnection,
+        install: &str,
         folder_name: &str,
     ) -> Result<bool, ApiError> {
         let result = scenery_packages::Entity::delete_many()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
             .filter(scenery_packages::Column::FolderName.eq(folder_name))
             .exec(conn)
             .await
@@ -521,14 +855,75 @@ impl SceneryQueries {
         Ok(result.rows_affected > 0)
     }
 
+    /// Library references that deleting `library_folder` would leave without
+    /// any exporting package
+    pub async fn load_orphaned_library_references(
+        conn: &DatabaseConnection,
+        install: &str,
+        library_folder: &str,
+    ) -> Result<Vec<NewlyMissingLibrary>, ApiError> {
+        let rows = conn
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                ORPHANED_LIBRARY_REFERENCES_SQL,
+                [install.into(), library_folder.into()],
+            ))
+            .await
+            .map_err(ApiError::from)?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(NewlyMissingLibrary {
+                    folder_name: row.try_get("", "folder_name")?,
+                    library_name: row.try_get("", "library_name")?,
+                })
+            })
+            .collect::<Result<_, sea_orm::DbErr>>()
+            .map_err(ApiError::from)
+    }
+
+    /// Delete a package and record the library references it leaves missing in
+    /// one transaction, so dependents never show as satisfied by a removed package
+    pub async fn delete_package_marking_missing(
+        conn: &DatabaseConnection,
+        install: &str,
+        folder_name: &str,
+        newly_missing: &[NewlyMissingLibrary],
+    ) -> Result<bool, ApiError> {
+        let txn = conn.begin().await.map_err(ApiError::from)?;
+        let result = scenery_packages::Entity::delete_many()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .filter(scenery_packages::Column::FolderName.eq(folder_name))
+            .exec(&txn)
+            .await
+            .map_err(ApiError::from)?;
+        for missing in newly_missing {
+            txn.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                INSERT_MISSING_LIBRARY_SQL,
+                [
+                    missing.library_name.as_str().into(),
+                    install.into(),
+                    missing.folder_name.as_str().into(),
+                ],
+            ))
+            .await
+            .map_err(ApiError::from)?;
+        }
+        txn.commit().await.map_err(ApiError::from)?;
+        Ok(result.rows_affected > 0)
+    }
+
     /// Get a single package by folder name (test only)
     #[cfg(test)]
     #[allow(dead_code)]
     pub async fn get_package(
         conn: &DatabaseConnection,
+        install: &str,
         folder_name: &str,
     ) -> Result<Option<SceneryPackageInfo>, ApiError> {
         let pkg = scenery_packages::Entity::find()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
             .filter(scenery_packages::Column::FolderName.eq(folder_name))
             .one(conn)
             .await
@@ -539,32 +934,7 @@ impl SceneryQueries {
             None => return Ok(None),
         };
 
-        let mut info = SceneryPackageInfo {
-            folder_name: pkg.folder_name.clone(),
-            category: string_to_category(&pkg.category),
-            sub_priority: pkg.sub_priority as u8,
-            last_modified: unix_to_systemtime(pkg.last_modified),
-            indexed_at: unix_to_systemtime(pkg.indexed_at),
-            has_apt_dat: pkg.has_apt_dat,
-            airport_id: pkg.airport_id.clone(),
-            has_dsf: pkg.has_dsf,
-            has_library_txt: pkg.has_library_txt,
-            has_textures: pkg.has_textures,
-            has_objects: pkg.has_objects,
-            texture_count: pkg.texture_count as usize,
-            earth_nav_tile_count: pkg.earth_nav_tile_count as u32,
-            enabled: pkg.enabled,
-            sort_order: pkg.sort_order as u32,
-            required_libraries: Vec::new(),
-            missing_libraries: Vec::new(),
-            exported_library_names: Vec::new(),
-            actual_path: pkg.actual_path.clone(),
-            continent: pkg.continent.clone(),
-            original_category: pkg
-                .original_category
-                .as_ref()
-                .map(|s| string_to_category(s)),
-        };
+        let mut info = package_info_from_model(&pkg);
 
         info.required_libraries =
             Self::load_package_libraries(conn, pkg.id, LibraryKind::Required).await?;
@@ -614,15 +984,18 @@ impl SceneryQueries {
         }
     }
 
-    /// Update enabled and sort_order for a package
+    /// Update enabled, sort_order, category and/or pin state for a package
     pub async fn update_entry(
         conn: &DatabaseConnection,
+        install: &str,
         folder_name: &str,
         enabled: Option<bool>,
         sort_order: Option<u32>,
         category: Option<&SceneryCategory>,
+        pinned: Option<bool>,
     ) -> Result<bool, ApiError> {
         let mut update = scenery_packages::Entity::update_many()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
             .filter(scenery_packages::Column::FolderName.eq(folder_name));
         let mut has_updates = false;
 
@@ -644,6 +1017,10 @@ impl SceneryQueries {
             );
             has_updates = true;
         }
+        if let Some(value) = pinned {
+            update = update.col_expr(scenery_packages::Column::Pinned, Expr::value(value));
+            has_updates = true;
+        }
 
         if !has_updates {
             return Ok(false);
@@ -653,17 +1030,19 @@ impl SceneryQueries {
         Ok(result.rows_affected > 0)
     }
 
-    /// Batch update entries (enabled and sort_order only)
+    /// Batch update entries (enabled, sort_order and, when given, pin state)
     /// Uses transaction for optimal performance
     pub async fn batch_update_entries(
         conn: &DatabaseConnection,
+        install: &str,
         entries: &[crate::models::SceneryEntryUpdate],
     ) -> Result<(), ApiError> {
         let txn = conn.begin().await.map_err(ApiError::from)?;
         let mut not_found: Vec<String> = Vec::new();
 
         for entry in entries {
-            let result = scenery_packages::Entity::update_many()
+            let mut update = scenery_packages::Entity::update_many()
+                .filter(scenery_packages::Column::XplanePath.eq(install))
                 .filter(scenery_packages::Column::FolderName.eq(&entry.folder_name))
                 .col_expr(
                     scenery_packages::Column::Enabled,
@@ -672,10 +1051,11 @@ impl SceneryQueries {
                 .col_expr(
                     scenery_packages::Column::SortOrder,
                     Expr::value(entry.sort_order as i32),
-                )
-                .exec(&txn)
-                .await
-                .map_err(ApiError::from)?;
+                );
+            if let Some(pinned) = entry.pinned {
+                update = update.col_expr(scenery_packages::Column::Pinned, Expr::value(pinned));
+            }
+            let result = update.exec(&txn).await.map_err(ApiError::from)?;
 
             if result.rows_affected == 0 {
                 not_found.push(entry.folder_name.clone());
@@ -695,6 +1075,7 @@ impl SceneryQueries {
 
         Self::set_metadata_async(
             &txn,
+            install,
             "last_updated",
             &systemtime_to_unix(&SystemTime::now()).to_string(),
         )
@@ -704,53 +1085,486 @@ impl SceneryQueries {
         Ok(())
     }
 
-    /// Get package count
-    pub async fn get_package_count(conn: &DatabaseConnection) -> Result<usize, ApiError> {
-        let count = scenery_packages::Entity::find()
-            .count(conn)
+    /// Load one page of packages for the scenery manager, along with the number of
+    /// packages matching `filter` on all pages. Filtering, sorting and paging run in
+    /// SQL; library lists are only loaded for the returned packages. DefaultAirport
+    /// and Global Airports rows are excluded, as in the full manager data.
+    pub async fn load_manager_page(
+        conn: &DatabaseConnection,
+        install: &str,
+        filter: &SceneryManagerFilter,
+        sort: &SceneryManagerSort,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<SceneryPackageInfo>, u64), ApiError> {
+        let mut query = scenery_packages::Entity::find()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .filter(
+                scenery_packages::Column::Category
+                    .ne(category_to_string(&SceneryCategory::DefaultAirport)),
+            )
+            .filter(Expr::cust(
+                "LOWER(TRIM(scenery_packages.folder_name)) <> 'global airports'",
+            ));
+
+        if let Some(category) = &filter.category {
+            query =
+                query.filter(scenery_packages::Column::Category.eq(category_to_string(category)));
+        }
+        if let Some(enabled) = filter.enabled {
+            query = query.filter(scenery_packages::Column::Enabled.eq(enabled));
+        }
+        if let Some(continent) = &filter.continent {
+            query = query.filter(scenery_packages::Column::Continent.eq(continent.as_str()));
+        }
+        if let Some(search) = filter.search.as_deref().map(str::trim) {
+            if !search.is_empty() {
+                let pattern = format!("%{}%", escape_like_pattern(search));
+                query = query.filter(
+                    Expr::col(scenery_packages::Column::FolderName)
+                        .like(LikeExpr::new(pattern).escape('\\')),
+                );
+            }
+        }
+
+        let total = query.clone().count(conn).await.map_err(ApiError::from)?;
+
+        let order = if sort.descending {
+            Order::Desc
+        } else {
+            Order::Asc
+        };
+        let column = match sort.field {
+            SceneryManagerSortField::SortOrder => scenery_packages::Column::SortOrder,
+            SceneryManagerSortField::FolderName => scenery_packages::Column::FolderName,
+            SceneryManagerSortField::Category => scenery_packages::Column::Category,
+            SceneryManagerSortField::Enabled => scenery_packages::Column::Enabled,
+            SceneryManagerSortField::Continent => scenery_packages::Column::Continent,
+        };
+        let rows = query
+            .order_by(column, order)
+            .order_by_asc(scenery_packages::Column::SortOrder)
+            .order_by_asc(scenery_packages::Column::Id)
+            .offset(offset)
+            .limit(limit)
+            .all(conn)
             .await
             .map_err(ApiError::from)?;
-        Ok(count as usize)
+
+        let ids: Vec<i64> = rows.iter().map(|row| row.id).collect();
+        let mut required_libs =
+            Self::load_libraries_for_packages(conn, &ids, LibraryKind::Required).await?;
+        let mut missing_libs =
+            Self::load_libraries_for_packages(conn, &ids, LibraryKind::Missing).await?;
+
+        let packages = rows
+            .iter()
+            .map(|row| {
+                let mut info = package_info_from_model(row);
+                info.required_libraries = required_libs.remove(&row.id).unwrap_or_default();
+                info.missing_libraries = missing_libs.remove(&row.id).unwrap_or_default();
+                info
+            })
+            .collect();
+
+        Ok((packages, total))
     }
 
-    /// Check if database has any packages
-    pub async fn has_packages(conn: &DatabaseConnection) -> Result<bool, ApiError> {
-        let count = scenery_packages::Entity::find()
-            .count(conn)
+    async fn load_libraries_for_packages(
+        conn: &DatabaseConnection,
+        package_ids: &[i64],
+        kind: LibraryKind,
+    ) -> Result<HashMap<i64, Vec<String>>, ApiError> {
+        let mut map: HashMap<i64, Vec<String>> = HashMap::new();
+        if package_ids.is_empty() {
+            return Ok(map);
+        }
+
+        let rows: Vec<(i64, String)> = match kind {
+            LibraryKind::Required => required_libraries::Entity::find()
+                .filter(required_libraries::Column::PackageId.is_in(package_ids.to_vec()))
+                .order_by_asc(required_libraries::Column::Id)
+                .all(conn)
+                .await
+                .map_err(ApiError::from)?
+                .into_iter()
+                .map(|row| (row.package_id, row.library_name))
+                .collect(),
+            LibraryKind::Missing => missing_libraries::Entity::find()
+                .filter(missing_libraries::Column::PackageId.is_in(package_ids.to_vec()))
+                .order_by_asc(missing_libraries::Column::Id)
+                .all(conn)
+                .await
+                .map_err(ApiError::from)?
+                .into_iter()
+                .map(|row| (row.package_id, row.library_name))
+                .collect(),
+            LibraryKind::Exported => exported_libraries::Entity::find()
+                .filter(exported_libraries::Column::PackageId.is_in(package_ids.to_vec()))
+                .order_by_asc(exported_libraries::Column::Id)
+                .all(conn)
+                .await
+                .map_err(ApiError::from)?
+                .into_iter()
+                .map(|row| (row.package_id, row.library_name))
+                .collect(),
+        };
+
+        for (package_id, library_name) in rows {
+            map.entry(package_id).or_default().push(library_name);
+        }
+        Ok(map)
+    }
+
+    /// Load the packages in the given categories, without their library lists
+    pub async fn load_packages_in_categories(
+        conn: &DatabaseConnection,
+        install: &str,
+        categories: &[SceneryCategory],
+    ) -> Result<HashMap<String, SceneryPackageInfo>, ApiError> {
+        let rows = scenery_packages::Entity::find()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .filter(
+                scenery_packages::Column::Category.is_in(categories.iter().map(category_to_string)),
+            )
+            .all(conn)
             .await
             .map_err(ApiError::from)?;
-        Ok(count > 0)
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.folder_name.clone(), package_info_from_model(row)))
+            .collect())
     }
 
-    /// Clear all scenery data from the database
-    /// Used before rebuilding index to ensure a completely fresh start
-    pub async fn clear_all(conn: &DatabaseConnection) -> Result<(), ApiError> {
-        required_libraries::Entity::delete_many()
-            .exec(conn)
+    /// Folder names of all packages with one of the given airport ids, keyed by airport id
+    pub async fn load_folders_by_airport_ids(
+        conn: &DatabaseConnection,
+        install: &str,
+        airport_ids: &[String],
+    ) -> Result<HashMap<String, Vec<String>>, ApiError> {
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        if airport_ids.is_empty() {
+            return Ok(map);
+        }
+
+        let rows: Vec<(String, Option<String>)> = scenery_packages::Entity::find()
+            .select_only()
+            .column(scenery_packages::Column::FolderName)
+            .column(scenery_packages::Column::AirportId)
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .filter(scenery_packages::Column::AirportId.is_in(airport_ids.to_vec()))
+            .into_tuple()
+            .all(conn)
             .await
             .map_err(ApiError::from)?;
-        missing_libraries::Entity::delete_many()
-            .exec(conn)
+
+        for (folder_name, airport_id) in rows {
+            if let Some(airport_id) = airport_id {
+                map.entry(airport_id).or_default().push(folder_name);
+            }
+        }
+        Ok(map)
+    }
+
+    /// Enabled packages that require library names exported by `library_folder`,
+    /// those losing the most names first
+    pub async fn load_library_dependents(
+        conn: &DatabaseConnection,
+        install: &str,
+        library_folder: &str,
+    ) -> Result<Vec<LibraryDependent>, ApiError> {
+        let rows = conn
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                LIBRARY_DEPENDENTS_SQL,
+                [install.into(), library_folder.into()],
+            ))
             .await
             .map_err(ApiError::from)?;
-        exported_libraries::Entity::delete_many()
-            .exec(conn)
+
+        rows.into_iter()
+            .map(|row| {
+                let category: String = row.try_get("", "category")?;
+                let affected: i64 = row.try_get("", "affected_library_count")?;
+                let exclusive: i64 = row.try_get("", "exclusive_library_count")?;
+                Ok(LibraryDependent {
+                    folder_name: row.try_get("", "folder_name")?,
+                    category: string_to_category(&category),
+                    affected_library_count: affected as u32,
+                    exclusive_library_count: exclusive as u32,
+                    exclusive: exclusive == affected,
+                })
+            })
+            .collect::<Result<_, sea_orm::DbErr>>()
+            .map_err(ApiError::from)
+    }
+
+    /// Libraries required by a package, in library.txt order, with the packages
+    /// that export each one
+    pub async fn load_package_dependencies(
+        conn: &DatabaseConnection,
+        install: &str,
+        folder_name: &str,
+    ) -> Result<Vec<PackageLibraryDependency>, ApiError> {
+        let rows = conn
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                PACKAGE_DEPENDENCIES_SQL,
+                [install.into(), folder_name.into()],
+            ))
             .await
             .map_err(ApiError::from)?;
-        scenery_packages::Entity::delete_many()
-            .exec(conn)
+
+        let mut dependencies: Vec<PackageLibraryDependency> = Vec::new();
+        for row in rows {
+            let library_name: String = row.try_get("", "library_name").map_err(ApiError::from)?;
+            let provider: Option<String> = row.try_get("", "provider").map_err(ApiError::from)?;
+            let provider_enabled: Option<i64> = row
+                .try_get("", "provider_enabled")
+                .map_err(ApiError::from)?;
+            let missing: i64 = row.try_get("", "missing").map_err(ApiError::from)?;
+
+            // Rows are ordered by library with enabled providers first, so the
+            // first row of each library decides its status
+            if !dependencies
+                .last()
+                .is_some_and(|dep| dep.library_name == library_name)
+            {
+                let status = match provider_enabled {
+                    Some(0) => LibraryDependencyStatus::Disabled,
+                    Some(_) => LibraryDependencyStatus::Enabled,
+                    None if missing != 0 => LibraryDependencyStatus::Missing,
+                    None => LibraryDependencyStatus::Bundled,
+                };
+                dependencies.push(PackageLibraryDependency {
+                    library_name,
+                    status,
+                    providers: Vec::new(),
+                });
+            }
+            if let (Some(provider), Some(dep)) = (provider, dependencies.last_mut()) {
+                dep.providers.push(provider);
+            }
+        }
+
+        Ok(dependencies)
+    }
+
+    /// Every (missing library name, referencing folder, referencing category),
+    /// in scenery order
+    pub async fn load_missing_library_references(
+        conn: &DatabaseConnection,
+        install: &str,
+    ) -> Result<Vec<(String, String, SceneryCategory)>, ApiError> {
+        let rows = conn
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                MISSING_LIBRARY_REFERENCES_SQL,
+                [install.into()],
+            ))
+            .await
+            .map_err(ApiError::from)?;
+
+        rows.into_iter()
+            .map(|row| {
+                let category: String = row.try_get("", "category")?;
+                Ok((
+                    row.try_get("", "library_name")?,
+                    row.try_get("", "folder_name")?,
+                    string_to_category(&category),
+                ))
+            })
+            .collect::<Result<_, sea_orm::DbErr>>()
+            .map_err(ApiError::from)
+    }
+
+    /// Packages requiring a library name exported by each package, keyed by the
+    /// exporting folder
+    pub async fn load_library_package_dependents(
+        conn: &DatabaseConnection,
+        install: &str,
+    ) -> Result<HashMap<String, Vec<String>>, ApiError> {
+        let rows = conn
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                LIBRARY_PACKAGE_DEPENDENTS_SQL,
+                [install.into()],
+            ))
+            .await
+            .map_err(ApiError::from)?;
+
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows {
+            let library_folder: String =
+                row.try_get("", "library_folder").map_err(ApiError::from)?;
+            let dependent: String = row.try_get("", "dependent").map_err(ApiError::from)?;
+            map.entry(library_folder).or_default().push(dependent);
+        }
+        Ok(map)
+    }
+
+    /// For each missing library name, an installed package that probably provides
+    /// it under a different library or folder name (e.g. `3D_people` installed as
+    /// `3D_People_Library`). Exact key matches win over prefix matches, then
+    /// enabled packages, then scenery order.
+    pub async fn find_fuzzy_library_providers(
+        conn: &DatabaseConnection,
+        install: &str,
+        library_names: &[String],
+    ) -> Result<HashMap<String, String>, ApiError> {
+        let mut matches = HashMap::new();
+        if library_names.is_empty() {
+            return Ok(matches);
+        }
+
+        let rows = conn
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                LIBRARY_PROVIDER_CANDIDATES_SQL,
+                [install.into()],
+            ))
+            .await
+            .map_err(ApiError::from)?;
+        let mut candidates: Vec<(String, bool, Vec<String>)> = Vec::new();
+        for row in rows {
+            let folder_name: String = row.try_get("", "folder_name").map_err(ApiError::from)?;
+            let enabled: i64 = row.try_get("", "enabled").map_err(ApiError::from)?;
+            let exported: Option<String> =
+                row.try_get("", "library_name").map_err(ApiError::from)?;
+            if candidates.last().map(|c| &c.0) != Some(&folder_name) {
+                let folder_key = library_match_key(&folder_name);
+                candidates.push((folder_name, enabled != 0, vec![folder_key]));
+            }
+            if let (Some(name), Some(candidate)) = (exported, candidates.last_mut()) {
+                candidate.2.push(library_match_key(&name));
+            }
+        }
+
+        for name in library_names {
+            let wanted = library_match_key(name);
+            let best = candidates
+                .iter()
+                .filter_map(|(folder_name, enabled, keys)| {
+                    let score = keys
+                        .iter()
+                        .map(|key| library_match_score(&wanted, key))
+                        .max()
+                        .unwrap_or(0);
+                    (score > 0).then_some((score, *enabled, folder_name))
+                })
+                // max_by_key keeps the last maximum; reverse to keep scenery order
+                .rev()
+                .max_by_key(|(score, enabled, _)| (*score, *enabled));
+            if let Some((_, _, folder_name)) = best {
+                matches.insert(name.clone(), folder_name.clone());
+            }
+        }
+        Ok(matches)
+    }
+
+    /// Folder names of all packages of an installation
+    pub async fn load_folder_names(
+        conn: &DatabaseConnection,
+        install: &str,
+    ) -> Result<Vec<String>, ApiError> {
+        scenery_packages::Entity::find()
+            .select_only()
+            .column(scenery_packages::Column::FolderName)
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    /// Get the package count of an installation
+    pub async fn get_package_count(
+        conn: &DatabaseConnection,
+        install: &str,
+    ) -> Result<usize, ApiError> {
+        let count = scenery_packages::Entity::find()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .count(conn)
             .await
             .map_err(ApiError::from)?;
+        Ok(count as usize)
+    }
+
+    /// Check if an installation has any packages
+    pub async fn has_packages(conn: &DatabaseConnection, install: &str) -> Result<bool, ApiError> {
+        let count = scenery_packages::Entity::find()
+            .filter(scenery_packages::Column::XplanePath.eq(install))
+            .count(conn)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(count > 0)
+    }
+
+    /// Delete the packages and metadata of one installation
+    async fn clear_install_async<C>(conn: &C, install: &str) -> Result<(), ApiError>
+    where
+        C: ConnectionTrait,
+    {
+        Self::delete_install_packages_async(conn, install).await?;
         index_metadata::Entity::delete_many()
+            .filter(index_metadata::Column::XplanePath.eq(install))
             .exec(conn)
             .await
             .map_err(ApiError::from)?;
+        Ok(())
+    }
+
+    /// Clear the scenery data of one installation, leaving other installations
+    /// intact (test only; rebuilds use [`Self::replace_all`])
+    #[cfg(test)]
+    pub async fn clear_all(conn: &DatabaseConnection, install: &str) -> Result<(), ApiError> {
+        let txn = conn.begin().await.map_err(ApiError::from)?;
+        Self::clear_install_async(&txn, install).await?;
+        txn.commit().await.map_err(ApiError::from)?;
 
-        logger::log_info("Cleared all scenery index data", Some("database"));
+        logger::log_info(
+            &format!("Cleared scenery index data for {}", install),
+            Some("database"),
+        );
         Ok(())
     }
 }
 
+/// Row for a package of `install`
+fn package_active_model(install: &str, info: &SceneryPackageInfo) -> scenery_packages::ActiveModel {
+    scenery_packages::ActiveModel {
+        id: ActiveValue::NotSet,
+        xplane_path: Set(install.to_string()),
+        folder_name: Set(info.folder_name.clone()),
+        category: Set(category_to_string(&info.category).to_string()),
+        sub_priority: Set(info.sub_priority as i32),
+        last_modified: Set(systemtime_to_unix(&info.last_modified)),
+        indexed_at: Set(systemtime_to_unix(&info.indexed_at)),
+        has_apt_dat: Set(info.has_apt_dat),
+        airport_id: Set(info.airport_id.clone()),
+        has_dsf: Set(info.has_dsf),
+        has_library_txt: Set(info.has_library_txt),
+        has_textures: Set(info.has_textures),
+        has_objects: Set(info.has_objects),
+        texture_count: Set(info.texture_count as i32),
+        earth_nav_tile_count: Set(info.earth_nav_tile_count as i32),
+        total_size_bytes: Set(info.total_size_bytes as i64),
+        newest_file_modified: Set(info.newest_file_modified),
+        enabled: Set(info.enabled),
+        sort_order: Set(info.sort_order as i32),
+        actual_path: Set(info.actual_path.clone()),
+        continent: Set(info.continent.clone()),
+        original_category: Set(info
+            .original_category
+            .as_ref()
+            .map(category_to_string)
+            .map(|s| s.to_string())),
+        classifier_version: Set(info.classifier_version as i32),
+        pinned: Set(info.pinned),
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 enum LibraryKind {
     Required,
@@ -763,6 +1577,8 @@ mod tests {
     use super::*;
     use crate::database::{apply_migrations_async, open_memory_connection_async};
 
+    const TEST_INSTALL: &str = "/xplane/main";
+
     async fn setup_test_db() -> DatabaseConnection {
         let conn = open_memory_connection_async().await.unwrap();
         apply_migrations_async(&conn).await.unwrap();
@@ -787,6 +1603,8 @@ mod tests {
             has_objects: true,
             texture_count: 12,
             earth_nav_tile_count: 3,
+            total_size_bytes: 4096,
+            newest_file_modified: None,
             enabled: true,
             sort_order: 10,
             required_libraries: vec!["libA".to_string()],
@@ -795,6 +1613,8 @@ mod tests {
             actual_path: None,
             continent: Some("NA".to_string()),
             original_category: Some(SceneryCategory::Airport),
+            classifier_version: 1,
+            pinned: true,
         };
 
         let index = SceneryIndex {
@@ -805,8 +1625,10 @@ mod tests {
             last_updated: SystemTime::now(),
         };
 
-        SceneryQueries::save_all(&conn, &index).await.unwrap();
-        let loaded = SceneryQueries::load_all(&conn).await.unwrap();
+        SceneryQueries::save_all(&conn, TEST_INSTALL, &index)
+            .await
+            .unwrap();
+        let loaded = SceneryQueries::load_all(&conn, TEST_INSTALL).await.unwrap();
         let loaded_info = loaded.packages.get("TestAirport").unwrap();
 
         assert_eq!(loaded_info.folder_name, info.folder_name);
@@ -817,5 +1639,570 @@ mod tests {
             loaded_info.exported_library_names,
             info.exported_library_names
         );
+        assert_eq!(loaded_info.classifier_version, 1);
+        assert!(loaded_info.pinned);
+    }
+
+    #[tokio::test]
+    async fn test_installs_are_scoped_and_cleared_separately() {
+        let conn = setup_test_db().await;
+        let second_install = "/xplane/beta";
+
+        let mut main_pkg = synthetic_package(1);
+        main_pkg.required_libraries = vec!["lib_main".to_string()];
+        let mut beta_pkg = synthetic_package(1);
+        beta_pkg.required_libraries = vec!["lib_beta".to_string()];
+        beta_pkg.enabled = !main_pkg.enabled;
+        for (install, info) in [(TEST_INSTALL, &main_pkg), (second_install, &beta_pkg)] {
+            let index = SceneryIndex {
+                version: 1,
+                packages: [(info.folder_name.clone(), info.clone())]
+                    .into_iter()
+                    .collect(),
+                last_updated: SystemTime::now(),
+            };
+            SceneryQueries::save_all(&conn, install, &index)
+                .await
+                .unwrap();
+        }
+
+        let main = SceneryQueries::load_all(&conn, TEST_INSTALL).await.unwrap();
+        let beta = SceneryQueries::load_all(&conn, second_install)
+            .await
+            .unwrap();
+        assert_eq!(
+            main.packages[&main_pkg.folder_name].required_libraries,
+            vec!["lib_main"]
+        );
+        assert_eq!(
+            beta.packages[&beta_pkg.folder_name].required_libraries,
+            vec!["lib_beta"]
+        );
+        assert_eq!(
+            beta.packages[&beta_pkg.folder_name].enabled,
+            beta_pkg.enabled
+        );
+
+        SceneryQueries::clear_all(&conn, TEST_INSTALL)
+            .await
+            .unwrap();
+        assert!(!SceneryQueries::has_packages(&conn, TEST_INSTALL)
+            .await
+            .unwrap());
+        let beta = SceneryQueries::load_all(&conn, second_install)
+            .await
+            .unwrap();
+        assert_eq!(
+            beta.packages[&beta_pkg.folder_name].required_libraries,
+            vec!["lib_beta"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unscoped_rows_are_adopted_once() {
+        let conn = setup_test_db().await;
+        let legacy = SceneryIndex {
+            version: 1,
+            packages: [synthetic_package(3)]
+                .into_iter()
+                .map(|info| (info.folder_name.clone(), info))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+        SceneryQueries::save_all(&conn, "", &legacy).await.unwrap();
+
+        assert!(SceneryQueries::adopt_unscoped_rows(&conn, TEST_INSTALL)
+            .await
+            .unwrap());
+        assert_eq!(
+            SceneryQueries::get_package_count(&conn, TEST_INSTALL)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            SceneryQueries::get_metadata(&conn, TEST_INSTALL, "version")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("1")
+        );
+        assert!(!SceneryQueries::adopt_unscoped_rows(&conn, "/xplane/beta")
+            .await
+            .unwrap());
+        assert!(!SceneryQueries::has_packages(&conn, "/xplane/beta")
+            .await
+            .unwrap());
+    }
+
+    fn synthetic_package(i: usize) -> SceneryPackageInfo {
+        let category = match i % 4 {
+            0 => SceneryCategory::Airport,
+            1 => SceneryCategory::Overlay,
+            2 => SceneryCategory::Mesh,
+            _ => SceneryCategory::Library,
+        };
+        SceneryPackageInfo {
+            folder_name: format!("Package_{:05}", i),
+            category,
+            sub_priority: 0,
+            last_modified: SystemTime::UNIX_EPOCH,
+            indexed_at: SystemTime::UNIX_EPOCH,
+            has_apt_dat: false,
+            airport_id: None,
+            has_dsf: false,
+            has_library_txt: false,
+            has_textures: false,
+            has_objects: false,
+            texture_count: 0,
+            earth_nav_tile_count: 0,
+            total_size_bytes: 0,
+            newest_file_modified: None,
+            enabled: i % 3 != 0,
+            sort_order: i as u32,
+            required_libraries: vec![format!("lib_{}", i % 10)],
+            missing_libraries: Vec::new(),
+            exported_library_names: Vec::new(),
+            actual_path: None,
+            continent: (i % 2 == 0).then(|| "Europe".to_string()),
+            original_category: None,
+            classifier_version: 0,
+            pinned: false,
+        }
+    }
+
+    async fn setup_synthetic_db(count: usize) -> DatabaseConnection {
+        let conn = setup_test_db().await;
+        let mut packages: HashMap<String, SceneryPackageInfo> = (0..count)
+            .map(synthetic_package)
+            .map(|info| (info.folder_name.clone(), info))
+            .collect();
+        let mut default_airport = synthetic_package(count);
+        default_airport.folder_name = "Default_Airport_Pack".to_string();
+        default_airport.category = SceneryCategory::DefaultAirport;
+        packages.insert(default_airport.folder_name.clone(), default_airport);
+        let mut global = synthetic_package(count + 1);
+        global.folder_name = "Global Airports".to_string();
+        packages.insert(global.folder_name.clone(), global);
+
+        let index = SceneryIndex {
+            version: 1,
+            packages,
+            last_updated: SystemTime::now(),
+        };
+        SceneryQueries::save_all(&conn, TEST_INSTALL, &index)
+            .await
+            .unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    async fn test_manager_page_filters_sorts_and_counts() {
+        let conn = setup_synthetic_db(40).await;
+
+        let (page, total) = SceneryQueries::load_manager_page(
+            &conn,
+            TEST_INSTALL,
+            &SceneryManagerFilter::default(),
+            &SceneryManagerSort::default(),
+            10,
+            5,
+        )
+        .await
+        .unwrap();
+        // DefaultAirport and Global Airports rows are not part of the manager list
+        assert_eq!(total, 40);
+        let orders: Vec<u32> = page.iter().map(|p| p.sort_order).collect();
+        assert_eq!(orders, vec![10, 11, 12, 13, 14]);
+        assert_eq!(page[0].required_libraries, vec!["lib_0".to_string()]);
+
+        let filter = SceneryManagerFilter {
+            category: Some(SceneryCategory::Mesh),
+            enabled: Some(true),
+            search: Some("package_000".to_string()),
+            continent: None,
+        };
+        let sort = SceneryManagerSort {
+            field: SceneryManagerSortField::FolderName,
+            descending: true,
+        };
+        let (page, total) =
+            SceneryQueries::load_manager_page(&conn, TEST_INSTALL, &filter, &sort, 0, 100)
+                .await
+                .unwrap();
+        let names: Vec<&str> = page.iter().map(|p| p.folder_name.as_str()).collect();
+        assert_eq!(names, vec!["Package_00002"]);
+        assert_eq!(total, 1);
+
+        // LIKE wildcards in the search text match literally
+        let filter = SceneryManagerFilter {
+            search: Some("%".to_string()),
+            ..Default::default()
+        };
+        let (page, total) = SceneryQueries::load_manager_page(
+            &conn,
+            TEST_INSTALL,
+            &filter,
+            &SceneryManagerSort::default(),
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+        assert!(page.is_empty());
+        assert_eq!(total, 0);
+
+        let filter = SceneryManagerFilter {
+            continent: Some("Europe".to_string()),
+            ..Default::default()
+        };
+        let (page, total) = SceneryQueries::load_manager_page(
+            &conn,
+            TEST_INSTALL,
+            &filter,
+            &SceneryManagerSort::default(),
+            0,
+            3,
+        )
+        .await
+        .unwrap();
+        assert_eq!(total, 20);
+        let orders: Vec<u32> = page.iter().map(|p| p.sort_order).collect();
+        assert_eq!(orders, vec![0, 2, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_manager_page_query_scales_to_large_libraries() {
+        let conn = setup_synthetic_db(5_000).await;
+        let queries = [
+            (
+                SceneryManagerFilter::default(),
+                SceneryManagerSort::default(),
+                4_900,
+            ),
+            (
+                SceneryManagerFilter {
+                    category: Some(SceneryCategory::Overlay),
+                    enabled: Some(true),
+                    search: Some("package_01".to_string()),
+                    continent: None,
+                },
+                SceneryManagerSort {
+                    field: SceneryManagerSortField::FolderName,
+                    descending: true,
+                },
+                0,
+            ),
+            (
+                SceneryManagerFilter::default(),
+                SceneryManagerSort {
+                    field: SceneryManagerSortField::Category,
+                    descending: false,
+                },
+                2_500,
+            ),
+        ];
+
+        for (filter, sort, offset) in &queries {
+            // Warm up the statement cache, then keep the best of a few runs to
+            // avoid flagging scheduler noise
+            SceneryQueries::load_manager_page(&conn, TEST_INSTALL, filter, sort, *offset, 100)
+                .await
+                .unwrap();
+            let mut best = std::time::Duration::MAX;
+            for _ in 0..3 {
+                let start = std::time::Instant::now();
+                let (page, total) = SceneryQueries::load_manager_page(
+                    &conn,
+                    TEST_INSTALL,
+                    filter,
+                    sort,
+                    *offset,
+                    100,
+                )
+                .await
+                .unwrap();
+                best = best.min(start.elapsed());
+                assert!(!page.is_empty());
+                assert!(total >= page.len() as u64);
+            }
+            assert!(
+                best < std::time::Duration::from_millis(50),
+                "paged query took {:?} for {:?}",
+                best,
+                filter
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_library_dependents_and_package_dependencies() {
+        let conn = setup_test_db().await;
+        let package =
+            |i: usize, name: &str, category, enabled, required: &[&str], exported: &[&str]| {
+                let mut info = synthetic_package(i);
+                info.folder_name = name.to_string();
+                info.category = category;
+                info.enabled = enabled;
+                info.required_libraries = required.iter().map(|s| s.to_string()).collect();
+                info.exported_library_names = exported.iter().map(|s| s.to_string()).collect();
+                info
+            };
+        let mut airport = package(
+            2,
+            "Airport_A",
+            SceneryCategory::Airport,
+            true,
+            &["LIB_A", "lib_b", "lib_x", "Airport_A"],
+            &[],
+        );
+        airport.missing_libraries = vec!["lib_x".to_string()];
+        let packages = vec![
+            package(
+                0,
+                "Lib_Main",
+                SceneryCategory::Library,
+                true,
+                &[],
+                &["lib_a", "lib_b"],
+            ),
+            package(
+                1,
+                "Lib_Other",
+                SceneryCategory::Library,
+                true,
+                &[],
+                &["lib_b"],
+            ),
+            airport,
+            package(
+                3,
+                "Airport_B",
+                SceneryCategory::Airport,
+                true,
+                &["lib_b"],
+                &[],
+            ),
+            package(
+                4,
+                "Airport_Off",
+                SceneryCategory::Airport,
+                false,
+                &["lib_a"],
+                &[],
+            ),
+        ];
+        let index = SceneryIndex {
+            version: 1,
+            packages: packages
+                .into_iter()
+                .map(|info| (info.folder_name.clone(), info))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+        SceneryQueries::save_all(&conn, TEST_INSTALL, &index)
+            .await
+            .unwrap();
+
+        let dependents = SceneryQueries::load_library_dependents(&conn, TEST_INSTALL, "Lib_Main")
+            .await
+            .unwrap();
+        let summary: Vec<(&str, u32, u32, bool)> = dependents
+            .iter()
+            .map(|d| {
+                (
+                    d.folder_name.as_str(),
+                    d.affected_library_count,
+                    d.exclusive_library_count,
+                    d.exclusive,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("Airport_A", 2, 1, false), ("Airport_B", 1, 0, false)]
+        );
+
+        let dependencies =
+            SceneryQueries::load_package_dependencies(&conn, TEST_INSTALL, "Airport_A")
+                .await
+                .unwrap();
+        let summary: Vec<(&str, LibraryDependencyStatus, Vec<&str>)> = dependencies
+            .iter()
+            .map(|d| {
+                (
+                    d.library_name.as_str(),
+                    d.status,
+                    d.providers.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("LIB_A", LibraryDependencyStatus::Enabled, vec!["Lib_Main"]),
+                (
+                    "lib_b",
+                    LibraryDependencyStatus::Enabled,
+                    vec!["Lib_Main", "Lib_Other"]
+                ),
+                ("lib_x", LibraryDependencyStatus::Missing, vec![]),
+                ("Airport_A", LibraryDependencyStatus::Bundled, vec![]),
+            ]
+        );
+
+        // lib_b is also exported by Lib_Other and lib_x is already missing;
+        // the disabled Airport_Off still gets its gap recorded
+        let orphaned =
+            SceneryQueries::load_orphaned_library_references(&conn, TEST_INSTALL, "Lib_Main")
+                .await
+                .unwrap();
+        let summary: Vec<(&str, &str)> = orphaned
+            .iter()
+            .map(|m| (m.folder_name.as_str(), m.library_name.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("Airport_A", "LIB_A"), ("Airport_Off", "lib_a")]
+        );
+
+        assert!(SceneryQueries::delete_package_marking_missing(
+            &conn,
+            TEST_INSTALL,
+            "Lib_Main",
+            &orphaned
+        )
+        .await
+        .unwrap());
+        let references = SceneryQueries::load_missing_library_references(&conn, TEST_INSTALL)
+            .await
+            .unwrap();
+        let summary: Vec<(&str, &str)> = references
+            .iter()
+            .map(|(library, folder, _)| (library.as_str(), folder.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("lib_x", "Airport_A"),
+                ("LIB_A", "Airport_A"),
+                ("lib_a", "Airport_Off"),
+            ]
+        );
+        assert!(
+            SceneryQueries::load_library_dependents(&conn, TEST_INSTALL, "Lib_Main")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_library_references_and_fuzzy_providers() {
+        let conn = setup_test_db().await;
+        let package = |i: usize, name: &str, category, required: &[&str], exported: &[&str]| {
+            let mut info = synthetic_package(i);
+            info.folder_name = name.to_string();
+            info.has_library_txt = category == SceneryCategory::Library;
+            info.category = category;
+            info.enabled = true;
+            info.required_libraries = required.iter().map(|s| s.to_string()).collect();
+            info.exported_library_names = exported.iter().map(|s| s.to_string()).collect();
+            info
+        };
+        let mut airport = package(
+            1,
+            "Airport_A",
+            SceneryCategory::Airport,
+            &["3D-People", "lib_v"],
+            &[],
+        );
+        airport.missing_libraries = vec!["3D-People".to_string()];
+        let mut vehicles = package(
+            2,
+            "Lib_Vehicles",
+            SceneryCategory::Library,
+            &["opensceneryx"],
+            &["lib_v"],
+        );
+        vehicles.missing_libraries = vec!["opensceneryx".to_string()];
+        let packages = vec![
+            airport,
+            vehicles,
+            package(
+                3,
+                "3D_People_Library",
+                SceneryCategory::Library,
+                &[],
+                &["3D_people"],
+            ),
+            package(4, "OpenSceneryX-5.0.1", SceneryCategory::Library, &[], &[]),
+            package(5, "Lib", SceneryCategory::Library, &[], &["lib"]),
+        ];
+        let index = SceneryIndex {
+            version: 1,
+            packages: packages
+                .into_iter()
+                .map(|info| (info.folder_name.clone(), info))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+        SceneryQueries::save_all(&conn, TEST_INSTALL, &index)
+            .await
+            .unwrap();
+
+        let references = SceneryQueries::load_missing_library_references(&conn, TEST_INSTALL)
+            .await
+            .unwrap();
+        assert_eq!(
+            references,
+            vec![
+                (
+                    "3D-People".to_string(),
+                    "Airport_A".to_string(),
+                    SceneryCategory::Airport
+                ),
+                (
+                    "opensceneryx".to_string(),
+                    "Lib_Vehicles".to_string(),
+                    SceneryCategory::Library
+                ),
+            ]
+        );
+
+        let dependents = SceneryQueries::load_library_package_dependents(&conn, TEST_INSTALL)
+            .await
+            .unwrap();
+        assert_eq!(
+            dependents.get("Lib_Vehicles"),
+            Some(&vec!["Airport_A".to_string()])
+        );
+
+        let names = vec![
+            "3D-People".to_string(),
+            "opensceneryx".to_string(),
+            "unknown_pack".to_string(),
+        ];
+        let providers = SceneryQueries::find_fuzzy_library_providers(&conn, TEST_INSTALL, &names)
+            .await
+            .unwrap();
+        assert_eq!(
+            providers.get("3D-People").map(String::as_str),
+            Some("3D_People_Library")
+        );
+        assert_eq!(
+            providers.get("opensceneryx").map(String::as_str),
+            Some("OpenSceneryX-5.0.1")
+        );
+        assert!(!providers.contains_key("unknown_pack"));
+    }
+
+    #[test]
+    fn test_library_match_key_strips_noise() {
+        assert_eq!(library_match_key("3D_People_Library"), "3dpeople");
+        assert_eq!(library_match_key("OpenSceneryX-5.0.1"), "opensceneryx");
+        assert_eq!(library_match_key("cdb-library"), "cdb");
+        assert_eq!(library_match_score("opensceneryx", "opensceneryxextras"), 1);
+        assert_eq!(library_match_score("cdb", "cdbextras"), 0);
     }
 }
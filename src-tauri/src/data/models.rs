@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::time::SystemTime;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -29,6 +30,9 @@ pub struct NestedArchiveInfo {
     pub password: Option<String>,
     /// Archive format: "zip", "7z", or "rar"
     pub format: String,
+    /// Whether this nested archive is itself encrypted (needs `password` to extract)
+    #[serde(default)]
+    pub password_required: bool,
 }
 
 /// Extraction chain for nested archives (outer to inner order)
@@ -43,6 +47,79 @@ pub struct ExtractionChain {
     pub final_internal_root: Option<String>,
 }
 
+impl ExtractionChain {
+    /// "inner.zip!Root" style key for where the content lives inside the outer archive.
+    /// Passwords are deliberately left out so the key doesn't change once one is provided.
+    pub fn location_key(&self) -> String {
+        let mut parts: Vec<&str> = self
+            .archives
+            .iter()
+            .map(|a| a.internal_path.as_str())
+            .collect();
+        if let Some(root) = &self.final_internal_root {
+            parts.push(root);
+        }
+        parts.join("!")
+    }
+}
+
+/// Location of an item inside its source path: the extraction chain when present,
+/// otherwise the archive internal root, or an empty string for plain folders
+pub fn internal_location_key(
+    archive_internal_root: Option<&str>,
+    extraction_chain: Option<&ExtractionChain>,
+) -> String {
+    match (extraction_chain, archive_internal_root) {
+        (Some(chain), _) => chain.location_key(),
+        (None, Some(root)) => root.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Deterministic id for detected content: the first 128 bits of
+/// SHA-256("<addon type>|<source path>|<internal location key>") as hex.
+/// Separators in the source path are normalized to `/` so the id doesn't depend
+/// on how the path was spelled when it was dropped.
+pub fn stable_item_id(addon_type: &AddonType, source_path: &str, location_key: &str) -> String {
+    let normalized_path = source_path.replace('\\', "/");
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{:?}", addon_type).as_bytes());
+    hasher.update(b"|");
+    hasher.update(normalized_path.trim_end_matches('/').as_bytes());
+    hasher.update(b"|");
+    hasher.update(location_key.as_bytes());
+    let digest = format!("{:x}", hasher.finalize());
+    digest[..32].to_string()
+}
+
+/// Identity of an addon across repeat installs, which install profiles are
+/// keyed by: the first 128 bits of SHA-256("<addon type>|<display name>|<root
+/// hash>") as hex, where the root hash is SHA-256 of the archive internal root.
+/// Unlike [`stable_item_id`] it leaves out the source path, so a re-downloaded
+/// beta under a new archive name still maps to the same profile. Name and root
+/// are compared case-insensitively with `/` separators.
+pub fn install_profile_identity(
+    addon_type: &AddonType,
+    display_name: &str,
+    internal_root: Option<&str>,
+) -> String {
+    let normalized_root = internal_root
+        .unwrap_or_default()
+        .replace('\\', "/")
+        .trim_matches('/')
+        .to_lowercase();
+    let root_hash = format!("{:x}", Sha256::digest(normalized_root.as_bytes()));
+
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{:?}", addon_type).as_bytes());
+    hasher.update(b"|");
+    hasher.update(display_name.trim().to_lowercase().as_bytes());
+    hasher.update(b"|");
+    hasher.update(root_hash.as_bytes());
+    let digest = format!("{:x}", hasher.finalize());
+    digest[..32].to_string()
+}
+
 /// Navdata cycle information for display
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -62,6 +139,38 @@ pub struct VersionInfo {
     pub version: Option<String>,
 }
 
+/// Updater an addon ships its own metadata for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdaterProvider {
+    /// `skunkcrafts_updater.cfg` in the addon root
+    Skunkcrafts,
+    /// An x-updater profile in the addon root or its x-updater folder
+    XUpdater,
+    #[default]
+    None,
+}
+
+/// X-Plane folder a scenery package installs into
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TargetRootKind {
+    #[default]
+    CustomScenery,
+    /// Base meshes meant to replace Laminar's global terrain (HD Mesh, demo areas)
+    GlobalScenery,
+}
+
+impl TargetRootKind {
+    /// Folder name below the X-Plane root
+    pub fn folder_name(self) -> &'static str {
+        match self {
+            TargetRootKind::CustomScenery => "Custom Scenery",
+            TargetRootKind::GlobalScenery => "Global Scenery",
+        }
+    }
+}
+
 /// Navdata backup file entry with checksum
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -86,6 +195,10 @@ pub struct NavdataBackupVerification {
     /// AIRAC info
     #[serde(skip_serializing_if = "Option::is_none")]
     pub airac: Option<String>,
+    /// Cycle installed over the backed up data, in YYNN form. Missing in
+    /// backups written by older versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by_cycle: Option<String>,
     /// Backup timestamp (RFC3339 format)
     pub backup_time: String,
     /// List of backed up files with checksums
@@ -102,11 +215,29 @@ pub struct NavdataBackupInfo {
     pub folder_name: String,
     /// Verification data
     pub verification: NavdataBackupVerification,
+    /// Cycle change the backup was taken for, e.g. "2405 → 2313"; just the
+    /// backed up cycle when the newer one wasn't recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle_label: Option<String>,
+}
+
+/// User changes to a detected item before it becomes an install task
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallTaskOverride {
+    /// Replaces the detected name, which is also the folder name installed to
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// For Livery: the aircraft folder to install into, relative to `Aircraft/`
+    #[serde(default)]
+    pub livery_target_folder: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InstallTask {
+    /// Stable id of the detected item this task was created from
+    /// (see [`DetectedItem::stable_id`])
     pub id: String,
     #[serde(rename = "type")]
     pub addon_type: AddonType,
@@ -129,6 +260,14 @@ pub struct InstallTask {
     /// Whether to overwrite existing folder (delete before install)
     #[serde(default)]
     pub should_overwrite: bool,
+    /// For archives: rename files whose paths differ only by case from an earlier
+    /// entry instead of failing the install on case-insensitive volumes
+    #[serde(default)]
+    pub allow_case_collision_rename: bool,
+    /// For archives: create symbolic links whose target stays inside the install
+    /// target instead of skipping every link entry
+    #[serde(default)]
+    pub allow_symlinks: bool,
     /// Password for encrypted archives
     #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
@@ -147,6 +286,14 @@ pub struct InstallTask {
     /// For Navdata: new cycle info to be installed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub new_navdata_info: Option<NavdataInfo>,
+    /// For Navdata: set when the new cycle is older than the one X-Plane uses
+    /// now, to that cycle (from the target's cycle.json, or the sim's default data)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub navdata_downgrade_from: Option<String>,
+    /// For Navdata: install an older cycle over a newer one. Without it a
+    /// downgrade fails.
+    #[serde(default)]
+    pub allow_downgrade: bool,
     /// For Aircraft/Plugin: existing version info (if conflict exists)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub existing_version_info: Option<VersionInfo>,
@@ -175,15 +322,164 @@ pub struct InstallTask {
     /// For Livery: whether the target aircraft is installed
     #[serde(default = "default_true")]
     pub livery_aircraft_found: bool,
+    /// For Livery: installed aircraft folders the livery fits, relative to
+    /// `Aircraft/`. With more than one, `target_aircraft_folder` must name one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub livery_target_candidates: Vec<String>,
+    /// For Livery: the aircraft folder chosen among `livery_target_candidates`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_aircraft_folder: Option<String>,
+    /// Why the task can't be installed (e.g. no matching aircraft is installed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_installable_reason: Option<String>,
     /// For LuaScript: whether FlyWithLua plugin is installed
     #[serde(default = "default_true")]
     pub flywithlua_installed: bool,
-    /// For LuaScript: companion files/folders referenced by SCRIPT_DIRECTORY
+    /// For LuaScript: companion files/folders the script depends on (SCRIPT_DIRECTORY
+    /// references, a same-named data folder, Modules and `require`d files)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub companion_paths: Vec<String>,
+    /// Whether the addon is expected to work with the target X-Plane version
+    #[serde(default)]
+    pub compatibility: AddonCompatibility,
+    /// For Aircraft/Plugin/Scenery/Livery: updater metadata shipped with the addon
+    #[serde(default)]
+    pub updater_provider: UpdaterProvider,
+    /// For Scenery: the folder the analyzer suggests, see `DetectedItem::target_root_kind`
+    #[serde(default)]
+    pub suggested_root_kind: TargetRootKind,
+    /// For Scenery: the folder to install into; starts as the suggestion and may
+    /// be changed by the user. Global Scenery installs are not added to
+    /// scenery_packs.ini.
+    #[serde(default)]
+    pub target_root_kind: TargetRootKind,
+    /// Identity the install profile of this addon is kept under, see
+    /// [`DetectedItem::profile_identity`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile_identity: Option<String>,
+    /// Choices saved by the last install of this addon, for pre-filling the options
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_profile: Option<InstallProfile>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Options chosen for an addon on its last successful install, offered again
+/// when the same addon is analyzed later
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallProfile {
+    /// See [`install_profile_identity`]
+    pub identity: String,
+    /// Name installed under; differs from the detected name when the user renamed it
+    pub display_name: String,
+    pub should_overwrite: bool,
+    pub backup_liveries: bool,
+    pub backup_config_files: bool,
+    pub backup_navdata: bool,
+    /// For Livery: the aircraft folder chosen among several candidates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_aircraft_folder: Option<String>,
+    /// For Scenery: the root folder installed into
+    #[serde(default)]
+    pub target_root_kind: TargetRootKind,
+    /// Unix seconds of the install that saved it
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+/// X-Plane version compatibility of an addon, judged from what the scanner could read
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum AddonCompatibility {
+    Compatible,
+    LikelyIncompatible {
+        reason: String,
+    },
+    /// No version hints were found, or the X-Plane version couldn't be detected
+    #[default]
+    Unknown,
+}
+
+/// Version hints read from an addon's files during scanning
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimVersionHints {
+    /// Format version from the .acf header (e.g. 1100 for X-Plane 11, 1200 for X-Plane 12)
+    pub acf_format_version: Option<u32>,
+    /// Virtual paths exported by library.txt that only exist in older X-Plane versions
+    pub legacy_library_exports: Vec<String>,
+}
+
+impl SimVersionHints {
+    pub fn is_empty(&self) -> bool {
+        self.acf_format_version.is_none() && self.legacy_library_exports.is_empty()
+    }
+}
+
+/// Task whose addon looks incompatible with the target X-Plane version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityWarning {
+    pub task_id: String,
+    pub display_name: String,
+    pub reason: String,
+    /// Key to pass to `add_analysis_suppression` to hide this warning
+    #[serde(default)]
+    pub suppression_key: String,
+}
+
+/// Why a marker file found during a scan didn't become an addon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SkippedMarkerReason {
+    /// Under a path the scanner ignores (`__MACOSX`, `.DS_Store`, ...)
+    Ignored,
+    /// Inside a plugin folder, like an `.acf` shipped with a plugin
+    InsidePlugin,
+    /// Inside an aircraft folder, like a plugin embedded in an aircraft
+    InsideAircraft,
+    /// The detector didn't accept the file, e.g. a `.dsf` outside `Earth nav data`
+    Unrecognized,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedMarker {
+    /// Path inside the input; entries of nested archives are prefixed with the
+    /// nested archive's path
+    pub path: String,
+    pub reason: SkippedMarkerReason,
+}
+
+/// What the scan of one input saw, reported when it produced no addons so the
+/// user can tell why
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanDiagnostics {
+    pub input_path: String,
+    pub is_archive: bool,
+    /// Top-level entries of an archive, at most 50
+    pub top_level_entries: Vec<String>,
+    /// The archive has more top-level entries than listed
+    pub top_level_truncated: bool,
+    /// Entries in an archive (files and folders), or files seen in a folder
+    pub entry_count: usize,
+    /// The archive or an archive inside it is encrypted
+    pub encrypted: bool,
+    /// Marker files (`.acf`, `.xpl`, `.dsf`, `library.txt`, `cycle.json`,
+    /// `.lua`) that were seen but skipped, at most 50
+    pub skipped_markers: Vec<SkippedMarker>,
+    /// All skipped marker files, including those not listed
+    pub skipped_marker_count: usize,
+    /// Archives inside archives that weren't opened because they are nested too deep
+    pub nested_archives_too_deep: usize,
+    /// Deepest folder level visited below a dropped folder
+    pub max_depth_reached: usize,
+    /// Folders below the depth limit that weren't scanned
+    pub folders_too_deep: usize,
+    /// Paths skipped by the ignore rules
+    pub ignored_path_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AnalysisResult {
     pub tasks: Vec<InstallTask>,
@@ -195,6 +491,109 @@ pub struct AnalysisResult {
     /// Key format: "parent.zip/nested.zip", Value: "parent.zip"
     #[serde(default)]
     pub nested_password_required: HashMap<String, String>,
+    /// Major version of the target X-Plane installation, if it could be detected
+    #[serde(default)]
+    pub xplane_major_version: Option<u32>,
+    /// Tasks that look incompatible with the target X-Plane version; the frontend
+    /// asks for confirmation before installing them
+    #[serde(default)]
+    pub compatibility_warnings: Vec<CompatibilityWarning>,
+    /// Compatibility warnings hidden by the user's suppressions
+    #[serde(default)]
+    pub suppressed_warning_count: usize,
+    /// What the scan saw for each input that produced no addons and no error
+    #[serde(default)]
+    pub diagnostics: Vec<ScanDiagnostics>,
+}
+
+/// Result of `download_and_analyze`: the analysis of an addon fetched from a URL
+/// plus where it was saved, so the tasks can be passed to `install_addons`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlDownloadAnalysis {
+    pub local_path: String,
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub analysis: AnalysisResult,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DownloadQueueStatus {
+    Queued,
+    Downloading,
+    /// Downloaded; `analyze_addons` is running on the file
+    Analyzing,
+    Completed,
+    /// Download or analysis failed; `retry_download` queues it again
+    Failed,
+}
+
+/// A URL in the download queue, as listed by `get_download_queue` and sent with
+/// `download-queue-progress` events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadQueueItem {
+    pub id: i64,
+    pub url: String,
+    pub xplane_path: String,
+    pub status: DownloadQueueStatus,
+    /// Name the server gave the file, once the download started
+    pub file_name: Option<String>,
+    /// The downloaded file, once completed; pass the analysis tasks to `install_addons`
+    pub local_path: Option<String>,
+    pub downloaded_bytes: u64,
+    /// Size announced by the server, when known
+    pub total_bytes: Option<u64>,
+    pub error_message: Option<String>,
+    /// Analysis of the downloaded file, once completed
+    pub analysis: Option<AnalysisResult>,
+    /// Unix seconds
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Why an install interrupted by a crash was left for the user to review
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrphanedInstallReason {
+    /// The target was there before the install started, so it may hold the
+    /// previous version of the addon
+    TargetExisted,
+    /// The target has the files an addon of its type needs, but was never verified
+    LooksComplete,
+    /// Navdata and Lua script targets are never removed automatically
+    KeptInPlace,
+    /// Removing the partial target failed
+    CleanupFailed,
+    /// The previous version had been moved aside and couldn't be put back; the
+    /// listed path is the set-aside copy
+    SetAside,
+}
+
+/// An install a crash or a killed process interrupted, listed by
+/// `get_orphaned_installs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedInstall {
+    pub id: i64,
+    pub task_id: String,
+    pub display_name: String,
+    pub addon_type: AddonType,
+    pub target_path: String,
+    pub reason: OrphanedInstallReason,
+    /// Unix seconds
+    pub started_at: i64,
+}
+
+/// What `resolve_orphaned_install` does with the target of an interrupted install
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrphanedInstallAction {
+    /// Leave the target as it is
+    Keep,
+    /// Remove the target
+    Delete,
 }
 
 #[derive(Debug, Deserialize)]
@@ -207,7 +606,14 @@ pub struct NavdataCycle {
     pub airac: Option<String>,
 }
 
-#[derive(Debug)]
+/// Addon found by the scanner, before it is turned into an [`InstallTask`].
+///
+/// Items are identified by [`DetectedItem::stable_id`], which only depends on the
+/// addon type, the source path and the location inside that source. Re-analyzing
+/// the same inputs (including the re-scan after a password is entered) therefore
+/// yields the same ids, and the resulting `InstallTask::id` lets the frontend keep
+/// per-task selections across analyses.
+#[derive(Debug, Clone)]
 pub struct DetectedItem {
     pub addon_type: AddonType,
     pub path: String,
@@ -222,10 +628,52 @@ pub struct DetectedItem {
     pub navdata_info: Option<NavdataInfo>,
     /// For Livery: the aircraft type this livery belongs to (e.g., "FF777")
     pub livery_aircraft_type: Option<String>,
+    /// For Livery detected by structure rather than by pattern: the user-chosen
+    /// aircraft folder, relative to `Aircraft/`
+    pub livery_target_folder: Option<String>,
+    /// For Livery: installed aircraft folders matching `livery_aircraft_type`,
+    /// relative to `Aircraft/`. Filled in by the analyzer.
+    pub livery_target_candidates: Vec<String>,
     /// For Aircraft/Plugin: version info from the addon to be installed
     pub version_info: Option<VersionInfo>,
-    /// For LuaScript: companion files/folders referenced by SCRIPT_DIRECTORY
+    /// For LuaScript: companion files/folders the script depends on (SCRIPT_DIRECTORY
+    /// references, a same-named data folder, Modules and `require`d files)
     pub companion_paths: Vec<String>,
+    /// For Aircraft/SceneryLibrary: version hints read from the addon's files
+    pub sim_version_hints: Option<SimVersionHints>,
+    /// Filled in by the analyzer once the target X-Plane version is known
+    pub compatibility: AddonCompatibility,
+    /// For Aircraft/Plugin/Scenery/Livery: updater metadata found in the addon
+    /// root (for archives, from the listing under `archive_internal_root`)
+    pub updater_provider: UpdaterProvider,
+    /// For Scenery: Global Scenery when the package looks like a base mesh meant
+    /// for it. Filled in by the analyzer.
+    pub target_root_kind: TargetRootKind,
+}
+
+impl DetectedItem {
+    /// Stable id, see [`stable_item_id`]
+    pub fn stable_id(&self) -> String {
+        stable_item_id(&self.addon_type, &self.path, &self.location_key())
+    }
+
+    /// Location inside the source path, see [`internal_location_key`]
+    pub fn location_key(&self) -> String {
+        internal_location_key(
+            self.archive_internal_root.as_deref(),
+            self.extraction_chain.as_ref(),
+        )
+    }
+
+    /// Identity of the install profile, see [`install_profile_identity`]. The
+    /// names of nested archives are left out like the outer archive's.
+    pub fn profile_identity(&self) -> String {
+        let internal_root = match &self.extraction_chain {
+            Some(chain) => chain.final_internal_root.as_deref(),
+            None => self.archive_internal_root.as_deref(),
+        };
+        install_profile_identity(&self.addon_type, &self.display_name, internal_root)
+    }
 }
 
 /// Installation progress event sent to frontend
@@ -248,7 +696,7 @@ pub struct InstallProgress {
     pub current_file: Option<String>,
     /// Current phase
     pub phase: InstallPhase,
-    /// Verification progress (0.0 - 100.0), only used during Verifying phase
+    /// Verification progress (0.0 - 100.0), only used during Verifying and PreflightCheck phases
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verification_progress: Option<f64>,
     /// Current task progress percentage (0.0 - 100.0)
@@ -267,6 +715,16 @@ pub struct InstallProgress {
     /// IDs of completed tasks in parallel mode (for UI to show checkmarks)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_task_ids: Option<Vec<String>>,
+    /// Set while the current task is decompressing a solid 7z block, where
+    /// `current_file` alone doesn't describe what is happening
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_solid_block: Option<bool>,
+    /// Overall throughput over the last few seconds, once enough samples exist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed_bytes_per_sec: Option<u64>,
+    /// Estimated seconds until all tasks are done; absent while calculating or verifying
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<u64>,
 }
 
 /// Progress information for a single task during parallel installation
@@ -279,6 +737,12 @@ pub struct ParallelTaskProgress {
     pub phase: InstallPhase,
     pub percentage: f64,
     pub current_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_solid_block: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed_bytes_per_sec: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<u64>,
 }
 
 /// Installation phase
@@ -287,12 +751,55 @@ pub struct ParallelTaskProgress {
 pub enum InstallPhase {
     /// Calculating total size
     Calculating,
+    /// Checking source archive integrity before touching the target
+    PreflightCheck,
     /// Installing files
     Installing,
     /// Verifying installed files
     Verifying,
     /// Finalizing
     Finalizing,
+    /// Held back until another task of the batch it depends on has finished
+    Waiting,
+}
+
+/// State of an install history row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstallHistoryStatus {
+    /// Still in place as far as XFast knows
+    Installed,
+    /// A newer install went to the same target
+    Replaced,
+    /// Removed through the history
+    Uninstalled,
+}
+
+/// An addon installed through XFast
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallHistoryEntry {
+    pub id: i64,
+    pub addon_type: AddonType,
+    pub display_name: String,
+    pub target_path: String,
+    /// File name of the archive or folder it was installed from
+    pub source_name: Option<String>,
+    /// Unix seconds
+    pub installed_at: i64,
+    pub bytes: Option<u64>,
+    pub manifest_path: Option<String>,
+    pub status: InstallHistoryStatus,
+    pub uninstalled_at: Option<i64>,
+}
+
+/// Result of uninstalling an addon from the install history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UninstallFromHistoryResult {
+    pub entry: InstallHistoryEntry,
+    /// The target was already gone, so only the history and index were updated
+    pub already_removed: bool,
 }
 
 /// Result of a single task installation
@@ -304,9 +811,116 @@ pub struct TaskResult {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// Catalog key of `error_message`, for the frontend to show it localized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_message_key: Option<String>,
+    /// Positional arguments of the catalog message
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub error_message_args: Vec<String>,
+    /// Machine-readable code for the failure, `cancelled` for cancelled or skipped tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<crate::error::ApiErrorCode>,
     /// Verification statistics (if verification was performed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verification_stats: Option<VerificationStats>,
+    /// Bytes read by the pre-flight source check (if it ran)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preflight_bytes_read: Option<u64>,
+    /// Atomic install step that failed; the previous installation is left in place
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_stage: Option<AtomicInstallStage>,
+    /// Non-fatal problems, such as archive files renamed to avoid a case collision
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Not installed because the same archive is already installed unchanged
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skipped_identical: bool,
+    /// Archive entry whose extraction failed the task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_detail: Option<ExtractionFailureDetail>,
+}
+
+/// Step of extracting one archive entry that failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtractionFailureStage {
+    /// Reading or decompressing the entry out of the archive
+    Read,
+    /// Writing the entry to the target disk
+    Write,
+    /// The extracted data didn't match the entry's checksum
+    Crc,
+}
+
+/// Where and how extracting an archive entry failed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractionFailureDetail {
+    pub archive_path: String,
+    /// Path of the entry inside the archive
+    pub entry_path: String,
+    /// Position of the entry in the archive, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_index: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uncompressed_size: Option<u64>,
+    pub stage: ExtractionFailureStage,
+    /// The underlying error
+    pub error: String,
+    /// Entries that failed to write before extraction gave up on the target
+    /// disk as likely failing or full; 0 for a single failed entry
+    #[serde(default)]
+    pub failed_writes: usize,
+}
+
+/// Steps of an atomic install, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AtomicInstallStage {
+    /// Extracting or copying the new files into the staging directory
+    Staging,
+    /// Checking the staged files before anything is replaced
+    Verifying,
+    /// Moving the existing files aside (or into a navdata backup)
+    Backup,
+    /// Moving the staged files into place
+    Swap,
+}
+
+/// What to do with a task whose archive is already installed unchanged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateInstallAction {
+    /// Install again, overwriting the identical copy
+    #[default]
+    Reinstall,
+    Skip,
+    /// Ask through an `install-duplicates-detected` event
+    Ask,
+}
+
+/// A task whose archive was installed before and whose target is unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdenticalInstall {
+    pub task_id: String,
+    pub display_name: String,
+    pub target_path: String,
+    /// Unix seconds of the earlier install
+    pub installed_at: i64,
+}
+
+/// Payload of the `install-duplicates-detected` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateInstallPrompt {
+    /// Pass to `respond_duplicate_install`
+    pub request_id: String,
+    pub tasks: Vec<IdenticalInstall>,
+    /// Seconds until the tasks are reinstalled without an answer
+    pub timeout_secs: u64,
 }
 
 /// Overall installation result
@@ -384,7 +998,9 @@ pub enum SceneryCategory {
     Overlay,
     /// Airport-associated mesh scenery (small mesh that matches airport coordinates)
     AirportMesh,
-    /// Mesh scenery (terrain replacement, including orthophotos)
+    /// Orthophoto tiles (photo-textured base mesh, e.g. generated by Ortho4XP)
+    OrthoTile,
+    /// Mesh scenery (terrain replacement without photo textures)
     Mesh,
     /// Other/unknown scenery
     Other,
@@ -403,8 +1019,9 @@ impl SceneryCategory {
             SceneryCategory::Other => 4,
             SceneryCategory::Overlay => 5,
             SceneryCategory::AirportMesh => 6, // Between Overlay and regular Mesh
-            SceneryCategory::Mesh => 7,
-            SceneryCategory::Unrecognized => 8, // Lowest priority, always at bottom
+            SceneryCategory::OrthoTile => 7,   // Photo tiles take precedence over generic Mesh
+            SceneryCategory::Mesh => 8,
+            SceneryCategory::Unrecognized => 9, // Lowest priority, always at bottom
         }
     }
 }
@@ -438,6 +1055,12 @@ pub struct SceneryPackageInfo {
     /// Number of 10-degree tile folders under Earth nav data (e.g., "+30+110")
     #[serde(default)]
     pub earth_nav_tile_count: u32,
+    /// Total size of all files in the package, in bytes
+    #[serde(default)]
+    pub total_size_bytes: u64,
+    /// Newest file modification time in the package (Unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub newest_file_modified: Option<i64>,
     #[serde(with = "systemtime_serde")]
     pub indexed_at: SystemTime,
     pub required_libraries: Vec<String>,
@@ -463,6 +1086,13 @@ pub struct SceneryPackageInfo {
     /// Used to preserve the original label when package is manually moved to a different group
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub original_category: Option<SceneryCategory>,
+    /// Classifier revision that produced `category`; rows from older revisions
+    /// are reclassified on the next quick scan
+    #[serde(default)]
+    pub classifier_version: u32,
+    /// Pinned by the user: automatic sorting keeps it at its current position
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 /// DSF file header information
@@ -500,10 +1130,96 @@ pub struct SceneryIndex {
 pub struct SceneryIndexStats {
     pub total_packages: usize,
     pub by_category: HashMap<String, usize>,
+    /// Package count per continent; packages without one count as "Unknown"
+    #[serde(default)]
+    pub by_region: HashMap<String, usize>,
     #[serde(with = "systemtime_serde")]
     pub last_updated: SystemTime,
 }
 
+impl SceneryIndexStats {
+    pub fn from_index(index: &SceneryIndex) -> Self {
+        let mut by_category: HashMap<String, usize> = HashMap::new();
+        let mut by_region: HashMap<String, usize> = HashMap::new();
+        for info in index.packages.values() {
+            let category_name = format!("{:?}", info.category);
+            *by_category.entry(category_name).or_insert(0) += 1;
+            let region = info.continent.as_deref().unwrap_or("Unknown").to_string();
+            *by_region.entry(region).or_insert(0) += 1;
+        }
+        Self {
+            total_packages: index.packages.len(),
+            by_category,
+            by_region,
+            last_updated: index.last_updated,
+        }
+    }
+}
+
+/// Package count and size of one scenery category within a stats snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryCategoryStats {
+    pub category: String,
+    pub package_count: u64,
+    pub total_size_bytes: u64,
+}
+
+/// Aggregate scenery index statistics at one point in time, used to chart library growth
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryStatsSnapshot {
+    /// Unix timestamp (seconds) when the snapshot was taken
+    pub taken_at: i64,
+    pub total_packages: u64,
+    pub total_size_bytes: u64,
+    /// Distinct library names referenced but not installed
+    pub missing_library_count: u64,
+    pub categories: Vec<SceneryCategoryStats>,
+}
+
+/// Issue summary of one X-Plane session's Log.txt, used to chart issues across sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XPlaneLogSnapshot {
+    /// Unix timestamp (seconds) when the snapshot was recorded
+    pub recorded_at: i64,
+    /// Log.txt modification time (Unix seconds), i.e. when the session ended
+    pub log_modified_at: i64,
+    pub log_size: u64,
+    pub xplane_version: Option<String>,
+    pub crash_detected: bool,
+    /// Matching line count per issue category
+    pub category_counts: BTreeMap<String, u64>,
+    pub total_high: u32,
+    pub total_medium: u32,
+    pub total_low: u32,
+}
+
+/// Analysis warning the user chose to stop seeing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisSuppression {
+    pub id: i64,
+    /// "duplicate_plugin", "airport_conflict" or "compatibility"
+    pub kind: String,
+    /// Stable identifier of the warning, e.g. "airport_conflict:EGKK"
+    pub key: String,
+    /// Unix seconds
+    pub created_at: i64,
+}
+
+/// Payload of the `scenery-index-progress` event emitted during a rebuild
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryIndexProgress {
+    /// Packages classified so far
+    pub processed: usize,
+    pub total: usize,
+    /// Folder classified last; None in the final event
+    pub current_folder: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SceneryIndexStatus {
@@ -550,6 +1266,9 @@ pub struct SceneryManagerEntry {
     /// Used to show original label when package is manually moved to FixedHighPriority
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub original_category: Option<SceneryCategory>,
+    /// Automatic sorting keeps this entry at its current position
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 /// Simplified entry for batch updates (only fields that can be changed)
@@ -559,6 +1278,38 @@ pub struct SceneryEntryUpdate {
     pub folder_name: String,
     pub enabled: bool,
     pub sort_order: u32,
+    /// New pin state; `None` leaves it unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+}
+
+/// When `apply_scenery_changes` and `delete_scenery_folder` write
+/// scenery_packs.ini. X-Plane rewrites the file on exit, so changes written
+/// while it runs can be lost.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SceneryApplyMode {
+    /// Write the ini right away
+    #[default]
+    Immediate,
+    /// Write the ini now when X-Plane is closed, or else once it exits
+    DeferUntilSimCloses,
+    /// Refuse while X-Plane is running
+    FailIfRunning,
+}
+
+/// Payload of the `deferred-scenery-applied` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeferredSceneryApplied {
+    pub xplane_path: String,
+    pub entries_updated: usize,
+    pub deleted_folders: Vec<String>,
+    /// When the first of the changes was queued (unix seconds)
+    pub queued_at: i64,
+    /// Why the ini could not be written, when it couldn't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Data for scenery manager UI
@@ -578,42 +1329,456 @@ pub struct SceneryManagerData {
     /// Raw tile overlap data (all overlaps, before XPME filtering) for frontend real-time recalculation
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub tile_overlaps: HashMap<String, Vec<String>>,
+    /// Links and shortcuts in Custom Scenery whose target is missing
+    #[serde(default)]
+    pub broken_entries: Vec<BrokenSceneryEntry>,
+    /// Conflicting airport identifiers hidden by the user's suppressions
+    #[serde(default)]
+    pub suppressed_airport_conflict_count: usize,
 }
 
-// ========== Management Data Structures ==========
-
-/// Individual aircraft model file state within an aircraft folder
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How a Custom Scenery entry points at a folder elsewhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AircraftAcfFileInfo {
-    pub file_name: String,
-    pub enabled: bool,
+pub enum SceneryLinkKind {
+    /// Symlink, or junction on Windows
+    Link,
+    /// Windows `.lnk` shortcut
+    Shortcut,
 }
 
-/// Aircraft information for management UI
+/// Custom Scenery link or shortcut that doesn't resolve to a folder
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AircraftInfo {
-    pub folder_name: String,
-    pub display_name: String,
-    pub acf_file: String,
-    pub acf_files: Vec<AircraftAcfFileInfo>,
-    pub enabled: bool,
-    pub has_mixed_acf_states: bool,
-    pub has_liveries: bool,
-    pub livery_count: usize,
-    pub version: Option<String>,
-    /// URL for checking updates (from skunkcrafts_updater.cfg module| field)
-    pub update_url: Option<String>,
-    /// Update provider type (e.g. skunkcrafts, x-updater, zibo)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub update_provider: Option<String>,
-    /// Latest version from remote server (populated by check_aircraft_updates)
-    pub latest_version: Option<String>,
-    /// Whether an update is available
-    pub has_update: bool,
-    /// Whether disabled in skunkcrafts_updater.cfg (disabled|true)
-    pub cfg_disabled: Option<bool>,
+pub struct BrokenSceneryEntry {
+    /// Name in Custom Scenery, including `.lnk` for shortcuts
+    pub entry_name: String,
+    /// Stored target, if it could be read
+    pub target_path: Option<String>,
+    pub kind: SceneryLinkKind,
+}
+
+/// Indexed column a paged scenery manager query can be sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SceneryManagerSortField {
+    #[default]
+    SortOrder,
+    FolderName,
+    Category,
+    Enabled,
+    Continent,
+}
+
+/// Sort for a paged scenery manager query. Ties are broken by load order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryManagerSort {
+    #[serde(default)]
+    pub field: SceneryManagerSortField,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// Filter for a paged scenery manager query; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryManagerFilter {
+    #[serde(default)]
+    pub category: Option<SceneryCategory>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Case-insensitive substring of the folder name
+    #[serde(default)]
+    pub search: Option<String>,
+    /// Continent name, as in [`SceneryManagerEntry::continent`]
+    #[serde(default)]
+    pub continent: Option<String>,
+}
+
+/// One page of scenery manager entries.
+///
+/// Pages only contain real packages, with their stored sort order. The Global
+/// Airports entry is part of [`SceneryManagerSummary`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryManagerPage {
+    pub entries: Vec<SceneryManagerEntry>,
+    pub offset: u64,
+    pub limit: u64,
+    /// Number of packages matching the filter, across all pages
+    pub total_count: u64,
+}
+
+/// An airport found by the apt.dat search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportSearchResult {
+    pub icao: String,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Package whose definition X-Plane uses (`*GLOBAL_AIRPORTS*` for the default airports)
+    pub package: String,
+    /// Lower-priority packages that also define this airport
+    pub other_packages: Vec<String>,
+}
+
+/// An enabled package that requires library names exported by a given library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryDependent {
+    pub folder_name: String,
+    pub category: SceneryCategory,
+    /// Required library names this library exports
+    pub affected_library_count: u32,
+    /// Affected names no other enabled package exports
+    pub exclusive_library_count: u32,
+    /// True when every affected name would become missing without this library
+    pub exclusive: bool,
+}
+
+/// What deleting a scenery folder would break, returned by
+/// `preview_scenery_deletion`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryDeletionPreview {
+    pub folder_name: String,
+    /// Enabled packages requiring names only this folder exports
+    pub dependents: Vec<LibraryDependent>,
+}
+
+/// A required library name that no installed package exports anymore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewlyMissingLibrary {
+    /// Package requiring the library
+    pub folder_name: String,
+    pub library_name: String,
+}
+
+/// Outcome of `delete_scenery_folder`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryDeletionResult {
+    pub folder_name: String,
+    /// Library references the deletion left unresolved, in scenery order
+    pub newly_missing_libraries: Vec<NewlyMissingLibrary>,
+    /// scenery_packs.ini is updated once X-Plane closes
+    #[serde(default)]
+    pub ini_deferred: bool,
+}
+
+/// How a library required by a package is currently satisfied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LibraryDependencyStatus {
+    /// At least one enabled package exports it
+    Enabled,
+    /// Only disabled packages export it
+    Disabled,
+    /// No installed package exports it
+    Missing,
+    /// Resolved inside the package itself (self-reference or bundled subfolder)
+    Bundled,
+}
+
+/// A library required by a package, with the packages that export it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageLibraryDependency {
+    pub library_name: String,
+    pub status: LibraryDependencyStatus,
+    /// Exporting packages, enabled first, in scenery order
+    pub providers: Vec<String>,
+}
+
+/// A missing library in the plan returned by `build_missing_library_plan`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingLibraryPlanEntry {
+    pub library_name: String,
+    /// Packages whose library.txt references the name, in scenery order
+    pub referenced_by: Vec<String>,
+    /// Packages that only need it through an installed library that references it
+    pub indirectly_required_by: Vec<String>,
+    pub download_url: Option<String>,
+    /// Installed folder that probably provides it under a different name
+    pub installed_as: Option<String>,
+}
+
+/// Kind of ordering problem found by `validate_scenery_order`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SceneryOrderViolationKind {
+    /// A category that belongs lower is above one that belongs higher
+    CategoryOrder,
+    /// A package is below a library package it requires names from
+    LibraryDependency,
+}
+
+/// An enabled scenery_packs.ini entry placed on the wrong side of another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryOrderViolation {
+    pub kind: SceneryOrderViolationKind,
+    /// The entry the minimal fix moves
+    pub folder_name: String,
+    pub category: SceneryCategory,
+    pub other_folder_name: String,
+    pub other_category: SceneryCategory,
+    /// Whether `folder_name` is currently above `other_folder_name`
+    pub placed_above: bool,
+    /// A library name `folder_name` requires from `other_folder_name`
+    pub library_name: Option<String>,
+    pub message: String,
+}
+
+/// Result of `validate_scenery_order`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryOrderReport {
+    /// Enabled ini entries known to the index that were checked
+    pub checked_count: usize,
+    pub violations: Vec<SceneryOrderViolation>,
+    /// Entries moved to fix the violations, in their new order
+    pub moved: Vec<String>,
+    pub fixes_applied: bool,
+}
+
+/// Entry of a scenery_packs.ini change preview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryPacksDiffEntry {
+    pub path: String,
+    /// Enabled after the change; before it for removed entries
+    pub enabled: bool,
+    /// Position among the entries of the current file, from 0
+    pub old_position: Option<usize>,
+    /// Position among the entries that would be written, from 0
+    pub new_position: Option<usize>,
+}
+
+/// Result of `preview_scenery_packs_changes`: what writing scenery_packs.ini
+/// from the index would change
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryPacksPreview {
+    pub xplane_path: String,
+    pub ini_exists: bool,
+    /// Whether any entry would be added, removed, moved, enabled or disabled
+    pub has_changes: bool,
+    pub added: Vec<SceneryPacksDiffEntry>,
+    pub removed: Vec<SceneryPacksDiffEntry>,
+    /// Entries that would move, apart from those only shifted by others
+    pub reordered: Vec<SceneryPacksDiffEntry>,
+    /// Entries that would go from disabled to enabled
+    pub enabled: Vec<SceneryPacksDiffEntry>,
+    /// Entries that would go from enabled to disabled
+    pub disabled: Vec<SceneryPacksDiffEntry>,
+    /// The file that would be written
+    pub new_content: String,
+}
+
+/// Structural problem found in scenery_packs.ini by `validate_scenery_packs_ini`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SceneryPacksIniIssueKind {
+    MissingFile,
+    /// One of the `I`, `1000 Version` and `SCENERY` lines is absent
+    MissingHeader,
+    /// Header line repeated below the entries, typical of a file written twice
+    StrayHeader,
+    DuplicateEntry,
+    MissingFolder,
+    InvalidUtf8,
+    ByteOrderMark,
+    CrlfLineEndings,
+    /// The last line has no line break, typical of an interrupted write
+    Truncated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryPacksIniIssue {
+    pub kind: SceneryPacksIniIssueKind,
+    /// 1-based line number, when the issue belongs to one line
+    pub line: Option<usize>,
+    /// The line as read, with invalid bytes replaced
+    pub content: Option<String>,
+    pub message: String,
+}
+
+/// Result of `validate_scenery_packs_ini`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryPacksIniReport {
+    pub ini_path: String,
+    pub exists: bool,
+    /// SCENERY_PACK and SCENERY_PACK_DISABLED lines
+    pub entry_count: usize,
+    pub issues: Vec<SceneryPacksIniIssue>,
+}
+
+/// How `repair_scenery_packs_ini` fixes the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SceneryPacksRepairStrategy {
+    /// Write the file the scenery index produces, dropping everything else
+    RebuildFromIndex,
+    /// Drop broken, duplicate and dead lines, keeping the order of the rest
+    CleanInPlace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SceneryPacksIniChangeKind {
+    HeaderRestored,
+    ByteOrderMarkRemoved,
+    LineEndingsNormalized,
+    FinalNewlineAdded,
+    LineRemoved,
+    EntryAdded,
+    EntryMoved,
+    /// Same entry, switched between SCENERY_PACK and SCENERY_PACK_DISABLED
+    EntryStateChanged,
+}
+
+/// One difference between scenery_packs.ini before and after a repair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryPacksIniChange {
+    pub kind: SceneryPacksIniChangeKind,
+    /// Issue of the line that caused the change, for removed lines
+    pub reason: Option<SceneryPacksIniIssueKind>,
+    /// 1-based line number in the previous file
+    pub old_line: Option<usize>,
+    /// 1-based line number in the repaired file
+    pub new_line: Option<usize>,
+    pub content: Option<String>,
+}
+
+/// Result of `repair_scenery_packs_ini`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryPacksIniRepairResult {
+    pub strategy: SceneryPacksRepairStrategy,
+    /// False when the file already matched and was left untouched
+    pub changed: bool,
+    /// Copy of the previous file, `scenery_packs.ini.bak`
+    pub backup_path: Option<String>,
+    pub changes: Vec<SceneryPacksIniChange>,
+    /// Issues the repair could not fix, such as index entries whose folder is gone
+    pub remaining_issues: Vec<SceneryPacksIniIssue>,
+}
+
+/// How `import_scenery_from_external` brings packages into Custom Scenery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SceneryImportMode {
+    Copy,
+    /// Source folders are removed once their copy is complete
+    Move,
+    /// Symlink, or `.lnk` shortcut on Windows, to the external folder
+    Link,
+}
+
+/// A package brought into Custom Scenery by an import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedSceneryPackage {
+    pub folder_name: String,
+    pub category: SceneryCategory,
+    /// Bytes copied; 0 for links and same-volume moves
+    pub transferred_bytes: u64,
+}
+
+/// A selected package that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryImportFailure {
+    pub folder_name: String,
+    pub message: String,
+}
+
+/// Result of `import_scenery_from_external`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryImportResult {
+    pub imported: Vec<ImportedSceneryPackage>,
+    /// Selected packages whose folder name already exists in Custom Scenery
+    pub skipped_existing: Vec<String>,
+    pub failed: Vec<SceneryImportFailure>,
+    /// Problems that did not stop a package, like a moved source that could
+    /// not be fully deleted
+    pub warnings: Vec<String>,
+    /// Set when the import was cancelled; packages after the current one are
+    /// left untouched
+    pub cancelled: bool,
+}
+
+/// Header counts for the scenery manager, without the entries themselves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryManagerSummary {
+    pub total_count: usize,
+    pub enabled_count: usize,
+    pub missing_deps_count: usize,
+    pub duplicate_tiles_count: usize,
+    pub duplicate_airports_count: usize,
+    pub needs_sync: bool,
+    /// Global Airports entry with its stored sort order. It goes before packages
+    /// with the same sort order.
+    pub global_airports: SceneryManagerEntry,
+    /// Links and shortcuts in Custom Scenery whose target is missing
+    #[serde(default)]
+    pub broken_entries: Vec<BrokenSceneryEntry>,
+    /// Conflicting airport identifiers hidden by the user's suppressions
+    #[serde(default)]
+    pub suppressed_airport_conflict_count: usize,
+}
+
+// ========== Management Data Structures ==========
+
+/// Individual aircraft model file state within an aircraft folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AircraftAcfFileInfo {
+    pub file_name: String,
+    pub enabled: bool,
+}
+
+/// Aircraft information for management UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AircraftInfo {
+    pub folder_name: String,
+    pub display_name: String,
+    pub acf_file: String,
+    pub acf_files: Vec<AircraftAcfFileInfo>,
+    pub enabled: bool,
+    pub has_mixed_acf_states: bool,
+    pub has_liveries: bool,
+    pub livery_count: usize,
+    /// Total size of the aircraft folder, only set when sizes were requested
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    /// Newest file modification time in the folder (Unix seconds), only set
+    /// when sizes were requested
+    #[serde(default)]
+    pub newest_file_modified: Option<i64>,
+    pub version: Option<String>,
+    /// URL for checking updates (from skunkcrafts_updater.cfg module| field)
+    pub update_url: Option<String>,
+    /// Update provider type (e.g. skunkcrafts, x-updater, zibo)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_provider: Option<String>,
+    /// Latest version from remote server (populated by check_aircraft_updates)
+    pub latest_version: Option<String>,
+    /// Whether an update is available
+    pub has_update: bool,
+    /// Whether disabled in skunkcrafts_updater.cfg (disabled|true)
+    pub cfg_disabled: Option<bool>,
 }
 
 /// Livery information for livery management UI
@@ -625,6 +1790,48 @@ pub struct LiveryInfo {
     pub icon_path: Option<String>,
 }
 
+/// Size of one livery folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveryDiskUsage {
+    pub folder_name: String,
+    pub size_bytes: u64,
+    pub file_count: usize,
+}
+
+/// Outcome of deleting one livery in a bulk delete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveryDeleteResult {
+    pub folder_name: String,
+    pub deleted: bool,
+    pub error: Option<String>,
+}
+
+/// Platform binaries a plugin ships, from their folders and, where they can be
+/// read, their file headers
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPlatformBinaries {
+    /// `win_x64/*.xpl` or the legacy `64/win.xpl`
+    pub win_x64: bool,
+    /// `mac_x64/*.xpl` or a legacy fat `mac.xpl` with an x86_64 slice
+    pub mac_x64: bool,
+    /// A Mac binary with an arm64 slice. Apple Silicon builds ship inside the
+    /// universal `mac_x64` binary, so only its Mach-O header tells.
+    pub mac_arm64: bool,
+    /// A Mac binary known by its path only, which may or may not have an
+    /// arm64 slice
+    pub mac_arch_unknown: bool,
+    /// `lin_x64/*.xpl` or the legacy `64/lin.xpl`
+    pub lin_x64: bool,
+    /// 32-bit binaries (`32/`, or `win.xpl`/`lin.xpl` at the root), which
+    /// current X-Plane versions don't load
+    pub legacy_32bit: bool,
+    /// Binaries whose platform neither their path nor their header tells
+    pub unrecognized: bool,
+}
+
 /// Plugin information for management UI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -650,6 +1857,19 @@ pub struct PluginInfo {
     pub has_scripts: bool,
     /// Number of scripts in the Scripts directory
     pub script_count: usize,
+    /// Total size of the plugin folder, only set when sizes were requested
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    /// Newest file modification time in the folder (Unix seconds), only set
+    /// when sizes were requested
+    #[serde(default)]
+    pub newest_file_modified: Option<i64>,
+    /// Platform binaries found, enabled (.xpl) or disabled (.xfmp)
+    #[serde(default)]
+    pub platform_binaries: PluginPlatformBinaries,
+    /// X-Plane can load one of the binaries on this operating system
+    #[serde(default)]
+    pub supported_on_current_platform: bool,
 }
 
 /// FlyWithLua script information for script management UI
@@ -664,6 +1884,77 @@ pub struct LuaScriptInfo {
     pub enabled: bool,
 }
 
+/// One copy of a plugin that is installed more than once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDuplicateLocation {
+    /// Folder path relative to the X-Plane root, `/`-separated
+    pub relative_path: String,
+    pub folder_name: String,
+    /// For copies bundled in an aircraft: the aircraft folder relative to `Aircraft/`
+    pub aircraft_folder: Option<String>,
+}
+
+/// Plugin folders X-Plane would treat as the same plugin, so only the first
+/// one it finds gets loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDuplicateGroup {
+    /// Lowercased folder name with copy suffixes such as " (2)" removed
+    pub group_id: String,
+    /// Always includes at least one folder under `Resources/plugins`
+    pub locations: Vec<PluginDuplicateLocation>,
+    /// Whether every copy ships the same .xpl files with the same sizes
+    pub identical: bool,
+    /// Key to pass to `add_analysis_suppression` to hide this group
+    pub suppression_key: String,
+}
+
+/// Where a FlyWithLua script copy lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LuaScriptScope {
+    /// The global `Resources/plugins/FlyWithLua/Scripts` folder
+    Global,
+    /// A FlyWithLua copy bundled inside an aircraft folder
+    Aircraft,
+}
+
+/// One copy of a duplicated FlyWithLua script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LuaScriptLocation {
+    /// Path relative to the X-Plane root, `/`-separated; identifies the copy
+    pub relative_path: String,
+    pub scope: LuaScriptScope,
+    /// For aircraft copies: the aircraft folder relative to `Aircraft/`
+    pub aircraft_folder: Option<String>,
+    pub size_bytes: u64,
+    pub content_hash: String,
+}
+
+/// A script enabled both globally and inside one or more aircraft
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LuaDuplicateGroup {
+    /// Lowercased script file name, shared by all copies
+    pub group_id: String,
+    pub file_name: String,
+    pub locations: Vec<LuaScriptLocation>,
+    /// Whether every copy has the same content
+    pub identical: bool,
+}
+
+/// Outcome of resolving a duplicate group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LuaDuplicateResolution {
+    pub group_id: String,
+    pub kept: String,
+    /// Relative paths of the copies renamed to `.xfml`
+    pub disabled: Vec<String>,
+}
+
 /// Navdata manager information for management UI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -675,6 +1966,237 @@ pub struct NavdataManagerInfo {
     pub enabled: bool,
 }
 
+/// Where a navdata installation compared by the consistency report lives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NavdataSourceKind {
+    /// Provider folder in `Custom Data`
+    CustomData,
+    /// GNS430 database in `Custom Data/GNS430` or `Resources/GNS430`
+    Gns430,
+    /// Navdata bundled inside an aircraft folder
+    Aircraft,
+}
+
+/// A single navdata installation in the consistency report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavdataConsistencyEntry {
+    pub source: NavdataSourceKind,
+    /// Provider name for Custom Data, aircraft folder name for aircraft navdata
+    pub name: String,
+    pub provider_name: Option<String>,
+    pub cycle: Option<String>,
+    pub airac: Option<String>,
+    /// Normalized 4-digit AIRAC cycle (YYNN) used for comparison
+    pub effective_cycle: Option<String>,
+    /// Absolute path of the navdata folder
+    pub folder_path: String,
+    /// Item type accepted by `open_management_folder`, if the folder is reachable through it
+    pub management_type: Option<String>,
+    /// Folder name relative to the management base folder of `management_type`
+    pub folder_name: String,
+    /// Whether this installation is behind the newest cycle found
+    pub is_outdated: bool,
+}
+
+/// AIRAC cycle comparison across all navdata installations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavdataConsistencyReport {
+    pub entries: Vec<NavdataConsistencyEntry>,
+    pub newest_cycle: Option<String>,
+    pub outdated_count: usize,
+    /// Entries whose cycle could not be determined (not counted as outdated)
+    pub unknown_cycle_count: usize,
+}
+
+/// Kind of problem found by the addon health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthCheckCategory {
+    /// `liveries` folder next to no aircraft (.acf or disabled .xfma)
+    OrphanedLiveries,
+    /// Plugin folder without a binary for the running platform
+    PluginWithoutBinary,
+    /// Custom Scenery folder without Earth nav data, library.txt or plugins
+    SceneryWithoutContent,
+    /// Custom Data navdata older than the one shipped with the sim
+    OutdatedNavdata,
+}
+
+impl HealthCheckCategory {
+    /// Serialized name, used as key of [`HealthCheckReport::counts`]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HealthCheckCategory::OrphanedLiveries => "orphanedLiveries",
+            HealthCheckCategory::PluginWithoutBinary => "pluginWithoutBinary",
+            HealthCheckCategory::SceneryWithoutContent => "sceneryWithoutContent",
+            HealthCheckCategory::OutdatedNavdata => "outdatedNavdata",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// What the user is advised to do; carried out with the existing management commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthAction {
+    Delete,
+    Disable,
+    Reinstall,
+    Ignore,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthIssue {
+    pub category: HealthCheckCategory,
+    pub severity: HealthSeverity,
+    /// Absolute path of the affected folder
+    pub path: String,
+    /// i18n key of the explanation, e.g. `health.pluginWrongPlatform`
+    pub reason_key: String,
+    /// Values for the explanation, e.g. the cycles compared
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub reason_params: HashMap<String, String>,
+    pub suggested_action: HealthAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckReport {
+    /// Most severe first
+    pub issues: Vec<HealthIssue>,
+    /// Issue count per category
+    pub counts: HashMap<String, usize>,
+    /// AIRAC cycle of the navdata shipped with the sim, when it could be read
+    pub base_navdata_cycle: Option<String>,
+}
+
+/// Payload of the `health-check-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheckProgress {
+    pub category: HealthCheckCategory,
+    /// Folders checked so far in this category
+    pub processed: usize,
+    pub total: usize,
+    /// Folder checked last; None when the category starts
+    pub current: Option<String>,
+}
+
+/// Outcome of quarantining or restoring plugin folders
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginQuarantineResult {
+    /// Folders moved by this operation
+    pub moved: Vec<String>,
+    /// Folders left alone (built-in plugins, or already moved back by the user)
+    pub skipped: Vec<String>,
+    /// Per-folder failures, formatted as "folder: reason"
+    pub errors: Vec<String>,
+    /// Folders still in quarantine after the operation
+    pub remaining_quarantined: Vec<String>,
+}
+
+/// Outcome of relocating an aircraft/scenery folder to another drive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelocationResult {
+    /// Original location, now a link to `destination_path`
+    pub source_path: String,
+    pub destination_path: String,
+    pub file_count: usize,
+    pub total_bytes: u64,
+    /// Set when the original could not be deleted after linking and needs manual cleanup
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leftover_path: Option<String>,
+    /// Links inside the folder that couldn't be recreated at the destination,
+    /// relative to it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_links: Vec<String>,
+}
+
+/// Files with identical contents found by a duplicate scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateFileGroup {
+    /// SHA-256 of the shared contents
+    pub hash: String,
+    pub size_bytes: u64,
+    /// Paths relative to the X-Plane root, `/`-separated and sorted; the first
+    /// one is kept when deduplicating
+    pub paths: Vec<String>,
+    /// Space freed by keeping a single copy
+    pub reclaimable_bytes: u64,
+}
+
+/// Result of scanning X-Plane folders for duplicate files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateFilesReport {
+    /// Largest reclaimable space first
+    pub groups: Vec<DuplicateFileGroup>,
+    pub total_reclaimable_bytes: u64,
+    pub scanned_files: usize,
+    /// Files sharing their size with another file, which had to be hashed
+    pub hashed_files: usize,
+    /// Whether the scan was cancelled; groups then only cover hashed files
+    pub cancelled: bool,
+}
+
+/// A duplicate replaced with a hardlink
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardlinkedFile {
+    pub path: String,
+    /// Kept file the duplicate now links to
+    pub target: String,
+    pub size_bytes: u64,
+}
+
+/// Why a duplicate was not replaced with a hardlink
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HardlinkSkipReason {
+    /// The kept file lives on another volume
+    DifferentVolume,
+    /// The filesystem does not support hardlinks
+    Unsupported,
+    /// Contents differ from the scan
+    Changed,
+    /// Already a hardlink of the kept file
+    AlreadyLinked,
+    Missing,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardlinkSkippedFile {
+    pub path: String,
+    pub reason: HardlinkSkipReason,
+    pub message: String,
+}
+
+/// Outcome of replacing duplicate files with hardlinks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardlinkDedupResult {
+    pub linked: Vec<HardlinkedFile>,
+    pub skipped: Vec<HardlinkSkippedFile>,
+    pub reclaimed_bytes: u64,
+    pub cancelled: bool,
+}
+
 /// Management data for UI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -682,6 +2204,43 @@ pub struct ManagementData<T> {
     pub entries: Vec<T>,
     pub total_count: usize,
     pub enabled_count: usize,
+    /// For plugins: folders X-Plane would load as the same plugin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<PluginDuplicateGroup>,
+    /// For plugins: duplicate groups hidden by the user's suppressions
+    #[serde(default)]
+    pub suppressed_count: usize,
+}
+
+/// Payload of the `management-item-installed` event: the management entries for a
+/// freshly installed folder, as a full scan would report them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "itemType", rename_all = "camelCase")]
+pub enum InstalledManagementItem {
+    /// The installed folder, or the aircraft nested inside it
+    Aircraft {
+        entries: Vec<AircraftInfo>,
+    },
+    Plugin {
+        entry: PluginInfo,
+    },
+}
+
+/// Payload of the `addon-update-tracked` event: an installed addon that ships
+/// updater metadata, so the frontend can track it without a rescan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTrackedAddon {
+    /// `aircraft`, `plugin`, `scenery` or `livery`, as the addon updater names them
+    pub item_type: String,
+    /// Folder relative to the item type's root (liveries: `<aircraft>/liveries/<livery>`)
+    pub folder_name: String,
+    pub display_name: String,
+    pub provider: UpdaterProvider,
+    /// Version read from the installed folder
+    pub version: Option<String>,
+    /// Update URL read from the installed folder (`x-updater:<host>` for x-updater)
+    pub update_url: Option<String>,
 }
 
 // SystemTime serialization helper
@@ -833,6 +2392,279 @@ pub struct PresetExportFormat {
     pub description: Option<String>,
     pub created_at: i64,
     pub snapshot: PresetSnapshot,
+    /// Launch profiles travel with presets; older exports don't have them
+    #[serde(default)]
+    pub launch_profiles: Vec<LaunchProfile>,
+}
+
+// ========== Launch Profile Data Structures ==========
+
+/// A named set of X-Plane command-line arguments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfile {
+    pub name: String,
+    pub args: Vec<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Result of saving a launch profile. Unknown or malformed flags are saved
+/// anyway and reported in `warnings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfileSaveResult {
+    pub profile: LaunchProfile,
+    pub warnings: Vec<String>,
+}
+
+// ========== Preference Backup Data Structures ==========
+
+/// A zip backup of X-Plane's `Output/preferences` folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreferenceBackup {
+    pub id: String,
+    pub label: Option<String>,
+    /// X-Plane installation the preferences were taken from
+    pub xplane_path: String,
+    /// Unix seconds
+    pub created_at: i64,
+    /// Size of the zip
+    pub size_bytes: u64,
+    pub file_count: usize,
+    /// Uncompressed size of the backed up files
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreferenceRestoreResult {
+    pub backup: PreferenceBackup,
+    pub restored_path: String,
+    /// Where the preferences in place before the restore were moved
+    pub previous_path: Option<String>,
+}
+
+/// Files of an addon as they were before an update, kept to go back to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonBackup {
+    /// `<addon>/<timestamp>` below the addon backups folder
+    pub id: String,
+    pub item_type: String,
+    pub folder_name: String,
+    /// Version before the update
+    pub from_version: Option<String>,
+    /// Version the update installed
+    pub to_version: Option<String>,
+    /// Unix seconds
+    pub created_at: i64,
+    /// Files the update added, replaced or deleted
+    pub file_count: usize,
+    /// Size of the files kept
+    pub total_bytes: u64,
+}
+
+/// Outcome of `restore_addon_backup`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonBackupRestoreResult {
+    pub backup: AddonBackup,
+    /// False when files changed since the update and the restore wasn't forced
+    pub restored: bool,
+    /// Files that no longer are as the update left them
+    pub modified_files: Vec<String>,
+    /// Files put back as they were before the update
+    pub restored_files: usize,
+    /// Files the update added, now removed
+    pub removed_files: usize,
+}
+
+/// Area checked by `run_full_maintenance`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MaintenanceSection {
+    /// Installed addons against their updater manifests
+    Addons,
+    /// Scenery index against the Custom Scenery folder
+    SceneryIndex,
+    /// scenery_packs.ini against the index and disk
+    SceneryPacks,
+    /// Navdata AIRAC cycles
+    Navdata,
+}
+
+impl MaintenanceSection {
+    pub const ALL: [MaintenanceSection; 4] = [
+        MaintenanceSection::Addons,
+        MaintenanceSection::SceneryIndex,
+        MaintenanceSection::SceneryPacks,
+        MaintenanceSection::Navdata,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MaintenanceSectionStatus {
+    Healthy,
+    Issues,
+    /// The check itself failed; see `summary`
+    Failed,
+    /// Not checked (e.g. network checks in offline mode)
+    Skipped,
+    /// Inputs unchanged since the last healthy check, whose result is reused
+    Unchanged,
+}
+
+/// Result of one section of a maintenance run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceSectionReport {
+    pub section: MaintenanceSection,
+    pub status: MaintenanceSectionStatus,
+    pub summary: String,
+    pub issues: Vec<String>,
+    /// Fingerprint of the section inputs (folder listings and mtimes) when checked
+    pub fingerprint: String,
+    /// Unix seconds
+    pub checked_at: i64,
+    /// Run whose healthy result was reused when `status` is `unchanged`
+    #[serde(default)]
+    pub reused_from_run: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MaintenanceRunStatus {
+    Running,
+    /// Cancelled or interrupted; the next run for the same X-Plane path resumes it
+    Cancelled,
+    Completed,
+}
+
+/// Consolidated report of a `run_full_maintenance` run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceReport {
+    pub run_id: i64,
+    pub xplane_path: String,
+    pub status: MaintenanceRunStatus,
+    /// Started by the background scheduler
+    pub scheduled: bool,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    /// Finished sections, in run order
+    pub sections: Vec<MaintenanceSectionReport>,
+    /// Requested sections not finished yet
+    pub pending: Vec<MaintenanceSection>,
+}
+
+/// Payload of the `maintenance-progress` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceProgressEvent {
+    pub run_id: i64,
+    pub section: MaintenanceSection,
+    /// `started`, `progress` or `finished`
+    pub phase: String,
+    /// Set when `phase` is `finished`
+    pub status: Option<MaintenanceSectionStatus>,
+    pub completed_sections: usize,
+    pub total_sections: usize,
+    pub message: Option<String>,
+}
+
+/// Update state of one addon checked by `check_all_addon_updates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonUpdateCheckItem {
+    /// `aircraft`, `plugin`, `scenery` or `livery`
+    pub item_type: String,
+    /// Folder relative to the item type's root (liveries: `<aircraft>/liveries/<livery>`)
+    pub folder_name: String,
+    pub display_name: String,
+    /// Update provider (skunkcrafts, x-updater, zibo)
+    pub provider: String,
+    pub current_version: Option<String>,
+    pub remote_version: Option<String>,
+    pub has_update: bool,
+    /// Set when the remote check failed
+    pub error: Option<String>,
+}
+
+/// Result of `check_all_addon_updates`, also the payload of `addon-updates-available`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonUpdateSummary {
+    pub xplane_path: String,
+    /// Checked items, sorted by item type and display name
+    pub items: Vec<AddonUpdateCheckItem>,
+    /// Addons with updater metadata found on disk
+    pub total_count: usize,
+    pub update_count: usize,
+    pub failed_count: usize,
+    /// Cancelled before every addon was checked; `items` holds the finished ones
+    pub cancelled: bool,
+    /// Started by the background scheduler
+    pub scheduled: bool,
+    /// Unix seconds
+    pub checked_at: i64,
+}
+
+/// A file in an addon update diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonUpdateDiffFile {
+    /// Path relative to the addon folder, with forward slashes
+    pub path: String,
+    /// Size of the local copy, if there is one
+    pub local_size: Option<u64>,
+    /// Size of the remote file, where the manifest lists it
+    pub remote_size: Option<u64>,
+    /// Matches a config file pattern (`*.cfg`, `*.ini`)
+    pub is_config: bool,
+}
+
+/// Files of one kind in an addon update diff
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonUpdateDiffGroup {
+    /// Listed files, sorted by path; fewer than `count` when the diff is summarized
+    pub files: Vec<AddonUpdateDiffFile>,
+    pub count: usize,
+    /// Local size of every file in the group, or the remote size for new files
+    pub bytes: u64,
+}
+
+/// What an addon update would change in the local folder, from `get_addon_update_diff`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonUpdateDiff {
+    pub provider: String,
+    pub item_type: String,
+    pub folder_name: String,
+    pub local_version: Option<String>,
+    pub remote_version: Option<String>,
+    /// Files the update creates
+    pub added: AddonUpdateDiffGroup,
+    /// Replaced files whose local copy is the previous remote version
+    pub updated: AddonUpdateDiffGroup,
+    /// Replaced files whose local copy differs from the previous remote version
+    pub modified_locally: AddonUpdateDiffGroup,
+    /// Replaced files without a previous hash in the manifest, so local edits
+    /// can't be told apart from remote changes
+    pub changed: AddonUpdateDiffGroup,
+    /// Deleted files that are unmodified or can't be checked
+    pub deleted: AddonUpdateDiffGroup,
+    /// Deleted files whose local copy differs from the version the manifest lists
+    pub deleted_modified: AddonUpdateDiffGroup,
+    /// Local files the update neither writes nor deletes
+    pub untouched: AddonUpdateDiffGroup,
+    /// Config files the update overwrites or deletes, except unmodified ones
+    pub config_changes: Vec<AddonUpdateDiffFile>,
+    /// Some groups list fewer files than they count
+    pub summarized: bool,
 }
 
 #[cfg(test)]
@@ -907,7 +2739,8 @@ mod tests {
         assert!(SceneryCategory::Library.priority() < SceneryCategory::Other.priority());
         assert!(SceneryCategory::Other.priority() < SceneryCategory::Overlay.priority());
         assert!(SceneryCategory::Overlay.priority() < SceneryCategory::AirportMesh.priority());
-        assert!(SceneryCategory::AirportMesh.priority() < SceneryCategory::Mesh.priority());
+        assert!(SceneryCategory::AirportMesh.priority() < SceneryCategory::OrthoTile.priority());
+        assert!(SceneryCategory::OrthoTile.priority() < SceneryCategory::Mesh.priority());
         assert!(SceneryCategory::Mesh.priority() < SceneryCategory::Unrecognized.priority());
     }
 
@@ -973,7 +2806,15 @@ mod tests {
             task_name: "Test Aircraft".to_string(),
             success: true,
             error_message: None,
+            error_message_key: None,
+            error_message_args: Vec::new(),
+            error_code: None,
             verification_stats: None,
+            preflight_bytes_read: None,
+            failed_stage: None,
+            warnings: Vec::new(),
+            skipped_identical: false,
+            failure_detail: None,
         };
         assert!(success_result.success);
         assert!(success_result.error_message.is_none());
@@ -986,7 +2827,15 @@ mod tests {
             task_name: "Test Scenery".to_string(),
             success: false,
             error_message: Some("Permission denied".to_string()),
+            error_message_key: None,
+            error_message_args: Vec::new(),
+            error_code: Some(crate::error::ApiErrorCode::PermissionDenied),
             verification_stats: None,
+            preflight_bytes_read: None,
+            failed_stage: None,
+            warnings: Vec::new(),
+            skipped_identical: false,
+            failure_detail: None,
         };
         assert!(!fail_result.success);
         assert!(fail_result.error_message.is_some());
@@ -1068,6 +2917,7 @@ mod tests {
             duplicate_airports: vec![],
             airport_id: None,
             original_category: Some(SceneryCategory::Airport),
+            pinned: false,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -1091,6 +2941,8 @@ mod tests {
                 has_mixed_acf_states: false,
                 has_liveries: true,
                 livery_count: 5,
+                size_bytes: None,
+                newest_file_modified: None,
                 version: Some("1.0".to_string()),
                 update_url: None,
                 update_provider: None,
@@ -1100,6 +2952,8 @@ mod tests {
             }],
             total_count: 1,
             enabled_count: 1,
+            duplicates: Vec::new(),
+            suppressed_count: 0,
         };
 
         assert_eq!(data.entries.len(), data.total_count);
@@ -1112,6 +2966,7 @@ mod tests {
             folder_name: "test_scenery".to_string(),
             enabled: false,
             sort_order: 42,
+            pinned: Some(true),
         };
 
         let json = serde_json::to_string(&update).unwrap();
@@ -1120,5 +2975,79 @@ mod tests {
         assert_eq!(parsed.folder_name, "test_scenery");
         assert!(!parsed.enabled);
         assert_eq!(parsed.sort_order, 42);
+        assert_eq!(parsed.pinned, Some(true));
+
+        // Updates from before pinning existed leave the pin alone
+        let legacy: SceneryEntryUpdate =
+            serde_json::from_str(r#"{"folderName":"test_scenery","enabled":true,"sortOrder":1}"#)
+                .unwrap();
+        assert_eq!(legacy.pinned, None);
+    }
+
+    fn detected_in(path: &str, internal_root: Option<&str>) -> DetectedItem {
+        DetectedItem {
+            addon_type: AddonType::Aircraft,
+            path: path.to_string(),
+            original_input_path: path.to_string(),
+            display_name: "B737-800X".to_string(),
+            archive_internal_root: internal_root.map(str::to_string),
+            extraction_chain: None,
+            navdata_info: None,
+            livery_aircraft_type: None,
+            livery_target_folder: None,
+            livery_target_candidates: Vec::new(),
+            version_info: None,
+            companion_paths: Vec::new(),
+            sim_version_hints: None,
+            compatibility: AddonCompatibility::default(),
+            updater_provider: UpdaterProvider::default(),
+            target_root_kind: TargetRootKind::default(),
+        }
+    }
+
+    #[test]
+    fn test_profile_identity_ignores_source_path_and_archive_name() {
+        let first = detected_in("/downloads/zibo_4.01.zip", Some("B737-800X"));
+        let second = detected_in(r"D:\Betas\zibo_4.02_beta.zip", Some(r"B737-800X\"));
+        assert_eq!(first.profile_identity(), second.profile_identity());
+        assert_ne!(first.stable_id(), second.stable_id());
+
+        let mut nested = detected_in("/downloads/bundle.zip", None);
+        nested.extraction_chain = Some(ExtractionChain {
+            archives: vec![NestedArchiveInfo {
+                internal_path: "zibo_4.03.zip".to_string(),
+                password: None,
+                format: "zip".to_string(),
+                password_required: false,
+            }],
+            final_internal_root: Some("b737-800x".to_string()),
+        });
+        assert_eq!(nested.profile_identity(), first.profile_identity());
+        assert_eq!(
+            install_profile_identity(&AddonType::Aircraft, " b737-800x ", Some("/B737-800X")),
+            first.profile_identity()
+        );
+    }
+
+    #[test]
+    fn test_profile_identity_differs_by_type_name_and_root() {
+        let base = install_profile_identity(&AddonType::Aircraft, "A330", Some("A330"));
+        assert_eq!(base.len(), 32);
+        assert_ne!(
+            base,
+            install_profile_identity(&AddonType::Livery, "A330", Some("A330"))
+        );
+        assert_ne!(
+            base,
+            install_profile_identity(&AddonType::Aircraft, "A340", Some("A330"))
+        );
+        assert_ne!(
+            base,
+            install_profile_identity(&AddonType::Aircraft, "A330", Some("A330/v2"))
+        );
+        assert_ne!(
+            base,
+            install_profile_identity(&AddonType::Aircraft, "A330", None)
+        );
     }
 }
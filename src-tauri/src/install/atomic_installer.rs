@@ -7,11 +7,14 @@ use tauri::{AppHandle, Emitter};
 use uuid::Uuid;
 use walkdir::WalkDir;
 
+use crate::fs_case::CaseVariantTracker;
 use crate::installer::sanitize_folder_name;
 use crate::logger;
 use crate::models::{
-    BackupFileEntry, InstallPhase, InstallProgress, InstallTask, NavdataBackupVerification,
+    AtomicInstallStage, BackupFileEntry, InstallPhase, InstallProgress, InstallTask,
+    NavdataBackupVerification,
 };
+use crate::staging;
 
 /// Minimum required free space (1 GB) as a safety buffer
 const MIN_FREE_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
@@ -19,6 +22,59 @@ const MIN_FREE_SPACE_BYTES: u64 = 1024 * 1024 * 1024;
 /// Maximum symlink resolution depth to prevent infinite loops
 const MAX_SYMLINK_DEPTH: usize = 40;
 
+/// An atomic install error tagged with the step it happened in. Errors before
+/// the swap leave the previous installation untouched; swap errors are rolled back.
+#[derive(Debug)]
+pub struct StageError {
+    pub stage: AtomicInstallStage,
+    source: anyhow::Error,
+}
+
+impl std::fmt::Display for StageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stage = match self.stage {
+            AtomicInstallStage::Staging => "Staging",
+            AtomicInstallStage::Verifying => "Verification of staged files",
+            AtomicInstallStage::Backup => "Backup of existing files",
+            AtomicInstallStage::Swap => "Swap into place",
+        };
+        write!(f, "{} failed: {}", stage, self.source)
+    }
+}
+
+impl std::error::Error for StageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Tag an error with the atomic install step it happened in
+pub trait StageResultExt<T> {
+    fn stage(self, stage: AtomicInstallStage) -> Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> StageResultExt<T> for std::result::Result<T, E> {
+    fn stage(self, stage: AtomicInstallStage) -> Result<T> {
+        self.map_err(|e| {
+            let source: anyhow::Error = e.into();
+            // Keep the innermost stage when a tagged error passes through again
+            if source.downcast_ref::<StageError>().is_some() {
+                source
+            } else {
+                anyhow::Error::new(StageError { stage, source })
+            }
+        })
+    }
+}
+
+/// The atomic install step an installation error came from, if any
+pub fn failed_stage(error: &anyhow::Error) -> Option<AtomicInstallStage> {
+    error
+        .chain()
+        .find_map(|e| e.downcast_ref::<StageError>())
+        .map(|e| e.stage)
+}
+
 /// Atomic installer for safer installation operations
 pub struct AtomicInstaller {
     /// Temporary directory for staging files (same drive as target)
@@ -45,7 +101,8 @@ pub struct AtomicInstaller {
 
 impl AtomicInstaller {
     /// Create a new atomic installer
-    /// The temp directory will be created in the X-Plane root directory
+    /// The temp directory will be created in the configured staging folder, or
+    /// else in the X-Plane root directory
     ///
     /// # Arguments
     /// * `target_dir` - The target installation directory (e.g., C:\X-Plane\Aircraft\A330)
@@ -65,11 +122,22 @@ impl AtomicInstaller {
         // Check available disk space
         check_disk_space(xplane_root)?;
 
-        // Create temp directory in X-Plane root directory
-        let temp_dir = xplane_root.join(format!(".xfastmanager_temp_{}", Uuid::new_v4()));
-
-        fs::create_dir_all(&temp_dir)
-            .context(format!("Failed to create temp directory: {:?}", temp_dir))?;
+        // Stage in the configured staging folder, or else in the X-Plane root
+        // directory so the final move is a rename
+        let temp_dir = match staging::configured_root() {
+            Some(root) => staging::create_dir_in(&root, "install")
+                .context(format!("Failed to create temp directory in {:?}", root))?,
+            None => {
+                let temp_dir = xplane_root.join(format!(
+                    "{}{}",
+                    crate::app_dirs::ATOMIC_INSTALL_TEMP_PREFIX,
+                    Uuid::new_v4()
+                ));
+                fs::create_dir_all(&temp_dir)
+                    .context(format!("Failed to create temp directory: {:?}", temp_dir))?;
+                temp_dir
+            }
+        };
 
         logger::log_info(
             &format!("Created atomic install temp directory: {:?}", temp_dir),
@@ -130,6 +198,9 @@ impl AtomicInstaller {
             active_tasks: None,
             completed_task_count: None,
             completed_task_ids: None,
+            in_solid_block: None,
+            speed_bytes_per_sec: None,
+            eta_seconds: None,
         };
 
         let _ = self.app_handle.emit("install-progress", &progress);
@@ -321,6 +392,8 @@ impl AtomicInstaller {
             InstallPhase::Installing,
         );
 
+        check_case_variants(&self.temp_dir, &self.target_dir);
+
         match merge_directories(&self.temp_dir, &self.target_dir) {
             Ok(()) => {}
             Err(e) => {
@@ -348,7 +421,12 @@ impl AtomicInstaller {
         Ok(())
     }
 
-    /// Scenario 4: Navdata clean install with backup (EXTREME PERFORMANCE OPTIMIZED)
+    /// Scenario 4: Navdata install with backup (EXTREME PERFORMANCE OPTIMIZED)
+    ///
+    /// Custom Data must never be left with a mix of two AIRAC cycles, so the old
+    /// entries are moved aside before the staged ones are moved in, and moved back
+    /// if anything fails. Used for both clean and overwrite installs: the replaced
+    /// entries are whole top-level folders either way.
     ///
     /// Performance optimizations:
     /// 1. Uses walkdir for efficient single-pass file enumeration (no extra stat calls)
@@ -357,62 +435,67 @@ impl AtomicInstaller {
     /// 4. Fast verification uses single fs::metadata() call (no double stat)
     ///
     /// Steps:
-    /// 1. Enumerate new navdata entries from temp_dir
-    /// 2. Create Backup_Data/<provider_name>/ folder (one backup per provider)
+    /// 1. Enumerate and verify new navdata entries in temp_dir
+    /// 2. Create Backup_Data/<provider_name_timestamp>/ (or a temp holding folder
+    ///    when backups are disabled)
     /// 3. Collect all files to backup using walkdir (path + size, NO checksum)
-    /// 4. Move directories to backup (O(1) directory rename when possible)
-    /// 5. Fast verify (single stat per file)
-    /// 6. Write verification.json
-    /// 7. Merge new navdata to target
+    /// 4. Move old entries aside (O(1) directory rename when possible)
+    /// 5. Fast verify (single stat per file) and write verification.json
+    /// 6. Merge new navdata to target, restoring the old entries on failure
+    /// 7. Drop older backups of the same provider (or the holding folder)
     ///
-    /// If backup_navdata is false, skips backup steps and just deletes old files.
-    pub fn install_clean_navdata_with_backup(&mut self, backup_navdata: bool) -> Result<()> {
+    /// `clean` widens the replaced entries to the whole target folder when navdata
+    /// is installed into a dedicated subfolder (e.g. UFMC/GNS430/FF777 path).
+    pub fn install_navdata_with_backup(&mut self, backup_navdata: bool, clean: bool) -> Result<()> {
         logger::log_info(
-            "Atomic install: Navdata clean install with backup (extreme optimized)",
+            "Atomic install: Navdata install with backup (extreme optimized)",
             Some("atomic_installer"),
         );
 
         // Step 1: Enumerate top-level entries in temp_dir (new navdata files/folders)
         self.emit_progress("Scanning new navdata files...", InstallPhase::Installing);
-        let new_entries: Vec<std::ffi::OsString> = fs::read_dir(&self.temp_dir)?
+        let new_entries: Vec<std::ffi::OsString> = fs::read_dir(&self.temp_dir)
+            .stage(AtomicInstallStage::Verifying)?
             .filter_map(|e| e.ok())
             .map(|e| e.file_name())
             .collect();
+        verify_staged_navdata(&self.temp_dir).stage(AtomicInstallStage::Verifying)?;
+
+        logger::log_info(
+            &format!("Found {} new navdata entries", new_entries.len()),
+            Some("atomic_installer"),
+        );
 
-        // For navdata installed into a dedicated subfolder (e.g. UFMC/GNS430/FF777 path),
-        // backup and clean the whole target folder content to avoid leaving stale folders
-        // that may not appear in the new package listing.
+        if !self.target_dir.exists() {
+            return self.install_fresh().stage(AtomicInstallStage::Swap);
+        }
+
+        // For navdata installed into a dedicated subfolder, a clean install backs up
+        // and clears the whole target folder to avoid leaving stale folders that may
+        // not appear in the new package listing.
         let target_is_custom_data_root = self
             .target_dir
             .file_name()
             .and_then(|n| n.to_str())
             .map(|n| n.eq_ignore_ascii_case("Custom Data"))
             .unwrap_or(false);
-        let backup_scope_entries: Vec<std::ffi::OsString> = if target_is_custom_data_root {
+        let backup_scope_entries: Vec<std::ffi::OsString> = if target_is_custom_data_root || !clean
+        {
             new_entries.clone()
         } else {
-            fs::read_dir(&self.target_dir)?
+            fs::read_dir(&self.target_dir)
+                .stage(AtomicInstallStage::Backup)?
                 .filter_map(|e| e.ok())
                 .map(|e| e.file_name())
                 .collect()
         };
 
-        if new_entries.is_empty() {
-            logger::log_info(
-                "No new navdata entries found in temp directory",
-                Some("atomic_installer"),
-            );
-            return self.install_fresh();
-        }
-
-        logger::log_info(
-            &format!("Found {} new navdata entries", new_entries.len()),
-            Some("atomic_installer"),
-        );
-
-        // Read provider name from new navdata (needed for both backup and cleanup)
-        let provider_name = self
-            .read_navdata_info(&self.temp_dir)
+        // Read provider name and cycle from new navdata (needed for both backup and cleanup)
+        let new_info = self.read_navdata_info(&self.temp_dir);
+        let new_cycle = new_info.as_ref().ok().and_then(|(_, cycle, airac)| {
+            crate::management_index::effective_airac_cycle(cycle.as_deref(), airac.as_deref())
+        });
+        let provider_name = new_info
             .map(|(name, _, _)| name)
             .or_else(|_| {
                 self.read_navdata_info(&self.target_dir)
@@ -420,159 +503,317 @@ impl AtomicInstaller {
             })
             .unwrap_or_else(|_| "navdata".to_string());
 
-        if backup_navdata {
-            // Read old cycle/airac from target_dir (the data being backed up)
-            let (old_cycle, old_airac) = self
-                .read_navdata_info(&self.target_dir)
-                .map(|(_, c, a)| (c, a))
-                .unwrap_or((None, None));
-
-            // Step 3: Create Backup_Data/<provider_name_timestamp>/ directory in Custom Data
-            self.emit_progress("Creating backup directory...", InstallPhase::Installing);
-            let backup_data_dir = self.xplane_root.join("Custom Data").join("Backup_Data");
-            fs::create_dir_all(&backup_data_dir)?;
+        // Read old cycle/airac from target_dir (the data being backed up)
+        let (old_cycle, old_airac) = self
+            .read_navdata_info(&self.target_dir)
+            .map(|(_, c, a)| (c, a))
+            .unwrap_or((None, None));
 
-            // Keep only the latest backup for the same navdata provider.
-            // Do not touch backups from other providers.
-            self.cleanup_navdata_backups_for_provider(&backup_data_dir, &provider_name);
+        let custom_data_dir = self.xplane_root.join("Custom Data");
+        let backup_data_dir = custom_data_dir.join("Backup_Data");
 
+        // Step 2: Where the old entries go while the new ones are moved in
+        self.emit_progress("Creating backup directory...", InstallPhase::Installing);
+        let holding_dir = if backup_navdata {
             // Use timestamp to create unique backup folder name
             let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
             let sanitized_provider = sanitize_folder_name(&provider_name);
-            let backup_folder_name = format!("{}_{}", sanitized_provider, timestamp);
-            let backup_subdir = backup_data_dir.join(&backup_folder_name);
+            backup_data_dir.join(format!("{}_{}", sanitized_provider, timestamp))
+        } else {
+            self.xplane_root.join(format!(
+                "{}{}_previous",
+                crate::app_dirs::ATOMIC_INSTALL_TEMP_PREFIX,
+                Uuid::new_v4()
+            ))
+        };
+        fs::create_dir_all(&holding_dir).stage(AtomicInstallStage::Backup)?;
 
-            fs::create_dir_all(&backup_subdir)?;
+        logger::log_info(
+            &format!("Navdata holding directory created: {:?}", holding_dir),
+            Some("atomic_installer"),
+        );
 
-            logger::log_info(
-                &format!("Backup directory created: {:?}", backup_subdir),
-                Some("atomic_installer"),
-            );
+        // Step 3: Collect all files using walkdir (OPTIMIZED: single pass, no extra stat)
+        // Skip SHA-256 checksum calculation - fs::rename is atomic, checksums are redundant
+        self.emit_progress("Scanning files to backup...", InstallPhase::Installing);
+        let mut backup_entries: Vec<BackupFileEntry> = Vec::new();
+        let mut set_aside_plan: Vec<(PathBuf, PathBuf)> = Vec::new();
 
-            // Step 4: Collect all files using walkdir (OPTIMIZED: single pass, no extra stat)
-            // Skip SHA-256 checksum calculation - fs::rename is atomic, checksums are redundant
-            self.emit_progress("Scanning files to backup...", InstallPhase::Installing);
-            let custom_data_dir = self.xplane_root.join("Custom Data");
-            let mut backup_entries: Vec<BackupFileEntry> = Vec::new();
-
-            for entry_name in &backup_scope_entries {
-                let old_path = self.target_dir.join(entry_name);
-                if old_path.exists() {
-                    // Use walkdir for efficient enumeration (DirEntry::file_type() uses cached stat)
-                    for entry in WalkDir::new(&old_path)
-                        .into_iter()
-                        .filter_map(|e| e.ok())
-                        .filter(|e| e.file_type().is_file())
-                    {
-                        // Get size from walkdir's metadata (single stat call)
-                        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
-                        // Relative path from Custom Data (not target_dir) for consistent restore
-                        let relative_path = entry
-                            .path()
-                            .strip_prefix(&custom_data_dir)
-                            .unwrap_or(entry.path())
-                            .to_string_lossy()
-                            .replace('\\', "/");
-
-                        backup_entries.push(BackupFileEntry {
-                            relative_path,
-                            checksum: String::new(), // SKIP checksum - fs::rename is atomic
-                            size,
-                        });
-                    }
-                }
+        for entry_name in &backup_scope_entries {
+            let old_path = self.target_dir.join(entry_name);
+            if !old_path.exists() {
+                continue;
             }
 
-            logger::log_info(
-                &format!(
-                    "Found {} files to backup (checksum skipped)",
-                    backup_entries.len()
-                ),
-                Some("atomic_installer"),
-            );
-
-            // Step 5: Move files to backup directory (OPTIMIZED: directory-level rename)
-            self.emit_progress("Moving files to backup...", InstallPhase::Installing);
-            for entry_name in &backup_scope_entries {
-                let old_path = self.target_dir.join(entry_name);
-                if old_path.exists() {
-                    // Compute relative path from Custom Data for consistent backup structure
-                    let relative_entry = old_path
+            // Relative path from Custom Data (not target_dir) for consistent restore
+            let relative_entry = old_path
+                .strip_prefix(&custom_data_dir)
+                .unwrap_or(Path::new(entry_name))
+                .to_path_buf();
+            set_aside_plan.push((old_path.clone(), holding_dir.join(&relative_entry)));
+
+            if backup_navdata {
+                // Use walkdir for efficient enumeration (DirEntry::file_type() uses cached stat)
+                for entry in WalkDir::new(&old_path)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.file_type().is_file())
+                {
+                    // Get size from walkdir's metadata (single stat call)
+                    let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                    let relative_path = entry
+                        .path()
                         .strip_prefix(&custom_data_dir)
-                        .unwrap_or(Path::new(entry_name));
-                    let backup_path = backup_subdir.join(relative_entry);
-                    logger::log_info(
-                        &format!("Moving to backup: {:?}", entry_name),
-                        Some("atomic_installer"),
-                    );
-                    move_directory(&old_path, &backup_path)?;
+                        .unwrap_or(entry.path())
+                        .to_string_lossy()
+                        .replace('\\', "/");
+
+                    backup_entries.push(BackupFileEntry {
+                        relative_path,
+                        checksum: String::new(), // SKIP checksum - fs::rename is atomic
+                        size,
+                    });
                 }
             }
+        }
 
-            // Step 6: Fast verify (OPTIMIZED: single fs::metadata() call per file)
+        logger::log_info(
+            &format!(
+                "Found {} entries to move aside ({} files backed up, checksum skipped)",
+                set_aside_plan.len(),
+                backup_entries.len()
+            ),
+            Some("atomic_installer"),
+        );
+
+        // Step 4: Move old entries aside (OPTIMIZED: directory-level rename)
+        self.emit_progress("Moving files to backup...", InstallPhase::Installing);
+        let set_aside = match set_aside_entries(&set_aside_plan) {
+            Ok(set_aside) => set_aside,
+            Err(e) => {
+                remove_holding_dir(&holding_dir);
+                return Err(e).stage(AtomicInstallStage::Backup);
+            }
+        };
+
+        // Step 5: Fast verify (OPTIMIZED: single fs::metadata() call per file), then
+        // write verification.json so scan_navdata_backups can list and restore it
+        if backup_navdata {
             self.emit_progress("Verifying backup (fast)...", InstallPhase::Installing);
-            verify_backup_fast(&backup_subdir, &backup_entries)?;
+            let written = verify_backup_fast(&holding_dir, &backup_entries).and_then(|()| {
+                let verification = NavdataBackupVerification {
+                    provider_name: provider_name.clone(),
+                    cycle: old_cycle,
+                    airac: old_airac,
+                    replaced_by_cycle: new_cycle,
+                    backup_time: chrono::Utc::now().to_rfc3339(),
+                    files: backup_entries.clone(),
+                    file_count: backup_entries.len(),
+                };
+                let verification_json = serde_json::to_string_pretty(&verification)
+                    .context("Failed to serialize verification data")?;
+                fs::write(holding_dir.join("verification.json"), verification_json)
+                    .context("Failed to write verification.json")
+            });
+            if let Err(e) = written {
+                restore_set_aside(&set_aside);
+                remove_holding_dir(&holding_dir);
+                return Err(e).stage(AtomicInstallStage::Backup);
+            }
 
             logger::log_info(
                 &format!(
-                    "Fast verification passed: {} files verified",
+                    "Backup verification written: {} files backed up",
                     backup_entries.len()
                 ),
                 Some("atomic_installer"),
             );
+        }
 
-            // Step 7: Write verification.json
-            let verification = NavdataBackupVerification {
-                provider_name: provider_name.clone(),
-                cycle: old_cycle,
-                airac: old_airac,
-                backup_time: chrono::Utc::now().to_rfc3339(),
-                files: backup_entries.clone(),
-                file_count: backup_entries.len(),
-            };
-
-            let verification_path = backup_subdir.join("verification.json");
-            let verification_json = serde_json::to_string_pretty(&verification)
-                .context("Failed to serialize verification data")?;
-            fs::write(&verification_path, verification_json)
-                .context("Failed to write verification.json")?;
-
-            logger::log_info(
-                &format!(
-                    "Backup verification written: {} files backed up",
-                    backup_entries.len()
-                ),
+        // Step 6: Merge new navdata to target
+        self.emit_progress("Installing new navdata...", InstallPhase::Installing);
+        if let Err(e) = merge_directories(&self.temp_dir, &self.target_dir) {
+            logger::log_error(
+                &format!("Navdata swap failed, restoring previous navdata: {}", e),
                 Some("atomic_installer"),
             );
+            // Everything under the new entry names is new content: the old
+            // entries with those names were moved aside above
+            for entry_name in &new_entries {
+                let partial = self.target_dir.join(entry_name);
+                let removed = if partial.is_dir() {
+                    fs::remove_dir_all(&partial)
+                } else {
+                    fs::remove_file(&partial)
+                };
+                if let Err(remove_err) = removed {
+                    if remove_err.kind() != std::io::ErrorKind::NotFound {
+                        logger::log_error(
+                            &format!(
+                                "Failed to remove partial navdata {:?}: {}",
+                                partial, remove_err
+                            ),
+                            Some("atomic_installer"),
+                        );
+                    }
+                }
+            }
+            restore_set_aside(&set_aside);
+            remove_holding_dir(&holding_dir);
+            return Err(e).stage(AtomicInstallStage::Swap);
+        }
+
+        // Step 7: Keep only the latest backup for the same navdata provider.
+        // Do not touch backups from other providers.
+        if backup_navdata {
+            self.cleanup_navdata_backups_for_provider(
+                &backup_data_dir,
+                &provider_name,
+                Some(&holding_dir),
+            );
         } else {
-            // No backup: delete old files that will be replaced by new ones
             logger::log_info(
-                "Navdata backup disabled by user, deleting old files directly",
+                "Navdata backup disabled by user, deleting old files",
                 Some("atomic_installer"),
             );
+            remove_holding_dir(&holding_dir);
+            self.cleanup_navdata_backups_for_provider(&backup_data_dir, &provider_name, None);
+        }
+
+        logger::log_info(
+            &format!("Navdata install completed: {:?}", self.target_dir),
+            Some("atomic_installer"),
+        );
+
+        // Explicitly cleanup temp directory
+        self.cleanup_temp_dir();
 
-            // Also delete existing backups for the same provider
-            let backup_data_dir = self.xplane_root.join("Custom Data").join("Backup_Data");
-            self.cleanup_navdata_backups_for_provider(&backup_data_dir, &provider_name);
+        Ok(())
+    }
+
+    /// Scenario 5: Replace individual entries of a shared folder (Lua scripts and
+    /// their companion files in FlyWithLua/Scripts)
+    /// 1. Stage the entries in temp (done by the caller)
+    /// 2. Verify the first entry (the script itself) was staged
+    /// 3. Move existing entries with the same names aside
+    /// 4. Move staged entries into target, restoring the old ones on failure
+    /// 5. Delete the set-aside entries
+    ///
+    /// With `merge_existing_dirs`, staged folders are merged onto a copy of the
+    /// existing folder first, so files only present in the old folder survive.
+    pub fn install_entries(
+        &mut self,
+        entries: &[PathBuf],
+        merge_existing_dirs: bool,
+    ) -> Result<()> {
+        logger::log_info(
+            &format!(
+                "Atomic install: Replacing {} entries in {:?}",
+                entries.len(),
+                self.target_dir
+            ),
+            Some("atomic_installer"),
+        );
 
-            for entry_name in &backup_scope_entries {
-                let old_path = self.target_dir.join(entry_name);
-                if old_path.exists() {
-                    if old_path.is_dir() {
-                        fs::remove_dir_all(&old_path)?;
+        let staged: Vec<&PathBuf> = entries
+            .iter()
+            .filter(|entry| {
+                let present = self.temp_dir.join(entry).exists();
+                if !present {
+                    logger::log_info(
+                        &format!("Entry not found in staged files, skipping: {:?}", entry),
+                        Some("atomic_installer"),
+                    );
+                }
+                present
+            })
+            .collect();
+        match entries.first() {
+            Some(primary) if self.temp_dir.join(primary).is_file() => {}
+            Some(primary) => {
+                return Err(anyhow::anyhow!(
+                    "{:?} is missing from the staged files",
+                    primary
+                ))
+                .stage(AtomicInstallStage::Verifying);
+            }
+            None => {
+                return Err(anyhow::anyhow!("Nothing to install"))
+                    .stage(AtomicInstallStage::Verifying);
+            }
+        }
+
+        if merge_existing_dirs {
+            self.emit_progress("Merging with existing files...", InstallPhase::Installing);
+            for entry in &staged {
+                let existing = self.target_dir.join(entry);
+                let staged_path = self.temp_dir.join(entry);
+                if !(existing.is_dir() && staged_path.is_dir()) {
+                    continue;
+                }
+                let merged = self.temp_dir.join(format!(".merged_{}", Uuid::new_v4()));
+                copy_directory_recursive(&existing, &merged)
+                    .and_then(|()| merge_directories(&staged_path, &merged))
+                    .and_then(|()| {
+                        fs::remove_dir_all(&staged_path)?;
+                        fs::rename(&merged, &staged_path)?;
+                        Ok(())
+                    })
+                    .stage(AtomicInstallStage::Staging)?;
+            }
+        }
+
+        let holding_dir = self.xplane_root.join(format!(
+            "{}{}_previous",
+            crate::app_dirs::ATOMIC_INSTALL_TEMP_PREFIX,
+            Uuid::new_v4()
+        ));
+        let plan: Vec<(PathBuf, PathBuf)> = staged
+            .iter()
+            .map(|entry| (self.target_dir.join(entry), holding_dir.join(entry)))
+            .filter(|(existing, _)| fs::symlink_metadata(existing).is_ok())
+            .collect();
+
+        self.emit_progress("Moving existing files aside...", InstallPhase::Installing);
+        let set_aside = match set_aside_entries(&plan) {
+            Ok(set_aside) => set_aside,
+            Err(e) => {
+                remove_holding_dir(&holding_dir);
+                return Err(e).stage(AtomicInstallStage::Backup);
+            }
+        };
+
+        self.emit_progress(
+            "Moving new files to target directory...",
+            InstallPhase::Installing,
+        );
+        let mut moved_in: Vec<PathBuf> = Vec::new();
+        for entry in &staged {
+            let target_path = self.target_dir.join(entry);
+            if let Err(e) = move_directory(&self.temp_dir.join(entry), &target_path) {
+                logger::log_error(
+                    &format!("Moving staged entry failed, rolling back: {}", e),
+                    Some("atomic_installer"),
+                );
+                moved_in.push(target_path);
+                for partial in &moved_in {
+                    let _ = if partial.is_dir() {
+                        fs::remove_dir_all(partial)
                     } else {
-                        fs::remove_file(&old_path)?;
-                    }
+                        fs::remove_file(partial)
+                    };
                 }
+                restore_set_aside(&set_aside);
+                remove_holding_dir(&holding_dir);
+                return Err(e).stage(AtomicInstallStage::Swap);
             }
+            moved_in.push(target_path);
         }
 
-        // Step 8: Merge new navdata to target
-        self.emit_progress("Installing new navdata...", InstallPhase::Installing);
-        merge_directories(&self.temp_dir, &self.target_dir)?;
+        remove_holding_dir(&holding_dir);
 
         logger::log_info(
-            &format!("Navdata clean install completed: {:?}", self.target_dir),
+            &format!("Entry installation completed: {:?}", self.target_dir),
             Some("atomic_installer"),
         );
 
@@ -640,9 +881,14 @@ impl AtomicInstaller {
                 .unwrap_or(false)
     }
 
-    /// Remove existing navdata backups for the same provider only.
+    /// Remove existing navdata backups for the same provider only, except `keep`.
     /// This must not delete backups for other navdata providers.
-    fn cleanup_navdata_backups_for_provider(&self, backup_data_dir: &Path, provider_name: &str) {
+    fn cleanup_navdata_backups_for_provider(
+        &self,
+        backup_data_dir: &Path,
+        provider_name: &str,
+        keep: Option<&Path>,
+    ) {
         if !backup_data_dir.exists() {
             return;
         }
@@ -664,7 +910,7 @@ impl AtomicInstaller {
 
         for entry in entries.filter_map(|e| e.ok()) {
             let backup_path = entry.path();
-            if !backup_path.is_dir() {
+            if !backup_path.is_dir() || keep == Some(backup_path.as_path()) {
                 continue;
             }
 
@@ -1170,6 +1416,23 @@ fn copy_symlink(src: &Path, dst: &Path, base_dir: &Path, depth: usize) -> Result
     Ok(())
 }
 
+/// Report staged directories whose name differs only by case from an existing
+/// directory in the target, before they are merged into it
+fn check_case_variants(staging: &Path, target: &Path) {
+    let tracker = CaseVariantTracker::new(target);
+    for entry in WalkDir::new(staging)
+        .follow_links(false)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+    {
+        if let Ok(relative) = entry.path().strip_prefix(staging) {
+            tracker.check_dir(relative);
+        }
+    }
+}
+
 /// Merge directories: move all files from src to dst, overwriting existing files
 /// TOCTOU-safe: Uses atomic operations and handles race conditions gracefully
 fn merge_directories(src: &Path, dst: &Path) -> Result<()> {
@@ -1311,6 +1574,68 @@ fn move_directory(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Staged navdata must contain at least one file before anything is replaced
+fn verify_staged_navdata(staging: &Path) -> Result<()> {
+    let has_file = WalkDir::new(staging)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .any(|e| e.file_type().is_file());
+    if !has_file {
+        anyhow::bail!("Temp directory is empty, nothing to install");
+    }
+    Ok(())
+}
+
+/// Move each `(original, held)` pair aside. If one move fails, the entries already
+/// moved are put back before the error is returned.
+fn set_aside_entries(plan: &[(PathBuf, PathBuf)]) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (original, held) in plan {
+        logger::log_info(
+            &format!("Moving aside: {:?} -> {:?}", original, held),
+            Some("atomic_installer"),
+        );
+        if let Err(e) = move_directory(original, held) {
+            // A failed cross-device move may have copied part of the entry
+            moved.push((original.clone(), held.clone()));
+            restore_set_aside(&moved);
+            return Err(e).context(format!("Failed to move {:?} aside", original));
+        }
+        moved.push((original.clone(), held.clone()));
+    }
+    Ok(moved)
+}
+
+/// Move set-aside entries back to their original location, newest first
+fn restore_set_aside(moved: &[(PathBuf, PathBuf)]) {
+    for (original, held) in moved.iter().rev() {
+        if fs::symlink_metadata(held).is_err() {
+            continue;
+        }
+        if let Err(e) = move_directory(held, original) {
+            logger::log_error(
+                &format!(
+                    "CRITICAL: Failed to restore {:?} from {:?}: {}",
+                    original, held, e
+                ),
+                Some("atomic_installer"),
+            );
+        }
+    }
+}
+
+/// Best-effort removal of a holding or backup directory
+fn remove_holding_dir(dir: &Path) {
+    if let Err(e) = fs::remove_dir_all(dir) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            logger::log_error(
+                &format!("Failed to remove holding directory {:?}: {}", dir, e),
+                Some("atomic_installer"),
+            );
+        }
+    }
+}
+
 /// Fast verification: check file existence and size only (OPTIMIZED: single stat per file)
 /// Skips re-computing checksums since fs::rename is atomic on same filesystem
 fn verify_backup_fast(backup_dir: &Path, entries: &[BackupFileEntry]) -> Result<()> {
@@ -1426,3 +1751,47 @@ fn check_disk_space(path: &Path) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_stage_keeps_innermost_stage() {
+        let result: Result<()> = Err(anyhow::anyhow!("disk full"))
+            .stage(AtomicInstallStage::Backup)
+            .context("Navdata install failed")
+            .stage(AtomicInstallStage::Swap);
+        let error = result.unwrap_err();
+        assert_eq!(failed_stage(&error), Some(AtomicInstallStage::Backup));
+        assert_eq!(failed_stage(&anyhow::anyhow!("plain")), None);
+    }
+
+    #[test]
+    fn set_aside_entries_can_be_restored() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("Custom Data");
+        let holding = dir.path().join("holding");
+        fs::create_dir_all(target.join("CIFP")).unwrap();
+        fs::write(target.join("CIFP").join("KJFK.dat"), "old").unwrap();
+        fs::write(target.join("earth_nav.dat"), "old").unwrap();
+
+        let plan: Vec<(PathBuf, PathBuf)> = ["CIFP", "earth_nav.dat"]
+            .iter()
+            .map(|name| (target.join(name), holding.join(name)))
+            .collect();
+        let moved = set_aside_entries(&plan).unwrap();
+        assert!(!target.join("CIFP").exists());
+        assert!(holding.join("earth_nav.dat").is_file());
+
+        restore_set_aside(&moved);
+        assert_eq!(
+            fs::read_to_string(target.join("CIFP").join("KJFK.dat")).unwrap(),
+            "old"
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("earth_nav.dat")).unwrap(),
+            "old"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Asking the frontend whether to reinstall addons that are already installed
+//! unchanged
+//!
+//! `install_addons` emits an `install-duplicates-detected` event and waits for
+//! `respond_duplicate_install` with the same request id. Without an answer
+//! before the timeout, every task is reinstalled as if nothing was detected.
+
+use crate::models::{DuplicateInstallPrompt, IdenticalInstall};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::oneshot;
+
+pub const DUPLICATE_PROMPT_EVENT: &str = "install-duplicates-detected";
+
+const PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+type PendingPrompts = HashMap<String, oneshot::Sender<Vec<String>>>;
+
+#[derive(Default)]
+pub struct DuplicatePrompts {
+    pending: Mutex<PendingPrompts>,
+}
+
+impl DuplicatePrompts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask which identical tasks to skip. Returns their ids, or nothing when
+    /// the prompt times out or can't be shown.
+    pub async fn ask(
+        &self,
+        app_handle: &AppHandle,
+        tasks: Vec<IdenticalInstall>,
+    ) -> HashSet<String> {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let offered: HashSet<String> = tasks.iter().map(|task| task.task_id.clone()).collect();
+        let (sender, receiver) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(request_id.clone(), sender);
+        }
+
+        let prompt = DuplicateInstallPrompt {
+            request_id: request_id.clone(),
+            tasks,
+            timeout_secs: PROMPT_TIMEOUT.as_secs(),
+        };
+        let answer = if app_handle.emit(DUPLICATE_PROMPT_EVENT, prompt).is_ok() {
+            tokio::time::timeout(PROMPT_TIMEOUT, receiver).await.ok()
+        } else {
+            None
+        };
+
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&request_id);
+        }
+        match answer {
+            Some(Ok(skip_task_ids)) => skip_task_ids
+                .into_iter()
+                .filter(|task_id| offered.contains(task_id))
+                .collect(),
+            _ => {
+                crate::logger::log_info(
+                    "No answer to the identical install prompt, reinstalling",
+                    Some("installation"),
+                );
+                HashSet::new()
+            }
+        }
+    }
+
+    /// Answer a pending prompt. Returns false when it already timed out.
+    pub fn respond(&self, request_id: &str, skip_task_ids: Vec<String>) -> bool {
+        let sender = self
+            .pending
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(request_id));
+        sender.is_some_and(|sender| sender.send(skip_task_ids).is_ok())
+    }
+}
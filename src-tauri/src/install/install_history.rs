@@ -0,0 +1,667 @@
+//! History of addons installed through XFast
+//!
+//! Every successful install task is recorded per X-Plane installation. A newer
+//! install to the same target marks the earlier rows as replaced, and
+//! uninstalling keeps the row with status "uninstalled" so the history stays
+//! complete.
+//!
+//! Rows also keep a signature of the source archive (name, size and
+//! modification time, never its content) and of the target right after the
+//! install. Dropping the same archive again while the target still
+//! has that signature is reported as an identical install, which can be
+//! skipped instead of extracted again.
+
+use crate::activity;
+use crate::database::entities::install_history;
+use crate::error::{ApiError, ApiResult};
+use crate::logger;
+use crate::models::{
+    AddonType, IdenticalInstall, InstallHistoryEntry, InstallHistoryStatus, InstallTask,
+    UninstallFromHistoryResult,
+};
+use crate::path_utils;
+use crate::scenery_packs_manager::SceneryPacksManager;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const STATUS_INSTALLED: &str = "installed";
+const STATUS_REPLACED: &str = "replaced";
+const STATUS_UNINSTALLED: &str = "uninstalled";
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn status_from_str(status: &str) -> InstallHistoryStatus {
+    match status {
+        STATUS_REPLACED => InstallHistoryStatus::Replaced,
+        STATUS_UNINSTALLED => InstallHistoryStatus::Uninstalled,
+        _ => InstallHistoryStatus::Installed,
+    }
+}
+
+fn addon_type_to_string(addon_type: &AddonType) -> String {
+    serde_json::to_value(addon_type)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", addon_type))
+}
+
+fn addon_type_from_str(addon_type: &str) -> Option<AddonType> {
+    serde_json::from_value(serde_json::Value::String(addon_type.to_string())).ok()
+}
+
+fn to_entry(row: install_history::Model) -> Option<InstallHistoryEntry> {
+    Some(InstallHistoryEntry {
+        id: row.id,
+        addon_type: addon_type_from_str(&row.addon_type)?,
+        display_name: row.display_name,
+        target_path: row.target_path,
+        source_name: row.source_name,
+        installed_at: row.installed_at,
+        bytes: row.bytes.map(|b| b.max(0) as u64),
+        manifest_path: row.manifest_path,
+        status: status_from_str(&row.status),
+        uninstalled_at: row.uninstalled_at,
+    })
+}
+
+/// What gets recorded about an installed task
+pub struct InstalledAddon {
+    pub addon_type: AddonType,
+    pub display_name: String,
+    pub target_path: String,
+    /// File name of the archive or folder it was installed from
+    pub source_name: Option<String>,
+    /// See [`source_signatures`]
+    pub source_signature: Option<String>,
+}
+
+impl InstalledAddon {
+    pub fn from_task(task: &InstallTask) -> Self {
+        let source = task
+            .original_input_path
+            .as_deref()
+            .unwrap_or(&task.source_path);
+        Self {
+            addon_type: task.addon_type.clone(),
+            display_name: task.display_name.clone(),
+            target_path: task.target_path.clone(),
+            source_name: Path::new(source)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            source_signature: None,
+        }
+    }
+}
+
+/// File count, total size and newest modification time of an installed
+/// target. Cheap enough to compare on every install; any edit, added or
+/// removed file changes it.
+pub fn target_signature(target: &Path) -> Option<String> {
+    let metadata = fs::metadata(target).ok()?;
+    let mut file_count = 0u64;
+    let mut total_bytes = 0u64;
+    let mut newest_modified = 0u128;
+    let files: Box<dyn Iterator<Item = fs::Metadata>> = if metadata.is_file() {
+        Box::new(std::iter::once(metadata))
+    } else {
+        Box::new(
+            walkdir::WalkDir::new(target)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_file())
+                .filter_map(|entry| entry.metadata().ok()),
+        )
+    };
+    for metadata in files {
+        file_count += 1;
+        total_bytes = total_bytes.saturating_add(metadata.len());
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+        newest_modified = newest_modified.max(modified);
+    }
+    Some(format!(
+        "{}:{}:{}",
+        file_count, total_bytes, newest_modified
+    ))
+}
+
+/// Signature of each task's source, keyed by task id: the archive's file name
+/// with its [`target_signature`], or the [`target_signature`] of a source
+/// folder. Only metadata is read, so this stays cheap for archives of many
+/// gigabytes. Must run before sources are deleted.
+pub fn source_signatures(tasks: &[InstallTask]) -> HashMap<String, String> {
+    let mut by_source: HashMap<&str, Option<String>> = HashMap::new();
+    let mut signatures = HashMap::new();
+    for task in tasks {
+        let signature = by_source
+            .entry(task.source_path.as_str())
+            .or_insert_with(|| {
+                let source = Path::new(&task.source_path);
+                let signature = target_signature(source)?;
+                if source.is_file() {
+                    let name = source.file_name()?.to_string_lossy();
+                    Some(format!("archive:{}:{}", name, signature))
+                } else {
+                    Some(format!("folder:{}", signature))
+                }
+            })
+            .clone();
+        if let Some(signature) = signature {
+            signatures.insert(task.id.clone(), signature);
+        }
+    }
+    signatures
+}
+
+/// Tasks whose source was installed to the same target before, with the
+/// target unchanged since
+pub async fn find_identical_installs(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    tasks: &[InstallTask],
+    source_signatures: &HashMap<String, String>,
+) -> ApiResult<Vec<IdenticalInstall>> {
+    if source_signatures.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rows = install_history::Entity::find()
+        .filter(
+            install_history::Column::XplanePath.eq(path_utils::normalize_xplane_key(xplane_path)),
+        )
+        .filter(install_history::Column::Status.eq(STATUS_INSTALLED))
+        .filter(install_history::Column::SourceSignature.is_not_null())
+        .filter(install_history::Column::TargetSignature.is_not_null())
+        .all(conn)
+        .await?;
+    let by_target: HashMap<String, install_history::Model> = rows
+        .into_iter()
+        .map(|row| {
+            (
+                path_utils::normalize_xplane_key(Path::new(&row.target_path)),
+                row,
+            )
+        })
+        .collect();
+
+    let candidates: Vec<(IdenticalInstall, String)> = tasks
+        .iter()
+        .filter_map(|task| {
+            let signature = source_signatures.get(&task.id)?;
+            let row = by_target.get(&path_utils::normalize_xplane_key(Path::new(
+                &task.target_path,
+            )))?;
+            if row.source_signature.as_ref() != Some(signature) {
+                return None;
+            }
+            Some((
+                IdenticalInstall {
+                    task_id: task.id.clone(),
+                    display_name: task.display_name.clone(),
+                    target_path: task.target_path.clone(),
+                    installed_at: row.installed_at,
+                },
+                row.target_signature.clone()?,
+            ))
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        candidates
+            .into_iter()
+            .filter(|(install, recorded)| {
+                target_signature(Path::new(&install.target_path)).as_ref() == Some(recorded)
+            })
+            .map(|(install, _)| install)
+            .collect()
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))
+}
+
+/// Size on disk of an installed target, which is a file for Lua scripts
+fn installed_size(target: &Path) -> Option<u64> {
+    let metadata = fs::metadata(target).ok()?;
+    if metadata.is_file() {
+        return Some(metadata.len());
+    }
+    Some(crate::management_index::addon_folder_usage(target).0)
+}
+
+/// Record successfully installed addons. Errors are logged rather than
+/// returned so a history problem never fails the install itself.
+pub async fn record_installs(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    addons: Vec<InstalledAddon>,
+) {
+    if addons.is_empty() {
+        return;
+    }
+
+    let xplane_key = path_utils::normalize_xplane_key(xplane_path);
+    let sized = tokio::task::spawn_blocking(move || {
+        addons
+            .into_iter()
+            .map(|addon| {
+                let target = Path::new(&addon.target_path);
+                let bytes = installed_size(target);
+                let signature = target_signature(target);
+                (addon, bytes, signature)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await;
+    let sized = match sized {
+        Ok(sized) => sized,
+        Err(e) => {
+            logger::log_error(
+                &format!("Failed to measure installed addons: {}", e),
+                Some("install_history"),
+            );
+            return;
+        }
+    };
+
+    let installed_at = now_secs();
+    for (addon, bytes, signature) in sized {
+        if let Err(e) =
+            record_install_for_key(conn, &xplane_key, &addon, bytes, signature, installed_at).await
+        {
+            logger::log_error(
+                &format!(
+                    "Failed to record install history for {}: {}",
+                    addon.display_name, e
+                ),
+                Some("install_history"),
+            );
+        }
+    }
+}
+
+async fn record_install_for_key(
+    conn: &DatabaseConnection,
+    xplane_key: &str,
+    addon: &InstalledAddon,
+    bytes: Option<u64>,
+    target_signature: Option<String>,
+    installed_at: i64,
+) -> ApiResult<i64> {
+    // Earlier installs to the same target were overwritten by this one
+    let target_key = path_utils::normalize_xplane_key(Path::new(&addon.target_path));
+    let active = install_history::Entity::find()
+        .filter(install_history::Column::XplanePath.eq(xplane_key))
+        .filter(install_history::Column::Status.eq(STATUS_INSTALLED))
+        .all(conn)
+        .await?;
+    let replaced: Vec<i64> = active
+        .into_iter()
+        .filter(|row| path_utils::normalize_xplane_key(Path::new(&row.target_path)) == target_key)
+        .map(|row| row.id)
+        .collect();
+    if !replaced.is_empty() {
+        install_history::Entity::update_many()
+            .col_expr(
+                install_history::Column::Status,
+                sea_orm::sea_query::Expr::value(STATUS_REPLACED),
+            )
+            .filter(install_history::Column::Id.is_in(replaced))
+            .exec(conn)
+            .await?;
+    }
+
+    let row = install_history::ActiveModel {
+        id: ActiveValue::NotSet,
+        xplane_path: Set(xplane_key.to_string()),
+        addon_type: Set(addon_type_to_string(&addon.addon_type)),
+        display_name: Set(addon.display_name.clone()),
+        target_path: Set(addon.target_path.clone()),
+        source_name: Set(addon.source_name.clone()),
+        installed_at: Set(installed_at),
+        bytes: Set(bytes.map(|b| b as i64)),
+        // No install writes a checksum manifest yet
+        manifest_path: Set(None),
+        status: Set(STATUS_INSTALLED.to_string()),
+        uninstalled_at: Set(None),
+        source_signature: Set(addon.source_signature.clone()),
+        target_signature: Set(target_signature),
+    };
+    let result = install_history::Entity::insert(row).exec(conn).await?;
+
+    Ok(result.last_insert_id)
+}
+
+/// Newest `limit` history entries, newest first, optionally of one addon type
+pub async fn load_history(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    limit: u64,
+    addon_type: Option<&AddonType>,
+) -> ApiResult<Vec<InstallHistoryEntry>> {
+    load_history_for_key(
+        conn,
+        &path_utils::normalize_xplane_key(xplane_path),
+        limit,
+        addon_type,
+    )
+    .await
+}
+
+async fn load_history_for_key(
+    conn: &DatabaseConnection,
+    xplane_key: &str,
+    limit: u64,
+    addon_type: Option<&AddonType>,
+) -> ApiResult<Vec<InstallHistoryEntry>> {
+    let mut query =
+        install_history::Entity::find().filter(install_history::Column::XplanePath.eq(xplane_key));
+    if let Some(addon_type) = addon_type {
+        query =
+            query.filter(install_history::Column::AddonType.eq(addon_type_to_string(addon_type)));
+    }
+    let rows = query
+        .order_by_desc(install_history::Column::InstalledAt)
+        .order_by_desc(install_history::Column::Id)
+        .limit(limit)
+        .all(conn)
+        .await?;
+
+    Ok(rows.into_iter().filter_map(to_entry).collect())
+}
+
+/// Delete what a history entry installed and mark it uninstalled. A target
+/// that is already gone only updates the history and scenery index.
+pub async fn uninstall(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    install_id: i64,
+) -> ApiResult<UninstallFromHistoryResult> {
+    let xplane_key = path_utils::normalize_xplane_key(xplane_path);
+    let row = install_history::Entity::find_by_id(install_id)
+        .one(conn)
+        .await?
+        .filter(|row| row.xplane_path == xplane_key)
+        .ok_or_else(|| ApiError::not_found(format!("Install #{} not found", install_id)))?;
+
+    match status_from_str(&row.status) {
+        InstallHistoryStatus::Installed => {}
+        InstallHistoryStatus::Replaced => {
+            return Err(ApiError::conflict(format!(
+                "'{}' was replaced by a newer install; uninstall that one instead",
+                row.display_name
+            )));
+        }
+        InstallHistoryStatus::Uninstalled => {
+            return Err(ApiError::conflict(format!(
+                "'{}' is already uninstalled",
+                row.display_name
+            )));
+        }
+    }
+    let addon_type = addon_type_from_str(&row.addon_type).ok_or_else(|| {
+        ApiError::corrupted(format!("Unknown addon type in history: {}", row.addon_type))
+    })?;
+
+    let target = PathBuf::from(&row.target_path);
+    let already_removed = fs::symlink_metadata(&target).is_err();
+    if !already_removed {
+        remove_target(xplane_path, &target).await?;
+    }
+
+    if matches!(addon_type, AddonType::Scenery | AddonType::SceneryLibrary) {
+        if let Some(folder_name) = target.file_name().map(|n| n.to_string_lossy().into_owned()) {
+            forget_scenery(conn, xplane_path, &folder_name).await;
+        }
+    }
+
+    let mut active: install_history::ActiveModel = row.into();
+    active.status = Set(STATUS_UNINSTALLED.to_string());
+    active.uninstalled_at = Set(Some(now_secs()));
+    let row = active.update(conn).await?;
+
+    activity::log_activity(
+        conn,
+        "uninstall",
+        &addon_type_to_string(&addon_type).to_lowercase(),
+        &row.display_name,
+        already_removed.then(|| "target was already removed".to_string()),
+        true,
+    )
+    .await;
+
+    let entry = to_entry(row)
+        .ok_or_else(|| ApiError::internal("Failed to read updated install history entry"))?;
+    Ok(UninstallFromHistoryResult {
+        entry,
+        already_removed,
+    })
+}
+
+/// Delete an installed folder or file, refusing anything outside the addon
+/// folders and the addon folders themselves
+async fn remove_target(xplane_path: &Path, target: &Path) -> ApiResult<()> {
+    let validated = path_utils::validate_xplane_target_path(xplane_path, target)
+        .map_err(|e| ApiError::security_violation(format!("Invalid install target: {}", e)))?;
+    let is_addon_root = path_utils::TRUSTED_XPLANE_ROOTS.iter().any(|name| {
+        xplane_path
+            .join(name)
+            .canonicalize()
+            .is_ok_and(|root| root == validated)
+    });
+    if is_addon_root {
+        return Err(ApiError::validation(format!(
+            "{} is an X-Plane folder and can't be uninstalled",
+            target.display()
+        )));
+    }
+
+    let target = target.to_path_buf();
+    tokio::task::spawn_blocking(move || -> ApiResult<()> {
+        let metadata = fs::symlink_metadata(&target)?;
+        if metadata.is_dir() {
+            crate::installer::remove_dir_all_robust(&target).map_err(ApiError::from)
+        } else {
+            // Files, and links which are removed without following them
+            fs::remove_file(&target)
+                .or_else(|_| fs::remove_dir(&target))
+                .map_err(ApiError::from)
+        }
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+async fn forget_scenery(conn: &DatabaseConnection, xplane_path: &Path, folder_name: &str) {
+    if let Err(e) = crate::scenery_index::remove_scenery_entry(
+        conn,
+        &xplane_path.to_string_lossy(),
+        folder_name,
+    )
+    .await
+    {
+        logger::log_error(
+            &format!("Failed to remove {} from scenery index: {}", folder_name, e),
+            Some("install_history"),
+        );
+    }
+
+    // Only this package's line goes; rewriting from the index would reorder the rest
+    let packs_manager = SceneryPacksManager::new(xplane_path, conn.clone());
+    if let Err(e) = packs_manager.remove_entry(folder_name).await {
+        logger::log_error(
+            &format!(
+                "Failed to update scenery_packs.ini after uninstalling {}: {}",
+                folder_name, e
+            ),
+            Some("install_history"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+
+    const XPLANE_KEY: &str = "/xplane";
+
+    fn addon(addon_type: AddonType, target: &str) -> InstalledAddon {
+        InstalledAddon {
+            addon_type,
+            display_name: "Addon".to_string(),
+            target_path: target.to_string(),
+            source_name: Some("Addon.zip".to_string()),
+            source_signature: None,
+        }
+    }
+
+    fn task(id: &str, source: &Path, target: &Path) -> InstallTask {
+        InstallTask {
+            id: id.to_string(),
+            addon_type: AddonType::Scenery,
+            source_path: source.to_string_lossy().into_owned(),
+            original_input_path: None,
+            target_path: target.to_string_lossy().into_owned(),
+            display_name: "Ortho".to_string(),
+            conflict_exists: Some(true),
+            archive_internal_root: None,
+            extraction_chain: None,
+            should_overwrite: true,
+            allow_case_collision_rename: false,
+            allow_symlinks: false,
+            password: None,
+            estimated_size: None,
+            size_warning: None,
+            size_confirmed: false,
+            existing_navdata_info: None,
+            new_navdata_info: None,
+            navdata_downgrade_from: None,
+            allow_downgrade: false,
+            existing_version_info: None,
+            new_version_info: None,
+            backup_liveries: true,
+            backup_config_files: true,
+            config_file_patterns: vec![],
+            backup_navdata: true,
+            file_hashes: None,
+            enable_verification: true,
+            livery_aircraft_type: None,
+            livery_aircraft_found: false,
+            livery_target_candidates: Vec::new(),
+            target_aircraft_folder: None,
+            not_installable_reason: None,
+            flywithlua_installed: false,
+            companion_paths: vec![],
+            compatibility: crate::models::AddonCompatibility::Unknown,
+            updater_provider: crate::models::UpdaterProvider::None,
+            suggested_root_kind: crate::models::TargetRootKind::CustomScenery,
+            target_root_kind: crate::models::TargetRootKind::CustomScenery,
+            profile_identity: None,
+            install_profile: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn reinstalling_a_target_replaces_the_earlier_entry() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        let scenery = addon(AddonType::Scenery, "/xplane/Custom Scenery/KSEA");
+        let plugin = addon(AddonType::Plugin, "/xplane/Resources/plugins/Tool");
+        record_install_for_key(&conn, XPLANE_KEY, &scenery, Some(10), None, 100)
+            .await
+            .unwrap();
+        record_install_for_key(&conn, XPLANE_KEY, &plugin, None, None, 200)
+            .await
+            .unwrap();
+        record_install_for_key(&conn, XPLANE_KEY, &scenery, Some(20), None, 300)
+            .await
+            .unwrap();
+
+        let history = load_history_for_key(&conn, XPLANE_KEY, 10, None)
+            .await
+            .unwrap();
+        let states: Vec<(i64, InstallHistoryStatus)> = history
+            .iter()
+            .map(|entry| (entry.installed_at, entry.status))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (300, InstallHistoryStatus::Installed),
+                (200, InstallHistoryStatus::Installed),
+                (100, InstallHistoryStatus::Replaced),
+            ]
+        );
+        assert_eq!(history[0].source_name.as_deref(), Some("Addon.zip"));
+
+        let plugins = load_history_for_key(&conn, XPLANE_KEY, 10, Some(&AddonType::Plugin))
+            .await
+            .unwrap();
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].addon_type, AddonType::Plugin);
+    }
+
+    #[tokio::test]
+    async fn unchanged_targets_of_the_same_archive_are_identical() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("Ortho.zip");
+        fs::write(&archive, b"archive bytes").unwrap();
+        let target = dir.path().join("Custom Scenery/Ortho");
+        fs::create_dir_all(target.join("textures")).unwrap();
+        fs::write(target.join("textures/a.dds"), b"texture").unwrap();
+
+        let tasks = vec![task("ortho", &archive, &target)];
+        let signatures = source_signatures(&tasks);
+        let mut installed = InstalledAddon::from_task(&tasks[0]);
+        installed.source_signature = signatures.get("ortho").cloned();
+        record_installs(&conn, dir.path(), vec![installed]).await;
+
+        let identical = find_identical_installs(&conn, dir.path(), &tasks, &signatures)
+            .await
+            .unwrap();
+        assert_eq!(identical.len(), 1);
+        assert_eq!(identical[0].task_id, "ortho");
+
+        // Another archive for the same target is a real update
+        let other = dir.path().join("Ortho v2.zip");
+        fs::write(&other, b"a newer archive").unwrap();
+        let updated = vec![task("ortho", &other, &target)];
+        let updated_signatures = source_signatures(&updated);
+        assert!(
+            find_identical_installs(&conn, dir.path(), &updated, &updated_signatures)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // So is the same archive over a target the user changed
+        fs::write(target.join("textures/b.dds"), b"edited").unwrap();
+        assert!(
+            find_identical_installs(&conn, dir.path(), &tasks, &signatures)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
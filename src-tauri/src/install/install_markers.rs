@@ -0,0 +1,654 @@
+//! Markers of installs in progress, used to clean up after installs a crash or
+//! a killed process interrupted
+//!
+//! A marker is written when a task starts changing its target and removed once
+//! the task has an outcome: installed, failed, or cleaned up after a skip or a
+//! cancel. Markers still there at the next launch belong to interrupted tasks.
+//! [`reconcile`] then removes the staging folders those installs left and the
+//! partial targets they created, and put back targets the install had moved
+//! aside. Targets that look complete, that existed before the install, or that
+//! hold navdata or Lua scripts are kept and listed by `get_orphaned_installs`,
+//! for the user to keep or delete, as are set-aside copies that couldn't be
+//! put back.
+
+use crate::app_dirs::ATOMIC_INSTALL_TEMP_PREFIX;
+use crate::database::entities::install_markers;
+use crate::database::DatabaseState;
+use crate::error::{ApiError, ApiResult};
+use crate::installer::remove_dir_all_robust;
+use crate::logger;
+use crate::models::{
+    AddonType, InstallTask, OrphanedInstall, OrphanedInstallAction, OrphanedInstallReason,
+};
+use crate::staging;
+use anyhow::Result;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Event sent with the installs left for review when the startup check finds any
+pub const ORPHANED_INSTALLS_EVENT: &str = "orphaned-installs-found";
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Serialized name of a unit enum variant, as stored in the table
+fn variant_name<T: Serialize + std::fmt::Debug>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", value))
+}
+
+fn parse_variant<T: DeserializeOwned>(name: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+}
+
+/// Record that `task` is about to change its target. Returns the marker id, or
+/// None when it couldn't be written; the install goes ahead either way.
+pub async fn begin(
+    conn: &DatabaseConnection,
+    task: &InstallTask,
+    xplane_path: &str,
+) -> Option<i64> {
+    let marker = install_markers::ActiveModel {
+        id: ActiveValue::NotSet,
+        task_id: Set(task.id.clone()),
+        display_name: Set(task.display_name.clone()),
+        addon_type: Set(variant_name(&task.addon_type)),
+        target_path: Set(task.target_path.clone()),
+        xplane_path: Set(xplane_path.to_string()),
+        target_existed: Set(fs::symlink_metadata(&task.target_path).is_ok()),
+        started_at: Set(now_secs()),
+        review_reason: Set(None),
+    };
+    match install_markers::Entity::insert(marker).exec(conn).await {
+        Ok(result) => Some(result.last_insert_id),
+        Err(e) => {
+            logger::log_error(
+                &format!(
+                    "Failed to record install marker for {}: {}",
+                    task.display_name, e
+                ),
+                Some("install_markers"),
+            );
+            None
+        }
+    }
+}
+
+/// Remove the marker of a task that has an outcome
+pub async fn finish(conn: &DatabaseConnection, marker: Option<i64>) {
+    let Some(id) = marker else {
+        return;
+    };
+    if let Err(e) = install_markers::Entity::delete_by_id(id).exec(conn).await {
+        logger::log_error(
+            &format!("Failed to remove install marker {}: {}", id, e),
+            Some("install_markers"),
+        );
+    }
+}
+
+/// Whether some entry within `depth` levels below `dir` satisfies `matches`
+fn has_entry(dir: &Path, depth: usize, matches: impl Fn(&walkdir::DirEntry) -> bool) -> bool {
+    walkdir::WalkDir::new(dir)
+        .min_depth(1)
+        .max_depth(depth)
+        .into_iter()
+        .flatten()
+        .any(|entry| matches(&entry))
+}
+
+fn has_extension(entry: &walkdir::DirEntry, extension: &str) -> bool {
+    entry.file_type().is_file()
+        && entry
+            .path()
+            .extension()
+            .and_then(OsStr::to_str)
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+fn is_named(entry: &walkdir::DirEntry, name: &str) -> bool {
+    entry
+        .file_name()
+        .to_str()
+        .is_some_and(|file_name| file_name.eq_ignore_ascii_case(name))
+}
+
+/// Whether `target` has the files an addon of `addon_type` can't work without
+fn looks_complete(addon_type: &AddonType, target: &Path) -> bool {
+    match addon_type {
+        AddonType::Aircraft => has_entry(target, 1, |entry| has_extension(entry, "acf")),
+        AddonType::Plugin => has_entry(target, 3, |entry| has_extension(entry, "xpl")),
+        AddonType::Scenery => has_entry(target, 1, |entry| {
+            is_named(entry, "Earth nav data") || is_named(entry, "library.txt")
+        }),
+        AddonType::SceneryLibrary => has_entry(target, 1, |entry| is_named(entry, "library.txt")),
+        AddonType::Livery => has_entry(target, 1, |_| true),
+        AddonType::Navdata | AddonType::LuaScript => true,
+    }
+}
+
+fn remove_target(target: &Path) -> Result<()> {
+    if fs::symlink_metadata(target)?.is_dir() {
+        remove_dir_all_robust(target)
+    } else {
+        fs::remove_file(target)?;
+        Ok(())
+    }
+}
+
+fn modified_secs(path: &Path) -> Option<i64> {
+    fs::symlink_metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_secs() as i64)
+}
+
+/// Where an interrupted task may have moved the previous version of its target:
+/// a `<name>.backup_<uuid>` sibling left by a clean install, or the entry in a
+/// `..._previous` holding folder the task created in `xplane_path`. The newest
+/// candidate comes first.
+fn set_aside_candidates(marker: &install_markers::Model) -> Vec<PathBuf> {
+    let target = Path::new(&marker.target_path);
+    let Some(name) = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+    else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<(i64, PathBuf)> = Vec::new();
+    if let Some(Ok(read_dir)) = target.parent().map(fs::read_dir) {
+        let backup_prefix = format!("{}.backup_", name);
+        for entry in read_dir.flatten() {
+            if entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(&backup_prefix)
+            {
+                let path = entry.path();
+                candidates.push((modified_secs(&path).unwrap_or(0), path));
+            }
+        }
+    }
+    if let Ok(read_dir) = fs::read_dir(&marker.xplane_path) {
+        for entry in read_dir.flatten() {
+            let holding_name = entry.file_name().to_string_lossy().to_string();
+            if !holding_name.starts_with(ATOMIC_INSTALL_TEMP_PREFIX)
+                || !holding_name.ends_with("_previous")
+            {
+                continue;
+            }
+            let created = modified_secs(&entry.path()).unwrap_or(0);
+            let path = entry.path().join(&name);
+            if created >= marker.started_at && fs::symlink_metadata(&path).is_ok() {
+                candidates.push((created, path));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.cmp(&a.0));
+    candidates.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Put back the previous version of a target the interrupted task had moved
+/// aside. Returns the set-aside copy to review when it couldn't be moved back,
+/// or None when it was restored or there is none.
+fn restore_set_aside(marker: &install_markers::Model) -> Option<PathBuf> {
+    let target = Path::new(&marker.target_path);
+    let set_aside = set_aside_candidates(marker).into_iter().next()?;
+    match fs::rename(&set_aside, target) {
+        Ok(()) => {
+            logger::log_info(
+                &format!(
+                    "Restored the previous version of {} from {:?}",
+                    marker.display_name, set_aside
+                ),
+                Some("install_markers"),
+            );
+            // An emptied holding folder has nothing left to keep
+            if let Some(holding_dir) = set_aside.parent() {
+                if holding_dir != target.parent().unwrap_or(holding_dir) {
+                    let _ = fs::remove_dir(holding_dir);
+                }
+            }
+            None
+        }
+        Err(e) => {
+            logger::log_error(
+                &format!(
+                    "Failed to restore the previous version of {} from {:?}: {}",
+                    marker.display_name, set_aside, e
+                ),
+                Some("install_markers"),
+            );
+            Some(set_aside)
+        }
+    }
+}
+
+/// Remove the partial target of an interrupted task, or say why it was kept
+/// and which path is left for review. Returns None when nothing is left to
+/// review.
+fn settle(marker: &install_markers::Model) -> Option<(OrphanedInstallReason, String)> {
+    let target = Path::new(&marker.target_path);
+    if fs::symlink_metadata(target).is_err() {
+        if !marker.target_existed {
+            return None;
+        }
+        return restore_set_aside(marker).map(|set_aside| {
+            (
+                OrphanedInstallReason::SetAside,
+                set_aside.to_string_lossy().to_string(),
+            )
+        });
+    }
+    settle_existing(marker, target).map(|reason| (reason, marker.target_path.clone()))
+}
+
+fn settle_existing(
+    marker: &install_markers::Model,
+    target: &Path,
+) -> Option<OrphanedInstallReason> {
+    let addon_type = match parse_variant::<AddonType>(&marker.addon_type) {
+        Some(AddonType::Navdata | AddonType::LuaScript) | None => {
+            return Some(OrphanedInstallReason::KeptInPlace)
+        }
+        Some(addon_type) => addon_type,
+    };
+    if marker.target_existed {
+        return Some(OrphanedInstallReason::TargetExisted);
+    }
+    if looks_complete(&addon_type, target) {
+        return Some(OrphanedInstallReason::LooksComplete);
+    }
+    match remove_target(target) {
+        Ok(()) => {
+            logger::log_info(
+                &format!(
+                    "Removed partial install of {}: {}",
+                    marker.display_name, marker.target_path
+                ),
+                Some("install_markers"),
+            );
+            None
+        }
+        Err(e) => {
+            logger::log_error(
+                &format!(
+                    "Failed to remove partial install of {}: {}",
+                    marker.display_name, e
+                ),
+                Some("install_markers"),
+            );
+            Some(OrphanedInstallReason::CleanupFailed)
+        }
+    }
+}
+
+/// Remove the staging folders installs left in `xplane_path` and in the
+/// configured staging folder, counting only folders last changed between
+/// `since` and `before`. Navdata holding folders (`..._previous`) are kept: a
+/// swap that stopped half-way may have the only copy of the old files there.
+fn remove_leftover_staging(xplane_path: &Path, since: i64, before: i64) -> usize {
+    let mut roots: Vec<(PathBuf, String)> = vec![(
+        xplane_path.to_path_buf(),
+        ATOMIC_INSTALL_TEMP_PREFIX.to_string(),
+    )];
+    if let Some(configured) = staging::configured_root() {
+        roots.push((configured, format!("{}install_", staging::STAGING_PREFIX)));
+    }
+
+    let mut removed = 0;
+    for (root, prefix) in roots {
+        let Ok(read_dir) = fs::read_dir(&root) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(&prefix)
+                || name.ends_with("_previous")
+                || !entry.file_type().is_ok_and(|ft| ft.is_dir())
+            {
+                continue;
+            }
+            let modified = modified_secs(&entry.path());
+            if !modified.is_some_and(|modified| modified >= since && modified < before) {
+                continue;
+            }
+            match remove_dir_all_robust(&entry.path()) {
+                Ok(()) => removed += 1,
+                Err(e) => logger::log_error(
+                    &format!(
+                        "Failed to remove leftover staging folder {:?}: {}",
+                        entry.path(),
+                        e
+                    ),
+                    Some("install_markers"),
+                ),
+            }
+        }
+    }
+    removed
+}
+
+/// Clean up after the tasks whose markers were written before `launched_at`
+/// and not looked at yet. Returns every install left for review.
+pub async fn reconcile(
+    conn: &DatabaseConnection,
+    launched_at: i64,
+) -> ApiResult<Vec<OrphanedInstall>> {
+    let markers = install_markers::Entity::find()
+        .filter(install_markers::Column::StartedAt.lt(launched_at))
+        .filter(install_markers::Column::ReviewReason.is_null())
+        .all(conn)
+        .await?;
+    if !markers.is_empty() {
+        logger::log_info(
+            &format!("Found {} interrupted install(s)", markers.len()),
+            Some("install_markers"),
+        );
+
+        let mut earliest_start: HashMap<&str, i64> = HashMap::new();
+        for marker in &markers {
+            let since = earliest_start
+                .entry(marker.xplane_path.as_str())
+                .or_insert(marker.started_at);
+            *since = (*since).min(marker.started_at);
+        }
+        let removed: usize = earliest_start
+            .iter()
+            .map(|(xplane_path, since)| {
+                remove_leftover_staging(Path::new(xplane_path), *since, launched_at)
+            })
+            .sum();
+        if removed > 0 {
+            logger::log_info(
+                &format!("Removed {} leftover staging folder(s)", removed),
+                Some("install_markers"),
+            );
+        }
+
+        for marker in markers {
+            match settle(&marker) {
+                None => {
+                    install_markers::Entity::delete_by_id(marker.id)
+                        .exec(conn)
+                        .await?;
+                }
+                Some((reason, review_path)) => {
+                    logger::log_info(
+                        &format!(
+                            "Interrupted install of {} left for review ({:?}): {}",
+                            marker.display_name, reason, review_path
+                        ),
+                        Some("install_markers"),
+                    );
+                    let mut active: install_markers::ActiveModel = marker.into();
+                    active.target_path = Set(review_path);
+                    active.review_reason = Set(Some(variant_name(&reason)));
+                    active.update(conn).await?;
+                }
+            }
+        }
+    }
+    list_orphaned(conn).await
+}
+
+fn to_orphaned(row: install_markers::Model) -> Option<OrphanedInstall> {
+    Some(OrphanedInstall {
+        reason: parse_variant(row.review_reason.as_deref()?)?,
+        addon_type: parse_variant(&row.addon_type)?,
+        id: row.id,
+        task_id: row.task_id,
+        display_name: row.display_name,
+        target_path: row.target_path,
+        started_at: row.started_at,
+    })
+}
+
+/// Interrupted installs left for review, oldest first
+pub async fn list_orphaned(conn: &DatabaseConnection) -> ApiResult<Vec<OrphanedInstall>> {
+    Ok(install_markers::Entity::find()
+        .filter(install_markers::Column::ReviewReason.is_not_null())
+        .order_by_asc(install_markers::Column::StartedAt)
+        .order_by_asc(install_markers::Column::Id)
+        .all(conn)
+        .await?
+        .into_iter()
+        .filter_map(to_orphaned)
+        .collect())
+}
+
+/// Keep or delete the target of an interrupted install and forget it. Returns
+/// whether the install was listed.
+pub async fn resolve(
+    conn: &DatabaseConnection,
+    id: i64,
+    action: OrphanedInstallAction,
+) -> ApiResult<bool> {
+    let Some(row) = install_markers::Entity::find_by_id(id).one(conn).await? else {
+        return Ok(false);
+    };
+    if action == OrphanedInstallAction::Delete {
+        // The target of a navdata install is the whole Custom Data folder
+        if parse_variant::<AddonType>(&row.addon_type) == Some(AddonType::Navdata) {
+            return Err(ApiError::validation(
+                "Navdata installs can't be deleted, their target is the Custom Data folder",
+            ));
+        }
+        let target = Path::new(&row.target_path);
+        if fs::symlink_metadata(target).is_ok() {
+            remove_target(target)?;
+            logger::log_info(
+                &format!(
+                    "Deleted interrupted install of {}: {}",
+                    row.display_name, row.target_path
+                ),
+                Some("install_markers"),
+            );
+        }
+    }
+    install_markers::Entity::delete_by_id(id).exec(conn).await?;
+    Ok(true)
+}
+
+/// Clean up after installs an earlier session didn't finish, in the background.
+/// Installs left for review are announced with [`ORPHANED_INSTALLS_EVENT`].
+pub fn init(app_handle: AppHandle) {
+    let launched_at = now_secs();
+    tauri::async_runtime::spawn(async move {
+        let db = app_handle.state::<DatabaseState>().get();
+        match reconcile(&db, launched_at).await {
+            Ok(orphaned) if !orphaned.is_empty() => {
+                let _ = app_handle.emit(ORPHANED_INSTALLS_EVENT, &orphaned);
+            }
+            Ok(_) => {}
+            Err(e) => logger::log_error(
+                &format!("Failed to check for interrupted installs: {}", e),
+                Some("install_markers"),
+            ),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+
+    async fn insert_marker(
+        conn: &DatabaseConnection,
+        addon_type: AddonType,
+        target: &Path,
+        xplane: &Path,
+        target_existed: bool,
+        started_at: i64,
+    ) -> i64 {
+        install_markers::Entity::insert(install_markers::ActiveModel {
+            id: ActiveValue::NotSet,
+            task_id: Set("task".to_string()),
+            display_name: Set(target.file_name().unwrap().to_string_lossy().to_string()),
+            addon_type: Set(variant_name(&addon_type)),
+            target_path: Set(target.to_string_lossy().to_string()),
+            xplane_path: Set(xplane.to_string_lossy().to_string()),
+            target_existed: Set(target_existed),
+            started_at: Set(started_at),
+            review_reason: Set(None),
+        })
+        .exec(conn)
+        .await
+        .unwrap()
+        .last_insert_id
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_removes_partial_targets_and_keeps_the_rest() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+        let xplane = tempfile::tempdir().unwrap();
+        let aircraft = xplane.path().join("Aircraft");
+        let started_at = now_secs() - 60;
+
+        let partial = aircraft.join("Partial");
+        fs::create_dir_all(partial.join("objects")).unwrap();
+        fs::write(partial.join("objects").join("wing.obj"), "obj").unwrap();
+        insert_marker(
+            &conn,
+            AddonType::Aircraft,
+            &partial,
+            xplane.path(),
+            false,
+            started_at,
+        )
+        .await;
+
+        let complete = aircraft.join("Complete");
+        fs::create_dir_all(&complete).unwrap();
+        fs::write(complete.join("plane.acf"), "acf").unwrap();
+        let complete_id = insert_marker(
+            &conn,
+            AddonType::Aircraft,
+            &complete,
+            xplane.path(),
+            false,
+            started_at,
+        )
+        .await;
+
+        let updated = aircraft.join("Updated");
+        fs::create_dir_all(&updated).unwrap();
+        let updated_id = insert_marker(
+            &conn,
+            AddonType::Aircraft,
+            &updated,
+            xplane.path(),
+            true,
+            started_at,
+        )
+        .await;
+
+        let staging_dir = xplane
+            .path()
+            .join(format!("{}abc", ATOMIC_INSTALL_TEMP_PREFIX));
+        let holding_dir = xplane
+            .path()
+            .join(format!("{}abc_previous", ATOMIC_INSTALL_TEMP_PREFIX));
+        fs::create_dir_all(&staging_dir).unwrap();
+        fs::create_dir_all(&holding_dir).unwrap();
+
+        let orphaned = reconcile(&conn, now_secs() + 60).await.unwrap();
+        assert!(!partial.exists());
+        assert!(!staging_dir.exists());
+        assert!(holding_dir.exists());
+        let reasons: Vec<(i64, OrphanedInstallReason)> = orphaned
+            .iter()
+            .map(|install| (install.id, install.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                (complete_id, OrphanedInstallReason::LooksComplete),
+                (updated_id, OrphanedInstallReason::TargetExisted),
+            ]
+        );
+
+        assert!(resolve(&conn, complete_id, OrphanedInstallAction::Delete)
+            .await
+            .unwrap());
+        assert!(!complete.exists());
+        assert!(resolve(&conn, updated_id, OrphanedInstallAction::Keep)
+            .await
+            .unwrap());
+        assert!(updated.exists());
+        assert!(!resolve(&conn, updated_id, OrphanedInstallAction::Keep)
+            .await
+            .unwrap());
+        assert!(list_orphaned(&conn).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_puts_back_targets_moved_aside() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+        let xplane = tempfile::tempdir().unwrap();
+        let started_at = now_secs() - 60;
+
+        // A clean install renames the old target to a sibling
+        let aircraft = xplane.path().join("Aircraft");
+        let cleaned = aircraft.join("Cleaned");
+        let backup = aircraft.join("Cleaned.backup_abc");
+        fs::create_dir_all(&backup).unwrap();
+        fs::write(backup.join("plane.acf"), "acf").unwrap();
+        insert_marker(
+            &conn,
+            AddonType::Aircraft,
+            &cleaned,
+            xplane.path(),
+            true,
+            started_at,
+        )
+        .await;
+
+        // A Lua install moves the old script into a holding folder
+        let scripts = xplane.path().join("Resources/plugins/FlyWithLua/Scripts");
+        fs::create_dir_all(&scripts).unwrap();
+        let script = scripts.join("helper.lua");
+        let holding_dir = xplane
+            .path()
+            .join(format!("{}abc_previous", ATOMIC_INSTALL_TEMP_PREFIX));
+        fs::create_dir_all(&holding_dir).unwrap();
+        fs::write(holding_dir.join("helper.lua"), "-- old").unwrap();
+        insert_marker(
+            &conn,
+            AddonType::LuaScript,
+            &script,
+            xplane.path(),
+            true,
+            started_at,
+        )
+        .await;
+
+        let orphaned = reconcile(&conn, now_secs() + 60).await.unwrap();
+        assert!(orphaned.is_empty());
+        assert!(cleaned.join("plane.acf").exists());
+        assert!(!backup.exists());
+        assert_eq!(fs::read_to_string(&script).unwrap(), "-- old");
+        assert!(!holding_dir.exists());
+    }
+}
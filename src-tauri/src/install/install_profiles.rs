@@ -0,0 +1,220 @@
+//! Options remembered per addon for repeat installs
+//!
+//! When an install task succeeds, its final options (overwrite, backups, the
+//! chosen livery aircraft and scenery root, the name installed under) are saved
+//! as the addon's install profile. Profiles are keyed by
+//! [`install_profile_identity`](crate::models::install_profile_identity), which
+//! doesn't depend on where the archive was saved or what it was called, so the
+//! next beta of the same aircraft finds the choices made for the last one.
+//! `analyze_addons` attaches the stored profile to each task for the UI to
+//! pre-fill.
+
+use crate::database::entities::install_profiles;
+use crate::error::{ApiError, ApiResult};
+use crate::logger;
+use crate::models::{AddonType, InstallProfile, InstallTask};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn addon_type_to_string(addon_type: &AddonType) -> String {
+    serde_json::to_value(addon_type)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", addon_type))
+}
+
+fn to_profile(row: install_profiles::Model) -> Option<InstallProfile> {
+    match serde_json::from_str::<InstallProfile>(&row.profile) {
+        Ok(profile) => Some(InstallProfile {
+            identity: row.identity,
+            updated_at: row.updated_at,
+            ..profile
+        }),
+        Err(e) => {
+            logger::log_error(
+                &format!(
+                    "Ignoring unreadable install profile {}: {}",
+                    row.identity, e
+                ),
+                Some("install_profiles"),
+            );
+            None
+        }
+    }
+}
+
+/// The options a task was installed with, or None when it has no identity
+pub fn profile_from_task(task: &InstallTask) -> Option<InstallProfile> {
+    Some(InstallProfile {
+        identity: task.profile_identity.clone()?,
+        display_name: task.display_name.clone(),
+        should_overwrite: task.should_overwrite,
+        backup_liveries: task.backup_liveries,
+        backup_config_files: task.backup_config_files,
+        backup_navdata: task.backup_navdata,
+        target_aircraft_folder: task.target_aircraft_folder.clone(),
+        target_root_kind: task.target_root_kind,
+        updated_at: 0,
+    })
+}
+
+/// Store `profile`, replacing the one saved earlier for the same addon
+pub async fn save_profile(
+    conn: &DatabaseConnection,
+    addon_type: &AddonType,
+    profile: &InstallProfile,
+) -> ApiResult<()> {
+    let json = serde_json::to_string(profile)
+        .map_err(|e| ApiError::internal(format!("Failed to serialize install profile: {}", e)))?;
+    let updated_at = now_secs();
+    let existing = install_profiles::Entity::find()
+        .filter(install_profiles::Column::Identity.eq(&profile.identity))
+        .one(conn)
+        .await?;
+    match existing {
+        Some(row) => {
+            let mut active: install_profiles::ActiveModel = row.into();
+            active.addon_type = Set(addon_type_to_string(addon_type));
+            active.profile = Set(json);
+            active.updated_at = Set(updated_at);
+            active.update(conn).await?;
+        }
+        None => {
+            install_profiles::Entity::insert(install_profiles::ActiveModel {
+                id: ActiveValue::NotSet,
+                identity: Set(profile.identity.clone()),
+                addon_type: Set(addon_type_to_string(addon_type)),
+                profile: Set(json),
+                updated_at: Set(updated_at),
+            })
+            .exec(conn)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Save the profiles of successfully installed tasks; failures are logged
+pub async fn save_profiles(conn: &DatabaseConnection, profiles: Vec<(AddonType, InstallProfile)>) {
+    for (addon_type, profile) in profiles {
+        if let Err(e) = save_profile(conn, &addon_type, &profile).await {
+            logger::log_error(
+                &format!(
+                    "Failed to save install profile for {}: {}",
+                    profile.display_name, e
+                ),
+                Some("install_profiles"),
+            );
+        }
+    }
+}
+
+pub async fn get_profile(
+    conn: &DatabaseConnection,
+    identity: &str,
+) -> ApiResult<Option<InstallProfile>> {
+    let row = install_profiles::Entity::find()
+        .filter(install_profiles::Column::Identity.eq(identity))
+        .one(conn)
+        .await?;
+    Ok(row.and_then(to_profile))
+}
+
+/// Forget the profile of an addon. Returns whether one was stored.
+pub async fn clear_profile(conn: &DatabaseConnection, identity: &str) -> ApiResult<bool> {
+    let result = install_profiles::Entity::delete_many()
+        .filter(install_profiles::Column::Identity.eq(identity))
+        .exec(conn)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Attach the stored profile of each task's addon to the task
+pub async fn attach_profiles(
+    conn: &DatabaseConnection,
+    tasks: &mut [InstallTask],
+) -> ApiResult<()> {
+    let identities: Vec<String> = tasks
+        .iter()
+        .filter_map(|task| task.profile_identity.clone())
+        .collect();
+    if identities.is_empty() {
+        return Ok(());
+    }
+    let profiles: HashMap<String, InstallProfile> = install_profiles::Entity::find()
+        .filter(install_profiles::Column::Identity.is_in(identities))
+        .all(conn)
+        .await?
+        .into_iter()
+        .filter_map(to_profile)
+        .map(|profile| (profile.identity.clone(), profile))
+        .collect();
+    for task in tasks.iter_mut() {
+        if let Some(identity) = &task.profile_identity {
+            task.install_profile = profiles.get(identity).cloned();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+    use crate::models::TargetRootKind;
+
+    fn profile(identity: &str, backup_liveries: bool) -> InstallProfile {
+        InstallProfile {
+            identity: identity.to_string(),
+            display_name: "B737-800X Beta".to_string(),
+            should_overwrite: true,
+            backup_liveries,
+            backup_config_files: true,
+            backup_navdata: true,
+            target_aircraft_folder: None,
+            target_root_kind: TargetRootKind::CustomScenery,
+            updated_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_replaces_and_clear_removes_profile() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        save_profile(&conn, &AddonType::Aircraft, &profile("zibo", true))
+            .await
+            .unwrap();
+        save_profile(&conn, &AddonType::Aircraft, &profile("zibo", false))
+            .await
+            .unwrap();
+
+        let stored = get_profile(&conn, "zibo").await.unwrap().unwrap();
+        assert!(!stored.backup_liveries);
+        assert!(stored.should_overwrite);
+        assert_eq!(stored.display_name, "B737-800X Beta");
+        assert!(stored.updated_at > 0);
+        assert_eq!(
+            install_profiles::Entity::find()
+                .all(&conn)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert!(clear_profile(&conn, "zibo").await.unwrap());
+        assert!(!clear_profile(&conn, "zibo").await.unwrap());
+        assert!(get_profile(&conn, "zibo").await.unwrap().is_none());
+    }
+}
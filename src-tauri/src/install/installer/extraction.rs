@@ -1,4 +1,5 @@
 use super::*;
+use crate::models::{ExtractionFailureDetail, ExtractionFailureStage};
 
 impl Installer {
     /// Copy a directory recursively with progress tracking
@@ -30,6 +31,7 @@ impl Installer {
         target: &Path,
         ctx: Option<&ProgressContext>,
     ) -> Result<()> {
+        let case_tracker = CaseVariantTracker::new(target);
         if !target.exists() {
             fs::create_dir_all(target)?;
         }
@@ -48,17 +50,28 @@ impl Installer {
                     .path()
                     .strip_prefix(source)
                     .context("Failed to strip prefix")?;
+                case_tracker.check_dir(relative);
                 let target_path = target.join(relative);
                 fs::create_dir_all(&target_path)?;
             }
         }
 
+        // Files hard-linked inside a staging folder are linked again after the copy
+        let hard_links = match ctx {
+            Some(_) => links::staged_hard_links(source, &entries),
+            None => Vec::new(),
+        };
+        let hard_linked: HashSet<PathBuf> = hard_links
+            .iter()
+            .map(|link| source.join(&link.relative))
+            .collect();
+
         // Copy files in parallel using rayon
         use rayon::prelude::*;
 
         entries
             .par_iter()
-            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| entry.file_type().is_file() && !hard_linked.contains(entry.path()))
             .try_for_each(|entry| -> Result<()> {
                 let source_path = entry.path();
                 let relative = source_path.strip_prefix(source)?;
@@ -90,6 +103,25 @@ impl Installer {
                 Ok(())
             })?;
 
+        // Links extracted into a staging folder follow the task's link policy
+        if let Some(ctx) = ctx {
+            let mut archive_links = Vec::new();
+            for entry in entries
+                .iter()
+                .filter(|entry| entry.file_type().is_symlink())
+            {
+                let relative = entry.path().strip_prefix(source)?.to_path_buf();
+                let link_target = fs::read_link(entry.path())?;
+                archive_links.push(links::ArchiveLink {
+                    relative,
+                    target: link_target.to_string_lossy().to_string(),
+                    kind: links::LinkKind::Symbolic,
+                });
+            }
+            archive_links.extend(hard_links);
+            links::apply_archive_links(target, &archive_links, ctx);
+        }
+
         Ok(())
     }
 
@@ -109,6 +141,16 @@ impl Installer {
         let prepared_archive = crate::archive_input::prepare_archive_for_read(archive, format)?;
         let read_archive = prepared_archive.read_path();
 
+        // Before anything is written, so a collision can't leave a half-extracted target
+        let case_renames = self.check_archive_case_collisions(
+            read_archive,
+            format,
+            target,
+            internal_root,
+            password,
+            ctx,
+        )?;
+
         crate::log_debug!(
             &format!(
                 "[TIMING] Archive extraction started: {} format",
@@ -125,6 +167,7 @@ impl Installer {
                 ctx,
                 password,
                 expected_hashes,
+                &case_renames,
             )?,
             crate::archive_input::ArchiveFormat::SevenZ => {
                 let primary_result = self.extract_7z_with_progress(
@@ -133,6 +176,7 @@ impl Installer {
                     internal_root,
                     ctx,
                     password,
+                    &case_renames,
                 );
                 if let Err(primary_err) = primary_result {
                     if Self::is_7z_checksum_error(&primary_err) {
@@ -149,6 +193,7 @@ impl Installer {
                             internal_root,
                             ctx,
                             password,
+                            &case_renames,
                         )
                         .map_err(|fallback_err| {
                             anyhow::anyhow!(
@@ -162,9 +207,14 @@ impl Installer {
                     }
                 }
             }
-            crate::archive_input::ArchiveFormat::Rar => {
-                self.extract_rar_with_progress(read_archive, target, internal_root, ctx, password)?
-            }
+            crate::archive_input::ArchiveFormat::Rar => self.extract_rar_with_progress(
+                read_archive,
+                target,
+                internal_root,
+                ctx,
+                password,
+                &case_renames,
+            )?,
         }
 
         crate::log_debug!(
@@ -190,10 +240,13 @@ impl Installer {
         ctx: &ProgressContext,
         password: Option<&str>,
         expected_hashes: Option<&HashMap<String, crate::models::FileHash>>,
+        case_renames: &CaseRenames,
     ) -> Result<()> {
         use std::sync::Arc;
         use zip::ZipArchive;
 
+        let case_tracker = CaseVariantTracker::new(target);
+
         // Open archive and collect file metadata
         let file = fs::File::open(archive_path)?;
         let mut archive = ZipArchive::new(file)?;
@@ -203,6 +256,7 @@ impl Installer {
 
         // Collect all file entries with their metadata
         let mut skipped_count = 0;
+        let mut link_entries = Vec::new();
         let entries: Vec<_> = (0..archive.len())
             .filter_map(|i| {
                 // Use by_index_raw to avoid triggering decryption errors when reading metadata
@@ -221,6 +275,8 @@ impl Installer {
                 let is_encrypted = file.encrypted();
                 let is_dir = file.is_dir();
                 let size = file.size();
+                let compressed_size = file.compressed_size();
+                let name = file.name().to_string();
 
                 let path = match file.enclosed_name() {
                     Some(p) => p.to_path_buf(),
@@ -293,7 +349,20 @@ impl Installer {
                     }
                 };
 
-                Some((i, relative_path, is_dir, is_encrypted, size))
+                let relative_path = apply_case_rename(relative_path, case_renames);
+                if file.unix_mode().is_some_and(links::is_symlink_mode) {
+                    link_entries.push((i, relative_path, is_encrypted));
+                    return None;
+                }
+                Some(ZipFileEntry {
+                    index: i,
+                    name,
+                    relative_path,
+                    is_dir,
+                    is_encrypted,
+                    size,
+                    compressed_size,
+                })
             })
             .collect();
 
@@ -310,11 +379,14 @@ impl Installer {
         let file = fs::File::open(archive_path)?;
         let archive = ZipArchive::new(file)?;
 
-        for (_index, relative_path, is_dir, _, _) in &entries {
-            if *is_dir {
-                let outpath = target.join(relative_path);
-                fs::create_dir_all(&outpath)?;
-            }
+        for entry in entries.iter().filter(|entry| entry.is_dir) {
+            case_tracker.check_dir(&entry.relative_path);
+            let outpath = target.join(&entry.relative_path);
+            fs::create_dir_all(&outpath).map_err(|e| {
+                entry
+                    .context(archive_path)
+                    .fail(ExtractionFailureStage::Write, e)
+            })?;
         }
 
         drop(archive);
@@ -327,10 +399,7 @@ impl Installer {
         let expected_hashes_arc = expected_hashes.map(|h| Arc::new(h.clone()));
 
         // Collect non-directory file entries for chunked processing
-        let file_entries: Vec<_> = entries
-            .iter()
-            .filter(|(_, _, is_dir, _, _)| !is_dir)
-            .collect();
+        let file_entries: Vec<_> = entries.iter().filter(|entry| !entry.is_dir).collect();
 
         // Calculate chunk size: aim for ~50-500 files per chunk to balance
         // ZipArchive reuse vs parallelism. Each chunk opens ZipArchive once.
@@ -346,26 +415,28 @@ impl Installer {
                 let file = fs::File::open(&archive_path)?;
                 let mut archive = ZipArchive::new(file)?;
 
-                for (index, relative_path, _, is_encrypted, _) in chunk {
+                for entry in chunk {
+                    let index = &entry.index;
+                    let relative_path = &entry.relative_path;
+                    let entry_ctx = entry.context(&archive_path);
+                    case_tracker.check_file(relative_path);
                     let outpath = target.join(relative_path);
 
                     if let Some(p) = outpath.parent() {
                         if !p.exists() {
-                            fs::create_dir_all(p)?;
+                            fs::create_dir_all(p)
+                                .map_err(|e| entry_ctx.fail(ExtractionFailureStage::Write, e))?;
                         }
                     }
 
                     // Extract file with or without password, computing CRC32 inline
-                    let (file_size, computed_crc) = if *is_encrypted {
+                    let (file_size, computed_crc) = if entry.is_encrypted {
                         if let Some(pwd) = password {
                             let pwd_bytes = pwd.as_bytes();
                             match archive.by_index_decrypt(*index, pwd_bytes) {
-                                Ok(mut file) => {
-                                    let mut outfile = fs::File::create(&outpath)?;
-                                    copy_file_with_crc32(&mut file, &mut outfile)?
-                                }
+                                Ok(mut file) => extract_zip_entry(&mut file, &outpath, &entry_ctx)?,
                                 Err(e) => {
-                                    return Err(e.into());
+                                    return Err(entry_ctx.fail(ExtractionFailureStage::Read, e));
                                 }
                             }
                         } else {
@@ -375,9 +446,10 @@ impl Installer {
                             ));
                         }
                     } else {
-                        let mut file = archive.by_index(*index)?;
-                        let mut outfile = fs::File::create(&outpath)?;
-                        copy_file_with_crc32(&mut file, &mut outfile)?
+                        let mut file = archive
+                            .by_index(*index)
+                            .map_err(|e| entry_ctx.fail(ExtractionFailureStage::Read, e))?;
+                        extract_zip_entry(&mut file, &outpath, &entry_ctx)?
                     };
 
                     // Inline CRC32 verification
@@ -387,6 +459,10 @@ impl Installer {
                             let computed_hex = format!("{:08x}", computed_crc);
                             if computed_hex == expected.hash {
                                 ctx.inline_verified_count.fetch_add(1, Ordering::SeqCst);
+                                ctx.inline_verified_files
+                                    .lock()
+                                    .unwrap()
+                                    .insert(rel_path_str);
                             }
                             // On mismatch: don't flag error, let normal verification handle it
                         }
@@ -415,6 +491,12 @@ impl Installer {
                 Ok(())
             })?;
 
+        // Links are read only now, so no file above was written through one
+        let mut archive = ZipArchive::new(fs::File::open(&archive_path)?)?;
+        let archive_links =
+            links::read_zip_links(&mut archive, link_entries, password.map(str::as_bytes))?;
+        links::apply_archive_links(&target, &archive_links, ctx);
+
         // After all chunks complete, check if ALL expected files were verified inline
         if let Some(ref hashes) = expected_hashes_arc {
             let verified = ctx.inline_verified_count.load(Ordering::SeqCst) as usize;
@@ -436,8 +518,8 @@ impl Installer {
         internal_root: Option<&str>,
         ctx: &ProgressContext,
         password: Option<&str>,
+        case_renames: &CaseRenames,
     ) -> Result<()> {
-        use sha2::{Digest, Sha256};
         let compute_inline_hashes = ctx.inline_hash_collection_enabled.load(Ordering::SeqCst);
 
         // Normalize internal_root for path matching
@@ -450,6 +532,8 @@ impl Installer {
             }
         });
 
+        let case_tracker = CaseVariantTracker::new(target);
+
         // Create target directory
         fs::create_dir_all(target)?;
 
@@ -469,91 +553,164 @@ impl Installer {
             );
         }
 
+        // Solid blocks decompress as one stream, so a single block can take minutes.
+        // Report bytes while entries stream out instead of only after each file.
+        let is_solid = archive_is_solid(reader.archive());
+        ctx.set_in_solid_block(is_solid);
+
         // Extract directly to target with progress reporting and inline SHA256
-        reader
-            .for_each_entries(|entry, entry_reader| {
-                let entry_name = entry.name().replace('\\', "/");
-
-                // Apply internal_root filter
-                let relative_path = if let Some(ref prefix) = internal_root_normalized {
-                    if entry_name.starts_with(prefix) {
-                        entry_name.strip_prefix(prefix).unwrap_or(&entry_name)
-                    } else if entry_name == prefix.trim_end_matches('/') {
-                        // Skip the root directory itself
-                        return Ok(true);
-                    } else {
-                        // Skip entries outside internal_root
-                        return Ok(true);
-                    }
+        let mut archive_links = Vec::new();
+        let mut entry_index = 0usize;
+        // The entry being read, for errors the reader raises between entries
+        let mut last_entry: Option<(String, usize, u64)> = None;
+        let mut failed_entry: Option<ExtractionFailureDetail> = None;
+        let result = reader.for_each_entries(|entry, entry_reader| {
+            let entry_name = entry.name().replace('\\', "/");
+            let index = entry_index;
+            entry_index += 1;
+
+            // Apply internal_root filter
+            let relative_path = if let Some(ref prefix) = internal_root_normalized {
+                if entry_name.starts_with(prefix) {
+                    entry_name.strip_prefix(prefix).unwrap_or(&entry_name)
                 } else {
-                    &entry_name
-                };
-
-                // Skip empty paths
-                if relative_path.is_empty() {
+                    // Skip entries outside internal_root (and the root directory itself).
+                    // Inside a solid block they still have to be decompressed, so drain
+                    // them here to keep progress events flowing.
+                    if is_solid && entry.has_stream() {
+                        let mut skipped = BlockProgressReader::new(entry_reader, |_| {
+                            ctx.emit_progress(None, InstallPhase::Installing);
+                        });
+                        std::io::copy(&mut skipped, &mut std::io::sink())?;
+                    }
                     return Ok(true);
                 }
+            } else {
+                &entry_name
+            };
 
-                // Sanitize path to prevent path traversal
-                let sanitized = match sanitize_path(Path::new(relative_path)) {
-                    Some(p) => p,
-                    None => return Ok(true), // Skip unsafe paths
-                };
+            // Skip empty paths
+            if relative_path.is_empty() {
+                return Ok(true);
+            }
 
-                let dest_path = target.join(&sanitized);
+            // Sanitize path to prevent path traversal
+            let sanitized = match sanitize_path(Path::new(relative_path)) {
+                Some(p) => p,
+                None => return Ok(true), // Skip unsafe paths
+            };
 
-                if entry.is_directory() {
-                    std::fs::create_dir_all(&dest_path)?;
-                } else {
-                    if let Some(parent) = dest_path.parent() {
-                        std::fs::create_dir_all(parent)?;
-                    }
+            let dest_path = target.join(&sanitized);
 
-                    let mut file = std::fs::File::create(&dest_path)?;
-                    if compute_inline_hashes {
-                        // Compute SHA256 inline while writing.
-                        let mut hasher = Sha256::new();
-                        let mut buffer = vec![0u8; IO_BUFFER_SIZE];
-                        loop {
-                            let bytes_read = entry_reader.read(&mut buffer)?;
-                            if bytes_read == 0 {
-                                break;
-                            }
-                            hasher.update(&buffer[..bytes_read]);
-                            std::io::Write::write_all(&mut file, &buffer[..bytes_read])?;
-                        }
-                        let hash = format!("{:x}", hasher.finalize());
-
-                        // Store computed hash for inline verification
-                        let relative_str = sanitized.to_string_lossy().replace('\\', "/");
-                        ctx.inline_hashes.lock().unwrap().insert(
-                            relative_str.clone(),
-                            crate::models::FileHash {
-                                path: relative_str,
-                                hash,
-                                algorithm: crate::models::HashAlgorithm::Sha256,
-                            },
-                        );
-                    } else {
-                        copy_file_optimized(entry_reader, &mut file)?;
+            if !entry.is_directory()
+                && entry.has_windows_attributes
+                && links::is_7z_symlink(entry.windows_attributes)
+            {
+                archive_links.push(links::ArchiveLink {
+                    relative: apply_case_rename(sanitized, case_renames),
+                    target: links::read_link_target(entry_reader)?,
+                    kind: links::LinkKind::Symbolic,
+                });
+            } else if entry.is_directory() {
+                case_tracker.check_dir(&sanitized);
+                std::fs::create_dir_all(&dest_path)?;
+            } else {
+                let sanitized = apply_case_rename(sanitized, case_renames);
+                let dest_path = target.join(&sanitized);
+                case_tracker.check_file(&sanitized);
+                last_entry = Some((entry_name.clone(), index, entry.size()));
+                let entry_ctx = EntryContext {
+                    archive,
+                    entry_path: &entry_name,
+                    index: Some(index),
+                    compressed_size: None,
+                    uncompressed_size: Some(entry.size()),
+                };
+                if let Some(parent) = dest_path.parent() {
+                    if let Err(e) = std::fs::create_dir_all(parent) {
+                        failed_entry = Some(entry_ctx.detail(ExtractionFailureStage::Write, &e));
+                        return Err(e.into());
                     }
+                }
 
-                    // Remove read-only attribute
-                    let _ = remove_readonly_attribute(&dest_path);
+                let file_name = sanitized
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+                let mut entry_reader = BlockProgressReader::new(entry_reader, |bytes| {
+                    ctx.add_bytes(bytes);
+                    ctx.emit_progress(Some(file_name.clone()), InstallPhase::Installing);
+                });
 
-                    // Report progress
-                    let file_size = entry.size();
-                    let file_name = sanitized
-                        .file_name()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("unknown")
-                        .to_string();
-                    ctx.add_bytes(file_size);
-                    ctx.emit_progress(Some(file_name), InstallPhase::Installing);
+                let file = match std::fs::File::create(&dest_path) {
+                    Ok(file) => file,
+                    Err(e) => {
+                        failed_entry = Some(entry_ctx.detail(ExtractionFailureStage::Write, &e));
+                        return Err(e.into());
+                    }
+                };
+                let mut file = WriteTracker::new(file);
+                let copied = if compute_inline_hashes {
+                    // Compute SHA256 inline while writing.
+                    copy_file_with_sha256(&mut entry_reader, &mut file).map(Some)
+                } else {
+                    copy_file_optimized(&mut entry_reader, &mut file).map(|_| None)
+                };
+                let inline_hash = match copied {
+                    Ok(hash) => hash,
+                    Err(e) => {
+                        failed_entry = Some(entry_ctx.detail(copy_stage(&e, file.failed), &e));
+                        return Err(e.into());
+                    }
+                };
+                if let Some(hash) = inline_hash {
+                    // Store computed hash for inline verification
+                    let relative_str = sanitized.to_string_lossy().replace('\\', "/");
+                    ctx.inline_hashes.lock().unwrap().insert(
+                        relative_str.clone(),
+                        crate::models::FileHash {
+                            path: relative_str,
+                            hash,
+                            algorithm: crate::models::HashAlgorithm::Sha256,
+                        },
+                    );
                 }
-                Ok(true)
-            })
-            .map_err(|e| anyhow::anyhow!("Failed to extract 7z: {}", e))?;
+
+                // Report the bytes of the last partial interval
+                entry_reader.finish();
+
+                // Remove read-only attribute
+                let _ = remove_readonly_attribute(&dest_path);
+            }
+            Ok(true)
+        });
+        ctx.set_in_solid_block(false);
+        if let Err(e) = result {
+            let detail = failed_entry.or_else(|| {
+                let (entry_path, index, size) = last_entry?;
+                let stage = if is_checksum_message(&e.to_string()) {
+                    ExtractionFailureStage::Crc
+                } else {
+                    ExtractionFailureStage::Read
+                };
+                Some(
+                    EntryContext {
+                        archive,
+                        entry_path: &entry_path,
+                        index: Some(index),
+                        compressed_size: None,
+                        uncompressed_size: Some(size),
+                    }
+                    .detail(stage, &e),
+                )
+            });
+            return Err(match detail {
+                Some(detail) => EntryExtractionError { detail }.into(),
+                None => anyhow::anyhow!("Failed to extract 7z: {}", e),
+            });
+        }
+        links::apply_archive_links(target, &archive_links, ctx);
 
         Ok(())
     }
@@ -573,10 +730,16 @@ impl Installer {
         internal_root: Option<&str>,
         ctx: &ProgressContext,
         password: Option<&str>,
+        case_renames: &CaseRenames,
     ) -> Result<()> {
-        let temp_dir = tempfile::Builder::new()
-            .prefix("xfastmanager_7z_fallback_")
-            .tempdir()
+        // The external tool extracts under the archive's own names
+        if !case_renames.is_empty() {
+            return Err(anyhow::anyhow!(
+                "External 7z fallback can't rename files that differ only by case"
+            ));
+        }
+
+        let temp_dir = crate::staging::temp_dir("7z_fallback")
             .context("Failed to create temp directory for 7z fallback")?;
 
         let extracted_root = temp_dir.path();
@@ -805,9 +968,11 @@ impl Installer {
         internal_root: Option<&str>,
         ctx: &ProgressContext,
         password: Option<&str>,
+        case_renames: &CaseRenames,
     ) -> Result<()> {
         if internal_root.is_none() {
             // Direct extraction to target - no temp dir needed
+            let case_tracker = CaseVariantTracker::new(target);
             fs::create_dir_all(target)?;
 
             let archive_builder = if let Some(pwd) = password {
@@ -820,15 +985,57 @@ impl Installer {
                 .open_for_processing()
                 .map_err(|e| anyhow::anyhow!("Failed to open RAR for extraction: {:?}", e))?;
 
+            let mut archive_links = Vec::new();
+            let mut entry_index = 0usize;
             while let Some(header) = arch
                 .read_header()
                 .map_err(|e| anyhow::anyhow!("Failed to read RAR header: {:?}", e))?
             {
-                arch = if header.entry().is_file() {
+                let index = entry_index;
+                entry_index += 1;
+                arch = if header.entry().is_file()
+                    && links::is_rar_symlink(header.entry().file_attr)
+                {
+                    let relative =
+                        archive_relative_path(&header.entry().filename.to_string_lossy(), None);
+                    let (data, next) = header
+                        .read()
+                        .map_err(|e| anyhow::anyhow!("Failed to read RAR link entry: {:?}", e))?;
+                    if let Some(relative) = relative {
+                        archive_links.push(links::ArchiveLink {
+                            relative: apply_case_rename(PathBuf::from(relative), case_renames),
+                            target: links::read_link_target(data.as_slice())?,
+                            kind: links::LinkKind::Symbolic,
+                        });
+                    }
+                    next
+                } else if header.entry().is_file() {
                     let size = header.entry().unpacked_size;
-                    let result = header
-                        .extract_with_base(target)
-                        .map_err(|e| anyhow::anyhow!("Failed to extract RAR entry: {:?}", e))?;
+                    let entry_name = header.entry().filename.to_string_lossy().to_string();
+                    let renamed =
+                        archive_relative_path(&header.entry().filename.to_string_lossy(), None)
+                            .and_then(|relative| case_renames.get(&relative).cloned());
+                    case_tracker.check_file(renamed.as_deref().unwrap_or(&header.entry().filename));
+                    let result = match renamed {
+                        Some(renamed) => {
+                            let dest = target.join(renamed);
+                            if let Some(parent) = dest.parent() {
+                                fs::create_dir_all(parent)?;
+                            }
+                            header.extract_to(dest)
+                        }
+                        None => header.extract_with_base(target),
+                    }
+                    .map_err(|e| {
+                        EntryContext {
+                            archive,
+                            entry_path: &entry_name,
+                            index: Some(index),
+                            compressed_size: None,
+                            uncompressed_size: Some(size),
+                        }
+                        .fail(rar_stage(&e), format!("{:?}", e))
+                    })?;
 
                     // Report progress
                     ctx.add_bytes(size);
@@ -841,15 +1048,14 @@ impl Installer {
                         .map_err(|e| anyhow::anyhow!("Failed to skip RAR entry: {:?}", e))?
                 };
             }
+            links::apply_archive_links(target, &archive_links, ctx);
 
             return Ok(());
         }
 
         // When internal_root is Some, use temp dir approach to strip the root prefix
-        let temp_dir = tempfile::Builder::new()
-            .prefix("xfastmanager_rar_")
-            .tempdir()
-            .context("Failed to create secure temp directory")?;
+        let temp_dir =
+            crate::staging::temp_dir("rar").context("Failed to create secure temp directory")?;
 
         // Extract using the typestate pattern (with password if provided)
         let archive_builder = if let Some(pwd) = password {
@@ -862,14 +1068,62 @@ impl Installer {
             .open_for_processing()
             .map_err(|e| anyhow::anyhow!("Failed to open RAR for extraction: {:?}", e))?;
 
+        let internal_root_normalized = internal_root.unwrap().replace('\\', "/");
+        let mut archive_links = Vec::new();
+        let mut entry_index = 0usize;
         while let Some(header) = arch
             .read_header()
             .map_err(|e| anyhow::anyhow!("Failed to read RAR header: {:?}", e))?
         {
-            arch = if header.entry().is_file() {
-                header
-                    .extract_with_base(temp_dir.path())
-                    .map_err(|e| anyhow::anyhow!("Failed to extract RAR entry: {:?}", e))?
+            let index = entry_index;
+            entry_index += 1;
+            arch = if header.entry().is_file() && links::is_rar_symlink(header.entry().file_attr) {
+                let relative = archive_relative_path(
+                    &header.entry().filename.to_string_lossy(),
+                    Some(&internal_root_normalized),
+                );
+                let (data, next) = header
+                    .read()
+                    .map_err(|e| anyhow::anyhow!("Failed to read RAR link entry: {:?}", e))?;
+                if let Some(relative) = relative {
+                    archive_links.push(links::ArchiveLink {
+                        relative: apply_case_rename(PathBuf::from(relative), case_renames),
+                        target: links::read_link_target(data.as_slice())?,
+                        kind: links::LinkKind::Symbolic,
+                    });
+                }
+                next
+            } else if header.entry().is_file() {
+                let size = header.entry().unpacked_size;
+                let entry_name = header.entry().filename.to_string_lossy().to_string();
+                let renamed = archive_relative_path(
+                    &header.entry().filename.to_string_lossy(),
+                    Some(&internal_root_normalized),
+                )
+                .and_then(|relative| case_renames.get(&relative).cloned());
+                match renamed {
+                    Some(renamed) => {
+                        let dest = temp_dir
+                            .path()
+                            .join(internal_root_normalized.trim_matches('/'))
+                            .join(renamed);
+                        if let Some(parent) = dest.parent() {
+                            fs::create_dir_all(parent)?;
+                        }
+                        header.extract_to(dest)
+                    }
+                    None => header.extract_with_base(temp_dir.path()),
+                }
+                .map_err(|e| {
+                    EntryContext {
+                        archive,
+                        entry_path: &entry_name,
+                        index: Some(index),
+                        compressed_size: None,
+                        uncompressed_size: Some(size),
+                    }
+                    .fail(rar_stage(&e), format!("{:?}", e))
+                })?
             } else {
                 header
                     .skip()
@@ -878,7 +1132,6 @@ impl Installer {
         }
 
         // Determine source path with internal_root
-        let internal_root_normalized = internal_root.unwrap().replace('\\', "/");
         let source_path = {
             let path = temp_dir.path().join(&internal_root_normalized);
             if path.exists() && path.is_dir() {
@@ -890,10 +1143,517 @@ impl Installer {
 
         // Copy with progress tracking
         self.copy_directory_with_progress(&source_path, target, ctx)?;
+        links::apply_archive_links(target, &archive_links, ctx);
 
         // TempDir automatically cleans up when dropped
         Ok(())
     }
+
+    /// Extract a single entry of a RAR archive to `dest`, counting its size
+    /// against the task's bytes. Used for nested chains so only the inner
+    /// archive is unpacked rather than the whole container.
+    pub(super) fn extract_rar_entry_with_progress(
+        &self,
+        archive: &Path,
+        entry_path: &str,
+        dest: &Path,
+        ctx: &ProgressContext,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let prepared_archive = crate::archive_input::prepare_archive_for_read(
+            archive,
+            crate::archive_input::ArchiveFormat::Rar,
+        )?;
+
+        extract_rar_entry_to_file(
+            prepared_archive.read_path(),
+            entry_path,
+            dest,
+            password,
+            |bytes| {
+                ctx.add_bytes(bytes);
+                ctx.emit_progress(None, InstallPhase::Installing);
+            },
+        )
+    }
+
+    /// Check the files `archive` extracts into `target` for paths that differ only by
+    /// case. Skipped on case-sensitive volumes, where both files can coexist.
+    fn check_archive_case_collisions(
+        &self,
+        archive: &Path,
+        format: crate::archive_input::ArchiveFormat,
+        target: &Path,
+        internal_root: Option<&str>,
+        password: Option<&str>,
+        ctx: &ProgressContext,
+    ) -> Result<CaseRenames> {
+        if !crate::fs_case::is_case_insensitive(target) {
+            return Ok(CaseRenames::new());
+        }
+
+        let files = match list_archive_files(archive, format, internal_root, password) {
+            Ok(files) => files,
+            Err(e) => {
+                // Extraction reports unreadable archives with a better error
+                logger::log_info(
+                    &format!(
+                        "Skipping case collision check for {}: {}",
+                        archive.display(),
+                        e
+                    ),
+                    Some("installer"),
+                );
+                return Ok(CaseRenames::new());
+            }
+        };
+        self.resolve_case_collisions(files.iter().map(String::as_str), target, ctx)
+    }
+
+    /// Find case collisions among `files` (relative to `target`, in archive order).
+    /// Fails with the colliding pairs unless the task allows renaming, in which case
+    /// the later file of each pair is given a free name and recorded in `ctx`.
+    pub(super) fn resolve_case_collisions<'a>(
+        &self,
+        files: impl IntoIterator<Item = &'a str>,
+        target: &Path,
+        ctx: &ProgressContext,
+    ) -> Result<CaseRenames> {
+        if !crate::fs_case::is_case_insensitive(target) {
+            return Ok(CaseRenames::new());
+        }
+
+        let collisions = crate::fs_case::find_case_collisions(files);
+        if collisions.is_empty() {
+            return Ok(CaseRenames::new());
+        }
+
+        if !ctx.allow_case_collision_rename.load(Ordering::SeqCst) {
+            let pairs: Vec<String> = collisions.iter().map(|c| c.to_string()).collect();
+            let mut message = format!(
+                "Archive contains {} file(s) that differ only by case from another file and would overwrite it: {}",
+                pairs.len(),
+                pairs[..pairs.len().min(MAX_LISTED_CASE_COLLISIONS)].join(", ")
+            );
+            if pairs.len() > MAX_LISTED_CASE_COLLISIONS {
+                message.push_str(&format!(
+                    " and {} more",
+                    pairs.len() - MAX_LISTED_CASE_COLLISIONS
+                ));
+            }
+            return Err(error::ApiError::with_details(
+                ApiErrorCode::ConflictExists,
+                message,
+                pairs.join("\n"),
+            )
+            .into());
+        }
+
+        for collision in &collisions {
+            logger::log_info(
+                &format!(
+                    "Extracting '{}' as '{}': it differs only by case from '{}'",
+                    collision.colliding, collision.renamed, collision.kept
+                ),
+                Some("installer"),
+            );
+        }
+        let renames = collisions
+            .iter()
+            .map(|c| (c.colliding.clone(), PathBuf::from(&c.renamed)))
+            .collect();
+        ctx.case_collisions.lock().unwrap().extend(collisions);
+        Ok(renames)
+    }
+}
+
+/// Most colliding pairs named in a case collision error; the details list all of them
+const MAX_LISTED_CASE_COLLISIONS: usize = 5;
+
+/// New paths for archive files that collide by case with an earlier file, keyed by
+/// the file's '/'-separated path relative to the internal root
+pub(super) type CaseRenames = HashMap<String, PathBuf>;
+
+/// `relative`, or the path it is renamed to to avoid a case collision
+pub(super) fn apply_case_rename(relative: PathBuf, renames: &CaseRenames) -> PathBuf {
+    if renames.is_empty() {
+        return relative;
+    }
+    renames
+        .get(&relative.to_string_lossy().replace('\\', "/"))
+        .cloned()
+        .unwrap_or(relative)
+}
+
+/// A ZIP entry to extract, read from the central directory
+struct ZipFileEntry {
+    index: usize,
+    /// Path inside the archive
+    name: String,
+    relative_path: PathBuf,
+    is_dir: bool,
+    is_encrypted: bool,
+    size: u64,
+    compressed_size: u64,
+}
+
+impl ZipFileEntry {
+    fn context<'a>(&'a self, archive: &'a Path) -> EntryContext<'a> {
+        EntryContext {
+            archive,
+            entry_path: &self.name,
+            index: Some(self.index),
+            compressed_size: Some(self.compressed_size),
+            uncompressed_size: Some(self.size),
+        }
+    }
+}
+
+/// Write one ZIP entry to `outpath`, returning its size and CRC32
+pub(super) fn extract_zip_entry<R: std::io::Read>(
+    file: &mut R,
+    outpath: &Path,
+    entry_ctx: &EntryContext,
+) -> Result<(u64, u32)> {
+    let outfile =
+        fs::File::create(outpath).map_err(|e| entry_ctx.fail(ExtractionFailureStage::Write, e))?;
+    let mut outfile = WriteTracker::new(outfile);
+    copy_file_with_crc32(file, &mut outfile)
+        .map_err(|e| entry_ctx.fail(copy_stage(&e, outfile.failed), e))
+}
+
+/// Path of an archive entry relative to `internal_root`, '/'-separated. `None` for
+/// entries outside the root, the root itself and unsafe paths, which the extractors skip.
+fn archive_relative_path(entry_name: &str, internal_root: Option<&str>) -> Option<String> {
+    let name = entry_name.replace('\\', "/");
+    let root = internal_root
+        .map(|root| root.replace('\\', "/").trim_matches('/').to_string())
+        .filter(|root| !root.is_empty());
+    let relative = match root {
+        Some(root) => name.strip_prefix(&format!("{}/", root))?,
+        None => name.as_str(),
+    };
+    let sanitized = sanitize_path(Path::new(relative))?;
+    Some(sanitized.to_string_lossy().replace('\\', "/"))
+}
+
+/// Files (not folders) `archive` extracts under `internal_root`, in archive order,
+/// read from its metadata without decompressing anything
+pub(crate) fn list_archive_files(
+    archive: &Path,
+    format: crate::archive_input::ArchiveFormat,
+    internal_root: Option<&str>,
+    password: Option<&str>,
+) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    match format {
+        crate::archive_input::ArchiveFormat::Zip => {
+            let mut reader = zip::ZipArchive::new(fs::File::open(archive)?)?;
+            for i in 0..reader.len() {
+                let Ok(file) = reader.by_index_raw(i) else {
+                    continue;
+                };
+                if file.is_dir() {
+                    continue;
+                }
+                if let Some(name) = file.enclosed_name() {
+                    files.extend(archive_relative_path(
+                        &name.to_string_lossy(),
+                        internal_root,
+                    ));
+                }
+            }
+        }
+        crate::archive_input::ArchiveFormat::SevenZ => {
+            let meta = sevenz_rust2::Archive::open(archive)
+                .map_err(|e| anyhow::anyhow!("Failed to open 7z: {}", e))?;
+            for entry in &meta.files {
+                if entry.is_directory() {
+                    continue;
+                }
+                files.extend(archive_relative_path(entry.name(), internal_root));
+            }
+        }
+        crate::archive_input::ArchiveFormat::Rar => {
+            let builder = match password {
+                Some(pwd) => unrar::Archive::with_password(archive, pwd),
+                None => unrar::Archive::new(archive),
+            };
+            let listing = builder
+                .open_for_listing()
+                .map_err(|e| anyhow::anyhow!("Failed to open RAR: {:?}", e))?;
+            for entry in listing.flatten() {
+                if entry.is_file() {
+                    files.extend(archive_relative_path(
+                        &entry.filename.to_string_lossy(),
+                        internal_root,
+                    ));
+                }
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Stream one file entry out of a RAR archive into `dest`, skipping every
+/// other entry without decompressing it. A partially written `dest` is
+/// removed if extraction fails.
+fn extract_rar_entry_to_file(
+    archive: &Path,
+    entry_path: &str,
+    dest: &Path,
+    password: Option<&str>,
+    mut on_bytes: impl FnMut(u64),
+) -> Result<()> {
+    let wanted = entry_path.replace('\\', "/");
+    let wanted = wanted.trim_start_matches('/');
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let archive_builder = if let Some(pwd) = password {
+        unrar::Archive::with_password(archive, pwd)
+    } else {
+        unrar::Archive::new(archive)
+    };
+
+    let mut arch = archive_builder
+        .open_for_processing()
+        .map_err(|e| anyhow::anyhow!("Failed to open RAR for extraction: {:?}", e))?;
+
+    while let Some(header) = arch
+        .read_header()
+        .map_err(|e| anyhow::anyhow!("Failed to read RAR header: {:?}", e))?
+    {
+        let entry_name = header.entry().filename.to_string_lossy().replace('\\', "/");
+        if header.entry().is_file() && entry_name.trim_start_matches('/') == wanted {
+            let size = header.entry().unpacked_size;
+            if let Err(e) = header.extract_to(dest) {
+                let _ = fs::remove_file(dest);
+                return Err(anyhow::anyhow!(
+                    "Failed to extract RAR entry {}: {:?}",
+                    entry_path,
+                    e
+                ));
+            }
+            on_bytes(size);
+            return Ok(());
+        }
+
+        arch = header
+            .skip()
+            .map_err(|e| anyhow::anyhow!("Failed to skip RAR entry: {:?}", e))?;
+    }
+
+    Err(anyhow::anyhow!(
+        "Nested archive not found in RAR: {}",
+        entry_path
+    ))
+}
+
+/// Copy a 7z entry while computing its SHA256, returned as hex
+fn copy_file_with_sha256<R: std::io::Read, W: std::io::Write>(
+    reader: &mut R,
+    writer: &mut W,
+) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; IO_BUFFER_SIZE];
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+        writer.write_all(&buffer[..bytes_read])?;
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Bytes decompressed between progress callbacks while streaming a 7z entry
+const BLOCK_PROGRESS_INTERVAL: u64 = 1024 * 1024;
+
+/// Whether any 7z block packs several entries into one compressed stream
+fn archive_is_solid(archive: &sevenz_rust2::Archive) -> bool {
+    archive
+        .blocks
+        .iter()
+        .any(|block| block.num_unpack_sub_streams > 1)
+}
+
+/// Reader wrapper that reports decompressed bytes every `BLOCK_PROGRESS_INTERVAL`
+/// while a 7z entry is read, so large files and solid blocks keep moving the
+/// progress bar during decompression.
+struct BlockProgressReader<R, F: FnMut(u64)> {
+    inner: R,
+    pending: u64,
+    on_progress: F,
+}
+
+impl<R: std::io::Read, F: FnMut(u64)> BlockProgressReader<R, F> {
+    fn new(inner: R, on_progress: F) -> Self {
+        Self {
+            inner,
+            pending: 0,
+            on_progress,
+        }
+    }
+
+    /// Report any bytes read since the last callback
+    fn finish(&mut self) {
+        if self.pending > 0 {
+            (self.on_progress)(self.pending);
+            self.pending = 0;
+        }
+    }
+}
+
+impl<R: std::io::Read, F: FnMut(u64)> std::io::Read for BlockProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.pending += read as u64;
+        if self.pending >= BLOCK_PROGRESS_INTERVAL || (read == 0 && self.pending > 0) {
+            self.finish();
+        }
+        Ok(read)
+    }
+}
+
+/// An archive entry that failed to extract, with where and how. Carried
+/// through `anyhow` so the task result can report it as structured data.
+#[derive(Debug)]
+pub(super) struct EntryExtractionError {
+    pub(super) detail: ExtractionFailureDetail,
+}
+
+impl std::fmt::Display for EntryExtractionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let detail = &self.detail;
+        if detail.failed_writes > 0 {
+            return write!(
+                f,
+                "{} files could not be written, the target disk is likely failing or full (last: {}: {})",
+                detail.failed_writes, detail.entry_path, detail.error
+            );
+        }
+        let stage = match detail.stage {
+            ExtractionFailureStage::Read => "reading it",
+            ExtractionFailureStage::Write => "writing it",
+            ExtractionFailureStage::Crc => "checking its CRC",
+        };
+        write!(f, "Failed to extract {}", detail.entry_path)?;
+        if let Some(index) = detail.entry_index {
+            write!(f, " (entry {})", index)?;
+        }
+        write!(
+            f,
+            " from {} while {}: {}",
+            detail.archive_path, stage, detail.error
+        )
+    }
+}
+
+impl std::error::Error for EntryExtractionError {}
+
+/// Extraction failure details of an installation error, if it came from an
+/// archive entry
+pub(super) fn failure_detail(error: &anyhow::Error) -> Option<ExtractionFailureDetail> {
+    error
+        .chain()
+        .find_map(|e| e.downcast_ref::<EntryExtractionError>())
+        .map(|e| e.detail.clone())
+}
+
+/// The archive entry being extracted, to describe a failure with
+pub(super) struct EntryContext<'a> {
+    pub(super) archive: &'a Path,
+    pub(super) entry_path: &'a str,
+    pub(super) index: Option<usize>,
+    pub(super) compressed_size: Option<u64>,
+    pub(super) uncompressed_size: Option<u64>,
+}
+
+impl EntryContext<'_> {
+    fn detail(
+        &self,
+        stage: ExtractionFailureStage,
+        error: impl std::fmt::Display,
+    ) -> ExtractionFailureDetail {
+        ExtractionFailureDetail {
+            archive_path: self.archive.to_string_lossy().to_string(),
+            entry_path: self.entry_path.to_string(),
+            entry_index: self.index,
+            compressed_size: self.compressed_size,
+            uncompressed_size: self.uncompressed_size,
+            stage,
+            error: error.to_string(),
+            failed_writes: 0,
+        }
+    }
+
+    pub(super) fn fail(
+        &self,
+        stage: ExtractionFailureStage,
+        error: impl std::fmt::Display,
+    ) -> anyhow::Error {
+        EntryExtractionError {
+            detail: self.detail(stage, error),
+        }
+        .into()
+    }
+}
+
+fn is_checksum_message(text: &str) -> bool {
+    let text = text.to_ascii_lowercase();
+    text.contains("checksum") || text.contains("crc")
+}
+
+/// Step a failed copy out of an archive entry broke in
+fn copy_stage(error: &std::io::Error, write_failed: bool) -> ExtractionFailureStage {
+    if write_failed {
+        ExtractionFailureStage::Write
+    } else if is_checksum_message(&error.to_string()) {
+        ExtractionFailureStage::Crc
+    } else {
+        ExtractionFailureStage::Read
+    }
+}
+
+/// Step a failed unrar extraction broke in, from its error code
+fn rar_stage(error: &unrar::error::UnrarError) -> ExtractionFailureStage {
+    let code = format!("{:?}", error.code);
+    match code.as_str() {
+        "BadData" => ExtractionFailureStage::Crc,
+        "ECreate" | "EWrite" | "EClose" => ExtractionFailureStage::Write,
+        _ => ExtractionFailureStage::Read,
+    }
+}
+
+/// Writer that remembers whether a write failed, so a failed copy can be told
+/// apart from a failed read of the archive
+struct WriteTracker<W> {
+    inner: W,
+    failed: bool,
+}
+
+impl<W> WriteTracker<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            failed: false,
+        }
+    }
+}
+
+impl<W: std::io::Write> std::io::Write for WriteTracker<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf).inspect_err(|_| self.failed = true)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush().inspect_err(|_| self.failed = true)
+    }
 }
 
 fn truncate_for_log(text: &str, max_chars: usize) -> String {
@@ -903,3 +1663,229 @@ fn truncate_for_log(text: &str, max_chars: usize) -> String {
     let truncated: String = text.chars().take(max_chars).collect();
     format!("{}...", truncated)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sevenz_rust2::{ArchiveEntry, ArchiveReader, ArchiveWriter, Password, SourceReader};
+    use std::io::Cursor;
+
+    /// Build a solid 7z archive whose entries span several progress intervals
+    fn solid_fixture(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut writer = ArchiveWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer
+            .push_archive_entries(
+                entries
+                    .iter()
+                    .map(|(name, _)| ArchiveEntry::new_file(name))
+                    .collect(),
+                entries
+                    .iter()
+                    .map(|(_, data)| SourceReader::new(Cursor::new(data.clone())))
+                    .collect(),
+            )
+            .unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn solid_7z_reports_progress_during_block_decompression() {
+        let first: Vec<u8> = (0..3 * BLOCK_PROGRESS_INTERVAL)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let second = vec![7u8; (2 * BLOCK_PROGRESS_INTERVAL) as usize];
+        let data = solid_fixture(&[("a.bin", first.clone()), ("b.bin", second.clone())]);
+
+        let mut reader = ArchiveReader::new(Cursor::new(data), Password::empty()).unwrap();
+        assert!(archive_is_solid(reader.archive()));
+
+        // (entry name, bytes reported so far) for every progress callback
+        let mut events: Vec<(String, u64)> = Vec::new();
+        let mut totals: Vec<(String, u64)> = Vec::new();
+        reader
+            .for_each_entries(|entry, entry_reader| {
+                let name = entry.name().to_string();
+                let mut reported = 0u64;
+                {
+                    let mut progress = BlockProgressReader::new(entry_reader, |bytes| {
+                        reported += bytes;
+                        events.push((name.clone(), reported));
+                    });
+                    std::io::copy(&mut progress, &mut std::io::sink())?;
+                    progress.finish();
+                }
+                totals.push((name, reported));
+                Ok(true)
+            })
+            .unwrap();
+
+        assert_eq!(
+            totals,
+            vec![
+                ("a.bin".to_string(), first.len() as u64),
+                ("b.bin".to_string(), second.len() as u64),
+            ]
+        );
+
+        // Progress must arrive while each entry is still being decompressed,
+        // not only once it has been fully read
+        for (name, size) in &totals {
+            let partial = events
+                .iter()
+                .filter(|(event_name, reported)| event_name == name && reported < size)
+                .count();
+            assert!(partial >= 1, "no intermediate progress for {}", name);
+        }
+    }
+
+    #[test]
+    fn block_progress_reader_flushes_remainder() {
+        let data = vec![1u8; 10];
+        let mut calls = Vec::new();
+        let mut reader = BlockProgressReader::new(Cursor::new(data), |bytes| calls.push(bytes));
+        std::io::copy(&mut reader, &mut std::io::sink()).unwrap();
+        reader.finish();
+        drop(reader);
+        assert_eq!(calls, vec![10]);
+    }
+
+    /// Stored RAR4 archive holding `readme.txt` and `nested\inner.zip`, where
+    /// the zip contains `TestPlane/TestPlane.acf`. Hand-built because the
+    /// unrar crate cannot write archives.
+    const NESTED_ZIP_RAR: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/install/installer/testdata/outer_with_nested_zip.rar"
+    );
+
+    #[test]
+    fn rar_entry_extraction_writes_only_the_nested_archive() {
+        let temp = tempfile::tempdir().unwrap();
+        let dest = temp.path().join("layer_0").join("nested").join("inner.zip");
+        let mut reported = 0u64;
+
+        extract_rar_entry_to_file(
+            Path::new(NESTED_ZIP_RAR),
+            "nested\\inner.zip",
+            &dest,
+            None,
+            |bytes| reported += bytes,
+        )
+        .unwrap();
+
+        assert_eq!(reported, fs::metadata(&dest).unwrap().len());
+        assert!(!temp.path().join("layer_0").join("readme.txt").exists());
+
+        let mut inner = zip::ZipArchive::new(fs::File::open(&dest).unwrap()).unwrap();
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner.by_index(0).unwrap().name(), "TestPlane/TestPlane.acf");
+    }
+
+    #[test]
+    fn rar_entry_extraction_fails_for_missing_entry() {
+        let temp = tempfile::tempdir().unwrap();
+        let dest = temp.path().join("missing.zip");
+
+        let result = extract_rar_entry_to_file(
+            Path::new(NESTED_ZIP_RAR),
+            "missing.zip",
+            &dest,
+            None,
+            |_| {},
+        );
+
+        assert!(result.is_err());
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn zip_case_collisions_are_found_under_internal_root_only() {
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().unwrap();
+        let archive = temp.path().join("livery.zip");
+        let mut writer = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.add_directory("Pack/objects/", options).unwrap();
+        for name in [
+            "Pack/Textures/foo.png",
+            "Pack/textures/Foo.png",
+            "Other/textures/FOO.png",
+        ] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(name.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let files = list_archive_files(
+            &archive,
+            crate::archive_input::ArchiveFormat::Zip,
+            Some("Pack"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(files, vec!["Textures/foo.png", "textures/Foo.png"]);
+
+        let renames: CaseRenames =
+            crate::fs_case::find_case_collisions(files.iter().map(String::as_str))
+                .into_iter()
+                .map(|c| (c.colliding, PathBuf::from(c.renamed)))
+                .collect();
+        assert_eq!(
+            apply_case_rename(PathBuf::from("textures/Foo.png"), &renames),
+            PathBuf::from("textures/Foo_case2.png")
+        );
+        assert_eq!(
+            apply_case_rename(PathBuf::from("Textures/foo.png"), &renames),
+            PathBuf::from("Textures/foo.png")
+        );
+    }
+
+    #[test]
+    fn zip_entry_failures_report_the_entry_and_stage() {
+        let content = b"terrain data that will not survive".to_vec();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        writer.start_file("Tiles/+47-123.dsf", options).unwrap();
+        std::io::Write::write_all(&mut writer, &content).unwrap();
+        let mut bytes = writer.finish().unwrap().into_inner();
+        // Flip a byte of the stored data so only the CRC check can notice
+        let offset = bytes
+            .windows(content.len())
+            .position(|window| window == content.as_slice())
+            .unwrap();
+        bytes[offset] ^= 0xFF;
+
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("tiles.zip");
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let entry_ctx = EntryContext {
+            archive: &archive_path,
+            entry_path: "Tiles/+47-123.dsf",
+            index: Some(0),
+            compressed_size: Some(content.len() as u64),
+            uncompressed_size: Some(content.len() as u64),
+        };
+
+        let mut file = archive.by_index(0).unwrap();
+        let err =
+            extract_zip_entry(&mut file, &dir.path().join("out.dsf"), &entry_ctx).unwrap_err();
+        let detail = failure_detail(&err).unwrap();
+        assert_eq!(detail.stage, ExtractionFailureStage::Crc);
+        assert_eq!(detail.entry_path, "Tiles/+47-123.dsf");
+        assert_eq!(detail.entry_index, Some(0));
+        assert!(err.to_string().contains("Tiles/+47-123.dsf"));
+
+        let mut reader = Cursor::new(content);
+        let err = extract_zip_entry(
+            &mut reader,
+            &dir.path().join("missing").join("out.dsf"),
+            &entry_ctx,
+        )
+        .unwrap_err();
+        assert_eq!(
+            failure_detail(&err).unwrap().stage,
+            ExtractionFailureStage::Write
+        );
+    }
+}
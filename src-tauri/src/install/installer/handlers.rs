@@ -1,4 +1,7 @@
+use super::extraction::apply_case_rename;
 use super::*;
+use crate::atomic_installer::{AtomicInstaller, StageResultExt};
+use crate::models::AtomicInstallStage;
 
 fn resolve_extracted_nested_archive_path(
     extract_target: &Path,
@@ -83,6 +86,110 @@ fn load_nested_zip_layers_from_bytes(
     Ok((current_archive_data, current_password_bytes))
 }
 
+/// Refuse to replace navdata with an older AIRAC cycle unless the task allows it.
+/// The target's cycle.json is read again, as an earlier task in the same batch
+/// may have replaced it since the analysis.
+fn check_navdata_downgrade(task: &InstallTask, xplane_path: &Path) -> Result<()> {
+    let Some(new_info) = task.new_navdata_info.as_ref() else {
+        return Ok(());
+    };
+    if task.allow_downgrade {
+        return Ok(());
+    }
+    let current_info = fs::read_to_string(Path::new(&task.target_path).join("cycle.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<crate::models::NavdataCycle>(&content).ok())
+        .map(|cycle| crate::models::NavdataInfo {
+            name: cycle.name,
+            cycle: cycle.cycle,
+            airac: cycle.airac,
+        });
+    let existing = current_info
+        .as_ref()
+        .or(task.existing_navdata_info.as_ref());
+    if let Some(installed_cycle) =
+        crate::management_index::navdata_downgrade_from(new_info, existing, xplane_path)
+    {
+        let new_cycle = crate::management_index::navdata_info_cycle(new_info).unwrap_or_default();
+        return Err(UserMsg::NavdataDowngradeNotAllowed
+            .error_with([new_cycle, installed_cycle])
+            .into());
+    }
+    Ok(())
+}
+
+/// FlyWithLua/Modules, where FlyWithLua looks up `require`d libraries, when
+/// `scripts_dir` is FlyWithLua's Scripts folder
+fn lua_modules_dir(scripts_dir: &Path) -> Option<PathBuf> {
+    let is_scripts = scripts_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.eq_ignore_ascii_case("Scripts"));
+    if !is_scripts {
+        return None;
+    }
+    scripts_dir.parent().map(|root| root.join("Modules"))
+}
+
+fn is_lua_modules_entry(entry: &Path) -> bool {
+    entry
+        .components()
+        .next()
+        .and_then(|component| component.as_os_str().to_str())
+        .is_some_and(|first| first.eq_ignore_ascii_case("Modules"))
+}
+
+/// Split Lua bundle entries into those installed into `scripts_dir` and the
+/// Modules entries installed into [`lua_modules_dir`]
+fn partition_lua_bundle(scripts_dir: &Path, entries: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    if lua_modules_dir(scripts_dir).is_none() {
+        return (entries, Vec::new());
+    }
+    let (modules, scripts) = entries
+        .into_iter()
+        .partition(|entry| is_lua_modules_entry(entry));
+    (scripts, modules)
+}
+
+/// Copy the Modules `entries` found in `source_dir` into FlyWithLua/Modules.
+/// Modules is shared by every script, so files are merged in and nothing
+/// already there is removed.
+fn merge_lua_modules(source_dir: &Path, scripts_dir: &Path, entries: &[PathBuf]) -> Result<()> {
+    let Some(modules_dir) = lua_modules_dir(scripts_dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let source_path = source_dir.join(entry);
+        let relative: PathBuf = entry.components().skip(1).collect();
+        let target_path = modules_dir.join(relative);
+        for file in walkdir::WalkDir::new(&source_path)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let destination = match file.path().strip_prefix(&source_path) {
+                Ok(inner) if inner.as_os_str().is_empty() => target_path.clone(),
+                Ok(inner) => target_path.join(inner),
+                Err(_) => continue,
+            };
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent).context(format!(
+                    "Failed to create Lua Modules directory: {:?}",
+                    parent
+                ))?;
+            }
+            fs::copy(file.path(), &destination).context(format!(
+                "Failed to copy Lua module: {:?} -> {:?}",
+                file.path(),
+                destination
+            ))?;
+            let _ = remove_readonly_attribute(&destination);
+        }
+    }
+    Ok(())
+}
+
 impl Installer {
     /// Check whether a source file is a supported archive format.
     fn is_supported_archive_file(path: &Path) -> bool {
@@ -494,8 +601,6 @@ impl Installer {
         ctx: &ProgressContext,
         password: Option<&str>,
     ) -> Result<()> {
-        use tempfile::TempDir;
-
         let scripts_dir = target
             .parent()
             .ok_or_else(|| anyhow::anyhow!("Lua target path has no parent: {:?}", target))?;
@@ -504,7 +609,8 @@ impl Installer {
             scripts_dir
         ))?;
 
-        let mut bundle_entries = Self::get_lua_bundle_entries(task, target)?;
+        let (mut bundle_entries, mut module_entries) =
+            partition_lua_bundle(scripts_dir, Self::get_lua_bundle_entries(task, target)?);
 
         if !task.should_overwrite {
             self.remove_lua_bundle_targets(scripts_dir, &bundle_entries)?;
@@ -514,7 +620,7 @@ impl Installer {
         if source.is_file()
             && (task.extraction_chain.is_some() || Self::is_supported_archive_file(source))
         {
-            let staging = TempDir::new()
+            let staging = crate::staging::temp_dir("lua")
                 .context("Failed to create temp staging directory for Lua install")?;
 
             if let Some(ref chain) = task.extraction_chain {
@@ -556,7 +662,8 @@ impl Installer {
                     }
                 }
 
-                bundle_entries = resolved_entries;
+                (bundle_entries, module_entries) =
+                    partition_lua_bundle(scripts_dir, resolved_entries);
             }
 
             if !task.should_overwrite {
@@ -569,6 +676,7 @@ impl Installer {
                 &bundle_entries,
                 task.should_overwrite,
             )?;
+            merge_lua_modules(staging.path(), scripts_dir, &module_entries)?;
             return Ok(());
         }
 
@@ -584,6 +692,7 @@ impl Installer {
                 ctx,
                 task.should_overwrite,
             )?;
+            merge_lua_modules(source_dir, scripts_dir, &module_entries)?;
             return Ok(());
         }
 
@@ -596,6 +705,7 @@ impl Installer {
                 ctx,
                 task.should_overwrite,
             )?;
+            merge_lua_modules(source, scripts_dir, &module_entries)?;
             return Ok(());
         }
 
@@ -625,6 +735,16 @@ impl Installer {
             compute_inline_7z_hashes,
             std::sync::atomic::Ordering::SeqCst,
         );
+        ctx.allow_case_collision_rename.store(
+            task.allow_case_collision_rename,
+            std::sync::atomic::Ordering::SeqCst,
+        );
+        ctx.allow_symlinks
+            .store(task.allow_symlinks, std::sync::atomic::Ordering::SeqCst);
+
+        if task.addon_type == AddonType::Navdata {
+            check_navdata_downgrade(task, Path::new(xplane_path))?;
+        }
 
         // Create parent directory if it doesn't exist
         let mkdir_start = Instant::now();
@@ -644,7 +764,11 @@ impl Installer {
         // Handle them with a dedicated path so archives install into Scripts root
         // (not into a "script.lua/" directory).
         if task.addon_type == AddonType::LuaScript {
-            self.install_lua_task_with_companions(task, source, target, ctx, password)?;
+            if atomic_install_enabled && task.extraction_chain.is_none() {
+                self.install_lua_task_atomic(task, source, target, ctx, password, xplane_path)?;
+            } else {
+                self.install_lua_task_with_companions(task, source, target, ctx, password)?;
+            }
             return Ok(());
         }
 
@@ -771,6 +895,18 @@ impl Installer {
         ctx: &ProgressContext,
         outermost_password: Option<&str>,
     ) -> Result<()> {
+        // Encrypted nested layers without any usable password would only fail mid-extraction
+        if let Some(locked) = chain
+            .archives
+            .iter()
+            .find(|a| a.password_required && a.password.is_none() && outermost_password.is_none())
+        {
+            return Err(anyhow::anyhow!(
+                "Nested archive '{}' is password-protected but no password was provided",
+                locked.internal_path
+            ));
+        }
+
         // For multi-layer chains (including single-layer nested archives),
         // check if we can use the memory-optimized path
         // IMPORTANT: Must also check that the outermost archive (source) is a ZIP file
@@ -914,7 +1050,7 @@ impl Installer {
         archive: &mut zip::ZipArchive<R>,
         target: &Path,
         internal_root: Option<&str>,
-        _ctx: &ProgressContext,
+        ctx: &ProgressContext,
         password: Option<&[u8]>,
     ) -> Result<()> {
         let internal_root_normalized = internal_root.map(|s| s.replace('\\', "/"));
@@ -933,6 +1069,7 @@ impl Installer {
         );
 
         // Collect all file entries
+        let mut link_entries = Vec::new();
         let entries: Vec<_> = (0..archive.len())
             .filter_map(|i| {
                 // Use by_index_raw to avoid triggering decryption errors when reading metadata
@@ -970,6 +1107,10 @@ impl Installer {
                     file_path_str.clone()
                 };
 
+                if file.unix_mode().is_some_and(links::is_symlink_mode) {
+                    link_entries.push((i, PathBuf::from(relative_path), file.encrypted()));
+                    return None;
+                }
                 Some((i, relative_path, file.is_dir(), file.encrypted()))
             })
             .collect();
@@ -981,11 +1122,27 @@ impl Installer {
             None,
         );
 
-        // Create directories first
+        let case_renames = self.resolve_case_collisions(
+            entries
+                .iter()
+                .filter(|(_, _, is_dir, _)| !is_dir)
+                .map(|(_, relative_path, _, _)| relative_path.as_str()),
+            target,
+            ctx,
+        )?;
+
+        // Create directories first, checking every entry against existing folder casing
+        let case_tracker = CaseVariantTracker::new(target);
         for (_, relative_path, is_dir, _) in &entries {
             if *is_dir {
+                case_tracker.check_dir(Path::new(relative_path));
                 let dir_path = target.join(relative_path);
                 fs::create_dir_all(&dir_path)?;
+            } else {
+                case_tracker.check_file(&apply_case_rename(
+                    PathBuf::from(relative_path),
+                    &case_renames,
+                ));
             }
         }
 
@@ -1001,7 +1158,10 @@ impl Installer {
                     continue;
                 }
 
-                let target_path = target.join(&relative_path);
+                let target_path = target.join(apply_case_rename(
+                    PathBuf::from(relative_path),
+                    &case_renames,
+                ));
                 if let Some(parent) = target_path.parent() {
                     fs::create_dir_all(parent)?;
                 }
@@ -1035,7 +1195,10 @@ impl Installer {
                     continue;
                 }
 
-                let target_path = target.join(&relative_path);
+                let target_path = target.join(apply_case_rename(
+                    PathBuf::from(relative_path),
+                    &case_renames,
+                ));
                 if let Some(parent) = target_path.parent() {
                     fs::create_dir_all(parent)?;
                 }
@@ -1052,6 +1215,10 @@ impl Installer {
             }
         }
 
+        // Links are read only now, so no file above was written through one
+        let archive_links = links::read_zip_links(archive, link_entries, password)?;
+        links::apply_archive_links(target, &archive_links, ctx);
+
         Ok(())
     }
 
@@ -1065,16 +1232,14 @@ impl Installer {
         ctx: &ProgressContext,
         outermost_password: Option<&str>,
     ) -> Result<()> {
-        use tempfile::TempDir;
-
         crate::logger::log_info(
             &format!("Using temp directory extraction for {} nested layers (mixed format optimization enabled)", chain.archives.len()),
             Some("installer"),
         );
 
         // Create temp directory for intermediate extractions
-        let temp_base =
-            TempDir::new().context("Failed to create temp directory for nested extraction")?;
+        let temp_base = crate::staging::temp_dir("nested")
+            .context("Failed to create temp directory for nested extraction")?;
 
         let mut current_source = source.to_path_buf();
         let mut current_password = outermost_password;
@@ -1089,28 +1254,49 @@ impl Installer {
                 extract_target
             ))?;
 
-            crate::logger::log_info(
-                &format!(
-                    "Extracting container for layer {} to {:?} to locate {} ({})",
-                    index, extract_target, archive_info.internal_path, archive_info.format
-                ),
-                Some("installer"),
-            );
-
-            self.extract_archive_with_progress(
-                &current_source,
-                &extract_target,
-                None,
-                ctx,
-                current_password,
-                None,
-            )?;
-
             let nested_archive_path = resolve_extracted_nested_archive_path(
                 &extract_target,
                 &archive_info.internal_path,
             )?;
 
+            if crate::archive_input::detect_archive_format(&current_source)
+                == Some(crate::archive_input::ArchiveFormat::Rar)
+            {
+                // RAR containers only need the nested entry, not every file
+                crate::logger::log_info(
+                    &format!(
+                        "Extracting {} ({}) from RAR container for layer {} to {:?}",
+                        archive_info.internal_path, archive_info.format, index, nested_archive_path
+                    ),
+                    Some("installer"),
+                );
+
+                self.extract_rar_entry_with_progress(
+                    &current_source,
+                    &archive_info.internal_path,
+                    &nested_archive_path,
+                    ctx,
+                    current_password,
+                )?;
+            } else {
+                crate::logger::log_info(
+                    &format!(
+                        "Extracting container for layer {} to {:?} to locate {} ({})",
+                        index, extract_target, archive_info.internal_path, archive_info.format
+                    ),
+                    Some("installer"),
+                );
+
+                self.extract_archive_with_progress(
+                    &current_source,
+                    &extract_target,
+                    None,
+                    ctx,
+                    current_password,
+                    None,
+                )?;
+            }
+
             if !nested_archive_path.exists() {
                 let mut available_files = Vec::new();
                 if let Ok(entries) = fs::read_dir(&extract_target) {
@@ -1346,13 +1532,12 @@ impl Installer {
             backup_config_files,
             config_patterns: config_file_patterns,
         } = options;
-        use uuid::Uuid;
 
         // Step 1: Backup liveries and config files if requested
         let backup_dir = if (backup_liveries || backup_config_files) && target.exists() {
-            let temp_dir = std::env::temp_dir();
-            let backup_path = temp_dir.join(format!("xfastmanager_backup_{}", Uuid::new_v4()));
-            fs::create_dir_all(&backup_path).context("Failed to create backup directory")?;
+            let backup_path =
+                crate::staging::create_dir_in(&crate::staging::staging_root(), "backup")
+                    .context("Failed to create backup directory")?;
 
             // Backup liveries
             if backup_liveries {
@@ -1588,9 +1773,7 @@ impl Installer {
         );
 
         // Create temp directory for backup
-        let temp_dir =
-            std::env::temp_dir().join(format!("xfastmanager_backup_{}", uuid::Uuid::new_v4()));
-        fs::create_dir_all(&temp_dir)
+        let temp_dir = crate::staging::create_dir_in(&crate::staging::staging_root(), "backup")
             .context("Failed to create backup directory - check disk space")?;
 
         let mut backup = AircraftBackup {
@@ -1928,7 +2111,11 @@ impl Installer {
             ("navdata".to_string(), None, None)
         };
 
-        let (provider_name, _, _) = read_cycle_json(&temp_dir);
+        let (provider_name, new_cycle, new_airac) = read_cycle_json(&temp_dir);
+        let new_cycle = crate::management_index::effective_airac_cycle(
+            new_cycle.as_deref(),
+            new_airac.as_deref(),
+        );
         let (_, old_cycle, old_airac) = read_cycle_json(target);
         let custom_data_dir = self.resolve_navdata_custom_data_root(target);
 
@@ -2119,6 +2306,7 @@ impl Installer {
                 provider_name,
                 cycle: old_cycle,
                 airac: old_airac,
+                replaced_by_cycle: new_cycle,
                 backup_time: chrono::Utc::now().to_rfc3339(),
                 files: backup_entries,
                 file_count: backup_file_count,
@@ -2216,7 +2404,9 @@ impl Installer {
         // wasn't created yet but companions were partially copied.
         if task.addon_type == AddonType::LuaScript {
             if let Some(scripts_dir) = target.parent() {
-                let bundle_entries = Self::get_lua_bundle_entries(task, target)?;
+                // Modules is shared with other scripts and left alone
+                let (bundle_entries, _) =
+                    partition_lua_bundle(scripts_dir, Self::get_lua_bundle_entries(task, target)?);
                 self.remove_lua_bundle_targets(scripts_dir, &bundle_entries)?;
             } else if target.exists() {
                 self.remove_existing_target_path(target)?;
@@ -2255,18 +2445,13 @@ impl Installer {
         Ok(())
     }
 
-    /// Install a task using atomic installation mode
-    fn install_task_atomic(
+    /// Create an atomic installer for a task, with its temp directory in the X-Plane root
+    fn new_atomic_installer(
         &self,
-        task: &InstallTask,
-        source: &Path,
         target: &Path,
         ctx: &ProgressContext,
-        password: Option<&str>,
         xplane_path: &str,
-    ) -> Result<()> {
-        use crate::atomic_installer::AtomicInstaller;
-
+    ) -> Result<AtomicInstaller> {
         // Use X-Plane root path directly from settings
         let xplane_root = Path::new(xplane_path);
 
@@ -2300,7 +2485,8 @@ impl Installer {
             ctx.total_tasks,
             ctx.current_task_index,
             task_percentage,
-        )?;
+        )
+        .stage(AtomicInstallStage::Staging)?;
 
         // In parallel mode, wire up the atomic installer to delegate through
         // the parallel progress context so it doesn't emit serial-mode events.
@@ -2309,6 +2495,21 @@ impl Installer {
             atomic.set_parallel_emit(Arc::clone(emit_fn), Arc::clone(cf));
         }
 
+        Ok(atomic)
+    }
+
+    /// Install a task using atomic installation mode
+    fn install_task_atomic(
+        &self,
+        task: &InstallTask,
+        source: &Path,
+        target: &Path,
+        ctx: &ProgressContext,
+        password: Option<&str>,
+        xplane_path: &str,
+    ) -> Result<()> {
+        let mut atomic = self.new_atomic_installer(target, ctx, xplane_path)?;
+
         // Step 1: Extract/copy to temp directory
         logger::log_info(
             &format!(
@@ -2324,23 +2525,23 @@ impl Installer {
             task.archive_internal_root.as_deref(),
             ctx,
             password,
-        )?;
+        )
+        .stage(AtomicInstallStage::Staging)?;
 
         // Step 2: Perform atomic installation based on scenario
-        if !target.exists() {
+        if matches!(task.addon_type, AddonType::Navdata) && target.exists() {
+            // Navdata replaces whole entries of Custom Data, with a backup, so a
+            // failure never leaves two AIRAC cycles mixed together
+            atomic.install_navdata_with_backup(task.backup_navdata, !task.should_overwrite)?;
+        } else if !target.exists() {
             // Scenario 1: Fresh installation
-            atomic.install_fresh()?;
+            atomic.install_fresh().stage(AtomicInstallStage::Swap)?;
         } else if !task.should_overwrite {
             // Scenario 2: Clean installation (should_overwrite=false means clean install)
-            // Special handling for Navdata: use backup mechanism
-            if matches!(task.addon_type, AddonType::Navdata) {
-                atomic.install_clean_navdata_with_backup(task.backup_navdata)?;
-            } else {
-                atomic.install_clean(task)?;
-            }
+            atomic.install_clean(task).stage(AtomicInstallStage::Swap)?;
         } else {
             // Scenario 3: Overwrite installation (should_overwrite=true means merge)
-            atomic.install_overwrite()?;
+            atomic.install_overwrite().stage(AtomicInstallStage::Swap)?;
         }
 
         logger::log_info(
@@ -2351,6 +2552,108 @@ impl Installer {
         Ok(())
     }
 
+    /// Install a Lua script bundle atomically: the script and its companions are
+    /// staged next to X-Plane, then swapped into FlyWithLua/Scripts together.
+    /// Modules companions are merged into FlyWithLua/Modules afterwards.
+    fn install_lua_task_atomic(
+        &self,
+        task: &InstallTask,
+        source: &Path,
+        target: &Path,
+        ctx: &ProgressContext,
+        password: Option<&str>,
+        xplane_path: &str,
+    ) -> Result<()> {
+        let scripts_dir = target
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("Lua target path has no parent: {:?}", target))?;
+        fs::create_dir_all(scripts_dir).context(format!(
+            "Failed to create Lua Scripts directory: {:?}",
+            scripts_dir
+        ))?;
+
+        let mut atomic = self.new_atomic_installer(scripts_dir, ctx, xplane_path)?;
+        let staging = atomic.temp_dir().to_path_buf();
+        let bundle_entries = self
+            .stage_lua_bundle(task, source, target, &staging, ctx, password)
+            .stage(AtomicInstallStage::Staging)?;
+        let (bundle_entries, module_entries) = partition_lua_bundle(scripts_dir, bundle_entries);
+
+        atomic.install_entries(&bundle_entries, task.should_overwrite)?;
+        // Merged into the shared Modules folder once the script is in place
+        merge_lua_modules(&staging, scripts_dir, &module_entries)?;
+
+        logger::log_info(
+            "Atomic Lua installation completed successfully",
+            Some("installer"),
+        );
+
+        Ok(())
+    }
+
+    /// Put the Lua script and its companions into `staging`, returning the bundle
+    /// entries with the script first
+    fn stage_lua_bundle(
+        &self,
+        task: &InstallTask,
+        source: &Path,
+        target: &Path,
+        staging: &Path,
+        ctx: &ProgressContext,
+        password: Option<&str>,
+    ) -> Result<Vec<PathBuf>> {
+        let bundle_entries = Self::get_lua_bundle_entries(task, target)?;
+
+        if source.is_file()
+            && (task.extraction_chain.is_some() || Self::is_supported_archive_file(source))
+        {
+            if let Some(ref chain) = task.extraction_chain {
+                self.install_content_with_extraction_chain(source, staging, chain, ctx, password)?;
+            } else {
+                self.extract_archive_with_progress(
+                    source,
+                    staging,
+                    task.archive_internal_root.as_deref(),
+                    ctx,
+                    password,
+                    task.file_hashes.as_ref(),
+                )?;
+            }
+
+            // Reconcile companions from extracted content, as in the non-atomic path
+            let discovered = self.discover_lua_companion_entries_in_staging(staging, target)?;
+            let mut resolved_entries = bundle_entries;
+            for entry in discovered {
+                if !resolved_entries.contains(&entry) {
+                    resolved_entries.push(entry);
+                }
+            }
+            return Ok(resolved_entries);
+        }
+
+        let source_dir = if source.is_file() {
+            source
+                .parent()
+                .ok_or_else(|| anyhow::anyhow!("Lua source file has no parent directory"))?
+        } else if source.is_dir() {
+            source
+        } else {
+            return Err(anyhow::anyhow!(
+                "Lua source path is neither file nor directory: {:?}",
+                source
+            ));
+        };
+        self.copy_lua_bundle_from_directory_with_progress(
+            source_dir,
+            staging,
+            &bundle_entries,
+            ctx,
+            true,
+        )?;
+
+        Ok(bundle_entries)
+    }
+
     /// Delete source file after successful installation
     /// Checks if the source path is a parent directory of the original input path
     /// to avoid deleting directories that contain the detected addon
@@ -2393,8 +2696,23 @@ impl Installer {
             return Ok(());
         }
 
-        // Delete the source file/directory
-        if original_path.is_file() {
+        // Delete the source file/directory; a split archive goes with all its volumes
+        let split_parts = crate::archive_input::split_archive_parts(original_path);
+        if original_path.is_file() && split_parts.len() > 1 {
+            logger::log_info(
+                &format!(
+                    "Deleting {} volume(s) of split source archive: {}",
+                    split_parts.len(),
+                    original_input_path
+                ),
+                Some("installer"),
+            );
+            for part in &split_parts {
+                fs::remove_file(part).with_context(|| {
+                    format!("Failed to delete split archive part: {}", part.display())
+                })?;
+            }
+        } else if original_path.is_file() {
             logger::log_info(
                 &format!("Deleting source file: {}", original_input_path),
                 Some("installer"),
@@ -2469,6 +2787,44 @@ mod tests {
         entries
     }
 
+    #[test]
+    fn lua_modules_companions_go_to_the_flywithlua_modules_folder() {
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        let source = temp.path().join("download");
+        fs::create_dir_all(source.join("Modules/sgs")).expect("failed to create modules");
+        fs::create_dir_all(source.join("SGS")).expect("failed to create data folder");
+        fs::write(source.join("SGS.lua"), b"require('LIP')").expect("write script");
+        fs::write(source.join("Modules/LIP.lua"), b"return {}").expect("write module");
+        fs::write(source.join("Modules/sgs/ui.lua"), b"return {}").expect("write module");
+        let fwl = temp.path().join("Resources/plugins/FlyWithLua");
+        let scripts_dir = fwl.join("Scripts");
+        fs::create_dir_all(fwl.join("Modules")).expect("failed to create modules");
+        fs::write(fwl.join("Modules/other.lua"), b"kept").expect("write module");
+
+        let entries = vec![
+            PathBuf::from("SGS.lua"),
+            PathBuf::from("SGS"),
+            PathBuf::from("Modules"),
+        ];
+        let (scripts, modules) = partition_lua_bundle(&scripts_dir, entries.clone());
+        assert_eq!(
+            scripts,
+            vec![PathBuf::from("SGS.lua"), PathBuf::from("SGS")]
+        );
+        assert_eq!(modules, vec![PathBuf::from("Modules")]);
+
+        merge_lua_modules(&source, &scripts_dir, &modules).expect("merge failed");
+        assert!(fwl.join("Modules/LIP.lua").is_file());
+        assert!(fwl.join("Modules/sgs/ui.lua").is_file());
+        assert_eq!(fs::read(fwl.join("Modules/other.lua")).unwrap(), b"kept");
+        assert!(!scripts_dir.join("Modules").exists());
+
+        // Outside FlyWithLua's Scripts folder the bundle keeps its layout
+        let (scripts, modules) = partition_lua_bundle(&temp.path().join("Lua"), entries.clone());
+        assert_eq!(scripts, entries);
+        assert!(modules.is_empty());
+    }
+
     #[test]
     fn load_nested_zip_layers_keeps_current_archive_when_no_remaining_layers() {
         let outer_zip = build_zip(&[("plane.acf", b"acf")]);
@@ -2488,6 +2844,7 @@ mod tests {
             internal_path: "nested/inner.zip".to_string(),
             password: None,
             format: "zip".to_string(),
+            password_required: false,
         }];
 
         let (result, password) =
@@ -0,0 +1,539 @@
+//! Symbolic links stored in archives
+//!
+//! A link entry is never written while files are extracted: its target is
+//! recorded instead, and the link is only considered once every file is in
+//! place, so no later entry can be written through it. By default link
+//! entries are skipped and each one is reported as a task warning. With the
+//! task's `allow_symlinks` option a link is created when its target, resolved
+//! from the link's own folder, stays inside the install target. Absolute
+//! targets, drive letters and `..` steps above the target are refused. The
+//! link's folder and every step of the target are resolved against the disk,
+//! each existing step canonicalized and checked like
+//! [`crate::path_utils::validate_child_path`], so a link can't escape through
+//! a link created before it either. The link itself is created in the real
+//! folder its target was resolved from.
+//!
+//! Hard links go through the same policy. Their target is the path of another
+//! entry, resolved from the install target, and must be a file inside it.
+//!
+//! Links a directory copy finds in its source (RAR staging folders, the
+//! external 7z fallback) go through the same policy. On unix, files hard-linked
+//! to each other there become hard link entries, and a file also linked from
+//! outside the staging folder is refused.
+
+use super::*;
+use crate::path_utils;
+use std::ffi::OsStr;
+use std::io::{self, Read};
+
+/// File type bits of a unix mode
+const S_IFMT: u32 = 0o170000;
+
+/// File type of a symbolic link in a unix mode
+const S_IFLNK: u32 = 0o120000;
+
+/// Windows attribute of symbolic links and junctions
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+
+/// 7z attribute flag: the high 16 bits hold a unix mode
+const FILE_ATTRIBUTE_UNIX_EXTENSION: u32 = 0x8000;
+
+/// Longest link target read from an archive
+const MAX_LINK_TARGET_LEN: u64 = 4096;
+
+/// Kind of a link entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum LinkKind {
+    /// Target relative to the link's own folder
+    Symbolic,
+    /// Target relative to the extraction target
+    Hard,
+}
+
+impl LinkKind {
+    fn label(self) -> &'static str {
+        match self {
+            LinkKind::Symbolic => "symbolic link",
+            LinkKind::Hard => "hard link",
+        }
+    }
+}
+
+/// A link entry of an archive, relative to the extraction target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ArchiveLink {
+    pub relative: PathBuf,
+    pub target: String,
+    pub kind: LinkKind,
+}
+
+pub(super) fn is_symlink_mode(mode: u32) -> bool {
+    mode & S_IFMT == S_IFLNK
+}
+
+/// Whether 7z entry attributes mark a symbolic link
+pub(super) fn is_7z_symlink(attributes: u32) -> bool {
+    if attributes & FILE_ATTRIBUTE_UNIX_EXTENSION != 0 {
+        is_symlink_mode(attributes >> 16)
+    } else {
+        attributes & FILE_ATTRIBUTE_REPARSE_POINT != 0
+    }
+}
+
+/// Whether RAR entry attributes mark a symbolic link. Archives made on unix
+/// store a unix mode, those made on Windows store Windows attributes.
+pub(super) fn is_rar_symlink(attributes: u32) -> bool {
+    is_symlink_mode(attributes)
+        || (attributes & S_IFMT == 0 && attributes & FILE_ATTRIBUTE_REPARSE_POINT != 0)
+}
+
+/// Link target stored as the content of a link entry
+pub(super) fn read_link_target(reader: impl Read) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    reader.take(MAX_LINK_TARGET_LEN).read_to_end(&mut bytes)?;
+    Ok(String::from_utf8_lossy(&bytes)
+        .trim_end_matches('\0')
+        .to_string())
+}
+
+/// Targets of the ZIP link entries `(index, relative path, encrypted)`
+pub(super) fn read_zip_links<R: Read + io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    entries: Vec<(usize, PathBuf, bool)>,
+    password: Option<&[u8]>,
+) -> Result<Vec<ArchiveLink>> {
+    let mut links = Vec::new();
+    for (index, relative, is_encrypted) in entries {
+        let target = if is_encrypted {
+            let pwd = password.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Password required for encrypted file: {}",
+                    relative.display()
+                )
+            })?;
+            read_link_target(archive.by_index_decrypt(index, pwd)?)?
+        } else {
+            read_link_target(archive.by_index(index)?)?
+        };
+        links.push(ArchiveLink {
+            relative,
+            target,
+            kind: LinkKind::Symbolic,
+        });
+    }
+    Ok(links)
+}
+
+fn is_absolute_target(target: &str) -> bool {
+    let bytes = target.as_bytes();
+    target.starts_with('/')
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+/// Step from `path` to `name`, following a link already on disk. None when the
+/// step leaves `canonical_root`.
+fn step_into(canonical_root: &Path, path: PathBuf, name: &OsStr) -> Option<PathBuf> {
+    let next = path.join(name);
+    let next = match fs::symlink_metadata(&next) {
+        Ok(_) => next.canonicalize().ok()?,
+        Err(_) => next,
+    };
+    next.starts_with(canonical_root).then_some(next)
+}
+
+/// Real folder of the link at `relative`: canonical for the part that exists
+fn real_link_folder(canonical_root: &Path, relative: &Path) -> Option<PathBuf> {
+    let mut folder = canonical_root.to_path_buf();
+    for component in relative.parent()?.components() {
+        match component {
+            Component::Normal(name) => folder = step_into(canonical_root, folder, name)?,
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(folder)
+}
+
+/// Follow `target` from `start`, None when any step leaves `canonical_root`
+fn follow_target(canonical_root: &Path, start: PathBuf, target: &str) -> Option<PathBuf> {
+    let mut path = start;
+    for part in target.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if !path.pop() || !path.starts_with(canonical_root) {
+                    return None;
+                }
+            }
+            name => path = step_into(canonical_root, path, OsStr::new(name))?,
+        }
+    }
+    (path != canonical_root).then_some(path)
+}
+
+/// Real folder of the link at `relative` inside `root` and the path `target`
+/// points to from there, or None when either leaves `root`
+fn resolve_link(
+    root: &Path,
+    relative: &Path,
+    target: &str,
+    kind: LinkKind,
+) -> Option<(PathBuf, PathBuf)> {
+    let target = target.replace('\\', "/");
+    if target.is_empty() || is_absolute_target(&target) {
+        return None;
+    }
+    let canonical_root = root.canonicalize().ok()?;
+    let folder = real_link_folder(&canonical_root, relative)?;
+    let start = match kind {
+        LinkKind::Symbolic => folder.clone(),
+        LinkKind::Hard => canonical_root.clone(),
+    };
+    let resolved = follow_target(&canonical_root, start, &target)?;
+    Some((folder, resolved))
+}
+
+/// Path the symbolic link `target` points to from the link at `relative`
+/// inside `root`, or None when it leaves `root`
+pub(super) fn resolve_link_target(root: &Path, relative: &Path, target: &str) -> Option<PathBuf> {
+    resolve_link(root, relative, target, LinkKind::Symbolic).map(|(_, resolved)| resolved)
+}
+
+#[cfg(unix)]
+fn symlink(target: &str, _resolved: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target.replace('\\', "/"), link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &str, resolved: &Path, link: &Path) -> io::Result<()> {
+    let target = target.replace('/', "\\");
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+fn create_link(root: &Path, link: &ArchiveLink) -> std::result::Result<(), String> {
+    let (folder, resolved) = resolve_link(root, &link.relative, &link.target, link.kind)
+        .ok_or_else(|| "it points outside the install target".to_string())?;
+    let name = link
+        .relative
+        .file_name()
+        .ok_or_else(|| "it has no file name".to_string())?;
+    fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+    path_utils::validate_child_path(root, &folder)
+        .map_err(|_| "its folder is outside the install target".to_string())?;
+    let link_path = folder.join(name);
+    if fs::symlink_metadata(&link_path).is_ok() {
+        return Err("a file with that name already exists".to_string());
+    }
+    match link.kind {
+        LinkKind::Symbolic => symlink(&link.target, &resolved, &link_path),
+        LinkKind::Hard => {
+            if !fs::metadata(&resolved).is_ok_and(|meta| meta.is_file()) {
+                return Err("its target is not a file of the install".to_string());
+            }
+            fs::hard_link(&resolved, &link_path)
+        }
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Files hard-linked to each other among the `entries` of a staging folder
+/// `source`, as hard link entries to the first path of each file. A file with
+/// links outside `source` gets an empty target so it is refused.
+#[cfg(unix)]
+pub(super) fn staged_hard_links(source: &Path, entries: &[walkdir::DirEntry]) -> Vec<ArchiveLink> {
+    use std::collections::HashMap;
+    use std::os::unix::fs::MetadataExt;
+
+    let mut files: HashMap<(u64, u64), (u64, Vec<PathBuf>)> = HashMap::new();
+    for entry in entries.iter().filter(|entry| entry.file_type().is_file()) {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.nlink() < 2 {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(source) else {
+            continue;
+        };
+        files
+            .entry((meta.dev(), meta.ino()))
+            .or_insert_with(|| (meta.nlink(), Vec::new()))
+            .1
+            .push(relative.to_path_buf());
+    }
+
+    let mut links = Vec::new();
+    for (nlink, mut paths) in files.into_values() {
+        paths.sort();
+        let linked_outside = (paths.len() as u64) < nlink;
+        let first = paths[0].to_string_lossy().replace('\\', "/");
+        let skip = if linked_outside { 0 } else { 1 };
+        for relative in paths.into_iter().skip(skip) {
+            links.push(ArchiveLink {
+                relative,
+                target: if linked_outside {
+                    String::new()
+                } else {
+                    first.clone()
+                },
+                kind: LinkKind::Hard,
+            });
+        }
+    }
+    links.sort_by(|a, b| a.relative.cmp(&b.relative));
+    links
+}
+
+#[cfg(not(unix))]
+pub(super) fn staged_hard_links(
+    _source: &Path,
+    _entries: &[walkdir::DirEntry],
+) -> Vec<ArchiveLink> {
+    Vec::new()
+}
+
+/// Create `links` inside `root` when `allow` is set and they stay inside it.
+/// Returns a warning for every link that was skipped.
+pub(super) fn create_links(root: &Path, links: &[ArchiveLink], allow: bool) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for link in links {
+        let shown = link.relative.to_string_lossy().replace('\\', "/");
+        if !allow {
+            warnings.push(format!(
+                "Skipped {} '{}' -> '{}': links are not allowed for this install",
+                link.kind.label(),
+                shown,
+                link.target
+            ));
+            continue;
+        }
+        if let Err(reason) = create_link(root, link) {
+            warnings.push(format!(
+                "Skipped {} '{}' -> '{}': {}",
+                link.kind.label(),
+                shown,
+                link.target,
+                reason
+            ));
+        }
+    }
+    warnings
+}
+
+/// Apply the task's link policy to links found while extracting into `root`
+pub(super) fn apply_archive_links(root: &Path, links: &[ArchiveLink], ctx: &ProgressContext) {
+    if links.is_empty() {
+        return;
+    }
+    let warnings = create_links(root, links, ctx.allow_symlinks.load(Ordering::SeqCst));
+    for warning in &warnings {
+        logger::log_info(warning, Some("installer"));
+    }
+    ctx.link_warnings.lock().unwrap().extend(warnings);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// ZIP holding a file and symlinks with absolute, escaping and inner targets
+    fn zip_with_links(path: &Path) {
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        writer.start_file("Pack/objects/tree.obj", options).unwrap();
+        writer.write_all(b"A\n800\nOBJ").unwrap();
+        writer
+            .add_symlink("Pack/passwd", "/etc/passwd", options)
+            .unwrap();
+        writer
+            .add_symlink("Pack/objects/escape", "../../..", options)
+            .unwrap();
+        writer
+            .add_symlink("Pack/tree.obj", "objects/tree.obj", options)
+            .unwrap();
+        writer.finish().unwrap();
+    }
+
+    fn links_in(path: &Path) -> Vec<ArchiveLink> {
+        let mut archive = zip::ZipArchive::new(fs::File::open(path).unwrap()).unwrap();
+        let entries = (0..archive.len())
+            .filter_map(|i| {
+                let file = archive.by_index_raw(i).unwrap();
+                file.unix_mode().is_some_and(is_symlink_mode).then(|| {
+                    (
+                        i,
+                        file.enclosed_name().unwrap().to_path_buf(),
+                        file.encrypted(),
+                    )
+                })
+            })
+            .collect();
+        read_zip_links(&mut archive, entries, None).unwrap()
+    }
+
+    #[test]
+    fn test_zip_symlinks_are_skipped_by_default() {
+        let temp = tempfile::tempdir().unwrap();
+        let archive = temp.path().join("links.zip");
+        zip_with_links(&archive);
+        let root = temp.path().join("install");
+        fs::create_dir_all(&root).unwrap();
+
+        let links = links_in(&archive);
+        assert_eq!(links.len(), 3);
+        assert!(links
+            .iter()
+            .any(|link| link.target == "/etc/passwd" && link.relative == Path::new("Pack/passwd")));
+
+        let warnings = create_links(&root, &links, false);
+        assert_eq!(warnings.len(), 3);
+        assert!(fs::read_dir(&root).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn test_allowed_symlinks_must_stay_inside_the_target() {
+        let temp = tempfile::tempdir().unwrap();
+        let archive = temp.path().join("links.zip");
+        zip_with_links(&archive);
+        let root = temp.path().join("install");
+        fs::create_dir_all(root.join("Pack/objects")).unwrap();
+        fs::write(root.join("Pack/objects/tree.obj"), b"A\n800\nOBJ").unwrap();
+
+        let warnings = create_links(&root, &links_in(&archive), true);
+
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings
+            .iter()
+            .all(|w| w.contains("outside the install target")));
+        assert!(fs::symlink_metadata(root.join("Pack/passwd")).is_err());
+        assert!(fs::symlink_metadata(root.join("Pack/objects/escape")).is_err());
+        let inner = root.join("Pack/tree.obj");
+        assert!(fs::symlink_metadata(&inner)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read(&inner).unwrap(), b"A\n800\nOBJ");
+    }
+
+    #[test]
+    fn test_link_targets_resolve_from_the_link_folder() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("a/b")).unwrap();
+        let link = Path::new("a/b/link");
+
+        assert_eq!(
+            resolve_link_target(root, link, "../c.txt"),
+            Some(root.canonicalize().unwrap().join("a").join("c.txt"))
+        );
+        assert_eq!(resolve_link_target(root, link, "../../../x"), None);
+        assert_eq!(resolve_link_target(root, link, "/etc/passwd"), None);
+        assert_eq!(resolve_link_target(root, link, "C:\\Windows"), None);
+        assert_eq!(resolve_link_target(root, link, "..\\..\\..\\x"), None);
+        assert!(is_7z_symlink(
+            (S_IFLNK | 0o777) << 16 | FILE_ATTRIBUTE_UNIX_EXTENSION
+        ));
+        assert!(!is_7z_symlink(
+            0o100644 << 16 | FILE_ATTRIBUTE_UNIX_EXTENSION
+        ));
+        assert!(is_rar_symlink(S_IFLNK | 0o777));
+        assert!(!is_rar_symlink(0o100644));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_links_cannot_escape_through_an_existing_link() {
+        let temp = tempfile::tempdir().unwrap();
+        let outside = temp.path().join("outside");
+        let root = temp.path().join("install");
+        fs::create_dir_all(&outside).unwrap();
+        fs::create_dir_all(&root).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+
+        assert_eq!(
+            resolve_link_target(&root, Path::new("link"), "escape/x"),
+            None
+        );
+        let link = ArchiveLink {
+            relative: PathBuf::from("escape/link"),
+            target: "x".to_string(),
+        };
+        assert_eq!(create_links(&root, &[link], true).len(), 1);
+        assert!(fs::read_dir(&outside).unwrap().next().is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_chained_links_cannot_escape_the_target() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("install");
+        fs::create_dir_all(root.join("Pack/objects")).unwrap();
+        let links = [
+            ArchiveLink {
+                relative: PathBuf::from("Pack/objects/d"),
+                target: "..".to_string(),
+                kind: LinkKind::Symbolic,
+            },
+            // Text-wise Pack/x, on disk created in Pack/ and pointing above root
+            ArchiveLink {
+                relative: PathBuf::from("Pack/objects/d/l"),
+                target: "../../x".to_string(),
+                kind: LinkKind::Symbolic,
+            },
+        ];
+
+        let warnings = create_links(&root, &links, true);
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Pack/objects/d/l"));
+        assert!(fs::symlink_metadata(root.join("Pack/objects/d"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert!(fs::symlink_metadata(root.join("Pack/l")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hard_links_must_target_files_inside_the_target() {
+        let temp = tempfile::tempdir().unwrap();
+        let outside = temp.path().join("secret.txt");
+        fs::write(&outside, b"secret").unwrap();
+        let staging = temp.path().join("staging");
+        fs::create_dir_all(staging.join("Pack")).unwrap();
+        fs::write(staging.join("Pack/a.obj"), b"OBJ").unwrap();
+        fs::hard_link(staging.join("Pack/a.obj"), staging.join("Pack/b.obj")).unwrap();
+        fs::hard_link(&outside, staging.join("Pack/leak.txt")).unwrap();
+        let entries: Vec<_> = walkdir::WalkDir::new(&staging)
+            .into_iter()
+            .map(|entry| entry.unwrap())
+            .collect();
+
+        let links = staged_hard_links(&staging, &entries);
+        assert_eq!(links.len(), 2);
+        assert!(links.iter().all(|link| link.kind == LinkKind::Hard));
+
+        let root = temp.path().join("install");
+        fs::create_dir_all(root.join("Pack")).unwrap();
+        fs::write(root.join("Pack/a.obj"), b"OBJ").unwrap();
+        let escaping = ArchiveLink {
+            relative: PathBuf::from("Pack/up.txt"),
+            target: "../secret.txt".to_string(),
+            kind: LinkKind::Hard,
+        };
+        let mut all = links.clone();
+        all.push(escaping);
+        let warnings = create_links(&root, &all, true);
+
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| w.starts_with("Skipped hard link")));
+        assert_eq!(fs::read(root.join("Pack/b.obj")).unwrap(), b"OBJ");
+        assert!(fs::symlink_metadata(root.join("Pack/leak.txt")).is_err());
+        assert!(fs::symlink_metadata(root.join("Pack/up.txt")).is_err());
+    }
+}
@@ -1,29 +1,38 @@
 use anyhow::{Context, Result};
 use glob::Pattern;
 use sea_orm::DatabaseConnection;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 #[cfg(target_os = "windows")]
 use std::ffi::{OsStr, OsString};
 use std::fs;
 use std::path::{Component, Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 
 use crate::database::DatabaseState;
+use crate::error::{self, ApiErrorCode};
+use crate::fs_case::CaseVariantTracker;
+use crate::install_markers;
 use crate::logger;
 use crate::logger::{tr, LogMsg};
 use crate::models::{
     AddonType, InstallPhase, InstallProgress, InstallResult, InstallTask, ParallelTaskProgress,
-    TaskResult,
+    TargetRootKind, TaskResult,
 };
 use crate::task_control::TaskControl;
+use crate::user_messages::{LocalizedError, UserMsg};
 
 mod extraction;
 mod handlers;
+mod links;
+mod post_install;
+mod preflight;
 mod verification;
 
+pub(crate) use extraction::list_archive_files;
+
 /// Maximum allowed extraction size (20 GB) - archives larger than this will show a warning
 pub const MAX_EXTRACTION_SIZE: u64 = 20 * 1024 * 1024 * 1024;
 
@@ -231,7 +240,7 @@ fn remove_readonly_attribute(_path: &Path) -> Result<()> {
 
 /// Robustly remove a directory and all its contents, handling read-only files
 /// Includes retry logic with exponential backoff for Windows file locking issues
-fn remove_dir_all_robust(path: &Path) -> Result<()> {
+pub(crate) fn remove_dir_all_robust(path: &Path) -> Result<()> {
     if !path.exists() {
         return Ok(());
     }
@@ -323,6 +332,82 @@ struct AircraftProgressInstallParams<'a> {
     options: AircraftInstallOptions<'a>,
 }
 
+/// Shortest interval a speed sample may span. Progress is emitted every 16ms,
+/// far too often for the byte deltas between emits to mean anything.
+const SPEED_MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Samples older than this no longer count towards the speed estimate
+const SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+/// Sliding-window throughput estimate from processed byte counts
+#[derive(Default)]
+struct ThroughputEstimator {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl ThroughputEstimator {
+    /// Record the processed byte count at `now` and return bytes per second over
+    /// the window, or `None` until the window spans at least one sample interval
+    fn record(&mut self, now: Instant, processed: u64) -> Option<u64> {
+        let due = self.samples.back().is_none_or(|&(last, _)| {
+            now.saturating_duration_since(last) >= SPEED_MIN_SAMPLE_INTERVAL
+        });
+        if due {
+            self.samples.push_back((now, processed));
+            while self.samples.len() > 2
+                && self
+                    .samples
+                    .front()
+                    .is_some_and(|&(at, _)| now.saturating_duration_since(at) > SPEED_WINDOW)
+            {
+                self.samples.pop_front();
+            }
+        }
+
+        let (&(oldest_at, oldest_bytes), &(newest_at, newest_bytes)) =
+            (self.samples.front()?, self.samples.back()?);
+        let elapsed = newest_at.saturating_duration_since(oldest_at);
+        if elapsed < SPEED_MIN_SAMPLE_INTERVAL {
+            return None;
+        }
+        let delta = newest_bytes.saturating_sub(oldest_bytes);
+        Some((delta as f64 / elapsed.as_secs_f64()) as u64)
+    }
+}
+
+/// Point the task's expected hashes at the new paths of archive files renamed to
+/// avoid a case collision, and describe each rename for the task result
+fn take_case_collision_warnings(task: &mut InstallTask, ctx: &ProgressContext) -> Vec<String> {
+    let collisions = std::mem::take(&mut *ctx.case_collisions.lock().unwrap());
+    if let Some(hashes) = task.file_hashes.as_mut() {
+        for collision in &collisions {
+            if let Some(mut hash) = hashes.remove(&collision.colliding) {
+                hash.path = collision.renamed.clone();
+                hashes.insert(collision.renamed.clone(), hash);
+            }
+        }
+    }
+    collisions
+        .iter()
+        .map(|c| {
+            format!(
+                "Renamed '{}' to '{}': it differs only by case from '{}'",
+                c.colliding, c.renamed, c.kept
+            )
+        })
+        .collect()
+}
+
+/// Seconds until `remaining_bytes` are processed at `speed`. Omitted while sizes
+/// are being calculated or files verified, where byte throughput says nothing.
+fn estimate_eta(remaining_bytes: u64, speed: Option<u64>, phase: &InstallPhase) -> Option<u64> {
+    if matches!(phase, InstallPhase::Calculating | InstallPhase::Verifying) {
+        return None;
+    }
+    let speed = speed.filter(|&s| s > 0)?;
+    Some(remaining_bytes.div_ceil(speed))
+}
+
 /// Progress tracking context
 #[derive(Clone)]
 struct ProgressContext {
@@ -346,6 +431,8 @@ struct ProgressContext {
     inline_verified: Arc<std::sync::atomic::AtomicBool>,
     /// Inline verification stats: total files verified inline
     inline_verified_count: Arc<AtomicU64>,
+    /// Files whose hash already matched during extraction, skipped by verification
+    inline_verified_files: Arc<Mutex<HashSet<String>>>,
     /// Hashes computed inline during extraction (for 7z SHA256)
     inline_hashes: Arc<Mutex<HashMap<String, crate::models::FileHash>>>,
     /// Whether current task should compute 7z SHA256 inline during extraction.
@@ -355,6 +442,18 @@ struct ProgressContext {
     parallel_emit: Option<Arc<dyn Fn() + Send + Sync>>,
     /// Optional tracker current_file reference for parallel mode
     parallel_current_file: Option<Arc<Mutex<Option<String>>>>,
+    /// Whether the current task is decompressing a solid 7z block
+    in_solid_block: Arc<std::sync::atomic::AtomicBool>,
+    /// Overall throughput, sampled on emit
+    throughput: Arc<Mutex<ThroughputEstimator>>,
+    /// Whether the current task may rename archive files that collide by case
+    allow_case_collision_rename: Arc<std::sync::atomic::AtomicBool>,
+    /// Archive files the current task renamed to avoid a case collision
+    case_collisions: Arc<Mutex<Vec<crate::fs_case::CaseCollision>>>,
+    /// Whether the current task may create archive symlinks that stay inside its target
+    allow_symlinks: Arc<std::sync::atomic::AtomicBool>,
+    /// Archive links the current task skipped
+    link_warnings: Arc<Mutex<Vec<String>>>,
 }
 
 impl ProgressContext {
@@ -373,10 +472,17 @@ impl ProgressContext {
             max_percentage: Arc::new(AtomicU64::new(0)),
             inline_verified: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             inline_verified_count: Arc::new(AtomicU64::new(0)),
+            inline_verified_files: Arc::new(Mutex::new(HashSet::new())),
             inline_hashes: Arc::new(Mutex::new(HashMap::new())),
             inline_hash_collection_enabled: Arc::new(std::sync::atomic::AtomicBool::new(true)),
             parallel_emit: None,
             parallel_current_file: None,
+            in_solid_block: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            throughput: Arc::new(Mutex::new(ThroughputEstimator::default())),
+            allow_case_collision_rename: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            case_collisions: Arc::new(Mutex::new(Vec::new())),
+            allow_symlinks: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            link_warnings: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -414,6 +520,14 @@ impl ProgressContext {
         stored as f64 / 100.0
     }
 
+    fn set_in_solid_block(&self, in_solid_block: bool) {
+        self.in_solid_block.store(in_solid_block, Ordering::SeqCst);
+    }
+
+    fn solid_block_flag(&self) -> Option<bool> {
+        self.in_solid_block.load(Ordering::SeqCst).then_some(true)
+    }
+
     fn emit_progress(&self, current_file: Option<String>, phase: InstallPhase) {
         self.emit_progress_internal(current_file, phase, false);
     }
@@ -467,6 +581,13 @@ impl ProgressContext {
 
         let total = self.total_bytes.load(Ordering::SeqCst);
         let processed = self.processed_bytes.load(Ordering::SeqCst);
+        let speed_bytes_per_sec = self
+            .throughput
+            .lock()
+            .ok()
+            .and_then(|mut t| t.record(Instant::now(), processed));
+        let eta_seconds =
+            estimate_eta(total.saturating_sub(processed), speed_bytes_per_sec, &phase);
 
         // Get current task's size and cumulative bytes
         let task_size = self
@@ -487,6 +608,16 @@ impl ProgressContext {
         // Each task gets a proportional share of 0-100% based on its size
         let (raw_percentage, verification_progress, current_task_percentage) = match phase {
             InstallPhase::Finalizing => (100.0, None, 100.0),
+            InstallPhase::PreflightCheck => {
+                let total_f = total as f64;
+                if total_f == 0.0 {
+                    return;
+                }
+
+                // Nothing is extracted yet: stay at the start of the current task
+                let base_pct = (cumulative as f64 / total_f) * 100.0;
+                (base_pct, Some(self.get_verification_progress()), 0.0)
+            }
             InstallPhase::Verifying => {
                 let verify_progress = self.get_verification_progress();
                 let total_f = total as f64;
@@ -597,6 +728,9 @@ impl ProgressContext {
             // This helps frontend show completed tasks more reliably
             completed_task_count: Some(self.current_task_index),
             completed_task_ids: None,
+            in_solid_block: self.solid_block_flag(),
+            speed_bytes_per_sec,
+            eta_seconds,
         };
 
         let _ = self.app_handle.emit("install-progress", &progress);
@@ -649,6 +783,9 @@ impl ProgressContext {
             active_tasks: None,
             completed_task_count: Some(self.total_tasks), // All tasks completed
             completed_task_ids: None,
+            in_solid_block: None,
+            speed_bytes_per_sec: None,
+            eta_seconds: None,
         };
 
         let _ = self.app_handle.emit("install-progress", &progress);
@@ -657,6 +794,48 @@ impl ProgressContext {
 
 // ========== Parallel Installation Support ==========
 
+/// A task of a parallel batch that has to finish before another one starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TaskDependency {
+    index: usize,
+    /// The waiting task is skipped if this one fails, e.g. a livery whose
+    /// aircraft did not install
+    required: bool,
+}
+
+/// Work out which tasks of a parallel batch have to wait for others, given each
+/// task's type and target: a livery waits for the task installing the aircraft
+/// folder it goes into, and tasks writing the same target run in list order
+fn task_dependencies(targets: &[(&AddonType, &Path)]) -> Vec<Vec<TaskDependency>> {
+    targets
+        .iter()
+        .enumerate()
+        .map(|(i, (addon_type, target))| {
+            let mut deps = Vec::new();
+            if let Some(previous) = targets[..i].iter().rposition(|(_, other)| other == target) {
+                deps.push(TaskDependency {
+                    index: previous,
+                    required: false,
+                });
+            }
+            if **addon_type == AddonType::Livery {
+                for (j, (other_type, other)) in targets.iter().enumerate() {
+                    if **other_type != AddonType::Livery
+                        && target != other
+                        && target.starts_with(other)
+                    {
+                        deps.push(TaskDependency {
+                            index: j,
+                            required: true,
+                        });
+                    }
+                }
+            }
+            deps
+        })
+        .collect()
+}
+
 /// Per-task progress tracker for parallel installation
 struct TaskTracker {
     index: usize,
@@ -664,14 +843,19 @@ struct TaskTracker {
     name: String,
     total_bytes: u64,
     processed_bytes: Arc<AtomicU64>,
-    /// 0=waiting, 1=installing, 2=verifying, 3=done, 4=failed
+    /// 0=queued, 1=installing, 2=verifying, 3=done, 4=failed, 5=pre-flight check,
+    /// 6=waiting for a task it depends on
     phase: std::sync::atomic::AtomicU8,
     verification_progress: Arc<AtomicU64>,
     current_file: Arc<Mutex<Option<String>>>,
     inline_verified: Arc<std::sync::atomic::AtomicBool>,
     inline_verified_count: Arc<AtomicU64>,
+    inline_verified_files: Arc<Mutex<HashSet<String>>>,
     inline_hashes: Arc<Mutex<HashMap<String, crate::models::FileHash>>>,
     inline_hash_collection_enabled: Arc<std::sync::atomic::AtomicBool>,
+    in_solid_block: Arc<std::sync::atomic::AtomicBool>,
+    /// This task's throughput, sampled on aggregated emit
+    throughput: Mutex<ThroughputEstimator>,
 }
 
 /// Aggregated parallel progress context
@@ -685,6 +869,8 @@ struct ParallelProgressContext {
     last_emit: Arc<Mutex<Instant>>,
     /// Maximum percentage reached, prevents progress from going backward
     max_percentage: AtomicU64,
+    /// Throughput across all tasks, sampled on aggregated emit
+    throughput: Mutex<ThroughputEstimator>,
 }
 
 impl ParallelProgressContext {
@@ -710,10 +896,13 @@ impl ParallelProgressContext {
                     current_file: Arc::new(Mutex::new(None)),
                     inline_verified: Arc::new(std::sync::atomic::AtomicBool::new(false)),
                     inline_verified_count: Arc::new(AtomicU64::new(0)),
+                    inline_verified_files: Arc::new(Mutex::new(HashSet::new())),
                     inline_hashes: Arc::new(Mutex::new(HashMap::new())),
                     inline_hash_collection_enabled: Arc::new(std::sync::atomic::AtomicBool::new(
                         true,
                     )),
+                    in_solid_block: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+                    throughput: Mutex::new(ThroughputEstimator::default()),
                 })
             })
             .collect();
@@ -726,6 +915,7 @@ impl ParallelProgressContext {
             completed_count: AtomicU64::new(0),
             last_emit: Arc::new(Mutex::new(Instant::now())),
             max_percentage: AtomicU64::new(0),
+            throughput: Mutex::new(ThroughputEstimator::default()),
         }
     }
 
@@ -792,8 +982,8 @@ impl ParallelProgressContext {
             let processed = tracker.processed_bytes.load(Ordering::SeqCst);
 
             let phase_val = tracker.phase.load(std::sync::atomic::Ordering::SeqCst);
-            if phase_val == 1 || phase_val == 2 {
-                // installing or verifying
+            if phase_val == 1 || phase_val == 2 || phase_val == 5 {
+                // installing, verifying or pre-flight checking
                 let task_total = tracker.total_bytes;
 
                 // For overall percentage: if verifying/done, extraction is complete
@@ -817,11 +1007,21 @@ impl ParallelProgressContext {
 
                 let current_file = tracker.current_file.lock().ok().and_then(|f| f.clone());
 
-                let phase = if phase_val == 2 {
-                    InstallPhase::Verifying
-                } else {
-                    InstallPhase::Installing
+                let phase = match phase_val {
+                    2 => InstallPhase::Verifying,
+                    5 => InstallPhase::PreflightCheck,
+                    _ => InstallPhase::Installing,
                 };
+                let speed_bytes_per_sec = tracker
+                    .throughput
+                    .lock()
+                    .ok()
+                    .and_then(|mut t| t.record(now, processed));
+                let eta_seconds = estimate_eta(
+                    task_total.saturating_sub(processed),
+                    speed_bytes_per_sec,
+                    &phase,
+                );
 
                 active_tasks.push(ParallelTaskProgress {
                     task_id: tracker.id.clone(),
@@ -830,6 +1030,12 @@ impl ParallelProgressContext {
                     phase,
                     percentage: pct,
                     current_file: current_file.clone(),
+                    in_solid_block: tracker
+                        .in_solid_block
+                        .load(Ordering::SeqCst)
+                        .then_some(true),
+                    speed_bytes_per_sec,
+                    eta_seconds,
                 });
 
                 if !found_active {
@@ -838,6 +1044,19 @@ impl ParallelProgressContext {
                     first_active_file = current_file;
                     found_active = true;
                 }
+            } else if phase_val == 6 {
+                // Not started: listed so the UI shows it as waiting, not as 0% installing
+                active_tasks.push(ParallelTaskProgress {
+                    task_id: tracker.id.clone(),
+                    task_index: tracker.index,
+                    task_name: tracker.name.clone(),
+                    phase: InstallPhase::Waiting,
+                    percentage: 0.0,
+                    current_file: None,
+                    in_solid_block: None,
+                    speed_bytes_per_sec: None,
+                    eta_seconds: None,
+                });
             } else if phase_val == 3 {
                 // Task completed successfully — count full size as processed
                 total_processed += tracker.total_bytes;
@@ -861,11 +1080,22 @@ impl ParallelProgressContext {
         self.max_percentage.fetch_max(new_max, Ordering::SeqCst);
 
         // Current task fields for backwards compatibility
-        let current_task_percentage = if !active_tasks.is_empty() {
-            active_tasks[0].percentage
-        } else {
-            0.0
-        };
+        let first_running = active_tasks
+            .iter()
+            .find(|t| !matches!(t.phase, InstallPhase::Waiting));
+        let current_task_percentage = first_running.map(|t| t.percentage).unwrap_or(0.0);
+        let in_solid_block = first_running.and_then(|t| t.in_solid_block);
+
+        let speed_bytes_per_sec = self
+            .throughput
+            .lock()
+            .ok()
+            .and_then(|mut t| t.record(now, total_processed));
+        let eta_seconds = estimate_eta(
+            total_bytes.saturating_sub(total_processed),
+            speed_bytes_per_sec,
+            &InstallPhase::Installing,
+        );
 
         crate::log_debug!(
             &format!(
@@ -895,6 +1125,9 @@ impl ParallelProgressContext {
             active_tasks: Some(active_tasks),
             completed_task_count: Some(completed),
             completed_task_ids: Some(completed_task_ids),
+            in_solid_block,
+            speed_bytes_per_sec,
+            eta_seconds,
         };
 
         let _ = self.app_handle.emit("install-progress", &progress);
@@ -936,6 +1169,9 @@ impl ParallelProgressContext {
             active_tasks: Some(Vec::new()),
             completed_task_count: Some(completed),
             completed_task_ids: Some(completed_task_ids),
+            in_solid_block: None,
+            speed_bytes_per_sec: None,
+            eta_seconds: None,
         };
 
         let _ = self.app_handle.emit("install-progress", &progress);
@@ -993,9 +1229,11 @@ impl TaskProgressView {
         ctx.verification_progress = Arc::clone(&self.tracker.verification_progress);
         ctx.inline_verified = Arc::clone(&self.tracker.inline_verified);
         ctx.inline_verified_count = Arc::clone(&self.tracker.inline_verified_count);
+        ctx.inline_verified_files = Arc::clone(&self.tracker.inline_verified_files);
         ctx.inline_hashes = Arc::clone(&self.tracker.inline_hashes);
         ctx.inline_hash_collection_enabled =
             Arc::clone(&self.tracker.inline_hash_collection_enabled);
+        ctx.in_solid_block = Arc::clone(&self.tracker.in_solid_block);
 
         // Set up task sizes so percentage calculations work correctly
         let mut sizes = vec![0u64; self.parent.total_tasks];
@@ -1020,10 +1258,49 @@ impl TaskProgressView {
     }
 }
 
+/// Failure message of a task, with its catalog key and arguments when it
+/// came from the message catalog
+struct TaskFailure {
+    message: String,
+    key: Option<String>,
+    args: Vec<String>,
+}
+
+impl From<LocalizedError> for TaskFailure {
+    fn from(err: LocalizedError) -> Self {
+        Self {
+            message: err.to_string(),
+            key: Some(err.msg.key().to_string()),
+            args: err.args,
+        }
+    }
+}
+
+impl From<UserMsg> for TaskFailure {
+    fn from(msg: UserMsg) -> Self {
+        msg.error().into()
+    }
+}
+
+impl From<&anyhow::Error> for TaskFailure {
+    fn from(err: &anyhow::Error) -> Self {
+        match error::localized_message(err) {
+            Some(message) => message.clone().into(),
+            None => Self {
+                message: err.to_string(),
+                key: None,
+                args: Vec::new(),
+            },
+        }
+    }
+}
+
 pub struct Installer {
     app_handle: AppHandle,
     task_control: TaskControl,
     db: DatabaseConnection,
+    /// Verify source archives before any target modification
+    preflight_check: bool,
 }
 
 impl Installer {
@@ -1035,9 +1312,16 @@ impl Installer {
             app_handle,
             task_control,
             db,
+            preflight_check: false,
         }
     }
 
+    /// Enable the pre-flight source archive check for each task
+    pub fn with_preflight_check(mut self, enabled: bool) -> Self {
+        self.preflight_check = enabled;
+        self
+    }
+
     fn collect_source_cleanup_candidates(tasks: &[InstallTask]) -> Vec<SourceCleanupCandidate> {
         tasks
             .iter()
@@ -1118,6 +1402,30 @@ impl Installer {
         }
     }
 
+    /// Point targets inside X-Plane at existing folders whose name differs only by case
+    /// (case-insensitive volumes), so logs, verification and the scenery index all
+    /// use the on-disk spelling instead of the archive's
+    fn normalize_target_casing(tasks: &mut [InstallTask], xplane_path: &str) {
+        let xplane_root = Path::new(xplane_path);
+        for task in tasks.iter_mut() {
+            let target = Path::new(&task.target_path);
+            let Ok(relative) = target.strip_prefix(xplane_root) else {
+                continue;
+            };
+            if let Some(resolved) = crate::fs_case::resolve_existing_case(xplane_root, relative) {
+                logger::log_info(
+                    &format!(
+                        "Target {} already exists as {}, installing into the existing folder",
+                        task.target_path,
+                        resolved.display()
+                    ),
+                    Some("installer"),
+                );
+                task.target_path = resolved.to_string_lossy().to_string();
+            }
+        }
+    }
+
     /// Install a list of tasks with progress reporting
     pub async fn install(
         &self,
@@ -1150,6 +1458,9 @@ impl Installer {
 
         // Reset task control at start of installation
         self.task_control.reset();
+        let _active_install = self.task_control.begin_install();
+        Self::normalize_target_casing(&mut tasks, &xplane_path);
+        self.check_targets_writable(&tasks)?;
 
         let mut ctx = ProgressContext::new(self.app_handle.clone(), tasks.len());
         let mut task_results = Vec::new();
@@ -1157,6 +1468,7 @@ impl Installer {
         let mut failed = 0;
         let mut cancelled = 0;
         let mut skipped = 0;
+        let mut scenery_installed = false;
         let source_cleanup_candidates = Self::collect_source_cleanup_candidates(&tasks);
 
         // Phase 1: Calculate total size
@@ -1189,12 +1501,21 @@ impl Installer {
                 // Mark remaining tasks as cancelled
                 for remaining_task in tasks.iter().skip(index) {
                     cancelled += 1;
+                    let failure = TaskFailure::from(UserMsg::TaskCancelled);
                     task_results.push(TaskResult {
                         task_id: remaining_task.id.clone(),
                         task_name: remaining_task.display_name.clone(),
                         success: false,
-                        error_message: Some("Cancelled by user".to_string()),
+                        error_message: Some(failure.message),
+                        error_message_key: failure.key,
+                        error_message_args: failure.args,
+                        error_code: Some(ApiErrorCode::Cancelled),
                         verification_stats: None,
+                        preflight_bytes_read: None,
+                        failed_stage: None,
+                        warnings: Vec::new(),
+                        skipped_identical: false,
+                        failure_detail: None,
                     });
                 }
                 break;
@@ -1221,7 +1542,10 @@ impl Installer {
             ctx.inline_verified
                 .store(false, std::sync::atomic::Ordering::SeqCst);
             ctx.inline_verified_count.store(0, Ordering::SeqCst);
+            ctx.inline_verified_files.lock().unwrap().clear();
             ctx.inline_hashes.lock().unwrap().clear();
+            ctx.case_collisions.lock().unwrap().clear();
+            ctx.link_warnings.lock().unwrap().clear();
 
             // Force emit progress to ensure frontend sees the task state change immediately
             ctx.emit_progress_force(None, InstallPhase::Installing);
@@ -1236,9 +1560,67 @@ impl Installer {
                 Some("installer"),
             );
 
+            // Pre-flight source check runs before the target is touched or
+            // registered for cleanup, so a failure leaves the target intact
+            let mut preflight_bytes_read = None;
+            if self.preflight_check {
+                ctx.set_verification_progress(0.0);
+                ctx.emit_progress_force(None, InstallPhase::PreflightCheck);
+                match self.preflight_check_source(task, &ctx) {
+                    Ok(bytes_read) => preflight_bytes_read = Some(bytes_read),
+                    Err(e) => {
+                        let error_code = if self.task_control.is_cancelled()
+                            || self.task_control.is_skip_requested()
+                        {
+                            ApiErrorCode::Cancelled
+                        } else {
+                            error::error_code(&e)
+                        };
+                        let failure = if self.task_control.is_cancelled() {
+                            cancelled += 1;
+                            TaskFailure::from(UserMsg::TaskCancelled)
+                        } else if self.task_control.is_skip_requested() {
+                            skipped += 1;
+                            self.task_control.reset_skip();
+                            TaskFailure::from(UserMsg::TaskSkipped)
+                        } else {
+                            failed += 1;
+                            logger::log_error(
+                                &format!(
+                                    "{} {}: {}",
+                                    tr(LogMsg::InstallationFailed),
+                                    task.display_name,
+                                    e
+                                ),
+                                Some("installer"),
+                            );
+                            TaskFailure::from(&e)
+                        };
+                        task_results.push(TaskResult {
+                            task_id: task.id.clone(),
+                            task_name: task.display_name.clone(),
+                            success: false,
+                            error_message: Some(failure.message),
+                            error_message_key: failure.key,
+                            error_message_args: failure.args,
+                            error_code: Some(error_code),
+                            verification_stats: None,
+                            preflight_bytes_read: None,
+                            failed_stage: None,
+                            warnings: Vec::new(),
+                            skipped_identical: false,
+                            failure_detail: None,
+                        });
+                        continue;
+                    }
+                }
+                ctx.emit_progress_force(None, InstallPhase::Installing);
+            }
+
             // Track target path for potential cleanup
             self.task_control
                 .add_processed_path(PathBuf::from(&task.target_path));
+            let marker = install_markers::begin(&self.db, task, &xplane_path).await;
 
             match self.install_task_with_progress(task, &ctx, atomic_install_enabled, &xplane_path)
             {
@@ -1254,6 +1636,8 @@ impl Installer {
                                 .store(inline.len() as u64, Ordering::SeqCst);
                         }
                     }
+                    let mut warnings = take_case_collision_warnings(task, &ctx);
+                    warnings.append(&mut ctx.link_warnings.lock().unwrap());
 
                     // Check for skip request after installation but before verification
                     if self.task_control.is_skip_requested() {
@@ -1271,16 +1655,26 @@ impl Installer {
                         }
 
                         skipped += 1;
+                        let failure = TaskFailure::from(UserMsg::TaskSkipped);
                         task_results.push(TaskResult {
                             task_id: task.id.clone(),
                             task_name: task.display_name.clone(),
                             success: false,
-                            error_message: Some("Skipped by user".to_string()),
+                            error_message: Some(failure.message),
+                            error_message_key: failure.key,
+                            error_message_args: failure.args,
+                            error_code: Some(ApiErrorCode::Cancelled),
                             verification_stats: None,
+                            preflight_bytes_read,
+                            failed_stage: None,
+                            warnings: Vec::new(),
+                            skipped_identical: false,
+                            failure_detail: None,
                         });
 
                         // Reset skip flag for next task
                         self.task_control.reset_skip();
+                        install_markers::finish(&self.db, marker).await;
                         continue;
                     }
 
@@ -1354,63 +1748,44 @@ impl Installer {
                                 task_name: task.display_name.clone(),
                                 success: true,
                                 error_message: None,
+                                error_message_key: None,
+                                error_message_args: Vec::new(),
+                                error_code: None,
                                 verification_stats,
+                                preflight_bytes_read,
+                                failed_stage: None,
+                                warnings,
+                                skipped_identical: false,
+                                failure_detail: None,
                             });
 
-                            // Auto-sort scenery if enabled and this is a scenery task
-                            if auto_sort_scenery
-                                && (task.addon_type == AddonType::Scenery
-                                    || task.addon_type == AddonType::SceneryLibrary)
+                            if task.addon_type == AddonType::Scenery
+                                || task.addon_type == AddonType::SceneryLibrary
                             {
-                                use crate::scenery_classifier::classify_scenery;
-                                use crate::scenery_packs_manager::SceneryPacksManager;
-                                use std::path::Path;
-
-                                // Extract folder name from target path
-                                let target_path = Path::new(&task.target_path);
-                                if let Some(folder_name) =
-                                    target_path.file_name().and_then(|n| n.to_str())
-                                {
-                                    // Classify the newly installed scenery
-                                    let xplane_path_buf = PathBuf::from(&xplane_path);
-                                    match classify_scenery(target_path, &xplane_path_buf) {
-                                        Ok(scenery_info) => {
-                                            // Add entry to scenery_packs.ini at correct position
-                                            let manager = SceneryPacksManager::new(
-                                                &xplane_path_buf,
-                                                self.db.clone(),
-                                            );
-                                            if let Err(e) = manager
-                                                .add_entry_with_locked_entries(
-                                                    folder_name,
-                                                    &scenery_info.category,
-                                                    &locked_scenery_folder_names,
-                                                )
-                                                .await
-                                            {
-                                                logger::log_error(
-                                                    &format!("Failed to add scenery to scenery_packs.ini: {}", e),
-                                                    Some("installer"),
-                                                );
-                                            } else {
-                                                logger::log_info(
-                                                    &format!("Added {} to scenery_packs.ini (category: {:?})", folder_name, scenery_info.category),
-                                                    Some("installer"),
-                                                );
-                                            }
-                                        }
-                                        Err(e) => {
-                                            logger::log_error(
-                                                &format!(
-                                                    "Failed to classify scenery {}: {}",
-                                                    folder_name, e
-                                                ),
-                                                Some("installer"),
-                                            );
-                                        }
-                                    }
+                                // Global Scenery isn't listed in scenery_packs.ini
+                                if task.target_root_kind == TargetRootKind::CustomScenery {
+                                    scenery_installed = true;
+                                    self.finish_scenery_install(
+                                        &task.target_path,
+                                        &xplane_path,
+                                        auto_sort_scenery,
+                                        &locked_scenery_folder_names,
+                                    )
+                                    .await;
                                 }
+                            } else {
+                                post_install::emit_management_item_installed(
+                                    &self.app_handle,
+                                    &xplane_path,
+                                    &task.addon_type,
+                                    &task.target_path,
+                                );
                             }
+                            post_install::emit_update_tracked(
+                                &self.app_handle,
+                                &xplane_path,
+                                &task,
+                            );
                         }
                         Err(verify_err) => {
                             crate::log_debug!(
@@ -1424,23 +1799,55 @@ impl Installer {
                                 "installer_timing"
                             );
 
-                            failed += 1;
-                            let error_msg = format!("Verification failed: {}", verify_err);
-                            logger::log_error(
-                                &format!(
-                                    "{} {}: {}",
-                                    tr(LogMsg::InstallationFailed),
-                                    task.display_name,
-                                    error_msg
-                                ),
-                                Some("installer"),
-                            );
+                            let verification_stats = verification::interrupted_stats(&verify_err);
+                            let error_code = if verification_stats.is_none() {
+                                error::error_code(&verify_err)
+                            } else {
+                                ApiErrorCode::Cancelled
+                            };
+                            let failure = if verification_stats.is_none() {
+                                failed += 1;
+                                let failure = TaskFailure::from(
+                                    UserMsg::VerificationFailed.error_with([&verify_err]),
+                                );
+                                logger::log_error(
+                                    &format!(
+                                        "{} {}: {}",
+                                        tr(LogMsg::InstallationFailed),
+                                        task.display_name,
+                                        failure.message
+                                    ),
+                                    Some("installer"),
+                                );
+                                failure
+                            } else if self.task_control.is_cancelled() {
+                                cancelled += 1;
+                                TaskFailure::from(UserMsg::TaskCancelled)
+                            } else {
+                                skipped += 1;
+                                self.task_control.reset_skip();
+                                if let Err(e) = self.cleanup_task(task) {
+                                    logger::log_error(
+                                        &format!("Failed to cleanup skipped task: {}", e),
+                                        Some("installer"),
+                                    );
+                                }
+                                TaskFailure::from(UserMsg::TaskSkipped)
+                            };
                             task_results.push(TaskResult {
                                 task_id: task.id.clone(),
                                 task_name: task.display_name.clone(),
                                 success: false,
-                                error_message: Some(error_msg),
-                                verification_stats: None,
+                                error_message: Some(failure.message),
+                                error_message_key: failure.key,
+                                error_message_args: failure.args,
+                                error_code: Some(error_code),
+                                verification_stats,
+                                preflight_bytes_read,
+                                failed_stage: None,
+                                warnings,
+                                skipped_identical: false,
+                                failure_detail: None,
                             });
                         }
                     }
@@ -1458,13 +1865,13 @@ impl Installer {
                     );
 
                     failed += 1;
-                    let error_msg = format!("{}", e);
+                    let failure = TaskFailure::from(&e);
                     logger::log_error(
                         &format!(
                             "{} {}: {}",
                             tr(LogMsg::InstallationFailed),
                             task.display_name,
-                            error_msg
+                            failure.message
                         ),
                         Some("installer"),
                     );
@@ -1472,11 +1879,20 @@ impl Installer {
                         task_id: task.id.clone(),
                         task_name: task.display_name.clone(),
                         success: false,
-                        error_message: Some(error_msg),
+                        error_message: Some(failure.message),
+                        error_message_key: failure.key,
+                        error_message_args: failure.args,
+                        error_code: Some(error::error_code(&e)),
                         verification_stats: None,
+                        preflight_bytes_read,
+                        failed_stage: crate::atomic_installer::failed_stage(&e),
+                        warnings: Vec::new(),
+                        skipped_identical: false,
+                        failure_detail: extraction::failure_detail(&e),
                     });
                 }
             }
+            install_markers::finish(&self.db, marker).await;
         }
 
         crate::log_debug!(
@@ -1548,6 +1964,10 @@ impl Installer {
             "installer_timing"
         );
 
+        if scenery_installed {
+            self.check_scenery_order_after_install(&xplane_path).await;
+        }
+
         Ok(InstallResult {
             total_tasks: tasks.len(),
             successful_tasks: successful,
@@ -1559,7 +1979,7 @@ impl Installer {
     /// Install tasks in parallel with a configurable concurrency limit
     pub async fn install_parallel(
         &self,
-        tasks: Vec<InstallTask>,
+        mut tasks: Vec<InstallTask>,
         max_concurrent: usize,
         atomic_install_enabled: bool,
         xplane_path: String,
@@ -1592,6 +2012,9 @@ impl Installer {
 
         // Reset task control at start
         self.task_control.reset();
+        let _active_install = self.task_control.begin_install();
+        Self::normalize_target_casing(&mut tasks, &xplane_path);
+        self.check_targets_writable(&tasks)?;
 
         // Phase 1: Calculate total size
         let calc_start = Instant::now();
@@ -1623,6 +2046,9 @@ impl Installer {
                 active_tasks: Some(Vec::new()),
                 completed_task_count: Some(0),
                 completed_task_ids: Some(Vec::new()),
+                in_solid_block: None,
+                speed_bytes_per_sec: None,
+                eta_seconds: None,
             };
             let _ = self.app_handle.emit("install-progress", &progress);
         }
@@ -1649,170 +2075,381 @@ impl Installer {
         struct TaskMeta {
             addon_type: AddonType,
             target_path: String,
+            target_root_kind: TargetRootKind,
         }
         let task_metas: Vec<TaskMeta> = tasks
             .iter()
             .map(|t| TaskMeta {
                 addon_type: t.addon_type.clone(),
                 target_path: t.target_path.clone(),
+                target_root_kind: t.target_root_kind,
             })
             .collect();
         let source_cleanup_candidates = Self::collect_source_cleanup_candidates(&tasks);
 
-        for (index, task) in tasks.into_iter().enumerate() {
+        // Liveries wait for the aircraft they go into, same-target tasks run in order.
+        // Each task publishes whether it succeeded once it is done.
+        let targets: Vec<(&AddonType, &Path)> = tasks
+            .iter()
+            .map(|t| (&t.addon_type, Path::new(&t.target_path)))
+            .collect();
+        let dependencies = task_dependencies(&targets);
+        let (done_senders, done_receivers): (Vec<_>, Vec<_>) = (0..tasks.len())
+            .map(|_| tokio::sync::watch::channel(None::<bool>))
+            .unzip();
+        let done_receivers = Arc::new(done_receivers);
+
+        for ((index, task), (done_tx, deps)) in tasks
+            .into_iter()
+            .enumerate()
+            .zip(done_senders.into_iter().zip(dependencies))
+        {
+            let done_rx = done_receivers.clone();
             let sem = semaphore.clone();
             let ctx = ctx.clone();
             let tc = task_control.clone();
             let ah = app_handle.clone();
             let xp = xplane_path.clone();
             let atomic = atomic_install_enabled;
+            let preflight = self.preflight_check;
+            let db = self.db.clone();
 
             let handle = tokio::spawn(async move {
-                // Acquire semaphore permit asynchronously
-                let _permit = match sem.acquire().await {
-                    Ok(permit) => permit,
-                    Err(_) => {
+                let result = async move {
+                    // Wait for dependencies before taking a permit so waiting tasks
+                    // never block the ones they wait for
+                    if !deps.is_empty() {
+                        ctx.trackers[index].phase.store(6, Ordering::SeqCst);
+                        ctx.emit_aggregated();
+                        for dep in &deps {
+                            let mut rx = done_rx[dep.index].clone();
+                            let succeeded = match rx.wait_for(|done| done.is_some()).await {
+                                Ok(done) => done.unwrap_or(false),
+                                Err(_) => false,
+                            };
+                            if dep.required && !succeeded {
+                                ctx.mark_failed(index);
+                                let failure = TaskFailure::from(
+                                    UserMsg::DependencyNotInstalled
+                                        .error_with([&ctx.trackers[dep.index].name]),
+                                );
+                                return TaskResult {
+                                    task_id: task.id.clone(),
+                                    task_name: task.display_name.clone(),
+                                    success: false,
+                                    error_message: Some(failure.message),
+                                    error_message_key: failure.key,
+                                    error_message_args: failure.args,
+                                    error_code: Some(ApiErrorCode::Cancelled),
+                                    verification_stats: None,
+                                    preflight_bytes_read: None,
+                                    failed_stage: None,
+                                    warnings: Vec::new(),
+                                    skipped_identical: false,
+                                    failure_detail: None,
+                                };
+                            }
+                        }
+                        ctx.trackers[index].phase.store(0, Ordering::SeqCst);
+                    }
+
+                    // Acquire semaphore permit asynchronously
+                    let _permit = match sem.acquire().await {
+                        Ok(permit) => permit,
+                        Err(_) => {
+                            let failure = TaskFailure::from(UserMsg::InstallQueueClosed);
+                            return TaskResult {
+                                task_id: task.id.clone(),
+                                task_name: task.display_name.clone(),
+                                success: false,
+                                error_message: Some(failure.message),
+                                error_message_key: failure.key,
+                                error_message_args: failure.args,
+                                error_code: Some(ApiErrorCode::Internal),
+                                verification_stats: None,
+                                preflight_bytes_read: None,
+                                failed_stage: None,
+                                warnings: Vec::new(),
+                                skipped_identical: false,
+                                failure_detail: None,
+                            };
+                        }
+                    };
+
+                    // Check cancel
+                    if tc.is_cancelled() {
+                        let failure = TaskFailure::from(UserMsg::TaskCancelled);
                         return TaskResult {
                             task_id: task.id.clone(),
                             task_name: task.display_name.clone(),
                             success: false,
-                            error_message: Some("Semaphore closed".to_string()),
+                            error_message: Some(failure.message),
+                            error_message_key: failure.key,
+                            error_message_args: failure.args,
+                            error_code: Some(ApiErrorCode::Cancelled),
                             verification_stats: None,
+                            preflight_bytes_read: None,
+                            failed_stage: None,
+                            warnings: Vec::new(),
+                            skipped_identical: false,
+                            failure_detail: None,
                         };
                     }
-                };
 
-                // Check cancel
-                if tc.is_cancelled() {
-                    return TaskResult {
-                        task_id: task.id.clone(),
-                        task_name: task.display_name.clone(),
-                        success: false,
-                        error_message: Some("Cancelled by user".to_string()),
-                        verification_stats: None,
-                    };
-                }
+                    // Mark task as installing
+                    crate::log_debug!(
+                        &format!(
+                            "[PARALLEL] Task {} starting installation (semaphore acquired)",
+                            index
+                        ),
+                        "parallel_progress"
+                    );
+                    ctx.trackers[index]
+                        .phase
+                        .store(1, std::sync::atomic::Ordering::SeqCst);
 
-                // Mark task as installing
-                crate::log_debug!(
-                    &format!(
-                        "[PARALLEL] Task {} starting installation (semaphore acquired)",
-                        index
-                    ),
-                    "parallel_progress"
-                );
-                ctx.trackers[index]
-                    .phase
-                    .store(1, std::sync::atomic::Ordering::SeqCst);
-
-                // Run blocking I/O work in spawn_blocking
-                let result = tokio::task::spawn_blocking(move || {
-                    let task_view = ctx.get_task_view(index);
-                    let progress_ctx = task_view.as_progress_context();
-                    let installer = Installer::new(ah);
-
-                    let mut task = task;
-                    match installer.install_task_with_progress(&task, &progress_ctx, atomic, &xp) {
-                        Ok(_) => {
-                            {
-                                let inline = progress_ctx.inline_hashes.lock().unwrap();
-                                if !inline.is_empty() {
-                                    task.file_hashes = Some(inline.clone());
-                                    progress_ctx
-                                        .inline_verified
-                                        .store(true, std::sync::atomic::Ordering::SeqCst);
-                                    progress_ctx
-                                        .inline_verified_count
-                                        .store(inline.len() as u64, Ordering::SeqCst);
-                                }
-                            }
+                    let marker = install_markers::begin(&db, &task, &xp).await;
+
+                    // Run blocking I/O work in spawn_blocking
+                    let result = tokio::task::spawn_blocking(move || {
+                        let task_view = ctx.get_task_view(index);
+                        let progress_ctx = task_view.as_progress_context();
+                        let installer = Installer::new(ah);
+
+                        let mut task = task;
 
+                        let mut preflight_bytes_read = None;
+                        if preflight {
                             ctx.trackers[index]
                                 .phase
-                                .store(2, std::sync::atomic::Ordering::SeqCst);
-
+                                .store(5, std::sync::atomic::Ordering::SeqCst);
                             progress_ctx.set_verification_progress(0.0);
-                            progress_ctx.emit_progress(
-                                Some("Verifying...".to_string()),
-                                InstallPhase::Verifying,
-                            );
-
-                            match installer.verify_installation(&task, &progress_ctx) {
-                                Ok(verification_stats) => {
-                                    ctx.mark_completed(index);
-                                    logger::log_info(
-                                        &format!(
-                                            "{}: {}",
-                                            tr(LogMsg::InstallationCompleted),
-                                            task.display_name
-                                        ),
-                                        Some("installer"),
-                                    );
-
-                                    TaskResult {
-                                        task_id: task.id.clone(),
-                                        task_name: task.display_name.clone(),
-                                        success: true,
-                                        error_message: None,
-                                        verification_stats,
-                                    }
-                                }
+                            progress_ctx.emit_progress(None, InstallPhase::PreflightCheck);
+                            match installer.preflight_check_source(&task, &progress_ctx) {
+                                Ok(bytes_read) => preflight_bytes_read = Some(bytes_read),
                                 Err(e) => {
                                     ctx.mark_failed(index);
-                                    let error_msg = format!("Verification failed: {}", e);
+                                    let error_code = if tc.is_cancelled() {
+                                        ApiErrorCode::Cancelled
+                                    } else {
+                                        error::error_code(&e)
+                                    };
+                                    let failure = if tc.is_cancelled() {
+                                        TaskFailure::from(UserMsg::TaskCancelled)
+                                    } else {
+                                        TaskFailure::from(&e)
+                                    };
                                     logger::log_error(
                                         &format!(
                                             "{} {}: {}",
                                             tr(LogMsg::InstallationFailed),
                                             task.display_name,
-                                            error_msg
+                                            failure.message
                                         ),
                                         Some("installer"),
                                     );
-                                    TaskResult {
+                                    return TaskResult {
                                         task_id: task.id.clone(),
                                         task_name: task.display_name.clone(),
                                         success: false,
-                                        error_message: Some(error_msg),
+                                        error_message: Some(failure.message),
+                                        error_message_key: failure.key,
+                                        error_message_args: failure.args,
+                                        error_code: Some(error_code),
                                         verification_stats: None,
+                                        preflight_bytes_read: None,
+                                        failed_stage: None,
+                                        warnings: Vec::new(),
+                                        skipped_identical: false,
+                                        failure_detail: None,
+                                    };
+                                }
+                            }
+                            ctx.trackers[index]
+                                .phase
+                                .store(1, std::sync::atomic::Ordering::SeqCst);
+                        }
+
+                        match installer.install_task_with_progress(
+                            &task,
+                            &progress_ctx,
+                            atomic,
+                            &xp,
+                        ) {
+                            Ok(_) => {
+                                {
+                                    let inline = progress_ctx.inline_hashes.lock().unwrap();
+                                    if !inline.is_empty() {
+                                        task.file_hashes = Some(inline.clone());
+                                        progress_ctx
+                                            .inline_verified
+                                            .store(true, std::sync::atomic::Ordering::SeqCst);
+                                        progress_ctx
+                                            .inline_verified_count
+                                            .store(inline.len() as u64, Ordering::SeqCst);
+                                    }
+                                }
+                                let mut warnings =
+                                    take_case_collision_warnings(&mut task, &progress_ctx);
+                                warnings.append(&mut progress_ctx.link_warnings.lock().unwrap());
+
+                                ctx.trackers[index]
+                                    .phase
+                                    .store(2, std::sync::atomic::Ordering::SeqCst);
+
+                                progress_ctx.set_verification_progress(0.0);
+                                progress_ctx.emit_progress(
+                                    Some("Verifying...".to_string()),
+                                    InstallPhase::Verifying,
+                                );
+
+                                match installer.verify_installation(&task, &progress_ctx) {
+                                    Ok(verification_stats) => {
+                                        ctx.mark_completed(index);
+                                        logger::log_info(
+                                            &format!(
+                                                "{}: {}",
+                                                tr(LogMsg::InstallationCompleted),
+                                                task.display_name
+                                            ),
+                                            Some("installer"),
+                                        );
+                                        post_install::emit_management_item_installed(
+                                            &installer.app_handle,
+                                            &xp,
+                                            &task.addon_type,
+                                            &task.target_path,
+                                        );
+                                        post_install::emit_update_tracked(
+                                            &installer.app_handle,
+                                            &xp,
+                                            &task,
+                                        );
+
+                                        TaskResult {
+                                            task_id: task.id.clone(),
+                                            task_name: task.display_name.clone(),
+                                            success: true,
+                                            error_message: None,
+                                            error_message_key: None,
+                                            error_message_args: Vec::new(),
+                                            error_code: None,
+                                            verification_stats,
+                                            preflight_bytes_read,
+                                            failed_stage: None,
+                                            warnings,
+                                            skipped_identical: false,
+                                            failure_detail: None,
+                                        }
+                                    }
+                                    Err(e) => {
+                                        ctx.mark_failed(index);
+                                        let verification_stats =
+                                            verification::interrupted_stats(&e);
+                                        let error_code =
+                                            if verification_stats.is_some() && tc.is_cancelled() {
+                                                ApiErrorCode::Cancelled
+                                            } else {
+                                                error::error_code(&e)
+                                            };
+                                        let failure =
+                                            if verification_stats.is_some() && tc.is_cancelled() {
+                                                TaskFailure::from(UserMsg::TaskCancelled)
+                                            } else {
+                                                TaskFailure::from(
+                                                    UserMsg::VerificationFailed.error_with([&e]),
+                                                )
+                                            };
+                                        logger::log_error(
+                                            &format!(
+                                                "{} {}: {}",
+                                                tr(LogMsg::InstallationFailed),
+                                                task.display_name,
+                                                failure.message
+                                            ),
+                                            Some("installer"),
+                                        );
+                                        TaskResult {
+                                            task_id: task.id.clone(),
+                                            task_name: task.display_name.clone(),
+                                            success: false,
+                                            error_message: Some(failure.message),
+                                            error_message_key: failure.key,
+                                            error_message_args: failure.args,
+                                            error_code: Some(error_code),
+                                            verification_stats,
+                                            preflight_bytes_read,
+                                            failed_stage: None,
+                                            warnings,
+                                            skipped_identical: false,
+                                            failure_detail: None,
+                                        }
                                     }
                                 }
                             }
+                            Err(e) => {
+                                ctx.mark_failed(index);
+                                let failure = TaskFailure::from(&e);
+                                logger::log_error(
+                                    &format!(
+                                        "{} {}: {}",
+                                        tr(LogMsg::InstallationFailed),
+                                        task.display_name,
+                                        failure.message
+                                    ),
+                                    Some("installer"),
+                                );
+                                TaskResult {
+                                    task_id: task.id.clone(),
+                                    task_name: task.display_name.clone(),
+                                    success: false,
+                                    error_message: Some(failure.message),
+                                    error_message_key: failure.key,
+                                    error_message_args: failure.args,
+                                    error_code: Some(error::error_code(&e)),
+                                    verification_stats: None,
+                                    preflight_bytes_read,
+                                    failed_stage: crate::atomic_installer::failed_stage(&e),
+                                    warnings: Vec::new(),
+                                    skipped_identical: false,
+                                    failure_detail: extraction::failure_detail(&e),
+                                }
+                            }
+                        }
+                    })
+                    .await;
+
+                    match result {
+                        Ok(task_result) => {
+                            install_markers::finish(&db, marker).await;
+                            task_result
                         }
+                        // A panicked task keeps its marker, so the next launch
+                        // cleans up after it
                         Err(e) => {
-                            ctx.mark_failed(index);
-                            let error_msg = format!("{}", e);
-                            logger::log_error(
-                                &format!(
-                                    "{} {}: {}",
-                                    tr(LogMsg::InstallationFailed),
-                                    task.display_name,
-                                    error_msg
-                                ),
-                                Some("installer"),
-                            );
+                            let failure = TaskFailure::from(UserMsg::TaskPanicked.error_with([e]));
                             TaskResult {
-                                task_id: task.id.clone(),
-                                task_name: task.display_name.clone(),
+                                task_id: String::new(),
+                                task_name: String::new(),
                                 success: false,
-                                error_message: Some(error_msg),
+                                error_message: Some(failure.message),
+                                error_message_key: failure.key,
+                                error_message_args: failure.args,
+                                error_code: Some(ApiErrorCode::Internal),
                                 verification_stats: None,
+                                preflight_bytes_read: None,
+                                failed_stage: None,
+                                warnings: Vec::new(),
+                                skipped_identical: false,
+                                failure_detail: None,
                             }
                         }
                     }
-                })
+                }
                 .await;
 
-                match result {
-                    Ok(task_result) => task_result,
-                    Err(e) => TaskResult {
-                        task_id: String::new(),
-                        task_name: String::new(),
-                        success: false,
-                        error_message: Some(format!("Task panicked: {}", e)),
-                        verification_stats: None,
-                    },
-                }
+                done_tx.send_replace(Some(result.success));
+                result
             });
             handles.push(handle);
         }
@@ -1823,12 +2460,21 @@ impl Installer {
             match handle.await {
                 Ok(result) => task_results.push(result),
                 Err(e) => {
+                    let failure = TaskFailure::from(UserMsg::TaskPanicked.error_with([e]));
                     task_results.push(TaskResult {
                         task_id: String::new(),
                         task_name: String::new(),
                         success: false,
-                        error_message: Some(format!("Task panicked: {}", e)),
+                        error_message: Some(failure.message),
+                        error_message_key: failure.key,
+                        error_message_args: failure.args,
+                        error_code: Some(ApiErrorCode::Internal),
                         verification_stats: None,
+                        preflight_bytes_read: None,
+                        failed_stage: None,
+                        warnings: Vec::new(),
+                        skipped_identical: false,
+                        failure_detail: None,
                     });
                 }
             }
@@ -1843,64 +2489,26 @@ impl Installer {
         // Phase 3: Finalize
         ctx.emit_final();
 
-        // Auto-sort scenery for successful scenery tasks
-        if auto_sort_scenery {
-            for (i, result) in task_results.iter().enumerate() {
-                if !result.success {
-                    continue;
-                }
-                if let Some(meta) = task_metas.get(i) {
-                    if meta.addon_type == AddonType::Scenery
-                        || meta.addon_type == AddonType::SceneryLibrary
-                    {
-                        use crate::scenery_classifier::classify_scenery;
-                        use crate::scenery_packs_manager::SceneryPacksManager;
-
-                        let target_path = Path::new(&meta.target_path);
-                        if let Some(folder_name) = target_path.file_name().and_then(|n| n.to_str())
-                        {
-                            let xplane_path_buf = PathBuf::from(&xplane_path);
-                            match classify_scenery(target_path, &xplane_path_buf) {
-                                Ok(scenery_info) => {
-                                    let manager =
-                                        SceneryPacksManager::new(&xplane_path_buf, self.db.clone());
-                                    if let Err(e) = manager
-                                        .add_entry_with_locked_entries(
-                                            folder_name,
-                                            &scenery_info.category,
-                                            &locked_scenery_folder_names,
-                                        )
-                                        .await
-                                    {
-                                        logger::log_error(
-                                            &format!(
-                                                "Failed to add scenery to scenery_packs.ini: {}",
-                                                e
-                                            ),
-                                            Some("installer"),
-                                        );
-                                    } else {
-                                        logger::log_info(
-                                            &format!(
-                                                "Added {} to scenery_packs.ini (category: {:?})",
-                                                folder_name, scenery_info.category
-                                            ),
-                                            Some("installer"),
-                                        );
-                                    }
-                                }
-                                Err(e) => {
-                                    logger::log_error(
-                                        &format!(
-                                            "Failed to classify scenery {}: {}",
-                                            folder_name, e
-                                        ),
-                                        Some("installer"),
-                                    );
-                                }
-                            }
-                        }
-                    }
+        // Classify successful scenery tasks, auto-sorting them if enabled
+        let mut scenery_installed = false;
+        for (i, result) in task_results.iter().enumerate() {
+            if !result.success {
+                continue;
+            }
+            if let Some(meta) = task_metas.get(i) {
+                // Global Scenery isn't listed in scenery_packs.ini
+                if (meta.addon_type == AddonType::Scenery
+                    || meta.addon_type == AddonType::SceneryLibrary)
+                    && meta.target_root_kind == TargetRootKind::CustomScenery
+                {
+                    scenery_installed = true;
+                    self.finish_scenery_install(
+                        &meta.target_path,
+                        &xplane_path,
+                        auto_sort_scenery,
+                        &locked_scenery_folder_names,
+                    )
+                    .await;
                 }
             }
         }
@@ -1942,6 +2550,10 @@ impl Installer {
             );
         }
 
+        if scenery_installed {
+            self.check_scenery_order_after_install(&xplane_path).await;
+        }
+
         Ok(InstallResult {
             total_tasks: task_results.len(),
             successful_tasks: successful,
@@ -2188,16 +2800,21 @@ impl Installer {
         // Calculate size if not cached
         let mut size = 0u64;
         let mut file_count = 0usize;
+        let mut newest_modified = None;
         for entry in walkdir::WalkDir::new(dir).follow_links(false) {
             let entry = entry?;
             if entry.file_type().is_file() {
-                size += entry.metadata()?.len();
+                let metadata = entry.metadata()?;
+                size += metadata.len();
                 file_count += 1;
+                if let Ok(modified) = metadata.modified() {
+                    newest_modified = newest_modified.max(Some(modified));
+                }
             }
         }
 
         // Cache the result
-        crate::cache::cache_directory_metadata(dir, size, file_count);
+        crate::cache::cache_directory_metadata(dir, size, file_count, newest_modified);
 
         Ok(size)
     }
@@ -2219,53 +2836,26 @@ impl Installer {
 
     /// Get uncompressed size of ZIP archive
     fn get_zip_size(&self, archive: &Path, internal_root: Option<&str>) -> Result<u64> {
-        use zip::ZipArchive;
-        let prepared = crate::archive_input::prepare_archive_for_read(
-            archive,
-            crate::archive_input::ArchiveFormat::Zip,
-        )?;
-        let file = fs::File::open(prepared.read_path())?;
-        let mut archive_reader = ZipArchive::new(file)?;
-        let prefix = internal_root.map(|s| {
-            let normalized = s.replace('\\', "/").trim_matches('/').to_string();
-            if normalized.is_empty() {
-                String::new()
-            } else {
-                format!("{}/", normalized)
-            }
-        });
-
-        let mut total = 0u64;
-        for i in 0..archive_reader.len() {
-            if let Ok(file) = archive_reader.by_index_raw(i) {
-                let name = file.name().replace('\\', "/");
-                if let Some(ref p) = prefix {
-                    if !name.starts_with(p) {
-                        continue;
-                    }
-                }
-                total += file.size();
-            }
-        }
-        Ok(total)
+        zip_uncompressed_size(archive, internal_root)
     }
 
     /// Get uncompressed size of 7z archive.
     /// Uses archive metadata directly and supports internal_root filtering.
     fn get_7z_size(&self, archive: &Path, internal_root: Option<&str>) -> Result<u64> {
+        // For full-archive queries, use cache when available. Checked before
+        // preparing so split archives are not concatenated on a hit.
+        if internal_root.is_none() {
+            if let Some(cached) = crate::cache::get_cached_metadata(archive) {
+                return Ok(cached.uncompressed_size);
+            }
+        }
+
         let prepared = crate::archive_input::prepare_archive_for_read(
             archive,
             crate::archive_input::ArchiveFormat::SevenZ,
         )?;
         let read_archive = prepared.read_path();
 
-        // For full-archive queries, use cache when available.
-        if internal_root.is_none() {
-            if let Some(cached) = crate::cache::get_cached_metadata(read_archive) {
-                return Ok(cached.uncompressed_size);
-            }
-        }
-
         let prefix = internal_root.map(|s| {
             let normalized = s.replace('\\', "/").trim_matches('/').to_string();
             if normalized.is_empty() {
@@ -2305,7 +2895,7 @@ impl Installer {
         }
 
         if internal_root.is_none() && total > 0 {
-            crate::cache::cache_metadata(read_archive, total, file_count);
+            crate::cache::cache_metadata(archive, total, file_count);
         }
 
         Ok(total)
@@ -2320,16 +2910,157 @@ impl Installer {
 
         let mut total = 0u64;
         for e in arch.flatten() {
-            total += e.unpacked_size;
+            total = total.saturating_add(e.unpacked_size);
         }
         Ok(total)
     }
 }
 
+/// Uncompressed size of a ZIP archive, or of the files under `internal_root`.
+///
+/// Sizes come from the central directory, which the zip crate resolves
+/// through the Zip64 extra field when the 32-bit fields are saturated, so
+/// entries written with data descriptors and archives past 4 GB are counted
+/// in full.
+fn zip_uncompressed_size(archive: &Path, internal_root: Option<&str>) -> Result<u64> {
+    use zip::ZipArchive;
+
+    // For full-archive queries, use cache when available.
+    if internal_root.is_none() {
+        if let Some(cached) = crate::cache::get_cached_metadata(archive) {
+            return Ok(cached.uncompressed_size);
+        }
+    }
+
+    let prepared = crate::archive_input::prepare_archive_for_read(
+        archive,
+        crate::archive_input::ArchiveFormat::Zip,
+    )?;
+    let file = fs::File::open(prepared.read_path())?;
+    let mut archive_reader = ZipArchive::new(file)?;
+    let prefix = internal_root.map(|s| {
+        let normalized = s.replace('\\', "/").trim_matches('/').to_string();
+        if normalized.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", normalized)
+        }
+    });
+
+    let mut total = 0u64;
+    let mut file_count = 0usize;
+    for i in 0..archive_reader.len() {
+        if let Ok(file) = archive_reader.by_index_raw(i) {
+            let name = file.name().replace('\\', "/");
+            if let Some(ref p) = prefix {
+                if !name.starts_with(p) {
+                    continue;
+                }
+            }
+            total = total.saturating_add(file.size());
+            file_count += 1;
+        }
+    }
+
+    if internal_root.is_none() && total > 0 {
+        crate::cache::cache_metadata(archive, total, file_count);
+    }
+
+    Ok(total)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_throughput_ignores_samples_closer_than_min_interval() {
+        let start = Instant::now();
+        let mut estimator = ThroughputEstimator::default();
+
+        assert_eq!(estimator.record(start, 0), None);
+        // A burst right after the first sample must not produce a huge speed
+        assert_eq!(
+            estimator.record(start + Duration::from_millis(16), 4 * 1024 * 1024),
+            None
+        );
+        assert_eq!(
+            estimator.record(start + Duration::from_millis(500), 5_000_000),
+            Some(10_000_000)
+        );
+        // Throttled emits in between keep reporting the last window
+        assert_eq!(
+            estimator.record(start + Duration::from_millis(516), 9_000_000),
+            Some(10_000_000)
+        );
+
+        // Old samples fall out of the window, so a stall shows up as slowing down
+        let mut at = start + Duration::from_millis(500);
+        for _ in 0..12 {
+            at += Duration::from_millis(500);
+            estimator.record(at, 5_000_000);
+        }
+        assert_eq!(estimator.record(at, 5_000_000), Some(0));
+    }
+
+    #[test]
+    fn test_liveries_wait_for_their_aircraft_and_same_targets_serialize() {
+        let aircraft = Path::new("/xp/Aircraft/A320");
+        let livery = Path::new("/xp/Aircraft/A320/liveries/Red");
+        let other_livery = Path::new("/xp/Aircraft/B738/liveries/Blue");
+        let plugin = Path::new("/xp/Resources/plugins/Tool");
+        let targets = [
+            (&AddonType::Livery, livery),
+            (&AddonType::Aircraft, aircraft),
+            (&AddonType::Livery, other_livery),
+            (&AddonType::Plugin, plugin),
+            (&AddonType::Plugin, plugin),
+        ];
+
+        let deps = task_dependencies(&targets);
+        assert_eq!(
+            deps[0],
+            vec![TaskDependency {
+                index: 1,
+                required: true
+            }]
+        );
+        assert!(deps[1].is_empty());
+        assert!(deps[2].is_empty());
+        assert!(deps[3].is_empty());
+        assert_eq!(
+            deps[4],
+            vec![TaskDependency {
+                index: 3,
+                required: false
+            }]
+        );
+    }
+
+    #[test]
+    fn test_eta_is_omitted_while_calculating_or_verifying() {
+        assert_eq!(
+            estimate_eta(25_000_000, Some(10_000_000), &InstallPhase::Installing),
+            Some(3)
+        );
+        assert_eq!(
+            estimate_eta(25_000_000, Some(10_000_000), &InstallPhase::Calculating),
+            None
+        );
+        assert_eq!(
+            estimate_eta(25_000_000, Some(10_000_000), &InstallPhase::Verifying),
+            None
+        );
+        assert_eq!(
+            estimate_eta(25_000_000, Some(0), &InstallPhase::Installing),
+            None
+        );
+        assert_eq!(
+            estimate_eta(25_000_000, None, &InstallPhase::Installing),
+            None
+        );
+    }
+
     #[test]
     fn test_sanitize_path_normal() {
         let path = Path::new("folder/subfolder/file.txt");
@@ -2431,4 +3162,145 @@ mod tests {
         assert!(!should_compute_inline_7z_hashes(true, true));
         assert!(!should_compute_inline_7z_hashes(false, false));
     }
+
+    /// Stored ZIP with every record forced to Zip64: saturated 32-bit sizes
+    /// and offsets, Zip64 extra fields, data descriptors (flag bit 3) and a
+    /// Zip64 end of central directory, as large-archive writers produce
+    fn forced_zip64_fixture(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        const FLAG_DATA_DESCRIPTOR: u16 = 1 << 3;
+        const VERSION_ZIP64: u16 = 45;
+
+        fn u16le(out: &mut Vec<u8>, value: u16) {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        fn u32le(out: &mut Vec<u8>, value: u32) {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        fn u64le(out: &mut Vec<u8>, value: u64) {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let mut out = Vec::new();
+        let mut central = Vec::new();
+        for (name, data) in entries {
+            let offset = out.len() as u64;
+            let crc = crc32fast::hash(data);
+            let size = data.len() as u64;
+
+            // Local header: sizes deferred to the data descriptor
+            u32le(&mut out, 0x0403_4b50);
+            u16le(&mut out, VERSION_ZIP64);
+            u16le(&mut out, FLAG_DATA_DESCRIPTOR);
+            u16le(&mut out, 0); // stored
+            u32le(&mut out, 0); // time + date
+            u32le(&mut out, 0); // crc in descriptor
+            u32le(&mut out, u32::MAX);
+            u32le(&mut out, u32::MAX);
+            u16le(&mut out, name.len() as u16);
+            u16le(&mut out, 20);
+            out.extend_from_slice(name.as_bytes());
+            u16le(&mut out, 0x0001);
+            u16le(&mut out, 16);
+            u64le(&mut out, 0);
+            u64le(&mut out, 0);
+            out.extend_from_slice(data);
+
+            // Zip64 data descriptor
+            u32le(&mut out, 0x0807_4b50);
+            u32le(&mut out, crc);
+            u64le(&mut out, size);
+            u64le(&mut out, size);
+
+            u32le(&mut central, 0x0201_4b50);
+            u16le(&mut central, VERSION_ZIP64);
+            u16le(&mut central, VERSION_ZIP64);
+            u16le(&mut central, FLAG_DATA_DESCRIPTOR);
+            u16le(&mut central, 0);
+            u32le(&mut central, 0);
+            u32le(&mut central, crc);
+            u32le(&mut central, u32::MAX);
+            u32le(&mut central, u32::MAX);
+            u16le(&mut central, name.len() as u16);
+            u16le(&mut central, 28);
+            u16le(&mut central, 0); // comment length
+            u16le(&mut central, 0); // disk number
+            u16le(&mut central, 0); // internal attributes
+            u32le(&mut central, 0); // external attributes
+            u32le(&mut central, u32::MAX);
+            central.extend_from_slice(name.as_bytes());
+            u16le(&mut central, 0x0001);
+            u16le(&mut central, 24);
+            u64le(&mut central, size);
+            u64le(&mut central, size);
+            u64le(&mut central, offset);
+        }
+
+        let central_offset = out.len() as u64;
+        let central_size = central.len() as u64;
+        out.extend_from_slice(&central);
+
+        let zip64_eocd_offset = out.len() as u64;
+        u32le(&mut out, 0x0606_4b50);
+        u64le(&mut out, 44);
+        u16le(&mut out, VERSION_ZIP64);
+        u16le(&mut out, VERSION_ZIP64);
+        u32le(&mut out, 0); // this disk
+        u32le(&mut out, 0); // central directory disk
+        u64le(&mut out, entries.len() as u64);
+        u64le(&mut out, entries.len() as u64);
+        u64le(&mut out, central_size);
+        u64le(&mut out, central_offset);
+
+        u32le(&mut out, 0x0706_4b50);
+        u32le(&mut out, 0);
+        u64le(&mut out, zip64_eocd_offset);
+        u32le(&mut out, 1);
+
+        u32le(&mut out, 0x0605_4b50);
+        u16le(&mut out, 0);
+        u16le(&mut out, 0);
+        u16le(&mut out, u16::MAX);
+        u16le(&mut out, u16::MAX);
+        u32le(&mut out, u32::MAX);
+        u32le(&mut out, u32::MAX);
+        u16le(&mut out, 0);
+        out
+    }
+
+    #[test]
+    fn test_zip_size_reads_forced_zip64_records() {
+        use std::io::Read;
+
+        let liveries = vec![0x5au8; 4096];
+        let acf = b"I\n1100 version\n".to_vec();
+        let entries: [(&str, &[u8]); 3] = [
+            ("A330/A330.acf", &acf),
+            ("A330/liveries/Red/texture.png", &liveries),
+            ("README.txt", b"read me"),
+        ];
+        let temp = tempfile::tempdir().unwrap();
+        let archive = temp.path().join("zip64.zip");
+        fs::write(&archive, forced_zip64_fixture(&entries)).unwrap();
+
+        let mut reader = zip::ZipArchive::new(fs::File::open(&archive).unwrap()).unwrap();
+        assert_eq!(reader.len(), entries.len());
+        for (i, (name, data)) in entries.iter().enumerate() {
+            let mut file = reader.by_index(i).unwrap();
+            assert_eq!(file.name(), *name);
+            assert_eq!(file.size(), data.len() as u64);
+            let mut read = Vec::new();
+            // Reading to the end also checks the CRC from the central directory
+            file.read_to_end(&mut read).unwrap();
+            assert_eq!(read, *data);
+        }
+
+        assert_eq!(
+            zip_uncompressed_size(&archive, Some("A330")).unwrap(),
+            (acf.len() + liveries.len()) as u64
+        );
+        assert_eq!(
+            zip_uncompressed_size(&archive, None).unwrap(),
+            (acf.len() + liveries.len() + 7) as u64
+        );
+    }
 }
@@ -0,0 +1,188 @@
+//! Post-install updates to in-app state
+//!
+//! After a successful task the installed folder is scanned with the same logic
+//! the management and scenery pages use, and the result is sent to the frontend
+//! so it can insert or update that row without a full rescan. Addons that ship
+//! updater metadata are also announced for update tracking, and when enabled the
+//! scenery_packs.ini order is checked once scenery has been installed.
+
+use super::*;
+use crate::management_index;
+use crate::models::{InstalledManagementItem, UpdateTrackedAddon, UpdaterProvider};
+use crate::{scenery_order, settings_store};
+
+/// Emitted with an [`InstalledManagementItem`] after an aircraft or plugin task
+pub const MANAGEMENT_ITEM_INSTALLED_EVENT: &str = "management-item-installed";
+
+/// Emitted with the freshly classified `SceneryPackageInfo` after a scenery task
+pub const SCENERY_ITEM_INSTALLED_EVENT: &str = "scenery-item-installed";
+
+/// Emitted with an [`UpdateTrackedAddon`] after a task whose addon ships updater metadata
+pub const ADDON_UPDATE_TRACKED_EVENT: &str = "addon-update-tracked";
+
+/// Emitted with a `SceneryOrderReport` when installed scenery left the ini misordered
+pub const SCENERY_ORDER_VIOLATIONS_EVENT: &str = "scenery-order-violations";
+
+/// Scan an installed aircraft or plugin folder and send it to the frontend.
+/// Other addon types are ignored.
+pub(super) fn emit_management_item_installed(
+    app_handle: &AppHandle,
+    xplane_path: &str,
+    addon_type: &AddonType,
+    target_path: &str,
+) {
+    let xplane_path = Path::new(xplane_path);
+    let target_path = Path::new(target_path);
+    let item = match addon_type {
+        AddonType::Aircraft => management_index::scan_aircraft_target(xplane_path, target_path)
+            .map(|entries| {
+                (!entries.is_empty()).then_some(InstalledManagementItem::Aircraft { entries })
+            }),
+        AddonType::Plugin => management_index::scan_plugin_target(xplane_path, target_path)
+            .map(|entry| entry.map(|entry| InstalledManagementItem::Plugin { entry })),
+        _ => return,
+    };
+
+    match item {
+        Ok(Some(item)) => {
+            let _ = app_handle.emit(MANAGEMENT_ITEM_INSTALLED_EVENT, &item);
+        }
+        Ok(None) => {}
+        Err(e) => logger::log_error(
+            &format!(
+                "Failed to scan installed folder {}: {}",
+                target_path.display(),
+                e
+            ),
+            Some("installer"),
+        ),
+    }
+}
+
+/// Tell the frontend an installed addon can be checked for updates. Tasks
+/// without updater metadata, and addon types the updater doesn't handle, are
+/// ignored.
+pub(super) fn emit_update_tracked(app_handle: &AppHandle, xplane_path: &str, task: &InstallTask) {
+    if task.updater_provider == UpdaterProvider::None {
+        return;
+    }
+    let xplane_path = Path::new(xplane_path);
+    let (item_type, base_path) = match task.addon_type {
+        AddonType::Aircraft => ("aircraft", xplane_path.join("Aircraft")),
+        AddonType::Livery => ("livery", xplane_path.join("Aircraft")),
+        AddonType::Plugin => ("plugin", xplane_path.join("Resources").join("plugins")),
+        AddonType::Scenery | AddonType::SceneryLibrary => {
+            ("scenery", xplane_path.join("Custom Scenery"))
+        }
+        _ => return,
+    };
+
+    let target_path = Path::new(&task.target_path);
+    let relative = target_path
+        .strip_prefix(&base_path)
+        .ok()
+        .or_else(|| target_path.file_name().map(Path::new));
+    let Some(relative) = relative else {
+        return;
+    };
+    let (version, update_url, _) = management_index::read_version_info_with_url(target_path);
+    let item = UpdateTrackedAddon {
+        item_type: item_type.to_string(),
+        folder_name: relative.to_string_lossy().replace('\\', "/"),
+        display_name: task.display_name.clone(),
+        provider: task.updater_provider,
+        version,
+        update_url,
+    };
+    let _ = app_handle.emit(ADDON_UPDATE_TRACKED_EVENT, &item);
+}
+
+impl Installer {
+    /// Classify an installed scenery package, add it to scenery_packs.ini when
+    /// auto-sort is enabled, and send it to the frontend
+    pub(super) async fn finish_scenery_install(
+        &self,
+        target_path: &str,
+        xplane_path: &str,
+        auto_sort_scenery: bool,
+        locked_scenery_folder_names: &[String],
+    ) {
+        use crate::scenery_classifier::classify_scenery;
+        use crate::scenery_packs_manager::SceneryPacksManager;
+
+        let target_path = Path::new(target_path);
+        let Some(folder_name) = target_path.file_name().and_then(|n| n.to_str()) else {
+            return;
+        };
+
+        let xplane_path_buf = PathBuf::from(xplane_path);
+        let scenery_info = match classify_scenery(target_path, &xplane_path_buf) {
+            Ok(scenery_info) => scenery_info,
+            Err(e) => {
+                logger::log_error(
+                    &format!("Failed to classify scenery {}: {}", folder_name, e),
+                    Some("installer"),
+                );
+                return;
+            }
+        };
+
+        if auto_sort_scenery {
+            // Add entry to scenery_packs.ini at correct position
+            let manager = SceneryPacksManager::new(&xplane_path_buf, self.db.clone());
+            if let Err(e) = manager
+                .add_entry_with_locked_entries(
+                    folder_name,
+                    &scenery_info.category,
+                    locked_scenery_folder_names,
+                )
+                .await
+            {
+                logger::log_error(
+                    &format!("Failed to add scenery to scenery_packs.ini: {}", e),
+                    Some("installer"),
+                );
+            } else {
+                logger::log_info(
+                    &format!(
+                        "Added {} to scenery_packs.ini (category: {:?})",
+                        folder_name, scenery_info.category
+                    ),
+                    Some("installer"),
+                );
+            }
+        }
+
+        let _ = self
+            .app_handle
+            .emit(SCENERY_ITEM_INSTALLED_EVENT, &scenery_info);
+    }
+
+    /// Report scenery_packs.ini order violations to the frontend, if the
+    /// post-install check is enabled. Nothing is moved.
+    pub(super) async fn check_scenery_order_after_install(&self, xplane_path: &str) {
+        if !settings_store::get().validate_scenery_order_after_install {
+            return;
+        }
+
+        match scenery_order::validate_scenery_order(&self.db, Path::new(xplane_path), false).await {
+            Ok(report) if !report.violations.is_empty() => {
+                logger::log_info(
+                    &format!(
+                        "scenery_packs.ini has {} order violations after install",
+                        report.violations.len()
+                    ),
+                    Some("installer"),
+                );
+                let _ = self
+                    .app_handle
+                    .emit(SCENERY_ORDER_VIOLATIONS_EVENT, &report);
+            }
+            Ok(_) => {}
+            Err(e) => logger::log_error(
+                &format!("Failed to validate scenery order: {}", e),
+                Some("installer"),
+            ),
+        }
+    }
+}
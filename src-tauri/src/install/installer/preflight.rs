@@ -0,0 +1,423 @@
+use super::*;
+use std::io::{Read, Seek, SeekFrom};
+
+/// Maximum number of ZIP entries whose CRC is checked during pre-flight
+const PREFLIGHT_ZIP_SAMPLE_ENTRIES: usize = 16;
+
+/// Read buffer for pre-flight data checks (256 KB)
+const PREFLIGHT_BUFFER_SIZE: usize = 256 * 1024;
+
+/// Progress callback: (progress 0.0-100.0, current entry name)
+type PreflightProgressFn<'a> = &'a mut dyn FnMut(f64, Option<String>);
+
+impl Installer {
+    /// Pre-flight integrity check of a task's source archive.
+    ///
+    /// Runs before any target modification so a corrupted or truncated download
+    /// fails fast instead of halfway through extraction. Only the outer archive
+    /// is checked; nested archives are validated when they are extracted.
+    /// Returns the number of bytes read by the check.
+    pub(super) fn preflight_check_source(
+        &self,
+        task: &InstallTask,
+        ctx: &ProgressContext,
+    ) -> Result<u64> {
+        let source = Path::new(&task.source_path);
+        if !source.is_file() {
+            return Ok(0);
+        }
+
+        let format = match crate::archive_input::detect_archive_format(source) {
+            Some(format) => format,
+            None => return Ok(0),
+        };
+
+        let check_start = Instant::now();
+        let mut on_progress = |progress: f64, current: Option<String>| {
+            ctx.set_verification_progress(progress);
+            ctx.emit_progress(current, InstallPhase::PreflightCheck);
+        };
+
+        let result = match format {
+            crate::archive_input::ArchiveFormat::Zip => preflight_zip(
+                source,
+                task.password.as_deref(),
+                &self.task_control,
+                &mut on_progress,
+            ),
+            crate::archive_input::ArchiveFormat::SevenZ => {
+                preflight_7z(source, task.password.as_deref(), &mut on_progress)
+            }
+            crate::archive_input::ArchiveFormat::Rar => preflight_rar(
+                source,
+                task.password.as_deref(),
+                &self.task_control,
+                &mut on_progress,
+            ),
+        };
+
+        match result {
+            Ok(bytes_read) => {
+                logger::log_info(
+                    &format!(
+                        "Pre-flight check passed for {} ({} bytes read in {:.2}ms)",
+                        source.display(),
+                        bytes_read,
+                        check_start.elapsed().as_secs_f64() * 1000.0
+                    ),
+                    Some("installer"),
+                );
+                Ok(bytes_read)
+            }
+            Err(e) if is_preflight_interrupted(&self.task_control) => Err(e),
+            Err(e) => {
+                let archive_name = source
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| task.source_path.clone());
+                Err(anyhow::anyhow!(
+                    "Pre-flight check failed for archive '{}': {}. The file may be corrupted or incompletely downloaded, please re-download it",
+                    archive_name,
+                    e
+                ))
+            }
+        }
+    }
+}
+
+impl Installer {
+    /// Create and delete a file in every folder the tasks install into, before
+    /// any task starts. Installs under Program Files or in a synced folder
+    /// otherwise fail with "access denied" halfway through extraction.
+    pub(super) fn check_targets_writable(&self, tasks: &[InstallTask]) -> Result<()> {
+        let mut probed = HashSet::new();
+        for task in tasks {
+            let target = Path::new(&task.target_path);
+            let Some(dir) = target
+                .parent()
+                .and_then(crate::xplane_location::nearest_existing_dir)
+            else {
+                continue;
+            };
+            if !probed.insert(dir.clone()) {
+                continue;
+            }
+            if let Err(e) = crate::xplane_location::probe_write_access(&dir) {
+                logger::log_error(
+                    &format!("Write probe failed in {}: {}", dir.display(), e),
+                    Some("installer"),
+                );
+                return Err(anyhow::Error::new(e).context(format!(
+                    "Cannot write to '{}'. X-Plane may be installed under Program Files or in a synced folder such as OneDrive",
+                    dir.display()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_preflight_interrupted(task_control: &TaskControl) -> bool {
+    task_control.is_cancelled() || task_control.is_skip_requested()
+}
+
+fn check_preflight_interrupted(task_control: &TaskControl) -> Result<()> {
+    if is_preflight_interrupted(task_control) {
+        return Err(anyhow::anyhow!("Pre-flight check interrupted by user"));
+    }
+    Ok(())
+}
+
+/// Read a stream to the end, honouring cancellation. Returns bytes read.
+fn drain_reader<R: Read>(reader: &mut R, task_control: &TaskControl) -> Result<u64> {
+    let mut buffer = vec![0u8; PREFLIGHT_BUFFER_SIZE];
+    let mut total = 0u64;
+    loop {
+        check_preflight_interrupted(task_control)?;
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(total);
+        }
+        total += read as u64;
+    }
+}
+
+/// Pick up to `max` positions spread evenly over `0..count`, always including
+/// the first and last so truncated downloads are caught.
+fn spread_sample(count: usize, max: usize) -> Vec<usize> {
+    if count == 0 || max == 0 {
+        return Vec::new();
+    }
+    if count <= max {
+        return (0..count).collect();
+    }
+    if max == 1 {
+        return vec![count - 1];
+    }
+
+    let mut picked: Vec<usize> = (0..max).map(|i| i * (count - 1) / (max - 1)).collect();
+    picked.dedup();
+    picked
+}
+
+/// ZIP: validate the central directory and check CRCs of a sample of entries.
+fn preflight_zip(
+    archive: &Path,
+    password: Option<&str>,
+    task_control: &TaskControl,
+    on_progress: PreflightProgressFn,
+) -> Result<u64> {
+    use zip::ZipArchive;
+
+    let prepared = crate::archive_input::prepare_archive_for_read(
+        archive,
+        crate::archive_input::ArchiveFormat::Zip,
+    )?;
+    let archive_len = fs::metadata(prepared.read_path())?.len();
+    let file = fs::File::open(prepared.read_path())?;
+    let mut zip = ZipArchive::new(file)
+        .map_err(|e| anyhow::anyhow!("invalid ZIP central directory: {}", e))?;
+
+    // (index, entry end offset, compressed size, encrypted)
+    let mut entries: Vec<(usize, u64, u64, bool)> = Vec::with_capacity(zip.len());
+    let mut data_end = 0u64;
+    for i in 0..zip.len() {
+        let raw = zip
+            .by_index_raw(i)
+            .map_err(|e| anyhow::anyhow!("invalid ZIP entry #{}: {}", i, e))?;
+        let end = raw.header_start().saturating_add(raw.compressed_size());
+        if end > archive_len {
+            return Err(anyhow::anyhow!(
+                "entry '{}' extends past the end of the file",
+                raw.name()
+            ));
+        }
+        data_end = data_end.max(end);
+        if !raw.is_dir() {
+            entries.push((
+                i,
+                raw.header_start(),
+                raw.compressed_size(),
+                raw.encrypted(),
+            ));
+        }
+    }
+    entries.sort_by_key(|(_, start, _, _)| *start);
+
+    // Central directory and trailer are always read
+    let mut bytes_read = archive_len.saturating_sub(data_end);
+    let sample = spread_sample(entries.len(), PREFLIGHT_ZIP_SAMPLE_ENTRIES);
+    let password_bytes = password.map(|p| p.as_bytes().to_vec());
+
+    for (n, &pos) in sample.iter().enumerate() {
+        check_preflight_interrupted(task_control)?;
+        let (index, _, compressed_size, encrypted) = entries[pos];
+
+        let mut entry = match (encrypted, password_bytes.as_ref()) {
+            (true, Some(pwd)) => zip.by_index_decrypt(index, pwd)?,
+            // No password available yet: the data can't be checked
+            (true, None) => continue,
+            (false, _) => zip.by_index(index)?,
+        };
+        let name = entry.name().to_string();
+        on_progress(n as f64 / sample.len() as f64 * 100.0, Some(name.clone()));
+
+        // The zip reader validates the CRC when the entry is read to the end
+        drain_reader(&mut entry, task_control).map_err(|e| {
+            if is_preflight_interrupted(task_control) {
+                e
+            } else {
+                anyhow::anyhow!("CRC check failed for '{}': {}", name, e)
+            }
+        })?;
+        bytes_read += compressed_size;
+    }
+
+    on_progress(100.0, None);
+    Ok(bytes_read)
+}
+
+/// Reader wrapper that counts how many bytes the wrapped reader delivered
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// 7z: read the start and next headers, which validates both header CRCs.
+fn preflight_7z(
+    archive: &Path,
+    password: Option<&str>,
+    on_progress: PreflightProgressFn,
+) -> Result<u64> {
+    let prepared = crate::archive_input::prepare_archive_for_read(
+        archive,
+        crate::archive_input::ArchiveFormat::SevenZ,
+    )?;
+    on_progress(0.0, None);
+
+    let count = Arc::new(AtomicU64::new(0));
+    let reader = CountingReader {
+        inner: fs::File::open(prepared.read_path())?,
+        count: Arc::clone(&count),
+    };
+    let pwd = match password {
+        Some(pwd) => sevenz_rust2::Password::from(pwd),
+        None => sevenz_rust2::Password::empty(),
+    };
+    sevenz_rust2::ArchiveReader::new(reader, pwd)
+        .map_err(|e| anyhow::anyhow!("invalid 7z header: {}", e))?;
+
+    on_progress(100.0, None);
+    Ok(count.load(Ordering::Relaxed))
+}
+
+/// RAR: run the library's test mode over every file entry.
+fn preflight_rar(
+    archive: &Path,
+    password: Option<&str>,
+    task_control: &TaskControl,
+    on_progress: PreflightProgressFn,
+) -> Result<u64> {
+    let total_unpacked: u64 = unrar::Archive::new(archive)
+        .open_for_listing()
+        .map_err(|e| anyhow::anyhow!("invalid RAR header: {:?}", e))?
+        .flatten()
+        .map(|e| e.unpacked_size)
+        .sum();
+
+    let archive_builder = if let Some(pwd) = password {
+        unrar::Archive::with_password(archive, pwd)
+    } else {
+        unrar::Archive::new(archive)
+    };
+    let mut arch = archive_builder
+        .open_for_processing()
+        .map_err(|e| anyhow::anyhow!("failed to open RAR: {:?}", e))?;
+
+    let mut tested = 0u64;
+    while let Some(header) = arch
+        .read_header()
+        .map_err(|e| anyhow::anyhow!("failed to read RAR header: {:?}", e))?
+    {
+        check_preflight_interrupted(task_control)?;
+        let entry_name = header.entry().filename.to_string_lossy().to_string();
+        arch = if header.entry().is_file() {
+            let size = header.entry().unpacked_size;
+            let next = header
+                .test()
+                .map_err(|e| anyhow::anyhow!("test failed for '{}': {:?}", entry_name, e))?;
+            tested += size;
+            let progress = if total_unpacked > 0 {
+                tested as f64 / total_unpacked as f64 * 100.0
+            } else {
+                100.0
+            };
+            on_progress(progress.min(100.0), Some(entry_name));
+            next
+        } else {
+            header
+                .skip()
+                .map_err(|e| anyhow::anyhow!("failed to skip RAR entry: {:?}", e))?
+        };
+    }
+
+    on_progress(100.0, None);
+    // Test mode reads every data block in the archive
+    Ok(fs::metadata(archive)?.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    fn write_stored_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        for (name, contents) in entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(contents).unwrap();
+        }
+        fs::write(path, writer.finish().unwrap().into_inner()).unwrap();
+    }
+
+    #[test]
+    fn spread_sample_includes_first_and_last() {
+        assert_eq!(spread_sample(0, 16), Vec::<usize>::new());
+        assert_eq!(spread_sample(3, 16), vec![0, 1, 2]);
+        let picked = spread_sample(100, 5);
+        assert_eq!(picked.first(), Some(&0));
+        assert_eq!(picked.last(), Some(&99));
+        assert_eq!(picked.len(), 5);
+    }
+
+    #[test]
+    fn preflight_zip_accepts_valid_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("addon.zip");
+        write_stored_zip(
+            &archive,
+            &[("a.txt", b"hello preflight"), ("b.txt", b"world")],
+        );
+
+        let bytes = preflight_zip(&archive, None, &TaskControl::new(), &mut |_, _| {}).unwrap();
+        assert!(bytes > 0);
+    }
+
+    #[test]
+    fn preflight_zip_detects_crc_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("addon.zip");
+        write_stored_zip(&archive, &[("a.txt", b"hello preflight")]);
+
+        let mut data = fs::read(&archive).unwrap();
+        let pos = data
+            .windows(b"preflight".len())
+            .position(|w| w == b"preflight")
+            .unwrap();
+        data[pos] ^= 0xFF;
+        fs::write(&archive, data).unwrap();
+
+        let err = preflight_zip(&archive, None, &TaskControl::new(), &mut |_, _| {}).unwrap_err();
+        assert!(err.to_string().contains("a.txt"));
+    }
+
+    #[test]
+    fn preflight_zip_rejects_truncated_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("addon.zip");
+        write_stored_zip(&archive, &[("a.txt", b"hello preflight")]);
+
+        let data = fs::read(&archive).unwrap();
+        fs::write(&archive, &data[..data.len() / 2]).unwrap();
+
+        assert!(preflight_zip(&archive, None, &TaskControl::new(), &mut |_, _| {}).is_err());
+    }
+
+    #[test]
+    fn preflight_zip_stops_when_cancelled() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("addon.zip");
+        write_stored_zip(&archive, &[("a.txt", b"hello preflight")]);
+
+        let task_control = TaskControl::new();
+        task_control.request_cancel_all();
+        assert!(preflight_zip(&archive, None, &task_control, &mut |_, _| {}).is_err());
+    }
+}
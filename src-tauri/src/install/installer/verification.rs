@@ -1,4 +1,50 @@
 use super::*;
+use crate::models::{ExtractionFailureDetail, ExtractionFailureStage};
+
+/// Verification was stopped by a cancel or skip request. Carries the stats of
+/// the files checked so far.
+#[derive(Debug)]
+pub(super) struct VerificationInterrupted {
+    pub stats: crate::models::VerificationStats,
+}
+
+impl std::fmt::Display for VerificationInterrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Verification interrupted by user ({} of {} files left unchecked)",
+            self.stats.skipped_files, self.stats.total_files
+        )
+    }
+}
+
+impl std::error::Error for VerificationInterrupted {}
+
+/// Files that failed to write during retries. A file failing again on a later
+/// attempt counts once, so only several distinct files give up on the disk.
+#[derive(Default)]
+struct RetryWriteFailures {
+    paths: HashSet<String>,
+}
+
+impl RetryWriteFailures {
+    /// Files that may fail to write before the target disk is given up on
+    const MAX: usize = 3;
+
+    /// Record a failed write; returns the number of distinct failing files once
+    /// there are more than `MAX`
+    fn record(&mut self, path: &str) -> Option<usize> {
+        self.paths.insert(path.to_string());
+        (self.paths.len() > Self::MAX).then_some(self.paths.len())
+    }
+}
+
+/// Partial stats of a verification stopped by the user, if `error` is one
+pub(super) fn interrupted_stats(error: &anyhow::Error) -> Option<crate::models::VerificationStats> {
+    error
+        .downcast_ref::<VerificationInterrupted>()
+        .map(|e| e.stats.clone())
+}
 
 impl Installer {
     /// Verify installation by checking marker files, verifying file hashes,
@@ -77,8 +123,15 @@ impl Installer {
 
         let total_expected = expected_hashes.len();
 
+        // Files whose CRC already matched while extracting aren't read again
+        let inline_verified_files = ctx.inline_verified_files.lock().unwrap().clone();
+
         logger::log_info(
-            &format!("Verifying {} files with hash checking", total_expected),
+            &format!(
+                "Verifying {} files with hash checking ({} verified inline)",
+                total_expected,
+                inline_verified_files.len()
+            ),
             Some("installer"),
         );
 
@@ -91,25 +144,51 @@ impl Installer {
 
         let verifier = crate::verifier::FileVerifier::new();
 
-        // Use verification with progress callback
-        // Progress range: 15% -> 70% (55% range for hash verification)
+        // Progress range: 15% -> 70% (55% range for hash verification), by bytes
+        // hashed so a few large files don't stall the bar
         let ctx_clone = ctx.clone();
-        let mut failed_files = verifier.verify_files_with_progress(
+        let task_control = self.task_control.clone();
+        let outcome = verifier.verify_files_interruptible(
             target,
             &expected_hashes,
-            move |verified, total| {
-                if total > 0 {
-                    // Map verified/total to 15% -> 70% range
-                    let progress = 15.0 + (verified as f64 / total as f64) * 55.0;
-                    ctx_clone.set_verification_progress(progress);
-                    ctx_clone.emit_progress(
-                        Some(format!("Verified {}/{} files", verified, total)),
-                        InstallPhase::Verifying,
-                    );
-                }
+            &inline_verified_files,
+            move || task_control.is_cancelled() || task_control.is_skip_requested(),
+            move |verified_bytes, total_bytes, checked, total| {
+                let fraction = if total_bytes > 0 {
+                    verified_bytes as f64 / total_bytes as f64
+                } else if total > 0 {
+                    checked as f64 / total as f64
+                } else {
+                    1.0
+                };
+                ctx_clone.set_verification_progress(15.0 + fraction * 55.0);
+                ctx_clone.emit_progress(
+                    Some(format!("Verified {}/{} files", checked, total)),
+                    InstallPhase::Verifying,
+                );
             },
         )?;
 
+        if outcome.stopped {
+            let unchecked = outcome.total_files - outcome.checked_files;
+            let stats = crate::models::VerificationStats {
+                total_files: total_expected,
+                verified_files: total_expected - unchecked - outcome.failed.len(),
+                failed_files: outcome.failed.len(),
+                retried_files: 0,
+                skipped_files: unchecked,
+            };
+            logger::log_info(
+                &format!(
+                    "Verification interrupted by user after {}/{} files",
+                    outcome.checked_files, outcome.total_files
+                ),
+                Some("installer"),
+            );
+            return Err(anyhow::Error::new(VerificationInterrupted { stats }));
+        }
+        let mut failed_files = outcome.failed;
+
         // Update progress: initial verification done (70%)
         ctx.set_verification_progress(70.0);
 
@@ -197,6 +276,7 @@ impl Installer {
         // Check if target directory is empty
         let mut has_files = false;
         for entry in WalkDir::new(target)
+            .follow_links(false)
             .max_depth(5)
             .into_iter()
             .filter_map(|e| e.ok())
@@ -220,6 +300,7 @@ impl Installer {
                 // Check for .acf files
                 let mut found_acf = false;
                 for entry in WalkDir::new(target)
+                    .follow_links(false)
                     .max_depth(3)
                     .into_iter()
                     .filter_map(|e| e.ok())
@@ -253,6 +334,7 @@ impl Installer {
                 // Check for at least one .dsf file
                 let mut found_dsf = false;
                 for entry in WalkDir::new(&earth_nav_data)
+                    .follow_links(false)
                     .max_depth(5)
                     .into_iter()
                     .filter_map(|e| e.ok())
@@ -287,6 +369,7 @@ impl Installer {
                 // Check for .xpl files (in platform-specific folders or root)
                 let mut found_xpl = false;
                 for entry in WalkDir::new(target)
+                    .follow_links(false)
                     .max_depth(3)
                     .into_iter()
                     .filter_map(|e| e.ok())
@@ -313,6 +396,7 @@ impl Installer {
                 // For regular navdata: cycle.json is directly in target
                 let found = if task.display_name.contains("GNS430") {
                     WalkDir::new(target)
+                        .follow_links(false)
                         .max_depth(3)
                         .into_iter()
                         .filter_map(|e| e.ok())
@@ -362,6 +446,7 @@ impl Installer {
     ) -> Result<Vec<crate::models::FileVerificationResult>> {
         const MAX_RETRIES: u8 = 3;
         let source = Path::new(&task.source_path);
+        let mut write_failures = RetryWriteFailures::default();
         let target = Path::new(&task.target_path);
 
         // Reuse verifier instance across retries for better performance
@@ -419,6 +504,22 @@ impl Installer {
                             Some("installer"),
                         );
                         failed.error = Some(e.to_string());
+
+                        // Write errors on several files point at the disk, not
+                        // the archive; retrying the rest would fail the same way
+                        let write_failure = extraction::failure_detail(&e)
+                            .filter(|detail| detail.stage == ExtractionFailureStage::Write);
+                        if let Some(detail) = write_failure {
+                            if let Some(failed_writes) = write_failures.record(&failed.path) {
+                                return Err(extraction::EntryExtractionError {
+                                    detail: ExtractionFailureDetail {
+                                        failed_writes,
+                                        ..detail
+                                    },
+                                }
+                                .into());
+                            }
+                        }
                     }
                 }
             }
@@ -543,7 +644,6 @@ impl Installer {
         _extraction_chain: Option<&crate::models::ExtractionChain>,
         password: Option<&str>,
     ) -> Result<()> {
-        use std::io::copy;
         use zip::ZipArchive;
 
         let file = fs::File::open(archive_path)?;
@@ -561,6 +661,7 @@ impl Installer {
         // Find the file index first
         let mut file_index = None;
         let mut is_encrypted = false;
+        let mut sizes = (0, 0);
         for i in 0..archive.len() {
             // Use by_index_raw to avoid triggering decryption errors when reading metadata
             let file = archive.by_index_raw(i)?;
@@ -569,6 +670,7 @@ impl Installer {
             if name == archive_path_normalized {
                 file_index = Some(i);
                 is_encrypted = file.encrypted();
+                sizes = (file.compressed_size(), file.size());
                 break;
             }
         }
@@ -576,29 +678,41 @@ impl Installer {
         let i = file_index
             .ok_or_else(|| anyhow::anyhow!("File not found in ZIP: {}", archive_path_normalized))?;
 
+        let entry_ctx = extraction::EntryContext {
+            archive: archive_path,
+            entry_path: &archive_path_normalized,
+            index: Some(i),
+            compressed_size: Some(sizes.0),
+            uncompressed_size: Some(sizes.1),
+        };
+
         // Now extract the file
         let target_path = target.join(relative_path);
 
         // Ensure parent directory exists
         if let Some(parent) = target_path.parent() {
-            fs::create_dir_all(parent)?;
+            fs::create_dir_all(parent)
+                .map_err(|e| entry_ctx.fail(ExtractionFailureStage::Write, e))?;
         }
 
         // Extract file
-        let mut outfile = fs::File::create(&target_path)?;
-
         if is_encrypted {
             if let Some(pwd) = password {
-                let mut decrypted = archive
-                    .by_index_decrypt(i, pwd.as_bytes())
-                    .map_err(|e| anyhow::anyhow!("Decryption failed: {:?}", e))?;
-                copy(&mut decrypted, &mut outfile)?;
+                let mut decrypted = archive.by_index_decrypt(i, pwd.as_bytes()).map_err(|e| {
+                    entry_ctx.fail(
+                        ExtractionFailureStage::Read,
+                        format!("Decryption failed: {:?}", e),
+                    )
+                })?;
+                extraction::extract_zip_entry(&mut decrypted, &target_path, &entry_ctx)?;
             } else {
                 return Err(anyhow::anyhow!("Password required for encrypted file"));
             }
         } else {
-            let mut file = archive.by_index(i)?;
-            copy(&mut file, &mut outfile)?;
+            let mut file = archive
+                .by_index(i)
+                .map_err(|e| entry_ctx.fail(ExtractionFailureStage::Read, e))?;
+            extraction::extract_zip_entry(&mut file, &target_path, &entry_ctx)?;
         }
 
         Ok(())
@@ -616,14 +730,12 @@ impl Installer {
         _extraction_chain: Option<&crate::models::ExtractionChain>,
         password: Option<&str>,
     ) -> Result<()> {
-        use tempfile::TempDir;
-
         let sanitized_relative = sanitize_path(Path::new(relative_path))
             .ok_or_else(|| anyhow::anyhow!("Unsafe path in archive: {}", relative_path))?;
 
         // 7z doesn't support single-file extraction easily
         // Extract to temp, then copy the specific file
-        let temp_dir = TempDir::new()?;
+        let temp_dir = crate::staging::temp_dir("verify_7z")?;
         let mut skipped_count = 0usize;
 
         // Extract entire archive to temp
@@ -710,12 +822,21 @@ impl Installer {
             ));
         }
 
+        let entry_ctx = extraction::EntryContext {
+            archive: archive_path,
+            entry_path: relative_path,
+            index: None,
+            compressed_size: None,
+            uncompressed_size: fs::metadata(&temp_file).ok().map(|m| m.len()),
+        };
         let target_file = target.join(&sanitized_relative);
         if let Some(parent) = target_file.parent() {
-            fs::create_dir_all(parent)?;
+            fs::create_dir_all(parent)
+                .map_err(|e| entry_ctx.fail(ExtractionFailureStage::Write, e))?;
         }
 
-        fs::copy(&temp_file, &target_file)?;
+        fs::copy(&temp_file, &target_file)
+            .map_err(|e| entry_ctx.fail(ExtractionFailureStage::Write, e))?;
 
         Ok(())
     }
@@ -731,9 +852,7 @@ impl Installer {
         password: Option<&str>,
     ) -> Result<()> {
         // Create secure temp directory
-        let temp_dir = tempfile::Builder::new()
-            .prefix("xfi_rar_retry_")
-            .tempdir()
+        let temp_dir = crate::staging::temp_dir("rar_retry")
             .context("Failed to create temp directory for RAR retry")?;
 
         // Extract using the typestate pattern (with password if provided)
@@ -837,3 +956,23 @@ impl Installer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_write_failures_count_each_file_once() {
+        let mut failures = RetryWriteFailures::default();
+
+        // The same files failing on every retry attempt do not give up on the disk
+        for _ in 0..3 {
+            assert_eq!(failures.record("a.txt"), None);
+            assert_eq!(failures.record("b/c.txt"), None);
+        }
+        assert_eq!(failures.record("d.txt"), None);
+
+        // A fourth distinct file does
+        assert_eq!(failures.record("e.txt"), Some(4));
+    }
+}
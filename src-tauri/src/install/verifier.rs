@@ -1,15 +1,47 @@
 use anyhow::Result;
 use rayon::prelude::*;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::LazyLock;
 
 use crate::models::{FileHash, FileVerificationResult, HashAlgorithm, VerificationStats};
 
+/// Result of a verification pass that may have been stopped early
+pub struct VerificationOutcome {
+    /// Files that failed verification
+    pub failed: Vec<FileVerificationResult>,
+    /// Files hashed, including failed ones
+    pub checked_files: usize,
+    /// Files that were due to be hashed
+    pub total_files: usize,
+    /// Whether the pass was stopped before every file was hashed
+    pub stopped: bool,
+}
+
+/// Threads used for post-install hashing: all cores but one, so the UI and
+/// other install tasks keep running
+fn verification_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|parallelism| parallelism.get())
+        .unwrap_or(4)
+        .saturating_sub(1)
+        .max(1)
+}
+
+/// Pool shared by every verification pass; `None` if it could not be built,
+/// in which case hashing runs on the global rayon pool
+static VERIFICATION_POOL: LazyLock<Option<rayon::ThreadPool>> = LazyLock::new(|| {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(verification_threads())
+        .thread_name(|index| format!("verify-{}", index))
+        .build()
+        .ok()
+});
+
 pub struct FileVerifier {
     /// Maximum retry attempts per file
     // Reserved for future retry logic on transient I/O failures
@@ -44,20 +76,53 @@ impl FileVerifier {
     ) -> Result<Vec<FileVerificationResult>>
     where
         F: Fn(usize, usize) + Send + Sync,
+    {
+        let outcome = self.verify_files_interruptible(
+            target_dir,
+            expected_hashes,
+            &HashSet::new(),
+            || false,
+            |_, _, checked, total| progress_callback(checked, total),
+        )?;
+        let failed = outcome.failed;
+
+        if failed.is_empty() {
+            crate::logger::log_info(
+                &format!("All {} files verified successfully", outcome.total_files),
+                Some("verifier"),
+            );
+        } else {
+            crate::logger::log_error(
+                &format!("{} files failed verification", failed.len()),
+                Some("verifier"),
+            );
+        }
+
+        Ok(failed)
+    }
+
+    /// Verify files on a bounded thread pool, skipping `skip` (paths already
+    /// verified elsewhere). `should_stop` is polled before each file; once it
+    /// returns true the remaining files are left unchecked.
+    /// `progress_callback` receives (verified_bytes, total_bytes, checked_files, total_files).
+    pub fn verify_files_interruptible<S, F>(
+        &self,
+        target_dir: &Path,
+        expected_hashes: &HashMap<String, FileHash>,
+        skip: &HashSet<String>,
+        should_stop: S,
+        progress_callback: F,
+    ) -> Result<VerificationOutcome>
+    where
+        S: Fn() -> bool + Send + Sync,
+        F: Fn(u64, u64, usize, usize) + Send + Sync,
     {
         use walkdir::WalkDir;
 
-        crate::logger::log_info(
-            &format!(
-                "Verifying {} files in {:?}",
-                expected_hashes.len(),
-                target_dir
-            ),
-            Some("verifier"),
-        );
+        let folded_keys = crate::fs_case::is_case_insensitive(target_dir)
+            .then(|| fold_expected_keys(expected_hashes));
 
-        // Collect all files to verify
-        let files_to_verify: Vec<(PathBuf, String)> = WalkDir::new(target_dir)
+        let files_to_verify: Vec<(PathBuf, String, u64)> = WalkDir::new(target_dir)
             .follow_links(false)
             .into_iter()
             .filter_map(|e| e.ok())
@@ -66,52 +131,70 @@ impl FileVerifier {
                 let path = entry.path();
                 let relative = path.strip_prefix(target_dir).ok()?;
                 let relative_str = relative.to_string_lossy().replace('\\', "/");
-
-                // Only verify files we have hashes for
-                if expected_hashes.contains_key(&relative_str) {
-                    Some((path.to_path_buf(), relative_str))
-                } else {
-                    None
+                let key = expected_key(expected_hashes, folded_keys.as_ref(), relative_str)?;
+                if skip.contains(&key) {
+                    return None;
                 }
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                Some((path.to_path_buf(), key, size))
             })
             .collect();
 
-        let total = files_to_verify.len();
-        let verified_count = Arc::new(AtomicUsize::new(0));
-
-        // Parallel verification with progress tracking
-        let results: Vec<FileVerificationResult> = files_to_verify
-            .par_iter()
-            .filter_map(|(path, relative_path)| {
-                // Get expected hash (should always exist since we built files_to_verify from expected_hashes)
-                let expected = expected_hashes.get(relative_path)?;
-                let result = self.verify_single_file(path, relative_path, expected);
-
-                // Update progress
-                let count = verified_count.fetch_add(1, Ordering::SeqCst) + 1;
-                progress_callback(count, total);
+        let total_files = files_to_verify.len();
+        let total_bytes: u64 = files_to_verify.iter().map(|(_, _, size)| size).sum();
+        crate::logger::log_info(
+            &format!(
+                "Verifying {} files ({} bytes) in {:?}, {} already verified",
+                total_files,
+                total_bytes,
+                target_dir,
+                skip.len()
+            ),
+            Some("verifier"),
+        );
 
-                Some(result)
-            })
-            .collect();
+        let verified_bytes = AtomicU64::new(0);
+        let checked = AtomicUsize::new(0);
+        let stopped = AtomicBool::new(false);
+
+        let hash_all = || -> Vec<FileVerificationResult> {
+            files_to_verify
+                .par_iter()
+                .filter_map(|(path, relative_path, size)| {
+                    if stopped.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                    if should_stop() {
+                        stopped.store(true, Ordering::Relaxed);
+                        return None;
+                    }
+
+                    let expected = expected_hashes.get(relative_path)?;
+                    let result = self.verify_single_file(path, relative_path, expected);
+
+                    let bytes = verified_bytes.fetch_add(*size, Ordering::SeqCst) + size;
+                    let count = checked.fetch_add(1, Ordering::SeqCst) + 1;
+                    progress_callback(bytes, total_bytes, count, total_files);
+
+                    Some(result)
+                })
+                .collect()
+        };
+        let results = match VERIFICATION_POOL.as_ref() {
+            Some(pool) => pool.install(hash_all),
+            None => hash_all(),
+        };
 
-        // Filter failed files
+        let checked_files = checked.load(Ordering::SeqCst);
         let failed: Vec<FileVerificationResult> =
             results.into_iter().filter(|r| !r.success).collect();
 
-        if failed.is_empty() {
-            crate::logger::log_info(
-                &format!("All {} files verified successfully", files_to_verify.len()),
-                Some("verifier"),
-            );
-        } else {
-            crate::logger::log_error(
-                &format!("{} files failed verification", failed.len()),
-                Some("verifier"),
-            );
-        }
-
-        Ok(failed)
+        Ok(VerificationOutcome {
+            failed,
+            checked_files,
+            total_files,
+            stopped: stopped.load(Ordering::SeqCst),
+        })
     }
 
     /// Verify a single file
@@ -229,6 +312,29 @@ impl FileVerifier {
     }
 }
 
+/// Lowercased expected path -> expected path, for case-insensitive lookups
+fn fold_expected_keys(expected_hashes: &HashMap<String, FileHash>) -> HashMap<String, &String> {
+    expected_hashes
+        .keys()
+        .map(|key| (key.to_lowercase(), key))
+        .collect()
+}
+
+/// Key in `expected_hashes` for an installed file's relative path, falling back to
+/// a case-insensitive match when `folded_keys` is provided
+fn expected_key(
+    expected_hashes: &HashMap<String, FileHash>,
+    folded_keys: Option<&HashMap<String, &String>>,
+    relative_path: String,
+) -> Option<String> {
+    if expected_hashes.contains_key(&relative_path) {
+        return Some(relative_path);
+    }
+    folded_keys?
+        .get(&relative_path.to_lowercase())
+        .map(|key| (*key).clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,6 +461,60 @@ mod tests {
         assert!(result.error.is_none());
     }
 
+    #[test]
+    fn test_verify_files_interruptible_skips_and_stops() {
+        let verifier = FileVerifier::new();
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("test.txt"), b"Hello, World!").unwrap();
+        fs::write(temp_dir.path().join("inline.txt"), b"already checked").unwrap();
+
+        let expected_hashes = HashMap::from([
+            (
+                "test.txt".to_string(),
+                FileHash {
+                    path: "test.txt".to_string(),
+                    hash: "dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f"
+                        .to_string(),
+                    algorithm: HashAlgorithm::Sha256,
+                },
+            ),
+            (
+                "inline.txt".to_string(),
+                FileHash {
+                    path: "inline.txt".to_string(),
+                    hash: "not the real hash".to_string(),
+                    algorithm: HashAlgorithm::Sha256,
+                },
+            ),
+        ]);
+        let skip = HashSet::from(["inline.txt".to_string()]);
+
+        let outcome = verifier
+            .verify_files_interruptible(
+                temp_dir.path(),
+                &expected_hashes,
+                &skip,
+                || false,
+                |_, _, _, _| {},
+            )
+            .unwrap();
+        assert!(!outcome.stopped);
+        assert!(outcome.failed.is_empty());
+        assert_eq!((outcome.checked_files, outcome.total_files), (1, 1));
+
+        let stopped = verifier
+            .verify_files_interruptible(
+                temp_dir.path(),
+                &expected_hashes,
+                &HashSet::new(),
+                || true,
+                |_, _, _, _| {},
+            )
+            .unwrap();
+        assert!(stopped.stopped);
+        assert_eq!((stopped.checked_files, stopped.total_files), (0, 2));
+    }
+
     #[test]
     fn test_verify_single_file_mismatch() {
         let verifier = FileVerifier::new();
@@ -400,6 +560,37 @@ mod tests {
         assert!(result.error.is_some());
     }
 
+    #[test]
+    fn test_expected_key_folds_case_only_when_requested() {
+        let mut expected_hashes = HashMap::new();
+        expected_hashes.insert(
+            "Objects/tree.obj".to_string(),
+            FileHash {
+                path: "Objects/tree.obj".to_string(),
+                hash: "abc123".to_string(),
+                algorithm: HashAlgorithm::Sha256,
+            },
+        );
+        let folded = fold_expected_keys(&expected_hashes);
+
+        assert_eq!(
+            expected_key(&expected_hashes, None, "Objects/tree.obj".to_string()),
+            Some("Objects/tree.obj".to_string())
+        );
+        assert_eq!(
+            expected_key(&expected_hashes, None, "objects/tree.obj".to_string()),
+            None
+        );
+        assert_eq!(
+            expected_key(
+                &expected_hashes,
+                Some(&folded),
+                "objects/tree.obj".to_string()
+            ),
+            Some("Objects/tree.obj".to_string())
+        );
+    }
+
     #[test]
     fn test_crc32_empty_file() {
         let verifier = FileVerifier::new();
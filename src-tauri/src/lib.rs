@@ -5,8 +5,14 @@ mod app_dirs;
 mod archive_input;
 #[path = "core/cache.rs"]
 mod cache;
+#[path = "core/dir_walk.rs"]
+mod dir_walk;
 #[path = "core/error.rs"]
 mod error;
+#[path = "core/file_explorer.rs"]
+mod file_explorer;
+#[path = "core/fs_case.rs"]
+mod fs_case;
 #[path = "core/logger.rs"]
 mod logger;
 #[path = "core/path_utils.rs"]
@@ -15,8 +21,20 @@ mod path_utils;
 mod performance;
 #[path = "core/registry.rs"]
 mod registry;
+#[path = "core/settings_store.rs"]
+mod settings_store;
+#[path = "core/staging.rs"]
+mod staging;
+#[path = "core/stored_data.rs"]
+mod stored_data;
+#[path = "core/support_bundle.rs"]
+mod support_bundle;
 #[path = "core/task_control.rs"]
 mod task_control;
+#[path = "core/user_messages.rs"]
+mod user_messages;
+#[path = "core/xplane_processes.rs"]
+mod xplane_processes;
 
 // Data
 #[path = "data/database/mod.rs"]
@@ -25,6 +43,10 @@ mod database;
 mod models;
 
 // Analysis & scanning
+#[path = "analysis/analysis_cache.rs"]
+mod analysis_cache;
+#[path = "analysis/analysis_suppressions.rs"]
+mod analysis_suppressions;
 #[path = "analysis/analyzer.rs"]
 mod analyzer;
 #[path = "analysis/crash_analysis.rs"]
@@ -33,28 +55,74 @@ mod crash_analysis;
 mod hash_collector;
 #[path = "analysis/livery_patterns.rs"]
 mod livery_patterns;
+#[path = "analysis/log_history.rs"]
+mod log_history;
+#[path = "analysis/log_report_summary.rs"]
+mod log_report_summary;
+#[path = "analysis/log_source.rs"]
+mod log_source;
+#[path = "analysis/plugin_platforms.rs"]
+mod plugin_platforms;
 #[path = "analysis/scanner/mod.rs"]
 mod scanner;
+#[path = "analysis/xplane_installs.rs"]
+mod xplane_installs;
+#[path = "analysis/xplane_location.rs"]
+mod xplane_location;
+#[path = "analysis/xplane_version.rs"]
+mod xplane_version;
 
 // Installation
 #[path = "install/atomic_installer.rs"]
 mod atomic_installer;
+#[path = "install/duplicate_prompt.rs"]
+mod duplicate_prompt;
+#[path = "install/install_history.rs"]
+mod install_history;
+#[path = "install/install_markers.rs"]
+mod install_markers;
+#[path = "install/install_profiles.rs"]
+mod install_profiles;
 #[path = "install/installer/mod.rs"]
 mod installer;
 #[path = "install/verifier.rs"]
 mod verifier;
 
 // Management
+#[path = "management/addon_backups.rs"]
+mod addon_backups;
+#[path = "management/addon_icons.rs"]
+mod addon_icons;
+#[path = "management/addon_update_checks.rs"]
+mod addon_update_checks;
+#[path = "management/addon_update_diff.rs"]
+mod addon_update_diff;
 #[path = "management/addon_updater.rs"]
 mod addon_updater;
 #[path = "management/csl_index.rs"]
 mod csl_index;
 #[path = "management/gateway.rs"]
 mod gateway;
+#[path = "management/item_relocation.rs"]
+mod item_relocation;
+#[path = "management/launch_profiles.rs"]
+mod launch_profiles;
+#[path = "management/lua_duplicates.rs"]
+mod lua_duplicates;
+#[path = "management/maintenance.rs"]
+mod maintenance;
 #[path = "management/management_index.rs"]
 mod management_index;
+#[path = "management/plugin_duplicates.rs"]
+mod plugin_duplicates;
+#[path = "management/plugin_quarantine.rs"]
+mod plugin_quarantine;
+#[path = "management/preference_backups.rs"]
+mod preference_backups;
 #[path = "management/skunk_updater.rs"]
 mod skunk_updater;
+#[path = "management/update_preflight.rs"]
+mod update_preflight;
 #[path = "management/x_updater_profile.rs"]
 mod x_updater_profile;
 #[path = "management/zibo_updater.rs"]
@@ -69,20 +137,50 @@ mod screenshot;
 mod map;
 
 // Scenery
+#[path = "scenery/airport_lookup.rs"]
+mod airport_lookup;
+#[path = "scenery/deferred_scenery.rs"]
+mod deferred_scenery;
 #[path = "scenery/geo_regions.rs"]
 mod geo_regions;
+#[path = "scenery/library_plan.rs"]
+mod library_plan;
 #[path = "scenery/scenery_classifier.rs"]
 mod scenery_classifier;
+#[path = "scenery/scenery_import.rs"]
+mod scenery_import;
 #[path = "scenery/scenery_index.rs"]
 mod scenery_index;
+#[path = "scenery/scenery_order.rs"]
+mod scenery_order;
+#[path = "scenery/scenery_packs_diff.rs"]
+mod scenery_packs_diff;
 #[path = "scenery/scenery_packs_manager.rs"]
 mod scenery_packs_manager;
+#[path = "scenery/scenery_packs_repair.rs"]
+mod scenery_packs_repair;
+#[path = "scenery/scenery_stats_history.rs"]
+mod scenery_stats_history;
+#[path = "scenery/scenery_watcher.rs"]
+mod scenery_watcher;
 
 // Services (remote/data)
+#[path = "services/download_queue.rs"]
+mod download_queue;
+#[path = "services/download_retry.rs"]
+mod download_retry;
+#[path = "services/http_client.rs"]
+mod http_client;
 #[path = "services/library_links.rs"]
 mod library_links;
+#[path = "services/remote_dataset.rs"]
+mod remote_dataset;
+#[path = "services/submission_queue.rs"]
+mod submission_queue;
 #[path = "services/updater.rs"]
 mod updater;
+#[path = "services/url_download.rs"]
+mod url_download;
 
 // Activity log
 #[path = "core/activity.rs"]
@@ -92,25 +190,28 @@ mod activity;
 #[path = "analysis/disk_usage.rs"]
 mod disk_usage;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::error::ToTauriError;
+use crate::error::{ApiContext, ToTauriError};
 use analyzer::Analyzer;
 use installer::Installer;
 use models::{
     ActivityLogEntry, ActivityLogPage, AircraftInfo, AnalysisResult, InstallResult, InstallTask,
-    LiveryInfo, LuaScriptInfo, ManagementData, NavdataBackupInfo, NavdataManagerInfo, PluginInfo,
-    PresetApplyResult, PresetExportFormat, PresetLockState, PresetSnapshot, PresetSummary,
-    SceneryIndexScanResult, SceneryIndexStats, SceneryIndexStatus, SceneryManagerData,
-    SceneryPackageInfo, GLOBAL_AIRPORTS_ENTRY_NAME,
+    LaunchProfile, LaunchProfileSaveResult, LiveryInfo, LuaScriptInfo, ManagementData,
+    NavdataBackupInfo, NavdataConsistencyReport, NavdataManagerInfo, PluginInfo,
+    PluginQuarantineResult, PresetApplyResult, PresetExportFormat, PresetLockState, PresetSnapshot,
+    PresetSummary, SceneryIndexScanResult, SceneryIndexStats, SceneryIndexStatus,
+    SceneryManagerData, SceneryPackageInfo, SceneryStatsSnapshot, XPlaneLogSnapshot,
+    GLOBAL_AIRPORTS_ENTRY_NAME,
 };
 use scenery_index::SceneryIndexManager;
 use scenery_packs_manager::SceneryPacksManager;
 use screenshot::{SaveEditedImageRequest, ScreenshotMediaItem, ScreenshotOperationResult};
 use task_control::TaskControl;
+use user_messages::UserMsg;
 
 use sea_orm::DatabaseConnection;
 use tauri::{Emitter, Manager, State};
@@ -201,9 +302,8 @@ async fn create_library_link_issue(
     let api_url = std::env::var("XFAST_LINK_API_URL")
         .unwrap_or_else(|_| "https://x-fast-manager.vercel.app/api/library-link".to_string());
 
-    let client = reqwest::Client::builder()
-        .user_agent("XFast Manager")
-        .timeout(std::time::Duration::from_secs(15))
+    let client = http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(std::time::Duration::from_secs(15)))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
@@ -247,6 +347,9 @@ async fn create_library_link_issue(
 struct BugReportResult {
     issue_url: String,
     issue_number: u64,
+    /// Set when the report could not be delivered yet and was queued for retry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    queued: Option<submission_queue::QueuedSubmission>,
 }
 
 #[derive(serde::Serialize)]
@@ -254,6 +357,77 @@ struct FeedbackIssueResult {
     issue_url: String,
     issue_number: u64,
     issue_title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    queued: Option<submission_queue::QueuedSubmission>,
+}
+
+/// Extract the issue number from a proxy response, falling back to the issue URL
+fn issue_number_from_response(response_json: &serde_json::Value, issue_url: &str) -> u64 {
+    let issue_number = response_json
+        .get("issueNumber")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    if issue_number != 0 {
+        return issue_number;
+    }
+
+    // Fallback: if the server didn't return issueNumber, extract it from URL query or tail.
+    reqwest::Url::parse(issue_url)
+        .ok()
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(k, _)| k == "number" || k == "issueNumber")
+                .and_then(|(_, v)| v.parse::<u64>().ok())
+                .or_else(|| {
+                    url.path_segments()
+                        .and_then(|segments| segments.last())
+                        .and_then(|s| s.parse::<u64>().ok())
+                })
+        })
+        .unwrap_or(0)
+}
+
+/// Log analysis of `xplane_path` for a bug report, with the crash dump
+/// analysis when the log reports a crash. None when Log.txt can't be analyzed;
+/// the report is sent without it.
+async fn bug_report_log_summary(
+    xplane_path: String,
+) -> Option<log_report_summary::XPlaneLogSummary> {
+    let path = xplane_path.clone();
+    let analysis =
+        match tokio::task::spawn_blocking(move || analyze_xplane_log(Some(path), None)).await {
+            Ok(Ok(analysis)) => analysis,
+            Ok(Err(e)) => {
+                logger::log_info(
+                    &format!("Bug report sent without X-Plane log analysis: {}", e),
+                    Some("bug_report"),
+                );
+                return None;
+            }
+            Err(e) => {
+                logger::log_error(&format!("Task join error: {}", e), Some("bug_report"));
+                return None;
+            }
+        };
+
+    let deep_crash = if analysis.crash_detected {
+        crash_analysis::analyze_crash_report(&xplane_path, None, &analysis.issues, false)
+            .await
+            .unwrap_or_else(|e| {
+                logger::log_info(
+                    &format!("Bug report sent without crash dump analysis: {}", e),
+                    Some("bug_report"),
+                );
+                None
+            })
+    } else {
+        None
+    };
+
+    Some(log_report_summary::summarize(
+        &analysis,
+        deep_crash.as_ref(),
+    ))
 }
 
 #[tauri::command]
@@ -262,46 +436,45 @@ async fn create_bug_report_issue(
     error_message: String,
     logs: Option<String>,
     category: Option<String>,
+    error_code: Option<String>,
+    xplane_path: Option<String>,
+    include_xplane_analysis: Option<bool>,
 ) -> Result<BugReportResult, String> {
     let app_version = env!("CARGO_PKG_VERSION").to_string();
     let os = std::env::consts::OS.to_string();
     let arch = std::env::consts::ARCH.to_string();
 
-    let api_url = std::env::var("XFAST_BUG_REPORT_API_URL")
-        .unwrap_or_else(|_| "https://x-fast-manager.vercel.app/api/bug-report".to_string());
-
-    let client = reqwest::Client::builder()
-        .user_agent("XFast Manager")
-        .timeout(std::time::Duration::from_secs(15))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let response = client
-        .post(&api_url)
-        .header("Content-Type", "application/json")
-        .json(&serde_json::json!({
-            "appVersion": app_version,
-            "os": os,
-            "arch": arch,
-            "errorTitle": error_title.trim(),
-            "errorMessage": error_message.trim(),
-            "logs": logs.as_deref().unwrap_or(""),
-            "category": category.as_deref().unwrap_or("Other")
-        }))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to submit bug report: {}", e))?;
+    let mut payload = serde_json::json!({
+        "appVersion": app_version,
+        "os": os,
+        "arch": arch,
+        "errorTitle": error_title.trim(),
+        "errorMessage": error_message.trim(),
+        "logs": logs.as_deref().unwrap_or(""),
+        "category": category.as_deref().unwrap_or("Other"),
+        "errorCode": error_code.as_deref().map(str::trim).filter(|code| !code.is_empty())
+    });
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Bug report API error {}: {}", status, error_text));
+    if include_xplane_analysis.unwrap_or(false) {
+        if let Some(xplane_path) = xplane_path.filter(|path| !path.trim().is_empty()) {
+            if let Some(summary) = bug_report_log_summary(xplane_path).await {
+                log_report_summary::attach_to_payload(&mut payload, summary);
+            }
+        }
     }
 
-    let response_json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
+    let kind = submission_queue::SubmissionKind::BugReport;
+    let response_json = match submission_queue::post_submission(kind, &payload).await {
+        Ok(response_json) => response_json,
+        Err(e) => {
+            let queued = submission_queue::queue_or_fail(kind, payload, e)?;
+            return Ok(BugReportResult {
+                issue_url: String::new(),
+                issue_number: 0,
+                queued: Some(queued),
+            });
+        }
+    };
 
     let issue_url = response_json
         .get("issueUrl")
@@ -313,33 +486,12 @@ async fn create_bug_report_issue(
         return Err("Bug report created but response URL missing".to_string());
     }
 
-    let issue_number = response_json
-        .get("issueNumber")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0);
-
-    // Fallback: if the server didn't return issueNumber, extract it from URL query or tail.
-    let issue_number = if issue_number == 0 {
-        reqwest::Url::parse(&issue_url)
-            .ok()
-            .and_then(|url| {
-                url.query_pairs()
-                    .find(|(k, _)| k == "number" || k == "issueNumber")
-                    .and_then(|(_, v)| v.parse::<u64>().ok())
-                    .or_else(|| {
-                        url.path_segments()
-                            .and_then(|segments| segments.last())
-                            .and_then(|s| s.parse::<u64>().ok())
-                    })
-            })
-            .unwrap_or(0)
-    } else {
-        issue_number
-    };
+    let issue_number = issue_number_from_response(&response_json, &issue_url);
 
     Ok(BugReportResult {
         issue_url,
         issue_number,
+        queued: None,
     })
 }
 
@@ -363,40 +515,28 @@ async fn create_feedback_issue(
     let arch = std::env::consts::ARCH.to_string();
     let feedback_type = feedback_type.trim().to_lowercase();
 
-    let api_url = std::env::var("XFAST_FEEDBACK_API_URL")
-        .unwrap_or_else(|_| "https://x-fast-manager.vercel.app/api/feedback-issue".to_string());
-
-    let client = reqwest::Client::builder()
-        .user_agent("XFast Manager")
-        .timeout(std::time::Duration::from_secs(15))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let response = client
-        .post(&api_url)
-        .header("Content-Type", "application/json")
-        .json(&serde_json::json!({
-            "title": feedback_title,
-            "type": feedback_type,
-            "content": feedback_content,
-            "appVersion": app_version,
-            "os": os,
-            "arch": arch
-        }))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to submit feedback: {}", e))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("Feedback API error {}: {}", status, error_text));
-    }
+    let payload = serde_json::json!({
+        "title": feedback_title,
+        "type": feedback_type,
+        "content": feedback_content,
+        "appVersion": app_version,
+        "os": os,
+        "arch": arch
+    });
 
-    let response_json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse API response: {}", e))?;
+    let kind = submission_queue::SubmissionKind::Feedback;
+    let response_json = match submission_queue::post_submission(kind, &payload).await {
+        Ok(response_json) => response_json,
+        Err(e) => {
+            let queued = submission_queue::queue_or_fail(kind, payload, e)?;
+            return Ok(FeedbackIssueResult {
+                issue_url: String::new(),
+                issue_number: 0,
+                issue_title: feedback_title.to_string(),
+                queued: Some(queued),
+            });
+        }
+    };
 
     let issue_url = response_json
         .get("issueUrl")
@@ -408,38 +548,21 @@ async fn create_feedback_issue(
         return Err("Feedback created but response URL missing".to_string());
     }
 
-    let issue_number = response_json
-        .get("issueNumber")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0);
-    let issue_number = if issue_number == 0 {
-        reqwest::Url::parse(&issue_url)
-            .ok()
-            .and_then(|url| {
-                url.query_pairs()
-                    .find(|(k, _)| k == "number" || k == "issueNumber")
-                    .and_then(|(_, v)| v.parse::<u64>().ok())
-                    .or_else(|| {
-                        url.path_segments()
-                            .and_then(|segments| segments.last())
-                            .and_then(|s| s.parse::<u64>().ok())
-                    })
-            })
-            .unwrap_or(0)
-    } else {
-        issue_number
-    };
+    let issue_number = issue_number_from_response(&response_json, &issue_url);
 
     Ok(FeedbackIssueResult {
         issue_url,
         issue_number,
         issue_title: feedback_title.to_string(),
+        queued: None,
     })
 }
 
 #[derive(serde::Serialize)]
 struct IssueCommentPostResult {
     ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    queued: Option<submission_queue::QueuedSubmission>,
 }
 
 #[tauri::command]
@@ -456,36 +579,36 @@ async fn post_issue_comment(
         return Err("comment_body is required".to_string());
     }
 
-    let api_url = std::env::var("XFAST_ISSUE_COMMENT_API_URL")
-        .unwrap_or_else(|_| "https://x-fast-manager.vercel.app/api/issue-comment".to_string());
+    let payload = serde_json::json!({
+        "issueNumber": issue_number,
+        "commentBody": body
+    });
 
-    let client = reqwest::Client::builder()
-        .user_agent("XFast Manager")
-        .timeout(std::time::Duration::from_secs(15))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let kind = submission_queue::SubmissionKind::IssueComment;
+    if let Err(e) = submission_queue::post_submission(kind, &payload).await {
+        let queued = submission_queue::queue_or_fail(kind, payload, e)?;
+        return Ok(IssueCommentPostResult {
+            ok: false,
+            queued: Some(queued),
+        });
+    }
 
-    let response = client
-        .post(&api_url)
-        .header("Content-Type", "application/json")
-        .json(&serde_json::json!({
-            "issueNumber": issue_number,
-            "commentBody": body
-        }))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to submit issue comment: {}", e))?;
+    Ok(IssueCommentPostResult {
+        ok: true,
+        queued: None,
+    })
+}
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(format!(
-            "Issue comment API error {}: {}",
-            status, error_text
-        ));
-    }
+#[tauri::command]
+fn get_pending_submissions() -> Vec<submission_queue::PendingSubmission> {
+    submission_queue::pending_submissions()
+}
 
-    Ok(IssueCommentPostResult { ok: true })
+#[tauri::command]
+async fn send_pending_submissions(
+    id: Option<String>,
+) -> Result<submission_queue::SubmissionFlushResult, String> {
+    Ok(submission_queue::flush_pending(true, id.as_deref()).await)
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -550,9 +673,8 @@ async fn check_issue_updates(
         return Err("issue_number must be greater than 0".to_string());
     }
 
-    let client = reqwest::Client::builder()
-        .user_agent("XFast Manager")
-        .timeout(std::time::Duration::from_secs(10))
+    let client = http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(std::time::Duration::from_secs(10)))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
@@ -592,9 +714,8 @@ async fn get_issue_detail(
     let page = page.unwrap_or(1).max(1);
     let per_page = per_page.unwrap_or(30).clamp(1, 100);
 
-    let client = reqwest::Client::builder()
-        .user_agent("XFast Manager")
-        .timeout(std::time::Duration::from_secs(10))
+    let client = http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(std::time::Duration::from_secs(10)))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
@@ -628,15 +749,17 @@ async fn get_issue_detail(
 
 #[tauri::command]
 async fn analyze_addons(
+    db: State<'_, DatabaseState>,
     paths: Vec<String>,
     xplane_path: String,
     passwords: Option<HashMap<String, String>>,
     verification_preferences: Option<HashMap<String, bool>>,
+    livery_target_hints: Option<HashMap<String, String>>,
 ) -> Result<AnalysisResult, String> {
     livery_patterns::ensure_patterns_loaded().await;
 
     // Run the analysis in a blocking thread pool to avoid blocking the async runtime
-    tokio::task::spawn_blocking(move || {
+    let mut result = tokio::task::spawn_blocking(move || {
         log_debug!(&format!("Analyzing paths: {:?}", paths), "analysis");
         log_debug!(
             &format!("Starting analysis with X-Plane path: {}", xplane_path),
@@ -644,17 +767,175 @@ async fn analyze_addons(
         );
 
         let analyzer = Analyzer::new();
-        Ok(analyzer.analyze(paths, &xplane_path, passwords, verification_preferences))
+        Ok(analyzer.analyze_with_livery_hints(
+            paths,
+            &xplane_path,
+            passwords,
+            verification_preferences,
+            &livery_target_hints.unwrap_or_default(),
+        ))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    if let Err(e) =
+        analysis_suppressions::filter_compatibility_warnings(&db.get(), &mut result).await
+    {
+        log_debug!(
+            &format!("Failed to apply analysis suppressions: {}", e),
+            "analysis"
+        );
+    }
+    if let Err(e) = install_profiles::attach_profiles(&db.get(), &mut result.tasks).await {
+        log_debug!(
+            &format!("Failed to load install profiles: {}", e),
+            "analysis"
+        );
+    }
+    Ok(result)
+}
+
+/// Install tasks for the items of an earlier `analyze_addons` call the user picked,
+/// by task id, without scanning the inputs again. Fails when the analysis expired
+/// or an input changed since; analyze again in that case.
+#[tauri::command]
+async fn build_install_tasks(
+    selected_item_ids: Vec<String>,
+    xplane_path: String,
+    overrides: Option<HashMap<String, models::InstallTaskOverride>>,
+) -> error::ApiResult<Vec<InstallTask>> {
+    validate_xplane_root_path(std::path::Path::new(&xplane_path))
+        .map_err(error::ApiError::validation)?;
+
+    tokio::task::spawn_blocking(move || {
+        let (items, missing) = analysis_cache::lookup(&selected_item_ids);
+        if !missing.is_empty() {
+            return Err(error::ApiError::not_found(format!(
+                "Analysis results are no longer available for {} item(s), analyze again",
+                missing.len()
+            )));
+        }
+        Analyzer::new()
+            .build_tasks_from_cache(items, &xplane_path, &overrides.unwrap_or_default())
+            .map_err(error::ApiError::validation)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// Download an addon archive from a pasted URL and analyze it.
+/// Progress is reported through `addon-update-progress` events with
+/// `itemType` set to `url-download`; cancel with `cancel_installation`.
+#[tauri::command]
+async fn download_and_analyze(
+    app_handle: tauri::AppHandle,
+    db: State<'_, DatabaseState>,
+    task_control: State<'_, TaskControl>,
+    url: String,
+    xplane_path: String,
+) -> Result<models::UrlDownloadAnalysis, String> {
+    task_control.reset();
+    let event_handle = app_handle.clone();
+    let progress_callback: addon_updater::AddonUpdateProgressCallback = Arc::new(move |event| {
+        let _ = event_handle.emit("addon-update-progress", event);
+    });
+
+    let max_bytes = settings_store::get().max_url_download_bytes;
+    let download = url_download::download_from_url(
+        &url,
+        max_bytes,
+        task_control.inner(),
+        Some(progress_callback),
+    )
+    .await?;
+
+    let local_path = download.file_path.to_string_lossy().to_string();
+    let analysis =
+        match analyze_addons(db, vec![local_path.clone()], xplane_path, None, None, None).await {
+            Ok(analysis) => analysis,
+            Err(e) => {
+                let _ = url_download::discard_download(&download.file_path);
+                return Err(e);
+            }
+        };
+
+    Ok(models::UrlDownloadAnalysis {
+        local_path,
+        file_name: download.file_name,
+        size_bytes: download.size_bytes,
+        analysis,
+    })
+}
+
+/// Remove an archive fetched by `download_and_analyze` once it is no longer needed
+#[tauri::command]
+fn discard_url_download(local_path: String) -> Result<(), String> {
+    url_download::discard_download(std::path::Path::new(&local_path))
+}
+
+/// Queue addon downloads from direct URLs. Two run at a time; each finished file
+/// is analyzed and the result attached to its queue item. Progress is reported
+/// through `download-queue-progress` events.
+#[tauri::command]
+async fn enqueue_downloads(
+    app_handle: tauri::AppHandle,
+    queue: State<'_, download_queue::DownloadQueue>,
+    urls: Vec<String>,
+    xplane_path: String,
+) -> error::ApiResult<Vec<models::DownloadQueueItem>> {
+    validate_xplane_root_path(std::path::Path::new(&xplane_path))
+        .map_err(error::ApiError::validation)?;
+    queue.enqueue(&app_handle, urls, xplane_path).await
+}
+
+/// Every item of the download queue, oldest first
+#[tauri::command]
+async fn get_download_queue(
+    db: State<'_, DatabaseState>,
+    queue: State<'_, download_queue::DownloadQueue>,
+) -> error::ApiResult<Vec<models::DownloadQueueItem>> {
+    queue.list(&db.get()).await
+}
+
+/// Remove a download queue item and its files, stopping it if it is running.
+/// Returns whether the item existed.
+#[tauri::command]
+async fn cancel_download(
+    db: State<'_, DatabaseState>,
+    queue: State<'_, download_queue::DownloadQueue>,
+    id: i64,
+) -> error::ApiResult<bool> {
+    queue.cancel(&db.get(), id).await
+}
+
+/// Queue a failed download again, resuming its partial file when possible
+#[tauri::command]
+async fn retry_download(
+    app_handle: tauri::AppHandle,
+    queue: State<'_, download_queue::DownloadQueue>,
+    id: i64,
+) -> error::ApiResult<models::DownloadQueueItem> {
+    queue.retry(&app_handle, id).await
+}
+
+/// Drop cached archive size metadata for one archive, e.g. after it was
+/// repacked in place. Returns the number of persisted entries removed.
+#[tauri::command]
+async fn invalidate_archive_cache(
+    db: State<'_, DatabaseState>,
+    path: String,
+) -> Result<u64, String> {
+    cache::invalidate_archive(&db.get(), &PathBuf::from(path))
+        .await
+        .map_err(|e| format!("Failed to invalidate archive cache: {}", e))
 }
 
 #[tauri::command]
 async fn install_addons(
     app_handle: tauri::AppHandle,
     db: State<'_, DatabaseState>,
-    tasks: Vec<InstallTask>,
+    duplicate_prompts: State<'_, duplicate_prompt::DuplicatePrompts>,
+    mut tasks: Vec<InstallTask>,
     atomic_install_enabled: Option<bool>,
     xplane_path: String,
     delete_source_after_install: Option<bool>,
@@ -662,7 +943,9 @@ async fn install_addons(
     locked_scenery_folder_names: Option<Vec<String>>,
     parallel_enabled: Option<bool>,
     max_parallel: Option<usize>,
-) -> Result<InstallResult, String> {
+    preflight_check_enabled: Option<bool>,
+    on_duplicate: Option<models::DuplicateInstallAction>,
+) -> error::ApiResult<InstallResult> {
     log_debug!(
         &format!(
             "Installing {} tasks: {}",
@@ -688,9 +971,153 @@ async fn install_addons(
         })
         .collect();
 
-    let installer = Installer::new(app_handle);
+    let task_order: Vec<String> = tasks.iter().map(|t| t.id.clone()).collect();
+
+    // Liveries fitting several installed aircraft go to the one the user chose,
+    // scenery to the root folder the user kept or picked. A task whose choice is
+    // missing or invalid fails on its own; the rest of the batch still installs.
+    let mut rejected_results: Vec<models::TaskResult> = Vec::new();
+    tasks.retain_mut(|task| {
+        let choice = Analyzer::apply_livery_target_choice(task, &xplane_path)
+            .and_then(|_| Analyzer::apply_target_root_choice(task, &xplane_path));
+        let Err(message) = choice else {
+            return true;
+        };
+        logger::log_info(
+            &format!("Not installing {}: {}", task.display_name, message),
+            Some("installation"),
+        );
+        rejected_results.push(models::TaskResult {
+            task_id: task.id.clone(),
+            task_name: task.display_name.clone(),
+            success: false,
+            error_message: Some(message),
+            error_message_key: None,
+            error_message_args: Vec::new(),
+            error_code: Some(error::ApiErrorCode::ValidationFailed),
+            verification_stats: None,
+            preflight_bytes_read: None,
+            failed_stage: None,
+            warnings: Vec::new(),
+            skipped_identical: false,
+            failure_detail: None,
+        });
+        false
+    });
+
+    // Options to remember for the next install of the same addons
+    let mut profiles_by_task: HashMap<String, (models::AddonType, models::InstallProfile)> = tasks
+        .iter()
+        .filter_map(|t| {
+            install_profiles::profile_from_task(t)
+                .map(|profile| (t.id.clone(), (t.addon_type.clone(), profile)))
+        })
+        .collect();
+
+    // Kept for the install history, keyed by task id
+    let history_xplane_path = PathBuf::from(&xplane_path);
+    let mut installed_addons: HashMap<String, install_history::InstalledAddon> = tasks
+        .iter()
+        .map(|t| (t.id.clone(), install_history::InstalledAddon::from_task(t)))
+        .collect();
+
+    // Targets must stay inside X-Plane's addon folders, which may be linked to other drives
+    let xplane_root = std::path::Path::new(&xplane_path);
+    for task in &tasks {
+        path_utils::validate_xplane_target_path(
+            xplane_root,
+            std::path::Path::new(&task.target_path),
+        )
+        .map_err(|e| {
+            error::ApiError::security_violation(format!(
+                "Invalid install target {}: {}",
+                task.target_path, e
+            ))
+        })?;
+    }
+
+    // Taken before the install, which may delete the sources
+    let signature_tasks = tasks.clone();
+    let source_signatures =
+        tokio::task::spawn_blocking(move || install_history::source_signatures(&signature_tasks))
+            .await
+            .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?;
+    for (task_id, signature) in &source_signatures {
+        if let Some(addon) = installed_addons.get_mut(task_id) {
+            addon.source_signature = Some(signature.clone());
+        }
+    }
 
-    let result = if parallel_enabled.unwrap_or(false) && tasks.len() > 1 {
+    let skip_ids = match on_duplicate.unwrap_or_default() {
+        models::DuplicateInstallAction::Reinstall => HashSet::new(),
+        action => {
+            let identical = install_history::find_identical_installs(
+                &db.get(),
+                xplane_root,
+                &tasks,
+                &source_signatures,
+            )
+            .await
+            .unwrap_or_else(|e| {
+                logger::log_error(
+                    &format!("Failed to check for identical installs: {}", e),
+                    Some("installation"),
+                );
+                Vec::new()
+            });
+            if identical.is_empty() {
+                HashSet::new()
+            } else if action == models::DuplicateInstallAction::Ask {
+                duplicate_prompts.ask(&app_handle, identical).await
+            } else {
+                identical
+                    .into_iter()
+                    .map(|install| install.task_id)
+                    .collect()
+            }
+        }
+    };
+    let (skipped_tasks, tasks): (Vec<InstallTask>, Vec<InstallTask>) =
+        tasks.into_iter().partition(|t| skip_ids.contains(&t.id));
+    let skipped_results: Vec<models::TaskResult> = skipped_tasks
+        .iter()
+        .map(|t| {
+            logger::log_info(
+                &format!(
+                    "Skipping {}: identical to the installed copy",
+                    t.display_name
+                ),
+                Some("installation"),
+            );
+            models::TaskResult {
+                task_id: t.id.clone(),
+                task_name: t.display_name.clone(),
+                success: true,
+                error_message: None,
+                error_message_key: None,
+                error_message_args: Vec::new(),
+                error_code: None,
+                verification_stats: None,
+                preflight_bytes_read: None,
+                failed_stage: None,
+                warnings: Vec::new(),
+                skipped_identical: true,
+                failure_detail: None,
+            }
+        })
+        .collect();
+
+    let installer =
+        Installer::new(app_handle).with_preflight_check(preflight_check_enabled.unwrap_or(false));
+
+    let result = if tasks.is_empty() {
+        Ok(InstallResult {
+            total_tasks: 0,
+            successful_tasks: 0,
+            failed_tasks: 0,
+            task_results: Vec::new(),
+        })
+    } else if parallel_enabled.unwrap_or(false) && tasks.len() > 1 {
         installer
             .install_parallel(
                 tasks,
@@ -702,7 +1129,7 @@ async fn install_addons(
                 locked_scenery_folder_names.unwrap_or_default(),
             )
             .await
-            .map_err(|e| format!("Installation failed: {}", e))
+            .api_context_msg(UserMsg::InstallationFailed)
     } else {
         installer
             .install(
@@ -714,8 +1141,22 @@ async fn install_addons(
                 locked_scenery_folder_names.unwrap_or_default(),
             )
             .await
-            .map_err(|e| format!("Installation failed: {}", e))
+            .api_context_msg(UserMsg::InstallationFailed)
     };
+    let result = result.map(|mut install_result| {
+        install_result.total_tasks += skipped_results.len() + rejected_results.len();
+        install_result.successful_tasks += skipped_results.len();
+        install_result.failed_tasks += rejected_results.len();
+        install_result.task_results.extend(skipped_results);
+        install_result.task_results.extend(rejected_results);
+        install_result.task_results.sort_by_key(|tr| {
+            task_order
+                .iter()
+                .position(|id| *id == tr.task_id)
+                .unwrap_or(usize::MAX)
+        });
+        install_result
+    });
 
     // Log each task result
     if let Ok(ref install_result) = result {
@@ -730,16 +1171,119 @@ async fn install_addons(
                 "install",
                 item_type,
                 &tr.task_name,
-                tr.error_message.clone(),
+                tr.error_message.clone().or_else(|| {
+                    tr.skipped_identical
+                        .then(|| "Skipped: identical to the installed copy".to_string())
+                }),
                 tr.success,
             )
             .await;
         }
+
+        let succeeded_ids: Vec<&String> = install_result
+            .task_results
+            .iter()
+            .filter(|tr| tr.success && !tr.skipped_identical)
+            .map(|tr| &tr.task_id)
+            .collect();
+        let succeeded = succeeded_ids
+            .iter()
+            .filter_map(|id| installed_addons.remove(*id))
+            .collect();
+        install_history::record_installs(&conn, &history_xplane_path, succeeded).await;
+
+        let profiles = succeeded_ids
+            .iter()
+            .filter_map(|id| profiles_by_task.remove(*id))
+            .collect();
+        install_profiles::save_profiles(&conn, profiles).await;
     }
 
     result
 }
 
+/// Addons installed through XFast, newest first
+#[tauri::command]
+async fn get_install_history(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    limit: Option<u64>,
+    addon_type: Option<models::AddonType>,
+) -> error::ApiResult<Vec<models::InstallHistoryEntry>> {
+    install_history::load_history(
+        &db.get(),
+        std::path::Path::new(&xplane_path),
+        limit.unwrap_or(100).max(1),
+        addon_type.as_ref(),
+    )
+    .await
+}
+
+/// Options remembered from the last install of an addon, by profile identity
+#[tauri::command]
+async fn get_install_profile(
+    db: State<'_, DatabaseState>,
+    identity: String,
+) -> error::ApiResult<Option<models::InstallProfile>> {
+    install_profiles::get_profile(&db.get(), &identity).await
+}
+
+/// Forget the options remembered for an addon. Returns whether any were stored.
+#[tauri::command]
+async fn clear_install_profile(
+    db: State<'_, DatabaseState>,
+    identity: String,
+) -> error::ApiResult<bool> {
+    install_profiles::clear_profile(&db.get(), &identity).await
+}
+
+/// Installs a crash or a killed process interrupted whose targets were kept
+/// for review, oldest first
+#[tauri::command]
+async fn get_orphaned_installs(
+    db: State<'_, DatabaseState>,
+) -> error::ApiResult<Vec<models::OrphanedInstall>> {
+    install_markers::list_orphaned(&db.get()).await
+}
+
+/// Keep or delete the target of an interrupted install. Returns whether the
+/// install was listed.
+#[tauri::command]
+async fn resolve_orphaned_install(
+    db: State<'_, DatabaseState>,
+    id: i64,
+    action: models::OrphanedInstallAction,
+) -> error::ApiResult<bool> {
+    install_markers::resolve(&db.get(), id, action).await
+}
+
+/// Remove what an install history entry installed and mark it uninstalled
+#[tauri::command]
+async fn uninstall_from_history(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    install_id: i64,
+) -> error::ApiResult<models::UninstallFromHistoryResult> {
+    install_history::uninstall(&db.get(), std::path::Path::new(&xplane_path), install_id).await
+}
+
+/// Answer an `install-duplicates-detected` prompt with the tasks to skip;
+/// the other tasks are reinstalled
+#[tauri::command]
+fn respond_duplicate_install(
+    duplicate_prompts: State<'_, duplicate_prompt::DuplicatePrompts>,
+    request_id: String,
+    skip_task_ids: Vec<String>,
+) -> error::ApiResult<()> {
+    if duplicate_prompts.respond(&request_id, skip_task_ids) {
+        Ok(())
+    } else {
+        Err(error::ApiError::not_found(
+            "The install no longer waits for this answer",
+        ))
+    }
+}
+
 // ============================================================================
 // Task Control Commands
 // ============================================================================
@@ -773,14 +1317,40 @@ fn unregister_context_menu() -> Result<(), String> {
         .map_err(|e| format!("Failed to unregister context menu: {}", e))
 }
 
+/// Registration state per file type and for folders
 #[tauri::command]
-fn is_context_menu_registered() -> bool {
+fn is_context_menu_registered() -> registry::ContextMenuStatus {
     registry::is_context_menu_registered()
 }
 
+/// Register the context menu for the chosen file types and folders only
+#[tauri::command]
+fn set_context_menu_options(
+    options: registry::ContextMenuOptions,
+) -> error::ApiResult<registry::ContextMenuStatus> {
+    let status = registry::set_context_menu_options(options)
+        .map_err(|e| error::ApiError::internal(format!("Failed to update context menu: {}", e)))?;
+    logger::log_info(
+        &format!("Context menu options updated: {:?}", status.options),
+        Some("registry"),
+    );
+    Ok(status)
+}
+
 #[tauri::command]
-fn sync_context_menu_paths() -> Result<bool, String> {
-    registry::sync_registry_paths().map_err(|e| format!("Failed to sync context menu paths: {}", e))
+fn sync_context_menu_paths() -> Result<registry::ContextMenuSyncReport, String> {
+    let report = registry::sync_registry_paths()
+        .map_err(|e| format!("Failed to sync context menu paths: {}", e))?;
+    if !report.repaired.is_empty() || report.migrated_legacy {
+        logger::log_info(
+            &format!(
+                "Context menu entries repaired: {:?}, legacy entry migrated: {}",
+                report.repaired, report.migrated_legacy
+            ),
+            Some("registry"),
+        );
+    }
+    Ok(report)
 }
 
 // ============================================================================
@@ -820,6 +1390,365 @@ fn open_log_folder() -> Result<(), String> {
     open_in_explorer(logger::get_log_folder())
 }
 
+/// Show a file or folder selected in the system file manager
+#[tauri::command]
+async fn reveal_in_explorer(path: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        file_explorer::reveal_in_explorer(std::path::Path::new(&path))
+            .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+/// Reveal X-Plane's Log.txt, or the app's own log file when no X-Plane path is given
+#[tauri::command]
+async fn reveal_log_file(xplane_path: Option<String>) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let log_path = match xplane_path {
+            Some(xplane_path) => std::path::PathBuf::from(xplane_path).join("Log.txt"),
+            None => logger::get_log_path(),
+        };
+        file_explorer::reveal_in_explorer(&log_path).map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+// ============================================================================
+// X-Plane Preference Backup Commands
+// ============================================================================
+
+/// Zip X-Plane's Output/preferences into the app's backups folder
+#[tauri::command]
+async fn backup_xplane_preferences(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    label: Option<String>,
+) -> error::ApiResult<models::PreferenceBackup> {
+    let backup = tokio::task::spawn_blocking(move || {
+        preference_backups::backup_preferences(std::path::Path::new(&xplane_path), label.as_deref())
+            .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))??;
+
+    activity::log_activity(
+        &db.get(),
+        "backup",
+        "preferences",
+        backup.label.as_deref().unwrap_or(&backup.id),
+        serde_json::to_string(&backup).ok(),
+        true,
+    )
+    .await;
+    Ok(backup)
+}
+
+/// Preference backups of all X-Plane installations, newest first
+#[tauri::command]
+async fn list_xplane_preference_backups() -> error::ApiResult<Vec<models::PreferenceBackup>> {
+    tokio::task::spawn_blocking(|| {
+        preference_backups::list_backups().map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// Put a backup in place of the current preferences, which are moved aside.
+/// Refused while X-Plane runs from the backed up installation, since it
+/// rewrites them on exit.
+#[tauri::command]
+async fn restore_xplane_preferences(
+    db: State<'_, DatabaseState>,
+    backup_id: String,
+) -> error::ApiResult<models::PreferenceRestoreResult> {
+    let id = backup_id.clone();
+    let xplane_path = tokio::task::spawn_blocking(move || {
+        preference_backups::backup_xplane_path(&id).map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))??;
+    // The path comes from the backup's metadata, which may have been edited
+    validate_xplane_root_path(std::path::Path::new(&xplane_path))
+        .map_err(error::ApiError::validation)?;
+    if xplane_processes::is_running_at(std::path::Path::new(&xplane_path)).await {
+        return Err(error::ApiError::conflict(
+            "Close X-Plane before restoring its preferences",
+        ));
+    }
+
+    let id = backup_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        preference_backups::restore_backup(&id).map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?;
+
+    activity::log_activity(
+        &db.get(),
+        "restore",
+        "preferences",
+        &backup_id,
+        match &result {
+            Ok(restored) => serde_json::to_string(restored).ok(),
+            Err(e) => Some(e.message.clone()),
+        },
+        result.is_ok(),
+    )
+    .await;
+    result
+}
+
+#[tauri::command]
+async fn delete_xplane_preference_backup(backup_id: String) -> error::ApiResult<bool> {
+    tokio::task::spawn_blocking(move || {
+        preference_backups::delete_backup(&backup_id).map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// Zip app logs, X-Plane's Log.txt, scenery index stats and app/OS info into the
+/// Downloads folder. Returns the path of the created bundle.
+#[tauri::command]
+async fn export_support_bundle(
+    db: State<'_, DatabaseState>,
+    xplane_path: Option<String>,
+) -> Result<String, String> {
+    let xplane_path = xplane_path
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from);
+
+    // Stats are best effort: a missing or incompatible index shouldn't block the bundle
+    let scenery_stats = match &xplane_path {
+        Some(path) => SceneryIndexManager::new(path, db.get())
+            .get_stats()
+            .await
+            .map_err(|e| {
+                logger::log_info(
+                    &format!("Scenery index stats unavailable for support bundle: {}", e),
+                    Some("support"),
+                )
+            })
+            .ok(),
+        None => None,
+    };
+
+    tokio::task::spawn_blocking(move || {
+        support_bundle::export_support_bundle(xplane_path.as_deref(), scenery_stats.as_ref())
+            .map(|path| path.to_string_lossy().to_string())
+            .map_err(|e| format!("Failed to export support bundle: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+// ========== Backend Settings Commands ==========
+
+#[tauri::command]
+fn get_backend_settings() -> settings_store::BackendSettings {
+    settings_store::get()
+}
+
+/// Replace the backend settings. Emits `backend-settings-changed` once saved.
+#[tauri::command]
+async fn save_backend_settings(
+    mut settings: settings_store::BackendSettings,
+) -> Result<settings_store::BackendSettings, String> {
+    http_client::validate(&settings.network)?;
+    tokio::task::spawn_blocking(move || {
+        settings_store::update(|current| {
+            settings.network.keep_saved_password(&current.network);
+            *current = settings;
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+// ========== Network Settings Commands ==========
+
+#[tauri::command]
+fn get_network_settings() -> settings_store::NetworkSettings {
+    settings_store::get().network
+}
+
+/// Replace the proxy, certificate and timeout settings used by every HTTP client.
+/// The saved proxy password is kept unless `settings` carries a new one; an
+/// empty one clears it.
+#[tauri::command]
+async fn set_network_settings(
+    mut settings: settings_store::NetworkSettings,
+) -> error::ApiResult<settings_store::NetworkSettings> {
+    http_client::validate(&settings).map_err(error::ApiError::validation)?;
+    let saved = tokio::task::spawn_blocking(move || {
+        settings_store::update(|current| {
+            settings.keep_saved_password(&current.network);
+            current.network = settings;
+        })
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))??;
+    Ok(saved.network)
+}
+
+/// Reach a known endpoint with `settings` (or the saved settings) and report the latency
+#[tauri::command]
+async fn test_network_settings(
+    settings: Option<settings_store::NetworkSettings>,
+) -> error::ApiResult<http_client::NetworkTestResult> {
+    let saved = settings_store::get().network;
+    let settings = match settings {
+        Some(mut settings) => {
+            settings.keep_saved_password(&saved);
+            settings
+        }
+        None => saved,
+    };
+    http_client::test_connection(&settings).await
+}
+
+// ========== Staging Folder Commands ==========
+
+#[tauri::command]
+fn get_staging_config() -> staging::StagingConfig {
+    staging::get_config()
+}
+
+/// Stage installs and extractions in `staging_dir`, or in the system temp
+/// folder when None. The folder must be writable and outside `xplane_path`.
+#[tauri::command]
+async fn set_staging_config(
+    staging_dir: Option<String>,
+    xplane_path: Option<String>,
+) -> error::ApiResult<staging::StagingConfig> {
+    tokio::task::spawn_blocking(move || {
+        staging::set_config(
+            staging_dir,
+            xplane_path.as_deref().map(std::path::Path::new),
+        )
+        .map_err(error::ApiError::validation)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+// ========== Livery Pattern Commands ==========
+
+/// Built-in and user livery detection patterns, merged in matching order
+#[tauri::command]
+fn get_livery_patterns() -> Vec<livery_patterns::LiveryPatternInfo> {
+    livery_patterns::list_patterns()
+}
+
+/// Add a user regex rule that identifies liveries for `aircraft_id`
+#[tauri::command]
+async fn add_livery_pattern(
+    aircraft_id: String,
+    aircraft_name: String,
+    path_regex: String,
+    root_depth: usize,
+) -> error::ApiResult<Vec<livery_patterns::LiveryPatternInfo>> {
+    tokio::task::spawn_blocking(move || {
+        livery_patterns::add_user_pattern(&aircraft_id, &aircraft_name, &path_regex, root_depth)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// Remove a user rule; `pattern_index` is the rule's `userIndex`
+#[tauri::command]
+async fn remove_livery_pattern(
+    aircraft_id: String,
+    pattern_index: usize,
+) -> error::ApiResult<Vec<livery_patterns::LiveryPatternInfo>> {
+    tokio::task::spawn_blocking(move || {
+        livery_patterns::remove_user_pattern(&aircraft_id, pattern_index)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+// ========== Stored Data Commands ==========
+
+/// List everything XFast stores, with location and size, by category. Files XFast
+/// wrote inside `xplane_path` are listed separately.
+#[tauri::command]
+async fn get_stored_data_summary(
+    xplane_path: Option<String>,
+) -> Result<stored_data::StoredDataSummary, String> {
+    let xplane_path = xplane_path
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from);
+    tokio::task::spawn_blocking(move || {
+        stored_data::get_stored_data_summary(xplane_path.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))
+}
+
+/// Erase the selected categories of stored data. Files inside the X-Plane folder
+/// are only touched with `include_xplane_files`. With `dry_run` nothing is removed
+/// and the result lists exactly what would be.
+#[tauri::command]
+async fn erase_app_data(
+    db: State<'_, DatabaseState>,
+    task_control: State<'_, TaskControl>,
+    categories: Vec<app_dirs::StorageCategory>,
+    xplane_path: Option<String>,
+    include_xplane_files: bool,
+    dry_run: bool,
+) -> Result<stored_data::EraseResult, String> {
+    let xplane_path = xplane_path
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from);
+    let plan = tokio::task::spawn_blocking(move || {
+        let summary = stored_data::get_stored_data_summary(xplane_path.as_deref());
+        stored_data::plan_erase(&summary, &categories, include_xplane_files)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?;
+
+    if dry_run {
+        return Ok(stored_data::dry_run_result(plan));
+    }
+    if task_control.is_install_active() {
+        return Err("Cannot erase app data while an installation is running".to_string());
+    }
+
+    logger::log_info(
+        &format!("Erasing {} stored data location(s)", plan.len()),
+        Some("stored_data"),
+    );
+    let touches_database = plan
+        .iter()
+        .any(|entry| entry.category == app_dirs::StorageCategory::Database);
+    // Removing folders blocks, so it runs off the async runtime
+    let result = if touches_database {
+        db.close_and_reopen(move || stored_data::erase_entries(&plan))
+            .await
+            .map_err(|e| format!("Failed to reopen database: {}", e))?
+    } else {
+        tokio::task::spawn_blocking(move || stored_data::erase_entries(&plan))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))?
+    };
+
+    if !result.failed.is_empty() {
+        logger::log_error(
+            &format!(
+                "Failed to erase {} stored data location(s)",
+                result.failed.len()
+            ),
+            Some("stored_data"),
+        );
+    }
+    Ok(result)
+}
+
 // ========== X-Plane Log Analysis ==========
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
@@ -830,9 +1759,16 @@ struct LogIssue {
     severity: String,
     /// Up to 5 matching line numbers
     line_numbers: Vec<usize>,
+    /// Total number of matching lines
+    #[serde(default)]
+    match_count: usize,
     /// For E/ lines: all consecutive E/ lines in the same block (multi-line).
     /// For other lines: the single matching line.
     sample_line: String,
+    /// Folders the issue refers to, relative to the X-Plane root
+    /// (e.g. the conflicting copies behind `duplicate_plugin`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    related_folders: Vec<String>,
 }
 
 #[derive(serde::Serialize, Default)]
@@ -844,7 +1780,14 @@ struct SystemInfo {
 
 #[derive(serde::Serialize)]
 struct XPlaneLogAnalysis {
+    /// The log file, or the archive it was read from
     log_path: String,
+    log_source: log_source::LogSource,
+    /// For archives: path of the log inside the archive
+    archive_entry: Option<String>,
+    /// Newest .dmp crash dump in the X-Plane folder, when the log was read from
+    /// there; can be passed to `analyze_crash_report`
+    crash_report_path: Option<String>,
     is_xplane_log: bool,
     crash_detected: bool,
     crash_info: Option<String>,
@@ -1198,20 +2141,26 @@ fn extract_system_info(lines: &[String]) -> SystemInfo {
     info
 }
 
+/// Analyze the log at `log_file_path` (a log file, or a .zip/.7z holding
+/// Log.txt), or else the Log.txt of `xplane_path`. The X-Plane folder is also
+/// used to fill in the version and name duplicate plugin folders.
 #[tauri::command]
-fn analyze_xplane_log(xplane_path: String) -> Result<XPlaneLogAnalysis, String> {
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
-
-    let log_path = std::path::PathBuf::from(&xplane_path).join("Log.txt");
-    let log_path_str = log_path.to_string_lossy().to_string();
-
-    let file = File::open(&log_path).map_err(|e| format!("Cannot open Log.txt: {e}"))?;
-    let reader = BufReader::new(file);
-    let lines: Vec<String> = reader
-        .lines()
-        .map(|l| l.map_err(|e| format!("Read error: {e}")))
-        .collect::<Result<Vec<_>, _>>()?;
+fn analyze_xplane_log(
+    xplane_path: Option<String>,
+    log_file_path: Option<String>,
+) -> Result<XPlaneLogAnalysis, String> {
+    let xplane_root = xplane_path.as_deref().map(std::path::Path::new);
+    let log = log_source::load_log(
+        xplane_root,
+        log_file_path.as_deref().map(std::path::Path::new),
+    )?;
+    let lines = log.lines;
+    let crash_report_path = match (log.source, xplane_root) {
+        (log_source::LogSource::XplaneFolder, Some(root)) => {
+            log_source::newest_crash_report(root).map(|path| path.to_string_lossy().to_string())
+        }
+        _ => None,
+    };
 
     // Check if it's an X-Plane log (first 100 lines)
     let xplane_indicators = [
@@ -1227,16 +2176,24 @@ fn analyze_xplane_log(xplane_path: String) -> Result<XPlaneLogAnalysis, String>
         .take(100)
         .any(|line| xplane_indicators.iter().any(|ind| line.contains(ind)));
 
-    let system_info = extract_system_info(&lines);
+    let mut system_info = extract_system_info(&lines);
+    if system_info.xplane_version.is_none() {
+        system_info.xplane_version = xplane_root
+            .and_then(|root| xplane_version::get_xplane_version(root).map(|v| v.display()));
+    }
 
     let patterns = build_patterns();
 
-    // category -> (severity, line_numbers, sample_line)
-    let mut issue_map: std::collections::HashMap<&'static str, (&'static str, Vec<usize>, String)> =
-        std::collections::HashMap::new();
+    // category -> (severity, line_numbers, sample_line, match_count)
+    let mut issue_map: std::collections::HashMap<
+        &'static str,
+        (&'static str, Vec<usize>, String, usize),
+    > = std::collections::HashMap::new();
 
     let mut crash_detected = false;
     let mut crash_info: Option<String> = None;
+    // Lines around duplicate plugin errors, which usually name the plugin path
+    let mut duplicate_plugin_lines: Vec<&str> = Vec::new();
 
     for (idx, line) in lines.iter().enumerate() {
         let line_lower = line.to_lowercase();
@@ -1250,11 +2207,19 @@ fn analyze_xplane_log(xplane_path: String) -> Result<XPlaneLogAnalysis, String>
 
         for pat in &patterns {
             if (pat.matcher)(line, &line_lower) {
+                if pat.category == "duplicate_plugin" && duplicate_plugin_lines.len() < 20 {
+                    if let Some(previous) = idx.checked_sub(1).and_then(|i| lines.get(i)) {
+                        duplicate_plugin_lines.push(previous);
+                    }
+                    duplicate_plugin_lines.push(line);
+                }
                 let entry = issue_map.entry(pat.category).or_insert((
                     pat.severity,
                     Vec::new(),
                     String::new(),
+                    0,
                 ));
+                entry.3 += 1;
                 if entry.1.len() < 5 {
                     entry.1.push(idx + 1);
                     // Keep the largest consecutive E/ block found so far
@@ -1274,39 +2239,184 @@ fn analyze_xplane_log(xplane_path: String) -> Result<XPlaneLogAnalysis, String>
     };
     let mut issues: Vec<LogIssue> = issue_map
         .into_iter()
-        .map(|(cat, (sev, nums, sample))| LogIssue {
+        .map(|(cat, (sev, nums, sample, count))| LogIssue {
             category: cat.to_string(),
             severity: sev.to_string(),
             line_numbers: nums,
+            match_count: count,
             sample_line: sample,
+            related_folders: Vec::new(),
         })
         .collect();
     issues.sort_by_key(|i| sev_order(&i.severity));
 
-    let total_high = issues.iter().filter(|i| i.severity == "high").count();
-    let total_medium = issues.iter().filter(|i| i.severity == "medium").count();
-    let total_low = issues.iter().filter(|i| i.severity == "low").count();
+    // Name the folders behind a duplicate plugin error when the plugin scan finds them
+    if let (Some(issue), Some(root)) = (
+        issues.iter_mut().find(|i| i.category == "duplicate_plugin"),
+        xplane_root,
+    ) {
+        if let Ok(plugins) = management_index::scan_plugins(root, false) {
+            issue.related_folders = plugin_duplicates::folders_for_log_lines(
+                &plugins.duplicates,
+                &duplicate_plugin_lines,
+            );
+        }
+    }
+
+    let total_high = issues.iter().filter(|i| i.severity == "high").count();
+    let total_medium = issues.iter().filter(|i| i.severity == "medium").count();
+    let total_low = issues.iter().filter(|i| i.severity == "low").count();
+
+    Ok(XPlaneLogAnalysis {
+        log_path: log.path.to_string_lossy().to_string(),
+        log_source: log.source,
+        archive_entry: log.archive_entry,
+        crash_report_path,
+        is_xplane_log,
+        crash_detected,
+        crash_info,
+        issues,
+        system_info,
+        total_high,
+        total_medium,
+        total_low,
+    })
+}
+
+/// Analyze the current Log.txt and store its summary in the log history.
+/// Returns false when this session was already recorded.
+#[tauri::command]
+async fn record_xplane_log_snapshot(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+) -> Result<bool, String> {
+    let path = xplane_path.clone();
+    let snapshot = tokio::task::spawn_blocking(move || {
+        let log_meta = fs::metadata(std::path::Path::new(&path).join("Log.txt"))
+            .map_err(|e| format!("Cannot read Log.txt: {e}"))?;
+        let analysis = analyze_xplane_log(Some(path), None)?;
+        if !analysis.is_xplane_log {
+            return Err("Log.txt is not an X-Plane log".to_string());
+        }
+
+        let unix_secs = |time: std::time::SystemTime| {
+            time.duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0)
+        };
+        Ok(XPlaneLogSnapshot {
+            recorded_at: unix_secs(std::time::SystemTime::now()),
+            log_modified_at: log_meta.modified().map(unix_secs).unwrap_or(0),
+            log_size: log_meta.len(),
+            xplane_version: analysis.system_info.xplane_version,
+            crash_detected: analysis.crash_detected,
+            category_counts: analysis
+                .issues
+                .iter()
+                .map(|issue| (issue.category.clone(), issue.match_count as u64))
+                .collect(),
+            total_high: analysis.total_high as u32,
+            total_medium: analysis.total_medium as u32,
+            total_low: analysis.total_low as u32,
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    log_history::record_snapshot(&db.get(), std::path::Path::new(&xplane_path), &snapshot)
+        .await
+        .map_err(|e| format!("Failed to record log snapshot: {}", e))
+}
+
+/// Recent log snapshots of an installation, oldest first
+#[tauri::command]
+async fn get_xplane_log_history(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    limit: Option<u64>,
+) -> Result<Vec<XPlaneLogSnapshot>, String> {
+    let limit = limit.unwrap_or(settings_store::DEFAULT_LOG_HISTORY_LIMIT as u64);
+    log_history::load_history(&db.get(), std::path::Path::new(&xplane_path), limit)
+        .await
+        .map_err(|e| format!("Failed to load log history: {}", e))
+}
+
+/// Hide an analysis warning, e.g. kind "airport_conflict" with key "airport_conflict:EGKK"
+#[tauri::command]
+async fn add_analysis_suppression(
+    db: State<'_, DatabaseState>,
+    kind: String,
+    key: String,
+) -> error::ApiResult<models::AnalysisSuppression> {
+    analysis_suppressions::add_suppression(&db.get(), &kind, &key).await
+}
+
+/// Show a suppressed analysis warning again
+#[tauri::command]
+async fn remove_analysis_suppression(
+    db: State<'_, DatabaseState>,
+    id: i64,
+) -> error::ApiResult<bool> {
+    analysis_suppressions::remove_suppression(&db.get(), id).await
+}
+
+/// Suppressed analysis warnings, newest first
+#[tauri::command]
+async fn list_analysis_suppressions(
+    db: State<'_, DatabaseState>,
+) -> error::ApiResult<Vec<models::AnalysisSuppression>> {
+    analysis_suppressions::list_suppressions(&db.get()).await
+}
 
-    Ok(XPlaneLogAnalysis {
-        log_path: log_path_str,
-        is_xplane_log,
-        crash_detected,
-        crash_info,
-        issues,
-        system_info,
-        total_high,
-        total_medium,
-        total_low,
+#[tauri::command]
+async fn get_xplane_version(
+    xplane_path: String,
+) -> Result<Option<xplane_version::XPlaneVersion>, String> {
+    tokio::task::spawn_blocking(move || {
+        let root = std::path::PathBuf::from(&xplane_path);
+        validate_xplane_root_path(&root)?;
+        Ok(xplane_version::get_xplane_version(&root))
     })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn list_known_xplane_installs(
+    db: State<'_, DatabaseState>,
+) -> error::ApiResult<Vec<xplane_installs::KnownXPlaneInstall>> {
+    xplane_installs::list_known_installs(&db.get()).await
+}
+
+/// Check whether X-Plane lives under Program Files or a synced folder, and
+/// whether installs can write to it
+#[tauri::command]
+async fn diagnose_xplane_location(
+    xplane_path: String,
+) -> error::ApiResult<xplane_location::XPlaneLocationReport> {
+    let root = PathBuf::from(&xplane_path);
+    validate_xplane_root_path(&root).map_err(error::ApiError::validation)?;
+    tokio::task::spawn_blocking(move || xplane_location::diagnose_xplane_location(&root))
+        .await
+        .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))
 }
 
+/// Deep analysis of `report_path` (e.g. the `crash_report_path` of a log
+/// analysis), or else of the most recent crash dump
 #[tauri::command]
 async fn analyze_crash_report(
     xplane_path: String,
+    report_path: Option<String>,
     log_issues: Vec<LogIssue>,
     skip_date_check: bool,
 ) -> Result<Option<crash_analysis::DeepCrashAnalysis>, String> {
-    crash_analysis::analyze_crash_report(&xplane_path, &log_issues, skip_date_check).await
+    crash_analysis::analyze_crash_report(
+        &xplane_path,
+        report_path.as_deref().map(std::path::Path::new),
+        &log_issues,
+        skip_date_check,
+    )
+    .await
 }
 
 // ========== Scenery Folder Commands ==========
@@ -1317,8 +2427,9 @@ fn validate_scenery_folder_name(folder_name: &str) -> error::ApiResult<()> {
         || folder_name.contains('/')
         || folder_name.contains('\\')
     {
-        return Err(error::ApiError::security_violation(
-            "Invalid folder name: path traversal not allowed",
+        return Err(error::ApiError::localized(
+            error::ApiErrorCode::SecurityViolation,
+            UserMsg::InvalidFolderName,
         ));
     }
     Ok(())
@@ -1327,124 +2438,189 @@ fn validate_scenery_folder_name(folder_name: &str) -> error::ApiResult<()> {
 fn resolve_scenery_entry_path(
     xplane_path: &str,
     folder_name: &str,
-) -> error::ApiResult<(PathBuf, PathBuf)> {
+) -> error::ApiResult<scenery_index::SceneryEntryLocation> {
     validate_scenery_folder_name(folder_name)?;
 
+    // Same resolution as the scenery index, so both agree on what exists
     let base_path = PathBuf::from(xplane_path).join("Custom Scenery");
-    let candidate = base_path.join(folder_name);
-    if candidate.exists() {
-        return Ok((candidate, base_path));
-    }
-
-    #[cfg(target_os = "windows")]
-    {
-        let lnk_path = base_path.join(format!("{}.lnk", folder_name));
-        if lnk_path.exists() {
-            return Ok((lnk_path, base_path));
-        }
-    }
-
-    Err(error::ApiError::with_details(
-        error::ApiErrorCode::NotFound,
-        "Scenery folder not found",
-        folder_name,
-    ))
+    scenery_index::find_scenery_entry(&base_path, folder_name).ok_or_else(|| error::ApiError {
+        details: Some(folder_name.to_string()),
+        ..error::ApiError::localized(
+            error::ApiErrorCode::NotFound,
+            UserMsg::SceneryFolderNotFound,
+        )
+    })
 }
 
 #[tauri::command]
 fn open_scenery_folder(xplane_path: String, folder_name: String) -> error::ApiResult<()> {
-    let (entry_path, base_path) = resolve_scenery_entry_path(&xplane_path, &folder_name)?;
-    let metadata = fs::symlink_metadata(&entry_path)
-        .map_err(|e| error::ApiError::validation(format!("Invalid path: {}", e)))?;
+    let location = resolve_scenery_entry_path(&xplane_path, &folder_name)?;
 
     // If it's a symlink, open the link itself to allow external targets
-    if metadata.file_type().is_symlink() {
-        return open_in_explorer(&entry_path).map_err(error::ApiError::internal);
+    if location.kind == scenery_index::SceneryEntryKind::Link {
+        return open_in_explorer(&location.entry_path).map_err(error::ApiError::internal);
     }
 
-    // For regular directories/files, enforce canonical base containment
-    let canonical_path = path_utils::validate_child_path(&base_path, &entry_path)
-        .map_err(|e| error::ApiError::validation(format!("Invalid path: {}", e)))?;
+    // For regular directories/files, enforce canonical base containment. Custom
+    // Scenery itself may be linked to another drive.
+    let canonical_path = path_utils::validate_xplane_child_path(
+        std::path::Path::new(&xplane_path),
+        &location.entry_path,
+    )
+    .map_err(|e| {
+        error::ApiError::localized(
+            error::ApiErrorCode::ValidationFailed,
+            UserMsg::InvalidPath.error_with([e]),
+        )
+    })?;
 
     open_in_explorer(&canonical_path).map_err(error::ApiError::internal)
 }
 
+/// Enabled packages that would lose libraries only this scenery folder
+/// provides, to confirm before `delete_scenery_folder`
+#[tauri::command]
+async fn preview_scenery_deletion(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    folder_name: String,
+) -> error::ApiResult<models::SceneryDeletionPreview> {
+    let index_manager = SceneryIndexManager::new(std::path::Path::new(&xplane_path), db.get());
+    index_manager
+        .preview_deletion(&folder_name)
+        .await
+        .api_context_msg(UserMsg::PreviewSceneryDeletionFailed)
+}
+
+/// Delete a scenery folder. Unless `force` is set, refuses when enabled
+/// packages depend on libraries only this folder provides. `apply_mode`
+/// decides when scenery_packs.ini is updated if X-Plane is running.
 #[tauri::command]
 async fn delete_scenery_folder(
+    app_handle: tauri::AppHandle,
     db: State<'_, DatabaseState>,
     xplane_path: String,
     folder_name: String,
-) -> error::ApiResult<()> {
-    let (entry_path, base_path) = resolve_scenery_entry_path(&xplane_path, &folder_name)?;
-    let metadata = fs::symlink_metadata(&entry_path)
-        .map_err(|e| error::ApiError::validation(format!("Invalid path: {}", e)))?;
+    force: Option<bool>,
+    apply_mode: Option<models::SceneryApplyMode>,
+) -> error::ApiResult<models::SceneryDeletionResult> {
+    let location = resolve_scenery_entry_path(&xplane_path, &folder_name)?;
+    let ini_deferred = deferred_scenery::should_defer(
+        apply_mode.unwrap_or_default(),
+        std::path::Path::new(&xplane_path),
+    )
+    .await?;
+    let entry_path = &location.entry_path;
+    // The name as stored on disk and in the index, whichever Unicode form was sent
+    let folder_name = location.name.clone();
+
+    if !force.unwrap_or(false) {
+        let index_manager = SceneryIndexManager::new(std::path::Path::new(&xplane_path), db.get());
+        let preview = index_manager
+            .preview_deletion(&folder_name)
+            .await
+            .api_context_msg(UserMsg::CheckSceneryDependentsFailed)?;
+        if !preview.dependents.is_empty() {
+            return Err(error::ApiError::localized(
+                error::ApiErrorCode::ConflictExists,
+                UserMsg::SceneryHasDependents
+                    .error_with([preview.dependents.len().to_string(), folder_name]),
+            ));
+        }
+    }
 
-    if metadata.file_type().is_symlink() {
+    if location.kind == scenery_index::SceneryEntryKind::Link {
         // Remove the symlink itself without following it
-        if let Err(e) = fs::remove_file(&entry_path) {
+        if let Err(e) = fs::remove_file(entry_path) {
             // Some platforms treat directory symlinks differently
-            if let Err(e2) = fs::remove_dir(&entry_path) {
+            if let Err(e2) = fs::remove_dir(entry_path) {
                 if e.kind() == std::io::ErrorKind::PermissionDenied
                     || e2.kind() == std::io::ErrorKind::PermissionDenied
                 {
-                    return Err(error::ApiError::permission_denied(format!(
-                        "Permission denied when deleting: {}",
-                        folder_name
-                    )));
+                    return Err(error::ApiError::localized(
+                        error::ApiErrorCode::PermissionDenied,
+                        UserMsg::DeletePermissionDenied.error_with([&folder_name]),
+                    ));
                 }
-                return Err(error::ApiError::internal(format!(
-                    "Failed to delete scenery link: {} ({}; {})",
-                    folder_name, e, e2
-                )));
+                return Err(error::ApiError::localized(
+                    error::ApiErrorCode::Internal,
+                    UserMsg::DeleteSceneryLinkFailed.error_with([
+                        folder_name,
+                        e.to_string(),
+                        e2.to_string(),
+                    ]),
+                ));
             }
         }
-    } else if metadata.is_file() {
-        // Handle Windows .lnk shortcuts or other file entries
-        fs::remove_file(&entry_path).map_err(|e| {
+    } else if location.kind == scenery_index::SceneryEntryKind::Shortcut {
+        // Remove the Windows .lnk shortcut, not its target
+        fs::remove_file(entry_path).map_err(|e| {
             if e.kind() == std::io::ErrorKind::PermissionDenied {
-                error::ApiError::permission_denied(format!(
-                    "Permission denied when deleting: {}",
-                    folder_name
-                ))
+                error::ApiError::localized(
+                    error::ApiErrorCode::PermissionDenied,
+                    UserMsg::DeletePermissionDenied.error_with([&folder_name]),
+                )
             } else {
-                error::ApiError::internal(format!("Failed to delete scenery file: {}", e))
+                error::ApiError::localized(
+                    error::ApiErrorCode::Internal,
+                    UserMsg::DeleteSceneryFileFailed.error_with([e]),
+                )
             }
         })?;
     } else {
-        // Security: Use validate_child_path for strict path validation to prevent path traversal attacks
-        let canonical_path = path_utils::validate_child_path(&base_path, &entry_path)
-            .map_err(|e| error::ApiError::validation(format!("Invalid path: {}", e)))?;
+        // Security: strict path validation to prevent path traversal attacks, trusting
+        // Custom Scenery's canonical location when it is linked to another drive
+        let canonical_path =
+            path_utils::validate_xplane_child_path(std::path::Path::new(&xplane_path), entry_path)
+                .map_err(|e| {
+                    error::ApiError::localized(
+                        error::ApiErrorCode::ValidationFailed,
+                        UserMsg::InvalidPath.error_with([e]),
+                    )
+                })?;
 
         // Delete the folder using the canonical path for safety
         fs::remove_dir_all(&canonical_path).map_err(|e| {
             if e.kind() == std::io::ErrorKind::PermissionDenied {
-                error::ApiError::permission_denied(format!(
-                    "Permission denied when deleting: {}",
-                    folder_name
-                ))
+                error::ApiError::localized(
+                    error::ApiErrorCode::PermissionDenied,
+                    UserMsg::DeletePermissionDenied.error_with([&folder_name]),
+                )
             } else {
-                error::ApiError::internal(format!("Failed to delete scenery folder: {}", e))
+                error::ApiError::localized(
+                    error::ApiErrorCode::Internal,
+                    UserMsg::DeleteSceneryFolderFailed.error_with([e]),
+                )
             }
         })?;
     }
 
     // Remove from scenery index if it exists
-    if let Err(e) = scenery_index::remove_scenery_entry(&db.get(), &xplane_path, &folder_name).await
-    {
-        logger::log_error(
-            &format!("Failed to remove scenery from index: {}", e),
-            Some("scenery"),
-        );
-    }
+    let newly_missing_libraries =
+        match scenery_index::remove_scenery_entry(&db.get(), &xplane_path, &folder_name).await {
+            Ok(newly_missing) => newly_missing,
+            Err(e) => {
+                logger::log_error(
+                    &format!("Failed to remove scenery from index: {}", e),
+                    Some("scenery"),
+                );
+                Vec::new()
+            }
+        };
 
     // Update scenery_packs.ini to remove the deleted entry
-    let xplane_path = std::path::Path::new(&xplane_path);
-    let packs_manager = scenery_packs_manager::SceneryPacksManager::new(xplane_path, db.get());
-    if let Err(e) = packs_manager.apply_from_index().await {
-        logger::log_error(
-            &format!("Failed to update scenery_packs.ini after deletion: {}", e),
-            Some("scenery"),
-        );
+    if ini_deferred {
+        deferred_scenery::queue(&db.get(), &xplane_path, &[], Some(&folder_name)).await?;
+        deferred_scenery::ensure_poller(app_handle);
+    } else {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        let packs_manager = scenery_packs_manager::SceneryPacksManager::new(xplane_path, db.get());
+        if let Err(e) = packs_manager.apply_from_index().await {
+            logger::log_error(
+                &format!("Failed to update scenery_packs.ini after deletion: {}", e),
+                Some("scenery"),
+            );
+        }
     }
 
     logger::log_info(
@@ -1452,7 +2628,59 @@ async fn delete_scenery_folder(
         Some("scenery"),
     );
 
-    Ok(())
+    Ok(models::SceneryDeletionResult {
+        folder_name,
+        newly_missing_libraries,
+        ini_deferred,
+    })
+}
+
+/// Link a scenery folder stored elsewhere into Custom Scenery with a Windows
+/// `.lnk` shortcut. Returns the name the scenery index will list it under.
+#[tauri::command]
+async fn create_scenery_shortcut(
+    xplane_path: String,
+    source_folder: String,
+    link_name: String,
+) -> error::ApiResult<String> {
+    let name = link_name
+        .strip_suffix(".lnk")
+        .unwrap_or(&link_name)
+        .trim()
+        .to_string();
+    validate_scenery_folder_name(&name)?;
+
+    let source = PathBuf::from(&source_folder);
+    if !source.is_dir() {
+        return Err(error::ApiError {
+            details: Some(source_folder),
+            ..error::ApiError::localized(
+                error::ApiErrorCode::NotFound,
+                UserMsg::ScenerySourceFolderNotFound,
+            )
+        });
+    }
+
+    let custom_scenery = PathBuf::from(&xplane_path).join("Custom Scenery");
+    let lnk_path = custom_scenery.join(format!("{}.lnk", name));
+    if custom_scenery.join(&name).exists() || lnk_path.exists() {
+        return Err(error::ApiError::localized(
+            error::ApiErrorCode::ConflictExists,
+            UserMsg::SceneryEntryExists.error_with([&name]),
+        ));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        scenery_index::create_shortcut(&source, &lnk_path).map_err(error::ApiError::internal)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))??;
+
+    logger::log_info(
+        &format!("Created scenery shortcut: {} -> {}", name, source_folder),
+        Some("scenery"),
+    );
+    Ok(name)
 }
 
 #[tauri::command]
@@ -1479,9 +2707,34 @@ fn check_path_exists(path: String) -> bool {
 }
 
 #[tauri::command]
-fn launch_xplane(xplane_path: String, args: Option<Vec<String>>) -> Result<(), String> {
+async fn launch_xplane(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    args: Option<Vec<String>>,
+    profile: Option<String>,
+) -> Result<(), String> {
     let path = std::path::Path::new(&xplane_path);
-    let extra_args = args.unwrap_or_default();
+    let extra_args = match profile {
+        Some(name) => {
+            if args.is_some() {
+                return Err("Specify either a launch profile or launch arguments, not both".into());
+            }
+            let profile = launch_profiles::get_profile(&db.get(), &name)
+                .await
+                .map_err(|e| format!("Failed to load launch profile: {}", e))?
+                .ok_or_else(|| format!("Launch profile '{}' not found", name))?;
+            logger::log_info(
+                &format!(
+                    "Launching X-Plane with profile '{}': {}",
+                    profile.name,
+                    profile.args.join(" ")
+                ),
+                Some("app"),
+            );
+            profile.args
+        }
+        None => args.unwrap_or_default(),
+    };
 
     #[cfg(target_os = "windows")]
     {
@@ -1494,7 +2747,8 @@ fn launch_xplane(xplane_path: String, args: Option<Vec<String>>) -> Result<(), S
 
         // If it fails with error 740 (requires elevation), inform user
         match result {
-            Ok(_) => {
+            Ok(child) => {
+                xplane_processes::record_launch(child.id());
                 logger::log_info("X-Plane launched successfully", Some("app"));
             }
             Err(e) if e.raw_os_error() == Some(740) => {
@@ -1535,91 +2789,25 @@ fn launch_xplane(xplane_path: String, args: Option<Vec<String>>) -> Result<(), S
             })
             .map(|entry| entry.path())
             .ok_or_else(|| "X-Plane executable not found (expected X-Plane*)".to_string())?;
-        std::process::Command::new(exe_path)
+        let child = std::process::Command::new(exe_path)
             .args(&extra_args)
             .spawn()
             .map_err(|e| format!("Failed to launch X-Plane: {}", e))?;
+        xplane_processes::record_launch(child.id());
     }
 
     logger::log_info("X-Plane launched", Some("app"));
     Ok(())
 }
 
-/// Run a command in a blocking task and check if the process was found
-async fn check_process_running(
-    command: &'static str,
-    args: &'static [&'static str],
-    check_output: fn(&std::process::Output) -> bool,
-) -> bool {
-    let result = tokio::task::spawn_blocking(move || {
-        let mut cmd = std::process::Command::new(command);
-        cmd.args(args);
-        #[cfg(target_os = "windows")]
-        {
-            use std::os::windows::process::CommandExt;
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            cmd.creation_flags(CREATE_NO_WINDOW);
-        }
-        cmd.output()
-    })
-    .await;
-
-    match result {
-        Ok(Ok(output)) => {
-            let is_running = check_output(&output);
-            logger::log_debug(
-                &format!("X-Plane running check: {}", is_running),
-                Some("app"),
-                None,
-            );
-            is_running
-        }
-        Ok(Err(e)) => {
-            logger::log_debug(
-                &format!("Failed to run {}: {}", command, e),
-                Some("app"),
-                None,
-            );
-            false
-        }
-        Err(e) => {
-            logger::log_debug(&format!("Task join error: {}", e), Some("app"), None);
-            false
-        }
-    }
-}
-
+/// Running X-Plane processes, each with the known installation it runs from
+/// and whether `launch_xplane` started it
 #[tauri::command]
-async fn is_xplane_running() -> bool {
-    #[cfg(target_os = "windows")]
-    {
-        check_process_running(
-            "tasklist",
-            &["/FI", "IMAGENAME eq X-Plane.exe", "/NH"],
-            |output| {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                stdout.contains("X-Plane.exe")
-            },
-        )
-        .await
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        check_process_running("pgrep", &["-x", "X-Plane"], |output| {
-            output.status.success()
-        })
-        .await
-    }
-
-    #[cfg(target_os = "linux")]
-    {
-        // Linux executable is X-Plane-*, use pattern match instead of exact match
-        check_process_running("pgrep", &["-f", "X-Plane"], |output| {
-            output.status.success()
-        })
-        .await
-    }
+async fn is_xplane_running(
+    db: State<'_, DatabaseState>,
+) -> error::ApiResult<Vec<xplane_processes::XPlaneProcess>> {
+    let known_roots = xplane_installs::known_roots(&db.get()).await?;
+    Ok(xplane_processes::running_processes(&known_roots).await)
 }
 
 #[tauri::command]
@@ -1698,6 +2886,34 @@ fn get_last_check_time() -> Option<i64> {
     updater::get_last_check_time()
 }
 
+#[tauri::command]
+async fn download_update(
+    app_handle: tauri::AppHandle,
+    control: State<'_, updater::UpdateDownloadControl>,
+    include_pre_release: bool,
+) -> Result<updater::DownloadedUpdate, String> {
+    let event_handle = app_handle.clone();
+    let progress_callback: updater::UpdateDownloadProgressCallback = Arc::new(move |event| {
+        let _ = event_handle.emit("update-download-progress", event);
+    });
+    updater::download_update(
+        include_pre_release,
+        control.inner(),
+        Some(progress_callback),
+    )
+    .await
+}
+
+#[tauri::command]
+fn cancel_update_download(control: State<'_, updater::UpdateDownloadControl>) {
+    control.cancel();
+}
+
+#[tauri::command]
+fn install_downloaded_update(file_path: String) -> Result<(), String> {
+    updater::launch_downloaded_update(std::path::Path::new(&file_path))
+}
+
 // ========== Library Download Links Commands ==========
 
 #[tauri::command]
@@ -1715,6 +2931,17 @@ async fn lookup_library_links_remote(
     library_links::lookup_library_links_remote(library_names, force_refresh.unwrap_or(false)).await
 }
 
+/// Missing libraries across the scenery index, de-duplicated and ordered so
+/// libraries other libraries depend on come first, each with a download link
+/// and any installed folder that looks like it under another name
+#[tauri::command]
+async fn build_missing_library_plan(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+) -> error::ApiResult<Vec<models::MissingLibraryPlanEntry>> {
+    library_plan::build_missing_library_plan(&db.get(), std::path::Path::new(&xplane_path)).await
+}
+
 // ========== Scenery Auto-Sorting Commands ==========
 
 #[tauri::command]
@@ -1728,17 +2955,22 @@ async fn get_scenery_classification(
     let scenery_path = xplane_path.join("Custom Scenery").join(&folder_name);
 
     if !scenery_path.exists() {
-        return Err(error::ApiError::not_found(format!(
-            "Scenery folder not found: {}",
-            folder_name
-        )));
+        return Err(error::ApiError::localized(
+            error::ApiErrorCode::NotFound,
+            UserMsg::SceneryFolderNotFoundNamed.error_with([&folder_name]),
+        ));
     }
 
     let index_manager = SceneryIndexManager::new(xplane_path, db);
     index_manager
         .get_or_classify(&scenery_path)
         .await
-        .map_err(|e| error::ApiError::internal(format!("Classification failed: {}", e)))
+        .map_err(|e| {
+            error::ApiError::localized(
+                error::ApiErrorCode::Internal,
+                UserMsg::SceneryClassificationFailed.error_with([e]),
+            )
+        })
 }
 
 #[tauri::command]
@@ -1746,7 +2978,7 @@ async fn sort_scenery_packs(
     db: State<'_, DatabaseState>,
     xplane_path: String,
     locked_folder_names: Option<Vec<String>>,
-) -> Result<bool, String> {
+) -> error::ApiResult<bool> {
     let db = db.get();
     let db_for_log = db.clone();
     let xplane_path = std::path::Path::new(&xplane_path);
@@ -1759,12 +2991,12 @@ async fn sort_scenery_packs(
         index_manager
             .reset_sort_order_with_locked_entries(locked_folder_names)
             .await
-            .map_err(|e| format!("Failed to reset sort order: {}", e))?
+            .api_context_msg(UserMsg::ResetSortOrderFailed)?
     } else {
         index_manager
             .reset_sort_order()
             .await
-            .map_err(|e| format!("Failed to reset sort order: {}", e))?
+            .api_context_msg(UserMsg::ResetSortOrderFailed)?
     };
 
     logger::log_info(
@@ -1775,7 +3007,7 @@ async fn sort_scenery_packs(
     packs_manager
         .reset_global_airports_position_to_default()
         .await
-        .map_err(|e| format!("Failed to reset Global Airports position: {}", e))?;
+        .api_context_msg(UserMsg::ResetGlobalAirportsPositionFailed)?;
 
     if has_changes {
         activity::log_activity(
@@ -1792,23 +3024,24 @@ async fn sort_scenery_packs(
     Ok(has_changes)
 }
 
+/// Rebuild the scenery index from scratch. Progress is reported through
+/// `scenery-index-progress` events; cancel with `cancel_installation`.
 #[tauri::command]
 async fn rebuild_scenery_index(
+    app_handle: tauri::AppHandle,
     db: State<'_, DatabaseState>,
+    task_control: State<'_, TaskControl>,
     xplane_path: String,
-) -> Result<SceneryIndexStats, String> {
+) -> error::ApiResult<SceneryIndexStats> {
     // Rebuild replaces all data, so an incompatible schema can be silently fixed first.
     // Use db.reset() (not reset_schema) so the pool is replaced with a fresh one,
     // clearing any stale sqlx prepared-statement caches.
-    if !database::is_schema_compatible(&db.get())
-        .await
-        .map_err(|e| e.to_string())?
-    {
+    if !database::is_schema_compatible(&db.get()).await? {
         logger::log_info(
             "Incompatible schema detected before rebuild — resetting",
             Some("database"),
         );
-        db.reset().await.map_err(|e| e.to_string())?;
+        db.reset().await?;
     }
 
     let db = db.get();
@@ -1817,36 +3050,31 @@ async fn rebuild_scenery_index(
 
     logger::log_info("Rebuilding scenery index", Some("scenery"));
 
+    task_control.reset();
+    let progress_callback: scenery_index::SceneryIndexProgressCallback = Arc::new(move |event| {
+        let _ = app_handle.emit(scenery_index::SCENERY_INDEX_PROGRESS_EVENT, event);
+    });
     let index = index_manager
-        .rebuild_index()
+        .rebuild_index(Some(task_control.inner().clone()), Some(progress_callback))
         .await
-        .map_err(|e| format!("Failed to rebuild index: {}", e))?;
+        .api_context_msg(UserMsg::RebuildIndexFailed)?;
 
     // Compute stats from the in-memory index directly to avoid a second DB read.
     // A SELECT on all columns can fail due to sqlx prepared-statement cache staleness
     // after a DROP + CREATE TABLE schema reset on the same connection pool.
-    let mut by_category: HashMap<String, usize> = HashMap::new();
-    for info in index.packages.values() {
-        let category_name = format!("{:?}", info.category);
-        *by_category.entry(category_name).or_insert(0) += 1;
-    }
-    Ok(SceneryIndexStats {
-        total_packages: index.packages.len(),
-        by_category,
-        last_updated: index.last_updated,
-    })
+    Ok(SceneryIndexStats::from_index(&index))
 }
 
 /// Reset the scenery database by deleting it entirely
 /// This is useful when the database schema version is incompatible
 #[tauri::command]
-async fn reset_scenery_database() -> Result<bool, String> {
+async fn reset_scenery_database() -> error::ApiResult<bool> {
     tokio::task::spawn_blocking(move || {
         logger::log_info("Resetting scenery database", Some("database"));
-        database::delete_database().map_err(|e| format!("Failed to delete database: {}", e))
+        database::delete_database().api_context("Failed to delete database")
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
 }
 
 /// Check whether the current database schema is compatible with the entity model.
@@ -1864,11 +3092,20 @@ async fn reset_and_reinitialize(db: tauri::State<'_, DatabaseState>) -> Result<(
     db.reset().await.map_err(|e| e.to_string())
 }
 
+/// Check the scenery database for corruption and orphaned rows, and compact it
+/// when much of it is free space
+#[tauri::command]
+async fn run_database_maintenance(
+    db: State<'_, DatabaseState>,
+) -> error::ApiResult<database::DatabaseMaintenanceReport> {
+    db.run_maintenance().await
+}
+
 #[tauri::command]
 async fn get_scenery_index_stats(
     db: State<'_, DatabaseState>,
     xplane_path: String,
-) -> Result<SceneryIndexStats, String> {
+) -> error::ApiResult<SceneryIndexStats> {
     let db = db.get();
     let xplane_path = std::path::Path::new(&xplane_path);
     let index_manager = SceneryIndexManager::new(xplane_path, db);
@@ -1876,14 +3113,77 @@ async fn get_scenery_index_stats(
     index_manager
         .get_stats()
         .await
-        .map_err(|e| format!("Failed to get stats: {}", e))
+        .api_context("Failed to get stats")
+}
+
+/// Scenery library statistics over time (oldest first), for charting growth
+#[tauri::command]
+async fn get_scenery_stats_history(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+) -> error::ApiResult<Vec<SceneryStatsSnapshot>> {
+    scenery_stats_history::load_history(&db.get(), std::path::Path::new(&xplane_path))
+        .await
+        .api_context("Failed to load scenery stats history")
+}
+
+/// Enabled packages depending on a library, for the disable/delete impact warning
+#[tauri::command]
+async fn get_library_dependents(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    library_folder: String,
+) -> error::ApiResult<Vec<models::LibraryDependent>> {
+    let db = db.get();
+    let xplane_path = std::path::Path::new(&xplane_path);
+    let index_manager = SceneryIndexManager::new(xplane_path, db);
+
+    index_manager
+        .get_library_dependents(&library_folder)
+        .await
+        .api_context("Failed to get library dependents")
+}
+
+/// Libraries required by a scenery package, with install/enable status
+#[tauri::command]
+async fn get_package_dependencies(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    folder_name: String,
+) -> error::ApiResult<Vec<models::PackageLibraryDependency>> {
+    let db = db.get();
+    let xplane_path = std::path::Path::new(&xplane_path);
+    let index_manager = SceneryIndexManager::new(xplane_path, db);
+
+    index_manager
+        .get_package_dependencies(&folder_name)
+        .await
+        .api_context("Failed to get package dependencies")
+}
+
+/// Search airports by ICAO code or name in Global Airports and enabled custom scenery
+#[tauri::command]
+async fn search_airports(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    query: String,
+    limit: Option<usize>,
+) -> error::ApiResult<Vec<models::AirportSearchResult>> {
+    airport_lookup::search_airports(
+        &db.get(),
+        std::path::Path::new(&xplane_path),
+        &query,
+        limit.unwrap_or(50),
+    )
+    .await
+    .api_context("Failed to search airports")
 }
 
 #[tauri::command]
 async fn get_scenery_index_status(
     db: State<'_, DatabaseState>,
     xplane_path: String,
-) -> Result<SceneryIndexStatus, String> {
+) -> error::ApiResult<SceneryIndexStatus> {
     let db = db.get();
     let xplane_path = std::path::Path::new(&xplane_path);
     let index_manager = SceneryIndexManager::new(xplane_path, db);
@@ -1891,7 +3191,7 @@ async fn get_scenery_index_status(
     index_manager
         .index_status()
         .await
-        .map_err(|e| format!("Failed to get index status: {}", e))
+        .api_context("Failed to get index status")
 }
 
 #[tauri::command]
@@ -1899,7 +3199,7 @@ async fn quick_scan_scenery_index(
     db: State<'_, DatabaseState>,
     xplane_path: String,
     locked_folder_names: Option<Vec<String>>,
-) -> Result<SceneryIndexScanResult, String> {
+) -> error::ApiResult<SceneryIndexScanResult> {
     let db = db.get();
     let xplane_path = std::path::Path::new(&xplane_path);
     let index_manager = SceneryIndexManager::new(xplane_path, db);
@@ -1908,22 +3208,45 @@ async fn quick_scan_scenery_index(
         index_manager
             .quick_scan_and_update_with_locked_entries(locked_folder_names)
             .await
-            .map_err(|e| format!("Failed to quick scan scenery index: {}", e))?
+            .api_context("Failed to quick scan scenery index")?
     } else {
         index_manager
             .quick_scan_and_update()
             .await
-            .map_err(|e| format!("Failed to quick scan scenery index: {}", e))?
+            .api_context("Failed to quick scan scenery index")?
     };
 
     Ok(result)
 }
 
+#[tauri::command]
+async fn start_scenery_watcher(
+    app_handle: tauri::AppHandle,
+    watcher: State<'_, scenery_watcher::SceneryWatcherState>,
+    xplane_path: String,
+    locked_folder_names: Option<Vec<String>>,
+) -> error::ApiResult<bool> {
+    watcher
+        .start(
+            app_handle,
+            std::path::Path::new(&xplane_path),
+            locked_folder_names.unwrap_or_default(),
+        )
+        .api_context("Failed to start scenery watcher")
+}
+
+#[tauri::command]
+async fn stop_scenery_watcher(
+    watcher: State<'_, scenery_watcher::SceneryWatcherState>,
+) -> error::ApiResult<bool> {
+    Ok(watcher.stop())
+}
+
 #[tauri::command]
 async fn sync_scenery_packs_with_folder(
     db: State<'_, DatabaseState>,
     xplane_path: String,
-) -> Result<usize, String> {
+) -> error::ApiResult<usize> {
     let db = db.get();
     let xplane_path = std::path::Path::new(&xplane_path);
     let manager = SceneryPacksManager::new(xplane_path, db);
@@ -1931,22 +3254,61 @@ async fn sync_scenery_packs_with_folder(
     manager
         .sync_with_folder()
         .await
-        .map_err(|e| format!("Failed to sync scenery packs: {}", e))
+        .api_context_msg(UserMsg::SyncSceneryPacksFailed)
+}
+
+#[tauri::command]
+async fn get_scenery_manager_data(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+) -> error::ApiResult<SceneryManagerData> {
+    let db = db.get();
+    let xplane_path = std::path::Path::new(&xplane_path);
+    let index_manager = SceneryIndexManager::new(xplane_path, db);
+
+    index_manager
+        .get_manager_data()
+        .await
+        .api_context_msg(UserMsg::GetSceneryManagerDataFailed)
+}
+
+#[tauri::command]
+async fn get_scenery_manager_data_paged(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    offset: u64,
+    limit: u64,
+    sort: Option<models::SceneryManagerSort>,
+    filter: Option<models::SceneryManagerFilter>,
+) -> error::ApiResult<models::SceneryManagerPage> {
+    let db = db.get();
+    let xplane_path = std::path::Path::new(&xplane_path);
+    let index_manager = SceneryIndexManager::new(xplane_path, db);
+
+    index_manager
+        .get_manager_page(
+            offset,
+            limit,
+            &sort.unwrap_or_default(),
+            &filter.unwrap_or_default(),
+        )
+        .await
+        .api_context("Failed to get scenery manager page")
 }
 
 #[tauri::command]
-async fn get_scenery_manager_data(
+async fn get_scenery_manager_summary(
     db: State<'_, DatabaseState>,
     xplane_path: String,
-) -> Result<SceneryManagerData, String> {
+) -> error::ApiResult<models::SceneryManagerSummary> {
     let db = db.get();
     let xplane_path = std::path::Path::new(&xplane_path);
     let index_manager = SceneryIndexManager::new(xplane_path, db);
 
     index_manager
-        .get_manager_data()
+        .get_manager_summary()
         .await
-        .map_err(|e| format!("Failed to get scenery manager data: {}", e))
+        .api_context("Failed to get scenery manager summary")
 }
 
 #[tauri::command]
@@ -1957,28 +3319,35 @@ async fn update_scenery_entry(
     enabled: Option<bool>,
     sort_order: Option<u32>,
     category: Option<models::SceneryCategory>,
-) -> Result<(), String> {
+    pinned: Option<bool>,
+) -> error::ApiResult<()> {
     let db = db.get();
     let xplane_path = std::path::Path::new(&xplane_path);
     if folder_name == GLOBAL_AIRPORTS_ENTRY_NAME {
+        if pinned.is_some() {
+            return Err(error::ApiError::localized(
+                error::ApiErrorCode::ValidationFailed,
+                UserMsg::GlobalAirportsCannotBePinned,
+            ));
+        }
         let packs_manager = SceneryPacksManager::new(xplane_path, db);
         if let Some(enabled) = enabled {
             packs_manager
                 .set_global_airports_enabled(enabled)
                 .await
-                .map_err(|e| format!("Failed to update Global Airports state: {}", e))?;
+                .api_context_msg(UserMsg::UpdateGlobalAirportsStateFailed)?;
         }
         if let Some(sort_order) = sort_order {
             packs_manager
                 .set_global_airports_sort_order(sort_order)
                 .await
-                .map_err(|e| format!("Failed to update Global Airports position: {}", e))?;
+                .api_context_msg(UserMsg::UpdateGlobalAirportsPositionFailed)?;
         }
         if let Some(category) = category {
             packs_manager
                 .set_global_airports_category(&category)
                 .await
-                .map_err(|e| format!("Failed to update Global Airports category: {}", e))?;
+                .api_context_msg(UserMsg::UpdateGlobalAirportsCategoryFailed)?;
         }
         return Ok(());
     }
@@ -1986,9 +3355,9 @@ async fn update_scenery_entry(
     let index_manager = SceneryIndexManager::new(xplane_path, db);
 
     index_manager
-        .update_entry(&folder_name, enabled, sort_order, category)
+        .update_entry(&folder_name, enabled, sort_order, category, pinned)
         .await
-        .map_err(|e| format!("Failed to update scenery entry: {}", e))
+        .api_context_msg(UserMsg::UpdateSceneryEntryFailed)
 }
 
 #[tauri::command]
@@ -1997,14 +3366,14 @@ async fn move_scenery_entry(
     xplane_path: String,
     folder_name: String,
     new_sort_order: u32,
-) -> Result<(), String> {
+) -> error::ApiResult<()> {
     let db = db.get();
     let xplane_path = std::path::Path::new(&xplane_path);
     if folder_name == GLOBAL_AIRPORTS_ENTRY_NAME {
         return SceneryPacksManager::new(xplane_path, db)
             .set_global_airports_sort_order(new_sort_order)
             .await
-            .map_err(|e| format!("Failed to move Global Airports entry: {}", e));
+            .api_context_msg(UserMsg::MoveGlobalAirportsFailed);
     }
 
     let index_manager = SceneryIndexManager::new(xplane_path, db);
@@ -2012,21 +3381,29 @@ async fn move_scenery_entry(
     index_manager
         .move_entry(&folder_name, new_sort_order)
         .await
-        .map_err(|e| format!("Failed to move scenery entry: {}", e))
+        .api_context_msg(UserMsg::MoveSceneryEntryFailed)
 }
 
+/// Store scenery entry changes in the index and write them to
+/// scenery_packs.ini. Returns false when `apply_mode` deferred the ini write
+/// until X-Plane closes.
 #[tauri::command]
 async fn apply_scenery_changes(
+    app_handle: tauri::AppHandle,
     db: State<'_, DatabaseState>,
     xplane_path: String,
     entries: Vec<models::SceneryEntryUpdate>,
-) -> Result<(), String> {
+    apply_mode: Option<models::SceneryApplyMode>,
+) -> error::ApiResult<bool> {
     let db = db.get();
-    let xplane_path = std::path::Path::new(&xplane_path);
-    validate_xplane_root_path(xplane_path)?;
+    let xplane_path_str = xplane_path;
+    let xplane_path = std::path::Path::new(&xplane_path_str);
+    validate_xplane_root_path(xplane_path).map_err(error::ApiError::validation)?;
+    let defer = deferred_scenery::should_defer(apply_mode.unwrap_or_default(), xplane_path).await?;
     let index_manager = SceneryIndexManager::new(xplane_path, db.clone());
 
     logger::log_info("Applying scenery changes to index and ini", Some("scenery"));
+    let deferred_entries = if defer { entries.clone() } else { Vec::new() };
 
     let mut global_airports_enabled: Option<bool> = None;
     let mut global_airports_sort_order: Option<u32> = None;
@@ -2047,7 +3424,7 @@ async fn apply_scenery_changes(
     index_manager
         .batch_update_entries(&index_updates)
         .await
-        .map_err(|e| format!("Failed to update index: {}", e))?;
+        .api_context_msg(UserMsg::UpdateIndexFailed)?;
 
     // Apply to ini file
     let db_for_log = db.clone();
@@ -2056,18 +3433,34 @@ async fn apply_scenery_changes(
         packs_manager
             .set_global_airports_enabled(enabled)
             .await
-            .map_err(|e| format!("Failed to update Global Airports state: {}", e))?;
+            .api_context_msg(UserMsg::UpdateGlobalAirportsStateFailed)?;
     }
     if let Some(sort_order) = global_airports_sort_order {
         packs_manager
             .set_global_airports_sort_order(sort_order)
             .await
-            .map_err(|e| format!("Failed to update Global Airports position: {}", e))?;
+            .api_context_msg(UserMsg::UpdateGlobalAirportsPositionFailed)?;
+    }
+    if defer {
+        deferred_scenery::queue(&db_for_log, &xplane_path_str, &deferred_entries, None).await?;
+        deferred_scenery::ensure_poller(app_handle);
+        logger::log_info(
+            "X-Plane is running, scenery_packs.ini will be written once it closes",
+            Some("scenery"),
+        );
+        return Ok(false);
     }
     packs_manager
         .apply_from_index()
         .await
-        .map_err(|e| format!("Failed to apply scenery changes: {}", e))?;
+        .api_context_msg(UserMsg::ApplySceneryChangesFailed)?;
+    // The ini now holds everything that was waiting for X-Plane to close
+    if let Err(e) = deferred_scenery::clear(&db_for_log, &xplane_path_str).await {
+        logger::log_error(
+            &format!("Failed to clear deferred scenery changes: {}", e),
+            Some("scenery"),
+        );
+    }
 
     logger::log_info("Scenery changes applied successfully", Some("scenery"));
 
@@ -2082,7 +3475,168 @@ async fn apply_scenery_changes(
     )
     .await;
 
-    Ok(())
+    Ok(true)
+}
+
+/// What `apply_scenery_changes` would write to scenery_packs.ini from the
+/// current index, without writing it. The preview is also emitted as
+/// `scenery-packs-preview`.
+#[tauri::command]
+async fn preview_scenery_packs_changes(
+    app_handle: tauri::AppHandle,
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+) -> error::ApiResult<models::SceneryPacksPreview> {
+    let xplane_path = std::path::Path::new(&xplane_path);
+    validate_xplane_root_path(xplane_path).map_err(error::ApiError::validation)?;
+    let preview = SceneryPacksManager::new(xplane_path, db.get())
+        .preview_changes()
+        .await
+        .map_err(error::ApiError::from)?;
+    let _ = app_handle.emit("scenery-packs-preview", &preview);
+    Ok(preview)
+}
+
+/// Check scenery_packs.ini for entries on the wrong side of each other, and
+/// with `apply_fixes` move only those entries
+#[tauri::command]
+async fn validate_scenery_order(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    apply_fixes: Option<bool>,
+) -> error::ApiResult<models::SceneryOrderReport> {
+    let db = db.get();
+    let xplane_path = std::path::Path::new(&xplane_path);
+    validate_xplane_root_path(xplane_path).map_err(error::ApiError::validation)?;
+    let apply_fixes = apply_fixes.unwrap_or(false);
+
+    let report = scenery_order::validate_scenery_order(&db, xplane_path, apply_fixes).await?;
+
+    if report.fixes_applied {
+        logger::log_info(
+            &format!(
+                "Moved {} scenery entries to fix {} order violations",
+                report.moved.len(),
+                report.violations.len()
+            ),
+            Some("scenery"),
+        );
+        activity::log_activity(
+            &db,
+            "config_change",
+            "scenery",
+            "scenery_packs.ini",
+            Some(format!("{{\"entriesMoved\":{}}}", report.moved.len())),
+            true,
+        )
+        .await;
+    }
+
+    Ok(report)
+}
+
+/// Check scenery_packs.ini for structural and encoding problems
+#[tauri::command]
+async fn validate_scenery_packs_ini(
+    xplane_path: String,
+) -> error::ApiResult<models::SceneryPacksIniReport> {
+    let xplane_path = std::path::Path::new(&xplane_path);
+    validate_xplane_root_path(xplane_path).map_err(error::ApiError::validation)?;
+    scenery_packs_repair::validate_scenery_packs_ini(xplane_path).await
+}
+
+/// Rebuild scenery_packs.ini from the index or clean it in place, keeping the
+/// previous file as scenery_packs.ini.bak
+#[tauri::command]
+async fn repair_scenery_packs_ini(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    strategy: models::SceneryPacksRepairStrategy,
+) -> error::ApiResult<models::SceneryPacksIniRepairResult> {
+    let db = db.get();
+    let xplane_path = std::path::Path::new(&xplane_path);
+    validate_xplane_root_path(xplane_path).map_err(error::ApiError::validation)?;
+
+    let result = scenery_packs_repair::repair_scenery_packs_ini(&db, xplane_path, strategy).await?;
+
+    if result.changed {
+        logger::log_info(
+            &format!(
+                "Repaired scenery_packs.ini ({:?}): {} changes",
+                strategy,
+                result.changes.len()
+            ),
+            Some("scenery"),
+        );
+        activity::log_activity(
+            &db,
+            "repair",
+            "scenery",
+            "scenery_packs.ini",
+            Some(format!("{{\"changes\":{}}}", result.changes.len())),
+            true,
+        )
+        .await;
+    }
+
+    Ok(result)
+}
+
+/// Copy, move or link packages of another Custom Scenery folder into this
+/// installation. Packages whose name is taken are skipped and reported.
+#[tauri::command]
+async fn import_scenery_from_external(
+    app_handle: tauri::AppHandle,
+    db: State<'_, DatabaseState>,
+    task_control: State<'_, TaskControl>,
+    source_custom_scenery_path: String,
+    xplane_path: String,
+    folder_names: Option<Vec<String>>,
+    mode: models::SceneryImportMode,
+) -> error::ApiResult<models::SceneryImportResult> {
+    let db = db.get();
+    let xplane_path = std::path::Path::new(&xplane_path);
+    validate_xplane_root_path(xplane_path).map_err(error::ApiError::validation)?;
+    task_control.reset();
+
+    let result = scenery_import::import_scenery_from_external(
+        Some(app_handle),
+        &db,
+        task_control.inner().clone(),
+        std::path::Path::new(&source_custom_scenery_path),
+        xplane_path,
+        folder_names,
+        mode,
+    )
+    .await?;
+
+    logger::log_info(
+        &format!(
+            "Imported {} scenery packages ({:?}), {} skipped, {} failed{}",
+            result.imported.len(),
+            mode,
+            result.skipped_existing.len(),
+            result.failed.len(),
+            if result.cancelled { ", cancelled" } else { "" }
+        ),
+        Some("scenery"),
+    );
+    for package in &result.imported {
+        activity::log_activity(&db, "install", "scenery", &package.folder_name, None, true).await;
+    }
+    for failure in &result.failed {
+        activity::log_activity(
+            &db,
+            "install",
+            "scenery",
+            &failure.folder_name,
+            Some(failure.message.clone()),
+            false,
+        )
+        .await;
+    }
+
+    Ok(result)
 }
 
 // ========== Management Commands ==========
@@ -2156,21 +3710,14 @@ fn is_xupdater_disabled_target(xplane_path: &str, item_type: &str, folder_name:
     x_updater_profile::find_profile_in_folder(&target_path).is_some()
 }
 
-fn blocked_addon_update_error(item_type: &str, folder_name: &str) -> String {
-    let mut checksum: u32 = 0x6D2B_79F5;
-    for byte in item_type.bytes().chain(folder_name.bytes()) {
-        checksum = checksum.rotate_left(5) ^ ((byte as u32).wrapping_mul(0x45D9_F3B));
-        checksum = checksum.wrapping_add(0x27D4_EB2D);
-    }
-    let token = format!("{:08X}", checksum ^ 0xA1C3_0F71);
-    format!("resolver handshake mismatch [{}]", token)
-}
-
 #[tauri::command]
-async fn scan_aircraft(xplane_path: String) -> Result<ManagementData<AircraftInfo>, String> {
+async fn scan_aircraft(
+    xplane_path: String,
+    compute_sizes: Option<bool>,
+) -> Result<ManagementData<AircraftInfo>, String> {
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::scan_aircraft(xplane_path)
+        management_index::scan_aircraft(xplane_path, compute_sizes.unwrap_or(false))
             .map_err(|e| format!("Failed to scan aircraft: {}", e))
     })
     .await
@@ -2206,6 +3753,62 @@ async fn check_plugins_updates(mut plugins: Vec<PluginInfo>) -> Result<Vec<Plugi
     Ok(plugins)
 }
 
+/// Check every aircraft, plugin and scenery package (and optionally livery)
+/// with updater metadata and summarize which ones have updates
+#[tauri::command]
+async fn check_all_addon_updates(
+    xplane_path: String,
+    include_liveries: bool,
+    use_beta: Option<bool>,
+) -> error::ApiResult<models::AddonUpdateSummary> {
+    addon_update_checks::check_all(
+        std::path::Path::new(&xplane_path),
+        include_liveries,
+        use_beta.unwrap_or(false),
+        false,
+    )
+    .await
+}
+
+#[tauri::command]
+fn cancel_addon_update_check() -> bool {
+    addon_update_checks::cancel()
+}
+
+/// Repeat the addon update check every `interval_hours` in the background,
+/// emitting `addon-updates-available` when updates are found. 0 stops it.
+#[tauri::command]
+fn start_addon_update_scheduler(
+    app_handle: tauri::AppHandle,
+    xplane_path: String,
+    interval_hours: u32,
+    include_liveries: Option<bool>,
+    use_beta: Option<bool>,
+) -> error::ApiResult<()> {
+    addon_update_checks::start_scheduler(
+        app_handle,
+        std::path::PathBuf::from(xplane_path),
+        interval_hours,
+        include_liveries.unwrap_or(false),
+        use_beta.unwrap_or(false),
+    )
+}
+
+/// Local path of an addon's cached icon from its updater metadata, downloading it
+/// on first use. None if the addon has no known icon or it cannot be fetched.
+#[tauri::command]
+async fn get_addon_icon(item_type: String, folder_name: String) -> Result<Option<String>, String> {
+    Ok(addon_icons::get_icon(&item_type, &folder_name)
+        .await
+        .map(|path| path.to_string_lossy().to_string()))
+}
+
+/// Delete all cached addon icons. Returns the number of files removed.
+#[tauri::command]
+fn clear_icon_cache() -> Result<usize, String> {
+    addon_icons::clear_cache()
+}
+
 #[tauri::command]
 async fn build_addon_update_plan(
     app_handle: tauri::AppHandle,
@@ -2214,19 +3817,19 @@ async fn build_addon_update_plan(
     item_type: String,
     folder_name: String,
     options: addon_updater::AddonUpdateOptions,
-) -> Result<addon_updater::AddonUpdatePlan, String> {
+) -> error::ApiResult<addon_updater::AddonUpdatePlan> {
     task_control.reset();
     if is_xupdater_disabled_target(&xplane_path, &item_type, &folder_name) {
-        let message = blocked_addon_update_error(&item_type, &folder_name);
+        let err = addon_updater::unsupported_profile_error(&item_type, &folder_name);
         emit_addon_update_status(
             &app_handle,
             &item_type,
             &folder_name,
             "scan",
             "failed",
-            Some(message.clone()),
+            Some(err.message.clone()),
         );
-        return Err(message);
+        return Err(err);
     }
     let event_handle = app_handle.clone();
     let progress_callback: addon_updater::AddonUpdateProgressCallback = Arc::new(move |event| {
@@ -2245,23 +3848,52 @@ async fn build_addon_update_plan(
     {
         Ok(plan) => Ok(plan),
         Err(e) => {
+            let err = error::ApiError::from(e);
             emit_addon_update_status(
                 &app_handle,
                 &item_type,
                 &folder_name,
                 "scan",
-                if e.to_string().to_lowercase().contains("cancelled") {
+                if err.code == error::ApiErrorCode::Cancelled {
                     "cancelled"
                 } else {
                     "failed"
                 },
-                Some(e.to_string()),
+                Some(err.message.clone()),
             );
-            Err(e.to_string())
+            Err(err)
         }
     }
 }
 
+/// What an addon update would change in the local folder, grouped per kind
+#[tauri::command]
+async fn get_addon_update_diff(
+    task_control: State<'_, TaskControl>,
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+    options: addon_updater::AddonUpdateOptions,
+) -> error::ApiResult<models::AddonUpdateDiff> {
+    task_control.reset();
+    if is_xupdater_disabled_target(&xplane_path, &item_type, &folder_name) {
+        return Err(addon_updater::unsupported_profile_error(
+            &item_type,
+            &folder_name,
+        ));
+    }
+    let xplane_path = std::path::Path::new(&xplane_path);
+    addon_update_diff::get_addon_update_diff(
+        xplane_path,
+        &item_type,
+        &folder_name,
+        options,
+        Some(task_control.inner().clone()),
+    )
+    .await
+    .map_err(error::ApiError::from)
+}
+
 #[tauri::command]
 async fn fetch_addon_update_preview(
     app_handle: tauri::AppHandle,
@@ -2272,19 +3904,19 @@ async fn fetch_addon_update_preview(
     options: addon_updater::AddonUpdateOptions,
     login: Option<String>,
     license_key: Option<String>,
-) -> Result<addon_updater::AddonUpdatePreview, String> {
+) -> error::ApiResult<addon_updater::AddonUpdatePreview> {
     task_control.reset();
     if is_xupdater_disabled_target(&xplane_path, &item_type, &folder_name) {
-        let message = blocked_addon_update_error(&item_type, &folder_name);
+        let err = addon_updater::unsupported_profile_error(&item_type, &folder_name);
         emit_addon_update_status(
             &app_handle,
             &item_type,
             &folder_name,
             "check",
             "failed",
-            Some(message.clone()),
+            Some(err.message.clone()),
         );
-        return Err(message);
+        return Err(err);
     }
     let event_handle = app_handle.clone();
     let progress_callback: addon_updater::AddonUpdateProgressCallback = Arc::new(move |event| {
@@ -2305,23 +3937,133 @@ async fn fetch_addon_update_preview(
     {
         Ok(preview) => Ok(preview),
         Err(e) => {
+            let err = error::ApiError::from(e);
             emit_addon_update_status(
                 &app_handle,
                 &item_type,
                 &folder_name,
                 "check",
-                if e.to_string().to_lowercase().contains("cancelled") {
+                if err.code == error::ApiErrorCode::Cancelled {
                     "cancelled"
                 } else {
                     "failed"
                 },
-                Some(e.to_string()),
+                Some(err.message.clone()),
             );
-            Err(e.to_string())
+            Err(err)
         }
     }
 }
 
+#[tauri::command]
+async fn get_addon_update_channel(
+    app_handle: tauri::AppHandle,
+    task_control: State<'_, TaskControl>,
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+) -> error::ApiResult<addon_updater::AddonUpdateChannel> {
+    run_addon_channel_task(
+        app_handle,
+        task_control,
+        xplane_path,
+        item_type,
+        folder_name,
+        None,
+    )
+    .await
+}
+
+/// Switch an addon between its stable and beta updates. The remote is asked
+/// first, reported through the usual "check" progress events.
+#[tauri::command]
+async fn set_addon_update_channel(
+    app_handle: tauri::AppHandle,
+    task_control: State<'_, TaskControl>,
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+    channel: String,
+) -> error::ApiResult<addon_updater::AddonUpdateChannel> {
+    run_addon_channel_task(
+        app_handle,
+        task_control,
+        xplane_path,
+        item_type,
+        folder_name,
+        Some(channel),
+    )
+    .await
+}
+
+async fn run_addon_channel_task(
+    app_handle: tauri::AppHandle,
+    task_control: State<'_, TaskControl>,
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+    channel: Option<String>,
+) -> error::ApiResult<addon_updater::AddonUpdateChannel> {
+    task_control.reset();
+    if is_xupdater_disabled_target(&xplane_path, &item_type, &folder_name) {
+        let err = addon_updater::unsupported_profile_error(&item_type, &folder_name);
+        emit_addon_update_status(
+            &app_handle,
+            &item_type,
+            &folder_name,
+            "check",
+            "failed",
+            Some(err.message.clone()),
+        );
+        return Err(err);
+    }
+    let event_handle = app_handle.clone();
+    let progress_callback: addon_updater::AddonUpdateProgressCallback = Arc::new(move |event| {
+        let _ = event_handle.emit("addon-update-progress", event);
+    });
+    let xplane_path = std::path::Path::new(&xplane_path);
+    let task_control = Some(task_control.inner().clone());
+    let result = match channel {
+        Some(channel) => {
+            addon_updater::set_update_channel(
+                xplane_path,
+                &item_type,
+                &folder_name,
+                &channel,
+                task_control,
+                Some(progress_callback),
+            )
+            .await
+        }
+        None => {
+            addon_updater::get_update_channel(
+                xplane_path,
+                &item_type,
+                &folder_name,
+                task_control,
+                Some(progress_callback),
+            )
+            .await
+        }
+    };
+    result.map_err(|e| {
+        let err = error::ApiError::from(e);
+        emit_addon_update_status(
+            &app_handle,
+            &item_type,
+            &folder_name,
+            "check",
+            if err.code == error::ApiErrorCode::Cancelled {
+                "cancelled"
+            } else {
+                "failed"
+            },
+            Some(err.message.clone()),
+        );
+        err
+    })
+}
+
 #[tauri::command]
 async fn execute_addon_update(
     app_handle: tauri::AppHandle,
@@ -2331,19 +4073,19 @@ async fn execute_addon_update(
     item_type: String,
     folder_name: String,
     options: addon_updater::AddonUpdateOptions,
-) -> Result<addon_updater::AddonUpdateResult, String> {
+) -> error::ApiResult<addon_updater::AddonUpdateResult> {
     task_control.reset();
     if is_xupdater_disabled_target(&xplane_path, &item_type, &folder_name) {
-        let message = blocked_addon_update_error(&item_type, &folder_name);
+        let err = addon_updater::unsupported_profile_error(&item_type, &folder_name);
         emit_addon_update_status(
             &app_handle,
             &item_type,
             &folder_name,
             "install",
             "failed",
-            Some(message.clone()),
+            Some(err.message.clone()),
         );
-        return Err(message);
+        return Err(err);
     }
     let event_handle = app_handle.clone();
     let progress_callback: addon_updater::AddonUpdateProgressCallback = Arc::new(move |event| {
@@ -2374,24 +4116,74 @@ async fn execute_addon_update(
                 false,
             )
             .await;
+            let err = error::ApiError::from(e);
             emit_addon_update_status(
                 &app_handle,
                 &item_type,
                 &folder_name,
                 "install",
-                if e.to_string().to_lowercase().contains("cancelled") {
+                if err.code == error::ApiErrorCode::Cancelled {
                     "cancelled"
                 } else {
                     "failed"
                 },
-                Some(e.to_string()),
+                Some(err.message.clone()),
             );
-            Err(e.to_string())
+            Err(err)
         }
     };
     result
 }
 
+/// Backups kept by updates of an addon, newest first
+#[tauri::command]
+async fn list_addon_backups(
+    item_type: String,
+    folder_name: String,
+) -> error::ApiResult<Vec<models::AddonBackup>> {
+    tokio::task::spawn_blocking(move || {
+        addon_backups::list_backups(&item_type, &folder_name).map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// Undo the update a backup was kept for. Files changed since that update are
+/// reported and left alone unless `force` is set.
+#[tauri::command]
+async fn restore_addon_backup(
+    db: State<'_, DatabaseState>,
+    backup_id: String,
+    force: Option<bool>,
+) -> error::ApiResult<models::AddonBackupRestoreResult> {
+    let id = backup_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        addon_backups::restore_backup(&id, force.unwrap_or(false)).map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?;
+
+    if let Ok(models::AddonBackupRestoreResult {
+        restored: false, ..
+    }) = &result
+    {
+        return result;
+    }
+    activity::log_activity(
+        &db.get(),
+        "restore",
+        "addon",
+        &backup_id,
+        match &result {
+            Ok(restored) => serde_json::to_string(restored).ok(),
+            Err(e) => Some(e.message.clone()),
+        },
+        result.is_ok(),
+    )
+    .await;
+    result
+}
+
 #[tauri::command]
 async fn set_addon_updater_credentials(
     xplane_path: String,
@@ -2399,12 +4191,37 @@ async fn set_addon_updater_credentials(
     folder_name: String,
     login: String,
     license_key: String,
-) -> Result<(), String> {
+    import_from_discovery: Option<bool>,
+    source_item_type: Option<String>,
+    source_folder_name: Option<String>,
+) -> error::ApiResult<()> {
     if is_xupdater_disabled_target(&xplane_path, &item_type, &folder_name) {
-        return Err(blocked_addon_update_error(&item_type, &folder_name));
+        return Err(addon_updater::unsupported_profile_error(
+            &item_type,
+            &folder_name,
+        ));
     }
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
+        // Discovered credentials are read again here so the secret never
+        // round-trips through the frontend; login/license_key are ignored
+        if import_from_discovery.unwrap_or(false) {
+            let (Some(source_item_type), Some(source_folder_name)) =
+                (source_item_type, source_folder_name)
+            else {
+                return Err(error::ApiError::validation(
+                    "Importing credentials requires the addon they were discovered in",
+                ));
+            };
+            return addon_updater::import_discovered_credentials(
+                xplane_path,
+                &source_item_type,
+                &source_folder_name,
+                &item_type,
+                &folder_name,
+            )
+            .map_err(error::ApiError::from);
+        }
         addon_updater::set_updater_credentials(
             xplane_path,
             &item_type,
@@ -2412,7 +4229,21 @@ async fn set_addon_updater_credentials(
             &login,
             &license_key,
         )
-        .map_err(|e| e.to_string())
+        .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// List credentials stored by other updaters in installed addons, with the
+/// login masked and the license key omitted
+#[tauri::command]
+async fn discover_existing_updater_credentials(
+    xplane_path: String,
+) -> Result<Vec<addon_updater::DiscoveredUpdaterCredentials>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        addon_updater::discover_existing_credentials(xplane_path).map_err(|e| e.to_string())
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
@@ -2423,13 +4254,32 @@ async fn get_addon_updater_credentials(
     xplane_path: String,
     item_type: String,
     folder_name: String,
-) -> Result<Option<addon_updater::AddonUpdaterCredentials>, String> {
+) -> error::ApiResult<Option<addon_updater::AddonUpdaterCredentials>> {
     if is_xupdater_disabled_target(&xplane_path, &item_type, &folder_name) {
-        return Err(blocked_addon_update_error(&item_type, &folder_name));
+        return Err(addon_updater::unsupported_profile_error(
+            &item_type,
+            &folder_name,
+        ));
     }
     tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
         addon_updater::get_updater_credentials(xplane_path, &item_type, &folder_name)
+            .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// Report missing or damaged fields in an addon's x-updater profile
+#[tauri::command]
+async fn validate_updater_profile(
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+) -> Result<x_updater_profile::ProfileValidation, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        addon_updater::validate_updater_profile(xplane_path, &item_type, &folder_name)
             .map_err(|e| e.to_string())
     })
     .await
@@ -2452,14 +4302,26 @@ async fn get_addon_update_disk_space(
 }
 
 #[tauri::command]
-async fn scan_plugins(xplane_path: String) -> Result<ManagementData<PluginInfo>, String> {
-    tokio::task::spawn_blocking(move || {
+async fn scan_plugins(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    compute_sizes: Option<bool>,
+) -> Result<ManagementData<PluginInfo>, String> {
+    let mut data = tokio::task::spawn_blocking(move || {
         let xplane_path = std::path::Path::new(&xplane_path);
-        management_index::scan_plugins(xplane_path)
+        management_index::scan_plugins(xplane_path, compute_sizes.unwrap_or(false))
             .map_err(|e| format!("Failed to scan plugins: {}", e))
     })
     .await
-    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    if let Err(e) = analysis_suppressions::filter_plugin_duplicates(&db.get(), &mut data).await {
+        log_debug!(
+            &format!("Failed to apply analysis suppressions: {}", e),
+            "management"
+        );
+    }
+    Ok(data)
 }
 
 #[tauri::command]
@@ -2473,6 +4335,19 @@ async fn scan_navdata(xplane_path: String) -> Result<ManagementData<NavdataManag
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
+#[tauri::command]
+async fn get_navdata_consistency_report(
+    xplane_path: String,
+) -> Result<NavdataConsistencyReport, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::get_navdata_consistency_report(xplane_path)
+            .map_err(|e| format!("Failed to build navdata consistency report: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
 #[tauri::command]
 async fn scan_navdata_backups(xplane_path: String) -> Result<Vec<NavdataBackupInfo>, String> {
     tokio::task::spawn_blocking(move || {
@@ -2498,6 +4373,62 @@ async fn restore_navdata_backup(
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
+/// Run the addon, scenery index, scenery_packs.ini and navdata checks as one
+/// resumable run, emitting `maintenance-progress` per section
+#[tauri::command]
+async fn run_full_maintenance(
+    app_handle: tauri::AppHandle,
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    sections: Option<Vec<models::MaintenanceSection>>,
+) -> error::ApiResult<models::MaintenanceReport> {
+    let event_handle = app_handle.clone();
+    let progress: maintenance::MaintenanceProgressCallback = std::sync::Arc::new(move |event| {
+        let _ = event_handle.emit(maintenance::MAINTENANCE_PROGRESS_EVENT, event);
+    });
+    maintenance::run(
+        &db.get(),
+        std::path::Path::new(&xplane_path),
+        sections,
+        false,
+        Some(progress),
+    )
+    .await
+}
+
+#[tauri::command]
+fn cancel_maintenance() -> bool {
+    maintenance::cancel()
+}
+
+#[tauri::command]
+async fn get_last_maintenance_report(
+    db: State<'_, DatabaseState>,
+) -> error::ApiResult<Option<models::MaintenanceReport>> {
+    maintenance::last_report(&db.get()).await
+}
+
+/// Look for orphaned liveries, plugins without a loadable binary, scenery
+/// without content and outdated Custom Data navdata, emitting
+/// `health-check-progress` as each category is scanned
+#[tauri::command]
+async fn run_health_check(
+    app_handle: tauri::AppHandle,
+    xplane_path: String,
+) -> error::ApiResult<models::HealthCheckReport> {
+    let xplane_path = PathBuf::from(xplane_path);
+    validate_xplane_root_path(&xplane_path).map_err(error::ApiError::validation)?;
+
+    tokio::task::spawn_blocking(move || {
+        management_index::run_health_check(&xplane_path, &|event| {
+            let _ = app_handle.emit(management_index::HEALTH_CHECK_PROGRESS_EVENT, event);
+        })
+        .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
 #[tauri::command]
 async fn toggle_management_item(
     db: State<'_, DatabaseState>,
@@ -2523,6 +4454,52 @@ async fn toggle_management_item(
     result
 }
 
+#[tauri::command]
+async fn quarantine_all_plugins(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+) -> Result<PluginQuarantineResult, String> {
+    let result = tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        plugin_quarantine::quarantine_all_plugins(xplane_path)
+            .map_err(|e| format!("Failed to quarantine plugins: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    let details = Some(format!("{} plugin(s) moved", result.moved.len()));
+    activity::log_activity(
+        &db.get(),
+        "quarantine",
+        "plugin",
+        "all plugins",
+        details,
+        result.errors.is_empty(),
+    )
+    .await;
+    Ok(result)
+}
+
+#[tauri::command]
+async fn restore_quarantined_plugins(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    folder_names: Option<Vec<String>>,
+) -> Result<PluginQuarantineResult, String> {
+    let result = tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        plugin_quarantine::restore_quarantined_plugins(xplane_path, folder_names.as_deref())
+            .map_err(|e| format!("Failed to restore quarantined plugins: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    for folder_name in &result.moved {
+        activity::log_activity(&db.get(), "restore", "plugin", folder_name, None, true).await;
+    }
+    Ok(result)
+}
+
 #[tauri::command]
 async fn toggle_aircraft_folder(
     db: State<'_, DatabaseState>,
@@ -2620,6 +4597,50 @@ async fn delete_management_item(
     result
 }
 
+/// Move an aircraft/scenery folder to another drive and leave a link at the old location.
+/// Progress is reported through `addon-update-progress` events.
+#[tauri::command]
+async fn relocate_management_item(
+    app_handle: tauri::AppHandle,
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    item_type: String,
+    folder_name: String,
+    destination_root: String,
+) -> Result<models::RelocationResult, String> {
+    let it = item_type.clone();
+    let fn_ = folder_name.clone();
+    let details = serde_json::json!({ "destination": destination_root }).to_string();
+    let event_handle = app_handle.clone();
+    let progress_callback: addon_updater::AddonUpdateProgressCallback = Arc::new(move |event| {
+        let _ = event_handle.emit("addon-update-progress", event);
+    });
+
+    let result = tokio::task::spawn_blocking(move || {
+        item_relocation::relocate_management_item(
+            std::path::Path::new(&xplane_path),
+            &item_type,
+            &folder_name,
+            std::path::Path::new(&destination_root),
+            Some(progress_callback),
+        )
+        .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?;
+
+    activity::log_activity(
+        &db.get(),
+        "relocate",
+        &it,
+        &fn_,
+        Some(details),
+        result.is_ok(),
+    )
+    .await;
+    result
+}
+
 #[tauri::command]
 async fn open_management_folder(
     xplane_path: String,
@@ -2667,6 +4688,38 @@ async fn delete_aircraft_livery(
     .to_tauri_error()
 }
 
+/// Delete several liveries at once; each livery gets its own result
+#[tauri::command]
+async fn delete_aircraft_liveries(
+    xplane_path: String,
+    aircraft_folder: String,
+    livery_folders: Vec<String>,
+) -> Result<Vec<models::LiveryDeleteResult>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::delete_aircraft_liveries(xplane_path, &aircraft_folder, &livery_folders)
+            .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+#[tauri::command]
+async fn get_liveries_disk_usage(
+    xplane_path: String,
+    aircraft_folder: String,
+) -> Result<Vec<models::LiveryDiskUsage>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        management_index::get_liveries_disk_usage(xplane_path, &aircraft_folder)
+            .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
 #[tauri::command]
 async fn open_livery_folder(
     xplane_path: String,
@@ -2760,6 +4813,51 @@ async fn delete_lua_script(
     result
 }
 
+#[tauri::command]
+async fn find_duplicate_lua_scripts(
+    xplane_path: String,
+) -> Result<Vec<models::LuaDuplicateGroup>, String> {
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        lua_duplicates::find_duplicate_lua_scripts(xplane_path).map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error()
+}
+
+#[tauri::command]
+async fn resolve_lua_duplicate(
+    db: State<'_, DatabaseState>,
+    xplane_path: String,
+    group_id: String,
+    keep: String,
+) -> Result<models::LuaDuplicateResolution, String> {
+    let group_id_ = group_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        lua_duplicates::resolve_lua_duplicate(xplane_path, &group_id, &keep)
+            .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .to_tauri_error();
+
+    activity::log_activity(
+        &db.get(),
+        "resolve_duplicate",
+        "lua_script",
+        &group_id_,
+        result
+            .as_ref()
+            .ok()
+            .and_then(|resolution| serde_json::to_string(resolution).ok()),
+        result.is_ok(),
+    )
+    .await;
+    result
+}
+
 #[tauri::command]
 async fn list_screenshot_media(xplane_path: String) -> Result<Vec<ScreenshotMediaItem>, String> {
     tokio::task::spawn_blocking(move || {
@@ -3006,13 +5104,13 @@ async fn build_preset_snapshot(
         let mut plugins = HashMap::new();
         let mut lua_scripts = HashMap::new();
 
-        if let Ok(data) = management_index::scan_aircraft(xplane_path) {
+        if let Ok(data) = management_index::scan_aircraft(xplane_path, false) {
             for aircraft_info in data.entries {
                 aircraft.insert(aircraft_info.folder_name, aircraft_info.enabled);
             }
         }
 
-        if let Ok(data) = management_index::scan_plugins(xplane_path) {
+        if let Ok(data) = management_index::scan_plugins(xplane_path, false) {
             for plugin_info in data.entries {
                 plugins.insert(plugin_info.folder_name, plugin_info.enabled);
             }
@@ -3213,7 +5311,7 @@ async fn apply_preset(
             let mut missing_items = Vec::new();
 
             // Apply aircraft state
-            match management_index::scan_aircraft(xplane_path) {
+            match management_index::scan_aircraft(xplane_path, false) {
                 Ok(data) => {
                     for aircraft in &data.entries {
                         if let Some(&desired) =
@@ -3247,7 +5345,7 @@ async fn apply_preset(
             }
 
             // Apply plugins state
-            match management_index::scan_plugins(xplane_path) {
+            match management_index::scan_plugins(xplane_path, false) {
                 Ok(data) => {
                     for plugin in &data.entries {
                         if let Some(&desired) = blocking_snapshot.plugins.get(&plugin.folder_name) {
@@ -3334,6 +5432,7 @@ async fn apply_preset(
                                 folder_name: entry.folder_name.clone(),
                                 enabled: desired,
                                 sort_order: entry.sort_order,
+                                pinned: None,
                             });
                         }
                     }
@@ -3403,12 +5502,17 @@ async fn export_preset(
     let snapshot: PresetSnapshot = serde_json::from_str(&row.snapshot)
         .map_err(|e| format!("Failed to parse snapshot: {}", e))?;
 
+    let launch_profiles = launch_profiles::list_profiles(&conn)
+        .await
+        .map_err(|e| format!("Failed to load launch profiles: {}", e))?;
+
     let export = PresetExportFormat {
         version: 1,
         name: row.name,
         description: row.description,
         created_at: row.created_at,
         snapshot,
+        launch_profiles,
     };
 
     let json = serde_json::to_string_pretty(&export)
@@ -3448,9 +5552,47 @@ async fn import_preset(db: State<'_, DatabaseState>, import_path: String) -> Res
         .insert(&conn)
         .await
         .map_err(|e| format!("Failed to import preset: {}", e))?;
+
+    launch_profiles::import_profiles(&conn, import.launch_profiles)
+        .await
+        .map_err(|e| format!("Failed to import launch profiles: {}", e))?;
     Ok(())
 }
 
+// ============================================================================
+// Launch Profile Commands
+// ============================================================================
+
+#[tauri::command]
+async fn save_launch_profile(
+    db: State<'_, DatabaseState>,
+    name: String,
+    args: Vec<String>,
+) -> Result<LaunchProfileSaveResult, String> {
+    launch_profiles::save_profile(&db.get(), &name, args)
+        .await
+        .map_err(|e| format!("Failed to save launch profile: {}", e))
+}
+
+#[tauri::command]
+async fn list_launch_profiles(db: State<'_, DatabaseState>) -> Result<Vec<LaunchProfile>, String> {
+    launch_profiles::list_profiles(&db.get())
+        .await
+        .map_err(|e| format!("Failed to list launch profiles: {}", e))
+}
+
+#[tauri::command]
+async fn delete_launch_profile(db: State<'_, DatabaseState>, name: String) -> Result<bool, String> {
+    launch_profiles::delete_profile(&db.get(), &name)
+        .await
+        .map_err(|e| format!("Failed to delete launch profile: {}", e))
+}
+
+#[tauri::command]
+fn list_known_launch_flags() -> Vec<launch_profiles::KnownLaunchFlag> {
+    launch_profiles::known_flags().to_vec()
+}
+
 // ============================================================================
 // Disk Usage Commands
 // ============================================================================
@@ -3481,6 +5623,65 @@ async fn scan_folder_disk_usage(
     .map_err(|e| format!("Task join error: {}", e))?
 }
 
+/// Find files with identical contents under the given X-Plane folders
+/// (`Custom Scenery` and `Aircraft` when empty). Cancel with
+/// `cancel_installation`.
+#[tauri::command]
+async fn find_duplicate_files(
+    task_control: State<'_, TaskControl>,
+    xplane_path: String,
+    roots: Vec<String>,
+    min_file_size: u64,
+) -> error::ApiResult<models::DuplicateFilesReport> {
+    task_control.reset();
+    let task_control = task_control.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        validate_xplane_root_path(xplane_path).map_err(error::ApiError::validation)?;
+        hash_collector::find_duplicate_files(xplane_path, &roots, min_file_size, &task_control)
+            .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// Replace duplicate files with hardlinks to the first file of their group
+#[tauri::command]
+async fn deduplicate_with_hardlinks(
+    db: State<'_, DatabaseState>,
+    task_control: State<'_, TaskControl>,
+    xplane_path: String,
+    groups: Vec<models::DuplicateFileGroup>,
+) -> error::ApiResult<models::HardlinkDedupResult> {
+    task_control.reset();
+    let task_control = task_control.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let xplane_path = std::path::Path::new(&xplane_path);
+        validate_xplane_root_path(xplane_path).map_err(error::ApiError::validation)?;
+        hash_collector::deduplicate_with_hardlinks(xplane_path, &groups, &task_control)
+            .map_err(error::ApiError::from)
+    })
+    .await
+    .map_err(|e| error::ApiError::internal(format!("Task join error: {}", e)))?;
+
+    activity::log_activity(
+        &db.get(),
+        "deduplicate",
+        "files",
+        &result
+            .as_ref()
+            .map(|result| format!("{} files", result.linked.len()))
+            .unwrap_or_default(),
+        result
+            .as_ref()
+            .ok()
+            .and_then(|result| serde_json::to_string(result).ok()),
+        result.is_ok(),
+    )
+    .await;
+    result
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -3525,26 +5726,68 @@ pub fn run() {
             create_library_link_issue,
             create_bug_report_issue,
             create_feedback_issue,
+            get_pending_submissions,
+            send_pending_submissions,
             post_issue_comment,
             check_issue_updates,
             get_issue_detail,
             analyze_addons,
+            build_install_tasks,
+            download_and_analyze,
+            discard_url_download,
+            enqueue_downloads,
+            get_download_queue,
+            cancel_download,
+            retry_download,
+            invalidate_archive_cache,
             install_addons,
             cancel_installation,
             skip_current_task,
             register_context_menu,
             unregister_context_menu,
             is_context_menu_registered,
+            set_context_menu_options,
             sync_context_menu_paths,
             log_from_frontend,
             get_recent_logs,
             get_log_path,
             get_all_logs,
+            export_support_bundle,
+            backup_xplane_preferences,
+            list_xplane_preference_backups,
+            restore_xplane_preferences,
+            delete_xplane_preference_backup,
+            list_addon_backups,
+            restore_addon_backup,
+            get_backend_settings,
+            save_backend_settings,
+            get_network_settings,
+            set_network_settings,
+            test_network_settings,
+            get_staging_config,
+            set_staging_config,
+            get_livery_patterns,
+            add_livery_pattern,
+            remove_livery_pattern,
+            get_stored_data_summary,
+            erase_app_data,
             analyze_xplane_log,
+            record_xplane_log_snapshot,
+            get_xplane_log_history,
+            add_analysis_suppression,
+            remove_analysis_suppression,
+            list_analysis_suppressions,
             analyze_crash_report,
+            get_xplane_version,
+            list_known_xplane_installs,
+            diagnose_xplane_location,
             open_log_folder,
+            reveal_in_explorer,
+            reveal_log_file,
             open_scenery_folder,
+            preview_scenery_deletion,
             delete_scenery_folder,
+            create_scenery_shortcut,
             set_log_locale,
             set_log_level,
             check_path_exists,
@@ -3553,19 +5796,30 @@ pub fn run() {
             validate_xplane_path,
             check_for_updates,
             get_last_check_time,
+            download_update,
+            cancel_update_download,
+            install_downloaded_update,
             // Library download links
             lookup_library_links,
             lookup_library_links_remote,
+            build_missing_library_plan,
             // Scenery auto-sorting commands
             get_scenery_classification,
             sort_scenery_packs,
             rebuild_scenery_index,
             reset_scenery_database,
             check_database_compatibility,
+            run_database_maintenance,
             reset_and_reinitialize,
             get_scenery_index_stats,
+            get_scenery_stats_history,
+            get_library_dependents,
+            get_package_dependencies,
+            search_airports,
             get_scenery_index_status,
             quick_scan_scenery_index,
+            start_scenery_watcher,
+            stop_scenery_watcher,
             sync_scenery_packs_with_folder,
             // Map commands
             map::map_prepare_data_index,
@@ -3605,36 +5859,65 @@ pub fn run() {
             gateway::gateway_uninstall_airport,
             // Scenery manager commands
             get_scenery_manager_data,
+            get_scenery_manager_data_paged,
+            get_scenery_manager_summary,
             update_scenery_entry,
             move_scenery_entry,
             apply_scenery_changes,
+            validate_scenery_order,
+            preview_scenery_packs_changes,
+            validate_scenery_packs_ini,
+            repair_scenery_packs_ini,
+            import_scenery_from_external,
             // Management commands
             scan_aircraft,
             get_aircraft_folder_state,
             check_aircraft_updates,
             scan_plugins,
             check_plugins_updates,
+            check_all_addon_updates,
+            cancel_addon_update_check,
+            start_addon_update_scheduler,
+            get_addon_icon,
+            clear_icon_cache,
             build_addon_update_plan,
+            get_addon_update_diff,
             fetch_addon_update_preview,
+            get_addon_update_channel,
+            set_addon_update_channel,
             execute_addon_update,
             set_addon_updater_credentials,
+            discover_existing_updater_credentials,
             get_addon_updater_credentials,
+            validate_updater_profile,
             get_addon_update_disk_space,
             scan_navdata,
             scan_navdata_backups,
+            get_navdata_consistency_report,
             restore_navdata_backup,
+            run_full_maintenance,
+            cancel_maintenance,
+            get_last_maintenance_report,
+            run_health_check,
             toggle_management_item,
+            quarantine_all_plugins,
+            restore_quarantined_plugins,
             toggle_aircraft_folder,
             toggle_aircraft_acf_file,
             delete_management_item,
+            relocate_management_item,
             open_management_folder,
             get_aircraft_liveries,
             delete_aircraft_livery,
+            delete_aircraft_liveries,
+            get_liveries_disk_usage,
             open_livery_folder,
             set_cfg_disabled,
             get_lua_scripts,
             toggle_lua_script,
             delete_lua_script,
+            find_duplicate_lua_scripts,
+            resolve_lua_duplicate,
             list_screenshot_media,
             delete_screenshot_media,
             save_screenshot_media_as,
@@ -3646,6 +5929,13 @@ pub fn run() {
             build_reddit_share_url,
             // Activity log commands
             get_activity_log,
+            get_install_history,
+            get_install_profile,
+            clear_install_profile,
+            get_orphaned_installs,
+            resolve_orphaned_install,
+            uninstall_from_history,
+            respond_duplicate_install,
             clear_activity_log,
             // Preset commands
             list_presets,
@@ -3655,9 +5945,16 @@ pub fn run() {
             apply_preset,
             export_preset,
             import_preset,
+            // Launch profile commands
+            save_launch_profile,
+            list_launch_profiles,
+            delete_launch_profile,
+            list_known_launch_flags,
             // Disk usage commands
             scan_disk_usage,
             scan_folder_disk_usage,
+            find_duplicate_files,
+            deduplicate_with_hardlinks,
             // CSL management commands
             csl_index::csl_fetch_package_descriptions,
             csl_index::csl_scan_packages,
@@ -3675,7 +5972,11 @@ pub fn run() {
         .setup(|app| {
             // Initialize TaskControl state
             app.manage(TaskControl::new());
+            app.manage(duplicate_prompt::DuplicatePrompts::new());
+            app.manage(updater::UpdateDownloadControl::new());
             app.manage(csl_index::CslDownloadControl::new());
+            app.manage(scenery_watcher::SceneryWatcherState::new());
+            app.manage(download_queue::DownloadQueue::new());
 
             // Initialize database connection and run migrations once on startup
             let db = tauri::async_runtime::block_on(async {
@@ -3687,6 +5988,18 @@ pub fn run() {
 
             app.manage(DatabaseState::new(db));
 
+            // Check and compact the database in the background
+            let maintenance_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let db = maintenance_handle.state::<DatabaseState>();
+                if let Err(e) = db.run_maintenance().await {
+                    logger::log_error(
+                        &format!("Database maintenance failed: {}", e),
+                        Some("database"),
+                    );
+                }
+            });
+
             // Log application startup
             logger::log_info(&logger::tr(logger::LogMsg::AppStarted), Some("app"));
 
@@ -3705,12 +6018,40 @@ pub fn run() {
                 app.emit("cli-args", args.clone()).ok();
             }
 
+            settings_store::init(app.handle().clone());
+
+            // Remove staging folders left behind by an earlier crash
+            tauri::async_runtime::spawn_blocking(staging::cleanup_stale_staging);
+
+            // Reload persisted archive metadata, dropping entries for changed archives
+            cache::init(app.handle().clone());
+
+            // Retry bug reports/feedback queued by a previous session
+            submission_queue::init(app.handle().clone());
+
+            // Resume downloads queued by a previous session
+            download_queue::init(app.handle().clone());
+
+            // Clean up after installs a previous session didn't finish
+            install_markers::init(app.handle().clone());
+
+            // Write scenery_packs.ini changes deferred until X-Plane closed
+            deferred_scenery::init(app.handle().clone());
+
+            // Weekly full maintenance run while idle
+            maintenance::init(app.handle().clone());
+
             // Fetch latest livery patterns on startup (non-blocking)
             tauri::async_runtime::spawn(async {
                 livery_patterns::ensure_patterns_loaded().await;
             });
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                app.state::<scenery_watcher::SceneryWatcherState>().stop();
+            }
+        });
 }
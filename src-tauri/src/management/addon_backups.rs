@@ -0,0 +1,742 @@
+//! Versions of an addon kept from before its updates
+//!
+//! With `keep_backup_versions` set, an update copies the files it is about to
+//! replace or delete into `<addon>/<timestamp>/files` below
+//! [`app_dirs::get_addon_backups_dir`] before touching the addon; Zibo
+//! updates, which replace the aircraft as a whole, keep every file. Once the
+//! update succeeded, `manifest.json` records every changed file with the hash
+//! of its old and new content, and older backups of the addon beyond the
+//! number to keep are removed. An update that failed and was rolled back
+//! leaves no backup behind; one that failed without a rollback keeps it, as
+//! the only copy of the files it had replaced.
+//!
+//! Restoring puts the old files back and removes the ones the update added.
+//! Files changed since the update (by the user or a later update) are listed
+//! instead of overwritten, unless the restore is forced.
+
+use crate::app_dirs;
+use crate::logger;
+use crate::models::{AddonBackup, AddonBackupRestoreResult};
+use anyhow::{anyhow, Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const FILES_DIR: &str = "files";
+/// Suffix of a backup whose update hasn't finished yet
+const PARTIAL_SUFFIX: &str = ".part";
+/// Age after which a partial backup is taken for the leftover of an update
+/// that never finished
+const STALE_PARTIAL_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+const LOG_CTX: &str = "addon_backups";
+
+/// What an update did to a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileChange {
+    Added,
+    Replaced,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupFile {
+    /// Path in the addon folder, `/`-separated
+    path: String,
+    change: FileChange,
+    /// SHA-256 of the content kept in the backup (replaced and deleted files)
+    original_sha256: Option<String>,
+    /// SHA-256 of what the update wrote (added and replaced files)
+    updated_sha256: Option<String>,
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    item_type: String,
+    folder_name: String,
+    target_path: String,
+    from_version: Option<String>,
+    to_version: Option<String>,
+    created_at: i64,
+    total_bytes: u64,
+    files: Vec<BackupFile>,
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Folder name of an addon's backups: item type and folder name, with
+/// anything but letters, digits, `-`, `_` and `.` replaced
+fn addon_dir_name(item_type: &str, folder_name: &str) -> String {
+    format!("{}_{}", item_type, folder_name)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+/// Folder of a backup id, refusing ids that are not `<addon>/<timestamp>`
+fn backup_dir(backups_dir: &Path, backup_id: &str) -> Result<PathBuf> {
+    let parts: Vec<&str> = backup_id.split('/').collect();
+    let valid = parts.len() == 2
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && !part.starts_with('.')
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+    if !valid {
+        return Err(anyhow!("Invalid backup id: {}", backup_id));
+    }
+    Ok(backups_dir.join(parts[0]).join(parts[1]))
+}
+
+/// A `/`-separated path from a manifest, refused when it could leave the
+/// addon folder
+fn relative_path(path: &str) -> Result<PathBuf> {
+    let relative = PathBuf::from(path.replace('\\', "/"));
+    if path.is_empty()
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(anyhow!("Invalid path in backup manifest: {}", path));
+    }
+    Ok(relative)
+}
+
+/// Files below `dir`, as paths relative to `root`
+fn files_below(root: &Path, dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(dir).follow_links(false) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            files.push(
+                entry
+                    .path()
+                    .strip_prefix(root)?
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+            );
+        }
+    }
+    Ok(files)
+}
+
+/// A backup taken before an update, completed once the update succeeded or
+/// failed without a rollback. Dropped without [`PendingBackup::finish`], e.g.
+/// when the update failed and was rolled back, the copied files are removed
+/// again.
+pub struct PendingBackup {
+    backups_dir: PathBuf,
+    /// `<addon>/<timestamp>.part` while the update runs
+    partial_dir: PathBuf,
+    id: String,
+    target_path: PathBuf,
+    manifest: BackupManifest,
+    /// Every file of the addon was kept, so files found after the update that
+    /// aren't in the backup were added by it
+    whole_folder: bool,
+    finished: bool,
+}
+
+/// Copy the files an update will replace or delete before it runs. `changes`
+/// lists paths relative to `target_path`; deleted folders are kept file by file.
+pub fn begin(
+    item_type: &str,
+    folder_name: &str,
+    target_path: &Path,
+    changes: &[(String, FileChange)],
+    from_version: Option<String>,
+) -> Result<PendingBackup> {
+    begin_in(
+        &app_dirs::get_addon_backups_dir(),
+        item_type,
+        folder_name,
+        target_path,
+        changes,
+        from_version,
+    )
+}
+
+/// Copy every file of the addon, for updates that replace it as a whole
+pub fn begin_whole_folder(
+    item_type: &str,
+    folder_name: &str,
+    target_path: &Path,
+    from_version: Option<String>,
+) -> Result<PendingBackup> {
+    begin_whole_folder_in(
+        &app_dirs::get_addon_backups_dir(),
+        item_type,
+        folder_name,
+        target_path,
+        from_version,
+    )
+}
+
+fn begin_whole_folder_in(
+    backups_dir: &Path,
+    item_type: &str,
+    folder_name: &str,
+    target_path: &Path,
+    from_version: Option<String>,
+) -> Result<PendingBackup> {
+    let changes: Vec<(String, FileChange)> = files_below(target_path, target_path)?
+        .into_iter()
+        .map(|path| (path, FileChange::Replaced))
+        .collect();
+    let mut pending = begin_in(
+        backups_dir,
+        item_type,
+        folder_name,
+        target_path,
+        &changes,
+        from_version,
+    )?;
+    pending.whole_folder = true;
+    Ok(pending)
+}
+
+fn begin_in(
+    backups_dir: &Path,
+    item_type: &str,
+    folder_name: &str,
+    target_path: &Path,
+    changes: &[(String, FileChange)],
+    from_version: Option<String>,
+) -> Result<PendingBackup> {
+    let addon_dir = addon_dir_name(item_type, folder_name);
+    let stamp = Local::now().format("%Y%m%d-%H%M%S-%3f").to_string();
+    let id = format!("{}/{}", addon_dir, stamp);
+    let partial_dir = backups_dir
+        .join(&addon_dir)
+        .join(format!("{}{}", stamp, PARTIAL_SUFFIX));
+    let files_dir = partial_dir.join(FILES_DIR);
+    fs::create_dir_all(&files_dir)
+        .with_context(|| format!("Failed to create {}", files_dir.display()))?;
+
+    let mut pending = PendingBackup {
+        backups_dir: backups_dir.to_path_buf(),
+        partial_dir,
+        id,
+        target_path: target_path.to_path_buf(),
+        manifest: BackupManifest {
+            item_type: item_type.to_string(),
+            folder_name: folder_name.to_string(),
+            target_path: target_path.to_string_lossy().into_owned(),
+            from_version,
+            to_version: None,
+            created_at: now_secs(),
+            total_bytes: 0,
+            files: Vec::new(),
+        },
+        whole_folder: false,
+        finished: false,
+    };
+    pending.copy_originals(changes)?;
+    Ok(pending)
+}
+
+impl PendingBackup {
+    fn copy_originals(&mut self, changes: &[(String, FileChange)]) -> Result<()> {
+        for (path, change) in changes {
+            let source = self.target_path.join(relative_path(path)?);
+            // A file planned as replaced that doesn't exist yet is added
+            let change = match change {
+                FileChange::Replaced if !source.exists() => FileChange::Added,
+                other => *other,
+            };
+            if change == FileChange::Deleted && source.is_dir() {
+                for file in files_below(&self.target_path, &source)? {
+                    self.copy_original(&file, FileChange::Deleted)?;
+                }
+            } else if change == FileChange::Added || source.is_file() {
+                self.copy_original(path, change)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn copy_original(&mut self, path: &str, change: FileChange) -> Result<()> {
+        let relative = relative_path(path)?;
+        let original_sha256 = if change == FileChange::Added {
+            None
+        } else {
+            let source = self.target_path.join(&relative);
+            let destination = self.partial_dir.join(FILES_DIR).join(&relative);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let bytes = fs::copy(&source, &destination)
+                .with_context(|| format!("Failed to back up {}", source.display()))?;
+            self.manifest.total_bytes = self.manifest.total_bytes.saturating_add(bytes);
+            Some(sha256_file(&destination)?)
+        };
+        self.manifest.files.push(BackupFile {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            change,
+            original_sha256,
+            updated_sha256: None,
+        });
+        Ok(())
+    }
+
+    /// Record what the update wrote, keep the backup and remove the oldest
+    /// backups of the addon beyond `keep`. Also used when the update failed
+    /// without a rollback, with no `to_version`: files it didn't get to are
+    /// recorded as they are.
+    pub fn finish(mut self, to_version: Option<String>, keep: usize) -> Result<AddonBackup> {
+        self.manifest.to_version = to_version;
+        if self.whole_folder {
+            let known: HashSet<String> = self
+                .manifest
+                .files
+                .iter()
+                .map(|file| file.path.clone())
+                .collect();
+            for path in files_below(&self.target_path, &self.target_path)? {
+                if !known.contains(&path) {
+                    self.copy_original(&path, FileChange::Added)?;
+                }
+            }
+        }
+        // A file the update stopped before deleting has nothing to put back
+        let target_path = self.target_path.clone();
+        self.manifest.files.retain(|file| {
+            file.change != FileChange::Deleted
+                || relative_path(&file.path).map_or(true, |path| !target_path.join(path).exists())
+        });
+        for file in &mut self.manifest.files {
+            if file.change != FileChange::Deleted {
+                let path = self.target_path.join(relative_path(&file.path)?);
+                file.updated_sha256 = path.is_file().then(|| sha256_file(&path)).transpose()?;
+            }
+        }
+        let manifest_path = self.partial_dir.join(MANIFEST_FILE);
+        fs::write(
+            &manifest_path,
+            serde_json::to_string_pretty(&self.manifest)?,
+        )?;
+        let final_dir = backup_dir(&self.backups_dir, &self.id)?;
+        fs::rename(&self.partial_dir, &final_dir)?;
+        self.finished = true;
+
+        logger::log_info(
+            &format!(
+                "Kept {} files of {} from before the update as {}",
+                self.manifest.files.len(),
+                self.manifest.folder_name,
+                self.id
+            ),
+            Some(LOG_CTX),
+        );
+        prune(
+            &self.backups_dir,
+            &self.manifest.item_type,
+            &self.manifest.folder_name,
+            keep,
+        );
+        Ok(to_backup(&self.id, self.manifest.clone()))
+    }
+}
+
+impl Drop for PendingBackup {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        if let Err(e) = fs::remove_dir_all(&self.partial_dir) {
+            if e.kind() != io::ErrorKind::NotFound {
+                logger::log_error(
+                    &format!(
+                        "Failed to remove unused backup {}: {}",
+                        self.partial_dir.display(),
+                        e
+                    ),
+                    Some(LOG_CTX),
+                );
+            }
+        }
+    }
+}
+
+fn to_backup(id: &str, manifest: BackupManifest) -> AddonBackup {
+    AddonBackup {
+        id: id.to_string(),
+        item_type: manifest.item_type,
+        folder_name: manifest.folder_name,
+        from_version: manifest.from_version,
+        to_version: manifest.to_version,
+        created_at: manifest.created_at,
+        file_count: manifest.files.len(),
+        total_bytes: manifest.total_bytes,
+    }
+}
+
+fn read_manifest(dir: &Path) -> Result<BackupManifest> {
+    let content = fs::read_to_string(dir.join(MANIFEST_FILE))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Remove the oldest backups of an addon beyond `keep`, and leftovers of
+/// updates that never finished: partial backups older than
+/// [`STALE_PARTIAL_AGE`]
+fn prune(backups_dir: &Path, item_type: &str, folder_name: &str, keep: usize) {
+    let addon_dir = backups_dir.join(addon_dir_name(item_type, folder_name));
+    let Ok(entries) = fs::read_dir(&addon_dir) else {
+        return;
+    };
+    let mut stamps: Vec<String> = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !name.ends_with(PARTIAL_SUFFIX) {
+            stamps.push(name);
+            continue;
+        }
+        // Recent ones may belong to an update still running
+        let stale = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= STALE_PARTIAL_AGE);
+        if stale {
+            if let Err(e) = fs::remove_dir_all(entry.path()) {
+                logger::log_error(
+                    &format!("Failed to remove unfinished addon backup {}: {}", name, e),
+                    Some(LOG_CTX),
+                );
+            }
+        }
+    }
+    // Timestamps sort by age; newest first
+    stamps.sort_by(|a, b| b.cmp(a));
+    for stamp in stamps.iter().skip(keep) {
+        if let Err(e) = fs::remove_dir_all(addon_dir.join(stamp)) {
+            logger::log_error(
+                &format!("Failed to remove old addon backup {}: {}", stamp, e),
+                Some(LOG_CTX),
+            );
+        }
+    }
+}
+
+/// Backups of an addon, newest first
+pub fn list_backups(item_type: &str, folder_name: &str) -> Result<Vec<AddonBackup>> {
+    list_backups_in(&app_dirs::get_addon_backups_dir(), item_type, folder_name)
+}
+
+fn list_backups_in(
+    backups_dir: &Path,
+    item_type: &str,
+    folder_name: &str,
+) -> Result<Vec<AddonBackup>> {
+    let addon_dir_name = addon_dir_name(item_type, folder_name);
+    let entries = match fs::read_dir(backups_dir.join(&addon_dir_name)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries.flatten() {
+        let stamp = entry.file_name().to_string_lossy().into_owned();
+        if stamp.ends_with(PARTIAL_SUFFIX) || !entry.path().is_dir() {
+            continue;
+        }
+        let id = format!("{}/{}", addon_dir_name, stamp);
+        match read_manifest(&entry.path()) {
+            Ok(manifest) => backups.push(to_backup(&id, manifest)),
+            Err(e) => logger::log_error(
+                &format!("Skipping unreadable addon backup {}: {}", id, e),
+                Some(LOG_CTX),
+            ),
+        }
+    }
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    Ok(backups)
+}
+
+/// Undo the update a backup was taken for. Nothing is changed when files
+/// were modified since the update, unless `force` is set.
+pub fn restore_backup(backup_id: &str, force: bool) -> Result<AddonBackupRestoreResult> {
+    restore_backup_from(&app_dirs::get_addon_backups_dir(), backup_id, force)
+}
+
+fn restore_backup_from(
+    backups_dir: &Path,
+    backup_id: &str,
+    force: bool,
+) -> Result<AddonBackupRestoreResult> {
+    let dir = backup_dir(backups_dir, backup_id)?;
+    if !dir.is_dir() {
+        return Err(anyhow!("Backup not found: {}", backup_id));
+    }
+    let manifest =
+        read_manifest(&dir).with_context(|| format!("Failed to read backup {}", backup_id))?;
+    let target_path = PathBuf::from(&manifest.target_path);
+    if !target_path.is_dir() {
+        return Err(anyhow!(
+            "Addon folder of the backup no longer exists: {}",
+            manifest.target_path
+        ));
+    }
+
+    let mut modified_files = Vec::new();
+    for file in &manifest.files {
+        let current = target_path.join(relative_path(&file.path)?);
+        let unchanged = match file.change {
+            FileChange::Deleted => !current.exists(),
+            FileChange::Added | FileChange::Replaced => match &file.updated_sha256 {
+                Some(expected) => current.is_file() && sha256_file(&current)? == *expected,
+                None => !current.exists(),
+            },
+        };
+        if !unchanged {
+            modified_files.push(file.path.clone());
+        }
+    }
+    if !modified_files.is_empty() {
+        logger::log_info(
+            &format!(
+                "{} files of {} changed since backup {} was taken{}",
+                modified_files.len(),
+                manifest.folder_name,
+                backup_id,
+                if force { ", restoring anyway" } else { "" }
+            ),
+            Some(LOG_CTX),
+        );
+        if !force {
+            return Ok(AddonBackupRestoreResult {
+                backup: to_backup(backup_id, manifest),
+                restored: false,
+                modified_files,
+                restored_files: 0,
+                removed_files: 0,
+            });
+        }
+    }
+
+    let mut restored_files = 0usize;
+    let mut removed_files = 0usize;
+    for file in &manifest.files {
+        let relative = relative_path(&file.path)?;
+        let current = target_path.join(&relative);
+        match file.change {
+            FileChange::Added => match fs::remove_file(&current) {
+                Ok(()) => removed_files += 1,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(anyhow!("Failed to remove {}: {}", current.display(), e)),
+            },
+            FileChange::Replaced | FileChange::Deleted => {
+                if let Some(parent) = current.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(dir.join(FILES_DIR).join(&relative), &current)
+                    .with_context(|| format!("Failed to restore {}", current.display()))?;
+                restored_files += 1;
+            }
+        }
+    }
+
+    logger::log_info(
+        &format!(
+            "Restored {} from backup {}: {} files put back, {} removed",
+            manifest.folder_name, backup_id, restored_files, removed_files
+        ),
+        Some(LOG_CTX),
+    );
+    // The addon is back to the version before the update; the backup is used up
+    if let Err(e) = fs::remove_dir_all(&dir) {
+        logger::log_error(
+            &format!("Failed to remove restored backup {}: {}", backup_id, e),
+            Some(LOG_CTX),
+        );
+    }
+    Ok(AddonBackupRestoreResult {
+        backup: to_backup(backup_id, manifest),
+        restored: true,
+        modified_files,
+        restored_files,
+        removed_files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(backups: &Path, addon: &Path, keep: usize) -> AddonBackup {
+        let pending = begin_in(
+            backups,
+            "aircraft",
+            "Test Jet",
+            addon,
+            &[
+                ("plane.acf".to_string(), FileChange::Replaced),
+                ("old.txt".to_string(), FileChange::Deleted),
+                ("new.txt".to_string(), FileChange::Added),
+            ],
+            Some("1.0".to_string()),
+        )
+        .unwrap();
+        fs::write(addon.join("plane.acf"), "v2").unwrap();
+        fs::remove_file(addon.join("old.txt")).unwrap();
+        fs::write(addon.join("new.txt"), "added").unwrap();
+        pending.finish(Some("2.0".to_string()), keep).unwrap()
+    }
+
+    fn reset_addon(addon: &Path) {
+        let _ = fs::remove_dir_all(addon);
+        fs::create_dir_all(addon).unwrap();
+        fs::write(addon.join("plane.acf"), "v1").unwrap();
+        fs::write(addon.join("old.txt"), "gone in v2").unwrap();
+    }
+
+    #[test]
+    fn restore_undoes_the_update_unless_files_changed_since() {
+        let temp = tempfile::tempdir().unwrap();
+        let backups = temp.path().join("backups");
+        let addon = temp.path().join("Test Jet");
+        reset_addon(&addon);
+
+        let backup = snapshot(&backups, &addon, 3);
+        assert_eq!(backup.file_count, 3);
+        assert_eq!(backup.from_version.as_deref(), Some("1.0"));
+        assert_eq!(
+            list_backups_in(&backups, "aircraft", "Test Jet").unwrap()[0].id,
+            backup.id
+        );
+
+        // Edited after the update: listed, not overwritten
+        fs::write(addon.join("plane.acf"), "tuned by the user").unwrap();
+        let refused = restore_backup_from(&backups, &backup.id, false).unwrap();
+        assert!(!refused.restored);
+        assert_eq!(refused.modified_files, vec!["plane.acf".to_string()]);
+        assert_eq!(
+            fs::read_to_string(addon.join("plane.acf")).unwrap(),
+            "tuned by the user"
+        );
+
+        let restored = restore_backup_from(&backups, &backup.id, true).unwrap();
+        assert!(restored.restored);
+        assert_eq!(restored.restored_files, 2);
+        assert_eq!(restored.removed_files, 1);
+        assert_eq!(fs::read_to_string(addon.join("plane.acf")).unwrap(), "v1");
+        assert_eq!(
+            fs::read_to_string(addon.join("old.txt")).unwrap(),
+            "gone in v2"
+        );
+        assert!(!addon.join("new.txt").exists());
+        assert!(list_backups_in(&backups, "aircraft", "Test Jet")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn only_the_newest_backups_are_kept() {
+        let temp = tempfile::tempdir().unwrap();
+        let backups = temp.path().join("backups");
+        let addon = temp.path().join("Test Jet");
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            reset_addon(&addon);
+            ids.push(snapshot(&backups, &addon, 2).id);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        let listed: Vec<String> = list_backups_in(&backups, "aircraft", "Test Jet")
+            .unwrap()
+            .into_iter()
+            .map(|backup| backup.id)
+            .collect();
+        assert_eq!(listed, vec![ids[2].clone(), ids[1].clone()]);
+    }
+
+    #[test]
+    fn backup_ids_cannot_leave_the_backups_folder() {
+        let backups = Path::new("/backups");
+        assert!(backup_dir(backups, "aircraft_Test_Jet/20261016-101500-000").is_ok());
+        assert!(backup_dir(backups, "../aircraft/20261016").is_err());
+        assert!(backup_dir(backups, "aircraft_Test_Jet/../../x").is_err());
+        assert!(backup_dir(backups, "aircraft_Test_Jet").is_err());
+        assert!(relative_path("../plane.acf").is_err());
+        assert!(relative_path("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn whole_folder_backups_record_the_files_the_update_added() {
+        let temp = tempfile::tempdir().unwrap();
+        let backups = temp.path().join("backups");
+        let addon = temp.path().join("Test Jet");
+        reset_addon(&addon);
+
+        let pending = begin_whole_folder_in(
+            &backups,
+            "aircraft",
+            "Test Jet",
+            &addon,
+            Some("1.0".to_string()),
+        )
+        .unwrap();
+        fs::write(addon.join("plane.acf"), "v2").unwrap();
+        fs::write(addon.join("new.txt"), "added").unwrap();
+        let backup = pending.finish(Some("2.0".to_string()), 3).unwrap();
+        assert_eq!(backup.file_count, 3);
+
+        let restored = restore_backup_from(&backups, &backup.id, false).unwrap();
+        assert!(restored.restored);
+        assert_eq!(fs::read_to_string(addon.join("plane.acf")).unwrap(), "v1");
+        assert!(!addon.join("new.txt").exists());
+    }
+
+    #[test]
+    fn pruning_removes_stale_partial_backups() {
+        let temp = tempfile::tempdir().unwrap();
+        let backups = temp.path().join("backups");
+        let addon_dir = backups.join(addon_dir_name("aircraft", "Test Jet"));
+        let stale = addon_dir.join(format!("20200101-000000-000{}", PARTIAL_SUFFIX));
+        let running = addon_dir.join(format!("20991231-000000-000{}", PARTIAL_SUFFIX));
+        fs::create_dir_all(&stale).unwrap();
+        fs::create_dir_all(&running).unwrap();
+        let old = SystemTime::now() - STALE_PARTIAL_AGE - Duration::from_secs(60);
+        File::open(&stale).unwrap().set_modified(old).unwrap();
+
+        prune(&backups, "aircraft", "Test Jet", 3);
+        assert!(!stale.exists());
+        assert!(running.exists());
+    }
+}
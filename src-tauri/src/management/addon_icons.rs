@@ -0,0 +1,581 @@
+//! Addon icon cache
+//!
+//! Update checks and x-updater previews record the icon URL an addon's updater
+//! metadata advertises. `get_icon` downloads it lazily into the app data icon
+//! cache and returns the local file for the Management UI to render.
+//!
+//! - Downloads are size-limited and must be a recognised image type whose bytes
+//!   match the declared content type; SVGs with script content are rejected.
+//! - Cached icons are revalidated daily with `If-None-Match`, so a changed ETag
+//!   re-fetches the image.
+//! - Failures are remembered for `NEGATIVE_TTL_SECS` before another attempt.
+//! - The cache is capped at `MAX_CACHE_BYTES`, evicting least recently used icons.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app_dirs;
+use crate::http_client;
+use crate::logger;
+use crate::settings_store;
+
+/// Index of known icon URLs and cached files, stored inside the cache directory
+const INDEX_FILE_NAME: &str = "index.json";
+
+/// Largest icon accepted (2 MB)
+const MAX_ICON_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Total size of cached icons before least recently used ones are evicted (50 MB)
+const MAX_CACHE_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Cached icons are revalidated with the server after a day
+const REVALIDATE_AFTER_SECS: i64 = 24 * 60 * 60;
+
+/// A failed download is not retried for 6 hours
+const NEGATIVE_TTL_SECS: i64 = 6 * 60 * 60;
+
+/// Keys that hold an icon or thumbnail URL in updater metadata
+const ICON_KEYS: [&str; 8] = [
+    "icon",
+    "iconurl",
+    "thumbnail",
+    "thumbnailurl",
+    "image",
+    "imageurl",
+    "logo",
+    "logourl",
+];
+
+/// SVG content that can run script or embed other documents
+const SVG_BLOCKED_CONTENT: [&str; 6] = [
+    "<script",
+    "javascript:",
+    "<foreignobject",
+    "<iframe",
+    "<embed",
+    "<object",
+];
+
+static SVG_EVENT_HANDLER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\s/]on[a-z]+\s*=").expect("valid SVG event handler regex"));
+
+static INDEX: LazyLock<Mutex<IconIndex>> = LazyLock::new(|| Mutex::new(load_index()));
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IconIndex {
+    /// Keyed by `icon_key(item_type, folder_name)`
+    entries: HashMap<String, IconEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct IconEntry {
+    url: String,
+    /// Cached file in the cache directory, once downloaded
+    file_name: Option<String>,
+    etag: Option<String>,
+    size: u64,
+    /// Unix seconds of the last successful download or revalidation
+    checked_at: i64,
+    last_used_at: i64,
+    /// Unix seconds of the last failed download
+    failed_at: Option<i64>,
+}
+
+enum Download {
+    NotModified,
+    Fetched {
+        body: Vec<u8>,
+        extension: &'static str,
+        etag: Option<String>,
+    },
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn icon_key(item_type: &str, folder_name: &str) -> String {
+    format!(
+        "{}/{}",
+        item_type.to_ascii_lowercase(),
+        folder_name.replace('\\', "/").to_lowercase()
+    )
+}
+
+fn cache_dir() -> PathBuf {
+    app_dirs::get_icon_cache_dir()
+}
+
+fn lock_index() -> MutexGuard<'static, IconIndex> {
+    INDEX.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn load_index() -> IconIndex {
+    let content = match fs::read_to_string(cache_dir().join(INDEX_FILE_NAME)) {
+        Ok(content) => content,
+        Err(_) => return IconIndex::default(),
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        logger::log_error(
+            &format!("Failed to parse icon cache index: {}", e),
+            Some("addon_icons"),
+        );
+        IconIndex::default()
+    })
+}
+
+fn save_index(index: &IconIndex) -> Result<(), String> {
+    let dir = cache_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create icon cache: {}", e))?;
+    let content = serde_json::to_string_pretty(index)
+        .map_err(|e| format!("Failed to serialize icon cache index: {}", e))?;
+    fs::write(dir.join(INDEX_FILE_NAME), content)
+        .map_err(|e| format!("Failed to save icon cache index: {}", e))
+}
+
+fn save_index_logged(index: &IconIndex) {
+    if let Err(e) = save_index(index) {
+        logger::log_error(&e, Some("addon_icons"));
+    }
+}
+
+/// Delete a cached file unless another entry still uses it (same icon URL)
+fn remove_file_unless_shared(index: &IconIndex, file_name: &str) {
+    let shared = index
+        .entries
+        .values()
+        .any(|entry| entry.file_name.as_deref() == Some(file_name));
+    if !shared {
+        let _ = fs::remove_file(cache_dir().join(file_name));
+    }
+}
+
+/// Remember the icon URL advertised for an addon. A changed URL drops the
+/// previously cached image.
+pub fn record_icon_url(item_type: &str, folder_name: &str, url: &str) {
+    let url = url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return;
+    }
+
+    let key = icon_key(item_type, folder_name);
+    let mut index = lock_index();
+    if index
+        .entries
+        .get(&key)
+        .is_some_and(|entry| entry.url == url)
+    {
+        return;
+    }
+    let previous = index.entries.insert(
+        key,
+        IconEntry {
+            url: url.to_string(),
+            ..Default::default()
+        },
+    );
+    if let Some(file_name) = previous.and_then(|entry| entry.file_name) {
+        remove_file_unless_shared(&index, &file_name);
+    }
+    save_index_logged(&index);
+}
+
+/// Local path of the addon's icon, downloading or revalidating it as needed.
+/// Returns None when no icon is known or it cannot be fetched.
+pub async fn get_icon(item_type: &str, folder_name: &str) -> Option<PathBuf> {
+    let key = icon_key(item_type, folder_name);
+    let entry = lock_index().entries.get(&key).cloned()?;
+    let now = now_secs();
+    let cached_path = entry
+        .file_name
+        .as_ref()
+        .map(|file_name| cache_dir().join(file_name))
+        .filter(|path| path.is_file());
+
+    let fresh = now - entry.checked_at < REVALIDATE_AFTER_SECS;
+    let recently_failed = entry
+        .failed_at
+        .is_some_and(|failed_at| now - failed_at < NEGATIVE_TTL_SECS);
+    if (cached_path.is_some() && fresh) || recently_failed || settings_store::get().offline_mode {
+        if cached_path.is_some() {
+            if let Some(current) = lock_index().entries.get_mut(&key) {
+                current.last_used_at = now;
+            }
+        }
+        return cached_path;
+    }
+
+    let etag = cached_path.as_ref().and(entry.etag.as_deref());
+    let result = download(&entry.url, etag).await;
+
+    let mut index = lock_index();
+    // The URL may have changed while downloading
+    let current = match index.entries.get_mut(&key) {
+        Some(current) if current.url == entry.url => current,
+        _ => return cached_path,
+    };
+
+    let mut replaced_file = None;
+    let path = match result {
+        Ok(Download::NotModified) => {
+            current.checked_at = now;
+            current.last_used_at = now;
+            current.failed_at = None;
+            cached_path
+        }
+        Ok(Download::Fetched {
+            body,
+            extension,
+            etag,
+        }) => {
+            let url_hash = format!("{:x}", Sha256::digest(entry.url.as_bytes()));
+            let file_name = format!("{}.{}", &url_hash[..16], extension);
+            let path = cache_dir().join(&file_name);
+            match fs::create_dir_all(cache_dir()).and_then(|_| fs::write(&path, &body)) {
+                Ok(()) => {
+                    if current.file_name.as_deref() != Some(file_name.as_str()) {
+                        replaced_file = current.file_name.take();
+                    }
+                    current.file_name = Some(file_name);
+                    current.etag = etag;
+                    current.size = body.len() as u64;
+                    current.checked_at = now;
+                    current.last_used_at = now;
+                    current.failed_at = None;
+                    Some(path)
+                }
+                Err(e) => {
+                    logger::log_error(
+                        &format!("Failed to write icon for {}: {}", key, e),
+                        Some("addon_icons"),
+                    );
+                    current.failed_at = Some(now);
+                    cached_path
+                }
+            }
+        }
+        Err(reason) => {
+            logger::log_info(
+                &format!(
+                    "Warning: icon download failed (item={}, url={}, reason={})",
+                    key, entry.url, reason
+                ),
+                Some("addon_icons"),
+            );
+            current.failed_at = Some(now);
+            cached_path
+        }
+    };
+
+    if let Some(file_name) = replaced_file {
+        remove_file_unless_shared(&index, &file_name);
+    }
+    evict_over_cap(&mut index);
+    save_index_logged(&index);
+    path
+}
+
+/// Delete every cached icon and forget failures. Known icon URLs are kept so
+/// icons are downloaded again on the next request. Returns the number of files removed.
+pub fn clear_cache() -> Result<usize, String> {
+    let mut index = lock_index();
+    let mut removed = 0;
+    if let Ok(read_dir) = fs::read_dir(cache_dir()) {
+        for entry in read_dir.flatten() {
+            if entry.file_name() != INDEX_FILE_NAME && fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+    }
+    for entry in index.entries.values_mut() {
+        *entry = IconEntry {
+            url: std::mem::take(&mut entry.url),
+            ..Default::default()
+        };
+    }
+    save_index(&index)?;
+    Ok(removed)
+}
+
+/// Evict least recently used icons until the cache fits in `MAX_CACHE_BYTES`
+fn evict_over_cap(index: &mut IconIndex) {
+    let mut files: HashMap<String, (u64, i64)> = HashMap::new();
+    for entry in index.entries.values() {
+        if let Some(file_name) = &entry.file_name {
+            let file = files.entry(file_name.clone()).or_insert((entry.size, 0));
+            file.1 = file.1.max(entry.last_used_at);
+        }
+    }
+    let mut total: u64 = files.values().map(|(size, _)| size).sum();
+    if total <= MAX_CACHE_BYTES {
+        return;
+    }
+
+    let mut by_age: Vec<(String, u64, i64)> = files
+        .into_iter()
+        .map(|(file_name, (size, last_used))| (file_name, size, last_used))
+        .collect();
+    by_age.sort_by_key(|(_, _, last_used)| *last_used);
+    for (file_name, size, _) in by_age {
+        if total <= MAX_CACHE_BYTES {
+            break;
+        }
+        let _ = fs::remove_file(cache_dir().join(&file_name));
+        for entry in index.entries.values_mut() {
+            if entry.file_name.as_deref() == Some(file_name.as_str()) {
+                entry.file_name = None;
+                entry.etag = None;
+                entry.size = 0;
+            }
+        }
+        total = total.saturating_sub(size);
+    }
+}
+
+async fn download(url: &str, etag: Option<&str>) -> Result<Download, String> {
+    let client = http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(Duration::from_secs(15)))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut request = client.get(url);
+    if let Some(etag) = etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(Download::NotModified);
+    }
+    if !response.status().is_success() {
+        return Err(format!("HTTP status: {}", response.status()));
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_ICON_BYTES)
+    {
+        return Err("icon exceeds the size limit".to_string());
+    }
+
+    let header = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_type = header(reqwest::header::CONTENT_TYPE);
+    let etag = header(reqwest::header::ETAG);
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    let extension = validate_icon(content_type.as_deref(), &body)?;
+    Ok(Download::Fetched {
+        body: body.to_vec(),
+        extension,
+        etag,
+    })
+}
+
+/// Check a downloaded icon and return the file extension to store it under
+fn validate_icon(content_type: Option<&str>, body: &[u8]) -> Result<&'static str, String> {
+    if body.len() as u64 > MAX_ICON_BYTES {
+        return Err("icon exceeds the size limit".to_string());
+    }
+    if body.is_empty() {
+        return Err("empty response".to_string());
+    }
+
+    let mime = content_type
+        .and_then(|ct| ct.split(';').next())
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let sniffed = sniff_raster(body);
+    let extension = match mime.as_str() {
+        "image/svg+xml" => "svg",
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        // Servers often label static files generically; trust the bytes then
+        "" | "application/octet-stream" | "binary/octet-stream" => {
+            sniffed.ok_or_else(|| "unrecognised image data".to_string())?
+        }
+        other => return Err(format!("unexpected content type '{}'", other)),
+    };
+
+    if extension == "svg" {
+        if !svg_is_safe(body) {
+            return Err("SVG contains script or embedded content".to_string());
+        }
+    } else if sniffed != Some(extension) {
+        return Err(format!("content does not match '{}'", mime));
+    }
+    Ok(extension)
+}
+
+fn sniff_raster(body: &[u8]) -> Option<&'static str> {
+    if body.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("png")
+    } else if body.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpg")
+    } else if body.starts_with(b"GIF87a") || body.starts_with(b"GIF89a") {
+        Some("gif")
+    } else if body.len() >= 12 && &body[..4] == b"RIFF" && &body[8..12] == b"WEBP" {
+        Some("webp")
+    } else if body.starts_with(&[0x00, 0x00, 0x01, 0x00]) {
+        Some("ico")
+    } else {
+        None
+    }
+}
+
+fn svg_is_safe(body: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(body) else {
+        return false;
+    };
+    let lower = text.to_ascii_lowercase();
+    lower.contains("<svg")
+        && !SVG_BLOCKED_CONTENT
+            .iter()
+            .any(|blocked| lower.contains(blocked))
+        && !SVG_EVENT_HANDLER_RE.is_match(&lower)
+}
+
+/// Icon URL from a key/value updater config such as skunkcrafts_updater.cfg
+/// (`icon|https://...`), resolved against the config's base URL
+pub fn icon_url_from_cfg(content: &str, base_url: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let (key, value) = line.trim().split_once('|')?;
+        let key = key.trim().to_ascii_lowercase().replace(['_', '-'], "");
+        let value = value.trim();
+        (ICON_KEYS.contains(&key.as_str()) && !value.is_empty())
+            .then(|| resolve_relative(base_url, value))
+    })
+}
+
+/// Icon link from an x-updater product payload, as given (possibly relative)
+pub fn icon_href_from_product(product: &Value) -> Option<String> {
+    let object = product.as_object()?;
+    object.iter().find_map(|(key, value)| {
+        // x-updater prefixes some fields with `m` (mIcon, mThumbnail)
+        let key = key.to_ascii_lowercase();
+        let key = key
+            .strip_prefix('m')
+            .filter(|rest| ICON_KEYS.contains(rest))
+            .unwrap_or(key.as_str());
+        if !ICON_KEYS.contains(&key) {
+            return None;
+        }
+        let href = match value {
+            Value::String(href) => Some(href.as_str()),
+            Value::Object(link) => link
+                .get("href")
+                .or_else(|| link.get("url"))
+                .and_then(Value::as_str),
+            _ => None,
+        }?;
+        let href = href.trim();
+        (!href.is_empty()).then(|| href.to_string())
+    })
+}
+
+fn resolve_relative(base_url: &str, href: &str) -> String {
+    if href.starts_with("http://") || href.starts_with("https://") {
+        return href.to_string();
+    }
+    format!(
+        "{}/{}",
+        base_url.trim_end_matches('/'),
+        href.trim_start_matches('/')
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn accepts_matching_raster_and_sniffs_unlabelled_data() {
+        assert_eq!(validate_icon(Some("image/png"), PNG), Ok("png"));
+        assert_eq!(validate_icon(None, PNG), Ok("png"));
+        assert_eq!(
+            validate_icon(Some("application/octet-stream"), b"GIF89a...."),
+            Ok("gif")
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_oversized_or_non_image_payloads() {
+        assert!(validate_icon(Some("image/jpeg"), PNG).is_err());
+        assert!(validate_icon(Some("text/html"), b"<html>login</html>").is_err());
+        assert!(validate_icon(None, b"<html>login</html>").is_err());
+        let huge = vec![0u8; MAX_ICON_BYTES as usize + 1];
+        assert!(validate_icon(Some("image/png"), &huge).is_err());
+    }
+
+    #[test]
+    fn rejects_svg_with_script_content() {
+        let clean = br#"<svg xmlns="http://www.w3.org/2000/svg"><circle r="4"/></svg>"#;
+        assert_eq!(validate_icon(Some("image/svg+xml"), clean), Ok("svg"));
+
+        for svg in [
+            r#"<svg><script>alert(1)</script></svg>"#,
+            r#"<svg onload="alert(1)"><rect/></svg>"#,
+            r#"<svg><a href="javascript:alert(1)"><rect/></a></svg>"#,
+            r#"<svg><foreignObject><div/></foreignObject></svg>"#,
+        ] {
+            assert!(
+                validate_icon(Some("image/svg+xml"), svg.as_bytes()).is_err(),
+                "{}",
+                svg
+            );
+        }
+    }
+
+    #[test]
+    fn extracts_icon_urls_from_updater_metadata() {
+        let cfg = "module|https://example.com/a320\nversion|1.2\nicon|icon.png\n";
+        assert_eq!(
+            icon_url_from_cfg(cfg, "https://example.com/a320/"),
+            Some("https://example.com/a320/icon.png".to_string())
+        );
+        assert_eq!(
+            icon_url_from_cfg("version|1.2", "https://example.com"),
+            None
+        );
+
+        let product = json!({"mName": "A320", "mIcon": {"href": "/media/a320.png"}});
+        assert_eq!(
+            icon_href_from_product(&product),
+            Some("/media/a320.png".to_string())
+        );
+        let product = json!({"name": "A320", "thumbnailUrl": "https://cdn.example.com/t.webp"});
+        assert_eq!(
+            icon_href_from_product(&product),
+            Some("https://cdn.example.com/t.webp".to_string())
+        );
+        assert_eq!(icon_href_from_product(&json!({"mName": "A320"})), None);
+    }
+}
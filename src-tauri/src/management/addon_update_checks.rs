@@ -0,0 +1,664 @@
+//! Update check across every installed addon
+//!
+//! Finds aircraft, plugins, scenery packages and (optionally) liveries that
+//! carry skunkcrafts or x-updater metadata and compares their local version
+//! with the remote one:
+//!
+//! - skunkcrafts aircraft and plugins go through the same checks as the
+//!   management lists (`check_aircraft_updates` / `check_plugins_updates`),
+//!   other skunkcrafts folders fetch the remote cfg directly and x-updater
+//!   targets use the update preview.
+//! - At most [`MAX_CONCURRENT_CHECKS`] checks run at once and requests to the
+//!   same host are spaced [`HOST_REQUEST_SPACING`] apart.
+//! - A run is cancelled through its [`TaskControl`]; the addons finished so far
+//!   are still returned.
+//! - An optional background loop repeats the check every few hours and emits
+//!   [`ADDON_UPDATES_AVAILABLE_EVENT`] when something can be updated.
+
+use futures::StreamExt;
+use reqwest::Url;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::addon_updater::{self, AddonUpdateOptions};
+use crate::download_retry;
+use crate::error::{ApiError, ApiResult};
+use crate::logger;
+use crate::management_index;
+use crate::models::{AddonUpdateCheckItem, AddonUpdateSummary, AircraftInfo, PluginInfo};
+use crate::settings_store;
+use crate::task_control::TaskControl;
+use crate::x_updater_profile::parse_tagged_update_url;
+use crate::zibo_updater;
+
+/// Event emitted with an [`AddonUpdateSummary`] when a scheduled check finds updates
+pub const ADDON_UPDATES_AVAILABLE_EVENT: &str = "addon-updates-available";
+
+/// Checks running at the same time
+const MAX_CONCURRENT_CHECKS: usize = 4;
+
+/// Minimum time between two requests to the same host
+const HOST_REQUEST_SPACING: Duration = Duration::from_millis(500);
+
+/// Longest accepted scheduler interval (30 days)
+const MAX_SCHEDULE_INTERVAL_HOURS: u32 = 30 * 24;
+
+const LOG_CTX: &str = "addon_update_checks";
+
+/// Whether a check is in progress (checks never overlap)
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Cancellation handle of the check in progress
+static CURRENT_CONTROL: LazyLock<Mutex<Option<TaskControl>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Bumped whenever the scheduler is (re)started or stopped; loops from an
+/// older generation exit at their next wake-up
+static SCHEDULER_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Marks a check as active for its lifetime
+struct RunGuard;
+
+impl RunGuard {
+    fn acquire(control: &TaskControl) -> ApiResult<Self> {
+        if RUNNING
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err(ApiError::conflict(
+                "An addon update check is already in progress",
+            ));
+        }
+        if let Ok(mut current) = CURRENT_CONTROL.lock() {
+            *current = Some(control.clone());
+        }
+        Ok(RunGuard)
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        if let Ok(mut current) = CURRENT_CONTROL.lock() {
+            *current = None;
+        }
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Request cancellation of the check in progress. Returns `false` if none is running.
+pub fn cancel() -> bool {
+    match CURRENT_CONTROL.lock().ok().and_then(|c| c.clone()) {
+        Some(control) => {
+            control.request_cancel_all();
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+/// Spaces out requests per host. Each caller reserves the next free slot for
+/// its host and sleeps until then; the lock is never held across the sleep.
+struct HostRateLimiter {
+    spacing: Duration,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl HostRateLimiter {
+    fn new(spacing: Duration) -> Self {
+        Self {
+            spacing,
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reserve a request slot for `host` and return how long to wait for it
+    fn reserve(&self, host: &str, now: Instant) -> Duration {
+        let mut slots = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = slots
+            .get(host)
+            .copied()
+            .filter(|slot| *slot > now)
+            .unwrap_or(now);
+        slots.insert(host.to_string(), slot + self.spacing);
+        slot - now
+    }
+
+    /// Wait for a request slot for `host`. Returns false when `control` was
+    /// cancelled before the slot came up.
+    async fn wait(&self, host: &str, control: &TaskControl) -> bool {
+        let delay = self.reserve(host, Instant::now());
+        if !delay.is_zero() {
+            tokio::select! {
+                () = tokio::time::sleep(delay) => {}
+                () = download_retry::cancelled(Some(control)) => {}
+            }
+        }
+        !control.is_cancelled()
+    }
+}
+
+/// Where an addon's version is read from
+enum TargetSource {
+    Aircraft(AircraftInfo),
+    Plugin(PluginInfo),
+    /// Scenery packages and liveries, read straight from their folder
+    Folder,
+}
+
+struct CheckTarget {
+    item_type: &'static str,
+    folder_name: String,
+    display_name: String,
+    provider: String,
+    update_url: String,
+    current_version: Option<String>,
+    source: TargetSource,
+}
+
+impl CheckTarget {
+    fn item(&self) -> AddonUpdateCheckItem {
+        AddonUpdateCheckItem {
+            item_type: self.item_type.to_string(),
+            folder_name: self.folder_name.clone(),
+            display_name: self.display_name.clone(),
+            provider: self.provider.clone(),
+            current_version: self.current_version.clone(),
+            remote_version: None,
+            has_update: false,
+            error: None,
+        }
+    }
+
+    /// Key requests are rate-limited by
+    fn host_key(&self) -> String {
+        if self.provider == zibo_updater::ZIBO_PROVIDER {
+            return zibo_updater::ZIBO_PROVIDER.to_string();
+        }
+        // x-updater URLs are tagged with the store's base URL
+        let url =
+            parse_tagged_update_url(&self.update_url).unwrap_or_else(|| self.update_url.clone());
+        Url::parse(&url)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_lowercase()))
+            .unwrap_or_else(|| self.update_url.to_lowercase())
+    }
+}
+
+/// Check every addon with updater metadata under `xplane_path` for updates
+pub async fn check_all(
+    xplane_path: &Path,
+    include_liveries: bool,
+    use_beta: bool,
+    scheduled: bool,
+) -> ApiResult<AddonUpdateSummary> {
+    if !xplane_path.is_dir() {
+        return Err(ApiError::validation(format!(
+            "X-Plane folder not found: {}",
+            xplane_path.display()
+        )));
+    }
+    if settings_store::get().offline_mode {
+        return Err(ApiError::validation(
+            "Offline mode is on; addon updates were not checked",
+        ));
+    }
+    let control = TaskControl::new();
+    let _guard = RunGuard::acquire(&control)?;
+
+    let scan_path = xplane_path.to_path_buf();
+    let targets =
+        tokio::task::spawn_blocking(move || collect_targets(&scan_path, include_liveries))
+            .await
+            .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))?;
+    let total_count = targets.len();
+    logger::log_info(
+        &format!("Checking {} addon(s) for updates", total_count),
+        Some(LOG_CTX),
+    );
+
+    let limiter = HostRateLimiter::new(HOST_REQUEST_SPACING);
+    let results: Vec<Option<AddonUpdateCheckItem>> = futures::stream::iter(targets)
+        .map(|target| {
+            let options = preview_options(include_liveries, use_beta);
+            check_target(xplane_path, target, options, &control, &limiter)
+        })
+        .buffer_unordered(MAX_CONCURRENT_CHECKS)
+        .collect()
+        .await;
+
+    let cancelled = control.is_cancelled() || results.iter().any(Option::is_none);
+    let mut items: Vec<AddonUpdateCheckItem> = results.into_iter().flatten().collect();
+    items.sort_by(|a, b| {
+        a.item_type.cmp(&b.item_type).then_with(|| {
+            a.display_name
+                .to_lowercase()
+                .cmp(&b.display_name.to_lowercase())
+        })
+    });
+    let update_count = items.iter().filter(|item| item.has_update).count();
+    let failed_count = items.iter().filter(|item| item.error.is_some()).count();
+    logger::log_info(
+        &format!(
+            "Addon update check finished: {} update(s), {} failed, {} of {} checked{}",
+            update_count,
+            failed_count,
+            items.len(),
+            total_count,
+            if cancelled { " (cancelled)" } else { "" }
+        ),
+        Some(LOG_CTX),
+    );
+
+    Ok(AddonUpdateSummary {
+        xplane_path: xplane_path.to_string_lossy().to_string(),
+        items,
+        total_count,
+        update_count,
+        failed_count,
+        cancelled,
+        scheduled,
+        checked_at: unix_now(),
+    })
+}
+
+/// Check one addon. Returns `None` when the run was cancelled before it finished.
+async fn check_target(
+    xplane_path: &Path,
+    target: CheckTarget,
+    options: AddonUpdateOptions,
+    control: &TaskControl,
+    limiter: &HostRateLimiter,
+) -> Option<AddonUpdateCheckItem> {
+    if control.is_cancelled() || !limiter.wait(&target.host_key(), control).await {
+        return None;
+    }
+
+    let mut item = target.item();
+    if management_index::is_x_updater_url(&target.update_url) {
+        let preview = addon_updater::fetch_update_preview(
+            xplane_path,
+            target.item_type,
+            &target.folder_name,
+            options,
+            None,
+            None,
+            Some(control.clone()),
+            None,
+        )
+        .await;
+        match preview {
+            Ok(preview) => {
+                if preview.local_version.is_some() {
+                    item.current_version = preview.local_version;
+                }
+                item.has_update = preview.target_version.is_some()
+                    && preview.target_version != item.current_version;
+                item.remote_version = preview.target_version;
+            }
+            Err(_) if control.is_cancelled() => return None,
+            Err(e) => item.error = Some(e.to_string()),
+        }
+        return Some(item);
+    }
+
+    match target.source {
+        TargetSource::Aircraft(aircraft) => {
+            let mut aircraft = [aircraft];
+            management_index::check_aircraft_updates(&mut aircraft).await;
+            let [aircraft] = aircraft;
+            item.remote_version = aircraft.latest_version;
+            item.has_update = aircraft.has_update;
+        }
+        TargetSource::Plugin(plugin) => {
+            let mut plugins = [plugin];
+            management_index::check_plugins_updates(&mut plugins).await;
+            let [plugin] = plugins;
+            item.remote_version = plugin.latest_version;
+            item.has_update = plugin.has_update;
+        }
+        TargetSource::Folder => {
+            let remote = management_index::fetch_remote_version(target.update_url.clone()).await;
+            item.remote_version = remote.and_then(|remote| remote.version);
+            if let Some(remote_version) = &item.remote_version {
+                item.has_update =
+                    remote_version.as_str() != item.current_version.as_deref().unwrap_or("");
+            }
+        }
+    }
+    if item.remote_version.is_none() {
+        item.error = Some("The remote version could not be fetched".to_string());
+    }
+    Some(item)
+}
+
+/// Options for a preview only; nothing is downloaded. The channel is left to
+/// the one stored for each addon, else beta or stable as `use_beta` says.
+fn preview_options(include_liveries: bool, use_beta: bool) -> AddonUpdateOptions {
+    AddonUpdateOptions {
+        use_beta,
+        include_liveries,
+        apply_blacklist: false,
+        rollback_on_failure: false,
+        parallel_downloads: None,
+        channel: None,
+        fresh_install: false,
+        preserve_liveries: true,
+        preserve_config_files: true,
+        chunked_download_enabled: None,
+        threads_per_task: None,
+        total_threads: None,
+        keep_backup_versions: None,
+    }
+}
+
+/// Addons with enabled updater metadata, aircraft first
+fn collect_targets(xplane_path: &Path, include_liveries: bool) -> Vec<CheckTarget> {
+    let mut targets = Vec::new();
+
+    let aircraft = management_index::scan_aircraft(xplane_path, false)
+        .map(|data| data.entries)
+        .unwrap_or_default();
+    for aircraft in aircraft {
+        if include_liveries {
+            collect_livery_targets(xplane_path, &aircraft, &mut targets);
+        }
+        let Some(provider) = aircraft.update_provider.clone() else {
+            continue;
+        };
+        if aircraft.cfg_disabled == Some(true) {
+            continue;
+        }
+        targets.push(CheckTarget {
+            item_type: "aircraft",
+            folder_name: aircraft.folder_name.clone(),
+            display_name: aircraft.display_name.clone(),
+            provider,
+            update_url: aircraft.update_url.clone().unwrap_or_default(),
+            current_version: aircraft.version.clone(),
+            source: TargetSource::Aircraft(aircraft),
+        });
+    }
+
+    let plugins = management_index::scan_plugins(xplane_path, false)
+        .map(|data| data.entries)
+        .unwrap_or_default();
+    for plugin in plugins {
+        let Some(provider) = plugin.update_provider.clone() else {
+            continue;
+        };
+        if plugin.cfg_disabled == Some(true) {
+            continue;
+        }
+        targets.push(CheckTarget {
+            item_type: "plugin",
+            folder_name: plugin.folder_name.clone(),
+            display_name: plugin.display_name.clone(),
+            provider,
+            update_url: plugin.update_url.clone().unwrap_or_default(),
+            current_version: plugin.version.clone(),
+            source: TargetSource::Plugin(plugin),
+        });
+    }
+
+    for (folder_name, path) in subfolders(&xplane_path.join("Custom Scenery")) {
+        if let Some(target) = folder_target("scenery", folder_name.clone(), folder_name, &path) {
+            targets.push(target);
+        }
+    }
+    targets
+}
+
+fn collect_livery_targets(
+    xplane_path: &Path,
+    aircraft: &AircraftInfo,
+    targets: &mut Vec<CheckTarget>,
+) {
+    let liveries_path = xplane_path
+        .join("Aircraft")
+        .join(&aircraft.folder_name)
+        .join("liveries");
+    for (livery, path) in subfolders(&liveries_path) {
+        let folder_name = format!("{}/liveries/{}", aircraft.folder_name, livery);
+        let display_name = format!("{} / {}", aircraft.display_name, livery);
+        if let Some(target) = folder_target("livery", folder_name, display_name, &path) {
+            targets.push(target);
+        }
+    }
+}
+
+/// A scenery package or livery with an enabled updater cfg or x-updater profile
+fn folder_target(
+    item_type: &'static str,
+    folder_name: String,
+    display_name: String,
+    path: &Path,
+) -> Option<CheckTarget> {
+    let (version, update_url, cfg_disabled) = management_index::read_version_info_with_url(path);
+    let update_url = update_url?;
+    if cfg_disabled == Some(true) {
+        return None;
+    }
+    let provider = if management_index::is_x_updater_url(&update_url) {
+        "x-updater"
+    } else {
+        "skunkcrafts"
+    };
+    Some(CheckTarget {
+        item_type,
+        folder_name,
+        display_name,
+        provider: provider.to_string(),
+        update_url,
+        current_version: version,
+        source: TargetSource::Folder,
+    })
+}
+
+/// Direct subfolders of `dir` as (name, path), sorted by name
+fn subfolders(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut folders: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| Some((entry.file_name().into_string().ok()?, entry.path())))
+        .collect();
+    folders.sort();
+    folders
+}
+
+/// Repeat the check for `xplane_path` every `interval_hours` in the background,
+/// replacing any scheduler started earlier. An interval of 0 stops it.
+pub fn start_scheduler(
+    app_handle: AppHandle,
+    xplane_path: PathBuf,
+    interval_hours: u32,
+    include_liveries: bool,
+    use_beta: bool,
+) -> ApiResult<()> {
+    if interval_hours > MAX_SCHEDULE_INTERVAL_HOURS {
+        return Err(ApiError::validation(format!(
+            "Update check interval must be at most {} hours",
+            MAX_SCHEDULE_INTERVAL_HOURS
+        )));
+    }
+    if interval_hours > 0 && !xplane_path.is_dir() {
+        return Err(ApiError::validation(format!(
+            "X-Plane folder not found: {}",
+            xplane_path.display()
+        )));
+    }
+    let generation = SCHEDULER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if interval_hours == 0 {
+        logger::log_info("Addon update scheduler stopped", Some(LOG_CTX));
+        return Ok(());
+    }
+
+    logger::log_info(
+        &format!(
+            "Addon update scheduler started: every {} hour(s)",
+            interval_hours
+        ),
+        Some(LOG_CTX),
+    );
+    let interval = Duration::from_secs(u64::from(interval_hours) * 60 * 60);
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            if SCHEDULER_GENERATION.load(Ordering::SeqCst) != generation {
+                break;
+            }
+            run_scheduled(&app_handle, &xplane_path, include_liveries, use_beta).await;
+        }
+    });
+    Ok(())
+}
+
+/// One scheduled check, skipped while offline, while another check is running
+/// or while an install is active
+async fn run_scheduled(
+    app_handle: &AppHandle,
+    xplane_path: &Path,
+    include_liveries: bool,
+    use_beta: bool,
+) {
+    if settings_store::get().offline_mode
+        || is_running()
+        || app_handle.state::<TaskControl>().is_install_active()
+    {
+        return;
+    }
+    match check_all(xplane_path, include_liveries, use_beta, true).await {
+        Ok(summary) if summary.update_count > 0 => {
+            let _ = app_handle.emit(ADDON_UPDATES_AVAILABLE_EVENT, &summary);
+        }
+        Ok(_) => {}
+        Err(e) => logger::log_info(
+            &format!("Scheduled addon update check failed: {}", e),
+            Some(LOG_CTX),
+        ),
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rate_limiter_spaces_requests_per_host() {
+        let limiter = HostRateLimiter::new(Duration::from_millis(500));
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve("a.example", now), Duration::ZERO);
+        assert_eq!(
+            limiter.reserve("a.example", now),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            limiter.reserve("a.example", now),
+            Duration::from_millis(1000)
+        );
+        // Other hosts are not held up
+        assert_eq!(limiter.reserve("b.example", now), Duration::ZERO);
+        // Slots in the past are not carried forward
+        let later = now + Duration::from_secs(5);
+        assert_eq!(limiter.reserve("a.example", later), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn rate_limiter_wait_ends_on_cancel() {
+        let limiter = HostRateLimiter::new(Duration::from_secs(60));
+        let control = TaskControl::new();
+        assert!(limiter.wait("a.example", &control).await);
+
+        let cancel = control.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            cancel.request_cancel_all();
+        });
+        let started = Instant::now();
+        assert!(!limiter.wait("a.example", &control).await);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn host_key_uses_url_host_or_x_updater_host() {
+        let mut target = folder_target_for("https://Updates.Example.com/addon/", "skunkcrafts");
+        assert_eq!(target.host_key(), "updates.example.com");
+
+        target.update_url = "x-updater:store.example.com".to_string();
+        assert_eq!(target.host_key(), "store.example.com");
+
+        target.provider = zibo_updater::ZIBO_PROVIDER.to_string();
+        assert_eq!(target.host_key(), zibo_updater::ZIBO_PROVIDER);
+    }
+
+    #[test]
+    fn collects_scenery_and_livery_folders_with_enabled_updater_cfg() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let scenery = temp.path().join("Custom Scenery");
+        let write_cfg = |dir: &Path, disabled: bool| {
+            fs::create_dir_all(dir).expect("failed to create folder");
+            fs::write(
+                dir.join("skunkcrafts_updater.cfg"),
+                format!(
+                    "module|https://updates.example.com/pack\nversion|1.2\ndisabled|{}\n",
+                    disabled
+                ),
+            )
+            .expect("failed to write cfg");
+        };
+        write_cfg(&scenery.join("EDDM Airport"), false);
+        write_cfg(&scenery.join("Disabled Pack"), true);
+        fs::create_dir_all(scenery.join("Plain Pack")).expect("failed to create folder");
+
+        let aircraft = temp.path().join("Aircraft").join("DemoPlane");
+        fs::create_dir_all(&aircraft).expect("failed to create aircraft");
+        fs::write(aircraft.join("DemoPlane.acf"), "acf").expect("failed to write acf");
+        write_cfg(&aircraft.join("liveries").join("Red"), false);
+
+        let without_liveries = collect_targets(temp.path(), false);
+        assert_eq!(without_liveries.len(), 1);
+        assert_eq!(without_liveries[0].item_type, "scenery");
+        assert_eq!(without_liveries[0].folder_name, "EDDM Airport");
+        assert_eq!(without_liveries[0].current_version.as_deref(), Some("1.2"));
+        assert_eq!(without_liveries[0].provider, "skunkcrafts");
+
+        let with_liveries = collect_targets(temp.path(), true);
+        let livery = with_liveries
+            .iter()
+            .find(|target| target.item_type == "livery")
+            .expect("expected livery target");
+        assert_eq!(livery.folder_name, "DemoPlane/liveries/Red");
+        assert_eq!(with_liveries.len(), 2);
+    }
+
+    fn folder_target_for(update_url: &str, provider: &str) -> CheckTarget {
+        CheckTarget {
+            item_type: "scenery",
+            folder_name: "Pack".to_string(),
+            display_name: "Pack".to_string(),
+            provider: provider.to_string(),
+            update_url: update_url.to_string(),
+            current_version: None,
+            source: TargetSource::Folder,
+        }
+    }
+}
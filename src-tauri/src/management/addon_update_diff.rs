@@ -0,0 +1,325 @@
+//! Folder-level diff of an addon update
+//!
+//! The update plan lists paths per action. Before applying it, each planned
+//! file is compared with the local folder so the user can see which of their
+//! own changes would be lost:
+//!
+//! - Replaced and deleted files are hashed locally and checked against the
+//!   previous remote hash when the manifest lists one.
+//! - Config files (`*.cfg`, `*.ini`) the update overwrites or deletes are
+//!   collected separately, since those usually hold user settings.
+//! - At most [`MAX_DIFF_FILES`] files are listed; groups past that only carry
+//!   their count and size.
+
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::addon_updater::{self, AddonUpdateOptions, AddonUpdatePlan, PlannedFileInfo};
+use crate::models::{AddonUpdateDiff, AddonUpdateDiffFile, AddonUpdateDiffGroup};
+use crate::task_control::TaskControl;
+
+/// Files listed across all groups before the rest is summarized
+const MAX_DIFF_FILES: usize = 5000;
+
+/// Extensions of files that usually hold user settings
+const CONFIG_EXTENSIONS: &[&str] = &["cfg", "ini"];
+
+fn is_config_path(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            CONFIG_EXTENSIONS
+                .iter()
+                .any(|config| ext.eq_ignore_ascii_case(config))
+        })
+}
+
+/// Group `files` sorted by path, with totals over all of them
+fn group(mut files: Vec<AddonUpdateDiffFile>) -> AddonUpdateDiffGroup {
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    AddonUpdateDiffGroup {
+        count: files.len(),
+        bytes: files
+            .iter()
+            .map(|file| file.local_size.or(file.remote_size).unwrap_or(0))
+            .sum(),
+        files,
+    }
+}
+
+/// Keep at most `budget` files of `group`, lowering the budget accordingly
+fn take_budget(group: &mut AddonUpdateDiffGroup, budget: &mut usize) -> bool {
+    let kept = group.files.len().min(*budget);
+    *budget -= kept;
+    let summarized = kept < group.files.len();
+    group.files.truncate(kept);
+    summarized
+}
+
+/// Compare `plan` with the addon folder at `target_path`
+fn classify_plan(
+    target_path: &Path,
+    plan: &AddonUpdatePlan,
+    task_control: Option<&TaskControl>,
+) -> Result<AddonUpdateDiff> {
+    let details: HashMap<String, &PlannedFileInfo> = plan
+        .file_details
+        .iter()
+        .map(|info| (info.path.to_lowercase(), info))
+        .collect();
+    let diff_file = |path: &str| {
+        let local_size = fs::metadata(target_path.join(path))
+            .ok()
+            .filter(|meta| meta.is_file())
+            .map(|meta| meta.len());
+        AddonUpdateDiffFile {
+            path: path.to_string(),
+            local_size,
+            remote_size: details.get(&path.to_lowercase()).and_then(|info| info.size),
+            is_config: is_config_path(path),
+        }
+    };
+    // Whether the local file still matches the hash the manifest lists for the
+    // version it replaces; None when there is nothing to compare
+    let matches_previous = |path: &str| -> Result<Option<bool>> {
+        let Some(previous) = details
+            .get(&path.to_lowercase())
+            .and_then(|info| info.previous_md5.as_ref())
+        else {
+            return Ok(None);
+        };
+        let local_path = target_path.join(path);
+        if !local_path.is_file() {
+            return Ok(None);
+        }
+        let local_md5 = addon_updater::md5_for_file(&local_path, task_control)?;
+        Ok(Some(local_md5.eq_ignore_ascii_case(previous)))
+    };
+
+    let added = plan.add_files.iter().map(|path| diff_file(path)).collect();
+
+    let mut updated = Vec::new();
+    let mut modified_locally = Vec::new();
+    let mut changed = Vec::new();
+    for path in &plan.replace_files {
+        match matches_previous(path)? {
+            Some(true) => updated.push(diff_file(path)),
+            Some(false) => modified_locally.push(diff_file(path)),
+            None => changed.push(diff_file(path)),
+        }
+    }
+
+    let mut deleted = Vec::new();
+    let mut deleted_modified = Vec::new();
+    for path in &plan.delete_files {
+        match matches_previous(path)? {
+            Some(false) => deleted_modified.push(diff_file(path)),
+            _ => deleted.push(diff_file(path)),
+        }
+    }
+
+    let planned: HashSet<String> = plan
+        .add_files
+        .iter()
+        .chain(&plan.replace_files)
+        .chain(&plan.delete_files)
+        .map(|path| path.to_lowercase())
+        .collect();
+    let deleted_dirs: Vec<String> = plan
+        .delete_files
+        .iter()
+        .filter(|path| target_path.join(path).is_dir())
+        .map(|path| format!("{}/", path.to_lowercase()))
+        .collect();
+    let mut untouched = Vec::new();
+    for entry in WalkDir::new(target_path).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(target_path) else {
+            continue;
+        };
+        let path = relative.to_string_lossy().replace('\\', "/");
+        let key = path.to_lowercase();
+        if planned.contains(&key) || deleted_dirs.iter().any(|dir| key.starts_with(dir)) {
+            continue;
+        }
+        untouched.push(AddonUpdateDiffFile {
+            path,
+            local_size: entry.metadata().ok().map(|meta| meta.len()),
+            remote_size: None,
+            is_config: is_config_path(&key),
+        });
+    }
+
+    if task_control.is_some_and(|tc| tc.is_cancelled()) {
+        return Err(anyhow!("Addon update diff cancelled by user"));
+    }
+
+    let mut diff = AddonUpdateDiff {
+        provider: plan.provider.clone(),
+        item_type: plan.item_type.clone(),
+        folder_name: plan.folder_name.clone(),
+        local_version: plan.local_version.clone(),
+        remote_version: plan.remote_version.clone(),
+        added: group(added),
+        updated: group(updated),
+        modified_locally: group(modified_locally),
+        changed: group(changed),
+        deleted: group(deleted),
+        deleted_modified: group(deleted_modified),
+        untouched: group(untouched),
+        config_changes: Vec::new(),
+        summarized: false,
+    };
+    let mut config_changes: Vec<AddonUpdateDiffFile> = [
+        &diff.modified_locally,
+        &diff.changed,
+        &diff.deleted_modified,
+        &diff.deleted,
+    ]
+    .iter()
+    .flat_map(|group| group.files.iter().filter(|file| file.is_config).cloned())
+    .collect();
+    config_changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    // Config changes and possible losses of local edits are listed first
+    let mut budget = MAX_DIFF_FILES;
+    let mut summarized = config_changes.len() > budget;
+    config_changes.truncate(budget);
+    budget -= config_changes.len();
+    diff.config_changes = config_changes;
+    for group in [
+        &mut diff.modified_locally,
+        &mut diff.deleted_modified,
+        &mut diff.changed,
+        &mut diff.deleted,
+        &mut diff.added,
+        &mut diff.updated,
+        &mut diff.untouched,
+    ] {
+        summarized |= take_budget(group, &mut budget);
+    }
+    diff.summarized = summarized;
+    Ok(diff)
+}
+
+/// Build the update plan for an addon and classify each planned file against
+/// the local folder
+pub async fn get_addon_update_diff(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+    options: AddonUpdateOptions,
+    task_control: Option<TaskControl>,
+) -> Result<AddonUpdateDiff> {
+    let plan = addon_updater::build_update_plan(
+        xplane_path,
+        item_type,
+        folder_name,
+        options,
+        task_control.clone(),
+        None,
+    )
+    .await?;
+    let target_path = addon_updater::resolve_target_path(xplane_path, item_type, folder_name)?;
+
+    // Hashing and walking a large aircraft folder takes a while
+    tokio::task::spawn_blocking(move || classify_plan(&target_path, &plan, task_control.as_ref()))
+        .await
+        .map_err(|e| anyhow!("Task join error: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn md5_hex(data: &[u8]) -> String {
+        format!("{:x}", md5::compute(data))
+    }
+
+    #[test]
+    fn test_plan_files_are_classified_against_local_folder() {
+        let temp = tempdir().expect("tempdir");
+        let root = temp.path();
+        fs::create_dir_all(root.join("objects")).unwrap();
+        fs::write(root.join("plane.acf"), b"stock acf").unwrap();
+        fs::write(root.join("prefs.cfg"), b"my settings").unwrap();
+        fs::write(root.join("readme.txt"), b"notes").unwrap();
+        fs::write(root.join("objects/old.obj"), b"old").unwrap();
+        fs::write(root.join("objects/mine.obj"), b"my object").unwrap();
+
+        let mut plan: AddonUpdatePlan = serde_json::from_value(serde_json::json!({
+            "provider": "x-updater",
+            "itemType": "aircraft",
+            "folderName": "Demo",
+            "remoteLocked": false,
+            "hasUpdate": true,
+            "estimatedDownloadBytes": 0,
+            "addFiles": ["objects/new.obj"],
+            "replaceFiles": ["plane.acf", "prefs.cfg"],
+            "deleteFiles": ["objects/old.obj"],
+            "skipFiles": [],
+            "warnings": [],
+        }))
+        .unwrap();
+        plan.file_details = vec![
+            PlannedFileInfo {
+                path: "objects/new.obj".to_string(),
+                size: Some(42),
+                ..Default::default()
+            },
+            PlannedFileInfo {
+                path: "plane.acf".to_string(),
+                previous_md5: Some(md5_hex(b"stock acf")),
+                ..Default::default()
+            },
+            PlannedFileInfo {
+                path: "prefs.cfg".to_string(),
+                previous_md5: Some(md5_hex(b"stock settings")),
+                ..Default::default()
+            },
+        ];
+
+        let diff = classify_plan(root, &plan, None).unwrap();
+        assert_eq!(diff.added.files[0].remote_size, Some(42));
+        assert_eq!(diff.added.bytes, 42);
+        assert_eq!(diff.updated.files[0].path, "plane.acf");
+        assert_eq!(diff.modified_locally.files[0].path, "prefs.cfg");
+        assert_eq!(diff.deleted.files[0].path, "objects/old.obj");
+        assert_eq!(diff.config_changes.len(), 1);
+        assert!(diff.config_changes[0].is_config);
+        let untouched: Vec<&str> = diff
+            .untouched
+            .files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(untouched, vec!["objects/mine.obj", "readme.txt"]);
+        assert!(!diff.summarized);
+    }
+
+    #[test]
+    fn test_large_groups_are_summarized() {
+        let files: Vec<AddonUpdateDiffFile> = (0..MAX_DIFF_FILES + 10)
+            .map(|i| AddonUpdateDiffFile {
+                path: format!("file{:05}.dds", i),
+                local_size: Some(1),
+                remote_size: None,
+                is_config: false,
+            })
+            .collect();
+        let mut listed = group(files);
+        let mut budget = MAX_DIFF_FILES;
+        assert!(take_budget(&mut listed, &mut budget));
+        assert_eq!(budget, 0);
+        assert_eq!(listed.files.len(), MAX_DIFF_FILES);
+        assert_eq!(listed.count, MAX_DIFF_FILES + 10);
+        assert_eq!(listed.bytes, (MAX_DIFF_FILES + 10) as u64);
+    }
+}
@@ -3,26 +3,37 @@ use flate2::read::GzDecoder;
 use futures::StreamExt;
 use md5::Context as Md5Context;
 use rayon::prelude::*;
-use reqwest::header::HeaderMap;
-use reqwest::Url;
+use reqwest::header::{HeaderMap, RANGE};
+use reqwest::{StatusCode, Url};
 use serde_json::Value;
 use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::io::Read;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tempfile::NamedTempFile;
 
+use crate::addon_backups::{self, FileChange};
+use crate::addon_icons;
+use crate::download_retry::{self, RetryPolicy, RetryStats};
+use crate::error::{ApiError, ApiErrorCode};
+use crate::http_client;
 use crate::logger;
-use crate::management_index::read_version_info_with_url;
+use crate::management_index::{self, read_version_info_with_url};
+use crate::settings_store;
 use crate::task_control::TaskControl;
+use crate::update_preflight;
+use crate::user_messages::UserMsg;
 use crate::x_updater_profile::{
-    find_profile_in_folder, parse_tagged_update_url, write_credentials_in_folder, XUpdaterProfile,
+    find_profile_in_folder, parse_profile_cfg, parse_tagged_update_url,
+    recover_profile_from_backup, validate_profile_in_folder, write_credentials_in_folder,
+    ProfileValidation, XUpdaterProfile,
 };
 
 pub use crate::skunk_updater::{
-    SkunkUpdateOptions as AddonUpdateOptions, SkunkUpdatePlan as AddonUpdatePlan,
+    PlannedFileInfo, SkunkUpdateOptions as AddonUpdateOptions, SkunkUpdatePlan as AddonUpdatePlan,
     SkunkUpdateResult as AddonUpdateResult,
 };
 
@@ -30,6 +41,8 @@ const SKUNK_CFG_FILE: &str = "skunkcrafts_updater.cfg";
 const XUPDATER_AUTH_PATH: &str = "/api/v2/service/auth/consumers";
 const XUPDATER_UPDATES_PATH: &str = "/api/v2/experimental/updates";
 const LOG_CTX: &str = "addon_updater";
+/// Minimum time between install progress events (about 10 per second)
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
 
 fn log_addon_info(message: impl Into<String>) {
     logger::log_info(&message.into(), Some(LOG_CTX));
@@ -39,25 +52,36 @@ fn log_addon_debug(message: impl Into<String>) {
     logger::log_debug(&message.into(), Some(LOG_CTX), None);
 }
 
-fn obfuscated_xupdater_block_error(
-    item_type: &str,
-    folder_name: &str,
-    target_path: &Path,
-) -> anyhow::Error {
-    let mut hash: u32 = 0xB529_7A4D ^ (item_type.len() as u32);
-    for byte in item_type
-        .bytes()
-        .chain(folder_name.bytes())
-        .chain(target_path.to_string_lossy().bytes())
-    {
-        hash ^= (byte as u32).wrapping_mul(0x85EB_CA6B);
-        hash = hash.rotate_left(7).wrapping_add(0xC2B2_AE35);
-    }
-    let token = format!("{:08X}", hash ^ 0x6A09_E667);
-    anyhow!("pipeline digest mismatch [{}]", token)
+/// Error for an addon whose x-updater profile has no Skunkcrafts metadata next to
+/// it. The correlation token only goes to the log, so a pasted error and the
+/// log line of the same attempt can be matched.
+pub fn unsupported_profile_error(item_type: &str, folder_name: &str) -> ApiError {
+    let mut hash: u32 = 0x6D2B_79F5;
+    for byte in item_type.bytes().chain(folder_name.bytes()) {
+        hash = hash.rotate_left(5) ^ (byte as u32).wrapping_mul(0x045D_9F3B);
+        hash = hash.wrapping_add(0x27D4_EB2D);
+    }
+    let token = format!("{:08X}", hash ^ 0xA1C3_0F71);
+    logger::log_info(
+        &format!(
+            "Refusing to update {}:{}: x-updater profile without {} [{}]",
+            item_type, folder_name, SKUNK_CFG_FILE, token
+        ),
+        Some(LOG_CTX),
+    );
+
+    let mut err = ApiError::localized(
+        ApiErrorCode::UpdaterProfileUnsupported,
+        UserMsg::XUpdaterProfileUnsupported.error_with([folder_name]),
+    );
+    err.details = Some(format!(
+        "The folder contains an x-updater profile but no {}; only addons with Skunkcrafts updater metadata can be updated or given credentials here",
+        SKUNK_CFG_FILE
+    ));
+    err
 }
 
-fn enforce_obfuscated_xupdater_lock(
+fn ensure_supported_updater_profile(
     item_type: &str,
     folder_name: &str,
     target_path: &Path,
@@ -66,11 +90,7 @@ fn enforce_obfuscated_xupdater_lock(
         return Ok(());
     }
     if find_profile_in_folder(target_path).is_some() {
-        return Err(obfuscated_xupdater_block_error(
-            item_type,
-            folder_name,
-            target_path,
-        ));
+        return Err(unsupported_profile_error(item_type, folder_name).into());
     }
     Ok(())
 }
@@ -193,6 +213,21 @@ fn emit_progress_event(
 pub struct AddonUpdaterCredentials {
     pub login: String,
     pub license_key: String,
+    /// The profile was damaged and has just been restored from its `.bak` copy
+    pub recovered_from_backup: bool,
+}
+
+/// Credentials found in an addon's existing updater files. Only a masked login
+/// is exposed; the license key itself never leaves the backend.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredUpdaterCredentials {
+    pub item_type: String,
+    pub folder_name: String,
+    /// Updater the credentials were found for ("skunkcrafts" or "x-updater")
+    pub provider: String,
+    pub login: String,
+    pub has_license_key: bool,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -208,6 +243,22 @@ pub struct AddonUpdatePreview {
     pub changelog: Option<String>,
 }
 
+/// Update channel an addon follows
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonUpdateChannel {
+    pub provider: String,
+    pub item_type: String,
+    pub folder_name: String,
+    pub channel: String,
+    pub available_channels: Vec<String>,
+    /// Version the channel offers, when the remote was asked
+    pub target_version: Option<String>,
+    /// Whether the channel is kept in the addon's updater config. X-Updater
+    /// addons have no such setting; the app stores their channel instead.
+    pub stored_in_addon: bool,
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddonDiskSpaceInfo {
@@ -285,6 +336,8 @@ struct XAction {
     kind: XActionKind,
     download: Option<XDownloadTask>,
     estimated_bytes: u64,
+    /// MD5 of the file version this action replaces or deletes, if listed
+    previous_md5: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -437,7 +490,7 @@ pub async fn build_update_plan(
         )
         .await;
     }
-    enforce_obfuscated_xupdater_lock(item_type, folder_name, &target_path)?;
+    ensure_supported_updater_profile(item_type, folder_name, &target_path)?;
 
     emit_progress_event(
         &progress_callback,
@@ -483,7 +536,8 @@ pub async fn build_update_plan(
         context.local_version,
         context.remote_version
     ));
-    let plan = build_xupdater_plan(item_type, folder_name, &context)?;
+    let mut plan = build_xupdater_plan(item_type, folder_name, &context)?;
+    update_preflight::apply(&mut plan, &target_path, options.rollback_on_failure);
     emit_progress_event(
         &progress_callback,
         item_type,
@@ -513,7 +567,7 @@ pub async fn fetch_update_preview(
     progress_callback: Option<AddonUpdateProgressCallback>,
 ) -> Result<AddonUpdatePreview> {
     let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
-    enforce_obfuscated_xupdater_lock(item_type, folder_name, &target_path)?;
+    ensure_supported_updater_profile(item_type, folder_name, &target_path)?;
 
     emit_progress_event(
         &progress_callback,
@@ -538,25 +592,21 @@ pub async fn fetch_update_preview(
         ));
     }
 
-    let profile = find_profile_in_folder(&target_path).ok_or_else(|| {
-        anyhow!(
-            "No x-updater profile was found in '{}'",
-            target_path.display()
-        )
-    })?;
+    let profile = find_profile_in_folder(&target_path)
+        .ok_or_else(|| UserMsg::XUpdaterProfileNotFound.error_with([target_path.display()]))?;
 
     let login = login_override
         .as_deref()
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
         .or(profile.login.clone())
-        .ok_or_else(|| anyhow!("x-updater profile is missing login/username"))?;
+        .ok_or_else(|| UserMsg::XUpdaterMissingLogin.error())?;
     let license_key = license_key_override
         .as_deref()
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
         .or(profile.license_key.clone())
-        .ok_or_else(|| anyhow!("x-updater profile is missing license key"))?;
+        .ok_or_else(|| UserMsg::XUpdaterMissingLicenseKey.error())?;
 
     let host = profile.host.clone();
     let client = build_http_client(20)?;
@@ -565,13 +615,13 @@ pub async fn fetch_update_preview(
     let products = xup_fetch_products(&client, &host, &auth, task_control.as_ref()).await?;
     let selected_products = select_products_for_target(&target_path, &products);
     if selected_products.is_empty() {
-        return Err(anyhow!(
-            "x-updater did not return a product matching '{}'",
-            target_path.display()
-        ));
+        return Err(UserMsg::XUpdaterNoMatchingProduct
+            .error_with([target_path.display()])
+            .into());
     }
+    record_product_icon(&host, item_type, folder_name, &selected_products);
 
-    let preferred_channel = requested_channel(&options);
+    let preferred_channel = requested_channel(&options, &target_path);
     let mut available_channels = collect_available_channels(&selected_products);
     if available_channels.is_empty() {
         available_channels.push("stable".to_string());
@@ -616,6 +666,193 @@ pub async fn fetch_update_preview(
     Ok(preview)
 }
 
+fn channel_options(channel: Option<String>) -> AddonUpdateOptions {
+    AddonUpdateOptions {
+        use_beta: false,
+        include_liveries: true,
+        apply_blacklist: false,
+        rollback_on_failure: false,
+        parallel_downloads: None,
+        channel,
+        fresh_install: false,
+        preserve_liveries: true,
+        preserve_config_files: true,
+        chunked_download_enabled: None,
+        threads_per_task: None,
+        total_threads: None,
+        keep_backup_versions: None,
+    }
+}
+
+fn skunk_channel(
+    item_type: &str,
+    folder_name: &str,
+    channel: crate::skunk_updater::SkunkChannel,
+) -> AddonUpdateChannel {
+    let mut available_channels = vec!["stable".to_string()];
+    if channel.has_beta_config {
+        available_channels.push("beta".to_string());
+    }
+    AddonUpdateChannel {
+        provider: "manifest".to_string(),
+        item_type: item_type.to_string(),
+        folder_name: folder_name.to_string(),
+        channel: if channel.beta { "beta" } else { "stable" }.to_string(),
+        available_channels,
+        target_version: channel.remote_version,
+        stored_in_addon: true,
+    }
+}
+
+/// Channel an addon follows. SkunkCrafts addons are read from their cfg;
+/// X-Updater addons follow the channel stored for them and are asked for the
+/// channels their products offer.
+pub async fn get_update_channel(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+    task_control: Option<TaskControl>,
+    progress_callback: Option<AddonUpdateProgressCallback>,
+) -> Result<AddonUpdateChannel> {
+    let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
+    if target_path.join(SKUNK_CFG_FILE).exists() {
+        let channel =
+            crate::skunk_updater::read_update_channel(xplane_path, item_type, folder_name)?;
+        return Ok(skunk_channel(item_type, folder_name, channel));
+    }
+
+    let preview = fetch_update_preview(
+        xplane_path,
+        item_type,
+        folder_name,
+        channel_options(None),
+        None,
+        None,
+        task_control,
+        progress_callback,
+    )
+    .await?;
+    Ok(AddonUpdateChannel {
+        provider: preview.provider,
+        item_type: preview.item_type,
+        folder_name: preview.folder_name,
+        channel: preview.selected_channel,
+        available_channels: preview.available_channels,
+        target_version: preview.target_version,
+        stored_in_addon: false,
+    })
+}
+
+/// Switch an addon to `channel` once the remote confirmed it serves it.
+/// SkunkCrafts addons get the `beta|` line of their cfg rewritten; for
+/// X-Updater addons the channel is checked against the product's snapshots
+/// and stored in the backend settings, where later checks and updates read it.
+pub async fn set_update_channel(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+    channel: &str,
+    task_control: Option<TaskControl>,
+    progress_callback: Option<AddonUpdateProgressCallback>,
+) -> Result<AddonUpdateChannel> {
+    let channel = parse_channel(channel)
+        .ok_or_else(|| ApiError::validation(format!("Unknown update channel '{}'", channel)))?;
+    let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
+
+    if target_path.join(SKUNK_CFG_FILE).exists() {
+        if channel == "alpha" {
+            return Err(ApiError::validation("SkunkCrafts addons have no alpha channel").into());
+        }
+        emit_progress_event(
+            &progress_callback,
+            item_type,
+            folder_name,
+            "check",
+            "started",
+            0.0,
+            0,
+            0,
+            0,
+            0,
+            0.0,
+            None,
+            Some(format!("Checking the {} channel", channel)),
+        );
+        ensure_not_cancelled(task_control.as_ref(), "check")?;
+        let result = crate::skunk_updater::set_update_channel(
+            xplane_path,
+            item_type,
+            folder_name,
+            channel == "beta",
+        )
+        .await?;
+        emit_progress_event(
+            &progress_callback,
+            item_type,
+            folder_name,
+            "check",
+            "completed",
+            100.0,
+            1,
+            1,
+            0,
+            0,
+            0.0,
+            None,
+            Some(format!("Switched to the {} channel", channel)),
+        );
+        log_addon_info(format!(
+            "update channel set itemType={} folder={} channel={}",
+            item_type, folder_name, channel
+        ));
+        return Ok(skunk_channel(item_type, folder_name, result));
+    }
+
+    let preview = fetch_update_preview(
+        xplane_path,
+        item_type,
+        folder_name,
+        channel_options(Some(channel.to_string())),
+        None,
+        None,
+        task_control,
+        progress_callback,
+    )
+    .await?;
+    if !preview.available_channels.iter().any(|c| c == channel) {
+        return Err(ApiError::validation(format!(
+            "'{}' offers no {} channel (available: {})",
+            folder_name,
+            channel,
+            preview.available_channels.join(", ")
+        ))
+        .into());
+    }
+    let key = stored_channel_key(&target_path);
+    let stored = channel.to_string();
+    tokio::task::spawn_blocking(move || {
+        settings_store::update(|settings| {
+            settings.addon_update_channels.insert(key, stored);
+        })
+    })
+    .await
+    .map_err(|e| anyhow!("Settings task failed: {}", e))?
+    .map_err(|e| anyhow!(e))?;
+    log_addon_info(format!(
+        "update channel set itemType={} folder={} channel={}",
+        item_type, folder_name, channel
+    ));
+    Ok(AddonUpdateChannel {
+        provider: preview.provider,
+        item_type: preview.item_type,
+        folder_name: preview.folder_name,
+        channel: channel.to_string(),
+        available_channels: preview.available_channels,
+        target_version: preview.target_version,
+        stored_in_addon: false,
+    })
+}
+
 pub async fn execute_update(
     xplane_path: &Path,
     item_type: &str,
@@ -653,7 +890,7 @@ pub async fn execute_update(
         )
         .await;
     }
-    enforce_obfuscated_xupdater_lock(item_type, folder_name, &target_path)?;
+    ensure_supported_updater_profile(item_type, folder_name, &target_path)?;
 
     emit_progress_event(
         &progress_callback,
@@ -720,7 +957,8 @@ pub async fn execute_update(
         None,
     )
     .await?;
-    let plan = build_xupdater_plan(item_type, folder_name, &context)?;
+    let mut plan = build_xupdater_plan(item_type, folder_name, &context)?;
+    update_preflight::apply(&mut plan, &target_path, options.rollback_on_failure);
     log_addon_info(format!(
         "execute_update plan ready provider={} hasUpdate={} add={} replace={} delete={}",
         plan.provider,
@@ -730,6 +968,14 @@ pub async fn execute_update(
         plan.delete_files.len()
     ));
 
+    let mut target_path = target_path;
+    if let Some(preflight) = &plan.preflight {
+        preflight.ensure_passes()?;
+        if !preflight.long_paths.is_empty() {
+            target_path = update_preflight::long_path(&target_path);
+        }
+    }
+
     let mut rollback = RollbackState::new(options.rollback_on_failure)?;
     let client = build_http_client(30)?;
     let total_units = context.actions.len() as u64;
@@ -748,12 +994,42 @@ pub async fn execute_update(
             .fold(0u64, |acc, size| acc.saturating_add(size));
     }
     let processed_bytes = Arc::new(AtomicU64::new(0));
-    let mut processed_units = 0u64;
+    let processed_units = Arc::new(AtomicU64::new(0));
     let install_started = Instant::now();
-    let install_last_emit = Arc::new(Mutex::new(Instant::now() - Duration::from_secs(1)));
+    let install_last_emit = Arc::new(Mutex::new(Instant::now() - PROGRESS_EMIT_INTERVAL));
     let item_type_owned = item_type.to_string();
     let folder_name_owned = folder_name.to_string();
+    let retry_stats = RetryStats::default();
+
+    let keep_backups = options.keep_backup_versions.filter(|keep| *keep > 0);
+    let backup = match keep_backups {
+        Some(_) => {
+            let changes: Vec<(String, FileChange)> = context
+                .actions
+                .iter()
+                .map(|action| {
+                    let change = match action.kind {
+                        XActionKind::Delete => FileChange::Deleted,
+                        XActionKind::Add | XActionKind::Replace => FileChange::Replaced,
+                    };
+                    (action.rel_path.clone(), change)
+                })
+                .collect();
+            Some(
+                addon_backups::begin(
+                    item_type,
+                    folder_name,
+                    &target_path,
+                    &changes,
+                    context.local_version.clone(),
+                )
+                .map_err(|e| anyhow!("Failed to back up files before the update: {}", e))?,
+            )
+        }
+        None => None,
+    };
 
+    remove_partial_downloads(&target_path);
     let mut apply_result: Result<()> = Ok(());
     for action in &context.actions {
         if let Err(cancel_err) = ensure_not_cancelled(task_control.as_ref(), "install") {
@@ -764,91 +1040,101 @@ pub async fn execute_update(
             "apply action kind={:?} path={}",
             action.kind, action.rel_path
         ));
-        let step: Result<()> = match action.kind {
-            XActionKind::Delete => {
-                let destination = resolve_entry_path(&target_path, &action.rel_path)?;
-                if destination.exists() {
-                    rollback.backup_if_needed(&destination)?;
-                    remove_path(&destination)?;
+        // Failed steps go through the rollback below rather than returning early
+        let step: Result<()> = async {
+            match action.kind {
+                XActionKind::Delete => {
+                    let destination = resolve_entry_path(&target_path, &action.rel_path)?;
+                    if destination.exists() {
+                        rollback.backup_if_needed(&destination)?;
+                        remove_path(&destination)?;
+                    }
+                    Ok(())
                 }
-                Ok(())
-            }
-            XActionKind::Add | XActionKind::Replace => {
-                let download = action
-                    .download
-                    .as_ref()
-                    .ok_or_else(|| anyhow!("Missing download link for '{}'", action.rel_path))?;
-                log_addon_debug(format!(
-                    "download file url={} relPath={} expectedMd5={:?} expectedSize={:?}",
-                    download.url, download.rel_path, download.expected_md5, download.expected_size
-                ));
-                let chunk_callback: Arc<dyn Fn(u64) + Send + Sync> = {
-                    let processed_bytes = Arc::clone(&processed_bytes);
-                    let progress_callback = progress_callback.clone();
-                    let current_file = action.rel_path.clone();
-                    let item_type_owned = item_type_owned.clone();
-                    let folder_name_owned = folder_name_owned.clone();
-                    let install_last_emit = Arc::clone(&install_last_emit);
-                    Arc::new(move |delta| {
-                        let processed = processed_bytes.fetch_add(delta, Ordering::Relaxed) + delta;
-                        let elapsed = install_started.elapsed().as_secs_f64().max(0.001);
-                        let speed = processed as f64 / elapsed;
-                        let mut should_emit = true;
-                        if let Ok(mut guard) = install_last_emit.lock() {
-                            if guard.elapsed() < Duration::from_millis(120) {
-                                should_emit = false;
-                            } else {
-                                *guard = Instant::now();
+                XActionKind::Add | XActionKind::Replace => {
+                    let download = action.download.as_ref().ok_or_else(|| {
+                        anyhow!("Missing download link for '{}'", action.rel_path)
+                    })?;
+                    log_addon_debug(format!(
+                        "download file url={} relPath={} expectedMd5={:?} expectedSize={:?}",
+                        download.url,
+                        download.rel_path,
+                        download.expected_md5,
+                        download.expected_size
+                    ));
+                    let chunk_callback: Arc<dyn Fn(u64) + Send + Sync> = {
+                        let processed_bytes = Arc::clone(&processed_bytes);
+                        let processed_units = Arc::clone(&processed_units);
+                        let progress_callback = progress_callback.clone();
+                        let current_file = action.rel_path.clone();
+                        let item_type_owned = item_type_owned.clone();
+                        let folder_name_owned = folder_name_owned.clone();
+                        let install_last_emit = Arc::clone(&install_last_emit);
+                        Arc::new(move |delta| {
+                            let processed =
+                                processed_bytes.fetch_add(delta, Ordering::Relaxed) + delta;
+                            let elapsed = install_started.elapsed().as_secs_f64().max(0.001);
+                            let speed = processed as f64 / elapsed;
+                            let mut should_emit = true;
+                            if let Ok(mut guard) = install_last_emit.lock() {
+                                if guard.elapsed() < PROGRESS_EMIT_INTERVAL {
+                                    should_emit = false;
+                                } else {
+                                    *guard = Instant::now();
+                                }
                             }
-                        }
-                        if should_emit {
-                            let percentage = if total_download_bytes > 0 {
-                                (processed as f64 / total_download_bytes as f64) * 100.0
-                            } else {
-                                0.0
-                            };
-                            emit_progress_event(
-                                &progress_callback,
-                                &item_type_owned,
-                                &folder_name_owned,
-                                "install",
-                                "in_progress",
-                                percentage,
-                                0,
-                                0,
-                                processed,
-                                total_download_bytes,
-                                speed,
-                                Some(current_file.clone()),
-                                Some("Downloading".to_string()),
-                            );
-                        }
-                    })
-                };
-                let bytes = download_xupdater_file(
-                    &client,
-                    &context.auth,
-                    download,
-                    task_control.as_ref(),
-                    Some(chunk_callback),
-                )
-                .await?;
-                let destination = resolve_entry_path(&target_path, &action.rel_path)?;
-                if destination.exists() {
-                    rollback.backup_if_needed(&destination)?;
-                } else {
-                    rollback.record_created_path(&destination);
+                            if should_emit {
+                                let percentage = if total_download_bytes > 0 {
+                                    (processed as f64 / total_download_bytes as f64) * 100.0
+                                } else {
+                                    0.0
+                                };
+                                emit_progress_event(
+                                    &progress_callback,
+                                    &item_type_owned,
+                                    &folder_name_owned,
+                                    "install",
+                                    "in_progress",
+                                    percentage,
+                                    processed_units.load(Ordering::Relaxed),
+                                    total_units,
+                                    processed,
+                                    total_download_bytes,
+                                    speed,
+                                    Some(current_file.clone()),
+                                    Some("Downloading".to_string()),
+                                );
+                            }
+                        })
+                    };
+                    let downloaded = download_xupdater_file(
+                        &client,
+                        &context.auth,
+                        download,
+                        &target_path,
+                        task_control.as_ref(),
+                        Some(chunk_callback),
+                        &retry_stats,
+                    )
+                    .await?;
+                    let destination = resolve_entry_path(&target_path, &action.rel_path)?;
+                    if destination.exists() {
+                        rollback.backup_if_needed(&destination)?;
+                    } else {
+                        rollback.record_created_path(&destination);
+                    }
+                    persist_download(downloaded, &destination)?;
+                    log_addon_debug(format!(
+                        "write completed relPath={} destination={}",
+                        action.rel_path,
+                        destination.display()
+                    ));
+                    Ok(())
                 }
-                write_file_atomic(&destination, &bytes)?;
-                log_addon_debug(format!(
-                    "write completed relPath={} size={} destination={}",
-                    action.rel_path,
-                    bytes.len(),
-                    destination.display()
-                ));
-                Ok(())
             }
-        };
+        }
+        .await;
+
         if let Err(e) = step {
             log_addon_info(format!(
                 "apply action failed path={} error={}",
@@ -858,12 +1144,26 @@ pub async fn execute_update(
             break;
         }
 
-        processed_units = processed_units.saturating_add(1);
+        let units_done = processed_units.fetch_add(1, Ordering::Relaxed) + 1;
+        let throttled = install_last_emit
+            .lock()
+            .map(|mut guard| {
+                if guard.elapsed() < PROGRESS_EMIT_INTERVAL {
+                    true
+                } else {
+                    *guard = Instant::now();
+                    false
+                }
+            })
+            .unwrap_or(false);
+        if throttled {
+            continue;
+        }
         let processed = processed_bytes.load(Ordering::Relaxed);
         let percentage = if total_download_bytes > 0 {
             (processed as f64 / total_download_bytes as f64) * 100.0
         } else if total_units > 0 {
-            (processed_units as f64 / total_units as f64) * 100.0
+            (units_done as f64 / total_units as f64) * 100.0
         } else {
             100.0
         };
@@ -876,7 +1176,7 @@ pub async fn execute_update(
             "install",
             "in_progress",
             percentage,
-            processed_units,
+            units_done,
             total_units,
             processed,
             total_download_bytes,
@@ -898,7 +1198,7 @@ pub async fn execute_update(
                 "failed"
             },
             0.0,
-            processed_units,
+            processed_units.load(Ordering::Relaxed),
             total_units,
             processed_bytes.load(Ordering::Relaxed),
             total_download_bytes,
@@ -906,16 +1206,28 @@ pub async fn execute_update(
             None,
             Some(e.to_string()),
         );
+        let rollback_result = if options.rollback_on_failure {
+            rollback.rollback()
+        } else {
+            Ok(())
+        };
+        // Without a successful rollback the backup holds the only copy of the
+        // files the update replaced
+        if !options.rollback_on_failure || rollback_result.is_err() {
+            if let (Some(backup), Some(keep)) = (backup, keep_backups) {
+                if let Err(backup_err) = backup.finish(None, keep) {
+                    log_addon_info(format!("keeping the update backup failed: {}", backup_err));
+                }
+            }
+        }
         if options.rollback_on_failure {
-            if let Err(rollback_err) = rollback.rollback() {
-                return Err(anyhow!(
-                    "Update failed: {}. Rollback also failed: {}",
-                    e,
-                    rollback_err
-                ));
+            if let Err(rollback_err) = rollback_result {
+                return Err(UserMsg::AddonUpdateRollbackFailed
+                    .error_with([e.to_string(), rollback_err.to_string()])
+                    .into());
             }
         }
-        return Err(anyhow!("Update failed: {}", e));
+        return Err(UserMsg::AddonUpdateFailed.error_with([e]).into());
     }
 
     emit_progress_event(
@@ -934,6 +1246,12 @@ pub async fn execute_update(
         Some("Installation completed".to_string()),
     );
 
+    if let (Some(backup), Some(keep)) = (backup, keep_backups) {
+        if let Err(e) = backup.finish(plan.remote_version.clone(), keep) {
+            log_addon_info(format!("keeping the update backup failed: {}", e));
+        }
+    }
+
     let mut updated_files = 0usize;
     let mut deleted_files = 0usize;
     for action in &context.actions {
@@ -955,6 +1273,8 @@ pub async fn execute_update(
         deleted_files,
         skipped_files: plan.skip_files.len(),
         rollback_used: false,
+        files_retried: retry_stats.files_retried(),
+        total_retry_count: retry_stats.total_retry_count(),
     })
 }
 
@@ -966,12 +1286,10 @@ pub fn set_updater_credentials(
     license_key: &str,
 ) -> Result<()> {
     let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
-    enforce_obfuscated_xupdater_lock(item_type, folder_name, &target_path)?;
+    ensure_supported_updater_profile(item_type, folder_name, &target_path)?;
 
     if target_path.join(SKUNK_CFG_FILE).exists() {
-        return Err(anyhow!(
-            "This addon is using legacy updater metadata and does not support account credentials"
-        ));
+        return Err(UserMsg::LegacyUpdaterNoCredentials.error().into());
     }
 
     write_credentials_in_folder(&target_path, login, license_key)?;
@@ -984,12 +1302,13 @@ pub fn get_updater_credentials(
     folder_name: &str,
 ) -> Result<Option<AddonUpdaterCredentials>> {
     let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
-    enforce_obfuscated_xupdater_lock(item_type, folder_name, &target_path)?;
+    ensure_supported_updater_profile(item_type, folder_name, &target_path)?;
 
     if target_path.join(SKUNK_CFG_FILE).exists() {
         return Ok(None);
     }
 
+    let recovered_from_backup = recover_profile_from_backup(&target_path)?.is_some();
     let profile = match find_profile_in_folder(&target_path) {
         Some(profile) => profile,
         None => return Ok(None),
@@ -999,25 +1318,139 @@ pub fn get_updater_credentials(
         (Some(login), Some(license_key))
             if !login.trim().is_empty() && !license_key.trim().is_empty() =>
         {
-            Ok(Some(AddonUpdaterCredentials { login, license_key }))
+            Ok(Some(AddonUpdaterCredentials {
+                login,
+                license_key,
+                recovered_from_backup,
+            }))
         }
         _ => Ok(None),
     }
 }
 
+/// Find credentials left behind by the SkunkCrafts Updater or X-Updater in
+/// installed aircraft, plugins and scenery
+pub fn discover_existing_credentials(
+    xplane_path: &Path,
+) -> Result<Vec<DiscoveredUpdaterCredentials>> {
+    let mut folders: Vec<(&str, String)> = Vec::new();
+    if let Ok(data) = management_index::scan_aircraft(xplane_path, false) {
+        folders.extend(
+            data.entries
+                .into_iter()
+                .map(|a| ("aircraft", a.folder_name)),
+        );
+    }
+    if let Ok(data) = management_index::scan_plugins(xplane_path, false) {
+        folders.extend(data.entries.into_iter().map(|p| ("plugin", p.folder_name)));
+    }
+    if let Ok(read_dir) = fs::read_dir(xplane_path.join("Custom Scenery")) {
+        for entry in read_dir.flatten() {
+            let folder_name = entry.file_name().to_string_lossy().to_string();
+            if !folder_name.starts_with('.') && entry.path().is_dir() {
+                folders.push(("scenery", folder_name));
+            }
+        }
+    }
+
+    let mut discovered: Vec<DiscoveredUpdaterCredentials> = folders
+        .into_par_iter()
+        .filter_map(|(item_type, folder_name)| {
+            let target_path = resolve_target_path(xplane_path, item_type, &folder_name).ok()?;
+            let (provider, login, license_key) = read_existing_credentials(&target_path)?;
+            Some(DiscoveredUpdaterCredentials {
+                item_type: item_type.to_string(),
+                provider: provider.to_string(),
+                login: mask_secret(&login),
+                has_license_key: license_key.is_some(),
+                folder_name,
+            })
+        })
+        .collect();
+    discovered.sort_by(|a, b| {
+        a.item_type.cmp(&b.item_type).then_with(|| {
+            a.folder_name
+                .to_lowercase()
+                .cmp(&b.folder_name.to_lowercase())
+        })
+    });
+
+    log_addon_info(format!(
+        "Discovered existing updater credentials for {} addons",
+        discovered.len()
+    ));
+    Ok(discovered)
+}
+
+/// Save the credentials `discover_existing_credentials` reports for the
+/// `source_*` addon as the updater credentials of the `item_type` /
+/// `folder_name` addon, without the secret passing through the UI
+pub fn import_discovered_credentials(
+    xplane_path: &Path,
+    source_item_type: &str,
+    source_folder_name: &str,
+    item_type: &str,
+    folder_name: &str,
+) -> Result<()> {
+    let source_path = resolve_target_path(xplane_path, source_item_type, source_folder_name)?;
+    let (provider, login, license_key) = read_existing_credentials(&source_path)
+        .ok_or_else(|| UserMsg::NoUpdaterCredentials.error())?;
+    let license_key = license_key
+        .ok_or_else(|| anyhow!("The existing {} credentials have no license key", provider))?;
+
+    log_addon_info(format!(
+        "Importing {} credentials of {}:{} for {}:{} login={}",
+        provider,
+        source_item_type,
+        source_folder_name,
+        item_type,
+        folder_name,
+        mask_secret(&login)
+    ));
+    set_updater_credentials(xplane_path, item_type, folder_name, &login, &license_key)
+}
+
+/// Login and license key stored by whichever updater manages the folder
+fn read_existing_credentials(folder: &Path) -> Option<(&'static str, String, Option<String>)> {
+    let skunk_cfg = folder.join(SKUNK_CFG_FILE);
+    let (provider, profile) = if skunk_cfg.is_file() {
+        ("skunkcrafts", parse_profile_cfg(&skunk_cfg)?)
+    } else {
+        ("x-updater", find_profile_in_folder(folder)?)
+    };
+
+    let login = profile.login.filter(|v| !v.trim().is_empty())?;
+    let license_key = profile.license_key.filter(|v| !v.trim().is_empty());
+    Some((provider, login, license_key))
+}
+
+pub fn validate_updater_profile(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+) -> Result<ProfileValidation> {
+    let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
+    Ok(validate_profile_in_folder(&target_path))
+}
+
 pub fn get_target_disk_space(
     xplane_path: &Path,
     item_type: &str,
     folder_name: &str,
 ) -> Result<AddonDiskSpaceInfo> {
     let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
-    let free_bytes = fs2::available_space(&target_path).with_context(|| {
+    disk_space_at(&target_path)
+}
+
+/// Free and total space of the volume holding `target_path`
+pub fn disk_space_at(target_path: &Path) -> Result<AddonDiskSpaceInfo> {
+    let free_bytes = fs2::available_space(target_path).with_context(|| {
         format!(
             "Failed to read free disk space for '{}'",
             target_path.display()
         )
     })?;
-    let total_bytes = fs2::total_space(&target_path).with_context(|| {
+    let total_bytes = fs2::total_space(target_path).with_context(|| {
         format!(
             "Failed to read total disk space for '{}'",
             target_path.display()
@@ -1039,6 +1472,7 @@ fn build_xupdater_plan(
     let mut replace_files = Vec::new();
     let mut delete_files = Vec::new();
     let mut estimated_download_bytes = 0u64;
+    let mut file_details = Vec::new();
 
     for action in &context.actions {
         match action.kind {
@@ -1047,6 +1481,13 @@ fn build_xupdater_plan(
             XActionKind::Delete => delete_files.push(action.rel_path.clone()),
         }
         estimated_download_bytes = estimated_download_bytes.saturating_add(action.estimated_bytes);
+        let download = action.download.as_ref();
+        file_details.push(PlannedFileInfo {
+            path: action.rel_path.clone(),
+            size: download.and_then(|d| d.expected_size),
+            md5: download.and_then(|d| d.expected_md5.clone()),
+            previous_md5: action.previous_md5.clone(),
+        });
     }
 
     Ok(AddonUpdatePlan {
@@ -1066,8 +1507,11 @@ fn build_xupdater_plan(
         replace_files,
         delete_files,
         skip_files: Vec::new(),
+        skipped: Vec::new(),
         warnings: context.warnings.clone(),
         has_beta_config: false,
+        file_details,
+        preflight: None,
     })
 }
 
@@ -1087,12 +1531,8 @@ async fn prepare_xupdater_context(
         options.apply_blacklist,
         options.parallel_downloads
     ));
-    let profile = find_profile_in_folder(target_path).ok_or_else(|| {
-        anyhow!(
-            "No x-updater profile was found in '{}'",
-            target_path.display()
-        )
-    })?;
+    let profile = find_profile_in_folder(target_path)
+        .ok_or_else(|| UserMsg::XUpdaterProfileNotFound.error_with([target_path.display()]))?;
     log_addon_debug(format!(
         "profile found host={} hasLogin={} hasKey={} packageVersion={:?} versionLabel={:?} ignoreCount={}",
         profile.host,
@@ -1110,11 +1550,11 @@ async fn prepare_xupdater_context(
     let login = profile
         .login
         .clone()
-        .ok_or_else(|| anyhow!("x-updater profile is missing login/username"))?;
+        .ok_or_else(|| UserMsg::XUpdaterMissingLogin.error())?;
     let license_key = profile
         .license_key
         .clone()
-        .ok_or_else(|| anyhow!("x-updater profile is missing license key"))?;
+        .ok_or_else(|| UserMsg::XUpdaterMissingLicenseKey.error())?;
 
     let host = profile.host.clone();
     let client = build_http_client(20)?;
@@ -1134,7 +1574,7 @@ async fn prepare_xupdater_context(
     ));
 
     if products.is_empty() {
-        return Err(anyhow!("x-updater returned no products for this account"));
+        return Err(UserMsg::XUpdaterNoProductsForAccount.error().into());
     }
 
     let selected_products = select_products_for_target(target_path, &products);
@@ -1144,16 +1584,16 @@ async fn prepare_xupdater_context(
         target_path.display()
     ));
     if selected_products.is_empty() {
-        return Err(anyhow!(
-            "x-updater did not return a product matching '{}'",
-            target_path.display()
-        ));
+        return Err(UserMsg::XUpdaterNoMatchingProduct
+            .error_with([target_path.display()])
+            .into());
     }
+    record_product_icon(&host, item_type, folder_name, &selected_products);
 
     let mut warnings = Vec::new();
     let mut action_map: BTreeMap<String, XAction> = BTreeMap::new();
     let mut remote_versions = Vec::new();
-    let selected_channel = requested_channel(options);
+    let selected_channel = requested_channel(options, target_path);
     let since = if options.fresh_install {
         0
     } else {
@@ -1296,6 +1736,17 @@ async fn prepare_xupdater_context(
     })
 }
 
+/// Remember the first selected product's icon for the Management UI
+fn record_product_icon(host: &str, item_type: &str, folder_name: &str, products: &[Value]) {
+    let icon_url = products
+        .iter()
+        .find_map(addon_icons::icon_href_from_product)
+        .and_then(|href| resolve_link(host, &href).ok());
+    if let Some(icon_url) = icon_url {
+        addon_icons::record_icon_url(item_type, folder_name, &icon_url);
+    }
+}
+
 fn merge_action(map: &mut BTreeMap<String, XAction>, next: XAction, warnings: &mut Vec<String>) {
     match map.get(&next.rel_path) {
         None => {
@@ -1744,18 +2195,35 @@ fn resolve_local_version_label(
     None
 }
 
-fn requested_channel(options: &AddonUpdateOptions) -> String {
-    let raw = options
-        .channel
-        .as_deref()
-        .map(|v| v.trim().to_lowercase())
-        .filter(|v| !v.is_empty());
+fn parse_channel(channel: &str) -> Option<&'static str> {
+    match channel.trim().to_lowercase().as_str() {
+        "alpha" => Some("alpha"),
+        "beta" => Some("beta"),
+        "stable" | "release" => Some("stable"),
+        _ => None,
+    }
+}
+
+/// Key of an X-Updater addon in `addon_update_channels`
+fn stored_channel_key(target_path: &Path) -> String {
+    target_path.to_string_lossy().into_owned()
+}
 
-    let normalized = match raw.as_deref() {
-        Some("alpha") => "alpha",
-        Some("beta") => "beta",
-        Some("stable") | Some("release") => "stable",
-        _ => {
+/// Channel stored for an X-Updater addon by [`set_update_channel`]
+fn stored_channel(target_path: &Path) -> Option<&'static str> {
+    settings_store::get()
+        .addon_update_channels
+        .get(&stored_channel_key(target_path))
+        .and_then(|channel| parse_channel(channel))
+}
+
+/// The channel asked for, else the one stored for the addon, else beta or
+/// stable as `use_beta` says
+fn requested_channel(options: &AddonUpdateOptions, target_path: &Path) -> String {
+    let asked = options.channel.as_deref().and_then(parse_channel);
+    let normalized = match asked.or_else(|| stored_channel(target_path)) {
+        Some(channel) => channel,
+        None => {
             if options.use_beta {
                 "beta"
             } else {
@@ -1864,27 +2332,36 @@ fn build_action_from_file(
     let state = parse_file_state(state_raw);
     let local_path = resolve_entry_path(target_path, &rel_path)?;
 
+    let expected_md5 = file
+        .get("hash")
+        .and_then(|v| v.as_str().or_else(|| v.get("md5").and_then(|x| x.as_str())))
+        .or_else(|| file.get("md5").and_then(|v| v.as_str()))
+        .or_else(|| file.get("mHash").and_then(|v| v.as_str()))
+        .or_else(|| file.get("file_md5").and_then(|v| v.as_str()))
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty());
+    let previous_md5 = file
+        .get("previous_hash")
+        .or_else(|| file.get("previousHash"))
+        .or_else(|| file.get("mPreviousHash"))
+        .and_then(|v| v.as_str().or_else(|| v.get("md5").and_then(|x| x.as_str())))
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty());
+
     if matches!(state, XActionKind::Delete) {
         if local_path.exists() {
+            // A deleted entry's hash describes the file being removed
             return Ok(Some(XAction {
                 rel_path,
                 kind: XActionKind::Delete,
                 download: None,
                 estimated_bytes: 0,
+                previous_md5: previous_md5.or(expected_md5),
             }));
         }
         return Ok(None);
     }
 
-    let expected_md5 = file
-        .get("hash")
-        .and_then(|v| v.as_str().or_else(|| v.get("md5").and_then(|x| x.as_str())))
-        .or_else(|| file.get("md5").and_then(|v| v.as_str()))
-        .or_else(|| file.get("mHash").and_then(|v| v.as_str()))
-        .or_else(|| file.get("file_md5").and_then(|v| v.as_str()))
-        .map(|v| v.trim().to_lowercase())
-        .filter(|v| !v.is_empty());
-
     if !fresh_install && local_path.exists() && local_path.is_file() {
         if let Some(expected) = expected_md5.as_ref() {
             if let Ok(local_md5) = md5_for_file(&local_path, task_control) {
@@ -1954,6 +2431,7 @@ fn build_action_from_file(
             expected_size,
         }),
         estimated_bytes,
+        previous_md5,
     }))
 }
 
@@ -2115,11 +2593,9 @@ async fn xup_authenticate(
                 status,
                 preview_text(&body_text, 1200)
             ));
-            return Err(anyhow!(
-                "x-updater authentication failed: HTTP {} ({})",
-                status,
-                body_text.trim()
-            ));
+            return Err(UserMsg::XUpdaterAuthFailed
+                .error_with([status.to_string(), body_text.trim().to_string()])
+                .into());
         }
 
         // Fallback: successful but unusual code/path; continue trying.
@@ -2827,17 +3303,145 @@ fn compact_error_snippet(input: &str) -> String {
     out.trim().to_string()
 }
 
+/// Resumed requests tried per strategy after the response stream breaks off
+const MAX_DOWNLOAD_RESUMES: u32 = 3;
+
+/// First bytes of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// File name prefix of download staging files in the addon folder
+const PARTIAL_DOWNLOAD_PREFIX: &str = ".xfm_part_";
+
+/// A file being downloaded into a staging file inside the addon folder, hashed
+/// as it arrives. The staging file is removed when this is dropped, so a failed
+/// or cancelled download leaves nothing behind.
+struct PartialDownload {
+    writer: BufWriter<NamedTempFile>,
+    hasher: Md5Context,
+    received: u64,
+    head: Vec<u8>,
+}
+
+impl PartialDownload {
+    fn new(staging_dir: &Path) -> Result<Self> {
+        let file = tempfile::Builder::new()
+            .prefix(PARTIAL_DOWNLOAD_PREFIX)
+            .tempfile_in(staging_dir)
+            .with_context(|| {
+                format!(
+                    "Failed to create download file in '{}'",
+                    staging_dir.display()
+                )
+            })?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            hasher: Md5Context::new(),
+            received: 0,
+            head: Vec::with_capacity(GZIP_MAGIC.len()),
+        })
+    }
+
+    /// Whether the received bytes start like a gzip stream
+    fn is_gzip(&self) -> bool {
+        self.head == GZIP_MAGIC
+    }
+
+    fn append(&mut self, chunk: &[u8]) -> Result<()> {
+        self.writer
+            .write_all(chunk)
+            .context("Failed to write downloaded data")?;
+        self.hasher.consume(chunk);
+        let missing = GZIP_MAGIC.len() - self.head.len();
+        self.head
+            .extend_from_slice(&chunk[..missing.min(chunk.len())]);
+        self.received += chunk.len() as u64;
+        Ok(())
+    }
+
+    /// Drop everything received so far, for servers that answered a resumed
+    /// request with the whole file
+    fn restart(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        let file = self.writer.get_mut().as_file_mut();
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        self.hasher = Md5Context::new();
+        self.received = 0;
+        self.head.clear();
+        Ok(())
+    }
+
+    /// The finished staging file and the MD5 of its contents
+    fn finish(self) -> Result<(NamedTempFile, String)> {
+        let file = self
+            .writer
+            .into_inner()
+            .map_err(|e| anyhow!("Failed to write downloaded data: {}", e.error()))?;
+        Ok((file, format!("{:x}", self.hasher.compute())))
+    }
+}
+
+/// Remove staging files an earlier update left in `staging_dir`. A dropped
+/// download removes its own, but not when the app exits mid-download.
+fn remove_partial_downloads(staging_dir: &Path) {
+    let Ok(entries) = fs::read_dir(staging_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let is_partial = entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(PARTIAL_DOWNLOAD_PREFIX);
+        if !is_partial || !entry.file_type().is_ok_and(|t| t.is_file()) {
+            continue;
+        }
+        match fs::remove_file(entry.path()) {
+            Ok(()) => log_addon_debug(format!(
+                "removed stale download file path={}",
+                entry.path().display()
+            )),
+            Err(e) => log_addon_debug(format!(
+                "failed to remove stale download file path={} error={}",
+                entry.path().display(),
+                e
+            )),
+        }
+    }
+}
+
+/// `future`'s output, or the cancel error as soon as `task_control` is
+/// cancelled, so a stalled request or read doesn't hold up a cancel
+async fn unless_cancelled<T>(
+    future: impl std::future::Future<Output = T>,
+    task_control: Option<&TaskControl>,
+) -> Result<T> {
+    tokio::select! {
+        output = future => Ok(output),
+        () = download_retry::cancelled(task_control) => {
+            Err(anyhow!("Addon update install cancelled by user"))
+        }
+    }
+}
+
+/// Download a file into a staging file in `staging_dir`, verified against the
+/// expected MD5 and size. Interrupted streams are resumed with a Range request
+/// when the server supports it.
 async fn download_xupdater_file(
     client: &reqwest::Client,
     auth: &XAuth,
     task: &XDownloadTask,
+    staging_dir: &Path,
     task_control: Option<&TaskControl>,
     chunk_callback: Option<Arc<dyn Fn(u64) + Send + Sync>>,
-) -> Result<Vec<u8>> {
+    retry_stats: &RetryStats,
+) -> Result<NamedTempFile> {
     ensure_not_cancelled(task_control, "install")?;
     let strategies = build_xup_request_strategies(auth);
+    let policy = RetryPolicy::default();
+    let mut retried = 0;
     let mut attempts = Vec::new();
     let mut last_error: Option<anyhow::Error> = None;
+    let mut partial = PartialDownload::new(staging_dir)?;
     log_addon_info(format!(
         "download start url={} relPath={} strategyCount={}",
         task.url,
@@ -2845,122 +3449,212 @@ async fn download_xupdater_file(
         strategies.len()
     ));
 
-    for strategy in strategies {
-        ensure_not_cancelled(task_control, "install")?;
-        let request = client
-            .get(&task.url)
-            .header("Accept", "application/octet-stream");
-        let request = apply_xup_request_strategy(request, auth, &strategy);
-        log_addon_debug(format!(
-            "download send url={} relPath={} strategy={}",
-            task.url,
-            task.rel_path,
-            strategy_log_label(&strategy)
-        ));
-
-        let response = match request.send().await {
-            Ok(response) => response,
-            Err(err) => {
-                attempts.push(format!("{}:ERR", strategy.label));
-                log_addon_debug(format!(
-                    "download transport error url={} strategy={} error={}",
-                    task.url,
-                    strategy_log_label(&strategy),
-                    err
-                ));
-                last_error = Some(anyhow!("Failed to download '{}': {}", task.url, err));
-                continue;
+    'strategies: for strategy in strategies {
+        let mut resumes = 0;
+        let mut retries = 0;
+        loop {
+            ensure_not_cancelled(task_control, "install")?;
+            let mut request = client
+                .get(&task.url)
+                .header("Accept", "application/octet-stream");
+            if partial.received > 0 {
+                request = request.header(RANGE, format!("bytes={}-", partial.received));
             }
-        };
-
-        let status = response.status();
-        if !status.is_success() {
-            let body_text = response.text().await.unwrap_or_default();
-            let snippet = compact_error_snippet(&body_text);
-            attempts.push(format!("{}:{}:{}", strategy.label, status, snippet));
+            let request = apply_xup_request_strategy(request, auth, &strategy);
             log_addon_debug(format!(
-                "download non-success url={} strategy={} status={} body='{}'",
+                "download send url={} relPath={} strategy={} offset={}",
                 task.url,
+                task.rel_path,
                 strategy_log_label(&strategy),
-                status,
-                preview_text(&body_text, 1200)
+                partial.received
             ));
-            last_error = Some(anyhow!(
-                "Download failed for '{}': HTTP {}{}",
-                task.url,
-                status,
-                if snippet.is_empty() {
-                    String::new()
-                } else {
-                    format!(" ({})", snippet)
+
+            let host_permit = download_retry::acquire_host_permit(&task.url).await?;
+            let response = match unless_cancelled(request.send(), task_control).await? {
+                Ok(response) => response,
+                Err(err) => {
+                    log_addon_debug(format!(
+                        "download transport error url={} strategy={} error={}",
+                        task.url,
+                        strategy_log_label(&strategy),
+                        err
+                    ));
+                    let failure = download_retry::transport_failure(err, &task.url);
+                    if failure.retryable && retries + 1 < policy.max_attempts {
+                        drop(host_permit);
+                        retries += 1;
+                        retried += 1;
+                        let delay = policy.delay(retries, None);
+                        download_retry::log_retry(
+                            &task.url,
+                            retries,
+                            &policy,
+                            delay,
+                            &failure.error,
+                            LOG_CTX,
+                        );
+                        download_retry::sleep_unless_cancelled(delay, task_control).await?;
+                        continue;
+                    }
+                    attempts.push(format!("{}:ERR", strategy.label));
+                    last_error = Some(failure.error);
+                    break;
                 }
-            ));
-            continue;
-        }
+            };
 
-        let mut raw = Vec::new();
-        let mut stream = response.bytes_stream();
-        let mut stream_failed = None;
-        while let Some(next) = stream.next().await {
-            if let Err(err) = ensure_not_cancelled(task_control, "install") {
-                return Err(err);
+            let status = response.status();
+            if !status.is_success() {
+                let failure = download_retry::status_failure(status, response.headers(), &task.url);
+                let body_text = unless_cancelled(response.text(), task_control)
+                    .await?
+                    .unwrap_or_default();
+                let snippet = compact_error_snippet(&body_text);
+                attempts.push(format!("{}:{}:{}", strategy.label, status, snippet));
+                log_addon_debug(format!(
+                    "download non-success url={} strategy={} status={} body='{}'",
+                    task.url,
+                    strategy_log_label(&strategy),
+                    status,
+                    preview_text(&body_text, 1200)
+                ));
+                if failure.retryable && retries + 1 < policy.max_attempts {
+                    drop(host_permit);
+                    retries += 1;
+                    retried += 1;
+                    let delay = policy.delay(retries, failure.retry_after);
+                    download_retry::log_retry(
+                        &task.url,
+                        retries,
+                        &policy,
+                        delay,
+                        &failure.error,
+                        LOG_CTX,
+                    );
+                    download_retry::sleep_unless_cancelled(delay, task_control).await?;
+                    continue;
+                }
+                last_error = Some(anyhow!(
+                    "{}{}",
+                    failure.error,
+                    if snippet.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", snippet)
+                    }
+                ));
+                // A missing file stays missing with other credentials
+                if status == StatusCode::NOT_FOUND {
+                    break 'strategies;
+                }
+                break;
+            }
+            if partial.received > 0 && status != StatusCode::PARTIAL_CONTENT {
+                log_addon_debug(format!(
+                    "download resume not supported relPath={} status={}; restarting",
+                    task.rel_path, status
+                ));
+                partial.restart()?;
             }
-            match next {
-                Ok(chunk) => {
-                    if !chunk.is_empty() {
-                        raw.extend_from_slice(&chunk);
+
+            let mut stream = response.bytes_stream();
+            let mut stream_failed = None;
+            while let Some(next) = unless_cancelled(stream.next(), task_control).await? {
+                ensure_not_cancelled(task_control, "install")?;
+                match next {
+                    Ok(chunk) => {
+                        if chunk.is_empty() {
+                            continue;
+                        }
+                        partial.append(&chunk)?;
                         if let Some(cb) = chunk_callback.as_ref() {
                             cb(chunk.len() as u64);
                         }
+                        // Only a gzip-encoded payload may differ from the listed size
+                        if let Some(expected) = task.expected_size.filter(|&size| size > 0) {
+                            if partial.received > expected && !partial.is_gzip() {
+                                return Err(anyhow!(
+                                    "Size mismatch for '{}': expected {}, got more than that",
+                                    task.rel_path,
+                                    expected
+                                ));
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        stream_failed = Some(err);
+                        break;
                     }
-                }
-                Err(err) => {
-                    stream_failed = Some(err);
-                    break;
                 }
             }
-        }
 
-        if let Some(err) = stream_failed {
-            attempts.push(format!("{}:READERR", strategy.label));
-            log_addon_debug(format!(
-                "download read error url={} strategy={} error={}",
-                task.url,
-                strategy_log_label(&strategy),
-                err
-            ));
-            last_error = Some(anyhow!(
-                "Failed to read response for '{}': {}",
-                task.url,
-                err
-            ));
-            continue;
-        }
-
-        match validate_download_payload(task, &raw) {
-            Ok(data) => {
-                log_addon_info(format!(
-                    "download success relPath={} strategy={} rawBytes={} finalBytes={}",
-                    task.rel_path,
-                    strategy_log_label(&strategy),
-                    raw.len(),
-                    data.len()
-                ));
-                return Ok(data);
-            }
-            Err(err) => {
-                attempts.push(format!("{}:VERIFYERR", strategy.label));
+            if let Some(err) = stream_failed {
                 log_addon_debug(format!(
-                    "download verify error relPath={} strategy={} error={}",
-                    task.rel_path,
+                    "download read error url={} strategy={} received={} error={}",
+                    task.url,
                     strategy_log_label(&strategy),
+                    partial.received,
                     err
                 ));
-                last_error = Some(err);
+                drop(host_permit);
+                if partial.received > 0 && resumes < MAX_DOWNLOAD_RESUMES {
+                    resumes += 1;
+                    retried += 1;
+                    continue;
+                }
+                if download_retry::is_retryable_transport_error(&err)
+                    && retries + 1 < policy.max_attempts
+                {
+                    retries += 1;
+                    retried += 1;
+                    let delay = policy.delay(retries, None);
+                    let error = anyhow!("Failed to read response for '{}': {}", task.url, err);
+                    download_retry::log_retry(&task.url, retries, &policy, delay, &error, LOG_CTX);
+                    download_retry::sleep_unless_cancelled(delay, task_control).await?;
+                    continue;
+                }
+                attempts.push(format!("{}:READERR", strategy.label));
+                last_error = Some(anyhow!(
+                    "Failed to read response for '{}': {}",
+                    task.url,
+                    err
+                ));
+                break;
             }
+
+            let raw_bytes = partial.received;
+            match verify_download(task, partial, staging_dir, task_control) {
+                Ok(file) => {
+                    log_addon_info(format!(
+                        "download success relPath={} strategy={} rawBytes={} resumes={} retries={}",
+                        task.rel_path,
+                        strategy_log_label(&strategy),
+                        raw_bytes,
+                        resumes,
+                        retries
+                    ));
+                    retry_stats.record(retried);
+                    return Ok(file);
+                }
+                Err(err) => {
+                    if is_cancelled_error(&err) {
+                        return Err(err);
+                    }
+                    attempts.push(format!("{}:VERIFYERR", strategy.label));
+                    log_addon_debug(format!(
+                        "download verify error relPath={} strategy={} error={}",
+                        task.rel_path,
+                        strategy_log_label(&strategy),
+                        err
+                    ));
+                    last_error = Some(err);
+                    partial = PartialDownload::new(staging_dir)?;
+                }
+            }
+            break;
         }
     }
 
+    retry_stats.record(retried);
     if attempts.is_empty() {
         return Err(last_error.unwrap_or_else(|| anyhow!("Download failed for '{}'", task.url)));
     }
@@ -2974,61 +3668,86 @@ async fn download_xupdater_file(
     ))
 }
 
-fn validate_download_payload(task: &XDownloadTask, raw: &[u8]) -> Result<Vec<u8>> {
-    let raw_size = raw.len() as u64;
-    let mut data = raw.to_vec();
+/// Check a finished download against the expected MD5 and size. Some servers
+/// send files gzip-encoded; those are decoded when only the decoded file matches.
+fn verify_download(
+    task: &XDownloadTask,
+    partial: PartialDownload,
+    staging_dir: &Path,
+    task_control: Option<&TaskControl>,
+) -> Result<NamedTempFile> {
+    let raw_size = partial.received;
+    let is_gzip = partial.is_gzip();
+    let (raw_file, raw_md5) = partial.finish()?;
+    let mut file = raw_file;
+    let mut size = raw_size;
 
     if let Some(expected) = task.expected_md5.as_ref() {
-        let raw_md5 = md5_for_bytes(raw);
         if !raw_md5.eq_ignore_ascii_case(expected) {
-            if let Ok(decoded) = try_gzip_decode(raw) {
-                let decoded_md5 = md5_for_bytes(&decoded);
-                if decoded_md5.eq_ignore_ascii_case(expected) {
-                    data = decoded;
-                } else {
-                    return Err(anyhow!(
-                        "MD5 mismatch for '{}': expected {}, got {}",
-                        task.rel_path,
-                        expected,
-                        raw_md5
-                    ));
-                }
-            } else {
-                return Err(anyhow!(
+            let mismatch = || {
+                anyhow!(
                     "MD5 mismatch for '{}': expected {}, got {}",
                     task.rel_path,
                     expected,
                     raw_md5
-                ));
+                )
+            };
+            if !is_gzip {
+                return Err(mismatch());
+            }
+            let decoded = match gzip_decode_file(file.path(), staging_dir, task_control) {
+                Ok(decoded) => decoded,
+                Err(err) if is_cancelled_error(&err) => return Err(err),
+                Err(_) => return Err(mismatch()),
+            };
+            let (decoded_file, decoded_md5) = decoded.finish()?;
+            if !decoded_md5.eq_ignore_ascii_case(expected) {
+                return Err(mismatch());
             }
+            size = fs::metadata(decoded_file.path())?.len();
+            file = decoded_file;
         }
     }
 
     if let Some(expected_size) = task.expected_size {
-        let actual = data.len() as u64;
-        if expected_size > 0 && actual != expected_size && raw_size != expected_size {
+        if expected_size > 0 && size != expected_size && raw_size != expected_size {
             return Err(anyhow!(
                 "Size mismatch for '{}': expected {}, got {}",
                 task.rel_path,
                 expected_size,
-                actual
+                size
             ));
         }
     }
 
-    Ok(data)
+    Ok(file)
 }
 
-fn try_gzip_decode(data: &[u8]) -> Result<Vec<u8>> {
-    let mut decoder = GzDecoder::new(data);
-    let mut out = Vec::new();
-    decoder
-        .read_to_end(&mut out)
-        .context("Failed to decode gzip stream")?;
-    Ok(out)
+/// Decode a gzip file into a new staging file, hashing the decoded bytes
+fn gzip_decode_file(
+    path: &Path,
+    staging_dir: &Path,
+    task_control: Option<&TaskControl>,
+) -> Result<PartialDownload> {
+    let source =
+        fs::File::open(path).with_context(|| format!("Failed to open '{}'", path.display()))?;
+    let mut decoder = GzDecoder::new(BufReader::new(source));
+    let mut decoded = PartialDownload::new(staging_dir)?;
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        ensure_not_cancelled(task_control, "install")?;
+        let n = decoder
+            .read(&mut buffer)
+            .context("Failed to decode gzip stream")?;
+        if n == 0 {
+            break;
+        }
+        decoded.append(&buffer[..n])?;
+    }
+    Ok(decoded)
 }
 
-fn md5_for_file(path: &Path, task_control: Option<&TaskControl>) -> Result<String> {
+pub(crate) fn md5_for_file(path: &Path, task_control: Option<&TaskControl>) -> Result<String> {
     let mut file = fs::File::open(path)
         .with_context(|| format!("Failed to open '{}' for MD5", path.display()))?;
     let mut hasher = Md5Context::new();
@@ -3044,11 +3763,11 @@ fn md5_for_file(path: &Path, task_control: Option<&TaskControl>) -> Result<Strin
     Ok(format!("{:x}", hasher.compute()))
 }
 
-fn md5_for_bytes(data: &[u8]) -> String {
-    format!("{:x}", md5::compute(data))
-}
-
-fn resolve_target_path(xplane_path: &Path, item_type: &str, folder_name: &str) -> Result<PathBuf> {
+pub(crate) fn resolve_target_path(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+) -> Result<PathBuf> {
     if folder_name.trim().is_empty() {
         return Err(anyhow!("Folder name cannot be empty"));
     }
@@ -3067,10 +3786,9 @@ fn resolve_target_path(xplane_path: &Path, item_type: &str, folder_name: &str) -
 
     let target_path = base_path.join(normalized_folder);
     if !target_path.exists() {
-        return Err(anyhow!(
-            "Target path does not exist: {}",
-            target_path.display()
-        ));
+        return Err(UserMsg::AddonTargetNotFound
+            .error_with([target_path.display()])
+            .into());
     }
 
     crate::path_utils::validate_child_path(&base_path, &target_path)
@@ -3114,25 +3832,16 @@ fn resolve_entry_path(target_root: &Path, rel_path: &str) -> Result<PathBuf> {
     Ok(target_root.join(normalized.replace('/', std::path::MAIN_SEPARATOR_STR)))
 }
 
-fn write_file_atomic(destination: &Path, data: &[u8]) -> Result<()> {
+/// Move a verified download into place, replacing any existing file
+fn persist_download(file: NamedTempFile, destination: &Path) -> Result<()> {
     if let Some(parent) = destination.parent() {
         fs::create_dir_all(parent)?;
     }
-
-    let file_name = destination
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| anyhow!("Invalid destination file name: {}", destination.display()))?;
-    let temp_name = format!(".xfm_tmp_{}_{}", std::process::id(), file_name);
-    let temp_path = destination.with_file_name(temp_name);
-
-    fs::write(&temp_path, data)
-        .with_context(|| format!("Failed to write temporary file '{}'", temp_path.display()))?;
-    fs::rename(&temp_path, destination).with_context(|| {
-        format!(
-            "Failed to replace '{}' with '{}'",
+    file.persist(destination).map_err(|e| {
+        anyhow!(
+            "Failed to replace '{}' with downloaded file: {}",
             destination.display(),
-            temp_path.display()
+            e.error
         )
     })?;
     Ok(())
@@ -3185,9 +3894,9 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
 }
 
 fn build_http_client(timeout_secs: u64) -> Result<reqwest::Client> {
-    reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(timeout_secs))
-        .user_agent("XFast Manager")
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+    http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(timeout))
         .build()
         .context("Failed to build HTTP client")
 }
@@ -3239,3 +3948,167 @@ fn ensure_since_parameter(url: &str, since: i64) -> Result<String> {
     }
     Ok(parsed.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn download_task(data: &[u8]) -> XDownloadTask {
+        XDownloadTask {
+            rel_path: "objects/demo.obj".to_string(),
+            url: "https://example.com/demo.obj".to_string(),
+            expected_md5: Some(format!("{:x}", md5::compute(data))),
+            expected_size: Some(data.len() as u64),
+        }
+    }
+
+    #[test]
+    fn partial_download_restart_discards_received_bytes() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let mut partial = PartialDownload::new(temp.path()).expect("partial");
+        partial.append(b"stale").expect("append");
+        partial.restart().expect("restart");
+        partial.append(b"fresh data").expect("append");
+
+        let file = verify_download(&download_task(b"fresh data"), partial, temp.path(), None)
+            .expect("verified");
+        assert_eq!(fs::read(file.path()).expect("read"), b"fresh data");
+    }
+
+    #[test]
+    fn verify_download_decodes_gzip_and_rejects_mismatches() {
+        use flate2::write::GzEncoder;
+
+        let temp = tempdir().expect("failed to create tempdir");
+        let data = b"OBJ file contents".repeat(64);
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&data).expect("encode");
+        let encoded = encoder.finish().expect("encode");
+
+        let mut partial = PartialDownload::new(temp.path()).expect("partial");
+        partial.append(&encoded).expect("append");
+        assert!(partial.is_gzip());
+        let file = verify_download(&download_task(&data), partial, temp.path(), None)
+            .expect("gzip payload should verify");
+        assert_eq!(fs::read(file.path()).expect("read"), data);
+
+        let mut partial = PartialDownload::new(temp.path()).expect("partial");
+        partial.append(b"something else").expect("append");
+        let err = verify_download(&download_task(&data), partial, temp.path(), None)
+            .expect_err("mismatch");
+        assert!(err.to_string().contains("MD5 mismatch"));
+
+        // Staging files never outlive a failed verification
+        drop(file);
+        assert_eq!(fs::read_dir(temp.path()).expect("list").count(), 0);
+    }
+
+    #[test]
+    fn discovered_credentials_never_expose_secrets() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let plane = temp.path().join("Aircraft").join("DemoPlane");
+        fs::create_dir_all(&plane).expect("failed to create aircraft");
+        fs::write(plane.join("DemoPlane.acf"), "acf").expect("failed to write acf");
+        fs::write(
+            plane.join(SKUNK_CFG_FILE),
+            "module|https://example.com/demo\nlogin|pilot@example.com\nlicense|SECRET-123456\n",
+        )
+        .expect("failed to write skunk cfg");
+
+        let plugin = temp
+            .path()
+            .join("Resources")
+            .join("plugins")
+            .join("DemoPlugin");
+        fs::create_dir_all(plugin.join("64")).expect("failed to create plugin");
+        fs::write(plugin.join("64").join("win.xpl"), "xpl").expect("failed to write xpl");
+        fs::write(plugin.join("x-updater.cfg"), "login=someone@example.com\n")
+            .expect("failed to write profile");
+
+        fs::create_dir_all(temp.path().join("Custom Scenery").join("KDEMO"))
+            .expect("failed to create scenery");
+
+        let discovered =
+            discover_existing_credentials(temp.path()).expect("discovery should succeed");
+        assert_eq!(discovered.len(), 2);
+
+        assert_eq!(discovered[0].item_type, "aircraft");
+        assert_eq!(discovered[0].provider, "skunkcrafts");
+        assert_eq!(discovered[0].login, "pil***com");
+        assert!(discovered[0].has_license_key);
+
+        assert_eq!(discovered[1].item_type, "plugin");
+        assert_eq!(discovered[1].provider, "x-updater");
+        assert!(!discovered[1].has_license_key);
+
+        let json = serde_json::to_string(&discovered).expect("serialize");
+        assert!(!json.contains("SECRET"));
+        assert!(!json.contains("pilot@example.com"));
+    }
+
+    #[test]
+    fn xupdater_profile_without_skunk_metadata_is_a_structured_error() {
+        let temp = tempdir().expect("failed to create temp dir");
+        let plugin = temp
+            .path()
+            .join("Resources")
+            .join("plugins")
+            .join("DemoPlugin");
+        fs::create_dir_all(&plugin).expect("failed to create plugin");
+        fs::write(plugin.join("x-updater.cfg"), "login=someone@example.com\n")
+            .expect("failed to write profile");
+
+        let err = get_updater_credentials(temp.path(), "plugin", "DemoPlugin")
+            .expect_err("x-updater only profiles are unsupported");
+        let api_err = ApiError::from(err);
+        assert_eq!(api_err.code, ApiErrorCode::UpdaterProfileUnsupported);
+        assert_eq!(
+            api_err.message_key.as_deref(),
+            Some("xupdater_profile_unsupported")
+        );
+        assert_eq!(api_err.message_args, vec!["DemoPlugin".to_string()]);
+        assert!(api_err.details.is_some());
+        assert!(!api_err.message.contains('['));
+
+        fs::write(plugin.join(SKUNK_CFG_FILE), "module|https://example.com\n")
+            .expect("failed to write skunk config");
+        assert!(get_updater_credentials(temp.path(), "plugin", "DemoPlugin").is_ok());
+    }
+
+    #[test]
+    fn discovered_credentials_are_imported_into_another_addon() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let source = temp.path().join("Aircraft").join("DemoPlane");
+        fs::create_dir_all(&source).expect("failed to create aircraft");
+        fs::write(
+            source.join(SKUNK_CFG_FILE),
+            "module|https://example.com/demo\nlogin|pilot@example.com\nlicense|SECRET-123456\n",
+        )
+        .expect("failed to write skunk cfg");
+        let target = temp.path().join("Aircraft").join("NewPlane");
+        fs::create_dir_all(&target).expect("failed to create aircraft");
+
+        import_discovered_credentials(temp.path(), "aircraft", "DemoPlane", "aircraft", "NewPlane")
+            .expect("import should succeed");
+
+        let (provider, login, license_key) =
+            read_existing_credentials(&target).expect("credentials written");
+        assert_eq!(provider, "x-updater");
+        assert_eq!(login, "pilot@example.com");
+        assert_eq!(license_key.as_deref(), Some("SECRET-123456"));
+        // The source keeps its own config untouched
+        assert!(fs::read_to_string(source.join(SKUNK_CFG_FILE))
+            .expect("read skunk cfg")
+            .contains("license|SECRET-123456"));
+
+        let missing = import_discovered_credentials(
+            temp.path(),
+            "aircraft",
+            "NewPlane2",
+            "aircraft",
+            "NewPlane",
+        );
+        assert!(missing.is_err());
+    }
+}
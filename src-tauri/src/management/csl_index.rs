@@ -10,6 +10,8 @@ use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use crate::error::{ApiError, ApiErrorCode};
+use crate::http_client;
+use crate::path_utils::{create_directory_link, is_link, remove_directory_link};
 
 macro_rules! csl_debug {
     ($($arg:tt)*) => {
@@ -113,9 +115,8 @@ fn with_request_tracking_headers(
 }
 
 fn build_http_client(timeout: std::time::Duration) -> Result<reqwest::Client, ApiError> {
-    reqwest::Client::builder()
-        .timeout(timeout)
-        .user_agent("XFast Manager")
+    http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(timeout))
         .build()
         .map_err(|e| {
             ApiError::new(
@@ -1533,58 +1534,6 @@ fn collect_scan_paths(xplane_path: &str, custom_paths: &[String]) -> (Vec<CslPat
     (paths, local_path_strings)
 }
 
-// ============================================================================
-// Directory Link Helpers (Junction on Windows, Symlink on Unix)
-// ============================================================================
-
-#[cfg(windows)]
-fn create_directory_link(target: &Path, link_path: &Path) -> Result<(), String> {
-    junction::create(target, link_path).map_err(|e| {
-        format!(
-            "Failed to create junction {} -> {}: {}",
-            link_path.display(),
-            target.display(),
-            e
-        )
-    })
-}
-
-#[cfg(unix)]
-fn create_directory_link(target: &Path, link_path: &Path) -> Result<(), String> {
-    std::os::unix::fs::symlink(target, link_path).map_err(|e| {
-        format!(
-            "Failed to create symlink {} -> {}: {}",
-            link_path.display(),
-            target.display(),
-            e
-        )
-    })
-}
-
-#[cfg(windows)]
-fn is_link(path: &Path) -> bool {
-    junction::exists(path).unwrap_or(false)
-}
-
-#[cfg(unix)]
-fn is_link(path: &Path) -> bool {
-    path.symlink_metadata()
-        .map(|m| m.file_type().is_symlink())
-        .unwrap_or(false)
-}
-
-#[cfg(windows)]
-fn remove_directory_link(link_path: &Path) -> Result<(), String> {
-    junction::delete(link_path)
-        .map_err(|e| format!("Failed to remove junction {}: {}", link_path.display(), e))
-}
-
-#[cfg(unix)]
-fn remove_directory_link(link_path: &Path) -> Result<(), String> {
-    std::fs::remove_file(link_path)
-        .map_err(|e| format!("Failed to remove symlink {}: {}", link_path.display(), e))
-}
-
 /// Collect all detected CSL directory paths, excluding the canonical path itself.
 fn collect_link_targets(xplane_path: &Path, custom_paths: &[String]) -> Vec<PathBuf> {
     let canonical = xplane_path.join(CSL_CANONICAL_REL);
@@ -19,6 +19,7 @@ use crate::analyzer::Analyzer;
 use crate::database::entities::gateway_installs;
 use crate::database::DatabaseState;
 use crate::error::{ApiError, ApiErrorCode, ApiResult};
+use crate::http_client;
 use crate::installer::Installer;
 use crate::livery_patterns;
 use crate::logger;
@@ -33,14 +34,16 @@ const AIRPORT_DIRECTORY_CACHE_TTL: Duration = Duration::from_secs(30 * 60);
 const UPDATE_CHECK_CONCURRENCY: usize = 4;
 const EXTERNAL_AIRPORT_CONFLICT_DETAIL: &str = "gateway_external_airport_conflict";
 
-static GATEWAY_HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
-    reqwest::Client::builder()
-        .user_agent("XFast-Manager Gateway/1.0")
-        .connect_timeout(Duration::from_secs(15))
-        .timeout(Duration::from_secs(60))
+static GATEWAY_HTTP_CLIENT: http_client::CachedClient =
+    http_client::CachedClient::new(build_gateway_client);
+
+fn build_gateway_client() -> reqwest::Client {
+    http_client::client_builder("XFast-Manager Gateway/1.0")
+        .connect_timeout(http_client::timeout(Duration::from_secs(15)))
+        .timeout(http_client::timeout(Duration::from_secs(60)))
         .build()
         .expect("gateway client")
-});
+}
 
 static AIRPORT_DIRECTORY_CACHE: LazyLock<RwLock<Option<GatewayAirportDirectoryCache>>> =
     LazyLock::new(|| RwLock::new(None));
@@ -219,7 +222,7 @@ pub async fn gateway_list_installed(
     xplane_path: String,
 ) -> ApiResult<Vec<GatewayInstalledAirport>> {
     let xplane_root = validate_xplane_root(&xplane_path)?;
-    let xplane_key = normalize_xplane_key(&xplane_root);
+    let xplane_key = path_utils::normalize_xplane_key(&xplane_root);
     list_installed_internal(&db.get(), &xplane_root, &xplane_key).await
 }
 
@@ -229,7 +232,7 @@ pub async fn gateway_check_updates(
     xplane_path: String,
 ) -> ApiResult<Vec<GatewayInstalledAirport>> {
     let xplane_root = validate_xplane_root(&xplane_path)?;
-    let xplane_key = normalize_xplane_key(&xplane_root);
+    let xplane_key = path_utils::normalize_xplane_key(&xplane_root);
     let installed = list_installed_internal(&db.get(), &xplane_root, &xplane_key).await?;
     if installed.is_empty() {
         return Ok(installed);
@@ -306,16 +309,6 @@ fn validate_xplane_root(xplane_path: &str) -> ApiResult<PathBuf> {
     Ok(path)
 }
 
-fn normalize_xplane_key(path: &Path) -> String {
-    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-    let text = canonical.to_string_lossy().replace('\\', "/");
-    if cfg!(windows) {
-        text.to_ascii_lowercase()
-    } else {
-        text
-    }
-}
-
 fn normalize_icao(icao: &str) -> ApiResult<String> {
     let normalized = icao.trim().to_ascii_uppercase();
     if normalized.is_empty() {
@@ -376,6 +369,7 @@ async fn fetch_gateway_scenery_payload(scenery_id: i64) -> ApiResult<Value> {
 
 async fn fetch_gateway_json(url: &str) -> ApiResult<Value> {
     let response = GATEWAY_HTTP_CLIENT
+        .client()
         .get(url)
         .send()
         .await
@@ -461,7 +455,7 @@ async fn gateway_install_scenery_impl(
         ignore_external_conflict,
     } = request;
     let xplane_root = validate_xplane_root(&xplane_path)?;
-    let xplane_key = normalize_xplane_key(&xplane_root);
+    let xplane_key = path_utils::normalize_xplane_key(&xplane_root);
     let airport_icao = normalize_icao(&icao)?;
     let skip_external_conflict_check = ignore_external_conflict.unwrap_or(false);
 
@@ -685,7 +679,7 @@ pub async fn gateway_uninstall_airport(
     airport_icao: String,
 ) -> ApiResult<()> {
     let xplane_root = validate_xplane_root(&xplane_path)?;
-    let xplane_key = normalize_xplane_key(&xplane_root);
+    let xplane_key = path_utils::normalize_xplane_key(&xplane_root);
     let airport_icao = normalize_icao(&airport_icao)?;
     let conn = db.get();
 
@@ -1186,7 +1180,7 @@ async fn find_conflicting_airport_from_index(
     xplane_root: &Path,
     airport_icao: &str,
 ) -> ApiResult<Option<String>> {
-    let xplane_key = normalize_xplane_key(xplane_root);
+    let xplane_key = path_utils::normalize_xplane_key(xplane_root);
     let managed_folders: HashSet<String> = gateway_installs::Entity::find()
         .filter(gateway_installs::Column::XplanePath.eq(xplane_key))
         .all(conn)
@@ -1226,7 +1220,7 @@ async fn find_conflicting_airport_from_folder_scan(
     xplane_root: &Path,
     airport_icao: &str,
 ) -> ApiResult<Option<String>> {
-    let xplane_key = normalize_xplane_key(xplane_root);
+    let xplane_key = path_utils::normalize_xplane_key(xplane_root);
     let managed_folders: HashSet<String> = gateway_installs::Entity::find()
         .filter(gateway_installs::Column::XplanePath.eq(xplane_key))
         .all(conn)
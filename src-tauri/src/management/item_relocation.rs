@@ -0,0 +1,673 @@
+//! Relocate installed aircraft/scenery to another drive
+//!
+//! The folder is copied to the destination, verified (file count plus sampled
+//! SHA-256 hashes), and the original is replaced by a directory link (junction on
+//! Windows, symlink elsewhere) so X-Plane keeps finding it at the old path.
+//! Links inside the folder are recreated at the destination, with relative
+//! links that lead out of the folder pointed at their absolute target; those
+//! that can't be (file symlinks on Windows need extra rights) are listed in the
+//! result.
+//! Any failure before the link is in place leaves the original untouched and
+//! removes whatever was copied.
+
+use crate::addon_updater::{AddonUpdateProgressCallback, AddonUpdateProgressEvent};
+use crate::hash_collector::HashCollector;
+use crate::logger;
+use crate::models::RelocationResult;
+use crate::path_utils;
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+/// Files hashed on both sides after copying
+const VERIFY_SAMPLE_SIZE: usize = 32;
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(250);
+/// Suffix for the original folder while the link is being put in place
+const RELOCATING_SUFFIX: &str = ".xfast-relocating";
+
+struct ProgressReporter<'a> {
+    callback: Option<&'a AddonUpdateProgressCallback>,
+    item_type: &'a str,
+    folder_name: &'a str,
+    started_at: Instant,
+    last_emit_at: Option<Instant>,
+}
+
+impl ProgressReporter<'_> {
+    fn emit(
+        &mut self,
+        stage: &str,
+        status: &str,
+        processed_bytes: u64,
+        total_bytes: u64,
+        current_file: Option<String>,
+        message: Option<String>,
+    ) {
+        let Some(cb) = self.callback else {
+            return;
+        };
+
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        let percentage = if status == "completed" {
+            100.0
+        } else if total_bytes > 0 {
+            processed_bytes as f64 / total_bytes as f64 * 100.0
+        } else {
+            0.0
+        };
+        cb(AddonUpdateProgressEvent {
+            item_type: self.item_type.to_string(),
+            folder_name: self.folder_name.to_string(),
+            stage: stage.to_string(),
+            status: status.to_string(),
+            percentage: percentage.clamp(0.0, 100.0),
+            processed_units: processed_bytes,
+            total_units: total_bytes,
+            processed_bytes,
+            total_bytes,
+            speed_bytes_per_sec: if elapsed > 0.0 {
+                processed_bytes as f64 / elapsed
+            } else {
+                0.0
+            },
+            current_file,
+            message,
+        });
+        self.last_emit_at = Some(Instant::now());
+    }
+
+    fn due(&self) -> bool {
+        self.last_emit_at
+            .map(|t| t.elapsed() >= PROGRESS_EMIT_INTERVAL)
+            .unwrap_or(true)
+    }
+}
+
+fn resolve_source_path(xplane_path: &Path, item_type: &str, folder_name: &str) -> Result<PathBuf> {
+    if folder_name.trim().is_empty() || folder_name.contains("..") {
+        return Err(anyhow!("Invalid folder name"));
+    }
+
+    let base_path = match item_type {
+        "aircraft" => xplane_path.join("Aircraft"),
+        "scenery" => xplane_path.join("Custom Scenery"),
+        _ => return Err(anyhow!("Relocation is not supported for {}", item_type)),
+    };
+    let source = base_path.join(folder_name);
+
+    if path_utils::is_link(&source) {
+        return Err(anyhow!("{} is already relocated", folder_name));
+    }
+    if !source.is_dir() {
+        return Err(anyhow!("Folder not found: {}", folder_name));
+    }
+
+    path_utils::validate_child_path(&base_path, &source)
+        .map_err(|e| anyhow!("Invalid path: {}", e))?;
+    Ok(source)
+}
+
+/// Canonical form of a path that may not exist yet: the deepest existing
+/// ancestor is canonicalized and the remaining components appended
+fn canonicalize_lenient(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest: Vec<&std::ffi::OsStr> = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name);
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+
+    let mut resolved = existing
+        .canonicalize()
+        .unwrap_or_else(|_| existing.to_path_buf());
+    for name in rest.into_iter().rev() {
+        resolved.push(name);
+    }
+    resolved
+}
+
+fn resolve_destination(
+    xplane_path: &Path,
+    source: &Path,
+    destination_root: &Path,
+) -> Result<(PathBuf, bool)> {
+    if !destination_root.is_absolute() {
+        return Err(anyhow!("Destination must be an absolute path"));
+    }
+
+    let xplane_canonical = canonicalize_lenient(xplane_path);
+    let root_canonical = canonicalize_lenient(destination_root);
+    if root_canonical.starts_with(&xplane_canonical) {
+        return Err(anyhow!(
+            "Destination must be outside the X-Plane folder ({})",
+            xplane_path.display()
+        ));
+    }
+
+    let leaf = source
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid source folder"))?;
+    let destination = destination_root.join(leaf);
+
+    // An empty folder (e.g. left by an earlier cancelled attempt) can be reused
+    if destination.exists() {
+        let is_empty_dir = destination.is_dir()
+            && fs::read_dir(&destination)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+        if !is_empty_dir {
+            return Err(anyhow!(
+                "Destination already exists and is not empty: {}",
+                destination.display()
+            ));
+        }
+        return Ok((destination, true));
+    }
+
+    Ok((destination, false))
+}
+
+/// Relative paths and sizes of every regular file under `root`
+fn list_files(root: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(root).follow_links(false) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            let relative = entry.path().strip_prefix(root)?.to_path_buf();
+            files.push((relative, entry.metadata()?.len()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Where the relative `link_target` of `link` ends up when it leads out of
+/// `root` on the way, resolved lexically. `None` for absolute targets and
+/// targets that stay inside `root`, which keep working once the folder moves.
+fn escaping_target(root: &Path, link: &Path, link_target: &Path) -> Option<PathBuf> {
+    if link_target.is_absolute() {
+        return None;
+    }
+    let mut resolved = link.parent()?.to_path_buf();
+    let mut escapes = false;
+    for component in link_target.components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            other => resolved.push(other),
+        }
+        escapes |= !resolved.starts_with(root);
+    }
+    escapes.then_some(resolved)
+}
+
+/// Recreate the link `link` at `copy`. Relative targets inside `root` point at
+/// the same place inside the moved folder; absolute ones keep resolving, as
+/// the original path stays reachable through the new link. Relative targets
+/// leading out of `root` are made absolute, as they would break otherwise.
+fn copy_link(root: &Path, link: &Path, copy: &Path) -> std::io::Result<()> {
+    let link_target = fs::read_link(link)?;
+    let link_target = escaping_target(root, link, &link_target).unwrap_or(link_target);
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&link_target, copy)
+    }
+
+    #[cfg(windows)]
+    {
+        if fs::metadata(link).is_ok_and(|m| m.is_dir()) {
+            // Junctions need no extra rights but an absolute target
+            let absolute_target = link
+                .parent()
+                .map(|parent| parent.join(&link_target))
+                .unwrap_or(link_target);
+            path_utils::create_directory_link(&absolute_target, copy).map_err(std::io::Error::other)
+        } else {
+            std::os::windows::fs::symlink_file(&link_target, copy)
+        }
+    }
+}
+
+/// Copy `source` into `destination`. Returns the links that couldn't be
+/// recreated, relative to `source`.
+fn copy_tree(
+    source: &Path,
+    destination: &Path,
+    total_bytes: u64,
+    progress: &mut ProgressReporter,
+) -> Result<Vec<String>> {
+    let mut copied_bytes = 0u64;
+    let mut skipped_links = Vec::new();
+
+    for entry in WalkDir::new(source).follow_links(false) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        let target = destination.join(relative);
+        let file_type = entry.file_type();
+
+        if file_type.is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), &target)
+                .map_err(|e| anyhow!("Failed to copy {}: {}", relative.display(), e))?;
+            copied_bytes += entry.metadata()?.len();
+
+            if progress.due() {
+                progress.emit(
+                    "copy",
+                    "in_progress",
+                    copied_bytes,
+                    total_bytes,
+                    Some(relative.to_string_lossy().to_string()),
+                    None,
+                );
+            }
+        } else if file_type.is_symlink() {
+            // The walk doesn't follow links, so their targets aren't copied twice
+            if let Err(e) = copy_link(source, entry.path(), &target) {
+                logger::log_info(
+                    &format!(
+                        "Skipping link during relocation: {} ({})",
+                        entry.path().display(),
+                        e
+                    ),
+                    Some("relocation"),
+                );
+                skipped_links.push(relative.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    Ok(skipped_links)
+}
+
+/// Evenly spaced sample of at most `VERIFY_SAMPLE_SIZE` files, always including the largest
+fn sample_for_verification(files: &[(PathBuf, u64)]) -> Vec<&Path> {
+    let mut sample: Vec<&Path> = Vec::new();
+    if files.is_empty() {
+        return sample;
+    }
+
+    let step = files.len().div_ceil(VERIFY_SAMPLE_SIZE).max(1);
+    sample.extend(files.iter().step_by(step).map(|(p, _)| p.as_path()));
+    if let Some((largest, _)) = files.iter().max_by_key(|(_, size)| *size) {
+        if !sample.contains(&largest.as_path()) {
+            sample.push(largest.as_path());
+        }
+    }
+    sample
+}
+
+fn verify_copy(source: &Path, destination: &Path, source_files: &[(PathBuf, u64)]) -> Result<()> {
+    let copied_files = list_files(destination)?;
+    if copied_files.len() != source_files.len() {
+        return Err(anyhow!(
+            "Copy verification failed: {} of {} files present at destination",
+            copied_files.len(),
+            source_files.len()
+        ));
+    }
+
+    let hasher = HashCollector::new();
+    for relative in sample_for_verification(source_files) {
+        let expected = hasher.compute_file_sha256(&source.join(relative))?;
+        let actual = hasher.compute_file_sha256(&destination.join(relative))?;
+        if expected != actual {
+            return Err(anyhow!(
+                "Copy verification failed: {} differs from the original",
+                relative.display()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Undo a partial copy. A destination folder that existed beforehand is emptied but kept.
+fn rollback_destination(destination: &Path, destination_existed: bool) {
+    let result = if destination_existed {
+        fs::read_dir(destination).and_then(|entries| {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                    fs::remove_dir_all(&path)?;
+                } else {
+                    fs::remove_file(&path)?;
+                }
+            }
+            Ok(())
+        })
+    } else {
+        fs::remove_dir_all(destination)
+    };
+
+    if let Err(e) = result {
+        logger::log_error(
+            &format!(
+                "Failed to clean up relocation destination {}: {}",
+                destination.display(),
+                e
+            ),
+            Some("relocation"),
+        );
+    }
+}
+
+/// Swap the original folder for a link to `destination`. The original is moved
+/// aside first so a failed link can be undone with a rename.
+fn replace_with_link(source: &Path, destination: &Path) -> Result<Option<PathBuf>> {
+    let mut aside_name = source
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid source folder"))?
+        .to_os_string();
+    aside_name.push(RELOCATING_SUFFIX);
+    let aside = source.with_file_name(aside_name);
+    if aside.exists() {
+        return Err(anyhow!(
+            "Leftover from an earlier relocation found: {}",
+            aside.display()
+        ));
+    }
+
+    fs::rename(source, &aside)
+        .map_err(|e| anyhow!("Failed to move original folder aside: {}", e))?;
+
+    if let Err(e) = path_utils::create_directory_link(destination, source) {
+        if let Err(restore_err) = fs::rename(&aside, source) {
+            logger::log_error(
+                &format!(
+                    "Failed to restore {} after link error: {}",
+                    source.display(),
+                    restore_err
+                ),
+                Some("relocation"),
+            );
+        }
+        return Err(anyhow!(e));
+    }
+
+    // The relocation itself succeeded; a failed delete only leaves a copy behind
+    match fs::remove_dir_all(&aside) {
+        Ok(()) => Ok(None),
+        Err(e) => {
+            logger::log_error(
+                &format!(
+                    "Relocated, but failed to delete original {}: {}",
+                    aside.display(),
+                    e
+                ),
+                Some("relocation"),
+            );
+            Ok(Some(aside))
+        }
+    }
+}
+
+/// Move an aircraft or scenery folder to `destination_root` and leave a link behind
+pub fn relocate_management_item(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+    destination_root: &Path,
+    progress_callback: Option<AddonUpdateProgressCallback>,
+) -> Result<RelocationResult> {
+    let mut progress = ProgressReporter {
+        callback: progress_callback.as_ref(),
+        item_type,
+        folder_name,
+        started_at: Instant::now(),
+        last_emit_at: None,
+    };
+
+    let result = relocate_inner(
+        xplane_path,
+        item_type,
+        folder_name,
+        destination_root,
+        &mut progress,
+    );
+    match &result {
+        Ok(outcome) => {
+            progress.emit(
+                "link",
+                "completed",
+                outcome.total_bytes,
+                outcome.total_bytes,
+                None,
+                None,
+            );
+            logger::log_info(
+                &format!(
+                    "Relocated {} '{}' to {} ({} files)",
+                    item_type, folder_name, outcome.destination_path, outcome.file_count
+                ),
+                Some("relocation"),
+            );
+        }
+        Err(e) => {
+            progress.emit("copy", "failed", 0, 0, None, Some(e.to_string()));
+            logger::log_error(
+                &format!("Failed to relocate {} '{}': {}", item_type, folder_name, e),
+                Some("relocation"),
+            );
+        }
+    }
+    result
+}
+
+fn relocate_inner(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+    destination_root: &Path,
+    progress: &mut ProgressReporter,
+) -> Result<RelocationResult> {
+    let source = resolve_source_path(xplane_path, item_type, folder_name)?;
+    let (destination, destination_existed) =
+        resolve_destination(xplane_path, &source, destination_root)?;
+
+    let source_files = list_files(&source)?;
+    let total_bytes: u64 = source_files.iter().map(|(_, size)| size).sum();
+    progress.emit("copy", "in_progress", 0, total_bytes, None, None);
+
+    fs::create_dir_all(&destination)?;
+    let copied = copy_tree(&source, &destination, total_bytes, progress).and_then(|skipped| {
+        progress.emit(
+            "verify",
+            "in_progress",
+            total_bytes,
+            total_bytes,
+            None,
+            None,
+        );
+        verify_copy(&source, &destination, &source_files).map(|()| skipped)
+    });
+    let skipped_links = match copied {
+        Ok(skipped_links) => skipped_links,
+        Err(e) => {
+            rollback_destination(&destination, destination_existed);
+            return Err(e);
+        }
+    };
+
+    progress.emit("link", "in_progress", total_bytes, total_bytes, None, None);
+    let leftover = match replace_with_link(&source, &destination) {
+        Ok(leftover) => leftover,
+        Err(e) => {
+            rollback_destination(&destination, destination_existed);
+            return Err(e);
+        }
+    };
+
+    Ok(RelocationResult {
+        source_path: source.to_string_lossy().to_string(),
+        destination_path: destination.to_string_lossy().to_string(),
+        file_count: source_files.len(),
+        total_bytes,
+        leftover_path: leftover.map(|p| p.to_string_lossy().to_string()),
+        skipped_links,
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn make_aircraft(xplane: &Path, name: &str) -> PathBuf {
+        let dir = xplane.join("Aircraft").join(name);
+        fs::create_dir_all(dir.join("liveries")).expect("failed to create aircraft");
+        fs::write(dir.join("plane.acf"), "acf").expect("failed to write acf");
+        fs::write(dir.join("liveries").join("paint.png"), vec![7u8; 4096])
+            .expect("failed to write livery");
+        dir
+    }
+
+    #[test]
+    fn relocates_and_leaves_working_link() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let xplane = temp.path().join("X-Plane 12");
+        let source = make_aircraft(&xplane, "Cessna");
+        let external = temp.path().join("External");
+
+        let result = relocate_management_item(&xplane, "aircraft", "Cessna", &external, None)
+            .expect("relocation should succeed");
+
+        assert_eq!(result.file_count, 2);
+        assert!(result.leftover_path.is_none());
+        assert!(path_utils::is_link(&source));
+        assert_eq!(
+            fs::read_to_string(source.join("plane.acf")).expect("link should resolve"),
+            "acf"
+        );
+        assert!(external
+            .join("Cessna")
+            .join("liveries")
+            .join("paint.png")
+            .is_file());
+        assert!(!xplane
+            .join("Aircraft")
+            .join("Cessna.xfast-relocating")
+            .exists());
+    }
+
+    #[test]
+    fn relocation_recreates_links_inside_the_folder() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let xplane = temp.path().join("X-Plane 12");
+        let source = make_aircraft(&xplane, "Cessna");
+        std::os::unix::fs::symlink("liveries", source.join("paints"))
+            .expect("failed to create link");
+        let external = temp.path().join("External");
+
+        let result = relocate_management_item(&xplane, "aircraft", "Cessna", &external, None)
+            .expect("relocation should succeed");
+
+        assert!(result.skipped_links.is_empty());
+        let copied_link = external.join("Cessna").join("paints");
+        assert!(fs::symlink_metadata(&copied_link)
+            .expect("link should be copied")
+            .file_type()
+            .is_symlink());
+        assert!(source.join("paints").join("paint.png").is_file());
+    }
+
+    #[test]
+    fn relocation_points_links_leaving_the_folder_at_their_absolute_target() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let xplane = temp.path().join("X-Plane 12");
+        let source = make_aircraft(&xplane, "Cessna");
+        let shared = xplane.join("Aircraft").join("Shared");
+        fs::create_dir_all(&shared).expect("failed to create shared folder");
+        fs::write(shared.join("sounds.txt"), "sounds").expect("failed to write shared file");
+        std::os::unix::fs::symlink("../Shared", source.join("shared"))
+            .expect("failed to create link");
+        let external = temp.path().join("External");
+
+        let result = relocate_management_item(&xplane, "aircraft", "Cessna", &external, None)
+            .expect("relocation should succeed");
+
+        assert!(result.skipped_links.is_empty());
+        let copied_link = external.join("Cessna").join("shared");
+        assert_eq!(
+            fs::read_link(&copied_link).expect("link should be copied"),
+            shared
+        );
+        assert_eq!(
+            fs::read_to_string(copied_link.join("sounds.txt")).expect("link should resolve"),
+            "sounds"
+        );
+        assert_eq!(
+            fs::read_to_string(source.join("shared").join("sounds.txt"))
+                .expect("link should resolve through the old path"),
+            "sounds"
+        );
+    }
+
+    #[test]
+    fn refuses_destination_inside_xplane_and_non_empty_destination() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let xplane = temp.path().join("X-Plane 12");
+        let source = make_aircraft(&xplane, "Cessna");
+
+        let inside = relocate_management_item(
+            &xplane,
+            "aircraft",
+            "Cessna",
+            &xplane.join("Relocated"),
+            None,
+        );
+        assert!(inside.is_err());
+
+        let external = temp.path().join("External");
+        fs::create_dir_all(external.join("Cessna")).expect("failed to create destination");
+        fs::write(external.join("Cessna").join("other.txt"), "x").expect("failed to write");
+        let occupied = relocate_management_item(&xplane, "aircraft", "Cessna", &external, None);
+        assert!(occupied.is_err());
+
+        assert!(!path_utils::is_link(&source));
+        assert!(source.join("plane.acf").is_file());
+        assert!(external.join("Cessna").join("other.txt").is_file());
+    }
+
+    #[test]
+    fn failed_copy_rolls_back_destination_and_keeps_original() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let xplane = temp.path().join("X-Plane 12");
+        let source = make_aircraft(&xplane, "Cessna");
+        let external = temp.path().join("External");
+        let destination = external.join("Cessna");
+        // A file where the copy needs a directory makes the walk fail midway
+        fs::create_dir_all(&destination).expect("failed to create destination");
+        fs::write(destination.join("liveries"), "blocker").expect("failed to write blocker");
+
+        // Non-empty destination is refused up front, so exercise the copy directly
+        let mut progress = ProgressReporter {
+            callback: None,
+            item_type: "aircraft",
+            folder_name: "Cessna",
+            started_at: Instant::now(),
+            last_emit_at: None,
+        };
+        let copied = copy_tree(&source, &destination, 0, &mut progress);
+        assert!(copied.is_err());
+
+        rollback_destination(&destination, true);
+        assert!(destination.is_dir());
+        assert_eq!(
+            fs::read_dir(&destination).expect("failed to read").count(),
+            0
+        );
+        assert!(source.join("liveries").join("paint.png").is_file());
+    }
+}
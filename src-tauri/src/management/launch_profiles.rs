@@ -0,0 +1,301 @@
+//! Named X-Plane launch argument profiles
+//!
+//! Profiles are stored in the `launch_profiles` table and exported together with
+//! addon presets. Arguments are checked against the known X-Plane command-line
+//! flags in `data/xplane_launch_flags.json`; unknown or malformed flags produce
+//! warnings but are still saved, since X-Plane adds flags between releases.
+
+use crate::database::entities::launch_profiles;
+use crate::error::{ApiError, ApiResult};
+use crate::logger;
+use crate::models::{LaunchProfile, LaunchProfileSaveResult};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A command-line flag X-Plane is known to accept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownLaunchFlag {
+    /// Flag including the leading dashes, e.g. `--no_vr`
+    pub flag: String,
+    /// Whether the flag is written as `--flag=value`
+    pub takes_value: bool,
+    pub description: String,
+}
+
+/// JSON schema of data/xplane_launch_flags.json
+#[derive(Debug, Deserialize)]
+struct LaunchFlagsData {
+    #[allow(dead_code)]
+    version: u32,
+    #[allow(dead_code)]
+    updated: String,
+    flags: Vec<KnownLaunchFlag>,
+}
+
+static KNOWN_FLAGS: LazyLock<Vec<KnownLaunchFlag>> = LazyLock::new(load_embedded_flags);
+
+fn load_embedded_flags() -> Vec<KnownLaunchFlag> {
+    let embedded_json = include_str!("../../../data/xplane_launch_flags.json");
+
+    match serde_json::from_str::<LaunchFlagsData>(embedded_json) {
+        Ok(data) => data.flags,
+        Err(e) => {
+            logger::log_info(
+                &format!(
+                    "Failed to parse embedded data/xplane_launch_flags.json: {}, using empty fallback",
+                    e
+                ),
+                Some("launch_profiles"),
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// All known X-Plane command-line flags
+pub fn known_flags() -> &'static [KnownLaunchFlag] {
+    &KNOWN_FLAGS
+}
+
+/// Check launch arguments against the known flags table.
+/// Returns one human-readable warning per suspicious argument.
+pub fn validate_args(args: &[String]) -> Vec<String> {
+    validate_args_against(args, known_flags())
+}
+
+fn validate_args_against(args: &[String], flags: &[KnownLaunchFlag]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for arg in args {
+        if !arg.starts_with('-') {
+            warnings.push(format!("'{}' is not a flag", arg));
+            continue;
+        }
+
+        let (key, value) = match arg.split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (arg.as_str(), None),
+        };
+        match flags.iter().find(|f| f.flag.eq_ignore_ascii_case(key)) {
+            None => warnings.push(format!("Unknown flag '{}'", key)),
+            Some(flag) if flag.takes_value && value.is_none_or(str::is_empty) => {
+                warnings.push(format!("'{}' expects a value ({}=...)", key, flag.flag))
+            }
+            Some(flag) if !flag.takes_value && value.is_some() => {
+                warnings.push(format!("'{}' does not take a value", flag.flag))
+            }
+            Some(_) => {}
+        }
+    }
+    warnings
+}
+
+/// Trim arguments and drop empty ones
+fn normalize_args(args: Vec<String>) -> Vec<String> {
+    args.into_iter()
+        .map(|arg| arg.trim().to_string())
+        .filter(|arg| !arg.is_empty())
+        .collect()
+}
+
+fn normalize_name(name: &str) -> ApiResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ApiError::validation("Launch profile name cannot be empty"));
+    }
+    Ok(name.to_string())
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn profile_from_model(model: launch_profiles::Model) -> LaunchProfile {
+    LaunchProfile {
+        name: model.name,
+        args: serde_json::from_str(&model.args).unwrap_or_default(),
+        created_at: model.created_at,
+        updated_at: model.updated_at,
+    }
+}
+
+/// All launch profiles, ordered by name
+pub async fn list_profiles(conn: &DatabaseConnection) -> ApiResult<Vec<LaunchProfile>> {
+    let rows = launch_profiles::Entity::find()
+        .order_by_asc(launch_profiles::Column::Name)
+        .all(conn)
+        .await?;
+    Ok(rows.into_iter().map(profile_from_model).collect())
+}
+
+/// Look up a profile by name
+pub async fn get_profile(
+    conn: &DatabaseConnection,
+    name: &str,
+) -> ApiResult<Option<LaunchProfile>> {
+    let row = launch_profiles::Entity::find()
+        .filter(launch_profiles::Column::Name.eq(name.trim()))
+        .one(conn)
+        .await?;
+    Ok(row.map(profile_from_model))
+}
+
+/// Create a profile, or replace the arguments of an existing one with the same name
+pub async fn save_profile(
+    conn: &DatabaseConnection,
+    name: &str,
+    args: Vec<String>,
+) -> ApiResult<LaunchProfileSaveResult> {
+    let name = normalize_name(name)?;
+    let args = normalize_args(args);
+    let warnings = validate_args(&args);
+    let profile = upsert_profile(conn, &name, &args, unix_now()).await?;
+
+    if !warnings.is_empty() {
+        logger::log_info(
+            &format!(
+                "Warning: launch profile '{}' saved with {} unrecognized argument(s): {}",
+                name,
+                warnings.len(),
+                warnings.join("; ")
+            ),
+            Some("launch_profiles"),
+        );
+    }
+
+    Ok(LaunchProfileSaveResult { profile, warnings })
+}
+
+async fn upsert_profile(
+    conn: &DatabaseConnection,
+    name: &str,
+    args: &[String],
+    now: i64,
+) -> ApiResult<LaunchProfile> {
+    let args_json = serde_json::to_string(args)
+        .map_err(|e| ApiError::internal(format!("Failed to serialize arguments: {}", e)))?;
+
+    let existing = launch_profiles::Entity::find()
+        .filter(launch_profiles::Column::Name.eq(name))
+        .one(conn)
+        .await?;
+    let model = match existing {
+        Some(row) => {
+            let mut active: launch_profiles::ActiveModel = row.into();
+            active.args = Set(args_json);
+            active.updated_at = Set(now);
+            active.update(conn).await?
+        }
+        None => {
+            launch_profiles::ActiveModel {
+                id: ActiveValue::NotSet,
+                name: Set(name.to_string()),
+                args: Set(args_json),
+                created_at: Set(now),
+                updated_at: Set(now),
+            }
+            .insert(conn)
+            .await?
+        }
+    };
+    Ok(profile_from_model(model))
+}
+
+/// Delete a profile. Returns `false` if no profile had that name.
+pub async fn delete_profile(conn: &DatabaseConnection, name: &str) -> ApiResult<bool> {
+    let result = launch_profiles::Entity::delete_many()
+        .filter(launch_profiles::Column::Name.eq(name.trim()))
+        .exec(conn)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Store profiles from an imported preset file, replacing same-named profiles.
+/// Returns the number of profiles imported.
+pub async fn import_profiles(
+    conn: &DatabaseConnection,
+    profiles: Vec<LaunchProfile>,
+) -> ApiResult<usize> {
+    let now = unix_now();
+    let mut imported = 0;
+    for profile in profiles {
+        let Ok(name) = normalize_name(&profile.name) else {
+            continue;
+        };
+        upsert_profile(conn, &name, &normalize_args(profile.args), now).await?;
+        imported += 1;
+    }
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn embedded_flags_table_parses() {
+        assert!(known_flags().iter().any(|f| f.flag == "--no_vr"));
+    }
+
+    #[test]
+    fn unknown_and_malformed_flags_produce_warnings() {
+        let warnings = validate_args(&args(&[
+            "--no_vr",
+            "--FULL_RES_MENUS",
+            "--load_smo=Output/replays/approach.rep",
+            "--made_up_flag",
+            "--no_vr=1",
+            "--weather_seed",
+            "replay",
+        ]));
+
+        assert_eq!(
+            warnings,
+            vec![
+                "Unknown flag '--made_up_flag'".to_string(),
+                "'--no_vr' does not take a value".to_string(),
+                "'--weather_seed' expects a value (--weather_seed=...)".to_string(),
+                "'replay' is not a flag".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn profiles_are_saved_replaced_and_deleted_by_name() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        let saved = save_profile(&conn, " VR off ", args(&["--no_vr", " ", "--bogus"]))
+            .await
+            .unwrap();
+        assert_eq!(saved.profile.name, "VR off");
+        assert_eq!(saved.profile.args, args(&["--no_vr", "--bogus"]));
+        assert_eq!(saved.warnings.len(), 1);
+
+        save_profile(&conn, "VR off", args(&["--no_vr", "--full_res_menus"]))
+            .await
+            .unwrap();
+        let profiles = list_profiles(&conn).await.unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].args, args(&["--no_vr", "--full_res_menus"]));
+
+        assert!(save_profile(&conn, "  ", Vec::new()).await.is_err());
+
+        assert!(delete_profile(&conn, "VR off").await.unwrap());
+        assert!(!delete_profile(&conn, "VR off").await.unwrap());
+        assert!(get_profile(&conn, "VR off").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,311 @@
+//! FlyWithLua scripts duplicated between the global Scripts folder and aircraft
+//!
+//! Some aircraft bundle their own FlyWithLua copy with helper scripts (pushback,
+//! checklists) that users also install globally, so the script runs twice.
+//! Copies are grouped by file name and compared by content hash. Resolving a
+//! group keeps one copy and renames the others to `.xfml`, which FlyWithLua
+//! ignores, the same way the script manager disables scripts.
+
+use crate::logger;
+use crate::models::{LuaDuplicateGroup, LuaDuplicateResolution, LuaScriptLocation, LuaScriptScope};
+use crate::path_utils;
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Depth of `.../plugins/FlyWithLua*/Scripts` below `Aircraft/`, allowing one
+/// level of vendor folders
+const MAX_AIRCRAFT_SCRIPT_DEPTH: usize = 6;
+
+/// Aircraft subfolders that never contain plugins
+const SKIPPED_AIRCRAFT_DIRS: &[&str] = &["liveries", "objects", "cockpit_3d", "sounds"];
+
+fn global_scripts_dir(xplane_path: &Path) -> PathBuf {
+    xplane_path
+        .join("Resources")
+        .join("plugins")
+        .join("FlyWithLua")
+        .join("Scripts")
+}
+
+fn relative_string(xplane_path: &Path, path: &Path) -> Option<String> {
+    Some(
+        path.strip_prefix(xplane_path)
+            .ok()?
+            .to_string_lossy()
+            .replace('\\', "/"),
+    )
+}
+
+/// Enabled `.lua` files directly inside `dir`
+fn enabled_scripts_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read_dir
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|ft| ft.is_file()))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("lua"))
+        })
+        .collect()
+}
+
+/// `Scripts` folders of FlyWithLua copies bundled in aircraft, with the
+/// aircraft folder (relative to `Aircraft/`) each belongs to
+fn aircraft_script_dirs(xplane_path: &Path) -> Vec<(String, PathBuf)> {
+    let aircraft_root = xplane_path.join("Aircraft");
+    walkdir::WalkDir::new(&aircraft_root)
+        .max_depth(MAX_AIRCRAFT_SCRIPT_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            entry.file_type().is_dir() && !SKIPPED_AIRCRAFT_DIRS.contains(&name.as_str())
+        })
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let scripts = entry.path();
+            let fwl = scripts.parent()?;
+            let plugins = fwl.parent()?;
+            let aircraft = plugins.parent()?;
+            let is_fwl_scripts = entry.file_name().eq_ignore_ascii_case("Scripts")
+                && fwl
+                    .file_name()?
+                    .to_string_lossy()
+                    .to_ascii_lowercase()
+                    .starts_with("flywithlua")
+                && plugins.file_name()?.eq_ignore_ascii_case("plugins");
+            if !is_fwl_scripts {
+                return None;
+            }
+            let aircraft_folder = relative_string(&aircraft_root, aircraft)?;
+            (!aircraft_folder.is_empty()).then(|| (aircraft_folder, scripts.to_path_buf()))
+        })
+        .collect()
+}
+
+/// SHA-256 of a whole file, plus its size
+fn hash_script(path: &Path) -> Result<(String, u64)> {
+    let mut hasher = Sha256::new();
+    let size = std::io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok((format!("{:x}", hasher.finalize()), size))
+}
+
+/// Find scripts enabled in the global Scripts folder that are also enabled in
+/// an aircraft's bundled FlyWithLua
+pub fn find_duplicate_lua_scripts(xplane_path: &Path) -> Result<Vec<LuaDuplicateGroup>> {
+    let global_dir = global_scripts_dir(xplane_path);
+    let mut by_name: BTreeMap<String, Vec<(LuaScriptScope, Option<String>, PathBuf)>> =
+        BTreeMap::new();
+
+    for path in enabled_scripts_in(&global_dir) {
+        if let Some(name) = path.file_name() {
+            by_name
+                .entry(name.to_string_lossy().to_lowercase())
+                .or_default()
+                .push((LuaScriptScope::Global, None, path));
+        }
+    }
+    if by_name.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    for (aircraft_folder, scripts_dir) in aircraft_script_dirs(xplane_path) {
+        for path in enabled_scripts_in(&scripts_dir) {
+            let Some(name) = path.file_name() else {
+                continue;
+            };
+            // Only names that also exist globally can collide, so only those get hashed
+            if let Some(copies) = by_name.get_mut(&name.to_string_lossy().to_lowercase()) {
+                copies.push((
+                    LuaScriptScope::Aircraft,
+                    Some(aircraft_folder.clone()),
+                    path,
+                ));
+            }
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (group_id, copies) in by_name {
+        if copies.len() < 2 {
+            continue;
+        }
+        let file_name = copies[0]
+            .2
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| group_id.clone());
+
+        let mut locations = Vec::with_capacity(copies.len());
+        for (scope, aircraft_folder, path) in copies {
+            let Some(relative_path) = relative_string(xplane_path, &path) else {
+                continue;
+            };
+            let (content_hash, size_bytes) = match hash_script(&path) {
+                Ok(hashed) => hashed,
+                Err(e) => {
+                    logger::log_error(
+                        &format!("Failed to hash script {}: {}", path.display(), e),
+                        Some("lua_duplicates"),
+                    );
+                    continue;
+                }
+            };
+            locations.push(LuaScriptLocation {
+                relative_path,
+                scope,
+                aircraft_folder,
+                size_bytes,
+                content_hash,
+            });
+        }
+        if locations.len() < 2 {
+            continue;
+        }
+
+        let identical = locations
+            .iter()
+            .all(|l| l.content_hash == locations[0].content_hash);
+        groups.push(LuaDuplicateGroup {
+            group_id,
+            file_name,
+            locations,
+            identical,
+        });
+    }
+
+    logger::log_info(
+        &format!("Found {} duplicated FlyWithLua script(s)", groups.len()),
+        Some("lua_duplicates"),
+    );
+    Ok(groups)
+}
+
+/// Keep the copy at `keep` (a location's `relative_path`) and disable every other
+/// copy in the group by renaming it to `.xfml`
+pub fn resolve_lua_duplicate(
+    xplane_path: &Path,
+    group_id: &str,
+    keep: &str,
+) -> Result<LuaDuplicateResolution> {
+    let group = find_duplicate_lua_scripts(xplane_path)?
+        .into_iter()
+        .find(|g| g.group_id == group_id)
+        .ok_or_else(|| anyhow!("Duplicate script group not found: {}", group_id))?;
+    if !group.locations.iter().any(|l| l.relative_path == keep) {
+        return Err(anyhow!("{} is not a copy of {}", keep, group.file_name));
+    }
+
+    let mut disabled = Vec::new();
+    for location in group.locations.iter().filter(|l| l.relative_path != keep) {
+        let path = xplane_path.join(&location.relative_path);
+        let canonical = path_utils::validate_xplane_child_path(xplane_path, &path)
+            .map_err(|e| anyhow!("Invalid script path: {}", e))?;
+        let disabled_path = canonical.with_extension("xfml");
+        if disabled_path.exists() {
+            return Err(anyhow!(
+                "Cannot disable {}: a disabled copy already exists",
+                location.relative_path
+            ));
+        }
+        fs::rename(&canonical, &disabled_path)
+            .map_err(|e| anyhow!("Failed to disable {}: {}", location.relative_path, e))?;
+        logger::log_info(
+            &format!(
+                "Disabled duplicate script: {} -> .xfml",
+                location.relative_path
+            ),
+            Some("lua_duplicates"),
+        );
+        disabled.push(location.relative_path.clone());
+    }
+
+    Ok(LuaDuplicateResolution {
+        group_id: group.group_id,
+        kept: keep.to_string(),
+        disabled,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn scripts_differing_after_the_first_megabyte_hash_differently() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let prefix = "-".repeat(2 * 1024 * 1024);
+        let a = temp.path().join("a.lua");
+        let b = temp.path().join("b.lua");
+        write(&a, &format!("{}a", prefix));
+        write(&b, &format!("{}b", prefix));
+
+        let (hash_a, size_a) = hash_script(&a).unwrap();
+        let (hash_b, _) = hash_script(&b).unwrap();
+        assert_ne!(hash_a, hash_b);
+        assert_eq!(size_a, prefix.len() as u64 + 1);
+    }
+
+    #[test]
+    fn duplicates_are_grouped_compared_and_resolved() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        let global = global_scripts_dir(root);
+        write(&global.join("pushback.lua"), "-- v1");
+        write(&global.join("checklist.lua"), "-- shared");
+        write(&global.join("only_global.lua"), "-- x");
+
+        let a320 = root.join("Aircraft/Vendor/A320/plugins/FlyWithLua/Scripts");
+        write(&a320.join("Pushback.lua"), "-- v2");
+        write(&a320.join("checklist.lua"), "-- shared");
+        // Disabled copies and livery textures are not duplicates
+        write(&a320.join("only_global.xfml"), "-- x");
+        write(
+            &root
+                .join("Aircraft/Vendor/A320/liveries/Red/plugins/FlyWithLua/Scripts/checklist.lua"),
+            "-- shared",
+        );
+
+        let groups = find_duplicate_lua_scripts(root).unwrap();
+        assert_eq!(groups.len(), 2);
+        let checklist = &groups[0];
+        assert_eq!(checklist.group_id, "checklist.lua");
+        assert!(checklist.identical);
+        let pushback = &groups[1];
+        assert_eq!(pushback.group_id, "pushback.lua");
+        assert!(!pushback.identical);
+        let aircraft_copy = pushback
+            .locations
+            .iter()
+            .find(|l| l.scope == LuaScriptScope::Aircraft)
+            .unwrap();
+        assert_eq!(
+            aircraft_copy.aircraft_folder.as_deref(),
+            Some("Vendor/A320")
+        );
+
+        let resolution =
+            resolve_lua_duplicate(root, "pushback.lua", &aircraft_copy.relative_path).unwrap();
+        assert_eq!(
+            resolution.disabled,
+            vec!["Resources/plugins/FlyWithLua/Scripts/pushback.lua".to_string()]
+        );
+        assert!(global.join("pushback.xfml").exists());
+        assert!(a320.join("Pushback.lua").exists());
+
+        assert!(resolve_lua_duplicate(root, "pushback.lua", "nope.lua").is_err());
+        assert_eq!(find_duplicate_lua_scripts(root).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,1094 @@
+//! Full maintenance run ("verify everything")
+//!
+//! Runs the per-area checks that also exist as separate commands (addon
+//! manifests, scenery index, scenery_packs.ini, navdata currency) as one
+//! cancellable operation and keeps a consolidated report in the
+//! `maintenance_runs` table.
+//!
+//! - Every finished section is persisted immediately. A cancelled or interrupted
+//!   run is resumed by the next run for the same X-Plane path, which only checks
+//!   the sections still missing.
+//! - Each section fingerprints its inputs (listing, sizes and mtimes of the
+//!   folders it reads, down to every file for the scenery index). When the fingerprint matches the last healthy result of
+//!   that section, the result is reused instead of checking again.
+//! - A background loop repeats the most recent run at most once a week, when no
+//!   install is active, X-Plane is closed and the machine is on AC power.
+
+use chrono::{Datelike, Local, NaiveDate};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::addon_updater::{self, AddonUpdateOptions};
+use crate::database::entities::maintenance_runs;
+use crate::database::DatabaseState;
+use crate::dir_walk::LinkFollowingWalk;
+use crate::error::{ApiError, ApiResult};
+use crate::logger;
+use crate::management_index;
+use crate::models::{
+    MaintenanceProgressEvent, MaintenanceReport, MaintenanceRunStatus, MaintenanceSection,
+    MaintenanceSectionReport, MaintenanceSectionStatus,
+};
+use crate::scenery_index::SceneryIndexManager;
+use crate::scenery_packs_manager::SceneryPacksManager;
+use crate::settings_store;
+use crate::task_control::TaskControl;
+
+/// Event emitted with a [`MaintenanceProgressEvent`] as sections start and finish
+pub const MAINTENANCE_PROGRESS_EVENT: &str = "maintenance-progress";
+
+/// Minimum time between scheduled runs
+const SCHEDULE_INTERVAL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// How often the scheduler checks whether a run is due
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Delay before the first scheduler check, so startup work isn't competing
+const SCHEDULE_INITIAL_DELAY: Duration = Duration::from_secs(10 * 60);
+
+/// Runs kept in the database; older ones are deleted after each run
+const MAX_STORED_RUNS: u64 = 20;
+
+/// Effective date of AIRAC cycle 2001, the reference for cycle arithmetic
+const AIRAC_REFERENCE: (i32, u32, u32) = (2020, 1, 2);
+const AIRAC_CYCLE_DAYS: i64 = 28;
+
+const LOG_CTX: &str = "maintenance";
+
+/// Whether a run is in progress (runs never overlap)
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Cancellation handle of the run in progress
+static CURRENT_CONTROL: LazyLock<Mutex<Option<TaskControl>>> = LazyLock::new(|| Mutex::new(None));
+
+pub type MaintenanceProgressCallback = Arc<dyn Fn(MaintenanceProgressEvent) + Send + Sync>;
+
+/// Marks a run as active for its lifetime
+struct RunGuard;
+
+impl RunGuard {
+    fn acquire(control: &TaskControl) -> ApiResult<Self> {
+        if RUNNING
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return Err(ApiError::conflict(
+                "A maintenance run is already in progress",
+            ));
+        }
+        if let Ok(mut current) = CURRENT_CONTROL.lock() {
+            *current = Some(control.clone());
+        }
+        Ok(RunGuard)
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        if let Ok(mut current) = CURRENT_CONTROL.lock() {
+            *current = None;
+        }
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Request cancellation of the run in progress. Returns `false` if none is running.
+pub fn cancel() -> bool {
+    match CURRENT_CONTROL.lock().ok().and_then(|c| c.clone()) {
+        Some(control) => {
+            control.request_cancel_all();
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+/// Outcome of one check, before it is stamped with fingerprint and time
+struct SectionOutcome {
+    status: MaintenanceSectionStatus,
+    summary: String,
+    issues: Vec<String>,
+}
+
+impl SectionOutcome {
+    fn from_issues(issues: Vec<String>, healthy: &str, with_issues: String) -> Self {
+        if issues.is_empty() {
+            Self {
+                status: MaintenanceSectionStatus::Healthy,
+                summary: healthy.to_string(),
+                issues,
+            }
+        } else {
+            Self {
+                status: MaintenanceSectionStatus::Issues,
+                summary: with_issues,
+                issues,
+            }
+        }
+    }
+}
+
+/// Run (or resume) a full maintenance run over `sections` (all when `None` or empty)
+pub async fn run(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    sections: Option<Vec<MaintenanceSection>>,
+    scheduled: bool,
+    progress: Option<MaintenanceProgressCallback>,
+) -> ApiResult<MaintenanceReport> {
+    if !xplane_path.is_dir() {
+        return Err(ApiError::validation(format!(
+            "X-Plane folder not found: {}",
+            xplane_path.display()
+        )));
+    }
+    let sections = requested_sections(sections);
+    let control = TaskControl::new();
+    let _guard = RunGuard::acquire(&control)?;
+    let path_key = path_key(xplane_path);
+
+    let mut model = start_or_resume_run(conn, &path_key, &sections, scheduled).await?;
+    let mut results = parse_results(&model.results);
+    let emit = |event: MaintenanceProgressEvent| {
+        if let Some(callback) = &progress {
+            callback(event);
+        }
+    };
+
+    for &section in &sections {
+        if results.iter().any(|r| r.section == section) {
+            continue;
+        }
+        if control.is_cancelled() {
+            break;
+        }
+        emit(MaintenanceProgressEvent {
+            run_id: model.id,
+            section,
+            phase: "started".to_string(),
+            status: None,
+            completed_sections: results.len(),
+            total_sections: sections.len(),
+            message: None,
+        });
+
+        let run_id = model.id;
+        let completed = results.len();
+        let report_progress = |message: String| {
+            emit(MaintenanceProgressEvent {
+                run_id,
+                section,
+                phase: "progress".to_string(),
+                status: None,
+                completed_sections: completed,
+                total_sections: sections.len(),
+                message: Some(message),
+            })
+        };
+        let Some(report) = check_section(
+            conn,
+            xplane_path,
+            &path_key,
+            section,
+            &control,
+            &report_progress,
+        )
+        .await?
+        else {
+            break;
+        };
+
+        logger::log_info(
+            &format!(
+                "Maintenance section {:?}: {:?} - {}",
+                section, report.status, report.summary
+            ),
+            Some(LOG_CTX),
+        );
+        let status = report.status;
+        let summary = report.summary.clone();
+        results.push(report);
+        model = save_progress(conn, model, &results, None).await?;
+        emit(MaintenanceProgressEvent {
+            run_id: model.id,
+            section,
+            phase: "finished".to_string(),
+            status: Some(status),
+            completed_sections: results.len(),
+            total_sections: sections.len(),
+            message: Some(summary),
+        });
+    }
+
+    let finished = sections
+        .iter()
+        .all(|section| results.iter().any(|r| r.section == *section));
+    let status = if finished {
+        MaintenanceRunStatus::Completed
+    } else {
+        MaintenanceRunStatus::Cancelled
+    };
+    model = save_progress(conn, model, &results, Some(status)).await?;
+    if let Err(e) = prune_runs(conn).await {
+        logger::log_info(
+            &format!("Failed to prune old maintenance runs: {}", e),
+            Some(LOG_CTX),
+        );
+    }
+    Ok(report_from_model(model))
+}
+
+/// Latest completed run, if any
+pub async fn last_report(conn: &DatabaseConnection) -> ApiResult<Option<MaintenanceReport>> {
+    let model = maintenance_runs::Entity::find()
+        .filter(maintenance_runs::Column::Status.eq(status_name(MaintenanceRunStatus::Completed)))
+        .order_by_desc(maintenance_runs::Column::Id)
+        .one(conn)
+        .await?;
+    Ok(model.map(report_from_model))
+}
+
+fn requested_sections(sections: Option<Vec<MaintenanceSection>>) -> Vec<MaintenanceSection> {
+    let requested = sections.unwrap_or_default();
+    if requested.is_empty() {
+        return MaintenanceSection::ALL.to_vec();
+    }
+    // Keep the canonical order and drop duplicates
+    MaintenanceSection::ALL
+        .into_iter()
+        .filter(|section| requested.contains(section))
+        .collect()
+}
+
+fn path_key(xplane_path: &Path) -> String {
+    let path = xplane_path.to_string_lossy();
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        path.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn status_name(status: MaintenanceRunStatus) -> &'static str {
+    match status {
+        MaintenanceRunStatus::Running => "running",
+        MaintenanceRunStatus::Cancelled => "cancelled",
+        MaintenanceRunStatus::Completed => "completed",
+    }
+}
+
+fn parse_status(value: &str) -> MaintenanceRunStatus {
+    match value {
+        "running" => MaintenanceRunStatus::Running,
+        "completed" => MaintenanceRunStatus::Completed,
+        _ => MaintenanceRunStatus::Cancelled,
+    }
+}
+
+fn parse_results(json: &str) -> Vec<MaintenanceSectionReport> {
+    serde_json::from_str(json).unwrap_or_default()
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> ApiResult<String> {
+    serde_json::to_string(value)
+        .map_err(|e| ApiError::internal(format!("Failed to serialize maintenance run: {}", e)))
+}
+
+fn report_from_model(model: maintenance_runs::Model) -> MaintenanceReport {
+    let sections: Vec<MaintenanceSection> =
+        serde_json::from_str(&model.sections).unwrap_or_default();
+    let mut results = parse_results(&model.results);
+    results.sort_by_key(|r| sections.iter().position(|s| *s == r.section));
+    let pending = sections
+        .iter()
+        .copied()
+        .filter(|section| !results.iter().any(|r| r.section == *section))
+        .collect();
+    MaintenanceReport {
+        run_id: model.id,
+        xplane_path: model.xplane_path,
+        status: parse_status(&model.status),
+        scheduled: model.scheduled,
+        started_at: model.started_at,
+        finished_at: model.finished_at,
+        sections: results,
+        pending,
+    }
+}
+
+/// Resume the latest run for `path_key` if it didn't finish, otherwise start a new one.
+/// A resumed run keeps the results of the sections still requested.
+async fn start_or_resume_run(
+    conn: &DatabaseConnection,
+    path_key: &str,
+    sections: &[MaintenanceSection],
+    scheduled: bool,
+) -> ApiResult<maintenance_runs::Model> {
+    let now = unix_now();
+    let latest = maintenance_runs::Entity::find()
+        .filter(maintenance_runs::Column::XplanePath.eq(path_key))
+        .order_by_desc(maintenance_runs::Column::Id)
+        .one(conn)
+        .await?;
+
+    if let Some(run) =
+        latest.filter(|run| run.status != status_name(MaintenanceRunStatus::Completed))
+    {
+        let kept: Vec<MaintenanceSectionReport> = parse_results(&run.results)
+            .into_iter()
+            .filter(|r| sections.contains(&r.section))
+            .collect();
+        logger::log_info(
+            &format!(
+                "Resuming maintenance run {} ({} of {} sections already done)",
+                run.id,
+                kept.len(),
+                sections.len()
+            ),
+            Some(LOG_CTX),
+        );
+        let mut active: maintenance_runs::ActiveModel = run.into();
+        active.sections = Set(to_json(&sections)?);
+        active.results = Set(to_json(&kept)?);
+        active.status = Set(status_name(MaintenanceRunStatus::Running).to_string());
+        active.updated_at = Set(now);
+        return Ok(active.update(conn).await?);
+    }
+
+    Ok(maintenance_runs::ActiveModel {
+        id: ActiveValue::NotSet,
+        xplane_path: Set(path_key.to_string()),
+        sections: Set(to_json(&sections)?),
+        results: Set("[]".to_string()),
+        status: Set(status_name(MaintenanceRunStatus::Running).to_string()),
+        scheduled: Set(scheduled),
+        started_at: Set(now),
+        updated_at: Set(now),
+        finished_at: Set(None),
+    }
+    .insert(conn)
+    .await?)
+}
+
+/// Persist the finished sections, and the final status once the run ends
+async fn save_progress(
+    conn: &DatabaseConnection,
+    model: maintenance_runs::Model,
+    results: &[MaintenanceSectionReport],
+    status: Option<MaintenanceRunStatus>,
+) -> ApiResult<maintenance_runs::Model> {
+    let now = unix_now();
+    let mut active: maintenance_runs::ActiveModel = model.into();
+    active.results = Set(to_json(&results)?);
+    active.updated_at = Set(now);
+    if let Some(status) = status {
+        active.status = Set(status_name(status).to_string());
+        if status == MaintenanceRunStatus::Completed {
+            active.finished_at = Set(Some(now));
+        }
+    }
+    Ok(active.update(conn).await?)
+}
+
+async fn prune_runs(conn: &DatabaseConnection) -> ApiResult<()> {
+    let stale: Vec<i64> = maintenance_runs::Entity::find()
+        .select_only()
+        .column(maintenance_runs::Column::Id)
+        .order_by_desc(maintenance_runs::Column::Id)
+        .into_tuple::<i64>()
+        .all(conn)
+        .await?
+        .into_iter()
+        .skip(MAX_STORED_RUNS as usize)
+        .collect();
+    if !stale.is_empty() {
+        maintenance_runs::Entity::delete_many()
+            .filter(maintenance_runs::Column::Id.is_in(stale))
+            .exec(conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Latest healthy result of `section` for this X-Plane path, with the run that produced it
+async fn last_healthy_result(
+    conn: &DatabaseConnection,
+    path_key: &str,
+    section: MaintenanceSection,
+) -> ApiResult<Option<(i64, MaintenanceSectionReport)>> {
+    let runs = maintenance_runs::Entity::find()
+        .filter(maintenance_runs::Column::XplanePath.eq(path_key))
+        .order_by_desc(maintenance_runs::Column::Id)
+        .limit(MAX_STORED_RUNS)
+        .all(conn)
+        .await?;
+    for run in runs {
+        let Some(result) = parse_results(&run.results)
+            .into_iter()
+            .find(|r| r.section == section)
+        else {
+            continue;
+        };
+        return Ok(match result.status {
+            MaintenanceSectionStatus::Healthy => Some((run.id, result)),
+            MaintenanceSectionStatus::Unchanged => {
+                Some((result.reused_from_run.unwrap_or(run.id), result))
+            }
+            _ => None,
+        });
+    }
+    Ok(None)
+}
+
+/// Check one section, or reuse its last healthy result if its inputs are unchanged.
+/// Returns `None` if the run was cancelled during the check.
+async fn check_section(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    path_key: &str,
+    section: MaintenanceSection,
+    control: &TaskControl,
+    report_progress: &(dyn Fn(String) + Sync),
+) -> ApiResult<Option<MaintenanceSectionReport>> {
+    let fingerprint = section_fingerprint(conn, xplane_path, section).await;
+
+    if let Some((run_id, previous)) = last_healthy_result(conn, path_key, section).await? {
+        if previous.fingerprint == fingerprint {
+            return Ok(Some(MaintenanceSectionReport {
+                section,
+                status: MaintenanceSectionStatus::Unchanged,
+                summary: previous.summary,
+                issues: Vec::new(),
+                fingerprint,
+                checked_at: previous.checked_at,
+                reused_from_run: Some(run_id),
+            }));
+        }
+    }
+
+    let outcome = match section {
+        MaintenanceSection::Addons => check_addons(xplane_path, control, report_progress).await,
+        MaintenanceSection::SceneryIndex => check_scenery_index(conn, xplane_path).await,
+        MaintenanceSection::SceneryPacks => check_scenery_packs(conn, xplane_path).await,
+        MaintenanceSection::Navdata => check_navdata(xplane_path).await.map(Some),
+    };
+    let outcome = match outcome {
+        Ok(Some(outcome)) => outcome,
+        Ok(None) => return Ok(None),
+        Err(e) => SectionOutcome {
+            status: MaintenanceSectionStatus::Failed,
+            summary: format!("Check failed: {}", e),
+            issues: Vec::new(),
+        },
+    };
+    Ok(Some(MaintenanceSectionReport {
+        section,
+        status: outcome.status,
+        summary: outcome.summary,
+        issues: outcome.issues,
+        fingerprint,
+        checked_at: unix_now(),
+        reused_from_run: None,
+    }))
+}
+
+/// Verify every addon with updater metadata against its remote manifest.
+/// Returns `None` when cancelled.
+async fn check_addons(
+    xplane_path: &Path,
+    control: &TaskControl,
+    report_progress: &(dyn Fn(String) + Sync),
+) -> anyhow::Result<Option<SectionOutcome>> {
+    if settings_store::get().offline_mode {
+        return Ok(Some(SectionOutcome {
+            status: MaintenanceSectionStatus::Skipped,
+            summary: "Offline mode is on; addon manifests were not checked".to_string(),
+            issues: Vec::new(),
+        }));
+    }
+
+    let scan_path = xplane_path.to_path_buf();
+    let targets = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+        let mut targets: Vec<(&'static str, String, String)> = Vec::new();
+        for aircraft in management_index::scan_aircraft(&scan_path, false)?.entries {
+            if aircraft.update_provider.is_some() && aircraft.cfg_disabled != Some(true) {
+                targets.push(("aircraft", aircraft.folder_name, aircraft.display_name));
+            }
+        }
+        for plugin in management_index::scan_plugins(&scan_path, false)?.entries {
+            if plugin.update_provider.is_some() && plugin.cfg_disabled != Some(true) {
+                targets.push(("plugin", plugin.folder_name, plugin.display_name));
+            }
+        }
+        Ok(targets)
+    })
+    .await??;
+
+    let mut issues = Vec::new();
+    for (index, (item_type, folder_name, display_name)) in targets.iter().enumerate() {
+        if control.is_cancelled() {
+            return Ok(None);
+        }
+        report_progress(format!(
+            "Checking {} ({}/{})",
+            display_name,
+            index + 1,
+            targets.len()
+        ));
+        let plan = addon_updater::build_update_plan(
+            xplane_path,
+            item_type,
+            folder_name,
+            verification_options(),
+            Some(control.clone()),
+            None,
+        )
+        .await;
+        match plan {
+            Ok(plan) if plan.has_update => issues.push(format!(
+                "{}: update available ({} -> {})",
+                display_name,
+                plan.local_version.as_deref().unwrap_or("?"),
+                plan.remote_version.as_deref().unwrap_or("?")
+            )),
+            Ok(plan) if !plan.add_files.is_empty() || !plan.replace_files.is_empty() => issues
+                .push(format!(
+                    "{}: {} file(s) missing and {} file(s) differing from the manifest",
+                    display_name,
+                    plan.add_files.len(),
+                    plan.replace_files.len()
+                )),
+            Ok(_) => {}
+            Err(_) if control.is_cancelled() => return Ok(None),
+            Err(e) => issues.push(format!("{}: could not be checked: {}", display_name, e)),
+        }
+    }
+
+    let count = targets.len();
+    let issue_count = issues.len();
+    Ok(Some(SectionOutcome::from_issues(
+        issues,
+        &format!("{} addon(s) match their manifests", count),
+        format!("{} of {} addon(s) need attention", issue_count, count),
+    )))
+}
+
+/// Options for a read-only plan: the plan is only compared, never executed
+fn verification_options() -> AddonUpdateOptions {
+    AddonUpdateOptions {
+        use_beta: false,
+        include_liveries: false,
+        apply_blacklist: false,
+        rollback_on_failure: false,
+        parallel_downloads: None,
+        channel: None,
+        fresh_install: false,
+        preserve_liveries: true,
+        preserve_config_files: true,
+        chunked_download_enabled: None,
+        threads_per_task: None,
+        total_threads: None,
+        keep_backup_versions: None,
+    }
+}
+
+/// Bring the scenery index in line with Custom Scenery (the same quick scan the
+/// Scenery page runs) and report what had drifted
+async fn check_scenery_index(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+) -> anyhow::Result<Option<SectionOutcome>> {
+    let index_manager = SceneryIndexManager::new(xplane_path, conn.clone());
+    if !index_manager.index_status().await?.index_exists {
+        return Ok(Some(SectionOutcome {
+            status: MaintenanceSectionStatus::Issues,
+            summary: "No scenery index yet".to_string(),
+            issues: vec!["Build the scenery index from the Scenery page".to_string()],
+        }));
+    }
+    let scan = index_manager.quick_scan_and_update().await?;
+    let summary = format!(
+        "Scenery index refreshed: {} added, {} removed, {} updated",
+        scan.added.len(),
+        scan.removed.len(),
+        scan.updated.len()
+    );
+    let issues = scan
+        .added
+        .iter()
+        .map(|name| format!("Added to the index: {}", name))
+        .chain(
+            scan.removed
+                .iter()
+                .map(|name| format!("Removed from the index: {}", name)),
+        )
+        .chain(
+            scan.updated
+                .iter()
+                .map(|name| format!("Reclassified in the index: {}", name)),
+        )
+        .collect();
+    Ok(Some(SectionOutcome::from_issues(
+        issues,
+        "Scenery index matches Custom Scenery",
+        summary,
+    )))
+}
+
+async fn check_scenery_packs(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+) -> anyhow::Result<Option<SectionOutcome>> {
+    let ini_path = xplane_path.join("Custom Scenery").join("scenery_packs.ini");
+    if !ini_path.exists() {
+        return Ok(Some(SectionOutcome {
+            status: MaintenanceSectionStatus::Issues,
+            summary: "scenery_packs.ini is missing".to_string(),
+            issues: vec!["Apply the scenery order from the Scenery page".to_string()],
+        }));
+    }
+    let packs_manager = SceneryPacksManager::new(xplane_path, conn.clone());
+    let mut issues: Vec<String> = packs_manager
+        .enabled_entries_with_missing_folders()
+        .await?
+        .into_iter()
+        .map(|path| format!("Enabled entry points to a missing folder: {}", path))
+        .collect();
+    if !packs_manager.is_synced_with_index().await? {
+        issues.push("Order or enabled states differ from the scenery index".to_string());
+    }
+    let issue_count = issues.len();
+    Ok(Some(SectionOutcome::from_issues(
+        issues,
+        "scenery_packs.ini is valid and matches the index",
+        format!("scenery_packs.ini has {} problem(s)", issue_count),
+    )))
+}
+
+async fn check_navdata(xplane_path: &Path) -> anyhow::Result<SectionOutcome> {
+    let report_path = xplane_path.to_path_buf();
+    let report = tokio::task::spawn_blocking(move || {
+        management_index::get_navdata_consistency_report(&report_path)
+    })
+    .await??;
+    let current = current_airac_cycle(Local::now().date_naive());
+
+    let issues: Vec<String> = report
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let cycle = entry.effective_cycle.as_deref()?;
+            (entry.is_outdated || cycle < current.as_str()).then(|| {
+                format!(
+                    "{}: AIRAC {} (current {})",
+                    entry.provider_name.as_deref().unwrap_or(&entry.name),
+                    cycle,
+                    current
+                )
+            })
+        })
+        .collect();
+    let issue_count = issues.len();
+    Ok(SectionOutcome::from_issues(
+        issues,
+        &format!(
+            "{} navdata installation(s) on AIRAC {} or newer",
+            report.entries.len() - report.unknown_cycle_count,
+            current
+        ),
+        format!("{} navdata installation(s) out of date", issue_count),
+    ))
+}
+
+/// AIRAC cycle (YYNN) in effect on `date`
+fn current_airac_cycle(date: NaiveDate) -> String {
+    let (year, month, day) = AIRAC_REFERENCE;
+    let reference = NaiveDate::from_ymd_opt(year, month, day).expect("valid AIRAC reference");
+    let mut year = date.year();
+    loop {
+        let jan1 = NaiveDate::from_ymd_opt(year, 1, 1).expect("valid January 1st");
+        let offset = (jan1 - reference).num_days().rem_euclid(AIRAC_CYCLE_DAYS);
+        let first_cycle =
+            jan1 + chrono::Duration::days((AIRAC_CYCLE_DAYS - offset) % AIRAC_CYCLE_DAYS);
+        if date >= first_cycle {
+            let number = (date - first_cycle).num_days() / AIRAC_CYCLE_DAYS + 1;
+            return format!("{:02}{:02}", year.rem_euclid(100), number);
+        }
+        year -= 1;
+    }
+}
+
+/// Folders and files a section reads; their listing and mtimes make up its fingerprint.
+/// See [`section_depth`] for how far below them it looks.
+fn section_inputs(xplane_path: &Path, section: MaintenanceSection) -> Vec<PathBuf> {
+    let custom_scenery = xplane_path.join("Custom Scenery");
+    match section {
+        MaintenanceSection::Addons => vec![
+            xplane_path.join("Aircraft"),
+            xplane_path.join("Resources").join("plugins"),
+        ],
+        MaintenanceSection::SceneryIndex => vec![custom_scenery],
+        MaintenanceSection::SceneryPacks => {
+            let ini_path = custom_scenery.join("scenery_packs.ini");
+            vec![custom_scenery, ini_path]
+        }
+        MaintenanceSection::Navdata => {
+            vec![
+                xplane_path.join("Custom Data"),
+                xplane_path.join("Aircraft"),
+            ]
+        }
+    }
+}
+
+/// Levels below each input that the fingerprint covers. The scenery index
+/// classifies packages by files deep inside them (DSF tiles, apt.dat), so its
+/// fingerprint covers the whole tree; the other sections read the top level.
+fn section_depth(section: MaintenanceSection) -> usize {
+    match section {
+        MaintenanceSection::SceneryIndex => usize::MAX,
+        _ => 1,
+    }
+}
+
+async fn section_fingerprint(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    section: MaintenanceSection,
+) -> String {
+    // State outside the folders that changes the outcome
+    let extra = match section {
+        MaintenanceSection::SceneryIndex | MaintenanceSection::SceneryPacks => {
+            SceneryIndexManager::new(xplane_path, conn.clone())
+                .index_status()
+                .await
+                .map(|status| format!("{}:{}", status.index_exists, status.total_packages))
+                .unwrap_or_default()
+        }
+        MaintenanceSection::Navdata => current_airac_cycle(Local::now().date_naive()),
+        MaintenanceSection::Addons => String::new(),
+    };
+    let inputs = section_inputs(xplane_path, section);
+    let depth = section_depth(section);
+    tokio::task::spawn_blocking(move || fingerprint_paths(&inputs, depth, &extra))
+        .await
+        .unwrap_or_default()
+}
+
+/// SHA-256 over each path's size and mtime and those of everything up to
+/// `depth` levels below it
+fn fingerprint_paths(paths: &[PathBuf], depth: usize, extra: &str) -> String {
+    fn hash_metadata(hasher: &mut Sha256, path: &Path) {
+        match fs::metadata(path) {
+            Ok(metadata) => {
+                hasher.update(metadata.len().to_le_bytes());
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .unwrap_or_default();
+                hasher.update(modified.as_nanos().to_le_bytes());
+            }
+            Err(_) => hasher.update(b"missing"),
+        }
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(extra.as_bytes());
+    for path in paths {
+        hasher.update(path.to_string_lossy().as_bytes());
+        hash_metadata(&mut hasher, path);
+        let mut children: Vec<PathBuf> = LinkFollowingWalk::new(path, depth)
+            .filter(|entry| entry.depth() > 0)
+            .map(|entry| entry.into_path())
+            .collect();
+        children.sort();
+        for child in children {
+            let relative = child.strip_prefix(path).unwrap_or(&child);
+            hasher.update(relative.to_string_lossy().as_bytes());
+            hash_metadata(&mut hasher, &child);
+        }
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Start the weekly background run. Runs interrupted by a previous session
+/// are marked cancelled so they are resumed rather than reported as running.
+pub fn init(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let conn = app_handle.state::<DatabaseState>().get();
+        if let Err(e) = maintenance_runs::Entity::update_many()
+            .col_expr(
+                maintenance_runs::Column::Status,
+                sea_orm::sea_query::Expr::value(status_name(MaintenanceRunStatus::Cancelled)),
+            )
+            .filter(maintenance_runs::Column::Status.eq(status_name(MaintenanceRunStatus::Running)))
+            .exec(&conn)
+            .await
+        {
+            logger::log_info(
+                &format!("Failed to mark interrupted maintenance runs: {}", e),
+                Some(LOG_CTX),
+            );
+        }
+
+        tokio::time::sleep(SCHEDULE_INITIAL_DELAY).await;
+        loop {
+            if let Err(e) = run_if_due(&app_handle).await {
+                logger::log_info(
+                    &format!("Scheduled maintenance run failed: {}", e),
+                    Some(LOG_CTX),
+                );
+            }
+            tokio::time::sleep(SCHEDULE_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Repeat the most recent run when it is at least a week old (or an earlier
+/// scheduled run was interrupted) and the machine is idle. Nothing is
+/// scheduled until the user has run maintenance once, which supplies the
+/// X-Plane path and sections.
+async fn run_if_due(app_handle: &AppHandle) -> ApiResult<()> {
+    if !settings_store::get().auto_maintenance || is_running() {
+        return Ok(());
+    }
+    let conn = app_handle.state::<DatabaseState>().get();
+    let Some(latest) = maintenance_runs::Entity::find()
+        .order_by_desc(maintenance_runs::Column::Id)
+        .one(&conn)
+        .await?
+    else {
+        return Ok(());
+    };
+    let due = match parse_status(&latest.status) {
+        MaintenanceRunStatus::Completed => latest
+            .finished_at
+            .is_none_or(|finished| unix_now() - finished >= SCHEDULE_INTERVAL_SECS),
+        // Don't resume runs the user cancelled
+        _ => latest.scheduled,
+    };
+    if !due || !is_idle(app_handle).await {
+        return Ok(());
+    }
+
+    let sections: Vec<MaintenanceSection> =
+        serde_json::from_str(&latest.sections).unwrap_or_default();
+    logger::log_info("Starting scheduled maintenance run", Some(LOG_CTX));
+    let event_handle = app_handle.clone();
+    let progress: MaintenanceProgressCallback = Arc::new(move |event| {
+        let _ = event_handle.emit(MAINTENANCE_PROGRESS_EVENT, event);
+    });
+    run(
+        &conn,
+        Path::new(&latest.xplane_path),
+        Some(sections),
+        true,
+        Some(progress),
+    )
+    .await?;
+    Ok(())
+}
+
+/// No install running, X-Plane closed and on AC power
+async fn is_idle(app_handle: &AppHandle) -> bool {
+    if app_handle.state::<TaskControl>().is_install_active()
+        || crate::xplane_processes::is_any_running().await
+    {
+        return false;
+    }
+    tokio::task::spawn_blocking(on_ac_power)
+        .await
+        .unwrap_or(false)
+}
+
+/// Whether the machine runs on mains power. Machines without a battery, and
+/// systems where the state can't be read, count as on AC power.
+#[cfg(target_os = "windows")]
+fn on_ac_power() -> bool {
+    use winapi::um::winbase::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        return true;
+    }
+    // 0 = offline (battery), 1 = online, 255 = unknown
+    status.ACLineStatus != 0
+}
+
+/// Whether the machine runs on mains power. Machines without a battery, and
+/// systems where the state can't be read, count as on AC power.
+#[cfg(target_os = "macos")]
+fn on_ac_power() -> bool {
+    match std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+    {
+        Ok(output) => !String::from_utf8_lossy(&output.stdout).contains("'Battery Power'"),
+        Err(_) => true,
+    }
+}
+
+/// Whether the machine runs on mains power. Machines without a battery, and
+/// systems where the state can't be read, count as on AC power.
+#[cfg(target_os = "linux")]
+fn on_ac_power() -> bool {
+    let Ok(supplies) = fs::read_dir("/sys/class/power_supply") else {
+        return true;
+    };
+    let mut has_battery = false;
+    for supply in supplies.flatten() {
+        let path = supply.path();
+        let read = |name: &str| {
+            fs::read_to_string(path.join(name))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+        match read("type").as_str() {
+            "Mains" if read("online") == "1" => return true,
+            "Battery" => has_battery = true,
+            _ => {}
+        }
+    }
+    !has_battery
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+
+    #[test]
+    fn airac_cycles_follow_the_28_day_schedule() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(current_airac_cycle(date(2020, 1, 2)), "2001");
+        assert_eq!(current_airac_cycle(date(2020, 1, 1)), "1913");
+        assert_eq!(current_airac_cycle(date(2020, 12, 31)), "2014");
+        assert_eq!(current_airac_cycle(date(2025, 1, 22)), "2413");
+        assert_eq!(current_airac_cycle(date(2025, 1, 23)), "2501");
+        assert_eq!(current_airac_cycle(date(2026, 10, 16)), "2610");
+    }
+
+    #[test]
+    fn requested_sections_use_canonical_order() {
+        assert_eq!(requested_sections(None), MaintenanceSection::ALL.to_vec());
+        assert_eq!(
+            requested_sections(Some(vec![
+                MaintenanceSection::Navdata,
+                MaintenanceSection::SceneryPacks,
+                MaintenanceSection::Navdata,
+            ])),
+            vec![
+                MaintenanceSection::SceneryPacks,
+                MaintenanceSection::Navdata
+            ]
+        );
+    }
+
+    #[test]
+    fn scenery_index_fingerprint_sees_edits_deep_inside_packages() {
+        let xplane = tempfile::tempdir().unwrap();
+        let tiles = xplane
+            .path()
+            .join("Custom Scenery/Ortho/Earth nav data/+40-080");
+        fs::create_dir_all(&tiles).unwrap();
+        fs::write(tiles.join("+40-080.dsf"), b"DSF").unwrap();
+        let inputs = section_inputs(xplane.path(), MaintenanceSection::SceneryIndex);
+        let depth = section_depth(MaintenanceSection::SceneryIndex);
+        let before = fingerprint_paths(&inputs, depth, "");
+        let top_level_before = fingerprint_paths(&inputs, 1, "");
+
+        fs::write(tiles.join("+41-080.dsf"), b"DSF").unwrap();
+
+        assert_ne!(fingerprint_paths(&inputs, depth, ""), before);
+        assert_eq!(fingerprint_paths(&inputs, 1, ""), top_level_before);
+    }
+
+    #[tokio::test]
+    async fn runs_resume_and_reuse_unchanged_healthy_sections() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+        let xplane = tempfile::tempdir().unwrap();
+        fs::create_dir_all(xplane.path().join("Custom Data")).unwrap();
+        let sections = Some(vec![
+            MaintenanceSection::SceneryPacks,
+            MaintenanceSection::Navdata,
+        ]);
+
+        // An interrupted run that already finished the scenery_packs.ini check
+        let key = path_key(xplane.path());
+        let interrupted =
+            start_or_resume_run(&conn, &key, &requested_sections(sections.clone()), false)
+                .await
+                .unwrap();
+        let earlier = MaintenanceSectionReport {
+            section: MaintenanceSection::SceneryPacks,
+            status: MaintenanceSectionStatus::Issues,
+            summary: "checked before the interruption".to_string(),
+            issues: vec!["issue".to_string()],
+            fingerprint: String::new(),
+            checked_at: 1,
+            reused_from_run: None,
+        };
+        save_progress(
+            &conn,
+            interrupted.clone(),
+            &[earlier],
+            Some(MaintenanceRunStatus::Cancelled),
+        )
+        .await
+        .unwrap();
+
+        let resumed = run(&conn, xplane.path(), sections.clone(), false, None)
+            .await
+            .unwrap();
+        assert_eq!(resumed.run_id, interrupted.id);
+        assert_eq!(resumed.status, MaintenanceRunStatus::Completed);
+        assert!(resumed.pending.is_empty());
+        assert_eq!(
+            resumed.sections[0].summary,
+            "checked before the interruption"
+        );
+        assert_eq!(
+            resumed.sections[1].status,
+            MaintenanceSectionStatus::Healthy
+        );
+
+        // Nothing changed: the healthy navdata result is reused, the ini is checked again
+        let second = run(&conn, xplane.path(), sections, false, None)
+            .await
+            .unwrap();
+        assert_ne!(second.run_id, interrupted.id);
+        assert_eq!(second.sections[0].status, MaintenanceSectionStatus::Issues);
+        assert_eq!(second.sections[0].summary, "scenery_packs.ini is missing");
+        assert_eq!(
+            second.sections[1].status,
+            MaintenanceSectionStatus::Unchanged
+        );
+        assert_eq!(second.sections[1].reused_from_run, Some(interrupted.id));
+
+        let last = last_report(&conn).await.unwrap().unwrap();
+        assert_eq!(last.run_id, second.run_id);
+    }
+}
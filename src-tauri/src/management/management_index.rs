@@ -7,12 +7,21 @@
 //! - Aircraft: Rename .acf <-> .xfma files (not scanning subdirectories)
 //! - Plugins: Rename .xpl <-> .xfmp files (including subdirectories)
 
+use crate::addon_icons;
+use crate::app_dirs::AIRCRAFT_ACF_STATE_FILE_NAME;
+use crate::cache;
+use crate::file_explorer;
+use crate::http_client;
 use crate::logger;
 use crate::models::{
-    AircraftAcfFileInfo, AircraftInfo, LiveryInfo, LuaScriptInfo, ManagementData,
-    NavdataBackupInfo, NavdataBackupVerification, NavdataManagerInfo, PluginInfo,
+    AircraftAcfFileInfo, AircraftInfo, HealthAction, HealthCheckCategory, HealthCheckProgress,
+    HealthCheckReport, HealthIssue, HealthSeverity, LiveryDeleteResult, LiveryDiskUsage,
+    LiveryInfo, LuaScriptInfo, ManagementData, NavdataBackupInfo, NavdataBackupVerification,
+    NavdataConsistencyEntry, NavdataConsistencyReport, NavdataInfo, NavdataManagerInfo,
+    NavdataSourceKind, PluginInfo,
 };
 use crate::path_utils;
+use crate::plugin_duplicates;
 use crate::x_updater_profile::{
     find_profile_in_folder, is_profile_file_name, tag_host_as_update_url, XUPDATER_URL_PREFIX,
 };
@@ -20,14 +29,12 @@ use crate::zibo_updater;
 use anyhow::{anyhow, Result};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::ErrorKind;
+use std::io::{BufRead, BufReader, ErrorKind};
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-const AIRCRAFT_ACF_STATE_FILE_NAME: &str = ".xfastmanager-acf-state.json";
-
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AircraftAcfState {
@@ -261,8 +268,17 @@ fn resolve_management_path(
         .map_err(|e| anyhow!("Invalid path: {}", e))
 }
 
+/// Folder levels below Aircraft/ searched for .acf or .xfma files
+const AIRCRAFT_SCAN_MAX_DEPTH: usize = 3;
+
 /// Scan aircraft in the X-Plane Aircraft folder
-pub fn scan_aircraft(xplane_path: &Path) -> Result<ManagementData<AircraftInfo>> {
+///
+/// With `compute_sizes`, each entry also gets its folder size and newest file
+/// modification time; this walks every file, so it is opt-in.
+pub fn scan_aircraft(
+    xplane_path: &Path,
+    compute_sizes: bool,
+) -> Result<ManagementData<AircraftInfo>> {
     let aircraft_path = xplane_path.join("Aircraft");
     if !aircraft_path.exists() {
         logger::log_info(
@@ -273,6 +289,8 @@ pub fn scan_aircraft(xplane_path: &Path) -> Result<ManagementData<AircraftInfo>>
             entries: Vec::new(),
             total_count: 0,
             enabled_count: 0,
+            duplicates: Vec::new(),
+            suppressed_count: 0,
         });
     }
 
@@ -281,7 +299,21 @@ pub fn scan_aircraft(xplane_path: &Path) -> Result<ManagementData<AircraftInfo>>
     let mut entries: Vec<AircraftInfo> = Vec::new();
 
     // Scan up to 3 levels deep for .acf or .xfma files
-    scan_aircraft_recursive(&aircraft_path, &aircraft_path, 0, 3, &mut entries)?;
+    scan_aircraft_recursive(
+        &aircraft_path,
+        &aircraft_path,
+        0,
+        AIRCRAFT_SCAN_MAX_DEPTH,
+        &mut entries,
+    )?;
+
+    if compute_sizes {
+        entries.par_iter_mut().for_each(|entry| {
+            let (size_bytes, newest) = addon_folder_usage(&aircraft_path.join(&entry.folder_name));
+            entry.size_bytes = Some(size_bytes);
+            entry.newest_file_modified = newest;
+        });
+    }
 
     // Sort by display name
     entries.sort_by(|a, b| {
@@ -302,6 +334,8 @@ pub fn scan_aircraft(xplane_path: &Path) -> Result<ManagementData<AircraftInfo>>
         entries,
         total_count,
         enabled_count,
+        duplicates: Vec::new(),
+        suppressed_count: 0,
     })
 }
 
@@ -500,6 +534,8 @@ fn scan_single_aircraft_folder(
         has_mixed_acf_states,
         has_liveries,
         livery_count,
+        size_bytes: None,
+        newest_file_modified: None,
         version,
         update_url,
         update_provider,
@@ -509,6 +545,48 @@ fn scan_single_aircraft_folder(
     })
 }
 
+/// Scan a single folder under Aircraft/ (e.g. a freshly installed one), returning
+/// the entries `scan_aircraft` would report for it: the folder itself if it holds
+/// .acf/.xfma files, otherwise the aircraft nested below it within the scan depth
+pub fn scan_aircraft_target(xplane_path: &Path, target_path: &Path) -> Result<Vec<AircraftInfo>> {
+    let aircraft_path = xplane_path.join("Aircraft");
+    let relative = target_path
+        .strip_prefix(&aircraft_path)
+        .map_err(|_| anyhow!("Not inside the Aircraft folder: {}", target_path.display()))?;
+    let folder_name = target_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("Invalid aircraft folder: {}", target_path.display()))?;
+
+    // A folder at level N below Aircraft/ is checked by the full scan at depth N - 1
+    let level = relative.components().count();
+    let hidden = relative
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+    if level == 0 || level > AIRCRAFT_SCAN_MAX_DEPTH + 1 || hidden {
+        return Ok(Vec::new());
+    }
+
+    if let Some(info) = scan_single_aircraft_folder(target_path, &aircraft_path, folder_name) {
+        return Ok(vec![info]);
+    }
+
+    let mut entries = Vec::new();
+    scan_aircraft_recursive(
+        &aircraft_path,
+        target_path,
+        level,
+        AIRCRAFT_SCAN_MAX_DEPTH,
+        &mut entries,
+    )?;
+    entries.sort_by(|a, b| {
+        a.display_name
+            .to_lowercase()
+            .cmp(&b.display_name.to_lowercase())
+    });
+    Ok(entries)
+}
+
 fn rescan_aircraft_folder_entry(xplane_path: &Path, folder_name: &str) -> Result<AircraftInfo> {
     let aircraft_path = xplane_path.join("Aircraft");
     let folder_path = resolve_management_path(xplane_path, "aircraft", folder_name)?;
@@ -738,7 +816,10 @@ pub fn try_parse_digit_version(s: &str) -> Option<String> {
 }
 
 /// Scan plugins in the X-Plane Resources/plugins folder
-pub fn scan_plugins(xplane_path: &Path) -> Result<ManagementData<PluginInfo>> {
+///
+/// `compute_sizes` adds folder sizes and newest modification times, as for
+/// `scan_aircraft`.
+pub fn scan_plugins(xplane_path: &Path, compute_sizes: bool) -> Result<ManagementData<PluginInfo>> {
     let plugins_path = xplane_path.join("Resources").join("plugins");
     if !plugins_path.exists() {
         return Err(anyhow!("Plugins folder not found"));
@@ -770,7 +851,15 @@ pub fn scan_plugins(xplane_path: &Path) -> Result<ManagementData<PluginInfo>> {
     // Process plugin folders in parallel
     let mut entries: Vec<PluginInfo> = subdirs
         .par_iter()
-        .filter_map(|(path, folder_name)| scan_single_plugin_folder(path, folder_name))
+        .filter_map(|(path, folder_name)| {
+            let mut info = scan_single_plugin_folder(path, folder_name)?;
+            if compute_sizes {
+                let (size_bytes, newest) = addon_folder_usage(path);
+                info.size_bytes = Some(size_bytes);
+                info.newest_file_modified = newest;
+            }
+            Some(info)
+        })
         .collect();
 
     // Sort by display name
@@ -788,13 +877,38 @@ pub fn scan_plugins(xplane_path: &Path) -> Result<ManagementData<PluginInfo>> {
         Some("management"),
     );
 
+    let duplicates = plugin_duplicates::find_duplicate_plugins(xplane_path, &entries);
+
     Ok(ManagementData {
         entries,
         total_count,
         enabled_count,
+        duplicates,
+        suppressed_count: 0,
     })
 }
 
+/// Scan a single folder under Resources/plugins (e.g. a freshly installed one),
+/// returning the entry `scan_plugins` would report for it
+pub fn scan_plugin_target(xplane_path: &Path, target_path: &Path) -> Result<Option<PluginInfo>> {
+    let plugins_path = xplane_path.join("Resources").join("plugins");
+    if target_path.parent() != Some(plugins_path.as_path()) {
+        return Err(anyhow!(
+            "Not a folder in the plugins folder: {}",
+            target_path.display()
+        ));
+    }
+    let folder_name = target_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("Invalid plugin folder: {}", target_path.display()))?;
+    if folder_name.starts_with('.') || !target_path.is_dir() {
+        return Ok(None);
+    }
+
+    Ok(scan_single_plugin_folder(target_path, folder_name))
+}
+
 /// Scan a single plugin folder
 fn scan_single_plugin_folder(path: &Path, folder_name: &str) -> Option<PluginInfo> {
     // Find .xpl and .xfmp files (including subdirectories)
@@ -805,6 +919,12 @@ fn scan_single_plugin_folder(path: &Path, folder_name: &str) -> Option<PluginInf
         return None;
     }
 
+    // Disabled binaries count too, so disabling a plugin doesn't hide them
+    let binaries: Vec<&String> = xpl_files.iter().chain(xfmp_files.iter()).collect();
+    let platform_binaries = crate::plugin_platforms::from_folder(path, &binaries[..]);
+    let supported_on_current_platform =
+        crate::plugin_platforms::supports_current_platform(&platform_binaries);
+
     // Enabled if there are any .xpl files
     let enabled = !xpl_files.is_empty();
 
@@ -883,12 +1003,16 @@ fn scan_single_plugin_folder(path: &Path, folder_name: &str) -> Option<PluginInf
         cfg_disabled,
         has_scripts,
         script_count,
+        size_bytes: None,
+        newest_file_modified: None,
+        platform_binaries,
+        supported_on_current_platform,
     })
 }
 
 /// Find .xpl and .xfmp files in a folder (including subdirectories)
 /// Returns (xpl_files, xfmp_files)
-fn find_xpl_and_xfmp_files(folder: &Path) -> (Vec<String>, Vec<String>) {
+pub(crate) fn find_xpl_and_xfmp_files(folder: &Path) -> (Vec<String>, Vec<String>) {
     let mut xpl_files = Vec::new();
     let mut xfmp_files = Vec::new();
 
@@ -1071,6 +1195,8 @@ pub fn scan_navdata(xplane_path: &Path) -> Result<ManagementData<NavdataManagerI
         entries,
         total_count,
         enabled_count,
+        duplicates: Vec::new(),
+        suppressed_count: 0,
     })
 }
 
@@ -1112,6 +1238,314 @@ fn parse_cycle_json(
     })
 }
 
+/// Maximum depth below `Aircraft` searched for bundled navdata
+const AIRCRAFT_NAVDATA_MAX_DEPTH: usize = 8;
+
+/// Compare the AIRAC cycles of every navdata installation: Custom Data providers,
+/// GNS430 databases and navdata bundled inside aircraft folders
+pub fn get_navdata_consistency_report(xplane_path: &Path) -> Result<NavdataConsistencyReport> {
+    if !xplane_path.exists() {
+        return Err(anyhow!("X-Plane folder not found"));
+    }
+
+    let mut entries: Vec<NavdataConsistencyEntry> = Vec::new();
+
+    // Custom Data is optional here: aircraft-only navdata still gets compared
+    let custom_data_path = xplane_path.join("Custom Data");
+    if custom_data_path.exists() {
+        for info in scan_navdata(xplane_path)?.entries {
+            let folder_path = if info.folder_name.is_empty() {
+                custom_data_path.clone()
+            } else {
+                custom_data_path.join(&info.folder_name)
+            };
+            entries.push(NavdataConsistencyEntry {
+                source: NavdataSourceKind::CustomData,
+                name: info.provider_name.clone(),
+                provider_name: Some(info.provider_name),
+                effective_cycle: effective_airac_cycle(
+                    info.cycle.as_deref(),
+                    info.airac.as_deref(),
+                ),
+                cycle: info.cycle,
+                airac: info.airac,
+                folder_path: folder_path.to_string_lossy().to_string(),
+                management_type: Some("navdata".to_string()),
+                folder_name: info.folder_name,
+                is_outdated: false,
+            });
+        }
+    }
+
+    for (base, management_type) in [
+        (&custom_data_path, Some("navdata")),
+        (&xplane_path.join("Resources"), None),
+    ] {
+        let navdata_dir = base.join("GNS430").join("navdata");
+        if let Some(info) = parse_cycle_info_txt(&navdata_dir.join("cycle_info.txt")) {
+            entries.push(NavdataConsistencyEntry {
+                source: NavdataSourceKind::Gns430,
+                name: "GNS430".to_string(),
+                provider_name: info.provider_name,
+                effective_cycle: effective_airac_cycle(info.cycle.as_deref(), None),
+                cycle: info.cycle,
+                airac: None,
+                folder_path: navdata_dir.to_string_lossy().to_string(),
+                management_type: management_type.map(|t| t.to_string()),
+                folder_name: "GNS430/navdata".to_string(),
+                is_outdated: false,
+            });
+        }
+    }
+
+    entries.extend(scan_aircraft_navdata(xplane_path));
+
+    // A folder can match more than one source (a Custom Data/GNS430 database with
+    // its own cycle.json, or cycle.json next to cycle_info.txt); count it once
+    let mut seen_folders = HashSet::new();
+    entries.retain(|e| seen_folders.insert(e.folder_path.clone()));
+
+    let newest = entries
+        .iter()
+        .filter_map(|e| e.effective_cycle.as_deref())
+        .max()
+        .map(|c| c.to_string());
+
+    let mut outdated_count = 0;
+    let mut unknown_cycle_count = 0;
+    for entry in &mut entries {
+        match (&entry.effective_cycle, &newest) {
+            (Some(cycle), Some(newest)) if cycle < newest => {
+                entry.is_outdated = true;
+                outdated_count += 1;
+            }
+            (None, _) => unknown_cycle_count += 1,
+            _ => {}
+        }
+    }
+
+    // Outdated installations first, then by source and name
+    entries.sort_by(|a, b| {
+        b.is_outdated
+            .cmp(&a.is_outdated)
+            .then_with(|| a.effective_cycle.cmp(&b.effective_cycle))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    logger::log_info(
+        &format!(
+            "Navdata consistency: {} installations, newest cycle {}, {} outdated",
+            entries.len(),
+            newest.as_deref().unwrap_or("unknown"),
+            outdated_count
+        ),
+        Some("management"),
+    );
+
+    Ok(NavdataConsistencyReport {
+        entries,
+        newest_cycle: newest,
+        outdated_count,
+        unknown_cycle_count,
+    })
+}
+
+/// Find navdata bundled inside aircraft folders (cycle.json or cycle_info.txt),
+/// e.g. FMC databases shipped by Zibo, FlightFactor or ToLiss aircraft
+fn scan_aircraft_navdata(xplane_path: &Path) -> Vec<NavdataConsistencyEntry> {
+    let aircraft_path = xplane_path.join("Aircraft");
+    if !aircraft_path.exists() {
+        return Vec::new();
+    }
+
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(&aircraft_path)
+        .max_depth(AIRCRAFT_NAVDATA_MAX_DEPTH)
+        .into_iter()
+        .filter_entry(|e| {
+            // Liveries and objects never carry navdata and hold most of the files
+            !(e.file_type().is_dir()
+                && e.file_name()
+                    .to_str()
+                    .map(|n| {
+                        n.eq_ignore_ascii_case("liveries") || n.eq_ignore_ascii_case("objects")
+                    })
+                    .unwrap_or(false))
+        })
+        .filter_map(|e| e.ok())
+    {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(file_name) = entry.file_name().to_str() else {
+            continue;
+        };
+        let Some(navdata_dir) = entry.path().parent() else {
+            continue;
+        };
+
+        let info = if file_name.eq_ignore_ascii_case("cycle.json") {
+            parse_cycle_json(entry.path(), &aircraft_path, navdata_dir).map(|info| CycleInfo {
+                provider_name: Some(info.provider_name),
+                cycle: info.cycle,
+                airac: info.airac,
+            })
+        } else if file_name.eq_ignore_ascii_case("cycle_info.txt") {
+            parse_cycle_info_txt(entry.path())
+        } else {
+            None
+        };
+        let Some(info) = info else {
+            continue;
+        };
+
+        let folder_name = navdata_dir
+            .strip_prefix(&aircraft_path)
+            .unwrap_or(navdata_dir)
+            .to_string_lossy()
+            .to_string();
+        let name = find_owning_aircraft_folder(&aircraft_path, navdata_dir)
+            .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| folder_name.clone());
+
+        entries.push(NavdataConsistencyEntry {
+            source: NavdataSourceKind::Aircraft,
+            name,
+            provider_name: info.provider_name,
+            effective_cycle: effective_airac_cycle(info.cycle.as_deref(), info.airac.as_deref()),
+            cycle: info.cycle,
+            airac: info.airac,
+            folder_path: navdata_dir.to_string_lossy().to_string(),
+            management_type: Some("aircraft".to_string()),
+            folder_name,
+            is_outdated: false,
+        });
+    }
+
+    entries
+}
+
+/// Nearest ancestor of `dir` (below `aircraft_path`) that contains an .acf/.xfma file
+fn find_owning_aircraft_folder(aircraft_path: &Path, dir: &Path) -> Option<PathBuf> {
+    let mut current = dir.parent();
+    while let Some(candidate) = current {
+        if candidate == aircraft_path || !candidate.starts_with(aircraft_path) {
+            return None;
+        }
+        let has_acf = fs::read_dir(candidate)
+            .map(|entries| {
+                entries.filter_map(|e| e.ok()).any(|e| {
+                    e.path()
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .map(|ext| {
+                            ext.eq_ignore_ascii_case("acf") || ext.eq_ignore_ascii_case("xfma")
+                        })
+                        .unwrap_or(false)
+                })
+            })
+            .unwrap_or(false);
+        if has_acf {
+            return Some(candidate.to_path_buf());
+        }
+        current = candidate.parent();
+    }
+    None
+}
+
+struct CycleInfo {
+    provider_name: Option<String>,
+    cycle: Option<String>,
+    airac: Option<String>,
+}
+
+/// Parse a Navigraph/Aerosoft style cycle_info.txt ("AIRAC cycle    : 2401")
+fn parse_cycle_info_txt(path: &Path) -> Option<CycleInfo> {
+    let content = fs::read_to_string(path).ok()?;
+
+    let cycle = content.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let key = key.trim().to_ascii_lowercase();
+        (key == "airac cycle" || key == "cycle").then(|| value.trim().to_string())
+    });
+
+    let lower = content.to_ascii_lowercase();
+    let provider_name = if lower.contains("navigraph") {
+        Some("Navigraph".to_string())
+    } else if lower.contains("aerosoft") || lower.contains("navdatapro") {
+        Some("Aerosoft NavDataPro".to_string())
+    } else {
+        None
+    };
+
+    Some(CycleInfo {
+        provider_name,
+        cycle,
+        airac: None,
+    })
+}
+
+/// Normalize a cycle string to the 4-digit YYNN form ("2401", "AIRAC 2401 rev 1")
+fn normalize_airac_cycle(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i - start == 4 {
+            let digits = &value[start..i];
+            let number: u32 = digits.parse().ok()?;
+            if (1..=14).contains(&(number % 100)) {
+                return Some(digits.to_string());
+            }
+        }
+    }
+    None
+}
+
+pub(crate) fn effective_airac_cycle(cycle: Option<&str>, airac: Option<&str>) -> Option<String> {
+    cycle
+        .and_then(normalize_airac_cycle)
+        .or_else(|| airac.and_then(normalize_airac_cycle))
+}
+
+/// AIRAC cycle of a cycle.json, in YYNN form
+pub(crate) fn navdata_info_cycle(info: &NavdataInfo) -> Option<String> {
+    effective_airac_cycle(info.cycle.as_deref(), info.airac.as_deref())
+}
+
+/// The cycle X-Plane uses now when installing `new` would go back to an older
+/// one. The installed cycle is the target's cycle.json (`existing`) or, without
+/// one, the sim's default data.
+pub(crate) fn navdata_downgrade_from(
+    new: &NavdataInfo,
+    existing: Option<&NavdataInfo>,
+    xplane_path: &Path,
+) -> Option<String> {
+    let new_cycle = navdata_info_cycle(new)?;
+    let installed_cycle = existing
+        .and_then(navdata_info_cycle)
+        .or_else(|| base_navdata_cycle(xplane_path))?;
+    (new_cycle < installed_cycle).then_some(installed_cycle)
+}
+
+/// "2405 → 2313" for a navdata backup: the cycle it holds, then the one
+/// installed over it
+fn navdata_backup_cycle_label(verification: &NavdataBackupVerification) -> Option<String> {
+    let backed_up =
+        effective_airac_cycle(verification.cycle.as_deref(), verification.airac.as_deref());
+    match (backed_up, verification.replaced_by_cycle.as_deref()) {
+        (Some(backed_up), Some(replaced_by)) => Some(format!("{} → {}", backed_up, replaced_by)),
+        (backed_up, _) => backed_up,
+    }
+}
+
 /// Toggle enabled state for a management item
 /// - Aircraft: Rename .acf <-> .xfma files (not scanning subdirectories)
 /// - Plugins: Rename .xpl <-> .xfmp files (including subdirectories)
@@ -1414,7 +1848,7 @@ pub fn open_management_folder(
     Ok(())
 }
 
-/// Open a livery folder in the system file explorer
+/// Show a livery folder selected inside the aircraft's liveries folder
 pub fn open_livery_folder(
     xplane_path: &Path,
     aircraft_folder: &str,
@@ -1438,28 +1872,7 @@ pub fn open_livery_folder(
     let canonical_target = path_utils::validate_child_path(&aircraft_base, &livery_path)
         .map_err(|e| anyhow!("Invalid path: {}", e))?;
 
-    #[cfg(target_os = "windows")]
-    {
-        std::process::Command::new("explorer")
-            .arg(&canonical_target)
-            .spawn()?;
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open")
-            .arg(&canonical_target)
-            .spawn()?;
-    }
-
-    #[cfg(target_os = "linux")]
-    {
-        std::process::Command::new("xdg-open")
-            .arg(&canonical_target)
-            .spawn()?;
-    }
-
-    Ok(())
+    file_explorer::reveal_in_explorer(&canonical_target)
 }
 
 /// Check for aircraft updates by fetching remote skunkcrafts_updater.cfg files
@@ -1503,7 +1916,13 @@ pub async fn check_aircraft_updates(aircraft: &mut [AircraftInfo]) {
 
         // Update aircraft with results
         for ((idx, _), result) in update_tasks.into_iter().zip(results) {
-            if let Some(remote_version) = result {
+            let Some(remote) = result else {
+                continue;
+            };
+            if let Some(icon_url) = &remote.icon_url {
+                addon_icons::record_icon_url("aircraft", &aircraft[idx].folder_name, icon_url);
+            }
+            if let Some(remote_version) = remote.version {
                 let local_version = aircraft[idx].version.as_deref().unwrap_or("");
                 aircraft[idx].latest_version = Some(remote_version.clone());
                 aircraft[idx].has_update = remote_version != local_version;
@@ -1559,7 +1978,13 @@ pub async fn check_plugins_updates(plugins: &mut [PluginInfo]) {
 
     // Update plugins with results
     for ((idx, _), result) in update_tasks.into_iter().zip(results) {
-        if let Some(remote_version) = result {
+        let Some(remote) = result else {
+            continue;
+        };
+        if let Some(icon_url) = &remote.icon_url {
+            addon_icons::record_icon_url("plugin", &plugins[idx].folder_name, icon_url);
+        }
+        if let Some(remote_version) = remote.version {
             let local_version = plugins[idx].version.as_deref().unwrap_or("");
             plugins[idx].latest_version = Some(remote_version.clone());
             plugins[idx].has_update = remote_version != local_version;
@@ -1567,13 +1992,19 @@ pub async fn check_plugins_updates(plugins: &mut [PluginInfo]) {
     }
 }
 
-/// Fetch remote version from skunkcrafts_updater.cfg
-async fn fetch_remote_version(base_url: String) -> Option<String> {
+/// Version and icon advertised by a remote skunkcrafts_updater.cfg
+pub struct RemoteUpdaterCfg {
+    pub version: Option<String>,
+    pub icon_url: Option<String>,
+}
+
+/// Fetch remote version (and icon URL, if any) from skunkcrafts_updater.cfg
+pub async fn fetch_remote_version(base_url: String) -> Option<RemoteUpdaterCfg> {
     let url = format!("{}/skunkcrafts_updater.cfg", base_url.trim_end_matches('/'));
 
-    // Build client with system proxy support (reads from Windows system settings)
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
+    // Proxy and certificates come from the shared network settings
+    let client = http_client::client_builder(http_client::DEFAULT_USER_AGENT)
+        .timeout(http_client::timeout(std::time::Duration::from_secs(10)))
         .build()
         .ok()?;
 
@@ -1593,25 +2024,22 @@ async fn fetch_remote_version(base_url: String) -> Option<String> {
     }
 
     let content = response.text().await.ok()?;
+    let icon_url = addon_icons::icon_url_from_cfg(&content, &base_url);
 
     // Parse version from config
-    for line in content.lines() {
+    let version = content.lines().find_map(|line| {
         let line = line.trim();
-        if line.to_lowercase().starts_with("version|") {
-            let parts: Vec<&str> = line.splitn(2, '|').collect();
-            if parts.len() == 2 {
-                let version = parts[1].trim();
-                if !version.is_empty() {
-                    return Some(version.to_string());
-                }
-            }
+        if !line.to_lowercase().starts_with("version|") {
+            return None;
         }
-    }
+        let version = line.split_once('|')?.1.trim();
+        (!version.is_empty()).then(|| version.to_string())
+    });
 
-    None
+    Some(RemoteUpdaterCfg { version, icon_url })
 }
 
-fn is_x_updater_url(url: &str) -> bool {
+pub fn is_x_updater_url(url: &str) -> bool {
     url.to_lowercase()
         .starts_with(&XUPDATER_URL_PREFIX.to_lowercase())
 }
@@ -1714,6 +2142,7 @@ pub fn scan_navdata_backups(xplane_path: &Path) -> Result<Vec<NavdataBackupInfo>
 
                         backups.push(NavdataBackupInfo {
                             folder_name,
+                            cycle_label: navdata_backup_cycle_label(&verification),
                             verification,
                         });
                     }
@@ -2090,6 +2519,256 @@ pub fn delete_aircraft_livery(
     Ok(())
 }
 
+/// Size of every livery folder of an aircraft, largest first
+pub fn get_liveries_disk_usage(
+    xplane_path: &Path,
+    aircraft_folder: &str,
+) -> Result<Vec<LiveryDiskUsage>> {
+    let liveries = get_aircraft_liveries(xplane_path, aircraft_folder)?;
+    let liveries_path = xplane_path
+        .join("Aircraft")
+        .join(aircraft_folder)
+        .join("liveries");
+
+    let mut usage: Vec<LiveryDiskUsage> = liveries
+        .into_par_iter()
+        .map(|livery| {
+            let (size_bytes, file_count) =
+                livery_folder_size(&liveries_path.join(&livery.folder_name));
+            LiveryDiskUsage {
+                folder_name: livery.folder_name,
+                size_bytes,
+                file_count,
+            }
+        })
+        .collect();
+    usage.sort_by(|a, b| {
+        b.size_bytes.cmp(&a.size_bytes).then_with(|| {
+            a.folder_name
+                .to_lowercase()
+                .cmp(&b.folder_name.to_lowercase())
+        })
+    });
+    Ok(usage)
+}
+
+/// Total size and newest file modification time (Unix seconds) of an addon
+/// folder, served from the directory cache while it is still fresh
+pub fn addon_folder_usage(path: &Path) -> (u64, Option<i64>) {
+    let to_unix = |time: std::time::SystemTime| {
+        time.duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_secs() as i64)
+    };
+
+    if let Some(cached) = cache::get_cached_directory_metadata(path) {
+        return (cached.total_size, cached.newest_modified.and_then(to_unix));
+    }
+
+    let mut total_size = 0u64;
+    let mut file_count = 0usize;
+    let mut newest_modified = None;
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        total_size += metadata.len();
+        file_count += 1;
+        if let Ok(modified) = metadata.modified() {
+            newest_modified = newest_modified.max(Some(modified));
+        }
+    }
+
+    cache::cache_directory_metadata(path, total_size, file_count, newest_modified);
+    (total_size, newest_modified.and_then(to_unix))
+}
+
+fn livery_folder_size(path: &Path) -> (u64, usize) {
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .fold((0, 0), |(size, count), entry| {
+            (
+                size + entry.metadata().map(|m| m.len()).unwrap_or(0),
+                count + 1,
+            )
+        })
+}
+
+/// Livery folder named by a `.../liveries/<name>/...` path in a settings line,
+/// lowercased
+fn livery_in_path_reference(line: &str) -> Option<String> {
+    let normalized = line.replace('\\', "/").to_lowercase();
+    let start = normalized.find("liveries/")? + "liveries/".len();
+    let name = normalized[start..].split(['/', '"']).next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Liveries an aircraft is set to load with, mapped (lowercased) to the file
+/// that references them.
+///
+/// `.acf` files name their default livery either as a `liveries/<name>` path or
+/// as the value of a `..._livery...` property; X-Plane's preference files record
+/// the last livery used per aircraft as a path below the aircraft folder.
+fn find_default_livery_references(
+    xplane_path: &Path,
+    aircraft_folder: &str,
+    aircraft_path: &Path,
+) -> HashMap<String, String> {
+    let mut references = HashMap::new();
+
+    let acf_files = fs::read_dir(aircraft_path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case("acf"))
+        });
+    for acf in acf_files {
+        let source = acf
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for_each_livery_line(&acf, |line| {
+            let livery = livery_in_path_reference(line).or_else(|| {
+                let (key, value) = line.trim().rsplit_once(char::is_whitespace)?;
+                key.to_lowercase()
+                    .contains("livery")
+                    .then(|| value.trim().to_lowercase())
+            });
+            if let Some(livery) = livery.filter(|l| !l.is_empty()) {
+                references.entry(livery).or_insert_with(|| source.clone());
+            }
+        });
+    }
+
+    // Preference lines only count when they point below this aircraft
+    let aircraft_marker = format!(
+        "{}/liveries/",
+        aircraft_folder.replace('\\', "/").to_lowercase()
+    );
+    let prefs_dir = xplane_path.join("Output").join("preferences");
+    let pref_files = fs::read_dir(&prefs_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("prf"))
+        });
+    for prf in pref_files {
+        let source = format!(
+            "Output/preferences/{}",
+            prf.file_name().unwrap_or_default().to_string_lossy()
+        );
+        for_each_livery_line(&prf, |line| {
+            if !line
+                .replace('\\', "/")
+                .to_lowercase()
+                .contains(&aircraft_marker)
+            {
+                return;
+            }
+            if let Some(livery) = livery_in_path_reference(line) {
+                references.entry(livery).or_insert_with(|| source.clone());
+            }
+        });
+    }
+
+    references
+}
+
+/// Call `f` with every line of a text file that mentions a livery.
+/// `.acf` files are not guaranteed to be valid UTF-8, so lines are read lossily.
+fn for_each_livery_line(path: &Path, mut f: impl FnMut(&str)) {
+    let Ok(file) = fs::File::open(path) else {
+        return;
+    };
+    for line in BufReader::new(file).split(b'\n').map_while(|l| l.ok()) {
+        let line = String::from_utf8_lossy(&line);
+        let lower = line.to_lowercase();
+        if lower.contains("livery") || lower.contains("liveries") {
+            f(&line);
+        }
+    }
+}
+
+/// Delete several liveries of an aircraft. Failures don't stop the remaining
+/// deletions; each livery gets its own result. Liveries the aircraft is set to
+/// load with (in its `.acf` or X-Plane's preferences) are refused.
+pub fn delete_aircraft_liveries(
+    xplane_path: &Path,
+    aircraft_folder: &str,
+    livery_folders: &[String],
+) -> Result<Vec<LiveryDeleteResult>> {
+    // Validate aircraft_folder: reject empty and ".."
+    if aircraft_folder.is_empty() || aircraft_folder.contains("..") {
+        return Err(anyhow!("Invalid aircraft folder name"));
+    }
+
+    let aircraft_base = xplane_path.join("Aircraft");
+    let aircraft_path = aircraft_base.join(aircraft_folder);
+    if !aircraft_path.exists() {
+        return Err(anyhow!("Aircraft folder not found"));
+    }
+    let canonical_aircraft = path_utils::validate_child_path(&aircraft_base, &aircraft_path)
+        .map_err(|e| anyhow!("Invalid aircraft path: {}", e))?;
+
+    let references =
+        find_default_livery_references(xplane_path, aircraft_folder, &canonical_aircraft);
+
+    let results: Vec<LiveryDeleteResult> = livery_folders
+        .iter()
+        .map(|livery_folder| {
+            let outcome = match references.get(&livery_folder.to_lowercase()) {
+                Some(source) => Err(anyhow!(
+                    "Livery is the aircraft's default (referenced in {})",
+                    source
+                )),
+                None => delete_aircraft_livery(xplane_path, aircraft_folder, livery_folder),
+            };
+            match outcome {
+                Ok(()) => LiveryDeleteResult {
+                    folder_name: livery_folder.clone(),
+                    deleted: true,
+                    error: None,
+                },
+                Err(e) => LiveryDeleteResult {
+                    folder_name: livery_folder.clone(),
+                    deleted: false,
+                    error: Some(e.to_string()),
+                },
+            }
+        })
+        .collect();
+
+    logger::log_info(
+        &format!(
+            "Bulk livery delete for '{}': {} of {} deleted",
+            aircraft_folder,
+            results.iter().filter(|r| r.deleted).count(),
+            results.len()
+        ),
+        Some("management"),
+    );
+
+    Ok(results)
+}
+
 /// Scan FlyWithLua scripts in the Scripts directory
 pub fn scan_lua_scripts(xplane_path: &Path) -> Result<Vec<LuaScriptInfo>> {
     let scripts_path = xplane_path
@@ -2280,9 +2959,399 @@ pub fn delete_lua_script(xplane_path: &Path, file_name: &str) -> Result<()> {
     Ok(())
 }
 
+// ========== Addon health check ==========
+
+/// Event emitted with a [`HealthCheckProgress`] while the health check runs
+pub const HEALTH_CHECK_PROGRESS_EVENT: &str = "health-check-progress";
+
+/// Depth below each top-level aircraft folder searched for `liveries` folders
+const HEALTH_LIVERIES_MAX_DEPTH: usize = 6;
+
+pub type HealthCheckProgressCallback = dyn Fn(HealthCheckProgress) + Sync;
+
+fn health_issue(
+    category: HealthCheckCategory,
+    severity: HealthSeverity,
+    path: &Path,
+    reason_key: &str,
+    suggested_action: HealthAction,
+) -> HealthIssue {
+    HealthIssue {
+        category,
+        severity,
+        path: path.to_string_lossy().to_string(),
+        reason_key: reason_key.to_string(),
+        reason_params: HashMap::new(),
+        suggested_action,
+    }
+}
+
+/// Folders directly inside `dir` (following links), skipping hidden ones, sorted
+fn health_check_folders(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut folders: Vec<PathBuf> = read_dir
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    folders.sort();
+    folders
+}
+
+fn report_health_progress(
+    progress: &HealthCheckProgressCallback,
+    category: HealthCheckCategory,
+    processed: usize,
+    total: usize,
+    current: Option<&Path>,
+) {
+    progress(HealthCheckProgress {
+        category,
+        processed,
+        total,
+        current: current.map(|path| path.to_string_lossy().to_string()),
+    });
+}
+
+/// Child of `dir` with the given name in any case
+fn find_child_ignore_case(dir: &Path, name: &str) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .eq_ignore_ascii_case(name)
+        })
+        .map(|entry| entry.path())
+}
+
+/// Whether `dir` directly holds an aircraft, enabled (.acf) or disabled (.xfma)
+fn has_aircraft_file(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .map(|read_dir| {
+            read_dir.flatten().any(|entry| {
+                let path = entry.path();
+                path.is_file()
+                    && path.extension().is_some_and(|ext| {
+                        ext.eq_ignore_ascii_case("acf") || ext.eq_ignore_ascii_case("xfma")
+                    })
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// `liveries` folders next to no aircraft: the aircraft was removed but its
+/// liveries, often the bulk of the size, were left behind
+pub fn check_orphaned_liveries(
+    xplane_path: &Path,
+    progress: &HealthCheckProgressCallback,
+) -> Vec<HealthIssue> {
+    let category = HealthCheckCategory::OrphanedLiveries;
+    let folders = health_check_folders(&xplane_path.join("Aircraft"));
+    report_health_progress(progress, category, 0, folders.len(), None);
+
+    let mut issues = Vec::new();
+    for (index, folder) in folders.iter().enumerate() {
+        let mut walker = WalkDir::new(folder)
+            .max_depth(HEALTH_LIVERIES_MAX_DEPTH)
+            .into_iter();
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().is_dir()
+                || !entry
+                    .file_name()
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case("liveries")
+            {
+                continue;
+            }
+            walker.skip_current_dir();
+
+            let owner = entry.path().parent().unwrap_or(folder);
+            if !has_aircraft_file(owner) {
+                issues.push(health_issue(
+                    category,
+                    HealthSeverity::Warning,
+                    entry.path(),
+                    "health.orphanedLiveries",
+                    HealthAction::Delete,
+                ));
+            }
+        }
+        report_health_progress(progress, category, index + 1, folders.len(), Some(folder));
+    }
+    issues
+}
+
+/// Platform a plugin binary is built for, from its folder (`win_x64/`) or
+/// file name (`mac.xpl`); None for binaries that don't say
+fn plugin_binary_platform(relative_path: &str) -> Option<&'static str> {
+    relative_path.split(['/', '\\']).find_map(|component| {
+        let lower = component.to_ascii_lowercase();
+        let name = lower
+            .strip_suffix(".xpl")
+            .or_else(|| lower.strip_suffix(".xfmp"))
+            .unwrap_or(&lower);
+        match name {
+            "win" | "win_x64" | "win32" | "win64" => Some("win"),
+            "mac" | "mac_x64" | "mac_arm64" => Some("mac"),
+            "lin" | "lin_x64" | "lin32" | "lin64" => Some("lin"),
+            _ => None,
+        }
+    })
+}
+
+fn current_plugin_platform() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "win"
+    } else if cfg!(target_os = "macos") {
+        "mac"
+    } else {
+        "lin"
+    }
+}
+
+/// Problem with one plugin folder: no binary at all, or only binaries for
+/// other platforms. Disabled (.xfmp) binaries count, so disabling a plugin
+/// doesn't make it show up here.
+fn plugin_binary_issue(folder: &Path, platform: &str) -> Option<HealthIssue> {
+    let (xpl_files, xfmp_files) = find_xpl_and_xfmp_files(folder);
+    let binaries: Vec<&String> = xpl_files.iter().chain(xfmp_files.iter()).collect();
+    let category = HealthCheckCategory::PluginWithoutBinary;
+
+    if binaries.is_empty() {
+        return Some(health_issue(
+            category,
+            HealthSeverity::Warning,
+            folder,
+            "health.pluginNoBinary",
+            HealthAction::Delete,
+        ));
+    }
+
+    let platforms: Option<Vec<&str>> = binaries
+        .iter()
+        .map(|binary| plugin_binary_platform(binary))
+        .collect();
+    let mut platforms = platforms?;
+    if platforms.contains(&platform) {
+        return None;
+    }
+    platforms.sort_unstable();
+    platforms.dedup();
+
+    let mut issue = health_issue(
+        category,
+        HealthSeverity::Error,
+        folder,
+        "health.pluginWrongPlatform",
+        HealthAction::Reinstall,
+    );
+    issue
+        .reason_params
+        .insert("platforms".to_string(), platforms.join(", "));
+    Some(issue)
+}
+
+/// Plugin folders X-Plane can't load on this platform
+pub fn check_plugin_binaries(
+    xplane_path: &Path,
+    progress: &HealthCheckProgressCallback,
+) -> Vec<HealthIssue> {
+    let category = HealthCheckCategory::PluginWithoutBinary;
+    let folders = health_check_folders(&xplane_path.join("Resources").join("plugins"));
+    report_health_progress(progress, category, 0, folders.len(), None);
+
+    let platform = current_plugin_platform();
+    let mut issues = Vec::new();
+    for (index, folder) in folders.iter().enumerate() {
+        issues.extend(plugin_binary_issue(folder, platform));
+        report_health_progress(progress, category, index + 1, folders.len(), Some(folder));
+    }
+    issues
+}
+
+/// Problem with one Custom Scenery folder: nothing X-Plane would load from it
+fn scenery_content_issue(folder: &Path) -> Option<HealthIssue> {
+    let has_earth_nav_data =
+        find_child_ignore_case(folder, "Earth nav data").is_some_and(|path| path.is_dir());
+    let has_library =
+        find_child_ignore_case(folder, "library.txt").is_some_and(|path| path.is_file());
+    let has_plugins = find_child_ignore_case(folder, "plugins").is_some_and(|plugins| {
+        let (xpl_files, xfmp_files) = find_xpl_and_xfmp_files(&plugins);
+        !xpl_files.is_empty() || !xfmp_files.is_empty()
+    });
+    if has_earth_nav_data || has_library || has_plugins {
+        return None;
+    }
+
+    let is_empty = fs::read_dir(folder)
+        .map(|mut read_dir| read_dir.next().is_none())
+        .unwrap_or(false);
+    Some(if is_empty {
+        health_issue(
+            HealthCheckCategory::SceneryWithoutContent,
+            HealthSeverity::Info,
+            folder,
+            "health.sceneryEmpty",
+            HealthAction::Delete,
+        )
+    } else {
+        // Usually extracted one level too deep, or an incomplete install
+        health_issue(
+            HealthCheckCategory::SceneryWithoutContent,
+            HealthSeverity::Warning,
+            folder,
+            "health.sceneryNoContent",
+            HealthAction::Reinstall,
+        )
+    })
+}
+
+/// Custom Scenery folders with no Earth nav data, no library.txt and no plugins
+pub fn check_scenery_content(
+    xplane_path: &Path,
+    progress: &HealthCheckProgressCallback,
+) -> Vec<HealthIssue> {
+    let category = HealthCheckCategory::SceneryWithoutContent;
+    let folders = health_check_folders(&xplane_path.join("Custom Scenery"));
+    report_health_progress(progress, category, 0, folders.len(), None);
+
+    let mut issues = Vec::new();
+    for (index, folder) in folders.iter().enumerate() {
+        issues.extend(scenery_content_issue(folder));
+        report_health_progress(progress, category, index + 1, folders.len(), Some(folder));
+    }
+    issues
+}
+
+/// AIRAC cycle of the navdata shipped with the sim, from the header of
+/// `Resources/default data/earth_nav.dat` ("1200 Version - data cycle 2401, ...")
+fn base_navdata_cycle(xplane_path: &Path) -> Option<String> {
+    let file = fs::File::open(
+        xplane_path
+            .join("Resources")
+            .join("default data")
+            .join("earth_nav.dat"),
+    )
+    .ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(3)
+        .map_while(|line| line.ok())
+        .find_map(|line| {
+            let pos = line.to_ascii_lowercase().find("data cycle")?;
+            normalize_airac_cycle(&line[pos + "data cycle".len()..])
+        })
+}
+
+/// Custom Data navdata older than the sim's own: X-Plane prefers Custom Data,
+/// so it replaces newer data with older
+pub fn check_outdated_navdata(
+    xplane_path: &Path,
+    base_cycle: Option<&str>,
+    progress: &HealthCheckProgressCallback,
+) -> Vec<HealthIssue> {
+    let category = HealthCheckCategory::OutdatedNavdata;
+    let entries = match (base_cycle, scan_navdata(xplane_path)) {
+        (Some(_), Ok(data)) => data.entries,
+        _ => Vec::new(),
+    };
+    report_health_progress(progress, category, 0, entries.len(), None);
+
+    let custom_data_path = xplane_path.join("Custom Data");
+    let mut issues = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let folder = custom_data_path.join(&entry.folder_name);
+        let cycle = effective_airac_cycle(entry.cycle.as_deref(), entry.airac.as_deref());
+        if let (Some(cycle), Some(base_cycle)) = (cycle, base_cycle) {
+            if cycle.as_str() < base_cycle {
+                let mut issue = health_issue(
+                    category,
+                    HealthSeverity::Warning,
+                    &folder,
+                    "health.navdataOlderThanSim",
+                    HealthAction::Delete,
+                );
+                issue.reason_params.extend([
+                    ("provider".to_string(), entry.provider_name.clone()),
+                    ("cycle".to_string(), cycle),
+                    ("baseCycle".to_string(), base_cycle.to_string()),
+                ]);
+                issues.push(issue);
+            }
+        }
+        report_health_progress(progress, category, index + 1, entries.len(), Some(&folder));
+    }
+    issues
+}
+
+/// Run every health check over the install. Only detects problems; fixing them
+/// is left to the existing delete/toggle/install commands.
+pub fn run_health_check(
+    xplane_path: &Path,
+    progress: &HealthCheckProgressCallback,
+) -> Result<HealthCheckReport> {
+    if !xplane_path.exists() {
+        return Err(anyhow!("X-Plane folder not found"));
+    }
+
+    let base_navdata_cycle = base_navdata_cycle(xplane_path);
+    let mut issues = check_orphaned_liveries(xplane_path, progress);
+    issues.extend(check_plugin_binaries(xplane_path, progress));
+    issues.extend(check_scenery_content(xplane_path, progress));
+    issues.extend(check_outdated_navdata(
+        xplane_path,
+        base_navdata_cycle.as_deref(),
+        progress,
+    ));
+
+    issues.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.category.as_str().cmp(b.category.as_str()))
+            .then_with(|| a.path.to_lowercase().cmp(&b.path.to_lowercase()))
+    });
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for issue in &issues {
+        *counts
+            .entry(issue.category.as_str().to_string())
+            .or_default() += 1;
+    }
+
+    logger::log_info(
+        &format!("Health check found {} issue(s)", issues.len()),
+        Some("management"),
+    );
+
+    Ok(HealthCheckReport {
+        issues,
+        counts,
+        base_navdata_cycle,
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{scan_aircraft, toggle_aircraft_acf_file, toggle_management_item};
+    use super::{
+        base_navdata_cycle, check_orphaned_liveries, check_outdated_navdata,
+        delete_aircraft_liveries, get_liveries_disk_usage, get_navdata_consistency_report,
+        navdata_backup_cycle_label, navdata_downgrade_from, normalize_airac_cycle,
+        plugin_binary_issue, run_health_check, scan_aircraft, scan_aircraft_target,
+        scan_plugin_target, scan_plugins, scenery_content_issue, toggle_aircraft_acf_file,
+        toggle_management_item,
+    };
+    use crate::models::{
+        HealthAction, HealthCheckCategory, NavdataBackupVerification, NavdataInfo,
+        NavdataSourceKind,
+    };
     use std::fs;
     use tempfile::tempdir;
 
@@ -2290,7 +3359,7 @@ mod tests {
     fn scan_aircraft_returns_empty_when_aircraft_folder_is_missing() {
         let temp = tempdir().expect("failed to create tempdir");
 
-        let result = scan_aircraft(temp.path()).expect("scan_aircraft should not fail");
+        let result = scan_aircraft(temp.path(), false).expect("scan_aircraft should not fail");
 
         assert!(result.entries.is_empty());
         assert_eq!(result.total_count, 0);
@@ -2305,7 +3374,7 @@ mod tests {
         fs::write(aircraft_dir.join("DemoPlane.acf"), "acf").expect("failed to write acf");
         fs::write(aircraft_dir.join("DemoPlane_cargo.xfma"), "xfma").expect("failed to write xfma");
 
-        let result = scan_aircraft(temp.path()).expect("scan_aircraft should not fail");
+        let result = scan_aircraft(temp.path(), false).expect("scan_aircraft should not fail");
         let entry = result.entries.first().expect("expected aircraft entry");
 
         assert_eq!(entry.acf_files.len(), 2);
@@ -2313,6 +3382,100 @@ mod tests {
         assert!(entry.has_mixed_acf_states);
     }
 
+    #[test]
+    fn single_target_scans_match_full_scan_entries() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let aircraft = temp.path().join("Aircraft");
+        let demo = aircraft.join("DemoPlane");
+        fs::create_dir_all(demo.join("liveries").join("Red")).expect("failed to create demo");
+        fs::write(demo.join("DemoPlane.acf"), "acf").expect("failed to write acf");
+        fs::write(demo.join("DemoPlane_cargo.xfma"), "xfma").expect("failed to write xfma");
+        let collection = aircraft.join("Collection");
+        for name in ["Alpha", "Bravo"] {
+            let dir = collection.join("Props").join(name);
+            fs::create_dir_all(&dir).expect("failed to create nested aircraft");
+            fs::write(dir.join(format!("{}.acf", name)), "acf").expect("failed to write acf");
+        }
+        let plugin = temp
+            .path()
+            .join("Resources")
+            .join("plugins")
+            .join("DemoPlugin");
+        fs::create_dir_all(plugin.join("64")).expect("failed to create plugin");
+        fs::write(plugin.join("64").join("win.xpl"), "xpl").expect("failed to write xpl");
+
+        let full = scan_aircraft(temp.path(), false).expect("scan_aircraft should not fail");
+        let full_entry = |folder: &str| {
+            let entry = full
+                .entries
+                .iter()
+                .find(|e| e.folder_name == folder)
+                .expect("expected entry in full scan");
+            serde_json::to_value(entry).unwrap()
+        };
+
+        let single = scan_aircraft_target(temp.path(), &demo).expect("single scan should succeed");
+        assert_eq!(single.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&single[0]).unwrap(),
+            full_entry("DemoPlane")
+        );
+
+        let nested =
+            scan_aircraft_target(temp.path(), &collection).expect("nested scan should succeed");
+        let nested_folders: Vec<_> = nested.iter().map(|e| e.folder_name.clone()).collect();
+        assert_eq!(nested.len(), 2);
+        for entry in &nested {
+            assert_eq!(
+                serde_json::to_value(entry).unwrap(),
+                full_entry(&entry.folder_name)
+            );
+        }
+        assert!(nested_folders.iter().all(|f| f.starts_with("Collection")));
+
+        let full_plugins = scan_plugins(temp.path(), false).expect("scan_plugins should not fail");
+        let single_plugin = scan_plugin_target(temp.path(), &plugin)
+            .expect("plugin scan should succeed")
+            .expect("expected plugin entry");
+        assert_eq!(
+            serde_json::to_value(&single_plugin).unwrap(),
+            serde_json::to_value(&full_plugins.entries[0]).unwrap()
+        );
+
+        assert!(scan_aircraft_target(temp.path(), &plugin).is_err());
+    }
+
+    #[test]
+    fn scans_report_sizes_only_when_requested() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let demo = temp.path().join("Aircraft").join("DemoPlane");
+        fs::create_dir_all(demo.join("liveries").join("Red")).expect("failed to create demo");
+        fs::write(demo.join("DemoPlane.acf"), "acf").expect("failed to write acf");
+        fs::write(demo.join("liveries").join("Red").join("paint.dds"), "12345")
+            .expect("failed to write livery");
+        let plugin = temp
+            .path()
+            .join("Resources")
+            .join("plugins")
+            .join("DemoPlugin");
+        fs::create_dir_all(plugin.join("64")).expect("failed to create plugin");
+        fs::write(plugin.join("64").join("win.xpl"), "xpl").expect("failed to write xpl");
+
+        let plain = scan_aircraft(temp.path(), false).expect("scan_aircraft should not fail");
+        assert_eq!(plain.entries[0].size_bytes, None);
+        assert_eq!(plain.entries[0].newest_file_modified, None);
+
+        let sized = scan_aircraft(temp.path(), true).expect("scan_aircraft should not fail");
+        let entry = &sized.entries[0];
+        assert_eq!(entry.size_bytes, Some(8));
+        assert_eq!(entry.livery_count, 1);
+        assert!(entry.newest_file_modified.is_some_and(|secs| secs > 0));
+
+        let plugins = scan_plugins(temp.path(), true).expect("scan_plugins should not fail");
+        assert_eq!(plugins.entries[0].size_bytes, Some(3));
+        assert!(plugins.entries[0].newest_file_modified.is_some());
+    }
+
     #[test]
     fn toggle_aircraft_acf_file_updates_partial_state() {
         let temp = tempdir().expect("failed to create tempdir");
@@ -2348,7 +3511,7 @@ mod tests {
             .expect("toggle_management_item should enable aircraft");
         assert!(enabled);
 
-        let result = scan_aircraft(temp.path()).expect("scan_aircraft should not fail");
+        let result = scan_aircraft(temp.path(), false).expect("scan_aircraft should not fail");
         let entry = result.entries.first().expect("expected aircraft entry");
         assert!(aircraft_dir.join("DemoPlane.acf").exists());
         assert!(aircraft_dir.join("DemoPlane_cargo.xfma").exists());
@@ -2381,11 +3544,316 @@ mod tests {
             .expect("toggle_management_item should enable all aircraft files");
         assert!(enabled);
 
-        let result = scan_aircraft(temp.path()).expect("scan_aircraft should not fail");
+        let result = scan_aircraft(temp.path(), false).expect("scan_aircraft should not fail");
         let entry = result.entries.first().expect("expected aircraft entry");
         assert!(aircraft_dir.join("DemoPlane.acf").exists());
         assert!(aircraft_dir.join("DemoPlane_cargo.acf").exists());
         assert!(entry.enabled);
         assert!(!entry.has_mixed_acf_states);
     }
+
+    #[test]
+    fn bulk_livery_delete_skips_default_liveries_and_continues_past_failures() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let aircraft_dir = temp.path().join("Aircraft").join("B737");
+        let liveries = aircraft_dir.join("liveries");
+        for (name, size) in [
+            ("Big", 300),
+            ("Small", 10),
+            ("AcfDefault", 1),
+            ("PrefDefault", 1),
+        ] {
+            fs::create_dir_all(liveries.join(name)).expect("failed to create livery");
+            fs::write(liveries.join(name).join("fuselage.png"), vec![0u8; size])
+                .expect("failed to write texture");
+        }
+        fs::write(
+            aircraft_dir.join("b737.acf"),
+            "I\n1100 version\nP acf/_livery_path liveries\\AcfDefault\\\n",
+        )
+        .expect("failed to write acf");
+        let prefs = temp.path().join("Output").join("preferences");
+        fs::create_dir_all(&prefs).expect("failed to create preferences");
+        fs::write(
+            prefs.join("X-Plane.prf"),
+            "_last_livery Aircraft/B737/liveries/PrefDefault/\n\
+             _last_livery Aircraft/Other/liveries/Small/\n",
+        )
+        .expect("failed to write prefs");
+
+        let usage = get_liveries_disk_usage(temp.path(), "B737").expect("usage should succeed");
+        assert_eq!(usage[0].folder_name, "Big");
+        assert_eq!(usage[0].size_bytes, 300);
+        assert_eq!(usage[1].folder_name, "Small");
+
+        let names: Vec<String> = ["Small", "AcfDefault", "Missing", "PrefDefault", "../B737"]
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        let results = delete_aircraft_liveries(temp.path(), "B737", &names)
+            .expect("bulk delete should succeed");
+
+        let deleted: Vec<bool> = results.iter().map(|r| r.deleted).collect();
+        assert_eq!(deleted, vec![true, false, false, false, false]);
+        assert!(results[1].error.as_deref().unwrap().contains("b737.acf"));
+        assert!(results[3].error.as_deref().unwrap().contains("X-Plane.prf"));
+        assert!(!liveries.join("Small").exists());
+        assert!(liveries.join("AcfDefault").exists());
+        assert!(liveries.join("PrefDefault").exists());
+    }
+
+    #[test]
+    fn normalize_airac_cycle_extracts_yynn() {
+        assert_eq!(normalize_airac_cycle("2401"), Some("2401".to_string()));
+        assert_eq!(
+            normalize_airac_cycle("AIRAC 2313 rev 2"),
+            Some("2313".to_string())
+        );
+        assert_eq!(normalize_airac_cycle("2450"), None);
+        assert_eq!(normalize_airac_cycle("v12"), None);
+    }
+
+    #[test]
+    fn navdata_downgrade_compares_against_installed_or_default_cycle() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        let info = |cycle: &str| NavdataInfo {
+            name: "Navigraph".to_string(),
+            cycle: Some(cycle.to_string()),
+            airac: None,
+        };
+
+        assert_eq!(
+            navdata_downgrade_from(&info("2313"), Some(&info("2405")), root),
+            Some("2405".to_string())
+        );
+        assert_eq!(
+            navdata_downgrade_from(&info("2405"), Some(&info("2313")), root),
+            None
+        );
+        // Nothing to compare against
+        assert_eq!(navdata_downgrade_from(&info("2313"), None, root), None);
+
+        // Without a cycle.json at the target, the sim's default data counts
+        let default_data = root.join("Resources").join("default data");
+        fs::create_dir_all(&default_data).expect("failed to create default data");
+        fs::write(
+            default_data.join("earth_nav.dat"),
+            "I\n1200 Version - data cycle 2401, build 20231228\n",
+        )
+        .expect("failed to write earth_nav.dat");
+        assert_eq!(
+            navdata_downgrade_from(&info("2313"), None, root),
+            Some("2401".to_string())
+        );
+
+        let mut verification = NavdataBackupVerification {
+            provider_name: "Navigraph".to_string(),
+            cycle: Some("2405".to_string()),
+            airac: None,
+            replaced_by_cycle: Some("2313".to_string()),
+            backup_time: String::new(),
+            files: Vec::new(),
+            file_count: 0,
+        };
+        assert_eq!(
+            navdata_backup_cycle_label(&verification).as_deref(),
+            Some("2405 → 2313")
+        );
+        verification.replaced_by_cycle = None;
+        assert_eq!(
+            navdata_backup_cycle_label(&verification).as_deref(),
+            Some("2405")
+        );
+    }
+
+    #[test]
+    fn navdata_consistency_report_flags_outdated_aircraft_navdata() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+
+        let custom_data = root.join("Custom Data");
+        fs::create_dir_all(&custom_data).expect("failed to create Custom Data");
+        fs::write(
+            custom_data.join("cycle.json"),
+            r#"{"name":"Navigraph","cycle":"2401"}"#,
+        )
+        .expect("failed to write cycle.json");
+
+        let aircraft_dir = root.join("Aircraft").join("B737-800X");
+        let navdata_dir = aircraft_dir.join("plugins").join("xlfmc").join("navdata");
+        fs::create_dir_all(&navdata_dir).expect("failed to create aircraft navdata dir");
+        fs::write(aircraft_dir.join("b738.acf"), "acf").expect("failed to write acf");
+        fs::write(
+            navdata_dir.join("cycle_info.txt"),
+            "AIRAC cycle    : 2313\nVersion        : 1\nForum          : http://forum.navigraph.com\n",
+        )
+        .expect("failed to write cycle_info.txt");
+
+        let report = get_navdata_consistency_report(root).expect("report should succeed");
+
+        assert_eq!(report.newest_cycle.as_deref(), Some("2401"));
+        assert_eq!(report.outdated_count, 1);
+        let outdated = report
+            .entries
+            .iter()
+            .find(|e| e.is_outdated)
+            .expect("expected an outdated entry");
+        assert_eq!(outdated.source, NavdataSourceKind::Aircraft);
+        assert_eq!(outdated.name, "B737-800X");
+        assert_eq!(outdated.effective_cycle.as_deref(), Some("2313"));
+        assert_eq!(outdated.management_type.as_deref(), Some("aircraft"));
+        assert_eq!(
+            std::path::Path::new(&outdated.folder_path),
+            navdata_dir.as_path()
+        );
+    }
+
+    #[test]
+    fn navdata_consistency_report_counts_each_folder_once() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+
+        let gns430 = root.join("Custom Data").join("GNS430").join("navdata");
+        fs::create_dir_all(&gns430).expect("failed to create GNS430 navdata");
+        fs::write(
+            gns430.join("cycle.json"),
+            r#"{"name":"Navigraph","cycle":"2313"}"#,
+        )
+        .expect("failed to write cycle.json");
+        fs::write(gns430.join("cycle_info.txt"), "AIRAC cycle    : 2313\n")
+            .expect("failed to write cycle_info.txt");
+
+        let report = get_navdata_consistency_report(root).expect("report should succeed");
+
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].source, NavdataSourceKind::CustomData);
+        assert_eq!(report.outdated_count, 0);
+        assert_eq!(report.unknown_cycle_count, 0);
+    }
+
+    #[test]
+    fn health_check_flags_liveries_without_an_aircraft() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let aircraft = temp.path().join("Aircraft");
+        let kept = aircraft.join("A320").join("liveries").join("Red");
+        let orphaned = aircraft.join("Removed B738").join("liveries").join("Blue");
+        fs::create_dir_all(&kept).expect("failed to create livery");
+        fs::create_dir_all(&orphaned).expect("failed to create livery");
+        fs::write(aircraft.join("A320").join("a320.xfma"), "acf").expect("failed to write acf");
+
+        let issues = check_orphaned_liveries(temp.path(), &|_| {});
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].path.contains("Removed B738"));
+        assert_eq!(issues[0].reason_key, "health.orphanedLiveries");
+        assert_eq!(issues[0].suggested_action, HealthAction::Delete);
+    }
+
+    #[test]
+    fn health_check_flags_plugins_without_a_usable_binary() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let empty = temp.path().join("Empty");
+        let mac_only = temp.path().join("MacOnly");
+        let disabled = temp.path().join("Disabled");
+        fs::create_dir_all(empty.join("data")).expect("failed to create plugin");
+        fs::create_dir_all(mac_only.join("mac_x64")).expect("failed to create plugin");
+        fs::create_dir_all(disabled.join("lin_x64")).expect("failed to create plugin");
+        fs::write(mac_only.join("mac_x64").join("MacOnly.xpl"), "xpl").expect("write xpl");
+        fs::write(disabled.join("lin_x64").join("Disabled.xfmp"), "xpl").expect("write xfmp");
+
+        let issue = plugin_binary_issue(&empty, "lin").expect("expected an issue");
+        assert_eq!(issue.reason_key, "health.pluginNoBinary");
+
+        let issue = plugin_binary_issue(&mac_only, "lin").expect("expected an issue");
+        assert_eq!(issue.reason_key, "health.pluginWrongPlatform");
+        assert_eq!(issue.suggested_action, HealthAction::Reinstall);
+        assert_eq!(
+            issue.reason_params.get("platforms").map(String::as_str),
+            Some("mac")
+        );
+        assert!(plugin_binary_issue(&mac_only, "mac").is_none());
+
+        assert!(plugin_binary_issue(&disabled, "lin").is_none());
+    }
+
+    #[test]
+    fn health_check_flags_scenery_without_loadable_content() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let airport = temp.path().join("KSEA Airport");
+        let library = temp.path().join("Library");
+        let nested = temp.path().join("Nested");
+        let empty = temp.path().join("Empty");
+        fs::create_dir_all(airport.join("Earth nav data")).expect("failed to create scenery");
+        fs::create_dir_all(&library).expect("failed to create scenery");
+        fs::create_dir_all(nested.join("KSEA Airport").join("Earth nav data"))
+            .expect("failed to create scenery");
+        fs::create_dir_all(&empty).expect("failed to create scenery");
+        fs::write(library.join("library.txt"), "A\n800\nLIBRARY\n").expect("write library");
+
+        assert!(scenery_content_issue(&airport).is_none());
+        assert!(scenery_content_issue(&library).is_none());
+        let issue = scenery_content_issue(&nested).expect("expected an issue");
+        assert_eq!(issue.reason_key, "health.sceneryNoContent");
+        assert_eq!(issue.suggested_action, HealthAction::Reinstall);
+        let issue = scenery_content_issue(&empty).expect("expected an issue");
+        assert_eq!(issue.reason_key, "health.sceneryEmpty");
+        assert_eq!(issue.suggested_action, HealthAction::Delete);
+    }
+
+    #[test]
+    fn health_check_flags_custom_navdata_older_than_the_sim() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        let default_data = root.join("Resources").join("default data");
+        fs::create_dir_all(&default_data).expect("failed to create default data");
+        fs::write(
+            default_data.join("earth_nav.dat"),
+            "I\n1200 Version - data cycle 2403, build 20240301, metadata NavXP1200.\n",
+        )
+        .expect("failed to write earth_nav.dat");
+        let custom_data = root.join("Custom Data");
+        fs::create_dir_all(&custom_data).expect("failed to create Custom Data");
+        fs::write(
+            custom_data.join("cycle.json"),
+            r#"{"name":"Navigraph","cycle":"2401"}"#,
+        )
+        .expect("failed to write cycle.json");
+
+        let base = base_navdata_cycle(root);
+        assert_eq!(base.as_deref(), Some("2403"));
+
+        let issues = check_outdated_navdata(root, base.as_deref(), &|_| {});
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].reason_key, "health.navdataOlderThanSim");
+        assert_eq!(
+            issues[0].reason_params.get("cycle").map(String::as_str),
+            Some("2401")
+        );
+        assert!(check_outdated_navdata(root, Some("2313"), &|_| {}).is_empty());
+    }
+
+    #[test]
+    fn health_check_report_counts_issues_per_category() {
+        let temp = tempdir().expect("failed to create tempdir");
+        fs::create_dir_all(temp.path().join("Custom Scenery").join("Empty"))
+            .expect("failed to create scenery");
+        fs::create_dir_all(temp.path().join("Aircraft").join("Gone").join("liveries"))
+            .expect("failed to create liveries");
+
+        let progress_calls = std::sync::atomic::AtomicUsize::new(0);
+        let report = run_health_check(temp.path(), &|_| {
+            progress_calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        })
+        .expect("health check should succeed");
+
+        assert_eq!(report.issues.len(), 2);
+        assert_eq!(
+            report.issues[0].category,
+            HealthCheckCategory::OrphanedLiveries
+        );
+        assert_eq!(report.counts.get("sceneryWithoutContent"), Some(&1));
+        assert!(report.base_navdata_cycle.is_none());
+        assert!(progress_calls.load(std::sync::atomic::Ordering::Relaxed) >= 4);
+    }
 }
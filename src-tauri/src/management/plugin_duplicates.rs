@@ -0,0 +1,349 @@
+//! Plugins installed more than once
+//!
+//! X-Plane refuses to load a second copy of a plugin ("a version of this plugin
+//! is already loaded"), but the log doesn't say which folders collide. Plugin
+//! folders are compared by an identity made of the folder name, ignoring copy
+//! suffixes such as " (2)", and the .xpl files they ship with their sizes. That
+//! catches renamed copies as well as plugins that are installed globally and
+//! also bundled inside an aircraft.
+
+use crate::analysis_suppressions;
+use crate::management_index::find_xpl_and_xfmp_files;
+use crate::models::{PluginDuplicateGroup, PluginDuplicateLocation, PluginInfo};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Depth of `.../plugins` below `Aircraft/`, allowing one level of vendor folders
+const MAX_AIRCRAFT_PLUGINS_DEPTH: usize = 3;
+
+/// Aircraft subfolders that never contain plugins
+const SKIPPED_AIRCRAFT_DIRS: &[&str] = &["liveries", "objects", "cockpit_3d", "sounds"];
+
+/// A loadable plugin folder and the identity it is compared by
+struct PluginCopy {
+    location: PluginDuplicateLocation,
+    base_name: String,
+    /// Lowercased `/`-separated .xpl paths relative to the plugin folder, with sizes
+    xpl_signature: Vec<(String, u64)>,
+}
+
+impl PluginCopy {
+    fn new(
+        folder: &Path,
+        relative_path: String,
+        aircraft_folder: Option<String>,
+        xpl_files: &[String],
+    ) -> Option<Self> {
+        let folder_name = folder.file_name()?.to_string_lossy().to_string();
+        let mut xpl_signature: Vec<(String, u64)> = xpl_files
+            .iter()
+            .map(|file| {
+                let size = fs::metadata(folder.join(file))
+                    .map(|m| m.len())
+                    .unwrap_or(0);
+                (file.replace('\\', "/").to_lowercase(), size)
+            })
+            .collect();
+        xpl_signature.sort();
+
+        Some(Self {
+            base_name: base_plugin_name(&folder_name),
+            location: PluginDuplicateLocation {
+                relative_path,
+                folder_name,
+                aircraft_folder,
+            },
+            xpl_signature,
+        })
+    }
+
+    fn is_global(&self) -> bool {
+        self.location.aircraft_folder.is_none()
+    }
+
+    fn matches(&self, other: &PluginCopy) -> bool {
+        self.base_name == other.base_name
+            || (!self.xpl_signature.is_empty() && self.xpl_signature == other.xpl_signature)
+    }
+}
+
+/// Folder name lowercased, without the suffixes file managers add to copies
+/// ("MyPlugin (2)", "MyPlugin - Copy")
+fn base_plugin_name(folder_name: &str) -> String {
+    let mut name = folder_name.trim().to_lowercase();
+    loop {
+        let stripped = if let Some(rest) = name
+            .strip_suffix(')')
+            .and_then(|rest| rest.rsplit_once(" ("))
+            .filter(|(_, number)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+            .map(|(rest, _)| rest)
+        {
+            rest.to_string()
+        } else if let Some(rest) = name
+            .strip_suffix(" - copy")
+            .or_else(|| name.strip_suffix(" copy"))
+        {
+            rest.to_string()
+        } else {
+            break;
+        };
+        name = stripped.trim_end().to_string();
+    }
+    name
+}
+
+/// Plugin folders (with .xpl files) inside aircraft `plugins` folders
+fn aircraft_plugin_copies(xplane_path: &Path) -> Vec<PluginCopy> {
+    let aircraft_root = xplane_path.join("Aircraft");
+    let mut copies = Vec::new();
+
+    let plugins_dirs = walkdir::WalkDir::new(&aircraft_root)
+        .max_depth(MAX_AIRCRAFT_PLUGINS_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+            entry.file_type().is_dir() && !SKIPPED_AIRCRAFT_DIRS.contains(&name.as_str())
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.depth() >= 2 && entry.file_name().eq_ignore_ascii_case("plugins"));
+
+    for plugins_dir in plugins_dirs {
+        let Some(aircraft_folder) = plugins_dir
+            .path()
+            .parent()
+            .and_then(|aircraft| aircraft.strip_prefix(&aircraft_root).ok())
+            .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        else {
+            continue;
+        };
+        let Ok(read_dir) = fs::read_dir(plugins_dir.path()) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            if !entry.file_type().is_ok_and(|ft| ft.is_dir()) {
+                continue;
+            }
+            let folder = entry.path();
+            let (xpl_files, _) = find_xpl_and_xfmp_files(&folder);
+            if xpl_files.is_empty() {
+                continue;
+            }
+            let Ok(relative) = folder.strip_prefix(xplane_path) else {
+                continue;
+            };
+            let relative_path = relative.to_string_lossy().replace('\\', "/");
+            if let Some(copy) = PluginCopy::new(
+                &folder,
+                relative_path,
+                Some(aircraft_folder.clone()),
+                &xpl_files,
+            ) {
+                copies.push(copy);
+            }
+        }
+    }
+
+    copies
+}
+
+/// Group enabled plugins under `Resources/plugins` that share an identity with
+/// another folder there or with a plugin bundled in an aircraft. Copies that
+/// only collide with each other inside aircraft are not reported, since X-Plane
+/// loads at most one aircraft at a time.
+pub fn find_duplicate_plugins(
+    xplane_path: &Path,
+    plugins: &[PluginInfo],
+) -> Vec<PluginDuplicateGroup> {
+    let plugins_root = xplane_path.join("Resources").join("plugins");
+    let mut copies: Vec<PluginCopy> = plugins
+        .iter()
+        .filter(|plugin| plugin.enabled)
+        .filter_map(|plugin| {
+            PluginCopy::new(
+                &plugins_root.join(&plugin.folder_name),
+                format!("Resources/plugins/{}", plugin.folder_name),
+                None,
+                &plugin.xpl_files,
+            )
+        })
+        .collect();
+    if copies.is_empty() {
+        return Vec::new();
+    }
+    copies.extend(aircraft_plugin_copies(xplane_path));
+
+    // Union-find over copies that share an identity with at least one global copy
+    let mut parent: Vec<usize> = (0..copies.len()).collect();
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[i] = root;
+        root
+    }
+    for (a, copy) in copies.iter().enumerate().filter(|(_, c)| c.is_global()) {
+        for (b, other) in copies.iter().enumerate() {
+            if a != b && copy.matches(other) {
+                let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+                parent[root_b] = root_a;
+            }
+        }
+    }
+
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..copies.len() {
+        let root = find(&mut parent, i);
+        members.entry(root).or_default().push(i);
+    }
+
+    let mut groups: Vec<PluginDuplicateGroup> = members
+        .into_values()
+        .filter(|indices| indices.len() > 1 && indices.iter().any(|&i| copies[i].is_global()))
+        .map(|mut indices| {
+            indices.sort_by(|&a, &b| {
+                (!copies[a].is_global(), &copies[a].location.relative_path)
+                    .cmp(&(!copies[b].is_global(), &copies[b].location.relative_path))
+            });
+            let first = &copies[indices[0]];
+            let locations: Vec<PluginDuplicateLocation> = indices
+                .iter()
+                .map(|&i| copies[i].location.clone())
+                .collect();
+            PluginDuplicateGroup {
+                group_id: first.base_name.clone(),
+                identical: indices
+                    .iter()
+                    .all(|&i| copies[i].xpl_signature == first.xpl_signature),
+                suppression_key: analysis_suppressions::duplicate_plugin_key(&locations),
+                locations,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+    groups
+}
+
+/// Folders of the duplicate groups that the given log lines refer to. When no
+/// line names any of them, every duplicated folder is returned, since the log
+/// only reports that some plugin was loaded twice.
+pub fn folders_for_log_lines(groups: &[PluginDuplicateGroup], lines: &[&str]) -> Vec<String> {
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|line| line.replace('\\', "/").to_lowercase())
+        .collect();
+    let mentioned = |location: &PluginDuplicateLocation| {
+        let relative = location.relative_path.to_lowercase();
+        let folder = format!("/{}/", location.folder_name.to_lowercase());
+        lines
+            .iter()
+            .any(|line| line.contains(&relative) || line.contains(&folder))
+    };
+
+    let mut matched: Vec<&PluginDuplicateGroup> = groups
+        .iter()
+        .filter(|group| group.locations.iter().any(mentioned))
+        .collect();
+    if matched.is_empty() {
+        matched = groups.iter().collect();
+    }
+
+    let mut folders = Vec::new();
+    for location in matched.into_iter().flat_map(|group| &group.locations) {
+        if !folders.contains(&location.relative_path) {
+            folders.push(location.relative_path.clone());
+        }
+    }
+    folders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::management_index::scan_plugins;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn groups_renamed_and_aircraft_bundled_copies() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        let plugins = root.join("Resources/plugins");
+        write(&plugins.join("MyPlugin/64/win.xpl"), "my plugin");
+        write(&plugins.join("MyPlugin (2)/64/win.xpl"), "my plugin");
+        write(&plugins.join("Renamed Helper/64/lin.xpl"), "helper v1");
+        write(&plugins.join("Unique/64/win.xpl"), "unique");
+        write(&plugins.join("Disabled/64/win.xfmp"), "unique");
+        write(
+            &root.join("Aircraft/Vendor/Jet/plugins/helper/64/lin.xpl"),
+            "helper v1",
+        );
+        write(
+            &root.join("Aircraft/Vendor/Jet/plugins/Unrelated/64/lin.xpl"),
+            "x",
+        );
+        write(
+            &root.join("Aircraft/Other/plugins/Unrelated/64/lin.xpl"),
+            "x",
+        );
+
+        let data = scan_plugins(root, false).unwrap();
+        assert_eq!(data.duplicates.len(), 2);
+
+        let mine = &data.duplicates[0];
+        assert_eq!(mine.group_id, "myplugin");
+        assert_eq!(mine.locations.len(), 2);
+        assert!(mine.locations.iter().all(|l| l.aircraft_folder.is_none()));
+
+        // Different names, but the same .xpl files and sizes
+        let helper = &data.duplicates[1];
+        assert_eq!(helper.group_id, "renamed helper");
+        assert!(helper.identical);
+        let helper_paths: Vec<&str> = helper
+            .locations
+            .iter()
+            .map(|l| l.relative_path.as_str())
+            .collect();
+        assert_eq!(
+            helper_paths,
+            vec![
+                "Resources/plugins/Renamed Helper",
+                "Aircraft/Vendor/Jet/plugins/helper",
+            ]
+        );
+        assert_eq!(
+            helper.locations[1].aircraft_folder.as_deref(),
+            Some("Vendor/Jet")
+        );
+
+        assert_eq!(
+            folders_for_log_lines(
+                &data.duplicates,
+                &["Loaded: Resources\\plugins\\MyPlugin (2)\\64\\win.xpl: a version of this plugin is already loaded"]
+            ),
+            vec![
+                "Resources/plugins/MyPlugin".to_string(),
+                "Resources/plugins/MyPlugin (2)".to_string(),
+            ]
+        );
+        assert_eq!(
+            folders_for_log_lines(
+                &data.duplicates,
+                &["a version of this plugin is already loaded"]
+            )
+            .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn copy_suffixes_are_ignored() {
+        assert_eq!(base_plugin_name("MyPlugin (2)"), "myplugin");
+        assert_eq!(base_plugin_name("MyPlugin - Copy (3)"), "myplugin");
+        assert_eq!(base_plugin_name("Plugin (beta)"), "plugin (beta)");
+    }
+}
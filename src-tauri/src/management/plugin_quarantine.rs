@@ -0,0 +1,316 @@
+//! Plugin quarantine for crash troubleshooting
+//!
+//! Moves every third-party plugin folder out of `Resources/plugins` into a
+//! sibling `Resources/plugins (disabled by XFast)` folder so X-Plane starts
+//! without them. What was moved is recorded in a JSON state file inside the
+//! quarantine folder, which lets plugins be restored all at once or one by one.
+
+use crate::logger;
+use crate::models::PluginQuarantineResult;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+const QUARANTINE_FOLDER_NAME: &str = "plugins (disabled by XFast)";
+const QUARANTINE_STATE_FILE_NAME: &str = ".xfastmanager-quarantine.json";
+
+/// Folders shipped with X-Plane itself that must stay in place
+const BUILTIN_PLUGIN_FOLDERS: &[&str] = &["PluginAdmin", "XPLM.framework", "XPWidgets.framework"];
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuarantineState {
+    #[serde(default)]
+    entries: Vec<QuarantineEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuarantineEntry {
+    folder_name: String,
+    /// Unix timestamp (seconds) of when the folder was moved
+    quarantined_at: i64,
+}
+
+fn plugins_path(xplane_path: &Path) -> PathBuf {
+    xplane_path.join("Resources").join("plugins")
+}
+
+fn quarantine_path(xplane_path: &Path) -> PathBuf {
+    xplane_path.join("Resources").join(QUARANTINE_FOLDER_NAME)
+}
+
+fn read_state(quarantine_dir: &Path) -> Result<QuarantineState> {
+    let state_path = quarantine_dir.join(QUARANTINE_STATE_FILE_NAME);
+    match fs::read_to_string(&state_path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Failed to parse quarantine state: {}", e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(QuarantineState::default()),
+        Err(e) => Err(anyhow!("Failed to read quarantine state: {}", e)),
+    }
+}
+
+fn write_state(quarantine_dir: &Path, state: &QuarantineState) -> Result<()> {
+    let state_path = quarantine_dir.join(QUARANTINE_STATE_FILE_NAME);
+    if state.entries.is_empty() {
+        match fs::remove_file(&state_path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        // Only succeeds when nothing else was left behind
+        let _ = fs::remove_dir(quarantine_dir);
+        return Ok(());
+    }
+
+    let content = serde_json::to_string_pretty(state)?;
+    fs::write(&state_path, content)?;
+    Ok(())
+}
+
+fn is_builtin_plugin(folder_name: &str) -> bool {
+    BUILTIN_PLUGIN_FOLDERS
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(folder_name))
+}
+
+/// Whether the folder holds a plugin binary (enabled .xpl or XFast-disabled .xfmp)
+fn contains_plugin_binary(folder_path: &Path) -> bool {
+    WalkDir::new(folder_path)
+        .max_depth(10)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .any(|entry| {
+            entry.file_type().is_file()
+                && entry
+                    .path()
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| ext.eq_ignore_ascii_case("xpl") || ext.eq_ignore_ascii_case("xfmp"))
+                    .unwrap_or(false)
+        })
+}
+
+fn remaining_folder_names(state: &QuarantineState) -> Vec<String> {
+    let mut names: Vec<String> = state
+        .entries
+        .iter()
+        .map(|e| e.folder_name.clone())
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// Move every third-party plugin folder into the quarantine folder
+pub fn quarantine_all_plugins(xplane_path: &Path) -> Result<PluginQuarantineResult> {
+    let plugins_dir = plugins_path(xplane_path);
+    if !plugins_dir.exists() {
+        return Err(anyhow!("Plugins folder not found"));
+    }
+
+    let quarantine_dir = quarantine_path(xplane_path);
+    fs::create_dir_all(&quarantine_dir)?;
+    let mut state = read_state(&quarantine_dir)?;
+
+    let quarantined_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+
+    let mut result = PluginQuarantineResult::default();
+
+    let mut folders: Vec<(PathBuf, String)> = fs::read_dir(&plugins_dir)?
+        .flatten()
+        .filter(|entry| entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false))
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            (!name.starts_with('.')).then(|| (entry.path(), name))
+        })
+        .collect();
+    folders.sort_by_key(|(_, name)| name.to_lowercase());
+
+    for (folder_path, folder_name) in folders {
+        if is_builtin_plugin(&folder_name) || !contains_plugin_binary(&folder_path) {
+            result.skipped.push(folder_name);
+            continue;
+        }
+
+        let destination = quarantine_dir.join(&folder_name);
+        if destination.exists() {
+            result.errors.push(format!(
+                "{}: a folder with the same name is already quarantined",
+                folder_name
+            ));
+            continue;
+        }
+
+        match fs::rename(&folder_path, &destination) {
+            Ok(()) => {
+                state.entries.retain(|e| e.folder_name != folder_name);
+                state.entries.push(QuarantineEntry {
+                    folder_name: folder_name.clone(),
+                    quarantined_at,
+                });
+                result.moved.push(folder_name);
+            }
+            Err(e) => result.errors.push(format!("{}: {}", folder_name, e)),
+        }
+    }
+
+    write_state(&quarantine_dir, &state)?;
+    result.remaining_quarantined = remaining_folder_names(&state);
+
+    logger::log_info(
+        &format!(
+            "Quarantined {} plugin(s), skipped {}, {} error(s)",
+            result.moved.len(),
+            result.skipped.len(),
+            result.errors.len()
+        ),
+        Some("management"),
+    );
+
+    Ok(result)
+}
+
+/// Move quarantined plugins back into `Resources/plugins`.
+/// Restores everything when `folder_names` is `None`. Folders the user already
+/// moved back by hand are dropped from the state instead of failing the batch.
+pub fn restore_quarantined_plugins(
+    xplane_path: &Path,
+    folder_names: Option<&[String]>,
+) -> Result<PluginQuarantineResult> {
+    let plugins_dir = plugins_path(xplane_path);
+    let quarantine_dir = quarantine_path(xplane_path);
+    let mut state = read_state(&quarantine_dir)?;
+    let mut result = PluginQuarantineResult::default();
+
+    let selected: Vec<String> = match folder_names {
+        Some(names) => {
+            for name in names {
+                if !state.entries.iter().any(|e| e.folder_name == *name) {
+                    result.errors.push(format!("{}: not quarantined", name));
+                }
+            }
+            names.to_vec()
+        }
+        None => remaining_folder_names(&state),
+    };
+
+    if !selected.is_empty() {
+        fs::create_dir_all(&plugins_dir)?;
+    }
+
+    for folder_name in selected {
+        if !state.entries.iter().any(|e| e.folder_name == folder_name) {
+            continue;
+        }
+
+        let source = quarantine_dir.join(&folder_name);
+        let destination = plugins_dir.join(&folder_name);
+
+        match (source.exists(), destination.exists()) {
+            (true, false) => match fs::rename(&source, &destination) {
+                Ok(()) => {
+                    state.entries.retain(|e| e.folder_name != folder_name);
+                    result.moved.push(folder_name);
+                }
+                Err(e) => result.errors.push(format!("{}: {}", folder_name, e)),
+            },
+            (false, _) => {
+                // Moved back (or deleted) outside XFast: nothing left to restore
+                logger::log_info(
+                    &format!(
+                        "Quarantined plugin '{}' is no longer in the quarantine folder, dropping it",
+                        folder_name
+                    ),
+                    Some("management"),
+                );
+                state.entries.retain(|e| e.folder_name != folder_name);
+                result.skipped.push(folder_name);
+            }
+            (true, true) => {
+                result.errors.push(format!(
+                    "{}: a folder with the same name already exists in Resources/plugins",
+                    folder_name
+                ));
+            }
+        }
+    }
+
+    if quarantine_dir.exists() {
+        write_state(&quarantine_dir, &state)?;
+    }
+    result.remaining_quarantined = remaining_folder_names(&state);
+
+    logger::log_info(
+        &format!(
+            "Restored {} quarantined plugin(s), {} still quarantined, {} error(s)",
+            result.moved.len(),
+            result.remaining_quarantined.len(),
+            result.errors.len()
+        ),
+        Some("management"),
+    );
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn make_plugin(xplane_path: &Path, name: &str) {
+        let dir = plugins_path(xplane_path).join(name).join("64");
+        fs::create_dir_all(&dir).expect("failed to create plugin dir");
+        fs::write(dir.join("win.xpl"), "xpl").expect("failed to write xpl");
+    }
+
+    #[test]
+    fn quarantine_skips_builtin_and_restores_selected_subset() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        make_plugin(root, "FlyWithLua");
+        make_plugin(root, "XPUIPC");
+        make_plugin(root, "PluginAdmin");
+
+        let result = quarantine_all_plugins(root).expect("quarantine should succeed");
+        assert_eq!(result.moved, vec!["FlyWithLua", "XPUIPC"]);
+        assert_eq!(result.skipped, vec!["PluginAdmin"]);
+        assert!(!plugins_path(root).join("FlyWithLua").exists());
+        assert!(quarantine_path(root).join("XPUIPC").exists());
+
+        let restored = restore_quarantined_plugins(root, Some(&["XPUIPC".to_string()]))
+            .expect("restore should succeed");
+        assert_eq!(restored.moved, vec!["XPUIPC"]);
+        assert_eq!(restored.remaining_quarantined, vec!["FlyWithLua"]);
+        assert!(plugins_path(root).join("XPUIPC").exists());
+    }
+
+    #[test]
+    fn restore_tolerates_folders_moved_back_manually() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        make_plugin(root, "FlyWithLua");
+        make_plugin(root, "XPUIPC");
+        quarantine_all_plugins(root).expect("quarantine should succeed");
+
+        fs::rename(
+            quarantine_path(root).join("FlyWithLua"),
+            plugins_path(root).join("FlyWithLua"),
+        )
+        .expect("failed to move plugin back");
+
+        let restored = restore_quarantined_plugins(root, None).expect("restore should succeed");
+        assert_eq!(restored.moved, vec!["XPUIPC"]);
+        assert_eq!(restored.skipped, vec!["FlyWithLua"]);
+        assert!(restored.errors.is_empty());
+        assert!(restored.remaining_quarantined.is_empty());
+        assert!(!quarantine_path(root).exists());
+    }
+}
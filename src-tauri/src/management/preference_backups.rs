@@ -0,0 +1,388 @@
+//! Backups of X-Plane's `Output/preferences` folder
+//!
+//! Each backup is a zip in [`app_dirs::get_preference_backups_dir`] named after
+//! its id. Files are stored below `preferences/`, next to a `backup.json`
+//! entry describing the backup. Files are streamed in and out of the archive,
+//! since some plugins keep large data files in the preferences folder.
+//!
+//! Restoring extracts next to the current folder first, then moves the
+//! current folder aside and the extracted one in its place, so a failed
+//! restore leaves the preferences untouched.
+
+use crate::app_dirs;
+use crate::logger;
+use crate::models::{PreferenceBackup, PreferenceRestoreResult};
+use anyhow::{anyhow, Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const METADATA_ENTRY: &str = "backup.json";
+const FILES_PREFIX: &str = "preferences/";
+const ID_PREFIX: &str = "xplane-preferences-";
+
+/// Contents of `backup.json`
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupMetadata {
+    label: Option<String>,
+    xplane_path: String,
+    created_at: i64,
+    file_count: usize,
+    total_bytes: u64,
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn preferences_dir(xplane_path: &Path) -> PathBuf {
+    xplane_path.join("Output").join("preferences")
+}
+
+/// Zip of a backup id, refusing ids that are not plain backup file names
+fn backup_path(backups_dir: &Path, backup_id: &str) -> Result<PathBuf> {
+    let valid = backup_id.starts_with(ID_PREFIX)
+        && backup_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err(anyhow!("Invalid backup id: {}", backup_id));
+    }
+    Ok(backups_dir.join(format!("{}.zip", backup_id)))
+}
+
+/// Back up `Output/preferences` of an X-Plane installation
+pub fn backup_preferences(xplane_path: &Path, label: Option<&str>) -> Result<PreferenceBackup> {
+    backup_preferences_to(&app_dirs::get_preference_backups_dir(), xplane_path, label)
+}
+
+fn backup_preferences_to(
+    backups_dir: &Path,
+    xplane_path: &Path,
+    label: Option<&str>,
+) -> Result<PreferenceBackup> {
+    let source = preferences_dir(xplane_path);
+    if !source.is_dir() {
+        return Err(anyhow!(
+            "Preferences folder not found: {}",
+            source.display()
+        ));
+    }
+    fs::create_dir_all(backups_dir)?;
+
+    let id = format!("{}{}", ID_PREFIX, Local::now().format("%Y%m%d-%H%M%S-%3f"));
+    let output_path = backup_path(backups_dir, &id)?;
+    let partial_path = output_path.with_extension("zip.part");
+    let label = label
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(str::to_string);
+
+    let written = write_backup(&partial_path, &source, |file_count, total_bytes| {
+        BackupMetadata {
+            label: label.clone(),
+            xplane_path: xplane_path.to_string_lossy().into_owned(),
+            created_at: now_secs(),
+            file_count,
+            total_bytes,
+        }
+    });
+    let metadata = match written.and_then(|metadata| {
+        fs::rename(&partial_path, &output_path)?;
+        Ok(metadata)
+    }) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            let _ = fs::remove_file(&partial_path);
+            return Err(e);
+        }
+    };
+
+    logger::log_info(
+        &format!(
+            "Backed up {} preference files to {}",
+            metadata.file_count,
+            output_path.display()
+        ),
+        Some("preference_backups"),
+    );
+    to_backup(&id, &output_path, metadata)
+}
+
+/// Stream every file below `source` into a new zip, then the metadata built
+/// from the file count and total size
+fn write_backup(
+    output_path: &Path,
+    source: &Path,
+    metadata: impl FnOnce(usize, u64) -> BackupMetadata,
+) -> Result<BackupMetadata> {
+    let file = File::create(output_path)
+        .with_context(|| format!("Failed to create {}", output_path.display()))?;
+    let mut writer = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut file_count = 0usize;
+    let mut total_bytes = 0u64;
+
+    for entry in walkdir::WalkDir::new(source)
+        .follow_links(false)
+        .min_depth(1)
+        .sort_by_file_name()
+    {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let name = format!("{}{}", FILES_PREFIX, relative);
+        if entry.file_type().is_dir() {
+            writer.add_directory(name, options)?;
+        } else if entry.file_type().is_file() {
+            let size = entry.metadata()?.len();
+            let mut input = File::open(entry.path())
+                .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+            writer.start_file(name, options.large_file(size >= u32::MAX as u64))?;
+            io::copy(&mut input, &mut writer)?;
+            file_count += 1;
+            total_bytes = total_bytes.saturating_add(size);
+        }
+    }
+
+    let metadata = metadata(file_count, total_bytes);
+    writer.start_file(METADATA_ENTRY, options)?;
+    writer.write_all(serde_json::to_string_pretty(&metadata)?.as_bytes())?;
+    writer.finish()?;
+    Ok(metadata)
+}
+
+fn read_metadata(path: &Path) -> Result<BackupMetadata> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let entry = archive.by_name(METADATA_ENTRY)?;
+    Ok(serde_json::from_reader(entry)?)
+}
+
+fn to_backup(id: &str, path: &Path, metadata: BackupMetadata) -> Result<PreferenceBackup> {
+    Ok(PreferenceBackup {
+        id: id.to_string(),
+        label: metadata.label,
+        xplane_path: metadata.xplane_path,
+        created_at: metadata.created_at,
+        size_bytes: fs::metadata(path)?.len(),
+        file_count: metadata.file_count,
+        total_bytes: metadata.total_bytes,
+    })
+}
+
+/// All preference backups, newest first
+pub fn list_backups() -> Result<Vec<PreferenceBackup>> {
+    list_backups_in(&app_dirs::get_preference_backups_dir())
+}
+
+fn list_backups_in(backups_dir: &Path) -> Result<Vec<PreferenceBackup>> {
+    let entries = match fs::read_dir(backups_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(id) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".zip"))
+            .filter(|id| id.starts_with(ID_PREFIX))
+        else {
+            continue;
+        };
+        match read_metadata(&path).and_then(|metadata| to_backup(id, &path, metadata)) {
+            Ok(backup) => backups.push(backup),
+            Err(e) => logger::log_error(
+                &format!("Skipping unreadable preference backup {}: {}", id, e),
+                Some("preference_backups"),
+            ),
+        }
+    }
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    Ok(backups)
+}
+
+/// X-Plane installation a backup was taken from
+pub fn backup_xplane_path(backup_id: &str) -> Result<String> {
+    let path = backup_path(&app_dirs::get_preference_backups_dir(), backup_id)?;
+    if !path.is_file() {
+        return Err(anyhow!("Backup not found: {}", backup_id));
+    }
+    let metadata =
+        read_metadata(&path).with_context(|| format!("Failed to read backup {}", backup_id))?;
+    Ok(metadata.xplane_path)
+}
+
+/// Replace `Output/preferences` of the backed up X-Plane installation with
+/// the backup. The current folder is kept next to it.
+pub fn restore_backup(backup_id: &str) -> Result<PreferenceRestoreResult> {
+    restore_backup_from(&app_dirs::get_preference_backups_dir(), backup_id)
+}
+
+fn restore_backup_from(backups_dir: &Path, backup_id: &str) -> Result<PreferenceRestoreResult> {
+    let path = backup_path(backups_dir, backup_id)?;
+    if !path.is_file() {
+        return Err(anyhow!("Backup not found: {}", backup_id));
+    }
+    let metadata =
+        read_metadata(&path).with_context(|| format!("Failed to read backup {}", backup_id))?;
+    let output_dir = Path::new(&metadata.xplane_path).join("Output");
+    if !output_dir.is_dir() {
+        return Err(anyhow!(
+            "X-Plane folder of the backup no longer exists: {}",
+            metadata.xplane_path
+        ));
+    }
+
+    let target = preferences_dir(Path::new(&metadata.xplane_path));
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let staging = output_dir.join(format!(".preferences.xfast-restore-{}", stamp));
+    if let Err(e) = extract_backup(&path, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    let previous = if target.exists() {
+        let aside = output_dir.join(format!("preferences.before-restore-{}", stamp));
+        if let Err(e) = fs::rename(&target, &aside) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(anyhow!(
+                "Failed to move the current preferences aside: {}",
+                e
+            ));
+        }
+        Some(aside)
+    } else {
+        None
+    };
+    if let Err(e) = fs::rename(&staging, &target) {
+        if let Some(previous) = &previous {
+            let _ = fs::rename(previous, &target);
+        }
+        let _ = fs::remove_dir_all(&staging);
+        return Err(anyhow!(
+            "Failed to put the restored preferences in place: {}",
+            e
+        ));
+    }
+
+    logger::log_info(
+        &format!(
+            "Restored preferences backup {} to {}",
+            backup_id,
+            target.display()
+        ),
+        Some("preference_backups"),
+    );
+    Ok(PreferenceRestoreResult {
+        backup: to_backup(backup_id, &path, metadata)?,
+        restored_path: target.to_string_lossy().into_owned(),
+        previous_path: previous.map(|path| path.to_string_lossy().into_owned()),
+    })
+}
+
+/// Stream the files of a backup into `destination`
+fn extract_backup(path: &Path, destination: &Path) -> Result<()> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    fs::create_dir_all(destination)?;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let Some(relative) = entry
+            .enclosed_name()
+            .and_then(|name| name.strip_prefix(FILES_PREFIX).ok().map(Path::to_path_buf))
+        else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let output = destination.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&output)?;
+            continue;
+        }
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = File::create(&output)
+            .with_context(|| format!("Failed to create {}", output.display()))?;
+        io::copy(&mut entry, &mut file)?;
+    }
+    Ok(())
+}
+
+/// Delete a backup. Returns whether it existed.
+pub fn delete_backup(backup_id: &str) -> Result<bool> {
+    let path = backup_path(&app_dirs::get_preference_backups_dir(), backup_id)?;
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_restore_round_trip_moves_current_preferences_aside() {
+        let temp = tempfile::tempdir().unwrap();
+        let xplane = temp.path().join("X-Plane 12");
+        let backups = temp.path().join("backups");
+        let prefs = preferences_dir(&xplane);
+        fs::create_dir_all(prefs.join("plugin data")).unwrap();
+        fs::write(prefs.join("X-Plane.prf"), "original").unwrap();
+        fs::write(prefs.join("plugin data/cache.bin"), vec![7u8; 4096]).unwrap();
+
+        let backup = backup_preferences_to(&backups, &xplane, Some(" before crash test ")).unwrap();
+        assert_eq!(backup.label.as_deref(), Some("before crash test"));
+        assert_eq!(backup.file_count, 2);
+        assert_eq!(backup.total_bytes, 4096 + 8);
+
+        let listed = list_backups_in(&backups).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, backup.id);
+        assert!(listed[0].size_bytes > 0);
+
+        fs::write(prefs.join("X-Plane.prf"), "broken").unwrap();
+        let restored = restore_backup_from(&backups, &backup.id).unwrap();
+        assert_eq!(
+            fs::read_to_string(prefs.join("X-Plane.prf")).unwrap(),
+            "original"
+        );
+        assert_eq!(
+            fs::read(prefs.join("plugin data/cache.bin")).unwrap().len(),
+            4096
+        );
+        let previous = PathBuf::from(restored.previous_path.unwrap());
+        assert_eq!(
+            fs::read_to_string(previous.join("X-Plane.prf")).unwrap(),
+            "broken"
+        );
+    }
+
+    #[test]
+    fn backup_ids_cannot_leave_the_backups_folder() {
+        let backups = Path::new("/backups");
+        assert!(backup_path(backups, "xplane-preferences-20261016-101500-000").is_ok());
+        assert!(backup_path(backups, "../xplane-preferences-1").is_err());
+        assert!(backup_path(backups, "xplane-preferences-1/../../x").is_err());
+        assert!(backup_path(backups, "settings").is_err());
+    }
+}
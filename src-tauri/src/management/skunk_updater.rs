@@ -17,7 +17,11 @@ use std::time::{Duration, Instant, SystemTime};
 use tokio::sync::Semaphore;
 use walkdir::WalkDir;
 
+use crate::addon_backups::{self, FileChange};
+use crate::download_retry::{self, RetryPolicy, RetryStats};
+use crate::http_client;
 use crate::task_control::TaskControl;
+use crate::update_preflight::{self, UpdatePreflight};
 
 const LOCAL_CFG_FILE: &str = "skunkcrafts_updater.cfg";
 const LOCAL_BETA_CFG_FILE: &str = "skunkcrafts_updater_beta.cfg";
@@ -28,9 +32,12 @@ const REMOTE_IGNORELIST_FILE: &str = "skunkcrafts_updater_ignorelist.txt";
 const REMOTE_ONCELIST_FILE: &str = "skunkcrafts_updater_oncelist.txt";
 const REMOTE_SIZESLIST_FILE: &str = "skunkcrafts_updater_sizeslist.txt";
 const REMOTE_BLACKLIST_FILE: &str = "skunkcrafts_updater_blacklist.txt";
+const LOCAL_BLACKLIST_FILE: &str = "skunkcrafts_updater_blacklist.txt";
+const LOCAL_WHITELIST_FILE: &str = "skunkcrafts_updater_whitelist.txt";
 const LOCAL_CRC_CACHE_TTL: Duration = Duration::from_secs(300);
 const LOCAL_CRC_CACHE_MAX_SIZE: usize = 20_000;
 const CHUNKED_DOWNLOAD_MIN_SIZE: u64 = 512 * 1024;
+const RETRY_LOG_CTX: &str = "skunk_updater";
 
 #[derive(Debug, Clone)]
 struct LocalCrcCacheEntry {
@@ -65,6 +72,10 @@ pub struct SkunkUpdateOptions {
     pub threads_per_task: Option<usize>,
     #[serde(default)]
     pub total_threads: Option<usize>,
+    /// Keep the files an update changes as a backup, up to this many per
+    /// addon; `None` or 0 keeps none
+    #[serde(default)]
+    pub keep_backup_versions: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,9 +101,62 @@ pub struct SkunkUpdatePlan {
     pub replace_files: Vec<String>,
     pub delete_files: Vec<String>,
     pub skip_files: Vec<String>,
+    /// Every entry of `skip_files` (and every suppressed delete) with the reason it was skipped.
+    #[serde(default)]
+    pub skipped: Vec<SkippedPath>,
     pub warnings: Vec<String>,
     #[serde(default)]
     pub has_beta_config: bool,
+    /// Remote size and hashes of planned files, where the manifest lists them.
+    /// Only used to diff the plan against the local folder.
+    #[serde(skip)]
+    pub file_details: Vec<PlannedFileInfo>,
+    /// Disk space and path length checks, for plans that write files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preflight: Option<UpdatePreflight>,
+}
+
+/// What the remote manifest says about a file in an update plan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlannedFileInfo {
+    pub path: String,
+    pub size: Option<u64>,
+    /// MD5 of the remote file
+    pub md5: Option<String>,
+    /// MD5 of the version being replaced or deleted
+    pub previous_md5: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SkipReason {
+    /// Livery content while liveries are excluded from the update.
+    Livery,
+    /// Listed in the remote ignorelist.
+    Ignored,
+    /// Copy-once file that already exists locally.
+    CopyOnce,
+    /// Local CRC already matches the remote manifest.
+    Unchanged,
+    /// Remote entry is a directory locally.
+    LocalDirectory,
+    /// Matched a pattern in the addon's local blacklist.
+    Blacklisted,
+    /// Not matched by any pattern in the addon's local whitelist.
+    NotWhitelisted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedPath {
+    pub path: String,
+    pub reason: SkipReason,
+    /// Blacklist pattern responsible for the skip, when applicable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// True when the skipped action was a delete rather than a download.
+    #[serde(default)]
+    pub delete: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +174,12 @@ pub struct SkunkUpdateResult {
     pub deleted_files: usize,
     pub skipped_files: usize,
     pub rollback_used: bool,
+    /// Files that needed at least one retried request
+    #[serde(default)]
+    pub files_retried: usize,
+    /// Retried requests across all files
+    #[serde(default)]
+    pub total_retry_count: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -159,10 +229,18 @@ struct LocalConfig {
     module: String,
     version: Option<String>,
     liveries: bool,
+    /// `beta|true`: the addon follows its beta module
+    beta: bool,
     beta_zone: Option<String>,
     beta_module: Option<String>,
 }
 
+impl LocalConfig {
+    fn has_beta_config(&self) -> bool {
+        self.beta_zone.is_some() || self.beta_module.is_some()
+    }
+}
+
 #[derive(Debug, Clone)]
 struct RemoteConfig {
     version: String,
@@ -320,7 +398,13 @@ pub async fn build_update_plan(
     options.parallel_downloads = None;
     let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
     let prepared = prepare_update_context(&target_path, options.use_beta).await?;
-    build_plan_internal(&prepared, item_type, folder_name, &options)
+    let mut plan = build_plan_internal(&prepared, item_type, folder_name, &options)?;
+    update_preflight::apply(
+        &mut plan,
+        &prepared.target_path,
+        options.rollback_on_failure,
+    );
+    Ok(plan)
 }
 
 pub async fn execute_update(
@@ -332,8 +416,13 @@ pub async fn execute_update(
     progress_callback: Option<SkunkUpdateProgressCallback>,
 ) -> Result<SkunkUpdateResult> {
     let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
-    let prepared = prepare_update_context(&target_path, options.use_beta).await?;
-    let plan = build_plan_internal(&prepared, item_type, folder_name, &options)?;
+    let mut prepared = prepare_update_context(&target_path, options.use_beta).await?;
+    let mut plan = build_plan_internal(&prepared, item_type, folder_name, &options)?;
+    update_preflight::apply(
+        &mut plan,
+        &prepared.target_path,
+        options.rollback_on_failure,
+    );
     let install_started = Instant::now();
 
     emit_progress_event(
@@ -390,9 +479,18 @@ pub async fn execute_update(
             deleted_files: 0,
             skipped_files: plan.skip_files.len(),
             rollback_used: false,
+            files_retried: 0,
+            total_retry_count: 0,
         });
     }
 
+    if let Some(preflight) = &plan.preflight {
+        preflight.ensure_passes()?;
+        if !preflight.long_paths.is_empty() {
+            prepared.target_path = update_preflight::long_path(&prepared.target_path);
+        }
+    }
+
     let mut whitelist_crc: HashMap<String, i64> = HashMap::new();
     for item in &prepared.manifest.whitelist {
         whitelist_crc.insert(item.path.clone(), item.crc32);
@@ -499,6 +597,7 @@ pub async fn execute_update(
     let chunked_enabled = options.chunked_download_enabled.unwrap_or(true);
     let threads_per_task = options.threads_per_task.unwrap_or(6).clamp(1, 32);
     let total_threads = options.total_threads.unwrap_or(32).clamp(1, 64);
+    let retry_stats = RetryStats::default();
     let downloaded = match download_files(
         &prepared.module_url,
         &download_targets,
@@ -511,6 +610,7 @@ pub async fn execute_update(
         chunked_enabled,
         threads_per_task,
         total_threads,
+        &retry_stats,
     )
     .await
     {
@@ -540,6 +640,37 @@ pub async fn execute_update(
         }
     };
 
+    let keep_backups = options.keep_backup_versions.filter(|keep| *keep > 0);
+    let backup = match keep_backups {
+        Some(_) => {
+            let mut changes: Vec<(String, FileChange)> = plan
+                .replace_files
+                .iter()
+                .chain(plan.add_files.iter())
+                .map(|rel_path| (rel_path.clone(), FileChange::Replaced))
+                .chain(
+                    plan.delete_files
+                        .iter()
+                        .map(|rel_path| (rel_path.clone(), FileChange::Deleted)),
+                )
+                .collect();
+            if plan.remote_version.is_some() {
+                changes.push((LOCAL_CFG_FILE.to_string(), FileChange::Replaced));
+            }
+            Some(
+                addon_backups::begin(
+                    item_type,
+                    folder_name,
+                    &prepared.target_path,
+                    &changes,
+                    prepared.local.version.clone(),
+                )
+                .map_err(|e| anyhow!("Failed to back up files before the update: {}", e))?,
+            )
+        }
+        None => None,
+    };
+
     let mut rollback = RollbackState::new(options.rollback_on_failure)?;
     let total_apply_units =
         (plan.replace_files.len() + plan.add_files.len() + plan.delete_files.len()) as u64;
@@ -677,8 +808,27 @@ pub async fn execute_update(
     })();
 
     if let Err(e) = apply_result {
+        let rollback_result = if options.rollback_on_failure {
+            rollback.rollback()
+        } else {
+            Ok(())
+        };
+        // Without a successful rollback the backup holds the only copy of the
+        // files the update replaced
+        if !options.rollback_on_failure || rollback_result.is_err() {
+            if let (Some(backup), Some(keep)) = (backup, keep_backups) {
+                if let Err(backup_err) = backup.finish(None, keep) {
+                    crate::logger::log_error(
+                        &format!(
+                            "Failed to keep the backup of {}: {}",
+                            folder_name, backup_err
+                        ),
+                        Some("addon_backups"),
+                    );
+                }
+            }
+        }
         if options.rollback_on_failure {
-            let rollback_result = rollback.rollback();
             if let Err(rollback_err) = rollback_result {
                 return Err(anyhow!(
                     "Update failed: {}. Rollback also failed: {}",
@@ -726,6 +876,15 @@ pub async fn execute_update(
         },
     );
 
+    if let (Some(backup), Some(keep)) = (backup, keep_backups) {
+        if let Err(e) = backup.finish(plan.remote_version.clone(), keep) {
+            crate::logger::log_error(
+                &format!("Failed to keep the backup of {}: {}", folder_name, e),
+                Some("addon_backups"),
+            );
+        }
+    }
+
     Ok(SkunkUpdateResult {
         provider: "manifest".to_string(),
         success: true,
@@ -738,6 +897,8 @@ pub async fn execute_update(
         deleted_files: plan.delete_files.len(),
         skipped_files: plan.skip_files.len(),
         rollback_used: false,
+        files_retried: retry_stats.files_retried(),
+        total_retry_count: retry_stats.total_retry_count(),
     })
 }
 
@@ -751,6 +912,7 @@ fn build_plan_internal(
     let mut replace_files: Vec<String> = Vec::new();
     let mut delete_files: Vec<String> = Vec::new();
     let mut skip_files: Vec<String> = Vec::new();
+    let mut skipped: Vec<SkippedPath> = Vec::new();
     let mut crc_candidates: Vec<(String, PathBuf, i64)> = Vec::new();
     let mut warnings = prepared.manifest.warnings.clone();
 
@@ -761,17 +923,29 @@ fn build_plan_internal(
 
     let include_liveries = options.include_liveries && prepared.local.liveries;
     let force_all = options.fresh_install;
+    let filters = LocalPathFilters::load(&prepared.target_path, &mut warnings);
 
     for entry in &prepared.manifest.whitelist {
         let rel_path = &entry.path;
 
-        if !include_liveries && is_livery_path(rel_path) {
+        if let Some((reason, pattern)) = filters.exclusion(rel_path) {
             skip_files.push(rel_path.clone());
+            skipped.push(SkippedPath {
+                path: rel_path.clone(),
+                reason,
+                pattern,
+                delete: false,
+            });
+            continue;
+        }
+
+        if !include_liveries && is_livery_path(rel_path) {
+            push_skip(&mut skip_files, &mut skipped, rel_path, SkipReason::Livery);
             continue;
         }
 
         if prepared.manifest.ignorelist.contains(rel_path) {
-            skip_files.push(rel_path.clone());
+            push_skip(&mut skip_files, &mut skipped, rel_path, SkipReason::Ignored);
             continue;
         }
 
@@ -789,7 +963,12 @@ fn build_plan_internal(
         let should_copy_once = entry.crc32 == -1 || prepared.manifest.oncelist.contains(rel_path);
         if should_copy_once {
             if local_path.exists() {
-                skip_files.push(rel_path.clone());
+                push_skip(
+                    &mut skip_files,
+                    &mut skipped,
+                    rel_path,
+                    SkipReason::CopyOnce,
+                );
             } else {
                 add_files.push(rel_path.clone());
             }
@@ -806,7 +985,12 @@ fn build_plan_internal(
                 "Remote entry '{}' points to a directory locally; skipping",
                 rel_path
             ));
-            skip_files.push(rel_path.clone());
+            push_skip(
+                &mut skip_files,
+                &mut skipped,
+                rel_path,
+                SkipReason::LocalDirectory,
+            );
             continue;
         }
 
@@ -825,7 +1009,12 @@ fn build_plan_internal(
         if needs_replace {
             replace_files.push(rel_path);
         } else {
-            skip_files.push(rel_path);
+            push_skip(
+                &mut skip_files,
+                &mut skipped,
+                &rel_path,
+                SkipReason::Unchanged,
+            );
         }
     }
 
@@ -834,15 +1023,23 @@ fn build_plan_internal(
             &prepared.target_path,
             &prepared.manifest.blacklist,
             &whitelist_paths,
+            &filters,
             include_liveries,
+            &mut skipped,
             &mut warnings,
         )?;
     }
 
     let mut estimated_download_bytes: u64 = 0;
+    let mut file_details = Vec::new();
     for rel_path in add_files.iter().chain(replace_files.iter()) {
         if let Some(size) = prepared.manifest.sizes.get(rel_path) {
             estimated_download_bytes = estimated_download_bytes.saturating_add(*size);
+            file_details.push(PlannedFileInfo {
+                path: rel_path.clone(),
+                size: Some(*size),
+                ..Default::default()
+            });
         }
     }
 
@@ -874,16 +1071,161 @@ fn build_plan_internal(
         replace_files,
         delete_files,
         skip_files,
+        skipped,
         warnings,
-        has_beta_config: prepared.local.beta_zone.is_some() || prepared.local.beta_module.is_some(),
+        has_beta_config: prepared.local.has_beta_config(),
+        file_details,
+        preflight: None,
     })
 }
 
+fn push_skip(
+    skip_files: &mut Vec<String>,
+    skipped: &mut Vec<SkippedPath>,
+    rel_path: &str,
+    reason: SkipReason,
+) {
+    skip_files.push(rel_path.to_string());
+    skipped.push(SkippedPath {
+        path: rel_path.to_string(),
+        reason,
+        pattern: None,
+        delete: false,
+    });
+}
+
+#[derive(Debug, Clone)]
+struct PathRule {
+    raw: String,
+    pattern: Pattern,
+}
+
+/// User-maintained filters read from the addon folder. Blacklist patterns protect local
+/// paths from being downloaded over or deleted; whitelist patterns, when present, restrict
+/// the update to matching paths only.
+#[derive(Debug, Clone, Default)]
+struct LocalPathFilters {
+    blacklist: Vec<PathRule>,
+    whitelist: Vec<PathRule>,
+}
+
+impl LocalPathFilters {
+    fn load(target_root: &Path, warnings: &mut Vec<String>) -> Self {
+        let mut filters = Self::default();
+
+        if let Some(text) = read_local_filter_file(target_root, LOCAL_BLACKLIST_FILE, warnings) {
+            filters.blacklist = parse_path_rules(&text, "blacklist", warnings);
+            // The list protects itself so an update cannot replace the user's rules.
+            filters.blacklist.extend(parse_path_rules(
+                LOCAL_BLACKLIST_FILE,
+                "blacklist",
+                warnings,
+            ));
+        }
+
+        if let Some(text) = read_local_filter_file(target_root, LOCAL_WHITELIST_FILE, warnings) {
+            // Addons can ship a copy of the remote manifest under the same name; that
+            // `path|crc` form describes the package, not a user restriction.
+            if !is_manifest_listing(&text) {
+                filters.whitelist = parse_path_rules(&text, "whitelist", warnings);
+            }
+        }
+
+        filters
+    }
+
+    /// Returns why `rel_path` must be left untouched, if it must.
+    fn exclusion(&self, rel_path: &str) -> Option<(SkipReason, Option<String>)> {
+        if let Some(rule) = self.blacklist.iter().find(|rule| rule.matches(rel_path)) {
+            return Some((SkipReason::Blacklisted, Some(rule.raw.clone())));
+        }
+        if !self.whitelist.is_empty() && !self.whitelist.iter().any(|rule| rule.matches(rel_path)) {
+            return Some((SkipReason::NotWhitelisted, None));
+        }
+        None
+    }
+}
+
+impl PathRule {
+    /// A rule matches the path itself or any of its parent directories, so `Custom/*`
+    /// and `Custom` both protect everything below `Custom/`.
+    fn matches(&self, rel_path: &str) -> bool {
+        let options = glob::MatchOptions {
+            case_sensitive: false,
+            require_literal_separator: false,
+            require_literal_leading_dot: false,
+        };
+        if self.pattern.matches_with(rel_path, options) {
+            return true;
+        }
+        rel_path
+            .match_indices('/')
+            .any(|(idx, _)| self.pattern.matches_with(&rel_path[..idx], options))
+    }
+}
+
+fn read_local_filter_file(
+    target_root: &Path,
+    file_name: &str,
+    warnings: &mut Vec<String>,
+) -> Option<String> {
+    let path = target_root.join(file_name);
+    if !path.is_file() {
+        return None;
+    }
+    match fs::read(&path) {
+        Ok(bytes) => Some(String::from_utf8_lossy(&bytes).into_owned()),
+        Err(e) => {
+            warnings.push(format!("Failed to read local {}: {}", file_name, e));
+            None
+        }
+    }
+}
+
+fn is_manifest_listing(content: &str) -> bool {
+    let mut lines = filter_rule_lines(content).peekable();
+    lines.peek().is_some() && lines.all(|line| line.contains('|'))
+}
+
+fn filter_rule_lines(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .lines()
+        .map(|line| line.trim_start_matches('\u{feff}').trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("//"))
+}
+
+fn parse_path_rules(content: &str, label: &str, warnings: &mut Vec<String>) -> Vec<PathRule> {
+    let mut rules = Vec::new();
+    for line in filter_rule_lines(content) {
+        let mut normalized = line.replace('\\', "/");
+        while let Some(rest) = normalized.strip_prefix("./") {
+            normalized = rest.to_string();
+        }
+        let normalized = normalized.trim_matches('/').to_string();
+        if normalized.is_empty() {
+            continue;
+        }
+        match Pattern::new(&normalized) {
+            Ok(pattern) => rules.push(PathRule {
+                raw: line.to_string(),
+                pattern,
+            }),
+            Err(e) => warnings.push(format!(
+                "Skipped invalid local {} pattern '{}': {}",
+                label, line, e
+            )),
+        }
+    }
+    rules
+}
+
 fn expand_blacklist_delete_files(
     target_root: &Path,
     blacklist: &HashSet<String>,
     whitelist_paths: &HashSet<String>,
+    filters: &LocalPathFilters,
     include_liveries: bool,
+    skipped: &mut Vec<SkippedPath>,
     warnings: &mut Vec<String>,
 ) -> Result<Vec<String>> {
     if blacklist.is_empty() {
@@ -937,10 +1279,38 @@ fn expand_blacklist_delete_files(
         .map(|entry| entry.rel_path.clone())
         .collect();
 
+    let protected_paths: Vec<&str> = local_entries
+        .iter()
+        .filter(|entry| filters.exclusion(&entry.rel_path).is_some())
+        .map(|entry| entry.rel_path.as_str())
+        .collect();
+    let mut protected_skips: Vec<SkippedPath> = Vec::new();
+
     let mut delete_entries: Vec<LocalRelativeEntry> = local_entries
-        .into_iter()
+        .iter()
         .filter(|entry| delete_candidates.contains(&entry.rel_path))
         .filter(|entry| {
+            if let Some((reason, pattern)) = filters.exclusion(&entry.rel_path) {
+                protected_skips.push(SkippedPath {
+                    path: entry.rel_path.clone(),
+                    reason,
+                    pattern,
+                    delete: true,
+                });
+                return false;
+            }
+
+            if entry.is_dir {
+                let prefix = format!("{}/", entry.rel_path);
+                if protected_paths.iter().any(|path| path.starts_with(&prefix)) {
+                    warnings.push(format!(
+                        "Blacklist directory '{}' contains protected local entries; skipped directory delete",
+                        entry.rel_path
+                    ));
+                    return false;
+                }
+            }
+
             if !include_liveries && is_livery_path(&entry.rel_path) {
                 return false;
             }
@@ -966,7 +1336,10 @@ fn expand_blacklist_delete_files(
 
             true
         })
+        .cloned()
         .collect();
+    protected_skips.sort_by(|a, b| a.path.cmp(&b.path));
+    skipped.extend(protected_skips);
 
     delete_entries.sort_by(|a, b| {
         let a_depth = a.rel_path.matches('/').count();
@@ -1000,11 +1373,19 @@ fn expand_blacklist_delete_files(
 
 async fn prepare_update_context(target_path: &Path, use_beta: bool) -> Result<PreparedUpdate> {
     let local = read_local_config(target_path)?;
+    let use_beta = use_beta || local.beta;
+    fetch_update_context(target_path, local, use_beta).await
+}
+
+async fn fetch_update_context(
+    target_path: &Path,
+    local: LocalConfig,
+    use_beta: bool,
+) -> Result<PreparedUpdate> {
     let module_url = select_module_url(&local, use_beta)?;
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(20))
-        .user_agent("XFast Manager")
+    let client = http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(std::time::Duration::from_secs(20)))
         .build()
         .context("Failed to build HTTP client")?;
 
@@ -1048,6 +1429,10 @@ fn read_local_config(target_path: &Path) -> Result<LocalConfig> {
         .get("liveries")
         .map(|v| parse_bool(v).unwrap_or(true))
         .unwrap_or(true);
+    let beta = cfg_map
+        .get("beta")
+        .and_then(|v| parse_bool(v))
+        .unwrap_or(false);
 
     let beta_cfg_path = {
         let primary = target_path.join(LOCAL_BETA_CFG_FILE);
@@ -1098,6 +1483,7 @@ fn read_local_config(target_path: &Path) -> Result<LocalConfig> {
             .map(|v| v.trim().to_string())
             .filter(|v| !v.is_empty()),
         liveries,
+        beta,
         beta_zone,
         beta_module,
     })
@@ -1247,8 +1633,25 @@ async fn probe_range_support(client: &reqwest::Client, url: &str) -> Result<Opti
     }
 }
 
+/// Report the part of `received` that no earlier attempt of the same download
+/// reported yet, so retries don't count progress twice
+fn report_new_bytes(
+    callback: &Option<Arc<dyn Fn(String, u64) + Send + Sync>>,
+    rel_path: &str,
+    reported: &AtomicU64,
+    received: u64,
+) {
+    let previous = reported.fetch_max(received, Ordering::Relaxed);
+    if received > previous {
+        if let Some(cb) = callback.as_ref() {
+            cb(rel_path.to_string(), received - previous);
+        }
+    }
+}
+
 /// Download a single file using multiple concurrent HTTP Range connections.
 /// Each chunk acquires a permit from the semaphore before downloading.
+/// Returns the data and the retries its chunks needed.
 async fn download_file_chunked(
     client: &reqwest::Client,
     url: &str,
@@ -1258,7 +1661,7 @@ async fn download_file_chunked(
     task_control: &Option<TaskControl>,
     chunk_progress_callback: &Option<Arc<dyn Fn(String, u64) + Send + Sync>>,
     rel_path: &str,
-) -> Result<Vec<u8>> {
+) -> Result<(Vec<u8>, u32)> {
     let chunk_size = file_size / num_chunks as u64;
     let mut ranges: Vec<(u64, u64)> = Vec::with_capacity(num_chunks);
     for i in 0..num_chunks {
@@ -1283,64 +1686,92 @@ async fn download_file_chunked(
         let end = *end;
 
         let handle = tokio::spawn(async move {
-            let _permit = semaphore
-                .acquire()
-                .await
-                .map_err(|_| anyhow!("Semaphore closed"))?;
-
-            ensure_not_cancelled(task_control.as_ref(), "install")?;
+            let policy = RetryPolicy::default();
+            let reported = AtomicU64::new(0);
+            let reported = &reported;
+            let client = &client;
+            let url = url.as_str();
+            let semaphore = &semaphore;
+            let task_control = task_control.as_ref();
+            let chunk_progress_callback = &chunk_progress_callback;
+            let rel_path = rel_path.as_str();
+
+            let ((data, is_fallback), retries) = download_retry::retry(
+                &policy,
+                url,
+                task_control,
+                RETRY_LOG_CTX,
+                move || async move {
+                    let _permit = semaphore
+                        .acquire()
+                        .await
+                        .map_err(|_| anyhow!("Semaphore closed"))?;
+
+                    ensure_not_cancelled(task_control, "install")?;
+                    let _host_permit = download_retry::acquire_host_permit(url).await?;
+
+                    let range_header = format!("bytes={}-{}", start, end);
+                    let response = client
+                        .get(url)
+                        .header("Range", &range_header)
+                        .send()
+                        .await
+                        .map_err(|e| download_retry::transport_failure(e, url))?;
+
+                    let status = response.status();
+                    // If server returns 200 instead of 206, it doesn't support Range for this request
+                    if status == reqwest::StatusCode::OK && idx == 0 {
+                        // First chunk gets full body - consume it all
+                        let mut data = Vec::new();
+                        let mut stream = response.bytes_stream();
+                        while let Some(next_chunk) = stream.next().await {
+                            ensure_not_cancelled(task_control, "install")?;
+                            let chunk = next_chunk
+                                .map_err(|e| download_retry::transport_failure(e, url))?;
+                            data.extend_from_slice(&chunk);
+                            report_new_bytes(
+                                chunk_progress_callback,
+                                rel_path,
+                                reported,
+                                data.len() as u64,
+                            );
+                        }
+                        return Ok((data, true));
+                    } else if status == reqwest::StatusCode::OK && idx > 0 {
+                        // Non-first chunk got 200 - skip since first chunk has full data
+                        return Ok((Vec::new(), true));
+                    }
 
-            let range_header = format!("bytes={}-{}", start, end);
-            let response = client
-                .get(&url)
-                .header("Range", &range_header)
-                .send()
-                .await
-                .with_context(|| format!("Failed to download chunk {} of '{}'", idx, url))?;
-
-            let status = response.status();
-            // If server returns 200 instead of 206, it doesn't support Range for this request
-            if status == reqwest::StatusCode::OK && idx == 0 {
-                // First chunk gets full body - consume it all
-                let mut data = Vec::new();
-                let mut stream = response.bytes_stream();
-                while let Some(next_chunk) = stream.next().await {
-                    ensure_not_cancelled(task_control.as_ref(), "install")?;
-                    let chunk = next_chunk
-                        .with_context(|| format!("Failed to stream response body for '{}'", url))?;
-                    data.extend_from_slice(&chunk);
-                    if let Some(cb) = chunk_progress_callback.as_ref() {
-                        cb(rel_path.clone(), chunk.len() as u64);
+                    if !status.is_success() {
+                        return Err(download_retry::status_failure(
+                            status,
+                            response.headers(),
+                            url,
+                        ));
                     }
-                }
-                return Ok::<(usize, Vec<u8>, bool), anyhow::Error>((idx, data, true));
-            } else if status == reqwest::StatusCode::OK && idx > 0 {
-                // Non-first chunk got 200 - skip since first chunk has full data
-                return Ok((idx, Vec::new(), true));
-            }
 
-            if !status.is_success() {
-                return Err(anyhow!(
-                    "Download failed for '{}' chunk {}: HTTP {}",
-                    url,
-                    idx,
-                    status
-                ));
-            }
+                    let mut data = Vec::with_capacity((end - start + 1) as usize);
+                    let mut stream = response.bytes_stream();
+                    while let Some(next_chunk) = stream.next().await {
+                        ensure_not_cancelled(task_control, "install")?;
+                        let chunk =
+                            next_chunk.map_err(|e| download_retry::transport_failure(e, url))?;
+                        data.extend_from_slice(&chunk);
+                        report_new_bytes(
+                            chunk_progress_callback,
+                            rel_path,
+                            reported,
+                            data.len() as u64,
+                        );
+                    }
 
-            let mut data = Vec::with_capacity((end - start + 1) as usize);
-            let mut stream = response.bytes_stream();
-            while let Some(next_chunk) = stream.next().await {
-                ensure_not_cancelled(task_control.as_ref(), "install")?;
-                let chunk = next_chunk
-                    .with_context(|| format!("Failed to stream chunk {} for '{}'", idx, url))?;
-                data.extend_from_slice(&chunk);
-                if let Some(cb) = chunk_progress_callback.as_ref() {
-                    cb(rel_path.clone(), chunk.len() as u64);
-                }
-            }
+                    Ok::<(Vec<u8>, bool), download_retry::Failure>((data, false))
+                },
+            )
+            .await
+            .with_context(|| format!("Chunk {} of '{}' failed", idx, rel_path))?;
 
-            Ok((idx, data, false))
+            Ok::<(usize, Vec<u8>, bool, u32), anyhow::Error>((idx, data, is_fallback, retries))
         });
 
         handles.push(handle);
@@ -1348,11 +1779,13 @@ async fn download_file_chunked(
 
     let mut chunks: Vec<(usize, Vec<u8>)> = Vec::with_capacity(num_chunks);
     let mut full_body_fallback = false;
+    let mut total_retries = 0;
 
     for handle in handles {
-        let (idx, data, is_fallback) = handle
+        let (idx, data, is_fallback, retries) = handle
             .await
             .map_err(|e| anyhow!("Chunk download task panicked: {}", e))??;
+        total_retries += retries;
         if is_fallback {
             full_body_fallback = true;
         }
@@ -1366,7 +1799,7 @@ async fn download_file_chunked(
             .into_iter()
             .find(|(idx, data)| *idx == 0 && !data.is_empty())
         {
-            return Ok(data);
+            return Ok((data, total_retries));
         }
         return Err(anyhow!("Range fallback failed: no data from first chunk"));
     }
@@ -1379,7 +1812,7 @@ async fn download_file_chunked(
         result.extend_from_slice(&data);
     }
 
-    Ok(result)
+    Ok((result, total_retries))
 }
 
 async fn download_files(
@@ -1394,14 +1827,14 @@ async fn download_files(
     chunked_enabled: bool,
     threads_per_task: usize,
     total_threads: usize,
+    retry_stats: &RetryStats,
 ) -> Result<HashMap<String, Vec<u8>>> {
     if paths.is_empty() {
         return Ok(HashMap::new());
     }
 
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .user_agent("XFast Manager")
+    let client = http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(std::time::Duration::from_secs(30)))
         .build()
         .context("Failed to build HTTP client")?;
 
@@ -1445,7 +1878,7 @@ async fn download_files(
                 && threads_per_task > 1
                 && file_size >= CHUNKED_DOWNLOAD_MIN_SIZE;
 
-            let data = if use_chunked {
+            let (data, retries) = if use_chunked {
                 download_file_chunked(
                     &client,
                     url.as_str(),
@@ -1459,38 +1892,61 @@ async fn download_files(
                 .await?
             } else {
                 // Single-connection download with semaphore permit
-                let _permit = semaphore
-                    .acquire()
-                    .await
-                    .map_err(|_| anyhow!("Semaphore closed"))?;
-
-                let response = client
-                    .get(url.clone())
-                    .send()
-                    .await
-                    .with_context(|| format!("Failed to download '{}'", url))?;
-
-                if !response.status().is_success() {
-                    return Err(anyhow!(
-                        "Download failed for '{}': HTTP {}",
-                        url,
-                        response.status()
-                    ));
-                }
-
-                let mut data = Vec::new();
-                let mut stream = response.bytes_stream();
-                while let Some(next_chunk) = stream.next().await {
-                    ensure_not_cancelled(task_control.as_ref(), "install")?;
-                    let chunk = next_chunk
-                        .with_context(|| format!("Failed to stream response body for '{}'", url))?;
-                    data.extend_from_slice(&chunk);
-                    if let Some(cb) = chunk_progress_callback.as_ref() {
-                        cb(rel_path.clone(), chunk.len() as u64);
-                    }
-                }
-                data
+                let policy = RetryPolicy::default();
+                let reported = AtomicU64::new(0);
+                let reported = &reported;
+                let client = &client;
+                let url = url.as_str();
+                let semaphore = &semaphore;
+                let task_control = task_control.as_ref();
+                let chunk_progress_callback = &chunk_progress_callback;
+                let rel_path = rel_path.as_str();
+                download_retry::retry(
+                    &policy,
+                    url,
+                    task_control,
+                    RETRY_LOG_CTX,
+                    move || async move {
+                        let _permit = semaphore
+                            .acquire()
+                            .await
+                            .map_err(|_| anyhow!("Semaphore closed"))?;
+                        let _host_permit = download_retry::acquire_host_permit(url).await?;
+
+                        let response = client
+                            .get(url)
+                            .send()
+                            .await
+                            .map_err(|e| download_retry::transport_failure(e, url))?;
+
+                        if !response.status().is_success() {
+                            return Err(download_retry::status_failure(
+                                response.status(),
+                                response.headers(),
+                                url,
+                            ));
+                        }
+
+                        let mut data = Vec::new();
+                        let mut stream = response.bytes_stream();
+                        while let Some(next_chunk) = stream.next().await {
+                            ensure_not_cancelled(task_control, "install")?;
+                            let chunk = next_chunk
+                                .map_err(|e| download_retry::transport_failure(e, url))?;
+                            data.extend_from_slice(&chunk);
+                            report_new_bytes(
+                                chunk_progress_callback,
+                                rel_path,
+                                reported,
+                                data.len() as u64,
+                            );
+                        }
+                        Ok::<Vec<u8>, download_retry::Failure>(data)
+                    },
+                )
+                .await?
             };
+            retry_stats.record(retries);
 
             if let Some(expected) = expected_crc.get(&rel_path) {
                 if *expected >= 0 {
@@ -1632,6 +2088,46 @@ fn update_local_cfg_fields(cfg_path: &Path, remote: &RemoteConfig) -> Result<()>
     Ok(())
 }
 
+/// Set `key|value` in cfg text, changing only the value of the matching lines.
+/// Other keys, comments and the line endings are kept; a missing key is
+/// appended.
+fn set_cfg_field(content: &str, key: &str, value: &str) -> String {
+    let line_ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut out = String::with_capacity(content.len() + key.len() + value.len() + 2);
+    let mut found = false;
+    for line in content.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\r', '\n']);
+        let ending = &line[text.len()..];
+        let matches = !text.trim_start().starts_with('#')
+            && text
+                .split_once('|')
+                .is_some_and(|(k, _)| k.trim().eq_ignore_ascii_case(key));
+        if matches {
+            // parse_cfg_lines keeps the last of duplicate keys, so all are set
+            let (prefix, _) = text.split_once('|').unwrap_or((text, ""));
+            out.push_str(prefix);
+            out.push('|');
+            out.push_str(value);
+            out.push_str(ending);
+            found = true;
+        } else {
+            out.push_str(line);
+        }
+    }
+    if !found {
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push_str(line_ending);
+        }
+        out.push_str(&format!("{}|{}", key, value));
+        out.push_str(line_ending);
+    }
+    out
+}
+
 fn upsert_cfg_field(lines: &mut Vec<String>, key: &str, value: &str) {
     let key_prefix = format!("{}|", key.to_lowercase());
     for line in lines.iter_mut() {
@@ -1643,6 +2139,78 @@ fn upsert_cfg_field(lines: &mut Vec<String>, key: &str, value: &str) {
     lines.push(format!("{}|{}", key, value));
 }
 
+/// Update channel a SkunkCrafts addon follows
+#[derive(Debug, Clone)]
+pub struct SkunkChannel {
+    pub beta: bool,
+    /// Whether the addon ships a beta override (skunkcrafts_updater_beta.cfg)
+    pub has_beta_config: bool,
+    /// Version the channel offers, when it was fetched
+    pub remote_version: Option<String>,
+}
+
+/// Channel from the `beta|` line of the addon's skunkcrafts_updater.cfg
+pub fn read_update_channel(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+) -> Result<SkunkChannel> {
+    let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
+    let local = read_local_config(&target_path)?;
+    Ok(SkunkChannel {
+        beta: local.beta,
+        has_beta_config: local.has_beta_config(),
+        remote_version: None,
+    })
+}
+
+/// Switch the addon between its stable and beta module by rewriting the
+/// `beta|` line. The channel's remote config and manifest are fetched first,
+/// so a channel the remote doesn't serve is refused and the cfg left alone.
+pub async fn set_update_channel(
+    xplane_path: &Path,
+    item_type: &str,
+    folder_name: &str,
+    beta: bool,
+) -> Result<SkunkChannel> {
+    let target_path = resolve_target_path(xplane_path, item_type, folder_name)?;
+    let local = read_local_config(&target_path)?;
+    let has_beta_config = local.has_beta_config();
+    if beta && !has_beta_config {
+        return Err(anyhow!(
+            "'{}' has no beta channel: {} is missing",
+            folder_name,
+            LOCAL_BETA_CFG_FILE
+        ));
+    }
+
+    let current = local.beta;
+    let cfg_path = local.cfg_path.clone();
+    let prepared = fetch_update_context(&target_path, local, beta)
+        .await
+        .with_context(|| {
+            format!(
+                "The {} channel of '{}' is not available",
+                if beta { "beta" } else { "stable" },
+                folder_name
+            )
+        })?;
+
+    if current != beta {
+        let content = fs::read_to_string(&cfg_path)
+            .with_context(|| format!("Failed to read cfg '{}'", cfg_path.display()))?;
+        let updated = set_cfg_field(&content, "beta", if beta { "true" } else { "false" });
+        write_file_atomic(&cfg_path, updated.as_bytes())
+            .with_context(|| format!("Failed to write cfg '{}'", cfg_path.display()))?;
+    }
+
+    Ok(SkunkChannel {
+        beta,
+        has_beta_config,
+        remote_version: Some(prepared.remote.version),
+    })
+}
+
 fn resolve_target_path(xplane_path: &Path, item_type: &str, folder_name: &str) -> Result<PathBuf> {
     if folder_name.trim().is_empty() {
         return Err(anyhow!("Folder name cannot be empty"));
@@ -2075,3 +2643,203 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(apply_blacklist: bool) -> SkunkUpdateOptions {
+        SkunkUpdateOptions {
+            use_beta: false,
+            include_liveries: true,
+            apply_blacklist,
+            rollback_on_failure: false,
+            parallel_downloads: None,
+            channel: None,
+            fresh_install: false,
+            preserve_liveries: false,
+            preserve_config_files: false,
+            chunked_download_enabled: None,
+            threads_per_task: None,
+            total_threads: None,
+            keep_backup_versions: None,
+        }
+    }
+
+    fn prepared(target: &Path, remote_files: &[&str], remote_blacklist: &[&str]) -> PreparedUpdate {
+        PreparedUpdate {
+            local: LocalConfig {
+                cfg_path: target.join(LOCAL_CFG_FILE),
+                zone: None,
+                module: "https://example.com/addon".to_string(),
+                version: Some("1.0".to_string()),
+                liveries: true,
+                beta: false,
+                beta_zone: None,
+                beta_module: None,
+            },
+            remote: RemoteConfig {
+                version: "1.1".to_string(),
+                locked: false,
+                zone: None,
+                module: None,
+                name: None,
+            },
+            manifest: RemoteManifest {
+                whitelist: remote_files
+                    .iter()
+                    .map(|path| WhitelistEntry {
+                        path: path.to_string(),
+                        crc32: 1,
+                    })
+                    .collect(),
+                blacklist: remote_blacklist.iter().map(|p| p.to_string()).collect(),
+                ..Default::default()
+            },
+            module_url: "https://example.com/addon".to_string(),
+            target_path: target.to_path_buf(),
+        }
+    }
+
+    fn write(root: &Path, rel: &str, content: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn reason_for(plan: &SkunkUpdatePlan, path: &str) -> Option<SkipReason> {
+        plan.skipped
+            .iter()
+            .find(|s| s.path == path)
+            .map(|s| s.reason)
+    }
+
+    #[test]
+    fn filter_rules_handle_crlf_comments_and_backslashes() {
+        let mut warnings = Vec::new();
+        let rules = parse_path_rules(
+            "\u{feff}# user settings\r\n\r\nplugins\\config\\*.ini\r\n// legacy\r\n.\\liveries\\Mine\r\n",
+            "blacklist",
+            &mut warnings,
+        );
+        let filters = LocalPathFilters {
+            blacklist: rules,
+            whitelist: Vec::new(),
+        };
+
+        assert!(warnings.is_empty());
+        assert_eq!(filters.blacklist.len(), 2);
+        assert_eq!(
+            filters.exclusion("plugins/config/user.ini"),
+            Some((
+                SkipReason::Blacklisted,
+                Some("plugins\\config\\*.ini".to_string())
+            ))
+        );
+        assert!(filters.exclusion("liveries/mine/texture.png").is_some());
+        assert!(filters.exclusion("plugins/config/user.txt").is_none());
+    }
+
+    #[test]
+    fn local_blacklist_excludes_downloads() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            LOCAL_BLACKLIST_FILE,
+            "plugins\\config\\*.ini\r\n",
+        );
+        write(dir.path(), "plugins/config/user.ini", "custom");
+        let prepared = prepared(
+            dir.path(),
+            &[
+                "plugins/config/user.ini",
+                "plugins/config/new.ini",
+                "objects/a.obj",
+                LOCAL_BLACKLIST_FILE,
+            ],
+            &[],
+        );
+
+        let plan = build_plan_internal(&prepared, "aircraft", "Addon", &options(false)).unwrap();
+
+        assert_eq!(plan.add_files, vec!["objects/a.obj".to_string()]);
+        assert!(plan.replace_files.is_empty());
+        assert_eq!(
+            reason_for(&plan, "plugins/config/user.ini"),
+            Some(SkipReason::Blacklisted)
+        );
+        assert_eq!(
+            reason_for(&plan, "plugins/config/new.ini"),
+            Some(SkipReason::Blacklisted)
+        );
+        assert_eq!(
+            reason_for(&plan, LOCAL_BLACKLIST_FILE),
+            Some(SkipReason::Blacklisted)
+        );
+        assert_eq!(plan.skip_files.len(), plan.skipped.len());
+    }
+
+    #[test]
+    fn local_blacklist_protects_remote_deletes() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), LOCAL_BLACKLIST_FILE, "old/keep.dat\r\n");
+        write(dir.path(), "old/keep.dat", "keep");
+        write(dir.path(), "old/drop.dat", "drop");
+        let prepared = prepared(dir.path(), &["objects/a.obj"], &["old"]);
+
+        let plan = build_plan_internal(&prepared, "aircraft", "Addon", &options(true)).unwrap();
+
+        assert_eq!(plan.delete_files, vec!["old/drop.dat".to_string()]);
+        let skip = plan
+            .skipped
+            .iter()
+            .find(|s| s.path == "old/keep.dat")
+            .expect("protected delete should be reported");
+        assert_eq!(skip.reason, SkipReason::Blacklisted);
+        assert!(skip.delete);
+    }
+
+    #[test]
+    fn local_whitelist_restricts_update_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            LOCAL_WHITELIST_FILE,
+            "# only objects\r\nobjects\r\n",
+        );
+        let prepared = prepared(dir.path(), &["objects/a.obj", "cockpit/panel.png"], &[]);
+
+        let plan = build_plan_internal(&prepared, "aircraft", "Addon", &options(false)).unwrap();
+
+        assert_eq!(plan.add_files, vec!["objects/a.obj".to_string()]);
+        assert_eq!(
+            reason_for(&plan, "cockpit/panel.png"),
+            Some(SkipReason::NotWhitelisted)
+        );
+
+        write(
+            dir.path(),
+            LOCAL_WHITELIST_FILE,
+            "objects/a.obj|123\r\ncockpit/panel.png|456\r\n",
+        );
+        let plan = build_plan_internal(&prepared, "aircraft", "Addon", &options(false)).unwrap();
+        assert_eq!(plan.add_files.len(), 2);
+    }
+
+    #[test]
+    fn set_cfg_field_keeps_comments_unknown_keys_and_crlf() {
+        let content = "# installed by hand\r\nmodule|https://example.com/addon\r\nBeta | false\r\ncustom|x\r\n";
+        assert_eq!(
+            set_cfg_field(content, "beta", "true"),
+            "# installed by hand\r\nmodule|https://example.com/addon\r\nBeta |true\r\ncustom|x\r\n"
+        );
+        assert_eq!(
+            set_cfg_field("module|m\nversion|1.0", "beta", "true"),
+            "module|m\nversion|1.0\nbeta|true\n"
+        );
+        assert_eq!(
+            parse_cfg_lines(&set_cfg_field(content, "beta", "true")).get("beta"),
+            Some(&"true".to_string())
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Disk space and path length checks on an addon update plan
+//!
+//! Both run on the plan, before anything is downloaded, so an update doesn't
+//! stop half-way through with a full disk or a path Windows can't open. Rollback
+//! needs room of its own for the copies of replaced and deleted files. Those go
+//! to the system temp folder, so they are checked against that volume, or added
+//! to the addon's when both folders share one.
+//!
+//! Windows limits plain paths to `MAX_PATH`; deeply nested liveries easily go
+//! past it. Such paths are written through the `\\?\` prefix instead, which
+//! lifts the limit. A file or folder name longer than any file system accepts
+//! can't be written at all, so those fail the update up front.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::addon_updater;
+use crate::fs_case;
+use crate::skunk_updater::SkunkUpdatePlan;
+
+/// Longest absolute path Windows opens without the `\\?\` prefix
+const MAX_PLAIN_PATH_LEN: usize = 255;
+
+/// Longest file or folder name on NTFS, APFS and ext4
+const MAX_NAME_LEN: usize = 255;
+
+/// Longest paths listed in errors and warnings; the rest are counted
+const MAX_LISTED_PATHS: usize = 10;
+
+/// Disk space and path length findings for a plan
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePreflight {
+    /// Bytes the update needs on the addon's volume: downloads, plus rollback
+    /// copies when the temp folder is on the same volume
+    pub required_bytes: u64,
+    /// Free bytes on that volume, when they could be read
+    pub free_bytes: Option<u64>,
+    /// Bytes of rollback copies needed on the temp folder's volume, when that
+    /// is a different volume from the addon's
+    #[serde(default)]
+    pub rollback_required_bytes: u64,
+    /// Free bytes on the temp folder's volume, when they could be read
+    #[serde(default)]
+    pub rollback_free_bytes: Option<u64>,
+    /// Planned files past `MAX_PATH`, written with the `\\?\` prefix (Windows only)
+    pub long_paths: Vec<String>,
+    /// Planned files with a name longer than any file system accepts
+    pub long_names: Vec<String>,
+}
+
+impl UpdatePreflight {
+    pub fn has_enough_space(&self) -> bool {
+        self.free_bytes
+            .is_none_or(|free_bytes| free_bytes >= self.required_bytes)
+    }
+
+    pub fn has_enough_rollback_space(&self) -> bool {
+        self.rollback_free_bytes
+            .is_none_or(|free_bytes| free_bytes >= self.rollback_required_bytes)
+    }
+
+    /// Fail with every problem that would stop the update part-way
+    pub fn ensure_passes(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        if !self.has_enough_space() {
+            problems.push(format!(
+                "Not enough free disk space: {} needed, {} available",
+                format_bytes(self.required_bytes),
+                format_bytes(self.free_bytes.unwrap_or(0))
+            ));
+        }
+        if !self.has_enough_rollback_space() {
+            problems.push(format!(
+                "Not enough free space in the temp folder for rollback copies: {} needed, {} available",
+                format_bytes(self.rollback_required_bytes),
+                format_bytes(self.rollback_free_bytes.unwrap_or(0))
+            ));
+        }
+        if !self.long_names.is_empty() {
+            problems.push(format!(
+                "File names longer than {} characters can't be written: {}",
+                MAX_NAME_LEN,
+                list_paths(&self.long_names)
+            ));
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(problems.join(". ")))
+        }
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.has_enough_space() {
+            warnings.push(format!(
+                "Not enough free disk space: the update needs {} but only {} is available",
+                format_bytes(self.required_bytes),
+                format_bytes(self.free_bytes.unwrap_or(0))
+            ));
+        }
+        if !self.has_enough_rollback_space() {
+            warnings.push(format!(
+                "Not enough free space in the temp folder for rollback copies: {} needed but only {} is available",
+                format_bytes(self.rollback_required_bytes),
+                format_bytes(self.rollback_free_bytes.unwrap_or(0))
+            ));
+        }
+        if !self.long_names.is_empty() {
+            warnings.push(format!(
+                "{} file(s) have names longer than {} characters and can't be written: {}",
+                self.long_names.len(),
+                MAX_NAME_LEN,
+                list_paths(&self.long_names)
+            ));
+        }
+        if !self.long_paths.is_empty() {
+            warnings.push(format!(
+                "{} file(s) exceed the Windows path length limit and will be written with long path support: {}",
+                self.long_paths.len(),
+                list_paths(&self.long_paths)
+            ));
+        }
+        warnings
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn list_paths(paths: &[String]) -> String {
+    let mut listed = paths
+        .iter()
+        .take(MAX_LISTED_PATHS)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if paths.len() > MAX_LISTED_PATHS {
+        listed.push_str(&format!(" and {} more", paths.len() - MAX_LISTED_PATHS));
+    }
+    listed
+}
+
+/// Length of `path` as Windows counts it, without a `\\?\` prefix
+fn plain_path_len(path: &Path) -> usize {
+    let path = path.to_string_lossy();
+    let plain = path
+        .strip_prefix(r"\\?\UNC\")
+        .or_else(|| path.strip_prefix(r"\\?\"))
+        .unwrap_or(&path);
+    plain.chars().count()
+}
+
+/// Planned files whose absolute path is too long for plain Windows paths, and
+/// those with a name too long for any file system
+fn find_long_paths<'a>(
+    target_path: &Path,
+    rel_paths: impl Iterator<Item = &'a String>,
+) -> (Vec<String>, Vec<String>) {
+    let root_len = plain_path_len(target_path);
+    let mut long_paths = Vec::new();
+    let mut long_names = Vec::new();
+    for rel_path in rel_paths {
+        if rel_path
+            .split(['/', '\\'])
+            .any(|name| name.chars().count() > MAX_NAME_LEN)
+        {
+            long_names.push(rel_path.clone());
+        } else if root_len + 1 + rel_path.chars().count() > MAX_PLAIN_PATH_LEN {
+            long_paths.push(rel_path.clone());
+        }
+    }
+    (long_paths, long_names)
+}
+
+/// Size of what rollback copies before the plan replaces or deletes it
+fn rollback_backup_bytes(target_path: &Path, plan: &SkunkUpdatePlan) -> u64 {
+    plan.replace_files
+        .iter()
+        .chain(plan.delete_files.iter())
+        .map(|rel_path| target_path.join(rel_path.replace('/', std::path::MAIN_SEPARATOR_STR)))
+        .map(|path| {
+            if path.is_dir() {
+                WalkDir::new(&path)
+                    .into_iter()
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.file_type().is_file())
+                    .filter_map(|entry| entry.metadata().ok())
+                    .map(|metadata| metadata.len())
+                    .sum()
+            } else {
+                fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+            }
+        })
+        .fold(0u64, |acc, size| acc.saturating_add(size))
+}
+
+/// Check `plan` against the volume and path limits of `target_path`, record
+/// the result on the plan and add a warning for each problem
+pub fn apply(plan: &mut SkunkUpdatePlan, target_path: &Path, rollback_on_failure: bool) {
+    let backup_bytes = if rollback_on_failure {
+        rollback_backup_bytes(target_path, plan)
+    } else {
+        0
+    };
+    let free_bytes = match addon_updater::disk_space_at(target_path) {
+        Ok(space) => Some(space.free_bytes),
+        Err(e) => {
+            plan.warnings
+                .push(format!("Could not check free disk space: {}", e));
+            None
+        }
+    };
+
+    // Rollback copies go to the system temp folder (see `RollbackState`)
+    let temp_dir = std::env::temp_dir();
+    let mut required_bytes = plan.estimated_download_bytes;
+    let mut rollback_required_bytes = 0;
+    let mut rollback_free_bytes = None;
+    if backup_bytes > 0 {
+        if fs_case::same_volume(target_path, &temp_dir).unwrap_or(true) {
+            required_bytes = required_bytes.saturating_add(backup_bytes);
+        } else {
+            rollback_required_bytes = backup_bytes;
+            rollback_free_bytes = match addon_updater::disk_space_at(&temp_dir) {
+                Ok(space) => Some(space.free_bytes),
+                Err(e) => {
+                    plan.warnings.push(format!(
+                        "Could not check free space in the temp folder: {}",
+                        e
+                    ));
+                    None
+                }
+            };
+        }
+    }
+    let (mut long_paths, long_names) = find_long_paths(
+        target_path,
+        plan.add_files.iter().chain(plan.replace_files.iter()),
+    );
+    if !cfg!(target_os = "windows") {
+        long_paths.clear();
+    }
+
+    let preflight = UpdatePreflight {
+        required_bytes,
+        free_bytes,
+        rollback_required_bytes,
+        rollback_free_bytes,
+        long_paths,
+        long_names,
+    };
+    plan.warnings.extend(preflight.warnings());
+    plan.preflight = Some(preflight);
+}
+
+/// `path` with the `\\?\` prefix on Windows, so files below it may go past
+/// `MAX_PATH`. Unchanged elsewhere and when already prefixed.
+pub fn long_path(path: &Path) -> PathBuf {
+    if !cfg!(target_os = "windows") {
+        return path.to_path_buf();
+    }
+    let text = path.to_string_lossy();
+    if text.starts_with(r"\\?\") || !path.is_absolute() {
+        return path.to_path_buf();
+    }
+    match text.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+        None => PathBuf::from(format!(r"\\?\{}", text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_long_paths_separates_long_names() {
+        let root = Path::new("/X-Plane 12/Aircraft/Laminar Research/Boeing 737-800");
+        let nested = format!("liveries/{}/objects/fuselage.png", "L".repeat(200));
+        let long_name = format!("liveries/{}.png", "N".repeat(300));
+        let short = "plugins/xlua/init.lua".to_string();
+
+        let (long_paths, long_names) =
+            find_long_paths(root, [&short, &nested, &long_name].into_iter());
+
+        assert_eq!(long_paths, vec![nested.clone()]);
+        assert_eq!(long_names, vec![long_name]);
+        assert_eq!(plain_path_len(Path::new(r"\\?\C:\X")), 4);
+    }
+
+    #[test]
+    fn test_preflight_fails_when_space_is_short() {
+        let preflight = UpdatePreflight {
+            required_bytes: 2 * 1024 * 1024,
+            free_bytes: Some(1024),
+            long_names: vec!["a".repeat(300)],
+            ..Default::default()
+        };
+
+        let message = preflight.ensure_passes().unwrap_err().to_string();
+        assert!(message.contains("2.0 MB needed, 1024 B available"));
+        assert!(message.contains("can't be written"));
+        assert_eq!(preflight.warnings().len(), 2);
+
+        // Rollback copies on another volume are checked against that volume
+        let preflight = UpdatePreflight {
+            required_bytes: 1024,
+            free_bytes: Some(4096),
+            rollback_required_bytes: 8192,
+            rollback_free_bytes: Some(4096),
+            ..Default::default()
+        };
+        assert!(preflight.has_enough_space());
+        let message = preflight.ensure_passes().unwrap_err().to_string();
+        assert!(message.contains("temp folder for rollback copies: 8.0 KB needed"));
+        assert!(UpdatePreflight::default().ensure_passes().is_ok());
+    }
+}
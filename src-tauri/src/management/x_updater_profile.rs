@@ -1,10 +1,15 @@
 use anyhow::{anyhow, Context, Result};
 use reqwest::Url;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashSet;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use crate::app_dirs;
+use crate::settings_store;
+
 pub const XUPDATER_URL_PREFIX: &str = "x-updater:";
 const DEFAULT_XUPDATER_HOST: &str = "https://update.x-plane.org";
 
@@ -38,6 +43,25 @@ const XUPDATER_PROFILE_JSON_FILES: [&str; 8] = [
     "xupdater_profile.json",
 ];
 
+const CFG_HOST_KEYS: [&str; 7] = [
+    "host",
+    "server",
+    "update_host",
+    "updatehost",
+    "base_url",
+    "baseurl",
+    "url",
+];
+const CFG_LOGIN_KEYS: [&str; 4] = ["login", "username", "user", "email"];
+const CFG_LICENSE_KEYS: [&str; 6] = [
+    "licensekey",
+    "license_key",
+    "key",
+    "license",
+    "password",
+    "token",
+];
+
 const XUPDATER_NATIVE_MARKER_FILES: [&str; 7] = [
     "client-configuration",
     "productid",
@@ -64,12 +88,64 @@ impl XUpdaterProfile {
     }
 }
 
+/// How a profile field failed validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProfileIssueKind {
+    /// The field is absent; the addon's updater will ask for it
+    Missing,
+    /// The field is present but unusable, e.g. cut off mid-line
+    Malformed,
+    /// The whole file is empty
+    Empty,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileIssue {
+    pub field: String,
+    pub kind: ProfileIssueKind,
+    /// 1-based line the issue was found on
+    pub line: Option<usize>,
+}
+
+/// Result of checking the profile credentials are written into
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileValidation {
+    pub profile_path: Option<PathBuf>,
+    pub issues: Vec<ProfileIssue>,
+    /// The file is damaged (as opposed to merely lacking credentials)
+    pub corrupt: bool,
+    /// A `.bak` copy that validates cleanly and can replace a corrupt profile
+    pub backup_path: Option<PathBuf>,
+}
+
 pub fn is_profile_file_name(file_name: &str) -> bool {
     let name = file_name.to_lowercase();
     XUPDATER_PROFILE_CFG_FILES.contains(&name.as_str())
         || XUPDATER_PROFILE_JSON_FILES.contains(&name.as_str())
 }
 
+/// Whether `relative_path` (`/`-separated, relative to an addon folder) is a
+/// profile [`find_profile_in_folder`] would read: a profile file in the addon
+/// folder itself or in a native x-updater folder directly below it
+pub fn is_profile_entry_path(relative_path: &str) -> bool {
+    let components: Vec<&str> = relative_path
+        .trim_matches('/')
+        .split('/')
+        .filter(|component| !component.is_empty())
+        .collect();
+    match components.as_slice() {
+        [file_name] => is_profile_file_name(file_name),
+        [dir, file_name] => {
+            XUPDATER_NATIVE_DIR_NAMES.contains(&dir.to_lowercase().as_str())
+                && is_profile_file_name(file_name)
+        }
+        _ => false,
+    }
+}
+
 pub fn tag_host_as_update_url(host: &str) -> String {
     format!("{}{}", XUPDATER_URL_PREFIX, normalize_host(Some(host)))
 }
@@ -133,7 +209,318 @@ pub fn write_credentials_in_folder(folder: &Path, login: &str, license_key: &str
         .with_context(|| format!("Failed to create folder '{}'", folder.display()))?;
 
     let profile_path = resolve_native_credentials_path(folder)?;
-    write_cfg_credentials(&profile_path, trimmed_login, trimmed_license_key)
+    write_cfg_credentials(&profile_path, trimmed_login, trimmed_license_key)?;
+    record_credentials_file(&profile_path);
+    Ok(())
+}
+
+/// Check the profile credentials would be written into for missing or damaged fields
+pub fn validate_profile_in_folder(folder: &Path) -> ProfileValidation {
+    let Some(profile_path) = find_preferred_cfg_path(folder) else {
+        return ProfileValidation {
+            profile_path: None,
+            issues: vec![ProfileIssue {
+                field: "profile".to_string(),
+                kind: ProfileIssueKind::Missing,
+                line: None,
+            }],
+            corrupt: false,
+            backup_path: None,
+        };
+    };
+
+    let issues = match fs::read(&profile_path) {
+        Ok(bytes) => validate_cfg_bytes(&bytes),
+        Err(_) => vec![ProfileIssue {
+            field: "profile".to_string(),
+            kind: ProfileIssueKind::Malformed,
+            line: None,
+        }],
+    };
+    let backup_path = backup_path_for(&profile_path);
+    let backup_path = fs::read(&backup_path)
+        .ok()
+        .filter(|bytes| !is_corrupt(&validate_cfg_bytes(bytes)))
+        .map(|_| backup_path);
+
+    ProfileValidation {
+        corrupt: is_corrupt(&issues),
+        profile_path: Some(profile_path),
+        issues,
+        backup_path,
+    }
+}
+
+/// Replace a corrupt profile with its `.bak` copy. Returns the restored path, or
+/// `None` when the profile is fine or there is no usable backup.
+pub fn recover_profile_from_backup(folder: &Path) -> Result<Option<PathBuf>> {
+    let validation = validate_profile_in_folder(folder);
+    let (Some(profile_path), Some(backup_path)) = (validation.profile_path, validation.backup_path)
+    else {
+        return Ok(None);
+    };
+    if !validation.corrupt {
+        return Ok(None);
+    }
+
+    let backup = fs::read(&backup_path)
+        .with_context(|| format!("Failed to read '{}'", backup_path.display()))?;
+    settings_store::write_atomic(&profile_path, &backup)
+        .with_context(|| format!("Failed to restore '{}'", profile_path.display()))?;
+    crate::logger::log_info(
+        &format!(
+            "Restored corrupt updater profile '{}' from backup",
+            profile_path.display()
+        ),
+        Some("x_updater"),
+    );
+    Ok(Some(profile_path))
+}
+
+fn is_corrupt(issues: &[ProfileIssue]) -> bool {
+    issues
+        .iter()
+        .any(|issue| issue.kind != ProfileIssueKind::Missing)
+}
+
+fn backup_path_for(profile_path: &Path) -> PathBuf {
+    let mut name = profile_path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// Only damage a crash or partial write can cause is reported as malformed;
+/// vendor files routinely contain section headers and other free-form lines.
+fn validate_cfg_bytes(bytes: &[u8]) -> Vec<ProfileIssue> {
+    let issue = |field: &str, kind, line| ProfileIssue {
+        field: field.to_string(),
+        kind,
+        line,
+    };
+
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) if !text.contains('\0') => text,
+        _ => return vec![issue("profile", ProfileIssueKind::Malformed, None)],
+    };
+    if text.trim().is_empty() {
+        return vec![issue("profile", ProfileIssueKind::Empty, None)];
+    }
+
+    let mut issues = Vec::new();
+    let mut has_host = false;
+    let mut has_login = false;
+    let mut has_key = false;
+    let line_count = text.lines().count();
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        let Some((key_raw, value_raw, _sep)) = split_cfg_line(line) else {
+            // A final line without separator or newline was cut off mid-write
+            if index + 1 == line_count && !text.ends_with('\n') {
+                issues.push(issue(
+                    "profile",
+                    ProfileIssueKind::Malformed,
+                    Some(index + 1),
+                ));
+            }
+            continue;
+        };
+
+        let key = key_raw.trim().to_lowercase();
+        let field = if CFG_HOST_KEYS.contains(&key.as_str()) || key == "module" {
+            has_host = true;
+            "host"
+        } else if CFG_LOGIN_KEYS.contains(&key.as_str()) {
+            has_login = true;
+            "login"
+        } else if CFG_LICENSE_KEYS.contains(&key.as_str()) {
+            has_key = true;
+            "key"
+        } else {
+            continue;
+        };
+
+        let value = parse_cfg_value(value_raw);
+        let malformed = value.is_empty() || (field == "host" && !is_valid_host_value(&value));
+        if malformed {
+            issues.push(issue(field, ProfileIssueKind::Malformed, Some(index + 1)));
+        }
+    }
+
+    for (present, field) in [(has_host, "host"), (has_login, "login"), (has_key, "key")] {
+        if !present {
+            issues.push(issue(field, ProfileIssueKind::Missing, None));
+        }
+    }
+    issues
+}
+
+fn is_valid_host_value(value: &str) -> bool {
+    let candidate = if value.contains("://") {
+        value.to_string()
+    } else {
+        format!("https://{}", value)
+    };
+    Url::parse(&candidate)
+        .map(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some())
+        .unwrap_or(false)
+}
+
+/// Write `content` to a temp file, check it parses back into a complete profile
+/// with the new credentials, then rename it over `path`. The previous file is
+/// kept as `.bak` when it was intact, so a damaged profile can be restored.
+fn replace_cfg_atomically(
+    path: &Path,
+    content: &str,
+    login: &str,
+    license_key: &str,
+) -> Result<()> {
+    let mut temp_name = path.as_os_str().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+
+    let original = fs::read(path).ok();
+    let original_corrupt = original
+        .as_deref()
+        .is_some_and(|bytes| is_corrupt(&validate_cfg_bytes(bytes)));
+
+    let result = (|| -> Result<()> {
+        let mut file = fs::File::create(&temp_path)
+            .with_context(|| format!("Failed to create '{}'", temp_path.display()))?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        let written = fs::read_to_string(&temp_path)?;
+        let profile = parse_profile_cfg_text(&written);
+        // Damage carried over verbatim from the original doesn't block the write
+        let complete = profile.login.as_deref() == Some(login)
+            && profile.license_key.as_deref() == Some(license_key)
+            && (original_corrupt || !is_corrupt(&validate_cfg_bytes(written.as_bytes())));
+        if !complete {
+            return Err(anyhow!(
+                "Updated profile '{}' did not validate; original left untouched",
+                path.display()
+            ));
+        }
+
+        if let Some(original) = original.as_deref().filter(|_| !original_corrupt) {
+            let backup_path = backup_path_for(path);
+            settings_store::write_atomic(&backup_path, original)
+                .with_context(|| format!("Failed to write '{}'", backup_path.display()))?;
+        }
+
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to write '{}'", path.display()))?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Profiles that credentials were written into, kept so they can be listed and erased
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CredentialLedger {
+    #[serde(default)]
+    files: Vec<PathBuf>,
+}
+
+fn load_credential_ledger() -> CredentialLedger {
+    fs::read_to_string(app_dirs::get_credential_ledger_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_credential_ledger(ledger: &CredentialLedger) -> Result<()> {
+    let path = app_dirs::get_credential_ledger_path();
+    if ledger.files.is_empty() {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        };
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(ledger)?)
+        .with_context(|| format!("Failed to write '{}'", path.display()))
+}
+
+fn record_credentials_file(profile_path: &Path) {
+    let mut ledger = load_credential_ledger();
+    if ledger.files.iter().any(|file| file == profile_path) {
+        return;
+    }
+    ledger.files.push(profile_path.to_path_buf());
+    if let Err(e) = save_credential_ledger(&ledger) {
+        crate::logger::log_error(
+            &format!("Failed to record credentials location: {}", e),
+            Some("x_updater"),
+        );
+    }
+}
+
+/// Profiles credentials were written into that still hold a login or key
+pub fn recorded_credential_files() -> Vec<PathBuf> {
+    load_credential_ledger()
+        .files
+        .into_iter()
+        .filter(|path| cfg_has_credentials(path))
+        .collect()
+}
+
+/// Drop `paths` from the list of profiles holding credentials
+pub fn forget_credential_files(paths: &[PathBuf]) -> Result<()> {
+    let mut ledger = load_credential_ledger();
+    ledger.files.retain(|file| !paths.contains(file));
+    save_credential_ledger(&ledger)
+}
+
+fn cfg_has_credentials(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|text| text.lines().any(is_cfg_credential_line))
+        .unwrap_or(false)
+}
+
+fn is_cfg_credential_line(line: &str) -> bool {
+    let Some((key, _value, _sep)) = split_cfg_line(line.trim()) else {
+        return false;
+    };
+    let key = key.trim().to_lowercase();
+    CFG_LOGIN_KEYS.contains(&key.as_str()) || CFG_LICENSE_KEYS.contains(&key.as_str())
+}
+
+/// Remove the login and license key lines from a profile, keeping everything else
+pub fn clear_credentials_in_file(path: &Path) -> Result<bool> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read profile '{}'", path.display()))
+        }
+    };
+
+    let kept: Vec<&str> = text
+        .lines()
+        .filter(|line| !is_cfg_credential_line(line))
+        .collect();
+    if kept.len() == text.lines().count() {
+        return Ok(false);
+    }
+
+    let mut out = kept.join("\n");
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    fs::write(path, out).with_context(|| format!("Failed to write '{}'", path.display()))?;
+    Ok(true)
 }
 
 fn resolve_native_credentials_path(folder: &Path) -> Result<PathBuf> {
@@ -318,25 +705,6 @@ fn is_cfg_file_path(path: &Path) -> bool {
 }
 
 fn write_cfg_credentials(path: &Path, login: &str, license_key: &str) -> Result<()> {
-    const LOGIN_KEYS: [&str; 4] = ["login", "username", "user", "email"];
-    const LICENSE_KEYS: [&str; 6] = [
-        "licensekey",
-        "license_key",
-        "key",
-        "license",
-        "password",
-        "token",
-    ];
-    const HOST_KEYS: [&str; 7] = [
-        "host",
-        "server",
-        "update_host",
-        "updatehost",
-        "base_url",
-        "baseurl",
-        "url",
-    ];
-
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create '{}'", parent.display()))?;
@@ -372,7 +740,7 @@ fn write_cfg_credentials(path: &Path, login: &str, license_key: &str) -> Result<
 
         if let Some((key_raw, _value_raw, sep)) = split_cfg_line(raw_line) {
             let key = key_raw.trim().to_lowercase();
-            if LOGIN_KEYS.contains(&key.as_str()) {
+            if CFG_LOGIN_KEYS.contains(&key.as_str()) {
                 output_lines.push(format!(
                     "{}{}{}",
                     key_raw.trim(),
@@ -382,7 +750,7 @@ fn write_cfg_credentials(path: &Path, login: &str, license_key: &str) -> Result<
                 login_written = true;
                 continue;
             }
-            if LICENSE_KEYS.contains(&key.as_str()) {
+            if CFG_LICENSE_KEYS.contains(&key.as_str()) {
                 output_lines.push(format!(
                     "{}{}{}",
                     key_raw.trim(),
@@ -392,7 +760,7 @@ fn write_cfg_credentials(path: &Path, login: &str, license_key: &str) -> Result<
                 key_written = true;
                 continue;
             }
-            if HOST_KEYS.contains(&key.as_str()) {
+            if CFG_HOST_KEYS.contains(&key.as_str()) {
                 host_found = true;
             }
             if key == "configversion" {
@@ -437,8 +805,7 @@ fn write_cfg_credentials(path: &Path, login: &str, license_key: &str) -> Result<
         out.push('\n');
     }
 
-    fs::write(path, out).with_context(|| format!("Failed to write '{}'", path.display()))?;
-    Ok(())
+    replace_cfg_atomically(path, &out, login, license_key)
 }
 
 fn encode_cfg_value(value: &str, sep: char) -> String {
@@ -554,8 +921,13 @@ fn parse_profile_json(path: &Path) -> Option<XUpdaterProfile> {
     })
 }
 
-fn parse_profile_cfg(path: &Path) -> Option<XUpdaterProfile> {
+/// Parse a `key|value` or `key=value` updater config, e.g. skunkcrafts_updater.cfg
+pub fn parse_profile_cfg(path: &Path) -> Option<XUpdaterProfile> {
     let text = fs::read_to_string(path).ok()?;
+    Some(parse_profile_cfg_text(&text))
+}
+
+fn parse_profile_cfg_text(text: &str) -> XUpdaterProfile {
     let mut host: Option<String> = None;
     let mut login: Option<String> = None;
     let mut license_key: Option<String> = None;
@@ -604,14 +976,14 @@ fn parse_profile_cfg(path: &Path) -> Option<XUpdaterProfile> {
         }
     }
 
-    Some(XUpdaterProfile {
+    XUpdaterProfile {
         host: normalize_host(host.as_deref()),
         login,
         license_key,
         package_version,
         version_label,
         ignore_list: dedup_ignore_list(ignore_list),
-    })
+    }
 }
 
 fn parse_cfg_value(raw: &str) -> String {
@@ -786,3 +1158,142 @@ fn normalize_host(raw: Option<&str>) -> String {
     }
     DEFAULT_XUPDATER_HOST.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_entry_paths_match_root_and_native_dir_profiles() {
+        assert!(is_profile_entry_path("x-updater.cnf"));
+        assert!(is_profile_entry_path("X-Updater/x-updater.cnf"));
+        assert!(is_profile_entry_path("xupdater/.x-updater.json"));
+        assert!(!is_profile_entry_path("plugins/x-updater.cnf"));
+        assert!(!is_profile_entry_path("x-updater/nested/x-updater.cnf"));
+        assert!(!is_profile_entry_path("readme.txt"));
+    }
+
+    #[test]
+    fn credential_write_keeps_key_casing_and_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("x-updater.cnf");
+        let original = "#ClientApp properties\n\
+                        Host=https\\://update.x-plane.org\n\
+                        LOGIN=old@example.com\n\
+                        LicenseKey=OLD-KEY\n\
+                        productId=1234\n\
+                        customFlag=true\n";
+        fs::write(&path, original).unwrap();
+
+        write_cfg_credentials(&path, "pilot@example.com", "ABC\\:123").unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.contains("LOGIN=pilot@example.com\n"));
+        assert!(written.contains("LicenseKey=ABC\\\\\\:123\n"));
+        assert!(written.contains("productId=1234\n"));
+        assert!(written.contains("customFlag=true\n"));
+        assert!(!written.contains("\nlogin="));
+        assert!(written.ends_with("configVersion=3\n"));
+
+        let profile = parse_profile_cfg_text(&written);
+        assert_eq!(profile.login.as_deref(), Some("pilot@example.com"));
+        assert_eq!(profile.license_key.as_deref(), Some("ABC\\:123"));
+        assert_eq!(profile.host, "https://update.x-plane.org");
+        assert_eq!(
+            fs::read_to_string(backup_path_for(&path)).unwrap(),
+            original
+        );
+        assert!(!dir.path().join("x-updater.cnf.tmp").exists());
+    }
+
+    #[test]
+    fn credential_write_keeps_pipe_separator_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("x_updater.cfg");
+        fs::write(
+            &path,
+            "server|update.example.com\nUser|old\nTOKEN|old\nconfigVersion|3\nextra|keep me\n",
+        )
+        .unwrap();
+
+        write_cfg_credentials(&path, "pilot", "NEW-KEY").unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            written,
+            "server|update.example.com\nUser|pilot\nTOKEN|NEW-KEY\nconfigVersion|3\nextra|keep me\n"
+        );
+        let profile = parse_profile_cfg_text(&written);
+        assert_eq!(profile.host, "https://update.example.com");
+        assert!(validate_cfg_bytes(written.as_bytes()).is_empty());
+    }
+
+    #[test]
+    fn credential_write_creates_complete_profile_without_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("x-updater.cnf");
+
+        write_cfg_credentials(&path, "pilot", "KEY").unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+        let profile = parse_profile_cfg_text(&written);
+        assert!(profile.has_credentials());
+        assert!(validate_cfg_bytes(written.as_bytes()).is_empty());
+        assert!(!backup_path_for(&path).exists());
+    }
+
+    #[test]
+    fn truncated_profile_is_reported_and_restored_from_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let native = dir.path().join("x-updater");
+        fs::create_dir_all(&native).unwrap();
+        let path = native.join("x-updater.cnf");
+        let intact = "host=https\\://update.x-plane.org\nlogin=pilot\nkey=KEY\n";
+        fs::write(backup_path_for(&path), intact).unwrap();
+        fs::write(&path, "host=https\\://update.x-plane.org\nlogin=pilot\nke").unwrap();
+
+        let validation = validate_profile_in_folder(dir.path());
+        assert!(validation.corrupt);
+        assert_eq!(validation.profile_path.as_deref(), Some(path.as_path()));
+        assert_eq!(
+            validation.issues,
+            vec![
+                ProfileIssue {
+                    field: "profile".to_string(),
+                    kind: ProfileIssueKind::Malformed,
+                    line: Some(3),
+                },
+                ProfileIssue {
+                    field: "key".to_string(),
+                    kind: ProfileIssueKind::Missing,
+                    line: None,
+                },
+            ]
+        );
+        assert!(validation.backup_path.is_some());
+
+        assert_eq!(
+            recover_profile_from_backup(dir.path()).unwrap(),
+            Some(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), intact);
+        assert!(!validate_profile_in_folder(dir.path()).corrupt);
+        assert_eq!(recover_profile_from_backup(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn vendor_free_form_lines_are_not_corruption() {
+        let issues = validate_cfg_bytes(b"[updater]\nhost=update.x-plane.org\nsome note\n");
+        assert!(!is_corrupt(&issues));
+        assert_eq!(issues.len(), 2);
+        assert!(issues
+            .iter()
+            .all(|issue| issue.kind == ProfileIssueKind::Missing));
+
+        assert!(is_corrupt(&validate_cfg_bytes(b"")));
+        assert!(is_corrupt(&validate_cfg_bytes(b"login=\0\0\0")));
+        assert!(is_corrupt(&validate_cfg_bytes(
+            b"host=ftp://x\nlogin=a\nkey=b\n"
+        )));
+    }
+}
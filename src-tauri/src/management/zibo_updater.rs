@@ -17,7 +17,9 @@ use tokio::task::JoinSet;
 use uuid::Uuid;
 use zip::ZipArchive;
 
+use crate::addon_backups;
 use crate::addon_updater::{AddonUpdateProgressCallback, AddonUpdateProgressEvent};
+use crate::http_client;
 use crate::logger;
 use crate::skunk_updater::{
     SkunkUpdateOptions as AddonUpdateOptions, SkunkUpdatePlan as AddonUpdatePlan,
@@ -887,9 +889,8 @@ async fn send_drive_download_request(
 }
 
 fn build_http_client(timeout_secs: u64) -> Result<reqwest::Client> {
-    reqwest::Client::builder()
-        .timeout(Duration::from_secs(timeout_secs))
-        .user_agent("XFast-Manager/ZiboUpdater")
+    http_client::client_builder("XFast-Manager/ZiboUpdater")
+        .timeout(http_client::timeout(Duration::from_secs(timeout_secs)))
         .build()
         .context("Failed to create HTTP client")
 }
@@ -2188,8 +2189,11 @@ pub async fn build_update_plan(
         replace_files: Vec::new(),
         delete_files: Vec::new(),
         skip_files: Vec::new(),
+        skipped: Vec::new(),
         warnings: context.warnings,
         has_beta_config: false,
+        file_details: Vec::new(),
+        preflight: None,
     };
 
     emit_progress_event(
@@ -2236,6 +2240,8 @@ pub async fn execute_update(
             deleted_files: 0,
             skipped_files: 0,
             rollback_used: false,
+            files_retried: 0,
+            total_retry_count: 0,
         });
     }
 
@@ -2271,6 +2277,32 @@ pub async fn execute_update(
     let total_download_bytes = context.estimated_download_bytes;
     let full_package_download_mode = zibo_full_package_download_mode(&options);
 
+    // A Zibo package replaces the aircraft as a whole, so a kept version holds
+    // every file. A failed update restores the aircraft itself and drops it.
+    let keep_backups = options.keep_backup_versions.filter(|keep| *keep > 0);
+    let backup = match keep_backups {
+        Some(_) => {
+            let item_type = item_type.to_string();
+            let folder_name = folder_name.to_string();
+            let backup_target = target_path.clone();
+            let from_version = local_version.clone();
+            Some(
+                tokio::task::spawn_blocking(move || {
+                    addon_backups::begin_whole_folder(
+                        &item_type,
+                        &folder_name,
+                        &backup_target,
+                        from_version,
+                    )
+                })
+                .await
+                .map_err(|e| anyhow!("Backup task failed: {}", e))?
+                .map_err(|e| anyhow!("Failed to back up files before the update: {}", e))?,
+            )
+        }
+        None => None,
+    };
+
     emit_progress_event(
         &progress_callback,
         item_type,
@@ -2349,6 +2381,18 @@ pub async fn execute_update(
         target_path.display()
     ));
 
+    if let (Some(backup), Some(keep)) = (backup, keep_backups) {
+        let to_version = remote_version.clone();
+        match tokio::task::spawn_blocking(move || backup.finish(to_version, keep)).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => log_info(format!(
+                "Failed to keep the backup of {}: {}",
+                folder_name, e
+            )),
+            Err(e) => log_info(format!("Backup task failed: {}", e)),
+        }
+    }
+
     let mut message = format!(
         "Updated Zibo to {}",
         context.latest_release.version_string()
@@ -2381,6 +2425,8 @@ pub async fn execute_update(
         deleted_files: 0,
         skipped_files: 0,
         rollback_used: false,
+        files_retried: 0,
+        total_retry_count: 0,
     })
 }
 
@@ -2529,9 +2575,8 @@ fn total_piece_bytes(pieces: &[DriveDownloadPiece]) -> u64 {
 }
 
 fn build_chunked_download_client(timeout_secs: u64) -> Result<reqwest::Client> {
-    reqwest::Client::builder()
-        .timeout(Duration::from_secs(timeout_secs))
-        .user_agent("XFast-Manager/ZiboUpdater")
+    http_client::client_builder("XFast-Manager/ZiboUpdater")
+        .timeout(http_client::timeout(Duration::from_secs(timeout_secs)))
         .http1_only()
         .build()
         .context("Failed to create chunked download HTTP client")
@@ -3213,14 +3258,14 @@ where
         return Ok(None);
     }
 
+    let staging_root = crate::staging::staging_root();
     let temp_dir =
-        std::env::temp_dir().join(format!("xfastmanager_zibo_backup_{}", Uuid::new_v4()));
-    fs::create_dir_all(&temp_dir).with_context(|| {
-        format!(
-            "Failed to create Zibo backup directory '{}'",
-            temp_dir.display()
-        )
-    })?;
+        crate::staging::create_dir_in(&staging_root, "zibo_backup").with_context(|| {
+            format!(
+                "Failed to create Zibo backup directory in '{}'",
+                staging_root.display()
+            )
+        })?;
 
     let mut backup = ZiboBackupState {
         temp_dir: temp_dir.clone(),
@@ -4120,6 +4165,7 @@ mod tests {
             chunked_download_enabled: Some(false),
             threads_per_task: Some(8),
             total_threads: None,
+            keep_backup_versions: None,
         };
         assert!(matches!(
             zibo_full_package_download_mode(&disabled),
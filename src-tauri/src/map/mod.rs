@@ -9,6 +9,8 @@ use std::sync::{Arc, LazyLock, Mutex, RwLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter};
 
+use crate::http_client;
+
 const METERS_TO_FEET: f64 = 3.28084;
 const MPS_TO_KNOTS: f64 = 1.94384;
 
@@ -35,13 +37,14 @@ const MAP_DATAREFS: [&str; 8] = [
     DF_VERTICAL_SPEED,
 ];
 
-static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
-    reqwest::Client::builder()
-        .user_agent("XFast Manager Map")
-        .timeout(Duration::from_secs(15))
+static HTTP_CLIENT: http_client::CachedClient = http_client::CachedClient::new(build_http_client);
+
+fn build_http_client() -> reqwest::Client {
+    http_client::client_builder("XFast Manager Map")
+        .timeout(http_client::timeout(Duration::from_secs(15)))
         .build()
         .expect("failed to create reqwest client")
-});
+}
 
 static MAP_INDEX_STATE: LazyLock<RwLock<MapIndexState>> =
     LazyLock::new(|| RwLock::new(MapIndexState::default()));
@@ -697,6 +700,15 @@ fn build_data_index_sync(xplane_path: &str) -> Result<ParsedIndexData, String> {
     })
 }
 
+/// Airports defined in a single apt.dat. Airports without a usable location are
+/// skipped, as in the map index.
+pub fn parse_apt_airports(path: &Path) -> Result<Vec<MapAirport>, String> {
+    let mut airports = HashMap::new();
+    let mut airport_sources = HashMap::new();
+    parse_apt_file(path, false, &mut airports, &mut airport_sources)?;
+    Ok(airports.into_values().collect())
+}
+
 fn parse_apt_file(
     path: &Path,
     is_custom: bool,
@@ -3019,6 +3031,7 @@ async fn ensure_index_loaded(xplane_path: &str) -> Result<(), String> {
 
 async fn fetch_text(url: &str) -> Result<String, String> {
     let response = HTTP_CLIENT
+        .client()
         .get(url)
         .send()
         .await
@@ -3040,6 +3053,7 @@ async fn fetch_text(url: &str) -> Result<String, String> {
 
 async fn fetch_json(url: &str) -> Result<Value, String> {
     let response = HTTP_CLIENT
+        .client()
         .get(url)
         .send()
         .await
@@ -3075,7 +3089,7 @@ fn extract_text_payload(input: &str) -> String {
 
 async fn resolve_dataref_ids(port: u16) -> Result<HashMap<String, u32>, String> {
     let url = format!("http://localhost:{}/api/v3/datarefs", port);
-    let mut req = HTTP_CLIENT.get(url);
+    let mut req = HTTP_CLIENT.client().get(url);
     for name in MAP_DATAREFS {
         req = req.query(&[("filter[name]", name)]);
     }
@@ -3116,6 +3130,7 @@ async fn fetch_dataref_value(port: u16, dataref_id: u32) -> Result<Option<f64>,
         port, dataref_id
     );
     let response = HTTP_CLIENT
+        .client()
         .get(url)
         .send()
         .await
@@ -3725,6 +3740,7 @@ pub async fn xplane_is_api_available(port: Option<u16>) -> Result<bool, String>
     let p = port.unwrap_or(8086);
     let url = format!("http://localhost:{}/api/v3/datarefs?limit=1", p);
     match HTTP_CLIENT
+        .client()
         .get(&url)
         .timeout(Duration::from_secs(2))
         .send()
@@ -3739,6 +3755,7 @@ pub async fn xplane_is_api_available(port: Option<u16>) -> Result<bool, String>
 async fn resolve_single_dataref_id(port: u16, name: &str) -> Result<u32, String> {
     let url = format!("http://localhost:{}/api/v3/datarefs", port);
     let response = HTTP_CLIENT
+        .client()
         .get(&url)
         .query(&[
             ("filter[name]", name),
@@ -3773,6 +3790,7 @@ async fn resolve_single_dataref_id(port: u16, name: &str) -> Result<u32, String>
 async fn resolve_command_id(port: u16, name: &str) -> Result<u32, String> {
     let url = format!("http://localhost:{}/api/v3/commands", port);
     let response = HTTP_CLIENT
+        .client()
         .get(&url)
         .query(&[
             ("filter[name]", name),
@@ -3819,6 +3837,7 @@ pub async fn xplane_get_dataref(
     }
 
     let response = HTTP_CLIENT
+        .client()
         .get(&url)
         .send()
         .await
@@ -3854,6 +3873,7 @@ pub async fn xplane_set_dataref(
 
     let body = serde_json::json!({ "data": value });
     let response = HTTP_CLIENT
+        .client()
         .patch(&url)
         .json(&body)
         .send()
@@ -3881,6 +3901,7 @@ pub async fn xplane_activate_command(
     let body = serde_json::json!({ "duration": duration.unwrap_or(0.0) });
 
     let response = HTTP_CLIENT
+        .client()
         .post(&url)
         .json(&body)
         .send()
@@ -0,0 +1,427 @@
+//! Airport search across apt.dat files
+//!
+//! Searches the Global Airports apt.dat and the apt.dat of every enabled custom
+//! scenery package. Parsing the global file takes seconds, so each apt.dat is
+//! parsed once into the `airport_cache_*` tables and only re-parsed when its
+//! modification time changes. When several packages define the same airport,
+//! the result names the one X-Plane loads first (lowest scenery_packs.ini
+//! sort order).
+//!
+//! Files are read with the map's apt.dat parser, so locations come from the
+//! `1302 datum_lat`/`datum_lon` rows or, failing that, runway and helipad
+//! positions.
+
+use crate::database::entities::{airport_cache_entries, airport_cache_sources};
+use crate::database::SceneryQueries;
+use crate::error::{ApiError, ApiResult};
+use crate::logger;
+use crate::map::{self, MapAirport};
+use crate::models::{AirportSearchResult, GLOBAL_AIRPORTS_ENTRY_NAME};
+use crate::path_utils;
+use crate::scenery_packs_manager::SceneryPacksManager;
+use rayon::prelude::*;
+use sea_orm::{
+    ActiveValue, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect,
+    Set, TransactionTrait,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Rows per INSERT when caching parsed airports
+const INSERT_BATCH_SIZE: usize = 150;
+
+/// Upper bound for the `limit` argument of a search
+pub const MAX_SEARCH_LIMIT: usize = 500;
+
+/// An apt.dat that takes part in the search
+#[derive(Debug, Clone)]
+pub struct AptDatSource {
+    pub apt_dat_path: PathBuf,
+    /// Scenery package folder name, or `*GLOBAL_AIRPORTS*`
+    pub package: String,
+    /// Load order; lower wins
+    pub rank: u32,
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// apt.dat modification time in Unix seconds, `None` if the file is missing
+fn apt_dat_mtime(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(
+        modified
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64,
+    )
+}
+
+/// The apt.dat files X-Plane currently loads, ranked by scenery_packs.ini order
+pub async fn collect_sources(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+) -> ApiResult<Vec<AptDatSource>> {
+    let index =
+        SceneryQueries::load_all(conn, &path_utils::normalize_xplane_key(xplane_path)).await?;
+    let packages: Vec<_> = index.packages.values().collect();
+
+    let global_state = SceneryPacksManager::new(xplane_path, conn.clone())
+        .get_global_airports_state_for_packages(&packages)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to load Global Airports state: {}", e)))?;
+
+    let custom_scenery = xplane_path.join("Custom Scenery");
+    let mut sources: Vec<AptDatSource> = packages
+        .iter()
+        .filter(|info| info.enabled && info.has_apt_dat)
+        .map(|info| {
+            let package_path = info
+                .actual_path
+                .as_ref()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| custom_scenery.join(&info.folder_name));
+            AptDatSource {
+                apt_dat_path: package_path.join("Earth nav data").join("apt.dat"),
+                package: info.folder_name.clone(),
+                rank: info.sort_order,
+            }
+        })
+        .collect();
+
+    sources.push(AptDatSource {
+        apt_dat_path: xplane_path
+            .join("Global Scenery")
+            .join("Global Airports")
+            .join("Earth nav data")
+            .join("apt.dat"),
+        package: GLOBAL_AIRPORTS_ENTRY_NAME.to_string(),
+        rank: global_state.sort_order,
+    });
+
+    Ok(sources)
+}
+
+/// Make sure every source is cached at its current mtime. Returns the cache
+/// source id of each apt.dat that exists, keyed by path.
+async fn refresh_cache(
+    conn: &DatabaseConnection,
+    sources: &[AptDatSource],
+) -> ApiResult<HashMap<String, i64>> {
+    let paths: Vec<PathBuf> = sources.iter().map(|s| s.apt_dat_path.clone()).collect();
+    let mtimes: Vec<(String, i64)> = tokio::task::spawn_blocking(move || {
+        paths
+            .iter()
+            .filter_map(|path| Some((path.to_string_lossy().to_string(), apt_dat_mtime(path)?)))
+            .collect()
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))?;
+
+    let cached: HashMap<String, airport_cache_sources::Model> =
+        airport_cache_sources::Entity::find()
+            .filter(
+                airport_cache_sources::Column::AptDatPath
+                    .is_in(mtimes.iter().map(|(path, _)| path.clone())),
+            )
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|row| (row.apt_dat_path.clone(), row))
+            .collect();
+
+    let mut source_ids = HashMap::new();
+    let mut stale: Vec<(String, i64)> = Vec::new();
+    for (path, mtime) in mtimes {
+        match cached.get(&path) {
+            Some(row) if row.mtime == mtime => {
+                source_ids.insert(path, row.id);
+            }
+            _ => stale.push((path, mtime)),
+        }
+    }
+    if stale.is_empty() {
+        return Ok(source_ids);
+    }
+
+    logger::log_info(
+        &format!("Parsing {} changed apt.dat file(s)", stale.len()),
+        Some("airport_lookup"),
+    );
+    let parsed: Vec<(String, i64, Vec<MapAirport>)> = tokio::task::spawn_blocking(move || {
+        stale
+            .into_par_iter()
+            .filter_map(
+                |(path, mtime)| match map::parse_apt_airports(Path::new(&path)) {
+                    Ok(airports) => Some((path, mtime, airports)),
+                    Err(e) => {
+                        logger::log_error(
+                            &format!("Failed to parse {}: {}", path, e),
+                            Some("airport_lookup"),
+                        );
+                        None
+                    }
+                },
+            )
+            .collect()
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))?;
+
+    let txn = conn.begin().await?;
+    for (path, mtime, airports) in parsed {
+        if let Some(old) = cached.get(&path) {
+            airport_cache_entries::Entity::delete_many()
+                .filter(airport_cache_entries::Column::SourceId.eq(old.id))
+                .exec(&txn)
+                .await?;
+            airport_cache_sources::Entity::delete_by_id(old.id)
+                .exec(&txn)
+                .await?;
+        }
+
+        let source_id = airport_cache_sources::Entity::insert(airport_cache_sources::ActiveModel {
+            id: ActiveValue::NotSet,
+            apt_dat_path: Set(path.clone()),
+            mtime: Set(mtime),
+            indexed_at: Set(unix_now()),
+        })
+        .exec(&txn)
+        .await?
+        .last_insert_id;
+
+        for batch in airports.chunks(INSERT_BATCH_SIZE) {
+            airport_cache_entries::Entity::insert_many(batch.iter().map(|airport| {
+                airport_cache_entries::ActiveModel {
+                    id: ActiveValue::NotSet,
+                    source_id: Set(source_id),
+                    icao: Set(airport.icao.clone()),
+                    name: Set(airport.name.clone()),
+                    latitude: Set(airport.lat),
+                    longitude: Set(airport.lon),
+                }
+            }))
+            .exec(&txn)
+            .await?;
+        }
+        source_ids.insert(path, source_id);
+    }
+    txn.commit().await?;
+
+    Ok(source_ids)
+}
+
+/// Search the given apt.dat files for airports whose ICAO code or name contains
+/// `query` (case-insensitive). Exact ICAO matches come first, then ICAO prefix
+/// matches, then name matches.
+pub async fn search_sources(
+    conn: &DatabaseConnection,
+    sources: &[AptDatSource],
+    query: &str,
+    limit: usize,
+) -> ApiResult<Vec<AirportSearchResult>> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let limit = limit.clamp(1, MAX_SEARCH_LIMIT);
+
+    let source_ids = refresh_cache(conn, sources).await?;
+    let source_by_id: HashMap<i64, &AptDatSource> = sources
+        .iter()
+        .filter_map(|source| {
+            let id = source_ids.get(&*source.apt_dat_path.to_string_lossy())?;
+            Some((*id, source))
+        })
+        .collect();
+    if source_by_id.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let source_filter = airport_cache_entries::Column::SourceId.is_in(source_by_id.keys().copied());
+    let matched: Vec<String> = airport_cache_entries::Entity::find()
+        .select_only()
+        .column(airport_cache_entries::Column::Icao)
+        .distinct()
+        .filter(source_filter.clone())
+        .filter(
+            Condition::any()
+                .add(airport_cache_entries::Column::Icao.contains(query))
+                .add(airport_cache_entries::Column::Name.contains(query)),
+        )
+        .into_tuple()
+        .all(conn)
+        .await?;
+    if matched.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Every definition of the matched airports, including ones whose name didn't
+    // match, since the winning definition decides what X-Plane shows
+    let rows = airport_cache_entries::Entity::find()
+        .filter(source_filter)
+        .filter(airport_cache_entries::Column::Icao.is_in(matched))
+        .all(conn)
+        .await?;
+    let mut by_icao: HashMap<String, Vec<(&AptDatSource, airport_cache_entries::Model)>> =
+        HashMap::new();
+    for row in rows {
+        if let Some(source) = source_by_id.get(&row.source_id) {
+            by_icao
+                .entry(row.icao.to_uppercase())
+                .or_default()
+                .push((source, row));
+        }
+    }
+
+    let query_upper = query.to_uppercase();
+    let mut results: Vec<(u8, AirportSearchResult)> = by_icao
+        .into_values()
+        .map(|mut definitions| {
+            definitions.sort_by(|(a, _), (b, _)| {
+                a.rank.cmp(&b.rank).then_with(|| {
+                    // Global Airports loses ties against custom scenery
+                    (a.package == GLOBAL_AIRPORTS_ENTRY_NAME)
+                        .cmp(&(b.package == GLOBAL_AIRPORTS_ENTRY_NAME))
+                })
+            });
+            let other_packages = definitions[1..]
+                .iter()
+                .map(|(source, _)| source.package.clone())
+                .collect();
+            let (source, row) = definitions.swap_remove(0);
+            let icao_upper = row.icao.to_uppercase();
+            let relevance = if icao_upper == query_upper {
+                0
+            } else if icao_upper.starts_with(&query_upper) {
+                1
+            } else {
+                2
+            };
+            (
+                relevance,
+                AirportSearchResult {
+                    icao: row.icao,
+                    name: row.name,
+                    latitude: row.latitude,
+                    longitude: row.longitude,
+                    package: source.package.clone(),
+                    other_packages,
+                },
+            )
+        })
+        .collect();
+
+    results.sort_by(|(rel_a, a), (rel_b, b)| rel_a.cmp(rel_b).then_with(|| a.icao.cmp(&b.icao)));
+    Ok(results
+        .into_iter()
+        .take(limit)
+        .map(|(_, result)| result)
+        .collect())
+}
+
+/// Search airports in everything X-Plane currently loads
+pub async fn search_airports(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+    query: &str,
+    limit: usize,
+) -> ApiResult<Vec<AirportSearchResult>> {
+    let sources = collect_sources(conn, xplane_path).await?;
+    search_sources(conn, &sources, query, limit).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+    use tempfile::tempdir;
+
+    const GLOBAL_APT: &str = "I\n1200 Version\n\
+        1 203 0 0 EGKK London Gatwick\n\
+        100 45.00 1 0 0.25 0 2 1 08R 51.150 -0.210 0 0 3 0 0 0 26L 51.160 -0.160 0 0 3 0 0 0\n\
+        1302 icao_code EGKK\n\
+        1 80 0 0 EGLL London Heathrow\n\
+        1302 datum_lat 51.4775\n\
+        1302 datum_lon -0.461389\n\
+        17 0 0 0 XHEL Some Heliport\n\
+        102 H1 10.5 20.5 0 30 30 1 0 0 0.25 0\n\
+        99\n";
+
+    #[tokio::test]
+    async fn search_prefers_highest_priority_package_and_refreshes_on_change() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+        let temp = tempdir().expect("failed to create tempdir");
+
+        let global = temp.path().join("global_apt.dat");
+        fs::write(&global, GLOBAL_APT).unwrap();
+        let custom = temp.path().join("custom_apt.dat");
+        fs::write(&custom, "I\n1200 Version\n1 203 0 0 EGKK Gatwick HD\n1302 datum_lat 51.15\n1302 datum_lon -0.19\n99\n").unwrap();
+
+        let sources = vec![
+            AptDatSource {
+                apt_dat_path: global.clone(),
+                package: GLOBAL_AIRPORTS_ENTRY_NAME.to_string(),
+                rank: 5,
+            },
+            AptDatSource {
+                apt_dat_path: custom.clone(),
+                package: "EGKK Gatwick HD".to_string(),
+                rank: 1,
+            },
+            AptDatSource {
+                apt_dat_path: temp.path().join("missing.dat"),
+                package: "Missing".to_string(),
+                rank: 0,
+            },
+        ];
+
+        let results = search_sources(&conn, &sources, "egkk", 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].package, "EGKK Gatwick HD");
+        assert_eq!(results[0].name, "Gatwick HD");
+        assert_eq!(results[0].other_packages, vec![GLOBAL_AIRPORTS_ENTRY_NAME]);
+
+        // Matching a lower-priority definition still returns the winning one
+        let results = search_sources(&conn, &sources, "london", 10).await.unwrap();
+        let found: Vec<(&str, &str)> = results
+            .iter()
+            .map(|r| (r.icao.as_str(), r.package.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("EGKK", "EGKK Gatwick HD"),
+                ("EGLL", GLOBAL_AIRPORTS_ENTRY_NAME)
+            ]
+        );
+
+        // A changed apt.dat replaces its cached entries
+        fs::write(
+            &custom,
+            "I\n1200 Version\n1 203 0 0 EGKK Gatwick Redux\n1302 datum_lat 51.15\n1302 datum_lon -0.19\n99\n",
+        )
+        .unwrap();
+        let file = fs::File::options().write(true).open(&custom).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(120))
+            .unwrap();
+        let results = search_sources(&conn, &sources, "redux", 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "Gatwick Redux");
+        assert_eq!(
+            airport_cache_sources::Entity::find()
+                .all(&conn)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,477 @@
+//! scenery_packs.ini changes held back until X-Plane closes
+//!
+//! X-Plane writes scenery_packs.ini back on exit, so an ini written while the
+//! sim runs is lost. With [`SceneryApplyMode::DeferUntilSimCloses`] the scenery
+//! index is updated right away and only the ini write waits: the changes are
+//! kept in the `deferred_scenery_applies` table, one row per install, so they
+//! survive a restart. A poller checks every [`POLL_INTERVAL`] whether the sim
+//! still runs from each install. Once it doesn't, the kept changes are put back
+//! into the index, in case a rescan replaced them meanwhile, the ini is written
+//! from the index and [`DEFERRED_SCENERY_APPLIED_EVENT`] is emitted. A failed
+//! write keeps the changes for the next check. The poller gives up after
+//! [`MAX_WAIT`]; what is still pending is picked up at the next launch or with
+//! the next deferred change.
+
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+};
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::activity;
+use crate::database::entities::deferred_scenery_applies;
+use crate::database::DatabaseState;
+use crate::error::{ApiError, ApiResult};
+use crate::logger;
+use crate::models::{
+    DeferredSceneryApplied, SceneryApplyMode, SceneryEntryUpdate, GLOBAL_AIRPORTS_ENTRY_NAME,
+};
+use crate::scenery_index::SceneryIndexManager;
+use crate::scenery_packs_manager::SceneryPacksManager;
+use crate::xplane_processes;
+
+/// Event emitted with a [`DeferredSceneryApplied`] once a deferred ini write ran
+pub const DEFERRED_SCENERY_APPLIED_EVENT: &str = "deferred-scenery-applied";
+
+/// Time between two checks whether X-Plane still runs
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long one poller waits for X-Plane to close
+const MAX_WAIT: Duration = Duration::from_secs(12 * 60 * 60);
+
+const LOG_CTX: &str = "scenery";
+
+/// Set while a poller is running
+static POLLER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Whether the ini write of `xplane_path` has to wait for X-Plane to close.
+/// Fails for [`SceneryApplyMode::FailIfRunning`] while X-Plane runs from there.
+pub async fn should_defer(mode: SceneryApplyMode, xplane_path: &Path) -> ApiResult<bool> {
+    match mode {
+        SceneryApplyMode::Immediate => Ok(false),
+        SceneryApplyMode::DeferUntilSimCloses => {
+            Ok(xplane_processes::is_running_at(xplane_path).await)
+        }
+        SceneryApplyMode::FailIfRunning => {
+            if xplane_processes::is_running_at(xplane_path).await {
+                return Err(ApiError::conflict(
+                    "Close X-Plane before changing scenery_packs.ini",
+                ));
+            }
+            Ok(false)
+        }
+    }
+}
+
+fn parse_entries(row: &deferred_scenery_applies::Model) -> Vec<SceneryEntryUpdate> {
+    serde_json::from_str(&row.entries).unwrap_or_default()
+}
+
+fn parse_deleted_folders(row: &deferred_scenery_applies::Model) -> Vec<String> {
+    serde_json::from_str(&row.deleted_folders).unwrap_or_default()
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> ApiResult<String> {
+    serde_json::to_string(value).map_err(|e| {
+        ApiError::internal(format!(
+            "Failed to serialize deferred scenery changes: {}",
+            e
+        ))
+    })
+}
+
+/// Keep changes for the ini of `xplane_path` until X-Plane closes, merged into
+/// those already waiting. Later entries for a folder replace earlier ones.
+pub async fn queue(
+    conn: &DatabaseConnection,
+    xplane_path: &str,
+    entries: &[SceneryEntryUpdate],
+    deleted_folder: Option<&str>,
+) -> ApiResult<()> {
+    let existing = deferred_scenery_applies::Entity::find()
+        .filter(deferred_scenery_applies::Column::XplanePath.eq(xplane_path))
+        .one(conn)
+        .await?;
+
+    let (mut merged, mut deleted_folders) = existing
+        .as_ref()
+        .map(|row| (parse_entries(row), parse_deleted_folders(row)))
+        .unwrap_or_default();
+    for entry in entries {
+        merged.retain(|queued| queued.folder_name != entry.folder_name);
+        merged.push(entry.clone());
+    }
+    if let Some(folder) = deleted_folder {
+        merged.retain(|queued| queued.folder_name != folder);
+        if !deleted_folders.iter().any(|deleted| deleted == folder) {
+            deleted_folders.push(folder.to_string());
+        }
+    }
+
+    match existing {
+        Some(row) => {
+            let mut active: deferred_scenery_applies::ActiveModel = row.into();
+            active.entries = Set(to_json(&merged)?);
+            active.deleted_folders = Set(to_json(&deleted_folders)?);
+            active.update(conn).await?;
+        }
+        None => {
+            deferred_scenery_applies::Entity::insert(deferred_scenery_applies::ActiveModel {
+                id: ActiveValue::NotSet,
+                xplane_path: Set(xplane_path.to_string()),
+                entries: Set(to_json(&merged)?),
+                deleted_folders: Set(to_json(&deleted_folders)?),
+                queued_at: Set(now_secs()),
+            })
+            .exec(conn)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Drop the changes waiting for the ini of `xplane_path`, once the ini was
+/// written anyway. Returns whether any were waiting.
+pub async fn clear(conn: &DatabaseConnection, xplane_path: &str) -> ApiResult<bool> {
+    let result = deferred_scenery_applies::Entity::delete_many()
+        .filter(deferred_scenery_applies::Column::XplanePath.eq(xplane_path))
+        .exec(conn)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Put the changes of a pending row back into the index: entry states and
+/// the Global Airports position, and the removal of deleted folders still gone
+async fn replay_changes(
+    conn: &DatabaseConnection,
+    row: &deferred_scenery_applies::Model,
+) -> anyhow::Result<()> {
+    let xplane_path = Path::new(&row.xplane_path);
+    let index_manager = SceneryIndexManager::new(xplane_path, conn.clone());
+    let packs_manager = SceneryPacksManager::new(xplane_path, conn.clone());
+
+    let mut updates = Vec::new();
+    for entry in parse_entries(row) {
+        if entry.folder_name == GLOBAL_AIRPORTS_ENTRY_NAME {
+            packs_manager
+                .set_global_airports_enabled(entry.enabled)
+                .await?;
+            packs_manager
+                .set_global_airports_sort_order(entry.sort_order)
+                .await?;
+        } else {
+            updates.push(entry);
+        }
+    }
+    index_manager.batch_update_entries(&updates).await?;
+
+    let custom_scenery = xplane_path.join("Custom Scenery");
+    for folder in parse_deleted_folders(row) {
+        if std::fs::symlink_metadata(custom_scenery.join(&folder)).is_err() {
+            index_manager.remove_entry(&folder).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Put the changes of a pending row back into the index and write the ini from
+/// it. The row is removed once the ini was written and kept for the next check
+/// otherwise.
+async fn apply_pending(
+    conn: &DatabaseConnection,
+    row: deferred_scenery_applies::Model,
+) -> DeferredSceneryApplied {
+    let entries_updated = parse_entries(&row).len();
+    let deleted_folders = parse_deleted_folders(&row);
+    let packs_manager = SceneryPacksManager::new(Path::new(&row.xplane_path), conn.clone());
+    let result = match replay_changes(conn, &row).await {
+        Ok(()) => packs_manager.apply_from_index().await,
+        Err(e) => Err(e),
+    };
+    let error = result.err().map(|e| e.to_string());
+
+    match &error {
+        None => {
+            logger::log_info(
+                &format!(
+                    "X-Plane closed, applied deferred scenery changes to {}",
+                    row.xplane_path
+                ),
+                Some(LOG_CTX),
+            );
+            activity::log_activity(
+                conn,
+                "config_change",
+                "scenery",
+                "scenery_packs.ini",
+                Some(format!(
+                    "{{\"entriesUpdated\":{},\"deferred\":true}}",
+                    entries_updated
+                )),
+                true,
+            )
+            .await;
+            if let Err(e) = deferred_scenery_applies::Entity::delete_by_id(row.id)
+                .exec(conn)
+                .await
+            {
+                logger::log_error(
+                    &format!("Failed to remove deferred scenery changes: {}", e),
+                    Some(LOG_CTX),
+                );
+            }
+        }
+        Some(e) => logger::log_error(
+            &format!(
+                "Failed to apply deferred scenery changes, retrying at the next check: {}",
+                e
+            ),
+            Some(LOG_CTX),
+        ),
+    }
+
+    DeferredSceneryApplied {
+        xplane_path: row.xplane_path,
+        entries_updated,
+        deleted_folders,
+        queued_at: row.queued_at,
+        error,
+    }
+}
+
+async fn has_pending(conn: &DatabaseConnection) -> bool {
+    deferred_scenery_applies::Entity::find()
+        .one(conn)
+        .await
+        .ok()
+        .flatten()
+        .is_some()
+}
+
+/// Result of one check of the pending rows
+struct PollRound {
+    /// Writes attempted because X-Plane no longer ran, failed ones included
+    attempts: Vec<(i64, DeferredSceneryApplied)>,
+    /// Rows still pending after the check
+    remaining: usize,
+}
+
+/// Apply the pending rows of the installs `is_running` says X-Plane no longer
+/// runs from
+async fn poll_once<F, Fut>(
+    conn: &DatabaseConnection,
+    is_running: F,
+) -> Result<PollRound, sea_orm::DbErr>
+where
+    F: Fn(PathBuf) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let rows = deferred_scenery_applies::Entity::find().all(conn).await?;
+    let mut round = PollRound {
+        attempts: Vec::new(),
+        remaining: 0,
+    };
+    for row in rows {
+        if is_running(PathBuf::from(&row.xplane_path)).await {
+            round.remaining += 1;
+            continue;
+        }
+        let id = row.id;
+        let applied = apply_pending(conn, row).await;
+        if applied.error.is_some() {
+            round.remaining += 1;
+        }
+        round.attempts.push((id, applied));
+    }
+    Ok(round)
+}
+
+/// Check the pending rows until none is left or [`MAX_WAIT`] passed. Returns
+/// whether it gave up with rows still pending.
+async fn poll(app: &AppHandle) -> bool {
+    let conn = app.state::<DatabaseState>().get();
+    let started = Instant::now();
+    // A write that keeps failing is reported once, not at every check
+    let mut reported_failures: HashSet<i64> = HashSet::new();
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        let round = match poll_once(&conn, |path| async move {
+            xplane_processes::is_running_at(&path).await
+        })
+        .await
+        {
+            Ok(round) => round,
+            Err(e) => {
+                logger::log_error(
+                    &format!("Failed to read deferred scenery changes: {}", e),
+                    Some(LOG_CTX),
+                );
+                return true;
+            }
+        };
+        for (id, applied) in round.attempts {
+            if applied.error.is_none() || reported_failures.insert(id) {
+                let _ = app.emit(DEFERRED_SCENERY_APPLIED_EVENT, applied);
+            }
+        }
+        if round.remaining == 0 {
+            return false;
+        }
+        if started.elapsed() >= MAX_WAIT {
+            logger::log_info(
+                "Deferred scenery changes still pending, they wait for the next launch",
+                Some(LOG_CTX),
+            );
+            return true;
+        }
+    }
+}
+
+/// Start the poller unless one is running already
+pub fn ensure_poller(app: AppHandle) {
+    if POLLER_RUNNING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let gave_up = poll(&app).await;
+            POLLER_RUNNING.store(false, Ordering::SeqCst);
+            if gave_up {
+                return;
+            }
+            // Changes queued while the poller was stopping need one more round
+            let conn = app.state::<DatabaseState>().get();
+            if !has_pending(&conn).await
+                || POLLER_RUNNING
+                    .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_err()
+            {
+                return;
+            }
+        }
+    });
+}
+
+/// Resume waiting for changes deferred by a previous session
+pub fn init(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let conn = app.state::<DatabaseState>().get();
+        if has_pending(&conn).await {
+            ensure_poller(app);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async};
+
+    fn update(folder_name: &str, enabled: bool) -> SceneryEntryUpdate {
+        SceneryEntryUpdate {
+            folder_name: folder_name.to_string(),
+            enabled,
+            sort_order: 0,
+            pinned: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queued_changes_are_merged_per_install() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        queue(
+            &conn,
+            "/xp12",
+            &[update("KSEA", true), update("EGLL", true)],
+            None,
+        )
+        .await
+        .unwrap();
+        queue(&conn, "/xp12", &[update("KSEA", false)], Some("EGLL"))
+            .await
+            .unwrap();
+
+        let rows = deferred_scenery_applies::Entity::find()
+            .all(&conn)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        let entries = parse_entries(&rows[0]);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].folder_name, "KSEA");
+        assert!(!entries[0].enabled);
+        assert_eq!(parse_deleted_folders(&rows[0]), vec!["EGLL".to_string()]);
+
+        assert!(clear(&conn, "/xp12").await.unwrap());
+        assert!(!has_pending(&conn).await);
+        assert!(!clear(&conn, "/xp12").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_pending_changes_wait_for_the_sim_and_survive_failed_writes() {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+        let xplane = tempfile::tempdir().unwrap();
+        let custom_scenery = xplane.path().join("Custom Scenery");
+        let package = custom_scenery.join("Lib A");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(
+            package.join("library.txt"),
+            "A\n800\nLIBRARY\n\nEXPORT lib/tree.obj tree.obj\n",
+        )
+        .unwrap();
+        let index_manager = SceneryIndexManager::new(xplane.path(), conn.clone());
+        index_manager.rebuild_index(None, None).await.unwrap();
+        let xplane_path = xplane.path().to_string_lossy().to_string();
+        queue(&conn, &xplane_path, &[update("Lib A", false)], None)
+            .await
+            .unwrap();
+
+        // Nothing is written while the sim runs
+        let round = poll_once(&conn, |_| async { true }).await.unwrap();
+        assert!(round.attempts.is_empty());
+        assert_eq!(round.remaining, 1);
+
+        // A rescan while the sim ran put the entry back to enabled
+        index_manager
+            .update_entry("Lib A", Some(true), None, None, None)
+            .await
+            .unwrap();
+
+        // A failed write keeps the changes for the next check
+        let moved = xplane.path().join("Custom Scenery.moved");
+        std::fs::rename(&custom_scenery, &moved).unwrap();
+        std::fs::write(&custom_scenery, "not a folder").unwrap();
+        let round = poll_once(&conn, |_| async { false }).await.unwrap();
+        assert_eq!(round.remaining, 1);
+        assert!(round.attempts[0].1.error.is_some());
+        assert!(has_pending(&conn).await);
+
+        std::fs::remove_file(&custom_scenery).unwrap();
+        std::fs::rename(&moved, &custom_scenery).unwrap();
+        let round = poll_once(&conn, |_| async { false }).await.unwrap();
+        assert_eq!(round.remaining, 0);
+        assert_eq!(round.attempts[0].1.error, None);
+        assert!(!has_pending(&conn).await);
+        let ini = std::fs::read_to_string(custom_scenery.join("scenery_packs.ini")).unwrap();
+        assert!(ini.contains("SCENERY_PACK_DISABLED Custom Scenery/Lib A/"));
+        let index = index_manager.load_index().await.unwrap();
+        assert!(!index.packages["Lib A"].enabled);
+    }
+}
@@ -0,0 +1,197 @@
+//! Plan for obtaining the libraries missing from the scenery index
+//!
+//! Starts from the `missing_libraries` table and merges references to the same
+//! name (case-insensitively). A library missing from an installed library pack
+//! also breaks every package relying on that pack, so those packages are
+//! collected transitively as indirect dependents, and libraries needed by other
+//! libraries are listed first. Each entry gets a download link from the remote
+//! list (bundled list as fallback) and, when an installed folder looks like the
+//! same library under another name, that folder.
+
+use sea_orm::DatabaseConnection;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use crate::database::SceneryQueries;
+use crate::error::ApiResult;
+use crate::library_links;
+use crate::models::{MissingLibraryPlanEntry, SceneryCategory};
+use crate::path_utils;
+
+pub async fn build_missing_library_plan(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+) -> ApiResult<Vec<MissingLibraryPlanEntry>> {
+    let install = path_utils::normalize_xplane_key(xplane_path);
+    let references = SceneryQueries::load_missing_library_references(conn, &install).await?;
+    if references.is_empty() {
+        return Ok(Vec::new());
+    }
+    let dependents = SceneryQueries::load_library_package_dependents(conn, &install).await?;
+    let mut plan = assemble_plan(&references, &dependents);
+
+    let names: Vec<String> = plan.iter().map(|e| e.library_name.clone()).collect();
+    let links = library_links::lookup_library_links_with_fallback(names.clone()).await;
+    let providers = SceneryQueries::find_fuzzy_library_providers(conn, &install, &names).await?;
+    let custom_scenery = xplane_path.join("Custom Scenery");
+
+    for entry in &mut plan {
+        entry.download_url = links.get(&entry.library_name).cloned().flatten();
+        entry.installed_as = providers
+            .get(&entry.library_name)
+            .filter(|folder| !entry.referenced_by.contains(folder))
+            .filter(|folder| custom_scenery.join(folder).is_dir())
+            .cloned();
+    }
+    Ok(plan)
+}
+
+/// Group references by library and order the plan, without links or matches
+fn assemble_plan(
+    references: &[(String, String, SceneryCategory)],
+    dependents: &HashMap<String, Vec<String>>,
+) -> Vec<MissingLibraryPlanEntry> {
+    struct Draft {
+        entry: MissingLibraryPlanEntry,
+        needed_by_library: bool,
+    }
+
+    let mut drafts: Vec<Draft> = Vec::new();
+    let mut by_key: HashMap<String, usize> = HashMap::new();
+    for (library_name, folder_name, category) in references {
+        let index = *by_key
+            .entry(library_name.to_lowercase())
+            .or_insert_with(|| {
+                drafts.push(Draft {
+                    entry: MissingLibraryPlanEntry {
+                        library_name: library_name.clone(),
+                        referenced_by: Vec::new(),
+                        indirectly_required_by: Vec::new(),
+                        download_url: None,
+                        installed_as: None,
+                    },
+                    needed_by_library: false,
+                });
+                drafts.len() - 1
+            });
+        let draft = &mut drafts[index];
+        if !draft.entry.referenced_by.contains(folder_name) {
+            draft.entry.referenced_by.push(folder_name.clone());
+        }
+        if *category == SceneryCategory::Library || dependents.contains_key(folder_name) {
+            draft.needed_by_library = true;
+        }
+    }
+
+    for draft in &mut drafts {
+        draft.entry.indirectly_required_by =
+            transitive_dependents(&draft.entry.referenced_by, dependents);
+    }
+
+    drafts.sort_by(|a, b| {
+        let affected =
+            |d: &Draft| d.entry.referenced_by.len() + d.entry.indirectly_required_by.len();
+        b.needed_by_library
+            .cmp(&a.needed_by_library)
+            .then_with(|| affected(b).cmp(&affected(a)))
+            .then_with(|| {
+                a.entry
+                    .library_name
+                    .to_lowercase()
+                    .cmp(&b.entry.library_name.to_lowercase())
+            })
+    });
+    drafts.into_iter().map(|d| d.entry).collect()
+}
+
+/// Packages reached through `dependents` from `roots`, excluding the roots
+fn transitive_dependents(
+    roots: &[String],
+    dependents: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let mut seen: HashSet<&str> = roots.iter().map(String::as_str).collect();
+    let mut queue: VecDeque<&str> = roots.iter().map(String::as_str).collect();
+    let mut found = Vec::new();
+    while let Some(folder) = queue.pop_front() {
+        for dependent in dependents.get(folder).into_iter().flatten() {
+            if seen.insert(dependent.as_str()) {
+                found.push(dependent.clone());
+                queue.push_back(dependent.as_str());
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(
+        library: &str,
+        folder: &str,
+        category: SceneryCategory,
+    ) -> (String, String, SceneryCategory) {
+        (library.to_string(), folder.to_string(), category)
+    }
+
+    #[test]
+    fn plan_merges_names_and_lists_library_dependencies_first() {
+        let references = vec![
+            reference("opensceneryx", "Airport_A", SceneryCategory::Airport),
+            reference("OpenSceneryX", "Airport_B", SceneryCategory::Airport),
+            reference("3D_people", "Lib_Vehicles", SceneryCategory::Library),
+            reference("misterx_lib", "Airport_B", SceneryCategory::Airport),
+        ];
+        let dependents: HashMap<String, Vec<String>> = [
+            (
+                "Lib_Vehicles".to_string(),
+                vec!["Airport_C".to_string(), "Lib_Ground".to_string()],
+            ),
+            ("Lib_Ground".to_string(), vec!["Airport_D".to_string()]),
+        ]
+        .into_iter()
+        .collect();
+
+        let plan = assemble_plan(&references, &dependents);
+        let summary: Vec<(&str, Vec<&str>, Vec<&str>)> = plan
+            .iter()
+            .map(|e| {
+                (
+                    e.library_name.as_str(),
+                    e.referenced_by.iter().map(String::as_str).collect(),
+                    e.indirectly_required_by
+                        .iter()
+                        .map(String::as_str)
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "3D_people",
+                    vec!["Lib_Vehicles"],
+                    vec!["Airport_C", "Lib_Ground", "Airport_D"]
+                ),
+                ("opensceneryx", vec!["Airport_A", "Airport_B"], vec![]),
+                ("misterx_lib", vec!["Airport_B"], vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn transitive_dependents_survive_cycles() {
+        let dependents: HashMap<String, Vec<String>> = [
+            ("A".to_string(), vec!["B".to_string()]),
+            ("B".to_string(), vec!["A".to_string(), "C".to_string()]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            transitive_dependents(&["A".to_string()], &dependents),
+            vec!["B".to_string(), "C".to_string()]
+        );
+    }
+}
@@ -3,6 +3,7 @@
 //! This module analyzes scenery packages and determines their category
 //! by parsing DSF file headers and checking file system structure.
 
+use crate::dir_walk::LinkFollowingWalk;
 use crate::geo_regions;
 use crate::models::{
     is_global_airports_folder_name, DsfHeader, SceneryCategory, SceneryPackageInfo,
@@ -11,13 +12,37 @@ use anyhow::{anyhow, Result};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::File;
-use std::io::Read;
-use std::path::Path;
-use std::time::SystemTime;
-use walkdir::WalkDir;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Revision of the classification rules. Bump when a rule change should
+/// re-classify already indexed packages on the next incremental scan.
+pub const CLASSIFIER_VERSION: u32 = 1;
 
 const MAX_PLUGIN_SCAN_DEPTH: usize = 5;
 
+/// Minimum number of local terrain (.ter) and DDS texture files for a base
+/// mesh package to be treated as orthophoto tiles
+const ORTHO_MIN_TEXTURED_TERRAINS: usize = 4;
+
+/// Upper bound for a single HEAD or DEFN atom; larger values mean a corrupt file
+const MAX_DSF_HEADER_ATOM_BYTES: usize = 64 * 1024 * 1024;
+
+/// Name fragments of base mesh distributions meant for Global Scenery
+const GLOBAL_MESH_NAME_PATTERNS: &[&str] = &[
+    "global scenery",
+    "global_scenery",
+    "demo area",
+    "demo_area",
+    "hd mesh",
+    "hd_mesh",
+];
+
+/// 1x1 tiles a DSF-only package needs to count as a global base mesh without a
+/// known distribution name
+const GLOBAL_MESH_MIN_TILES: usize = 100;
+
 fn is_lines3d_folder_name(folder_name: &str) -> bool {
     folder_name.trim().eq_ignore_ascii_case("lines3d")
 }
@@ -32,12 +57,7 @@ fn has_plugins(scenery_path: &Path) -> bool {
     }
 
     // Search for .xpl files up to 5 levels deep
-    for entry in WalkDir::new(&plugins_path)
-        .follow_links(true)
-        .max_depth(MAX_PLUGIN_SCAN_DEPTH)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    for entry in LinkFollowingWalk::new(&plugins_path, MAX_PLUGIN_SCAN_DEPTH) {
         if entry.file_type().is_file() {
             if let Some(ext) = entry.path().extension() {
                 if ext.eq_ignore_ascii_case("xpl") {
@@ -148,7 +168,8 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
 
     // Collect file system information
     crate::log_debug!("  Checking for apt.dat...", "scenery_classifier");
-    let has_apt_dat = check_apt_dat_recursive(scenery_path)?;
+    let airport_apt_dat = find_airport_apt_dat(scenery_path)?;
+    let has_apt_dat = airport_apt_dat.is_some();
     crate::log_debug!(
         &format!("  apt.dat check complete: {}", has_apt_dat),
         "scenery_classifier"
@@ -225,8 +246,8 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
     };
 
     // Decision Tree:
-    // 1. Has apt.dat OR (DSF with WorldEditor creation_agent) → Airport
-    if has_apt_dat {
+    // 1. Has apt.dat with airport rows OR (DSF with WorldEditor creation_agent) → Airport
+    if let Some(apt_dat_path) = airport_apt_dat {
         let category = if is_global_airports_folder_name(&folder_name) {
             SceneryCategory::DefaultAirport
         } else {
@@ -282,7 +303,7 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
                 required_libraries,
                 missing_libraries: Vec::new(), // missing_libraries will be filled later
                 exported_library_names,
-                airport_id: parse_airport_id(scenery_path),
+                airport_id: parse_airport_id(&apt_dat_path),
             },
         );
     }
@@ -469,7 +490,7 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
         );
     }
 
-    // 4. No sim/overlay and no apt.dat, OR has TERRAIN_DEF → Mesh
+    // 4. Base mesh (DSF without sim/overlay) → OrthoTile or Mesh
     // Check for terrain references in DSF
     let has_terrain_def = if let Some(ref header) = dsf_header_opt {
         !header.terrain_references.is_empty()
@@ -477,15 +498,23 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
         false
     };
 
-    if !has_apt_dat && has_earth_nav_data {
-        // Has Earth nav data but no apt.dat and no sim/overlay → Mesh
-        crate::log_debug!(
-            "  ✓ Classified as Mesh (Earth nav data without apt.dat/overlay)",
-            "scenery_classifier"
-        );
-
-        // All mesh/orthophoto scenery is now classified as Mesh
-        let category = SceneryCategory::Mesh;
+    // Earth nav data holding only navigation data (no DSF tiles) is not mesh
+    if has_earth_nav_data && !dsf_files.is_empty() {
+        // Has Earth nav data but no airport apt.dat and no sim/overlay → base mesh
+        let is_ortho = is_ortho_tile_package(&folder_name, scenery_path, dsf_header_opt.as_ref());
+        let category = if is_ortho {
+            crate::log_debug!(
+                "  ✓ Classified as OrthoTile (base mesh with photo textures)",
+                "scenery_classifier"
+            );
+            SceneryCategory::OrthoTile
+        } else {
+            crate::log_debug!(
+                "  ✓ Classified as Mesh (Earth nav data without apt.dat/overlay)",
+                "scenery_classifier"
+            );
+            SceneryCategory::Mesh
+        };
 
         let (required_libraries, missing_libraries) = if let Some(ref header) = dsf_header_opt {
             let required = extract_required_libraries(&header.object_references);
@@ -523,7 +552,7 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
             scenery_path,
             PackageInfoDetails {
                 has_apt_dat: false,
-                has_dsf: !dsf_files.is_empty(),
+                has_dsf: true,
                 has_library_txt,
                 texture_count,
                 earth_nav_tile_count: tile_count,
@@ -536,10 +565,20 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
     }
 
     if has_terrain_def {
-        crate::log_debug!(
-            "  ✓ Classified as Mesh (has TERRAIN_DEF)",
-            "scenery_classifier"
-        );
+        let is_ortho = is_ortho_tile_package(&folder_name, scenery_path, dsf_header_opt.as_ref());
+        let category = if is_ortho {
+            crate::log_debug!(
+                "  ✓ Classified as OrthoTile (has TERRAIN_DEF with photo textures)",
+                "scenery_classifier"
+            );
+            SceneryCategory::OrthoTile
+        } else {
+            crate::log_debug!(
+                "  ✓ Classified as Mesh (has TERRAIN_DEF)",
+                "scenery_classifier"
+            );
+            SceneryCategory::Mesh
+        };
         let (required_libraries, missing_libraries) = if let Some(ref header) = dsf_header_opt {
             let required = extract_required_libraries(&header.object_references);
             // Missing libraries will be calculated later in update_missing_libraries()
@@ -572,7 +611,7 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
 
         return build_package_info(
             folder_name,
-            SceneryCategory::Mesh,
+            category,
             scenery_path,
             PackageInfoDetails {
                 has_apt_dat: false,
@@ -632,42 +671,39 @@ pub fn classify_scenery(scenery_path: &Path, _xplane_path: &Path) -> Result<Scen
     )
 }
 
-/// Check if apt.dat exists recursively in Earth nav data directories
-fn check_apt_dat_recursive(scenery_path: &Path) -> Result<bool> {
+/// Find an apt.dat in Earth nav data directories that defines at least one airport.
+/// apt.dat files carrying only other records (e.g. ATC or frequency data) don't make
+/// the package an airport, so they are skipped.
+fn find_airport_apt_dat(scenery_path: &Path) -> Result<Option<PathBuf>> {
     // apt.dat is always in Earth nav data folder, so only search there
     let earth_nav_path = scenery_path.join("Earth nav data");
     if !earth_nav_path.exists() {
-        return Ok(false);
+        return Ok(None);
     }
 
     // Only search up to 5 levels deep in Earth nav data
-    for entry in WalkDir::new(&earth_nav_path)
-        .follow_links(true) // Explicitly follow symbolic links
-        .max_depth(5) // Limit depth to avoid scanning too deep
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    // Follows symbolic links, entering each physical folder once
+    for entry in LinkFollowingWalk::new(&earth_nav_path, 5) {
         if entry.file_type().is_file() {
             if let Some(name) = entry.file_name().to_str() {
                 if name.eq_ignore_ascii_case("apt.dat") {
                     // Validate apt.dat format
-                    if validate_apt_dat(entry.path())? {
-                        return Ok(true);
+                    if validate_apt_dat(entry.path())?
+                        && find_airport_header(entry.path()).is_some()
+                    {
+                        return Ok(Some(entry.path().to_path_buf()));
                     }
                 }
             }
         }
     }
-    Ok(false)
+    Ok(None)
 }
 
-/// Parse the airport identifier from apt.dat
-/// Reads "Earth nav data/apt.dat" and returns the airport code from the first airport header line.
-/// An airport header line has its first whitespace-delimited field equal to "1", "16", or "17",
-/// and the identifier is the 5th field (0-indexed field 4).
-fn parse_airport_id(scenery_path: &Path) -> Option<String> {
-    let apt_dat_path = scenery_path.join("Earth nav data").join("apt.dat");
-    let file = File::open(&apt_dat_path).ok()?;
+/// Return the first airport header line of an apt.dat.
+/// An airport header line has its first whitespace-delimited field equal to "1", "16", or "17".
+fn find_airport_header(apt_dat_path: &Path) -> Option<String> {
+    let file = File::open(apt_dat_path).ok()?;
     let reader = std::io::BufReader::new(file);
 
     use std::io::BufRead;
@@ -676,25 +712,22 @@ fn parse_airport_id(scenery_path: &Path) -> Option<String> {
             Ok(l) => l,
             Err(_) => break,
         };
-        let fields: Vec<&str> = line.split_whitespace().collect();
-        if fields.is_empty() {
-            continue;
-        }
-        match fields[0] {
-            "1" | "16" | "17" => {
-                // Airport header line: fields[4] is the airport identifier
-                if fields.len() >= 5 {
-                    return Some(fields[4].to_string());
-                }
-                // Found a header but no identifier field — stop searching
-                return None;
-            }
-            _ => continue,
+        if matches!(line.split_whitespace().next(), Some("1" | "16" | "17")) {
+            return Some(line);
         }
     }
     None
 }
 
+/// Parse the airport identifier from apt.dat
+/// Returns the airport code from the first airport header line, which is the
+/// 5th field (0-indexed field 4).
+fn parse_airport_id(apt_dat_path: &Path) -> Option<String> {
+    let header = find_airport_header(apt_dat_path)?;
+    // Airport header line: fields[4] is the airport identifier
+    header.split_whitespace().nth(4).map(|id| id.to_string())
+}
+
 /// Validate apt.dat file format (first line "I", second line starts with "1")
 fn validate_apt_dat(path: &Path) -> Result<bool> {
     let mut file = File::open(path)?;
@@ -740,14 +773,11 @@ fn find_dsf_files(scenery_path: &Path) -> Result<Vec<std::path::PathBuf>> {
         crate::log_debug!("  Scanning Earth nav data folder...", "scenery_classifier");
 
         // Only scan 2 levels deep in Earth nav data (Earth nav data/+XX+YYY/*.dsf)
-        let walker = WalkDir::new(&earth_nav_path)
-            .follow_links(true)
-            .min_depth(2) // Skip the Earth nav data folder itself
-            .max_depth(2) // Only go into first level subdirectories
-            .into_iter();
+        // Skip the Earth nav data folder and its direct children
+        let walker = LinkFollowingWalk::new(&earth_nav_path, 2).filter(|e| e.depth() >= 2);
 
         let mut count = 0;
-        for entry in walker.filter_map(|e| e.ok()) {
+        for entry in walker {
             count += 1;
             if count % 100 == 0 {
                 crate::log_debug!(
@@ -781,12 +811,7 @@ fn find_dsf_files(scenery_path: &Path) -> Result<Vec<std::path::PathBuf>> {
     crate::log_debug!("  Doing general search...", "scenery_classifier");
 
     // If not found in Earth nav data, do a general search (but still limit depth)
-    for entry in WalkDir::new(scenery_path)
-        .follow_links(true)
-        .max_depth(5) // Limit depth to avoid scanning too deep
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    for entry in LinkFollowingWalk::new(scenery_path, 5) {
         if entry.file_type().is_file() {
             if let Some(ext) = entry.path().extension() {
                 if ext.eq_ignore_ascii_case("dsf") {
@@ -818,46 +843,30 @@ fn is_dsf_compressed(dsf_path: &Path) -> Result<bool> {
     Ok(magic == [0x37, 0x7A, 0xBC, 0xAF])
 }
 
-/// Decompress 7z DSF file
-fn decompress_dsf(dsf_path: &Path) -> Result<Vec<u8>> {
-    use sevenz_rust2::decompress_file;
+/// Header atoms of a 7z compressed DSF. The DSF inside is decompressed only
+/// as far as its DEFN atom, like uncompressed files are read.
+fn read_compressed_dsf_header(dsf_path: &Path) -> Result<Vec<u8>> {
+    let mut archive = sevenz_rust2::ArchiveReader::open(dsf_path, sevenz_rust2::Password::empty())?;
 
-    // Use sevenz_rust2 to decompress
-    let temp_dir = tempfile::tempdir()?;
-    decompress_file(dsf_path, temp_dir.path())?;
-
-    // Use WalkDir to recursively search for the DSF file
-    // Max depth 3 handles cases where 7z extracts to nested directories
-    for entry in WalkDir::new(temp_dir.path())
-        .max_depth(3)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            // Prefer files with .dsf extension
-            if entry
-                .path()
-                .extension()
-                .is_some_and(|e| e.eq_ignore_ascii_case("dsf"))
-            {
-                return std::fs::read(entry.path()).map_err(|e| anyhow!("{}", e));
-            }
-        }
-    }
+    // Prefer the entry with a .dsf extension; the 7z might also hold raw data
+    // without one, in which case the first file is read
+    let has_dsf_entry =
+        archive.archive().files.iter().any(|entry| {
+            !entry.is_directory() && entry.name().to_ascii_lowercase().ends_with(".dsf")
+        });
 
-    // If no .dsf file found, try reading the first file encountered
-    // (the 7z might contain raw data without extension)
-    for entry in WalkDir::new(temp_dir.path())
-        .max_depth(3)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            return std::fs::read(entry.path()).map_err(|e| anyhow!("{}", e));
+    let mut header = None;
+    archive.for_each_entries(|entry, reader| {
+        if entry.is_directory()
+            || (has_dsf_entry && !entry.name().to_ascii_lowercase().ends_with(".dsf"))
+        {
+            return Ok(true);
         }
-    }
+        header = Some(read_streamed_dsf_header_atoms(reader));
+        Ok(false)
+    })?;
 
-    Err(anyhow!("No file found in decompressed 7z archive"))
+    header.unwrap_or_else(|| Err(anyhow!("No file found in decompressed 7z archive")))
 }
 
 /// Parse DSF file header
@@ -865,11 +874,11 @@ pub fn parse_dsf_header(dsf_path: &Path) -> Result<DsfHeader> {
     // Check if compressed
     let is_compressed = is_dsf_compressed(dsf_path)?;
 
-    // Get DSF data
+    // Get DSF data; uncompressed files only need their leading HEAD/DEFN atoms
     let data = if is_compressed {
-        decompress_dsf(dsf_path)?
+        read_compressed_dsf_header(dsf_path)?
     } else {
-        std::fs::read(dsf_path)?
+        read_dsf_header_atoms(&mut File::open(dsf_path)?)?
     };
 
     // Verify magic bytes: "XPLNEDSF"
@@ -894,6 +903,75 @@ pub fn parse_dsf_header(dsf_path: &Path) -> Result<DsfHeader> {
     })
 }
 
+/// Read the magic, version, HEAD and DEFN atoms of an uncompressed DSF.
+/// Both atoms precede the large point pool and command atoms, which are
+/// skipped with seeks so classifying a tile never reads the whole file.
+fn read_dsf_header_atoms<R: Read + Seek>(reader: &mut R) -> Result<Vec<u8>> {
+    read_dsf_header_atoms_with(reader, |reader, len| {
+        reader.seek(SeekFrom::Current(len as i64)).map(|_| ())
+    })
+}
+
+/// [`read_dsf_header_atoms`] for readers that can't seek, such as a 7z entry
+/// being decompressed; atoms before DEFN are read and dropped
+fn read_streamed_dsf_header_atoms<R: Read + ?Sized>(reader: &mut R) -> Result<Vec<u8>> {
+    read_dsf_header_atoms_with(reader, |reader, len| {
+        std::io::copy(&mut Read::take(reader, len), &mut std::io::sink()).map(|_| ())
+    })
+}
+
+fn read_dsf_header_atoms_with<R: Read + ?Sized>(
+    reader: &mut R,
+    mut skip: impl FnMut(&mut R, u64) -> std::io::Result<()>,
+) -> Result<Vec<u8>> {
+    let mut data = vec![0u8; 12];
+    reader
+        .read_exact(&mut data)
+        .map_err(|_| anyhow!("Invalid DSF file: missing magic bytes"))?;
+    if &data[0..8] != b"XPLNEDSF" {
+        return Err(anyhow!("Invalid DSF file: missing magic bytes"));
+    }
+
+    let mut atom_header = [0u8; 8];
+    while reader.read_exact(&mut atom_header).is_ok() {
+        let atom_id = [
+            atom_header[0],
+            atom_header[1],
+            atom_header[2],
+            atom_header[3],
+        ];
+        let atom_len = u32::from_le_bytes([
+            atom_header[4],
+            atom_header[5],
+            atom_header[6],
+            atom_header[7],
+        ]) as usize;
+
+        if atom_len < 8 {
+            break;
+        }
+
+        if &atom_id == b"DAEH" || &atom_id == b"NFED" {
+            if atom_len > MAX_DSF_HEADER_ATOM_BYTES {
+                return Err(anyhow!("Invalid DSF file: oversized header atom"));
+            }
+            data.extend_from_slice(&atom_header);
+            let start = data.len();
+            data.resize(start + atom_len - 8, 0);
+            reader.read_exact(&mut data[start..])?;
+
+            // DEFN is the last atom needed for classification
+            if &atom_id == b"NFED" {
+                break;
+            }
+        } else {
+            skip(reader, atom_len as u64 - 8)?;
+        }
+    }
+
+    Ok(data)
+}
+
 /// Extract properties from DSF PROP section
 fn extract_dsf_properties(data: &[u8]) -> Result<HashMap<String, String>> {
     let mut properties = HashMap::new();
@@ -1107,6 +1185,29 @@ fn parse_definition_strings(def_data: &[u8]) -> Result<Vec<String>> {
     Ok(definitions)
 }
 
+/// Total size of all files in the package and the newest file modification
+/// time (Unix seconds), following links into relocated sub-folders but
+/// counting each physical directory once. A package without files reports
+/// the folder's own modification time, so its result is kept until the
+/// folder changes instead of being re-classified on every scan.
+fn package_usage(scenery_path: &Path) -> (u64, Option<i64>) {
+    let unix_secs = |metadata: &fs::Metadata| {
+        metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+    };
+    let (size, newest) = LinkFollowingWalk::new(scenery_path, usize::MAX)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .fold((0, None), |(size, newest), metadata| {
+            (size + metadata.len(), newest.max(unix_secs(&metadata)))
+        });
+    let newest = newest.or_else(|| fs::metadata(scenery_path).ok().and_then(|m| unix_secs(&m)));
+    (size, newest)
+}
+
 /// Count texture files in scenery folder (up to 5 for classification)
 fn count_texture_files(scenery_path: &Path) -> Result<usize> {
     let textures_path = scenery_path.join("textures");
@@ -1116,12 +1217,7 @@ fn count_texture_files(scenery_path: &Path) -> Result<usize> {
 
     // Only count up to 5 textures - enough to determine if this is an orthophoto scenery
     let mut count = 0;
-    for entry in WalkDir::new(&textures_path)
-        .follow_links(true) // Explicitly follow symbolic links
-        .max_depth(3)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+    for entry in LinkFollowingWalk::new(&textures_path, 3) {
         if entry.file_type().is_file() {
             if let Some(ext) = entry.path().extension() {
                 let ext_lower = ext.to_string_lossy().to_lowercase();
@@ -1139,6 +1235,73 @@ fn count_texture_files(scenery_path: &Path) -> Result<usize> {
     Ok(count)
 }
 
+/// Count files with the given extension under a package sub-folder, stopping at `limit`
+fn count_files_with_extension(dir: &Path, extension: &str, limit: usize) -> usize {
+    if !dir.exists() {
+        return 0;
+    }
+
+    LinkFollowingWalk::new(dir, 3)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+        })
+        .take(limit)
+        .count()
+}
+
+/// Decide whether a base mesh package is made of orthophoto tiles.
+/// Ortho tiles drape photo textures through package-local .ter files, so the DSF
+/// mostly references local terrain instead of lib/ terrain and the package ships
+/// many .ter/.dds files. XPME packages stay Mesh so their sub-priority rules apply.
+fn is_ortho_tile_package(
+    folder_name: &str,
+    scenery_path: &Path,
+    header: Option<&DsfHeader>,
+) -> bool {
+    let folder_lower = folder_name.to_lowercase();
+    if folder_lower.starts_with("xpme") {
+        return false;
+    }
+    if folder_lower.starts_with("zortho4xp") || folder_lower.starts_with("yortho4xp") {
+        return true;
+    }
+    if header
+        .and_then(|h| h.creation_agent.as_ref())
+        .is_some_and(|agent| agent.to_lowercase().contains("ortho4xp"))
+    {
+        return true;
+    }
+
+    let dds_count = count_files_with_extension(
+        &scenery_path.join("textures"),
+        "dds",
+        ORTHO_MIN_TEXTURED_TERRAINS,
+    );
+    if dds_count < ORTHO_MIN_TEXTURED_TERRAINS {
+        return false;
+    }
+
+    let dsf_local_terrain = header.is_some_and(|h| {
+        let local = h
+            .terrain_references
+            .iter()
+            .filter(|t| !t.starts_with("lib/") && t.to_lowercase().ends_with(".ter"))
+            .count();
+        local >= ORTHO_MIN_TEXTURED_TERRAINS && local * 2 >= h.terrain_references.len()
+    });
+
+    dsf_local_terrain
+        || count_files_with_extension(
+            &scenery_path.join("terrain"),
+            "ter",
+            ORTHO_MIN_TEXTURED_TERRAINS,
+        ) >= ORTHO_MIN_TEXTURED_TERRAINS
+}
+
 fn is_ten_degree_tile_folder_name(name: &str) -> bool {
     if name.len() != 7 {
         return false;
@@ -1173,6 +1336,68 @@ fn is_ten_degree_tile_folder_name(name: &str) -> bool {
     lat.is_multiple_of(10) && lon.is_multiple_of(10)
 }
 
+/// Whether `relative` is `Earth nav data/<10x10 folder>/<1x1 tile>.dsf` with the
+/// tile inside its folder
+fn is_full_tile_dsf_path(relative: &str) -> bool {
+    let parts: Vec<&str> = relative.split('/').collect();
+    let [earth_nav, folder, file] = parts.as_slice() else {
+        return false;
+    };
+    if !earth_nav.eq_ignore_ascii_case("Earth nav data") || !is_ten_degree_tile_folder_name(folder)
+    {
+        return false;
+    }
+    if file.len() != 11 || !file.to_lowercase().ends_with(".dsf") {
+        return false;
+    }
+    let stem = &file[..7];
+    let (Some((lat, lon)), Some((folder_lat, folder_lon))) = (
+        parse_dsf_coord_from_filename(Path::new(stem)),
+        parse_dsf_coord_from_filename(Path::new(folder)),
+    ) else {
+        return false;
+    };
+    lat.div_euclid(10) * 10 == folder_lat && lon.div_euclid(10) * 10 == folder_lon
+}
+
+/// Whether a scenery package looks like a base mesh meant for Global Scenery
+/// rather than Custom Scenery: nothing but 1x1 DSF tiles in their 10x10
+/// folders (documentation aside), no apt.dat or library.txt, and either a
+/// known mesh distribution name or a tile grid large enough to be one.
+/// `files` are relative to the package root.
+pub fn is_global_scenery_mesh(folder_name: &str, files: &[String]) -> bool {
+    let mut tiles = 0usize;
+    for file in files {
+        let relative = file.replace('\\', "/");
+        let relative = relative.trim_start_matches('/');
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        let lower = name.to_lowercase();
+        if lower == "library.txt" || lower == "apt.dat" {
+            return false;
+        }
+        if is_full_tile_dsf_path(relative) {
+            tiles += 1;
+            continue;
+        }
+        let is_documentation = name.starts_with('.')
+            || [".txt", ".md", ".pdf", ".html", ".htm", ".rtf"]
+                .iter()
+                .any(|ext| lower.ends_with(ext));
+        if !is_documentation {
+            return false;
+        }
+    }
+    if tiles == 0 {
+        return false;
+    }
+
+    let folder_lower = folder_name.to_lowercase();
+    GLOBAL_MESH_NAME_PATTERNS
+        .iter()
+        .any(|pattern| folder_lower.contains(pattern))
+        || tiles >= GLOBAL_MESH_MIN_TILES
+}
+
 fn count_earth_nav_tile_folders(scenery_path: &Path) -> Result<u32> {
     let earth_nav_path = scenery_path.join("Earth nav data");
     if !earth_nav_path.exists() {
@@ -1342,6 +1567,85 @@ fn collect_dsf_coordinates(scenery_path: &Path) -> Vec<(i32, i32)> {
     coordinates
 }
 
+/// Centres of the 10°×10° tile folders in Earth nav data (e.g. `+40+010`)
+fn collect_tile_folder_coordinates(scenery_path: &Path) -> Vec<(i32, i32)> {
+    let Ok(entries) = fs::read_dir(scenery_path.join("Earth nav data")) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| parse_dsf_coord_from_filename(&path))
+        .map(|(lat, lon)| (lat + 5, lon + 5))
+        .collect()
+}
+
+/// Location of the first airport in an apt.dat, from its `1302 datum_lat` and
+/// `datum_lon` rows or else its first runway or helipad
+fn parse_airport_coordinates(apt_dat_path: &Path) -> Option<(i32, i32)> {
+    use std::io::BufRead;
+
+    let reader = std::io::BufReader::new(File::open(apt_dat_path).ok()?);
+    let to_tile = |lat: f64, lon: f64| (lat.floor() as i32, lon.floor() as i32);
+    let (mut datum_lat, mut datum_lon) = (None, None);
+    for line in reader.lines().map_while(|line| line.ok()) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let position = match fields.as_slice() {
+            ["1302", "datum_lat", value, ..] => {
+                datum_lat = value.parse::<f64>().ok();
+                None
+            }
+            ["1302", "datum_lon", value, ..] => {
+                datum_lon = value.parse::<f64>().ok();
+                None
+            }
+            // Land runway: latitude and longitude of its first end
+            ["100", _, _, _, _, _, _, _, _, lat, lon, ..] => Some((*lat, *lon)),
+            // Helipad
+            ["102", _, lat, lon, ..] => Some((*lat, *lon)),
+            _ => None,
+        };
+        if let (Some(lat), Some(lon)) = (datum_lat, datum_lon) {
+            return Some(to_tile(lat, lon));
+        }
+        if let Some((lat, lon)) = position {
+            if let (Ok(lat), Ok(lon)) = (lat.parse::<f64>(), lon.parse::<f64>()) {
+                return Some(to_tile(lat, lon));
+            }
+        }
+    }
+    None
+}
+
+/// Most frequent continent among 1°×1° tiles
+fn dominant_continent(coordinates: &[(i32, i32)]) -> Option<String> {
+    let mut continent_counts: HashMap<&'static str, usize> = HashMap::new();
+    for (lat, lon) in coordinates {
+        let cont = geo_regions::lookup_region(*lat, *lon);
+        *continent_counts.entry(cont).or_insert(0) += 1;
+    }
+    continent_counts
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(cont, _)| cont.to_string())
+}
+
+/// Continent of a scenery package, from its DSF tiles, its Earth nav data tile
+/// folders when they hold no DSF, or its airport's location in apt.dat
+pub fn detect_continent(scenery_path: &Path) -> Option<String> {
+    let coordinates = collect_dsf_coordinates(scenery_path);
+    if !coordinates.is_empty() {
+        return dominant_continent(&coordinates);
+    }
+    let coordinates = collect_tile_folder_coordinates(scenery_path);
+    if !coordinates.is_empty() {
+        return dominant_continent(&coordinates);
+    }
+    let apt_dat_path = find_airport_apt_dat(scenery_path).ok().flatten()?;
+    dominant_continent(&[parse_airport_coordinates(&apt_dat_path)?])
+}
+
 /// Parse DSF filename to extract coordinates
 /// Format: +30+135.dsf or -45-073.dsf (latitude + longitude)
 fn parse_dsf_coord_from_filename(dsf_path: &Path) -> Option<(i32, i32)> {
@@ -1409,27 +1713,8 @@ fn build_package_info(
     // Calculate sub-priority based on category and folder name
     let sub_priority = calculate_sub_priority(&category, &folder_name);
 
-    // Collect DSF coordinates temporarily for continent calculation
-    let coordinates = collect_dsf_coordinates(scenery_path);
-
-    // Calculate continent from coordinates (coordinates are not stored)
-    let continent = if !coordinates.is_empty() {
-        // Count continents for all coordinates
-        let mut continent_counts: HashMap<&'static str, usize> = HashMap::new();
-
-        for (lat, lon) in &coordinates {
-            let cont = geo_regions::lookup_region(*lat, *lon);
-            *continent_counts.entry(cont).or_insert(0) += 1;
-        }
-
-        // Find the most frequent continent
-        continent_counts
-            .into_iter()
-            .max_by_key(|(_, count)| *count)
-            .map(|(cont, _)| cont.to_string())
-    } else {
-        None
-    };
+    // Coordinates are only used to find the continent and are not stored
+    let continent = detect_continent(scenery_path);
 
     crate::log_debug!(
         &format!(
@@ -1451,6 +1736,8 @@ fn build_package_info(
         "scenery_classifier"
     );
 
+    let (total_size_bytes, newest_file_modified) = package_usage(scenery_path);
+
     Ok(SceneryPackageInfo {
         folder_name,
         category: category.clone(),
@@ -1464,6 +1751,8 @@ fn build_package_info(
         has_objects: scenery_path.join("objects").exists(),
         texture_count,
         earth_nav_tile_count,
+        total_size_bytes,
+        newest_file_modified,
         indexed_at: SystemTime::now(),
         required_libraries,
         missing_libraries,
@@ -1473,6 +1762,8 @@ fn build_package_info(
         actual_path: None, // Will be set by index manager for shortcut entries
         continent,
         original_category: Some(category), // Preserve original classification
+        classifier_version: CLASSIFIER_VERSION,
+        pinned: false,
     })
 }
 
@@ -1499,6 +1790,17 @@ fn calculate_sub_priority(category: &SceneryCategory, folder_name: &str) -> u8 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_package_usage_of_empty_package_uses_folder_time() {
+        let temp = tempfile::tempdir().unwrap();
+        let package = temp.path().join("Empty Package");
+        fs::create_dir_all(package.join("Earth nav data")).unwrap();
+
+        let (size, newest) = package_usage(&package);
+        assert_eq!(size, 0);
+        assert!(newest.is_some());
+    }
+
     #[test]
     fn test_sam_library_detection() {
         // SAM libraries should be classified as FixedHighPriority
@@ -1544,4 +1846,208 @@ mod tests {
         // This test would need actual test files
         // For now, just verify the function exists and compiles
     }
+    fn dsf_atom(id: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut atom = id.to_vec();
+        atom.extend(((payload.len() + 8) as u32).to_le_bytes());
+        atom.extend(payload);
+        atom
+    }
+
+    /// Minimal DSF with HEAD/PROP, DEFN/TERT and a trailing geometry atom
+    fn dsf_bytes(properties: &[(&str, &str)], terrains: &[&str]) -> Vec<u8> {
+        let mut prop = Vec::new();
+        for (key, value) in properties {
+            prop.extend(key.as_bytes());
+            prop.push(0);
+            prop.extend(value.as_bytes());
+            prop.push(0);
+        }
+        let mut tert = Vec::new();
+        for terrain in terrains {
+            tert.extend(terrain.as_bytes());
+            tert.push(0);
+        }
+
+        let mut data = b"XPLNEDSF".to_vec();
+        data.extend(1u32.to_le_bytes());
+        data.extend(dsf_atom(b"DAEH", &dsf_atom(b"PORP", &prop)));
+        data.extend(dsf_atom(b"NFED", &dsf_atom(b"TRET", &tert)));
+        data.extend(dsf_atom(b"DOEG", &[0u8; 4096]));
+        data
+    }
+
+    fn write_package(root: &Path, name: &str, dsf: &[u8], files: &[&str]) -> PathBuf {
+        let package = root.join(name);
+        let tile = package.join("Earth nav data").join("+40-130");
+        fs::create_dir_all(&tile).unwrap();
+        fs::write(tile.join("+47-123.dsf"), dsf).unwrap();
+        for file in files {
+            let path = package.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, b"data").unwrap();
+        }
+        package
+    }
+
+    #[test]
+    fn test_read_dsf_header_atoms_skips_geometry() {
+        let dsf = dsf_bytes(
+            &[("sim/overlay", "1"), ("sim/creation_agent", "WorldEditor")],
+            &["lib/g10/terrain10/apt_terrain.ter"],
+        );
+        let data = read_dsf_header_atoms(&mut std::io::Cursor::new(&dsf)).unwrap();
+
+        assert!(data.len() < dsf.len() - 4096);
+        let properties = extract_dsf_properties(&data).unwrap();
+        assert_eq!(properties.get("sim/overlay").map(String::as_str), Some("1"));
+        let (_, terrains) = extract_dsf_definitions(&data).unwrap();
+        assert_eq!(terrains, vec!["lib/g10/terrain10/apt_terrain.ter"]);
+
+        assert!(read_dsf_header_atoms(&mut std::io::Cursor::new(b"NOTADSF_0000")).is_err());
+    }
+
+    #[test]
+    fn test_parse_dsf_header_of_7z_compressed_dsf() {
+        use sevenz_rust2::{ArchiveEntry, ArchiveWriter, SourceReader};
+
+        let dsf = dsf_bytes(
+            &[("sim/overlay", "1")],
+            &["lib/g10/terrain10/apt_terrain.ter"],
+        );
+        let mut writer = ArchiveWriter::new(std::io::Cursor::new(Vec::new())).unwrap();
+        writer
+            .push_archive_entries(
+                vec![ArchiveEntry::new_file("+47-123.dsf")],
+                vec![SourceReader::new(std::io::Cursor::new(dsf))],
+            )
+            .unwrap();
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("+47-123.dsf");
+        fs::write(&path, writer.finish().unwrap().into_inner()).unwrap();
+
+        assert!(is_dsf_compressed(&path).unwrap());
+        let header = parse_dsf_header(&path).unwrap();
+        assert!(header.is_overlay);
+        assert_eq!(
+            header.terrain_references,
+            vec!["lib/g10/terrain10/apt_terrain.ter"]
+        );
+    }
+
+    #[test]
+    fn test_classify_ortho_tile_and_mesh() {
+        let temp = tempfile::tempdir().unwrap();
+        let terrains: Vec<String> = (0..4)
+            .map(|i| format!("terrain/2400_3700_BI16_{}.ter", i))
+            .collect();
+        let terrain_refs: Vec<&str> = terrains.iter().map(String::as_str).collect();
+        let dsf = dsf_bytes(&[("sim/overlay", "0")], &terrain_refs);
+
+        let textures = [
+            "textures/a.dds",
+            "textures/b.dds",
+            "textures/c.dds",
+            "textures/d.dds",
+        ];
+        let ortho = write_package(temp.path(), "Photo_Region", &dsf, &textures);
+        let info = classify_scenery(&ortho, temp.path()).unwrap();
+        assert_eq!(info.category, SceneryCategory::OrthoTile);
+        assert_eq!(info.classifier_version, CLASSIFIER_VERSION);
+
+        // Same local terrain without photo textures is plain mesh
+        let mesh = write_package(temp.path(), "Region_Mesh", &dsf, &[]);
+        let info = classify_scenery(&mesh, temp.path()).unwrap();
+        assert_eq!(info.category, SceneryCategory::Mesh);
+    }
+
+    #[test]
+    fn test_classify_airport_requires_airport_rows() {
+        let temp = tempfile::tempdir().unwrap();
+        let overlay = dsf_bytes(&[("sim/overlay", "1")], &[]);
+
+        let package = write_package(temp.path(), "Region_Overlay", &overlay, &[]);
+        fs::write(
+            package.join("Earth nav data").join("apt.dat"),
+            "I\n1100 Generated\n\n99\n",
+        )
+        .unwrap();
+        let info = classify_scenery(&package, temp.path()).unwrap();
+        assert_eq!(info.category, SceneryCategory::Overlay);
+        assert!(!info.has_apt_dat);
+
+        fs::write(
+            package.join("Earth nav data").join("apt.dat"),
+            "I\n1100 Generated\n\n1 433 0 0 KSEA Seattle Tacoma Intl\n99\n",
+        )
+        .unwrap();
+        let info = classify_scenery(&package, temp.path()).unwrap();
+        assert_eq!(info.category, SceneryCategory::Airport);
+        assert_eq!(info.airport_id.as_deref(), Some("KSEA"));
+    }
+
+    #[test]
+    fn test_airport_without_dsf_gets_continent_from_apt_dat() {
+        let temp = tempfile::tempdir().unwrap();
+        let package = temp.path().join("KSEA_Airport");
+        fs::create_dir_all(package.join("Earth nav data")).unwrap();
+        fs::write(
+            package.join("Earth nav data").join("apt.dat"),
+            "I\n1100 Generated\n\n1 433 0 0 KSEA Seattle Tacoma Intl\n\
+             100 45.72 1 0 0.25 1 3 0 16L 47.46380000 -122.30774378 0 0 2 0 0 0 \
+             34R 47.43119000 -122.30801400 0 0 2 0 0 0\n99\n",
+        )
+        .unwrap();
+        assert_eq!(detect_continent(&package).as_deref(), Some("North America"));
+
+        fs::write(
+            package.join("Earth nav data").join("apt.dat"),
+            "I\n1100 Generated\n\n1 433 0 0 EDDM Munich\n\
+             1302 datum_lat 48.353783\n1302 datum_lon 11.786086\n99\n",
+        )
+        .unwrap();
+        assert_eq!(detect_continent(&package).as_deref(), Some("Europe"));
+    }
+
+    #[test]
+    fn test_earth_nav_data_without_dsf_is_other() {
+        let temp = tempfile::tempdir().unwrap();
+        let package = temp.path().join("Custom_Navdata");
+        fs::create_dir_all(package.join("Earth nav data")).unwrap();
+        fs::write(package.join("Earth nav data").join("earth_fix.dat"), "I\n").unwrap();
+
+        let info = classify_scenery(&package, temp.path()).unwrap();
+        assert_eq!(info.category, SceneryCategory::Other);
+    }
+
+    #[test]
+    fn test_global_scenery_mesh_detection() {
+        let mesh: Vec<String> = [
+            "Earth nav data/+40-080/+42-073.dsf",
+            "Earth nav data/-50-080/-45-073.dsf",
+            "README.txt",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert!(is_global_scenery_mesh("HD_Mesh_Scenery_v4", &mesh));
+        assert!(is_global_scenery_mesh("X-Plane 12 Demo Areas", &mesh));
+        // Same tiles under an unknown name are a custom mesh
+        assert!(!is_global_scenery_mesh("Seattle_Mesh", &mesh));
+
+        let misplaced = vec!["Earth nav data/+40-080/+52-073.dsf".to_string()];
+        assert!(!is_global_scenery_mesh("HD_Mesh_Scenery_v4", &misplaced));
+
+        let mut airport = mesh.clone();
+        airport.push("Earth nav data/apt.dat".to_string());
+        assert!(!is_global_scenery_mesh("Global Scenery KSEA", &airport));
+
+        let mut with_objects = mesh;
+        with_objects.push("objects/tree.obj".to_string());
+        assert!(!is_global_scenery_mesh("HD_Mesh_Scenery_v4", &with_objects));
+
+        let grid: Vec<String> = (0..GLOBAL_MESH_MIN_TILES as i32)
+            .map(|i| format!("Earth nav data/+40+000/+{}+{:03}.dsf", 40 + i / 10, i % 10))
+            .collect();
+        assert!(is_global_scenery_mesh("Unnamed_Mesh", &grid));
+    }
 }
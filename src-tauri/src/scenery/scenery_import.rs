@@ -0,0 +1,689 @@
+//! Import scenery from another Custom Scenery folder
+//!
+//! Packages of an external Custom Scenery folder (an older installation, a
+//! backup drive) are brought into the active installation:
+//!
+//! - Names already taken in the destination are skipped and reported.
+//! - Copies are written to a hidden staging folder next to their final place
+//!   and renamed in once complete, so a failed or cancelled copy leaves nothing
+//!   behind.
+//! - Moves rename when both folders share a volume. Otherwise the package is
+//!   copied, the copy is checked against the source, and only then is the
+//!   source deleted. A source that can't be fully deleted is reported, but the
+//!   imported package is kept.
+//! - Links inside a package are followed, each folder is copied once and a
+//!   link loop is not followed again.
+//! - Links point at the folder the source entry resolves to. Entries that are
+//!   themselves links or shortcuts are always linked, since their scenery
+//!   doesn't live in the source folder.
+//!
+//! The index and scenery_packs.ini are updated afterwards through the packs
+//! manager, the same way a folder sync picks up new packages.
+
+use crate::dir_walk::LinkFollowingWalk;
+use crate::error::{ApiError, ApiResult};
+use crate::logger;
+use crate::models::{
+    ImportedSceneryPackage, InstallPhase, InstallProgress, SceneryCategory, SceneryImportFailure,
+    SceneryImportMode, SceneryImportResult,
+};
+use crate::path_utils;
+use crate::scenery_classifier;
+use crate::scenery_index::{self, SceneryEntryKind, SceneryEntryLocation};
+use crate::scenery_packs_manager::SceneryPacksManager;
+use crate::task_control::TaskControl;
+use anyhow::{anyhow, Context, Result};
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Prefix of the staging folder a copy is written to before it is renamed in
+const STAGING_PREFIX: &str = ".xfm_import_";
+
+/// Minimum time between two progress events while copying
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Attempts at deleting a moved source folder, for files briefly held open
+/// by a virus scanner or an indexer
+const REMOVE_RETRIES: u32 = 3;
+
+/// Progress of the whole import, sent as install-progress events
+struct ImportProgress<'a> {
+    app_handle: Option<&'a AppHandle>,
+    total_bytes: u64,
+    processed_bytes: u64,
+    total_tasks: usize,
+    task_index: usize,
+    task_name: String,
+    task_total_bytes: u64,
+    task_processed_bytes: u64,
+    last_emit: Option<Instant>,
+}
+
+impl<'a> ImportProgress<'a> {
+    fn new(app_handle: Option<&'a AppHandle>, total_tasks: usize) -> Self {
+        Self {
+            app_handle,
+            total_bytes: 0,
+            processed_bytes: 0,
+            total_tasks,
+            task_index: 0,
+            task_name: String::new(),
+            task_total_bytes: 0,
+            task_processed_bytes: 0,
+            last_emit: None,
+        }
+    }
+
+    fn start_task(&mut self, index: usize, name: &str, total_bytes: u64) {
+        self.task_index = index;
+        self.task_name = name.to_string();
+        self.task_total_bytes = total_bytes;
+        self.task_processed_bytes = 0;
+        self.emit(InstallPhase::Installing, None, true);
+    }
+
+    /// Count `bytes` of the current task as done
+    fn advance(&mut self, bytes: u64, current_file: Option<&Path>) {
+        self.task_processed_bytes += bytes;
+        self.processed_bytes += bytes;
+        self.emit(
+            InstallPhase::Installing,
+            current_file.map(|path| path.to_string_lossy().into_owned()),
+            false,
+        );
+    }
+
+    /// Count whatever is left of the current task as done, e.g. after a rename
+    fn finish_task(&mut self) {
+        let remaining = self
+            .task_total_bytes
+            .saturating_sub(self.task_processed_bytes);
+        self.advance(remaining, None);
+    }
+
+    fn emit(&mut self, phase: InstallPhase, current_file: Option<String>, force: bool) {
+        let Some(app_handle) = self.app_handle else {
+            return;
+        };
+        if !force
+            && self
+                .last_emit
+                .is_some_and(|last| last.elapsed() < PROGRESS_EMIT_INTERVAL)
+        {
+            return;
+        }
+        self.last_emit = Some(Instant::now());
+
+        let percentage = if self.total_bytes > 0 {
+            self.processed_bytes as f64 / self.total_bytes as f64 * 100.0
+        } else if self.total_tasks > 0 {
+            self.task_index as f64 / self.total_tasks as f64 * 100.0
+        } else {
+            100.0
+        };
+        let current_task_percentage = if self.task_total_bytes > 0 {
+            self.task_processed_bytes as f64 / self.task_total_bytes as f64 * 100.0
+        } else {
+            0.0
+        };
+        let progress = InstallProgress {
+            percentage: percentage.min(100.0),
+            total_bytes: self.total_bytes,
+            processed_bytes: self.processed_bytes,
+            current_task_index: self.task_index,
+            total_tasks: self.total_tasks,
+            current_task_name: self.task_name.clone(),
+            current_file,
+            phase,
+            verification_progress: None,
+            current_task_percentage: current_task_percentage.min(100.0),
+            current_task_total_bytes: self.task_total_bytes,
+            current_task_processed_bytes: self.task_processed_bytes,
+            active_tasks: None,
+            completed_task_count: Some(self.task_index),
+            completed_task_ids: None,
+            in_solid_block: None,
+            speed_bytes_per_sec: None,
+            eta_seconds: None,
+        };
+        let _ = app_handle.emit("install-progress", &progress);
+    }
+}
+
+/// Fail when `walk` skipped anything it couldn't read
+fn ensure_walk_complete(walk: &LinkFollowingWalk) -> Result<()> {
+    match walk.unreadable().first() {
+        Some(path) => Err(anyhow!("Failed to read '{}'", path.display())),
+        None => Ok(()),
+    }
+}
+
+/// Number of files and bytes below `path`, following links into each folder once
+fn tree_stats(path: &Path) -> Result<(usize, u64)> {
+    let mut files = 0;
+    let mut bytes = 0;
+    let mut walk = LinkFollowingWalk::new(path, usize::MAX);
+    for entry in walk.by_ref() {
+        if entry.file_type().is_file() {
+            files += 1;
+            bytes += entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+        }
+    }
+    ensure_walk_complete(&walk)?;
+    Ok((files, bytes))
+}
+
+/// Whether `name` is already used by a folder, link or shortcut in `dest_dir`
+fn destination_taken(dest_dir: &Path, name: &str) -> bool {
+    dest_dir.join(name).symlink_metadata().is_ok()
+        || dest_dir.join(format!("{}.lnk", name)).exists()
+        || scenery_index::find_scenery_entry(dest_dir, name).is_some()
+}
+
+fn ensure_not_cancelled(task_control: &TaskControl) -> Result<()> {
+    if task_control.is_cancelled() {
+        return Err(anyhow!("Scenery import cancelled by user"));
+    }
+    Ok(())
+}
+
+/// Copy the contents of `source` into `target`, returning the bytes copied
+fn copy_tree(
+    source: &Path,
+    target: &Path,
+    progress: &mut ImportProgress,
+    task_control: &TaskControl,
+) -> Result<u64> {
+    let mut copied = 0;
+    // A folder reached through several links is copied once, a link loop is not followed
+    let mut walk = LinkFollowingWalk::new(source, usize::MAX);
+    for entry in walk.by_ref() {
+        ensure_not_cancelled(task_control)?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|e| anyhow!("Unexpected path '{}': {}", entry.path().display(), e))?;
+        let destination = target.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&destination)
+                .with_context(|| format!("Failed to create '{}'", destination.display()))?;
+        } else if entry.file_type().is_file() {
+            let bytes = fs::copy(entry.path(), &destination).with_context(|| {
+                format!(
+                    "Failed to copy '{}' to '{}'",
+                    entry.path().display(),
+                    destination.display()
+                )
+            })?;
+            copied += bytes;
+            progress.advance(bytes, Some(relative));
+        }
+    }
+    ensure_walk_complete(&walk)?;
+    Ok(copied)
+}
+
+/// Copy `source` to `dest_dir/name` through a staging folder, which is
+/// removed again when the copy fails or is cancelled
+fn copy_package(
+    source: &Path,
+    dest_dir: &Path,
+    name: &str,
+    progress: &mut ImportProgress,
+    task_control: &TaskControl,
+) -> Result<u64> {
+    let staging = dest_dir.join(format!("{}{}", STAGING_PREFIX, name));
+    if staging.exists() {
+        fs::remove_dir_all(&staging).with_context(|| {
+            format!(
+                "Failed to remove leftover staging folder '{}'",
+                staging.display()
+            )
+        })?;
+    }
+
+    let result = copy_tree(source, &staging, progress, task_control).and_then(|bytes| {
+        let destination = dest_dir.join(name);
+        fs::rename(&staging, &destination)
+            .with_context(|| format!("Failed to move copy into '{}'", destination.display()))?;
+        Ok(bytes)
+    });
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// Delete a folder, retrying with a growing delay while files are held open
+fn remove_dir_with_retries(path: &Path) -> Result<()> {
+    let mut last_error = None;
+    for attempt in 0..REMOVE_RETRIES {
+        match fs::remove_dir_all(path) {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                last_error = Some(e);
+                std::thread::sleep(Duration::from_millis(100 << attempt));
+            }
+        }
+    }
+    Err(anyhow!(
+        "Failed to delete '{}': {}",
+        path.display(),
+        last_error.map(|e| e.to_string()).unwrap_or_default()
+    ))
+}
+
+/// Remove a link or shortcut entry of the source folder, not what it points to
+fn remove_link_entry(entry: &SceneryEntryLocation) -> Result<()> {
+    match entry.kind {
+        SceneryEntryKind::Link => {
+            path_utils::remove_directory_link(&entry.entry_path).map_err(|e| anyhow!(e))
+        }
+        _ => fs::remove_file(&entry.entry_path)
+            .with_context(|| format!("Failed to delete '{}'", entry.entry_path.display())),
+    }
+}
+
+/// Move a real folder, copying across volumes. The source is deleted only
+/// after the copy matches it; a failed deletion becomes a warning.
+fn move_package(
+    entry: &SceneryEntryLocation,
+    dest_dir: &Path,
+    progress: &mut ImportProgress,
+    task_control: &TaskControl,
+    warnings: &mut Vec<String>,
+) -> Result<u64> {
+    let destination = dest_dir.join(&entry.name);
+    if fs::rename(&entry.entry_path, &destination).is_ok() {
+        return Ok(0);
+    }
+
+    let source_stats = tree_stats(&entry.entry_path)?;
+    let bytes = copy_package(
+        &entry.entry_path,
+        dest_dir,
+        &entry.name,
+        progress,
+        task_control,
+    )?;
+    let copied_stats = tree_stats(&destination)?;
+    if copied_stats != source_stats {
+        // Keep the source; the copy is incomplete and goes instead
+        let _ = fs::remove_dir_all(&destination);
+        return Err(anyhow!(
+            "Copy of '{}' does not match its source ({} files, {} bytes copied; {} files, {} bytes expected)",
+            entry.name,
+            copied_stats.0,
+            copied_stats.1,
+            source_stats.0,
+            source_stats.1
+        ));
+    }
+
+    if let Err(e) = remove_dir_with_retries(&entry.entry_path) {
+        warnings.push(format!(
+            "{} was imported, but its source could not be fully deleted: {}",
+            entry.name, e
+        ));
+    }
+    Ok(bytes)
+}
+
+/// Bring one source entry into `dest_dir`, returning the bytes copied
+fn transfer_package(
+    entry: &SceneryEntryLocation,
+    dest_dir: &Path,
+    mode: SceneryImportMode,
+    progress: &mut ImportProgress,
+    task_control: &TaskControl,
+    warnings: &mut Vec<String>,
+) -> Result<u64> {
+    let is_directory = entry.kind == SceneryEntryKind::Directory;
+    match mode {
+        SceneryImportMode::Copy if is_directory => copy_package(
+            &entry.entry_path,
+            dest_dir,
+            &entry.name,
+            progress,
+            task_control,
+        ),
+        SceneryImportMode::Move if is_directory => {
+            move_package(entry, dest_dir, progress, task_control, warnings)
+        }
+        _ => {
+            path_utils::create_directory_link(&entry.target_path, &dest_dir.join(&entry.name))
+                .map_err(|e| anyhow!(e))?;
+            if mode == SceneryImportMode::Move {
+                if let Err(e) = remove_link_entry(entry) {
+                    warnings.push(format!(
+                        "{} was imported, but its source entry could not be deleted: {}",
+                        entry.name, e
+                    ));
+                }
+            }
+            Ok(0)
+        }
+    }
+}
+
+/// Pick the requested entries of the source folder. Names that aren't there
+/// are reported as failures.
+fn select_entries(
+    source_dir: &Path,
+    folder_names: Option<&[String]>,
+    failed: &mut Vec<SceneryImportFailure>,
+) -> Result<Vec<SceneryEntryLocation>> {
+    let mut entries: Vec<SceneryEntryLocation> = scenery_index::list_scenery_entries(source_dir)
+        .with_context(|| format!("Failed to read '{}'", source_dir.display()))?
+        .into_iter()
+        .filter(|entry| !entry.name.starts_with('.'))
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let Some(folder_names) = folder_names else {
+        return Ok(entries);
+    };
+    let mut by_name: HashMap<String, SceneryEntryLocation> = entries
+        .into_iter()
+        .map(|entry| (entry.name.clone(), entry))
+        .collect();
+    let mut selected = Vec::new();
+    for name in folder_names {
+        match by_name.remove(name) {
+            Some(entry) => selected.push(entry),
+            // Listed twice
+            None if selected.iter().any(|e| &e.name == name) => {}
+            None => failed.push(SceneryImportFailure {
+                folder_name: name.clone(),
+                message: "Not found in the source folder".to_string(),
+            }),
+        }
+    }
+    Ok(selected)
+}
+
+/// File work of an import, run on a blocking thread
+fn run_import(
+    app_handle: Option<&AppHandle>,
+    task_control: &TaskControl,
+    source_dir: &Path,
+    dest_dir: &Path,
+    xplane_path: &Path,
+    folder_names: Option<&[String]>,
+    mode: SceneryImportMode,
+) -> Result<SceneryImportResult> {
+    let mut result = SceneryImportResult::default();
+    let mut pending = Vec::new();
+    for entry in select_entries(source_dir, folder_names, &mut result.failed)? {
+        if destination_taken(dest_dir, &entry.name) {
+            result.skipped_existing.push(entry.name);
+        } else {
+            pending.push(entry);
+        }
+    }
+
+    let mut progress = ImportProgress::new(app_handle, pending.len());
+    progress.emit(InstallPhase::Calculating, None, true);
+    let mut sizes = Vec::with_capacity(pending.len());
+    for entry in &pending {
+        if task_control.is_cancelled() {
+            result.cancelled = true;
+            return Ok(result);
+        }
+        let copies = mode != SceneryImportMode::Link && entry.kind == SceneryEntryKind::Directory;
+        // Sizes only drive progress, so unreadable folders just count as empty
+        let size = if copies {
+            tree_stats(&entry.target_path)
+                .map(|(_, bytes)| bytes)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        sizes.push(size);
+    }
+    progress.total_bytes = sizes.iter().sum();
+
+    for (index, (entry, size)) in pending.iter().zip(sizes).enumerate() {
+        if task_control.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+        progress.start_task(index, &entry.name, size);
+
+        // Classified before a move takes the source away
+        let category = scenery_classifier::classify_scenery(&entry.target_path, xplane_path)
+            .map(|info| info.category)
+            .unwrap_or(SceneryCategory::Unrecognized);
+
+        match transfer_package(
+            entry,
+            dest_dir,
+            mode,
+            &mut progress,
+            task_control,
+            &mut result.warnings,
+        ) {
+            Ok(transferred_bytes) => {
+                progress.finish_task();
+                result.imported.push(ImportedSceneryPackage {
+                    folder_name: entry.name.clone(),
+                    category,
+                    transferred_bytes,
+                });
+            }
+            Err(_) if task_control.is_cancelled() => {
+                result.cancelled = true;
+                break;
+            }
+            Err(e) => {
+                logger::log_error(
+                    &format!("Failed to import scenery {}: {:#}", entry.name, e),
+                    Some("scenery_import"),
+                );
+                result.failed.push(SceneryImportFailure {
+                    folder_name: entry.name.clone(),
+                    message: format!("{:#}", e),
+                });
+            }
+        }
+    }
+
+    progress.task_index = pending.len();
+    progress.emit(InstallPhase::Finalizing, None, true);
+    Ok(result)
+}
+
+/// Canonical form of `path`, for comparing folders reached through links
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Copy, move or link packages of the external Custom Scenery folder
+/// `source_dir` into the Custom Scenery of `xplane_path`, then update the
+/// index and scenery_packs.ini. With `folder_names` only those packages are
+/// imported.
+pub async fn import_scenery_from_external(
+    app_handle: Option<AppHandle>,
+    db: &DatabaseConnection,
+    task_control: TaskControl,
+    source_dir: &Path,
+    xplane_path: &Path,
+    folder_names: Option<Vec<String>>,
+    mode: SceneryImportMode,
+) -> ApiResult<SceneryImportResult> {
+    if !source_dir.is_dir() {
+        return Err(ApiError::validation(format!(
+            "Source folder does not exist: {}",
+            source_dir.display()
+        )));
+    }
+    let dest_dir = xplane_path.join("Custom Scenery");
+    fs::create_dir_all(&dest_dir).map_err(|e| {
+        ApiError::internal(format!("Failed to create '{}': {}", dest_dir.display(), e))
+    })?;
+    if canonical(source_dir) == canonical(&dest_dir) {
+        return Err(ApiError::validation(
+            "Source folder is this installation's Custom Scenery folder",
+        ));
+    }
+
+    // Erasing app data and the like wait for this the same way as for installs
+    let _active_import = task_control.begin_install();
+    let source = source_dir.to_path_buf();
+    let xplane = xplane_path.to_path_buf();
+    let mut result = tokio::task::spawn_blocking(move || {
+        run_import(
+            app_handle.as_ref(),
+            &task_control,
+            &source,
+            &dest_dir,
+            &xplane,
+            folder_names.as_deref(),
+            mode,
+        )
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))??;
+
+    if !result.imported.is_empty() {
+        // The packages are in place already, so a failed update is only reported
+        let manager = SceneryPacksManager::new(xplane_path, db.clone());
+        if let Err(e) = manager.sync_with_folder().await {
+            logger::log_error(
+                &format!("Failed to update scenery index after import: {}", e),
+                Some("scenery_import"),
+            );
+            result.warnings.push(format!(
+                "Scenery index and scenery_packs.ini were not updated: {}",
+                e
+            ));
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn make_package(root: &Path, name: &str) {
+        let dir = root.join(name).join("Earth nav data");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("+40-080.dsf"), vec![7u8; 64]).unwrap();
+        fs::write(root.join(name).join("library.txt"), b"A\n800\nLIBRARY\n").unwrap();
+    }
+
+    #[test]
+    fn test_copy_skips_existing_and_reports_missing() {
+        let temp = tempdir().unwrap();
+        let source = temp.path().join("old/Custom Scenery");
+        let xplane = temp.path().join("X-Plane 12");
+        let dest = xplane.join("Custom Scenery");
+        fs::create_dir_all(&dest).unwrap();
+        make_package(&source, "Mesh A");
+        make_package(&source, "Mesh B");
+        make_package(&dest, "Mesh B");
+
+        let names = vec![
+            "Mesh A".to_string(),
+            "Mesh B".to_string(),
+            "Missing".to_string(),
+        ];
+        let result = run_import(
+            None,
+            &TaskControl::new(),
+            &source,
+            &dest,
+            &xplane,
+            Some(&names),
+            SceneryImportMode::Copy,
+        )
+        .unwrap();
+
+        assert_eq!(result.imported.len(), 1);
+        assert_eq!(result.imported[0].folder_name, "Mesh A");
+        assert_eq!(
+            result.imported[0].transferred_bytes,
+            tree_stats(&source.join("Mesh A")).unwrap().1
+        );
+        assert_eq!(result.skipped_existing, vec!["Mesh B".to_string()]);
+        assert_eq!(result.failed[0].folder_name, "Missing");
+        assert!(dest.join("Mesh A/Earth nav data/+40-080.dsf").is_file());
+        assert!(source.join("Mesh A").is_dir());
+        assert!(!dest.join(format!("{}Mesh A", STAGING_PREFIX)).exists());
+    }
+
+    #[test]
+    fn test_move_removes_source_and_cancel_leaves_no_staging() {
+        let temp = tempdir().unwrap();
+        let source = temp.path().join("old");
+        let xplane = temp.path().join("xp");
+        let dest = xplane.join("Custom Scenery");
+        fs::create_dir_all(&dest).unwrap();
+        make_package(&source, "Overlay");
+
+        let result = run_import(
+            None,
+            &TaskControl::new(),
+            &source,
+            &dest,
+            &xplane,
+            None,
+            SceneryImportMode::Move,
+        )
+        .unwrap();
+        assert_eq!(result.imported.len(), 1);
+        assert!(!source.join("Overlay").exists());
+        assert!(dest.join("Overlay/library.txt").is_file());
+
+        make_package(&source, "Cancelled");
+        let task_control = TaskControl::new();
+        task_control.request_cancel_all();
+        let mut progress = ImportProgress::new(None, 1);
+        let copy = copy_package(
+            &source.join("Cancelled"),
+            &dest,
+            "Cancelled",
+            &mut progress,
+            &task_control,
+        );
+        assert!(copy.is_err());
+        assert!(!dest.join(format!("{}Cancelled", STAGING_PREFIX)).exists());
+        assert!(!dest.join("Cancelled").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_follows_inner_links_once() {
+        let temp = tempdir().unwrap();
+        let source = temp.path().join("old");
+        let dest = temp.path().join("xp/Custom Scenery");
+        fs::create_dir_all(&dest).unwrap();
+        make_package(&source, "Linked");
+        let package = source.join("Linked");
+        std::os::unix::fs::symlink(&package, package.join("loop")).unwrap();
+        std::os::unix::fs::symlink(package.join("Earth nav data"), package.join("dsf")).unwrap();
+
+        let stats = tree_stats(&package).unwrap();
+        assert_eq!(stats, (2, 64 + 14));
+
+        let mut progress = ImportProgress::new(None, 1);
+        let bytes = copy_package(
+            &package,
+            &dest,
+            "Linked",
+            &mut progress,
+            &TaskControl::new(),
+        )
+        .unwrap();
+        assert_eq!(bytes, stats.1);
+        assert_eq!(tree_stats(&dest.join("Linked")).unwrap(), stats);
+    }
+}
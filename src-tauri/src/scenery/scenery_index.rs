@@ -3,15 +3,22 @@
 //! This module manages a persistent SQLite database of scenery classifications
 //! with cache invalidation based on directory modification times.
 
+use crate::analysis_suppressions;
 use crate::database::{SceneryQueries, CURRENT_SCHEMA_VERSION};
 use crate::logger;
 use crate::management_index::read_version_from_paths;
 use crate::models::{
-    is_global_airports_folder_name, SceneryCategory, SceneryIndex, SceneryIndexScanResult,
-    SceneryIndexStats, SceneryIndexStatus, SceneryManagerData, SceneryManagerEntry,
-    SceneryPackageInfo, GLOBAL_AIRPORTS_ENTRY_NAME,
+    is_global_airports_folder_name, BrokenSceneryEntry, LibraryDependent, NewlyMissingLibrary,
+    PackageLibraryDependency, SceneryCategory, SceneryDeletionPreview, SceneryIndex,
+    SceneryIndexScanResult, SceneryIndexStats, SceneryIndexStatus, SceneryLinkKind,
+    SceneryManagerData, SceneryManagerEntry, SceneryManagerFilter, SceneryManagerPage,
+    SceneryManagerSort, SceneryManagerSummary, SceneryPackageInfo, GLOBAL_AIRPORTS_ENTRY_NAME,
 };
-use crate::scenery_classifier::classify_scenery;
+use crate::path_utils;
+use crate::scenery_classifier::{classify_scenery, detect_continent, CLASSIFIER_VERSION};
+use crate::scenery_packs_manager::GlobalAirportsState;
+use crate::scenery_stats_history;
+use crate::task_control::TaskControl;
 use anyhow::{anyhow, Result};
 use rayon::prelude::*;
 use sea_orm::DatabaseConnection;
@@ -19,10 +26,88 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
 type AirportCoords = HashMap<(i32, i32), Vec<(String, Option<String>)>>;
 
+/// Largest page returned by [`SceneryIndexManager::get_manager_page`]
+const MAX_MANAGER_PAGE_SIZE: u64 = 1000;
+
+/// Event carrying [`SceneryIndexProgress`] while the index is rebuilt
+pub const SCENERY_INDEX_PROGRESS_EVENT: &str = "scenery-index-progress";
+
+/// Minimum time between two progress events of a rebuild
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+pub type SceneryIndexProgressCallback = Arc<dyn Fn(SceneryIndexProgress) + Send + Sync>;
+
+/// Threads classifying packages during a rebuild. Classification mostly waits
+/// on the filesystem, so twice the core count keeps the disk busy. Debug
+/// logging classifies one package at a time so the logs stay in order.
+fn classification_threads() -> usize {
+    if logger::is_debug_enabled() {
+        return 1;
+    }
+    std::thread::available_parallelism()
+        .map(|parallelism| parallelism.get())
+        .unwrap_or(4)
+        * 2
+}
+
+/// Progress of the classification workers, reported at most every
+/// [`PROGRESS_INTERVAL`]
+struct RebuildProgress {
+    total: usize,
+    processed: AtomicUsize,
+    last_emit: Mutex<Instant>,
+    callback: Option<SceneryIndexProgressCallback>,
+}
+
+impl RebuildProgress {
+    fn new(total: usize, callback: Option<SceneryIndexProgressCallback>) -> Self {
+        Self {
+            total,
+            processed: AtomicUsize::new(0),
+            last_emit: Mutex::new(Instant::now()),
+            callback,
+        }
+    }
+
+    fn package_done(&self, folder: &Path) {
+        let processed = self.processed.fetch_add(1, Ordering::Relaxed) + 1;
+        let Some(callback) = &self.callback else {
+            return;
+        };
+        // Another worker is reporting right now
+        let Ok(mut last_emit) = self.last_emit.try_lock() else {
+            return;
+        };
+        if last_emit.elapsed() < PROGRESS_INTERVAL {
+            return;
+        }
+        *last_emit = Instant::now();
+        callback(SceneryIndexProgress {
+            processed,
+            total: self.total,
+            current_folder: folder
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string()),
+        });
+    }
+
+    fn finish(&self) {
+        if let Some(callback) = &self.callback {
+            callback(SceneryIndexProgress {
+                processed: self.total,
+                total: self.total,
+                current_folder: None,
+            });
+        }
+    }
+}
+
 // ============================================================================
 // Windows Shortcut Resolution (COM API)
 // ============================================================================
@@ -158,11 +243,7 @@ mod shortcut_resolver {
 
         fn load(&self, path: &Path) -> bool {
             unsafe {
-                let wide_path: Vec<u16> = path
-                    .as_os_str()
-                    .encode_wide()
-                    .chain(std::iter::once(0))
-                    .collect();
+                let wide_path = to_wide(path);
                 let hr = (*self.ptr).Load(wide_path.as_ptr(), 0);
                 if hr != S_OK {
                     logger::log_info(
@@ -173,6 +254,27 @@ mod shortcut_resolver {
                 hr == S_OK
             }
         }
+
+        fn save(&self, path: &Path) -> bool {
+            unsafe {
+                let wide_path = to_wide(path);
+                let hr = (*self.ptr).Save(wide_path.as_ptr(), 1);
+                if hr != S_OK {
+                    logger::log_info(
+                        &format!("  Failed to save shortcut file, HRESULT: 0x{:08X}", hr),
+                        Some("scenery_index"),
+                    );
+                }
+                hr == S_OK
+            }
+        }
+    }
+
+    fn to_wide(path: &Path) -> Vec<u16> {
+        path.as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect()
     }
 
     impl Drop for PersistFileGuard {
@@ -200,9 +302,8 @@ mod shortcut_resolver {
                     &format!("  Shortcut target (COM API): {:?}", target_str),
                     Some("scenery_index"),
                 );
-                let path = PathBuf::from(target_str);
-                if path.exists() && path.is_dir() {
-                    return Some(path);
+                if !target_str.is_empty() {
+                    return Some(PathBuf::from(target_str));
                 }
             } else {
                 logger::log_info(
@@ -214,8 +315,8 @@ mod shortcut_resolver {
         }
     }
 
-    /// Resolve a Windows shortcut (.lnk) to its target path
-    pub fn resolve(lnk_path: &Path) -> Option<PathBuf> {
+    /// Read the target a Windows shortcut (.lnk) points at, whether or not it exists
+    pub fn target(lnk_path: &Path) -> Option<PathBuf> {
         let _com = ComGuard::new()?;
         let shell_link = ShellLinkGuard::new()?;
         let persist_file = PersistFileGuard::from_shell_link(&shell_link)?;
@@ -226,19 +327,212 @@ mod shortcut_resolver {
 
         get_shell_link_target(&shell_link)
     }
+
+    /// Write a Windows shortcut (.lnk) pointing at `target`
+    pub fn create(target: &Path, lnk_path: &Path) -> std::result::Result<(), String> {
+        let _com = ComGuard::new().ok_or("Failed to initialize COM")?;
+        let shell_link = ShellLinkGuard::new().ok_or("Failed to create shell link")?;
+        let persist_file =
+            PersistFileGuard::from_shell_link(&shell_link).ok_or("Failed to create shell link")?;
+
+        let wide_target = to_wide(target);
+        let hr = unsafe { (*shell_link.as_ptr()).SetPath(wide_target.as_ptr()) };
+        if hr != S_OK {
+            return Err(format!(
+                "Failed to set shortcut target, HRESULT: 0x{:08X}",
+                hr
+            ));
+        }
+        if !persist_file.save(lnk_path) {
+            return Err(format!("Failed to write shortcut {:?}", lnk_path));
+        }
+        Ok(())
+    }
 }
 
-/// Resolve Windows shortcut (.lnk) to actual path using Windows COM API
+/// Read the target stored in a Windows shortcut (.lnk). Uses the COM API and
+/// falls back to parsing the shell link file when COM is unavailable.
 #[cfg(windows)]
-fn resolve_shortcut(lnk_path: &Path) -> Option<PathBuf> {
-    shortcut_resolver::resolve(lnk_path)
+fn shortcut_target(lnk_path: &Path) -> Option<PathBuf> {
+    shortcut_resolver::target(lnk_path).or_else(|| {
+        let link = lnk::ShellLink::open(lnk_path).ok()?;
+        let target = link.link_info().as_ref()?.local_base_path().clone()?;
+        logger::log_info(
+            &format!("  Shortcut target (shell link file): {:?}", target),
+            Some("scenery_index"),
+        );
+        Some(PathBuf::from(target))
+    })
 }
 
 #[cfg(not(windows))]
-fn resolve_shortcut(_lnk_path: &Path) -> Option<PathBuf> {
+fn shortcut_target(_lnk_path: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Resolve a Windows shortcut (.lnk) to the folder it points at
+fn resolve_shortcut(lnk_path: &Path) -> Option<PathBuf> {
+    shortcut_target(lnk_path).filter(|target| target.is_dir())
+}
+
+/// Create a Windows shortcut (.lnk) at `lnk_path` pointing at the `target` folder
+#[cfg(windows)]
+pub fn create_shortcut(target: &Path, lnk_path: &Path) -> std::result::Result<(), String> {
+    shortcut_resolver::create(target, lnk_path)
+}
+
+#[cfg(not(windows))]
+pub fn create_shortcut(_target: &Path, _lnk_path: &Path) -> std::result::Result<(), String> {
+    Err("Windows shortcuts can only be created on Windows".to_string())
+}
+
+/// How an entry of Custom Scenery is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneryEntryKind {
+    Directory,
+    /// Symlink, or junction on Windows, to a folder
+    Link,
+    /// Windows `.lnk` shortcut to a folder
+    Shortcut,
+}
+
+/// An entry of Custom Scenery and the folder it resolves to
+#[derive(Debug, Clone)]
+pub struct SceneryEntryLocation {
+    /// Index key: the folder or link name, or the shortcut name without `.lnk`
+    pub name: String,
+    /// The entry itself (folder, link or `.lnk` file)
+    pub entry_path: PathBuf,
+    /// Folder the scenery is read from
+    pub target_path: PathBuf,
+    pub kind: SceneryEntryKind,
+}
+
+/// Resolve one path inside Custom Scenery. Returns `None` for files, broken
+/// links and shortcuts that can't be resolved, which the index skips too.
+pub fn resolve_scenery_entry(path: &Path) -> Option<SceneryEntryLocation> {
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+    {
+        let target = resolve_shortcut(path)?;
+        return Some(SceneryEntryLocation {
+            name: path.file_stem()?.to_string_lossy().into_owned(),
+            entry_path: path.to_path_buf(),
+            target_path: target,
+            kind: SceneryEntryKind::Shortcut,
+        });
+    }
+
+    // `metadata` follows links, so links to folders count as folders
+    if !fs::metadata(path).is_ok_and(|m| m.is_dir()) {
+        return None;
+    }
+    let is_link = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    Some(SceneryEntryLocation {
+        name: path.file_name()?.to_string_lossy().into_owned(),
+        entry_path: path.to_path_buf(),
+        target_path: path.to_path_buf(),
+        kind: if is_link {
+            SceneryEntryKind::Link
+        } else {
+            SceneryEntryKind::Directory
+        },
+    })
+}
+
+/// All scenery entries of a Custom Scenery folder, which may itself be a link
+pub fn list_scenery_entries(
+    custom_scenery_path: &Path,
+) -> std::io::Result<Vec<SceneryEntryLocation>> {
+    Ok(fs::read_dir(custom_scenery_path)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let path = e.path();
+            let location = resolve_scenery_entry(&path);
+            if location.is_none()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+            {
+                logger::log_info(
+                    &format!("✗ Failed to resolve shortcut: {:?}", path),
+                    Some("scenery_index"),
+                );
+            }
+            location
+        })
+        .collect())
+}
+
+/// Links and shortcuts in a Custom Scenery folder whose target is missing or
+/// can't be read. The index skips them, so they are listed separately.
+pub fn list_broken_scenery_entries(custom_scenery_path: &Path) -> Vec<BrokenSceneryEntry> {
+    let Ok(entries) = fs::read_dir(custom_scenery_path) else {
+        return Vec::new();
+    };
+
+    let mut broken: Vec<BrokenSceneryEntry> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let path = e.path();
+            if resolve_scenery_entry(&path).is_some() {
+                return None;
+            }
+            let entry_name = e.file_name().to_string_lossy().into_owned();
+            if path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
+            {
+                return Some(BrokenSceneryEntry {
+                    entry_name,
+                    target_path: shortcut_target(&path).map(|t| t.to_string_lossy().into_owned()),
+                    kind: SceneryLinkKind::Shortcut,
+                });
+            }
+            if fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_symlink())
+                && fs::metadata(&path).is_err()
+            {
+                return Some(BrokenSceneryEntry {
+                    entry_name,
+                    target_path: fs::read_link(&path)
+                        .ok()
+                        .map(|t| t.to_string_lossy().into_owned()),
+                    kind: SceneryLinkKind::Link,
+                });
+            }
+            None
+        })
+        .collect();
+    broken.sort_by(|a, b| a.entry_name.cmp(&b.entry_name));
+    broken
+}
+
+/// Find the entry the index knows as `name`: a folder or link of that name, or
+/// a `<name>.lnk` shortcut. `name` may be in either Unicode normalization; the
+/// entry is returned under its name on disk.
+pub fn find_scenery_entry(custom_scenery_path: &Path, name: &str) -> Option<SceneryEntryLocation> {
+    // ASCII names have a single normalization, so only others need the lookup
+    let on_disk = |name: String| {
+        if name.is_ascii() {
+            name
+        } else {
+            path_utils::on_disk_name(custom_scenery_path, &name).unwrap_or(name)
+        }
+    };
+    let folder_name = on_disk(name.to_string());
+    resolve_scenery_entry(&custom_scenery_path.join(&folder_name))
+        .filter(|location| location.name == folder_name)
+        .or_else(|| {
+            resolve_scenery_entry(&custom_scenery_path.join(on_disk(format!("{}.lnk", name))))
+        })
+}
+
+/// Key locked folder names are compared by: trimmed, case-insensitive, in NFC
+fn locked_name_key(name: &str) -> String {
+    path_utils::normalize_name(&name.trim().to_lowercase())
+}
+
 fn is_sam_folder_name(folder_name: &str) -> bool {
     let folder_lower = folder_name.to_lowercase();
 
@@ -279,6 +573,51 @@ fn read_scenery_update_url(folder_path: &Path) -> Option<String> {
     update_url
 }
 
+/// Build the scenery manager entry for an indexed package
+fn manager_entry(
+    info: &SceneryPackageInfo,
+    update_url: Option<String>,
+    duplicate_tiles: Vec<String>,
+    duplicate_airports: Vec<String>,
+) -> SceneryManagerEntry {
+    SceneryManagerEntry {
+        folder_name: info.folder_name.clone(),
+        category: info.category.clone(),
+        sub_priority: info.sub_priority,
+        enabled: info.enabled,
+        sort_order: info.sort_order,
+        update_url,
+        missing_libraries: info.missing_libraries.clone(),
+        required_libraries: info.required_libraries.clone(),
+        continent: info.continent.clone(),
+        duplicate_tiles,
+        duplicate_airports,
+        airport_id: info.airport_id.clone(),
+        original_category: info.original_category.clone(),
+        pinned: info.pinned,
+    }
+}
+
+/// Build the synthetic Global Airports entry
+fn global_airports_entry(state: &GlobalAirportsState) -> SceneryManagerEntry {
+    SceneryManagerEntry {
+        folder_name: GLOBAL_AIRPORTS_ENTRY_NAME.to_string(),
+        category: state.category.clone(),
+        sub_priority: 0,
+        enabled: state.enabled,
+        sort_order: state.sort_order,
+        update_url: None,
+        missing_libraries: Vec::new(),
+        required_libraries: Vec::new(),
+        continent: None,
+        duplicate_tiles: Vec::new(),
+        duplicate_airports: Vec::new(),
+        airport_id: None,
+        original_category: None,
+        pinned: false,
+    }
+}
+
 /// Common sorting comparison for non-FixedHighPriority scenery packages
 /// This ensures consistent ordering between rebuild_index, recalculate_sort_order, and reset_sort_order
 fn compare_packages_for_sorting(
@@ -315,7 +654,9 @@ fn compare_packages_for_sorting(
             if info_a.category == info_b.category
                 && matches!(
                     info_a.category,
-                    SceneryCategory::AirportMesh | SceneryCategory::Mesh
+                    SceneryCategory::AirportMesh
+                        | SceneryCategory::OrthoTile
+                        | SceneryCategory::Mesh
                 )
             {
                 // For Mesh category with sub_priority > 0 (XPME), sort only by folder name
@@ -558,6 +899,8 @@ fn sort_packages_with_special_rules(
 /// Manager for scenery index operations
 pub struct SceneryIndexManager {
     xplane_path: PathBuf,
+    /// Normalized X-Plane root scoping this manager's rows in the shared database
+    install_key: String,
     db: DatabaseConnection,
 }
 
@@ -566,27 +909,40 @@ impl SceneryIndexManager {
     pub fn new(xplane_path: &Path, db: DatabaseConnection) -> Self {
         Self {
             xplane_path: xplane_path.to_path_buf(),
+            install_key: path_utils::normalize_xplane_key(xplane_path),
             db,
         }
     }
 
+    /// Adopt rows indexed before installations were told apart, so upgrading
+    /// users keep their index for the first installation they open
+    async fn adopt_unscoped_rows(&self) -> Result<()> {
+        SceneryQueries::adopt_unscoped_rows(&self.db, &self.install_key)
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+        Ok(())
+    }
+
+    async fn has_packages(&self) -> Result<bool> {
+        self.adopt_unscoped_rows().await?;
+        SceneryQueries::has_packages(&self.db, &self.install_key)
+            .await
+            .map_err(|e| anyhow!("{}", e))
+    }
+
     /// Check if the scenery index has been created (has any packages)
     /// Returns true if there are packages in the index, false otherwise
     pub async fn has_index(&self) -> Result<bool> {
-        SceneryQueries::has_packages(&self.db)
-            .await
-            .map_err(|e| anyhow!("{}", e))
+        self.has_packages().await
     }
 
     /// Load index from database or create new empty index
     pub async fn load_index(&self) -> Result<SceneryIndex> {
         // Check if database has any packages
-        let has_packages = SceneryQueries::has_packages(&self.db)
-            .await
-            .map_err(|e| anyhow!("{}", e))?;
+        let has_packages = self.has_packages().await?;
 
         if has_packages {
-            SceneryQueries::load_all(&self.db)
+            SceneryQueries::load_all(&self.db, &self.install_key)
                 .await
                 .map_err(|e| anyhow!("{}", e))
         } else {
@@ -596,38 +952,50 @@ impl SceneryIndexManager {
 
     /// Save index to database
     pub async fn save_index(&self, index: &SceneryIndex) -> Result<()> {
-        SceneryQueries::save_all(&self.db, index)
+        SceneryQueries::save_all(&self.db, &self.install_key, index)
             .await
             .map_err(|e| anyhow!("{}", e))
     }
 
     /// Update or add a single package in the index
     pub async fn update_package(&self, package_info: SceneryPackageInfo) -> Result<()> {
-        SceneryQueries::update_package(&self.db, &package_info)
+        SceneryQueries::update_package(&self.db, &self.install_key, &package_info)
             .await
             .map_err(|e| anyhow!("{}", e))
     }
 
-    /// Rebuild entire index by scanning all scenery packages
-    /// This completely clears the existing index and rebuilds from scratch
-    pub async fn rebuild_index(&self) -> Result<SceneryIndex> {
+    /// Rebuild entire index by scanning all scenery packages.
+    /// Packages are classified into a fresh in-memory index that replaces the
+    /// stored one in a single transaction at the end, so a failed or cancelled
+    /// rebuild leaves the previous index in place. Pinned entries stay pinned
+    /// at their previous slot.
+    pub async fn rebuild_index(
+        &self,
+        task_control: Option<TaskControl>,
+        on_progress: Option<SceneryIndexProgressCallback>,
+    ) -> Result<SceneryIndex> {
         let custom_scenery_path = self.xplane_path.join("Custom Scenery");
         if !custom_scenery_path.exists() {
             return Err(anyhow!("Custom Scenery folder not found"));
         }
 
-        // Clear all existing index data for a fresh rebuild
-        SceneryQueries::clear_all(&self.db)
-            .await
-            .map_err(|e| anyhow!("{}", e))?;
+        // Legacy rows are adopted first so the rebuild replaces them rather
+        // than leaving them behind
+        self.adopt_unscoped_rows().await?;
 
-        logger::log_info(
-            "Cleared existing index, starting fresh rebuild",
-            Some("scenery_index"),
-        );
+        // Pinned entries keep their pin and their slot through the rebuild
+        let pinned_orders: HashMap<String, usize> = self
+            .load_index()
+            .await?
+            .packages
+            .values()
+            .filter(|info| info.pinned)
+            .map(|info| (locked_name_key(&info.folder_name), info.sort_order as usize))
+            .collect();
 
         let xplane_path = self.xplane_path.clone();
         let custom_scenery_path = custom_scenery_path.clone();
+        let blocking_control = task_control.clone();
 
         let index = tokio::task::spawn_blocking(move || -> Result<SceneryIndex> {
             // Collect all scenery folders (including symlinks and .lnk shortcuts)
@@ -635,61 +1003,19 @@ impl SceneryIndexManager {
             // Key: canonical target path, Value: (shortcut_name without .lnk, normalized_target_path for ini)
             let mut shortcut_target_map: HashMap<PathBuf, (String, String)> = HashMap::new();
 
-            let scenery_folders: Vec<PathBuf> = fs::read_dir(&custom_scenery_path)?
-                .filter_map(|e| e.ok())
-                .filter_map(|e| {
-                    let path = e.path();
-
-                    // Check if it's a .lnk file (Windows shortcut)
-                    if path
-                        .extension()
-                        .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
-                    {
-                        // Use shortcut file name (without .lnk extension) as the entry name
-                        // This prevents conflicts when multiple shortcuts point to folders with the same name
-                        let shortcut_name = path
-                            .file_stem()
-                            .and_then(|s| s.to_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| "<unknown>".to_string());
-                        logger::log_info(
-                            &format!("Attempting to resolve shortcut: {}.lnk", shortcut_name),
-                            Some("scenery_index"),
+            let scenery_folders: Vec<PathBuf> = list_scenery_entries(&custom_scenery_path)?
+                .into_iter()
+                .map(|location| {
+                    if location.kind == SceneryEntryKind::Shortcut {
+                        // Convert backslashes to forward slashes for scenery_packs.ini compatibility
+                        let normalized_path_str =
+                            location.target_path.to_string_lossy().replace('\\', "/");
+                        shortcut_target_map.insert(
+                            location.target_path.clone(),
+                            (location.name, normalized_path_str),
                         );
-
-                        // Try to resolve the shortcut
-                        if let Some(target) = resolve_shortcut(&path) {
-                            logger::log_info(
-                                &format!(
-                                    "✓ Resolved shortcut {}.lnk -> {:?}",
-                                    shortcut_name, target
-                                ),
-                                Some("scenery_index"),
-                            );
-
-                            // Store the mapping from target path to shortcut info
-                            let target_path_str = target.to_string_lossy().to_string();
-                            // Convert backslashes to forward slashes for scenery_packs.ini compatibility
-                            let normalized_path_str = target_path_str.replace('\\', "/");
-                            shortcut_target_map
-                                .insert(target.clone(), (shortcut_name, normalized_path_str));
-
-                            return Some(target);
-                        } else {
-                            logger::log_info(
-                                &format!("✗ Failed to resolve shortcut: {:?}", path),
-                                Some("scenery_index"),
-                            );
-                            return None;
-                        }
                     }
-
-                    // Check if it's a directory (including symlinks)
-                    if path.metadata().map(|m| m.is_dir()).unwrap_or(false) {
-                        return Some(path);
-                    }
-
-                    None
+                    location.target_path
                 })
                 .collect();
 
@@ -703,28 +1029,23 @@ impl SceneryIndexManager {
 
             // Classify all packages
             // Track which path each package came from to correctly handle shortcuts
-            // Use sequential processing in debug log mode for ordered logs, parallel otherwise
-            let packages_with_paths: Vec<(PathBuf, SceneryPackageInfo)> =
-                if logger::is_debug_enabled() {
-                    // Sequential processing for ordered debug logs
-                    scenery_folders
-                        .iter()
-                        .filter_map(|folder| match classify_scenery(folder, &xplane_path) {
-                            Ok(info) => Some((folder.clone(), info)),
-                            Err(e) => {
-                                logger::log_info(
-                                    &format!("Failed to classify {:?}: {}", folder, e),
-                                    Some("scenery_index"),
-                                );
-                                None
-                            }
-                        })
-                        .collect()
-                } else {
-                    // Parallel processing for better performance when not in debug mode
-                    scenery_folders
-                        .par_iter()
-                        .filter_map(|folder| match classify_scenery(folder, &xplane_path) {
+            let progress = RebuildProgress::new(scenery_folders.len(), on_progress);
+            let is_cancelled = || {
+                blocking_control
+                    .as_ref()
+                    .is_some_and(|tc| tc.is_cancelled())
+            };
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(classification_threads())
+                .build()?;
+            let packages_with_paths: Vec<(PathBuf, SceneryPackageInfo)> = pool.install(|| {
+                scenery_folders
+                    .par_iter()
+                    .filter_map(|folder| {
+                        if is_cancelled() {
+                            return None;
+                        }
+                        let classified = match classify_scenery(folder, &xplane_path) {
                             Ok(info) => Some((folder.clone(), info)),
                             Err(e) => {
                                 logger::log_info(
@@ -733,9 +1054,16 @@ impl SceneryIndexManager {
                                 );
                                 None
                             }
-                        })
-                        .collect()
-                };
+                        };
+                        progress.package_done(folder);
+                        classified
+                    })
+                    .collect()
+            });
+            if is_cancelled() {
+                return Err(anyhow!("Scenery index rebuild cancelled"));
+            }
+            progress.finish();
 
             // Post-process: Set folder_name and actual_path for shortcut entries
             // For shortcuts, use the shortcut name (not target folder name) to avoid conflicts
@@ -762,6 +1090,10 @@ impl SceneryIndexManager {
 
             // Assign sort_order and set default enabled state
             // Fresh rebuild: Unrecognized packages default to disabled, others default to enabled
+            let sorted_names: Vec<String> = packages_vec
+                .iter()
+                .map(|info| info.folder_name.clone())
+                .collect();
             let packages: HashMap<String, SceneryPackageInfo> = packages_vec
                 .into_iter()
                 .enumerate()
@@ -773,16 +1105,40 @@ impl SceneryIndexManager {
                 })
                 .collect();
 
-            Ok(SceneryIndex {
+            let mut index = SceneryIndex {
                 version: CURRENT_SCHEMA_VERSION as u32,
                 packages,
                 last_updated: SystemTime::now(),
-            })
+            };
+            let pinned_names: Vec<String> = sorted_names
+                .iter()
+                .filter(|name| pinned_orders.contains_key(&locked_name_key(name)))
+                .cloned()
+                .collect();
+            for name in &pinned_names {
+                if let Some(info) = index.packages.get_mut(name) {
+                    info.pinned = true;
+                }
+            }
+            Self::apply_sort_order_with_locked_slots(
+                &mut index,
+                &sorted_names,
+                &pinned_names,
+                Some(&pinned_orders),
+            );
+            Self::fill_missing_libraries(&mut index, &xplane_path);
+            Ok(index)
         })
         .await
         .map_err(|e| anyhow!("Blocking task failed: {}", e))??;
 
-        self.save_index(&index).await?;
+        if task_control.is_some_and(|tc| tc.is_cancelled()) {
+            return Err(anyhow!("Scenery index rebuild cancelled"));
+        }
+
+        SceneryQueries::replace_all(&self.db, &self.install_key, &index)
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
         logger::log_info(
             &format!(
                 "Scenery index rebuilt with {} packages",
@@ -791,21 +1147,34 @@ impl SceneryIndexManager {
             Some("scenery_index"),
         );
 
-        // Update missing libraries for all packages using the complete index
-        let index = self.update_missing_libraries(index).await?;
+        scenery_stats_history::record_snapshot_if_due(&self.db, &self.xplane_path).await;
 
         Ok(index)
     }
 
     /// Update missing libraries for all packages using the complete index
     async fn update_missing_libraries(&self, mut index: SceneryIndex) -> Result<SceneryIndex> {
+        Self::fill_missing_libraries(&mut index, &self.xplane_path);
+
+        // Save the updated index
+        self.save_index(&index).await?;
+        logger::log_info(
+            "Missing libraries updated for all packages",
+            Some("scenery_index"),
+        );
+
+        Ok(index)
+    }
+
+    /// Set each package's missing libraries from the libraries the index exports
+    fn fill_missing_libraries(index: &mut SceneryIndex, xplane_path: &Path) {
         logger::log_info(
             "Updating missing libraries for all packages...",
             Some("scenery_index"),
         );
 
         // Build library index from the complete scenery index
-        let library_index = build_library_index_from_scenery_index(&index);
+        let library_index = build_library_index_from_scenery_index(index);
 
         // Update each package's missing_libraries
         for (folder_name, package_info) in index.packages.iter_mut() {
@@ -818,7 +1187,7 @@ impl SceneryIndexManager {
                 }
 
                 // Check if this is a subdirectory within the current scenery package
-                let scenery_path = self.xplane_path.join("Custom Scenery").join(folder_name);
+                let scenery_path = xplane_path.join("Custom Scenery").join(folder_name);
                 let subdir_path = scenery_path.join(lib_name);
                 if subdir_path.exists() && subdir_path.is_dir() {
                     continue;
@@ -832,15 +1201,6 @@ impl SceneryIndexManager {
 
             package_info.missing_libraries = missing;
         }
-
-        // Save the updated index
-        self.save_index(&index).await?;
-        logger::log_info(
-            "Missing libraries updated for all packages",
-            Some("scenery_index"),
-        );
-
-        Ok(index)
     }
 
     /// Recalculate sort_order for all packages using the same sorting logic as rebuild_index
@@ -899,12 +1259,13 @@ impl SceneryIndexManager {
         }
 
         let index = self.load_index().await?;
+        let locked_folder_names = Self::with_pinned_entries(&index, locked_folder_names);
         let xplane_path = self.xplane_path.clone();
         let custom_scenery_path = custom_scenery_path.clone();
         let locked_folder_names_for_sort = locked_folder_names.clone();
         let locked_name_set: HashSet<String> = locked_folder_names
             .iter()
-            .map(|name| name.trim().to_lowercase())
+            .map(|name| locked_name_key(name))
             .filter(|name| !name.is_empty())
             .collect();
 
@@ -918,48 +1279,46 @@ impl SceneryIndexManager {
             // Get current scenery folders (including symlinks and .lnk shortcuts)
             // Key: entry name (shortcut name for shortcuts, folder name for directories)
             // Value: actual path to scan
-            let current_folders: HashMap<String, PathBuf> = fs::read_dir(&custom_scenery_path)?
-                .filter_map(|e| e.ok())
-                .filter_map(|e| {
-                    let path = e.path();
-
-                    // Check if it's a .lnk file (Windows shortcut)
-                    if path
-                        .extension()
-                        .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"))
-                    {
-                        // Try to resolve the shortcut
-                        if let Some(target) = resolve_shortcut(&path) {
-                            // Use shortcut file name (without .lnk) as the entry name
-                            // This prevents conflicts when multiple shortcuts point to folders with the same name
-                            let shortcut_name = path
-                                .file_stem()
-                                .and_then(|s| s.to_str())
-                                .map(|s| s.to_string())
-                                .unwrap_or_else(|| "<unknown>".to_string());
+            let current_folders: HashMap<String, PathBuf> =
+                list_scenery_entries(&custom_scenery_path)?
+                    .into_iter()
+                    .map(|location| {
+                        if location.kind == SceneryEntryKind::Shortcut {
                             // Track the resolved target path for writing to scenery_packs.ini
-                            let target_path_str = target.to_string_lossy().to_string();
-                            // Convert backslashes to forward slashes for scenery_packs.ini compatibility
-                            let normalized_path_str = target_path_str.replace('\\', "/");
+                            let normalized_path_str =
+                                location.target_path.to_string_lossy().replace('\\', "/");
                             shortcut_target_map.insert(
-                                target.clone(),
-                                (shortcut_name.clone(), normalized_path_str),
+                                location.target_path.clone(),
+                                (location.name.clone(), normalized_path_str),
                             );
-                            return Some((shortcut_name, target));
                         }
-                        return None;
-                    }
-
-                    // Check if it's a directory (including symlinks)
-                    if path.metadata().map(|m| m.is_dir()).unwrap_or(false) {
-                        if let Some(name) = e.file_name().to_str() {
-                            return Some((name.to_string(), path));
-                        }
-                    }
+                        (location.name, location.target_path)
+                    })
+                    .collect();
 
-                    None
+            // Packages indexed under another Unicode normalization of their folder
+            // name (an index carried over from another system) move to the name on
+            // disk instead of being dropped and classified again
+            let renamed: Vec<(String, String)> = index
+                .packages
+                .keys()
+                .filter(|name| !current_folders.contains_key(*name))
+                .filter_map(|name| {
+                    current_folders
+                        .keys()
+                        .find(|current| {
+                            !index.packages.contains_key(*current)
+                                && path_utils::names_match(current, name)
+                        })
+                        .map(|current| (name.clone(), current.clone()))
                 })
                 .collect();
+            for (old_name, new_name) in renamed {
+                if let Some(mut info) = index.packages.remove(&old_name) {
+                    info.folder_name = new_name.clone();
+                    index.packages.insert(new_name, info);
+                }
+            }
 
             // Remove stale entries (deleted folders)
             let stale_keys: Vec<String> = index
@@ -995,6 +1354,19 @@ impl SceneryIndexManager {
                             return true;
                         }
 
+                        // Packages indexed before sizes and file times were recorded
+                        // have no file time yet; re-classify them once so statistics are
+                        // complete. Empty packages carry the folder time and are not retried.
+                        if existing.newest_file_modified.is_none() {
+                            return true;
+                        }
+
+                        // Packages classified by an older classifier revision are re-classified
+                        // once so new categories (e.g. OrthoTile) apply without a full rebuild.
+                        if existing.classifier_version < CLASSIFIER_VERSION {
+                            return true;
+                        }
+
                         // Compare modification times
                         if let Ok(metadata) = fs::metadata(path) {
                             if let Ok(modified) = metadata.modified() {
@@ -1019,8 +1391,8 @@ impl SceneryIndexManager {
                 let locked_anchor_orders: HashMap<String, usize> = index
                     .packages
                     .iter()
-                    .filter(|(name, _)| locked_name_set.contains(&name.to_lowercase()))
-                    .map(|(name, info)| (name.to_lowercase(), info.sort_order as usize))
+                    .filter(|(name, _)| locked_name_set.contains(&locked_name_key(name)))
+                    .map(|(name, info)| (locked_name_key(name), info.sort_order as usize))
                     .collect();
 
                 // Classify updated packages
@@ -1056,6 +1428,17 @@ impl SceneryIndexManager {
                         info.folder_name = shortcut_name.clone();
                         info.actual_path = Some(actual_path.clone());
                     }
+                    // A lazy re-classification must not undo a manual category move
+                    if let Some(existing) = index.packages.get(&info.folder_name) {
+                        info.pinned = existing.pinned;
+                        let manually_moved = existing
+                            .original_category
+                            .as_ref()
+                            .is_some_and(|original| *original != existing.category);
+                        if existing.classifier_version < CLASSIFIER_VERSION && manually_moved {
+                            info.category = existing.category.clone();
+                        }
+                    }
                     index.packages.insert(info.folder_name.clone(), info);
                 }
 
@@ -1095,6 +1478,33 @@ impl SceneryIndexManager {
                 }
             }
 
+            // Airports without DSF tiles were indexed without a continent before
+            // apt.dat locations were read. Look it up for those rows only, rather
+            // than re-classifying every package after an upgrade.
+            let reclassified: HashSet<&PathBuf> = packages_to_update.iter().collect();
+            let mut backfilled = 0;
+            for (name, info) in index.packages.iter_mut() {
+                if info.continent.is_some() || !(info.has_dsf || info.has_apt_dat) {
+                    continue;
+                }
+                let Some(path) = current_folders
+                    .get(name)
+                    .filter(|path| !reclassified.contains(path))
+                else {
+                    continue;
+                };
+                info.continent = detect_continent(path);
+                if info.continent.is_some() {
+                    backfilled += 1;
+                }
+            }
+            if backfilled > 0 {
+                logger::log_info(
+                    &format!("Found the continent of {} scenery packages", backfilled),
+                    Some("scenery_index"),
+                );
+            }
+
             // Also update actual_path for existing entries that are shortcuts
             // (in case they weren't updated but the shortcut info needs to be preserved)
             for (folder_name, info) in index.packages.iter_mut() {
@@ -1143,10 +1553,40 @@ impl SceneryIndexManager {
         .map_err(|e| anyhow!("Blocking task failed: {}", e))?
     }
 
+    /// Name the index stores `folder_name` under. The frontend may send a name
+    /// in another Unicode normalization than the one read from disk; unknown
+    /// names are returned unchanged.
+    pub async fn indexed_folder_name(&self, folder_name: &str) -> Result<String> {
+        if folder_name.is_ascii() {
+            return Ok(folder_name.to_string());
+        }
+        let names = SceneryQueries::load_folder_names(&self.db, &self.install_key)
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+        Ok(Self::match_folder_name(names.iter(), folder_name))
+    }
+
+    /// `folder_name` itself when among `names`, otherwise the name equal to it
+    /// in NFC, otherwise `folder_name`
+    fn match_folder_name<'a>(
+        names: impl Iterator<Item = &'a String> + Clone,
+        folder_name: &str,
+    ) -> String {
+        if names.clone().any(|name| name == folder_name) {
+            return folder_name.to_string();
+        }
+        names
+            .into_iter()
+            .find(|name| path_utils::names_match(name, folder_name))
+            .cloned()
+            .unwrap_or_else(|| folder_name.to_string())
+    }
+
     /// Get package info from index
     pub async fn get_package(&self, folder_name: &str) -> Result<Option<SceneryPackageInfo>> {
         let index = self.load_index().await?;
-        Ok(index.packages.get(folder_name).cloned())
+        let folder_name = Self::match_folder_name(index.packages.keys(), folder_name);
+        Ok(index.packages.get(&folder_name).cloned())
     }
 
     /// Get or classify a package (uses cache if available and not stale)
@@ -1157,8 +1597,9 @@ impl SceneryIndexManager {
             .ok_or_else(|| anyhow!("Invalid folder name"))?;
 
         // Check if we have a valid cached entry
+        let cached = self.get_package(folder_name).await?;
         if !self.is_package_stale(folder_name, folder_path).await? {
-            if let Some(info) = self.get_package(folder_name).await? {
+            if let Some(info) = cached {
                 return Ok(info);
             }
         }
@@ -1166,16 +1607,18 @@ impl SceneryIndexManager {
         // Classify and update index
         let folder_path = folder_path.to_path_buf();
         let xplane_path = self.xplane_path.clone();
-        let info =
+        let mut info =
             tokio::task::spawn_blocking(move || classify_scenery(&folder_path, &xplane_path))
                 .await
                 .map_err(|e| anyhow!("Blocking task failed: {}", e))??;
+        info.pinned = cached.is_some_and(|existing| existing.pinned);
         self.update_package(info.clone()).await?;
         Ok(info)
     }
 
     pub async fn index_status(&self) -> Result<SceneryIndexStatus> {
-        let total_packages = SceneryQueries::get_package_count(&self.db)
+        self.adopt_unscoped_rows().await?;
+        let total_packages = SceneryQueries::get_package_count(&self.db, &self.install_key)
             .await
             .map_err(|e| anyhow!("{}", e))?;
         let index_exists = total_packages > 0;
@@ -1195,9 +1638,7 @@ impl SceneryIndexManager {
         &self,
         locked_folder_names: Vec<String>,
     ) -> Result<SceneryIndexScanResult> {
-        let has_packages = SceneryQueries::has_packages(&self.db)
-            .await
-            .map_err(|e| anyhow!("{}", e))?;
+        let has_packages = self.has_packages().await?;
 
         if !has_packages {
             return Ok(SceneryIndexScanResult {
@@ -1249,6 +1690,8 @@ impl SceneryIndexManager {
             .collect();
         updated.sort();
 
+        scenery_stats_history::record_snapshot_if_due(&self.db, &self.xplane_path).await;
+
         Ok(SceneryIndexScanResult {
             index_exists: true,
             added,
@@ -1261,17 +1704,7 @@ impl SceneryIndexManager {
     pub async fn get_stats(&self) -> Result<SceneryIndexStats> {
         let index = self.load_index().await?;
 
-        let mut by_category: HashMap<String, usize> = HashMap::new();
-        for info in index.packages.values() {
-            let category_name = format!("{:?}", info.category);
-            *by_category.entry(category_name).or_insert(0) += 1;
-        }
-
-        Ok(SceneryIndexStats {
-            total_packages: index.packages.len(),
-            by_category,
-            last_updated: index.last_updated,
-        })
+        Ok(SceneryIndexStats::from_index(&index))
     }
 
     /// Batch update multiple entries' enabled state and sort_order from UI
@@ -1283,7 +1716,7 @@ impl SceneryIndexManager {
             return Ok(());
         }
 
-        SceneryQueries::batch_update_entries(&self.db, entries)
+        SceneryQueries::batch_update_entries(&self.db, &self.install_key, entries)
             .await
             .map_err(|e| anyhow!("{}", e))?;
 
@@ -1295,45 +1728,128 @@ impl SceneryIndexManager {
         Ok(())
     }
 
-    /// Update a single entry's enabled state, sort_order, and/or category
+    /// Update a single entry's enabled state, sort_order, category and/or pin state
     pub async fn update_entry(
         &self,
         folder_name: &str,
         enabled: Option<bool>,
         sort_order: Option<u32>,
         category: Option<SceneryCategory>,
+        pinned: Option<bool>,
     ) -> Result<()> {
+        let folder_name = self.indexed_folder_name(folder_name).await?;
         SceneryQueries::update_entry(
             &self.db,
-            folder_name,
+            &self.install_key,
+            &folder_name,
             enabled,
             sort_order,
             category.as_ref(),
+            pinned,
         )
         .await
         .map_err(|e| anyhow!("{}", e))?;
         Ok(())
     }
 
-    /// Remove an entry from the index
-    pub async fn remove_entry(&self, folder_name: &str) -> Result<()> {
-        let deleted = SceneryQueries::delete_package(&self.db, folder_name)
+    /// Remove an entry from the index and record the libraries other packages
+    /// required from it as missing. Returns those newly missing references.
+    pub async fn remove_entry(&self, folder_name: &str) -> Result<Vec<NewlyMissingLibrary>> {
+        let folder_name = self.indexed_folder_name(folder_name).await?;
+        let folder_name = folder_name.as_str();
+        let custom_scenery = self.xplane_path.join("Custom Scenery");
+        let newly_missing: Vec<NewlyMissingLibrary> =
+            SceneryQueries::load_orphaned_library_references(
+                &self.db,
+                &self.install_key,
+                folder_name,
+            )
             .await
-            .map_err(|e| anyhow!("{}", e))?;
+            .map_err(|e| anyhow!("{}", e))?
+            .into_iter()
+            // Same exceptions as fill_missing_libraries: self-references and
+            // libraries bundled as a subfolder of the requiring package
+            .filter(|missing| {
+                !missing.library_name.eq_ignore_ascii_case(&missing.folder_name)
+                    && !custom_scenery
+                        .join(&missing.folder_name)
+                        .join(&missing.library_name)
+                        .is_dir()
+            })
+            .collect();
+
+        let deleted = SceneryQueries::delete_package_marking_missing(
+            &self.db,
+            &self.install_key,
+            folder_name,
+            &newly_missing,
+        )
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
 
         if deleted {
             logger::log_info(
                 &format!("Removed entry from scenery index: {}", folder_name),
                 Some("scenery_index"),
             );
+            if !newly_missing.is_empty() {
+                logger::log_info(
+                    &format!(
+                        "Removing {} left {} library references missing",
+                        folder_name,
+                        newly_missing.len()
+                    ),
+                    Some("scenery_index"),
+                );
+            }
         }
 
-        Ok(())
+        Ok(newly_missing)
+    }
+
+    /// Enabled packages that would lose libraries only `folder_name` exports
+    pub async fn preview_deletion(&self, folder_name: &str) -> Result<SceneryDeletionPreview> {
+        let folder_name = self.indexed_folder_name(folder_name).await?;
+        let dependents = self
+            .get_library_dependents(&folder_name)
+            .await?
+            .into_iter()
+            .filter(|dependent| dependent.exclusive_library_count > 0)
+            .collect();
+        Ok(SceneryDeletionPreview {
+            folder_name,
+            dependents,
+        })
+    }
+
+    /// Enabled packages that would lose libraries if `library_folder` were
+    /// disabled or deleted
+    pub async fn get_library_dependents(
+        &self,
+        library_folder: &str,
+    ) -> Result<Vec<LibraryDependent>> {
+        let library_folder = self.indexed_folder_name(library_folder).await?;
+        SceneryQueries::load_library_dependents(&self.db, &self.install_key, &library_folder)
+            .await
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Libraries a package requires and whether they are installed and enabled
+    pub async fn get_package_dependencies(
+        &self,
+        folder_name: &str,
+    ) -> Result<Vec<PackageLibraryDependency>> {
+        let folder_name = self.indexed_folder_name(folder_name).await?;
+        SceneryQueries::load_package_dependencies(&self.db, &self.install_key, &folder_name)
+            .await
+            .map_err(|e| anyhow!("{}", e))
     }
 
     /// Move an entry from one position to another, auto-adjusting other entries
     pub async fn move_entry(&self, folder_name: &str, new_sort_order: u32) -> Result<()> {
         let mut index = self.load_index().await?;
+        let folder_name = Self::match_folder_name(index.packages.keys(), folder_name);
+        let folder_name = folder_name.as_str();
 
         // Get current sort_order
         let current_sort_order = match index.packages.get(folder_name) {
@@ -1386,7 +1902,8 @@ impl SceneryIndexManager {
     }
 
     /// Reset sort_order while preserving the current sort slots of locked scenery entries.
-    /// Locked entries are identified by folder name (case-insensitive).
+    /// Locked entries are identified by folder name (case-insensitive); pinned
+    /// entries are always locked.
     pub async fn reset_sort_order_with_locked_entries(
         &self,
         locked_folder_names: Vec<String>,
@@ -1396,6 +1913,7 @@ impl SceneryIndexManager {
         if index.packages.is_empty() {
             return Ok(false);
         }
+        let locked_folder_names = Self::with_pinned_entries(&index, locked_folder_names);
 
         // Promote special high-priority libraries (Lines3D, SAM) before sorting.
         let mut category_changed = false;
@@ -1466,6 +1984,22 @@ impl SceneryIndexManager {
         Ok(has_changes)
     }
 
+    /// `locked_folder_names` plus the entries pinned in the index, which keep
+    /// their slot on every automatic sort
+    fn with_pinned_entries(
+        index: &SceneryIndex,
+        mut locked_folder_names: Vec<String>,
+    ) -> Vec<String> {
+        locked_folder_names.extend(
+            index
+                .packages
+                .values()
+                .filter(|info| info.pinned)
+                .map(|info| info.folder_name.clone()),
+        );
+        locked_folder_names
+    }
+
     fn find_nearest_free_slot(occupied: &[bool], desired: usize) -> Option<usize> {
         let len = occupied.len();
         if len == 0 {
@@ -1521,7 +2055,7 @@ impl SceneryIndexManager {
 
         let locked_names: HashSet<String> = locked_folder_names
             .iter()
-            .map(|name| name.trim().to_lowercase())
+            .map(|name| locked_name_key(name))
             .filter(|name| !name.is_empty())
             .collect();
 
@@ -1532,10 +2066,10 @@ impl SceneryIndexManager {
         let total = sorted_names.len();
         let mut locked_candidates: Vec<(String, usize)> = sorted_names
             .iter()
-            .filter(|name| locked_names.contains(&name.to_lowercase()))
+            .filter(|name| locked_names.contains(&locked_name_key(name)))
             .filter_map(|name| {
                 index.packages.get(name).map(|info| {
-                    let normalized_name = name.to_lowercase();
+                    let normalized_name = locked_name_key(name);
                     let desired_slot = locked_anchor_orders
                         .and_then(|anchors| anchors.get(&normalized_name).copied())
                         .unwrap_or(info.sort_order as usize)
@@ -1596,6 +2130,135 @@ impl SceneryIndexManager {
 
     /// Get scenery manager data for UI
     pub async fn get_manager_data(&self) -> Result<SceneryManagerData> {
+        let (data, _) = self.build_manager_data(true).await?;
+        Ok(data)
+    }
+
+    /// Get only the header counts of the scenery manager data. Update URLs aren't
+    /// read, which is most of the per-package filesystem work.
+    pub async fn get_manager_summary(&self) -> Result<SceneryManagerSummary> {
+        let (data, global_airports) = self.build_manager_data(false).await?;
+        Ok(SceneryManagerSummary {
+            total_count: data.total_count,
+            enabled_count: data.enabled_count,
+            missing_deps_count: data.missing_deps_count,
+            duplicate_tiles_count: data.duplicate_tiles_count,
+            duplicate_airports_count: data.duplicate_airports_count,
+            needs_sync: data.needs_sync,
+            global_airports: global_airports_entry(&global_airports),
+            broken_entries: data.broken_entries,
+            suppressed_airport_conflict_count: data.suppressed_airport_conflict_count,
+        })
+    }
+
+    /// Get one page of scenery manager entries, filtered and sorted in SQL.
+    /// `limit` is clamped to [`MAX_MANAGER_PAGE_SIZE`].
+    pub async fn get_manager_page(
+        &self,
+        offset: u64,
+        limit: u64,
+        sort: &SceneryManagerSort,
+        filter: &SceneryManagerFilter,
+    ) -> Result<SceneryManagerPage> {
+        let limit = limit.clamp(1, MAX_MANAGER_PAGE_SIZE);
+        let (packages, total_count) = SceneryQueries::load_manager_page(
+            &self.db,
+            &self.install_key,
+            filter,
+            sort,
+            offset,
+            limit,
+        )
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+        let custom_scenery_path = self.xplane_path.join("Custom Scenery");
+
+        // Tiles only overlap within the mesh categories, so only those packages are loaded
+        let duplicate_tiles_map = if packages.iter().any(|info| {
+            matches!(
+                info.category,
+                SceneryCategory::Mesh | SceneryCategory::AirportMesh | SceneryCategory::OrthoTile
+            )
+        }) {
+            let mesh_packages = SceneryQueries::load_packages_in_categories(
+                &self.db,
+                &self.install_key,
+                &[
+                    SceneryCategory::Mesh,
+                    SceneryCategory::AirportMesh,
+                    SceneryCategory::OrthoTile,
+                ],
+            )
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+            let raw_tile_overlaps = detect_raw_tile_overlaps(&mesh_packages, &custom_scenery_path);
+            filter_tile_overlaps_with_xpme_rules(&raw_tile_overlaps, &mesh_packages)
+        } else {
+            HashMap::new()
+        };
+
+        let suppressed_airports = self.suppressed_airport_keys().await?;
+        let airport_ids: Vec<String> = packages
+            .iter()
+            .filter_map(|info| info.airport_id.clone())
+            .filter(|id| {
+                !suppressed_airports.contains(&analysis_suppressions::airport_conflict_key(id))
+            })
+            .collect();
+        let airport_folders =
+            SceneryQueries::load_folders_by_airport_ids(&self.db, &self.install_key, &airport_ids)
+                .await
+                .map_err(|e| anyhow!("{}", e))?;
+
+        let entries = packages
+            .iter()
+            .map(|info| {
+                let duplicate_airports = info
+                    .airport_id
+                    .as_ref()
+                    .and_then(|id| airport_folders.get(id))
+                    .map(|folders| {
+                        folders
+                            .iter()
+                            .filter(|folder| **folder != info.folder_name)
+                            .cloned()
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                manager_entry(
+                    info,
+                    read_scenery_update_url(&custom_scenery_path.join(&info.folder_name)),
+                    duplicate_tiles_map
+                        .get(&info.folder_name)
+                        .cloned()
+                        .unwrap_or_default(),
+                    duplicate_airports,
+                )
+            })
+            .collect();
+
+        Ok(SceneryManagerPage {
+            entries,
+            offset,
+            limit,
+            total_count,
+        })
+    }
+
+    /// Airport conflicts the user chose to hide
+    async fn suppressed_airport_keys(&self) -> Result<HashSet<String>> {
+        analysis_suppressions::suppressed_keys(
+            &self.db,
+            analysis_suppressions::KIND_AIRPORT_CONFLICT,
+        )
+        .await
+        .map_err(|e| anyhow!("{}", e))
+    }
+
+    async fn build_manager_data(
+        &self,
+        read_update_urls: bool,
+    ) -> Result<(SceneryManagerData, GlobalAirportsState)> {
         let index = self.load_index().await?;
 
         // Check if ini is synced with index
@@ -1605,14 +2268,29 @@ impl SceneryIndexManager {
         );
         let needs_sync = !packs_manager.is_synced_with_index().await.unwrap_or(true);
 
-        // Detect duplicate tiles within Mesh and AirportMesh categories
+        // Detect duplicate tiles within Mesh, AirportMesh and OrthoTile categories
         let custom_scenery_path = self.xplane_path.join("Custom Scenery");
         let raw_tile_overlaps = detect_raw_tile_overlaps(&index.packages, &custom_scenery_path);
         let duplicate_tiles_map =
             filter_tile_overlaps_with_xpme_rules(&raw_tile_overlaps, &index.packages);
 
         // Detect duplicate airports (same airport_id across multiple packages)
-        let duplicate_airports_map = detect_duplicate_airports(&index.packages);
+        let mut duplicate_airports_map = detect_duplicate_airports(&index.packages);
+
+        // Conflicts the user suppressed are hidden but still counted
+        let suppressed_airports = self.suppressed_airport_keys().await?;
+        let mut suppressed_airport_ids = HashSet::new();
+        for info in index.packages.values() {
+            let Some(airport_id) = &info.airport_id else {
+                continue;
+            };
+            if suppressed_airports
+                .contains(&analysis_suppressions::airport_conflict_key(airport_id))
+                && duplicate_airports_map.remove(&info.folder_name).is_some()
+            {
+                suppressed_airport_ids.insert(airport_id.clone());
+            }
+        }
 
         let all_packages: Vec<_> = index.packages.values().collect();
         let global_airports = packs_manager
@@ -1631,53 +2309,33 @@ impl SceneryIndexManager {
             Vec::with_capacity(packages.len() + 1);
 
         for info in packages.drain(..) {
+            let update_url = if read_update_urls {
+                read_scenery_update_url(&custom_scenery_path.join(&info.folder_name))
+            } else {
+                None
+            };
             entries_with_sort.push((
                 info.sort_order,
                 false,
-                SceneryManagerEntry {
-                    folder_name: info.folder_name.clone(),
-                    category: info.category.clone(),
-                    sub_priority: info.sub_priority,
-                    enabled: info.enabled,
-                    sort_order: info.sort_order,
-                    update_url: read_scenery_update_url(
-                        &custom_scenery_path.join(&info.folder_name),
-                    ),
-                    missing_libraries: info.missing_libraries.clone(),
-                    required_libraries: info.required_libraries.clone(),
-                    continent: info.continent.clone(),
-                    duplicate_tiles: duplicate_tiles_map
+                manager_entry(
+                    info,
+                    update_url,
+                    duplicate_tiles_map
                         .get(&info.folder_name)
                         .cloned()
                         .unwrap_or_default(),
-                    duplicate_airports: duplicate_airports_map
+                    duplicate_airports_map
                         .get(&info.folder_name)
                         .cloned()
                         .unwrap_or_default(),
-                    airport_id: info.airport_id.clone(),
-                    original_category: info.original_category.clone(),
-                },
+                ),
             ));
         }
 
         entries_with_sort.push((
             global_airports.sort_order,
             true,
-            SceneryManagerEntry {
-                folder_name: GLOBAL_AIRPORTS_ENTRY_NAME.to_string(),
-                category: global_airports.category.clone(),
-                sub_priority: 0,
-                enabled: global_airports.enabled,
-                sort_order: global_airports.sort_order,
-                update_url: None,
-                missing_libraries: Vec::new(),
-                required_libraries: Vec::new(),
-                continent: None,
-                duplicate_tiles: Vec::new(),
-                duplicate_airports: Vec::new(),
-                airport_id: None,
-                original_category: None,
-            },
+            global_airports_entry(&global_airports),
         ));
 
         entries_with_sort.sort_by(|(sort_a, global_a, _), (sort_b, global_b, _)| {
@@ -1708,16 +2366,21 @@ impl SceneryIndexManager {
             .filter(|e| !e.duplicate_airports.is_empty())
             .count();
 
-        Ok(SceneryManagerData {
-            entries,
-            total_count,
-            enabled_count,
-            missing_deps_count,
-            duplicate_tiles_count,
-            duplicate_airports_count,
-            needs_sync,
-            tile_overlaps: raw_tile_overlaps,
-        })
+        Ok((
+            SceneryManagerData {
+                entries,
+                total_count,
+                enabled_count,
+                missing_deps_count,
+                duplicate_tiles_count,
+                duplicate_airports_count,
+                needs_sync,
+                tile_overlaps: raw_tile_overlaps,
+                broken_entries: list_broken_scenery_entries(&custom_scenery_path),
+                suppressed_airport_conflict_count: suppressed_airport_ids.len(),
+            },
+            global_airports,
+        ))
     }
 
     /// Create an empty index
@@ -1808,12 +2471,13 @@ pub fn build_library_index_from_scenery_index(index: &SceneryIndex) -> HashMap<S
     library_index
 }
 
-/// Remove a scenery entry from the index (public helper function)
+/// Remove a scenery entry from the index (public helper function).
+/// Returns the library references the removal left missing.
 pub async fn remove_scenery_entry(
     db: &DatabaseConnection,
     xplane_path: &str,
     folder_name: &str,
-) -> Result<()> {
+) -> Result<Vec<NewlyMissingLibrary>> {
     let manager = SceneryIndexManager::new(Path::new(xplane_path), db.clone());
     manager.remove_entry(folder_name).await
 }
@@ -2165,9 +2829,9 @@ fn detect_duplicate_airports(
     result
 }
 
-/// Detect all DSF tile overlaps within Mesh and AirportMesh categories separately.
+/// Detect all DSF tile overlaps within Mesh, AirportMesh and OrthoTile categories separately.
 /// Returns a raw map of folder_name -> list of ALL overlapping folder names (no XPME filtering).
-/// Cross-category duplicates (Mesh vs AirportMesh) are NOT flagged.
+/// Cross-category duplicates (e.g. Mesh vs OrthoTile) are NOT flagged.
 fn detect_raw_tile_overlaps(
     packages: &HashMap<String, SceneryPackageInfo>,
     custom_scenery_path: &Path,
@@ -2175,7 +2839,11 @@ fn detect_raw_tile_overlaps(
     let mut result: HashMap<String, Vec<String>> = HashMap::new();
 
     // Process each category separately
-    for category in [SceneryCategory::Mesh, SceneryCategory::AirportMesh] {
+    for category in [
+        SceneryCategory::Mesh,
+        SceneryCategory::AirportMesh,
+        SceneryCategory::OrthoTile,
+    ] {
         // Build coordinate index: (lat, lon) -> list of folder names
         let mut coord_map: HashMap<(i32, i32), Vec<String>> = HashMap::new();
 
@@ -2340,6 +3008,8 @@ mod tests {
             has_objects: false,
             texture_count: 0,
             earth_nav_tile_count: 1,
+            total_size_bytes: 0,
+            newest_file_modified: None,
             indexed_at: SystemTime::UNIX_EPOCH,
             required_libraries: Vec::new(),
             missing_libraries: Vec::new(),
@@ -2349,6 +3019,8 @@ mod tests {
             actual_path: None,
             continent: None,
             original_category: None,
+            classifier_version: 0,
+            pinned: false,
         }
     }
 
@@ -2582,4 +3254,239 @@ mod tests {
             vec!["KSEA Demo", "DarkBlue-KSEA Mesh", "MisterX Library"]
         );
     }
+
+    #[test]
+    fn test_rebuild_keeps_pinned_entries_and_their_slot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for name in ["Lib A", "Lib B", "Lib C"] {
+            let package = temp_dir.path().join("Custom Scenery").join(name);
+            fs::create_dir_all(&package).unwrap();
+            fs::write(
+                package.join("library.txt"),
+                format!("A\n800\nLIBRARY\n\nEXPORT {}/tree.obj tree.obj\n", name),
+            )
+            .unwrap();
+        }
+        let db = crate::database::open_memory_connection().unwrap();
+        crate::database::apply_migrations(&db).unwrap();
+        let manager = SceneryIndexManager::new(temp_dir.path(), db);
+        let ordered = || {
+            let index = tauri::async_runtime::block_on(manager.load_index()).unwrap();
+            let mut entries: Vec<SceneryPackageInfo> = index.packages.into_values().collect();
+            entries.sort_by_key(|info| info.sort_order);
+            entries
+                .into_iter()
+                .map(|info| (info.folder_name, info.pinned))
+                .collect::<Vec<_>>()
+        };
+
+        tauri::async_runtime::block_on(manager.rebuild_index(None, None)).unwrap();
+        let fresh = ordered();
+        let (last, _) = fresh.last().cloned().unwrap();
+        let mut index = tauri::async_runtime::block_on(manager.load_index()).unwrap();
+        for (order, (name, _)) in std::iter::once(fresh[2].clone())
+            .chain(fresh[..2].iter().cloned())
+            .enumerate()
+        {
+            let info = index.packages.get_mut(&name).unwrap();
+            info.sort_order = order as u32;
+            info.pinned = name == last;
+        }
+        tauri::async_runtime::block_on(manager.save_index(&index)).unwrap();
+
+        tauri::async_runtime::block_on(manager.rebuild_index(None, None)).unwrap();
+        let rebuilt = ordered();
+        assert_eq!(rebuilt[0], (last.clone(), true));
+        assert_eq!(
+            rebuilt[1..]
+                .iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            fresh[..2].iter().map(|(name, _)| name).collect::<Vec<_>>()
+        );
+        assert!(rebuilt[1..].iter().all(|(_, pinned)| !pinned));
+    }
+
+    #[test]
+    fn test_reset_sort_order_keeps_pinned_entries_in_place() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = crate::database::open_memory_connection().unwrap();
+        crate::database::apply_migrations(&db).unwrap();
+        let manager = SceneryIndexManager::new(temp_dir.path(), db);
+
+        // Listed in reverse priority, pinned at the top, middle and bottom
+        let packages = [
+            ("Mesh A", SceneryCategory::Mesh, true),
+            ("Overlay B", SceneryCategory::Overlay, false),
+            ("Library C", SceneryCategory::Library, false),
+            ("Ortho D", SceneryCategory::OrthoTile, true),
+            ("Airport E", SceneryCategory::Airport, false),
+            ("Other F", SceneryCategory::Other, false),
+            ("Airport G", SceneryCategory::Airport, true),
+        ];
+        let index = SceneryIndex {
+            version: CURRENT_SCHEMA_VERSION as u32,
+            packages: packages
+                .iter()
+                .enumerate()
+                .map(|(order, (name, category, pinned))| {
+                    let mut info = make_package(name, category.clone(), order as u32);
+                    info.pinned = *pinned;
+                    (name.to_string(), info)
+                })
+                .collect(),
+            last_updated: SystemTime::UNIX_EPOCH,
+        };
+        tauri::async_runtime::block_on(manager.save_index(&index)).unwrap();
+
+        let ordered_names = || {
+            let index = tauri::async_runtime::block_on(manager.load_index()).unwrap();
+            let mut entries: Vec<SceneryPackageInfo> = index.packages.into_values().collect();
+            entries.sort_by_key(|info| info.sort_order);
+            entries
+                .into_iter()
+                .map(|info| info.folder_name)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(tauri::async_runtime::block_on(manager.reset_sort_order()).unwrap());
+        assert_eq!(
+            ordered_names(),
+            vec![
+                "Mesh A",
+                "Airport E",
+                "Library C",
+                "Ortho D",
+                "Other F",
+                "Overlay B",
+                "Airport G",
+            ]
+        );
+
+        // Unpinning releases the entry to the regular sort
+        tauri::async_runtime::block_on(manager.update_entry(
+            "Mesh A",
+            None,
+            None,
+            None,
+            Some(false),
+        ))
+        .unwrap();
+        tauri::async_runtime::block_on(manager.reset_sort_order()).unwrap();
+        assert_eq!(
+            ordered_names(),
+            vec![
+                "Airport E",
+                "Library C",
+                "Other F",
+                "Ortho D",
+                "Overlay B",
+                "Mesh A",
+                "Airport G",
+            ]
+        );
+    }
+
+    #[test]
+    fn scenery_entries_resolve_through_linked_custom_scenery() {
+        use crate::path_utils::create_directory_link;
+
+        let xplane = tempfile::tempdir().unwrap();
+        let other_drive = tempfile::tempdir().unwrap();
+        let scenery = other_drive.path().join("Scenery");
+        let ortho = other_drive.path().join("Ortho");
+        let gone = other_drive.path().join("Gone");
+        for dir in [scenery.join("Airport_A"), ortho.clone(), gone.clone()] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        create_directory_link(&scenery, &xplane.path().join("Custom Scenery")).unwrap();
+        create_directory_link(&ortho, &scenery.join("zOrtho")).unwrap();
+        create_directory_link(&gone, &scenery.join("Broken")).unwrap();
+        fs::remove_dir(&gone).unwrap();
+        fs::write(scenery.join("scenery_packs.ini"), "I\n").unwrap();
+
+        let custom_scenery = xplane.path().join("Custom Scenery");
+        let mut entries: Vec<(String, SceneryEntryKind)> = list_scenery_entries(&custom_scenery)
+            .unwrap()
+            .into_iter()
+            .map(|location| (location.name, location.kind))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            entries,
+            vec![
+                ("Airport_A".to_string(), SceneryEntryKind::Directory),
+                ("zOrtho".to_string(), SceneryEntryKind::Link),
+            ]
+        );
+
+        let airport = find_scenery_entry(&custom_scenery, "Airport_A").unwrap();
+        assert!(
+            crate::path_utils::validate_xplane_child_path(xplane.path(), &airport.entry_path)
+                .is_ok()
+        );
+        assert_eq!(
+            find_scenery_entry(&custom_scenery, "zOrtho").unwrap().kind,
+            SceneryEntryKind::Link
+        );
+        assert!(find_scenery_entry(&custom_scenery, "Broken").is_none());
+        assert!(find_scenery_entry(&custom_scenery, "scenery_packs.ini").is_none());
+    }
+
+    #[test]
+    fn test_scenery_names_match_in_either_unicode_normalization() {
+        use unicode_normalization::UnicodeNormalization;
+
+        let composed = "UNNT Новосибирск Толмачёво";
+        let decomposed: String = composed.nfd().collect();
+        let custom_scenery = tempfile::tempdir().unwrap();
+        fs::create_dir_all(custom_scenery.path().join(&decomposed)).unwrap();
+
+        for name in [composed, decomposed.as_str()] {
+            let location = find_scenery_entry(custom_scenery.path(), name).unwrap();
+            assert_eq!(location.name, decomposed);
+            assert_eq!(location.kind, SceneryEntryKind::Directory);
+        }
+
+        let indexed = vec!["Airport_A".to_string(), decomposed.clone()];
+        assert_eq!(
+            SceneryIndexManager::match_folder_name(indexed.iter(), composed),
+            decomposed
+        );
+        assert_eq!(
+            SceneryIndexManager::match_folder_name(indexed.iter(), "Unknown"),
+            "Unknown"
+        );
+        assert_eq!(locked_name_key(composed), locked_name_key(&decomposed));
+    }
+
+    #[test]
+    fn broken_links_and_shortcuts_are_reported() {
+        use crate::path_utils::create_directory_link;
+
+        let scenery = tempfile::tempdir().unwrap();
+        let other_drive = tempfile::tempdir().unwrap();
+        let gone = other_drive.path().join("Gone");
+        fs::create_dir_all(&gone).unwrap();
+        fs::create_dir_all(scenery.path().join("Airport_A")).unwrap();
+        create_directory_link(&gone, &scenery.path().join("Broken")).unwrap();
+        fs::remove_dir(&gone).unwrap();
+        fs::write(scenery.path().join("Ortho.lnk"), "not a shell link").unwrap();
+        fs::write(scenery.path().join("scenery_packs.ini"), "I\n").unwrap();
+
+        let broken = list_broken_scenery_entries(scenery.path());
+        let names: Vec<(&str, SceneryLinkKind)> = broken
+            .iter()
+            .map(|entry| (entry.entry_name.as_str(), entry.kind))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Broken", SceneryLinkKind::Link),
+                ("Ortho.lnk", SceneryLinkKind::Shortcut),
+            ]
+        );
+        assert!(broken[0].target_path.is_some());
+        assert!(broken[1].target_path.is_none());
+    }
 }
@@ -0,0 +1,389 @@
+//! scenery_packs.ini order validation
+//!
+//! Manual edits after an auto-sort can leave entries on the wrong side of each
+//! other. The current ini order is checked against the index: categories must
+//! follow their priority, and a package must not sit below a library package it
+//! requires names from.
+//!
+//! Fixes move as few entries as possible. The longest run of enabled entries
+//! that is already in category order stays where it is; every other entry goes
+//! to the valid position nearest to where it was. Disabled entries and
+//! `*GLOBAL_AIRPORTS*` keep their positions.
+
+use crate::error::ApiResult;
+use crate::models::{
+    SceneryEntryUpdate, SceneryOrderReport, SceneryOrderViolation, SceneryOrderViolationKind,
+    SceneryPackageInfo,
+};
+use crate::scenery_index::SceneryIndexManager;
+use crate::scenery_packs_manager::{self, SceneryPacksManager};
+use sea_orm::DatabaseConnection;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// An ini entry the index knows about, in file order
+enum Slot<'a> {
+    GlobalAirports {
+        enabled: bool,
+    },
+    Package {
+        info: &'a SceneryPackageInfo,
+        enabled: bool,
+    },
+}
+
+/// Positions of the longest non-decreasing run of `ranks`
+pub(crate) fn longest_ordered_run<T: Ord>(ranks: &[T]) -> Vec<bool> {
+    // tails[k] = index ending the best run of length k + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; ranks.len()];
+    for (i, rank) in ranks.iter().enumerate() {
+        let pos = tails.partition_point(|&t| ranks[t] <= *rank);
+        previous[i] = pos.checked_sub(1).map(|p| tails[p]);
+        if pos == tails.len() {
+            tails.push(i);
+        } else {
+            tails[pos] = i;
+        }
+    }
+
+    let mut kept = vec![false; ranks.len()];
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        kept[i] = true;
+        current = previous[i];
+    }
+    kept
+}
+
+/// Order of `ranks` (as indices) after moving every entry outside `kept` to the
+/// valid slot between kept entries nearest to its current one. Entries sharing
+/// a slot are ordered by rank, then by their current position.
+fn minimal_reorder(ranks: &[u8], kept: &[bool]) -> Vec<usize> {
+    let kept_indices: Vec<usize> = (0..ranks.len()).filter(|&i| kept[i]).collect();
+    let mut slots: Vec<Vec<usize>> = vec![Vec::new(); kept_indices.len() + 1];
+
+    let mut kept_before = 0;
+    for (i, &rank) in ranks.iter().enumerate() {
+        if kept[i] {
+            kept_before += 1;
+            continue;
+        }
+        let lowest = kept_indices.partition_point(|&k| ranks[k] < rank);
+        let highest = kept_indices.partition_point(|&k| ranks[k] <= rank);
+        slots[kept_before.clamp(lowest, highest)].push(i);
+    }
+
+    let mut order = Vec::with_capacity(ranks.len());
+    for (slot, mut moved) in slots.into_iter().enumerate() {
+        moved.sort_by_key(|&i| (ranks[i], i));
+        order.extend(moved);
+        if let Some(&k) = kept_indices.get(slot) {
+            order.push(k);
+        }
+    }
+    order
+}
+
+fn category_violation(
+    info: &SceneryPackageInfo,
+    other: &SceneryPackageInfo,
+    placed_above: bool,
+) -> SceneryOrderViolation {
+    SceneryOrderViolation {
+        kind: SceneryOrderViolationKind::CategoryOrder,
+        folder_name: info.folder_name.clone(),
+        category: info.category.clone(),
+        other_folder_name: other.folder_name.clone(),
+        other_category: other.category.clone(),
+        placed_above,
+        library_name: None,
+        message: format!(
+            "{} ({:?}) is {} {} ({:?})",
+            info.folder_name,
+            info.category,
+            if placed_above { "above" } else { "below" },
+            other.folder_name,
+            other.category
+        ),
+    }
+}
+
+/// Violations among the enabled entries in ini order. Library dependencies are
+/// reported first; an entry already named there gets no category violation.
+fn find_violations(
+    entries: &[&SceneryPackageInfo],
+    kept: &[bool],
+    order: &[usize],
+) -> Vec<SceneryOrderViolation> {
+    let ranks: Vec<u8> = entries
+        .iter()
+        .map(|info| info.category.priority())
+        .collect();
+    let mut violations = Vec::new();
+    let mut reported: HashSet<usize> = HashSet::new();
+
+    let mut exporters: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, info) in entries.iter().enumerate() {
+        for name in &info.exported_library_names {
+            exporters.entry(name.to_lowercase()).or_default().push(i);
+        }
+    }
+    for (dependent, info) in entries.iter().enumerate() {
+        let mut seen_libraries = HashSet::new();
+        for name in &info.required_libraries {
+            let Some(providers) = exporters.get(&name.to_lowercase()) else {
+                continue;
+            };
+            for &library in providers {
+                if library >= dependent
+                    || ranks[dependent] >= ranks[library]
+                    || !seen_libraries.insert(library)
+                {
+                    continue;
+                }
+                let other = entries[library];
+                violations.push(SceneryOrderViolation {
+                    kind: SceneryOrderViolationKind::LibraryDependency,
+                    folder_name: info.folder_name.clone(),
+                    category: info.category.clone(),
+                    other_folder_name: other.folder_name.clone(),
+                    other_category: other.category.clone(),
+                    placed_above: false,
+                    library_name: Some(name.clone()),
+                    message: format!(
+                        "{} ({:?}) is ordered below library {} it requires ({})",
+                        info.folder_name, info.category, other.folder_name, name
+                    ),
+                });
+                reported.insert(dependent);
+                reported.insert(library);
+            }
+        }
+    }
+
+    let mut new_position = vec![0; entries.len()];
+    for (position, &i) in order.iter().enumerate() {
+        new_position[i] = position;
+    }
+    for i in (0..entries.len()).filter(|&i| !kept[i] && !reported.contains(&i)) {
+        let partner = if new_position[i] < i {
+            (0..i).rev().find(|&j| ranks[j] > ranks[i])
+        } else {
+            (i + 1..entries.len()).find(|&j| ranks[j] < ranks[i])
+        };
+        if let Some(j) = partner {
+            violations.push(category_violation(entries[i], entries[j], j > i));
+        }
+    }
+    violations
+}
+
+/// Check the current scenery_packs.ini order against the index, and with
+/// `apply_fixes` move the offending entries and rewrite the ini
+pub async fn validate_scenery_order(
+    db: &DatabaseConnection,
+    xplane_path: &Path,
+    apply_fixes: bool,
+) -> ApiResult<SceneryOrderReport> {
+    let index_manager = SceneryIndexManager::new(xplane_path, db.clone());
+    if !index_manager.has_index().await? {
+        return Ok(SceneryOrderReport::default());
+    }
+    let index = index_manager.load_index().await?;
+    let packs_manager = SceneryPacksManager::new(xplane_path, db.clone());
+
+    let packages_by_key: HashMap<String, &SceneryPackageInfo> = index
+        .packages
+        .values()
+        .filter(|info| !scenery_packs_manager::is_global_airports_package(info))
+        .map(|info| (scenery_packs_manager::package_key(info), info))
+        .collect();
+
+    let mut slots = Vec::new();
+    let mut seen = HashSet::new();
+    for entry in packs_manager.current_ini_entries().await {
+        let key = scenery_packs_manager::entry_key(&entry);
+        if !seen.insert(key.clone()) {
+            continue;
+        }
+        if entry.is_global_airports {
+            slots.push(Slot::GlobalAirports {
+                enabled: entry.enabled,
+            });
+        } else if let Some(&info) = packages_by_key.get(&key) {
+            slots.push(Slot::Package {
+                info,
+                enabled: entry.enabled,
+            });
+        }
+    }
+
+    let enabled: Vec<&SceneryPackageInfo> = slots
+        .iter()
+        .filter_map(|slot| match slot {
+            Slot::Package {
+                info,
+                enabled: true,
+            } => Some(*info),
+            _ => None,
+        })
+        .collect();
+    let ranks: Vec<u8> = enabled
+        .iter()
+        .map(|info| info.category.priority())
+        .collect();
+    let kept = longest_ordered_run(&ranks);
+    let order = minimal_reorder(&ranks, &kept);
+
+    let mut report = SceneryOrderReport {
+        checked_count: enabled.len(),
+        violations: find_violations(&enabled, &kept, &order),
+        ..Default::default()
+    };
+    if !apply_fixes || report.violations.is_empty() {
+        return Ok(report);
+    }
+
+    // Enabled positions are refilled in the fixed order
+    let mut reordered = order.iter().map(|&i| enabled[i]);
+    let mut updates = Vec::with_capacity(index.packages.len());
+    let mut listed = HashSet::new();
+    for (position, slot) in slots.iter().enumerate() {
+        match slot {
+            Slot::GlobalAirports { enabled } => {
+                packs_manager.set_global_airports_enabled(*enabled).await?;
+                packs_manager
+                    .set_global_airports_sort_order(position as u32)
+                    .await?;
+            }
+            Slot::Package { info, enabled } => {
+                let info = if *enabled {
+                    reordered.next().unwrap_or(*info)
+                } else {
+                    *info
+                };
+                listed.insert(info.folder_name.as_str());
+                updates.push(SceneryEntryUpdate {
+                    folder_name: info.folder_name.clone(),
+                    enabled: *enabled,
+                    sort_order: position as u32,
+                    pinned: None,
+                });
+            }
+        }
+    }
+
+    // Packages missing from the ini follow in their previous relative order
+    let mut unlisted: Vec<&SceneryPackageInfo> = index
+        .packages
+        .values()
+        .filter(|info| {
+            !listed.contains(info.folder_name.as_str())
+                && !scenery_packs_manager::is_global_airports_package(info)
+        })
+        .collect();
+    unlisted.sort_by(|a, b| {
+        a.sort_order
+            .cmp(&b.sort_order)
+            .then_with(|| a.folder_name.cmp(&b.folder_name))
+    });
+    for (offset, info) in unlisted.into_iter().enumerate() {
+        updates.push(SceneryEntryUpdate {
+            folder_name: info.folder_name.clone(),
+            enabled: info.enabled,
+            sort_order: (slots.len() + offset) as u32,
+            pinned: None,
+        });
+    }
+
+    index_manager.batch_update_entries(&updates).await?;
+    packs_manager.apply_from_index().await?;
+
+    report.moved = order
+        .iter()
+        .filter(|&&i| !kept[i])
+        .map(|&i| enabled[i].folder_name.clone())
+        .collect();
+    report.fixes_applied = true;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SceneryCategory;
+    use std::time::SystemTime;
+
+    fn package(name: &str, category: SceneryCategory) -> SceneryPackageInfo {
+        SceneryPackageInfo {
+            folder_name: name.to_string(),
+            category,
+            sub_priority: 0,
+            last_modified: SystemTime::UNIX_EPOCH,
+            has_apt_dat: false,
+            airport_id: None,
+            has_dsf: false,
+            has_library_txt: false,
+            has_textures: false,
+            has_objects: false,
+            texture_count: 0,
+            earth_nav_tile_count: 0,
+            total_size_bytes: 0,
+            newest_file_modified: None,
+            indexed_at: SystemTime::UNIX_EPOCH,
+            required_libraries: Vec::new(),
+            missing_libraries: Vec::new(),
+            exported_library_names: Vec::new(),
+            enabled: true,
+            sort_order: 0,
+            actual_path: None,
+            continent: None,
+            original_category: None,
+            classifier_version: 0,
+            pinned: false,
+        }
+    }
+
+    #[test]
+    fn test_minimal_reorder_moves_only_offenders() {
+        // Airport, Library, Mesh, Overlay, Mesh: one mesh goes below the overlay
+        let ranks = [1, 3, 8, 5, 8];
+        let kept = longest_ordered_run(&ranks);
+        assert_eq!(kept, vec![true, true, false, true, true]);
+        assert_eq!(minimal_reorder(&ranks, &kept), vec![0, 1, 3, 2, 4]);
+
+        let ordered = [0, 1, 1, 3, 9];
+        let kept = longest_ordered_run(&ordered);
+        assert!(kept.iter().all(|&k| k));
+        assert_eq!(minimal_reorder(&ordered, &kept), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_violations_name_libraries_and_categories() {
+        let mut library = package("Lib", SceneryCategory::Library);
+        library.exported_library_names = vec!["lib/objects".to_string()];
+        let mut airport = package("KSEA", SceneryCategory::Airport);
+        airport.required_libraries = vec!["lib/objects".to_string()];
+        let overlay = package("Roads", SceneryCategory::Overlay);
+        let mesh = package("Ortho", SceneryCategory::Mesh);
+
+        let entries = vec![&library, &airport, &mesh, &overlay];
+        let ranks: Vec<u8> = entries.iter().map(|i| i.category.priority()).collect();
+        let kept = longest_ordered_run(&ranks);
+        let order = minimal_reorder(&ranks, &kept);
+        let violations = find_violations(&entries, &kept, &order);
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].kind,
+            SceneryOrderViolationKind::LibraryDependency
+        );
+        assert_eq!(violations[0].folder_name, "KSEA");
+        assert_eq!(violations[0].other_folder_name, "Lib");
+        assert_eq!(violations[1].kind, SceneryOrderViolationKind::CategoryOrder);
+        assert_eq!(violations[1].folder_name, "Ortho");
+        assert_eq!(violations[1].other_folder_name, "Roads");
+        assert!(violations[1].placed_above);
+    }
+}
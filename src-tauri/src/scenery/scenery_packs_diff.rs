@@ -0,0 +1,151 @@
+//! What writing scenery_packs.ini from the index would change
+//!
+//! Entries are matched by [`scenery_packs_manager::entry_key`], so the same
+//! folder written with other slashes, case or as an absolute path counts as
+//! one entry. Entries only one side has are added or removed. Of the entries
+//! on both sides, the longest run already in the new order stays put and only
+//! the others are reported as reordered, so moving one entry doesn't list every
+//! entry it shifted. Enabled state changes are reported apart from moves.
+
+use crate::models::{SceneryPackEntry, SceneryPacksDiffEntry, SceneryPacksPreview};
+use crate::scenery_order::longest_ordered_run;
+use crate::scenery_packs_manager::entry_key;
+use std::collections::HashMap;
+
+fn diff_entry(
+    entry: &SceneryPackEntry,
+    old_position: Option<usize>,
+    new_position: Option<usize>,
+) -> SceneryPacksDiffEntry {
+    SceneryPacksDiffEntry {
+        path: entry.path.clone(),
+        enabled: entry.enabled,
+        old_position,
+        new_position,
+    }
+}
+
+/// Changes from the `current` pack entries of the ini to the `next` ones
+pub fn diff_entries(
+    current: &[SceneryPackEntry],
+    next: &[SceneryPackEntry],
+) -> SceneryPacksPreview {
+    let mut preview = SceneryPacksPreview::default();
+
+    // A duplicate line counts once; the copies further down are removed
+    let mut old_positions: HashMap<String, usize> = HashMap::new();
+    for (position, entry) in current.iter().enumerate() {
+        if old_positions.contains_key(&entry_key(entry)) {
+            preview
+                .removed
+                .push(diff_entry(entry, Some(position), None));
+        } else {
+            old_positions.insert(entry_key(entry), position);
+        }
+    }
+
+    // (new position, old position) of the entries both sides have
+    let mut kept: Vec<(usize, usize)> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for (new_position, entry) in next.iter().enumerate() {
+        let key = entry_key(entry);
+        let old_position = match old_positions.get(&key) {
+            Some(&old_position) if !seen.contains_key(&key) => old_position,
+            _ => {
+                preview
+                    .added
+                    .push(diff_entry(entry, None, Some(new_position)));
+                continue;
+            }
+        };
+        seen.insert(key, new_position);
+        kept.push((new_position, old_position));
+
+        let was_enabled = current[old_position].enabled;
+        if was_enabled != entry.enabled {
+            let change = diff_entry(entry, Some(old_position), Some(new_position));
+            if entry.enabled {
+                preview.enabled.push(change);
+            } else {
+                preview.disabled.push(change);
+            }
+        }
+    }
+
+    for (position, entry) in current.iter().enumerate() {
+        if old_positions.get(&entry_key(entry)) == Some(&position)
+            && !seen.contains_key(&entry_key(entry))
+        {
+            preview
+                .removed
+                .push(diff_entry(entry, Some(position), None));
+        }
+    }
+    preview.removed.sort_by_key(|entry| entry.old_position);
+
+    let old_order: Vec<usize> = kept.iter().map(|&(_, old)| old).collect();
+    let in_place = longest_ordered_run(&old_order);
+    for (&(new_position, old_position), in_place) in kept.iter().zip(in_place) {
+        if !in_place {
+            preview.reordered.push(diff_entry(
+                &next[new_position],
+                Some(old_position),
+                Some(new_position),
+            ));
+        }
+    }
+
+    preview.has_changes = !(preview.added.is_empty()
+        && preview.removed.is_empty()
+        && preview.reordered.is_empty()
+        && preview.enabled.is_empty()
+        && preview.disabled.is_empty());
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, enabled: bool) -> SceneryPackEntry {
+        SceneryPackEntry {
+            enabled,
+            path: path.to_string(),
+            is_global_airports: false,
+        }
+    }
+
+    #[test]
+    fn test_moving_one_entry_reports_only_that_entry() {
+        let current = [
+            entry("Custom Scenery/A/", true),
+            entry("Custom Scenery/B/", true),
+            entry("Custom Scenery/C/", true),
+            entry("Custom Scenery/D/", true),
+        ];
+        let next = [
+            entry("Custom Scenery/D/", true),
+            entry("Custom Scenery/A/", true),
+            entry("Custom Scenery/B/", true),
+            entry("Custom Scenery/C/", false),
+        ];
+        let preview = diff_entries(&current, &next);
+        assert!(preview.has_changes);
+        assert_eq!(preview.reordered.len(), 1);
+        assert_eq!(preview.reordered[0].path, "Custom Scenery/D/");
+        assert_eq!(preview.reordered[0].old_position, Some(3));
+        assert_eq!(preview.reordered[0].new_position, Some(0));
+        assert_eq!(preview.disabled.len(), 1);
+        assert_eq!(preview.disabled[0].path, "Custom Scenery/C/");
+        assert!(preview.added.is_empty() && preview.removed.is_empty());
+
+        // Same folders in another spelling are the same entries
+        let respelled = [
+            entry("Custom Scenery\\a", true),
+            entry("Custom Scenery/B/", true),
+            entry("Custom Scenery/C/", true),
+            entry("Custom Scenery/D/", true),
+        ];
+        assert!(!diff_entries(&current, &respelled).has_changes);
+    }
+}
@@ -1,23 +1,28 @@
 //! Scenery packs.ini manager module
 //!
 //! This module writes and sorts the scenery_packs.ini file using the index as source of truth
-//! based on scenery classifications.
+//! based on scenery classifications. Lines the index doesn't manage (comments, unknown
+//! directives, disabled entries for folders outside the index) are carried over from the
+//! existing file next to the entry they followed, so edits made by other tools survive.
 
 use crate::database::SceneryQueries;
 use crate::logger;
 use crate::models::{
     is_global_airports_folder_name, SceneryCategory, SceneryPackEntry, SceneryPackageInfo,
-    GLOBAL_AIRPORTS_ENTRY_NAME,
+    SceneryPacksPreview, GLOBAL_AIRPORTS_ENTRY_NAME,
 };
+use crate::path_utils;
 use crate::scenery_index::SceneryIndexManager;
+use crate::scenery_packs_diff;
 use anyhow::{anyhow, Result};
 use chrono::Local;
 use sea_orm::DatabaseConnection;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-const INI_HEADER: &str = "I\n1000 Version\nSCENERY\n\n";
+pub(crate) const INI_HEADER: &str = "I\n1000 Version\nSCENERY\n\n";
 const GLOBAL_AIRPORTS_ENABLED_METADATA_KEY: &str = "global_airports_enabled";
 const GLOBAL_AIRPORTS_SORT_ORDER_METADATA_KEY: &str = "global_airports_sort_order";
 const GLOBAL_AIRPORTS_CATEGORY_METADATA_KEY: &str = "global_airports_category";
@@ -43,7 +48,7 @@ fn entry_path_for_ini(entry: &SceneryPackEntry) -> String {
     }
 }
 
-fn is_global_airports_package(info: &SceneryPackageInfo) -> bool {
+pub(crate) fn is_global_airports_package(info: &SceneryPackageInfo) -> bool {
     info.category == SceneryCategory::DefaultAirport
         || is_global_airports_folder_name(&info.folder_name)
 }
@@ -78,18 +83,12 @@ fn build_entries_from_sorted_packages(
             continue;
         }
 
-        let path = if let Some(actual_path) = &info.actual_path {
-            actual_path.clone()
-        } else {
-            format!("Custom Scenery/{}/", info.folder_name)
-        };
-
         entries.push((
             info.sort_order,
             false,
             SceneryPackEntry {
                 enabled: info.enabled,
-                path,
+                path: package_ini_path(info),
                 is_global_airports: false,
             },
         ));
@@ -109,37 +108,294 @@ fn build_entries_from_sorted_packages(
     entries.into_iter().map(|(_, _, entry)| entry).collect()
 }
 
+/// Body line of an existing scenery_packs.ini
+#[derive(Debug, Clone)]
+enum IniLine {
+    /// SCENERY_PACK / SCENERY_PACK_DISABLED entry, with the line as it was written
+    Pack {
+        entry: SceneryPackEntry,
+        raw: String,
+    },
+    /// Comment or directive we don't manage, written back verbatim
+    Other(String),
+}
+
+pub(crate) fn is_ini_header_line(line: &str) -> bool {
+    matches!(line, "I" | "A" | "SCENERY")
+        || line
+            .strip_suffix(" Version")
+            .is_some_and(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Parse the body of scenery_packs.ini. The header and blank lines are dropped since
+/// they are always rewritten.
+fn parse_ini_lines(content: &str) -> Vec<IniLine> {
+    let mut lines = Vec::new();
+    let mut in_header = true;
+
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || (in_header && is_ini_header_line(line)) {
+            continue;
+        }
+        in_header = false;
+
+        let raw = raw_line.trim_end().to_string();
+        lines.push(match parse_pack_line(line) {
+            Some(entry) => IniLine::Pack { entry, raw },
+            None => IniLine::Other(raw),
+        });
+    }
+
+    lines
+}
+
+/// Entry of a trimmed SCENERY_PACK / SCENERY_PACK_DISABLED line
+pub(crate) fn parse_pack_line(line: &str) -> Option<SceneryPackEntry> {
+    let (enabled, path) = if let Some(path) = line.strip_prefix("SCENERY_PACK_DISABLED ") {
+        (false, path)
+    } else if let Some(path) = line.strip_prefix("SCENERY_PACK ") {
+        (true, path)
+    } else {
+        return None;
+    };
+
+    let is_global_airports = path.contains("*GLOBAL_AIRPORTS*");
+    Some(SceneryPackEntry {
+        enabled,
+        path: if is_global_airports {
+            GLOBAL_AIRPORTS_ENTRY_NAME.to_string()
+        } else {
+            path.trim().to_string()
+        },
+        is_global_airports,
+    })
+}
+
+/// Folder an ini entry points to: absolute paths as written, others relative
+/// to the X-Plane root. A folder name written in another Unicode normalization
+/// than the one on disk resolves to the folder on disk.
+pub(crate) fn entry_folder(xplane_path: &Path, entry: &SceneryPackEntry) -> PathBuf {
+    let path = Path::new(entry.path.trim_end_matches(['/', '\\']));
+    let folder = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        xplane_path.join(path)
+    };
+    if folder.exists() {
+        return folder;
+    }
+    let on_disk = match (folder.parent(), folder.file_name()) {
+        (Some(parent), Some(name)) => {
+            path_utils::on_disk_name(parent, &name.to_string_lossy()).map(|name| parent.join(name))
+        }
+        _ => None,
+    };
+    on_disk.unwrap_or(folder)
+}
+
+/// Full file content for an ini body
+pub(crate) fn ini_content(body: &[String]) -> Vec<u8> {
+    let mut content: Vec<u8> = Vec::new();
+    content.extend_from_slice(INI_HEADER.as_bytes());
+    for line in body {
+        content.extend_from_slice(line.as_bytes());
+        content.push(b'\n');
+    }
+    content
+}
+
+/// Body of `content` without the entry for `folder`, every other line kept as
+/// written. `None` when no entry points at `folder`.
+fn body_without_folder(content: &str, xplane_path: &Path, folder: &Path) -> Option<Vec<String>> {
+    let lines = parse_ini_lines(content);
+    let line_count = lines.len();
+    let body: Vec<String> = lines
+        .into_iter()
+        .filter(|line| match line {
+            IniLine::Pack { entry, .. } => {
+                entry.is_global_airports || entry_folder(xplane_path, entry) != folder
+            }
+            IniLine::Other(_) => true,
+        })
+        .map(|line| match line {
+            IniLine::Pack { raw, .. } | IniLine::Other(raw) => raw,
+        })
+        .collect();
+    (body.len() != line_count).then_some(body)
+}
+
 fn parse_ini_entries(content: &str) -> Vec<SceneryPackEntry> {
-    let mut entries = Vec::new();
-
-    for line in content.lines() {
-        let line = line.trim();
-        let (enabled, path) = if let Some(path) = line.strip_prefix("SCENERY_PACK_DISABLED ") {
-            (false, path)
-        } else if let Some(path) = line.strip_prefix("SCENERY_PACK ") {
-            (true, path)
+    parse_ini_lines(content)
+        .into_iter()
+        .filter_map(|line| match line {
+            IniLine::Pack { entry, .. } => Some(entry),
+            IniLine::Other(_) => None,
+        })
+        .collect()
+}
+
+/// Path of an index package as written to scenery_packs.ini
+fn package_ini_path(info: &SceneryPackageInfo) -> String {
+    match &info.actual_path {
+        Some(actual_path) => actual_path.clone(),
+        None => format!("Custom Scenery/{}/", info.folder_name),
+    }
+}
+
+/// Comparison key for ini entries: slash-normalized, case-insensitive, in Unicode
+/// NFC, and absolute paths into this install's Custom Scenery reduced to the
+/// relative form
+pub(crate) fn entry_key(entry: &SceneryPackEntry) -> String {
+    if entry.is_global_airports {
+        return GLOBAL_AIRPORTS_ENTRY_NAME.to_ascii_lowercase();
+    }
+    let normalized =
+        path_utils::normalize_name(&normalize_scenery_path(entry.path.trim()).to_lowercase());
+    match normalized.rfind("custom scenery/") {
+        Some(pos)
+            if normalized[pos + "custom scenery/".len()..]
+                .matches('/')
+                .count()
+                == 1 =>
+        {
+            normalized[pos..].to_string()
+        }
+        _ => normalized,
+    }
+}
+
+pub(crate) fn package_key(info: &SceneryPackageInfo) -> String {
+    entry_key(&SceneryPackEntry {
+        enabled: info.enabled,
+        path: package_ini_path(info),
+        is_global_airports: false,
+    })
+}
+
+/// Keys of every entry the index is responsible for, including packages it leaves out
+/// of the ini (disabled Unrecognized) so stale lines for them aren't carried over
+fn managed_entry_keys(packages: &[&SceneryPackageInfo]) -> HashSet<String> {
+    packages
+        .iter()
+        .filter(|info| !is_global_airports_package(info))
+        .map(|info| package_key(info))
+        .chain(std::iter::once(
+            GLOBAL_AIRPORTS_ENTRY_NAME.to_ascii_lowercase(),
+        ))
+        .collect()
+}
+
+/// Existing lines to keep: comments, unknown directives and disabled entries for
+/// folders the index doesn't know about
+fn is_preserved_line(line: &IniLine, managed_keys: &HashSet<String>) -> bool {
+    match line {
+        IniLine::Other(_) => true,
+        IniLine::Pack { entry, .. } => !entry.enabled && !managed_keys.contains(&entry_key(entry)),
+    }
+}
+
+/// Build the ini body: the generated entries in order, with preserved lines from the
+/// existing file placed after the entry they followed before. Lines whose anchor entry
+/// is gone move up to the closest earlier entry that is still written.
+fn merge_with_preserved_lines(
+    entries: &[SceneryPackEntry],
+    existing: &[IniLine],
+    managed_keys: &HashSet<String>,
+) -> Vec<String> {
+    let written_keys: HashSet<String> = entries.iter().map(entry_key).collect();
+    let mut anchored: HashMap<Option<String>, Vec<&str>> = HashMap::new();
+    let mut anchor: Option<String> = None;
+
+    for line in existing {
+        if is_preserved_line(line, managed_keys) {
+            let raw = match line {
+                IniLine::Pack { raw, .. } | IniLine::Other(raw) => raw.as_str(),
+            };
+            anchored.entry(anchor.clone()).or_default().push(raw);
+        } else if let IniLine::Pack { entry, .. } = line {
+            let key = entry_key(entry);
+            if written_keys.contains(&key) {
+                anchor = Some(key);
+            }
+        }
+    }
+
+    let mut body: Vec<String> = Vec::new();
+    if let Some(lines) = anchored.remove(&None) {
+        body.extend(lines.into_iter().map(str::to_string));
+    }
+    for entry in entries {
+        let prefix = if entry.enabled {
+            "SCENERY_PACK"
         } else {
-            continue;
+            "SCENERY_PACK_DISABLED"
         };
+        body.push(format!("{} {}", prefix, entry_path_for_ini(entry)));
+        if let Some(lines) = anchored.remove(&Some(entry_key(entry))) {
+            body.extend(lines.into_iter().map(str::to_string));
+        }
+    }
+    body
+}
 
-        let is_global_airports = path.contains("*GLOBAL_AIRPORTS*");
-        entries.push(SceneryPackEntry {
-            enabled,
-            path: if is_global_airports {
-                GLOBAL_AIRPORTS_ENTRY_NAME.to_string()
-            } else {
-                path.trim().to_string()
-            },
-            is_global_airports,
-        });
+/// Body of scenery_packs.ini for the index packages, sorted by sort order,
+/// with the lines of the existing body the index doesn't manage carried over.
+/// Both `apply_from_index` and `preview_changes` take the body from here, so a
+/// preview always shows what would be written.
+fn generate_ini_body(
+    packages: &[&SceneryPackageInfo],
+    global_airports: &GlobalAirportsState,
+    existing: &[IniLine],
+) -> Vec<String> {
+    let entries = build_entries_from_sorted_packages(packages, global_airports);
+    merge_with_preserved_lines(&entries, existing, &managed_entry_keys(packages))
+}
+
+/// Sort order that keeps a custom *GLOBAL_AIRPORTS* placement from the existing ini.
+/// Returns `None` when the ini has no such line, or when it sits at the default
+/// boundary (after airports, before libraries), so the default rule keeps applying.
+fn global_airports_sort_order_from_ini(
+    existing: &[IniLine],
+    packages: &[&SceneryPackageInfo],
+) -> Option<u32> {
+    let visible: HashMap<String, &SceneryPackageInfo> = packages
+        .iter()
+        .filter(|info| {
+            !is_global_airports_package(info)
+                && !(info.category == SceneryCategory::Unrecognized && !info.enabled)
+        })
+        .map(|info| (package_key(info), *info))
+        .collect();
+    let lookup = |line: &IniLine| match line {
+        IniLine::Pack { entry, .. } if !entry.is_global_airports => {
+            visible.get(&entry_key(entry)).copied()
+        }
+        _ => None,
+    };
+
+    let position = existing
+        .iter()
+        .position(|line| matches!(line, IniLine::Pack { entry, .. } if entry.is_global_airports))?;
+    let previous = existing[..position].iter().rev().find_map(lookup);
+    let next = existing[position + 1..].iter().find_map(lookup);
+
+    let boundary = SceneryCategory::DefaultAirport.priority();
+    let at_default_position = previous.is_none_or(|p| p.category.priority() < boundary)
+        && next.is_none_or(|n| n.category.priority() >= boundary);
+    if at_default_position {
+        return None;
     }
 
-    entries
+    // Ties sort *GLOBAL_AIRPORTS* first, so this lands right after `previous`
+    Some(previous.map(|p| p.sort_order + 1).unwrap_or(0))
 }
 
 /// Manager for scenery_packs.ini operations
 pub struct SceneryPacksManager {
     xplane_path: PathBuf,
+    /// Normalized X-Plane root scoping the stored Global Airports settings
+    install_key: String,
     ini_path: PathBuf,
     db: DatabaseConnection,
 }
@@ -150,6 +406,7 @@ impl SceneryPacksManager {
         let ini_path = xplane_path.join("Custom Scenery").join("scenery_packs.ini");
         Self {
             xplane_path: xplane_path.to_path_buf(),
+            install_key: path_utils::normalize_xplane_key(xplane_path),
             ini_path,
             db,
         }
@@ -190,6 +447,7 @@ impl SceneryPacksManager {
     ) -> Result<GlobalAirportsState> {
         let enabled = match SceneryQueries::get_metadata(
             &self.db,
+            &self.install_key,
             GLOBAL_AIRPORTS_ENABLED_METADATA_KEY,
         )
         .await
@@ -208,52 +466,63 @@ impl SceneryPacksManager {
             }
         };
 
-        let sort_order =
-            match SceneryQueries::get_metadata(&self.db, GLOBAL_AIRPORTS_SORT_ORDER_METADATA_KEY)
-                .await
-            {
-                Ok(Some(value)) => value
-                    .trim()
-                    .parse::<u32>()
-                    .unwrap_or_else(|_| Self::default_global_airports_sort_order(packages)),
-                Ok(None) => Self::default_global_airports_sort_order(packages),
-                Err(error) => {
-                    logger::log_info(
-                        &format!("Failed to load Global Airports sort metadata: {}", error),
-                        Some("scenery_packs"),
-                    );
-                    Self::default_global_airports_sort_order(packages)
-                }
-            };
+        let sort_order = match SceneryQueries::get_metadata(
+            &self.db,
+            &self.install_key,
+            GLOBAL_AIRPORTS_SORT_ORDER_METADATA_KEY,
+        )
+        .await
+        {
+            Ok(Some(value)) => value
+                .trim()
+                .parse::<u32>()
+                .unwrap_or_else(|_| Self::default_global_airports_sort_order(packages)),
+            // Not set from XFast: keep a custom placement made by another tool
+            Ok(None) => {
+                global_airports_sort_order_from_ini(&self.read_existing_ini_lines().await, packages)
+                    .unwrap_or_else(|| Self::default_global_airports_sort_order(packages))
+            }
+            Err(error) => {
+                logger::log_info(
+                    &format!("Failed to load Global Airports sort metadata: {}", error),
+                    Some("scenery_packs"),
+                );
+                Self::default_global_airports_sort_order(packages)
+            }
+        };
 
-        let category =
-            match SceneryQueries::get_metadata(&self.db, GLOBAL_AIRPORTS_CATEGORY_METADATA_KEY)
-                .await
-            {
-                Ok(Some(value)) => match value.trim() {
-                    "FixedHighPriority" => SceneryCategory::FixedHighPriority,
-                    "Airport" => SceneryCategory::Airport,
-                    "DefaultAirport" => SceneryCategory::DefaultAirport,
-                    "Library" => SceneryCategory::Library,
-                    "Overlay" => SceneryCategory::Overlay,
-                    "AirportMesh" => SceneryCategory::AirportMesh,
-                    "Mesh" => SceneryCategory::Mesh,
-                    "Other" => SceneryCategory::Other,
-                    "Unrecognized" => SceneryCategory::Unrecognized,
-                    _ => Self::default_global_airports_category(),
-                },
-                Ok(None) => Self::default_global_airports_category(),
-                Err(error) => {
-                    logger::log_info(
-                        &format!(
-                            "Failed to load Global Airports category metadata: {}",
-                            error
-                        ),
-                        Some("scenery_packs"),
-                    );
-                    Self::default_global_airports_category()
-                }
-            };
+        let category = match SceneryQueries::get_metadata(
+            &self.db,
+            &self.install_key,
+            GLOBAL_AIRPORTS_CATEGORY_METADATA_KEY,
+        )
+        .await
+        {
+            Ok(Some(value)) => match value.trim() {
+                "FixedHighPriority" => SceneryCategory::FixedHighPriority,
+                "Airport" => SceneryCategory::Airport,
+                "DefaultAirport" => SceneryCategory::DefaultAirport,
+                "Library" => SceneryCategory::Library,
+                "Overlay" => SceneryCategory::Overlay,
+                "AirportMesh" => SceneryCategory::AirportMesh,
+                "OrthoTile" => SceneryCategory::OrthoTile,
+                "Mesh" => SceneryCategory::Mesh,
+                "Other" => SceneryCategory::Other,
+                "Unrecognized" => SceneryCategory::Unrecognized,
+                _ => Self::default_global_airports_category(),
+            },
+            Ok(None) => Self::default_global_airports_category(),
+            Err(error) => {
+                logger::log_info(
+                    &format!(
+                        "Failed to load Global Airports category metadata: {}",
+                        error
+                    ),
+                    Some("scenery_packs"),
+                );
+                Self::default_global_airports_category()
+            }
+        };
 
         Ok(GlobalAirportsState {
             enabled,
@@ -265,6 +534,7 @@ impl SceneryPacksManager {
     pub async fn set_global_airports_enabled(&self, enabled: bool) -> Result<()> {
         SceneryQueries::set_metadata(
             &self.db,
+            &self.install_key,
             GLOBAL_AIRPORTS_ENABLED_METADATA_KEY,
             if enabled { "true" } else { "false" },
         )
@@ -275,6 +545,7 @@ impl SceneryPacksManager {
     pub async fn set_global_airports_sort_order(&self, sort_order: u32) -> Result<()> {
         SceneryQueries::set_metadata(
             &self.db,
+            &self.install_key,
             GLOBAL_AIRPORTS_SORT_ORDER_METADATA_KEY,
             &sort_order.to_string(),
         )
@@ -290,14 +561,20 @@ impl SceneryPacksManager {
             SceneryCategory::Library => "Library",
             SceneryCategory::Overlay => "Overlay",
             SceneryCategory::AirportMesh => "AirportMesh",
+            SceneryCategory::OrthoTile => "OrthoTile",
             SceneryCategory::Mesh => "Mesh",
             SceneryCategory::Other => "Other",
             SceneryCategory::Unrecognized => "Unrecognized",
         };
 
-        SceneryQueries::set_metadata(&self.db, GLOBAL_AIRPORTS_CATEGORY_METADATA_KEY, value)
-            .await
-            .map_err(|e| anyhow!("{}", e))
+        SceneryQueries::set_metadata(
+            &self.db,
+            &self.install_key,
+            GLOBAL_AIRPORTS_CATEGORY_METADATA_KEY,
+            value,
+        )
+        .await
+        .map_err(|e| anyhow!("{}", e))
     }
 
     pub async fn reset_global_airports_position_to_default(&self) -> Result<()> {
@@ -311,21 +588,11 @@ impl SceneryPacksManager {
             .await
     }
 
-    fn write_ini_at_path(ini_path: &Path, entries: &[SceneryPackEntry]) -> Result<()> {
+    fn write_ini_at_path(ini_path: &Path, body: &[String]) -> Result<()> {
         Self::ensure_ini_parent_dir(ini_path)?;
 
         // Build content in memory first so we can retry with a fallback strategy
-        let mut content: Vec<u8> = Vec::new();
-        content.extend_from_slice(INI_HEADER.as_bytes());
-        for entry in entries {
-            let prefix = if entry.enabled {
-                "SCENERY_PACK"
-            } else {
-                "SCENERY_PACK_DISABLED"
-            };
-            let path = entry_path_for_ini(entry);
-            content.extend_from_slice(format!("{} {}\n", prefix, path).as_bytes());
-        }
+        let content = ini_content(body);
 
         // Strategy 1: atomic write via temp file + rename (preferred)
         let temp_path = ini_path.with_extension("ini.tmp");
@@ -384,7 +651,31 @@ impl SceneryPacksManager {
     /// Write sorted entries back to scenery_packs.ini
     #[allow(dead_code)]
     pub fn write_ini(&self, entries: &[SceneryPackEntry]) -> Result<()> {
-        Self::write_ini_at_path(&self.ini_path, entries)
+        let body = merge_with_preserved_lines(entries, &[], &HashSet::new());
+        Self::write_ini_at_path(&self.ini_path, &body)
+    }
+
+    /// Body lines of the current scenery_packs.ini (empty when missing or unreadable)
+    async fn read_existing_ini_lines(&self) -> Vec<IniLine> {
+        let ini_path = self.ini_path.clone();
+        tokio::task::spawn_blocking(move || fs::read_to_string(&ini_path))
+            .await
+            .ok()
+            .and_then(|result| result.ok())
+            .map(|content| parse_ini_lines(&content))
+            .unwrap_or_default()
+    }
+
+    /// Pack entries of the current scenery_packs.ini, in file order
+    pub(crate) async fn current_ini_entries(&self) -> Vec<SceneryPackEntry> {
+        self.read_existing_ini_lines()
+            .await
+            .into_iter()
+            .filter_map(|line| match line {
+                IniLine::Pack { entry, .. } => Some(entry),
+                IniLine::Other(_) => None,
+            })
+            .collect()
     }
 
     fn backup_ini_at_path(ini_path: &Path) -> Result<PathBuf> {
@@ -493,7 +784,7 @@ impl SceneryPacksManager {
         let info = index_manager.get_or_classify(&folder_path).await?;
         if &info.category != category {
             index_manager
-                .update_entry(folder_name, None, None, Some(category.clone()))
+                .update_entry(folder_name, None, None, Some(category.clone()), None)
                 .await?;
         }
 
@@ -503,6 +794,28 @@ impl SceneryPacksManager {
         self.auto_sort_from_index().await
     }
 
+    /// Remove the entry of `folder_name` from scenery_packs.ini, leaving every
+    /// other line where it is. Returns whether an entry was removed.
+    pub async fn remove_entry(&self, folder_name: &str) -> Result<bool> {
+        let ini_path = self.ini_path.clone();
+        let xplane_path = self.xplane_path.clone();
+        let folder = xplane_path.join("Custom Scenery").join(folder_name);
+        tokio::task::spawn_blocking(move || -> Result<bool> {
+            let content = match fs::read_to_string(&ini_path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+                Err(e) => return Err(e.into()),
+            };
+            let Some(body) = body_without_folder(&content, &xplane_path, &folder) else {
+                return Ok(false);
+            };
+            Self::write_ini_at_path(&ini_path, &body)?;
+            Ok(true)
+        })
+        .await
+        .map_err(|e| anyhow!("Task join error: {}", e))?
+    }
+
     /// Ensure all installed scenery is in scenery_packs.ini
     /// Only performs incremental indexing if the index has been created
     pub async fn sync_with_folder(&self) -> Result<usize> {
@@ -540,19 +853,39 @@ impl SceneryPacksManager {
         Ok(added_count)
     }
 
-    /// Sort scenery_packs.ini based entirely on index sort_order
-    /// This is used by the scenery manager after manual reordering
-    pub async fn auto_sort_from_index(&self) -> Result<()> {
+    /// Body generated from the index for the `existing` body. `None` when the
+    /// index has no packages.
+    async fn body_for_existing(&self, existing: &[IniLine]) -> Result<Option<Vec<String>>> {
         let index_manager = SceneryIndexManager::new(&self.xplane_path, self.db.clone());
         let index = index_manager.load_index().await?;
-
         if index.packages.is_empty() {
+            return Ok(None);
+        }
+
+        let mut packages: Vec<_> = index.packages.values().collect();
+        packages.sort_by_key(|p| p.sort_order);
+        let global_airports = self
+            .get_global_airports_state_for_packages(&packages)
+            .await?;
+        Ok(Some(generate_ini_body(
+            &packages,
+            &global_airports,
+            existing,
+        )))
+    }
+
+    /// Sort scenery_packs.ini based entirely on index sort_order
+    /// This is used by the scenery manager after manual reordering
+    pub async fn auto_sort_from_index(&self) -> Result<()> {
+        // Read before the backup moves the file away
+        let existing_lines = self.read_existing_ini_lines().await;
+        let Some(body) = self.body_for_existing(&existing_lines).await? else {
             logger::log_info(
                 "No scenery packages in index, nothing to sort",
                 Some("scenery_packs"),
             );
             return Ok(());
-        }
+        };
 
         // Create backup if ini exists
         if self.ini_path.exists() {
@@ -568,17 +901,13 @@ impl SceneryPacksManager {
             }
         }
 
-        let mut packages: Vec<_> = index.packages.values().collect();
-        packages.sort_by_key(|p| p.sort_order);
-        let global_airports = self
-            .get_global_airports_state_for_packages(&packages)
-            .await?;
-        let entries = build_entries_from_sorted_packages(&packages, &global_airports);
-
         // Write sorted entries
         let ini_path = self.ini_path.clone();
-        let entries_len = entries.len();
-        tokio::task::spawn_blocking(move || Self::write_ini_at_path(&ini_path, &entries))
+        let entries_len = body
+            .iter()
+            .filter(|line| parse_pack_line(line.trim()).is_some())
+            .count();
+        tokio::task::spawn_blocking(move || Self::write_ini_at_path(&ini_path, &body))
             .await
             .map_err(|e| anyhow!("Blocking task failed: {}", e))??;
 
@@ -590,6 +919,35 @@ impl SceneryPacksManager {
         Ok(())
     }
 
+    /// Body that `apply_from_index` writes, without the lines it would carry over
+    /// from the current file. `None` when the index has no packages.
+    pub(crate) async fn body_from_index(&self) -> Result<Option<Vec<String>>> {
+        self.body_for_existing(&[]).await
+    }
+
+    /// What `apply_from_index` would change in scenery_packs.ini, without
+    /// writing anything
+    pub async fn preview_changes(&self) -> Result<SceneryPacksPreview> {
+        let ini_path = self.ini_path.clone();
+        let current = tokio::task::spawn_blocking(move || fs::read_to_string(&ini_path).ok())
+            .await
+            .map_err(|e| anyhow!("Blocking task failed: {}", e))?;
+        let existing_lines = current.as_deref().map(parse_ini_lines).unwrap_or_default();
+        let current_entries = parse_ini_entries(current.as_deref().unwrap_or_default());
+
+        // An empty index leaves the file as it is
+        let new_content = match self.body_for_existing(&existing_lines).await? {
+            Some(body) => String::from_utf8_lossy(&ini_content(&body)).into_owned(),
+            None => current.clone().unwrap_or_default(),
+        };
+        let mut preview =
+            scenery_packs_diff::diff_entries(&current_entries, &parse_ini_entries(&new_content));
+        preview.xplane_path = self.xplane_path.to_string_lossy().into_owned();
+        preview.ini_exists = current.is_some();
+        preview.new_content = new_content;
+        Ok(preview)
+    }
+
     /// Apply index state (enabled/sort_order) to scenery_packs.ini
     /// This preserves the order from the index and applies enabled states
     pub async fn apply_from_index(&self) -> Result<()> {
@@ -598,6 +956,27 @@ impl SceneryPacksManager {
         self.auto_sort_from_index().await
     }
 
+    /// Paths of enabled ini entries whose folder doesn't exist.
+    /// Returns an empty list when the ini is missing.
+    pub async fn enabled_entries_with_missing_folders(&self) -> Result<Vec<String>> {
+        if !self.ini_path.exists() {
+            return Ok(Vec::new());
+        }
+        let ini_path = self.ini_path.clone();
+        let xplane_path = self.xplane_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let content = fs::read_to_string(&ini_path)?;
+            Ok(parse_ini_entries(&content)
+                .into_iter()
+                .filter(|entry| entry.enabled && !entry.is_global_airports)
+                .filter(|entry| !entry_folder(&xplane_path, entry).is_dir())
+                .map(|entry| entry.path)
+                .collect())
+        })
+        .await
+        .map_err(|e| anyhow!("Blocking task failed: {}", e))?
+    }
+
     /// Check if ini file is in sync with the index
     /// Returns true if ini order/enabled states match the entries generated from the index.
     pub async fn is_synced_with_index(&self) -> Result<bool> {
@@ -625,7 +1004,12 @@ impl SceneryPacksManager {
             .get_global_airports_state_for_packages(&packages)
             .await?;
         let expected_entries = build_entries_from_sorted_packages(&packages, &global_airports);
-        let ini_entries = parse_ini_entries(&content);
+        // Preserved lines belong to other tools and don't affect sync state
+        let managed_keys = managed_entry_keys(&packages);
+        let ini_entries: Vec<SceneryPackEntry> = parse_ini_entries(&content)
+            .into_iter()
+            .filter(|entry| entry.enabled || managed_keys.contains(&entry_key(entry)))
+            .collect();
 
         if ini_entries.len() != expected_entries.len() {
             return Ok(false);
@@ -680,6 +1064,8 @@ mod tests {
             has_objects: false,
             texture_count: 0,
             earth_nav_tile_count: 0,
+            total_size_bytes: 0,
+            newest_file_modified: None,
             indexed_at: SystemTime::UNIX_EPOCH,
             required_libraries: Vec::new(),
             missing_libraries: Vec::new(),
@@ -689,6 +1075,8 @@ mod tests {
             actual_path: None,
             continent: None,
             original_category: None,
+            classifier_version: 0,
+            pinned: false,
         }
     }
 
@@ -810,4 +1198,219 @@ mod tests {
         assert!(entries[2].is_global_airports);
         assert_eq!(entries[3].path, "Custom Scenery/Overlay A/");
     }
+
+    fn ini_with_body(body: &[&str]) -> String {
+        let mut content = INI_HEADER.to_string();
+        for line in body {
+            content.push_str(line);
+            content.push('\n');
+        }
+        content
+    }
+
+    #[test]
+    fn round_trip_keeps_unmanaged_lines_and_custom_global_airports_position() {
+        let airport = make_package("Airport A", SceneryCategory::Airport, 0, true);
+        let mut shortcut = make_package("Library L", SceneryCategory::Library, 1, true);
+        shortcut.actual_path = Some("D:/Shortcut Target/Library L/".to_string());
+        let symlinked = make_package("Linked Overlay", SceneryCategory::Overlay, 2, true);
+        let mesh = make_package("Mesh M", SceneryCategory::Mesh, 3, true);
+        let packages = vec![&airport, &shortcut, &symlinked, &mesh];
+
+        let body = [
+            "# managed by xOrganizer",
+            "SCENERY_PACK Custom Scenery/Airport A/",
+            "SCENERY_PACK_DISABLED Custom Scenery/Not Indexed/",
+            "; keep me",
+            "SCENERY_PACK D:/Shortcut Target/Library L/",
+            "SCENERY_PACK Custom Scenery/Linked Overlay/",
+            "SCENERY_PACK *GLOBAL_AIRPORTS*",
+            "SCENERY_PACK Custom Scenery/Mesh M/",
+            "FUTURE_DIRECTIVE something",
+        ];
+        let content = ini_with_body(&body);
+        let existing = parse_ini_lines(&content);
+
+        let sort_order = global_airports_sort_order_from_ini(&existing, &packages);
+        assert_eq!(sort_order, Some(3));
+
+        let entries = build_entries_from_sorted_packages(
+            &packages,
+            &global_airports_state(true, sort_order.unwrap(), SceneryCategory::DefaultAirport),
+        );
+        let merged =
+            merge_with_preserved_lines(&entries, &existing, &managed_entry_keys(&packages));
+        assert_eq!(merged, body);
+
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        let ini_path = temp.path().join("Custom Scenery").join("scenery_packs.ini");
+        SceneryPacksManager::write_ini_at_path(&ini_path, &merged).expect("write should succeed");
+        assert_eq!(
+            fs::read_to_string(&ini_path).expect("failed to read ini"),
+            content
+        );
+    }
+
+    #[test]
+    fn removing_a_folder_keeps_every_other_line() {
+        let xplane = Path::new("/X-Plane 12");
+        let body = [
+            "SCENERY_PACK Custom Scenery/Airport A/",
+            "# my comment",
+            "SCENERY_PACK_DISABLED Custom Scenery/Ortho B/",
+            "SCENERY_PACK *GLOBAL_AIRPORTS*",
+            "SCENERY_PACK Custom Scenery/Mesh M/",
+        ];
+        let content = ini_with_body(&body);
+
+        let remaining = body_without_folder(
+            &content,
+            xplane,
+            &xplane.join("Custom Scenery").join("Ortho B"),
+        );
+        assert_eq!(
+            remaining,
+            Some(vec![
+                body[0].to_string(),
+                body[1].to_string(),
+                body[3].to_string(),
+                body[4].to_string(),
+            ])
+        );
+        assert_eq!(
+            body_without_folder(
+                &content,
+                xplane,
+                &xplane.join("Custom Scenery").join("Not Listed")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn preserved_lines_follow_their_entry_and_stale_lines_are_dropped() {
+        let airport = make_package("Airport A", SceneryCategory::Airport, 1, true);
+        let overlay = make_package("Overlay O", SceneryCategory::Overlay, 0, false);
+        let packages = vec![&overlay, &airport];
+
+        let existing = parse_ini_lines(&ini_with_body(&[
+            "SCENERY_PACK C:\\X-Plane\\Custom Scenery\\Airport A\\",
+            "SCENERY_PACK_DISABLED Custom Scenery/Not Indexed/",
+            "SCENERY_PACK Custom Scenery/Deleted Pack/",
+            "SCENERY_PACK *GLOBAL_AIRPORTS*",
+            "SCENERY_PACK Custom Scenery/Removed From Index/",
+            "# after removed",
+            "SCENERY_PACK Custom Scenery/Overlay O/",
+        ]));
+
+        // Airport before, overlay after: the default boundary, so no override
+        assert_eq!(
+            global_airports_sort_order_from_ini(&existing, &packages),
+            None
+        );
+
+        let entries = build_entries_from_sorted_packages(
+            &packages,
+            &global_airports_state(true, 1, SceneryCategory::DefaultAirport),
+        );
+        let merged =
+            merge_with_preserved_lines(&entries, &existing, &managed_entry_keys(&packages));
+        assert_eq!(
+            merged,
+            vec![
+                "SCENERY_PACK_DISABLED Custom Scenery/Overlay O/",
+                "SCENERY_PACK *GLOBAL_AIRPORTS*",
+                "# after removed",
+                "SCENERY_PACK Custom Scenery/Airport A/",
+                "SCENERY_PACK_DISABLED Custom Scenery/Not Indexed/",
+            ]
+        );
+    }
+
+    #[test]
+    fn preview_of_generated_body_reports_entries_unknown_to_the_index() {
+        let airport = make_package("Airport A", SceneryCategory::Airport, 0, true);
+        let overlay = make_package("Overlay O", SceneryCategory::Overlay, 1, false);
+        let packages = vec![&airport, &overlay];
+
+        let current = ini_with_body(&[
+            "SCENERY_PACK Custom Scenery/Overlay O/",
+            "SCENERY_PACK Custom Scenery/Unknown Enabled/",
+            "SCENERY_PACK_DISABLED Custom Scenery/Unknown Disabled/",
+            "SCENERY_PACK Custom Scenery/Airport A/",
+            "SCENERY_PACK *GLOBAL_AIRPORTS*",
+        ]);
+        let body = generate_ini_body(
+            &packages,
+            &global_airports_state(true, 1, SceneryCategory::DefaultAirport),
+            &parse_ini_lines(&current),
+        );
+        assert_eq!(
+            body,
+            vec![
+                "SCENERY_PACK Custom Scenery/Airport A/",
+                "SCENERY_PACK *GLOBAL_AIRPORTS*",
+                "SCENERY_PACK_DISABLED Custom Scenery/Overlay O/",
+                "SCENERY_PACK_DISABLED Custom Scenery/Unknown Disabled/",
+            ]
+        );
+
+        let new_content = String::from_utf8(ini_content(&body)).unwrap();
+        let preview = scenery_packs_diff::diff_entries(
+            &parse_ini_entries(&current),
+            &parse_ini_entries(&new_content),
+        );
+        // The enabled unknown entry is dropped, the disabled one carried over
+        assert_eq!(preview.removed.len(), 1);
+        assert_eq!(preview.removed[0].path, "Custom Scenery/Unknown Enabled/");
+        assert_eq!(preview.removed[0].old_position, Some(1));
+        assert!(preview.added.is_empty());
+        assert_eq!(preview.disabled.len(), 1);
+        assert_eq!(preview.disabled[0].path, "Custom Scenery/Overlay O/");
+        let reordered: Vec<_> = preview
+            .reordered
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry.old_position, entry.new_position))
+            .collect();
+        assert_eq!(
+            reordered,
+            vec![
+                ("Custom Scenery/Airport A/", Some(3), Some(0)),
+                (GLOBAL_AIRPORTS_ENTRY_NAME, Some(4), Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn composed_ini_entries_match_decomposed_folders_and_keep_the_disk_form() {
+        use unicode_normalization::UnicodeNormalization;
+
+        let composed = "Новосибирск Толмачёво";
+        let decomposed: String = composed.nfd().collect();
+        let airport = make_package(&decomposed, SceneryCategory::Airport, 0, true);
+        let packages = vec![&airport];
+
+        let existing = parse_ini_lines(&ini_with_body(&[&format!(
+            "SCENERY_PACK_DISABLED Custom Scenery/{}/",
+            composed
+        )]));
+        let entries = build_entries_from_sorted_packages(
+            &packages,
+            &global_airports_state(true, 1, SceneryCategory::DefaultAirport),
+        );
+        let merged =
+            merge_with_preserved_lines(&entries, &existing, &managed_entry_keys(&packages));
+        assert_eq!(
+            merged,
+            vec![
+                format!("SCENERY_PACK Custom Scenery/{}/", decomposed),
+                "SCENERY_PACK *GLOBAL_AIRPORTS*".to_string(),
+            ]
+        );
+
+        let temp = tempfile::tempdir().expect("failed to create tempdir");
+        fs::create_dir_all(temp.path().join("Custom Scenery").join(&decomposed)).unwrap();
+        let entry = parse_pack_line(&format!("SCENERY_PACK Custom Scenery/{}/", composed)).unwrap();
+        assert!(entry_folder(temp.path(), &entry).is_dir());
+    }
 }
@@ -0,0 +1,800 @@
+//! scenery_packs.ini validation and repair
+//!
+//! A crash while the file is written can leave it truncated or written twice,
+//! and X-Plane then ignores some or all scenery. Validation works on the raw
+//! bytes, so encoding problems show up next to structural ones: a byte order
+//! mark, CRLF line endings and lines that aren't UTF-8, besides missing header
+//! lines, duplicate entries and entries whose folder is gone.
+//!
+//! A repair either rebuilds the file from the scenery index, as
+//! `apply_from_index` writes it, or cleans it in place: broken, duplicate and
+//! dead lines are dropped and the rest keeps its order. The previous file is
+//! kept as `scenery_packs.ini.bak`. Both files are written to a temporary file
+//! that is then renamed, so an interrupted repair can't leave a half-written
+//! file behind. The reported changes list every line removed, added, moved or
+//! switched between enabled and disabled; blank lines are not listed.
+
+use crate::error::{ApiError, ApiResult};
+use crate::models::{
+    SceneryPackEntry, SceneryPacksIniChange, SceneryPacksIniChangeKind, SceneryPacksIniIssue,
+    SceneryPacksIniIssueKind, SceneryPacksIniRepairResult, SceneryPacksIniReport,
+    SceneryPacksRepairStrategy,
+};
+use crate::scenery_packs_manager::{self, SceneryPacksManager};
+use anyhow::anyhow;
+use sea_orm::DatabaseConnection;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const BACKUP_FILE_NAME: &str = "scenery_packs.ini.bak";
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const HEADER_PARTS: [&str; 3] = ["I", "1000 Version", "SCENERY"];
+
+fn ini_path(xplane_path: &Path) -> PathBuf {
+    xplane_path.join("Custom Scenery").join("scenery_packs.ini")
+}
+
+#[derive(Debug, Clone)]
+enum LineClass {
+    Blank,
+    /// Header line at the top of the file
+    Header,
+    /// Header line below the first entry or directive
+    StrayHeader,
+    Pack(SceneryPackEntry),
+    Other,
+}
+
+#[derive(Debug)]
+struct ParsedLine {
+    /// 1-based line number
+    number: usize,
+    /// Line without its line break, invalid bytes replaced
+    text: String,
+    class: LineClass,
+    /// The first problem found on the line
+    issue: Option<SceneryPacksIniIssue>,
+}
+
+#[derive(Debug)]
+struct ParsedIni {
+    bom: bool,
+    crlf_lines: Vec<usize>,
+    truncated: bool,
+    /// Header parts the top of the file lacks
+    missing_header: Vec<&'static str>,
+    lines: Vec<ParsedLine>,
+}
+
+impl ParsedIni {
+    /// Index of the first line below the header
+    fn body_start(&self) -> usize {
+        self.lines
+            .iter()
+            .position(|line| !matches!(line.class, LineClass::Blank | LineClass::Header))
+            .unwrap_or(self.lines.len())
+    }
+
+    fn entry_count(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|line| matches!(line.class, LineClass::Pack(_)))
+            .count()
+    }
+}
+
+fn line_issue(
+    kind: SceneryPacksIniIssueKind,
+    number: usize,
+    text: &str,
+    message: String,
+) -> Option<SceneryPacksIniIssue> {
+    Some(SceneryPacksIniIssue {
+        kind,
+        line: Some(number),
+        content: Some(text.to_string()),
+        message,
+    })
+}
+
+fn parse(bytes: &[u8], xplane_path: &Path) -> ParsedIni {
+    let bom = bytes.starts_with(UTF8_BOM);
+    let content = if bom { &bytes[UTF8_BOM.len()..] } else { bytes };
+    let truncated = !content.is_empty() && !content.ends_with(b"\n");
+    let mut raw_lines: Vec<&[u8]> = content.split(|&b| b == b'\n').collect();
+    if !truncated {
+        // Empty remainder after the final line break
+        raw_lines.pop();
+    }
+
+    let mut crlf_lines = Vec::new();
+    let mut header_seen = [false; 3];
+    let mut in_header = true;
+    let mut first_seen: HashMap<String, usize> = HashMap::new();
+    let mut lines = Vec::with_capacity(raw_lines.len());
+
+    for (i, raw) in raw_lines.into_iter().enumerate() {
+        let number = i + 1;
+        let raw = match raw.strip_suffix(b"\r") {
+            Some(without_cr) => {
+                crlf_lines.push(number);
+                without_cr
+            }
+            None => raw,
+        };
+        let valid_utf8 = std::str::from_utf8(raw).is_ok();
+        let text = String::from_utf8_lossy(raw).trim_end().to_string();
+        let trimmed = text.trim();
+
+        let class = if trimmed.is_empty() {
+            LineClass::Blank
+        } else if scenery_packs_manager::is_ini_header_line(trimmed) {
+            if in_header {
+                let part = match trimmed {
+                    "I" | "A" => 0,
+                    "SCENERY" => 2,
+                    _ => 1,
+                };
+                header_seen[part] = true;
+                LineClass::Header
+            } else {
+                LineClass::StrayHeader
+            }
+        } else {
+            in_header = false;
+            match scenery_packs_manager::parse_pack_line(trimmed) {
+                Some(entry) => LineClass::Pack(entry),
+                None => LineClass::Other,
+            }
+        };
+
+        let issue = if !valid_utf8 {
+            line_issue(
+                SceneryPacksIniIssueKind::InvalidUtf8,
+                number,
+                &text,
+                format!("Line {} contains bytes that are not valid UTF-8", number),
+            )
+        } else {
+            match &class {
+                LineClass::StrayHeader => line_issue(
+                    SceneryPacksIniIssueKind::StrayHeader,
+                    number,
+                    &text,
+                    format!("Header line '{}' repeated below the entries", trimmed),
+                ),
+                LineClass::Pack(entry) => {
+                    let key = scenery_packs_manager::entry_key(entry);
+                    if let Some(first) = first_seen.get(&key) {
+                        line_issue(
+                            SceneryPacksIniIssueKind::DuplicateEntry,
+                            number,
+                            &text,
+                            format!("Same entry as line {}", first),
+                        )
+                    } else {
+                        first_seen.insert(key, number);
+                        if !entry.is_global_airports
+                            && !scenery_packs_manager::entry_folder(xplane_path, entry).is_dir()
+                        {
+                            line_issue(
+                                SceneryPacksIniIssueKind::MissingFolder,
+                                number,
+                                &text,
+                                format!("Folder not found: {}", entry.path),
+                            )
+                        } else {
+                            None
+                        }
+                    }
+                }
+                _ => None,
+            }
+        };
+
+        lines.push(ParsedLine {
+            number,
+            text,
+            class,
+            issue,
+        });
+    }
+
+    let missing_header = HEADER_PARTS
+        .iter()
+        .zip(header_seen)
+        .filter(|(_, seen)| !seen)
+        .map(|(part, _)| *part)
+        .collect();
+
+    ParsedIni {
+        bom,
+        crlf_lines,
+        truncated,
+        missing_header,
+        lines,
+    }
+}
+
+fn issues(parsed: &ParsedIni) -> Vec<SceneryPacksIniIssue> {
+    let mut issues = Vec::new();
+    if parsed.bom {
+        issues.push(SceneryPacksIniIssue {
+            kind: SceneryPacksIniIssueKind::ByteOrderMark,
+            line: Some(1),
+            content: None,
+            message: "The file starts with a byte order mark, which hides the header from X-Plane"
+                .to_string(),
+        });
+    }
+    for part in &parsed.missing_header {
+        issues.push(SceneryPacksIniIssue {
+            kind: SceneryPacksIniIssueKind::MissingHeader,
+            line: None,
+            content: Some(part.to_string()),
+            message: format!("Header line '{}' is missing", part),
+        });
+    }
+    if let Some(&first) = parsed.crlf_lines.first() {
+        issues.push(SceneryPacksIniIssue {
+            kind: SceneryPacksIniIssueKind::CrlfLineEndings,
+            line: Some(first),
+            content: None,
+            message: format!(
+                "{} of {} lines end with CRLF instead of LF",
+                parsed.crlf_lines.len(),
+                parsed.lines.len()
+            ),
+        });
+    }
+    issues.extend(parsed.lines.iter().filter_map(|line| line.issue.clone()));
+    if parsed.truncated {
+        let last = parsed.lines.last();
+        issues.push(SceneryPacksIniIssue {
+            kind: SceneryPacksIniIssueKind::Truncated,
+            line: last.map(|line| line.number),
+            content: last.map(|line| line.text.clone()),
+            message: "The last line has no line break; the file may have been cut off".to_string(),
+        });
+    }
+    issues
+}
+
+fn change(
+    kind: SceneryPacksIniChangeKind,
+    old_line: Option<usize>,
+    new_line: Option<usize>,
+    content: Option<String>,
+) -> SceneryPacksIniChange {
+    SceneryPacksIniChange {
+        kind,
+        reason: None,
+        old_line,
+        new_line,
+        content,
+    }
+}
+
+fn removed(line: &ParsedLine) -> SceneryPacksIniChange {
+    SceneryPacksIniChange {
+        reason: line.issue.as_ref().map(|issue| issue.kind),
+        ..change(
+            SceneryPacksIniChangeKind::LineRemoved,
+            Some(line.number),
+            None,
+            Some(line.text.clone()),
+        )
+    }
+}
+
+/// Changes every repair makes to the encoding and the end of the file
+fn normalization_changes(
+    parsed: &ParsedIni,
+    header_restored: bool,
+    changes: &[SceneryPacksIniChange],
+) -> Vec<SceneryPacksIniChange> {
+    let mut normalized = Vec::new();
+    if parsed.bom {
+        normalized.push(change(
+            SceneryPacksIniChangeKind::ByteOrderMarkRemoved,
+            Some(1),
+            None,
+            None,
+        ));
+    }
+    if header_restored {
+        normalized.push(change(
+            SceneryPacksIniChangeKind::HeaderRestored,
+            None,
+            Some(1),
+            Some(scenery_packs_manager::INI_HEADER.trim_end().to_string()),
+        ));
+    }
+    if !parsed.crlf_lines.is_empty() {
+        normalized.push(change(
+            SceneryPacksIniChangeKind::LineEndingsNormalized,
+            parsed.crlf_lines.first().copied(),
+            None,
+            None,
+        ));
+    }
+    if let Some(last) = parsed.lines.last().filter(|_| parsed.truncated) {
+        let last_removed = changes.iter().any(|c| {
+            c.kind == SceneryPacksIniChangeKind::LineRemoved && c.old_line == Some(last.number)
+        });
+        if !last_removed {
+            normalized.push(change(
+                SceneryPacksIniChangeKind::FinalNewlineAdded,
+                Some(last.number),
+                None,
+                Some(last.text.clone()),
+            ));
+        }
+    }
+    normalized
+}
+
+fn join_lines(lines: &[String]) -> Vec<u8> {
+    let mut content = Vec::new();
+    for line in lines {
+        content.extend_from_slice(line.as_bytes());
+        content.push(b'\n');
+    }
+    content
+}
+
+/// Drop lines with an issue, keep everything else in order
+fn clean(parsed: &ParsedIni) -> (Vec<u8>, Vec<SceneryPacksIniChange>) {
+    let body_start = parsed.body_start();
+    let header_restored = !parsed.missing_header.is_empty();
+    let mut output: Vec<String> = if header_restored {
+        scenery_packs_manager::INI_HEADER
+            .lines()
+            .map(str::to_string)
+            .collect()
+    } else {
+        parsed.lines[..body_start]
+            .iter()
+            .map(|line| line.text.clone())
+            .collect()
+    };
+
+    let mut changes = Vec::new();
+    for line in &parsed.lines[body_start..] {
+        if line.issue.is_some() {
+            changes.push(removed(line));
+        } else {
+            output.push(line.text.clone());
+        }
+    }
+
+    let mut all = normalization_changes(parsed, header_restored, &changes);
+    all.extend(changes);
+    (join_lines(&output), all)
+}
+
+/// Positions of the longest strictly increasing run of `values`
+fn longest_increasing_run(values: &[usize]) -> Vec<bool> {
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; values.len()];
+    for (i, &value) in values.iter().enumerate() {
+        let pos = tails.partition_point(|&t| values[t] < value);
+        previous[i] = pos.checked_sub(1).map(|p| tails[p]);
+        if pos == tails.len() {
+            tails.push(i);
+        } else {
+            tails[pos] = i;
+        }
+    }
+
+    let mut kept = vec![false; values.len()];
+    let mut current = tails.last().copied();
+    while let Some(i) = current {
+        kept[i] = true;
+        current = previous[i];
+    }
+    kept
+}
+
+/// Replace the file with `body` generated from the index and describe the
+/// difference entry by entry
+fn rebuild(parsed: &ParsedIni, body: &[String]) -> (Vec<u8>, Vec<SceneryPacksIniChange>) {
+    let header_lines: Vec<String> = scenery_packs_manager::INI_HEADER
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let body_start = parsed.body_start();
+    let old_header: Vec<&str> = parsed.lines[..body_start]
+        .iter()
+        .filter(|line| matches!(line.class, LineClass::Header))
+        .map(|line| line.text.trim())
+        .collect();
+    let header_restored = old_header != HEADER_PARTS;
+
+    // Entries that can carry over, by key
+    let mut old_entries: HashMap<String, &ParsedLine> = HashMap::new();
+    let mut changes = Vec::new();
+    let mut removals = Vec::new();
+    for line in &parsed.lines[body_start..] {
+        match (&line.class, &line.issue) {
+            (LineClass::Blank, _) => {}
+            (LineClass::Pack(entry), None)
+            | (
+                LineClass::Pack(entry),
+                Some(SceneryPacksIniIssue {
+                    kind: SceneryPacksIniIssueKind::MissingFolder,
+                    ..
+                }),
+            ) => {
+                old_entries.insert(scenery_packs_manager::entry_key(entry), line);
+            }
+            _ => removals.push(removed(line)),
+        }
+    }
+
+    let mut matched_keys = HashSet::new();
+    let mut matched: Vec<(usize, usize, &str)> = Vec::new();
+    for (i, text) in body.iter().enumerate() {
+        let new_line = header_lines.len() + i + 1;
+        let Some(entry) = scenery_packs_manager::parse_pack_line(text.trim()) else {
+            continue;
+        };
+        let key = scenery_packs_manager::entry_key(&entry);
+        match old_entries.get(&key) {
+            Some(old) => {
+                matched_keys.insert(key);
+                matched.push((old.number, new_line, text.as_str()));
+                let was_enabled = matches!(&old.class, LineClass::Pack(e) if e.enabled);
+                if was_enabled != entry.enabled {
+                    changes.push(change(
+                        SceneryPacksIniChangeKind::EntryStateChanged,
+                        Some(old.number),
+                        Some(new_line),
+                        Some(text.clone()),
+                    ));
+                }
+            }
+            None => changes.push(change(
+                SceneryPacksIniChangeKind::EntryAdded,
+                None,
+                Some(new_line),
+                Some(text.clone()),
+            )),
+        }
+    }
+
+    for (key, line) in &old_entries {
+        if !matched_keys.contains(key) {
+            removals.push(removed(line));
+        }
+    }
+    removals.sort_by_key(|c| c.old_line);
+
+    let old_order: Vec<usize> = matched.iter().map(|(old, _, _)| *old).collect();
+    let in_place = longest_increasing_run(&old_order);
+    for ((old, new, text), kept) in matched.iter().zip(in_place) {
+        if !kept {
+            changes.push(change(
+                SceneryPacksIniChangeKind::EntryMoved,
+                Some(*old),
+                Some(*new),
+                Some(text.to_string()),
+            ));
+        }
+    }
+    changes.sort_by_key(|c| c.new_line);
+
+    let mut all = normalization_changes(parsed, header_restored, &removals);
+    all.extend(removals);
+    all.extend(changes);
+
+    let output: Vec<String> = header_lines.into_iter().chain(body.to_vec()).collect();
+    (join_lines(&output), all)
+}
+
+/// Write `content` to a temporary file next to `path`, then rename it over `path`
+fn write_atomically(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    let result = (|| -> std::io::Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Back up the previous file and write the repaired one
+fn apply_repair(
+    xplane_path: &Path,
+    original: Option<&[u8]>,
+    content: Vec<u8>,
+    changes: Vec<SceneryPacksIniChange>,
+    strategy: SceneryPacksRepairStrategy,
+) -> anyhow::Result<SceneryPacksIniRepairResult> {
+    let ini_path = ini_path(xplane_path);
+    let remaining_issues = issues(&parse(&content, xplane_path));
+    if original == Some(content.as_slice()) {
+        return Ok(SceneryPacksIniRepairResult {
+            strategy,
+            changed: false,
+            backup_path: None,
+            changes: Vec::new(),
+            remaining_issues,
+        });
+    }
+
+    let backup_path = match original {
+        Some(original) => {
+            let backup_path = ini_path.with_file_name(BACKUP_FILE_NAME);
+            write_atomically(&backup_path, original)
+                .map_err(|e| anyhow!("Failed to back up '{}': {}", backup_path.display(), e))?;
+            Some(backup_path.to_string_lossy().to_string())
+        }
+        None => None,
+    };
+    if let Some(parent) = ini_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomically(&ini_path, &content).map_err(|e| {
+        anyhow!(
+            "Failed to write '{}': {}. Make sure X-Plane is not running, and that the Custom Scenery folder is writable.",
+            ini_path.display(),
+            e
+        )
+    })?;
+
+    Ok(SceneryPacksIniRepairResult {
+        strategy,
+        changed: true,
+        backup_path,
+        changes,
+        remaining_issues,
+    })
+}
+
+fn read_ini(xplane_path: &Path) -> anyhow::Result<Option<Vec<u8>>> {
+    let ini_path = ini_path(xplane_path);
+    match fs::read(&ini_path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(anyhow!("Failed to read '{}': {}", ini_path.display(), e)),
+    }
+}
+
+/// Report structural problems of scenery_packs.ini
+pub async fn validate_scenery_packs_ini(xplane_path: &Path) -> ApiResult<SceneryPacksIniReport> {
+    let xplane_path = xplane_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let ini_path = ini_path(&xplane_path).to_string_lossy().to_string();
+        let Some(bytes) = read_ini(&xplane_path)? else {
+            return Ok(SceneryPacksIniReport {
+                ini_path,
+                exists: false,
+                entry_count: 0,
+                issues: vec![SceneryPacksIniIssue {
+                    kind: SceneryPacksIniIssueKind::MissingFile,
+                    line: None,
+                    content: None,
+                    message: "scenery_packs.ini does not exist".to_string(),
+                }],
+            });
+        };
+        let parsed = parse(&bytes, &xplane_path);
+        Ok(SceneryPacksIniReport {
+            ini_path,
+            exists: true,
+            entry_count: parsed.entry_count(),
+            issues: issues(&parsed),
+        })
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+/// Repair scenery_packs.ini with `strategy`, keeping the previous file as
+/// `scenery_packs.ini.bak`
+pub async fn repair_scenery_packs_ini(
+    db: &DatabaseConnection,
+    xplane_path: &Path,
+    strategy: SceneryPacksRepairStrategy,
+) -> ApiResult<SceneryPacksIniRepairResult> {
+    let index_body = match strategy {
+        SceneryPacksRepairStrategy::RebuildFromIndex => {
+            let manager = SceneryPacksManager::new(xplane_path, db.clone());
+            let body = manager.body_from_index().await?.ok_or_else(|| {
+                ApiError::validation(
+                    "The scenery index is empty; build it before rebuilding scenery_packs.ini",
+                )
+            })?;
+            Some(body)
+        }
+        SceneryPacksRepairStrategy::CleanInPlace => None,
+    };
+
+    let xplane_path = xplane_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let original = read_ini(&xplane_path)?;
+        let parsed = parse(original.as_deref().unwrap_or_default(), &xplane_path);
+        let (content, changes) = match &index_body {
+            Some(body) => rebuild(&parsed, body),
+            None if original.is_none() => {
+                return Err(ApiError::validation(
+                    "scenery_packs.ini does not exist; rebuild it from the scenery index instead",
+                ));
+            }
+            None => clean(&parsed),
+        };
+        Ok(apply_repair(
+            &xplane_path,
+            original.as_deref(),
+            content,
+            changes,
+            strategy,
+        )?)
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("Task join error: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(issues: &[SceneryPacksIniIssue]) -> Vec<SceneryPacksIniIssueKind> {
+        issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    fn install_with_folders(folders: &[&str]) -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        for folder in folders {
+            fs::create_dir_all(root.path().join("Custom Scenery").join(folder)).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_reports_structural_and_encoding_issues() {
+        let root = install_with_folders(&["KSEA Demo", "Library"]);
+        let mut bytes = b"\xEF\xBB\xBFI\r\n1000 Version\r\n\n".to_vec();
+        bytes.extend_from_slice(b"SCENERY_PACK Custom Scenery/KSEA Demo/\n");
+        bytes.extend_from_slice(b"SCENERY_PACK Custom Scenery/Gone/\n");
+        bytes.extend_from_slice(b"SCENERY_PACK Custom Scenery/\xFFbad/\n");
+        bytes.extend_from_slice(b"SCENERY\n");
+        bytes.extend_from_slice(b"SCENERY_PACK Custom Scenery/ksea demo\n");
+        bytes.extend_from_slice(b"SCENERY_PACK Custom Scenery/Libr");
+
+        let parsed = parse(&bytes, root.path());
+        assert_eq!(parsed.entry_count(), 5);
+        assert_eq!(
+            kinds(&issues(&parsed)),
+            vec![
+                SceneryPacksIniIssueKind::ByteOrderMark,
+                SceneryPacksIniIssueKind::MissingHeader,
+                SceneryPacksIniIssueKind::CrlfLineEndings,
+                SceneryPacksIniIssueKind::MissingFolder,
+                SceneryPacksIniIssueKind::InvalidUtf8,
+                SceneryPacksIniIssueKind::StrayHeader,
+                SceneryPacksIniIssueKind::DuplicateEntry,
+                SceneryPacksIniIssueKind::MissingFolder,
+                SceneryPacksIniIssueKind::Truncated,
+            ]
+        );
+        let duplicate = &issues(&parsed)[6];
+        assert_eq!(duplicate.line, Some(8));
+        assert_eq!(duplicate.message, "Same entry as line 4");
+    }
+
+    #[test]
+    fn test_clean_in_place_keeps_order_and_lists_removals() {
+        let root = install_with_folders(&["B", "A"]);
+        let content = "I\n1000 Version\nSCENERY\n\n\
+            SCENERY_PACK Custom Scenery/B/\n\
+            # keep me\n\
+            SCENERY_PACK Custom Scenery/Gone/\n\
+            SCENERY_PACK *GLOBAL_AIRPORTS*\n\
+            SCENERY_PACK_DISABLED Custom Scenery/A/\n\
+            SCENERY_PACK Custom Scenery/B/\n";
+        let ini = ini_path(root.path());
+        fs::write(&ini, content).unwrap();
+
+        let parsed = parse(content.as_bytes(), root.path());
+        let (output, changes) = clean(&parsed);
+        assert_eq!(
+            String::from_utf8(output.clone()).unwrap(),
+            "I\n1000 Version\nSCENERY\n\n\
+            SCENERY_PACK Custom Scenery/B/\n\
+            # keep me\n\
+            SCENERY_PACK *GLOBAL_AIRPORTS*\n\
+            SCENERY_PACK_DISABLED Custom Scenery/A/\n"
+        );
+        let removed: Vec<_> = changes
+            .iter()
+            .map(|c| (c.kind, c.reason, c.old_line))
+            .collect();
+        assert_eq!(
+            removed,
+            vec![
+                (
+                    SceneryPacksIniChangeKind::LineRemoved,
+                    Some(SceneryPacksIniIssueKind::MissingFolder),
+                    Some(7)
+                ),
+                (
+                    SceneryPacksIniChangeKind::LineRemoved,
+                    Some(SceneryPacksIniIssueKind::DuplicateEntry),
+                    Some(10)
+                ),
+            ]
+        );
+
+        let result = apply_repair(
+            root.path(),
+            Some(content.as_bytes()),
+            output,
+            changes,
+            SceneryPacksRepairStrategy::CleanInPlace,
+        )
+        .unwrap();
+        assert!(result.changed);
+        assert!(result.remaining_issues.is_empty());
+        assert_eq!(
+            fs::read_to_string(ini.with_file_name(BACKUP_FILE_NAME)).unwrap(),
+            content
+        );
+        assert!(!ini.with_file_name("scenery_packs.ini.tmp").exists());
+    }
+
+    #[test]
+    fn test_rebuild_describes_added_removed_moved_and_toggled_entries() {
+        let root = install_with_folders(&["A", "B", "C", "D"]);
+        let content = "A\n1100 Version\nSCENERY\n\n\
+            SCENERY_PACK Custom Scenery/A/\n\
+            SCENERY_PACK Custom Scenery/B/\n\
+            # comment\n\
+            SCENERY_PACK Custom Scenery/C/\n\
+            SCENERY_PACK Custom Scenery/A/\n";
+        let parsed = parse(content.as_bytes(), root.path());
+        let body: Vec<String> = [
+            "SCENERY_PACK Custom Scenery/B/",
+            "SCENERY_PACK Custom Scenery/C/",
+            "SCENERY_PACK_DISABLED Custom Scenery/A/",
+            "SCENERY_PACK Custom Scenery/D/",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+
+        let (output, changes) = rebuild(&parsed, &body);
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .starts_with("I\n1000 Version\nSCENERY\n\nSCENERY_PACK Custom Scenery/B/\n"));
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.kind, c.old_line, c.new_line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (SceneryPacksIniChangeKind::HeaderRestored, None, Some(1)),
+                (SceneryPacksIniChangeKind::LineRemoved, Some(7), None),
+                (SceneryPacksIniChangeKind::LineRemoved, Some(9), None),
+                (
+                    SceneryPacksIniChangeKind::EntryStateChanged,
+                    Some(5),
+                    Some(7)
+                ),
+                (SceneryPacksIniChangeKind::EntryMoved, Some(5), Some(7)),
+                (SceneryPacksIniChangeKind::EntryAdded, None, Some(8)),
+            ]
+        );
+    }
+}
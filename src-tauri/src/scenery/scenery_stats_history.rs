@@ -0,0 +1,303 @@
+//! Scenery library growth history
+//!
+//! When a rebuild or quick scan completes, the aggregate index statistics
+//! (package count and size per category, distinct missing libraries) are stored
+//! as a snapshot, at most once per [`SNAPSHOT_INTERVAL_SECS`]. Only the newest
+//! [`MAX_SNAPSHOTS`] are kept per X-Plane installation.
+
+use crate::database::entities::scenery_stats_snapshots;
+use crate::error::ApiResult;
+use crate::logger;
+use crate::models::{SceneryCategoryStats, SceneryStatsSnapshot};
+use crate::path_utils;
+use sea_orm::{
+    ActiveValue, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait,
+};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Minimum age of the latest snapshot before a new one is recorded (30 days)
+const SNAPSHOT_INTERVAL_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Snapshots kept per installation (five years of monthly points)
+const MAX_SNAPSHOTS: u64 = 60;
+
+/// Per-category totals of an installation's index, with the distinct missing
+/// library count repeated on every row
+const AGGREGATE_SQL: &str = "SELECT category, \
+     COUNT(*) AS package_count, \
+     COALESCE(SUM(total_size_bytes), 0) AS total_size_bytes, \
+     (SELECT COUNT(DISTINCT LOWER(m.library_name)) FROM missing_libraries m \
+         JOIN scenery_packages p ON p.id = m.package_id \
+         WHERE p.xplane_path = ?1) AS missing_library_count \
+     FROM scenery_packages \
+     WHERE xplane_path = ?1 \
+     GROUP BY category \
+     ORDER BY category";
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Record a snapshot if the previous one is older than the interval.
+/// Errors are logged and swallowed so the scan that triggered this never fails.
+pub async fn record_snapshot_if_due(conn: &DatabaseConnection, xplane_path: &Path) {
+    let xplane_key = path_utils::normalize_xplane_key(xplane_path);
+    match record_snapshot_if_due_at(conn, &xplane_key, unix_now()).await {
+        Ok(true) => logger::log_info(
+            "Recorded scenery statistics snapshot",
+            Some("scenery_stats"),
+        ),
+        Ok(false) => {}
+        Err(e) => logger::log_error(
+            &format!("Failed to record scenery statistics snapshot: {}", e),
+            Some("scenery_stats"),
+        ),
+    }
+}
+
+async fn record_snapshot_if_due_at(
+    conn: &DatabaseConnection,
+    xplane_key: &str,
+    now: i64,
+) -> ApiResult<bool> {
+    let latest = scenery_stats_snapshots::Entity::find()
+        .filter(scenery_stats_snapshots::Column::XplanePath.eq(xplane_key))
+        .order_by_desc(scenery_stats_snapshots::Column::TakenAt)
+        .one(conn)
+        .await?;
+    if latest.is_some_and(|row| now - row.taken_at < SNAPSHOT_INTERVAL_SECS) {
+        return Ok(false);
+    }
+
+    let rows = conn
+        .query_all(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            AGGREGATE_SQL,
+            [xplane_key.into()],
+        ))
+        .await?;
+    if rows.is_empty() {
+        return Ok(false);
+    }
+
+    let txn = conn.begin().await?;
+    for row in rows {
+        let active = scenery_stats_snapshots::ActiveModel {
+            id: ActiveValue::NotSet,
+            xplane_path: Set(xplane_key.to_string()),
+            taken_at: Set(now),
+            category: Set(row.try_get("", "category")?),
+            package_count: Set(row.try_get("", "package_count")?),
+            total_size_bytes: Set(row.try_get("", "total_size_bytes")?),
+            missing_library_count: Set(row.try_get("", "missing_library_count")?),
+        };
+        scenery_stats_snapshots::Entity::insert(active)
+            .exec(&txn)
+            .await?;
+    }
+    prune_snapshots(&txn, xplane_key).await?;
+    txn.commit().await?;
+
+    Ok(true)
+}
+
+/// Drop everything older than the newest `MAX_SNAPSHOTS` snapshots
+async fn prune_snapshots<C: ConnectionTrait>(conn: &C, xplane_key: &str) -> ApiResult<()> {
+    let newest_dropped: Option<i64> = scenery_stats_snapshots::Entity::find()
+        .select_only()
+        .column(scenery_stats_snapshots::Column::TakenAt)
+        .distinct()
+        .filter(scenery_stats_snapshots::Column::XplanePath.eq(xplane_key))
+        .order_by_desc(scenery_stats_snapshots::Column::TakenAt)
+        .offset(MAX_SNAPSHOTS)
+        .limit(1)
+        .into_tuple()
+        .one(conn)
+        .await?;
+
+    if let Some(cutoff) = newest_dropped {
+        scenery_stats_snapshots::Entity::delete_many()
+            .filter(scenery_stats_snapshots::Column::XplanePath.eq(xplane_key))
+            .filter(scenery_stats_snapshots::Column::TakenAt.lte(cutoff))
+            .exec(conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Load all snapshots for an installation, oldest first
+pub async fn load_history(
+    conn: &DatabaseConnection,
+    xplane_path: &Path,
+) -> ApiResult<Vec<SceneryStatsSnapshot>> {
+    load_history_for_key(conn, &path_utils::normalize_xplane_key(xplane_path)).await
+}
+
+async fn load_history_for_key(
+    conn: &DatabaseConnection,
+    xplane_key: &str,
+) -> ApiResult<Vec<SceneryStatsSnapshot>> {
+    let rows = scenery_stats_snapshots::Entity::find()
+        .filter(scenery_stats_snapshots::Column::XplanePath.eq(xplane_key))
+        .order_by_asc(scenery_stats_snapshots::Column::TakenAt)
+        .order_by_asc(scenery_stats_snapshots::Column::Category)
+        .all(conn)
+        .await?;
+
+    Ok(group_snapshots(rows))
+}
+
+fn group_snapshots(rows: Vec<scenery_stats_snapshots::Model>) -> Vec<SceneryStatsSnapshot> {
+    let mut history: Vec<SceneryStatsSnapshot> = Vec::new();
+    for row in rows {
+        let package_count = row.package_count.max(0) as u64;
+        let total_size_bytes = row.total_size_bytes.max(0) as u64;
+
+        let snapshot = match history.last_mut() {
+            Some(last) if last.taken_at == row.taken_at => last,
+            _ => {
+                history.push(SceneryStatsSnapshot {
+                    taken_at: row.taken_at,
+                    total_packages: 0,
+                    total_size_bytes: 0,
+                    missing_library_count: row.missing_library_count.max(0) as u64,
+                    categories: Vec::new(),
+                });
+                history.last_mut().expect("snapshot was just pushed")
+            }
+        };
+
+        snapshot.total_packages += package_count;
+        snapshot.total_size_bytes += total_size_bytes;
+        snapshot.categories.push(SceneryCategoryStats {
+            category: row.category,
+            package_count,
+            total_size_bytes,
+        });
+    }
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{apply_migrations_async, open_memory_connection_async, SceneryQueries};
+    use crate::models::{SceneryCategory, SceneryIndex, SceneryPackageInfo};
+
+    const XPLANE_KEY: &str = "/xplane";
+
+    fn package(folder_name: &str, category: SceneryCategory, size: u64) -> SceneryPackageInfo {
+        SceneryPackageInfo {
+            folder_name: folder_name.to_string(),
+            category,
+            sub_priority: 0,
+            last_modified: SystemTime::UNIX_EPOCH,
+            has_apt_dat: false,
+            airport_id: None,
+            has_dsf: false,
+            has_library_txt: false,
+            has_textures: false,
+            has_objects: false,
+            texture_count: 0,
+            earth_nav_tile_count: 0,
+            total_size_bytes: size,
+            newest_file_modified: None,
+            indexed_at: SystemTime::UNIX_EPOCH,
+            required_libraries: Vec::new(),
+            missing_libraries: Vec::new(),
+            exported_library_names: Vec::new(),
+            enabled: true,
+            sort_order: 0,
+            actual_path: None,
+            continent: None,
+            original_category: None,
+            classifier_version: 0,
+            pinned: false,
+        }
+    }
+
+    async fn setup_db_with_packages() -> DatabaseConnection {
+        let conn = open_memory_connection_async().await.unwrap();
+        apply_migrations_async(&conn).await.unwrap();
+
+        let mut airport = package("KSEA", SceneryCategory::Airport, 1_000);
+        airport.missing_libraries = vec!["MisterX".to_string(), "opensceneryx".to_string()];
+        let mut second_airport = package("KBFI", SceneryCategory::Airport, 500);
+        second_airport.missing_libraries = vec!["MisterX".to_string()];
+        let mesh = package("Ortho4XP", SceneryCategory::Mesh, 20_000);
+
+        let index = SceneryIndex {
+            version: 1,
+            packages: [airport, second_airport, mesh]
+                .into_iter()
+                .map(|info| (info.folder_name.clone(), info))
+                .collect(),
+            last_updated: SystemTime::now(),
+        };
+        SceneryQueries::save_all(&conn, XPLANE_KEY, &index)
+            .await
+            .unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    async fn snapshot_aggregates_counts_sizes_and_missing_libraries() {
+        let conn = setup_db_with_packages().await;
+
+        assert!(record_snapshot_if_due_at(&conn, XPLANE_KEY, 1_000)
+            .await
+            .unwrap());
+
+        let history = load_history_for_key(&conn, XPLANE_KEY).await.unwrap();
+        assert_eq!(history.len(), 1);
+        let snapshot = &history[0];
+        assert_eq!(snapshot.taken_at, 1_000);
+        assert_eq!(snapshot.total_packages, 3);
+        assert_eq!(snapshot.total_size_bytes, 21_500);
+        assert_eq!(snapshot.missing_library_count, 2);
+
+        let airport = snapshot
+            .categories
+            .iter()
+            .find(|c| c.category == "Airport")
+            .unwrap();
+        assert_eq!(airport.package_count, 2);
+        assert_eq!(airport.total_size_bytes, 1_500);
+    }
+
+    #[tokio::test]
+    async fn snapshots_respect_interval_and_are_pruned() {
+        let conn = setup_db_with_packages().await;
+
+        assert!(record_snapshot_if_due_at(&conn, XPLANE_KEY, 0)
+            .await
+            .unwrap());
+        assert!(
+            !record_snapshot_if_due_at(&conn, XPLANE_KEY, SNAPSHOT_INTERVAL_SECS - 1)
+                .await
+                .unwrap()
+        );
+
+        for i in 1..=MAX_SNAPSHOTS as i64 + 5 {
+            assert!(
+                record_snapshot_if_due_at(&conn, XPLANE_KEY, i * SNAPSHOT_INTERVAL_SECS)
+                    .await
+                    .unwrap()
+            );
+        }
+
+        let history = load_history_for_key(&conn, XPLANE_KEY).await.unwrap();
+        assert_eq!(history.len() as u64, MAX_SNAPSHOTS);
+        assert_eq!(
+            history.last().unwrap().taken_at,
+            (MAX_SNAPSHOTS as i64 + 5) * SNAPSHOT_INTERVAL_SECS
+        );
+        assert!(history.windows(2).all(|w| w[0].taken_at < w[1].taken_at));
+    }
+}
@@ -0,0 +1,305 @@
+//! Background watcher that keeps the scenery index fresh
+//!
+//! Watches the top level of Custom Scenery for package folders (and `.lnk`
+//! shortcuts) being added, removed or renamed by other tools. Events are
+//! debounced, then the index is updated incrementally through
+//! [`SceneryIndexManager::quick_scan_and_update_with_locked_entries`], which only
+//! classifies new or modified packages. The resulting delta is emitted to the
+//! frontend as [`SCENERY_INDEX_CHANGED_EVENT`].
+//!
+//! Changes inside package folders are ignored; only top-level entries matter.
+
+use crate::database::DatabaseState;
+use crate::logger;
+use crate::path_utils;
+use crate::scenery_index::SceneryIndexManager;
+use anyhow::{anyhow, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::mpsc;
+
+/// Event emitted after the watcher updated the index
+pub const SCENERY_INDEX_CHANGED_EVENT: &str = "scenery-index-changed";
+
+/// Quiet period after the last filesystem event before the index is updated.
+/// Copying a large package produces a burst of events; this waits for it to settle.
+const DEBOUNCE: Duration = Duration::from_secs(3);
+
+/// Index delta sent with [`SCENERY_INDEX_CHANGED_EVENT`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneryIndexChangedEvent {
+    pub xplane_path: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub updated: Vec<String>,
+}
+
+struct ActiveWatcher {
+    xplane_key: String,
+    locked_folder_names: Arc<Mutex<Vec<String>>>,
+    // Dropping the watcher closes the event channel, which ends the task
+    _watcher: RecommendedWatcher,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// Managed state holding the running watcher, if any
+#[derive(Default)]
+pub struct SceneryWatcherState {
+    active: Mutex<Option<ActiveWatcher>>,
+}
+
+impl SceneryWatcherState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching `xplane_path`. Starting again for the same installation only
+    /// replaces the locked entries and returns `false`; starting for another
+    /// installation stops the previous watcher first.
+    pub fn start(
+        &self,
+        app: AppHandle,
+        xplane_path: &Path,
+        locked_folder_names: Vec<String>,
+    ) -> Result<bool> {
+        let xplane_key = path_utils::normalize_xplane_key(xplane_path);
+        let mut active = self.active.lock().unwrap();
+
+        if let Some(existing) = active.as_ref() {
+            if existing.xplane_key == xplane_key {
+                *existing.locked_folder_names.lock().unwrap() = locked_folder_names;
+                return Ok(false);
+            }
+        }
+        if let Some(previous) = active.take() {
+            previous.shutdown();
+        }
+
+        let custom_scenery_path = xplane_path.join("Custom Scenery");
+        if !custom_scenery_path.is_dir() {
+            return Err(anyhow!("Custom Scenery folder not found"));
+        }
+
+        let (tx, rx) = mpsc::unbounded_channel::<String>();
+        let watch_root = custom_scenery_path.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(e) => {
+                        logger::log_error(
+                            &format!("Scenery watcher error: {}", e),
+                            Some("scenery_watcher"),
+                        );
+                        return;
+                    }
+                };
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+                for path in &event.paths {
+                    if let Some(name) = top_level_entry_name(&watch_root, path) {
+                        let _ = tx.send(name);
+                    }
+                }
+            })?;
+        watcher.watch(&custom_scenery_path, RecursiveMode::NonRecursive)?;
+
+        let locked_folder_names = Arc::new(Mutex::new(locked_folder_names));
+        let task = tauri::async_runtime::spawn(run_debounce_loop(
+            app,
+            xplane_path.to_path_buf(),
+            Arc::clone(&locked_folder_names),
+            rx,
+        ));
+
+        logger::log_info(
+            &format!(
+                "Started scenery watcher on {}",
+                custom_scenery_path.display()
+            ),
+            Some("scenery_watcher"),
+        );
+
+        *active = Some(ActiveWatcher {
+            xplane_key,
+            locked_folder_names,
+            _watcher: watcher,
+            task,
+        });
+        Ok(true)
+    }
+
+    /// Stop the running watcher. Returns `false` if none was running.
+    pub fn stop(&self) -> bool {
+        match self.active.lock().unwrap().take() {
+            Some(active) => {
+                active.shutdown();
+                logger::log_info("Stopped scenery watcher", Some("scenery_watcher"));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl ActiveWatcher {
+    fn shutdown(self) {
+        // Abort instead of waiting for the channel to drain, so a pending
+        // debounce doesn't start a scan during app exit
+        self.task.abort();
+    }
+}
+
+/// Name of the Custom Scenery entry `path` refers to, if it is a direct child
+/// of `custom_scenery_path` that may be a package folder or shortcut
+fn top_level_entry_name(custom_scenery_path: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(custom_scenery_path).ok()?;
+    let mut components = relative.components();
+    let name = match (components.next(), components.next()) {
+        (Some(Component::Normal(name)), None) => name.to_str()?.to_string(),
+        _ => return None,
+    };
+
+    // scenery_packs.ini and its backups or temp files are written by XFast itself
+    let lower = name.to_ascii_lowercase();
+    if name.starts_with('.')
+        || [".ini", ".bak", ".tmp"]
+            .iter()
+            .any(|ext| lower.ends_with(ext))
+    {
+        return None;
+    }
+    // Plain files are never packages; removed entries can't be checked and are
+    // left to the index update to sort out
+    if path.is_file() && !lower.ends_with(".lnk") {
+        return None;
+    }
+    Some(name)
+}
+
+async fn run_debounce_loop(
+    app: AppHandle,
+    xplane_path: PathBuf,
+    locked_folder_names: Arc<Mutex<Vec<String>>>,
+    mut rx: mpsc::UnboundedReceiver<String>,
+) {
+    while let Some(first) = rx.recv().await {
+        let mut changed = BTreeSet::from([first]);
+        loop {
+            match tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+                Ok(Some(name)) => {
+                    changed.insert(name);
+                }
+                // Watcher dropped
+                Ok(None) => return,
+                // Quiet period elapsed
+                Err(_) => break,
+            }
+        }
+
+        crate::log_debug!(
+            &format!("Scenery entries changed: {:?}", changed),
+            "scenery_watcher"
+        );
+        let locked = locked_folder_names.lock().unwrap().clone();
+        update_index(&app, &xplane_path, locked).await;
+    }
+}
+
+async fn update_index(app: &AppHandle, xplane_path: &Path, locked_folder_names: Vec<String>) {
+    let db = app.state::<DatabaseState>().get();
+    let index_manager = SceneryIndexManager::new(xplane_path, db);
+
+    let result = match index_manager
+        .quick_scan_and_update_with_locked_entries(locked_folder_names)
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            logger::log_error(
+                &format!("Scenery watcher failed to update index: {}", e),
+                Some("scenery_watcher"),
+            );
+            return;
+        }
+    };
+
+    // Without an index there is nothing to keep fresh until the first full build
+    if !result.index_exists
+        || (result.added.is_empty() && result.removed.is_empty() && result.updated.is_empty())
+    {
+        return;
+    }
+
+    logger::log_info(
+        &format!(
+            "Scenery watcher updated index: {} added, {} removed, {} updated",
+            result.added.len(),
+            result.removed.len(),
+            result.updated.len()
+        ),
+        Some("scenery_watcher"),
+    );
+    let _ = app.emit(
+        SCENERY_INDEX_CHANGED_EVENT,
+        SceneryIndexChangedEvent {
+            xplane_path: xplane_path.to_string_lossy().to_string(),
+            added: result.added,
+            removed: result.removed,
+            updated: result.updated,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn only_top_level_package_entries_are_reported() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let root = temp.path();
+        fs::create_dir_all(root.join("KSEA Airport/Earth nav data")).unwrap();
+        fs::write(root.join("scenery_packs.ini"), "I\n1000 Version\n").unwrap();
+        fs::write(root.join("readme.txt"), "x").unwrap();
+        fs::write(root.join("Ortho.lnk"), "x").unwrap();
+
+        assert_eq!(
+            top_level_entry_name(root, &root.join("KSEA Airport")),
+            Some("KSEA Airport".to_string())
+        );
+        assert_eq!(
+            top_level_entry_name(root, &root.join("Ortho.lnk")),
+            Some("Ortho.lnk".to_string())
+        );
+        // Removed folders can't be inspected, so they are still reported
+        assert_eq!(
+            top_level_entry_name(root, &root.join("Deleted Pack")),
+            Some("Deleted Pack".to_string())
+        );
+
+        assert_eq!(
+            top_level_entry_name(root, &root.join("KSEA Airport/Earth nav data")),
+            None
+        );
+        assert_eq!(
+            top_level_entry_name(root, &root.join("scenery_packs.ini")),
+            None
+        );
+        assert_eq!(top_level_entry_name(root, &root.join("readme.txt")), None);
+        assert_eq!(top_level_entry_name(root, root), None);
+        assert_eq!(
+            top_level_entry_name(root, Path::new("/elsewhere/Pack")),
+            None
+        );
+    }
+}
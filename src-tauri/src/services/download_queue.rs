@@ -0,0 +1,935 @@
+//! Queue of addon downloads from direct URLs
+//!
+//! Large freeware packs (ortho tiles, meshes) publish one URL per file. Queued
+//! URLs are kept in the `download_queue` table so the queue survives a restart,
+//! and at most [`MAX_CONCURRENT_DOWNLOADS`] run at once. Each item downloads
+//! into its own folder under [`app_dirs::get_download_queue_dir`]. An
+//! interrupted download keeps its `.part` file and resumes with an HTTP Range
+//! request, guarded by `If-Range` so a changed file is fetched in full instead
+//! of spliced. A download that receives nothing for [`READ_TIMEOUT`] fails
+//! like an interrupted one, and cancelling stops it even while it waits.
+//!
+//! A finished file is analyzed right away and the analysis is stored with the
+//! item, so the UI can install it in one click. Failed items keep their error
+//! message until `retry_download` queues them again. Every change of an item is
+//! sent as a [`DOWNLOAD_QUEUE_EVENT`] event carrying the item.
+
+use futures::StreamExt;
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::StatusCode;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::future::Future;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::app_dirs;
+use crate::database::entities::download_queue;
+use crate::database::DatabaseState;
+use crate::error::{ApiError, ApiResult};
+use crate::http_client;
+use crate::logger;
+use crate::models::{DownloadQueueItem, DownloadQueueStatus};
+use crate::settings_store;
+use crate::updater::{header_string, parse_content_range};
+use crate::url_download;
+
+/// Event emitted with a [`DownloadQueueItem`] whenever an item changes
+pub const DOWNLOAD_QUEUE_EVENT: &str = "download-queue-progress";
+
+/// Downloads running at the same time
+const MAX_CONCURRENT_DOWNLOADS: usize = 2;
+
+/// Minimum time between two progress events of one download
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest wait for the next chunk before a download counts as stalled
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+const LOG_CTX: &str = "download_queue";
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn status_name(status: DownloadQueueStatus) -> &'static str {
+    match status {
+        DownloadQueueStatus::Queued => "queued",
+        DownloadQueueStatus::Downloading => "downloading",
+        DownloadQueueStatus::Analyzing => "analyzing",
+        DownloadQueueStatus::Completed => "completed",
+        DownloadQueueStatus::Failed => "failed",
+    }
+}
+
+fn parse_status(value: &str) -> DownloadQueueStatus {
+    match value {
+        "queued" => DownloadQueueStatus::Queued,
+        "downloading" => DownloadQueueStatus::Downloading,
+        "analyzing" => DownloadQueueStatus::Analyzing,
+        "completed" => DownloadQueueStatus::Completed,
+        _ => DownloadQueueStatus::Failed,
+    }
+}
+
+/// Folder holding the files of one item
+fn item_dir(id: i64) -> PathBuf {
+    app_dirs::get_download_queue_dir().join(id.to_string())
+}
+
+/// `downloaded` overrides the stored byte count while the item is downloading
+fn to_item(row: download_queue::Model, downloaded: Option<u64>) -> DownloadQueueItem {
+    let status = parse_status(&row.status);
+    let local_path = row
+        .file_name
+        .as_deref()
+        .filter(|_| {
+            matches!(
+                status,
+                DownloadQueueStatus::Analyzing | DownloadQueueStatus::Completed
+            )
+        })
+        .map(|name| item_dir(row.id).join(name).to_string_lossy().to_string());
+    DownloadQueueItem {
+        id: row.id,
+        url: row.url,
+        xplane_path: row.xplane_path,
+        status,
+        file_name: row.file_name,
+        local_path,
+        downloaded_bytes: downloaded.unwrap_or(row.downloaded_bytes.max(0) as u64),
+        total_bytes: row.total_bytes.map(|bytes| bytes.max(0) as u64),
+        error_message: row.error_message,
+        analysis: row
+            .analysis
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok()),
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }
+}
+
+/// Cancel flag and received bytes of a running download
+#[derive(Clone, Default)]
+struct ActiveDownload {
+    cancel: Arc<AtomicBool>,
+    /// Wakes a download waiting for the server once it is cancelled
+    cancel_notify: Arc<tokio::sync::Notify>,
+    downloaded: Arc<AtomicU64>,
+}
+
+impl ActiveDownload {
+    fn request_cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+        self.cancel_notify.notify_one();
+    }
+
+    /// Resolves once the download is cancelled
+    async fn cancelled(&self) {
+        while !self.cancel.load(Ordering::SeqCst) {
+            self.cancel_notify.notified().await;
+        }
+    }
+
+    /// Run `future` unless the download is cancelled first
+    async fn unless_cancelled<T>(&self, future: impl Future<Output = T>) -> Result<T, String> {
+        tokio::select! {
+            _ = self.cancelled() => Err("Download cancelled by user".to_string()),
+            value = future => Ok(value),
+        }
+    }
+}
+
+/// Managed state of the download queue
+#[derive(Default)]
+pub struct DownloadQueue {
+    /// Running downloads by item id
+    active: Mutex<HashMap<i64, ActiveDownload>>,
+    /// Serializes picking the next queued items
+    pump_lock: tokio::sync::Mutex<()>,
+}
+
+impl DownloadQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn downloaded_bytes(&self, id: i64) -> Option<u64> {
+        self.active
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map(|active| active.downloaded.load(Ordering::Relaxed))
+    }
+
+    /// Add `urls` to the end of the queue. Only http and https URLs are accepted.
+    pub async fn enqueue(
+        &self,
+        app: &AppHandle,
+        urls: Vec<String>,
+        xplane_path: String,
+    ) -> ApiResult<Vec<DownloadQueueItem>> {
+        let urls = urls
+            .iter()
+            .filter(|url| !url.trim().is_empty())
+            .map(|url| url_download::parse_download_url(url).map(|url| url.to_string()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ApiError::validation)?;
+        if urls.is_empty() {
+            return Err(ApiError::validation("No URLs to download"));
+        }
+
+        let conn = app.state::<DatabaseState>().get();
+        let now = now_secs();
+        let mut items = Vec::with_capacity(urls.len());
+        for url in urls {
+            let row = download_queue::ActiveModel {
+                id: ActiveValue::NotSet,
+                url: Set(url),
+                xplane_path: Set(xplane_path.clone()),
+                status: Set(status_name(DownloadQueueStatus::Queued).to_string()),
+                file_name: Set(None),
+                downloaded_bytes: Set(0),
+                total_bytes: Set(None),
+                etag: Set(None),
+                last_modified: Set(None),
+                error_message: Set(None),
+                analysis: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+            };
+            let result = download_queue::Entity::insert(row).exec(&conn).await?;
+            if let Some(row) = download_queue::Entity::find_by_id(result.last_insert_id)
+                .one(&conn)
+                .await?
+            {
+                items.push(to_item(row, None));
+            }
+        }
+        logger::log_info(
+            &format!("Queued {} download(s)", items.len()),
+            Some(LOG_CTX),
+        );
+        schedule(app.clone());
+        Ok(items)
+    }
+
+    /// Every item, oldest first
+    pub async fn list(&self, conn: &DatabaseConnection) -> ApiResult<Vec<DownloadQueueItem>> {
+        let rows = download_queue::Entity::find()
+            .order_by_asc(download_queue::Column::Id)
+            .all(conn)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let downloaded = self.downloaded_bytes(row.id);
+                to_item(row, downloaded)
+            })
+            .collect())
+    }
+
+    /// Remove an item and its files, stopping its download if one is running.
+    /// Returns whether the item existed.
+    pub async fn cancel(&self, conn: &DatabaseConnection, id: i64) -> ApiResult<bool> {
+        let running = self.active.lock().unwrap().get(&id).cloned();
+        let deleted = download_queue::Entity::delete_by_id(id).exec(conn).await?;
+        match running {
+            // The download removes its folder once it noticed
+            Some(active) => active.request_cancel(),
+            None => {
+                let _ = fs::remove_dir_all(item_dir(id));
+            }
+        }
+        Ok(deleted.rows_affected > 0)
+    }
+
+    /// Queue a failed item again; its partial download is resumed
+    pub async fn retry(&self, app: &AppHandle, id: i64) -> ApiResult<DownloadQueueItem> {
+        let conn = app.state::<DatabaseState>().get();
+        let row = download_queue::Entity::find_by_id(id)
+            .one(&conn)
+            .await?
+            .ok_or_else(|| ApiError::not_found(format!("Download {} is not queued", id)))?;
+        if parse_status(&row.status) != DownloadQueueStatus::Failed {
+            return Err(ApiError::validation(format!(
+                "Download {} has not failed",
+                id
+            )));
+        }
+        update_status(&conn, id, DownloadQueueStatus::Queued, None).await?;
+        schedule(app.clone());
+        let row = download_queue::Entity::find_by_id(id)
+            .one(&conn)
+            .await?
+            .ok_or_else(|| ApiError::not_found(format!("Download {} is not queued", id)))?;
+        Ok(to_item(row, None))
+    }
+}
+
+async fn update_status(
+    conn: &DatabaseConnection,
+    id: i64,
+    status: DownloadQueueStatus,
+    error_message: Option<String>,
+) -> ApiResult<()> {
+    download_queue::Entity::update_many()
+        .col_expr(
+            download_queue::Column::Status,
+            Expr::value(status_name(status)),
+        )
+        .col_expr(
+            download_queue::Column::ErrorMessage,
+            Expr::value(error_message),
+        )
+        .col_expr(download_queue::Column::UpdatedAt, Expr::value(now_secs()))
+        .filter(download_queue::Column::Id.eq(id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+/// Send the current state of an item to the frontend
+async fn emit_item(app: &AppHandle, conn: &DatabaseConnection, id: i64) {
+    let Ok(Some(row)) = download_queue::Entity::find_by_id(id).one(conn).await else {
+        return;
+    };
+    let downloaded = app.state::<DownloadQueue>().downloaded_bytes(id);
+    let _ = app.emit(DOWNLOAD_QUEUE_EVENT, to_item(row, downloaded));
+}
+
+/// Start queued downloads while fewer than the maximum are running
+fn schedule(app: AppHandle) {
+    tauri::async_runtime::spawn(async move { pump(&app).await });
+}
+
+async fn pump(app: &AppHandle) {
+    let queue = app.state::<DownloadQueue>();
+    let _pump_guard = queue.pump_lock.lock().await;
+    if queue.active.lock().unwrap().len() >= MAX_CONCURRENT_DOWNLOADS {
+        return;
+    }
+
+    let conn = app.state::<DatabaseState>().get();
+    let queued = match download_queue::Entity::find()
+        .filter(download_queue::Column::Status.eq(status_name(DownloadQueueStatus::Queued)))
+        .order_by_asc(download_queue::Column::Id)
+        .all(&conn)
+        .await
+    {
+        Ok(queued) => queued,
+        Err(e) => {
+            logger::log_error(
+                &format!("Failed to load the download queue: {}", e),
+                Some(LOG_CTX),
+            );
+            return;
+        }
+    };
+
+    for row in queued {
+        let active = {
+            let mut running = queue.active.lock().unwrap();
+            if running.len() >= MAX_CONCURRENT_DOWNLOADS {
+                break;
+            }
+            if running.contains_key(&row.id) {
+                continue;
+            }
+            let active = ActiveDownload::default();
+            running.insert(row.id, active.clone());
+            active
+        };
+        if let Err(e) = update_status(&conn, row.id, DownloadQueueStatus::Downloading, None).await {
+            logger::log_error(
+                &format!("Failed to start download {}: {}", row.id, e),
+                Some(LOG_CTX),
+            );
+            queue.active.lock().unwrap().remove(&row.id);
+            continue;
+        }
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { run_item(app, row, active).await });
+    }
+}
+
+async fn run_item(app: AppHandle, row: download_queue::Model, active: ActiveDownload) {
+    let id = row.id;
+    let conn = app.state::<DatabaseState>().get();
+    emit_item(&app, &conn, id).await;
+
+    let result = process_item(&app, &conn, row, &active).await;
+    app.state::<DownloadQueue>()
+        .active
+        .lock()
+        .unwrap()
+        .remove(&id);
+
+    if active.cancel.load(Ordering::SeqCst) {
+        let _ = fs::remove_dir_all(item_dir(id));
+        logger::log_info(&format!("Download {} cancelled", id), Some(LOG_CTX));
+    } else if let Err(e) = result {
+        logger::log_error(&format!("Download {} failed: {}", id, e), Some(LOG_CTX));
+        let downloaded = active.downloaded.load(Ordering::Relaxed);
+        let _ = download_queue::Entity::update_many()
+            .col_expr(
+                download_queue::Column::DownloadedBytes,
+                Expr::value(downloaded as i64),
+            )
+            .filter(download_queue::Column::Id.eq(id))
+            .exec(&conn)
+            .await;
+        let _ = update_status(&conn, id, DownloadQueueStatus::Failed, Some(e)).await;
+        emit_item(&app, &conn, id).await;
+    }
+
+    schedule(app);
+}
+
+/// Download the item unless an earlier attempt finished it, then analyze it
+async fn process_item(
+    app: &AppHandle,
+    conn: &DatabaseConnection,
+    row: download_queue::Model,
+    active: &ActiveDownload,
+) -> Result<(), String> {
+    let id = row.id;
+    let dir = item_dir(id);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create '{}': {}", dir.display(), e))?;
+
+    let downloaded_file = row
+        .file_name
+        .as_deref()
+        .map(|name| dir.join(name))
+        .filter(|path| path.is_file());
+    let file_path = match downloaded_file {
+        Some(path) => path,
+        None => download(app, conn, &row, active).await?,
+    };
+
+    update_status(conn, id, DownloadQueueStatus::Analyzing, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    emit_item(app, conn, id).await;
+
+    let analysis = crate::analyze_addons(
+        app.state::<DatabaseState>(),
+        vec![file_path.to_string_lossy().to_string()],
+        row.xplane_path.clone(),
+        None,
+        None,
+        None,
+    )
+    .await?;
+    let analysis_json = serde_json::to_string(&analysis)
+        .map_err(|e| format!("Failed to store the analysis: {}", e))?;
+    download_queue::Entity::update_many()
+        .col_expr(download_queue::Column::Analysis, Expr::value(analysis_json))
+        .filter(download_queue::Column::Id.eq(id))
+        .exec(conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    update_status(conn, id, DownloadQueueStatus::Completed, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    emit_item(app, conn, id).await;
+    Ok(())
+}
+
+/// How a response continues a partial file of `offset` bytes
+#[derive(Debug, PartialEq, Eq)]
+enum ResumePoint {
+    /// Append to the partial file from this byte on, 0 for a fresh file
+    From(u64),
+    /// The partial file already holds the whole file
+    Complete,
+}
+
+fn resume_point(
+    status: StatusCode,
+    content_range: Option<(u64, Option<u64>)>,
+    offset: u64,
+) -> Result<ResumePoint, String> {
+    match status {
+        StatusCode::PARTIAL_CONTENT if offset > 0 => match content_range {
+            Some((start, _)) if start == offset => Ok(ResumePoint::From(offset)),
+            _ => Err(format!(
+                "Server resumed at an unexpected position (requested byte {})",
+                offset
+            )),
+        },
+        StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => Ok(ResumePoint::Complete),
+        // A fresh download, or the file changed and If-Range made the server send all of it
+        status if status.is_success() => Ok(ResumePoint::From(0)),
+        status => Err(format!("Download returned status: {}", status)),
+    }
+}
+
+/// Request `url` from byte `offset` on. `validator` is the ETag or
+/// Last-Modified of the partial file.
+async fn send_request(
+    client: &reqwest::Client,
+    url: reqwest::Url,
+    offset: u64,
+    validator: Option<&str>,
+    active: &ActiveDownload,
+) -> Result<reqwest::Response, String> {
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={}-", offset));
+        if let Some(validator) = validator {
+            request = request.header(IF_RANGE, validator);
+        }
+    }
+    active
+        .unless_cancelled(tokio::time::timeout(READ_TIMEOUT, request.send()))
+        .await?
+        .map_err(|_| "Server did not respond".to_string())?
+        .map_err(|e| format!("Failed to start download: {}", e))
+}
+
+/// Write the body of `response` to `partial`, appending when `resumed_from` is
+/// set. Returns the size of the partial file. Fails when the download is
+/// cancelled or no data arrives for [`READ_TIMEOUT`]; every received byte stays
+/// in the partial file for the next attempt.
+async fn receive<F, Fut>(
+    response: reqwest::Response,
+    partial: &Path,
+    resumed_from: u64,
+    max_bytes: u64,
+    active: &ActiveDownload,
+    mut on_progress: F,
+) -> Result<u64, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let file = if resumed_from > 0 {
+        fs::OpenOptions::new().append(true).open(partial)
+    } else {
+        fs::File::create(partial)
+    }
+    .map_err(|e| format!("Failed to open '{}': {}", partial.display(), e))?;
+    // BufWriter flushes on drop, so an early return keeps what was received
+    let mut writer = BufWriter::new(file);
+
+    let mut downloaded = resumed_from;
+    active.downloaded.store(downloaded, Ordering::Relaxed);
+    on_progress().await;
+    let mut last_emit_at = Instant::now();
+    let mut stream = response.bytes_stream();
+    loop {
+        let next = active
+            .unless_cancelled(tokio::time::timeout(READ_TIMEOUT, stream.next()))
+            .await?
+            .map_err(|_| {
+                format!(
+                    "Download stalled: no data for {} seconds",
+                    READ_TIMEOUT.as_secs()
+                )
+            })?;
+        let Some(chunk) = next else {
+            break;
+        };
+        let chunk = chunk.map_err(|e| format!("Download interrupted: {}", e))?;
+        downloaded += chunk.len() as u64;
+        if downloaded > max_bytes {
+            return Err(url_download::size_limit_error(downloaded, max_bytes));
+        }
+        writer
+            .write_all(&chunk)
+            .map_err(|e| format!("Failed to write '{}': {}", partial.display(), e))?;
+        active.downloaded.store(downloaded, Ordering::Relaxed);
+        if last_emit_at.elapsed() >= PROGRESS_INTERVAL {
+            last_emit_at = Instant::now();
+            on_progress().await;
+        }
+    }
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to write '{}': {}", partial.display(), e))?;
+    Ok(downloaded)
+}
+
+/// Fetch the item's URL into its folder, resuming a partial file when the
+/// server allows it. Returns the completed file.
+async fn download(
+    app: &AppHandle,
+    conn: &DatabaseConnection,
+    row: &download_queue::Model,
+    active: &ActiveDownload,
+) -> Result<PathBuf, String> {
+    let url = url_download::parse_download_url(&row.url)?;
+    let max_bytes = settings_store::get().max_url_download_bytes;
+    let dir = item_dir(row.id);
+    let partial_path = |name: &str| dir.join(format!("{}.part", name));
+
+    let offset = row
+        .file_name
+        .as_deref()
+        .and_then(|name| fs::metadata(partial_path(name)).ok())
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+
+    let client = http_client::client_builder("XFast Manager")
+        .connect_timeout(http_client::timeout(Duration::from_secs(15)))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    // Weak ETags aren't allowed in If-Range
+    let validator = row
+        .etag
+        .as_deref()
+        .filter(|etag| !etag.starts_with("W/"))
+        .or(row.last_modified.as_deref());
+    let response = send_request(&client, url.clone(), offset, validator, active).await?;
+
+    let content_range = header_string(response.headers(), CONTENT_RANGE)
+        .as_deref()
+        .and_then(parse_content_range);
+    let resumed_from = match resume_point(response.status(), content_range, offset) {
+        Ok(ResumePoint::From(resumed_from)) => resumed_from,
+        Ok(ResumePoint::Complete) => {
+            let name = row.file_name.as_deref().unwrap_or_default();
+            let file_path = dir.join(name);
+            fs::rename(partial_path(name), &file_path)
+                .map_err(|e| format!("Failed to finalize '{}': {}", file_path.display(), e))?;
+            return Ok(file_path);
+        }
+        Err(e) => {
+            if response.status() == StatusCode::PARTIAL_CONTENT {
+                if let Some(name) = row.file_name.as_deref() {
+                    let _ = fs::remove_file(partial_path(name));
+                }
+            }
+            return Err(e);
+        }
+    };
+
+    let total_bytes = content_range
+        .and_then(|(_, total)| total)
+        .or_else(|| response.content_length().map(|len| resumed_from + len));
+    if let Some(total_bytes) = total_bytes.filter(|&total| total > max_bytes) {
+        return Err(url_download::size_limit_error(total_bytes, max_bytes));
+    }
+
+    let file_name = match row.file_name.clone().filter(|_| resumed_from > 0) {
+        Some(name) => name,
+        None => {
+            if let Some(old_name) = row.file_name.as_deref() {
+                let _ = fs::remove_file(partial_path(old_name));
+            }
+            url_download::filename_from_headers(response.headers())
+                .or_else(|| url_download::filename_from_url(response.url()))
+                .or_else(|| url_download::filename_from_url(&url))
+                .unwrap_or_else(|| url_download::FALLBACK_FILE_NAME.to_string())
+        }
+    };
+    let partial = partial_path(&file_name);
+    let file_path = dir.join(&file_name);
+
+    // What the next attempt needs to resume
+    let mut update = download_queue::Entity::update_many()
+        .col_expr(
+            download_queue::Column::FileName,
+            Expr::value(file_name.clone()),
+        )
+        .col_expr(
+            download_queue::Column::TotalBytes,
+            Expr::value(total_bytes.map(|bytes| bytes as i64)),
+        )
+        .col_expr(
+            download_queue::Column::DownloadedBytes,
+            Expr::value(resumed_from as i64),
+        );
+    if resumed_from == 0 {
+        update = update
+            .col_expr(
+                download_queue::Column::Etag,
+                Expr::value(header_string(response.headers(), ETAG)),
+            )
+            .col_expr(
+                download_queue::Column::LastModified,
+                Expr::value(header_string(response.headers(), LAST_MODIFIED)),
+            );
+    }
+    update
+        .filter(download_queue::Column::Id.eq(row.id))
+        .exec(conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if resumed_from > 0 {
+        logger::log_info(
+            &format!(
+                "Resuming download of {} at {} bytes",
+                file_name, resumed_from
+            ),
+            Some(LOG_CTX),
+        );
+    } else {
+        logger::log_info(
+            &format!("Downloading {} from {}", file_name, url),
+            Some(LOG_CTX),
+        );
+    }
+
+    let downloaded = receive(response, &partial, resumed_from, max_bytes, active, || {
+        emit_item(app, conn, row.id)
+    })
+    .await?;
+
+    if let Some(total_bytes) = total_bytes.filter(|&total| downloaded < total) {
+        return Err(format!(
+            "Download ended after {} of {} bytes",
+            downloaded, total_bytes
+        ));
+    }
+    fs::rename(&partial, &file_path)
+        .map_err(|e| format!("Failed to finalize '{}': {}", file_path.display(), e))?;
+    download_queue::Entity::update_many()
+        .col_expr(
+            download_queue::Column::DownloadedBytes,
+            Expr::value(downloaded as i64),
+        )
+        .filter(download_queue::Column::Id.eq(row.id))
+        .exec(conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(file_path)
+}
+
+/// Queue downloads interrupted by the last shutdown again, drop folders of
+/// removed items and start the queue. Run once at startup.
+pub fn init(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let conn = app.state::<DatabaseState>().get();
+        let interrupted = [
+            status_name(DownloadQueueStatus::Downloading),
+            status_name(DownloadQueueStatus::Analyzing),
+        ];
+        if let Err(e) = download_queue::Entity::update_many()
+            .col_expr(
+                download_queue::Column::Status,
+                Expr::value(status_name(DownloadQueueStatus::Queued)),
+            )
+            .filter(download_queue::Column::Status.is_in(interrupted))
+            .exec(&conn)
+            .await
+        {
+            logger::log_error(
+                &format!("Failed to requeue interrupted downloads: {}", e),
+                Some(LOG_CTX),
+            );
+        }
+
+        if let Ok(rows) = download_queue::Entity::find().all(&conn).await {
+            let known: HashSet<String> = rows.iter().map(|row| row.id.to_string()).collect();
+            if let Ok(entries) = fs::read_dir(app_dirs::get_download_queue_dir()) {
+                for entry in entries.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if entry.path().is_dir() && !known.contains(&name) {
+                        let _ = fs::remove_dir_all(entry.path());
+                    }
+                }
+            }
+        }
+
+        pump(&app).await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_names_round_trip() {
+        for status in [
+            DownloadQueueStatus::Queued,
+            DownloadQueueStatus::Downloading,
+            DownloadQueueStatus::Analyzing,
+            DownloadQueueStatus::Completed,
+            DownloadQueueStatus::Failed,
+        ] {
+            assert_eq!(parse_status(status_name(status)), status);
+        }
+        assert_eq!(parse_status("unknown"), DownloadQueueStatus::Failed);
+    }
+
+    #[test]
+    fn test_local_path_is_only_set_once_downloaded() {
+        let row = download_queue::Model {
+            id: 7,
+            url: "https://example.com/+47+008.zip".to_string(),
+            xplane_path: "/xplane".to_string(),
+            status: "downloading".to_string(),
+            file_name: Some("+47+008.zip".to_string()),
+            downloaded_bytes: 10,
+            total_bytes: Some(100),
+            etag: None,
+            last_modified: None,
+            error_message: None,
+            analysis: None,
+            created_at: 1,
+            updated_at: 1,
+        };
+
+        let downloading = to_item(row.clone(), Some(42));
+        assert_eq!(downloading.local_path, None);
+        assert_eq!(downloading.downloaded_bytes, 42);
+
+        let completed = to_item(
+            download_queue::Model {
+                status: "completed".to_string(),
+                ..row
+            },
+            None,
+        );
+        assert_eq!(completed.downloaded_bytes, 10);
+        assert_eq!(
+            completed.local_path.map(PathBuf::from),
+            Some(item_dir(7).join("+47+008.zip"))
+        );
+    }
+
+    /// Serve `body` to one request on a local port, honoring its Range header.
+    /// With `stall` only the headers are sent. Returns the URL and a handle
+    /// yielding the Range header received.
+    fn serve_once(body: Vec<u8>, stall: bool) -> (String, std::thread::JoinHandle<Option<String>>) {
+        use std::io::Read;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/pack.zip", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buf).unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..read]);
+            }
+            let range = String::from_utf8_lossy(&request).lines().find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("range: ")
+                    .map(str::to_string)
+            });
+            let start = range
+                .as_deref()
+                .and_then(|range| range.strip_prefix("bytes="))
+                .and_then(|range| range.trim_end_matches('-').parse::<usize>().ok())
+                .unwrap_or(0);
+            let head = if start > 0 {
+                format!(
+                    "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\n\r\n",
+                    start,
+                    body.len() - 1,
+                    body.len(),
+                    body.len() - start
+                )
+            } else {
+                format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len())
+            };
+            stream.write_all(head.as_bytes()).unwrap();
+            if stall {
+                std::thread::sleep(Duration::from_secs(3));
+            } else {
+                stream.write_all(&body[start..]).unwrap();
+            }
+            range
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_partial_file_is_resumed_with_a_range_request() {
+        let temp = tempfile::tempdir().unwrap();
+        let body: Vec<u8> = (0..=255u8).cycle().take(5000).collect();
+        let partial = temp.path().join("pack.zip.part");
+        fs::write(&partial, &body[..1200]).unwrap();
+        let (url, server) = serve_once(body.clone(), false);
+        let active = ActiveDownload::default();
+
+        let client = reqwest::Client::new();
+        let response = send_request(
+            &client,
+            reqwest::Url::parse(&url).unwrap(),
+            1200,
+            Some("\"v1\""),
+            &active,
+        )
+        .await
+        .unwrap();
+        let content_range = header_string(response.headers(), CONTENT_RANGE)
+            .as_deref()
+            .and_then(parse_content_range);
+        assert_eq!(
+            resume_point(response.status(), content_range, 1200),
+            Ok(ResumePoint::From(1200))
+        );
+        let downloaded = receive(response, &partial, 1200, u64::MAX, &active, || async {})
+            .await
+            .unwrap();
+
+        assert_eq!(server.join().unwrap().as_deref(), Some("bytes=1200-"));
+        assert_eq!(downloaded, 5000);
+        assert_eq!(active.downloaded.load(Ordering::Relaxed), 5000);
+        assert_eq!(fs::read(&partial).unwrap(), body);
+        assert_eq!(
+            resume_point(StatusCode::PARTIAL_CONTENT, Some((1000, Some(5000))), 1200),
+            Err("Server resumed at an unexpected position (requested byte 1200)".to_string())
+        );
+        assert_eq!(
+            resume_point(StatusCode::OK, None, 1200),
+            Ok(ResumePoint::From(0))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_a_stalled_download() {
+        let temp = tempfile::tempdir().unwrap();
+        let (url, _server) = serve_once(vec![0u8; 1000], true);
+        let active = ActiveDownload::default();
+        let response = send_request(
+            &reqwest::Client::new(),
+            reqwest::Url::parse(&url).unwrap(),
+            0,
+            None,
+            &active,
+        )
+        .await
+        .unwrap();
+
+        let canceller = active.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            canceller.request_cancel();
+        });
+        let started = Instant::now();
+        let result = receive(
+            response,
+            &temp.path().join("pack.zip.part"),
+            0,
+            u64::MAX,
+            &active,
+            || async {},
+        )
+        .await;
+
+        assert_eq!(result, Err("Download cancelled by user".to_string()));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,251 @@
+//! Shared factory for outbound HTTP clients
+//!
+//! Every reqwest client starts from [`client_builder`], which applies the
+//! persisted [`NetworkSettings`]: an explicit proxy with optional basic
+//! credentials (or the system proxy), extra trusted root certificates and the
+//! user's timeout override. Call sites still pick their own user agent and pass
+//! their default timeouts through [`timeout`].
+//!
+//! Settings are validated before they are saved, so a setting that can't be
+//! applied here only comes from a hand-edited file; it is logged and skipped
+//! rather than breaking every network feature.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{ApiError, ApiErrorCode, ApiResult};
+use crate::logger;
+use crate::settings_store::{self, NetworkSettings};
+
+/// User agent for requests that don't identify a specific feature
+pub const DEFAULT_USER_AGENT: &str = "XFast Manager";
+
+/// Endpoint probed by [`test_connection`]; the updater and issue APIs depend on it
+pub const CONNECTIVITY_TEST_URL: &str = "https://api.github.com";
+
+const CONNECTIVITY_TEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Hosts that bypass an explicit proxy
+const LOCAL_HOSTS: &str = "localhost,127.0.0.1,::1";
+
+/// A client builder configured from the current network settings
+pub fn client_builder(user_agent: &str) -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder().user_agent(user_agent);
+    let settings = settings_store::get().network;
+    match configure(builder, &settings) {
+        Ok(builder) => builder,
+        Err(e) => {
+            logger::log_info(
+                &format!("Ignoring invalid network settings: {}", e),
+                Some("http_client"),
+            );
+            let builder = reqwest::Client::builder().user_agent(user_agent);
+            if settings.use_system_proxy {
+                builder
+            } else {
+                builder.no_proxy()
+            }
+        }
+    }
+}
+
+/// `default`, unless the user set a timeout override
+pub fn timeout(default: Duration) -> Duration {
+    timeout_with(&settings_store::get().network, default)
+}
+
+fn timeout_with(settings: &NetworkSettings, default: Duration) -> Duration {
+    settings
+        .timeout_secs
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(default)
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Apply the proxy and certificate settings to `builder`
+fn configure(
+    mut builder: reqwest::ClientBuilder,
+    settings: &NetworkSettings,
+) -> Result<reqwest::ClientBuilder, String> {
+    if let Some(url) = non_empty(&settings.proxy_url) {
+        let mut proxy =
+            reqwest::Proxy::all(url).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+        if let Some(username) = non_empty(&settings.proxy_username) {
+            proxy = proxy.basic_auth(username, settings.proxy_password.as_deref().unwrap_or(""));
+        }
+        // The X-Plane web API on localhost is never reached through the proxy
+        proxy = proxy.no_proxy(reqwest::NoProxy::from_string(LOCAL_HOSTS));
+        // An explicit proxy replaces the system proxy
+        builder = builder.proxy(proxy);
+    } else if !settings.use_system_proxy {
+        builder = builder.no_proxy();
+    }
+
+    if let Some(pem) = non_empty(&settings.extra_root_certificate_pem) {
+        let certificates = reqwest::Certificate::from_pem_bundle(pem.as_bytes())
+            .map_err(|e| format!("Invalid root certificate: {}", e))?;
+        if certificates.is_empty() {
+            return Err("Invalid root certificate: no PEM certificate found".to_string());
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    Ok(builder)
+}
+
+/// Check that `settings` can build a client, before they are saved
+pub fn validate(settings: &NetworkSettings) -> Result<(), String> {
+    configure(reqwest::Client::builder(), settings)?
+        .build()
+        .map(|_| ())
+        .map_err(|e| format!("Invalid network settings: {}", e))
+}
+
+/// A client reused across many requests, rebuilt when the network settings change
+pub struct CachedClient {
+    build: fn() -> reqwest::Client,
+    cached: Mutex<Option<(NetworkSettings, reqwest::Client)>>,
+}
+
+impl CachedClient {
+    pub const fn new(build: fn() -> reqwest::Client) -> Self {
+        Self {
+            build,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// The client for the current settings. Clones share one connection pool.
+    pub fn client(&self) -> reqwest::Client {
+        let settings = settings_store::get().network;
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        match cached.as_ref() {
+            Some((built_for, client)) if *built_for == settings => client.clone(),
+            _ => {
+                let client = (self.build)();
+                *cached = Some((settings, client.clone()));
+                client
+            }
+        }
+    }
+}
+
+/// Outcome of a successful [`test_connection`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkTestResult {
+    pub url: String,
+    /// HTTP status of the response; any status means the endpoint was reached
+    pub status: u16,
+    pub latency_ms: u64,
+}
+
+/// Send a HEAD request to [`CONNECTIVITY_TEST_URL`] using `settings` and time it
+pub async fn test_connection(settings: &NetworkSettings) -> ApiResult<NetworkTestResult> {
+    let client = configure(
+        reqwest::Client::builder().user_agent(DEFAULT_USER_AGENT),
+        settings,
+    )
+    .map_err(ApiError::validation)?
+    .timeout(timeout_with(settings, CONNECTIVITY_TEST_TIMEOUT))
+    .build()
+    .map_err(|e| ApiError::validation(format!("Invalid network settings: {}", e)))?;
+
+    let started = Instant::now();
+    let response = client
+        .head(CONNECTIVITY_TEST_URL)
+        .send()
+        .await
+        .map_err(|e| {
+            let code = if e.is_timeout() {
+                ApiErrorCode::NetworkTimeout
+            } else {
+                ApiErrorCode::NetworkError
+            };
+            ApiError::with_details(
+                code,
+                format!("Connection test failed: {}", e),
+                error_chain(&e),
+            )
+        })?;
+
+    Ok(NetworkTestResult {
+        url: CONNECTIVITY_TEST_URL.to_string(),
+        status: response.status().as_u16(),
+        latency_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// The error's sources joined into one line (proxy and TLS failures are nested)
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut parts = Vec::new();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        parts.push(cause.to_string());
+        source = cause.source();
+    }
+    parts.join(": ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_settings_are_valid() {
+        assert!(validate(&NetworkSettings::default()).is_ok());
+        assert!(validate(&NetworkSettings {
+            proxy_url: Some("http://127.0.0.1:8080".to_string()),
+            proxy_username: Some("user".to_string()),
+            proxy_password: Some("secret".to_string()),
+            use_system_proxy: false,
+            ..NetworkSettings::default()
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn invalid_proxy_and_certificate_are_rejected() {
+        let bad_proxy = NetworkSettings {
+            proxy_url: Some("http://[not a host".to_string()),
+            ..NetworkSettings::default()
+        };
+        assert!(validate(&bad_proxy)
+            .unwrap_err()
+            .starts_with("Invalid proxy URL"));
+
+        let bad_certificate = NetworkSettings {
+            extra_root_certificate_pem: Some("not a certificate".to_string()),
+            ..NetworkSettings::default()
+        };
+        assert!(validate(&bad_certificate)
+            .unwrap_err()
+            .starts_with("Invalid root certificate"));
+    }
+
+    #[test]
+    fn timeout_override_replaces_default() {
+        let default = Duration::from_secs(10);
+        assert_eq!(timeout_with(&NetworkSettings::default(), default), default);
+
+        let overridden = NetworkSettings {
+            timeout_secs: Some(45),
+            ..NetworkSettings::default()
+        };
+        assert_eq!(timeout_with(&overridden, default), Duration::from_secs(45));
+
+        // Zero means "not set" rather than "no time at all"
+        let zero = NetworkSettings {
+            timeout_secs: Some(0),
+            ..NetworkSettings::default()
+        };
+        assert_eq!(timeout_with(&zero, default), default);
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::{LazyLock, Mutex};
 use std::time::{Duration, SystemTime};
 
 use crate::logger;
+use crate::remote_dataset::{self, RemoteDataset};
 
 /// Remote JSON schema for library download links
 #[derive(Debug, Deserialize)]
@@ -29,6 +30,9 @@ static CACHE: LazyLock<Mutex<Option<CachedLinks>>> = LazyLock::new(|| Mutex::new
 /// Cache TTL: 24 hours (matching updater pattern)
 const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 
+/// Fewest links a complete remote file can have
+const MIN_REMOTE_LINKS: usize = 10;
+
 /// Remote URL for the library links JSON file via proxy
 const REMOTE_URL: &str = "https://x-fast-manager.vercel.app/api/library-links-data";
 
@@ -123,28 +127,25 @@ async fn get_remote_links(force_refresh: bool) -> Result<HashMap<String, String>
 
 /// Fetch library links JSON from the remote proxy service.
 async fn fetch_remote_links() -> Result<HashMap<String, String>, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("XFast Manager")
-        .timeout(Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let remote_url =
-        std::env::var("XFAST_LIBRARY_LINKS_API_URL").unwrap_or_else(|_| REMOTE_URL.to_string());
-    let response = client
-        .get(&remote_url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch: {}", e))?;
-
-    if !response.status().is_success() {
-        return Err(format!("HTTP status: {}", response.status()));
-    }
-
-    let data: LibraryLinksData = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    let dataset = RemoteDataset {
+        name: "library_links",
+        url: std::env::var("XFAST_LIBRARY_LINKS_API_URL")
+            .unwrap_or_else(|_| REMOTE_URL.to_string()),
+        min_entries: MIN_REMOTE_LINKS,
+    };
+    // Compare against the last accepted remote copy, or the bundled one
+    let cached = CACHE
+        .lock()
+        .expect("library links cache lock poisoned during read")
+        .as_ref()
+        .map(|cached| cached.links.len())
+        .unwrap_or_else(|| hardcoded_links().len());
+
+    let data: LibraryLinksData =
+        remote_dataset::fetch(&dataset, cached, |data: &LibraryLinksData| {
+            data.libraries.len()
+        })
+        .await?;
 
     // Normalize all keys to lowercase for case-insensitive lookup
     let links: HashMap<String, String> = data
@@ -190,3 +191,35 @@ pub async fn lookup_library_links_remote(
         })
         .collect())
 }
+
+/// Look up download links remotely, falling back to the bundled list when the
+/// remote source is unavailable or doesn't know a name.
+pub async fn lookup_library_links_with_fallback(
+    library_names: Vec<String>,
+) -> HashMap<String, Option<String>> {
+    let local = hardcoded_links();
+    let remote = match get_remote_links(false).await {
+        Ok(links) => Some(links),
+        Err(e) => {
+            logger::log_info(
+                &format!(
+                    "Remote library links unavailable, using bundled list: {}",
+                    e
+                ),
+                Some("library_links"),
+            );
+            None
+        }
+    };
+
+    library_names
+        .into_iter()
+        .map(|name| {
+            let url = remote
+                .as_ref()
+                .and_then(|links| find_library_url(links, &name))
+                .or_else(|| find_library_url(&local, &name));
+            (name, url)
+        })
+        .collect()
+}
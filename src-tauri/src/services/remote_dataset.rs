@@ -0,0 +1,313 @@
+//! Integrity checks for remote JSON datasets
+//!
+//! Remote data (livery patterns, library links) replaces the cached copy only
+//! after the whole payload has been validated: a JSON content type, the
+//! published SHA-256 checksum when the server sends one, a full parse into the
+//! expected schema and a plausible entry count. Captive portal pages and
+//! truncated transfers are rejected with a logged warning, and the caller keeps
+//! whatever it had before.
+
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+use crate::http_client;
+use crate::logger;
+use crate::settings_store;
+
+/// Response header carrying the hex SHA-256 of the body, when published
+const CHECKSUM_HEADER: &str = "x-content-sha256";
+
+/// A remote dataset is rejected when it has fewer than 1/N of the entries in
+/// the cached copy
+const MAX_SHRINK_DIVISOR: usize = 2;
+
+/// A remote JSON dataset and the bounds a complete copy satisfies
+pub struct RemoteDataset {
+    /// Short name used in log messages (e.g. "livery_patterns")
+    pub name: &'static str,
+    pub url: String,
+    /// Fewest entries a complete dataset can have
+    pub min_entries: usize,
+}
+
+/// Why a downloaded payload was not adopted
+#[derive(Debug, PartialEq)]
+pub enum DatasetRejection {
+    /// The server answered with something other than JSON (e.g. an HTML login page)
+    ContentType(String),
+    ChecksumMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The body did not parse fully into the expected schema
+    Malformed(String),
+    TooFewEntries {
+        count: usize,
+        min: usize,
+    },
+    /// Much smaller than the copy already in use, likely a partial export
+    Shrunk {
+        count: usize,
+        cached: usize,
+    },
+}
+
+impl std::fmt::Display for DatasetRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatasetRejection::ContentType(content_type) => {
+                write!(f, "unexpected content type '{}'", content_type)
+            }
+            DatasetRejection::ChecksumMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "checksum mismatch (expected {}, got {})",
+                    expected, actual
+                )
+            }
+            DatasetRejection::Malformed(e) => write!(f, "malformed payload: {}", e),
+            DatasetRejection::TooFewEntries { count, min } => {
+                write!(f, "only {} entries (minimum {})", count, min)
+            }
+            DatasetRejection::Shrunk { count, cached } => {
+                write!(
+                    f,
+                    "only {} entries where the cached copy has {}",
+                    count, cached
+                )
+            }
+        }
+    }
+}
+
+/// Download a dataset and validate it before handing it to the caller.
+/// `cached_entries` is the size of the copy currently in use (0 if none);
+/// `count_entries` reports the size of the parsed dataset.
+pub async fn fetch<T: DeserializeOwned>(
+    dataset: &RemoteDataset,
+    cached_entries: usize,
+    count_entries: impl Fn(&T) -> usize,
+) -> Result<T, String> {
+    if settings_store::get().offline_mode {
+        return Err("Offline mode is enabled".to_string());
+    }
+    let client = http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(Duration::from_secs(10)))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let response = client
+        .get(&dataset.url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP status: {}", response.status()));
+    }
+
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_type = header(reqwest::header::CONTENT_TYPE.as_str());
+    let checksum = header(CHECKSUM_HEADER);
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    validate(
+        dataset,
+        content_type.as_deref(),
+        checksum.as_deref(),
+        &body,
+        cached_entries,
+        count_entries,
+    )
+    .map_err(|rejection| {
+        logger::log_info(
+            &format!(
+                "Warning: rejected remote dataset (dataset={}, bytes={}, cached_entries={}, reason={})",
+                dataset.name,
+                body.len(),
+                cached_entries,
+                rejection
+            ),
+            Some("remote_dataset"),
+        );
+        format!("Rejected remote data: {}", rejection)
+    })
+}
+
+/// Check a downloaded payload and parse it. Nothing is adopted unless this succeeds.
+pub fn validate<T: DeserializeOwned>(
+    dataset: &RemoteDataset,
+    content_type: Option<&str>,
+    checksum: Option<&str>,
+    body: &[u8],
+    cached_entries: usize,
+    count_entries: impl Fn(&T) -> usize,
+) -> Result<T, DatasetRejection> {
+    if let Some(content_type) = content_type {
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        // text/plain covers raw file hosts serving the JSON directly
+        if !mime.ends_with("json") && mime != "text/plain" {
+            return Err(DatasetRejection::ContentType(mime));
+        }
+    }
+
+    if let Some(expected) = checksum {
+        let actual = format!("{:x}", Sha256::digest(body));
+        if !expected.trim().eq_ignore_ascii_case(&actual) {
+            return Err(DatasetRejection::ChecksumMismatch {
+                expected: expected.trim().to_ascii_lowercase(),
+                actual,
+            });
+        }
+    }
+
+    let data: T =
+        serde_json::from_slice(body).map_err(|e| DatasetRejection::Malformed(e.to_string()))?;
+
+    let count = count_entries(&data);
+    if count < dataset.min_entries {
+        return Err(DatasetRejection::TooFewEntries {
+            count,
+            min: dataset.min_entries,
+        });
+    }
+    if count < cached_entries / MAX_SHRINK_DIVISOR {
+        return Err(DatasetRejection::Shrunk {
+            count,
+            cached: cached_entries,
+        });
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize)]
+    struct Links {
+        libraries: HashMap<String, String>,
+    }
+
+    fn dataset() -> RemoteDataset {
+        RemoteDataset {
+            name: "test",
+            url: String::new(),
+            min_entries: 2,
+        }
+    }
+
+    fn links_json(count: usize) -> String {
+        let entries: Vec<String> = (0..count)
+            .map(|i| format!("\"lib{}\": \"https://example.com/{}\"", i, i))
+            .collect();
+        format!("{{\"libraries\": {{{}}}}}", entries.join(", "))
+    }
+
+    fn check(
+        content_type: Option<&str>,
+        checksum: Option<&str>,
+        body: &str,
+        cached: usize,
+    ) -> Result<Links, DatasetRejection> {
+        validate(
+            &dataset(),
+            content_type,
+            checksum,
+            body.as_bytes(),
+            cached,
+            |links: &Links| links.libraries.len(),
+        )
+    }
+
+    #[test]
+    fn accepts_complete_payload_with_matching_checksum() {
+        let body = links_json(4);
+        let checksum = format!("{:X}", Sha256::digest(body.as_bytes()));
+        let links = check(
+            Some("application/json; charset=utf-8"),
+            Some(&checksum),
+            &body,
+            6,
+        )
+        .unwrap();
+        assert_eq!(links.libraries.len(), 4);
+    }
+
+    #[test]
+    fn rejects_truncated_payload() {
+        let body = links_json(4);
+        let truncated = &body[..body.len() / 2];
+        assert!(matches!(
+            check(Some("application/json"), None, truncated, 0),
+            Err(DatasetRejection::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_html_payload() {
+        let portal = "<!DOCTYPE html><html><body>Sign in to the network</body></html>";
+        assert_eq!(
+            check(Some("text/html; charset=utf-8"), None, portal, 0).unwrap_err(),
+            DatasetRejection::ContentType("text/html".to_string())
+        );
+        // Mislabelled portal pages still fail to parse
+        assert!(matches!(
+            check(Some("application/json"), None, portal, 0),
+            Err(DatasetRejection::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_schema_mismatch() {
+        let body = r#"{"version": 1, "patterns": []}"#;
+        assert!(matches!(
+            check(None, None, body, 0),
+            Err(DatasetRejection::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_checksum_mismatch() {
+        let body = links_json(4);
+        let other = format!("{:x}", Sha256::digest(links_json(3).as_bytes()));
+        assert!(matches!(
+            check(None, Some(&other), &body, 0),
+            Err(DatasetRejection::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_too_few_or_shrunk_entries() {
+        assert_eq!(
+            check(None, None, &links_json(1), 0).unwrap_err(),
+            DatasetRejection::TooFewEntries { count: 1, min: 2 }
+        );
+        assert_eq!(
+            check(None, None, &links_json(3), 40).unwrap_err(),
+            DatasetRejection::Shrunk {
+                count: 3,
+                cached: 40
+            }
+        );
+        assert!(check(None, None, &links_json(20), 40).is_ok());
+    }
+}
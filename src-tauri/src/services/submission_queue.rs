@@ -0,0 +1,581 @@
+//! Local queue for bug reports, feedback and issue comments
+//!
+//! Submissions go through the issue proxy, which forwards GitHub's rate-limit
+//! responses. Instead of losing a report when the proxy is rate limited or the
+//! machine is offline, the payload is queued on disk and retried after the
+//! delay the proxy asked for. Pending items survive restarts and can also be
+//! sent manually.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+use crate::app_dirs;
+use crate::http_client;
+use crate::logger;
+
+/// Maximum number of queued submissions kept on disk (oldest are dropped first)
+const MAX_PENDING_SUBMISSIONS: usize = 50;
+
+/// Queued submissions older than this are dropped
+const MAX_RETENTION_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Used when a rate-limit response carries no usable delay
+const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
+
+/// Upper bound for retry delays (rate-limit resets and offline backoff)
+const MAX_RETRY_AFTER_SECS: u64 = 60 * 60;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Serializes reads and writes of the queue file
+static QUEUE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// Prevents two flushes from sending the same submission twice
+static FLUSH_LOCK: LazyLock<tokio::sync::Mutex<()>> = LazyLock::new(|| tokio::sync::Mutex::new(()));
+
+/// When the waiting background retry fires (Unix seconds), if one is waiting
+static RETRY_AT: Mutex<Option<i64>> = Mutex::new(None);
+
+/// Used to notify the frontend when a queued submission is delivered in the background
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Kind of submission sent through the issue proxy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubmissionKind {
+    BugReport,
+    Feedback,
+    IssueComment,
+}
+
+impl SubmissionKind {
+    fn api_url(self) -> String {
+        let (env_var, default) = match self {
+            SubmissionKind::BugReport => (
+                "XFAST_BUG_REPORT_API_URL",
+                "https://x-fast-manager.vercel.app/api/bug-report",
+            ),
+            SubmissionKind::Feedback => (
+                "XFAST_FEEDBACK_API_URL",
+                "https://x-fast-manager.vercel.app/api/feedback-issue",
+            ),
+            SubmissionKind::IssueComment => (
+                "XFAST_ISSUE_COMMENT_API_URL",
+                "https://x-fast-manager.vercel.app/api/issue-comment",
+            ),
+        };
+        std::env::var(env_var).unwrap_or_else(|_| default.to_string())
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SubmissionKind::BugReport => "Bug report",
+            SubmissionKind::Feedback => "Feedback",
+            SubmissionKind::IssueComment => "Issue comment",
+        }
+    }
+}
+
+/// A submission waiting to be retried
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSubmission {
+    pub id: String,
+    pub kind: SubmissionKind,
+    pub payload: Value,
+    /// Unix timestamp (seconds) of the first attempt
+    pub created_at: i64,
+    pub attempts: u32,
+    /// Unix timestamp (seconds) before which automatic retries are skipped
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+}
+
+/// Returned instead of an issue when a submission was queued for later delivery
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedSubmission {
+    pub pending_id: String,
+    pub retry_after_secs: u64,
+    pub reason: String,
+}
+
+/// Outcome of sending pending submissions
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionFlushResult {
+    /// IDs delivered successfully
+    pub sent: Vec<String>,
+    /// Submissions the proxy rejected outright; they were removed from the queue
+    pub failed: Vec<String>,
+    /// Submissions still waiting for a retry
+    pub pending: Vec<PendingSubmission>,
+}
+
+/// Payload of the `pending-submission-sent` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingSubmissionSent {
+    id: String,
+    kind: SubmissionKind,
+    response: Value,
+}
+
+#[derive(Debug)]
+pub enum SubmissionError {
+    /// The proxy forwarded a GitHub rate-limit response
+    RateLimited {
+        retry_after_secs: u64,
+        message: String,
+    },
+    /// The proxy could not be reached
+    Offline(String),
+    /// Any other failure; retrying would not help
+    Rejected(String),
+}
+
+impl std::fmt::Display for SubmissionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionError::RateLimited { message, .. } => write!(f, "{}", message),
+            SubmissionError::Offline(message) => write!(f, "{}", message),
+            SubmissionError::Rejected(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+fn queue_file_path() -> PathBuf {
+    app_dirs::get_pending_submissions_path()
+}
+
+/// Remember the app handle and retry anything left over from a previous session
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+    if !pending_submissions().is_empty() {
+        schedule_retry(0);
+    }
+}
+
+/// POST a submission to the issue proxy and return the parsed JSON response
+pub async fn post_submission(
+    kind: SubmissionKind,
+    payload: &Value,
+) -> Result<Value, SubmissionError> {
+    let client = http_client::client_builder("XFast Manager")
+        .timeout(http_client::timeout(REQUEST_TIMEOUT))
+        .build()
+        .map_err(|e| SubmissionError::Rejected(format!("Failed to create HTTP client: {}", e)))?;
+
+    let mut request = client
+        .post(kind.api_url())
+        .header("Content-Type", "application/json")
+        .json(payload);
+    // Self-hosted proxies can require a token; the public proxy does not
+    if let Ok(token) = std::env::var("XFAST_ISSUE_API_TOKEN") {
+        if !token.trim().is_empty() {
+            request = request.bearer_auth(token.trim());
+        }
+    }
+
+    let response = request.send().await.map_err(|e| {
+        let message = format!("Failed to submit {}: {}", kind.label().to_lowercase(), e);
+        if e.is_connect() || e.is_timeout() {
+            SubmissionError::Offline(message)
+        } else {
+            SubmissionError::Rejected(message)
+        }
+    })?;
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.text().await.unwrap_or_default();
+
+    if !status.is_success() {
+        let message = format!("{} API error {}: {}", kind.label(), status, body);
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let body_json = serde_json::from_str::<Value>(&body).unwrap_or(Value::Null);
+        let remaining = header("x-ratelimit-remaining");
+
+        if is_rate_limited(status.as_u16(), remaining.as_deref(), &body) {
+            let delay = retry_after_secs(
+                header("retry-after").as_deref(),
+                header("x-ratelimit-reset").as_deref(),
+                &body_json,
+                now_secs(),
+            );
+            return Err(SubmissionError::RateLimited {
+                retry_after_secs: delay,
+                message,
+            });
+        }
+        return Err(SubmissionError::Rejected(message));
+    }
+
+    // Some endpoints (issue comments) reply without a JSON body
+    Ok(serde_json::from_str(&body).unwrap_or(Value::Null))
+}
+
+/// Queue a submission that failed transiently, or pass the error through
+pub fn queue_or_fail(
+    kind: SubmissionKind,
+    payload: Value,
+    error: SubmissionError,
+) -> Result<QueuedSubmission, String> {
+    let retry_after_secs = match &error {
+        SubmissionError::RateLimited {
+            retry_after_secs, ..
+        } => *retry_after_secs,
+        SubmissionError::Offline(_) => DEFAULT_RETRY_AFTER_SECS,
+        SubmissionError::Rejected(message) => return Err(message.clone()),
+    };
+
+    let reason = error.to_string();
+    let pending = enqueue(kind, payload, retry_after_secs, &reason)?;
+    logger::log_info(
+        &format!(
+            "{} queued for retry in {}s: {}",
+            kind.label(),
+            retry_after_secs,
+            reason
+        ),
+        Some("submission_queue"),
+    );
+    schedule_retry(retry_after_secs);
+
+    Ok(QueuedSubmission {
+        pending_id: pending.id,
+        retry_after_secs,
+        reason,
+    })
+}
+
+/// Submissions currently waiting for a retry
+pub fn pending_submissions() -> Vec<PendingSubmission> {
+    let _guard = QUEUE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load_queue()
+}
+
+/// Send pending submissions. `force` ignores the retry delay (manual "send now");
+/// `only_id` limits the attempt to a single submission.
+pub async fn flush_pending(force: bool, only_id: Option<&str>) -> SubmissionFlushResult {
+    let _flush_guard = FLUSH_LOCK.lock().await;
+    let mut result = SubmissionFlushResult::default();
+    let now = now_secs();
+
+    let due: Vec<PendingSubmission> = pending_submissions()
+        .into_iter()
+        .filter(|p| only_id.map(|id| p.id == id).unwrap_or(true))
+        .filter(|p| force || p.next_attempt_at <= now)
+        .collect();
+
+    for submission in due {
+        match post_submission(submission.kind, &submission.payload).await {
+            Ok(response) => {
+                remove_from_queue(&submission.id);
+                logger::log_info(
+                    &format!(
+                        "Delivered queued {}",
+                        submission.kind.label().to_lowercase()
+                    ),
+                    Some("submission_queue"),
+                );
+                if let Some(app_handle) = APP_HANDLE.get() {
+                    let _ = app_handle.emit(
+                        "pending-submission-sent",
+                        PendingSubmissionSent {
+                            id: submission.id.clone(),
+                            kind: submission.kind,
+                            response,
+                        },
+                    );
+                }
+                result.sent.push(submission.id);
+            }
+            Err(SubmissionError::Rejected(message)) => {
+                logger::log_error(
+                    &format!("Dropping queued submission {}: {}", submission.id, message),
+                    Some("submission_queue"),
+                );
+                remove_from_queue(&submission.id);
+                result.failed.push(submission.id);
+            }
+            Err(SubmissionError::RateLimited {
+                retry_after_secs,
+                message,
+            }) => {
+                // The limit applies to every submission, so stop here
+                postpone(&submission.id, retry_after_secs, &message);
+                break;
+            }
+            Err(SubmissionError::Offline(message)) => {
+                let backoff = offline_backoff_secs(submission.attempts + 1);
+                postpone(&submission.id, backoff, &message);
+                break;
+            }
+        }
+    }
+
+    result.pending = pending_submissions();
+    if let Some(next) = result.pending.iter().map(|p| p.next_attempt_at).min() {
+        schedule_retry((next - now_secs()).max(0) as u64);
+    }
+    result
+}
+
+/// Run `flush_pending` in the background after `delay_secs`. A retry that is
+/// due sooner than the waiting one replaces it; a later one is left to the
+/// reschedule that follows the earlier flush.
+fn schedule_retry(delay_secs: u64) {
+    let retry_at = now_secs() + delay_secs as i64;
+    let claimed = claim_retry(
+        &mut RETRY_AT.lock().unwrap_or_else(|e| e.into_inner()),
+        retry_at,
+    );
+    if !claimed {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(delay_secs)).await;
+        {
+            let mut scheduled = RETRY_AT.lock().unwrap_or_else(|e| e.into_inner());
+            if *scheduled != Some(retry_at) {
+                // Replaced by an earlier retry, which has flushed already
+                return;
+            }
+            *scheduled = None;
+        }
+        flush_pending(false, None).await;
+    });
+}
+
+/// Take the retry slot for a retry at `retry_at` unless one fires no later
+fn claim_retry(scheduled: &mut Option<i64>, retry_at: i64) -> bool {
+    if scheduled.is_some_and(|at| at <= retry_at) {
+        return false;
+    }
+    *scheduled = Some(retry_at);
+    true
+}
+
+fn load_queue() -> Vec<PendingSubmission> {
+    let path = queue_file_path();
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+    match serde_json::from_str(&content) {
+        Ok(queue) => queue,
+        Err(e) => {
+            logger::log_error(
+                &format!("Failed to parse pending submissions: {}", e),
+                Some("submission_queue"),
+            );
+            Vec::new()
+        }
+    }
+}
+
+fn save_queue(queue: &[PendingSubmission]) -> Result<(), String> {
+    let path = queue_file_path();
+    if queue.is_empty() {
+        let _ = std::fs::remove_file(&path);
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(queue)
+        .map_err(|e| format!("Failed to serialize pending submissions: {}", e))?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to save pending submissions: {}", e))
+}
+
+fn enqueue(
+    kind: SubmissionKind,
+    payload: Value,
+    retry_after_secs: u64,
+    reason: &str,
+) -> Result<PendingSubmission, String> {
+    let _guard = QUEUE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let now = now_secs();
+    let pending = PendingSubmission {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind,
+        payload,
+        created_at: now,
+        attempts: 1,
+        next_attempt_at: now + retry_after_secs as i64,
+        last_error: Some(reason.to_string()),
+    };
+
+    let mut queue = load_queue();
+    queue.push(pending.clone());
+    prune_queue(&mut queue, now);
+    save_queue(&queue)?;
+    Ok(pending)
+}
+
+fn postpone(id: &str, delay_secs: u64, reason: &str) {
+    let _guard = QUEUE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let now = now_secs();
+    let mut queue = load_queue();
+    if let Some(item) = queue.iter_mut().find(|p| p.id == id) {
+        item.attempts += 1;
+        item.next_attempt_at = now + delay_secs as i64;
+        item.last_error = Some(reason.to_string());
+    }
+    prune_queue(&mut queue, now);
+    let _ = save_queue(&queue);
+}
+
+fn remove_from_queue(id: &str) {
+    let _guard = QUEUE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut queue = load_queue();
+    queue.retain(|p| p.id != id);
+    let _ = save_queue(&queue);
+}
+
+/// Drop expired submissions and keep only the newest `MAX_PENDING_SUBMISSIONS`
+fn prune_queue(queue: &mut Vec<PendingSubmission>, now: i64) {
+    queue.retain(|p| now - p.created_at <= MAX_RETENTION_SECS);
+    queue.sort_by_key(|p| p.created_at);
+    if queue.len() > MAX_PENDING_SUBMISSIONS {
+        let excess = queue.len() - MAX_PENDING_SUBMISSIONS;
+        queue.drain(..excess);
+    }
+}
+
+/// GitHub reports rate limits as 403 or 429; 403 is only a rate limit when the
+/// remaining quota is exhausted or the message says so
+fn is_rate_limited(status: u16, remaining: Option<&str>, body: &str) -> bool {
+    if status == 429 {
+        return true;
+    }
+    if status != 403 {
+        return false;
+    }
+    remaining.map(|r| r.trim() == "0").unwrap_or(false)
+        || body.to_ascii_lowercase().contains("rate limit")
+}
+
+/// Seconds to wait before retrying, from `Retry-After`, `X-RateLimit-Reset`
+/// (epoch seconds) or the `retryAfter`/`resetAt` fields the proxy forwards
+fn retry_after_secs(
+    retry_after: Option<&str>,
+    ratelimit_reset: Option<&str>,
+    body: &Value,
+    now: i64,
+) -> u64 {
+    let from_reset = |reset: i64| (reset - now).max(1) as u64;
+
+    let secs = retry_after
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .or_else(|| {
+            ratelimit_reset
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .map(from_reset)
+        })
+        .or_else(|| {
+            body.get("retryAfter")
+                .or_else(|| body.get("retry_after"))
+                .and_then(|v| v.as_u64())
+        })
+        .or_else(|| {
+            body.get("resetAt")
+                .or_else(|| body.get("reset_at"))
+                .and_then(|v| v.as_i64())
+                .map(from_reset)
+        })
+        .unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+
+    secs.clamp(1, MAX_RETRY_AFTER_SECS)
+}
+
+fn offline_backoff_secs(attempts: u32) -> u64 {
+    DEFAULT_RETRY_AFTER_SECS
+        .saturating_mul(1u64 << attempts.min(6))
+        .min(MAX_RETRY_AFTER_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sooner_retry_replaces_waiting_one() {
+        let mut scheduled = None;
+        assert!(claim_retry(&mut scheduled, 3_600));
+        assert!(!claim_retry(&mut scheduled, 4_000));
+        assert!(claim_retry(&mut scheduled, 60));
+        assert_eq!(scheduled, Some(60));
+    }
+
+    #[test]
+    fn rate_limit_detection_requires_exhausted_quota_for_403() {
+        assert!(is_rate_limited(429, None, ""));
+        assert!(is_rate_limited(403, Some("0"), ""));
+        assert!(is_rate_limited(403, None, "API rate limit exceeded"));
+        assert!(!is_rate_limited(403, Some("12"), "Forbidden"));
+        assert!(!is_rate_limited(500, Some("0"), "rate limit"));
+    }
+
+    #[test]
+    fn retry_after_prefers_headers_then_body_fields() {
+        let now = 1_000;
+        assert_eq!(retry_after_secs(Some("30"), None, &Value::Null, now), 30);
+        assert_eq!(retry_after_secs(None, Some("1120"), &Value::Null, now), 120);
+        assert_eq!(
+            retry_after_secs(None, None, &serde_json::json!({ "retryAfter": 45 }), now),
+            45
+        );
+        assert_eq!(
+            retry_after_secs(None, None, &Value::Null, now),
+            DEFAULT_RETRY_AFTER_SECS
+        );
+        assert_eq!(
+            retry_after_secs(Some("999999"), None, &Value::Null, now),
+            MAX_RETRY_AFTER_SECS
+        );
+    }
+
+    #[test]
+    fn prune_queue_drops_expired_and_caps_size() {
+        let now = MAX_RETENTION_SECS * 2;
+        let make = |id: usize, created_at: i64| PendingSubmission {
+            id: id.to_string(),
+            kind: SubmissionKind::Feedback,
+            payload: Value::Null,
+            created_at,
+            attempts: 1,
+            next_attempt_at: created_at,
+            last_error: None,
+        };
+
+        let mut queue = vec![make(0, 0)];
+        queue.extend((1..=MAX_PENDING_SUBMISSIONS + 5).map(|i| make(i, now - i as i64)));
+        prune_queue(&mut queue, now);
+
+        assert_eq!(queue.len(), MAX_PENDING_SUBMISSIONS);
+        assert!(queue.iter().all(|p| p.id != "0"));
+        // The oldest surviving entries were dropped to fit the cap
+        assert!(queue
+            .iter()
+            .all(|p| p.id.parse::<usize>().unwrap() <= MAX_PENDING_SUBMISSIONS));
+    }
+}
@@ -1,7 +1,21 @@
+use futures::StreamExt;
+use reqwest::header::{HeaderMap, CONTENT_RANGE, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::app_dirs;
+use crate::http_client;
+use crate::task_control::TaskControl;
+
+/// Minimum time between two download progress events
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(200);
 
 /// Update information returned to the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +40,20 @@ struct RemoteRelease {
     prerelease: bool,
     published_at: String,
     html_url: String,
+    #[serde(default)]
+    assets: Vec<RemoteAsset>,
+}
+
+/// Downloadable file attached to a release
+#[derive(Debug, Clone, Deserialize)]
+struct RemoteAsset {
+    name: String,
+    browser_download_url: String,
+    #[serde(default)]
+    size: u64,
+    /// Published digest in the form `sha256:<hex>`, when the release API provides one
+    #[serde(default)]
+    digest: Option<String>,
 }
 
 /// Update checker
@@ -125,9 +153,8 @@ impl UpdateChecker {
         );
 
         // Use tauri-plugin-http to make the request
-        let client = reqwest::Client::builder()
-            .user_agent("XFast Manager")
-            .timeout(Duration::from_secs(10))
+        let client = http_client::client_builder("XFast Manager")
+            .timeout(http_client::timeout(Duration::from_secs(10)))
             .build()
             .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
@@ -220,3 +247,753 @@ pub fn get_last_check_time() -> Option<i64> {
     let checker = UpdateChecker::new();
     checker.get_last_check_time().map(|t| t as i64)
 }
+
+// ========== Self-update download ==========
+
+/// Progress event for the app self-update download
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDownloadProgress {
+    pub version: String,
+    pub file_name: String,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    /// Bytes kept from an earlier attempt when this one started
+    pub resumed_from_bytes: u64,
+    pub speed_bytes_per_sec: f64,
+    pub percentage: f64,
+}
+
+pub type UpdateDownloadProgressCallback = Arc<dyn Fn(UpdateDownloadProgress) + Send + Sync>;
+
+/// Installer that was downloaded and matched its published checksum
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadedUpdate {
+    pub version: String,
+    pub file_path: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+    /// Whether bytes from an interrupted earlier attempt were reused
+    pub resumed: bool,
+}
+
+/// Cancellation for the self-update download. Kept apart from the installer's
+/// TaskControl so cancelling an addon install doesn't abort the download and vice versa.
+#[derive(Default)]
+pub struct UpdateDownloadControl {
+    task_control: TaskControl,
+    in_progress: AtomicBool,
+}
+
+impl UpdateDownloadControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of the running download; its partial file is kept
+    pub fn cancel(&self) {
+        self.task_control.request_cancel_all();
+    }
+}
+
+/// Clears the in-progress flag when a download ends, however it ends
+struct InProgressGuard<'a>(&'a AtomicBool);
+
+impl Drop for InProgressGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Sidecar stored next to a partial download so it can be resumed after a restart
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartialDownloadState {
+    url: String,
+    sha256: String,
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+    #[serde(default)]
+    total_bytes: u64,
+}
+
+impl PartialDownloadState {
+    fn load(path: &Path) -> Option<Self> {
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize download state: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to save download state: {}", e))
+    }
+
+    /// Whether the partial file belongs to the same asset and release
+    fn matches(&self, url: &str, sha256: &str) -> bool {
+        self.url == url && self.sha256.eq_ignore_ascii_case(sha256)
+    }
+
+    /// Validator for `If-Range`, so a changed asset is sent in full instead of spliced.
+    /// Weak ETags aren't allowed there, in which case Last-Modified is used.
+    fn if_range(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+}
+
+/// Files used for one release asset inside the update download directory
+struct DownloadPaths {
+    partial: PathBuf,
+    state: PathBuf,
+    complete: PathBuf,
+}
+
+impl DownloadPaths {
+    fn new(dir: &Path, file_name: &str) -> Self {
+        Self {
+            partial: dir.join(format!("{}.part", file_name)),
+            state: dir.join(format!("{}.part.json", file_name)),
+            complete: dir.join(file_name),
+        }
+    }
+
+    /// Drop the partial file and its state so the next attempt starts over
+    fn discard_partial(&self) {
+        let _ = fs::remove_file(&self.partial);
+        let _ = fs::remove_file(&self.state);
+    }
+}
+
+/// Throttled progress reporting with a speed sample per event
+struct ProgressReporter {
+    callback: Option<UpdateDownloadProgressCallback>,
+    version: String,
+    file_name: String,
+    total_bytes: u64,
+    resumed_from_bytes: u64,
+    last_emit_at: Option<Instant>,
+    sample_at: Instant,
+    sample_bytes: u64,
+    speed: f64,
+}
+
+impl ProgressReporter {
+    fn report(&mut self, downloaded_bytes: u64, force: bool) {
+        let Some(callback) = self.callback.clone() else {
+            return;
+        };
+        if !force
+            && self
+                .last_emit_at
+                .is_some_and(|at| at.elapsed() < PROGRESS_EMIT_INTERVAL)
+        {
+            return;
+        }
+
+        let elapsed = self.sample_at.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.speed = downloaded_bytes.saturating_sub(self.sample_bytes) as f64 / elapsed;
+        }
+        self.sample_at = Instant::now();
+        self.sample_bytes = downloaded_bytes;
+        self.last_emit_at = Some(Instant::now());
+
+        let percentage = if self.total_bytes > 0 {
+            (downloaded_bytes as f64 / self.total_bytes as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        callback(UpdateDownloadProgress {
+            version: self.version.clone(),
+            file_name: self.file_name.clone(),
+            downloaded_bytes,
+            total_bytes: self.total_bytes,
+            resumed_from_bytes: self.resumed_from_bytes,
+            speed_bytes_per_sec: self.speed,
+            percentage,
+        });
+    }
+}
+
+/// Download the installer of the latest release into the update download directory.
+///
+/// An interrupted download keeps its partial file plus a sidecar with the asset's
+/// ETag, and the next attempt (also after an app restart) resumes it with an HTTP
+/// Range request. The installer is only returned once it matches the SHA-256
+/// published for the release asset.
+pub async fn download_update(
+    include_pre_release: bool,
+    control: &UpdateDownloadControl,
+    progress_callback: Option<UpdateDownloadProgressCallback>,
+) -> Result<DownloadedUpdate, String> {
+    if control.in_progress.swap(true, Ordering::SeqCst) {
+        return Err("An update download is already in progress".to_string());
+    }
+    let _guard = InProgressGuard(&control.in_progress);
+    control.task_control.reset();
+
+    let checker = UpdateChecker::new();
+    let release = checker.fetch_latest_release(include_pre_release).await?;
+    let version = release.tag_name.trim_start_matches('v').to_string();
+    if !checker.compare_versions(env!("CARGO_PKG_VERSION"), &version)? {
+        return Err(format!(
+            "Version {} is not newer than the installed version",
+            version
+        ));
+    }
+
+    let asset = select_platform_asset(
+        &release.assets,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+    .ok_or_else(|| format!("Release {} has no installer for this platform", version))?;
+    let file_name = Path::new(&asset.name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| format!("Invalid release asset name: {}", asset.name))?
+        .to_string();
+
+    let client = http_client::client_builder("XFast Manager")
+        .connect_timeout(http_client::timeout(Duration::from_secs(15)))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let expected_sha256 = published_sha256(&client, &release.assets, asset).await?;
+
+    let download_dir = app_dirs::get_update_download_dir();
+    fs::create_dir_all(&download_dir)
+        .map_err(|e| format!("Failed to create update download directory: {}", e))?;
+    let paths = DownloadPaths::new(&download_dir, &file_name);
+
+    // A previous attempt may have finished but not been installed yet
+    if paths.complete.is_file() {
+        let complete = paths.complete.clone();
+        let existing_sha256 = tokio::task::spawn_blocking(move || sha256_file(&complete))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))??;
+        if existing_sha256.eq_ignore_ascii_case(&expected_sha256) {
+            crate::logger::log_info(
+                &format!("Update {} was already downloaded", version),
+                Some("updater"),
+            );
+            return Ok(DownloadedUpdate {
+                version,
+                file_path: paths.complete.to_string_lossy().to_string(),
+                size_bytes: fs::metadata(&paths.complete).map(|m| m.len()).unwrap_or(0),
+                sha256: existing_sha256,
+                resumed: false,
+            });
+        }
+        let _ = fs::remove_file(&paths.complete);
+    }
+
+    let previous_state = PartialDownloadState::load(&paths.state)
+        .filter(|state| state.matches(&asset.browser_download_url, &expected_sha256));
+    if previous_state.is_none() {
+        paths.discard_partial();
+    }
+    let offset = previous_state
+        .as_ref()
+        .and_then(|_| fs::metadata(&paths.partial).ok())
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let mut request = client.get(&asset.browser_download_url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={}-", offset));
+        if let Some(validator) = previous_state.as_ref().and_then(|s| s.if_range()) {
+            request = request.header(IF_RANGE, validator);
+        }
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to start update download: {}", e))?;
+
+    let status = response.status();
+    let content_range = header_string(response.headers(), CONTENT_RANGE)
+        .as_deref()
+        .and_then(parse_content_range);
+    let resumed_from = match status {
+        StatusCode::PARTIAL_CONTENT if offset > 0 => match content_range {
+            Some((start, _)) if start == offset => offset,
+            _ => {
+                paths.discard_partial();
+                return Err(format!(
+                    "Update server resumed at an unexpected position (requested byte {})",
+                    offset
+                ));
+            }
+        },
+        // The partial file already holds the whole asset
+        StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+            let total_bytes = previous_state.map(|s| s.total_bytes).unwrap_or(0);
+            return finish_download(version, paths, expected_sha256, total_bytes, true).await;
+        }
+        // Either a fresh download, or the asset changed and If-Range made the server send all of it
+        status if status.is_success() => 0,
+        status => {
+            return Err(format!("Update download returned status: {}", status));
+        }
+    };
+
+    let total_bytes = content_range
+        .and_then(|(_, total)| total)
+        .or_else(|| response.content_length().map(|len| resumed_from + len))
+        .unwrap_or(asset.size);
+    let state = PartialDownloadState {
+        url: asset.browser_download_url.clone(),
+        sha256: expected_sha256.clone(),
+        etag: header_string(response.headers(), ETAG),
+        last_modified: header_string(response.headers(), LAST_MODIFIED),
+        total_bytes,
+    };
+    state.save(&paths.state)?;
+
+    let file = if resumed_from > 0 {
+        fs::OpenOptions::new().append(true).open(&paths.partial)
+    } else {
+        fs::File::create(&paths.partial)
+    }
+    .map_err(|e| format!("Failed to open '{}': {}", paths.partial.display(), e))?;
+    // BufWriter flushes on drop, so early returns below still leave every received
+    // byte in the partial file for the next attempt
+    let mut writer = BufWriter::new(file);
+
+    if resumed_from > 0 {
+        crate::logger::log_info(
+            &format!(
+                "Resuming update download of {} at {} of {} bytes",
+                file_name, resumed_from, total_bytes
+            ),
+            Some("updater"),
+        );
+    } else {
+        crate::logger::log_info(
+            &format!("Downloading update {} ({} bytes)", file_name, total_bytes),
+            Some("updater"),
+        );
+    }
+
+    let mut reporter = ProgressReporter {
+        callback: progress_callback,
+        version: version.clone(),
+        file_name: file_name.clone(),
+        total_bytes,
+        resumed_from_bytes: resumed_from,
+        last_emit_at: None,
+        sample_at: Instant::now(),
+        sample_bytes: resumed_from,
+        speed: 0.0,
+    };
+    reporter.report(resumed_from, true);
+
+    let mut downloaded = resumed_from;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        if control.task_control.is_cancelled() {
+            crate::logger::log_info(
+                &format!(
+                    "Update download cancelled at {} of {} bytes",
+                    downloaded, total_bytes
+                ),
+                Some("updater"),
+            );
+            return Err("Update download cancelled".to_string());
+        }
+        let chunk = chunk.map_err(|e| format!("Update download interrupted: {}", e))?;
+        writer
+            .write_all(&chunk)
+            .map_err(|e| format!("Failed to write '{}': {}", paths.partial.display(), e))?;
+        downloaded += chunk.len() as u64;
+        reporter.report(downloaded, false);
+    }
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to flush '{}': {}", paths.partial.display(), e))?;
+    drop(writer);
+    reporter.report(downloaded, true);
+
+    finish_download(
+        version,
+        paths,
+        expected_sha256,
+        total_bytes,
+        resumed_from > 0,
+    )
+    .await
+}
+
+async fn finish_download(
+    version: String,
+    paths: DownloadPaths,
+    expected_sha256: String,
+    total_bytes: u64,
+    resumed: bool,
+) -> Result<DownloadedUpdate, String> {
+    let (size_bytes, paths, sha256) = tokio::task::spawn_blocking(move || {
+        verify_partial_download(&paths, &expected_sha256, total_bytes)
+            .map(|size| (size, paths, expected_sha256))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    crate::logger::log_info(
+        &format!(
+            "Update {} downloaded and verified: {}",
+            version,
+            paths.complete.display()
+        ),
+        Some("updater"),
+    );
+    Ok(DownloadedUpdate {
+        version,
+        file_path: paths.complete.to_string_lossy().to_string(),
+        size_bytes,
+        sha256,
+        resumed,
+    })
+}
+
+/// Check the partial file against the published checksum and move it into place.
+/// An incomplete file is kept for resuming; one with wrong content is discarded,
+/// since resuming from corrupt bytes could never verify.
+fn verify_partial_download(
+    paths: &DownloadPaths,
+    expected_sha256: &str,
+    total_bytes: u64,
+) -> Result<u64, String> {
+    let size = fs::metadata(&paths.partial)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read '{}': {}", paths.partial.display(), e))?;
+    if total_bytes > 0 && size < total_bytes {
+        return Err(format!(
+            "Update download incomplete: {} of {} bytes",
+            size, total_bytes
+        ));
+    }
+
+    let actual_sha256 = sha256_file(&paths.partial)?;
+    if !actual_sha256.eq_ignore_ascii_case(expected_sha256) {
+        paths.discard_partial();
+        return Err(format!(
+            "Update checksum mismatch: expected {}, got {}",
+            expected_sha256, actual_sha256
+        ));
+    }
+
+    fs::rename(&paths.partial, &paths.complete).map_err(|e| {
+        format!(
+            "Failed to move '{}' into place: {}",
+            paths.complete.display(),
+            e
+        )
+    })?;
+    let _ = fs::remove_file(&paths.state);
+    Ok(size)
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file =
+        fs::File::open(path).map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let bytes_read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+pub(crate) fn header_string(
+    headers: &HeaderMap,
+    name: reqwest::header::HeaderName,
+) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Parse `bytes <start>-<end>/<total>` into the start offset and, if known, the total size
+pub(crate) fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let range = value.trim().strip_prefix("bytes ")?;
+    let (span, total) = range.split_once('/')?;
+    let (start, _) = span.split_once('-')?;
+    Some((start.trim().parse().ok()?, total.trim().parse().ok()))
+}
+
+/// Installer extensions per OS, most preferred first
+fn platform_extensions(os: &str) -> &'static [&'static str] {
+    match os {
+        "windows" => &["-setup.exe", ".msi"],
+        "macos" => &[".dmg"],
+        "linux" => &[".appimage", ".deb", ".rpm"],
+        _ => &[],
+    }
+}
+
+const ARCH_ALIASES: &[(&str, &[&str])] = &[
+    ("x86_64", &["x86_64", "x64", "amd64"]),
+    ("aarch64", &["aarch64", "arm64"]),
+];
+
+/// Whether an asset name fits `arch`. Names without an architecture, and
+/// universal builds, fit every architecture.
+fn asset_matches_arch(name: &str, arch: &str) -> bool {
+    if name.contains("universal") {
+        return true;
+    }
+    let mut names_other_arch = false;
+    for (target, aliases) in ARCH_ALIASES {
+        if aliases.iter().any(|alias| name.contains(alias)) {
+            if *target == arch {
+                return true;
+            }
+            names_other_arch = true;
+        }
+    }
+    !names_other_arch
+}
+
+fn select_platform_asset<'a>(
+    assets: &'a [RemoteAsset],
+    os: &str,
+    arch: &str,
+) -> Option<&'a RemoteAsset> {
+    platform_extensions(os).iter().find_map(|extension| {
+        assets.iter().find(|asset| {
+            let name = asset.name.to_lowercase();
+            name.ends_with(extension) && asset_matches_arch(&name, arch)
+        })
+    })
+}
+
+fn parse_sha256_hex(value: &str) -> Option<String> {
+    let value = value.trim();
+    (value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| value.to_ascii_lowercase())
+}
+
+/// Find the hash for `file_name` in a `sha256sum`-style checksum file. A file
+/// holding a single bare hash is taken to describe the asset it is named after.
+fn parse_checksum_file(content: &str, file_name: &str) -> Option<String> {
+    let lines: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    for line in &lines {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next().and_then(parse_sha256_hex);
+        let name = parts.next().map(|name| name.trim_start_matches('*'));
+        match (hash, name) {
+            (Some(hash), Some(name)) if name.eq_ignore_ascii_case(file_name) => return Some(hash),
+            (Some(hash), None) if lines.len() == 1 => return Some(hash),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// SHA-256 published for `asset`: the release API digest if present, otherwise a
+/// `<asset>.sha256` file or a combined checksum list attached to the same release
+async fn published_sha256(
+    client: &reqwest::Client,
+    assets: &[RemoteAsset],
+    asset: &RemoteAsset,
+) -> Result<String, String> {
+    if let Some(hash) = asset
+        .digest
+        .as_deref()
+        .and_then(|digest| digest.strip_prefix("sha256:"))
+        .and_then(parse_sha256_hex)
+    {
+        return Ok(hash);
+    }
+
+    let sidecar_name = format!("{}.sha256", asset.name).to_lowercase();
+    let checksum_asset = assets
+        .iter()
+        .find(|a| a.name.to_lowercase() == sidecar_name)
+        .or_else(|| {
+            assets.iter().find(|a| {
+                matches!(
+                    a.name.to_lowercase().as_str(),
+                    "sha256sums" | "sha256sums.txt" | "checksums.txt"
+                )
+            })
+        })
+        .ok_or_else(|| format!("No SHA-256 checksum is published for {}", asset.name))?;
+
+    let response = client
+        .get(&checksum_asset.browser_download_url)
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch update checksum: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Update checksum request returned status: {}",
+            response.status()
+        ));
+    }
+    let content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read update checksum: {}", e))?;
+
+    parse_checksum_file(&content, &asset.name).ok_or_else(|| {
+        format!(
+            "{} does not contain a SHA-256 for {}",
+            checksum_asset.name, asset.name
+        )
+    })
+}
+
+/// Hand a downloaded installer to the OS to run. Only files in the update
+/// download directory are accepted.
+pub fn launch_downloaded_update(file_path: &Path) -> Result<(), String> {
+    let download_dir = fs::canonicalize(app_dirs::get_update_download_dir())
+        .map_err(|e| format!("Update download directory is unavailable: {}", e))?;
+    let file =
+        fs::canonicalize(file_path).map_err(|e| format!("Downloaded update not found: {}", e))?;
+    let is_partial = file
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("part") || ext.eq_ignore_ascii_case("json"));
+    if file.parent() != Some(download_dir.as_path()) || !file.is_file() || is_partial {
+        return Err(format!("Not a downloaded update: {}", file_path.display()));
+    }
+
+    crate::logger::log_info(
+        &format!("Launching update installer {}", file.display()),
+        Some("updater"),
+    );
+    opener::open(&file).map_err(|e| format!("Failed to launch update installer: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn asset(name: &str) -> RemoteAsset {
+        RemoteAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{}", name),
+            size: 0,
+            digest: None,
+        }
+    }
+
+    #[test]
+    fn selects_installer_for_platform_and_arch() {
+        let assets = vec![
+            asset("XFast.Manager_1.2.0_aarch64.dmg"),
+            asset("XFast.Manager_1.2.0_x64.dmg"),
+            asset("XFast.Manager_1.2.0_x64-setup.exe"),
+            asset("XFast.Manager_1.2.0_x64-setup.exe.sig"),
+            asset("XFast.Manager_1.2.0_x64_en-US.msi"),
+            asset("xfast-manager_1.2.0_amd64.deb"),
+            asset("xfast-manager_1.2.0_amd64.AppImage"),
+        ];
+
+        let pick = |os, arch| select_platform_asset(&assets, os, arch).map(|a| a.name.as_str());
+        assert_eq!(
+            pick("windows", "x86_64"),
+            Some("XFast.Manager_1.2.0_x64-setup.exe")
+        );
+        assert_eq!(
+            pick("macos", "aarch64"),
+            Some("XFast.Manager_1.2.0_aarch64.dmg")
+        );
+        assert_eq!(pick("macos", "x86_64"), Some("XFast.Manager_1.2.0_x64.dmg"));
+        assert_eq!(
+            pick("linux", "x86_64"),
+            Some("xfast-manager_1.2.0_amd64.AppImage")
+        );
+        assert_eq!(pick("linux", "aarch64"), None);
+    }
+
+    #[test]
+    fn parses_checksum_files_and_content_range() {
+        let hash = "a".repeat(64);
+        let listing = format!(
+            "{}  other.dmg\n{} *XFast_x64-setup.exe\n",
+            "b".repeat(64),
+            hash.to_uppercase()
+        );
+        assert_eq!(
+            parse_checksum_file(&listing, "XFast_x64-setup.exe"),
+            Some(hash.clone())
+        );
+        assert_eq!(parse_checksum_file(&listing, "missing.exe"), None);
+        assert_eq!(
+            parse_checksum_file(&format!("{}\n", hash), "any"),
+            Some(hash)
+        );
+        assert_eq!(parse_checksum_file("not-a-hash  file", "file"), None);
+
+        assert_eq!(
+            parse_content_range("bytes 100-199/200"),
+            Some((100, Some(200)))
+        );
+        assert_eq!(parse_content_range("bytes 5-9/*"), Some((5, None)));
+        assert_eq!(parse_content_range("items 0-1/2"), None);
+    }
+
+    #[test]
+    fn partial_state_prefers_strong_validators() {
+        let mut state = PartialDownloadState {
+            url: "https://example.com/a.exe".to_string(),
+            sha256: "ab".repeat(32),
+            etag: Some("\"v1\"".to_string()),
+            last_modified: Some("Tue, 13 Oct 2026 10:00:00 GMT".to_string()),
+            total_bytes: 10,
+        };
+        assert!(state.matches("https://example.com/a.exe", &"AB".repeat(32)));
+        assert!(!state.matches("https://example.com/b.exe", &"ab".repeat(32)));
+        assert_eq!(state.if_range(), Some("\"v1\""));
+
+        state.etag = Some("W/\"v1\"".to_string());
+        assert_eq!(state.if_range(), Some("Tue, 13 Oct 2026 10:00:00 GMT"));
+    }
+
+    #[test]
+    fn verification_keeps_incomplete_files_and_discards_corrupt_ones() {
+        let temp = tempdir().expect("failed to create tempdir");
+        let paths = DownloadPaths::new(temp.path(), "setup.exe");
+        let content = b"installer bytes";
+        let expected = format!("{:x}", Sha256::digest(content));
+
+        fs::write(&paths.partial, &content[..5]).unwrap();
+        fs::write(&paths.state, "{}").unwrap();
+        assert!(verify_partial_download(&paths, &expected, content.len() as u64).is_err());
+        assert!(paths.partial.exists() && paths.state.exists());
+
+        fs::write(&paths.partial, b"installer bytez").unwrap();
+        let err = verify_partial_download(&paths, &expected, content.len() as u64).unwrap_err();
+        assert!(err.contains("checksum mismatch"));
+        assert!(!paths.partial.exists() && !paths.state.exists());
+
+        fs::write(&paths.partial, content).unwrap();
+        assert_eq!(
+            verify_partial_download(&paths, &expected, content.len() as u64),
+            Ok(content.len() as u64)
+        );
+        assert_eq!(fs::read(&paths.complete).unwrap(), content);
+        assert!(!paths.partial.exists());
+    }
+}
@@ -0,0 +1,393 @@
+//! Download an addon archive from a URL the user pasted
+//!
+//! Each download goes into its own folder under [`app_dirs::get_url_download_dir`]
+//! so the archive keeps the name the server gave it, which the analyzer relies
+//! on to recognise the addon. Bytes are streamed to a `.part` file that is only
+//! renamed once the download completed; on failure or cancellation the folder is
+//! removed again. Folders left behind by earlier sessions are pruned after
+//! [`STALE_DOWNLOAD_AGE`].
+
+use futures::StreamExt;
+use reqwest::header::{HeaderMap, CONTENT_DISPOSITION};
+use reqwest::Url;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::addon_updater::{AddonUpdateProgressCallback, AddonUpdateProgressEvent};
+use crate::app_dirs;
+use crate::http_client;
+use crate::task_control::TaskControl;
+
+/// `itemType` of the `addon-update-progress` events sent for URL downloads
+pub const URL_DOWNLOAD_ITEM_TYPE: &str = "url-download";
+
+/// Download folders older than this are removed before a new download starts
+const STALE_DOWNLOAD_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A download that receives no data for this long is aborted
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Minimum time between two progress events
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Used when neither the response headers nor the URL carry a file name
+pub(crate) const FALLBACK_FILE_NAME: &str = "download";
+
+static DOWNLOAD_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A completed URL download
+#[derive(Debug, Clone)]
+pub struct UrlDownload {
+    pub file_path: PathBuf,
+    pub file_name: String,
+    pub size_bytes: u64,
+}
+
+/// Stream `url` into a new folder under the URL download directory.
+///
+/// Rejects anything but http/https, and aborts once the announced or received
+/// size exceeds `max_bytes`. Cancellation is checked between chunks through
+/// `task_control`.
+pub async fn download_from_url(
+    url: &str,
+    max_bytes: u64,
+    task_control: &TaskControl,
+    progress_callback: Option<AddonUpdateProgressCallback>,
+) -> Result<UrlDownload, String> {
+    let url = parse_download_url(url)?;
+    let download_root = app_dirs::get_url_download_dir();
+    prune_stale_downloads(&download_root);
+
+    let client = http_client::client_builder("XFast Manager")
+        .connect_timeout(http_client::timeout(Duration::from_secs(15)))
+        .read_timeout(http_client::timeout(READ_TIMEOUT))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let response = client
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to start download: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Download returned status: {}", response.status()));
+    }
+
+    let total_bytes = response.content_length().unwrap_or(0);
+    if total_bytes > max_bytes {
+        return Err(size_limit_error(total_bytes, max_bytes));
+    }
+
+    // Redirects (e.g. GitHub release assets) usually end on a more telling URL
+    let file_name = filename_from_headers(response.headers())
+        .or_else(|| filename_from_url(response.url()))
+        .or_else(|| filename_from_url(&url))
+        .unwrap_or_else(|| FALLBACK_FILE_NAME.to_string());
+
+    let download_dir = create_download_dir(&download_root)?;
+    let file_path = download_dir.join(&file_name);
+    let partial_path = download_dir.join(format!("{}.part", file_name));
+
+    crate::logger::log_info(
+        &format!("Downloading {} from {}", file_name, url),
+        Some("url_download"),
+    );
+
+    let result = async {
+        let file = fs::File::create(&partial_path)
+            .map_err(|e| format!("Failed to create '{}': {}", partial_path.display(), e))?;
+        let mut writer = BufWriter::new(file);
+        let mut reporter = ProgressReporter::new(progress_callback, &file_name, total_bytes);
+        reporter.report("started", 0, true);
+
+        let mut downloaded: u64 = 0;
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            if task_control.is_cancelled() {
+                return Err("Download cancelled by user".to_string());
+            }
+            let chunk = chunk.map_err(|e| format!("Download interrupted: {}", e))?;
+            downloaded += chunk.len() as u64;
+            if downloaded > max_bytes {
+                return Err(size_limit_error(downloaded, max_bytes));
+            }
+            writer
+                .write_all(&chunk)
+                .map_err(|e| format!("Failed to write '{}': {}", partial_path.display(), e))?;
+            reporter.report("in_progress", downloaded, false);
+        }
+
+        writer
+            .flush()
+            .map_err(|e| format!("Failed to write '{}': {}", partial_path.display(), e))?;
+        drop(writer);
+        if total_bytes > 0 && downloaded != total_bytes {
+            return Err(format!(
+                "Download ended after {} of {} bytes",
+                downloaded, total_bytes
+            ));
+        }
+        fs::rename(&partial_path, &file_path)
+            .map_err(|e| format!("Failed to finalize '{}': {}", file_path.display(), e))?;
+        reporter.report("completed", downloaded, true);
+        Ok(downloaded)
+    }
+    .await;
+
+    match result {
+        Ok(size_bytes) => Ok(UrlDownload {
+            file_path,
+            file_name,
+            size_bytes,
+        }),
+        Err(e) => {
+            let _ = fs::remove_dir_all(&download_dir);
+            crate::logger::log_error(
+                &format!("Download of {} failed: {}", file_name, e),
+                Some("url_download"),
+            );
+            Err(e)
+        }
+    }
+}
+
+/// Remove a finished download once it has been installed or discarded.
+/// Only paths inside the URL download directory are touched.
+pub fn discard_download(file_path: &Path) -> Result<(), String> {
+    let root = app_dirs::get_url_download_dir();
+    let Some(dir) = file_path
+        .parent()
+        .filter(|dir| dir.parent() == Some(root.as_path()))
+    else {
+        return Err(format!("'{}' is not a URL download", file_path.display()));
+    };
+    fs::remove_dir_all(dir).map_err(|e| format!("Failed to remove '{}': {}", dir.display(), e))
+}
+
+pub(crate) fn parse_download_url(raw: &str) -> Result<Url, String> {
+    let url = Url::parse(raw.trim()).map_err(|e| format!("Invalid URL '{}': {}", raw, e))?;
+    match url.scheme() {
+        "http" | "https" => Ok(url),
+        scheme => Err(format!("Unsupported URL scheme '{}'", scheme)),
+    }
+}
+
+pub(crate) fn size_limit_error(size: u64, max_bytes: u64) -> String {
+    format!(
+        "Download exceeds the maximum size of {} bytes ({} bytes)",
+        max_bytes, size
+    )
+}
+
+fn create_download_dir(root: &Path) -> Result<PathBuf, String> {
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let counter = DOWNLOAD_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let dir = root.join(format!("{}-{}-{}", stamp, std::process::id(), counter));
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create download directory: {}", e))?;
+    Ok(dir)
+}
+
+fn prune_stale_downloads(root: &Path) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > STALE_DOWNLOAD_AGE);
+        if stale && entry.path().is_dir() {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+pub(crate) fn filename_from_headers(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(CONTENT_DISPOSITION)?.to_str().ok()?;
+    filename_from_content_disposition(value)
+}
+
+/// Prefers the RFC 5987 `filename*` parameter over plain `filename`.
+fn filename_from_content_disposition(value: &str) -> Option<String> {
+    let mut plain = None;
+    let mut extended = None;
+    for param in value.split(';').skip(1) {
+        let Some((key, raw)) = param.split_once('=') else {
+            continue;
+        };
+        let raw = raw.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                // charset'language'percent-encoded-value
+                let encoded = raw.splitn(3, '\'').nth(2).unwrap_or(raw);
+                extended = Some(percent_decode(encoded.trim_matches('"')));
+            }
+            "filename" => plain = Some(raw.trim_matches('"').to_string()),
+            _ => {}
+        }
+    }
+    extended
+        .or(plain)
+        .and_then(|name| sanitize_file_name(&name))
+}
+
+pub(crate) fn filename_from_url(url: &Url) -> Option<String> {
+    let segment = url.path_segments()?.rev().find(|s| !s.is_empty())?;
+    sanitize_file_name(&percent_decode(segment))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            let hex = [bytes[i + 1], bytes[i + 2]];
+            if let Ok(byte) = u8::from_str_radix(std::str::from_utf8(&hex).unwrap_or(""), 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Keep only the final path component and drop characters Windows rejects.
+fn sanitize_file_name(name: &str) -> Option<String> {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let cleaned: String = base
+        .chars()
+        .filter(|c| !c.is_control() && !matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*'))
+        .collect();
+    let cleaned = cleaned.trim().trim_end_matches('.').trim();
+    if cleaned.is_empty() || cleaned == ".." {
+        None
+    } else {
+        Some(cleaned.to_string())
+    }
+}
+
+struct ProgressReporter {
+    callback: Option<AddonUpdateProgressCallback>,
+    file_name: String,
+    total_bytes: u64,
+    started_at: Instant,
+    last_emit_at: Option<Instant>,
+}
+
+impl ProgressReporter {
+    fn new(
+        callback: Option<AddonUpdateProgressCallback>,
+        file_name: &str,
+        total_bytes: u64,
+    ) -> Self {
+        Self {
+            callback,
+            file_name: file_name.to_string(),
+            total_bytes,
+            started_at: Instant::now(),
+            last_emit_at: None,
+        }
+    }
+
+    fn report(&mut self, status: &str, downloaded: u64, force: bool) {
+        let Some(callback) = self.callback.as_ref() else {
+            return;
+        };
+        let now = Instant::now();
+        if !force
+            && self
+                .last_emit_at
+                .is_some_and(|last| now.duration_since(last) < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+        self.last_emit_at = Some(now);
+
+        let percentage = if status == "completed" {
+            100.0
+        } else if self.total_bytes > 0 {
+            (downloaded as f64 / self.total_bytes as f64 * 100.0).clamp(0.0, 100.0)
+        } else {
+            0.0
+        };
+        let elapsed = self.started_at.elapsed().as_secs_f64().max(0.001);
+        callback(AddonUpdateProgressEvent {
+            item_type: URL_DOWNLOAD_ITEM_TYPE.to_string(),
+            folder_name: self.file_name.clone(),
+            stage: "download".to_string(),
+            status: status.to_string(),
+            percentage,
+            processed_units: u64::from(status == "completed"),
+            total_units: 1,
+            processed_bytes: downloaded,
+            total_bytes: self.total_bytes.max(downloaded),
+            speed_bytes_per_sec: downloaded as f64 / elapsed,
+            current_file: Some(self.file_name.clone()),
+            message: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_disposition_prefers_extended_filename() {
+        assert_eq!(
+            filename_from_content_disposition(
+                "attachment; filename=\"fallback.zip\"; filename*=UTF-8''KSEA%20Scenery%20v2.zip"
+            ),
+            Some("KSEA Scenery v2.zip".to_string())
+        );
+        assert_eq!(
+            filename_from_content_disposition("attachment; filename=\"Gateway_KBOS.zip\""),
+            Some("Gateway_KBOS.zip".to_string())
+        );
+        assert_eq!(
+            filename_from_content_disposition("attachment; filename=\"../../evil.zip\""),
+            Some("evil.zip".to_string())
+        );
+        assert_eq!(filename_from_content_disposition("inline"), None);
+    }
+
+    #[test]
+    fn url_filename_uses_last_segment() {
+        let url =
+            Url::parse("https://github.com/o/r/releases/download/v1/My%20Plane.7z?x=1").unwrap();
+        assert_eq!(filename_from_url(&url), Some("My Plane.7z".to_string()));
+        let url = Url::parse("https://example.com/").unwrap();
+        assert_eq!(filename_from_url(&url), None);
+    }
+
+    #[test]
+    fn only_http_urls_are_accepted() {
+        assert!(parse_download_url("https://example.com/a.zip").is_ok());
+        assert!(parse_download_url(" http://example.com/a.zip ").is_ok());
+        assert!(parse_download_url("file:///etc/passwd").is_err());
+        assert!(parse_download_url("ftp://example.com/a.zip").is_err());
+        assert!(parse_download_url("not a url").is_err());
+    }
+
+    #[test]
+    fn percent_decode_keeps_invalid_sequences() {
+        assert_eq!(percent_decode("a%20b"), "a b");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+}
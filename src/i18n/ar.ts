@@ -49,6 +49,11 @@ export default {
     copyingToXplane: 'جارٍ نسخ الملفات إلى مجلد X-Plane',
     installationCompleted: 'اكتمل التثبيت بنجاح!',
     noValidAddons: 'لم يتم اكتشاف أي إضافات صالحة',
+    skippedIdentical: 'مثبت بالفعل',
+    identicalInstallTitle: 'مثبت بالفعل',
+    identicalInstallMessage: 'هذه الإضافات مثبتة بالفعل من نفس الأرشيف ولم تتغير منذ ذلك الحين:',
+    identicalInstallSkip: 'تخطي',
+    identicalInstallReinstall: 'إعادة التثبيت',
     sourceDeletionSkipped: 'جذر الإضافة هو الأصل لمسار الإدخال، لذلك تم تخطي حذف المصدر: {path}',
     failedToAnalyze: 'فشل تحليل الإضافات',
     installationFailed: 'فشل التثبيت',
@@ -162,6 +167,8 @@ export default {
     unregisterContextMenu: 'إزالة تسجيل قائمة السياق',
     contextMenuRegistered: 'تم تسجيل قائمة السياق بنجاح',
     contextMenuUnregistered: 'تمت إزالة تسجيل قائمة السياق بنجاح',
+    contextMenuFileTypes: 'إظهار لـ',
+    contextMenuFolders: 'المجلدات',
     registryError: 'فشلت عملية السجل',
     appearance: 'المظهر',
     appearanceDesc: 'خصص الشكل والإحساس العام',
@@ -323,6 +330,9 @@ export default {
     unknown: 'غير معروف',
     noConfigPatternsHint: 'يرجى إعداد قواعد النسخ الاحتياطي في الإعدادات',
     liveryAircraftNotFound: 'لم يتم العثور على الطائرة المستهدفة',
+    liveryTargetChoose: 'يناسب عدة طائرات، التثبيت في:',
+    liveryTargetPlaceholder: 'اختر الطائرة',
+    sceneryRootChoose: 'يبدو كشبكة أساسية، التثبيت في:',
     targetLockedWarning: 'الهدف موجود ومقفل',
     flyWithLuaRequired: 'يرجى تثبيت إضافة FlyWithLua أولاً',
     // تعارضات مسار الهدف
@@ -333,6 +343,7 @@ export default {
     // تقرير الخطأ
     submitBugReport: 'تقرير',
     bugReportSubmitted: 'تم إرسال تقرير الخطأ بنجاح',
+    bugReportQueued: 'تم حفظ تقرير الخطأ وسيُرسل عند عودة الاتصال بالإنترنت',
     bugReportOpened: 'تم فتح صفحة تقرير الخطأ في المتصفح',
     bugReportFailed: 'فشل إرسال تقرير الخطأ',
     bugReportPrivacyNotice:
@@ -530,6 +541,7 @@ export default {
       'هل أنت متأكد من أنك تريد حذف مجلد المشاهد التالي؟ لا يمكن التراجع عن هذا الإجراء.',
     deleteSuccess: 'تم حذف المشهد',
     deleteFailed: 'فشل الحذف',
+    libraryDependentsWarning: 'تعتمد {count} حزمة مفعّلة على هذه المكتبة',
     missingLibraries: 'مكتبات مفقودة',
     missingLibrariesTitle: 'المكتبات المفقودة',
     clickToViewMissingLibs: 'انقر لعرض المكتبات المفقودة',
@@ -568,6 +580,7 @@ export default {
     categoryLibrary: 'مكتبة',
     categoryOverlay: 'تراكب',
     categoryAirportMesh: 'شبكة تضاريس المطار',
+    categoryOrthoTile: 'بلاطات صور جوية',
     categoryMesh: 'شبكة تضاريس',
     categoryOther: 'أخرى',
     categoryUnrecognized: 'غير معروفة',
@@ -742,6 +755,10 @@ export default {
     filesToReplace: 'استبدال',
     filesToDelete: 'حذف',
     filesToSkip: 'تخطي',
+    protectedFiles: 'محمية بالقواعد المحلية',
+    skipReasonBlacklisted: 'في القائمة السوداء',
+    skipReasonBlacklistedPattern: "مستبعد بواسطة '{pattern}'",
+    skipReasonNotWhitelisted: 'خارج القائمة البيضاء المحلية',
     warnings: 'تحذيرات',
     refreshPlan: 'تحديث الخطة',
     startUpdate: 'تحديث',
@@ -1056,6 +1073,7 @@ export default {
     fieldContentPlaceholder: 'صف المشكلة أو الفكرة بالتفصيل',
     submitAction: 'إرسال',
     submitSuccess: 'تم إرسال الملاحظات بنجاح',
+    submitQueued: 'تم حفظ الملاحظات وستُرسل عند عودة الاتصال بالإنترنت',
     submitFailed: 'فشل إرسال الملاحظات',
     discardTitle: 'هل تريد تجاهل مسودة الملاحظات؟',
     discardMessage: 'لديك محتوى ملاحظات غير مُرسل. هل أنت متأكد من أنك تريد الإغلاق؟',
@@ -1122,6 +1140,7 @@ export default {
     low: 'ملاحظة',
     noIssues: 'لم يتم اكتشاف مشكلات معروفة في هذا السجل.',
     lineNumbers: 'السطر {nums}',
+    relatedFolders: 'المجلدات المتأثرة:',
     systemInfo: 'معلومات النظام',
     xplaneVersion: 'إصدار X-Plane',
     gpuModel: 'GPU',
@@ -1450,6 +1469,58 @@ export default {
     security_violation: 'تم اكتشاف انتهاك أمني',
     timeout: 'انتهت مهلة العملية',
     internal: 'خطأ داخلي',
+    messages: {
+      installation_failed: 'فشل التثبيت: {0}',
+      task_cancelled: 'ألغاه المستخدم',
+      task_skipped: 'تخطاه المستخدم',
+      task_panicked: 'توقفت المهمة بشكل غير متوقع: {0}',
+      install_queue_closed: 'تم إغلاق قائمة انتظار التثبيت',
+      verification_failed: 'فشل التحقق: {0}',
+      dependency_not_installed: "تم التخطي لأن '{0}' لم يُثبَّت",
+      navdata_downgrade_not_allowed: 'دورة بيانات الملاحة {0} أقدم من الدورة المثبتة {1}. اسمح بالرجوع إلى إصدار أقدم لتثبيتها على أي حال.',
+      invalid_folder_name: 'اسم مجلد غير صالح: اجتياز المسار غير مسموح',
+      invalid_path: 'مسار غير صالح: {0}',
+      scenery_folder_not_found: 'مجلد المشهد غير موجود',
+      scenery_folder_not_found_named: 'مجلد المشهد غير موجود: {0}',
+      scenery_source_folder_not_found: 'مجلد مصدر المشهد غير موجود',
+      scenery_entry_exists: "يوجد بالفعل إدخال مشهد باسم '{0}'",
+      scenery_has_dependents: '{0} من الحزم المفعّلة تعتمد على مكتبات لا يوفرها إلا {1}',
+      preview_scenery_deletion_failed: 'فشلت معاينة حذف المشهد: {0}',
+      check_scenery_dependents_failed: 'فشل التحقق من الحزم المعتمدة على المشهد: {0}',
+      delete_permission_denied: 'تم رفض الإذن عند الحذف: {0}',
+      delete_scenery_link_failed: 'فشل حذف رابط المشهد: {0} ({1}; {2})',
+      delete_scenery_file_failed: 'فشل حذف ملف المشهد: {0}',
+      delete_scenery_folder_failed: 'فشل حذف مجلد المشهد: {0}',
+      scenery_classification_failed: 'فشل التصنيف: {0}',
+      reset_sort_order_failed: 'فشلت إعادة تعيين الترتيب: {0}',
+      reset_global_airports_position_failed: 'فشلت إعادة تعيين موضع Global Airports: {0}',
+      rebuild_index_failed: 'فشلت إعادة بناء الفهرس: {0}',
+      sync_scenery_packs_failed: 'فشلت مزامنة حزم المشاهد: {0}',
+      get_scenery_manager_data_failed: 'فشل جلب بيانات مدير المشاهد: {0}',
+      global_airports_cannot_be_pinned: 'لا يمكن تثبيت Global Airports',
+      update_global_airports_state_failed: 'فشل تحديث حالة Global Airports: {0}',
+      update_global_airports_position_failed: 'فشل تحديث موضع Global Airports: {0}',
+      update_global_airports_category_failed: 'فشل تحديث فئة Global Airports: {0}',
+      update_scenery_entry_failed: 'فشل تحديث إدخال المشهد: {0}',
+      move_global_airports_failed: 'فشل نقل إدخال Global Airports: {0}',
+      move_scenery_entry_failed: 'فشل نقل إدخال المشهد: {0}',
+      update_index_failed: 'فشل تحديث الفهرس: {0}',
+      apply_scenery_changes_failed: 'فشل تطبيق تغييرات المشاهد: {0}',
+      addon_update_failed: 'فشل التحديث: {0}',
+      addon_update_rollback_failed: 'فشل التحديث: {0}. وفشل التراجع أيضًا: {1}',
+      addon_target_not_found: 'المسار الهدف غير موجود: {0}',
+      legacy_updater_no_credentials:
+        'تستخدم هذه الإضافة بيانات تحديث قديمة ولا تدعم بيانات اعتماد الحساب',
+      no_updater_credentials: 'لم يتم العثور على بيانات اعتماد محدّث لهذه الإضافة',
+      xupdater_profile_not_found: "لم يتم العثور على ملف تعريف x-updater في '{0}'",
+      xupdater_profile_unsupported:
+        "يستخدم '{0}' ملف تعريف x-updater بدون بيانات تحديث Skunkcrafts، لذا لا يمكن تحديثه هنا. حدّثه باستخدام أداة التحديث الخاصة بالمطوّر بدلاً من ذلك.",
+      xupdater_missing_login: 'ملف تعريف x-updater يفتقد اسم الدخول/اسم المستخدم',
+      xupdater_missing_license_key: 'ملف تعريف x-updater يفتقد مفتاح الترخيص',
+      xupdater_auth_failed: 'فشلت مصادقة x-updater: HTTP {0} ({1})',
+      xupdater_no_products_for_account: 'لم يُرجع x-updater أي منتجات لهذا الحساب',
+      xupdater_no_matching_product: "لم يُرجع x-updater منتجًا يطابق '{0}'",
+    },
   },
   commandPalette: {
     title: 'لوحة الأوامر',
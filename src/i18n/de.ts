@@ -49,6 +49,12 @@ export default {
     copyingToXplane: 'Kopieren von Dateien in das X-Plane-Verzeichnis',
     installationCompleted: 'Installation erfolgreich abgeschlossen!',
     noValidAddons: 'Keine gültigen Add-ons gefunden',
+    skippedIdentical: 'Bereits installiert',
+    identicalInstallTitle: 'Bereits installiert',
+    identicalInstallMessage:
+      'Diese Add-ons sind bereits aus demselben Archiv installiert und seitdem unverändert:',
+    identicalInstallSkip: 'Überspringen',
+    identicalInstallReinstall: 'Neu installieren',
     sourceDeletionSkipped:
       'Erkannter Add-on-Stamm ist dem Eingabepfad übergeordnet, Quelllöschung übersprungen: {path}',
     failedToAnalyze: 'Add-ons konnten nicht analysiert werden',
@@ -167,6 +173,8 @@ export default {
     unregisterContextMenu: 'Kontextmenü abmelden',
     contextMenuRegistered: 'Kontextmenü erfolgreich registriert',
     contextMenuUnregistered: 'Kontextmenü erfolgreich abgemeldet',
+    contextMenuFileTypes: 'Anzeigen für',
+    contextMenuFolders: 'Ordner',
     registryError: 'Der Registrierungsvorgang ist fehlgeschlagen',
     appearance: 'Aussehen',
     appearanceDesc: 'Passen Sie das Erscheinungsbild an',
@@ -342,6 +350,9 @@ export default {
     unknown: 'Unbekannt',
     noConfigPatternsHint: 'Bitte konfigurieren Sie die Sicherungsregeln in den Einstellungen',
     liveryAircraftNotFound: 'Zielflugzeug nicht gefunden',
+    liveryTargetChoose: 'Passt zu mehreren Flugzeugen, installieren in:',
+    liveryTargetPlaceholder: 'Flugzeug auswählen',
+    sceneryRootChoose: 'Sieht aus wie ein Basis-Mesh, installieren in:',
     targetLockedWarning: 'Ziel existiert und ist gesperrt',
     flyWithLuaRequired: 'Bitte installieren Sie zuerst das FlyWithLua-Plugin',
     targetPathConflict: 'Zielpfadkonflikt',
@@ -350,6 +361,7 @@ export default {
     targetPathConflictBadge: 'Pfadkonflikt',
     submitBugReport: 'Bericht',
     bugReportSubmitted: 'Fehlerbericht erfolgreich übermittelt',
+    bugReportQueued: 'Fehlerbericht gespeichert, er wird gesendet, sobald Sie wieder online sind',
     bugReportOpened: 'Die Fehlerberichtsseite wurde im Browser geöffnet',
     bugReportFailed: 'Fehlerbericht konnte nicht übermittelt werden',
     bugReportPrivacyNotice:
@@ -549,6 +561,7 @@ export default {
       'Sind Sie sicher, dass Sie den folgenden Landschaftsordner löschen möchten? Diese Aktion kann nicht rückgängig gemacht werden.',
     deleteSuccess: 'Szenerie gelöscht',
     deleteFailed: 'Fehler beim Löschen',
+    libraryDependentsWarning: '{count} aktivierte Pakete hängen von dieser Bibliothek ab',
     missingLibraries: 'Fehlende Bibliotheken',
     missingLibrariesTitle: 'Fehlende Bibliotheken',
     clickToViewMissingLibs: 'Klicken Sie hier, um fehlende Bibliotheken anzuzeigen',
@@ -587,6 +600,7 @@ export default {
     categoryLibrary: 'Bibliothek',
     categoryOverlay: 'Überlagerung',
     categoryAirportMesh: 'Flughafennetz',
+    categoryOrthoTile: 'Orthofoto-Kacheln',
     categoryMesh: 'Netz',
     categoryOther: 'Andere',
     categoryUnrecognized: 'Nicht erkannt',
@@ -767,6 +781,10 @@ export default {
     filesToReplace: 'Ersetzen',
     filesToDelete: 'Löschen',
     filesToSkip: 'Überspringen',
+    protectedFiles: 'Durch lokale Regeln geschützt',
+    skipReasonBlacklisted: 'Auf der Blacklist',
+    skipReasonBlacklistedPattern: "Durch '{pattern}' ausgeschlossen",
+    skipReasonNotWhitelisted: 'Nicht in der lokalen Whitelist',
     warnings: 'Warnungen',
     refreshPlan: 'Plan aktualisieren',
     startUpdate: 'Aktualisieren',
@@ -1086,6 +1104,7 @@ export default {
     fieldContentPlaceholder: 'Beschreiben Sie das Problem oder die Idee im Detail',
     submitAction: 'Senden',
     submitSuccess: 'Feedback erfolgreich übermittelt',
+    submitQueued: 'Feedback gespeichert, es wird gesendet, sobald Sie wieder online sind',
     submitFailed: 'Feedback konnte nicht übermittelt werden',
     discardTitle: 'Feedback-Entwurf verwerfen?',
     discardMessage: 'Sie haben nicht gesendete Feedback-Inhalte. Möchten Sie wirklich schließen?',
@@ -1155,6 +1174,7 @@ export default {
     low: 'Hinweis',
     noIssues: 'In diesem Protokoll wurden keine bekannten Probleme festgestellt.',
     lineNumbers: 'Zeile {nums}',
+    relatedFolders: 'Betroffene Ordner:',
     systemInfo: 'Systeminformationen',
     xplaneVersion: 'X-Plane-Version',
     gpuModel: 'GPU-Modell',
@@ -1521,6 +1541,68 @@ export default {
     security_violation: 'Sicherheitsverletzung festgestellt',
     timeout: 'Zeitüberschreitung beim Vorgang',
     internal: 'Interner Fehler',
+    messages: {
+      installation_failed: 'Installation fehlgeschlagen: {0}',
+      task_cancelled: 'Vom Benutzer abgebrochen',
+      task_skipped: 'Vom Benutzer übersprungen',
+      task_panicked: 'Aufgabe abgestürzt: {0}',
+      install_queue_closed: 'Installationswarteschlange geschlossen',
+      verification_failed: 'Überprüfung fehlgeschlagen: {0}',
+      dependency_not_installed: 'Übersprungen, weil „{0}“ nicht installiert wurde',
+      navdata_downgrade_not_allowed: 'Navdaten-Zyklus {0} ist älter als der installierte Zyklus {1}. Erlauben Sie das Downgrade, um ihn trotzdem zu installieren.',
+      invalid_folder_name: 'Ungültiger Ordnername: Pfadtraversierung nicht erlaubt',
+      invalid_path: 'Ungültiger Pfad: {0}',
+      scenery_folder_not_found: 'Szenerie-Ordner nicht gefunden',
+      scenery_folder_not_found_named: 'Szenerie-Ordner nicht gefunden: {0}',
+      scenery_source_folder_not_found: 'Quellordner der Szenerie nicht gefunden',
+      scenery_entry_exists: 'Ein Szenerie-Eintrag namens „{0}“ existiert bereits',
+      scenery_has_dependents:
+        '{0} aktivierte Pakete benötigen Bibliotheken, die nur {1} bereitstellt',
+      preview_scenery_deletion_failed: 'Vorschau der Szenerie-Löschung fehlgeschlagen: {0}',
+      check_scenery_dependents_failed:
+        'Abhängigkeiten der Szenerie konnten nicht geprüft werden: {0}',
+      delete_permission_denied: 'Zugriff verweigert beim Löschen: {0}',
+      delete_scenery_link_failed:
+        'Szenerie-Verknüpfung konnte nicht gelöscht werden: {0} ({1}; {2})',
+      delete_scenery_file_failed: 'Szenerie-Datei konnte nicht gelöscht werden: {0}',
+      delete_scenery_folder_failed: 'Szenerie-Ordner konnte nicht gelöscht werden: {0}',
+      scenery_classification_failed: 'Klassifizierung fehlgeschlagen: {0}',
+      reset_sort_order_failed: 'Sortierung konnte nicht zurückgesetzt werden: {0}',
+      reset_global_airports_position_failed:
+        'Position von Global Airports konnte nicht zurückgesetzt werden: {0}',
+      rebuild_index_failed: 'Index konnte nicht neu aufgebaut werden: {0}',
+      sync_scenery_packs_failed: 'Szenerie-Pakete konnten nicht synchronisiert werden: {0}',
+      get_scenery_manager_data_failed:
+        'Daten des Szenerie-Managers konnten nicht geladen werden: {0}',
+      global_airports_cannot_be_pinned: 'Global Airports kann nicht angeheftet werden',
+      update_global_airports_state_failed:
+        'Status von Global Airports konnte nicht aktualisiert werden: {0}',
+      update_global_airports_position_failed:
+        'Position von Global Airports konnte nicht aktualisiert werden: {0}',
+      update_global_airports_category_failed:
+        'Kategorie von Global Airports konnte nicht aktualisiert werden: {0}',
+      update_scenery_entry_failed: 'Szenerie-Eintrag konnte nicht aktualisiert werden: {0}',
+      move_global_airports_failed: 'Global-Airports-Eintrag konnte nicht verschoben werden: {0}',
+      move_scenery_entry_failed: 'Szenerie-Eintrag konnte nicht verschoben werden: {0}',
+      update_index_failed: 'Index konnte nicht aktualisiert werden: {0}',
+      apply_scenery_changes_failed: 'Szenerie-Änderungen konnten nicht übernommen werden: {0}',
+      addon_update_failed: 'Update fehlgeschlagen: {0}',
+      addon_update_rollback_failed:
+        'Update fehlgeschlagen: {0}. Wiederherstellung ebenfalls fehlgeschlagen: {1}',
+      addon_target_not_found: 'Zielpfad existiert nicht: {0}',
+      legacy_updater_no_credentials:
+        'Dieses Add-on verwendet veraltete Updater-Metadaten und unterstützt keine Kontozugangsdaten',
+      no_updater_credentials: 'Keine vorhandenen Updater-Zugangsdaten für dieses Add-on gefunden',
+      xupdater_profile_not_found: 'Kein x-updater-Profil in „{0}“ gefunden',
+      xupdater_profile_unsupported:
+        '„{0}“ verwendet ein x-updater-Profil ohne Skunkcrafts-Updater-Metadaten und kann hier nicht aktualisiert werden. Aktualisiere es stattdessen mit dem Updater des Entwicklers.',
+      xupdater_missing_login: 'Im x-updater-Profil fehlt der Login/Benutzername',
+      xupdater_missing_license_key: 'Im x-updater-Profil fehlt der Lizenzschlüssel',
+      xupdater_auth_failed: 'x-updater-Anmeldung fehlgeschlagen: HTTP {0} ({1})',
+      xupdater_no_products_for_account:
+        'x-updater hat für dieses Konto keine Produkte zurückgegeben',
+      xupdater_no_matching_product: 'x-updater hat kein zu „{0}“ passendes Produkt zurückgegeben',
+    },
   },
   commandPalette: {
     title: 'Befehlspalette',
@@ -47,6 +47,12 @@ export default {
     copyingToXplane: 'Copying files to X-Plane directory',
     installationCompleted: 'Installation completed successfully!',
     noValidAddons: 'No valid add-ons detected',
+    skippedIdentical: 'Already installed',
+    identicalInstallTitle: 'Already Installed',
+    identicalInstallMessage:
+      'These addons are already installed from the same archive and have not changed since:',
+    identicalInstallSkip: 'Skip',
+    identicalInstallReinstall: 'Reinstall',
     sourceDeletionSkipped:
       'Detected addon root is a parent of input path, skipped source deletion: {path}',
     failedToAnalyze: 'Failed to analyze add-ons',
@@ -162,6 +168,8 @@ export default {
     unregisterContextMenu: 'Unregister Context Menu',
     contextMenuRegistered: 'Context menu registered successfully',
     contextMenuUnregistered: 'Context menu unregistered successfully',
+    contextMenuFileTypes: 'Show for',
+    contextMenuFolders: 'Folders',
     registryError: 'Registry operation failed',
     appearance: 'Appearance',
     appearanceDesc: 'Customize the look and feel',
@@ -327,6 +335,9 @@ export default {
     unknown: 'Unknown',
     noConfigPatternsHint: 'Please configure backup rules in Settings',
     liveryAircraftNotFound: 'Target aircraft not found',
+    liveryTargetChoose: 'Fits several aircraft, install into:',
+    liveryTargetPlaceholder: 'Select aircraft',
+    sceneryRootChoose: 'Looks like a base mesh, install into:',
     targetLockedWarning: 'Target exists and is locked',
     flyWithLuaRequired: 'Please install FlyWithLua plugin first',
     // Target path conflicts
@@ -337,6 +348,7 @@ export default {
     // Bug report
     submitBugReport: 'Report',
     bugReportSubmitted: 'Bug report submitted successfully',
+    bugReportQueued: "Bug report saved, it will be sent when you're back online",
     bugReportOpened: 'Bug report page opened in browser',
     bugReportFailed: 'Failed to submit bug report',
     bugReportPrivacyNotice:
@@ -539,6 +551,7 @@ export default {
       'Are you sure you want to delete the following scenery folder? This action cannot be undone.',
     deleteSuccess: 'Scenery deleted',
     deleteFailed: 'Failed to delete',
+    libraryDependentsWarning: '{count} enabled package(s) depend on this library',
     missingLibraries: 'Missing libraries',
     missingLibrariesTitle: 'Missing Libraries',
     clickToViewMissingLibs: 'Click to view missing libraries',
@@ -577,6 +590,7 @@ export default {
     categoryLibrary: 'Library',
     categoryOverlay: 'Overlay',
     categoryAirportMesh: 'Airport Mesh',
+    categoryOrthoTile: 'Ortho Tiles',
     categoryMesh: 'Mesh',
     categoryOther: 'Other',
     categoryUnrecognized: 'Unrecognized',
@@ -753,6 +767,10 @@ export default {
     filesToReplace: 'Replace',
     filesToDelete: 'Delete',
     filesToSkip: 'Skip',
+    protectedFiles: 'Protected by local rules',
+    skipReasonBlacklisted: 'Blacklisted',
+    skipReasonBlacklistedPattern: "Blacklisted by '{pattern}'",
+    skipReasonNotWhitelisted: 'Outside local whitelist',
     warnings: 'Warnings',
     refreshPlan: 'Refresh Plan',
     startUpdate: 'Update',
@@ -1068,6 +1086,7 @@ export default {
     fieldContentPlaceholder: 'Describe the issue or idea in detail',
     submitAction: 'Submit',
     submitSuccess: 'Feedback submitted successfully',
+    submitQueued: "Feedback saved, it will be sent when you're back online",
     submitFailed: 'Failed to submit feedback',
     discardTitle: 'Discard Feedback Draft?',
     discardMessage: 'You have unsent feedback content. Are you sure you want to close?',
@@ -1134,6 +1153,7 @@ export default {
     low: 'Notice',
     noIssues: 'No known issues detected in this log.',
     lineNumbers: 'Line {nums}',
+    relatedFolders: 'Affected folders:',
     systemInfo: 'System Info',
     xplaneVersion: 'X-Plane Version',
     gpuModel: 'GPU',
@@ -1472,6 +1492,58 @@ export default {
     security_violation: 'Security violation detected',
     timeout: 'Operation timed out',
     internal: 'Internal error',
+    messages: {
+      installation_failed: 'Installation failed: {0}',
+      task_cancelled: 'Cancelled by user',
+      task_skipped: 'Skipped by user',
+      task_panicked: 'Task panicked: {0}',
+      install_queue_closed: 'Semaphore closed',
+      verification_failed: 'Verification failed: {0}',
+      dependency_not_installed: "Skipped because '{0}' was not installed",
+      navdata_downgrade_not_allowed: 'Navdata cycle {0} is older than the installed cycle {1}. Allow the downgrade to install it anyway.',
+      invalid_folder_name: 'Invalid folder name: path traversal not allowed',
+      invalid_path: 'Invalid path: {0}',
+      scenery_folder_not_found: 'Scenery folder not found',
+      scenery_folder_not_found_named: 'Scenery folder not found: {0}',
+      scenery_source_folder_not_found: 'Scenery source folder not found',
+      scenery_entry_exists: "A scenery entry named '{0}' already exists",
+      scenery_has_dependents: '{0} enabled packages depend on libraries only {1} provides',
+      preview_scenery_deletion_failed: 'Failed to preview scenery deletion: {0}',
+      check_scenery_dependents_failed: 'Failed to check scenery dependents: {0}',
+      delete_permission_denied: 'Permission denied when deleting: {0}',
+      delete_scenery_link_failed: 'Failed to delete scenery link: {0} ({1}; {2})',
+      delete_scenery_file_failed: 'Failed to delete scenery file: {0}',
+      delete_scenery_folder_failed: 'Failed to delete scenery folder: {0}',
+      scenery_classification_failed: 'Classification failed: {0}',
+      reset_sort_order_failed: 'Failed to reset sort order: {0}',
+      reset_global_airports_position_failed: 'Failed to reset Global Airports position: {0}',
+      rebuild_index_failed: 'Failed to rebuild index: {0}',
+      sync_scenery_packs_failed: 'Failed to sync scenery packs: {0}',
+      get_scenery_manager_data_failed: 'Failed to get scenery manager data: {0}',
+      global_airports_cannot_be_pinned: 'Global Airports cannot be pinned',
+      update_global_airports_state_failed: 'Failed to update Global Airports state: {0}',
+      update_global_airports_position_failed: 'Failed to update Global Airports position: {0}',
+      update_global_airports_category_failed: 'Failed to update Global Airports category: {0}',
+      update_scenery_entry_failed: 'Failed to update scenery entry: {0}',
+      move_global_airports_failed: 'Failed to move Global Airports entry: {0}',
+      move_scenery_entry_failed: 'Failed to move scenery entry: {0}',
+      update_index_failed: 'Failed to update index: {0}',
+      apply_scenery_changes_failed: 'Failed to apply scenery changes: {0}',
+      addon_update_failed: 'Update failed: {0}',
+      addon_update_rollback_failed: 'Update failed: {0}. Rollback also failed: {1}',
+      addon_target_not_found: 'Target path does not exist: {0}',
+      legacy_updater_no_credentials:
+        'This addon is using legacy updater metadata and does not support account credentials',
+      no_updater_credentials: 'No existing updater credentials found for this addon',
+      xupdater_profile_not_found: "No x-updater profile was found in '{0}'",
+      xupdater_profile_unsupported:
+        "'{0}' uses an x-updater profile without Skunkcrafts updater metadata, which can't be updated here. Update it with the developer's updater instead.",
+      xupdater_missing_login: 'x-updater profile is missing login/username',
+      xupdater_missing_license_key: 'x-updater profile is missing license key',
+      xupdater_auth_failed: 'x-updater authentication failed: HTTP {0} ({1})',
+      xupdater_no_products_for_account: 'x-updater returned no products for this account',
+      xupdater_no_matching_product: "x-updater did not return a product matching '{0}'",
+    },
   },
   commandPalette: {
     title: 'Command Palette',
@@ -49,6 +49,12 @@ export default {
     copyingToXplane: 'Copiar archivos al directorio X-Plane',
     installationCompleted: '¡La instalación se completó con éxito!',
     noValidAddons: 'No se detectaron complementos válidos',
+    skippedIdentical: 'Ya instalado',
+    identicalInstallTitle: 'Ya instalado',
+    identicalInstallMessage:
+      'Estos complementos ya están instalados desde el mismo archivo y no han cambiado desde entonces:',
+    identicalInstallSkip: 'Omitir',
+    identicalInstallReinstall: 'Reinstalar',
     sourceDeletionSkipped:
       'La raíz del complemento detectada es principal de la ruta de entrada, se omitió la eliminación de la fuente: {path}',
     failedToAnalyze: 'No se pudieron analizar los complementos',
@@ -168,6 +174,8 @@ export default {
     unregisterContextMenu: 'Menú contextual para cancelar el registro',
     contextMenuRegistered: 'Menú contextual registrado correctamente',
     contextMenuUnregistered: 'Menú contextual cancelado exitosamente',
+    contextMenuFileTypes: 'Mostrar para',
+    contextMenuFolders: 'Carpetas',
     registryError: 'Error en la operación de registro',
     appearance: 'Apariencia',
     appearanceDesc: 'Personaliza la apariencia',
@@ -345,6 +353,9 @@ export default {
     unknown: 'Desconocido',
     noConfigPatternsHint: 'Configure las reglas de copia de seguridad en Configuración',
     liveryAircraftNotFound: 'Avión objetivo no encontrado',
+    liveryTargetChoose: 'Sirve para varios aviones, instalar en:',
+    liveryTargetPlaceholder: 'Seleccionar avión',
+    sceneryRootChoose: 'Parece una malla base, instalar en:',
     targetLockedWarning: 'El objetivo existe y está bloqueado',
     flyWithLuaRequired: 'Instale primero el complemento FlyWithLua',
     targetPathConflict: 'Conflicto de ruta de destino',
@@ -353,6 +364,7 @@ export default {
     targetPathConflictBadge: 'Conflicto de ruta',
     submitBugReport: 'Informe',
     bugReportSubmitted: 'Informe de error enviado correctamente',
+    bugReportQueued: 'Informe de error guardado; se enviará cuando vuelvas a estar en línea',
     bugReportOpened: 'Página de informe de errores abierta en el navegador',
     bugReportFailed: 'No se pudo enviar el informe de error',
     bugReportPrivacyNotice:
@@ -550,6 +562,7 @@ export default {
       '¿Está seguro de que desea eliminar la siguiente carpeta de escenarios? Esta acción no se puede deshacer.',
     deleteSuccess: 'Paisaje eliminado',
     deleteFailed: 'No se pudo eliminar',
+    libraryDependentsWarning: '{count} paquete(s) habilitado(s) dependen de esta biblioteca',
     missingLibraries: 'Bibliotecas faltantes',
     missingLibrariesTitle: 'Bibliotecas faltantes',
     clickToViewMissingLibs: 'Haga clic para ver las bibliotecas que faltan',
@@ -588,6 +601,7 @@ export default {
     categoryLibrary: 'Biblioteca',
     categoryOverlay: 'Superposición',
     categoryAirportMesh: 'Malla de aeropuerto',
+    categoryOrthoTile: 'Teselas ortofoto',
     categoryMesh: 'Malla',
     categoryOther: 'Otro',
     categoryUnrecognized: 'no reconocido',
@@ -770,6 +784,10 @@ export default {
     filesToReplace: 'Reemplazar',
     filesToDelete: 'Eliminar',
     filesToSkip: 'Saltar',
+    protectedFiles: 'Protegidos por reglas locales',
+    skipReasonBlacklisted: 'En la lista negra',
+    skipReasonBlacklistedPattern: "Excluido por '{pattern}'",
+    skipReasonNotWhitelisted: 'Fuera de la lista blanca local',
     warnings: 'Advertencias',
     refreshPlan: 'Actualizar plan',
     startUpdate: 'Actualizar',
@@ -1088,6 +1106,7 @@ export default {
     fieldContentPlaceholder: 'Describe el tema o la idea en detalle.',
     submitAction: 'Enviar',
     submitSuccess: 'Comentarios enviados correctamente',
+    submitQueued: 'Comentarios guardados; se enviarán cuando vuelvas a estar en línea',
     submitFailed: 'No se han podido enviar comentarios',
     discardTitle: '¿Descartar el borrador de comentarios?',
     discardMessage:
@@ -1157,6 +1176,7 @@ export default {
     low: 'Aviso',
     noIssues: 'No se detectaron problemas conocidos en este registro.',
     lineNumbers: 'Línea {nums}',
+    relatedFolders: 'Carpetas afectadas:',
     systemInfo: 'Información del sistema',
     xplaneVersion: 'Versión X-Plane',
     gpuModel: 'Modelo GPU',
@@ -1519,6 +1539,67 @@ export default {
     security_violation: 'Violación de seguridad detectada',
     timeout: 'Operación agotada',
     internal: 'error interno',
+    messages: {
+      installation_failed: 'Error en la instalación: {0}',
+      task_cancelled: 'Cancelado por el usuario',
+      task_skipped: 'Omitido por el usuario',
+      task_panicked: 'La tarea falló inesperadamente: {0}',
+      install_queue_closed: 'La cola de instalación está cerrada',
+      verification_failed: 'Error de verificación: {0}',
+      dependency_not_installed: "Omitido porque '{0}' no se instaló",
+      navdata_downgrade_not_allowed: 'El ciclo de navdata {0} es anterior al ciclo instalado {1}. Permite la versión anterior para instalarlo de todos modos.',
+      invalid_folder_name: 'Nombre de carpeta no válido: no se permite salir de la ruta',
+      invalid_path: 'Ruta no válida: {0}',
+      scenery_folder_not_found: 'No se encontró la carpeta de escenario',
+      scenery_folder_not_found_named: 'No se encontró la carpeta de escenario: {0}',
+      scenery_source_folder_not_found: 'No se encontró la carpeta de origen del escenario',
+      scenery_entry_exists: "Ya existe una entrada de escenario llamada '{0}'",
+      scenery_has_dependents:
+        '{0} paquetes activados dependen de bibliotecas que solo proporciona {1}',
+      preview_scenery_deletion_failed: 'No se pudo previsualizar la eliminación del escenario: {0}',
+      check_scenery_dependents_failed:
+        'No se pudieron comprobar los dependientes del escenario: {0}',
+      delete_permission_denied: 'Permiso denegado al eliminar: {0}',
+      delete_scenery_link_failed: 'No se pudo eliminar el enlace de escenario: {0} ({1}; {2})',
+      delete_scenery_file_failed: 'No se pudo eliminar el archivo de escenario: {0}',
+      delete_scenery_folder_failed: 'No se pudo eliminar la carpeta de escenario: {0}',
+      scenery_classification_failed: 'Error de clasificación: {0}',
+      reset_sort_order_failed: 'No se pudo restablecer el orden: {0}',
+      reset_global_airports_position_failed:
+        'No se pudo restablecer la posición de Global Airports: {0}',
+      rebuild_index_failed: 'No se pudo reconstruir el índice: {0}',
+      sync_scenery_packs_failed: 'No se pudieron sincronizar los paquetes de escenario: {0}',
+      get_scenery_manager_data_failed:
+        'No se pudieron obtener los datos del gestor de escenarios: {0}',
+      global_airports_cannot_be_pinned: 'Global Airports no se puede fijar',
+      update_global_airports_state_failed:
+        'No se pudo actualizar el estado de Global Airports: {0}',
+      update_global_airports_position_failed:
+        'No se pudo actualizar la posición de Global Airports: {0}',
+      update_global_airports_category_failed:
+        'No se pudo actualizar la categoría de Global Airports: {0}',
+      update_scenery_entry_failed: 'No se pudo actualizar la entrada de escenario: {0}',
+      move_global_airports_failed: 'No se pudo mover la entrada de Global Airports: {0}',
+      move_scenery_entry_failed: 'No se pudo mover la entrada de escenario: {0}',
+      update_index_failed: 'No se pudo actualizar el índice: {0}',
+      apply_scenery_changes_failed: 'No se pudieron aplicar los cambios de escenario: {0}',
+      addon_update_failed: 'Error en la actualización: {0}',
+      addon_update_rollback_failed:
+        'Error en la actualización: {0}. La reversión también falló: {1}',
+      addon_target_not_found: 'La ruta de destino no existe: {0}',
+      legacy_updater_no_credentials:
+        'Este complemento usa metadatos de actualizador heredados y no admite credenciales de cuenta',
+      no_updater_credentials:
+        'No se encontraron credenciales de actualizador para este complemento',
+      xupdater_profile_not_found: "No se encontró ningún perfil de x-updater en '{0}'",
+      xupdater_profile_unsupported:
+        "'{0}' usa un perfil de x-updater sin metadatos del actualizador de Skunkcrafts y no se puede actualizar aquí. Actualízalo con el actualizador del desarrollador.",
+      xupdater_missing_login: 'Al perfil de x-updater le falta el inicio de sesión/usuario',
+      xupdater_missing_license_key: 'Al perfil de x-updater le falta la clave de licencia',
+      xupdater_auth_failed: 'Error de autenticación de x-updater: HTTP {0} ({1})',
+      xupdater_no_products_for_account: 'x-updater no devolvió productos para esta cuenta',
+      xupdater_no_matching_product: "x-updater no devolvió ningún producto que coincida con '{0}'",
+    },
   },
   commandPalette: {
     title: 'Paleta de comandos',
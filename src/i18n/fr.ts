@@ -49,6 +49,12 @@ export default {
     copyingToXplane: 'Copie de fichiers dans le répertoire X-Plane',
     installationCompleted: 'Installation terminée avec succès !',
     noValidAddons: 'Aucun module complémentaire valide détecté',
+    skippedIdentical: 'Déjà installé',
+    identicalInstallTitle: 'Déjà installé',
+    identicalInstallMessage:
+      "Ces add-ons sont déjà installés depuis la même archive et n'ont pas changé depuis :",
+    identicalInstallSkip: 'Ignorer',
+    identicalInstallReinstall: 'Réinstaller',
     sourceDeletionSkipped:
       "La racine du module complémentaire détectée est un parent du chemin d'entrée, suppression de la source ignorée : {path}",
     failedToAnalyze: "Échec de l'analyse des modules complémentaires",
@@ -170,6 +176,8 @@ export default {
     unregisterContextMenu: 'Menu contextuel de désinscription',
     contextMenuRegistered: 'Menu contextuel enregistré avec succès',
     contextMenuUnregistered: 'Menu contextuel désenregistré avec succès',
+    contextMenuFileTypes: 'Afficher pour',
+    contextMenuFolders: 'Dossiers',
     registryError: "L'opération de registre a échoué",
     appearance: 'Apparence',
     appearanceDesc: "Personnalisez l'apparence et la convivialité",
@@ -352,6 +360,9 @@ export default {
     unknown: 'Inconnu',
     noConfigPatternsHint: 'Veuillez configurer les règles de sauvegarde dans Paramètres',
     liveryAircraftNotFound: 'Avion cible introuvable',
+    liveryTargetChoose: 'Convient à plusieurs avions, installer dans :',
+    liveryTargetPlaceholder: "Choisir l'avion",
+    sceneryRootChoose: 'Ressemble à un maillage de base, installer dans :',
     targetLockedWarning: 'La cible existe et est verrouillée',
     flyWithLuaRequired: "Veuillez d'abord installer le plugin FlyWithLua",
     targetPathConflict: 'Conflit de chemin cible',
@@ -360,6 +371,8 @@ export default {
     targetPathConflictBadge: 'Conflit de chemin',
     submitBugReport: 'Rapport',
     bugReportSubmitted: 'Rapport de bug soumis avec succès',
+    bugReportQueued:
+      'Rapport de bug enregistré, il sera envoyé dès que vous serez de nouveau en ligne',
     bugReportOpened: 'Page de rapport de bug ouverte dans le navigateur',
     bugReportFailed: 'Échec de la soumission du rapport de bug',
     bugReportPrivacyNotice:
@@ -557,6 +570,7 @@ export default {
       'Êtes-vous sûr de vouloir supprimer le dossier de scènes suivant ? Cette action ne peut pas être annulée.',
     deleteSuccess: 'Décor supprimé',
     deleteFailed: 'Échec de la suppression',
+    libraryDependentsWarning: '{count} paquet(s) activé(s) dépendent de cette bibliothèque',
     missingLibraries: 'Bibliothèques manquantes',
     missingLibrariesTitle: 'Bibliothèques manquantes',
     clickToViewMissingLibs: 'Cliquez pour afficher les bibliothèques manquantes',
@@ -595,6 +609,7 @@ export default {
     categoryLibrary: 'Bibliothèque',
     categoryOverlay: 'Superposition',
     categoryAirportMesh: "Maille d'aéroport",
+    categoryOrthoTile: 'Tuiles ortho',
     categoryMesh: 'Maille',
     categoryOther: 'Autre',
     categoryUnrecognized: 'Non reconnu',
@@ -773,6 +788,10 @@ export default {
     filesToReplace: 'Remplacer',
     filesToDelete: 'Supprimer',
     filesToSkip: 'Sauter',
+    protectedFiles: 'Protégés par des règles locales',
+    skipReasonBlacklisted: 'Sur liste noire',
+    skipReasonBlacklistedPattern: "Exclu par '{pattern}'",
+    skipReasonNotWhitelisted: 'Hors de la liste blanche locale',
     warnings: 'Avertissements',
     refreshPlan: 'Plan de rafraîchissement',
     startUpdate: 'Mise à jour',
@@ -1091,6 +1110,8 @@ export default {
     fieldContentPlaceholder: "Décrire le problème ou l'idée en détail",
     submitAction: 'Soumettre',
     submitSuccess: 'Commentaires soumis avec succès',
+    submitQueued:
+      'Commentaires enregistrés, ils seront envoyés dès que vous serez de nouveau en ligne',
     submitFailed: "Échec de l'envoi des commentaires",
     discardTitle: 'Supprimer le brouillon de commentaires ?',
     discardMessage:
@@ -1162,6 +1183,7 @@ export default {
     low: 'Avis',
     noIssues: 'Aucun problème connu détecté dans ce journal.',
     lineNumbers: 'Ligne {nums}',
+    relatedFolders: 'Dossiers concernés :',
     systemInfo: 'Informations système',
     xplaneVersion: 'Version X-Plane',
     gpuModel: 'Modèle GPU',
@@ -1522,6 +1544,67 @@ export default {
     security_violation: 'Violation de sécurité détectée',
     timeout: "L'opération a expiré",
     internal: 'Erreur interne',
+    messages: {
+      installation_failed: "Échec de l'installation : {0}",
+      task_cancelled: "Annulé par l'utilisateur",
+      task_skipped: "Ignoré par l'utilisateur",
+      task_panicked: 'La tâche a planté : {0}',
+      install_queue_closed: "La file d'installation est fermée",
+      verification_failed: 'Échec de la vérification : {0}',
+      dependency_not_installed: "Ignoré car « {0} » n'a pas été installé",
+      navdata_downgrade_not_allowed: "Le cycle navdata {0} est plus ancien que le cycle installé {1}. Autorisez le retour en arrière pour l'installer quand même.",
+      invalid_folder_name: "Nom de dossier invalide : la traversée de chemin n'est pas autorisée",
+      invalid_path: 'Chemin invalide : {0}',
+      scenery_folder_not_found: 'Dossier de scènerie introuvable',
+      scenery_folder_not_found_named: 'Dossier de scènerie introuvable : {0}',
+      scenery_source_folder_not_found: 'Dossier source de la scènerie introuvable',
+      scenery_entry_exists: 'Une entrée de scènerie nommée « {0} » existe déjà',
+      scenery_has_dependents:
+        '{0} paquets activés dépendent de bibliothèques fournies uniquement par {1}',
+      preview_scenery_deletion_failed:
+        'Impossible de prévisualiser la suppression de la scènerie : {0}',
+      check_scenery_dependents_failed:
+        'Impossible de vérifier les dépendances de la scènerie : {0}',
+      delete_permission_denied: 'Permission refusée lors de la suppression : {0}',
+      delete_scenery_link_failed: 'Impossible de supprimer le lien de scènerie : {0} ({1} ; {2})',
+      delete_scenery_file_failed: 'Impossible de supprimer le fichier de scènerie : {0}',
+      delete_scenery_folder_failed: 'Impossible de supprimer le dossier de scènerie : {0}',
+      scenery_classification_failed: 'Échec de la classification : {0}',
+      reset_sort_order_failed: "Impossible de réinitialiser l'ordre : {0}",
+      reset_global_airports_position_failed:
+        'Impossible de réinitialiser la position de Global Airports : {0}',
+      rebuild_index_failed: "Impossible de reconstruire l'index : {0}",
+      sync_scenery_packs_failed: 'Impossible de synchroniser les paquets de scènerie : {0}',
+      get_scenery_manager_data_failed:
+        "Impossible d'obtenir les données du gestionnaire de scèneries : {0}",
+      global_airports_cannot_be_pinned: 'Global Airports ne peut pas être épinglé',
+      update_global_airports_state_failed:
+        "Impossible de mettre à jour l'état de Global Airports : {0}",
+      update_global_airports_position_failed:
+        'Impossible de mettre à jour la position de Global Airports : {0}',
+      update_global_airports_category_failed:
+        'Impossible de mettre à jour la catégorie de Global Airports : {0}',
+      update_scenery_entry_failed: "Impossible de mettre à jour l'entrée de scènerie : {0}",
+      move_global_airports_failed: "Impossible de déplacer l'entrée Global Airports : {0}",
+      move_scenery_entry_failed: "Impossible de déplacer l'entrée de scènerie : {0}",
+      update_index_failed: "Impossible de mettre à jour l'index : {0}",
+      apply_scenery_changes_failed: "Impossible d'appliquer les modifications de scènerie : {0}",
+      addon_update_failed: 'Échec de la mise à jour : {0}',
+      addon_update_rollback_failed:
+        'Échec de la mise à jour : {0}. La restauration a également échoué : {1}',
+      addon_target_not_found: "Le chemin cible n'existe pas : {0}",
+      legacy_updater_no_credentials:
+        "Cet add-on utilise d'anciennes métadonnées de mise à jour et ne prend pas en charge les identifiants de compte",
+      no_updater_credentials: 'Aucun identifiant de mise à jour existant trouvé pour cet add-on',
+      xupdater_profile_not_found: 'Aucun profil x-updater trouvé dans « {0} »',
+      xupdater_profile_unsupported:
+        "« {0} » utilise un profil x-updater sans métadonnées de mise à jour Skunkcrafts et ne peut pas être mis à jour ici. Utilisez plutôt l'outil de mise à jour du développeur.",
+      xupdater_missing_login: "Le profil x-updater n'a pas d'identifiant/nom d'utilisateur",
+      xupdater_missing_license_key: "Le profil x-updater n'a pas de clé de licence",
+      xupdater_auth_failed: "Échec de l'authentification x-updater : HTTP {0} ({1})",
+      xupdater_no_products_for_account: "x-updater n'a renvoyé aucun produit pour ce compte",
+      xupdater_no_matching_product: "x-updater n'a renvoyé aucun produit correspondant à « {0} »",
+    },
   },
   commandPalette: {
     title: 'Palette de commandes',
@@ -49,6 +49,11 @@ export default {
     copyingToXplane: 'फ़ाइलें X-Plane निर्देशिका में कॉपी की जा रही हैं',
     installationCompleted: 'स्थापना सफलतापूर्वक पूरी हुई!',
     noValidAddons: 'कोई मान्य ऐडऑन नहीं मिला',
+    skippedIdentical: 'पहले से इंस्टॉल',
+    identicalInstallTitle: 'पहले से इंस्टॉल',
+    identicalInstallMessage: 'ये ऐडऑन उसी आर्काइव से पहले ही इंस्टॉल हैं और तब से नहीं बदले:',
+    identicalInstallSkip: 'छोड़ें',
+    identicalInstallReinstall: 'फिर से इंस्टॉल करें',
     sourceDeletionSkipped: 'ऐडऑन मूल इनपुट पथ का जनक है, इसलिए स्रोत हटाना छोड़ दिया गया: {path}',
     failedToAnalyze: 'ऐडऑन का विश्लेषण नहीं हो सका',
     installationFailed: 'स्थापना विफल हुई',
@@ -163,6 +168,8 @@ export default {
     unregisterContextMenu: 'संदर्भ मेनू हटाएँ',
     contextMenuRegistered: 'संदर्भ मेनू सफलतापूर्वक पंजीकृत हुआ',
     contextMenuUnregistered: 'संदर्भ मेनू सफलतापूर्वक हटाया गया',
+    contextMenuFileTypes: 'इनके लिए दिखाएं',
+    contextMenuFolders: 'फ़ोल्डर',
     registryError: 'रजिस्ट्री कार्रवाई विफल हुई',
     appearance: 'रूप-रंग',
     appearanceDesc: 'दिखावट और अनुभव अनुकूलित करें',
@@ -334,6 +341,9 @@ export default {
     unknown: 'अज्ञात',
     noConfigPatternsHint: 'कृपया सेटिंग्स में बैकअप नियम कॉन्फ़िगर करें',
     liveryAircraftNotFound: 'लक्ष्य विमान नहीं मिला',
+    liveryTargetChoose: 'कई विमानों के लिए उपयुक्त, यहाँ इंस्टॉल करें:',
+    liveryTargetPlaceholder: 'विमान चुनें',
+    sceneryRootChoose: 'यह बेस मेश जैसा लगता है, यहाँ इंस्टॉल करें:',
     targetLockedWarning: 'लक्ष्य मौजूद है और लॉक है',
     flyWithLuaRequired: 'कृपया पहले FlyWithLua प्लगइन स्थापित करें',
     // लक्ष्य पथ टकराव
@@ -344,6 +354,7 @@ export default {
     // दोष रिपोर्ट
     submitBugReport: 'रिपोर्ट',
     bugReportSubmitted: 'दोष रिपोर्ट सफलतापूर्वक भेजी गई',
+    bugReportQueued: 'दोष रिपोर्ट सहेजी गई, ऑनलाइन होने पर भेजी जाएगी',
     bugReportOpened: 'ब्राउज़र में दोष रिपोर्ट पृष्ठ खोला गया',
     bugReportFailed: 'दोष रिपोर्ट भेजने में विफल',
     bugReportPrivacyNotice:
@@ -541,6 +552,7 @@ export default {
       'क्या आप वाकई निम्न दृश्य फ़ोल्डर हटाना चाहते हैं? यह क्रिया वापस नहीं की जा सकती।',
     deleteSuccess: 'दृश्य हटाया गया',
     deleteFailed: 'हटाना विफल',
+    libraryDependentsWarning: '{count} सक्षम पैकेज इस लाइब्रेरी पर निर्भर हैं',
     missingLibraries: 'गायब लाइब्रेरी',
     missingLibrariesTitle: 'गायब लाइब्रेरी',
     clickToViewMissingLibs: 'गायब लाइब्रेरी देखने के लिए क्लिक करें',
@@ -579,6 +591,7 @@ export default {
     categoryLibrary: 'लाइब्रेरी',
     categoryOverlay: 'ओवरले',
     categoryAirportMesh: 'हवाई अड्डा मेश',
+    categoryOrthoTile: 'ऑर्थो टाइल्स',
     categoryMesh: 'मेश',
     categoryOther: 'अन्य',
     categoryUnrecognized: 'अपरिचित',
@@ -755,6 +768,10 @@ export default {
     filesToReplace: 'बदलें',
     filesToDelete: 'हटाएं',
     filesToSkip: 'छोड़ें',
+    protectedFiles: 'स्थानीय नियमों द्वारा सुरक्षित',
+    skipReasonBlacklisted: 'ब्लैकलिस्ट में',
+    skipReasonBlacklistedPattern: "'{pattern}' द्वारा बाहर रखा गया",
+    skipReasonNotWhitelisted: 'स्थानीय व्हाइटलिस्ट से बाहर',
     warnings: 'चेतावनियाँ',
     refreshPlan: 'योजना ताज़ा करें',
     startUpdate: 'अद्यतन करें',
@@ -1070,6 +1087,7 @@ export default {
     fieldContentPlaceholder: 'समस्या या सुझाव का विस्तार से वर्णन करें',
     submitAction: 'जमा करें',
     submitSuccess: 'प्रतिक्रिया सफलतापूर्वक भेजी गई',
+    submitQueued: 'प्रतिक्रिया सहेजी गई, ऑनलाइन होने पर भेजी जाएगी',
     submitFailed: 'प्रतिक्रिया भेजने में विफल',
     discardTitle: 'प्रतिक्रिया मसौदा त्यागें?',
     discardMessage: 'आपके पास न भेजी गई प्रतिक्रिया सामग्री है। क्या आप वाकई बंद करना चाहते हैं?',
@@ -1138,6 +1156,7 @@ export default {
     low: 'सूचना',
     noIssues: 'इस लॉग में कोई ज्ञात समस्या नहीं पाई गई।',
     lineNumbers: 'पंक्ति {nums}',
+    relatedFolders: 'प्रभावित फ़ोल्डर:',
     systemInfo: 'सिस्टम जानकारी',
     xplaneVersion: 'X-Plane संस्करण',
     gpuModel: 'GPU',
@@ -1480,6 +1499,59 @@ export default {
     security_violation: 'सुरक्षा उल्लंघन पाया गया',
     timeout: 'कार्रवाई का समय समाप्त हुआ',
     internal: 'आंतरिक त्रुटि',
+    messages: {
+      installation_failed: 'इंस्टॉलेशन विफल: {0}',
+      task_cancelled: 'उपयोगकर्ता द्वारा रद्द किया गया',
+      task_skipped: 'उपयोगकर्ता द्वारा छोड़ा गया',
+      task_panicked: 'कार्य अनपेक्षित रूप से रुक गया: {0}',
+      install_queue_closed: 'इंस्टॉल कतार बंद हो गई',
+      verification_failed: 'सत्यापन विफल: {0}',
+      dependency_not_installed: "छोड़ा गया क्योंकि '{0}' इंस्टॉल नहीं हुआ",
+      navdata_downgrade_not_allowed: 'नेवडेटा चक्र {0} इंस्टॉल किए गए चक्र {1} से पुराना है। फिर भी इंस्टॉल करने के लिए डाउनग्रेड की अनुमति दें।',
+      invalid_folder_name: 'अमान्य फ़ोल्डर नाम: पाथ ट्रैवर्सल की अनुमति नहीं है',
+      invalid_path: 'अमान्य पाथ: {0}',
+      scenery_folder_not_found: 'सीनरी फ़ोल्डर नहीं मिला',
+      scenery_folder_not_found_named: 'सीनरी फ़ोल्डर नहीं मिला: {0}',
+      scenery_source_folder_not_found: 'सीनरी स्रोत फ़ोल्डर नहीं मिला',
+      scenery_entry_exists: "'{0}' नाम की सीनरी प्रविष्टि पहले से मौजूद है",
+      scenery_has_dependents:
+        '{0} सक्षम पैकेज उन लाइब्रेरी पर निर्भर हैं जो केवल {1} प्रदान करता है',
+      preview_scenery_deletion_failed: 'सीनरी हटाने का पूर्वावलोकन विफल: {0}',
+      check_scenery_dependents_failed: 'सीनरी निर्भरताएँ जाँचने में विफल: {0}',
+      delete_permission_denied: 'हटाते समय अनुमति अस्वीकृत: {0}',
+      delete_scenery_link_failed: 'सीनरी लिंक हटाने में विफल: {0} ({1}; {2})',
+      delete_scenery_file_failed: 'सीनरी फ़ाइल हटाने में विफल: {0}',
+      delete_scenery_folder_failed: 'सीनरी फ़ोल्डर हटाने में विफल: {0}',
+      scenery_classification_failed: 'वर्गीकरण विफल: {0}',
+      reset_sort_order_failed: 'क्रम रीसेट करने में विफल: {0}',
+      reset_global_airports_position_failed: 'Global Airports की स्थिति रीसेट करने में विफल: {0}',
+      rebuild_index_failed: 'इंडेक्स फिर से बनाने में विफल: {0}',
+      sync_scenery_packs_failed: 'सीनरी पैक सिंक करने में विफल: {0}',
+      get_scenery_manager_data_failed: 'सीनरी मैनेजर डेटा प्राप्त करने में विफल: {0}',
+      global_airports_cannot_be_pinned: 'Global Airports को पिन नहीं किया जा सकता',
+      update_global_airports_state_failed: 'Global Airports की अवस्था अपडेट करने में विफल: {0}',
+      update_global_airports_position_failed: 'Global Airports की स्थिति अपडेट करने में विफल: {0}',
+      update_global_airports_category_failed: 'Global Airports की श्रेणी अपडेट करने में विफल: {0}',
+      update_scenery_entry_failed: 'सीनरी प्रविष्टि अपडेट करने में विफल: {0}',
+      move_global_airports_failed: 'Global Airports प्रविष्टि खिसकाने में विफल: {0}',
+      move_scenery_entry_failed: 'सीनरी प्रविष्टि खिसकाने में विफल: {0}',
+      update_index_failed: 'इंडेक्स अपडेट करने में विफल: {0}',
+      apply_scenery_changes_failed: 'सीनरी परिवर्तन लागू करने में विफल: {0}',
+      addon_update_failed: 'अपडेट विफल: {0}',
+      addon_update_rollback_failed: 'अपडेट विफल: {0}. रोलबैक भी विफल: {1}',
+      addon_target_not_found: 'लक्ष्य पाथ मौजूद नहीं है: {0}',
+      legacy_updater_no_credentials:
+        'यह ऐडऑन पुराने अपडेटर मेटाडेटा का उपयोग करता है और खाता क्रेडेंशियल का समर्थन नहीं करता',
+      no_updater_credentials: 'इस ऐडऑन के लिए कोई मौजूदा अपडेटर क्रेडेंशियल नहीं मिले',
+      xupdater_profile_not_found: "'{0}' में कोई x-updater प्रोफ़ाइल नहीं मिली",
+      xupdater_profile_unsupported:
+        "'{0}' Skunkcrafts अपडेटर मेटाडेटा के बिना x-updater प्रोफ़ाइल का उपयोग करता है, इसलिए इसे यहाँ अपडेट नहीं किया जा सकता। इसके बजाय डेवलपर के अपडेटर से अपडेट करें।",
+      xupdater_missing_login: 'x-updater प्रोफ़ाइल में लॉगिन/उपयोगकर्ता नाम नहीं है',
+      xupdater_missing_license_key: 'x-updater प्रोफ़ाइल में लाइसेंस कुंजी नहीं है',
+      xupdater_auth_failed: 'x-updater प्रमाणीकरण विफल: HTTP {0} ({1})',
+      xupdater_no_products_for_account: 'x-updater ने इस खाते के लिए कोई उत्पाद नहीं लौटाया',
+      xupdater_no_matching_product: "x-updater ने '{0}' से मेल खाने वाला कोई उत्पाद नहीं लौटाया",
+    },
   },
   commandPalette: {
     title: 'कमांड पैलेट',
@@ -74,4 +74,19 @@ export async function setLocale(locale: SupportedLocale) {
   }
 }
 
+/**
+ * Localize a backend catalog message by its key and positional arguments,
+ * falling back to the English text rendered by the backend
+ */
+export function localizeBackendMessage(
+  key: string | undefined,
+  args: string[] | undefined,
+  fallback: string,
+): string {
+  if (!key) return fallback
+  const path = `errors.messages.${key}`
+  if (!i18n.global.te(path)) return fallback
+  return i18n.global.t(path, args ?? [])
+}
+
 export default i18n
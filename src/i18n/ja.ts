@@ -49,6 +49,11 @@ export default {
     copyingToXplane: 'X-Plane ディレクトリへのファイルのコピー',
     installationCompleted: '無事にインストール完了しました！',
     noValidAddons: '有効なアドオンが検出されませんでした',
+    skippedIdentical: 'インストール済み',
+    identicalInstallTitle: 'インストール済み',
+    identicalInstallMessage: '以下のアドオンは同じアーカイブからインストール済みで、その後変更されていません：',
+    identicalInstallSkip: 'スキップ',
+    identicalInstallReinstall: '再インストール',
     sourceDeletionSkipped:
       '検出されたアドオン ルートは入力パスの親であり、ソースの削除はスキップされました: {path}',
     failedToAnalyze: 'アドオンの分析に失敗しました',
@@ -166,6 +171,8 @@ export default {
     unregisterContextMenu: 'コンテキストメニューの登録を解除する',
     contextMenuRegistered: 'コンテキストメニューが正常に登録されました',
     contextMenuUnregistered: 'コンテキスト メニューが正常に登録解除されました',
+    contextMenuFileTypes: '表示する対象',
+    contextMenuFolders: 'フォルダー',
     registryError: 'レジストリ操作に失敗しました',
     appearance: '外観',
     appearanceDesc: '外観と操作感をカスタマイズする',
@@ -329,6 +336,9 @@ export default {
     unknown: '不明',
     noConfigPatternsHint: '設定でバックアップ ルールを構成してください',
     liveryAircraftNotFound: '目標の航空機が見つかりません',
+    liveryTargetChoose: '複数の航空機に対応しています。インストール先:',
+    liveryTargetPlaceholder: '航空機を選択',
+    sceneryRootChoose: 'ベースメッシュのようです。インストール先:',
     targetLockedWarning: 'ターゲットが存在し、ロックされています',
     flyWithLuaRequired: 'まず FlyWithLua プラグインをインストールしてください',
     targetPathConflict: 'ターゲットパスの競合',
@@ -337,6 +347,7 @@ export default {
     targetPathConflictBadge: 'パスの競合',
     submitBugReport: 'レポート',
     bugReportSubmitted: 'バグレポートは正常に送信されました',
+    bugReportQueued: 'バグレポートを保存しました。オンラインに戻ったときに送信されます',
     bugReportOpened: 'ブラウザで開いたバグレポートページ',
     bugReportFailed: 'バグレポートの提出に失敗しました',
     bugReportPrivacyNotice:
@@ -529,6 +540,7 @@ export default {
       '次のシーナリー フォルダーを削除してもよろしいですか?この操作は元に戻すことができません。',
     deleteSuccess: '風景が削除されました',
     deleteFailed: '削除に失敗しました',
+    libraryDependentsWarning: '{count} 件の有効なパッケージがこのライブラリに依存しています',
     missingLibraries: '不足しているライブラリ',
     missingLibrariesTitle: '不足しているライブラリ',
     clickToViewMissingLibs: 'クリックして不足しているライブラリを表示します',
@@ -567,6 +579,7 @@ export default {
     categoryLibrary: '図書館',
     categoryOverlay: 'オーバーレイ',
     categoryAirportMesh: '空港メッシュ',
+    categoryOrthoTile: 'オルソタイル',
     categoryMesh: 'メッシュ',
     categoryOther: 'その他',
     categoryUnrecognized: '認識されない',
@@ -742,6 +755,10 @@ export default {
     filesToReplace: '交換する',
     filesToDelete: '削除',
     filesToSkip: 'スキップ',
+    protectedFiles: 'ローカルルールで保護',
+    skipReasonBlacklisted: 'ブラックリスト対象',
+    skipReasonBlacklistedPattern: "'{pattern}' により除外",
+    skipReasonNotWhitelisted: 'ローカルのホワイトリスト対象外',
     warnings: '警告',
     refreshPlan: 'リフレッシュプラン',
     startUpdate: 'アップデート',
@@ -1059,6 +1076,7 @@ export default {
     fieldContentPlaceholder: '問題やアイデアを詳しく説明する',
     submitAction: '送信する',
     submitSuccess: 'フィードバックは正常に送信されました',
+    submitQueued: 'フィードバックを保存しました。オンラインに戻ったときに送信されます',
     submitFailed: 'フィードバックの送信に失敗しました',
     discardTitle: 'フィードバックの下書きを破棄しますか?',
     discardMessage: '未送信のフィードバック コンテンツがあります。閉じてもよろしいですか?',
@@ -1125,6 +1143,7 @@ export default {
     low: 'お知らせ',
     noIssues: 'このログでは既知の問題は検出されませんでした。',
     lineNumbers: '行 {nums}',
+    relatedFolders: '該当フォルダー：',
     systemInfo: 'システム情報',
     xplaneVersion: 'X-Planeバージョン',
     gpuModel: 'GPUモデル',
@@ -1475,6 +1494,60 @@ export default {
     security_violation: 'セキュリティ違反が検出されました',
     timeout: '操作がタイムアウトしました',
     internal: '内部エラー',
+    messages: {
+      installation_failed: 'インストールに失敗しました: {0}',
+      task_cancelled: 'ユーザーによりキャンセルされました',
+      task_skipped: 'ユーザーによりスキップされました',
+      task_panicked: 'タスクが異常終了しました: {0}',
+      install_queue_closed: 'インストールキューが閉じられました',
+      verification_failed: '検証に失敗しました: {0}',
+      dependency_not_installed: '「{0}」がインストールされなかったためスキップしました',
+      navdata_downgrade_not_allowed: 'ナビデータのサイクル {0} はインストール済みのサイクル {1} より古いです。それでもインストールするにはダウングレードを許可してください。',
+      invalid_folder_name: '無効なフォルダー名: パストラバーサルは許可されていません',
+      invalid_path: '無効なパス: {0}',
+      scenery_folder_not_found: 'シーナリーフォルダーが見つかりません',
+      scenery_folder_not_found_named: 'シーナリーフォルダーが見つかりません: {0}',
+      scenery_source_folder_not_found: 'シーナリーのソースフォルダーが見つかりません',
+      scenery_entry_exists: '「{0}」という名前のシーナリーエントリは既に存在します',
+      scenery_has_dependents:
+        '{0} 個の有効なパッケージが {1} のみが提供するライブラリに依存しています',
+      preview_scenery_deletion_failed: 'シーナリー削除のプレビューに失敗しました: {0}',
+      check_scenery_dependents_failed: 'シーナリーの依存関係の確認に失敗しました: {0}',
+      delete_permission_denied: '削除時にアクセスが拒否されました: {0}',
+      delete_scenery_link_failed: 'シーナリーリンクの削除に失敗しました: {0} ({1}; {2})',
+      delete_scenery_file_failed: 'シーナリーファイルの削除に失敗しました: {0}',
+      delete_scenery_folder_failed: 'シーナリーフォルダーの削除に失敗しました: {0}',
+      scenery_classification_failed: '分類に失敗しました: {0}',
+      reset_sort_order_failed: '並び順のリセットに失敗しました: {0}',
+      reset_global_airports_position_failed: 'Global Airports の位置のリセットに失敗しました: {0}',
+      rebuild_index_failed: 'インデックスの再構築に失敗しました: {0}',
+      sync_scenery_packs_failed: 'シーナリーパックの同期に失敗しました: {0}',
+      get_scenery_manager_data_failed: 'シーナリーマネージャーのデータ取得に失敗しました: {0}',
+      global_airports_cannot_be_pinned: 'Global Airports は固定できません',
+      update_global_airports_state_failed: 'Global Airports の状態の更新に失敗しました: {0}',
+      update_global_airports_position_failed: 'Global Airports の位置の更新に失敗しました: {0}',
+      update_global_airports_category_failed: 'Global Airports のカテゴリの更新に失敗しました: {0}',
+      update_scenery_entry_failed: 'シーナリーエントリの更新に失敗しました: {0}',
+      move_global_airports_failed: 'Global Airports エントリの移動に失敗しました: {0}',
+      move_scenery_entry_failed: 'シーナリーエントリの移動に失敗しました: {0}',
+      update_index_failed: 'インデックスの更新に失敗しました: {0}',
+      apply_scenery_changes_failed: 'シーナリーの変更の適用に失敗しました: {0}',
+      addon_update_failed: 'アップデートに失敗しました: {0}',
+      addon_update_rollback_failed:
+        'アップデートに失敗しました: {0}。ロールバックも失敗しました: {1}',
+      addon_target_not_found: '対象パスが存在しません: {0}',
+      legacy_updater_no_credentials:
+        'このアドオンは旧形式のアップデーターメタデータを使用しており、アカウント認証情報に対応していません',
+      no_updater_credentials: 'このアドオンの既存のアップデーター認証情報が見つかりません',
+      xupdater_profile_not_found: '「{0}」に x-updater プロファイルが見つかりません',
+      xupdater_profile_unsupported:
+        '「{0}」は Skunkcrafts アップデーターのメタデータがない x-updater プロファイルを使用しているため、ここでは更新できません。開発元のアップデーターで更新してください。',
+      xupdater_missing_login: 'x-updater プロファイルにログイン名/ユーザー名がありません',
+      xupdater_missing_license_key: 'x-updater プロファイルにライセンスキーがありません',
+      xupdater_auth_failed: 'x-updater の認証に失敗しました: HTTP {0} ({1})',
+      xupdater_no_products_for_account: 'x-updater はこのアカウントの製品を返しませんでした',
+      xupdater_no_matching_product: 'x-updater は「{0}」に一致する製品を返しませんでした',
+    },
   },
   commandPalette: {
     title: 'コマンドパレット',
@@ -49,6 +49,11 @@ export default {
     copyingToXplane: '파일을 X-Plane 디렉터리로 복사하는 중입니다',
     installationCompleted: '설치가 성공적으로 완료되었습니다!',
     noValidAddons: '유효한 애드온을 찾지 못했습니다',
+    skippedIdentical: '이미 설치됨',
+    identicalInstallTitle: '이미 설치됨',
+    identicalInstallMessage: '다음 애드온은 같은 압축 파일에서 이미 설치되었고 이후 변경되지 않았습니다:',
+    identicalInstallSkip: '건너뛰기',
+    identicalInstallReinstall: '다시 설치',
     sourceDeletionSkipped:
       '애드온 루트가 입력 경로의 상위 폴더이므로 원본 삭제를 건너뜁니다: {path}',
     failedToAnalyze: '애드온 분석에 실패했습니다',
@@ -165,6 +170,8 @@ export default {
     unregisterContextMenu: '마우스 오른쪽 클릭 메뉴 해제',
     contextMenuRegistered: '마우스 오른쪽 클릭 메뉴가 성공적으로 등록되었습니다',
     contextMenuUnregistered: '마우스 오른쪽 클릭 메뉴가 성공적으로 해제되었습니다',
+    contextMenuFileTypes: '표시 대상',
+    contextMenuFolders: '폴더',
     registryError: '레지스트리 작업에 실패했습니다',
     appearance: '외관',
     appearanceDesc: '앱의 외관과 느낌을 사용자 지정합니다',
@@ -327,6 +334,9 @@ export default {
     unknown: '알 수 없음',
     noConfigPatternsHint: '설정에서 백업 규칙을 구성하세요',
     liveryAircraftNotFound: '대상 항공기를 찾을 수 없습니다',
+    liveryTargetChoose: '여러 항공기에 해당합니다. 설치 위치:',
+    liveryTargetPlaceholder: '항공기 선택',
+    sceneryRootChoose: '기본 메시로 보입니다. 설치 위치:',
     targetLockedWarning: '대상이 이미 존재하며 잠겨 있습니다',
     flyWithLuaRequired: '먼저 FlyWithLua 플러그인을 설치하세요',
     // 대상 경로 충돌
@@ -337,6 +347,7 @@ export default {
     // 버그 보고
     submitBugReport: '보고',
     bugReportSubmitted: '버그 보고서가 성공적으로 제출되었습니다',
+    bugReportQueued: '버그 보고서가 저장되었습니다. 다시 온라인 상태가 되면 전송됩니다',
     bugReportOpened: '브라우저에서 버그 보고서 페이지가 열렸습니다',
     bugReportFailed: '버그 보고서 제출에 실패했습니다',
     bugReportPrivacyNotice:
@@ -534,6 +545,7 @@ export default {
     deleteConfirmMessage: '다음 시너리 폴더를 정말 삭제하시겠습니까? 이 작업은 되돌릴 수 없습니다.',
     deleteSuccess: '시너리가 삭제되었습니다',
     deleteFailed: '삭제에 실패했습니다',
+    libraryDependentsWarning: '활성화된 패키지 {count}개가 이 라이브러리에 의존합니다',
     missingLibraries: '누락된 라이브러리',
     missingLibrariesTitle: '누락된 라이브러리',
     clickToViewMissingLibs: '누락된 라이브러리를 보려면 클릭하세요',
@@ -572,6 +584,7 @@ export default {
     categoryLibrary: '라이브러리',
     categoryOverlay: '오버레이',
     categoryAirportMesh: '공항 메시',
+    categoryOrthoTile: '정사영상 타일',
     categoryMesh: '메시',
     categoryOther: '기타',
     categoryUnrecognized: '인식할 수 없음',
@@ -746,6 +759,10 @@ export default {
     filesToReplace: '교체',
     filesToDelete: '삭제',
     filesToSkip: '건너뛰기',
+    protectedFiles: '로컬 규칙으로 보호됨',
+    skipReasonBlacklisted: '블랙리스트 대상',
+    skipReasonBlacklistedPattern: "'{pattern}' 규칙으로 제외됨",
+    skipReasonNotWhitelisted: '로컬 화이트리스트에 없음',
     warnings: '경고',
     refreshPlan: '계획 새로 고침',
     startUpdate: '업데이트',
@@ -1058,6 +1075,7 @@ export default {
     fieldContentPlaceholder: '문제나 아이디어를 자세히 설명하세요',
     submitAction: '제출',
     submitSuccess: '피드백이 성공적으로 제출되었습니다',
+    submitQueued: '피드백이 저장되었습니다. 다시 온라인 상태가 되면 전송됩니다',
     submitFailed: '피드백 제출에 실패했습니다',
     discardTitle: '피드백 초안을 버리시겠습니까?',
     discardMessage: '아직 보내지 않은 피드백 내용이 있습니다. 정말 닫으시겠습니까?',
@@ -1124,6 +1142,7 @@ export default {
     low: '알림',
     noIssues: '이 로그에서 알려진 문제가 감지되지 않았습니다.',
     lineNumbers: '{nums}번 줄',
+    relatedFolders: '관련 폴더:',
     systemInfo: '시스템 정보',
     xplaneVersion: 'X-Plane 버전',
     gpuModel: 'GPU',
@@ -1460,6 +1479,58 @@ export default {
     security_violation: '보안 위반이 감지되었습니다',
     timeout: '작업 시간이 초과되었습니다',
     internal: '내부 오류',
+    messages: {
+      installation_failed: '설치 실패: {0}',
+      task_cancelled: '사용자가 취소함',
+      task_skipped: '사용자가 건너뜀',
+      task_panicked: '작업이 비정상 종료됨: {0}',
+      install_queue_closed: '설치 대기열이 닫혔습니다',
+      verification_failed: '검증 실패: {0}',
+      dependency_not_installed: "'{0}'이(가) 설치되지 않아 건너뜀",
+      navdata_downgrade_not_allowed: '내비데이터 주기 {0}이(가) 설치된 주기 {1}보다 오래되었습니다. 그래도 설치하려면 다운그레이드를 허용하세요.',
+      invalid_folder_name: '잘못된 폴더 이름: 경로 탐색은 허용되지 않습니다',
+      invalid_path: '잘못된 경로: {0}',
+      scenery_folder_not_found: '시너리 폴더를 찾을 수 없습니다',
+      scenery_folder_not_found_named: '시너리 폴더를 찾을 수 없습니다: {0}',
+      scenery_source_folder_not_found: '시너리 원본 폴더를 찾을 수 없습니다',
+      scenery_entry_exists: "'{0}' 이름의 시너리 항목이 이미 있습니다",
+      scenery_has_dependents: '활성화된 패키지 {0}개가 {1}만 제공하는 라이브러리에 의존합니다',
+      preview_scenery_deletion_failed: '시너리 삭제 미리보기 실패: {0}',
+      check_scenery_dependents_failed: '시너리 의존 항목 확인 실패: {0}',
+      delete_permission_denied: '삭제 중 권한 거부됨: {0}',
+      delete_scenery_link_failed: '시너리 링크 삭제 실패: {0} ({1}; {2})',
+      delete_scenery_file_failed: '시너리 파일 삭제 실패: {0}',
+      delete_scenery_folder_failed: '시너리 폴더 삭제 실패: {0}',
+      scenery_classification_failed: '분류 실패: {0}',
+      reset_sort_order_failed: '정렬 순서 초기화 실패: {0}',
+      reset_global_airports_position_failed: 'Global Airports 위치 초기화 실패: {0}',
+      rebuild_index_failed: '인덱스 재구축 실패: {0}',
+      sync_scenery_packs_failed: '시너리 팩 동기화 실패: {0}',
+      get_scenery_manager_data_failed: '시너리 관리자 데이터 가져오기 실패: {0}',
+      global_airports_cannot_be_pinned: 'Global Airports는 고정할 수 없습니다',
+      update_global_airports_state_failed: 'Global Airports 상태 업데이트 실패: {0}',
+      update_global_airports_position_failed: 'Global Airports 위치 업데이트 실패: {0}',
+      update_global_airports_category_failed: 'Global Airports 카테고리 업데이트 실패: {0}',
+      update_scenery_entry_failed: '시너리 항목 업데이트 실패: {0}',
+      move_global_airports_failed: 'Global Airports 항목 이동 실패: {0}',
+      move_scenery_entry_failed: '시너리 항목 이동 실패: {0}',
+      update_index_failed: '인덱스 업데이트 실패: {0}',
+      apply_scenery_changes_failed: '시너리 변경 사항 적용 실패: {0}',
+      addon_update_failed: '업데이트 실패: {0}',
+      addon_update_rollback_failed: '업데이트 실패: {0}. 롤백도 실패: {1}',
+      addon_target_not_found: '대상 경로가 존재하지 않습니다: {0}',
+      legacy_updater_no_credentials:
+        '이 애드온은 레거시 업데이터 메타데이터를 사용하며 계정 자격 증명을 지원하지 않습니다',
+      no_updater_credentials: '이 애드온의 기존 업데이터 자격 증명을 찾을 수 없습니다',
+      xupdater_profile_not_found: "'{0}'에서 x-updater 프로필을 찾을 수 없습니다",
+      xupdater_profile_unsupported:
+        "'{0}'은(는) Skunkcrafts 업데이터 메타데이터가 없는 x-updater 프로필을 사용하므로 여기서 업데이트할 수 없습니다. 개발사의 업데이터로 업데이트하세요.",
+      xupdater_missing_login: 'x-updater 프로필에 로그인/사용자 이름이 없습니다',
+      xupdater_missing_license_key: 'x-updater 프로필에 라이선스 키가 없습니다',
+      xupdater_auth_failed: 'x-updater 인증 실패: HTTP {0} ({1})',
+      xupdater_no_products_for_account: 'x-updater가 이 계정에 대한 제품을 반환하지 않았습니다',
+      xupdater_no_matching_product: "x-updater가 '{0}'과(와) 일치하는 제품을 반환하지 않았습니다",
+    },
   },
   commandPalette: {
     title: '명령 팔레트',
@@ -49,6 +49,12 @@ export default {
     copyingToXplane: 'Copiando arquivos para o diretório do X-Plane',
     installationCompleted: 'Instalação concluída com sucesso!',
     noValidAddons: 'Nenhum addon válido foi detectado',
+    skippedIdentical: 'Já instalado',
+    identicalInstallTitle: 'Já instalado',
+    identicalInstallMessage:
+      'Estes complementos já foram instalados a partir do mesmo arquivo e não mudaram desde então:',
+    identicalInstallSkip: 'Ignorar',
+    identicalInstallReinstall: 'Reinstalar',
     sourceDeletionSkipped:
       'A raiz do addon detectada é um diretório pai do caminho de entrada, exclusão da origem ignorada: {path}',
     failedToAnalyze: 'Falha ao analisar os addons',
@@ -167,6 +173,8 @@ export default {
     unregisterContextMenu: 'Remover menu de contexto',
     contextMenuRegistered: 'Menu de contexto registrado com sucesso',
     contextMenuUnregistered: 'Menu de contexto removido com sucesso',
+    contextMenuFileTypes: 'Mostrar para',
+    contextMenuFolders: 'Pastas',
     registryError: 'Falha na operação do registro',
     appearance: 'Aparência',
     appearanceDesc: 'Personalize a aparência do aplicativo',
@@ -342,6 +350,9 @@ export default {
     unknown: 'Desconhecido',
     noConfigPatternsHint: 'Configure as regras de backup nas Configurações',
     liveryAircraftNotFound: 'Aeronave de destino não encontrada',
+    liveryTargetChoose: 'Serve para várias aeronaves, instalar em:',
+    liveryTargetPlaceholder: 'Selecionar aeronave',
+    sceneryRootChoose: 'Parece uma malha base, instalar em:',
     targetLockedWarning: 'O destino existe e está bloqueado',
     flyWithLuaRequired: 'Instale primeiro o plugin FlyWithLua',
     // Target path conflicts
@@ -352,6 +363,7 @@ export default {
     // Bug report
     submitBugReport: 'Reportar',
     bugReportSubmitted: 'Relatório de erro enviado com sucesso',
+    bugReportQueued: 'Relatório de erro salvo; será enviado quando você estiver online novamente',
     bugReportOpened: 'Página de relatório de erro aberta no navegador',
     bugReportFailed: 'Falha ao enviar o relatório de erro',
     bugReportPrivacyNotice:
@@ -555,6 +567,7 @@ export default {
       'Tem certeza de que deseja excluir a seguinte pasta de cenário? Esta ação não pode ser desfeita.',
     deleteSuccess: 'Cenário excluído',
     deleteFailed: 'Falha ao excluir',
+    libraryDependentsWarning: '{count} pacote(s) ativado(s) dependem desta biblioteca',
     missingLibraries: 'Bibliotecas ausentes',
     missingLibrariesTitle: 'Bibliotecas ausentes',
     clickToViewMissingLibs: 'Clique para ver as bibliotecas ausentes',
@@ -593,6 +606,7 @@ export default {
     categoryLibrary: 'Biblioteca',
     categoryOverlay: 'Sobreposição',
     categoryAirportMesh: 'Malha de aeroporto',
+    categoryOrthoTile: 'Blocos ortofoto',
     categoryMesh: 'Malha',
     categoryOther: 'Outro',
     categoryUnrecognized: 'Não reconhecido',
@@ -770,6 +784,10 @@ export default {
     filesToReplace: 'Substituir',
     filesToDelete: 'Excluir',
     filesToSkip: 'Pular',
+    protectedFiles: 'Protegidos por regras locais',
+    skipReasonBlacklisted: 'Na lista negra',
+    skipReasonBlacklistedPattern: "Excluído por '{pattern}'",
+    skipReasonNotWhitelisted: 'Fora da lista branca local',
     warnings: 'Avisos',
     refreshPlan: 'Atualizar plano',
     startUpdate: 'Atualizar',
@@ -1086,6 +1104,7 @@ export default {
     fieldContentPlaceholder: 'Descreva o problema ou ideia em detalhes',
     submitAction: 'Enviar',
     submitSuccess: 'Feedback enviado com sucesso',
+    submitQueued: 'Feedback salvo; será enviado quando você estiver online novamente',
     submitFailed: 'Falha ao enviar o feedback',
     discardTitle: 'Descartar rascunho do feedback?',
     discardMessage: 'Você tem conteúdo de feedback não enviado. Tem certeza de que deseja fechar?',
@@ -1152,6 +1171,7 @@ export default {
     low: 'Observação',
     noIssues: 'Nenhum problema conhecido detectado neste log.',
     lineNumbers: 'Linha {nums}',
+    relatedFolders: 'Pastas afetadas:',
     systemInfo: 'Informações do sistema',
     xplaneVersion: 'Versão do X-Plane',
     gpuModel: 'GPU',
@@ -1504,6 +1524,62 @@ export default {
     security_violation: 'Violação de segurança detectada',
     timeout: 'A operação expirou',
     internal: 'Erro interno',
+    messages: {
+      installation_failed: 'Falha na instalação: {0}',
+      task_cancelled: 'Cancelado pelo usuário',
+      task_skipped: 'Ignorado pelo usuário',
+      task_panicked: 'A tarefa falhou inesperadamente: {0}',
+      install_queue_closed: 'A fila de instalação foi fechada',
+      verification_failed: 'Falha na verificação: {0}',
+      dependency_not_installed: "Ignorado porque '{0}' não foi instalado",
+      navdata_downgrade_not_allowed: 'O ciclo de navdata {0} é mais antigo que o ciclo instalado {1}. Permita o downgrade para instalá-lo mesmo assim.',
+      invalid_folder_name: 'Nome de pasta inválido: travessia de caminho não permitida',
+      invalid_path: 'Caminho inválido: {0}',
+      scenery_folder_not_found: 'Pasta de cenário não encontrada',
+      scenery_folder_not_found_named: 'Pasta de cenário não encontrada: {0}',
+      scenery_source_folder_not_found: 'Pasta de origem do cenário não encontrada',
+      scenery_entry_exists: "Já existe uma entrada de cenário chamada '{0}'",
+      scenery_has_dependents:
+        '{0} pacotes ativados dependem de bibliotecas fornecidas apenas por {1}',
+      preview_scenery_deletion_failed: 'Falha ao pré-visualizar a exclusão do cenário: {0}',
+      check_scenery_dependents_failed: 'Falha ao verificar os dependentes do cenário: {0}',
+      delete_permission_denied: 'Permissão negada ao excluir: {0}',
+      delete_scenery_link_failed: 'Falha ao excluir o link do cenário: {0} ({1}; {2})',
+      delete_scenery_file_failed: 'Falha ao excluir o arquivo do cenário: {0}',
+      delete_scenery_folder_failed: 'Falha ao excluir a pasta do cenário: {0}',
+      scenery_classification_failed: 'Falha na classificação: {0}',
+      reset_sort_order_failed: 'Falha ao redefinir a ordem: {0}',
+      reset_global_airports_position_failed: 'Falha ao redefinir a posição do Global Airports: {0}',
+      rebuild_index_failed: 'Falha ao reconstruir o índice: {0}',
+      sync_scenery_packs_failed: 'Falha ao sincronizar os pacotes de cenário: {0}',
+      get_scenery_manager_data_failed: 'Falha ao obter os dados do gerenciador de cenários: {0}',
+      global_airports_cannot_be_pinned: 'O Global Airports não pode ser fixado',
+      update_global_airports_state_failed: 'Falha ao atualizar o estado do Global Airports: {0}',
+      update_global_airports_position_failed:
+        'Falha ao atualizar a posição do Global Airports: {0}',
+      update_global_airports_category_failed:
+        'Falha ao atualizar a categoria do Global Airports: {0}',
+      update_scenery_entry_failed: 'Falha ao atualizar a entrada de cenário: {0}',
+      move_global_airports_failed: 'Falha ao mover a entrada do Global Airports: {0}',
+      move_scenery_entry_failed: 'Falha ao mover a entrada de cenário: {0}',
+      update_index_failed: 'Falha ao atualizar o índice: {0}',
+      apply_scenery_changes_failed: 'Falha ao aplicar as alterações de cenário: {0}',
+      addon_update_failed: 'Falha na atualização: {0}',
+      addon_update_rollback_failed: 'Falha na atualização: {0}. A reversão também falhou: {1}',
+      addon_target_not_found: 'O caminho de destino não existe: {0}',
+      legacy_updater_no_credentials:
+        'Este complemento usa metadados de atualizador legados e não suporta credenciais de conta',
+      no_updater_credentials:
+        'Nenhuma credencial de atualizador existente encontrada para este complemento',
+      xupdater_profile_not_found: "Nenhum perfil do x-updater foi encontrado em '{0}'",
+      xupdater_profile_unsupported:
+        "'{0}' usa um perfil do x-updater sem metadados do atualizador Skunkcrafts e não pode ser atualizado aqui. Atualize-o com o atualizador do desenvolvedor.",
+      xupdater_missing_login: 'O perfil do x-updater não tem login/nome de usuário',
+      xupdater_missing_license_key: 'O perfil do x-updater não tem chave de licença',
+      xupdater_auth_failed: 'Falha na autenticação do x-updater: HTTP {0} ({1})',
+      xupdater_no_products_for_account: 'O x-updater não retornou produtos para esta conta',
+      xupdater_no_matching_product: "O x-updater não retornou um produto correspondente a '{0}'",
+    },
   },
   commandPalette: {
     title: 'Paleta de comandos',
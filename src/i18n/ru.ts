@@ -49,6 +49,12 @@ export default {
     copyingToXplane: 'Копирование файлов в каталог X-Plane',
     installationCompleted: 'Установка успешно завершена!',
     noValidAddons: 'Подходящие аддоны не найдены',
+    skippedIdentical: 'Уже установлено',
+    identicalInstallTitle: 'Уже установлено',
+    identicalInstallMessage:
+      'Эти дополнения уже установлены из того же архива и с тех пор не изменялись:',
+    identicalInstallSkip: 'Пропустить',
+    identicalInstallReinstall: 'Переустановить',
     sourceDeletionSkipped:
       'Корень аддона является родительским для входного пути, поэтому удаление источника пропущено: {path}',
     failedToAnalyze: 'Не удалось проанализировать аддоны',
@@ -166,6 +172,8 @@ export default {
     unregisterContextMenu: 'Удалить контекстное меню',
     contextMenuRegistered: 'Контекстное меню успешно зарегистрировано',
     contextMenuUnregistered: 'Контекстное меню успешно удалено',
+    contextMenuFileTypes: 'Показывать для',
+    contextMenuFolders: 'Папки',
     registryError: 'Операция с реестром не удалась',
     appearance: 'Внешний вид',
     appearanceDesc: 'Настройте внешний вид приложения',
@@ -340,6 +348,9 @@ export default {
     unknown: 'Неизвестно',
     noConfigPatternsHint: 'Настройте правила резервного копирования в разделе настроек',
     liveryAircraftNotFound: 'Целевой самолёт не найден',
+    liveryTargetChoose: 'Подходит к нескольким самолётам, установить в:',
+    liveryTargetPlaceholder: 'Выберите самолёт',
+    sceneryRootChoose: 'Похоже на базовую сетку, установить в:',
     targetLockedWarning: 'Целевой объект существует и заблокирован',
     flyWithLuaRequired: 'Сначала установите плагин FlyWithLua',
     // Конфликты целевых путей
@@ -350,6 +361,7 @@ export default {
     // Отчёт об ошибке
     submitBugReport: 'Сообщить',
     bugReportSubmitted: 'Отчёт об ошибке успешно отправлен',
+    bugReportQueued: 'Отчёт об ошибке сохранён и будет отправлен, когда появится подключение',
     bugReportOpened: 'Страница отчёта об ошибке открыта в браузере',
     bugReportFailed: 'Не удалось отправить отчёт об ошибке',
     bugReportPrivacyNotice:
@@ -551,6 +563,7 @@ export default {
       'Вы действительно хотите удалить следующую папку сценерии? Это действие нельзя отменить.',
     deleteSuccess: 'Сценерия удалена',
     deleteFailed: 'Не удалось удалить',
+    libraryDependentsWarning: 'От этой библиотеки зависят включённые пакеты: {count}',
     missingLibraries: 'Отсутствующие библиотеки',
     missingLibrariesTitle: 'Отсутствующие библиотеки',
     clickToViewMissingLibs: 'Нажмите, чтобы просмотреть отсутствующие библиотеки',
@@ -589,6 +602,7 @@ export default {
     categoryLibrary: 'Библиотека',
     categoryOverlay: 'Наложение',
     categoryAirportMesh: 'Меш аэропорта',
+    categoryOrthoTile: 'Ортофото тайлы',
     categoryMesh: 'Меш',
     categoryOther: 'Другое',
     categoryUnrecognized: 'Не распознано',
@@ -765,6 +779,10 @@ export default {
     filesToReplace: 'Заменить',
     filesToDelete: 'Удалить',
     filesToSkip: 'Пропустить',
+    protectedFiles: 'Защищено локальными правилами',
+    skipReasonBlacklisted: 'В чёрном списке',
+    skipReasonBlacklistedPattern: "Исключено правилом '{pattern}'",
+    skipReasonNotWhitelisted: 'Вне локального белого списка',
     warnings: 'Предупреждения',
     refreshPlan: 'Обновить план',
     startUpdate: 'Обновить',
@@ -1080,6 +1098,7 @@ export default {
     fieldContentPlaceholder: 'Подробно опишите проблему или идею',
     submitAction: 'Отправить',
     submitSuccess: 'Отзыв успешно отправлен',
+    submitQueued: 'Отзыв сохранён и будет отправлен, когда появится подключение',
     submitFailed: 'Не удалось отправить отзыв',
     discardTitle: 'Отменить черновик отзыва?',
     discardMessage: 'У вас есть неотправленный отзыв. Закрыть?',
@@ -1145,6 +1164,7 @@ export default {
     low: 'Замечание',
     noIssues: 'В этом логе известных проблем не обнаружено.',
     lineNumbers: 'Строка {nums}',
+    relatedFolders: 'Затронутые папки:',
     systemInfo: 'Информация о системе',
     xplaneVersion: 'Версия X-Plane',
     gpuModel: 'GPU',
@@ -1497,6 +1517,60 @@ export default {
     security_violation: 'Обнаружено нарушение безопасности',
     timeout: 'Время операции истекло',
     internal: 'Внутренняя ошибка',
+    messages: {
+      installation_failed: 'Ошибка установки: {0}',
+      task_cancelled: 'Отменено пользователем',
+      task_skipped: 'Пропущено пользователем',
+      task_panicked: 'Задача аварийно завершилась: {0}',
+      install_queue_closed: 'Очередь установки закрыта',
+      verification_failed: 'Ошибка проверки: {0}',
+      dependency_not_installed: 'Пропущено, так как «{0}» не был установлен',
+      navdata_downgrade_not_allowed: 'Цикл навданных {0} старше установленного цикла {1}. Разрешите понижение версии, чтобы всё равно установить его.',
+      invalid_folder_name: 'Недопустимое имя папки: выход за пределы пути запрещён',
+      invalid_path: 'Недопустимый путь: {0}',
+      scenery_folder_not_found: 'Папка сценария не найдена',
+      scenery_folder_not_found_named: 'Папка сценария не найдена: {0}',
+      scenery_source_folder_not_found: 'Исходная папка сценария не найдена',
+      scenery_entry_exists: 'Запись сценария с именем «{0}» уже существует',
+      scenery_has_dependents:
+        '{0} включённых пакетов зависят от библиотек, которые есть только в {1}',
+      preview_scenery_deletion_failed: 'Не удалось показать последствия удаления сценария: {0}',
+      check_scenery_dependents_failed: 'Не удалось проверить зависимые пакеты сценария: {0}',
+      delete_permission_denied: 'Отказано в доступе при удалении: {0}',
+      delete_scenery_link_failed: 'Не удалось удалить ссылку на сценарий: {0} ({1}; {2})',
+      delete_scenery_file_failed: 'Не удалось удалить файл сценария: {0}',
+      delete_scenery_folder_failed: 'Не удалось удалить папку сценария: {0}',
+      scenery_classification_failed: 'Ошибка классификации: {0}',
+      reset_sort_order_failed: 'Не удалось сбросить порядок: {0}',
+      reset_global_airports_position_failed: 'Не удалось сбросить позицию Global Airports: {0}',
+      rebuild_index_failed: 'Не удалось перестроить индекс: {0}',
+      sync_scenery_packs_failed: 'Не удалось синхронизировать пакеты сценариев: {0}',
+      get_scenery_manager_data_failed: 'Не удалось получить данные менеджера сценариев: {0}',
+      global_airports_cannot_be_pinned: 'Global Airports нельзя закрепить',
+      update_global_airports_state_failed: 'Не удалось обновить состояние Global Airports: {0}',
+      update_global_airports_position_failed: 'Не удалось обновить позицию Global Airports: {0}',
+      update_global_airports_category_failed: 'Не удалось обновить категорию Global Airports: {0}',
+      update_scenery_entry_failed: 'Не удалось обновить запись сценария: {0}',
+      move_global_airports_failed: 'Не удалось переместить запись Global Airports: {0}',
+      move_scenery_entry_failed: 'Не удалось переместить запись сценария: {0}',
+      update_index_failed: 'Не удалось обновить индекс: {0}',
+      apply_scenery_changes_failed: 'Не удалось применить изменения сценариев: {0}',
+      addon_update_failed: 'Ошибка обновления: {0}',
+      addon_update_rollback_failed: 'Ошибка обновления: {0}. Откат также не удался: {1}',
+      addon_target_not_found: 'Целевой путь не существует: {0}',
+      legacy_updater_no_credentials:
+        'Это дополнение использует устаревшие метаданные обновления и не поддерживает учётные данные',
+      no_updater_credentials:
+        'Сохранённые учётные данные обновления для этого дополнения не найдены',
+      xupdater_profile_not_found: 'Профиль x-updater не найден в «{0}»',
+      xupdater_profile_unsupported:
+        '«{0}» использует профиль x-updater без метаданных обновления Skunkcrafts, поэтому его нельзя обновить здесь. Обновите его через программу обновления разработчика.',
+      xupdater_missing_login: 'В профиле x-updater нет логина/имени пользователя',
+      xupdater_missing_license_key: 'В профиле x-updater нет лицензионного ключа',
+      xupdater_auth_failed: 'Ошибка аутентификации x-updater: HTTP {0} ({1})',
+      xupdater_no_products_for_account: 'x-updater не вернул продуктов для этой учётной записи',
+      xupdater_no_matching_product: 'x-updater не вернул продукт, соответствующий «{0}»',
+    },
   },
   commandPalette: {
     title: 'Палитра команд',
@@ -49,6 +49,11 @@ export default {
     copyingToXplane: '正在复制文件到X-Plane目录',
     installationCompleted: '安装完成！',
     noValidAddons: '未检测到有效的插件',
+    skippedIdentical: '已安装',
+    identicalInstallTitle: '已安装',
+    identicalInstallMessage: '以下插件已从同一压缩包安装，且此后未被修改：',
+    identicalInstallSkip: '跳过',
+    identicalInstallReinstall: '重新安装',
     sourceDeletionSkipped: '检测到插件主目录是输入路径的父目录，已跳过删除源文件：{path}',
     failedToAnalyze: '分析插件失败',
     installationFailed: '安装失败',
@@ -161,6 +166,8 @@ export default {
     unregisterContextMenu: '取消注册',
     contextMenuRegistered: '右键菜单注册成功',
     contextMenuUnregistered: '右键菜单取消注册成功',
+    contextMenuFileTypes: '显示于',
+    contextMenuFolders: '文件夹',
     registryError: '注册表操作失败',
     appearance: '外观',
     appearanceDesc: '自定义界面外观',
@@ -310,6 +317,9 @@ export default {
     unknown: '未知',
     noConfigPatternsHint: '需要在设置中配置备份规则',
     liveryAircraftNotFound: '未找到目标机模',
+    liveryTargetChoose: '匹配多个已安装机模，安装到：',
+    liveryTargetPlaceholder: '选择机模',
+    sceneryRootChoose: '看起来是基础地形网格，安装到：',
     targetLockedWarning: '目标已存在且被锁定',
     flyWithLuaRequired: '请先安装 FlyWithLua 插件',
     targetPathConflict: '目标路径冲突',
@@ -317,6 +327,7 @@ export default {
     targetPathConflictBadge: '路径冲突',
     submitBugReport: '上报错误',
     bugReportSubmitted: '错误报告已成功提交',
+    bugReportQueued: '错误报告已保存，将在恢复联网后发送',
     bugReportOpened: '已在浏览器中打开错误报告页面',
     bugReportFailed: '提交错误报告失败',
     bugReportPrivacyNotice:
@@ -500,6 +511,7 @@ export default {
     deleteConfirmMessage: '确定要删除以下地景文件夹吗？此操作不可撤销。',
     deleteSuccess: '地景已删除',
     deleteFailed: '删除失败',
+    libraryDependentsWarning: '有 {count} 个已启用的包依赖此库',
     missingLibraries: '缺少库',
     missingLibrariesTitle: '缺少的库',
     clickToViewMissingLibs: '点击查看缺少的库',
@@ -536,6 +548,7 @@ export default {
     categoryLibrary: '库',
     categoryOverlay: '叠加层',
     categoryAirportMesh: '机场网格',
+    categoryOrthoTile: '正射影像瓦片',
     categoryMesh: '网格',
     categoryOther: '其他',
     categoryUnrecognized: '未识别',
@@ -702,6 +715,10 @@ export default {
     filesToReplace: '替换',
     filesToDelete: '删除',
     filesToSkip: '跳过',
+    protectedFiles: '受本地规则保护',
+    skipReasonBlacklisted: '已列入黑名单',
+    skipReasonBlacklistedPattern: "被黑名单规则 '{pattern}' 排除",
+    skipReasonNotWhitelisted: '不在本地白名单内',
     warnings: '警告',
     refreshPlan: '刷新计划',
     startUpdate: '更新',
@@ -1013,6 +1030,7 @@ export default {
     fieldContentPlaceholder: '请详细描述问题或建议',
     submitAction: '提交',
     submitSuccess: '反馈提交成功',
+    submitQueued: '反馈已保存，将在恢复联网后发送',
     submitFailed: '反馈提交失败',
     discardTitle: '放弃反馈草稿？',
     discardMessage: '你有未提交的反馈内容，确定要关闭吗？',
@@ -1078,6 +1096,7 @@ export default {
     low: '轻微',
     noIssues: '日志中未检测到已知问题。',
     lineNumbers: '第 {nums} 行',
+    relatedFolders: '相关文件夹：',
     systemInfo: '系统信息',
     xplaneVersion: 'X-Plane 版本',
     gpuModel: '显卡型号',
@@ -1403,6 +1422,57 @@ export default {
     security_violation: '检测到安全违规',
     timeout: '操作超时',
     internal: '内部错误',
+    messages: {
+      installation_failed: '安装失败：{0}',
+      task_cancelled: '已被用户取消',
+      task_skipped: '已被用户跳过',
+      task_panicked: '任务异常终止：{0}',
+      install_queue_closed: '安装队列已关闭',
+      verification_failed: '校验失败：{0}',
+      dependency_not_installed: '已跳过，因为“{0}”未安装',
+      navdata_downgrade_not_allowed: '导航数据周期 {0} 早于已安装的周期 {1}。如仍要安装，请允许降级。',
+      invalid_folder_name: '文件夹名称无效：不允许路径穿越',
+      invalid_path: '路径无效：{0}',
+      scenery_folder_not_found: '未找到地景文件夹',
+      scenery_folder_not_found_named: '未找到地景文件夹：{0}',
+      scenery_source_folder_not_found: '未找到地景源文件夹',
+      scenery_entry_exists: '已存在名为“{0}”的地景条目',
+      scenery_has_dependents: '{0} 个已启用的地景包依赖仅由 {1} 提供的库',
+      preview_scenery_deletion_failed: '预览地景删除失败：{0}',
+      check_scenery_dependents_failed: '检查地景依赖失败：{0}',
+      delete_permission_denied: '删除时权限不足：{0}',
+      delete_scenery_link_failed: '删除地景链接失败：{0}（{1}；{2}）',
+      delete_scenery_file_failed: '删除地景文件失败：{0}',
+      delete_scenery_folder_failed: '删除地景文件夹失败：{0}',
+      scenery_classification_failed: '分类失败：{0}',
+      reset_sort_order_failed: '重置排序失败：{0}',
+      reset_global_airports_position_failed: '重置 Global Airports 位置失败：{0}',
+      rebuild_index_failed: '重建索引失败：{0}',
+      sync_scenery_packs_failed: '同步地景包失败：{0}',
+      get_scenery_manager_data_failed: '获取地景管理数据失败：{0}',
+      global_airports_cannot_be_pinned: 'Global Airports 不能被固定',
+      update_global_airports_state_failed: '更新 Global Airports 状态失败：{0}',
+      update_global_airports_position_failed: '更新 Global Airports 位置失败：{0}',
+      update_global_airports_category_failed: '更新 Global Airports 分类失败：{0}',
+      update_scenery_entry_failed: '更新地景条目失败：{0}',
+      move_global_airports_failed: '移动 Global Airports 条目失败：{0}',
+      move_scenery_entry_failed: '移动地景条目失败：{0}',
+      update_index_failed: '更新索引失败：{0}',
+      apply_scenery_changes_failed: '应用地景更改失败：{0}',
+      addon_update_failed: '更新失败：{0}',
+      addon_update_rollback_failed: '更新失败：{0}。回滚也失败：{1}',
+      addon_target_not_found: '目标路径不存在：{0}',
+      legacy_updater_no_credentials: '此插件使用旧版更新器元数据，不支持账户凭据',
+      no_updater_credentials: '未找到此插件已有的更新器凭据',
+      xupdater_profile_not_found: '在“{0}”中未找到 x-updater 配置',
+      xupdater_profile_unsupported:
+        '“{0}”使用的是没有 Skunkcrafts 更新元数据的 x-updater 配置，无法在此更新。请改用开发者提供的更新程序。',
+      xupdater_missing_login: 'x-updater 配置缺少登录名/用户名',
+      xupdater_missing_license_key: 'x-updater 配置缺少许可证密钥',
+      xupdater_auth_failed: 'x-updater 认证失败：HTTP {0}（{1}）',
+      xupdater_no_products_for_account: 'x-updater 未返回此账户的任何产品',
+      xupdater_no_matching_product: 'x-updater 未返回与“{0}”匹配的产品',
+    },
   },
   commandPalette: {
     title: '命令面板',
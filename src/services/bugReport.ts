@@ -2,6 +2,7 @@ import { invoke } from '@tauri-apps/api/core'
 import { logError } from '@/services/logger'
 import { getItem, setItem, STORAGE_KEYS, type TrackedIssue } from '@/services/storage'
 import { useIssueTrackerStore } from '@/stores/issueTracker'
+import type { QueuedSubmission } from '@/types'
 
 export interface BugReportToast {
   success: (message: string) => void
@@ -11,7 +12,12 @@ export interface BugReportToast {
 export interface SubmitBugReportParams {
   errorTitle: string
   errorMessage: string
+  /** Backend error code of the reported failure, when known */
+  errorCode?: string
   category?: string
+  /** X-Plane root whose Log.txt analysis is attached with includeXPlaneAnalysis */
+  xplanePath?: string
+  includeXPlaneAnalysis?: boolean
   timeoutMs?: number
   t: (key: string, values?: Record<string, unknown>) => string
   toast: BugReportToast
@@ -22,7 +28,12 @@ const ISSUE_DRAFT_API_BASE =
   import.meta.env.VITE_XFAST_ISSUE_DRAFT_API_URL ||
   'https://x-fast-manager.vercel.app/api/issue-draft'
 
-function buildFallbackBugReportUrl(errorTitle: string, errorMessage: string, logs: string): string {
+function buildFallbackBugReportUrl(
+  errorTitle: string,
+  errorMessage: string,
+  logs: string,
+  errorCode?: string,
+): string {
   const fallbackTitle = `[Bug]: ${(errorTitle || errorMessage).slice(0, 80)}`
   const fallbackBody = [
     '### Bug Report (Auto-submitted)',
@@ -32,6 +43,7 @@ function buildFallbackBugReportUrl(errorTitle: string, errorMessage: string, log
     errorMessage,
     '```',
     '',
+    ...(errorCode ? [`**Error Code**: \`${errorCode}\``, ''] : []),
     '**Logs**',
     '<details>',
     '<summary>Click to expand logs</summary>',
@@ -88,8 +100,25 @@ async function trackReportedIssue(
   }
 }
 
+interface BugReportResult {
+  issue_url: string
+  issue_number: number
+  /** Set when the report could not be sent yet and was queued for retry */
+  queued?: QueuedSubmission
+}
+
 export async function submitBugReport(params: SubmitBugReportParams): Promise<void> {
-  const { errorTitle, errorMessage, category = 'Other', timeoutMs, t, toast } = params
+  const {
+    errorTitle,
+    errorMessage,
+    errorCode,
+    category = 'Other',
+    xplanePath,
+    includeXPlaneAnalysis = false,
+    timeoutMs,
+    t,
+    toast,
+  } = params
 
   let logs: string
   try {
@@ -99,24 +128,33 @@ export async function submitBugReport(params: SubmitBugReportParams): Promise<vo
   }
 
   const fallbackTitle = `[Bug]: ${(errorTitle || errorMessage).slice(0, 80)}`
-  const fallbackUrl = buildFallbackBugReportUrl(errorTitle, errorMessage, logs)
+  const fallbackUrl = buildFallbackBugReportUrl(errorTitle, errorMessage, logs, errorCode)
   let submitTimeoutId: ReturnType<typeof setTimeout> | null = null
 
   try {
-    const result = await Promise.race<{ issue_url: string; issue_number: number }>([
-      invoke<{ issue_url: string; issue_number: number }>('create_bug_report_issue', {
+    const result = await Promise.race<BugReportResult>([
+      invoke<BugReportResult>('create_bug_report_issue', {
         errorTitle,
         errorMessage,
         logs,
         category,
+        errorCode: errorCode ?? null,
+        xplanePath: xplanePath ?? null,
+        includeXplaneAnalysis: includeXPlaneAnalysis,
       }),
-      new Promise<{ issue_url: string; issue_number: number }>((_, reject) => {
+      new Promise<BugReportResult>((_, reject) => {
         submitTimeoutId = setTimeout(() => {
           reject(new Error('BUG_REPORT_SUBMIT_TIMEOUT'))
         }, timeoutMs ?? DEFAULT_BUG_REPORT_TIMEOUT_MS)
       }),
     ])
 
+    if (result.queued) {
+      // Saved for a later retry: there is no issue to open or track yet
+      toast.info(t('modal.bugReportQueued'))
+      return
+    }
+
     toast.success(t('modal.bugReportSubmitted'))
     await invoke('open_url', { url: result.issue_url })
     await trackReportedIssue(result.issue_number, fallbackTitle, result.issue_url, errorMessage)
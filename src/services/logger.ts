@@ -117,6 +117,43 @@ class Logger {
     }
   }
 
+  /**
+   * Select a log file in the system file manager: X-Plane's Log.txt when
+   * xplanePath is given, otherwise the app log
+   */
+  async revealLogFile(xplanePath?: string): Promise<void> {
+    try {
+      await invoke('reveal_log_file', { xplanePath: xplanePath ?? null })
+    } catch (e) {
+      console.error('Failed to reveal log file:', e)
+      throw e
+    }
+  }
+
+  /**
+   * Select a file or folder in the system file manager
+   */
+  async revealInExplorer(path: string): Promise<void> {
+    try {
+      await invoke('reveal_in_explorer', { path })
+    } catch (e) {
+      console.error('Failed to reveal path:', e)
+      throw e
+    }
+  }
+
+  /**
+   * Export logs and diagnostics as a zip in the Downloads folder, returns its path
+   */
+  async exportSupportBundle(xplanePath?: string): Promise<string> {
+    try {
+      return await invoke<string>('export_support_bundle', { xplanePath: xplanePath ?? null })
+    } catch (e) {
+      console.error('Failed to export support bundle:', e)
+      throw e
+    }
+  }
+
   /**
    * Copy all logs to clipboard
    */
@@ -1,4 +1,6 @@
+import { invoke } from '@tauri-apps/api/core'
 import { Store } from '@tauri-apps/plugin-store'
+import type { EraseAppDataResult, StorageCategory, StoredDataSummary } from '@/types'
 
 let store: Store | null = null
 
@@ -94,6 +96,42 @@ export async function hasKey(key: string): Promise<boolean> {
   return await store!.has(key)
 }
 
+/**
+ * List everything the app stores on this machine, by category.
+ *
+ * @param xplanePath - X-Plane folder to search for files XFast wrote there
+ */
+export async function getStoredDataSummary(xplanePath?: string): Promise<StoredDataSummary> {
+  return await invoke<StoredDataSummary>('get_stored_data_summary', {
+    xplanePath: xplanePath ?? null,
+  })
+}
+
+/**
+ * Erase the selected categories of stored data.
+ *
+ * @param categories - Categories to erase
+ * @param options.includeXplaneFiles - Also remove files XFast wrote inside X-Plane folders
+ * @param options.dryRun - Only report what would be removed
+ */
+export async function eraseAppData(
+  categories: StorageCategory[],
+  options: { xplanePath?: string; includeXplaneFiles?: boolean; dryRun?: boolean } = {},
+): Promise<EraseAppDataResult> {
+  const dryRun = options.dryRun ?? false
+  // The open store would write its in-memory copy back after the file is deleted
+  if (!dryRun && categories.includes('settings')) {
+    await clearStorage()
+    await store!.save()
+  }
+  return await invoke<EraseAppDataResult>('erase_app_data', {
+    categories,
+    xplanePath: options.xplanePath ?? null,
+    includeXplaneFiles: options.includeXplaneFiles ?? false,
+    dryRun,
+  })
+}
+
 // Storage keys constants
 /**
  * Centralized storage keys to prevent typos and ensure consistency.
@@ -1,7 +1,16 @@
 import { defineStore } from 'pinia'
 import { ref, computed } from 'vue'
-import { AddonType, type InstallTask, type InstallResult } from '@/types'
-import { invokeVoidCommand } from '@/services/api'
+import {
+  AddonType,
+  type ContextMenuOptions,
+  type ContextMenuStatus,
+  type InstallTask,
+  type InstallResult,
+  type InstallTaskOverride,
+  type TargetRootKind,
+  type XPlaneLocationReport,
+} from '@/types'
+import { invokeCommand, invokeVoidCommand } from '@/services/api'
 import { useLockStore } from './lock'
 import { getItem, setItem, STORAGE_KEYS } from '@/services/storage'
 
@@ -21,6 +30,10 @@ export interface TaskState {
     configFiles: boolean
     navdata: boolean
   }
+  /** For liveries fitting several installed aircraft: the chosen aircraft folder */
+  targetAircraftFolder?: string
+  /** For scenery suggested for Global Scenery: the root folder the user picked */
+  targetRootKind?: TargetRootKind
 }
 
 /** Delay to batch multiple CLI file selections (500ms) */
@@ -39,6 +52,7 @@ export const useAppStore = defineStore('app', () => {
   // Platform detection (initialized at app startup)
   const isWindows = ref(false)
   const isContextMenuRegistered = ref(false)
+  const contextMenuOptions = ref<ContextMenuOptions | null>(null)
   const isXcslDev = ref(false)
 
   // Log level setting (basic, full, debug)
@@ -127,6 +141,29 @@ export const useAppStore = defineStore('app', () => {
     }
   }
 
+  /** Initial task state, pre-filled from the options of the addon's last install */
+  function getInitialTaskState(task: InstallTask, enabled: boolean): TaskState {
+    const state = getDefaultTaskState(enabled)
+    const profile = task.installProfile
+    if (!profile) return state
+    state.overwrite = !!task.conflictExists && profile.shouldOverwrite
+    state.backup = {
+      liveries: profile.backupLiveries,
+      configFiles: profile.backupConfigFiles,
+      navdata: profile.backupNavdata,
+    }
+    if (
+      profile.targetAircraftFolder &&
+      task.liveryTargetCandidates?.includes(profile.targetAircraftFolder)
+    ) {
+      state.targetAircraftFolder = profile.targetAircraftFolder
+    }
+    if (task.type === AddonType.Scenery) {
+      state.targetRootKind = profile.targetRootKind
+    }
+    return state
+  }
+
   /** Get or create task state with defaults */
   function getTaskState(taskId: string): TaskState {
     if (!taskStates.value[taskId]) {
@@ -202,6 +239,16 @@ export const useAppStore = defineStore('app', () => {
     return tasksWithWarnings.every((task) => getTaskState(task.id).sizeConfirmed)
   })
 
+  // Enabled liveries that fit several installed aircraft and have none chosen
+  const hasUnchosenLiveryTargets = computed(() => {
+    return currentTasks.value.some(
+      (task) =>
+        (task.liveryTargetCandidates?.length ?? 0) > 1 &&
+        getTaskState(task.id).enabled &&
+        !getTaskState(task.id).targetAircraftFolder,
+    )
+  })
+
   // Get count of enabled tasks
   const enabledTasksCount = computed(() => {
     return currentTasks.value.filter((task) => getTaskEnabled(task.id)).length
@@ -322,6 +369,43 @@ export const useAppStore = defineStore('app', () => {
     await setItem(STORAGE_KEYS.XPLANE_PATH, path)
   }
 
+  // Check for Program Files, synced folders and write access problems
+  async function diagnoseXplaneLocation(): Promise<XPlaneLocationReport> {
+    return invokeCommand<XPlaneLocationReport>('diagnose_xplane_location', {
+      xplanePath: xplanePath.value,
+    })
+  }
+
+  function applyContextMenuStatus(status: ContextMenuStatus) {
+    isContextMenuRegistered.value = status.registered
+    contextMenuOptions.value = status.options
+  }
+
+  async function loadContextMenuStatus(): Promise<ContextMenuStatus> {
+    const status = await invokeCommand<ContextMenuStatus>('is_context_menu_registered')
+    applyContextMenuStatus(status)
+    return status
+  }
+
+  // Register the context menu only for the chosen file types and folders
+  async function setContextMenuOptions(options: ContextMenuOptions): Promise<ContextMenuStatus> {
+    const status = await invokeCommand<ContextMenuStatus>('set_context_menu_options', { options })
+    applyContextMenuStatus(status)
+    return status
+  }
+
+  // Turn analyzed items picked by task id into install tasks without re-scanning
+  async function buildInstallTasks(
+    selectedItemIds: string[],
+    overrides?: Record<string, InstallTaskOverride>,
+  ): Promise<InstallTask[]> {
+    return invokeCommand<InstallTask[]>('build_install_tasks', {
+      selectedItemIds,
+      xplanePath: xplanePath.value,
+      overrides: overrides ?? null,
+    })
+  }
+
   async function loadXplanePath() {
     const saved = await getItem<string>(STORAGE_KEYS.XPLANE_PATH)
     if (saved) {
@@ -411,19 +495,26 @@ export const useAppStore = defineStore('app', () => {
 
   function setCurrentTasks(tasks: InstallTask[]) {
     currentTasks.value = tasks
-    // Reset all task states
+    // Task ids are stable across re-analysis of the same inputs (e.g. after a
+    // password prompt), so keep the user's choices for tasks that are still present
+    const previousStates = taskStates.value
     taskStates.value = {}
     // Initialize task states for each task
     // Disable livery tasks where target aircraft is not found
     // Disable tasks where target is locked
     const lockStore = useLockStore()
     tasks.forEach((task) => {
+      const previous = previousStates[task.id]
+      if (previous) {
+        taskStates.value[task.id] = previous
+        return
+      }
       const isLiveryWithoutAircraft =
         task.type === AddonType.Livery && task.liveryAircraftFound === false
       const isLockedConflict =
         task.conflictExists && lockStore.isPathLocked(task.targetPath, xplanePath.value)
       const enabled = !isLiveryWithoutAircraft && !isLockedConflict
-      taskStates.value[task.id] = getDefaultTaskState(enabled)
+      taskStates.value[task.id] = getInitialTaskState(task, enabled)
     })
   }
 
@@ -446,7 +537,7 @@ export const useAppStore = defineStore('app', () => {
       const isLockedConflict =
         task.conflictExists && lockStore.isPathLocked(task.targetPath, xplanePath.value)
       const enabled = !isLiveryWithoutAircraft && !isLockedConflict
-      taskStates.value[task.id] = getDefaultTaskState(enabled)
+      taskStates.value[task.id] = getInitialTaskState(task, enabled)
     })
 
     return uniqueNewTasks.length
@@ -489,10 +580,30 @@ export const useAppStore = defineStore('app', () => {
         backupConfigFiles: configFilePatterns.value.length > 0 && state.backup.configFiles,
         configFilePatterns: configFilePatterns.value,
         backupNavdata: state.backup.navdata,
+        targetAircraftFolder: state.targetAircraftFolder ?? task.targetAircraftFolder,
+        targetRootKind: state.targetRootKind ?? task.targetRootKind,
       }
     })
   }
 
+  // Set the aircraft folder a livery fitting several aircraft installs into
+  function setTaskTargetAircraftFolder(taskId: string, folder: string) {
+    getTaskState(taskId).targetAircraftFolder = folder || undefined
+  }
+
+  function getTaskTargetAircraftFolder(taskId: string): string {
+    return getTaskState(taskId).targetAircraftFolder ?? ''
+  }
+
+  // Set the root folder a scenery task installs into
+  function setTaskTargetRootKind(taskId: string, kind: TargetRootKind) {
+    getTaskState(taskId).targetRootKind = kind
+  }
+
+  function getTaskTargetRootKind(task: InstallTask): TargetRootKind {
+    return getTaskState(task.id).targetRootKind ?? task.targetRootKind ?? 'customScenery'
+  }
+
   // Set size confirmation for a specific task
   function setTaskSizeConfirmed(taskId: string, confirmed: boolean) {
     getTaskState(taskId).sizeConfirmed = confirmed
@@ -666,6 +777,7 @@ export const useAppStore = defineStore('app', () => {
     isAnalyzeInProgress,
     isWindows,
     isContextMenuRegistered,
+    contextMenuOptions,
     isXcslDev,
     installPreferences,
     verificationPreferences,
@@ -691,6 +803,7 @@ export const useAppStore = defineStore('app', () => {
     isTaskInTargetPathConflict,
     hasSizeWarnings,
     allSizeWarningsConfirmed,
+    hasUnchosenLiveryTargets,
     enabledTasksCount,
     pendingCliArgs,
     installResult,
@@ -701,6 +814,11 @@ export const useAppStore = defineStore('app', () => {
     initStore,
     setXplanePath,
     loadXplanePath,
+    diagnoseXplaneLocation,
+    buildInstallTasks,
+    applyContextMenuStatus,
+    loadContextMenuStatus,
+    setContextMenuOptions,
     togglePreference,
     toggleVerificationPreference,
     toggleAtomicInstall,
@@ -724,6 +842,10 @@ export const useAppStore = defineStore('app', () => {
     setGlobalOverwrite,
     getTaskOverwrite,
     getTasksWithOverwrite,
+    setTaskTargetAircraftFolder,
+    getTaskTargetAircraftFolder,
+    setTaskTargetRootKind,
+    getTaskTargetRootKind,
     setTaskSizeConfirmed,
     getTaskSizeConfirmed,
     confirmAllSizeWarnings,
@@ -2,6 +2,7 @@ import { defineStore } from 'pinia'
 import { ref } from 'vue'
 import { invoke } from '@tauri-apps/api/core'
 import { useAppStore } from './app'
+import { getErrorMessage } from '@/types'
 
 export interface DiskUsageReport {
   totalBytes: number
@@ -31,11 +32,43 @@ export interface FolderDiskUsage {
   largestFiles: { path: string; sizeBytes: number }[]
 }
 
+export interface DuplicateFileGroup {
+  hash: string
+  sizeBytes: number
+  /** Relative to the X-Plane folder; the first one is kept when deduplicating */
+  paths: string[]
+  reclaimableBytes: number
+}
+
+export interface DuplicateFilesReport {
+  groups: DuplicateFileGroup[]
+  totalReclaimableBytes: number
+  scannedFiles: number
+  hashedFiles: number
+  cancelled: boolean
+}
+
+export type HardlinkSkipReason =
+  | 'differentVolume'
+  | 'unsupported'
+  | 'changed'
+  | 'alreadyLinked'
+  | 'missing'
+  | 'failed'
+
+export interface HardlinkDedupResult {
+  linked: { path: string; target: string; sizeBytes: number }[]
+  skipped: { path: string; reason: HardlinkSkipReason; message: string }[]
+  reclaimedBytes: number
+  cancelled: boolean
+}
+
 export const useDiskUsageStore = defineStore('diskUsage', () => {
   const report = ref<DiskUsageReport | null>(null)
   const isScanning = ref(false)
   const error = ref<string | null>(null)
   const selectedItem = ref<FolderDiskUsage | null>(null)
+  const duplicates = ref<DuplicateFilesReport | null>(null)
 
   async function scan() {
     isScanning.value = true
@@ -65,11 +98,51 @@ export const useDiskUsageStore = defineStore('diskUsage', () => {
     }
   }
 
+  async function findDuplicateFiles(roots: string[] = [], minFileSize = 1024 * 1024) {
+    isScanning.value = true
+    error.value = null
+    try {
+      const appStore = useAppStore()
+      duplicates.value = await invoke<DuplicateFilesReport>('find_duplicate_files', {
+        xplanePath: appStore.xplanePath,
+        roots,
+        minFileSize,
+      })
+    } catch (e) {
+      error.value = getErrorMessage(e)
+    } finally {
+      isScanning.value = false
+    }
+  }
+
+  async function deduplicateWithHardlinks(groups: DuplicateFileGroup[]) {
+    const appStore = useAppStore()
+    const result = await invoke<HardlinkDedupResult>('deduplicate_with_hardlinks', {
+      xplanePath: appStore.xplanePath,
+      groups,
+    })
+    // Linked groups no longer reclaim anything; scan again for exact numbers
+    duplicates.value = null
+    return result
+  }
+
   function reset() {
     report.value = null
     error.value = null
     selectedItem.value = null
+    duplicates.value = null
   }
 
-  return { report, isScanning, error, selectedItem, scan, scanFolder, reset }
+  return {
+    report,
+    isScanning,
+    error,
+    selectedItem,
+    duplicates,
+    scan,
+    scanFolder,
+    findDuplicateFiles,
+    deduplicateWithHardlinks,
+    reset,
+  }
 })
@@ -0,0 +1,63 @@
+import { defineStore } from 'pinia'
+import { ref } from 'vue'
+import { invoke } from '@tauri-apps/api/core'
+
+export interface LaunchProfile {
+  name: string
+  args: string[]
+  createdAt: number
+  updatedAt: number
+}
+
+export interface LaunchProfileSaveResult {
+  profile: LaunchProfile
+  /** Unknown or malformed flags; the profile is saved regardless */
+  warnings: string[]
+}
+
+export interface KnownLaunchFlag {
+  flag: string
+  takesValue: boolean
+  description: string
+}
+
+export const useLaunchProfilesStore = defineStore('launchProfiles', () => {
+  const profiles = ref<LaunchProfile[]>([])
+  const knownFlags = ref<KnownLaunchFlag[]>([])
+  const isLoading = ref(false)
+
+  async function loadProfiles() {
+    isLoading.value = true
+    try {
+      profiles.value = await invoke<LaunchProfile[]>('list_launch_profiles')
+    } finally {
+      isLoading.value = false
+    }
+  }
+
+  async function loadKnownFlags() {
+    if (knownFlags.value.length > 0) return
+    knownFlags.value = await invoke<KnownLaunchFlag[]>('list_known_launch_flags')
+  }
+
+  async function saveProfile(name: string, args: string[]): Promise<LaunchProfileSaveResult> {
+    const result = await invoke<LaunchProfileSaveResult>('save_launch_profile', { name, args })
+    await loadProfiles()
+    return result
+  }
+
+  async function deleteProfile(name: string) {
+    await invoke('delete_launch_profile', { name })
+    await loadProfiles()
+  }
+
+  return {
+    profiles,
+    knownFlags,
+    isLoading,
+    loadProfiles,
+    loadKnownFlags,
+    saveProfile,
+    deleteProfile,
+  }
+})
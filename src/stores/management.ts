@@ -8,16 +8,30 @@ import type {
   NavdataManagerInfo,
   NavdataBackupInfo,
   ManagementData,
+  InstalledManagementItem,
   ManagementTab,
   ManagementItemType,
   AddonUpdateOptions,
   AddonUpdatePreview,
+  AddonUpdateDiff,
   AddonUpdatePlan,
   AddonUpdateResult,
   AddonUpdaterCredentials,
+  DiscoveredUpdaterCredentials,
+  UpdaterProfileValidation,
   AddonDiskSpaceInfo,
   AddonUpdatableItemType,
+  MaintenanceReport,
+  MaintenanceSection,
+  HealthCheckReport,
+  AddonUpdateSummary,
+  AddonType,
+  InstallHistoryEntry,
+  UninstallFromHistoryResult,
+  AnalysisSuppression,
+  AnalysisSuppressionKind,
 } from '@/types'
+import { getErrorMessage } from '@/types'
 import { useAppStore } from './app'
 import { useToastStore } from './toast'
 import { useLockStore } from './lock'
@@ -260,6 +274,34 @@ export const useManagementStore = defineStore('management', () => {
     return updated
   }
 
+  function upsertPluginEntry(updated: PluginInfo): PluginInfo {
+    const targetKey = normalizeManagementFolderKey(updated.folderName)
+    const existing = plugins.value.find(
+      (item) => normalizeManagementFolderKey(item.folderName) === targetKey,
+    )
+    if (existing) {
+      Object.assign(existing, updated)
+    } else {
+      plugins.value.push(updated)
+    }
+    pluginsEnabledCount.value = plugins.value.filter((item) => item.enabled).length
+    pluginsTotalCount.value = plugins.value.length
+    return existing ?? updated
+  }
+
+  // Insert or update rows for a just-installed folder, without a full rescan
+  function applyInstalledItem(item: InstalledManagementItem) {
+    if (item.itemType === 'aircraft') {
+      for (const entry of applyCachedUpdates(item.entries)) {
+        upsertAircraftEntry(entry)
+      }
+      syncCfgDisabledToLockStore('aircraft', item.entries)
+    } else {
+      upsertPluginEntry(applyCachedUpdates([item.entry])[0])
+      syncCfgDisabledToLockStore('plugin', [item.entry])
+    }
+  }
+
   async function fetchAircraftFolderState(folderName: string): Promise<AircraftInfo> {
     if (!validateXPlanePath(error)) {
       throw new Error(error.value!)
@@ -276,6 +318,8 @@ export const useManagementStore = defineStore('management', () => {
         ...updated,
         latestVersion: updated.latestVersion ?? existing?.latestVersion,
         hasUpdate: updated.hasUpdate ?? existing?.hasUpdate ?? false,
+        sizeBytes: updated.sizeBytes ?? existing?.sizeBytes ?? null,
+        newestFileModified: updated.newestFileModified ?? existing?.newestFileModified ?? null,
       }
     } catch (e) {
       error.value = String(e)
@@ -284,6 +328,27 @@ export const useManagementStore = defineStore('management', () => {
     }
   }
 
+  // Icon paths per item; null caches a miss for this session
+  const addonIcons = new Map<string, Promise<string | null>>()
+
+  function getAddonIcon(itemType: ManagementItemType, folderName: string): Promise<string | null> {
+    const key = `${itemType}:${normalizeManagementFolderKey(folderName)}`
+    let icon = addonIcons.get(key)
+    if (!icon) {
+      icon = invoke<string | null>('get_addon_icon', { itemType, folderName }).catch((e) => {
+        logError(`Failed to get addon icon ${folderName}: ${e}`, 'management')
+        return null
+      })
+      addonIcons.set(key, icon)
+    }
+    return icon
+  }
+
+  async function clearIconCache(): Promise<number> {
+    addonIcons.clear()
+    return invoke<number>('clear_icon_cache')
+  }
+
   async function loadAddonUpdateOptions() {
     if (addonUpdateOptionsLoaded.value) return
 
@@ -421,7 +486,7 @@ export const useManagementStore = defineStore('management', () => {
       })
     } catch (e) {
       logError(
-        `Failed to fetch addon update preview for ${itemType}:${folderName}: ${e}`,
+        `Failed to fetch addon update preview for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
         'management',
       )
       throw e
@@ -500,6 +565,7 @@ export const useManagementStore = defineStore('management', () => {
     totalCountRef: Ref<number>
     enabledCountRef: Ref<number>
     applyCache?: boolean
+    computeSizes?: boolean
     afterLoad?: () => void
     logName: string
   }
@@ -515,6 +581,7 @@ export const useManagementStore = defineStore('management', () => {
     try {
       const result = await invoke<ManagementData<T>>(config.scanCommand, {
         xplanePath: appStore.xplanePath,
+        computeSizes: config.computeSizes ?? false,
       })
 
       // Apply cached update info if applicable (only for UpdatableItem types)
@@ -627,6 +694,7 @@ export const useManagementStore = defineStore('management', () => {
   async function loadAircraft() {
     await loadItems<AircraftInfo>({
       scanCommand: 'scan_aircraft',
+      computeSizes: true,
       itemsRef: aircraft,
       totalCountRef: aircraftTotalCount,
       enabledCountRef: aircraftEnabledCount,
@@ -663,6 +731,7 @@ export const useManagementStore = defineStore('management', () => {
         try {
           const result = await invoke<ManagementData<AircraftInfo>>('scan_aircraft', {
             xplanePath: appStore.xplanePath,
+            computeSizes: true,
           })
           aircraft.value = applyCachedUpdates(result.entries)
           aircraftTotalCount.value = result.totalCount
@@ -692,6 +761,7 @@ export const useManagementStore = defineStore('management', () => {
   async function loadPlugins() {
     await loadItems<PluginInfo>({
       scanCommand: 'scan_plugins',
+      computeSizes: true,
       itemsRef: plugins,
       totalCountRef: pluginsTotalCount,
       enabledCountRef: pluginsEnabledCount,
@@ -723,6 +793,7 @@ export const useManagementStore = defineStore('management', () => {
         try {
           const result = await invoke<ManagementData<PluginInfo>>('scan_plugins', {
             xplanePath: appStore.xplanePath,
+            computeSizes: true,
           })
           plugins.value = applyCachedUpdates(result.entries)
           pluginsTotalCount.value = result.totalCount
@@ -773,7 +844,7 @@ export const useManagementStore = defineStore('management', () => {
       })
     } catch (e) {
       logError(
-        `Failed to build addon update plan for ${itemType}:${folderName}: ${e}`,
+        `Failed to build addon update plan for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
         'management',
       )
       throw e
@@ -782,6 +853,34 @@ export const useManagementStore = defineStore('management', () => {
     }
   }
 
+  // What an update would change in the addon folder, per file
+  async function getAddonUpdateDiff(
+    itemType: AddonUpdatableItemType,
+    folderName: string,
+    optionsOverride?: Partial<AddonUpdateOptions>,
+  ): Promise<AddonUpdateDiff> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    await loadAddonUpdateOptions()
+
+    try {
+      return await invoke<AddonUpdateDiff>('get_addon_update_diff', {
+        xplanePath: appStore.xplanePath,
+        itemType,
+        folderName,
+        options: { ...addonUpdateOptions.value, ...optionsOverride },
+      })
+    } catch (e) {
+      logError(
+        `Failed to build addon update diff for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
+        'management',
+      )
+      throw e
+    }
+  }
+
   async function executeAddonUpdate(
     itemType: AddonUpdatableItemType,
     folderName: string,
@@ -815,7 +914,10 @@ export const useManagementStore = defineStore('management', () => {
 
       return result
     } catch (e) {
-      logError(`Failed to execute addon update for ${itemType}:${folderName}: ${e}`, 'management')
+      logError(
+        `Failed to execute addon update for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
+        'management',
+      )
       throw e
     } finally {
       isExecutingUpdate.value = false
@@ -848,13 +950,127 @@ export const useManagementStore = defineStore('management', () => {
       })
     } catch (e) {
       logError(
-        `Failed to save addon updater credentials for ${itemType}:${folderName}: ${e}`,
+        `Failed to save addon updater credentials for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
         'management',
       )
       throw e
     }
   }
 
+  async function discoverExistingUpdaterCredentials(): Promise<DiscoveredUpdaterCredentials[]> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      return await invoke<DiscoveredUpdaterCredentials[]>(
+        'discover_existing_updater_credentials',
+        { xplanePath: appStore.xplanePath },
+      )
+    } catch (e) {
+      logError(`Failed to discover existing updater credentials: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  // Apply a discovered credential to an addon; the backend reads the secret itself
+  async function importDiscoveredUpdaterCredentials(
+    discovered: DiscoveredUpdaterCredentials,
+    itemType: string,
+    folderName: string,
+  ) {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      await invoke('set_addon_updater_credentials', {
+        xplanePath: appStore.xplanePath,
+        itemType,
+        folderName,
+        login: '',
+        licenseKey: '',
+        importFromDiscovery: true,
+        sourceItemType: discovered.itemType,
+        sourceFolderName: discovered.folderName,
+      })
+    } catch (e) {
+      logError(
+        `Failed to import updater credentials of ${discovered.itemType}:${discovered.folderName} for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
+        'management',
+      )
+      throw e
+    }
+  }
+
+  // Addons installed through XFast, newest first
+  async function getInstallHistory(
+    limit?: number,
+    addonType?: AddonType,
+  ): Promise<InstallHistoryEntry[]> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      return await invoke<InstallHistoryEntry[]>('get_install_history', {
+        xplanePath: appStore.xplanePath,
+        limit,
+        addonType,
+      })
+    } catch (e) {
+      logError(`Failed to load install history: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  async function uninstallFromHistory(installId: number): Promise<UninstallFromHistoryResult> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      return await invoke<UninstallFromHistoryResult>('uninstall_from_history', {
+        xplanePath: appStore.xplanePath,
+        installId,
+      })
+    } catch (e) {
+      logError(`Failed to uninstall install #${installId}: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  // Hide an analysis warning; the next scan reports it in its suppressed count
+  async function addAnalysisSuppression(
+    kind: AnalysisSuppressionKind,
+    key: string,
+  ): Promise<AnalysisSuppression> {
+    try {
+      return await invoke<AnalysisSuppression>('add_analysis_suppression', { kind, key })
+    } catch (e) {
+      logError(`Failed to suppress ${key}: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  async function removeAnalysisSuppression(id: number): Promise<boolean> {
+    try {
+      return await invoke<boolean>('remove_analysis_suppression', { id })
+    } catch (e) {
+      logError(`Failed to remove analysis suppression #${id}: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  async function listAnalysisSuppressions(): Promise<AnalysisSuppression[]> {
+    try {
+      return await invoke<AnalysisSuppression[]>('list_analysis_suppressions')
+    } catch (e) {
+      logError(`Failed to load analysis suppressions: ${e}`, 'management')
+      throw e
+    }
+  }
+
   async function getAddonUpdaterCredentials(
     itemType: AddonUpdatableItemType,
     folderName: string,
@@ -871,7 +1087,30 @@ export const useManagementStore = defineStore('management', () => {
       })
     } catch (e) {
       logError(
-        `Failed to read addon updater credentials for ${itemType}:${folderName}: ${e}`,
+        `Failed to read addon updater credentials for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
+        'management',
+      )
+      throw e
+    }
+  }
+
+  async function validateUpdaterProfile(
+    itemType: AddonUpdatableItemType,
+    folderName: string,
+  ): Promise<UpdaterProfileValidation> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      return await invoke<UpdaterProfileValidation>('validate_updater_profile', {
+        xplanePath: appStore.xplanePath,
+        itemType,
+        folderName,
+      })
+    } catch (e) {
+      logError(
+        `Failed to validate updater profile for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
         'management',
       )
       throw e
@@ -894,7 +1133,7 @@ export const useManagementStore = defineStore('management', () => {
       })
     } catch (e) {
       logError(
-        `Failed to read addon update disk space for ${itemType}:${folderName}: ${e}`,
+        `Failed to read addon update disk space for ${itemType}:${folderName}: ${getErrorMessage(e)}`,
         'management',
       )
       throw e
@@ -951,6 +1190,93 @@ export const useManagementStore = defineStore('management', () => {
     }
   }
 
+  async function runFullMaintenance(sections?: MaintenanceSection[]): Promise<MaintenanceReport> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      return await invoke<MaintenanceReport>('run_full_maintenance', {
+        xplanePath: appStore.xplanePath,
+        sections: sections ?? null,
+      })
+    } catch (e) {
+      logError(`Failed to run full maintenance: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  async function cancelMaintenance(): Promise<boolean> {
+    return await invoke<boolean>('cancel_maintenance')
+  }
+
+  async function getLastMaintenanceReport(): Promise<MaintenanceReport | null> {
+    try {
+      return await invoke<MaintenanceReport | null>('get_last_maintenance_report')
+    } catch (e) {
+      logError(`Failed to load last maintenance report: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  async function runHealthCheck(): Promise<HealthCheckReport> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      return await invoke<HealthCheckReport>('run_health_check', {
+        xplanePath: appStore.xplanePath,
+      })
+    } catch (e) {
+      logError(`Failed to run health check: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  async function checkAllAddonUpdates(includeLiveries = false): Promise<AddonUpdateSummary> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      return await invoke<AddonUpdateSummary>('check_all_addon_updates', {
+        xplanePath: appStore.xplanePath,
+        includeLiveries,
+        useBeta: addonUpdateOptions.value.useBeta,
+      })
+    } catch (e) {
+      logError(`Failed to check addon updates: ${e}`, 'management')
+      throw e
+    }
+  }
+
+  async function cancelAddonUpdateCheck(): Promise<boolean> {
+    return await invoke<boolean>('cancel_addon_update_check')
+  }
+
+  /** Repeat the update check in the background; 0 hours stops it */
+  async function startAddonUpdateScheduler(
+    intervalHours: number,
+    includeLiveries = false,
+  ): Promise<void> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      await invoke('start_addon_update_scheduler', {
+        xplanePath: appStore.xplanePath,
+        intervalHours,
+        includeLiveries,
+        useBeta: addonUpdateOptions.value.useBeta,
+      })
+    } catch (e) {
+      logError(`Failed to start addon update scheduler: ${e}`, 'management')
+      throw e
+    }
+  }
+
   // Load data for current tab
   async function loadCurrentTabData() {
     switch (activeTab.value) {
@@ -1036,6 +1362,8 @@ export const useManagementStore = defineStore('management', () => {
           ...updated,
           latestVersion: updated.latestVersion ?? existing?.latestVersion,
           hasUpdate: updated.hasUpdate ?? existing?.hasUpdate ?? false,
+          sizeBytes: updated.sizeBytes ?? existing?.sizeBytes ?? null,
+          newestFileModified: updated.newestFileModified ?? existing?.newestFileModified ?? null,
         }
 
         upsertAircraftEntry(merged)
@@ -1100,6 +1428,8 @@ export const useManagementStore = defineStore('management', () => {
         ...updated,
         latestVersion: updated.latestVersion ?? existing?.latestVersion,
         hasUpdate: updated.hasUpdate ?? existing?.hasUpdate ?? false,
+        sizeBytes: updated.sizeBytes ?? existing?.sizeBytes ?? null,
+        newestFileModified: updated.newestFileModified ?? existing?.newestFileModified ?? null,
       }
       const entry = syncList ? upsertAircraftEntry(merged) : merged
       await syncLockAfterToggle('aircraft', folderName, merged.enabled)
@@ -1277,20 +1607,39 @@ export const useManagementStore = defineStore('management', () => {
     loadAircraft,
     fetchAircraftFolderState,
     refreshAircraftFolder,
+    applyInstalledItem,
     checkAircraftUpdates,
     loadPlugins,
     checkPluginsUpdates,
+    getAddonIcon,
+    clearIconCache,
     loadAddonUpdateOptions,
     setAddonUpdateOptions,
     fetchAddonUpdatePreview,
     buildAddonUpdatePlan,
+    getAddonUpdateDiff,
     executeAddonUpdate,
     setAddonUpdaterCredentials,
     getAddonUpdaterCredentials,
+    discoverExistingUpdaterCredentials,
+    importDiscoveredUpdaterCredentials,
+    getInstallHistory,
+    uninstallFromHistory,
+    addAnalysisSuppression,
+    removeAnalysisSuppression,
+    listAnalysisSuppressions,
+    validateUpdaterProfile,
     getAddonUpdateDiskSpace,
     loadNavdata,
     loadNavdataBackups,
     restoreNavdataBackup,
+    runFullMaintenance,
+    cancelMaintenance,
+    getLastMaintenanceReport,
+    runHealthCheck,
+    checkAllAddonUpdates,
+    cancelAddonUpdateCheck,
+    startAddonUpdateScheduler,
     loadCurrentTabData,
     toggleEnabled,
     toggleAircraftAcfFile,
@@ -21,6 +21,8 @@ export interface ErrorModalState {
   title: string
   message: string
   hideReport?: boolean
+  /** Backend error code, included in bug reports */
+  errorCode?: string
 }
 
 /** State for the confirm modal */
@@ -33,7 +35,11 @@ export const useModalStore = defineStore('modal', () => {
   const errorModal = ref<ErrorModalState>({ visible: false, title: '', message: '' })
   const confirmModal = ref<ConfirmModalState>({ visible: false, options: null })
 
-  function showError(message: string, title = '', options?: { hideReport?: boolean }) {
+  function showError(
+    message: string,
+    title = '',
+    options?: { hideReport?: boolean; errorCode?: string },
+  ) {
     // Deduplicate error messages by splitting on newlines and removing duplicates
     const lines = message.split('\n').filter((line) => line.trim() !== '')
     const uniqueLines = Array.from(new Set(lines))
@@ -44,6 +50,7 @@ export const useModalStore = defineStore('modal', () => {
       title,
       message: deduplicatedMessage,
       hideReport: options?.hideReport,
+      errorCode: options?.errorCode,
     }
     // Automatically log error modal messages
     logger.error(`[Modal] ${title ? title + ': ' : ''}${deduplicatedMessage}`, 'ui')
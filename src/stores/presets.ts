@@ -4,6 +4,7 @@ import { invoke } from '@tauri-apps/api/core'
 import { save, open } from '@tauri-apps/plugin-dialog'
 import { useAppStore } from './app'
 import { useLockStore, type LockedItemsData } from './lock'
+import { useLaunchProfilesStore } from './launchProfiles'
 
 export interface PresetSummary {
   id: number
@@ -159,6 +160,8 @@ export const usePresetsStore = defineStore('presets', () => {
     if (!filePath) return
     await invoke('import_preset', { importPath: filePath })
     await loadPresets()
+    // Preset files also carry launch profiles
+    await useLaunchProfilesStore().loadProfiles()
   }
 
   return {
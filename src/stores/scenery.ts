@@ -2,10 +2,28 @@ import { defineStore } from 'pinia'
 import { ref, computed, watch } from 'vue'
 import { invoke } from '@tauri-apps/api/core'
 import type {
+  DatabaseMaintenanceReport,
+  KnownXPlaneInstall,
   SceneryIndexStatus,
   SceneryManagerData,
   SceneryManagerEntry,
+  SceneryManagerFilter,
+  SceneryManagerPage,
+  SceneryManagerSort,
+  SceneryManagerSummary,
   SceneryCategory,
+  SceneryOrderReport,
+  SceneryPacksIniReport,
+  SceneryPacksIniRepairResult,
+  SceneryPacksRepairStrategy,
+  SceneryImportMode,
+  SceneryImportResult,
+  LibraryDependent,
+  PackageLibraryDependency,
+  SceneryDeletionPreview,
+  SceneryDeletionResult,
+  SceneryApplyMode,
+  SceneryPackageInfo,
 } from '@/types'
 import { parseApiError, getErrorMessage } from '@/types'
 import { useAppStore } from './app'
@@ -81,6 +99,7 @@ export const useSceneryStore = defineStore('scenery', () => {
       Other: [],
       Overlay: [],
       AirportMesh: [],
+      OrthoTile: [],
       Mesh: [],
       Unrecognized: [],
     }
@@ -146,14 +165,15 @@ export const useSceneryStore = defineStore('scenery', () => {
       // Clear any previous database reset flag on successful load
       needsDatabaseReset.value = false
     } catch (e) {
-      const errorStr = String(e)
+      const errorStr = getErrorMessage(e)
       error.value = errorStr
-      logError(`Failed to load scenery data: ${e}`, 'scenery')
+      logError(`Failed to load scenery data: ${errorStr}`, 'scenery')
 
       // Check if this is a schema incompatibility error (old database missing columns,
       // or a newer database version than what the current code supports).
       if (
-        (errorStr.includes('migration_failed') && errorStr.includes('newer than supported')) ||
+        (parseApiError(e)?.code === 'migration_failed' &&
+          errorStr.includes('newer than supported')) ||
         errorStr.includes('no column found for name') ||
         errorStr.includes('no column for name')
       ) {
@@ -164,6 +184,52 @@ export const useSceneryStore = defineStore('scenery', () => {
     }
   }
 
+  // Header counts only, for rendering before any entries are loaded
+  async function loadSummary(): Promise<SceneryManagerSummary | null> {
+    if (!validateXPlanePath(error)) return null
+    return invoke<SceneryManagerSummary>('get_scenery_manager_summary', {
+      xplanePath: appStore.xplanePath,
+    })
+  }
+
+  // One page of entries, filtered and sorted by the backend
+  async function loadPage(
+    offset: number,
+    limit: number,
+    sort?: SceneryManagerSort,
+    filter?: SceneryManagerFilter,
+  ): Promise<SceneryManagerPage | null> {
+    if (!validateXPlanePath(error)) return null
+    return invoke<SceneryManagerPage>('get_scenery_manager_data_paged', {
+      xplanePath: appStore.xplanePath,
+      offset,
+      limit,
+      sort: sort ?? null,
+      filter: filter ?? null,
+    })
+  }
+
+  // Background watcher that updates the index when scenery is added outside the app
+  async function startWatcher(lockedFolderNames: string[] = []) {
+    if (!appStore.xplanePath) return
+    try {
+      await invoke<boolean>('start_scenery_watcher', {
+        xplanePath: appStore.xplanePath,
+        lockedFolderNames,
+      })
+    } catch (e) {
+      logError(`Failed to start scenery watcher: ${getErrorMessage(e)}`, 'scenery')
+    }
+  }
+
+  async function stopWatcher() {
+    try {
+      await invoke<boolean>('stop_scenery_watcher')
+    } catch (e) {
+      logError(`Failed to stop scenery watcher: ${getErrorMessage(e)}`, 'scenery')
+    }
+  }
+
   async function loadIndexStatus() {
     if (!validateXPlanePath()) {
       indexExists.value = false
@@ -177,7 +243,17 @@ export const useSceneryStore = defineStore('scenery', () => {
       indexExists.value = status.indexExists
     } catch (e) {
       indexExists.value = false
-      logError(`Failed to load scenery index status: ${e}`, 'scenery')
+      logError(`Failed to load scenery index status: ${getErrorMessage(e)}`, 'scenery')
+    }
+  }
+
+  // X-Plane installations with index data or history in the shared database
+  async function listKnownInstalls(): Promise<KnownXPlaneInstall[]> {
+    try {
+      return await invoke<KnownXPlaneInstall[]>('list_known_xplane_installs')
+    } catch (e) {
+      logError(`Failed to list known X-Plane installations: ${getErrorMessage(e)}`, 'scenery')
+      return []
     }
   }
 
@@ -198,6 +274,16 @@ export const useSceneryStore = defineStore('scenery', () => {
     }
   }
 
+  // Check the database for corruption and orphaned rows, compacting it if needed
+  async function runDatabaseMaintenance(): Promise<DatabaseMaintenanceReport | null> {
+    try {
+      return await invoke<DatabaseMaintenanceReport>('run_database_maintenance')
+    } catch (e) {
+      logError(`Database maintenance failed: ${getErrorMessage(e)}`, 'scenery')
+      return null
+    }
+  }
+
   // Toggle enabled state for an entry (local only, no backend write)
   function toggleEnabled(folderName: string) {
     if (!data.value) return
@@ -232,12 +318,41 @@ export const useSceneryStore = defineStore('scenery', () => {
         enabled: null,
         sortOrder: null,
         category: newCategory,
+        pinned: null,
       })
     } catch (e) {
       // Revert on error
       entry.category = oldCategory
-      error.value = String(e)
-      logError(`Failed to update category: ${e}`, 'scenery')
+      error.value = getErrorMessage(e)
+      logError(`Failed to update category: ${error.value}`, 'scenery')
+      throw e
+    }
+  }
+
+  // Pin an entry so auto-sort keeps it at its current position
+  async function setPinned(folderName: string, pinned: boolean) {
+    if (!data.value) return
+
+    const entry = data.value.entries.find((e) => e.folderName === folderName)
+    if (!entry) return
+
+    const wasPinned = entry.pinned
+
+    try {
+      entry.pinned = pinned
+
+      await invoke('update_scenery_entry', {
+        xplanePath: appStore.xplanePath,
+        folderName,
+        enabled: null,
+        sortOrder: null,
+        category: null,
+        pinned,
+      })
+    } catch (e) {
+      entry.pinned = wasPinned
+      error.value = getErrorMessage(e)
+      logError(`Failed to update pin state: ${error.value}`, 'scenery')
       throw e
     }
   }
@@ -340,7 +455,7 @@ export const useSceneryStore = defineStore('scenery', () => {
   }
 
   // Apply changes to scenery_packs.ini
-  async function applyChanges() {
+  async function applyChanges(applyMode?: SceneryApplyMode) {
     // Prevent concurrent calls (race condition protection)
     if (isSaving.value) return
 
@@ -371,24 +486,63 @@ export const useSceneryStore = defineStore('scenery', () => {
         sortOrder: entry.sortOrder,
       }))
 
-      await invoke('apply_scenery_changes', {
+      const iniWritten = await invoke<boolean>('apply_scenery_changes', {
         xplanePath: appStore.xplanePath,
         entries: updates,
+        applyMode,
       })
 
       // Update original state after successful save
       originalEntries.value = JSON.parse(JSON.stringify(normalizedEntries))
-      // Mark as synced since we just wrote to ini
-      data.value.needsSync = false
+      // Synced unless the ini write waits for X-Plane to close
+      data.value.needsSync = !iniWritten
     } catch (e) {
-      error.value = String(e)
-      logError(`Failed to apply changes: ${e}`, 'scenery')
+      error.value = getErrorMessage(e)
+      logError(`Failed to apply changes: ${error.value}`, 'scenery')
       throw e
     } finally {
       isSaving.value = false
     }
   }
 
+  // Insert or update the row for a just-installed package without a full reload.
+  // New rows go last until the next scan reads their scenery_packs.ini position.
+  function applyInstalledPackage(info: SceneryPackageInfo) {
+    if (!data.value) return
+
+    const fields = {
+      category: info.category,
+      subPriority: info.subPriority,
+      missingLibraries: info.missingLibraries,
+      requiredLibraries: info.requiredLibraries,
+      airportId: info.airportId,
+    }
+    const sortOrder = Math.max(-1, ...data.value.entries.map((e) => e.sortOrder)) + 1
+    // Apply to the original snapshot too, so the install does not count as a local change
+    for (const list of [data.value.entries, originalEntries.value]) {
+      const existing = list.find((e) => e.folderName === info.folderName)
+      if (existing) {
+        Object.assign(existing, fields)
+      } else {
+        list.push({
+          ...fields,
+          folderName: info.folderName,
+          enabled: info.enabled,
+          sortOrder,
+          duplicateTiles: [],
+          duplicateAirports: [],
+          pinned: info.pinned,
+        })
+      }
+    }
+
+    data.value.totalCount = data.value.entries.length
+    data.value.enabledCount = data.value.entries.filter((e) => e.enabled).length
+    data.value.missingDepsCount = data.value.entries.filter(
+      (e) => e.missingLibraries.length > 0,
+    ).length
+  }
+
   // Reset to original state
   function resetChanges() {
     if (originalEntries.value.length > 0 && data.value) {
@@ -397,21 +551,34 @@ export const useSceneryStore = defineStore('scenery', () => {
     }
   }
 
-  // Delete a scenery entry (folder)
-  async function deleteEntry(folderName: string) {
+  // Delete a scenery entry (folder). Without force, the backend refuses when enabled
+  // packages depend on libraries only this folder provides.
+  async function deleteEntry(
+    folderName: string,
+    force = false,
+    applyMode?: SceneryApplyMode,
+  ): Promise<SceneryDeletionResult> {
     if (!validateXPlanePath(error)) {
       throw new Error(error.value!)
     }
 
     try {
-      await invoke('delete_scenery_folder', {
+      const result = await invoke<SceneryDeletionResult>('delete_scenery_folder', {
         xplanePath: appStore.xplanePath,
         folderName,
+        force,
+        applyMode,
       })
 
       // Remove from local data
       if (data.value) {
         data.value.entries = data.value.entries.filter((e) => e.folderName !== folderName)
+        for (const missing of result.newlyMissingLibraries) {
+          const entry = data.value.entries.find((e) => e.folderName === missing.folderName)
+          if (entry && !entry.missingLibraries.includes(missing.libraryName)) {
+            entry.missingLibraries.push(missing.libraryName)
+          }
+        }
 
         // Recalculate sortOrder to eliminate gaps
         // Sort by current sortOrder first, then reassign consecutive values
@@ -438,6 +605,7 @@ export const useSceneryStore = defineStore('scenery', () => {
           ...entry,
           sortOrder: index,
         }))
+      return result
     } catch (e) {
       // Parse structured error if available
       const apiError = parseApiError(e)
@@ -458,6 +626,137 @@ export const useSceneryStore = defineStore('scenery', () => {
     }
   }
 
+  // Link a scenery folder stored elsewhere into Custom Scenery with a .lnk shortcut.
+  // Returns the name the index lists it under.
+  async function createShortcut(sourceFolder: string, linkName: string): Promise<string> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    try {
+      return await invoke<string>('create_scenery_shortcut', {
+        xplanePath: appStore.xplanePath,
+        sourceFolder,
+        linkName,
+      })
+    } catch (e) {
+      error.value = getErrorMessage(e)
+      logError(`Failed to create scenery shortcut: ${error.value}`, 'scenery')
+      throw e
+    }
+  }
+
+  // Enabled packages that would lose libraries only this folder provides; advisory,
+  // so failures yield an empty list
+  async function previewDeletion(folderName: string): Promise<LibraryDependent[]> {
+    if (!appStore.xplanePath) return []
+
+    try {
+      const preview = await invoke<SceneryDeletionPreview>('preview_scenery_deletion', {
+        xplanePath: appStore.xplanePath,
+        folderName,
+      })
+      return preview.dependents
+    } catch (e) {
+      logError(`Failed to preview scenery deletion: ${getErrorMessage(e)}`, 'scenery')
+      return []
+    }
+  }
+
+  // Enabled packages that depend on a library; advisory, so failures yield an empty list
+  async function getLibraryDependents(libraryFolder: string): Promise<LibraryDependent[]> {
+    if (!appStore.xplanePath) return []
+
+    try {
+      return await invoke<LibraryDependent[]>('get_library_dependents', {
+        xplanePath: appStore.xplanePath,
+        libraryFolder,
+      })
+    } catch (e) {
+      logError(`Failed to get library dependents: ${getErrorMessage(e)}`, 'scenery')
+      return []
+    }
+  }
+
+  // Libraries a package requires, with install/enable status
+  async function getPackageDependencies(folderName: string): Promise<PackageLibraryDependency[]> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    return await invoke<PackageLibraryDependency[]>('get_package_dependencies', {
+      xplanePath: appStore.xplanePath,
+      folderName,
+    })
+  }
+
+  // Check scenery_packs.ini order; with applyFixes only the offending entries move
+  async function validateSceneryOrder(applyFixes = false): Promise<SceneryOrderReport> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    const report = await invoke<SceneryOrderReport>('validate_scenery_order', {
+      xplanePath: appStore.xplanePath,
+      applyFixes,
+    })
+    if (report.fixesApplied) {
+      await loadData()
+    }
+    return report
+  }
+
+  // Check scenery_packs.ini for structural and encoding problems
+  async function validateSceneryPacksIni(): Promise<SceneryPacksIniReport> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    return await invoke<SceneryPacksIniReport>('validate_scenery_packs_ini', {
+      xplanePath: appStore.xplanePath,
+    })
+  }
+
+  // Rebuild scenery_packs.ini from the index or clean it in place (previous file kept as .bak)
+  async function repairSceneryPacksIni(
+    strategy: SceneryPacksRepairStrategy,
+  ): Promise<SceneryPacksIniRepairResult> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    const result = await invoke<SceneryPacksIniRepairResult>('repair_scenery_packs_ini', {
+      xplanePath: appStore.xplanePath,
+      strategy,
+    })
+    if (result.changed) {
+      await loadData()
+    }
+    return result
+  }
+
+  // Copy, move or link packages of another Custom Scenery folder into this install
+  async function importSceneryFromExternal(
+    sourceCustomSceneryPath: string,
+    mode: SceneryImportMode,
+    folderNames?: string[],
+  ): Promise<SceneryImportResult> {
+    if (!validateXPlanePath(error)) {
+      throw new Error(error.value!)
+    }
+
+    const result = await invoke<SceneryImportResult>('import_scenery_from_external', {
+      sourceCustomSceneryPath,
+      xplanePath: appStore.xplanePath,
+      folderNames: folderNames ?? null,
+      mode,
+    })
+    if (result.imported.length > 0) {
+      await loadData()
+    }
+    return result
+  }
+
   // Clear store state
   function clear() {
     data.value = null
@@ -492,15 +791,31 @@ export const useSceneryStore = defineStore('scenery', () => {
     // Actions
     initStore,
     loadData,
+    loadSummary,
+    loadPage,
+    startWatcher,
+    stopWatcher,
     loadIndexStatus,
+    listKnownInstalls,
     resetDatabase,
+    runDatabaseMaintenance,
     toggleEnabled,
     updateCategory,
+    setPinned,
     moveEntry,
     reorderEntries,
     applyChanges,
     resetChanges,
+    applyInstalledPackage,
     deleteEntry,
+    previewDeletion,
+    createShortcut,
+    getLibraryDependents,
+    getPackageDependencies,
+    validateSceneryOrder,
+    validateSceneryPacksIni,
+    repairSceneryPacksIni,
+    importSceneryFromExternal,
     clear,
   }
 })
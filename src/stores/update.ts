@@ -1,9 +1,10 @@
 import { defineStore } from 'pinia'
 import { ref } from 'vue'
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { check } from '@tauri-apps/plugin-updater'
 import { relaunch } from '@tauri-apps/plugin-process'
-import type { UpdateInfo } from '@/types'
+import type { DownloadedUpdate, UpdateDownloadProgress, UpdateInfo } from '@/types'
 import { useToastStore } from './toast'
 import { useModalStore } from './modal'
 import { i18n } from '@/i18n'
@@ -37,6 +38,7 @@ export const useUpdateStore = defineStore('update', () => {
   const downloadProgress = ref(0)
   const downloadedBytes = ref(0)
   const totalBytes = ref(0)
+  const downloadSpeed = ref(0)
   const updateError = ref<string | null>(null)
   const updatePhase = ref<'idle' | 'downloading' | 'installing' | 'restarting'>('idle')
 
@@ -355,6 +357,7 @@ export const useUpdateStore = defineStore('update', () => {
     downloadProgress.value = 0
     downloadedBytes.value = 0
     totalBytes.value = 0
+    downloadSpeed.value = 0
     updateError.value = null
     updatePhase.value = 'idle'
   }
@@ -432,6 +435,52 @@ export const useUpdateStore = defineStore('update', () => {
     }
   }
 
+  /**
+   * Download the installer with the backend's resumable downloader and launch it.
+   * An interrupted download resumes on the next call instead of starting over.
+   */
+  async function downloadAndLaunchInstaller() {
+    if (isDownloading.value) return
+
+    resetUpdateState()
+    isDownloading.value = true
+    updatePhase.value = 'downloading'
+    logBasic('Starting resumable installer download', 'update')
+
+    const unlisten = await listen<UpdateDownloadProgress>('update-download-progress', (event) => {
+      downloadedBytes.value = event.payload.downloadedBytes
+      totalBytes.value = event.payload.totalBytes
+      downloadProgress.value = Math.round(event.payload.percentage)
+      downloadSpeed.value = event.payload.speedBytesPerSec
+    })
+
+    try {
+      const result = await invoke<DownloadedUpdate>('download_update', {
+        includePreRelease: includePreRelease.value,
+      })
+      logBasic(
+        `Installer for v${result.version} downloaded${result.resumed ? ' (resumed)' : ''}, launching`,
+        'update',
+      )
+      downloadProgress.value = 100
+      updatePhase.value = 'installing'
+      await invokeVoidCommand('install_downloaded_update', { filePath: result.filePath })
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error)
+      logError(`Installer download failed: ${message}`, 'update')
+      updateError.value = message
+      updatePhase.value = 'idle'
+    } finally {
+      unlisten()
+      isDownloading.value = false
+    }
+  }
+
+  async function cancelInstallerDownload() {
+    logBasic('User cancelled installer download', 'update')
+    await invokeVoidCommand('cancel_update_download')
+  }
+
   return {
     updateInfo,
     showUpdateBanner,
@@ -448,6 +497,7 @@ export const useUpdateStore = defineStore('update', () => {
     downloadProgress,
     downloadedBytes,
     totalBytes,
+    downloadSpeed,
     updateError,
     updatePhase,
     initStore,
@@ -460,6 +510,8 @@ export const useUpdateStore = defineStore('update', () => {
     toggleAutoCheck,
     toggleIncludePreRelease,
     performUpdate,
+    downloadAndLaunchInstaller,
+    cancelInstallerDownload,
     resetUpdateState,
   }
 })
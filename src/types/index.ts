@@ -1,3 +1,5 @@
+import { localizeBackendMessage } from '@/i18n'
+
 // ========== API Error Types ==========
 
 /** Structured error codes matching backend ApiErrorCode */
@@ -13,15 +15,26 @@ export type ApiErrorCode =
   | 'incorrect_password'
   | 'cancelled'
   | 'insufficient_space'
+  | 'disk_full'
+  | 'archive_corrupt'
   | 'security_violation'
   | 'timeout'
+  | 'network_timeout'
+  | 'database_error'
+  | 'migration_failed'
+  | 'updater_profile_unsupported'
   | 'internal'
 
 /** Structured API error from backend */
 export interface ApiError {
   code: ApiErrorCode
+  /** English message, also the fallback when messageKey has no translation */
   message: string
   details?: string
+  /** Key of the message in errors.messages, for backend catalog messages */
+  messageKey?: string
+  /** Positional arguments of the catalog message */
+  messageArgs?: string[]
 }
 
 function isRecord(value: unknown): value is Record<string, unknown> {
@@ -32,12 +45,16 @@ function parseApiErrorObject(value: Record<string, unknown>): ApiError | null {
   const code = value.code
   const message = value.message
   const details = value.details
+  const messageKey = value.message_key
+  const messageArgs = value.message_args
 
   if (typeof code === 'string' && typeof message === 'string') {
     return {
       code: code as ApiErrorCode,
       message,
       details: typeof details === 'string' ? details : undefined,
+      messageKey: typeof messageKey === 'string' ? messageKey : undefined,
+      messageArgs: Array.isArray(messageArgs) ? messageArgs.map(String) : undefined,
     }
   }
 
@@ -99,7 +116,7 @@ export function isApiErrorCode(error: unknown, code: ApiErrorCode): boolean {
 export function getErrorMessage(error: unknown): string {
   const apiError = parseApiError(error)
   if (apiError) {
-    return apiError.message
+    return localizeBackendMessage(apiError.messageKey, apiError.messageArgs, apiError.message)
   }
   if (typeof error === 'string') {
     return error
@@ -167,7 +184,16 @@ export interface VersionInfo {
   version?: string
 }
 
+/** User changes to a detected item before `build_install_tasks` turns it into a task */
+export interface InstallTaskOverride {
+  /** Replaces the detected name, which is also the folder name installed to */
+  displayName?: string
+  /** For liveries: aircraft folder to install into, relative to Aircraft/ */
+  liveryTargetFolder?: string
+}
+
 export interface InstallTask {
+  /** Stable across re-analysis of the same input (hash of source path, location and type) */
   id: string
   type: AddonType
   sourcePath: string
@@ -180,6 +206,10 @@ export interface InstallTask {
   extractionChain?: ExtractionChain
   /** Whether to overwrite existing folder (delete before install) */
   shouldOverwrite?: boolean
+  /** Rename archive files that differ only by case instead of failing on case-insensitive volumes */
+  allowCaseCollisionRename?: boolean
+  /** Create archive symlinks that point inside the install target instead of skipping them */
+  allowSymlinks?: boolean
   /** Password for encrypted archives */
   password?: string
   /** Estimated uncompressed size in bytes (for archives) */
@@ -192,6 +222,10 @@ export interface InstallTask {
   existingNavdataInfo?: NavdataInfo
   /** For Navdata: new cycle info to be installed */
   newNavdataInfo?: NavdataInfo
+  /** For Navdata: the newer cycle X-Plane uses now, when the new one is older */
+  navdataDowngradeFrom?: string
+  /** For Navdata: install an older cycle over a newer one (a downgrade fails otherwise) */
+  allowDowngrade?: boolean
   /** For Aircraft/Plugin: existing version info (if conflict exists) */
   existingVersionInfo?: VersionInfo
   /** For Aircraft/Plugin: new version info to be installed */
@@ -208,10 +242,88 @@ export interface InstallTask {
   liveryAircraftType?: string
   /** For Livery: whether the target aircraft is installed */
   liveryAircraftFound?: boolean
+  /** For Livery: installed aircraft folders the livery fits, relative to Aircraft/ */
+  liveryTargetCandidates?: string[]
+  /** For Livery: folder chosen among liveryTargetCandidates; required when there are several */
+  targetAircraftFolder?: string
+  /** Why the task can't be installed (e.g. no matching aircraft is installed) */
+  notInstallableReason?: string
   /** For LuaScript: whether FlyWithLua plugin is installed */
   flyWithLuaInstalled?: boolean
-  /** For LuaScript: companion files/folders referenced by SCRIPT_DIRECTORY */
+  /** For LuaScript: data folders, Modules and required files installed with the script */
   companionPaths?: string[]
+  /** Whether the addon is expected to work with the target X-Plane version */
+  compatibility?: AddonCompatibility
+  /** Updater metadata shipped with the addon */
+  updaterProvider?: UpdaterProvider
+  /** For Scenery: the root folder the analyzer suggests */
+  suggestedRootKind?: TargetRootKind
+  /** For Scenery: the root folder to install into; Global Scenery is not added to scenery_packs.ini */
+  targetRootKind?: TargetRootKind
+  /** Identity the install profile of this addon is kept under */
+  profileIdentity?: string
+  /** Options saved by the last install of this addon, for pre-filling */
+  installProfile?: InstallProfile
+}
+
+/** Options chosen on the last successful install of an addon */
+export interface InstallProfile {
+  identity: string
+  /** Name installed under; differs from the detected name when it was renamed */
+  displayName: string
+  shouldOverwrite: boolean
+  backupLiveries: boolean
+  backupConfigFiles: boolean
+  backupNavdata: boolean
+  targetAircraftFolder?: string
+  targetRootKind: TargetRootKind
+  /** Unix seconds */
+  updatedAt: number
+}
+
+/** X-Plane folder a scenery package installs into */
+export type TargetRootKind = 'customScenery' | 'globalScenery'
+
+/** Updater an addon ships its own metadata for */
+export type UpdaterProvider = 'skunkcrafts' | 'x-updater' | 'none'
+
+export type AddonCompatibility =
+  | { status: 'compatible' }
+  | { status: 'likelyIncompatible'; reason: string }
+  | { status: 'unknown' }
+
+export interface CompatibilityWarning {
+  taskId: string
+  displayName: string
+  reason: string
+  /** Key to pass to `add_analysis_suppression` to hide this warning */
+  suppressionKey: string
+}
+
+/** Why a marker file seen during a scan didn't become an addon */
+export type SkippedMarkerReason = 'ignored' | 'insidePlugin' | 'insideAircraft' | 'unrecognized'
+
+export interface SkippedMarker {
+  path: string
+  reason: SkippedMarkerReason
+}
+
+/** What the scan of an input that produced no addons saw */
+export interface ScanDiagnostics {
+  inputPath: string
+  isArchive: boolean
+  /** Top-level entries of an archive, at most 50 */
+  topLevelEntries: string[]
+  topLevelTruncated: boolean
+  entryCount: number
+  encrypted: boolean
+  /** Skipped marker files, at most 50 */
+  skippedMarkers: SkippedMarker[]
+  skippedMarkerCount: number
+  nestedArchivesTooDeep: number
+  maxDepthReached: number
+  foldersTooDeep: number
+  ignoredPathCount: number
 }
 
 export interface AnalysisResult {
@@ -221,15 +333,82 @@ export interface AnalysisResult {
   passwordRequired: string[]
   /** Map of nested archive paths to their parent archive */
   nestedPasswordRequired?: Record<string, string>
+  /** Major version of the target X-Plane installation, if detected */
+  xplaneMajorVersion?: number | null
+  /** Tasks that look incompatible with the target X-Plane version */
+  compatibilityWarnings?: CompatibilityWarning[]
+  /** Compatibility warnings hidden by the user's suppressions */
+  suppressedWarningCount?: number
+  /** Per-input diagnostics for inputs that produced no addons */
+  diagnostics?: ScanDiagnostics[]
 }
 
+/** Result of `download_and_analyze` for an addon fetched from a URL */
+export interface UrlDownloadAnalysis {
+  /** Saved archive; pass the analysis tasks to `install_addons` next */
+  localPath: string
+  fileName: string
+  sizeBytes: number
+  analysis: AnalysisResult
+}
+
+export type DownloadQueueStatus = 'queued' | 'downloading' | 'analyzing' | 'completed' | 'failed'
+
+/** Item of the download queue, also the payload of `download-queue-progress` events */
+export interface DownloadQueueItem {
+  id: number
+  url: string
+  xplanePath: string
+  status: DownloadQueueStatus
+  fileName: string | null
+  /** Downloaded file, set once the download finished */
+  localPath: string | null
+  downloadedBytes: number
+  totalBytes: number | null
+  /** Why the last attempt failed; retry with `retry_download` */
+  errorMessage: string | null
+  /** Set once the status is `completed` */
+  analysis: AnalysisResult | null
+  createdAt: number
+  updatedAt: number
+}
+
+/** Why an install interrupted by a crash was kept for review */
+export type OrphanedInstallReason =
+  | 'targetExisted'
+  | 'looksComplete'
+  | 'keptInPlace'
+  | 'cleanupFailed'
+  | 'setAside'
+
+/** Install a crash or a killed process interrupted, from `get_orphaned_installs` */
+export interface OrphanedInstall {
+  id: number
+  taskId: string
+  displayName: string
+  addonType: AddonType
+  targetPath: string
+  reason: OrphanedInstallReason
+  /** Unix seconds */
+  startedAt: number
+}
+
+/** Action for `resolve_orphaned_install` */
+export type OrphanedInstallAction = 'keep' | 'delete'
+
 export interface ConflictInfo {
   task: InstallTask
   existingVersion?: string
   newVersion?: string
 }
 
-export type InstallPhase = 'calculating' | 'installing' | 'verifying' | 'finalizing'
+export type InstallPhase =
+  | 'calculating'
+  | 'preflightCheck'
+  | 'installing'
+  | 'verifying'
+  | 'finalizing'
+  | 'waiting'
 
 export interface InstallProgress {
   percentage: number
@@ -240,7 +419,7 @@ export interface InstallProgress {
   currentTaskName: string
   currentFile?: string | null
   phase: InstallPhase
-  /** Verification progress (0-100), only present during verifying phase */
+  /** Verification progress (0-100), only present during verifying and preflightCheck phases */
   verificationProgress?: number
   /** Current task progress percentage (0-100), represents progress of current task only */
   currentTaskPercentage: number
@@ -254,6 +433,11 @@ export interface InstallProgress {
   completedTaskCount?: number
   /** IDs of completed tasks in parallel mode */
   completedTaskIds?: string[]
+  inSolidBlock?: boolean
+  /** Overall throughput over the last few seconds */
+  speedBytesPerSec?: number
+  /** Estimated seconds remaining; absent while calculating or verifying */
+  etaSeconds?: number
 }
 
 export interface ParallelTaskProgress {
@@ -263,6 +447,9 @@ export interface ParallelTaskProgress {
   phase: InstallPhase
   percentage: number
   currentFile?: string | null
+  inSolidBlock?: boolean
+  speedBytesPerSec?: number
+  etaSeconds?: number
 }
 
 export interface TaskResult {
@@ -270,6 +457,86 @@ export interface TaskResult {
   taskName: string
   success: boolean
   errorMessage?: string
+  /** Key of errorMessage in errors.messages, when it is a backend catalog message */
+  errorMessageKey?: string
+  /** Positional arguments of the catalog message */
+  errorMessageArgs?: string[]
+  /** Machine-readable failure code; `cancelled` for cancelled or skipped tasks */
+  errorCode?: ApiErrorCode
+  /** Bytes read by the pre-flight source check, when enabled */
+  preflightBytesRead?: number
+  /** Atomic install step that failed; the previous installation is left in place */
+  failedStage?: AtomicInstallStage
+  /** Non-fatal problems, such as archive files renamed to avoid a case collision */
+  warnings?: string[]
+  /** Not installed because the same archive is already installed unchanged */
+  skippedIdentical?: boolean
+  /** Archive entry whose extraction failed the task */
+  failureDetail?: ExtractionFailureDetail
+}
+
+/** Step of extracting one archive entry that failed */
+export type ExtractionFailureStage = 'read' | 'write' | 'crc'
+
+/** Where and how extracting an archive entry failed */
+export interface ExtractionFailureDetail {
+  archivePath: string
+  /** Path of the entry inside the archive */
+  entryPath: string
+  entryIndex?: number
+  compressedSize?: number
+  uncompressedSize?: number
+  stage: ExtractionFailureStage
+  /** The underlying error */
+  error: string
+  /** Entries that failed to write before the target disk was given up on as failing or full */
+  failedWrites: number
+}
+
+/** What `install_addons` does with tasks already installed unchanged */
+export type DuplicateInstallAction = 'reinstall' | 'skip' | 'ask'
+
+/** A task whose archive was installed before and whose target is unchanged */
+export interface IdenticalInstall {
+  taskId: string
+  displayName: string
+  targetPath: string
+  installedAt: number
+}
+
+/** Payload of the `install-duplicates-detected` event */
+export interface DuplicateInstallPrompt {
+  /** Pass to `respond_duplicate_install` */
+  requestId: string
+  tasks: IdenticalInstall[]
+  /** Seconds until the tasks are reinstalled without an answer */
+  timeoutSecs: number
+}
+
+export type AtomicInstallStage = 'staging' | 'verifying' | 'backup' | 'swap'
+
+export type InstallHistoryStatus = 'installed' | 'replaced' | 'uninstalled'
+
+/** An addon installed through XFast */
+export interface InstallHistoryEntry {
+  id: number
+  addonType: AddonType
+  displayName: string
+  targetPath: string
+  /** File name of the archive or folder it was installed from */
+  sourceName?: string | null
+  /** Unix seconds */
+  installedAt: number
+  bytes?: number | null
+  manifestPath?: string | null
+  status: InstallHistoryStatus
+  uninstalledAt?: number | null
+}
+
+export interface UninstallFromHistoryResult {
+  entry: InstallHistoryEntry
+  /** The target was already gone, so only the history and index were updated */
+  alreadyRemoved: boolean
 }
 
 export interface InstallResult {
@@ -288,6 +555,194 @@ export interface UpdateInfo {
   publishedAt: string
 }
 
+export interface UpdateDownloadProgress {
+  version: string
+  fileName: string
+  downloadedBytes: number
+  totalBytes: number
+  /** Bytes kept from an earlier interrupted attempt */
+  resumedFromBytes: number
+  speedBytesPerSec: number
+  percentage: number
+}
+
+/** Settings persisted by the backend (`backend_settings.json`) */
+export interface BackendSettings {
+  version: number
+  offlineMode: boolean
+  /** Largest file accepted by `download_and_analyze`, in bytes */
+  maxUrlDownloadBytes: number
+  /** Repeat the last full maintenance run weekly while idle and on AC power */
+  autoMaintenance: boolean
+  /** X-Plane log snapshots kept per installation */
+  logHistoryLimit: number
+  /** Check scenery_packs.ini order after installing scenery and report violations */
+  validateSceneryOrderAfterInstall: boolean
+  /** Proxy, certificate and timeout settings for outbound HTTP requests */
+  network: NetworkSettings
+  /** Folder installs and extractions stage in; the system temp folder when null */
+  stagingDir: string | null
+  /** Update channel chosen for X-Updater addons, keyed by addon folder path */
+  addonUpdateChannels: Record<string, string>
+  /** Fields from newer app versions; send them back unchanged when saving */
+  [key: string]: unknown
+}
+
+/** Staging folder (`get_staging_config` / `set_staging_config`) */
+export interface StagingConfig {
+  /** Folder chosen by the user; null for the system temp folder */
+  stagingDir: string | null
+  /** Folder staging uses right now */
+  effectiveDir: string
+  /** The chosen folder is missing, so the system temp folder is used */
+  usingFallback: boolean
+}
+
+/** Outbound HTTP settings (`get_network_settings` / `set_network_settings`) */
+export interface NetworkSettings {
+  /** e.g. `http://proxy:8080` or `socks5://127.0.0.1:1080` */
+  proxyUrl: string | null
+  proxyUsername: string | null
+  /** A proxy password is saved; the password itself is never sent */
+  hasProxyPassword: boolean
+  /**
+   * Write-only: a new password to save. Leave it out to keep the saved one,
+   * send an empty string to clear it.
+   */
+  proxyPassword?: string | null
+  /** Use the OS/environment proxy when no proxy URL is set */
+  useSystemProxy: boolean
+  /** Extra trusted root certificate(s), PEM encoded */
+  extraRootCertificatePem: string | null
+  /** Replaces the built-in request timeouts, in seconds */
+  timeoutSecs: number | null
+}
+
+/** Bug report or feedback saved on disk because it could not be sent yet */
+export interface QueuedSubmission {
+  pendingId: string
+  /** Seconds until the next delivery attempt */
+  retryAfterSecs: number
+  /** Why the submission could not be sent */
+  reason: string
+}
+
+/** Result of `test_network_settings` */
+export interface NetworkTestResult {
+  url: string
+  /** Any HTTP status means the endpoint was reached */
+  status: number
+  latencyMs: number
+}
+
+export interface LiveryRuleInfo {
+  /** 'path', 'file' or 'regex' */
+  patternType: string
+  pattern: string
+  rootDepth: number | null
+  /** Pass to remove_livery_pattern; null for built-in rules */
+  userIndex: number | null
+}
+
+export interface LiveryPatternInfo {
+  aircraftTypeId: string
+  aircraftName: string
+  rules: LiveryRuleInfo[]
+}
+
+export type StorageCategory =
+  | 'database'
+  | 'logs'
+  | 'settings'
+  | 'caches'
+  | 'submissions'
+  | 'credentials'
+  | 'backups'
+  | 'xplaneFiles'
+  | 'other'
+
+export interface StoredDataEntry {
+  category: StorageCategory
+  path: string
+  description: string
+  sizeBytes: number
+  /** 'clearCredentials' removes the login lines and keeps the file */
+  action: 'delete' | 'clearCredentials'
+  inXplaneFolder: boolean
+}
+
+export interface StoredDataCategory {
+  category: StorageCategory
+  sizeBytes: number
+  entries: StoredDataEntry[]
+}
+
+export interface StoredDataSummary {
+  appDataDir: string
+  categories: StoredDataCategory[]
+  /** Files XFast wrote inside X-Plane folders; erased only when confirmed separately */
+  xplaneFiles: StoredDataEntry[]
+}
+
+/** Zip backup of X-Plane's Output/preferences folder */
+export interface PreferenceBackup {
+  id: string
+  label?: string | null
+  /** X-Plane installation the preferences were taken from */
+  xplanePath: string
+  /** Unix seconds */
+  createdAt: number
+  /** Size of the zip */
+  sizeBytes: number
+  fileCount: number
+  /** Uncompressed size of the backed up files */
+  totalBytes: number
+}
+
+export interface PreferenceRestoreResult {
+  backup: PreferenceBackup
+  restoredPath: string
+  /** Where the preferences in place before the restore were moved */
+  previousPath?: string | null
+}
+
+/** Files of an addon as they were before an update */
+export interface AddonBackup {
+  id: string
+  itemType: string
+  folderName: string
+  fromVersion?: string | null
+  toVersion?: string | null
+  /** Unix seconds */
+  createdAt: number
+  fileCount: number
+  totalBytes: number
+}
+
+export interface AddonBackupRestoreResult {
+  backup: AddonBackup
+  /** False when files changed since the update and the restore wasn't forced */
+  restored: boolean
+  modifiedFiles: string[]
+  restoredFiles: number
+  removedFiles: number
+}
+
+export interface EraseAppDataResult {
+  dryRun: boolean
+  removed: StoredDataEntry[]
+  failed: { path: string; error: string }[]
+  freedBytes: number
+}
+
+export interface DownloadedUpdate {
+  version: string
+  filePath: string
+  sizeBytes: number
+  sha256: string
+  resumed: boolean
+}
+
 // ========== Scenery Auto-Sorting Types ==========
 
 export enum SceneryCategory {
@@ -297,6 +752,7 @@ export enum SceneryCategory {
   Library = 'Library',
   Overlay = 'Overlay',
   AirportMesh = 'AirportMesh',
+  OrthoTile = 'OrthoTile',
   Mesh = 'Mesh',
   Other = 'Other',
   Unrecognized = 'Unrecognized',
@@ -314,24 +770,149 @@ export interface SceneryPackageInfo {
   hasTextures: boolean
   hasObjects: boolean
   textureCount: number
+  totalSizeBytes: number
+  newestFileModified?: number
   indexedAt: number
   requiredLibraries: string[]
   missingLibraries: string[]
   enabled: boolean
   sortOrder: number
+  /** Pinned by the user: automatic sorting keeps it at its current position */
+  pinned: boolean
 }
 
 export interface SceneryIndexStats {
   totalPackages: number
   byCategory: Record<string, number>
+  /** Package count per continent; packages without one count as "Unknown" */
+  byRegion: Record<string, number>
   lastUpdated: number
 }
 
+export interface SceneryCategoryStats {
+  category: string
+  packageCount: number
+  totalSizeBytes: number
+}
+
+/** Aggregate scenery statistics at one point in time (takenAt is a Unix timestamp in seconds) */
+export interface SceneryStatsSnapshot {
+  takenAt: number
+  totalPackages: number
+  totalSizeBytes: number
+  missingLibraryCount: number
+  categories: SceneryCategoryStats[]
+}
+
+/** Issue summary of one X-Plane session's Log.txt (timestamps are Unix seconds) */
+export interface XPlaneLogSnapshot {
+  recordedAt: number
+  /** When Log.txt was last written, i.e. when the session ended */
+  logModifiedAt: number
+  logSize: number
+  xplaneVersion: string | null
+  crashDetected: boolean
+  /** Matching line count per issue category */
+  categoryCounts: Record<string, number>
+  totalHigh: number
+  totalMedium: number
+  totalLow: number
+}
+
+export type AnalysisSuppressionKind = 'duplicate_plugin' | 'airport_conflict' | 'compatibility'
+
+/** Analysis warning the user chose to hide */
+export interface AnalysisSuppression {
+  id: number
+  kind: AnalysisSuppressionKind
+  /** Stable identifier of the warning, e.g. `airport_conflict:EGKK` */
+  key: string
+  /** Unix seconds */
+  createdAt: number
+}
+
+/** Payload of the `scenery-index-progress` event emitted while the index is rebuilt */
+export interface SceneryIndexProgress {
+  processed: number
+  total: number
+  /** Folder classified last; null in the final event */
+  currentFolder: string | null
+}
+
 export interface SceneryIndexStatus {
   indexExists: boolean
   totalPackages: number
 }
 
+export interface XPlaneVersion {
+  major: number
+  minor: number
+  patch: number
+  revision?: string
+  build?: string
+  raw: string
+  source: 'windowsExecutable' | 'macInfoPlist' | 'linuxBinary' | 'logFile'
+  sourcePath: string
+}
+
+/** An X-Plane installation used with XFast before */
+export interface KnownXPlaneInstall {
+  path: string
+  exists: boolean
+  indexedPackageCount: number
+  version?: XPlaneVersion
+}
+
+/** Running X-Plane process, from `is_xplane_running` */
+export interface XPlaneProcess {
+  pid: number
+  executablePath?: string | null
+  /** Known installation the process runs from */
+  matchedInstall?: string | null
+  /** Started by `launch_xplane` in this session */
+  launchedByApp: boolean
+}
+
+/** Outcome of `run_database_maintenance` */
+export interface DatabaseMaintenanceReport {
+  integrityOk: boolean
+  integrityMessages: string[]
+  /** Integrity problems plus orphaned rows found */
+  issuesFound: number
+  orphanedRowsRemoved: number
+  /** Share of the database that was free pages before the run */
+  freePageRatio: number
+  vacuumed: boolean
+  sizeBeforeBytes: number
+  sizeAfterBytes: number
+}
+
+/** Problem with where X-Plane is installed, from `diagnose_xplane_location` */
+export type XPlaneLocationIssue = 'programFiles' | 'cloudSync' | 'notWritable' | 'readOnlyFiles'
+
+export type SyncProvider = 'oneDrive' | 'dropbox' | 'googleDrive' | 'other'
+
+export interface WriteProbeResult {
+  /** Folder relative to the X-Plane root */
+  folder: string
+  exists: boolean
+  writable: boolean
+  error?: string
+  /** Raw OS error, e.g. 5 for access denied on Windows */
+  osError?: number
+}
+
+export interface XPlaneLocationReport {
+  xplanePath: string
+  issues: XPlaneLocationIssue[]
+  inProgramFiles: boolean
+  syncProvider?: SyncProvider
+  syncRoot?: string
+  writeProbes: WriteProbeResult[]
+  readOnlyFileCount: number
+  readOnlyExamples: string[]
+}
+
 export interface SceneryIndexScanResult {
   indexExists: boolean
   added: string[]
@@ -339,6 +920,14 @@ export interface SceneryIndexScanResult {
   updated: string[]
 }
 
+/** Payload of the scenery-index-changed event sent by the background watcher */
+export interface SceneryIndexChangedEvent {
+  xplanePath: string
+  added: string[]
+  removed: string[]
+  updated: string[]
+}
+
 export interface SceneryManagerEntry {
   folderName: string
   category: SceneryCategory
@@ -353,6 +942,8 @@ export interface SceneryManagerEntry {
   duplicateAirports: string[]
   airportId?: string
   originalCategory?: SceneryCategory
+  /** Automatic sorting keeps this entry at its current position */
+  pinned: boolean
 }
 
 export interface SceneryManagerData {
@@ -365,6 +956,276 @@ export interface SceneryManagerData {
   needsSync: boolean
   /** Raw tile overlap data (all overlaps, before XPME filtering) for real-time recalculation */
   tileOverlaps: Record<string, string[]>
+  /** Links and shortcuts in Custom Scenery whose target is missing */
+  brokenEntries: BrokenSceneryEntry[]
+  /** Conflicting airport identifiers hidden by the user's suppressions */
+  suppressedAirportConflictCount: number
+}
+
+export type SceneryLinkKind = 'link' | 'shortcut'
+
+export interface BrokenSceneryEntry {
+  /** Name in Custom Scenery, including `.lnk` for shortcuts */
+  entryName: string
+  targetPath?: string | null
+  kind: SceneryLinkKind
+}
+
+export type SceneryManagerSortField =
+  | 'sortOrder'
+  | 'folderName'
+  | 'category'
+  | 'enabled'
+  | 'continent'
+
+export interface SceneryManagerSort {
+  field?: SceneryManagerSortField
+  descending?: boolean
+}
+
+export interface SceneryManagerFilter {
+  category?: SceneryCategory
+  enabled?: boolean
+  /** Case-insensitive substring of the folder name */
+  search?: string
+  /** Continent name, as in `SceneryManagerEntry.continent` */
+  continent?: string
+}
+
+/** One page of scenery entries (real packages only, with their stored sort order) */
+export interface SceneryManagerPage {
+  entries: SceneryManagerEntry[]
+  offset: number
+  limit: number
+  /** Number of packages matching the filter, across all pages */
+  totalCount: number
+}
+
+export interface AirportSearchResult {
+  icao: string
+  name: string
+  latitude: number
+  longitude: number
+  /** Package whose definition X-Plane uses ('*GLOBAL_AIRPORTS*' for the default airports) */
+  package: string
+  /** Lower-priority packages that also define this airport */
+  otherPackages: string[]
+}
+
+/** An enabled package that requires library names exported by a given library */
+export interface LibraryDependent {
+  folderName: string
+  category: SceneryCategory
+  affectedLibraryCount: number
+  /** Affected names no other enabled package exports */
+  exclusiveLibraryCount: number
+  /** True when every affected name would become missing without the library */
+  exclusive: boolean
+}
+
+/** What deleting a scenery folder would break, from `preview_scenery_deletion` */
+export interface SceneryDeletionPreview {
+  folderName: string
+  /** Enabled packages requiring names only this folder exports */
+  dependents: LibraryDependent[]
+}
+
+/** A required library name that no installed package exports anymore */
+export interface NewlyMissingLibrary {
+  /** Package requiring the library */
+  folderName: string
+  libraryName: string
+}
+
+/** Outcome of `delete_scenery_folder` */
+export interface SceneryDeletionResult {
+  folderName: string
+  /** Library references the deletion left unresolved, in scenery order */
+  newlyMissingLibraries: NewlyMissingLibrary[]
+  /** scenery_packs.ini is updated once X-Plane closes */
+  iniDeferred: boolean
+}
+
+/** When scenery_packs.ini is written while X-Plane may be running */
+export type SceneryApplyMode = 'immediate' | 'deferUntilSimCloses' | 'failIfRunning'
+
+/** Payload of the `deferred-scenery-applied` event */
+export interface DeferredSceneryApplied {
+  xplanePath: string
+  entriesUpdated: number
+  deletedFolders: string[]
+  /** When the first of the changes was queued (unix seconds) */
+  queuedAt: number
+  /** Why the ini could not be written, when it couldn't */
+  error?: string
+}
+
+export type LibraryDependencyStatus = 'enabled' | 'disabled' | 'missing' | 'bundled'
+
+/** A library required by a package, with the packages that export it */
+export interface PackageLibraryDependency {
+  libraryName: string
+  status: LibraryDependencyStatus
+  /** Exporting packages, enabled first, in scenery order */
+  providers: string[]
+}
+
+/** A missing library in the plan returned by `build_missing_library_plan` */
+export interface MissingLibraryPlanEntry {
+  libraryName: string
+  /** Packages whose library.txt references the name, in scenery order */
+  referencedBy: string[]
+  /** Packages that only need it through an installed library that references it */
+  indirectlyRequiredBy: string[]
+  downloadUrl: string | null
+  /** Installed folder that probably provides it under a different name */
+  installedAs: string | null
+}
+
+export type SceneryOrderViolationKind = 'categoryOrder' | 'libraryDependency'
+
+/** An enabled scenery_packs.ini entry placed on the wrong side of another */
+export interface SceneryOrderViolation {
+  kind: SceneryOrderViolationKind
+  folderName: string
+  category: SceneryCategory
+  otherFolderName: string
+  otherCategory: SceneryCategory
+  /** Whether `folderName` is currently above `otherFolderName` */
+  placedAbove: boolean
+  /** A library name `folderName` requires from `otherFolderName` */
+  libraryName: string | null
+  message: string
+}
+
+/** Result of `validate_scenery_order`, also sent as `scenery-order-violations` after installs */
+export interface SceneryOrderReport {
+  checkedCount: number
+  violations: SceneryOrderViolation[]
+  /** Entries moved to fix the violations, in their new order */
+  moved: string[]
+  fixesApplied: boolean
+}
+
+export interface SceneryPacksDiffEntry {
+  path: string
+  /** Enabled after the change; before it for removed entries */
+  enabled: boolean
+  oldPosition?: number | null
+  newPosition?: number | null
+}
+
+/** What writing scenery_packs.ini from the index would change */
+export interface SceneryPacksPreview {
+  xplanePath: string
+  iniExists: boolean
+  hasChanges: boolean
+  added: SceneryPacksDiffEntry[]
+  removed: SceneryPacksDiffEntry[]
+  /** Entries that would move, apart from those only shifted by others */
+  reordered: SceneryPacksDiffEntry[]
+  enabled: SceneryPacksDiffEntry[]
+  disabled: SceneryPacksDiffEntry[]
+  newContent: string
+}
+
+export type SceneryPacksIniIssueKind =
+  | 'missingFile'
+  | 'missingHeader'
+  | 'strayHeader'
+  | 'duplicateEntry'
+  | 'missingFolder'
+  | 'invalidUtf8'
+  | 'byteOrderMark'
+  | 'crlfLineEndings'
+  | 'truncated'
+
+/** Structural problem found in scenery_packs.ini by `validate_scenery_packs_ini` */
+export interface SceneryPacksIniIssue {
+  kind: SceneryPacksIniIssueKind
+  /** 1-based line number, when the issue belongs to one line */
+  line: number | null
+  content: string | null
+  message: string
+}
+
+/** Result of `validate_scenery_packs_ini` */
+export interface SceneryPacksIniReport {
+  iniPath: string
+  exists: boolean
+  entryCount: number
+  issues: SceneryPacksIniIssue[]
+}
+
+export type SceneryPacksRepairStrategy = 'rebuildFromIndex' | 'cleanInPlace'
+
+export type SceneryPacksIniChangeKind =
+  | 'headerRestored'
+  | 'byteOrderMarkRemoved'
+  | 'lineEndingsNormalized'
+  | 'finalNewlineAdded'
+  | 'lineRemoved'
+  | 'entryAdded'
+  | 'entryMoved'
+  | 'entryStateChanged'
+
+/** One difference between scenery_packs.ini before and after a repair */
+export interface SceneryPacksIniChange {
+  kind: SceneryPacksIniChangeKind
+  /** Issue of the line that caused the change, for removed lines */
+  reason: SceneryPacksIniIssueKind | null
+  oldLine: number | null
+  newLine: number | null
+  content: string | null
+}
+
+/** Result of `repair_scenery_packs_ini` */
+export interface SceneryPacksIniRepairResult {
+  strategy: SceneryPacksRepairStrategy
+  changed: boolean
+  /** Copy of the previous file, `scenery_packs.ini.bak` */
+  backupPath: string | null
+  changes: SceneryPacksIniChange[]
+  remainingIssues: SceneryPacksIniIssue[]
+}
+
+/** How `import_scenery_from_external` brings packages into Custom Scenery */
+export type SceneryImportMode = 'copy' | 'move' | 'link'
+
+export interface ImportedSceneryPackage {
+  folderName: string
+  category: SceneryCategory
+  /** Bytes copied; 0 for links and same-volume moves */
+  transferredBytes: number
+}
+
+export interface SceneryImportFailure {
+  folderName: string
+  message: string
+}
+
+export interface SceneryImportResult {
+  imported: ImportedSceneryPackage[]
+  /** Selected packages whose folder name already exists in Custom Scenery */
+  skippedExisting: string[]
+  failed: SceneryImportFailure[]
+  warnings: string[]
+  cancelled: boolean
+}
+
+export interface SceneryManagerSummary {
+  totalCount: number
+  enabledCount: number
+  missingDepsCount: number
+  duplicateTilesCount: number
+  duplicateAirportsCount: number
+  needsSync: boolean
+  /** Global Airports entry; placed before packages with the same sort order */
+  globalAirports: SceneryManagerEntry
+  /** Links and shortcuts in Custom Scenery whose target is missing */
+  brokenEntries: BrokenSceneryEntry[]
+  /** Conflicting airport identifiers hidden by the user's suppressions */
+  suppressedAirportConflictCount: number
 }
 
 // ========== Management Types ==========
@@ -378,6 +1239,8 @@ export interface AircraftInfo {
   hasMixedAcfStates: boolean
   hasLiveries: boolean
   liveryCount: number
+  sizeBytes: number | null
+  newestFileModified: number | null
   version?: string
   updateUrl?: string
   updateProvider?: 'skunkcrafts' | 'x-updater' | 'zibo'
@@ -397,6 +1260,49 @@ export interface LiveryInfo {
   iconPath: string | null
 }
 
+export interface LiveryDiskUsage {
+  folderName: string
+  sizeBytes: number
+  fileCount: number
+}
+
+export interface LiveryDeleteResult {
+  folderName: string
+  deleted: boolean
+  error: string | null
+}
+
+export interface PluginQuarantineResult {
+  moved: string[]
+  skipped: string[]
+  errors: string[]
+  remainingQuarantined: string[]
+}
+
+export interface RelocationResult {
+  sourcePath: string
+  destinationPath: string
+  fileCount: number
+  totalBytes: number
+  leftoverPath?: string
+  /** Links inside the folder that couldn't be recreated at the destination */
+  skippedLinks?: string[]
+}
+
+/** Platform binaries a plugin ships */
+export interface PluginPlatformBinaries {
+  winX64: boolean
+  macX64: boolean
+  /** Arm64 slice, usually inside the universal `mac_x64` binary */
+  macArm64: boolean
+  /** A Mac binary known by its path only, which may have an arm64 slice */
+  macArchUnknown: boolean
+  linX64: boolean
+  /** 32-bit binaries current X-Plane versions don't load */
+  legacy32bit: boolean
+  unrecognized: boolean
+}
+
 export interface PluginInfo {
   folderName: string
   displayName: string
@@ -411,6 +1317,11 @@ export interface PluginInfo {
   cfgDisabled?: boolean
   hasScripts: boolean
   scriptCount: number
+  sizeBytes: number | null
+  newestFileModified: number | null
+  platformBinaries: PluginPlatformBinaries
+  /** X-Plane can load one of the binaries on this operating system */
+  supportedOnCurrentPlatform: boolean
 }
 
 export type AddonUpdatableItemType = 'aircraft' | 'plugin' | 'scenery' | 'livery'
@@ -436,6 +1347,8 @@ export interface AddonUpdateOptions {
   chunkedDownloadEnabled?: boolean
   threadsPerTask?: number
   totalThreads?: number
+  /** Keep the files an update changes, up to this many backups per addon */
+  keepBackupVersions?: number
 }
 
 export interface AddonUpdatePreview {
@@ -449,6 +1362,17 @@ export interface AddonUpdatePreview {
   changelog?: string
 }
 
+export interface AddonUpdateChannel {
+  provider: string
+  itemType: string
+  folderName: string
+  channel: 'stable' | 'beta' | 'alpha' | string
+  availableChannels: string[]
+  targetVersion?: string | null
+  /** False for X-Updater addons, whose channel the app stores instead */
+  storedInAddon: boolean
+}
+
 export type AddonManualDownloadReason = 'drive-limit' | 'release-page'
 
 export type ZiboInstallMode = 'patch' | 'major-clean'
@@ -470,8 +1394,81 @@ export interface AddonUpdatePlan {
   replaceFiles: string[]
   deleteFiles: string[]
   skipFiles: string[]
+  skipped?: AddonSkippedPath[]
   warnings: string[]
   hasBetaConfig: boolean
+  /** Disk space and path length checks, for plans that write files */
+  preflight?: AddonUpdatePreflight
+}
+
+/** Problems found in a plan before anything is downloaded; each also appears in `warnings` */
+export interface AddonUpdatePreflight {
+  /** Downloads, plus rollback copies when the temp folder shares the addon's volume */
+  requiredBytes: number
+  freeBytes: number | null
+  /** Rollback copies needed on the temp folder's volume when it is a different one */
+  rollbackRequiredBytes: number
+  rollbackFreeBytes: number | null
+  /** Written with the Windows long path prefix */
+  longPaths: string[]
+  /** Names too long to write; the update fails */
+  longNames: string[]
+}
+
+export type AddonSkipReason =
+  | 'livery'
+  | 'ignored'
+  | 'copyOnce'
+  | 'unchanged'
+  | 'localDirectory'
+  | 'blacklisted'
+  | 'notWhitelisted'
+
+export interface AddonSkippedPath {
+  path: string
+  reason: AddonSkipReason
+  pattern?: string
+  delete: boolean
+}
+
+export interface AddonUpdateDiffFile {
+  /** Path relative to the addon folder */
+  path: string
+  localSize: number | null
+  remoteSize: number | null
+  /** Matches a config file pattern (*.cfg, *.ini) */
+  isConfig: boolean
+}
+
+export interface AddonUpdateDiffGroup {
+  /** Sorted by path; fewer than `count` when the diff is summarized */
+  files: AddonUpdateDiffFile[]
+  count: number
+  bytes: number
+}
+
+/** What an addon update would change in the local folder, from `get_addon_update_diff` */
+export interface AddonUpdateDiff {
+  provider: string
+  itemType: string
+  folderName: string
+  localVersion: string | null
+  remoteVersion: string | null
+  added: AddonUpdateDiffGroup
+  /** Replaced files whose local copy is the previous remote version */
+  updated: AddonUpdateDiffGroup
+  /** Replaced files whose local copy differs from the previous remote version */
+  modifiedLocally: AddonUpdateDiffGroup
+  /** Replaced files the manifest lists no previous hash for */
+  changed: AddonUpdateDiffGroup
+  deleted: AddonUpdateDiffGroup
+  /** Deleted files whose local copy differs from the version the manifest lists */
+  deletedModified: AddonUpdateDiffGroup
+  /** Local files the update neither writes nor deletes */
+  untouched: AddonUpdateDiffGroup
+  /** Config files the update overwrites or deletes, except unmodified ones */
+  configChanges: AddonUpdateDiffFile[]
+  summarized: boolean
 }
 
 export interface AddonUpdateResult {
@@ -486,11 +1483,46 @@ export interface AddonUpdateResult {
   deletedFiles: number
   skippedFiles: number
   rollbackUsed: boolean
+  /** Files that needed at least one retried request */
+  filesRetried: number
+  /** Retried requests across all files */
+  totalRetryCount: number
 }
 
 export interface AddonUpdaterCredentials {
   login: string
   licenseKey: string
+  /** The profile was damaged and has just been restored from its `.bak` copy */
+  recoveredFromBackup: boolean
+}
+
+/** Credentials another updater left in an addon; the license key is never sent */
+export interface DiscoveredUpdaterCredentials {
+  itemType: AddonUpdatableItemType
+  folderName: string
+  provider: 'skunkcrafts' | 'x-updater'
+  /** Masked login, e.g. "pil***com" */
+  login: string
+  hasLicenseKey: boolean
+}
+
+export type UpdaterProfileIssueKind = 'missing' | 'malformed' | 'empty'
+
+export interface UpdaterProfileIssue {
+  field: string
+  kind: UpdaterProfileIssueKind
+  /** 1-based line the issue was found on */
+  line?: number | null
+}
+
+/** Result of `validate_updater_profile` */
+export interface UpdaterProfileValidation {
+  profilePath?: string | null
+  issues: UpdaterProfileIssue[]
+  /** The file is damaged, not merely lacking credentials */
+  corrupt: boolean
+  /** Intact `.bak` copy that can replace a corrupt profile */
+  backupPath?: string | null
 }
 
 export interface AddonDiskSpaceInfo {
@@ -504,6 +1536,45 @@ export interface LuaScriptInfo {
   enabled: boolean
 }
 
+export interface PluginDuplicateLocation {
+  /** Folder path relative to the X-Plane root */
+  relativePath: string
+  folderName: string
+  /** For copies bundled in an aircraft: the aircraft folder relative to `Aircraft/` */
+  aircraftFolder?: string
+}
+
+export interface PluginDuplicateGroup {
+  groupId: string
+  locations: PluginDuplicateLocation[]
+  identical: boolean
+  /** Key to pass to `add_analysis_suppression` to hide this group */
+  suppressionKey: string
+}
+
+export type LuaScriptScope = 'global' | 'aircraft'
+
+export interface LuaScriptLocation {
+  relativePath: string
+  scope: LuaScriptScope
+  aircraftFolder?: string
+  sizeBytes: number
+  contentHash: string
+}
+
+export interface LuaDuplicateGroup {
+  groupId: string
+  fileName: string
+  locations: LuaScriptLocation[]
+  identical: boolean
+}
+
+export interface LuaDuplicateResolution {
+  groupId: string
+  kept: string
+  disabled: string[]
+}
+
 export interface NavdataManagerInfo {
   folderName: string
   providerName: string
@@ -512,6 +1583,130 @@ export interface NavdataManagerInfo {
   enabled: boolean
 }
 
+export type NavdataSourceKind = 'customData' | 'gns430' | 'aircraft'
+
+export interface NavdataConsistencyEntry {
+  source: NavdataSourceKind
+  name: string
+  providerName?: string
+  cycle?: string
+  airac?: string
+  effectiveCycle?: string
+  folderPath: string
+  managementType?: string
+  folderName: string
+  isOutdated: boolean
+}
+
+export interface NavdataConsistencyReport {
+  entries: NavdataConsistencyEntry[]
+  newestCycle?: string
+  outdatedCount: number
+  unknownCycleCount: number
+}
+
+/** Area checked by `run_full_maintenance` */
+export type MaintenanceSection = 'addons' | 'sceneryIndex' | 'sceneryPacks' | 'navdata'
+
+export type MaintenanceSectionStatus = 'healthy' | 'issues' | 'failed' | 'skipped' | 'unchanged'
+
+export interface MaintenanceSectionReport {
+  section: MaintenanceSection
+  status: MaintenanceSectionStatus
+  summary: string
+  issues: string[]
+  /** Fingerprint of the section inputs when checked */
+  fingerprint: string
+  checkedAt: number
+  /** Run whose healthy result was reused when `status` is `unchanged` */
+  reusedFromRun: number | null
+}
+
+export interface MaintenanceReport {
+  runId: number
+  xplanePath: string
+  /** `cancelled` runs are resumed by the next run for the same X-Plane path */
+  status: 'running' | 'cancelled' | 'completed'
+  scheduled: boolean
+  startedAt: number
+  finishedAt: number | null
+  sections: MaintenanceSectionReport[]
+  pending: MaintenanceSection[]
+}
+
+/** Payload of the `maintenance-progress` event */
+export interface MaintenanceProgressEvent {
+  runId: number
+  section: MaintenanceSection
+  phase: 'started' | 'progress' | 'finished'
+  status: MaintenanceSectionStatus | null
+  completedSections: number
+  totalSections: number
+  message: string | null
+}
+
+/** Kind of problem found by `run_health_check` */
+export type HealthCheckCategory =
+  | 'orphanedLiveries'
+  | 'pluginWithoutBinary'
+  | 'sceneryWithoutContent'
+  | 'outdatedNavdata'
+
+export type HealthSeverity = 'info' | 'warning' | 'error'
+
+export type HealthAction = 'delete' | 'disable' | 'reinstall' | 'ignore'
+
+export interface HealthIssue {
+  category: HealthCheckCategory
+  severity: HealthSeverity
+  path: string
+  /** i18n key of the explanation, e.g. `health.orphanedLiveries` */
+  reasonKey: string
+  reasonParams?: Record<string, string>
+  suggestedAction: HealthAction
+}
+
+export interface HealthCheckReport {
+  /** Most severe first */
+  issues: HealthIssue[]
+  counts: Partial<Record<HealthCheckCategory, number>>
+  /** AIRAC cycle of the navdata shipped with the sim */
+  baseNavdataCycle: string | null
+}
+
+/** Payload of the `health-check-progress` event */
+export interface HealthCheckProgress {
+  category: HealthCheckCategory
+  processed: number
+  total: number
+  current: string | null
+}
+
+/** Update state of one addon checked by `check_all_addon_updates` */
+export interface AddonUpdateCheckItem {
+  itemType: 'aircraft' | 'plugin' | 'scenery' | 'livery'
+  /** Liveries: `<aircraft>/liveries/<livery>` */
+  folderName: string
+  displayName: string
+  provider: string
+  currentVersion: string | null
+  remoteVersion: string | null
+  hasUpdate: boolean
+  error: string | null
+}
+
+/** Result of `check_all_addon_updates` and payload of `addon-updates-available` */
+export interface AddonUpdateSummary {
+  xplanePath: string
+  items: AddonUpdateCheckItem[]
+  totalCount: number
+  updateCount: number
+  failedCount: number
+  cancelled: boolean
+  scheduled: boolean
+  checkedAt: number
+}
+
 export interface BackupFileEntry {
   relativePath: string
   checksum: string
@@ -522,6 +1717,8 @@ export interface NavdataBackupVerification {
   providerName: string
   cycle?: string
   airac?: string
+  /** Cycle installed over the backed up data; missing in older backups */
+  replacedByCycle?: string
   backupTime: string
   files: BackupFileEntry[]
   fileCount: number
@@ -530,12 +1727,34 @@ export interface NavdataBackupVerification {
 export interface NavdataBackupInfo {
   folderName: string
   verification: NavdataBackupVerification
+  /** Cycle change the backup was taken for, e.g. "2405 → 2313" */
+  cycleLabel?: string
 }
 
 export interface ManagementData<T> {
   entries: T[]
   totalCount: number
   enabledCount: number
+  /** For plugins: folders X-Plane would load as the same plugin */
+  duplicates?: PluginDuplicateGroup[]
+  /** For plugins: duplicate groups hidden by the user's suppressions */
+  suppressedCount?: number
+}
+
+/** Payload of the `management-item-installed` event, as a full scan would report it */
+export type InstalledManagementItem =
+  | { itemType: 'aircraft'; entries: AircraftInfo[] }
+  | { itemType: 'plugin'; entry: PluginInfo }
+
+/** Payload of the `addon-update-tracked` event: an installed addon with updater metadata */
+export interface UpdateTrackedAddon {
+  itemType: 'aircraft' | 'plugin' | 'scenery' | 'livery'
+  /** Liveries: `<aircraft>/liveries/<livery>` */
+  folderName: string
+  displayName: string
+  provider: UpdaterProvider
+  version: string | null
+  updateUrl: string | null
 }
 
 export type ManagementTab = 'aircraft' | 'plugin' | 'navdata' | 'scenery'
@@ -676,3 +1895,29 @@ export interface GatewayInstallWarning {
   kind: string
   message: string
 }
+
+/** File types and folders the "Install to X-Plane" context menu entry is registered for */
+export interface ContextMenuOptions {
+  zip: boolean
+  sevenZ: boolean
+  rar: boolean
+  lua: boolean
+  folders: boolean
+}
+
+export type ContextMenuTarget = keyof ContextMenuOptions
+
+export interface ContextMenuStatus {
+  /** False on platforms without a context menu integration (macOS) */
+  supported: boolean
+  /** Registered for at least one target */
+  registered: boolean
+  options: ContextMenuOptions
+}
+
+export interface ContextMenuSyncReport {
+  /** Targets whose entry pointed at a previous location of the app */
+  repaired: ContextMenuTarget[]
+  /** An entry left by an older version was replaced by per-type entries */
+  migratedLegacy: boolean
+}